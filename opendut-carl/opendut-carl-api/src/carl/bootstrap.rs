@@ -0,0 +1,235 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use url::Url;
+
+/// Progress of the initial setup of a CARL instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BootstrapStatus {
+    pub completed: bool,
+    pub admin: BootstrapStepState,
+    pub identity_provider: BootstrapStepState,
+    pub ca: BootstrapStepState,
+    pub vpn: BootstrapStepState,
+    /// Whether the configuration written by the bootstrap only takes effect after restarting CARL.
+    pub restart_required: bool,
+}
+
+impl BootstrapStatus {
+    pub fn steps(&self) -> [(BootstrapStep, &BootstrapStepState); 4] {
+        [
+            (BootstrapStep::Admin, &self.admin),
+            (BootstrapStep::IdentityProvider, &self.identity_provider),
+            (BootstrapStep::Ca, &self.ca),
+            (BootstrapStep::Vpn, &self.vpn),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BootstrapStep {
+    Admin,
+    IdentityProvider,
+    Ca,
+    Vpn,
+}
+
+impl fmt::Display for BootstrapStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapStep::Admin => write!(f, "admin"),
+            BootstrapStep::IdentityProvider => write!(f, "identity-provider"),
+            BootstrapStep::Ca => write!(f, "ca"),
+            BootstrapStep::Vpn => write!(f, "vpn"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BootstrapStepState {
+    Pending,
+    Done { detail: String },
+    Skipped { reason: String },
+    Failed { cause: String },
+}
+
+impl BootstrapStepState {
+    /// Whether the step does not prevent completing the bootstrap.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, BootstrapStepState::Done { .. } | BootstrapStepState::Skipped { .. })
+    }
+}
+
+impl fmt::Display for BootstrapStepState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapStepState::Pending => write!(f, "Pending"),
+            BootstrapStepState::Done { detail } => write!(f, "Done ({detail})"),
+            BootstrapStepState::Skipped { reason } => write!(f, "Skipped ({reason})"),
+            BootstrapStepState::Failed { cause } => write!(f, "Failed ({cause})"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdentityProviderConfiguration {
+    /// Run CARL without authentication.
+    Disabled,
+    Oidc {
+        issuer_url: Url,
+        /// Issuer URL which CARL tells the clients to use, if it differs from the one CARL uses.
+        issuer_remote_url: Option<Url>,
+        issuer_admin_url: Url,
+        client_id: String,
+        client_secret: String,
+        lea_client_id: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapError {
+    #[error("Bootstrapping is disabled on this CARL instance.")]
+    Disabled,
+    #[error("The bootstrap token is invalid! It can be found in the bootstrap directory of CARL.")]
+    InvalidToken,
+    #[error("The bootstrap has already been completed.")]
+    AlreadyCompleted,
+    #[error("Illegal input: {message}")]
+    IllegalInput { message: String },
+    #[error("The bootstrap cannot be completed, because the following steps are not finished: {}", steps.join(", "))]
+    StepsIncomplete { steps: Vec<String> },
+    #[error("An internal error occurred during the bootstrap:\n  {cause}")]
+    Internal { cause: String },
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use std::convert::Infallible;
+
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::bootstrap::{BootstrapError, BootstrapStatus, IdentityProviderConfiguration};
+    use crate::proto::services::bootstrap;
+    use crate::proto::services::bootstrap::bootstrap_client::BootstrapClient;
+
+    #[derive(Clone, Debug)]
+    pub struct Bootstrap<T> {
+        inner: BootstrapClient<T>,
+    }
+
+    impl<T> Bootstrap<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: BootstrapClient<T>) -> Bootstrap<T> {
+            Bootstrap {
+                inner
+            }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> Bootstrap<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = BootstrapClient::new(InterceptedService::new(inner, interceptor));
+            Bootstrap {
+                inner: inner_client
+            }
+        }
+
+        pub async fn get_status(&mut self) -> Result<BootstrapStatus, ClientError<Infallible>> {
+
+            let request = tonic::Request::new(bootstrap::GetBootstrapStatusRequest {});
+
+            let response = self.inner.get_bootstrap_status(request).await?
+                .into_inner();
+
+            let status = extract!(response.status)?;
+            Ok(status)
+        }
+
+        pub async fn create_admin(&mut self, token: String, name: String, email: String) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+
+            let request = tonic::Request::new(bootstrap::CreateAdminRequest { token, name, email });
+
+            let response = self.inner.create_admin(request).await?
+                .into_inner();
+
+            Self::extract_status(response)
+        }
+
+        pub async fn configure_identity_provider(&mut self, token: String, identity_provider: IdentityProviderConfiguration) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+
+            let request = tonic::Request::new(bootstrap::ConfigureIdentityProviderRequest {
+                token,
+                identity_provider: Some(identity_provider.into()),
+            });
+
+            let response = self.inner.configure_identity_provider(request).await?
+                .into_inner();
+
+            Self::extract_status(response)
+        }
+
+        /// Uploads the PEM-encoded certificate of the certificate authority, which signed the certificate of CARL.
+        pub async fn upload_ca(&mut self, token: String, certificate: String) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+
+            let request = tonic::Request::new(bootstrap::UploadCaRequest { token, certificate });
+
+            let response = self.inner.upload_ca(request).await?
+                .into_inner();
+
+            Self::extract_status(response)
+        }
+
+        pub async fn verify_vpn(&mut self, token: String) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+
+            let request = tonic::Request::new(bootstrap::VerifyVpnRequest { token });
+
+            let response = self.inner.verify_vpn(request).await?
+                .into_inner();
+
+            Self::extract_status(response)
+        }
+
+        pub async fn complete(&mut self, token: String) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+
+            let request = tonic::Request::new(bootstrap::CompleteBootstrapRequest { token });
+
+            let response = self.inner.complete_bootstrap(request).await?
+                .into_inner();
+
+            Self::extract_status(response)
+        }
+
+        fn extract_status(response: bootstrap::BootstrapResponse) -> Result<BootstrapStatus, ClientError<BootstrapError>> {
+            match extract!(response.reply)? {
+                bootstrap::bootstrap_response::Reply::Failure(failure) => {
+                    let error = BootstrapError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                bootstrap::bootstrap_response::Reply::Success(status) => {
+                    Ok(BootstrapStatus::try_from(status)?)
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,60 @@
+use std::fmt;
+
+use opendut_types::peer::executor::container::ContainerSecret;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
 #[cfg(feature = "client")]
 pub use client::*;
 
+/// The value of a secret, which an executor references by name.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExecutorSecret {
+    pub name: ContainerSecret,
+    pub value: Vec<u8>,
+}
+
+impl fmt::Debug for ExecutorSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutorSecret")
+            .field("name", &self.name)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetExecutorSecretsError {
+    #[error("Executor <{executor_id}> is not configured for peer <{peer_id}>!")]
+    ExecutorNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Secret '{secret}' referenced by executor <{executor_id}> could not be found!")]
+    SecretNotFound {
+        executor_id: ExecutorId,
+        secret: ContainerSecret,
+    },
+    #[error("An internal error occurred retrieving the executor secrets for peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum UploadSelfTestReportError {
+    #[error("Peer <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId,
+    },
+    #[error("An internal error occurred storing the self-test report of peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String,
+    },
+}
+
 pub mod error {
     #[derive(thiserror::Error, Debug)]
     #[error("{message}")]
@@ -24,8 +78,11 @@ mod client {
     use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
     use tonic::metadata::MetadataValue;
 
-    use crate::carl::broker::error;
+    use crate::carl::broker::{error, ExecutorSecret, GetExecutorSecretsError, UploadSelfTestReportError};
+    use crate::carl::{extract, ClientError};
     use crate::proto::services::peer_messaging_broker;
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::self_test::SelfTestReport;
     use opendut_types::peer::PeerId;
 
     #[derive(Clone, Debug)]
@@ -86,6 +143,7 @@ mod client {
                 let mut request = tonic::Request::new(ReceiverStream::new(rx));
                 request.metadata_mut().insert("id", MetadataValue::from_str(&id.to_string()).unwrap());
                 request.metadata_mut().insert("remote-host", MetadataValue::from_str(&remote_address.to_string()).unwrap());
+                request.metadata_mut().insert("version", MetadataValue::from_static(env!("CARGO_PKG_VERSION")));
 
                 self.inner
                     .open(request)
@@ -97,5 +155,49 @@ mod client {
 
             Ok((inbound, tx))
         }
+
+        pub async fn get_executor_secrets(&mut self, peer_id: PeerId, executor_id: ExecutorId) -> Result<Vec<ExecutorSecret>, ClientError<GetExecutorSecretsError>> {
+
+            let request = tonic::Request::new(peer_messaging_broker::GetExecutorSecretsRequest {
+                peer_id: Some(peer_id.into()),
+                executor_id: Some(executor_id.into()),
+            });
+
+            let response = self.inner.get_executor_secrets(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_messaging_broker::get_executor_secrets_response::Reply::Failure(failure) => {
+                    let error = GetExecutorSecretsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_messaging_broker::get_executor_secrets_response::Reply::Success(success) => {
+                    let secrets = success.secrets.into_iter()
+                        .map(ExecutorSecret::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(secrets)
+                }
+            }
+        }
+
+        pub async fn upload_self_test_report(&mut self, report: SelfTestReport) -> Result<(), ClientError<UploadSelfTestReportError>> {
+
+            let request = tonic::Request::new(peer_messaging_broker::UploadSelfTestReportRequest {
+                report: Some(report.into()),
+            });
+
+            let response = self.inner.upload_self_test_report(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_messaging_broker::upload_self_test_report_response::Reply::Failure(failure) => {
+                    let error = UploadSelfTestReportError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_messaging_broker::upload_self_test_report_response::Reply::Success(_) => {
+                    Ok(())
+                }
+            }
+        }
     }
 }
@@ -1,12 +1,19 @@
 use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
 
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
 use opendut_types::cluster::{ClusterId, ClusterName};
 use opendut_types::cluster::state::ClusterState;
-use opendut_types::peer::PeerId;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::{PeerId, PeerName};
+use opendut_types::topology::DeviceId;
+use opendut_types::util::net::NetworkInterfaceName;
 use opendut_types::ShortName;
 
+use crate::carl::policy;
+use crate::carl::policy::PolicyViolation;
+
 #[derive(thiserror::Error, Debug)]
 pub enum CreateClusterConfigurationError {
     #[error("ClusterConfigration '{actual_name}' <{actual_id}> could not be created, because ClusterConfigration '{other_name}' <{other_id}> is already registered with the same ClusterId!")]
@@ -16,6 +23,18 @@ pub enum CreateClusterConfigurationError {
         other_id: ClusterId,
         other_name: ClusterName
     },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> was rejected, as it violates the following policies:\n  {}", policy::format_violations(violations))]
+    PolicyViolation {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        violations: Vec<PolicyViolation>,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> was rejected, due to invalid dependencies:\n  {cause}")]
+    InvalidDependencies {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        cause: String
+    },
     #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -35,6 +54,11 @@ pub enum DeleteClusterConfigurationError {
         actual_state: ClusterState,
         required_states: Vec<ClusterState>,
     },
+    ReferencedAsDependency {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        dependents: Vec<ClusterId>,
+    },
     Internal {
         cluster_id: ClusterId,
         cluster_name: Option<ClusterName>,
@@ -52,6 +76,10 @@ impl Display for DeleteClusterConfigurationError {
                 let required_states = ClusterState::short_names_joined(required_states);
                 writeln!(f, "ClusterConfiguration '{cluster_name}' <{cluster_id}> cannot be deleted when cluster is in state '{actual_state}'! A ClusterConfiguration can be deleted when cluster is in state: {required_states}")
             }
+            DeleteClusterConfigurationError::ReferencedAsDependency { cluster_id, cluster_name, dependents } => {
+                let dependents = dependents.iter().map(|dependent| format!("<{dependent}>")).collect::<Vec<_>>().join(", ");
+                writeln!(f, "ClusterConfiguration '{cluster_name}' <{cluster_id}> cannot be deleted, because the following clusters depend on it: {dependents}")
+            }
             DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
                 let cluster_name = match cluster_name {
                     Some(cluster_name) => format!("'{cluster_name}' "),
@@ -170,12 +198,63 @@ pub struct ListClusterDeploymentsError {
     pub message: String,
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum GetClusterDeploymentStatusError {
+    #[error("Cluster <{cluster_id}> is not deployed, so there is no deployment status.")]
+    ClusterDeploymentNotFound { cluster_id: ClusterId },
+    #[error("Deployment status of cluster <{cluster_id}> could not be retrieved, due to internal errors:\n  {cause}")]
+    Internal { cluster_id: ClusterId, cause: String },
+}
+
+/// Outcome of evaluating a hypothetical ClusterConfiguration against the current resources, without persisting anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterDeploymentSimulation {
+    pub cluster_id: ClusterId,
+    /// Policies, which would reject the ClusterConfiguration.
+    pub violations: Vec<PolicyViolation>,
+    /// Reasons, why the cluster could not be deployed right now, e.g. unknown devices or unavailable peers.
+    pub problems: Vec<String>,
+    pub members: Vec<SimulatedClusterMember>,
+    /// Devices, which the device selectors of the ClusterConfiguration would bind.
+    pub bound_devices: Vec<DeviceId>,
+}
+
+impl ClusterDeploymentSimulation {
+    pub fn is_deployable(&self) -> bool {
+        self.violations.is_empty() && self.problems.is_empty()
+    }
+}
+
+/// A peer, which would become member of the simulated cluster.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedClusterMember {
+    pub peer_id: PeerId,
+    pub peer_name: PeerName,
+    pub leader: bool,
+    /// Address of the peer in the VPN, if it is currently connected.
+    pub vpn_address: Option<IpAddr>,
+    pub device_interfaces: Vec<NetworkInterfaceName>,
+    pub devices: Vec<DeviceId>,
+    /// Executors, which would be started on the peer.
+    pub executors: Vec<ExecutorId>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SimulateClusterDeploymentError {
+    #[error("Deployment of cluster <{cluster_id}> could not be simulated, due to internal errors:\n  {cause}")]
+    Internal {
+        cluster_id: ClusterId,
+        cause: String,
+    },
+}
+
 
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 mod client {
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
 
-    use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+    use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId};
+    use opendut_types::project::ProjectId;
 
     use crate::carl::{ClientError, extract};
     use crate::proto::services::cluster_manager;
@@ -224,9 +303,15 @@ mod client {
         }
 
         pub async fn store_cluster_configuration(&mut self, configuration: ClusterConfiguration) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
+            self.store_cluster_configuration_in_project(configuration, None).await
+        }
+
+        /// Stores the cluster configuration and assigns it to the project, if given, so that only members of the project can access it.
+        pub async fn store_cluster_configuration_in_project(&mut self, configuration: ClusterConfiguration, project_id: Option<ProjectId>) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
 
             let request = tonic::Request::new(cluster_manager::CreateClusterConfigurationRequest {
                 cluster_configuration: Some(configuration.into()),
+                project_id: project_id.map(Into::into),
             });
 
             let response = self.inner.create_cluster_configuration(request).await?
@@ -293,7 +378,14 @@ mod client {
         }
 
         pub async fn list_cluster_configurations(&mut self) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
-            let request = tonic::Request::new(cluster_manager::ListClusterConfigurationsRequest {});
+            self.list_cluster_configurations_in_project(None).await
+        }
+
+        /// Lists the cluster configurations, which the user may access. With a project, only the clusters assigned to that project are listed.
+        pub async fn list_cluster_configurations_in_project(&mut self, project_id: Option<ProjectId>) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
+            let request = tonic::Request::new(cluster_manager::ListClusterConfigurationsRequest {
+                project_id: project_id.map(Into::into),
+            });
 
             match self.inner.list_cluster_configurations(request).await {
                 Ok(response) => {
@@ -383,5 +475,47 @@ mod client {
                 }
             }
         }
+
+        pub async fn get_cluster_deployment_status(&mut self, cluster_id: ClusterId) -> Result<ClusterDeploymentStatus, ClientError<GetClusterDeploymentStatusError>> {
+
+            let request = tonic::Request::new(cluster_manager::GetClusterDeploymentStatusRequest {
+                cluster_id: Some(cluster_id.into()),
+            });
+
+            let response = self.inner.get_cluster_deployment_status(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::get_cluster_deployment_status_response::Reply::Failure(failure) => {
+                    let error = GetClusterDeploymentStatusError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::get_cluster_deployment_status_response::Reply::Success(success) => {
+                    let status = extract!(success.status)?;
+                    Ok(status)
+                }
+            }
+        }
+
+        pub async fn simulate_cluster_deployment(&mut self, configuration: ClusterConfiguration) -> Result<ClusterDeploymentSimulation, ClientError<SimulateClusterDeploymentError>> {
+
+            let request = tonic::Request::new(cluster_manager::SimulateClusterDeploymentRequest {
+                cluster_configuration: Some(configuration.into()),
+            });
+
+            let response = self.inner.simulate_cluster_deployment(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::simulate_cluster_deployment_response::Reply::Failure(failure) => {
+                    let error = SimulateClusterDeploymentError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::simulate_cluster_deployment_response::Reply::Success(success) => {
+                    let simulation = extract!(success.simulation)?;
+                    Ok(simulation)
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,446 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+#[cfg(feature = "client")]
+pub use client::*;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use uuid::Uuid;
+
+/// Inconsistency between the persisted resources, the in-memory resources and the connected peers,
+/// as found by checking the consistency of CARL's resources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsistencyFinding {
+    pub kind: ConsistencyFindingKind,
+    pub repaired: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsistencyFindingKind {
+    OrphanedPeerState { peer_id: PeerId },
+    OrphanedPeerConfiguration { peer_id: PeerId },
+    OrphanedOldPeerConfiguration { peer_id: PeerId },
+    PeerUpWithoutConnection { peer_id: PeerId },
+    ConnectionWithoutPeerUp { peer_id: PeerId },
+    AssignedClusterNotDeployed { peer_id: PeerId, cluster_id: ClusterId },
+}
+
+impl ConsistencyFindingKind {
+    /// Whether CARL knows how to resolve this inconsistency when repairing is requested.
+    pub fn is_repairable(&self) -> bool {
+        match self {
+            ConsistencyFindingKind::OrphanedPeerState { .. }
+            | ConsistencyFindingKind::OrphanedPeerConfiguration { .. }
+            | ConsistencyFindingKind::OrphanedOldPeerConfiguration { .. }
+            | ConsistencyFindingKind::PeerUpWithoutConnection { .. } => true,
+            ConsistencyFindingKind::ConnectionWithoutPeerUp { .. }
+            | ConsistencyFindingKind::AssignedClusterNotDeployed { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for ConsistencyFindingKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsistencyFindingKind::OrphanedPeerState { peer_id } =>
+                write!(f, "PeerState exists for peer <{peer_id}>, but the peer does not exist."),
+            ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id } =>
+                write!(f, "PeerConfiguration exists for peer <{peer_id}>, but the peer does not exist."),
+            ConsistencyFindingKind::OrphanedOldPeerConfiguration { peer_id } =>
+                write!(f, "OldPeerConfiguration exists for peer <{peer_id}>, but the peer does not exist."),
+            ConsistencyFindingKind::PeerUpWithoutConnection { peer_id } =>
+                write!(f, "Peer <{peer_id}> is marked as up, but has no open stream to CARL."),
+            ConsistencyFindingKind::ConnectionWithoutPeerUp { peer_id } =>
+                write!(f, "Peer <{peer_id}> has an open stream to CARL, but is not marked as up."),
+            ConsistencyFindingKind::AssignedClusterNotDeployed { peer_id, cluster_id } =>
+                write!(f, "Peer <{peer_id}> is assigned to cluster <{cluster_id}>, but no deployment exists for this cluster."),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CheckConsistencyError {
+    #[error("An internal error occurred while checking the consistency of resources:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+/// Persisted resource, which does not match the schema or the invariants of the running version of CARL,
+/// as found by checking the compatibility of CARL's resources, e.g. after an upgrade.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompatibilityFinding {
+    /// Kind of the resource, as stored in the database, e.g. `cluster_configuration`.
+    pub resource_kind: String,
+    /// ID of the affected resource. `None`, if none of the resources of this kind could be decoded.
+    pub resource_id: Option<String>,
+    pub problem: CompatibilityProblem,
+    /// Description of the transform, which CARL offers to resolve the finding. `None`, if the resource has to be fixed manually.
+    pub transform: Option<String>,
+    pub transformed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompatibilityProblem {
+    /// The stored data does not match the schema of the resource anymore.
+    Undecodable { cause: String },
+    /// The resource can be decoded, but would be rejected when stored, e.g. because a validation was added.
+    InvariantViolated { cause: String },
+}
+
+impl fmt::Display for CompatibilityFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let resource = match &self.resource_id {
+            Some(resource_id) => format!("Resource '{}' <{resource_id}>", self.resource_kind),
+            None => format!("Resources of kind '{}'", self.resource_kind),
+        };
+        match &self.problem {
+            CompatibilityProblem::Undecodable { cause } =>
+                write!(f, "{resource} cannot be decoded: {cause}"),
+            CompatibilityProblem::InvariantViolated { cause } =>
+                write!(f, "{resource} violates an invariant: {cause}"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CheckCompatibilityError {
+    #[error("An internal error occurred while checking the compatibility of resources:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TraceCaptureId(pub Uuid);
+
+impl fmt::Display for TraceCaptureId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for TraceCaptureId {
+    type Err = uuid::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Uuid::from_str(value).map(Self)
+    }
+}
+
+/// Which logs and traces are captured with raised verbosity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceCaptureScope {
+    /// Records mentioning the peer.
+    Peer { peer_id: PeerId },
+    /// Records mentioning the cluster.
+    Cluster { cluster_id: ClusterId },
+    /// Records emitted while handling requests to the gRPC method, e.g. `ListPeerDescriptors`.
+    GrpcMethod { method: String },
+}
+
+impl fmt::Display for TraceCaptureScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceCaptureScope::Peer { peer_id } => write!(f, "peer <{peer_id}>"),
+            TraceCaptureScope::Cluster { cluster_id } => write!(f, "cluster <{cluster_id}>"),
+            TraceCaptureScope::GrpcMethod { method } => write!(f, "gRPC method '{method}'"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceCaptureLevel {
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for TraceCaptureLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceCaptureLevel::Debug => write!(f, "DEBUG"),
+            TraceCaptureLevel::Trace => write!(f, "TRACE"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceCaptureInfo {
+    pub id: TraceCaptureId,
+    pub scope: TraceCaptureScope,
+    pub level: TraceCaptureLevel,
+    pub started_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub record_count: usize,
+    /// Number of the oldest records, which CARL discarded, because the maximum number of records per capture was exceeded.
+    pub dropped_record_count: usize,
+}
+
+/// Log record or span event, as captured by CARL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedRecord {
+    pub timestamp: SystemTime,
+    pub level: String,
+    pub target: String,
+    /// Names of the spans the record was emitted in, starting with the outermost span.
+    pub spans: Vec<String>,
+    /// Message and fields of the record.
+    pub fields: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceCaptureBundle {
+    pub capture: TraceCaptureInfo,
+    pub records: Vec<CapturedRecord>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StartTraceCaptureError {
+    #[error("Trace capture could not be started, because the duration of {} ms exceeds the maximum of {} ms!", duration.as_millis(), max_duration.as_millis())]
+    IllegalDuration {
+        duration: Duration,
+        max_duration: Duration,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DownloadTraceCaptureError {
+    #[error("Trace capture <{capture_id}> could not be downloaded, because a trace capture with that id does not exist!")]
+    TraceCaptureNotFound {
+        capture_id: TraceCaptureId,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteTraceCaptureError {
+    #[error("Trace capture <{capture_id}> could not be deleted, because a trace capture with that id does not exist!")]
+    TraceCaptureNotFound {
+        capture_id: TraceCaptureId,
+    },
+}
+
+/// Messaging stream of a connected peer, as seen by CARL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerStreamInfo {
+    pub peer_id: PeerId,
+    /// Address, which the peer reported for itself when opening the stream.
+    pub remote_host: IpAddr,
+    /// Address and port the connection originates from, as seen by CARL. Differs from the remote host, e.g. behind a NAT.
+    pub remote_address: Option<SocketAddr>,
+    /// Version of EDGAR, which the peer reported when opening the stream. Not reported by older versions of EDGAR.
+    pub version: Option<String>,
+    pub opened_at: SystemTime,
+    /// When CARL last received a message from the peer.
+    pub last_seen: Option<SystemTime>,
+    pub messages_received: u64,
+    pub messages_sent: u64,
+}
+
+impl PeerStreamInfo {
+    pub fn uptime(&self) -> Duration {
+        SystemTime::now().duration_since(self.opened_at).unwrap_or_default()
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TerminatePeerStreamError {
+    #[error("Stream of peer <{peer_id}> could not be terminated, because the peer is not connected!")]
+    PeerNotConnected {
+        peer_id: PeerId,
+    },
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use crate::carl::{ClientError, extract};
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use opendut_types::peer::PeerId;
+
+    use crate::carl::diagnostics::{CheckCompatibilityError, CheckConsistencyError, CompatibilityFinding, ConsistencyFinding, DeleteTraceCaptureError, DownloadTraceCaptureError, PeerStreamInfo, StartTraceCaptureError, TerminatePeerStreamError, TraceCaptureBundle, TraceCaptureId, TraceCaptureInfo, TraceCaptureLevel, TraceCaptureScope};
+    use crate::proto::services::diagnostics;
+    use crate::proto::services::diagnostics::diagnostics_client::DiagnosticsClient;
+
+    #[derive(Clone, Debug)]
+    pub struct Diagnostics<T> {
+        inner: DiagnosticsClient<T>,
+    }
+
+    impl<T> Diagnostics<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: DiagnosticsClient<T>) -> Diagnostics<T> {
+            Diagnostics {
+                inner
+            }
+        }
+
+        pub async fn check_consistency(&mut self, repair: bool) -> Result<Vec<ConsistencyFinding>, ClientError<CheckConsistencyError>> {
+
+            let request = tonic::Request::new(diagnostics::CheckConsistencyRequest { repair });
+
+            let response = self.inner.check_consistency(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::check_consistency_response::Reply::Failure(failure) => {
+                    let error = CheckConsistencyError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::check_consistency_response::Reply::Success(success) => {
+                    Ok(success.findings.into_iter()
+                        .map(ConsistencyFinding::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Checks that all persisted resources can be decoded and satisfy the invariants of the running version of CARL.
+        pub async fn check_compatibility(&mut self, transform: bool) -> Result<Vec<CompatibilityFinding>, ClientError<CheckCompatibilityError>> {
+
+            let request = tonic::Request::new(diagnostics::CheckCompatibilityRequest { transform });
+
+            let response = self.inner.check_compatibility(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::check_compatibility_response::Reply::Failure(failure) => {
+                    let error = CheckCompatibilityError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::check_compatibility_response::Reply::Success(success) => {
+                    Ok(success.findings.into_iter()
+                        .map(CompatibilityFinding::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Raises the verbosity for the given scope until the duration elapsed, capturing the resulting records on CARL.
+        pub async fn start_trace_capture(&mut self, scope: TraceCaptureScope, level: TraceCaptureLevel, duration: Duration) -> Result<TraceCaptureInfo, ClientError<StartTraceCaptureError>> {
+
+            let request = tonic::Request::new(diagnostics::StartTraceCaptureRequest {
+                scope: Some(scope.into()),
+                level: Some(level.into()),
+                duration_millis: duration.as_millis() as u64,
+            });
+
+            let response = self.inner.start_trace_capture(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::start_trace_capture_response::Reply::Failure(failure) => {
+                    let error = StartTraceCaptureError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::start_trace_capture_response::Reply::Success(success) => {
+                    let capture = extract!(success.capture)?;
+                    Ok(capture)
+                }
+            }
+        }
+
+        pub async fn list_trace_captures(&mut self) -> Result<Vec<TraceCaptureInfo>, ClientError<Infallible>> {
+
+            let request = tonic::Request::new(diagnostics::ListTraceCapturesRequest {});
+
+            let response = self.inner.list_trace_captures(request).await?
+                .into_inner();
+
+            Ok(response.captures.into_iter()
+                .map(TraceCaptureInfo::try_from)
+                .collect::<Result<Vec<_>, _>>()?
+            )
+        }
+
+        /// Returns the records captured so far, which is also possible while the capture is still active.
+        pub async fn download_trace_capture(&mut self, capture_id: TraceCaptureId) -> Result<TraceCaptureBundle, ClientError<DownloadTraceCaptureError>> {
+
+            let request = tonic::Request::new(diagnostics::DownloadTraceCaptureRequest {
+                capture_id: Some(capture_id.into()),
+            });
+
+            let response = self.inner.download_trace_capture(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::download_trace_capture_response::Reply::Failure(failure) => {
+                    let error = DownloadTraceCaptureError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::download_trace_capture_response::Reply::Success(success) => {
+                    let bundle = TraceCaptureBundle::try_from(success)?;
+                    Ok(bundle)
+                }
+            }
+        }
+
+        /// Stops the capture, if still active, and discards its records.
+        pub async fn delete_trace_capture(&mut self, capture_id: TraceCaptureId) -> Result<TraceCaptureId, ClientError<DeleteTraceCaptureError>> {
+
+            let request = tonic::Request::new(diagnostics::DeleteTraceCaptureRequest {
+                capture_id: Some(capture_id.into()),
+            });
+
+            let response = self.inner.delete_trace_capture(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::delete_trace_capture_response::Reply::Failure(failure) => {
+                    let error = DeleteTraceCaptureError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::delete_trace_capture_response::Reply::Success(success) => {
+                    let capture_id = extract!(success.capture_id)?;
+                    Ok(capture_id)
+                }
+            }
+        }
+
+        /// Lists the messaging streams of the connected peers with their connection metadata and message counters.
+        pub async fn list_peer_streams(&mut self) -> Result<Vec<PeerStreamInfo>, ClientError<Infallible>> {
+
+            let request = tonic::Request::new(diagnostics::ListPeerStreamsRequest {});
+
+            let response = self.inner.list_peer_streams(request).await?
+                .into_inner();
+
+            Ok(response.streams.into_iter()
+                .map(PeerStreamInfo::try_from)
+                .collect::<Result<Vec<_>, _>>()?
+            )
+        }
+
+        /// Ends the messaging stream of the peer from CARL's side. The peer is marked as down and connects again, once it notices the end of its stream.
+        pub async fn terminate_peer_stream(&mut self, peer_id: PeerId) -> Result<PeerId, ClientError<TerminatePeerStreamError>> {
+
+            let request = tonic::Request::new(diagnostics::TerminatePeerStreamRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.terminate_peer_stream(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                diagnostics::terminate_peer_stream_response::Reply::Failure(failure) => {
+                    let error = TerminatePeerStreamError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                diagnostics::terminate_peer_stream_response::Reply::Success(success) => {
+                    let peer_id = extract!(success.peer_id)?;
+                    Ok(peer_id)
+                }
+            }
+        }
+    }
+}
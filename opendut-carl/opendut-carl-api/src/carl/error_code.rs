@@ -0,0 +1,518 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, GetClusterDeploymentStatusError, StoreClusterDeploymentError};
+use crate::carl::diagnostics::{DeleteTraceCaptureError, DownloadTraceCaptureError, StartTraceCaptureError, TerminatePeerStreamError};
+use crate::carl::peer::{ClaimProvisioningTokenError, CreatePeerInClusterError, DeletePeerDescriptorError, DeletePeerGroupError, GetPeerDescriptorError, GetPeerStateError, GetMaintenanceOperationError, PingPeerError, RevokePeerSetupError, SendPeerCommandError, StartRollingRestartError, StorePeerDescriptorError, StorePeerGroupError};
+use crate::carl::project::{DeleteProjectError, StoreProjectError};
+use crate::carl::results::{DownloadExecutorResultError, ListExecutorResultsError};
+use crate::carl::service_account::{CreateServiceAccountError, DeleteServiceAccountError, IssueServiceAccountTokenError, RevokeServiceAccountTokenError};
+use crate::carl::snapshot::{CreateSnapshotError, DeleteSnapshotError, DiffSnapshotsError, RestoreSnapshotError};
+
+/// Stable identifier of a known kind of error. CARL provides remediation hints for these codes via its metadata API,
+/// so that clients can explain errors in line with the server's version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    CarlUnreachable,
+    InvalidRequest,
+    Internal,
+    PeerNotFound,
+    PeerAlreadyExists,
+    PeerIllegalState,
+    PeerIllegalDevices,
+    PeersUnavailable,
+    PeerUnreachable,
+    PeerCommandUnauthorized,
+    PeerCommandFailed,
+    PolicyViolation,
+    PeerGroupNotFound,
+    PeerGroupReferenced,
+    ClusterConfigurationAlreadyExists,
+    ClusterConfigurationNotFound,
+    ClusterIllegalState,
+    ClusterDeploymentNotFound,
+    ClusterDependencyInvalid,
+    ClusterReferencedAsDependency,
+    SnapshotNotFound,
+    SnapshotNameAlreadyExists,
+    SnapshotResourcesMissing,
+    TraceCaptureNotFound,
+    ExecutorNotFound,
+    ExecutorResultsNotConfigured,
+    ExecutorResultNotFound,
+    ExecutorResultsUnavailable,
+    MaintenanceOperationNotFound,
+    ProvisioningTokenInvalid,
+    ProvisioningTokenClaimed,
+    ProjectNameAlreadyExists,
+    ProjectNotFound,
+    ServiceAccountNameAlreadyExists,
+    ServiceAccountNotFound,
+    ServiceAccountTokenNotFound,
+    SelfTestReportNotFound,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 37] = [
+        ErrorCode::CarlUnreachable,
+        ErrorCode::InvalidRequest,
+        ErrorCode::Internal,
+        ErrorCode::PeerNotFound,
+        ErrorCode::PeerAlreadyExists,
+        ErrorCode::PeerIllegalState,
+        ErrorCode::PeerIllegalDevices,
+        ErrorCode::PeersUnavailable,
+        ErrorCode::PeerUnreachable,
+        ErrorCode::PeerCommandUnauthorized,
+        ErrorCode::PeerCommandFailed,
+        ErrorCode::PolicyViolation,
+        ErrorCode::PeerGroupNotFound,
+        ErrorCode::PeerGroupReferenced,
+        ErrorCode::ClusterConfigurationAlreadyExists,
+        ErrorCode::ClusterConfigurationNotFound,
+        ErrorCode::ClusterIllegalState,
+        ErrorCode::ClusterDeploymentNotFound,
+        ErrorCode::ClusterDependencyInvalid,
+        ErrorCode::ClusterReferencedAsDependency,
+        ErrorCode::SnapshotNotFound,
+        ErrorCode::SnapshotNameAlreadyExists,
+        ErrorCode::SnapshotResourcesMissing,
+        ErrorCode::TraceCaptureNotFound,
+        ErrorCode::ExecutorNotFound,
+        ErrorCode::ExecutorResultsNotConfigured,
+        ErrorCode::ExecutorResultNotFound,
+        ErrorCode::ExecutorResultsUnavailable,
+        ErrorCode::MaintenanceOperationNotFound,
+        ErrorCode::ProvisioningTokenInvalid,
+        ErrorCode::ProvisioningTokenClaimed,
+        ErrorCode::ProjectNameAlreadyExists,
+        ErrorCode::ProjectNotFound,
+        ErrorCode::ServiceAccountNameAlreadyExists,
+        ErrorCode::ServiceAccountNotFound,
+        ErrorCode::ServiceAccountTokenNotFound,
+        ErrorCode::SelfTestReportNotFound,
+    ];
+
+    pub fn value(&self) -> &'static str {
+        match self {
+            ErrorCode::CarlUnreachable => "carl-unreachable",
+            ErrorCode::InvalidRequest => "invalid-request",
+            ErrorCode::Internal => "internal",
+            ErrorCode::PeerNotFound => "peer-not-found",
+            ErrorCode::PeerAlreadyExists => "peer-already-exists",
+            ErrorCode::PeerIllegalState => "peer-illegal-state",
+            ErrorCode::PeerIllegalDevices => "peer-illegal-devices",
+            ErrorCode::PeersUnavailable => "peers-unavailable",
+            ErrorCode::PeerUnreachable => "peer-unreachable",
+            ErrorCode::PeerCommandUnauthorized => "peer-command-unauthorized",
+            ErrorCode::PeerCommandFailed => "peer-command-failed",
+            ErrorCode::PolicyViolation => "policy-violation",
+            ErrorCode::PeerGroupNotFound => "peer-group-not-found",
+            ErrorCode::PeerGroupReferenced => "peer-group-referenced",
+            ErrorCode::ClusterConfigurationAlreadyExists => "cluster-configuration-already-exists",
+            ErrorCode::ClusterConfigurationNotFound => "cluster-configuration-not-found",
+            ErrorCode::ClusterIllegalState => "cluster-illegal-state",
+            ErrorCode::ClusterDeploymentNotFound => "cluster-deployment-not-found",
+            ErrorCode::ClusterDependencyInvalid => "cluster-dependency-invalid",
+            ErrorCode::ClusterReferencedAsDependency => "cluster-referenced-as-dependency",
+            ErrorCode::SnapshotNotFound => "snapshot-not-found",
+            ErrorCode::SnapshotNameAlreadyExists => "snapshot-name-already-exists",
+            ErrorCode::SnapshotResourcesMissing => "snapshot-resources-missing",
+            ErrorCode::TraceCaptureNotFound => "trace-capture-not-found",
+            ErrorCode::ExecutorNotFound => "executor-not-found",
+            ErrorCode::ExecutorResultsNotConfigured => "executor-results-not-configured",
+            ErrorCode::ExecutorResultNotFound => "executor-result-not-found",
+            ErrorCode::ExecutorResultsUnavailable => "executor-results-unavailable",
+            ErrorCode::MaintenanceOperationNotFound => "maintenance-operation-not-found",
+            ErrorCode::ProvisioningTokenInvalid => "provisioning-token-invalid",
+            ErrorCode::ProvisioningTokenClaimed => "provisioning-token-claimed",
+            ErrorCode::ProjectNameAlreadyExists => "project-name-already-exists",
+            ErrorCode::ProjectNotFound => "project-not-found",
+            ErrorCode::ServiceAccountNameAlreadyExists => "service-account-name-already-exists",
+            ErrorCode::ServiceAccountNotFound => "service-account-not-found",
+            ErrorCode::ServiceAccountTokenNotFound => "service-account-token-not-found",
+            ErrorCode::SelfTestReportNotFound => "self-test-report-not-found",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("Unknown error code '{0}'.")]
+pub struct UnknownErrorCode(pub String);
+
+impl FromStr for ErrorCode {
+    type Err = UnknownErrorCode;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ErrorCode::ALL.into_iter()
+            .find(|code| code.value() == value)
+            .ok_or_else(|| UnknownErrorCode(value.to_owned()))
+    }
+}
+
+/// Remediation hint for an error code, as provided by CARL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorRemediation {
+    pub code: String,
+    pub summary: String,
+    pub remediation: String,
+}
+
+pub trait HasErrorCode {
+    fn error_code(&self) -> Option<ErrorCode>;
+}
+
+impl HasErrorCode for StorePeerDescriptorError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StorePeerDescriptorError::IllegalPeerState { .. } => Some(ErrorCode::PeerIllegalState),
+            StorePeerDescriptorError::IllegalDevices { .. } => Some(ErrorCode::PeerIllegalDevices),
+            StorePeerDescriptorError::PolicyViolation { .. } => Some(ErrorCode::PolicyViolation),
+            StorePeerDescriptorError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeletePeerDescriptorError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeletePeerDescriptorError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            DeletePeerDescriptorError::IllegalPeerState { .. } => Some(ErrorCode::PeerIllegalState),
+            DeletePeerDescriptorError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for GetPeerDescriptorError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            GetPeerDescriptorError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            GetPeerDescriptorError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for GetPeerStateError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            GetPeerStateError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            GetPeerStateError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for PingPeerError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            PingPeerError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            PingPeerError::PeerNotConnected { .. } => Some(ErrorCode::PeerUnreachable),
+            PingPeerError::PeerNotResponding { .. } => Some(ErrorCode::PeerUnreachable),
+            PingPeerError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for SendPeerCommandError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            SendPeerCommandError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            SendPeerCommandError::Unauthorized { .. } => Some(ErrorCode::PeerCommandUnauthorized),
+            SendPeerCommandError::PeerNotConnected { .. } => Some(ErrorCode::PeerUnreachable),
+            SendPeerCommandError::PeerNotResponding { .. } => Some(ErrorCode::PeerUnreachable),
+            SendPeerCommandError::CommandFailed { .. } => Some(ErrorCode::PeerCommandFailed),
+            SendPeerCommandError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for StartRollingRestartError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StartRollingRestartError::NoPeers => Some(ErrorCode::InvalidRequest),
+            StartRollingRestartError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            StartRollingRestartError::IllegalCommand { .. } => Some(ErrorCode::InvalidRequest),
+            StartRollingRestartError::IllegalMaxUnavailable { .. } => Some(ErrorCode::InvalidRequest),
+            StartRollingRestartError::Unauthorized { .. } => Some(ErrorCode::PeerCommandUnauthorized),
+            StartRollingRestartError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for GetMaintenanceOperationError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            GetMaintenanceOperationError::OperationNotFound { .. } => Some(ErrorCode::MaintenanceOperationNotFound),
+        }
+    }
+}
+
+impl HasErrorCode for CreatePeerInClusterError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            CreatePeerInClusterError::PeerAlreadyExists { .. } => Some(ErrorCode::PeerAlreadyExists),
+            CreatePeerInClusterError::ClusterNotFound { .. } => Some(ErrorCode::ClusterConfigurationNotFound),
+            CreatePeerInClusterError::ClusterDeployed { .. } => Some(ErrorCode::ClusterIllegalState),
+            CreatePeerInClusterError::UnknownDevices { .. } => Some(ErrorCode::PeerIllegalDevices),
+            CreatePeerInClusterError::PolicyViolation { .. } => Some(ErrorCode::PolicyViolation),
+            CreatePeerInClusterError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for ClaimProvisioningTokenError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            ClaimProvisioningTokenError::InvalidToken { .. } => Some(ErrorCode::ProvisioningTokenInvalid),
+            ClaimProvisioningTokenError::AlreadyClaimed { .. } => Some(ErrorCode::ProvisioningTokenClaimed),
+            ClaimProvisioningTokenError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for RevokePeerSetupError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            RevokePeerSetupError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            RevokePeerSetupError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for StorePeerGroupError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StorePeerGroupError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            StorePeerGroupError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeletePeerGroupError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeletePeerGroupError::PeerGroupNotFound { .. } => Some(ErrorCode::PeerGroupNotFound),
+            DeletePeerGroupError::ReferencedByClusters { .. } => Some(ErrorCode::PeerGroupReferenced),
+            DeletePeerGroupError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for CreateClusterConfigurationError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            CreateClusterConfigurationError::ClusterConfigurationAlreadyExists { .. } => Some(ErrorCode::ClusterConfigurationAlreadyExists),
+            CreateClusterConfigurationError::PolicyViolation { .. } => Some(ErrorCode::PolicyViolation),
+            CreateClusterConfigurationError::InvalidDependencies { .. } => Some(ErrorCode::ClusterDependencyInvalid),
+            CreateClusterConfigurationError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteClusterConfigurationError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteClusterConfigurationError::ClusterConfigurationNotFound { .. } => Some(ErrorCode::ClusterConfigurationNotFound),
+            DeleteClusterConfigurationError::IllegalClusterState { .. } => Some(ErrorCode::ClusterIllegalState),
+            DeleteClusterConfigurationError::ReferencedAsDependency { .. } => Some(ErrorCode::ClusterReferencedAsDependency),
+            DeleteClusterConfigurationError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for StoreClusterDeploymentError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StoreClusterDeploymentError::IllegalClusterState { .. } => Some(ErrorCode::ClusterIllegalState),
+            StoreClusterDeploymentError::IllegalPeerState { .. } => Some(ErrorCode::PeersUnavailable),
+            StoreClusterDeploymentError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteClusterDeploymentError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteClusterDeploymentError::ClusterDeploymentNotFound { .. } => Some(ErrorCode::ClusterDeploymentNotFound),
+            DeleteClusterDeploymentError::IllegalClusterState { .. } => Some(ErrorCode::ClusterIllegalState),
+            DeleteClusterDeploymentError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for GetClusterDeploymentStatusError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            GetClusterDeploymentStatusError::ClusterDeploymentNotFound { .. } => Some(ErrorCode::ClusterDeploymentNotFound),
+            GetClusterDeploymentStatusError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for CreateSnapshotError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            CreateSnapshotError::SnapshotNameAlreadyExists { .. } => Some(ErrorCode::SnapshotNameAlreadyExists),
+            CreateSnapshotError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DiffSnapshotsError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DiffSnapshotsError::SnapshotNotFound { .. } => Some(ErrorCode::SnapshotNotFound),
+            DiffSnapshotsError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for RestoreSnapshotError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            RestoreSnapshotError::SnapshotNotFound { .. } => Some(ErrorCode::SnapshotNotFound),
+            RestoreSnapshotError::ResourcesNotInSnapshot { .. } => Some(ErrorCode::SnapshotResourcesMissing),
+            RestoreSnapshotError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteSnapshotError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteSnapshotError::SnapshotNotFound { .. } => Some(ErrorCode::SnapshotNotFound),
+            DeleteSnapshotError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for StartTraceCaptureError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StartTraceCaptureError::IllegalDuration { .. } => Some(ErrorCode::InvalidRequest),
+        }
+    }
+}
+
+impl HasErrorCode for DownloadTraceCaptureError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DownloadTraceCaptureError::TraceCaptureNotFound { .. } => Some(ErrorCode::TraceCaptureNotFound),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteTraceCaptureError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteTraceCaptureError::TraceCaptureNotFound { .. } => Some(ErrorCode::TraceCaptureNotFound),
+        }
+    }
+}
+
+impl HasErrorCode for TerminatePeerStreamError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            TerminatePeerStreamError::PeerNotConnected { .. } => Some(ErrorCode::PeerUnreachable),
+        }
+    }
+}
+
+impl HasErrorCode for ListExecutorResultsError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            ListExecutorResultsError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            ListExecutorResultsError::ExecutorNotFound { .. } => Some(ErrorCode::ExecutorNotFound),
+            ListExecutorResultsError::ResultsUrlNotConfigured { .. } => Some(ErrorCode::ExecutorResultsNotConfigured),
+            ListExecutorResultsError::Storage { .. } => Some(ErrorCode::ExecutorResultsUnavailable),
+        }
+    }
+}
+
+impl HasErrorCode for DownloadExecutorResultError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DownloadExecutorResultError::PeerNotFound { .. } => Some(ErrorCode::PeerNotFound),
+            DownloadExecutorResultError::ExecutorNotFound { .. } => Some(ErrorCode::ExecutorNotFound),
+            DownloadExecutorResultError::ResultsUrlNotConfigured { .. } => Some(ErrorCode::ExecutorResultsNotConfigured),
+            DownloadExecutorResultError::ResultNotFound { .. } => Some(ErrorCode::ExecutorResultNotFound),
+            DownloadExecutorResultError::Storage { .. } => Some(ErrorCode::ExecutorResultsUnavailable),
+        }
+    }
+}
+
+impl HasErrorCode for StoreProjectError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            StoreProjectError::NameAlreadyExists { .. } => Some(ErrorCode::ProjectNameAlreadyExists),
+            StoreProjectError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteProjectError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteProjectError::NotFound { .. } => Some(ErrorCode::ProjectNotFound),
+            DeleteProjectError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for CreateServiceAccountError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            CreateServiceAccountError::NameAlreadyExists { .. } => Some(ErrorCode::ServiceAccountNameAlreadyExists),
+            CreateServiceAccountError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for DeleteServiceAccountError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            DeleteServiceAccountError::NotFound { .. } => Some(ErrorCode::ServiceAccountNotFound),
+            DeleteServiceAccountError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for IssueServiceAccountTokenError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            IssueServiceAccountTokenError::NotFound { .. } => Some(ErrorCode::ServiceAccountNotFound),
+            IssueServiceAccountTokenError::IllegalValidity { .. } => Some(ErrorCode::InvalidRequest),
+            IssueServiceAccountTokenError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+impl HasErrorCode for RevokeServiceAccountTokenError {
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            RevokeServiceAccountTokenError::NotFound { .. } => Some(ErrorCode::ServiceAccountTokenNotFound),
+            RevokeServiceAccountTokenError::Internal { .. } => Some(ErrorCode::Internal),
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+impl <A> HasErrorCode for crate::carl::ClientError<A>
+where
+    A: fmt::Display + HasErrorCode
+{
+    fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            crate::carl::ClientError::TransportError(_) => Some(ErrorCode::CarlUnreachable),
+            crate::carl::ClientError::InvalidRequest(_) => Some(ErrorCode::InvalidRequest),
+            crate::carl::ClientError::InvalidResponse(_) => None,
+            crate::carl::ClientError::UsageError(error) => error.error_code(),
+        }
+    }
+}
+
@@ -7,7 +7,9 @@ mod client {
 
     use opendut_types::proto::util::VersionInfo;
 
+    use crate::carl::error_code::ErrorRemediation;
     use crate::proto::services::metadata_provider;
+    use crate::proto::services::metadata_provider::download_cleo_response;
     use crate::proto::services::metadata_provider::metadata_provider_client::MetadataProviderClient;
 
     #[derive(Clone, Debug)]
@@ -63,6 +65,70 @@ mod client {
                 },
             }
         }
+
+        /// Remediation hints for the error codes known to CARL.
+        pub async fn error_catalog(&mut self) -> Result<Vec<ErrorRemediation>, ErrorCatalogError> {
+            let request = tonic::Request::new(metadata_provider::ErrorCatalogRequest {});
+
+            match self.inner.error_catalog(request).await {
+                Ok(response) => {
+                    let entries = response.into_inner()
+                        .entries
+                        .into_iter()
+                        .map(ErrorRemediation::from)
+                        .collect();
+                    Ok(entries)
+                },
+                Err(status) => {
+                    Err(ErrorCatalogError { message: format!("gRPC failure: {status}") })
+                },
+            }
+        }
+
+        pub async fn download_cleo(&mut self, architecture: &str) -> Result<CleoDownload, DownloadCleoError> {
+            let request = tonic::Request::new(metadata_provider::DownloadCleoRequest {
+                architecture: architecture.to_owned(),
+            });
+
+            let mut stream = self.inner.download_cleo(request).await
+                .map_err(|status| DownloadCleoError { message: format!("gRPC failure: {status}") })?
+                .into_inner();
+
+            let mut header = None;
+            let mut archive = Vec::new();
+
+            while let Some(response) = stream.message().await
+                .map_err(|status| DownloadCleoError { message: format!("gRPC failure while downloading: {status}") })? {
+
+                match response.part {
+                    Some(download_cleo_response::Part::Header(received_header)) => {
+                        header = Some(received_header);
+                    }
+                    Some(download_cleo_response::Part::Chunk(chunk)) => {
+                        archive.extend(chunk);
+                    }
+                    None => {
+                        return Err(DownloadCleoError { message: String::from("Response contains no part of the download!") });
+                    }
+                }
+            }
+
+            let header = header.ok_or(DownloadCleoError { message: String::from("Response contains no header!") })?;
+            let version_info = header.version_info
+                .ok_or(DownloadCleoError { message: String::from("Response contains no version info!") })?;
+
+            Ok(CleoDownload {
+                version_info,
+                signature: header.signature,
+                archive,
+            })
+        }
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("{message}")]
+    pub struct ErrorCatalogError {
+        message: String,
     }
 
     #[derive(thiserror::Error, Debug)]
@@ -70,4 +136,18 @@ mod client {
     pub struct VersionError {
         message: String,
     }
+
+    /// CLEO distribution archive as provided by CARL, alongside the detached signature of the contained executable.
+    #[derive(Debug)]
+    pub struct CleoDownload {
+        pub version_info: VersionInfo,
+        pub signature: Vec<u8>,
+        pub archive: Vec<u8>,
+    }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("{message}")]
+    pub struct DownloadCleoError {
+        message: String,
+    }
 }
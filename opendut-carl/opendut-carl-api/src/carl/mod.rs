@@ -1,9 +1,18 @@
 use cfg_if::cfg_if;
 
+pub mod bootstrap;
 pub mod broker;
 pub mod cluster;
+pub mod diagnostics;
+pub mod error_code;
 pub mod metadata;
+pub mod observer;
 pub mod peer;
+pub mod policy;
+pub mod project;
+pub mod results;
+pub mod service_account;
+pub mod snapshot;
 
 cfg_if! {
     if #[cfg(any(feature = "client", feature = "wasm-client"))] {
@@ -109,24 +118,45 @@ cfg_if! {
         use opendut_auth::confidential::client::ConfidentialClient;
         use opendut_auth::confidential::tonic_service::TonicAuthenticationService;
 
+        use crate::carl::bootstrap::Bootstrap;
         use crate::carl::cluster::ClusterManager;
+        use crate::carl::diagnostics::Diagnostics;
         use crate::carl::metadata::MetadataProvider;
+        use crate::carl::observer::ResourceObserver;
         use crate::carl::peer::PeersRegistrar;
         use crate::carl::broker::PeerMessagingBroker;
+        use crate::carl::project::ProjectManager;
+        use crate::carl::results::ResultsManager;
+        use crate::carl::service_account::ServiceAccountManager;
+        use crate::carl::snapshot::SnapshotManager;
 
+        use crate::proto::services::bootstrap::bootstrap_client::BootstrapClient;
         use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
+        use crate::proto::services::diagnostics::diagnostics_client::DiagnosticsClient;
         use crate::proto::services::metadata_provider::metadata_provider_client::MetadataProviderClient;
         use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
         use crate::proto::services::peer_messaging_broker::peer_messaging_broker_client::PeerMessagingBrokerClient;
+        use crate::proto::services::project_manager::project_manager_client::ProjectManagerClient;
+        use crate::proto::services::results_manager::results_manager_client::ResultsManagerClient;
+        use crate::proto::services::resource_observer::resource_observer_client::ResourceObserverClient;
+        use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
+        use crate::proto::services::snapshot_manager::snapshot_manager_client::SnapshotManagerClient;
 
         use tower::ServiceBuilder;
 
         #[derive(Debug, Clone)]
         pub struct CarlClient {
+            pub bootstrap: Bootstrap<TonicAuthenticationService>,
             pub broker: PeerMessagingBroker<TonicAuthenticationService>,
             pub cluster: ClusterManager<TonicAuthenticationService>,
+            pub diagnostics: Diagnostics<TonicAuthenticationService>,
             pub metadata: MetadataProvider<TonicAuthenticationService>,
+            pub observer: ResourceObserver<TonicAuthenticationService>,
             pub peers: PeersRegistrar<TonicAuthenticationService>,
+            pub projects: ProjectManager<TonicAuthenticationService>,
+            pub results: ResultsManager<TonicAuthenticationService>,
+            pub service_accounts: ServiceAccountManager<TonicAuthenticationService>,
+            pub snapshots: SnapshotManager<TonicAuthenticationService>,
         }
 
         pub enum CaCertInfo {
@@ -177,8 +207,17 @@ cfg_if! {
                     .tls_config(tls_config)
                     .map_err(|cause| InitializationError::TlsConfiguration { message: String::from("Failed to initialize secure channel with specified TLS configuration"), cause: cause.into() })?;
 
-                let oidc_client = ConfidentialClient::from_settings(settings).await
-                    .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
+                let service_account_token = settings.get_string("network.service.account.token").ok()
+                    .filter(|token| !token.is_empty());
+
+                let oidc_client = match service_account_token {
+                    Some(_) => {
+                        debug!("Authenticating with the token of a service account.");
+                        None
+                    }
+                    None => ConfidentialClient::from_settings(settings).await
+                        .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?,
+                };
                 match oidc_client {
                     None => {}
                     Some(ref client) => {
@@ -193,14 +232,24 @@ cfg_if! {
                 info!("Connected to CARL at '{address}'.");
 
                 let auth_svc = ServiceBuilder::new()
-                    .layer_fn(|channel| TonicAuthenticationService::new(channel, oidc_client.clone()))
+                    .layer_fn(|channel| match &service_account_token {
+                        Some(token) => TonicAuthenticationService::with_static_token(channel, Clone::clone(token)),
+                        None => TonicAuthenticationService::new(channel, oidc_client.clone()),
+                    })
                     .service(channel);
 
                 Ok(CarlClient {
+                    bootstrap: Bootstrap::new(BootstrapClient::new(Clone::clone(&auth_svc))),
                     broker: PeerMessagingBroker::new(PeerMessagingBrokerClient::new(Clone::clone(&auth_svc))),
                     cluster: ClusterManager::new(ClusterManagerClient::new(Clone::clone(&auth_svc))),
+                    diagnostics: Diagnostics::new(DiagnosticsClient::new(Clone::clone(&auth_svc))),
                     metadata: MetadataProvider::new(MetadataProviderClient::new(Clone::clone(&auth_svc))),
+                    observer: ResourceObserver::new(ResourceObserverClient::new(Clone::clone(&auth_svc))),
                     peers: PeersRegistrar::new(PeerManagerClient::new(Clone::clone(&auth_svc))),
+                    projects: ProjectManager::new(ProjectManagerClient::new(Clone::clone(&auth_svc))),
+                    results: ResultsManager::new(ResultsManagerClient::new(Clone::clone(&auth_svc))),
+                    service_accounts: ServiceAccountManager::new(ServiceAccountManagerClient::new(Clone::clone(&auth_svc))),
+                    snapshots: SnapshotManager::new(SnapshotManagerClient::new(Clone::clone(&auth_svc))),
                 })
             }
         }
@@ -214,6 +263,7 @@ pub mod wasm {
 
     use opendut_auth::public::{Auth, AuthInterceptor, OptionalAuthData};
 
+    use crate::carl::bootstrap::Bootstrap;
     use crate::carl::cluster::ClusterManager;
     use crate::carl::InitializationError;
     use crate::carl::metadata::MetadataProvider;
@@ -221,6 +271,7 @@ pub mod wasm {
 
     #[derive(Debug, Clone)]
     pub struct CarlClient {
+        pub bootstrap: Bootstrap<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub cluster: ClusterManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub metadata: MetadataProvider<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub peers: PeersRegistrar<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
@@ -245,6 +296,7 @@ pub mod wasm {
             let auth_interceptor = AuthInterceptor::new(auth);
 
             Ok(CarlClient {
+                bootstrap: Bootstrap::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 cluster: ClusterManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 metadata: MetadataProvider::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 peers: PeersRegistrar::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
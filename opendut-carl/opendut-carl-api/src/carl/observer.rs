@@ -0,0 +1,151 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+#[cfg(feature = "client")]
+pub use client::*;
+use uuid::Uuid;
+
+/// Kind of resource, whose changes can be observed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    PeerDescriptor,
+    PeerState,
+    ClusterConfiguration,
+    ClusterDeployment,
+    ClusterDeploymentStatus,
+    PeerConfiguration,
+    PeerGroup,
+    HierarchyNode,
+    ResourceSnapshot,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 9] = [
+        ResourceKind::PeerDescriptor,
+        ResourceKind::PeerState,
+        ResourceKind::ClusterConfiguration,
+        ResourceKind::ClusterDeployment,
+        ResourceKind::ClusterDeploymentStatus,
+        ResourceKind::PeerConfiguration,
+        ResourceKind::PeerGroup,
+        ResourceKind::HierarchyNode,
+        ResourceKind::ResourceSnapshot,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceKind::PeerDescriptor => "peer-descriptor",
+            ResourceKind::PeerState => "peer-state",
+            ResourceKind::ClusterConfiguration => "cluster-configuration",
+            ResourceKind::ClusterDeployment => "cluster-deployment",
+            ResourceKind::ClusterDeploymentStatus => "cluster-deployment-status",
+            ResourceKind::PeerConfiguration => "peer-configuration",
+            ResourceKind::PeerGroup => "peer-group",
+            ResourceKind::HierarchyNode => "hierarchy-node",
+            ResourceKind::ResourceSnapshot => "snapshot",
+        }
+    }
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Selects the events of one kind of resource, optionally only of the resources with the given ids.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceFilter {
+    pub kind: ResourceKind,
+    /// If empty, the events of all resources of the kind are selected.
+    pub ids: Vec<Uuid>,
+}
+
+impl ResourceFilter {
+    pub fn all(kind: ResourceKind) -> Self {
+        Self { kind, ids: Vec::new() }
+    }
+
+    pub fn matches(&self, id: &Uuid) -> bool {
+        self.ids.is_empty() || self.ids.contains(id)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceEvent {
+    Changed {
+        kind: ResourceKind,
+        id: Uuid,
+        change: ResourceChange,
+    },
+    /// The subscriber fell behind and missed events, so it should retrieve the resources anew.
+    Lagged {
+        skipped: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceChange {
+    Inserted,
+    Removed,
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use std::convert::Infallible;
+
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::observer::{ResourceEvent, ResourceFilter};
+    use crate::proto::services::resource_observer;
+    use crate::proto::services::resource_observer::resource_observer_client::ResourceObserverClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ResourceObserver<T> {
+        inner: ResourceObserverClient<T>,
+    }
+
+    impl<T> ResourceObserver<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ResourceObserverClient<T>) -> ResourceObserver<T> {
+            ResourceObserver {
+                inner
+            }
+        }
+
+        /// Opens a stream of events about changes to the resources selected by the filters. The stream stays open until it is dropped.
+        pub async fn subscribe_resource_events(&mut self, filters: Vec<ResourceFilter>) -> Result<ResourceEvents, ClientError<Infallible>> {
+
+            let request = tonic::Request::new(resource_observer::SubscribeResourceEventsRequest {
+                filters: filters.into_iter().map(Into::into).collect(),
+            });
+
+            let stream = self.inner.subscribe_resource_events(request).await?
+                .into_inner();
+
+            Ok(ResourceEvents { inner: stream })
+        }
+    }
+
+    pub struct ResourceEvents {
+        inner: tonic::Streaming<resource_observer::SubscribeResourceEventsResponse>,
+    }
+
+    impl ResourceEvents {
+        /// Waits for the next event. Returns `None`, when CARL closed the stream.
+        pub async fn next(&mut self) -> Result<Option<ResourceEvent>, ClientError<Infallible>> {
+            match self.inner.message().await? {
+                None => Ok(None),
+                Some(response) => {
+                    let event = extract!(response.event)?;
+                    Ok(Some(event))
+                }
+            }
+        }
+    }
+}
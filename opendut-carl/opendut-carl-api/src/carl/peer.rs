@@ -1,11 +1,23 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Formatter;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
+use opendut_types::cluster::{ClusterId, ClusterName};
 use opendut_types::peer::{PeerId, PeerName};
+use opendut_types::peer::group::{PeerGroupId, PeerGroupName};
+use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNodeId, HierarchyNodeName};
+use opendut_types::peer::label::{PeerAnnotations, PeerLabels};
+use opendut_types::peer::provisioning::{PeerTemplate, ProvisioningToken, ProvisioningTokenId};
 use opendut_types::peer::state::PeerState;
 use opendut_types::ShortName;
 use opendut_types::topology::DeviceId;
+use uuid::Uuid;
+
+use crate::carl::policy;
+use crate::carl::policy::PolicyViolation;
 
 #[derive(thiserror::Error, Debug)]
 pub enum StorePeerDescriptorError {
@@ -22,6 +34,12 @@ pub enum StorePeerDescriptorError {
         peer_name: PeerName,
         error: IllegalDevicesError
     },
+    #[error("Peer '{peer_name}' <{peer_id}> was rejected, as it violates the following policies:\n  {}", policy::format_violations(violations))]
+    PolicyViolation {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        violations: Vec<PolicyViolation>,
+    },
     #[error("Peer '{peer_name}' <{peer_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         peer_id: PeerId,
@@ -117,18 +135,568 @@ pub enum IllegalDevicesError {
     }
 }
 
+/// Change to the devices of a ClusterConfiguration, which results from changing the members of a PeerGroup it references.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerGroupClusterChange {
+    pub cluster_id: ClusterId,
+    pub cluster_name: ClusterName,
+    pub deployed: bool,
+    pub added_devices: HashSet<DeviceId>,
+    pub removed_devices: HashSet<DeviceId>,
+}
+
+impl PeerGroupClusterChange {
+    pub fn is_empty(&self) -> bool {
+        self.added_devices.is_empty() && self.removed_devices.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct StorePeerGroupOutcome {
+    pub peer_group_id: PeerGroupId,
+    pub applied_changes: Vec<PeerGroupClusterChange>,
+    pub skipped_changes: Vec<PeerGroupClusterChange>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StorePeerGroupError {
+    #[error("PeerGroup '{peer_group_name}' <{peer_group_id}> could not be stored, because peer <{peer_id}> does not exist!")]
+    PeerNotFound {
+        peer_group_id: PeerGroupId,
+        peer_group_name: PeerGroupName,
+        peer_id: PeerId,
+    },
+    #[error("PeerGroup '{peer_group_name}' <{peer_group_id}> could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        peer_group_id: PeerGroupId,
+        peer_group_name: PeerGroupName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PreviewPeerGroupChangesError {
+    #[error("An internal error occurred computing the changes of PeerGroup <{peer_group_id}>:\n  {cause}")]
+    Internal {
+        peer_group_id: PeerGroupId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeletePeerGroupError {
+    #[error("PeerGroup <{peer_group_id}> could not be deleted, because a PeerGroup with that id does not exist!")]
+    PeerGroupNotFound {
+        peer_group_id: PeerGroupId
+    },
+    #[error("PeerGroup <{peer_group_id}> could not be deleted, because it is still referenced by the clusters: {}", cluster_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    ReferencedByClusters {
+        peer_group_id: PeerGroupId,
+        cluster_ids: Vec<ClusterId>,
+    },
+    #[error("PeerGroup <{peer_group_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        peer_group_id: PeerGroupId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListPeerGroupsError {
+    #[error("An internal error occurred computing the list of peer groups:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EditPeerLabelsError {
+    #[error("An internal error occurred editing the labels of peers:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+/// Labels and annotations of a peer matched by a bulk edit, with the changes applied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EditedPeerLabels {
+    pub peer_id: PeerId,
+    pub peer_name: PeerName,
+    pub labels: PeerLabels,
+    pub annotations: PeerAnnotations,
+    /// Whether the changes resulted in different labels or annotations. Peers which already had the requested labels are not rewritten.
+    pub changed: bool,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PingPeerError {
+    #[error("A peer with id <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+    #[error("Peer <{peer_id}> is not connected to CARL.")]
+    PeerNotConnected {
+        peer_id: PeerId,
+        last_seen: Option<SystemTime>,
+    },
+    #[error("Peer <{peer_id}> is connected to CARL, but did not respond within {} ms.", timeout.as_millis())]
+    PeerNotResponding {
+        peer_id: PeerId,
+        timeout: Duration,
+        last_seen: Option<SystemTime>,
+    },
+    #[error("An internal error occurred pinging peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
+/// Result of a probe, which CARL round-tripped to a peer over its messaging stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerPing {
+    pub peer_id: PeerId,
+    pub round_trip_time: Duration,
+    /// When CARL last received a message from the peer, which is the probe response itself.
+    pub last_seen: SystemTime,
+}
+
+/// Administrative command, which CARL relays to a connected peer on behalf of a user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PeerCommand {
+    RestartService,
+    ReapplyConfiguration,
+    CollectDiagnostics,
+    RebootHost,
+    /// Retrieves the recent log lines of the EDGAR service, which include the output of its executors.
+    CollectLogs,
+    /// Starts capturing packets on the bridge and the CAN interfaces of the peer into rotating pcap files.
+    StartPacketCapture,
+    /// Stops capturing packets and uploads the pcap files, if an upload target is configured on the peer.
+    StopPacketCapture,
+}
+
+impl PeerCommand {
+    pub const ALL: [PeerCommand; 7] = [
+        PeerCommand::RestartService,
+        PeerCommand::ReapplyConfiguration,
+        PeerCommand::CollectDiagnostics,
+        PeerCommand::RebootHost,
+        PeerCommand::CollectLogs,
+        PeerCommand::StartPacketCapture,
+        PeerCommand::StopPacketCapture,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PeerCommand::RestartService => "restart-service",
+            PeerCommand::ReapplyConfiguration => "reapply-configuration",
+            PeerCommand::CollectDiagnostics => "collect-diagnostics",
+            PeerCommand::RebootHost => "reboot-host",
+            PeerCommand::CollectLogs => "collect-logs",
+            PeerCommand::StartPacketCapture => "start-packet-capture",
+            PeerCommand::StopPacketCapture => "stop-packet-capture",
+        }
+    }
+}
+
+impl fmt::Display for PeerCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for PeerCommand {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        PeerCommand::ALL.into_iter()
+            .find(|command| command.name() == value)
+            .ok_or_else(|| format!("Unknown peer command '{value}'. Expected one of: {}", PeerCommand::ALL.map(|command| command.name()).join(", ")))
+    }
+}
+
+/// Outcome of a command, which a peer executed successfully.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerCommandResult {
+    pub peer_id: PeerId,
+    pub command: PeerCommand,
+    pub output: String,
+    /// Archive collected by the command, e.g. the diagnostics bundle as .tar.gz, empty otherwise.
+    pub attachment: Vec<u8>,
+    pub duration: Duration,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SendPeerCommandError {
+    #[error("A peer with id <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+    #[error("User '{user}' is not authorized to send command '{command}' to peer <{peer_id}>. One of these roles is required: {}", required_roles.join(", "))]
+    Unauthorized {
+        peer_id: PeerId,
+        command: PeerCommand,
+        user: String,
+        required_roles: Vec<String>,
+    },
+    #[error("Command '{command}' could not be sent, since peer <{peer_id}> is not connected to CARL.")]
+    PeerNotConnected {
+        peer_id: PeerId,
+        command: PeerCommand,
+    },
+    #[error("Peer <{peer_id}> did not report the result of command '{command}' within {} ms.", timeout.as_millis())]
+    PeerNotResponding {
+        peer_id: PeerId,
+        command: PeerCommand,
+        timeout: Duration,
+    },
+    #[error("Peer <{peer_id}> failed to execute command '{command}':\n  {cause}")]
+    CommandFailed {
+        peer_id: PeerId,
+        command: PeerCommand,
+        cause: String,
+    },
+    #[error("An internal error occurred sending a command to peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
+/// Identifies a maintenance operation, which CARL performs in the background, e.g. a rolling restart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MaintenanceOperationId(pub Uuid);
+
+impl fmt::Display for MaintenanceOperationId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for MaintenanceOperationId {
+    type Err = uuid::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Uuid::from_str(value).map(Self)
+    }
+}
+
+/// Restarts the selected peers, a few at a time, so that no deployed cluster loses more than `max_unavailable_per_cluster` members at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollingRestartSpec {
+    pub peers: Vec<PeerId>,
+    /// Either `restart-service` or `reboot-host`.
+    pub command: PeerCommand,
+    pub max_unavailable_per_cluster: u32,
+    /// How long to wait for each peer to execute the command and to connect to CARL again.
+    pub peer_timeout: Duration,
+}
+
+impl RollingRestartSpec {
+    pub const COMMANDS: [PeerCommand; 2] = [PeerCommand::RestartService, PeerCommand::RebootHost];
+}
+
+/// Progress of a rolling restart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaintenanceOperation {
+    pub id: MaintenanceOperationId,
+    pub command: PeerCommand,
+    pub started_at: SystemTime,
+    pub peers: Vec<MaintenancePeerStatus>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaintenancePeerStatus {
+    pub peer_id: PeerId,
+    pub state: MaintenancePeerState,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MaintenancePeerState {
+    Waiting,
+    /// The command was sent and CARL waits for the peer to connect again.
+    Restarting,
+    Completed,
+    Failed { cause: String },
+    /// Not restarted, since the restart of another peer failed.
+    Skipped,
+}
+
+impl MaintenanceOperation {
+    /// Whether no peer is waiting or restarting anymore.
+    pub fn is_finished(&self) -> bool {
+        self.peers.iter()
+            .all(|peer| matches!(peer.state, MaintenancePeerState::Completed | MaintenancePeerState::Failed { .. } | MaintenancePeerState::Skipped))
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.peers.iter()
+            .any(|peer| matches!(peer.state, MaintenancePeerState::Failed { .. }))
+    }
+
+    pub fn count(&self, state: &MaintenancePeerState) -> usize {
+        self.peers.iter()
+            .filter(|peer| peer.state.short_name() == state.short_name())
+            .count()
+    }
+}
+
+impl ShortName for MaintenancePeerState {
+    fn short_name(&self) -> &'static str {
+        match self {
+            MaintenancePeerState::Waiting => "Waiting",
+            MaintenancePeerState::Restarting => "Restarting",
+            MaintenancePeerState::Completed => "Completed",
+            MaintenancePeerState::Failed { .. } => "Failed",
+            MaintenancePeerState::Skipped => "Skipped",
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum StartRollingRestartError {
+    #[error("No peers were selected for the rolling restart.")]
+    NoPeers,
+    #[error("A peer with id <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+    #[error("Command '{command}' cannot be used for a rolling restart. Expected one of: {}", RollingRestartSpec::COMMANDS.map(|command| command.name()).join(", "))]
+    IllegalCommand {
+        command: PeerCommand,
+    },
+    #[error("At least one member of each cluster must be allowed to restart at once, but {max_unavailable_per_cluster} were given.")]
+    IllegalMaxUnavailable {
+        max_unavailable_per_cluster: u32,
+    },
+    #[error("User '{user}' is not authorized to send command '{command}' to peers. One of these roles is required: {}", required_roles.join(", "))]
+    Unauthorized {
+        command: PeerCommand,
+        user: String,
+        required_roles: Vec<String>,
+    },
+    #[error("An internal error occurred starting a rolling restart:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetMaintenanceOperationError {
+    #[error("Maintenance operation <{operation_id}> could not be found!")]
+    OperationNotFound {
+        operation_id: MaintenanceOperationId,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreHierarchyNodeError {
+    #[error("Hierarchy node '{node_name}' <{node_id}> could not be stored, because it would be misplaced:\n  {reason}")]
+    IllegalPlacement {
+        node_id: HierarchyNodeId,
+        node_name: HierarchyNodeName,
+        reason: String,
+    },
+    #[error("Hierarchy node '{node_name}' <{node_id}> could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        node_id: HierarchyNodeId,
+        node_name: HierarchyNodeName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteHierarchyNodeError {
+    #[error("Hierarchy node <{node_id}> could not be deleted, because a node with that id does not exist!")]
+    NodeNotFound {
+        node_id: HierarchyNodeId
+    },
+    #[error("Hierarchy node <{node_id}> could not be deleted, because it still contains the nodes: {}", child_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    HasChildren {
+        node_id: HierarchyNodeId,
+        child_ids: Vec<HierarchyNodeId>,
+    },
+    #[error("Hierarchy node <{node_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        node_id: HierarchyNodeId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListHierarchyNodesError {
+    #[error("An internal error occurred computing the list of hierarchy nodes:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GetHierarchyHealthError {
+    #[error("An internal error occurred aggregating the health of the hierarchy:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+/// Health of the peers located in a node of the hierarchy, including the peers of all nodes below it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HierarchyNodeHealth {
+    pub node_id: HierarchyNodeId,
+    pub node_name: HierarchyNodeName,
+    pub level: HierarchyLevel,
+    /// Peers which are connected and not part of a deployed cluster.
+    pub available_peers: u32,
+    /// Peers which are connected and part of a deployed cluster.
+    pub blocked_peers: u32,
+    pub down_peers: u32,
+}
+
+impl HierarchyNodeHealth {
+    pub fn total_peers(&self) -> u32 {
+        self.available_peers + self.blocked_peers + self.down_peers
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreatePeerInClusterError {
+    #[error("Peer '{peer_name}' <{peer_id}> could not be created, because a peer with that id already exists!")]
+    PeerAlreadyExists {
+        peer_id: PeerId,
+        peer_name: PeerName,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be created, because cluster <{cluster_id}> does not exist!")]
+    ClusterNotFound {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cluster_id: ClusterId,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be created, because cluster '{cluster_name}' <{cluster_id}> is deployed and cannot be changed!")]
+    ClusterDeployed {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be created, because it does not contain the devices: {}", device_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    UnknownDevices {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        device_ids: Vec<DeviceId>,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> was rejected, as it or its cluster would violate the following policies:\n  {}", policy::format_violations(violations))]
+    PolicyViolation {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        violations: Vec<PolicyViolation>,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be created in a cluster, due to internal errors:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateProvisioningTokensError {
+    #[error("Cannot generate {count} provisioning tokens for pool '{pool}'. Between 1 and {max_count} tokens can be generated at once.")]
+    IllegalCount {
+        pool: String,
+        count: u32,
+        max_count: u32,
+    },
+    #[error("Provisioning tokens for pool '{pool}' could not be generated, due to internal errors:\n  {cause}")]
+    Internal {
+        pool: String,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListProvisioningTokensError {
+    #[error("An internal error occurred computing the list of provisioning tokens:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClaimProvisioningTokenError {
+    #[error("Provisioning token <{token}> does not exist or its secret does not match!")]
+    InvalidToken {
+        token: ProvisioningTokenId,
+    },
+    #[error("Provisioning token <{token}> was already claimed by another peer!")]
+    AlreadyClaimed {
+        token: ProvisioningTokenId,
+    },
+    #[error("Provisioning token <{token}> could not be claimed, due to internal errors:\n  {cause}")]
+    Internal {
+        token: ProvisioningTokenId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokePeerSetupError {
+    #[error("Setup-String of peer <{peer_id}> could not be revoked, because a peer with that ID does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId,
+    },
+    #[error("Setup-String of peer <{peer_id}> could not be revoked, due to internal errors:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
+/// Provisioning token as listed to users. In contrast to the stored token, it does not contain the hash of the secret.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProvisioningTokenInfo {
+    pub id: ProvisioningTokenId,
+    pub pool: String,
+    pub template: PeerTemplate,
+    pub created_by: String,
+    pub created_at: SystemTime,
+    pub claimed_by: Option<PeerId>,
+}
+
+impl From<ProvisioningToken> for ProvisioningTokenInfo {
+    fn from(token: ProvisioningToken) -> Self {
+        Self {
+            id: token.id,
+            pool: token.pool,
+            template: token.template,
+            created_by: token.created_by,
+            created_at: token.created_at,
+            claimed_by: token.claimed_by,
+        }
+    }
+}
+
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 mod client {
+    use std::collections::HashSet;
+    use std::time::Duration;
+
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
     use tracing::error;
     use opendut_types::cleo::CleoSetup;
 
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerSetup};
+    use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+    use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+    use opendut_types::peer::label::{AnnotationChange, LabelChange, LabelSelector};
+    use opendut_types::peer::provisioning::{PeerProvisioning, PeerTemplate, ProvisioningTokenId};
     use opendut_types::peer::state::PeerState;
-    use opendut_types::topology::DeviceDescriptor;
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::project::ProjectId;
+    use opendut_types::topology::{DeviceDescriptor, DeviceId};
 
     use crate::carl::{ClientError, extract};
-    use crate::carl::peer::{DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStateError, ListDevicesError, ListPeerDescriptorsError, StorePeerDescriptorError};
+    use crate::carl::peer::{ClaimProvisioningTokenError, CreatePeerInClusterError, DeleteHierarchyNodeError, DeletePeerDescriptorError, DeletePeerGroupError, EditedPeerLabels, EditPeerLabelsError, GenerateProvisioningTokensError, GetHierarchyHealthError, GetMaintenanceOperationError, GetPeerDescriptorError, GetPeerStateError, HierarchyNodeHealth, ListDevicesError, ListHierarchyNodesError, ListPeerDescriptorsError, ListPeerGroupsError, ListProvisioningTokensError, MaintenanceOperation, MaintenanceOperationId, PeerCommand, PeerCommandResult, PeerGroupClusterChange, PeerPing, PingPeerError, PreviewPeerGroupChangesError, ProvisioningTokenInfo, RevokePeerSetupError, RollingRestartSpec, SendPeerCommandError, StartRollingRestartError, StoreHierarchyNodeError, StorePeerDescriptorError, StorePeerGroupError, StorePeerGroupOutcome};
     use crate::proto::services::peer_manager;
     use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
 
@@ -173,9 +741,15 @@ mod client {
         }
 
         pub async fn store_peer_descriptor(&mut self, descriptor: PeerDescriptor) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
+            self.store_peer_descriptor_in_project(descriptor, None).await
+        }
+
+        /// Stores the peer and assigns it to the project, if given, so that only members of the project can access it.
+        pub async fn store_peer_descriptor_in_project(&mut self, descriptor: PeerDescriptor, project_id: Option<ProjectId>) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::StorePeerDescriptorRequest {
                 peer: Some(descriptor.into()),
+                project_id: project_id.map(Into::into),
             });
 
             let response = self.inner.store_peer_descriptor(request).await?
@@ -236,8 +810,15 @@ mod client {
         }
 
         pub async fn list_peer_descriptors(&mut self) -> Result<Vec<PeerDescriptor>, ClientError<ListPeerDescriptorsError>> {
+            self.list_peer_descriptors_in_project(None).await
+        }
 
-            let request = tonic::Request::new(peer_manager::ListPeerDescriptorsRequest {});
+        /// Lists the peers, which the user may access. With a project, only the peers assigned to that project are listed.
+        pub async fn list_peer_descriptors_in_project(&mut self, project_id: Option<ProjectId>) -> Result<Vec<PeerDescriptor>, ClientError<ListPeerDescriptorsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListPeerDescriptorsRequest {
+                project_id: project_id.map(Into::into),
+            });
 
             let response = self.inner.list_peer_descriptors(request).await?
                 .into_inner();
@@ -354,6 +935,425 @@ mod client {
                 },
             }
         }
+
+        pub async fn store_peer_group(&mut self, peer_group: PeerGroup, propagate_to_deployed_clusters: bool) -> Result<StorePeerGroupOutcome, ClientError<StorePeerGroupError>> {
+
+            let request = tonic::Request::new(peer_manager::StorePeerGroupRequest {
+                peer_group: Some(peer_group.into()),
+                propagate_to_deployed_clusters,
+            });
+
+            let response = self.inner.store_peer_group(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::store_peer_group_response::Reply::Failure(failure) => {
+                    let error = StorePeerGroupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::store_peer_group_response::Reply::Success(success) => {
+                    let peer_group_id = extract!(success.peer_group_id)?;
+                    Ok(StorePeerGroupOutcome {
+                        peer_group_id,
+                        applied_changes: success.applied_changes.into_iter()
+                            .map(PeerGroupClusterChange::try_from)
+                            .collect::<Result<Vec<_>, _>>()?,
+                        skipped_changes: success.skipped_changes.into_iter()
+                            .map(PeerGroupClusterChange::try_from)
+                            .collect::<Result<Vec<_>, _>>()?,
+                    })
+                }
+            }
+        }
+
+        pub async fn preview_peer_group_changes(&mut self, peer_group: PeerGroup) -> Result<Vec<PeerGroupClusterChange>, ClientError<PreviewPeerGroupChangesError>> {
+
+            let request = tonic::Request::new(peer_manager::PreviewPeerGroupChangesRequest {
+                peer_group: Some(peer_group.into()),
+            });
+
+            let response = self.inner.preview_peer_group_changes(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::preview_peer_group_changes_response::Reply::Failure(failure) => {
+                    let error = PreviewPeerGroupChangesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::preview_peer_group_changes_response::Reply::Success(success) => {
+                    Ok(success.changes.into_iter()
+                        .map(PeerGroupClusterChange::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn delete_peer_group(&mut self, peer_group_id: PeerGroupId) -> Result<PeerGroupId, ClientError<DeletePeerGroupError>> {
+
+            let request = tonic::Request::new(peer_manager::DeletePeerGroupRequest {
+                peer_group_id: Some(peer_group_id.into()),
+            });
+
+            let response = self.inner.delete_peer_group(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::delete_peer_group_response::Reply::Failure(failure) => {
+                    let error = DeletePeerGroupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::delete_peer_group_response::Reply::Success(success) => {
+                    let peer_group_id = extract!(success.peer_group_id)?;
+                    Ok(peer_group_id)
+                }
+            }
+        }
+
+        pub async fn list_peer_groups(&mut self) -> Result<Vec<PeerGroup>, ClientError<ListPeerGroupsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListPeerGroupsRequest {});
+
+            let response = self.inner.list_peer_groups(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_peer_groups_response::Reply::Failure(failure) => {
+                    let error = ListPeerGroupsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_peer_groups_response::Reply::Success(success) => {
+                    Ok(success.peer_groups.into_iter()
+                        .map(PeerGroup::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn edit_peer_labels(
+            &mut self,
+            selector: LabelSelector,
+            label_changes: Vec<LabelChange>,
+            annotation_changes: Vec<AnnotationChange>,
+            preview: bool,
+        ) -> Result<Vec<EditedPeerLabels>, ClientError<EditPeerLabelsError>> {
+
+            let request = tonic::Request::new(peer_manager::EditPeerLabelsRequest {
+                selector: Some(selector.into()),
+                label_changes: label_changes.into_iter().map(Into::into).collect(),
+                annotation_changes: annotation_changes.into_iter().map(Into::into).collect(),
+                preview,
+            });
+
+            let response = self.inner.edit_peer_labels(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::edit_peer_labels_response::Reply::Failure(failure) => {
+                    let error = EditPeerLabelsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::edit_peer_labels_response::Reply::Success(success) => {
+                    Ok(success.peers.into_iter()
+                        .map(EditedPeerLabels::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn ping_peer(&mut self, peer_id: PeerId, timeout: Duration) -> Result<PeerPing, ClientError<PingPeerError>> {
+
+            let request = tonic::Request::new(peer_manager::PingPeerRequest {
+                peer_id: Some(peer_id.into()),
+                timeout_ms: timeout.as_millis() as u64,
+            });
+
+            let response = self.inner.ping_peer(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::ping_peer_response::Reply::Failure(failure) => {
+                    let error = PingPeerError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::ping_peer_response::Reply::Success(success) => {
+                    Ok(PeerPing::try_from(success)?)
+                }
+            }
+        }
+
+        pub async fn send_peer_command(&mut self, peer_id: PeerId, command: PeerCommand, timeout: Duration) -> Result<PeerCommandResult, ClientError<SendPeerCommandError>> {
+
+            let request = tonic::Request::new(peer_manager::SendPeerCommandRequest {
+                peer_id: Some(peer_id.into()),
+                command: Some(command.into()),
+                timeout_ms: timeout.as_millis() as u64,
+            });
+
+            let response = self.inner.send_peer_command(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::send_peer_command_response::Reply::Failure(failure) => {
+                    let error = SendPeerCommandError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::send_peer_command_response::Reply::Success(success) => {
+                    Ok(PeerCommandResult::try_from(success)?)
+                }
+            }
+        }
+
+        pub async fn start_rolling_restart(&mut self, spec: RollingRestartSpec) -> Result<MaintenanceOperation, ClientError<StartRollingRestartError>> {
+
+            let request = tonic::Request::new(peer_manager::StartRollingRestartRequest {
+                peers: spec.peers.into_iter().map(Into::into).collect(),
+                command: Some(spec.command.into()),
+                max_unavailable_per_cluster: spec.max_unavailable_per_cluster,
+                peer_timeout_ms: spec.peer_timeout.as_millis() as u64,
+            });
+
+            let response = self.inner.start_rolling_restart(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::start_rolling_restart_response::Reply::Failure(failure) => {
+                    let error = StartRollingRestartError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::start_rolling_restart_response::Reply::Success(success) => {
+                    let operation = extract!(success.operation)?;
+                    Ok(operation)
+                }
+            }
+        }
+
+        pub async fn get_maintenance_operation(&mut self, operation_id: MaintenanceOperationId) -> Result<MaintenanceOperation, ClientError<GetMaintenanceOperationError>> {
+
+            let request = tonic::Request::new(peer_manager::GetMaintenanceOperationRequest {
+                operation_id: Some(operation_id.into()),
+            });
+
+            let response = self.inner.get_maintenance_operation(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_maintenance_operation_response::Reply::Failure(failure) => {
+                    let error = GetMaintenanceOperationError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_maintenance_operation_response::Reply::Success(success) => {
+                    let operation = extract!(success.operation)?;
+                    Ok(operation)
+                }
+            }
+        }
+
+        pub async fn store_hierarchy_node(&mut self, node: HierarchyNode) -> Result<HierarchyNodeId, ClientError<StoreHierarchyNodeError>> {
+
+            let request = tonic::Request::new(peer_manager::StoreHierarchyNodeRequest {
+                node: Some(node.into()),
+            });
+
+            let response = self.inner.store_hierarchy_node(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::store_hierarchy_node_response::Reply::Failure(failure) => {
+                    let error = StoreHierarchyNodeError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::store_hierarchy_node_response::Reply::Success(success) => {
+                    let node_id = extract!(success.node_id)?;
+                    Ok(node_id)
+                }
+            }
+        }
+
+        pub async fn delete_hierarchy_node(&mut self, node_id: HierarchyNodeId) -> Result<HierarchyNodeId, ClientError<DeleteHierarchyNodeError>> {
+
+            let request = tonic::Request::new(peer_manager::DeleteHierarchyNodeRequest {
+                node_id: Some(node_id.into()),
+            });
+
+            let response = self.inner.delete_hierarchy_node(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::delete_hierarchy_node_response::Reply::Failure(failure) => {
+                    let error = DeleteHierarchyNodeError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::delete_hierarchy_node_response::Reply::Success(success) => {
+                    let node_id = extract!(success.node_id)?;
+                    Ok(node_id)
+                }
+            }
+        }
+
+        pub async fn list_hierarchy_nodes(&mut self) -> Result<Vec<HierarchyNode>, ClientError<ListHierarchyNodesError>> {
+
+            let request = tonic::Request::new(peer_manager::ListHierarchyNodesRequest {});
+
+            let response = self.inner.list_hierarchy_nodes(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_hierarchy_nodes_response::Reply::Failure(failure) => {
+                    let error = ListHierarchyNodesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_hierarchy_nodes_response::Reply::Success(success) => {
+                    Ok(success.nodes.into_iter()
+                        .map(HierarchyNode::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn get_hierarchy_health(&mut self) -> Result<Vec<HierarchyNodeHealth>, ClientError<GetHierarchyHealthError>> {
+
+            let request = tonic::Request::new(peer_manager::GetHierarchyHealthRequest {});
+
+            let response = self.inner.get_hierarchy_health(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_hierarchy_health_response::Reply::Failure(failure) => {
+                    let error = GetHierarchyHealthError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_hierarchy_health_response::Reply::Success(success) => {
+                    Ok(success.nodes.into_iter()
+                        .map(HierarchyNodeHealth::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Creates the peer and adds the given devices of it to an existing cluster. Either both changes are applied or none of them.
+        pub async fn create_peer_in_cluster(&mut self, peer: PeerDescriptor, cluster_id: ClusterId, devices: HashSet<DeviceId>) -> Result<PeerId, ClientError<CreatePeerInClusterError>> {
+
+            let request = tonic::Request::new(peer_manager::CreatePeerInClusterRequest {
+                peer: Some(peer.into()),
+                cluster_id: Some(cluster_id.into()),
+                devices: devices.into_iter().map(Into::into).collect(),
+            });
+
+            let response = self.inner.create_peer_in_cluster(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::create_peer_in_cluster_response::Reply::Failure(failure) => {
+                    let error = CreatePeerInClusterError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::create_peer_in_cluster_response::Reply::Success(success) => {
+                    let peer_id = extract!(success.peer_id)?;
+                    Ok(peer_id)
+                }
+            }
+        }
+
+        /// Generates a pool of single-use tokens, with which devices can register themselves as peers based on the given template.
+        pub async fn generate_provisioning_tokens(&mut self, pool: String, count: u32, template: PeerTemplate, user_id: String) -> Result<Vec<PeerProvisioning>, ClientError<GenerateProvisioningTokensError>> {
+
+            let request = tonic::Request::new(peer_manager::GenerateProvisioningTokensRequest {
+                pool,
+                count,
+                template: Some(template.into()),
+                user_id,
+            });
+
+            let response = self.inner.generate_provisioning_tokens(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::generate_provisioning_tokens_response::Reply::Failure(failure) => {
+                    let error = GenerateProvisioningTokensError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::generate_provisioning_tokens_response::Reply::Success(success) => {
+                    Ok(success.provisionings.into_iter()
+                        .map(PeerProvisioning::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn list_provisioning_tokens(&mut self) -> Result<Vec<ProvisioningTokenInfo>, ClientError<ListProvisioningTokensError>> {
+
+            let request = tonic::Request::new(peer_manager::ListProvisioningTokensRequest {});
+
+            let response = self.inner.list_provisioning_tokens(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_provisioning_tokens_response::Reply::Failure(failure) => {
+                    let error = ListProvisioningTokensError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_provisioning_tokens_response::Reply::Success(success) => {
+                    Ok(success.tokens.into_iter()
+                        .map(ProvisioningTokenInfo::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Redeems a provisioning token, which registers a new peer and returns its setup. Does not require authentication.
+        pub async fn claim_provisioning_token(&mut self, token: ProvisioningTokenId, secret: String, hostname: String) -> Result<(PeerId, PeerSetup), ClientError<ClaimProvisioningTokenError>> {
+
+            let request = tonic::Request::new(peer_manager::ClaimProvisioningTokenRequest {
+                token: Some(token.into()),
+                secret,
+                hostname,
+            });
+
+            let response = self.inner.claim_provisioning_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::claim_provisioning_token_response::Reply::Failure(failure) => {
+                    let error = ClaimProvisioningTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::claim_provisioning_token_response::Reply::Success(success) => {
+                    let peer_id = extract!(success.peer_id)?;
+                    let setup = extract!(success.setup)?;
+                    Ok((peer_id, setup))
+                }
+            }
+        }
+
+        /// Revokes the Setup-String generated last for the peer, including the credentials contained in it.
+        /// The peer can only connect again after it was set up with a newly generated Setup-String.
+        pub async fn revoke_peer_setup(&mut self, peer_id: PeerId) -> Result<PeerId, ClientError<RevokePeerSetupError>> {
+
+            let request = tonic::Request::new(peer_manager::RevokePeerSetupRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.revoke_peer_setup(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::revoke_peer_setup_response::Reply::Failure(failure) => {
+                    let error = RevokePeerSetupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::revoke_peer_setup_response::Reply::Success(success) => {
+                    let peer_id = extract!(success.peer_id)?;
+                    Ok(peer_id)
+                }
+            }
+        }
     }
 
     #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Violation of an admission policy, due to which CARL rejected a resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Name of the violated rule, e.g. `naming-convention` or the name of a rule of an external policy.
+    pub rule: String,
+    pub message: String,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+pub(crate) fn format_violations(violations: &[PolicyViolation]) -> String {
+    violations.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n  ")
+}
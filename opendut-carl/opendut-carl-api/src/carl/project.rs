@@ -0,0 +1,134 @@
+#[cfg(feature = "client")]
+pub use client::*;
+use opendut_types::project::{ProjectId, ProjectName};
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreProjectError {
+    #[error("Project '{name}' <{project_id}> could not be stored, because another project with that name already exists!")]
+    NameAlreadyExists {
+        project_id: ProjectId,
+        name: ProjectName,
+    },
+    #[error("Project <{project_id}> could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        project_id: ProjectId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteProjectError {
+    #[error("Project <{project_id}> could not be deleted, because a project with that id does not exist!")]
+    NotFound {
+        project_id: ProjectId,
+    },
+    #[error("Project <{project_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        project_id: ProjectId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListProjectsError {
+    #[error("An internal error occurred computing the list of projects:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use opendut_types::project::{Project, ProjectId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::project::{DeleteProjectError, ListProjectsError, StoreProjectError};
+    use crate::proto::services::project_manager;
+    use crate::proto::services::project_manager::project_manager_client::ProjectManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ProjectManager<T> {
+        inner: ProjectManagerClient<T>,
+    }
+
+    impl<T> ProjectManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ProjectManagerClient<T>) -> ProjectManager<T> {
+            ProjectManager {
+                inner
+            }
+        }
+
+        /// Creates the project or replaces an existing project with the same ID, including its members and assigned resources.
+        pub async fn store_project(&mut self, project: Project) -> Result<ProjectId, ClientError<StoreProjectError>> {
+
+            let request = tonic::Request::new(project_manager::StoreProjectRequest {
+                project: Some(project.into()),
+            });
+
+            let response = self.inner.store_project(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                project_manager::store_project_response::Reply::Failure(failure) => {
+                    let error = StoreProjectError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                project_manager::store_project_response::Reply::Success(success) => {
+                    let project_id = extract!(success.project_id)?;
+                    Ok(project_id)
+                }
+            }
+        }
+
+        /// Deletes the project. Its peers and clusters are kept and become accessible to all users, unless they are assigned to other projects.
+        pub async fn delete_project(&mut self, project_id: ProjectId) -> Result<ProjectId, ClientError<DeleteProjectError>> {
+
+            let request = tonic::Request::new(project_manager::DeleteProjectRequest {
+                project_id: Some(project_id.into()),
+            });
+
+            let response = self.inner.delete_project(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                project_manager::delete_project_response::Reply::Failure(failure) => {
+                    let error = DeleteProjectError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                project_manager::delete_project_response::Reply::Success(success) => {
+                    let project_id = extract!(success.project_id)?;
+                    Ok(project_id)
+                }
+            }
+        }
+
+        /// Lists the projects, which the user is a member of, or all projects for administrators.
+        pub async fn list_projects(&mut self) -> Result<Vec<Project>, ClientError<ListProjectsError>> {
+
+            let request = tonic::Request::new(project_manager::ListProjectsRequest {});
+
+            let response = self.inner.list_projects(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                project_manager::list_projects_response::Reply::Failure(failure) => {
+                    let error = ListProjectsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                project_manager::list_projects_response::Reply::Success(success) => {
+                    Ok(success.projects.into_iter()
+                        .map(Project::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::time::SystemTime;
+
+#[cfg(feature = "client")]
+pub use client::*;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+/// File, which an executor uploaded to the results storage configured as its results URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutorResultFile {
+    /// Name of the file within the results URL, e.g. '2024-11-08_10-00-00_test.zip'.
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<SystemTime>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListExecutorResultsError {
+    #[error("Results of executor <{executor_id}> could not be listed, because a peer with the id <{peer_id}> does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Results of executor <{executor_id}> could not be listed, because peer <{peer_id}> has no executor with that id!")]
+    ExecutorNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Results of executor <{executor_id}> of peer <{peer_id}> could not be listed, because the executor has no results URL!")]
+    ResultsUrlNotConfigured {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Results of executor <{executor_id}> of peer <{peer_id}> could not be listed, due to an error of the results storage:\n  {cause}")]
+    Storage {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DownloadExecutorResultError {
+    #[error("Result of executor <{executor_id}> could not be downloaded, because a peer with the id <{peer_id}> does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Result of executor <{executor_id}> could not be downloaded, because peer <{peer_id}> has no executor with that id!")]
+    ExecutorNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Result of executor <{executor_id}> of peer <{peer_id}> could not be downloaded, because the executor has no results URL!")]
+    ResultsUrlNotConfigured {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+    },
+    #[error("Result '{name}' of executor <{executor_id}> of peer <{peer_id}> could not be downloaded, because it does not exist!")]
+    ResultNotFound {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+        name: String,
+    },
+    #[error("Result of executor <{executor_id}> of peer <{peer_id}> could not be downloaded, due to an error of the results storage:\n  {cause}")]
+    Storage {
+        peer_id: PeerId,
+        executor_id: ExecutorId,
+        cause: String,
+    },
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::PeerId;
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::results::{DownloadExecutorResultError, ExecutorResultFile, ListExecutorResultsError};
+    use crate::proto::services::results_manager;
+    use crate::proto::services::results_manager::download_executor_result_response;
+    use crate::proto::services::results_manager::results_manager_client::ResultsManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ResultsManager<T> {
+        inner: ResultsManagerClient<T>,
+    }
+
+    impl<T> ResultsManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ResultsManagerClient<T>) -> ResultsManager<T> {
+            ResultsManager {
+                inner
+            }
+        }
+
+        /// Lists the files, which the executor uploaded to its results URL, as seen by CARL.
+        pub async fn list_executor_results(&mut self, peer_id: PeerId, executor_id: ExecutorId) -> Result<Vec<ExecutorResultFile>, ClientError<ListExecutorResultsError>> {
+
+            let request = tonic::Request::new(results_manager::ListExecutorResultsRequest {
+                peer_id: Some(peer_id.into()),
+                executor_id: Some(executor_id.into()),
+            });
+
+            let response = self.inner.list_executor_results(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                results_manager::list_executor_results_response::Reply::Failure(failure) => {
+                    let error = ListExecutorResultsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                results_manager::list_executor_results_response::Reply::Success(success) => {
+                    Ok(success.files.into_iter()
+                        .map(ExecutorResultFile::from)
+                        .collect()
+                    )
+                }
+            }
+        }
+
+        /// Downloads a file of the executor's results through CARL, so that no credentials for the results storage are needed.
+        pub async fn download_executor_result(&mut self, peer_id: PeerId, executor_id: ExecutorId, name: &str) -> Result<(ExecutorResultFile, Vec<u8>), ClientError<DownloadExecutorResultError>> {
+
+            let request = tonic::Request::new(results_manager::DownloadExecutorResultRequest {
+                peer_id: Some(peer_id.into()),
+                executor_id: Some(executor_id.into()),
+                name: name.to_owned(),
+            });
+
+            let mut stream = self.inner.download_executor_result(request).await?
+                .into_inner();
+
+            let mut header = None;
+            let mut content = Vec::new();
+
+            while let Some(response) = stream.message().await? {
+                match extract!(response.part)? {
+                    download_executor_result_response::Part::Header(received_header) => {
+                        header = Some(ExecutorResultFile::from(received_header));
+                    }
+                    download_executor_result_response::Part::Chunk(chunk) => {
+                        content.extend(chunk);
+                    }
+                    download_executor_result_response::Part::Failure(failure) => {
+                        let error = DownloadExecutorResultError::try_from(failure)?;
+                        return Err(ClientError::UsageError(error));
+                    }
+                }
+            }
+
+            let header = header
+                .ok_or_else(|| ClientError::InvalidResponse(String::from("Response contains no header!")))?;
+
+            Ok((header, content))
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::BTreeSet;
+use std::time::SystemTime;
+
+#[cfg(feature = "client")]
+pub use client::*;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName, ServiceAccountScope, ServiceAccountToken, ServiceAccountTokenId};
+
+/// Service account as listed to users. In contrast to the stored account, it does not contain the hashes of the token secrets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServiceAccountInfo {
+    pub id: ServiceAccountId,
+    pub name: ServiceAccountName,
+    pub scopes: BTreeSet<ServiceAccountScope>,
+    pub created_by: String,
+    pub created_at: SystemTime,
+    pub tokens: Vec<ServiceAccountTokenInfo>,
+}
+
+impl From<ServiceAccount> for ServiceAccountInfo {
+    fn from(account: ServiceAccount) -> Self {
+        Self {
+            id: account.id,
+            name: account.name,
+            scopes: account.scopes,
+            created_by: account.created_by,
+            created_at: account.created_at,
+            tokens: account.tokens.into_iter().map(ServiceAccountTokenInfo::from).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceAccountTokenInfo {
+    pub id: ServiceAccountTokenId,
+    pub created_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub read_only: bool,
+}
+
+impl From<ServiceAccountToken> for ServiceAccountTokenInfo {
+    fn from(token: ServiceAccountToken) -> Self {
+        Self {
+            id: token.id,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            read_only: token.read_only,
+        }
+    }
+}
+
+/// Newly issued token. The credentials contain the secret, which cannot be retrieved again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuedServiceAccountToken {
+    pub token: ServiceAccountTokenInfo,
+    pub credentials: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateServiceAccountError {
+    #[error("Service account '{name}' could not be created, because a service account with that name already exists!")]
+    NameAlreadyExists {
+        name: ServiceAccountName,
+    },
+    #[error("Service account '{name}' could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        name: ServiceAccountName,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListServiceAccountsError {
+    #[error("An internal error occurred computing the list of service accounts:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteServiceAccountError {
+    #[error("Service account <{service_account_id}> could not be deleted, because a service account with that id does not exist!")]
+    NotFound {
+        service_account_id: ServiceAccountId,
+    },
+    #[error("Service account <{service_account_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IssueServiceAccountTokenError {
+    #[error("No token could be issued for service account <{service_account_id}>, because a service account with that id does not exist!")]
+    NotFound {
+        service_account_id: ServiceAccountId,
+    },
+    #[error("No token could be issued for service account <{service_account_id}>, because a validity of {validity_seconds} seconds is not allowed. Expected between 1 and {max_validity_seconds} seconds.")]
+    IllegalValidity {
+        service_account_id: ServiceAccountId,
+        validity_seconds: u64,
+        max_validity_seconds: u64,
+    },
+    #[error("No token could be issued for service account <{service_account_id}>, due to internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokeServiceAccountTokenError {
+    #[error("Token <{token_id}> could not be revoked, because service account <{service_account_id}> does not exist or has no such token!")]
+    NotFound {
+        service_account_id: ServiceAccountId,
+        token_id: ServiceAccountTokenId,
+    },
+    #[error("Token <{token_id}> of service account <{service_account_id}> could not be revoked, due to internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        token_id: ServiceAccountTokenId,
+        cause: String,
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use std::time::Duration;
+
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use opendut_types::service_account::{ServiceAccountId, ServiceAccountName, ServiceAccountScope, ServiceAccountTokenId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::service_account::{CreateServiceAccountError, DeleteServiceAccountError, IssuedServiceAccountToken, IssueServiceAccountTokenError, ListServiceAccountsError, RevokeServiceAccountTokenError, ServiceAccountInfo};
+    use crate::proto::services::service_account_manager;
+    use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ServiceAccountManager<T> {
+        inner: ServiceAccountManagerClient<T>,
+    }
+
+    impl<T> ServiceAccountManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ServiceAccountManagerClient<T>) -> ServiceAccountManager<T> {
+            ServiceAccountManager {
+                inner
+            }
+        }
+
+        pub async fn create_service_account(&mut self, name: ServiceAccountName, scopes: Vec<ServiceAccountScope>, user_id: String) -> Result<ServiceAccountInfo, ClientError<CreateServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::CreateServiceAccountRequest {
+                name: name.into(),
+                scopes: scopes.into_iter().map(Into::into).collect(),
+                user_id,
+            });
+
+            let response = self.inner.create_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::create_service_account_response::Reply::Failure(failure) => {
+                    let error = CreateServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::create_service_account_response::Reply::Success(success) => {
+                    let service_account = extract!(success.service_account)?;
+                    Ok(service_account)
+                }
+            }
+        }
+
+        pub async fn list_service_accounts(&mut self) -> Result<Vec<ServiceAccountInfo>, ClientError<ListServiceAccountsError>> {
+
+            let request = tonic::Request::new(service_account_manager::ListServiceAccountsRequest {});
+
+            let response = self.inner.list_service_accounts(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::list_service_accounts_response::Reply::Failure(failure) => {
+                    let error = ListServiceAccountsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::list_service_accounts_response::Reply::Success(success) => {
+                    Ok(success.service_accounts.into_iter()
+                        .map(ServiceAccountInfo::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Deletes the service account, which also revokes all of its tokens.
+        pub async fn delete_service_account(&mut self, service_account_id: ServiceAccountId) -> Result<ServiceAccountId, ClientError<DeleteServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::DeleteServiceAccountRequest {
+                service_account_id: Some(service_account_id.into()),
+            });
+
+            let response = self.inner.delete_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::delete_service_account_response::Reply::Failure(failure) => {
+                    let error = DeleteServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::delete_service_account_response::Reply::Success(success) => {
+                    let service_account_id = extract!(success.service_account_id)?;
+                    Ok(service_account_id)
+                }
+            }
+        }
+
+        /// Issues a further token for the service account, which expires after the given duration.
+        /// A read-only token only allows listing, retrieving and observing resources, regardless of the scopes of the service account.
+        pub async fn issue_service_account_token(&mut self, service_account_id: ServiceAccountId, validity: Duration, read_only: bool) -> Result<IssuedServiceAccountToken, ClientError<IssueServiceAccountTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::IssueServiceAccountTokenRequest {
+                service_account_id: Some(service_account_id.into()),
+                validity_seconds: validity.as_secs(),
+                read_only,
+            });
+
+            let response = self.inner.issue_service_account_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::issue_service_account_token_response::Reply::Failure(failure) => {
+                    let error = IssueServiceAccountTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::issue_service_account_token_response::Reply::Success(success) => {
+                    let token = extract!(success.token)?;
+                    Ok(IssuedServiceAccountToken { token, credentials: success.credentials })
+                }
+            }
+        }
+
+        pub async fn revoke_service_account_token(&mut self, service_account_id: ServiceAccountId, token_id: ServiceAccountTokenId) -> Result<ServiceAccountTokenId, ClientError<RevokeServiceAccountTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::RevokeServiceAccountTokenRequest {
+                service_account_id: Some(service_account_id.into()),
+                token_id: Some(token_id.into()),
+            });
+
+            let response = self.inner.revoke_service_account_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::revoke_service_account_token_response::Reply::Failure(failure) => {
+                    let error = RevokeServiceAccountTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::revoke_service_account_token_response::Reply::Success(success) => {
+                    let token_id = extract!(success.token_id)?;
+                    Ok(token_id)
+                }
+            }
+        }
+    }
+}
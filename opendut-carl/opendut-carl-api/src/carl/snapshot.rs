@@ -0,0 +1,233 @@
+use std::time::SystemTime;
+
+#[cfg(feature = "client")]
+pub use client::*;
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId, SnapshotName, SnapshotResourceReference};
+
+/// Overview of a snapshot, without the contained resources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    pub id: SnapshotId,
+    pub name: SnapshotName,
+    pub created_at: SystemTime,
+    pub resource_count: usize,
+}
+
+impl From<&ResourceSnapshot> for SnapshotInfo {
+    fn from(snapshot: &ResourceSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            name: Clone::clone(&snapshot.name),
+            created_at: snapshot.created_at,
+            resource_count: snapshot.content.references().len(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateSnapshotError {
+    #[error("Snapshot '{snapshot_name}' could not be created, because a snapshot with that name already exists!")]
+    SnapshotNameAlreadyExists {
+        snapshot_name: SnapshotName,
+    },
+    #[error("Snapshot '{snapshot_name}' could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        snapshot_name: SnapshotName,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListSnapshotsError {
+    #[error("An internal error occurred computing the list of snapshots:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DiffSnapshotsError {
+    #[error("Snapshots could not be compared, because a snapshot with id <{snapshot_id}> does not exist!")]
+    SnapshotNotFound {
+        snapshot_id: SnapshotId,
+    },
+    #[error("Snapshots could not be compared, due to internal errors:\n  {cause}")]
+    Internal {
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RestoreSnapshotError {
+    #[error("Snapshot <{snapshot_id}> could not be restored, because a snapshot with that id does not exist!")]
+    SnapshotNotFound {
+        snapshot_id: SnapshotId,
+    },
+    #[error("Snapshot <{snapshot_id}> could not be restored, because it does not contain the resources: {}", resources.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    ResourcesNotInSnapshot {
+        snapshot_id: SnapshotId,
+        resources: Vec<SnapshotResourceReference>,
+    },
+    #[error("Snapshot <{snapshot_id}> could not be restored, due to internal errors:\n  {cause}")]
+    Internal {
+        snapshot_id: SnapshotId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteSnapshotError {
+    #[error("Snapshot <{snapshot_id}> could not be deleted, because a snapshot with that id does not exist!")]
+    SnapshotNotFound {
+        snapshot_id: SnapshotId,
+    },
+    #[error("Snapshot <{snapshot_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        snapshot_id: SnapshotId,
+        cause: String,
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use tonic::codegen::{Body, Bytes, StdError};
+
+    use opendut_types::snapshot::{SnapshotDiffEntry, SnapshotId, SnapshotName, SnapshotResourceReference};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::snapshot::{CreateSnapshotError, DeleteSnapshotError, DiffSnapshotsError, ListSnapshotsError, RestoreSnapshotError, SnapshotInfo};
+    use crate::proto::services::snapshot_manager;
+    use crate::proto::services::snapshot_manager::snapshot_manager_client::SnapshotManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct SnapshotManager<T> {
+        inner: SnapshotManagerClient<T>,
+    }
+
+    impl<T> SnapshotManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: SnapshotManagerClient<T>) -> SnapshotManager<T> {
+            SnapshotManager {
+                inner
+            }
+        }
+
+        pub async fn create_snapshot(&mut self, name: SnapshotName) -> Result<SnapshotInfo, ClientError<CreateSnapshotError>> {
+
+            let request = tonic::Request::new(snapshot_manager::CreateSnapshotRequest {
+                name: name.into(),
+            });
+
+            let response = self.inner.create_snapshot(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                snapshot_manager::create_snapshot_response::Reply::Failure(failure) => {
+                    let error = CreateSnapshotError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                snapshot_manager::create_snapshot_response::Reply::Success(success) => {
+                    let snapshot = extract!(success.snapshot)?;
+                    Ok(snapshot)
+                }
+            }
+        }
+
+        pub async fn list_snapshots(&mut self) -> Result<Vec<SnapshotInfo>, ClientError<ListSnapshotsError>> {
+
+            let request = tonic::Request::new(snapshot_manager::ListSnapshotsRequest {});
+
+            let response = self.inner.list_snapshots(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                snapshot_manager::list_snapshots_response::Reply::Failure(failure) => {
+                    let error = ListSnapshotsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                snapshot_manager::list_snapshots_response::Reply::Success(success) => {
+                    Ok(success.snapshots.into_iter()
+                        .map(SnapshotInfo::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Compares the snapshot `from` with the snapshot `to` or, if not specified, with the current resources.
+        pub async fn diff_snapshots(&mut self, from: SnapshotId, to: Option<SnapshotId>) -> Result<Vec<SnapshotDiffEntry>, ClientError<DiffSnapshotsError>> {
+
+            let request = tonic::Request::new(snapshot_manager::DiffSnapshotsRequest {
+                from: Some(from.into()),
+                to: to.map(Into::into),
+            });
+
+            let response = self.inner.diff_snapshots(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                snapshot_manager::diff_snapshots_response::Reply::Failure(failure) => {
+                    let error = DiffSnapshotsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                snapshot_manager::diff_snapshots_response::Reply::Success(success) => {
+                    Ok(success.entries.into_iter()
+                        .map(SnapshotDiffEntry::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Restores the given resources from the snapshot or, if no resources are given, all resources of the snapshot.
+        /// Returns the restored resources.
+        pub async fn restore_snapshot(&mut self, snapshot_id: SnapshotId, resources: Vec<SnapshotResourceReference>) -> Result<Vec<SnapshotResourceReference>, ClientError<RestoreSnapshotError>> {
+
+            let request = tonic::Request::new(snapshot_manager::RestoreSnapshotRequest {
+                snapshot_id: Some(snapshot_id.into()),
+                resources: resources.into_iter().map(Into::into).collect(),
+            });
+
+            let response = self.inner.restore_snapshot(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                snapshot_manager::restore_snapshot_response::Reply::Failure(failure) => {
+                    let error = RestoreSnapshotError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                snapshot_manager::restore_snapshot_response::Reply::Success(success) => {
+                    Ok(success.restored_resources.into_iter()
+                        .map(SnapshotResourceReference::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn delete_snapshot(&mut self, snapshot_id: SnapshotId) -> Result<SnapshotId, ClientError<DeleteSnapshotError>> {
+
+            let request = tonic::Request::new(snapshot_manager::DeleteSnapshotRequest {
+                snapshot_id: Some(snapshot_id.into()),
+            });
+
+            let response = self.inner.delete_snapshot(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                snapshot_manager::delete_snapshot_response::Reply::Failure(failure) => {
+                    let error = DeleteSnapshotError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                snapshot_manager::delete_snapshot_response::Reply::Success(success) => {
+                    let snapshot_id = extract!(success.snapshot_id)?;
+                    Ok(snapshot_id)
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,176 @@
+pub mod bootstrap {
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use url::Url;
+
+    use crate::carl::bootstrap::{BootstrapError, IdentityProviderConfiguration};
+
+    tonic::include_proto!("opendut.carl.services.bootstrap");
+
+    impl From<crate::carl::bootstrap::BootstrapStatus> for BootstrapStatus {
+        fn from(status: crate::carl::bootstrap::BootstrapStatus) -> Self {
+            BootstrapStatus {
+                completed: status.completed,
+                admin: Some(status.admin.into()),
+                identity_provider: Some(status.identity_provider.into()),
+                ca: Some(status.ca.into()),
+                vpn: Some(status.vpn.into()),
+                restart_required: status.restart_required,
+            }
+        }
+    }
+
+    impl TryFrom<BootstrapStatus> for crate::carl::bootstrap::BootstrapStatus {
+        type Error = ConversionError;
+        fn try_from(status: BootstrapStatus) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<BootstrapStatus, crate::carl::bootstrap::BootstrapStatus>;
+
+            Ok(crate::carl::bootstrap::BootstrapStatus {
+                completed: status.completed,
+                admin: status.admin.ok_or_else(|| ErrorBuilder::field_not_set("admin"))?.try_into()?,
+                identity_provider: status.identity_provider.ok_or_else(|| ErrorBuilder::field_not_set("identity_provider"))?.try_into()?,
+                ca: status.ca.ok_or_else(|| ErrorBuilder::field_not_set("ca"))?.try_into()?,
+                vpn: status.vpn.ok_or_else(|| ErrorBuilder::field_not_set("vpn"))?.try_into()?,
+                restart_required: status.restart_required,
+            })
+        }
+    }
+
+    impl From<crate::carl::bootstrap::BootstrapStepState> for BootstrapStepState {
+        fn from(state: crate::carl::bootstrap::BootstrapStepState) -> Self {
+            let state = match state {
+                crate::carl::bootstrap::BootstrapStepState::Pending => bootstrap_step_state::State::Pending(BootstrapStepPending {}),
+                crate::carl::bootstrap::BootstrapStepState::Done { detail } => bootstrap_step_state::State::Done(BootstrapStepDone { detail }),
+                crate::carl::bootstrap::BootstrapStepState::Skipped { reason } => bootstrap_step_state::State::Skipped(BootstrapStepSkipped { reason }),
+                crate::carl::bootstrap::BootstrapStepState::Failed { cause } => bootstrap_step_state::State::Failed(BootstrapStepFailed { cause }),
+            };
+            BootstrapStepState {
+                state: Some(state)
+            }
+        }
+    }
+
+    impl TryFrom<BootstrapStepState> for crate::carl::bootstrap::BootstrapStepState {
+        type Error = ConversionError;
+        fn try_from(state: BootstrapStepState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<BootstrapStepState, crate::carl::bootstrap::BootstrapStepState>;
+
+            let state = match state.state.ok_or_else(|| ErrorBuilder::field_not_set("state"))? {
+                bootstrap_step_state::State::Pending(_) => crate::carl::bootstrap::BootstrapStepState::Pending,
+                bootstrap_step_state::State::Done(BootstrapStepDone { detail }) => crate::carl::bootstrap::BootstrapStepState::Done { detail },
+                bootstrap_step_state::State::Skipped(BootstrapStepSkipped { reason }) => crate::carl::bootstrap::BootstrapStepState::Skipped { reason },
+                bootstrap_step_state::State::Failed(BootstrapStepFailed { cause }) => crate::carl::bootstrap::BootstrapStepState::Failed { cause },
+            };
+            Ok(state)
+        }
+    }
+
+    impl From<IdentityProviderConfiguration> for configure_identity_provider_request::IdentityProvider {
+        fn from(identity_provider: IdentityProviderConfiguration) -> Self {
+            match identity_provider {
+                IdentityProviderConfiguration::Disabled => {
+                    configure_identity_provider_request::IdentityProvider::Disabled(IdentityProviderDisabled {})
+                }
+                IdentityProviderConfiguration::Oidc { issuer_url, issuer_remote_url, issuer_admin_url, client_id, client_secret, lea_client_id } => {
+                    configure_identity_provider_request::IdentityProvider::Oidc(IdentityProviderOidc {
+                        issuer_url: issuer_url.to_string(),
+                        issuer_remote_url: issuer_remote_url.map(|url| url.to_string()).unwrap_or_default(),
+                        issuer_admin_url: issuer_admin_url.to_string(),
+                        client_id,
+                        client_secret,
+                        lea_client_id,
+                    })
+                }
+            }
+        }
+    }
+
+    impl TryFrom<configure_identity_provider_request::IdentityProvider> for IdentityProviderConfiguration {
+        type Error = ConversionError;
+        fn try_from(identity_provider: configure_identity_provider_request::IdentityProvider) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<configure_identity_provider_request::IdentityProvider, IdentityProviderConfiguration>;
+
+            fn parse_url(field: &str, value: &str) -> Result<Url, ConversionError> {
+                Url::parse(value)
+                    .map_err(|cause| ErrorBuilder::message(format!("Field '{field}' is not a valid URL: {cause}")))
+            }
+
+            let identity_provider = match identity_provider {
+                configure_identity_provider_request::IdentityProvider::Disabled(_) => IdentityProviderConfiguration::Disabled,
+                configure_identity_provider_request::IdentityProvider::Oidc(oidc) => {
+                    let issuer_remote_url = if oidc.issuer_remote_url.is_empty() {
+                        None
+                    } else {
+                        Some(parse_url("issuer_remote_url", &oidc.issuer_remote_url)?)
+                    };
+                    IdentityProviderConfiguration::Oidc {
+                        issuer_url: parse_url("issuer_url", &oidc.issuer_url)?,
+                        issuer_remote_url,
+                        issuer_admin_url: parse_url("issuer_admin_url", &oidc.issuer_admin_url)?,
+                        client_id: oidc.client_id,
+                        client_secret: oidc.client_secret,
+                        lea_client_id: oidc.lea_client_id,
+                    }
+                }
+            };
+            Ok(identity_provider)
+        }
+    }
+
+    impl From<BootstrapError> for BootstrapFailure {
+        fn from(error: BootstrapError) -> Self {
+            let proto_error = match error {
+                BootstrapError::Disabled => {
+                    bootstrap_failure::Error::Disabled(BootstrapFailureDisabled {})
+                }
+                BootstrapError::InvalidToken => {
+                    bootstrap_failure::Error::InvalidToken(BootstrapFailureInvalidToken {})
+                }
+                BootstrapError::AlreadyCompleted => {
+                    bootstrap_failure::Error::AlreadyCompleted(BootstrapFailureAlreadyCompleted {})
+                }
+                BootstrapError::IllegalInput { message } => {
+                    bootstrap_failure::Error::IllegalInput(BootstrapFailureIllegalInput { message })
+                }
+                BootstrapError::StepsIncomplete { steps } => {
+                    bootstrap_failure::Error::StepsIncomplete(BootstrapFailureStepsIncomplete { steps })
+                }
+                BootstrapError::Internal { cause } => {
+                    bootstrap_failure::Error::Internal(BootstrapFailureInternal { cause })
+                }
+            };
+            BootstrapFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<BootstrapFailure> for BootstrapError {
+        type Error = ConversionError;
+        fn try_from(failure: BootstrapFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<BootstrapFailure, BootstrapError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                bootstrap_failure::Error::Disabled(_) => BootstrapError::Disabled,
+                bootstrap_failure::Error::InvalidToken(_) => BootstrapError::InvalidToken,
+                bootstrap_failure::Error::AlreadyCompleted(_) => BootstrapError::AlreadyCompleted,
+                bootstrap_failure::Error::IllegalInput(error) => BootstrapError::IllegalInput { message: error.message },
+                bootstrap_failure::Error::StepsIncomplete(error) => BootstrapError::StepsIncomplete { steps: error.steps },
+                bootstrap_failure::Error::Internal(error) => BootstrapError::Internal { cause: error.cause },
+            };
+            Ok(error)
+        }
+    }
+}
+
 pub mod cluster_manager {
     use opendut_types::cluster::{ClusterId, ClusterName};
     use opendut_types::cluster::state::ClusterState;
+    use opendut_types::peer::{PeerId, PeerName};
     use opendut_types::proto;
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
 
-    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError};
+    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, GetClusterDeploymentStatusError, SimulateClusterDeploymentError, StoreClusterDeploymentError};
 
     tonic::include_proto!("opendut.carl.services.cluster_manager");
 
@@ -19,6 +185,20 @@ pub mod cluster_manager {
                         other_name: Some(other_name.into()),
                     })
                 }
+                CreateClusterConfigurationError::PolicyViolation { cluster_id, cluster_name, violations } => {
+                    create_cluster_configuration_failure::Error::PolicyViolation(CreateClusterConfigurationFailurePolicyViolation {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        violations: violations.into_iter().map(super::policy::PolicyViolation::from).collect(),
+                    })
+                }
+                CreateClusterConfigurationError::InvalidDependencies { cluster_id, cluster_name, cause } => {
+                    create_cluster_configuration_failure::Error::InvalidDependencies(CreateClusterConfigurationFailureInvalidDependencies {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        cause
+                    })
+                }
                 CreateClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
                     create_cluster_configuration_failure::Error::Internal(CreateClusterConfigurationFailureInternal {
                         cluster_id: Some(cluster_id.into()),
@@ -43,6 +223,12 @@ pub mod cluster_manager {
                 create_cluster_configuration_failure::Error::ClusterConfigurationAlreadyExists(error) => {
                     error.try_into()?
                 }
+                create_cluster_configuration_failure::Error::PolicyViolation(error) => {
+                    error.try_into()?
+                }
+                create_cluster_configuration_failure::Error::InvalidDependencies(error) => {
+                    error.try_into()?
+                }
                 create_cluster_configuration_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -71,6 +257,37 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<CreateClusterConfigurationFailurePolicyViolation> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailurePolicyViolation) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailurePolicyViolation, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let violations = failure.violations.into_iter()
+                .map(crate::carl::policy::PolicyViolation::from)
+                .collect();
+            Ok(CreateClusterConfigurationError::PolicyViolation { cluster_id, cluster_name, violations })
+        }
+    }
+
+    impl TryFrom<CreateClusterConfigurationFailureInvalidDependencies> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailureInvalidDependencies) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureInvalidDependencies, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(CreateClusterConfigurationError::InvalidDependencies { cluster_id, cluster_name, cause: failure.cause })
+        }
+    }
+
     impl TryFrom<CreateClusterConfigurationFailureInternal> for CreateClusterConfigurationError {
         type Error = ConversionError;
         fn try_from(failure: CreateClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
@@ -101,6 +318,13 @@ pub mod cluster_manager {
                         required_states: required_states.into_iter().map(Into::into).collect(),
                     })
                 }
+                DeleteClusterConfigurationError::ReferencedAsDependency { cluster_id, cluster_name, dependents } => {
+                    delete_cluster_configuration_failure::Error::ReferencedAsDependency(DeleteClusterConfigurationFailureReferencedAsDependency {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        dependents: dependents.into_iter().map(Into::into).collect(),
+                    })
+                }
                 DeleteClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
                     delete_cluster_configuration_failure::Error::Internal(DeleteClusterConfigurationFailureInternal {
                         cluster_id: Some(cluster_id.into()),
@@ -128,6 +352,9 @@ pub mod cluster_manager {
                 delete_cluster_configuration_failure::Error::IllegalClusterState(error) => {
                     error.try_into()?
                 }
+                delete_cluster_configuration_failure::Error::ReferencedAsDependency(error) => {
+                    error.try_into()?
+                }
                 delete_cluster_configuration_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -167,6 +394,23 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<DeleteClusterConfigurationFailureReferencedAsDependency> for DeleteClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteClusterConfigurationFailureReferencedAsDependency) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureReferencedAsDependency, DeleteClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let dependents = failure.dependents.into_iter()
+                .map(proto::cluster::ClusterId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeleteClusterConfigurationError::ReferencedAsDependency { cluster_id, cluster_name, dependents })
+        }
+    }
+
     impl TryFrom<DeleteClusterConfigurationFailureInternal> for DeleteClusterConfigurationError {
         type Error = ConversionError;
         fn try_from(failure: DeleteClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
@@ -381,70 +625,110 @@ pub mod cluster_manager {
         }
     }
 
-}
-
-pub mod metadata_provider {
-    tonic::include_proto!("opendut.carl.services.metadata_provider");
-}
+    impl From<crate::carl::cluster::ClusterDeploymentSimulation> for ClusterDeploymentSimulation {
+        fn from(simulation: crate::carl::cluster::ClusterDeploymentSimulation) -> Self {
+            ClusterDeploymentSimulation {
+                cluster_id: Some(simulation.cluster_id.into()),
+                violations: simulation.violations.into_iter().map(super::policy::PolicyViolation::from).collect(),
+                problems: simulation.problems,
+                members: simulation.members.into_iter().map(SimulatedClusterMember::from).collect(),
+                bound_devices: simulation.bound_devices.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
 
-#[allow(clippy::large_enum_variant)]
-pub mod peer_manager {
-    use opendut_types::peer::{PeerId, PeerName};
-    use opendut_types::peer::state::PeerState;
-    use opendut_types::proto;
-    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
-    use opendut_types::topology::DeviceId;
+    impl TryFrom<ClusterDeploymentSimulation> for crate::carl::cluster::ClusterDeploymentSimulation {
+        type Error = ConversionError;
+        fn try_from(simulation: ClusterDeploymentSimulation) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ClusterDeploymentSimulation, crate::carl::cluster::ClusterDeploymentSimulation>;
+            let cluster_id: ClusterId = simulation.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let violations = simulation.violations.into_iter()
+                .map(crate::carl::policy::PolicyViolation::from)
+                .collect();
+            let members = simulation.members.into_iter()
+                .map(crate::carl::cluster::SimulatedClusterMember::try_from)
+                .collect::<Result<_, _>>()?;
+            let bound_devices = simulation.bound_devices.into_iter()
+                .map(proto::topology::DeviceId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(crate::carl::cluster::ClusterDeploymentSimulation { cluster_id, violations, problems: simulation.problems, members, bound_devices })
+        }
+    }
 
-    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError};
+    impl From<crate::carl::cluster::SimulatedClusterMember> for SimulatedClusterMember {
+        fn from(member: crate::carl::cluster::SimulatedClusterMember) -> Self {
+            SimulatedClusterMember {
+                peer_id: Some(member.peer_id.into()),
+                peer_name: Some(member.peer_name.into()),
+                leader: member.leader,
+                vpn_address: member.vpn_address.map(Into::into),
+                device_interfaces: member.device_interfaces.into_iter().map(Into::into).collect(),
+                devices: member.devices.into_iter().map(Into::into).collect(),
+                executors: member.executors.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
 
-    tonic::include_proto!("opendut.carl.services.peer_manager");
+    impl TryFrom<SimulatedClusterMember> for crate::carl::cluster::SimulatedClusterMember {
+        type Error = ConversionError;
+        fn try_from(member: SimulatedClusterMember) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SimulatedClusterMember, crate::carl::cluster::SimulatedClusterMember>;
+            let peer_id: PeerId = member.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = member.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let vpn_address = member.vpn_address
+                .map(TryInto::try_into)
+                .transpose()?;
+            let device_interfaces = member.device_interfaces.into_iter()
+                .map(proto::util::NetworkInterfaceName::try_into)
+                .collect::<Result<_, _>>()?;
+            let devices = member.devices.into_iter()
+                .map(proto::topology::DeviceId::try_into)
+                .collect::<Result<_, _>>()?;
+            let executors = member.executors.into_iter()
+                .map(proto::peer::executor::ExecutorId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(crate::carl::cluster::SimulatedClusterMember { peer_id, peer_name, leader: member.leader, vpn_address, device_interfaces, devices, executors })
+        }
+    }
 
-    impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
-        fn from(error: StorePeerDescriptorError) -> Self {
+    impl From<GetClusterDeploymentStatusError> for GetClusterDeploymentStatusFailure {
+        fn from(error: GetClusterDeploymentStatusError) -> Self {
             let proto_error = match error {
-                StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
-                    store_peer_descriptor_failure::Error::IllegalPeerState(StorePeerDescriptorFailureIllegalPeerState {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        actual_state: Some(actual_state.into()),
-                        required_states: required_states.into_iter().map(Into::into).collect(),
-                    })
-                }
-                StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error } => {
-                    store_peer_descriptor_failure::Error::IllegalDevices(StorePeerDescriptorFailureIllegalDevices {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        error: Some(error.into()),
+                GetClusterDeploymentStatusError::ClusterDeploymentNotFound { cluster_id } => {
+                    get_cluster_deployment_status_failure::Error::ClusterDeploymentNotFound(GetClusterDeploymentStatusFailureClusterDeploymentNotFound {
+                        cluster_id: Some(cluster_id.into()),
                     })
                 }
-                StorePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
-                    store_peer_descriptor_failure::Error::Internal(StorePeerDescriptorFailureInternal {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        cause
+                GetClusterDeploymentStatusError::Internal { cluster_id, cause } => {
+                    get_cluster_deployment_status_failure::Error::Internal(GetClusterDeploymentStatusFailureInternal {
+                        cluster_id: Some(cluster_id.into()),
+                        cause,
                     })
                 }
             };
-            StorePeerDescriptorFailure {
+            GetClusterDeploymentStatusFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<StorePeerDescriptorFailure> for StorePeerDescriptorError {
+    impl TryFrom<GetClusterDeploymentStatusFailure> for GetClusterDeploymentStatusError {
         type Error = ConversionError;
-        fn try_from(failure: StorePeerDescriptorFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailure, StorePeerDescriptorError>;
+        fn try_from(failure: GetClusterDeploymentStatusFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterDeploymentStatusFailure, GetClusterDeploymentStatusError>;
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                store_peer_descriptor_failure::Error::IllegalPeerState(error) => {
+                get_cluster_deployment_status_failure::Error::ClusterDeploymentNotFound(error) => {
                     error.try_into()?
                 }
-                store_peer_descriptor_failure::Error::IllegalDevices(error) => {
-                    error.try_into()?
-                }
-                store_peer_descriptor_failure::Error::Internal(error) => {
+                get_cluster_deployment_status_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
             };
@@ -452,353 +736,4153 @@ pub mod peer_manager {
         }
     }
 
-    impl TryFrom<StorePeerDescriptorFailureIllegalPeerState> for StorePeerDescriptorError {
-        type Error = ConversionError;
-        fn try_from(failure: StorePeerDescriptorFailureIllegalPeerState) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureIllegalPeerState, StorePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            let peer_name: PeerName = failure.peer_name
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
-                .try_into()?;
-            let actual_state: PeerState = failure.actual_state
-                .ok_or_else(|| ErrorBuilder::field_not_set("actual_state"))?
-                .try_into()?;
-            let required_states = failure.required_states.into_iter()
-                .map(proto::peer::PeerState::try_into)
-                .collect::<Result<_, _>>()?;
-            Ok(StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states })
-        }
-    }
-
-    impl TryFrom<StorePeerDescriptorFailureIllegalDevices> for StorePeerDescriptorError {
+    impl TryFrom<GetClusterDeploymentStatusFailureClusterDeploymentNotFound> for GetClusterDeploymentStatusError {
         type Error = ConversionError;
-        fn try_from(failure: StorePeerDescriptorFailureIllegalDevices) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureIllegalDevices, StorePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            let peer_name: PeerName = failure.peer_name
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
-                .try_into()?;
-            let error: crate::carl::peer::IllegalDevicesError = failure.error
-                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?
+        fn try_from(failure: GetClusterDeploymentStatusFailureClusterDeploymentNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterDeploymentStatusFailureClusterDeploymentNotFound, GetClusterDeploymentStatusError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
                 .try_into()?;
-            Ok(StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error })
+            Ok(GetClusterDeploymentStatusError::ClusterDeploymentNotFound { cluster_id })
         }
     }
 
-    impl TryFrom<StorePeerDescriptorFailureInternal> for StorePeerDescriptorError {
+    impl TryFrom<GetClusterDeploymentStatusFailureInternal> for GetClusterDeploymentStatusError {
         type Error = ConversionError;
-        fn try_from(failure: StorePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureInternal, StorePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            let peer_name: PeerName = failure.peer_name
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+        fn try_from(failure: GetClusterDeploymentStatusFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterDeploymentStatusFailureInternal, GetClusterDeploymentStatusError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
                 .try_into()?;
-            Ok(StorePeerDescriptorError::Internal { peer_id, peer_name, cause: failure.cause })
+            Ok(GetClusterDeploymentStatusError::Internal { cluster_id, cause: failure.cause })
         }
     }
 
-    impl From<DeletePeerDescriptorError> for DeletePeerDescriptorFailure {
-        fn from(error: DeletePeerDescriptorError) -> Self {
+    impl From<SimulateClusterDeploymentError> for SimulateClusterDeploymentFailure {
+        fn from(error: SimulateClusterDeploymentError) -> Self {
             let proto_error = match error {
-                DeletePeerDescriptorError::PeerNotFound { peer_id } => {
-                    delete_peer_descriptor_failure::Error::PeerNotFound(DeletePeerDescriptorFailurePeerNotFound {
-                        peer_id: Some(peer_id.into())
-                    })
-                }
-                DeletePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
-                    delete_peer_descriptor_failure::Error::IllegalPeerState(DeletePeerDescriptorFailureIllegalPeerState {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        actual_state: Some(actual_state.into()),
-                        required_states: required_states.into_iter().map(Into::into).collect(),
-                    })
-                }
-                DeletePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
-                    delete_peer_descriptor_failure::Error::Internal(DeletePeerDescriptorFailureInternal {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: peer_name.map(Into::into),
-                        cause
+                SimulateClusterDeploymentError::Internal { cluster_id, cause } => {
+                    simulate_cluster_deployment_failure::Error::Internal(SimulateClusterDeploymentFailureInternal {
+                        cluster_id: Some(cluster_id.into()),
+                        cause,
                     })
                 }
             };
-            DeletePeerDescriptorFailure {
+            SimulateClusterDeploymentFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<DeletePeerDescriptorFailure> for DeletePeerDescriptorError {
+    impl TryFrom<SimulateClusterDeploymentFailure> for SimulateClusterDeploymentError {
         type Error = ConversionError;
-        fn try_from(failure: DeletePeerDescriptorFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailure, DeletePeerDescriptorError>;
+        fn try_from(failure: SimulateClusterDeploymentFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SimulateClusterDeploymentFailure, SimulateClusterDeploymentError>;
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                delete_peer_descriptor_failure::Error::PeerNotFound(error) => {
+                simulate_cluster_deployment_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
-                delete_peer_descriptor_failure::Error::IllegalPeerState(error) => {
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<SimulateClusterDeploymentFailureInternal> for SimulateClusterDeploymentError {
+        type Error = ConversionError;
+        fn try_from(failure: SimulateClusterDeploymentFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SimulateClusterDeploymentFailureInternal, SimulateClusterDeploymentError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(SimulateClusterDeploymentError::Internal { cluster_id, cause: failure.cause })
+        }
+    }
+
+}
+
+pub mod diagnostics {
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::peer::PeerId;
+    use opendut_types::proto;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use std::time::{Duration, SystemTime};
+
+    use crate::carl::diagnostics::{CheckCompatibilityError, CheckConsistencyError, CompatibilityProblem, ConsistencyFindingKind, DeleteTraceCaptureError, DownloadTraceCaptureError, StartTraceCaptureError, TerminatePeerStreamError, TraceCaptureBundle};
+
+    tonic::include_proto!("opendut.carl.services.diagnostics");
+
+    impl From<crate::carl::diagnostics::ConsistencyFinding> for ConsistencyFinding {
+        fn from(finding: crate::carl::diagnostics::ConsistencyFinding) -> Self {
+            let kind = match finding.kind {
+                ConsistencyFindingKind::OrphanedPeerState { peer_id } => {
+                    consistency_finding::Kind::OrphanedPeerState(OrphanedPeerState {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id } => {
+                    consistency_finding::Kind::OrphanedPeerConfiguration(OrphanedPeerConfiguration {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                ConsistencyFindingKind::OrphanedOldPeerConfiguration { peer_id } => {
+                    consistency_finding::Kind::OrphanedOldPeerConfiguration(OrphanedOldPeerConfiguration {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                ConsistencyFindingKind::PeerUpWithoutConnection { peer_id } => {
+                    consistency_finding::Kind::PeerUpWithoutConnection(PeerUpWithoutConnection {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                ConsistencyFindingKind::ConnectionWithoutPeerUp { peer_id } => {
+                    consistency_finding::Kind::ConnectionWithoutPeerUp(ConnectionWithoutPeerUp {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                ConsistencyFindingKind::AssignedClusterNotDeployed { peer_id, cluster_id } => {
+                    consistency_finding::Kind::AssignedClusterNotDeployed(AssignedClusterNotDeployed {
+                        peer_id: Some(peer_id.into()),
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
+            };
+            ConsistencyFinding {
+                kind: Some(kind),
+                repaired: finding.repaired,
+            }
+        }
+    }
+
+    impl TryFrom<ConsistencyFinding> for crate::carl::diagnostics::ConsistencyFinding {
+        type Error = ConversionError;
+        fn try_from(finding: ConsistencyFinding) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ConsistencyFinding, crate::carl::diagnostics::ConsistencyFinding>;
+
+            fn extract_peer_id(peer_id: Option<proto::peer::PeerId>) -> Result<PeerId, ConversionError> {
+                peer_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                    .try_into()
+            }
+
+            let kind = finding.kind
+                .ok_or_else(|| ErrorBuilder::field_not_set("kind"))?;
+            let kind = match kind {
+                consistency_finding::Kind::OrphanedPeerState(finding) => {
+                    ConsistencyFindingKind::OrphanedPeerState { peer_id: extract_peer_id(finding.peer_id)? }
+                }
+                consistency_finding::Kind::OrphanedPeerConfiguration(finding) => {
+                    ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id: extract_peer_id(finding.peer_id)? }
+                }
+                consistency_finding::Kind::OrphanedOldPeerConfiguration(finding) => {
+                    ConsistencyFindingKind::OrphanedOldPeerConfiguration { peer_id: extract_peer_id(finding.peer_id)? }
+                }
+                consistency_finding::Kind::PeerUpWithoutConnection(finding) => {
+                    ConsistencyFindingKind::PeerUpWithoutConnection { peer_id: extract_peer_id(finding.peer_id)? }
+                }
+                consistency_finding::Kind::ConnectionWithoutPeerUp(finding) => {
+                    ConsistencyFindingKind::ConnectionWithoutPeerUp { peer_id: extract_peer_id(finding.peer_id)? }
+                }
+                consistency_finding::Kind::AssignedClusterNotDeployed(finding) => {
+                    let cluster_id: ClusterId = finding.cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                        .try_into()?;
+                    ConsistencyFindingKind::AssignedClusterNotDeployed { peer_id: extract_peer_id(finding.peer_id)?, cluster_id }
+                }
+            };
+            Ok(crate::carl::diagnostics::ConsistencyFinding { kind, repaired: finding.repaired })
+        }
+    }
+
+    impl From<CheckConsistencyError> for CheckConsistencyFailure {
+        fn from(error: CheckConsistencyError) -> Self {
+            let proto_error = match error {
+                CheckConsistencyError::Internal { cause } => {
+                    check_consistency_failure::Error::Internal(CheckConsistencyFailureInternal {
+                        cause
+                    })
+                }
+            };
+            CheckConsistencyFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CheckConsistencyFailure> for CheckConsistencyError {
+        type Error = ConversionError;
+        fn try_from(failure: CheckConsistencyFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CheckConsistencyFailure, CheckConsistencyError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                check_consistency_failure::Error::Internal(error) => {
+                    CheckConsistencyError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::diagnostics::CompatibilityFinding> for CompatibilityFinding {
+        fn from(finding: crate::carl::diagnostics::CompatibilityFinding) -> Self {
+            let problem = match finding.problem {
+                CompatibilityProblem::Undecodable { cause } => {
+                    compatibility_finding::Problem::Undecodable(CompatibilityProblemUndecodable { cause })
+                }
+                CompatibilityProblem::InvariantViolated { cause } => {
+                    compatibility_finding::Problem::InvariantViolated(CompatibilityProblemInvariantViolated { cause })
+                }
+            };
+            CompatibilityFinding {
+                resource_kind: finding.resource_kind,
+                resource_id: finding.resource_id,
+                problem: Some(problem),
+                transform: finding.transform,
+                transformed: finding.transformed,
+            }
+        }
+    }
+
+    impl TryFrom<CompatibilityFinding> for crate::carl::diagnostics::CompatibilityFinding {
+        type Error = ConversionError;
+        fn try_from(finding: CompatibilityFinding) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CompatibilityFinding, crate::carl::diagnostics::CompatibilityFinding>;
+
+            let problem = match finding.problem.ok_or_else(|| ErrorBuilder::field_not_set("problem"))? {
+                compatibility_finding::Problem::Undecodable(problem) => {
+                    CompatibilityProblem::Undecodable { cause: problem.cause }
+                }
+                compatibility_finding::Problem::InvariantViolated(problem) => {
+                    CompatibilityProblem::InvariantViolated { cause: problem.cause }
+                }
+            };
+            Ok(crate::carl::diagnostics::CompatibilityFinding {
+                resource_kind: finding.resource_kind,
+                resource_id: finding.resource_id,
+                problem,
+                transform: finding.transform,
+                transformed: finding.transformed,
+            })
+        }
+    }
+
+    impl From<CheckCompatibilityError> for CheckCompatibilityFailure {
+        fn from(error: CheckCompatibilityError) -> Self {
+            let proto_error = match error {
+                CheckCompatibilityError::Internal { cause } => {
+                    check_compatibility_failure::Error::Internal(CheckCompatibilityFailureInternal {
+                        cause
+                    })
+                }
+            };
+            CheckCompatibilityFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CheckCompatibilityFailure> for CheckCompatibilityError {
+        type Error = ConversionError;
+        fn try_from(failure: CheckCompatibilityFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CheckCompatibilityFailure, CheckCompatibilityError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                check_compatibility_failure::Error::Internal(error) => {
+                    CheckCompatibilityError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    fn to_unix_millis(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn from_unix_millis(millis: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    impl From<crate::carl::diagnostics::TraceCaptureId> for TraceCaptureId {
+        fn from(value: crate::carl::diagnostics::TraceCaptureId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<TraceCaptureId> for crate::carl::diagnostics::TraceCaptureId {
+        type Error = ConversionError;
+
+        fn try_from(value: TraceCaptureId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TraceCaptureId, crate::carl::diagnostics::TraceCaptureId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
+        }
+    }
+
+    impl From<crate::carl::diagnostics::TraceCaptureScope> for TraceCaptureScope {
+        fn from(value: crate::carl::diagnostics::TraceCaptureScope) -> Self {
+            let scope = match value {
+                crate::carl::diagnostics::TraceCaptureScope::Peer { peer_id } => {
+                    trace_capture_scope::Scope::Peer(TraceCaptureScopePeer { peer_id: Some(peer_id.into()) })
+                }
+                crate::carl::diagnostics::TraceCaptureScope::Cluster { cluster_id } => {
+                    trace_capture_scope::Scope::Cluster(TraceCaptureScopeCluster { cluster_id: Some(cluster_id.into()) })
+                }
+                crate::carl::diagnostics::TraceCaptureScope::GrpcMethod { method } => {
+                    trace_capture_scope::Scope::GrpcMethod(TraceCaptureScopeGrpcMethod { method })
+                }
+            };
+            Self {
+                scope: Some(scope)
+            }
+        }
+    }
+
+    impl TryFrom<TraceCaptureScope> for crate::carl::diagnostics::TraceCaptureScope {
+        type Error = ConversionError;
+
+        fn try_from(value: TraceCaptureScope) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TraceCaptureScope, crate::carl::diagnostics::TraceCaptureScope>;
+
+            let scope = value.scope
+                .ok_or_else(|| ErrorBuilder::field_not_set("scope"))?;
+            let scope = match scope {
+                trace_capture_scope::Scope::Peer(scope) => {
+                    let peer_id: PeerId = scope.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    crate::carl::diagnostics::TraceCaptureScope::Peer { peer_id }
+                }
+                trace_capture_scope::Scope::Cluster(scope) => {
+                    let cluster_id: ClusterId = scope.cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                        .try_into()?;
+                    crate::carl::diagnostics::TraceCaptureScope::Cluster { cluster_id }
+                }
+                trace_capture_scope::Scope::GrpcMethod(scope) => {
+                    if scope.method.is_empty() {
+                        return Err(ErrorBuilder::message("Name of the gRPC method must not be empty."));
+                    }
+                    crate::carl::diagnostics::TraceCaptureScope::GrpcMethod { method: scope.method }
+                }
+            };
+            Ok(scope)
+        }
+    }
+
+    impl From<crate::carl::diagnostics::TraceCaptureLevel> for TraceCaptureLevel {
+        fn from(value: crate::carl::diagnostics::TraceCaptureLevel) -> Self {
+            let level = match value {
+                crate::carl::diagnostics::TraceCaptureLevel::Debug => trace_capture_level::Level::Debug(TraceCaptureLevelDebug {}),
+                crate::carl::diagnostics::TraceCaptureLevel::Trace => trace_capture_level::Level::Trace(TraceCaptureLevelTrace {}),
+            };
+            Self {
+                level: Some(level)
+            }
+        }
+    }
+
+    impl TryFrom<TraceCaptureLevel> for crate::carl::diagnostics::TraceCaptureLevel {
+        type Error = ConversionError;
+
+        fn try_from(value: TraceCaptureLevel) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TraceCaptureLevel, crate::carl::diagnostics::TraceCaptureLevel>;
+
+            let level = value.level
+                .ok_or_else(|| ErrorBuilder::field_not_set("level"))?;
+            let level = match level {
+                trace_capture_level::Level::Debug(_) => crate::carl::diagnostics::TraceCaptureLevel::Debug,
+                trace_capture_level::Level::Trace(_) => crate::carl::diagnostics::TraceCaptureLevel::Trace,
+            };
+            Ok(level)
+        }
+    }
+
+    impl From<crate::carl::diagnostics::TraceCaptureInfo> for TraceCaptureInfo {
+        fn from(value: crate::carl::diagnostics::TraceCaptureInfo) -> Self {
+            Self {
+                id: Some(value.id.into()),
+                scope: Some(value.scope.into()),
+                level: Some(value.level.into()),
+                started_at_unix_millis: to_unix_millis(value.started_at),
+                expires_at_unix_millis: to_unix_millis(value.expires_at),
+                record_count: value.record_count as u64,
+                dropped_record_count: value.dropped_record_count as u64,
+            }
+        }
+    }
+
+    impl TryFrom<TraceCaptureInfo> for crate::carl::diagnostics::TraceCaptureInfo {
+        type Error = ConversionError;
+
+        fn try_from(value: TraceCaptureInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TraceCaptureInfo, crate::carl::diagnostics::TraceCaptureInfo>;
+
+            let id = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+            let scope = value.scope
+                .ok_or_else(|| ErrorBuilder::field_not_set("scope"))?
+                .try_into()?;
+            let level = value.level
+                .ok_or_else(|| ErrorBuilder::field_not_set("level"))?
+                .try_into()?;
+
+            Ok(Self {
+                id,
+                scope,
+                level,
+                started_at: from_unix_millis(value.started_at_unix_millis),
+                expires_at: from_unix_millis(value.expires_at_unix_millis),
+                record_count: value.record_count as usize,
+                dropped_record_count: value.dropped_record_count as usize,
+            })
+        }
+    }
+
+    impl From<crate::carl::diagnostics::CapturedRecord> for CapturedRecord {
+        fn from(value: crate::carl::diagnostics::CapturedRecord) -> Self {
+            Self {
+                timestamp_unix_millis: to_unix_millis(value.timestamp),
+                level: value.level,
+                target: value.target,
+                spans: value.spans,
+                fields: value.fields,
+            }
+        }
+    }
+
+    impl From<CapturedRecord> for crate::carl::diagnostics::CapturedRecord {
+        fn from(value: CapturedRecord) -> Self {
+            Self {
+                timestamp: from_unix_millis(value.timestamp_unix_millis),
+                level: value.level,
+                target: value.target,
+                spans: value.spans,
+                fields: value.fields,
+            }
+        }
+    }
+
+    impl From<TraceCaptureBundle> for DownloadTraceCaptureSuccess {
+        fn from(value: TraceCaptureBundle) -> Self {
+            Self {
+                capture: Some(value.capture.into()),
+                records: value.records.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<DownloadTraceCaptureSuccess> for TraceCaptureBundle {
+        type Error = ConversionError;
+
+        fn try_from(value: DownloadTraceCaptureSuccess) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DownloadTraceCaptureSuccess, TraceCaptureBundle>;
+
+            let capture = value.capture
+                .ok_or_else(|| ErrorBuilder::field_not_set("capture"))?
+                .try_into()?;
+
+            Ok(Self {
+                capture,
+                records: value.records.into_iter().map(Into::into).collect(),
+            })
+        }
+    }
+
+    impl From<StartTraceCaptureError> for StartTraceCaptureFailure {
+        fn from(error: StartTraceCaptureError) -> Self {
+            let proto_error = match error {
+                StartTraceCaptureError::IllegalDuration { duration, max_duration } => {
+                    start_trace_capture_failure::Error::IllegalDuration(StartTraceCaptureFailureIllegalDuration {
+                        duration_millis: duration.as_millis() as u64,
+                        max_duration_millis: max_duration.as_millis() as u64,
+                    })
+                }
+            };
+            StartTraceCaptureFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StartTraceCaptureFailure> for StartTraceCaptureError {
+        type Error = ConversionError;
+
+        fn try_from(failure: StartTraceCaptureFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartTraceCaptureFailure, StartTraceCaptureError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                start_trace_capture_failure::Error::IllegalDuration(error) => {
+                    StartTraceCaptureError::IllegalDuration {
+                        duration: Duration::from_millis(error.duration_millis),
+                        max_duration: Duration::from_millis(error.max_duration_millis),
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DownloadTraceCaptureError> for DownloadTraceCaptureFailure {
+        fn from(error: DownloadTraceCaptureError) -> Self {
+            let proto_error = match error {
+                DownloadTraceCaptureError::TraceCaptureNotFound { capture_id } => {
+                    download_trace_capture_failure::Error::TraceCaptureNotFound(TraceCaptureNotFound {
+                        capture_id: Some(capture_id.into()),
+                    })
+                }
+            };
+            DownloadTraceCaptureFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DownloadTraceCaptureFailure> for DownloadTraceCaptureError {
+        type Error = ConversionError;
+
+        fn try_from(failure: DownloadTraceCaptureFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DownloadTraceCaptureFailure, DownloadTraceCaptureError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                download_trace_capture_failure::Error::TraceCaptureNotFound(error) => {
+                    let capture_id = error.capture_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("capture_id"))?
+                        .try_into()?;
+                    DownloadTraceCaptureError::TraceCaptureNotFound { capture_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteTraceCaptureError> for DeleteTraceCaptureFailure {
+        fn from(error: DeleteTraceCaptureError) -> Self {
+            let proto_error = match error {
+                DeleteTraceCaptureError::TraceCaptureNotFound { capture_id } => {
+                    delete_trace_capture_failure::Error::TraceCaptureNotFound(TraceCaptureNotFound {
+                        capture_id: Some(capture_id.into()),
+                    })
+                }
+            };
+            DeleteTraceCaptureFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteTraceCaptureFailure> for DeleteTraceCaptureError {
+        type Error = ConversionError;
+
+        fn try_from(failure: DeleteTraceCaptureFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteTraceCaptureFailure, DeleteTraceCaptureError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_trace_capture_failure::Error::TraceCaptureNotFound(error) => {
+                    let capture_id = error.capture_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("capture_id"))?
+                        .try_into()?;
+                    DeleteTraceCaptureError::TraceCaptureNotFound { capture_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::diagnostics::PeerStreamInfo> for PeerStreamInfo {
+        fn from(value: crate::carl::diagnostics::PeerStreamInfo) -> Self {
+            Self {
+                peer_id: Some(value.peer_id.into()),
+                remote_host: Some(value.remote_host.into()),
+                remote_address: value.remote_address.map(|address| address.to_string()),
+                version: value.version,
+                opened_at_unix_millis: to_unix_millis(value.opened_at),
+                last_seen_unix_millis: value.last_seen.map(to_unix_millis),
+                messages_received: value.messages_received,
+                messages_sent: value.messages_sent,
+            }
+        }
+    }
+
+    impl TryFrom<PeerStreamInfo> for crate::carl::diagnostics::PeerStreamInfo {
+        type Error = ConversionError;
+
+        fn try_from(value: PeerStreamInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PeerStreamInfo, crate::carl::diagnostics::PeerStreamInfo>;
+
+            let peer_id = value.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let remote_host = value.remote_host
+                .ok_or_else(|| ErrorBuilder::field_not_set("remote_host"))?
+                .try_into()?;
+            let remote_address = value.remote_address
+                .map(|address| SocketAddr::from_str(&address)
+                    .map_err(|cause| ErrorBuilder::message(format!("Remote address '{address}' is not a valid socket address: {cause}")))
+                )
+                .transpose()?;
+
+            Ok(Self {
+                peer_id,
+                remote_host,
+                remote_address,
+                version: value.version,
+                opened_at: from_unix_millis(value.opened_at_unix_millis),
+                last_seen: value.last_seen_unix_millis.map(from_unix_millis),
+                messages_received: value.messages_received,
+                messages_sent: value.messages_sent,
+            })
+        }
+    }
+
+    impl From<TerminatePeerStreamError> for TerminatePeerStreamFailure {
+        fn from(error: TerminatePeerStreamError) -> Self {
+            let proto_error = match error {
+                TerminatePeerStreamError::PeerNotConnected { peer_id } => {
+                    terminate_peer_stream_failure::Error::PeerNotConnected(TerminatePeerStreamFailurePeerNotConnected {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+            };
+            TerminatePeerStreamFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<TerminatePeerStreamFailure> for TerminatePeerStreamError {
+        type Error = ConversionError;
+
+        fn try_from(failure: TerminatePeerStreamFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TerminatePeerStreamFailure, TerminatePeerStreamError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                terminate_peer_stream_failure::Error::PeerNotConnected(error) => {
+                    let peer_id = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    TerminatePeerStreamError::PeerNotConnected { peer_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod metadata_provider {
+    use crate::carl::error_code;
+
+    tonic::include_proto!("opendut.carl.services.metadata_provider");
+
+    impl From<error_code::ErrorRemediation> for ErrorRemediation {
+        fn from(value: error_code::ErrorRemediation) -> Self {
+            Self {
+                code: value.code,
+                summary: value.summary,
+                remediation: value.remediation,
+            }
+        }
+    }
+
+    impl From<ErrorRemediation> for error_code::ErrorRemediation {
+        fn from(value: ErrorRemediation) -> Self {
+            Self {
+                code: value.code,
+                summary: value.summary,
+                remediation: value.remediation,
+            }
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub mod peer_manager {
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::group::{PeerGroupId, PeerGroupName};
+    use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNodeId, HierarchyNodeName};
+    use opendut_types::peer::provisioning::ProvisioningTokenId;
+    use opendut_types::peer::state::PeerState;
+    use opendut_types::proto;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::topology::DeviceId;
+
+    use std::time::{Duration, SystemTime};
+
+    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError, StorePeerGroupError, PreviewPeerGroupChangesError, DeletePeerGroupError, ListPeerGroupsError, EditPeerLabelsError, PingPeerError, StoreHierarchyNodeError, DeleteHierarchyNodeError, ListHierarchyNodesError, GetHierarchyHealthError, CreatePeerInClusterError, PeerCommand, SendPeerCommandError, StartRollingRestartError, GetMaintenanceOperationError, GenerateProvisioningTokensError, ListProvisioningTokensError, ClaimProvisioningTokenError, RevokePeerSetupError};
+
+    tonic::include_proto!("opendut.carl.services.peer_manager");
+
+    impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
+        fn from(error: StorePeerDescriptorError) -> Self {
+            let proto_error = match error {
+                StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
+                    store_peer_descriptor_failure::Error::IllegalPeerState(StorePeerDescriptorFailureIllegalPeerState {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        actual_state: Some(actual_state.into()),
+                        required_states: required_states.into_iter().map(Into::into).collect(),
+                    })
+                }
+                StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error } => {
+                    store_peer_descriptor_failure::Error::IllegalDevices(StorePeerDescriptorFailureIllegalDevices {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        error: Some(error.into()),
+                    })
+                }
+                StorePeerDescriptorError::PolicyViolation { peer_id, peer_name, violations } => {
+                    store_peer_descriptor_failure::Error::PolicyViolation(StorePeerDescriptorFailurePolicyViolation {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        violations: violations.into_iter().map(super::policy::PolicyViolation::from).collect(),
+                    })
+                }
+                StorePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
+                    store_peer_descriptor_failure::Error::Internal(StorePeerDescriptorFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cause
+                    })
+                }
+            };
+            StorePeerDescriptorFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailure> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailure, StorePeerDescriptorError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_peer_descriptor_failure::Error::IllegalPeerState(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::IllegalDevices(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::PolicyViolation(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureIllegalPeerState> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureIllegalPeerState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureIllegalPeerState, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let actual_state: PeerState = failure.actual_state
+                .ok_or_else(|| ErrorBuilder::field_not_set("actual_state"))?
+                .try_into()?;
+            let required_states = failure.required_states.into_iter()
+                .map(proto::peer::PeerState::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureIllegalDevices> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureIllegalDevices) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureIllegalDevices, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let error: crate::carl::peer::IllegalDevicesError = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailurePolicyViolation> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailurePolicyViolation) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailurePolicyViolation, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let violations = failure.violations.into_iter()
+                .map(crate::carl::policy::PolicyViolation::from)
+                .collect();
+            Ok(StorePeerDescriptorError::PolicyViolation { peer_id, peer_name, violations })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureInternal> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureInternal, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::Internal { peer_id, peer_name, cause: failure.cause })
+        }
+    }
+
+    impl From<DeletePeerDescriptorError> for DeletePeerDescriptorFailure {
+        fn from(error: DeletePeerDescriptorError) -> Self {
+            let proto_error = match error {
+                DeletePeerDescriptorError::PeerNotFound { peer_id } => {
+                    delete_peer_descriptor_failure::Error::PeerNotFound(DeletePeerDescriptorFailurePeerNotFound {
+                        peer_id: Some(peer_id.into())
+                    })
+                }
+                DeletePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
+                    delete_peer_descriptor_failure::Error::IllegalPeerState(DeletePeerDescriptorFailureIllegalPeerState {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        actual_state: Some(actual_state.into()),
+                        required_states: required_states.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeletePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
+                    delete_peer_descriptor_failure::Error::Internal(DeletePeerDescriptorFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: peer_name.map(Into::into),
+                        cause
+                    })
+                }
+            };
+            DeletePeerDescriptorFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeletePeerDescriptorFailure> for DeletePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerDescriptorFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailure, DeletePeerDescriptorError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_peer_descriptor_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+                delete_peer_descriptor_failure::Error::IllegalPeerState(error) => {
+                    error.try_into()?
+                }
+                delete_peer_descriptor_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeletePeerDescriptorFailurePeerNotFound> for DeletePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerDescriptorFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailurePeerNotFound, DeletePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(DeletePeerDescriptorError::PeerNotFound { peer_id })
+        }
+    }
+
+    impl TryFrom<DeletePeerDescriptorFailureIllegalPeerState> for DeletePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerDescriptorFailureIllegalPeerState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailureIllegalPeerState, DeletePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let actual_state: PeerState = failure.actual_state
+                .ok_or_else(|| ErrorBuilder::field_not_set("actual_state"))?
+                .try_into()?;
+            let required_states = failure.required_states.into_iter()
+                .map(proto::peer::PeerState::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeletePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states })
+        }
+    }
+
+    impl TryFrom<DeletePeerDescriptorFailureInternal> for DeletePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailureInternal, DeletePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: Option<PeerName> = failure.peer_name
+                .map(TryInto::try_into)
+                .transpose()?;
+            Ok(DeletePeerDescriptorError::Internal { peer_id, peer_name, cause: failure.cause })
+        }
+    }
+
+    impl From<crate::carl::peer::IllegalDevicesError> for IllegalDevicesError {
+        fn from(error: crate::carl::peer::IllegalDevicesError) -> Self {
+            match error {
+                crate::carl::peer::IllegalDevicesError::DeviceAlreadyExists { device_id } => {
+                    IllegalDevicesError {
+                        error: Some(illegal_devices_error::Error::DeviceAlreadyExists(IllegalDevicesErrorDeviceAlreadyExists {
+                            device_id: Some(device_id.into())
+                        })),
+                    }
+                }
+            }
+        }
+    }
+
+    impl TryFrom<IllegalDevicesError> for crate::carl::peer::IllegalDevicesError {
+        type Error = ConversionError;
+        fn try_from(error: IllegalDevicesError) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<IllegalDevicesError, crate::carl::peer::IllegalDevicesError>;
+            let inner = error.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            match inner {
+                illegal_devices_error::Error::DeviceAlreadyExists(error) => {
+                    error.try_into()
+                }
+            }
+        }
+    }
+
+    impl TryFrom<IllegalDevicesErrorDeviceAlreadyExists> for crate::carl::peer::IllegalDevicesError {
+        type Error = ConversionError;
+        fn try_from(error: IllegalDevicesErrorDeviceAlreadyExists) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<IllegalDevicesErrorDeviceAlreadyExists, crate::carl::peer::IllegalDevicesError>;
+            let device_id: DeviceId = error.device_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+                .try_into()?;
+            Ok(crate::carl::peer::IllegalDevicesError::DeviceAlreadyExists { device_id })
+        }
+    }
+
+    impl From<GetPeerDescriptorError> for GetPeerDescriptorFailure {
+        fn from(error: GetPeerDescriptorError) -> Self {
+            let proto_error = match error {
+                GetPeerDescriptorError::PeerNotFound { peer_id } => {
+                    get_peer_descriptor_failure::Error::PeerNotFound(GetPeerDescriptorFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                GetPeerDescriptorError::Internal { peer_id, cause } => {
+                    get_peer_descriptor_failure::Error::Internal(GetPeerDescriptorFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetPeerDescriptorFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetPeerDescriptorFailure> for GetPeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerDescriptorFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailure, GetPeerDescriptorError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_peer_descriptor_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+                get_peer_descriptor_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<GetPeerDescriptorFailurePeerNotFound> for GetPeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerDescriptorFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailurePeerNotFound, GetPeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerDescriptorError::PeerNotFound { peer_id })
+        }
+    }
+
+    impl TryFrom<GetPeerDescriptorFailureInternal> for GetPeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailureInternal, GetPeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerDescriptorError::Internal{ peer_id, cause: failure.cause})
+        }
+    }
+
+    impl From<ListPeerDescriptorsError> for ListPeerDescriptorsFailure {
+        fn from(error: ListPeerDescriptorsError) -> Self {
+            let proto_error = match error {
+                ListPeerDescriptorsError::Internal { cause } => {
+                    list_peer_descriptors_failure::Error::Internal(ListPeerDescriptorsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListPeerDescriptorsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListPeerDescriptorsFailure> for ListPeerDescriptorsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPeerDescriptorsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListPeerDescriptorsFailure, ListPeerDescriptorsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_peer_descriptors_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<ListPeerDescriptorsFailureInternal> for ListPeerDescriptorsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPeerDescriptorsFailureInternal) -> Result<Self, Self::Error> {
+            // type ErrorBuilder = ConversionErrorBuilder<ListPeersFailureInternal, ListPeersError>;
+            Ok(ListPeerDescriptorsError::Internal{ cause: failure.cause})
+        }
+    }
+
+    impl From<GetPeerStateError> for GetPeerStateFailure {
+        fn from(error: GetPeerStateError) -> Self {
+            let proto_error = match error {
+                GetPeerStateError::PeerNotFound { peer_id } => {
+                    get_peer_state_failure::Error::PeerNotFound(GetPeerStateFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                GetPeerStateError::Internal { peer_id, cause } => {
+                    get_peer_state_failure::Error::Internal(GetPeerStateFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetPeerStateFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetPeerStateFailurePeerNotFound> for GetPeerStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerStateFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailurePeerNotFound, GetPeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerStateError::PeerNotFound { peer_id })
+        }
+    }
+
+    impl TryFrom<GetPeerStateFailureInternal> for GetPeerStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerStateFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailureInternal, GetPeerStateError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerStateError::Internal{ peer_id, cause: failure.cause})
+        }
+    }
+
+    impl TryFrom<GetPeerStateFailure> for GetPeerStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerStateFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailure, GetPeerStateError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_peer_state_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+                get_peer_state_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    fn to_unix_millis(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn from_unix_millis(millis: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    impl From<crate::carl::peer::PeerPing> for PingPeerSuccess {
+        fn from(value: crate::carl::peer::PeerPing) -> Self {
+            PingPeerSuccess {
+                peer_id: Some(value.peer_id.into()),
+                round_trip_time_ms: value.round_trip_time.as_millis() as u64,
+                last_seen_unix_millis: to_unix_millis(value.last_seen),
+            }
+        }
+    }
+
+    impl TryFrom<PingPeerSuccess> for crate::carl::peer::PeerPing {
+        type Error = ConversionError;
+        fn try_from(value: PingPeerSuccess) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PingPeerSuccess, crate::carl::peer::PeerPing>;
+            let peer_id: PeerId = value.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(crate::carl::peer::PeerPing {
+                peer_id,
+                round_trip_time: Duration::from_millis(value.round_trip_time_ms),
+                last_seen: from_unix_millis(value.last_seen_unix_millis),
+            })
+        }
+    }
+
+    impl From<PingPeerError> for PingPeerFailure {
+        fn from(error: PingPeerError) -> Self {
+            let proto_error = match error {
+                PingPeerError::PeerNotFound { peer_id } => {
+                    ping_peer_failure::Error::PeerNotFound(PingPeerFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                PingPeerError::PeerNotConnected { peer_id, last_seen } => {
+                    ping_peer_failure::Error::PeerNotConnected(PingPeerFailurePeerNotConnected {
+                        peer_id: Some(peer_id.into()),
+                        last_seen_unix_millis: last_seen.map(to_unix_millis),
+                    })
+                }
+                PingPeerError::PeerNotResponding { peer_id, timeout, last_seen } => {
+                    ping_peer_failure::Error::PeerNotResponding(PingPeerFailurePeerNotResponding {
+                        peer_id: Some(peer_id.into()),
+                        timeout_ms: timeout.as_millis() as u64,
+                        last_seen_unix_millis: last_seen.map(to_unix_millis),
+                    })
+                }
+                PingPeerError::Internal { peer_id, cause } => {
+                    ping_peer_failure::Error::Internal(PingPeerFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            PingPeerFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<PingPeerFailure> for PingPeerError {
+        type Error = ConversionError;
+        fn try_from(failure: PingPeerFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PingPeerFailure, PingPeerError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                ping_peer_failure::Error::PeerNotFound(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    PingPeerError::PeerNotFound { peer_id }
+                }
+                ping_peer_failure::Error::PeerNotConnected(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    PingPeerError::PeerNotConnected {
+                        peer_id,
+                        last_seen: error.last_seen_unix_millis.map(from_unix_millis),
+                    }
+                }
+                ping_peer_failure::Error::PeerNotResponding(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    PingPeerError::PeerNotResponding {
+                        peer_id,
+                        timeout: Duration::from_millis(error.timeout_ms),
+                        last_seen: error.last_seen_unix_millis.map(from_unix_millis),
+                    }
+                }
+                ping_peer_failure::Error::Internal(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    PingPeerError::Internal { peer_id, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::peer::PeerGroupClusterChange> for PeerGroupClusterChange {
+        fn from(change: crate::carl::peer::PeerGroupClusterChange) -> Self {
+            PeerGroupClusterChange {
+                cluster_id: Some(change.cluster_id.into()),
+                cluster_name: Some(change.cluster_name.into()),
+                deployed: change.deployed,
+                added_devices: change.added_devices.into_iter().map(Into::into).collect(),
+                removed_devices: change.removed_devices.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<PeerGroupClusterChange> for crate::carl::peer::PeerGroupClusterChange {
+        type Error = ConversionError;
+        fn try_from(change: PeerGroupClusterChange) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PeerGroupClusterChange, crate::carl::peer::PeerGroupClusterChange>;
+            let cluster_id: ClusterId = change.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = change.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let added_devices = change.added_devices.into_iter()
+                .map(proto::topology::DeviceId::try_into)
+                .collect::<Result<_, _>>()?;
+            let removed_devices = change.removed_devices.into_iter()
+                .map(proto::topology::DeviceId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(crate::carl::peer::PeerGroupClusterChange { cluster_id, cluster_name, deployed: change.deployed, added_devices, removed_devices })
+        }
+    }
+
+    impl From<StorePeerGroupError> for StorePeerGroupFailure {
+        fn from(error: StorePeerGroupError) -> Self {
+            let proto_error = match error {
+                StorePeerGroupError::PeerNotFound { peer_group_id, peer_group_name, peer_id } => {
+                    store_peer_group_failure::Error::PeerNotFound(StorePeerGroupFailurePeerNotFound {
+                        peer_group_id: Some(peer_group_id.into()),
+                        peer_group_name: Some(peer_group_name.into()),
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                StorePeerGroupError::Internal { peer_group_id, peer_group_name, cause } => {
+                    store_peer_group_failure::Error::Internal(StorePeerGroupFailureInternal {
+                        peer_group_id: Some(peer_group_id.into()),
+                        peer_group_name: Some(peer_group_name.into()),
+                        cause
+                    })
+                }
+            };
+            StorePeerGroupFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StorePeerGroupFailure> for StorePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerGroupFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerGroupFailure, StorePeerGroupError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_peer_group_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+                store_peer_group_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<StorePeerGroupFailurePeerNotFound> for StorePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerGroupFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerGroupFailurePeerNotFound, StorePeerGroupError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            let peer_group_name: PeerGroupName = failure.peer_group_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_name"))?
+                .try_into()?;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(StorePeerGroupError::PeerNotFound { peer_group_id, peer_group_name, peer_id })
+        }
+    }
+
+    impl TryFrom<StorePeerGroupFailureInternal> for StorePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerGroupFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerGroupFailureInternal, StorePeerGroupError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            let peer_group_name: PeerGroupName = failure.peer_group_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_name"))?
+                .try_into()?;
+            Ok(StorePeerGroupError::Internal { peer_group_id, peer_group_name, cause: failure.cause })
+        }
+    }
+
+    impl From<PreviewPeerGroupChangesError> for PreviewPeerGroupChangesFailure {
+        fn from(error: PreviewPeerGroupChangesError) -> Self {
+            let proto_error = match error {
+                PreviewPeerGroupChangesError::Internal { peer_group_id, cause } => {
+                    preview_peer_group_changes_failure::Error::Internal(PreviewPeerGroupChangesFailureInternal {
+                        peer_group_id: Some(peer_group_id.into()),
+                        cause
+                    })
+                }
+            };
+            PreviewPeerGroupChangesFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<PreviewPeerGroupChangesFailure> for PreviewPeerGroupChangesError {
+        type Error = ConversionError;
+        fn try_from(failure: PreviewPeerGroupChangesFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PreviewPeerGroupChangesFailure, PreviewPeerGroupChangesError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                preview_peer_group_changes_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::peer::EditedPeerLabels> for EditedPeerLabels {
+        fn from(edited: crate::carl::peer::EditedPeerLabels) -> Self {
+            EditedPeerLabels {
+                peer_id: Some(edited.peer_id.into()),
+                peer_name: Some(edited.peer_name.into()),
+                labels: edited.labels.into_iter().map(proto::peer::label::Label::from).collect(),
+                annotations: edited.annotations.into_iter().map(proto::peer::label::Annotation::from).collect(),
+                changed: edited.changed,
+            }
+        }
+    }
+
+    impl TryFrom<EditedPeerLabels> for crate::carl::peer::EditedPeerLabels {
+        type Error = ConversionError;
+        fn try_from(edited: EditedPeerLabels) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<EditedPeerLabels, crate::carl::peer::EditedPeerLabels>;
+            let peer_id: PeerId = edited.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = edited.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let labels = edited.labels.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?;
+            let annotations = edited.annotations.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(crate::carl::peer::EditedPeerLabels { peer_id, peer_name, labels, annotations, changed: edited.changed })
+        }
+    }
+
+    impl From<EditPeerLabelsError> for EditPeerLabelsFailure {
+        fn from(error: EditPeerLabelsError) -> Self {
+            let proto_error = match error {
+                EditPeerLabelsError::Internal { cause } => {
+                    edit_peer_labels_failure::Error::Internal(EditPeerLabelsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            EditPeerLabelsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<EditPeerLabelsFailure> for EditPeerLabelsError {
+        type Error = ConversionError;
+        fn try_from(failure: EditPeerLabelsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<EditPeerLabelsFailure, EditPeerLabelsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                edit_peer_labels_failure::Error::Internal(error) => {
+                    EditPeerLabelsError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<PreviewPeerGroupChangesFailureInternal> for PreviewPeerGroupChangesError {
+        type Error = ConversionError;
+        fn try_from(failure: PreviewPeerGroupChangesFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PreviewPeerGroupChangesFailureInternal, PreviewPeerGroupChangesError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            Ok(PreviewPeerGroupChangesError::Internal { peer_group_id, cause: failure.cause })
+        }
+    }
+
+    impl From<DeletePeerGroupError> for DeletePeerGroupFailure {
+        fn from(error: DeletePeerGroupError) -> Self {
+            let proto_error = match error {
+                DeletePeerGroupError::PeerGroupNotFound { peer_group_id } => {
+                    delete_peer_group_failure::Error::PeerGroupNotFound(DeletePeerGroupFailurePeerGroupNotFound {
+                        peer_group_id: Some(peer_group_id.into()),
+                    })
+                }
+                DeletePeerGroupError::ReferencedByClusters { peer_group_id, cluster_ids } => {
+                    delete_peer_group_failure::Error::ReferencedByClusters(DeletePeerGroupFailureReferencedByClusters {
+                        peer_group_id: Some(peer_group_id.into()),
+                        cluster_ids: cluster_ids.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeletePeerGroupError::Internal { peer_group_id, cause } => {
+                    delete_peer_group_failure::Error::Internal(DeletePeerGroupFailureInternal {
+                        peer_group_id: Some(peer_group_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeletePeerGroupFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeletePeerGroupFailure> for DeletePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerGroupFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerGroupFailure, DeletePeerGroupError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_peer_group_failure::Error::PeerGroupNotFound(error) => {
+                    error.try_into()?
+                }
+                delete_peer_group_failure::Error::ReferencedByClusters(error) => {
+                    error.try_into()?
+                }
+                delete_peer_group_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeletePeerGroupFailurePeerGroupNotFound> for DeletePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerGroupFailurePeerGroupNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerGroupFailurePeerGroupNotFound, DeletePeerGroupError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            Ok(DeletePeerGroupError::PeerGroupNotFound { peer_group_id })
+        }
+    }
+
+    impl TryFrom<DeletePeerGroupFailureReferencedByClusters> for DeletePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerGroupFailureReferencedByClusters) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerGroupFailureReferencedByClusters, DeletePeerGroupError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            let cluster_ids = failure.cluster_ids.into_iter()
+                .map(proto::cluster::ClusterId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeletePeerGroupError::ReferencedByClusters { peer_group_id, cluster_ids })
+        }
+    }
+
+    impl TryFrom<DeletePeerGroupFailureInternal> for DeletePeerGroupError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePeerGroupFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePeerGroupFailureInternal, DeletePeerGroupError>;
+            let peer_group_id: PeerGroupId = failure.peer_group_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_group_id"))?
+                .try_into()?;
+            Ok(DeletePeerGroupError::Internal { peer_group_id, cause: failure.cause })
+        }
+    }
+
+    impl From<ListPeerGroupsError> for ListPeerGroupsFailure {
+        fn from(error: ListPeerGroupsError) -> Self {
+            let proto_error = match error {
+                ListPeerGroupsError::Internal { cause } => {
+                    list_peer_groups_failure::Error::Internal(ListPeerGroupsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListPeerGroupsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListPeerGroupsFailure> for ListPeerGroupsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPeerGroupsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListPeerGroupsFailure, ListPeerGroupsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_peer_groups_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<ListPeerGroupsFailureInternal> for ListPeerGroupsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPeerGroupsFailureInternal) -> Result<Self, Self::Error> {
+            Ok(ListPeerGroupsError::Internal { cause: failure.cause })
+        }
+    }
+
+    impl From<StoreHierarchyNodeError> for StoreHierarchyNodeFailure {
+        fn from(error: StoreHierarchyNodeError) -> Self {
+            let proto_error = match error {
+                StoreHierarchyNodeError::IllegalPlacement { node_id, node_name, reason } => {
+                    store_hierarchy_node_failure::Error::IllegalPlacement(StoreHierarchyNodeFailureIllegalPlacement {
+                        node_id: Some(node_id.into()),
+                        node_name: Some(node_name.into()),
+                        reason,
+                    })
+                }
+                StoreHierarchyNodeError::Internal { node_id, node_name, cause } => {
+                    store_hierarchy_node_failure::Error::Internal(StoreHierarchyNodeFailureInternal {
+                        node_id: Some(node_id.into()),
+                        node_name: Some(node_name.into()),
+                        cause
+                    })
+                }
+            };
+            StoreHierarchyNodeFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StoreHierarchyNodeFailure> for StoreHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreHierarchyNodeFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreHierarchyNodeFailure, StoreHierarchyNodeError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_hierarchy_node_failure::Error::IllegalPlacement(error) => {
+                    error.try_into()?
+                }
+                store_hierarchy_node_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<StoreHierarchyNodeFailureIllegalPlacement> for StoreHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreHierarchyNodeFailureIllegalPlacement) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreHierarchyNodeFailureIllegalPlacement, StoreHierarchyNodeError>;
+            let node_id: HierarchyNodeId = failure.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            let node_name: HierarchyNodeName = failure.node_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_name"))?
+                .try_into()?;
+            Ok(StoreHierarchyNodeError::IllegalPlacement { node_id, node_name, reason: failure.reason })
+        }
+    }
+
+    impl TryFrom<StoreHierarchyNodeFailureInternal> for StoreHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreHierarchyNodeFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreHierarchyNodeFailureInternal, StoreHierarchyNodeError>;
+            let node_id: HierarchyNodeId = failure.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            let node_name: HierarchyNodeName = failure.node_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_name"))?
+                .try_into()?;
+            Ok(StoreHierarchyNodeError::Internal { node_id, node_name, cause: failure.cause })
+        }
+    }
+
+    impl From<DeleteHierarchyNodeError> for DeleteHierarchyNodeFailure {
+        fn from(error: DeleteHierarchyNodeError) -> Self {
+            let proto_error = match error {
+                DeleteHierarchyNodeError::NodeNotFound { node_id } => {
+                    delete_hierarchy_node_failure::Error::NodeNotFound(DeleteHierarchyNodeFailureNodeNotFound {
+                        node_id: Some(node_id.into()),
+                    })
+                }
+                DeleteHierarchyNodeError::HasChildren { node_id, child_ids } => {
+                    delete_hierarchy_node_failure::Error::HasChildren(DeleteHierarchyNodeFailureHasChildren {
+                        node_id: Some(node_id.into()),
+                        child_ids: child_ids.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeleteHierarchyNodeError::Internal { node_id, cause } => {
+                    delete_hierarchy_node_failure::Error::Internal(DeleteHierarchyNodeFailureInternal {
+                        node_id: Some(node_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteHierarchyNodeFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteHierarchyNodeFailure> for DeleteHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteHierarchyNodeFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteHierarchyNodeFailure, DeleteHierarchyNodeError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_hierarchy_node_failure::Error::NodeNotFound(error) => {
+                    error.try_into()?
+                }
+                delete_hierarchy_node_failure::Error::HasChildren(error) => {
+                    error.try_into()?
+                }
+                delete_hierarchy_node_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeleteHierarchyNodeFailureNodeNotFound> for DeleteHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteHierarchyNodeFailureNodeNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteHierarchyNodeFailureNodeNotFound, DeleteHierarchyNodeError>;
+            let node_id: HierarchyNodeId = failure.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            Ok(DeleteHierarchyNodeError::NodeNotFound { node_id })
+        }
+    }
+
+    impl TryFrom<DeleteHierarchyNodeFailureHasChildren> for DeleteHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteHierarchyNodeFailureHasChildren) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteHierarchyNodeFailureHasChildren, DeleteHierarchyNodeError>;
+            let node_id: HierarchyNodeId = failure.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            let child_ids = failure.child_ids.into_iter()
+                .map(proto::peer::hierarchy::HierarchyNodeId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeleteHierarchyNodeError::HasChildren { node_id, child_ids })
+        }
+    }
+
+    impl TryFrom<DeleteHierarchyNodeFailureInternal> for DeleteHierarchyNodeError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteHierarchyNodeFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteHierarchyNodeFailureInternal, DeleteHierarchyNodeError>;
+            let node_id: HierarchyNodeId = failure.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            Ok(DeleteHierarchyNodeError::Internal { node_id, cause: failure.cause })
+        }
+    }
+
+    impl From<ListHierarchyNodesError> for ListHierarchyNodesFailure {
+        fn from(error: ListHierarchyNodesError) -> Self {
+            let proto_error = match error {
+                ListHierarchyNodesError::Internal { cause } => {
+                    list_hierarchy_nodes_failure::Error::Internal(ListHierarchyNodesFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListHierarchyNodesFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListHierarchyNodesFailure> for ListHierarchyNodesError {
+        type Error = ConversionError;
+        fn try_from(failure: ListHierarchyNodesFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListHierarchyNodesFailure, ListHierarchyNodesError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_hierarchy_nodes_failure::Error::Internal(error) => {
+                    ListHierarchyNodesError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<GetHierarchyHealthError> for GetHierarchyHealthFailure {
+        fn from(error: GetHierarchyHealthError) -> Self {
+            let proto_error = match error {
+                GetHierarchyHealthError::Internal { cause } => {
+                    get_hierarchy_health_failure::Error::Internal(GetHierarchyHealthFailureInternal {
+                        cause
+                    })
+                }
+            };
+            GetHierarchyHealthFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetHierarchyHealthFailure> for GetHierarchyHealthError {
+        type Error = ConversionError;
+        fn try_from(failure: GetHierarchyHealthFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetHierarchyHealthFailure, GetHierarchyHealthError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_hierarchy_health_failure::Error::Internal(error) => {
+                    GetHierarchyHealthError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::peer::HierarchyNodeHealth> for HierarchyNodeHealth {
+        fn from(health: crate::carl::peer::HierarchyNodeHealth) -> Self {
+            HierarchyNodeHealth {
+                node_id: Some(health.node_id.into()),
+                node_name: Some(health.node_name.into()),
+                level: Some(health.level.into()),
+                available_peers: health.available_peers,
+                blocked_peers: health.blocked_peers,
+                down_peers: health.down_peers,
+            }
+        }
+    }
+
+    impl TryFrom<HierarchyNodeHealth> for crate::carl::peer::HierarchyNodeHealth {
+        type Error = ConversionError;
+        fn try_from(health: HierarchyNodeHealth) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<HierarchyNodeHealth, crate::carl::peer::HierarchyNodeHealth>;
+            let node_id: HierarchyNodeId = health.node_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_id"))?
+                .try_into()?;
+            let node_name: HierarchyNodeName = health.node_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("node_name"))?
+                .try_into()?;
+            let level: HierarchyLevel = health.level
+                .ok_or_else(|| ErrorBuilder::field_not_set("level"))?
+                .try_into()?;
+            Ok(crate::carl::peer::HierarchyNodeHealth {
+                node_id,
+                node_name,
+                level,
+                available_peers: health.available_peers,
+                blocked_peers: health.blocked_peers,
+                down_peers: health.down_peers,
+            })
+        }
+    }
+
+    impl From<CreatePeerInClusterError> for CreatePeerInClusterFailure {
+        fn from(error: CreatePeerInClusterError) -> Self {
+            let proto_error = match error {
+                CreatePeerInClusterError::PeerAlreadyExists { peer_id, peer_name } => {
+                    create_peer_in_cluster_failure::Error::PeerAlreadyExists(CreatePeerInClusterFailurePeerAlreadyExists {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                    })
+                }
+                CreatePeerInClusterError::ClusterNotFound { peer_id, peer_name, cluster_id } => {
+                    create_peer_in_cluster_failure::Error::ClusterNotFound(CreatePeerInClusterFailureClusterNotFound {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
+                CreatePeerInClusterError::ClusterDeployed { peer_id, peer_name, cluster_id, cluster_name } => {
+                    create_peer_in_cluster_failure::Error::ClusterDeployed(CreatePeerInClusterFailureClusterDeployed {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                    })
+                }
+                CreatePeerInClusterError::UnknownDevices { peer_id, peer_name, device_ids } => {
+                    create_peer_in_cluster_failure::Error::UnknownDevices(CreatePeerInClusterFailureUnknownDevices {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        device_ids: device_ids.into_iter().map(Into::into).collect(),
+                    })
+                }
+                CreatePeerInClusterError::PolicyViolation { peer_id, peer_name, violations } => {
+                    create_peer_in_cluster_failure::Error::PolicyViolation(CreatePeerInClusterFailurePolicyViolation {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        violations: violations.into_iter().map(super::policy::PolicyViolation::from).collect(),
+                    })
+                }
+                CreatePeerInClusterError::Internal { peer_id, peer_name, cause } => {
+                    create_peer_in_cluster_failure::Error::Internal(CreatePeerInClusterFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cause
+                    })
+                }
+            };
+            CreatePeerInClusterFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailure> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailure, CreatePeerInClusterError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_peer_in_cluster_failure::Error::PeerAlreadyExists(error) => {
+                    error.try_into()?
+                }
+                create_peer_in_cluster_failure::Error::ClusterNotFound(error) => {
+                    error.try_into()?
+                }
+                create_peer_in_cluster_failure::Error::ClusterDeployed(error) => {
+                    error.try_into()?
+                }
+                create_peer_in_cluster_failure::Error::UnknownDevices(error) => {
+                    error.try_into()?
+                }
+                create_peer_in_cluster_failure::Error::PolicyViolation(error) => {
+                    error.try_into()?
+                }
+                create_peer_in_cluster_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailurePeerAlreadyExists> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailurePeerAlreadyExists) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailurePeerAlreadyExists, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(CreatePeerInClusterError::PeerAlreadyExists { peer_id, peer_name })
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailureClusterNotFound> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailureClusterNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailureClusterNotFound, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(CreatePeerInClusterError::ClusterNotFound { peer_id, peer_name, cluster_id })
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailureClusterDeployed> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailureClusterDeployed) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailureClusterDeployed, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            Ok(CreatePeerInClusterError::ClusterDeployed { peer_id, peer_name, cluster_id, cluster_name })
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailureUnknownDevices> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailureUnknownDevices) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailureUnknownDevices, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let device_ids = failure.device_ids.into_iter()
+                .map(proto::topology::DeviceId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(CreatePeerInClusterError::UnknownDevices { peer_id, peer_name, device_ids })
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailurePolicyViolation> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailurePolicyViolation) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailurePolicyViolation, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let violations = failure.violations.into_iter()
+                .map(crate::carl::policy::PolicyViolation::from)
+                .collect();
+            Ok(CreatePeerInClusterError::PolicyViolation { peer_id, peer_name, violations })
+        }
+    }
+
+    impl TryFrom<CreatePeerInClusterFailureInternal> for CreatePeerInClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: CreatePeerInClusterFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreatePeerInClusterFailureInternal, CreatePeerInClusterError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(CreatePeerInClusterError::Internal { peer_id, peer_name, cause: failure.cause })
+        }
+    }
+
+    impl From<crate::carl::peer::PeerCommandResult> for SendPeerCommandSuccess {
+        fn from(value: crate::carl::peer::PeerCommandResult) -> Self {
+            SendPeerCommandSuccess {
+                peer_id: Some(value.peer_id.into()),
+                command: Some(value.command.into()),
+                output: value.output,
+                attachment: value.attachment,
+                duration_ms: value.duration.as_millis() as u64,
+            }
+        }
+    }
+
+    impl TryFrom<SendPeerCommandSuccess> for crate::carl::peer::PeerCommandResult {
+        type Error = ConversionError;
+        fn try_from(value: SendPeerCommandSuccess) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SendPeerCommandSuccess, crate::carl::peer::PeerCommandResult>;
+            let peer_id: PeerId = value.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let command: PeerCommand = value.command
+                .ok_or_else(|| ErrorBuilder::field_not_set("command"))?
+                .try_into()?;
+            Ok(crate::carl::peer::PeerCommandResult {
+                peer_id,
+                command,
+                output: value.output,
+                attachment: value.attachment,
+                duration: Duration::from_millis(value.duration_ms),
+            })
+        }
+    }
+
+    impl From<SendPeerCommandError> for SendPeerCommandFailure {
+        fn from(error: SendPeerCommandError) -> Self {
+            let proto_error = match error {
+                SendPeerCommandError::PeerNotFound { peer_id } => {
+                    send_peer_command_failure::Error::PeerNotFound(SendPeerCommandFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                SendPeerCommandError::Unauthorized { peer_id, command, user, required_roles } => {
+                    send_peer_command_failure::Error::Unauthorized(SendPeerCommandFailureUnauthorized {
+                        peer_id: Some(peer_id.into()),
+                        command: Some(command.into()),
+                        user,
+                        required_roles,
+                    })
+                }
+                SendPeerCommandError::PeerNotConnected { peer_id, command } => {
+                    send_peer_command_failure::Error::PeerNotConnected(SendPeerCommandFailurePeerNotConnected {
+                        peer_id: Some(peer_id.into()),
+                        command: Some(command.into()),
+                    })
+                }
+                SendPeerCommandError::PeerNotResponding { peer_id, command, timeout } => {
+                    send_peer_command_failure::Error::PeerNotResponding(SendPeerCommandFailurePeerNotResponding {
+                        peer_id: Some(peer_id.into()),
+                        command: Some(command.into()),
+                        timeout_ms: timeout.as_millis() as u64,
+                    })
+                }
+                SendPeerCommandError::CommandFailed { peer_id, command, cause } => {
+                    send_peer_command_failure::Error::CommandFailed(SendPeerCommandFailureCommandFailed {
+                        peer_id: Some(peer_id.into()),
+                        command: Some(command.into()),
+                        cause,
+                    })
+                }
+                SendPeerCommandError::Internal { peer_id, cause } => {
+                    send_peer_command_failure::Error::Internal(SendPeerCommandFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            SendPeerCommandFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<SendPeerCommandFailure> for SendPeerCommandError {
+        type Error = ConversionError;
+        fn try_from(failure: SendPeerCommandFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SendPeerCommandFailure, SendPeerCommandError>;
+
+            fn extract_peer_id(peer_id: Option<proto::peer::PeerId>) -> Result<PeerId, ConversionError> {
+                peer_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                    .try_into()
+            }
+            fn extract_command(command: Option<super::peer_messaging_broker::PeerCommandKind>) -> Result<PeerCommand, ConversionError> {
+                command
+                    .ok_or_else(|| ErrorBuilder::field_not_set("command"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                send_peer_command_failure::Error::PeerNotFound(error) => {
+                    SendPeerCommandError::PeerNotFound { peer_id: extract_peer_id(error.peer_id)? }
+                }
+                send_peer_command_failure::Error::Unauthorized(error) => {
+                    SendPeerCommandError::Unauthorized {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        command: extract_command(error.command)?,
+                        user: error.user,
+                        required_roles: error.required_roles,
+                    }
+                }
+                send_peer_command_failure::Error::PeerNotConnected(error) => {
+                    SendPeerCommandError::PeerNotConnected {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        command: extract_command(error.command)?,
+                    }
+                }
+                send_peer_command_failure::Error::PeerNotResponding(error) => {
+                    SendPeerCommandError::PeerNotResponding {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        command: extract_command(error.command)?,
+                        timeout: Duration::from_millis(error.timeout_ms),
+                    }
+                }
+                send_peer_command_failure::Error::CommandFailed(error) => {
+                    SendPeerCommandError::CommandFailed {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        command: extract_command(error.command)?,
+                        cause: error.cause,
+                    }
+                }
+                send_peer_command_failure::Error::Internal(error) => {
+                    SendPeerCommandError::Internal {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::peer::MaintenanceOperationId> for MaintenanceOperationId {
+        fn from(value: crate::carl::peer::MaintenanceOperationId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<MaintenanceOperationId> for crate::carl::peer::MaintenanceOperationId {
+        type Error = ConversionError;
+
+        fn try_from(value: MaintenanceOperationId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<MaintenanceOperationId, crate::carl::peer::MaintenanceOperationId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
+        }
+    }
+
+    impl From<crate::carl::peer::MaintenancePeerState> for MaintenancePeerState {
+        fn from(value: crate::carl::peer::MaintenancePeerState) -> Self {
+            let inner = match value {
+                crate::carl::peer::MaintenancePeerState::Waiting => maintenance_peer_state::Inner::Waiting(MaintenancePeerStateWaiting {}),
+                crate::carl::peer::MaintenancePeerState::Restarting => maintenance_peer_state::Inner::Restarting(MaintenancePeerStateRestarting {}),
+                crate::carl::peer::MaintenancePeerState::Completed => maintenance_peer_state::Inner::Completed(MaintenancePeerStateCompleted {}),
+                crate::carl::peer::MaintenancePeerState::Failed { cause } => maintenance_peer_state::Inner::Failed(MaintenancePeerStateFailed { cause }),
+                crate::carl::peer::MaintenancePeerState::Skipped => maintenance_peer_state::Inner::Skipped(MaintenancePeerStateSkipped {}),
+            };
+            MaintenancePeerState {
+                inner: Some(inner)
+            }
+        }
+    }
+
+    impl TryFrom<MaintenancePeerState> for crate::carl::peer::MaintenancePeerState {
+        type Error = ConversionError;
+        fn try_from(value: MaintenancePeerState) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<MaintenancePeerState, crate::carl::peer::MaintenancePeerState>;
+            let state = match value.inner.ok_or_else(|| ErrorBuilder::field_not_set("inner"))? {
+                maintenance_peer_state::Inner::Waiting(_) => crate::carl::peer::MaintenancePeerState::Waiting,
+                maintenance_peer_state::Inner::Restarting(_) => crate::carl::peer::MaintenancePeerState::Restarting,
+                maintenance_peer_state::Inner::Completed(_) => crate::carl::peer::MaintenancePeerState::Completed,
+                maintenance_peer_state::Inner::Failed(failed) => crate::carl::peer::MaintenancePeerState::Failed { cause: failed.cause },
+                maintenance_peer_state::Inner::Skipped(_) => crate::carl::peer::MaintenancePeerState::Skipped,
+            };
+            Ok(state)
+        }
+    }
+
+    impl From<crate::carl::peer::MaintenanceOperation> for MaintenanceOperation {
+        fn from(value: crate::carl::peer::MaintenanceOperation) -> Self {
+            MaintenanceOperation {
+                id: Some(value.id.into()),
+                command: Some(value.command.into()),
+                started_at_unix_millis: to_unix_millis(value.started_at),
+                peers: value.peers.into_iter()
+                    .map(|peer| MaintenancePeerStatus {
+                        peer_id: Some(peer.peer_id.into()),
+                        state: Some(peer.state.into()),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl TryFrom<MaintenanceOperation> for crate::carl::peer::MaintenanceOperation {
+        type Error = ConversionError;
+        fn try_from(value: MaintenanceOperation) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<MaintenanceOperation, crate::carl::peer::MaintenanceOperation>;
+            let id = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+            let command: PeerCommand = value.command
+                .ok_or_else(|| ErrorBuilder::field_not_set("command"))?
+                .try_into()?;
+            let peers = value.peers.into_iter()
+                .map(|peer| {
+                    let peer_id: PeerId = peer.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    let state = peer.state
+                        .ok_or_else(|| ErrorBuilder::field_not_set("state"))?
+                        .try_into()?;
+                    Ok(crate::carl::peer::MaintenancePeerStatus { peer_id, state })
+                })
+                .collect::<Result<Vec<_>, ConversionError>>()?;
+            Ok(crate::carl::peer::MaintenanceOperation {
+                id,
+                command,
+                started_at: from_unix_millis(value.started_at_unix_millis),
+                peers,
+            })
+        }
+    }
+
+    impl From<StartRollingRestartError> for StartRollingRestartFailure {
+        fn from(error: StartRollingRestartError) -> Self {
+            let proto_error = match error {
+                StartRollingRestartError::NoPeers => {
+                    start_rolling_restart_failure::Error::NoPeers(StartRollingRestartFailureNoPeers {})
+                }
+                StartRollingRestartError::PeerNotFound { peer_id } => {
+                    start_rolling_restart_failure::Error::PeerNotFound(StartRollingRestartFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                StartRollingRestartError::IllegalCommand { command } => {
+                    start_rolling_restart_failure::Error::IllegalCommand(StartRollingRestartFailureIllegalCommand {
+                        command: Some(command.into()),
+                    })
+                }
+                StartRollingRestartError::IllegalMaxUnavailable { max_unavailable_per_cluster } => {
+                    start_rolling_restart_failure::Error::IllegalMaxUnavailable(StartRollingRestartFailureIllegalMaxUnavailable {
+                        max_unavailable_per_cluster,
+                    })
+                }
+                StartRollingRestartError::Unauthorized { command, user, required_roles } => {
+                    start_rolling_restart_failure::Error::Unauthorized(StartRollingRestartFailureUnauthorized {
+                        command: Some(command.into()),
+                        user,
+                        required_roles,
+                    })
+                }
+                StartRollingRestartError::Internal { cause } => {
+                    start_rolling_restart_failure::Error::Internal(StartRollingRestartFailureInternal {
+                        cause
+                    })
+                }
+            };
+            StartRollingRestartFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StartRollingRestartFailure> for StartRollingRestartError {
+        type Error = ConversionError;
+        fn try_from(failure: StartRollingRestartFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartRollingRestartFailure, StartRollingRestartError>;
+
+            fn extract_command(command: Option<super::peer_messaging_broker::PeerCommandKind>) -> Result<PeerCommand, ConversionError> {
+                command
+                    .ok_or_else(|| ErrorBuilder::field_not_set("command"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                start_rolling_restart_failure::Error::NoPeers(_) => {
+                    StartRollingRestartError::NoPeers
+                }
+                start_rolling_restart_failure::Error::PeerNotFound(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    StartRollingRestartError::PeerNotFound { peer_id }
+                }
+                start_rolling_restart_failure::Error::IllegalCommand(error) => {
+                    StartRollingRestartError::IllegalCommand { command: extract_command(error.command)? }
+                }
+                start_rolling_restart_failure::Error::IllegalMaxUnavailable(error) => {
+                    StartRollingRestartError::IllegalMaxUnavailable { max_unavailable_per_cluster: error.max_unavailable_per_cluster }
+                }
+                start_rolling_restart_failure::Error::Unauthorized(error) => {
+                    StartRollingRestartError::Unauthorized {
+                        command: extract_command(error.command)?,
+                        user: error.user,
+                        required_roles: error.required_roles,
+                    }
+                }
+                start_rolling_restart_failure::Error::Internal(error) => {
+                    StartRollingRestartError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<GetMaintenanceOperationError> for GetMaintenanceOperationFailure {
+        fn from(error: GetMaintenanceOperationError) -> Self {
+            let proto_error = match error {
+                GetMaintenanceOperationError::OperationNotFound { operation_id } => {
+                    get_maintenance_operation_failure::Error::OperationNotFound(GetMaintenanceOperationFailureOperationNotFound {
+                        operation_id: Some(operation_id.into()),
+                    })
+                }
+            };
+            GetMaintenanceOperationFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetMaintenanceOperationFailure> for GetMaintenanceOperationError {
+        type Error = ConversionError;
+        fn try_from(failure: GetMaintenanceOperationFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetMaintenanceOperationFailure, GetMaintenanceOperationError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_maintenance_operation_failure::Error::OperationNotFound(error) => {
+                    let operation_id = error.operation_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("operation_id"))?
+                        .try_into()?;
+                    GetMaintenanceOperationError::OperationNotFound { operation_id }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<GenerateProvisioningTokensError> for GenerateProvisioningTokensFailure {
+        fn from(error: GenerateProvisioningTokensError) -> Self {
+            let proto_error = match error {
+                GenerateProvisioningTokensError::IllegalCount { pool, count, max_count } => {
+                    generate_provisioning_tokens_failure::Error::IllegalCount(GenerateProvisioningTokensFailureIllegalCount {
+                        pool,
+                        count,
+                        max_count,
+                    })
+                }
+                GenerateProvisioningTokensError::Internal { pool, cause } => {
+                    generate_provisioning_tokens_failure::Error::Internal(GenerateProvisioningTokensFailureInternal {
+                        pool,
+                        cause
+                    })
+                }
+            };
+            GenerateProvisioningTokensFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GenerateProvisioningTokensFailure> for GenerateProvisioningTokensError {
+        type Error = ConversionError;
+        fn try_from(failure: GenerateProvisioningTokensFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GenerateProvisioningTokensFailure, GenerateProvisioningTokensError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                generate_provisioning_tokens_failure::Error::IllegalCount(error) => {
+                    GenerateProvisioningTokensError::IllegalCount {
+                        pool: error.pool,
+                        count: error.count,
+                        max_count: error.max_count,
+                    }
+                }
+                generate_provisioning_tokens_failure::Error::Internal(error) => {
+                    GenerateProvisioningTokensError::Internal { pool: error.pool, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListProvisioningTokensError> for ListProvisioningTokensFailure {
+        fn from(error: ListProvisioningTokensError) -> Self {
+            let proto_error = match error {
+                ListProvisioningTokensError::Internal { cause } => {
+                    list_provisioning_tokens_failure::Error::Internal(ListProvisioningTokensFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListProvisioningTokensFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListProvisioningTokensFailure> for ListProvisioningTokensError {
+        type Error = ConversionError;
+        fn try_from(failure: ListProvisioningTokensFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListProvisioningTokensFailure, ListProvisioningTokensError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_provisioning_tokens_failure::Error::Internal(error) => {
+                    ListProvisioningTokensError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<crate::carl::peer::ProvisioningTokenInfo> for ProvisioningTokenInfo {
+        fn from(value: crate::carl::peer::ProvisioningTokenInfo) -> Self {
+            ProvisioningTokenInfo {
+                id: Some(value.id.into()),
+                pool: value.pool,
+                template: Some(value.template.into()),
+                created_by: value.created_by,
+                created_at_unix_millis: to_unix_millis(value.created_at),
+                claimed_by: value.claimed_by.map(Into::into),
+            }
+        }
+    }
+
+    impl TryFrom<ProvisioningTokenInfo> for crate::carl::peer::ProvisioningTokenInfo {
+        type Error = ConversionError;
+        fn try_from(value: ProvisioningTokenInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ProvisioningTokenInfo, crate::carl::peer::ProvisioningTokenInfo>;
+            let id = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+            let template = value.template
+                .ok_or_else(|| ErrorBuilder::field_not_set("template"))?
+                .try_into()?;
+            let claimed_by = value.claimed_by
+                .map(PeerId::try_from)
+                .transpose()?;
+            Ok(crate::carl::peer::ProvisioningTokenInfo {
+                id,
+                pool: value.pool,
+                template,
+                created_by: value.created_by,
+                created_at: from_unix_millis(value.created_at_unix_millis),
+                claimed_by,
+            })
+        }
+    }
+
+    impl From<ClaimProvisioningTokenError> for ClaimProvisioningTokenFailure {
+        fn from(error: ClaimProvisioningTokenError) -> Self {
+            let proto_error = match error {
+                ClaimProvisioningTokenError::InvalidToken { token } => {
+                    claim_provisioning_token_failure::Error::InvalidToken(ClaimProvisioningTokenFailureInvalidToken {
+                        token: Some(token.into()),
+                    })
+                }
+                ClaimProvisioningTokenError::AlreadyClaimed { token } => {
+                    claim_provisioning_token_failure::Error::AlreadyClaimed(ClaimProvisioningTokenFailureAlreadyClaimed {
+                        token: Some(token.into()),
+                    })
+                }
+                ClaimProvisioningTokenError::Internal { token, cause } => {
+                    claim_provisioning_token_failure::Error::Internal(ClaimProvisioningTokenFailureInternal {
+                        token: Some(token.into()),
+                        cause
+                    })
+                }
+            };
+            ClaimProvisioningTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ClaimProvisioningTokenFailure> for ClaimProvisioningTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: ClaimProvisioningTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ClaimProvisioningTokenFailure, ClaimProvisioningTokenError>;
+
+            fn extract_token(token: Option<proto::peer::provisioning::ProvisioningTokenId>) -> Result<ProvisioningTokenId, ConversionError> {
+                token
+                    .ok_or_else(|| ErrorBuilder::field_not_set("token"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                claim_provisioning_token_failure::Error::InvalidToken(error) => {
+                    ClaimProvisioningTokenError::InvalidToken { token: extract_token(error.token)? }
+                }
+                claim_provisioning_token_failure::Error::AlreadyClaimed(error) => {
+                    ClaimProvisioningTokenError::AlreadyClaimed { token: extract_token(error.token)? }
+                }
+                claim_provisioning_token_failure::Error::Internal(error) => {
+                    ClaimProvisioningTokenError::Internal {
+                        token: extract_token(error.token)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<RevokePeerSetupError> for RevokePeerSetupFailure {
+        fn from(error: RevokePeerSetupError) -> Self {
+            let proto_error = match error {
+                RevokePeerSetupError::PeerNotFound { peer_id } => {
+                    revoke_peer_setup_failure::Error::PeerNotFound(RevokePeerSetupFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                RevokePeerSetupError::Internal { peer_id, cause } => {
+                    revoke_peer_setup_failure::Error::Internal(RevokePeerSetupFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            RevokePeerSetupFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokePeerSetupFailure> for RevokePeerSetupError {
+        type Error = ConversionError;
+        fn try_from(failure: RevokePeerSetupFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokePeerSetupFailure, RevokePeerSetupError>;
+
+            fn extract_peer_id(peer_id: Option<proto::peer::PeerId>) -> Result<PeerId, ConversionError> {
+                peer_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_peer_setup_failure::Error::PeerNotFound(error) => {
+                    RevokePeerSetupError::PeerNotFound { peer_id: extract_peer_id(error.peer_id)? }
+                }
+                revoke_peer_setup_failure::Error::Internal(error) => {
+                    RevokePeerSetupError::Internal {
+                        peer_id: extract_peer_id(error.peer_id)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod peer_messaging_broker {
+    use std::time::{Duration, SystemTime};
+
+    use opendut_types::peer::configuration::validation::CheckOutcome;
+    use opendut_types::peer::executor::container::ContainerSecret;
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::PeerId;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::broker::{GetExecutorSecretsError, UploadSelfTestReportError};
+    use crate::carl::peer::PeerCommand;
+
+    tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
+
+    fn to_unix_millis(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn from_unix_millis(millis: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    impl From<PeerCommand> for PeerCommandKind {
+        fn from(value: PeerCommand) -> Self {
+            let kind = match value {
+                PeerCommand::RestartService => peer_command_kind::Kind::RestartService(PeerCommandRestartService {}),
+                PeerCommand::ReapplyConfiguration => peer_command_kind::Kind::ReapplyConfiguration(PeerCommandReapplyConfiguration {}),
+                PeerCommand::CollectDiagnostics => peer_command_kind::Kind::CollectDiagnostics(PeerCommandCollectDiagnostics {}),
+                PeerCommand::RebootHost => peer_command_kind::Kind::RebootHost(PeerCommandRebootHost {}),
+                PeerCommand::CollectLogs => peer_command_kind::Kind::CollectLogs(PeerCommandCollectLogs {}),
+                PeerCommand::StartPacketCapture => peer_command_kind::Kind::StartPacketCapture(PeerCommandStartPacketCapture {}),
+                PeerCommand::StopPacketCapture => peer_command_kind::Kind::StopPacketCapture(PeerCommandStopPacketCapture {}),
+            };
+            Self {
+                kind: Some(kind)
+            }
+        }
+    }
+
+    impl TryFrom<PeerCommandKind> for PeerCommand {
+        type Error = ConversionError;
+
+        fn try_from(value: PeerCommandKind) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<PeerCommandKind, PeerCommand>;
+
+            let kind = value.kind
+                .ok_or_else(|| ErrorBuilder::field_not_set("kind"))?;
+            let command = match kind {
+                peer_command_kind::Kind::RestartService(_) => PeerCommand::RestartService,
+                peer_command_kind::Kind::ReapplyConfiguration(_) => PeerCommand::ReapplyConfiguration,
+                peer_command_kind::Kind::CollectDiagnostics(_) => PeerCommand::CollectDiagnostics,
+                peer_command_kind::Kind::RebootHost(_) => PeerCommand::RebootHost,
+                peer_command_kind::Kind::CollectLogs(_) => PeerCommand::CollectLogs,
+                peer_command_kind::Kind::StartPacketCapture(_) => PeerCommand::StartPacketCapture,
+                peer_command_kind::Kind::StopPacketCapture(_) => PeerCommand::StopPacketCapture,
+            };
+            Ok(command)
+        }
+    }
+
+    impl From<crate::carl::broker::ExecutorSecret> for ExecutorSecret {
+        fn from(secret: crate::carl::broker::ExecutorSecret) -> Self {
+            ExecutorSecret {
+                name: Some(secret.name.into()),
+                value: secret.value,
+            }
+        }
+    }
+
+    impl TryFrom<ExecutorSecret> for crate::carl::broker::ExecutorSecret {
+        type Error = ConversionError;
+        fn try_from(secret: ExecutorSecret) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ExecutorSecret, crate::carl::broker::ExecutorSecret>;
+            let name: ContainerSecret = secret.name
+                .ok_or_else(|| ErrorBuilder::field_not_set("name"))?
+                .try_into()?;
+            Ok(crate::carl::broker::ExecutorSecret { name, value: secret.value })
+        }
+    }
+
+    impl From<GetExecutorSecretsError> for GetExecutorSecretsFailure {
+        fn from(error: GetExecutorSecretsError) -> Self {
+            let proto_error = match error {
+                GetExecutorSecretsError::ExecutorNotFound { peer_id, executor_id } => {
+                    get_executor_secrets_failure::Error::ExecutorNotFound(GetExecutorSecretsFailureExecutorNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                GetExecutorSecretsError::SecretNotFound { executor_id, secret } => {
+                    get_executor_secrets_failure::Error::SecretNotFound(GetExecutorSecretsFailureSecretNotFound {
+                        executor_id: Some(executor_id.into()),
+                        secret: Some(secret.into()),
+                    })
+                }
+                GetExecutorSecretsError::Internal { peer_id, cause } => {
+                    get_executor_secrets_failure::Error::Internal(GetExecutorSecretsFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetExecutorSecretsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetExecutorSecretsFailureExecutorNotFound> for GetExecutorSecretsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorSecretsFailureExecutorNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorSecretsFailureExecutorNotFound, GetExecutorSecretsError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(GetExecutorSecretsError::ExecutorNotFound { peer_id, executor_id })
+        }
+    }
+
+    impl TryFrom<GetExecutorSecretsFailureSecretNotFound> for GetExecutorSecretsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorSecretsFailureSecretNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorSecretsFailureSecretNotFound, GetExecutorSecretsError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            let secret: ContainerSecret = failure.secret
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret"))?
+                .try_into()?;
+            Ok(GetExecutorSecretsError::SecretNotFound { executor_id, secret })
+        }
+    }
+
+    impl TryFrom<GetExecutorSecretsFailureInternal> for GetExecutorSecretsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorSecretsFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorSecretsFailureInternal, GetExecutorSecretsError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetExecutorSecretsError::Internal { peer_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<GetExecutorSecretsFailure> for GetExecutorSecretsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorSecretsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorSecretsFailure, GetExecutorSecretsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_executor_secrets_failure::Error::ExecutorNotFound(error) => {
+                    error.try_into()?
+                }
+                get_executor_secrets_failure::Error::SecretNotFound(error) => {
                     error.try_into()?
                 }
-                delete_peer_descriptor_failure::Error::Internal(error) => {
-                    error.try_into()?
+                get_executor_secrets_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<opendut_types::peer::self_test::SelfTestReport> for SelfTestReport {
+        fn from(report: opendut_types::peer::self_test::SelfTestReport) -> Self {
+            SelfTestReport {
+                peer_id: Some(report.peer_id.into()),
+                checked_at_unix_millis: to_unix_millis(report.checked_at),
+                checks: report.checks.into_iter().map(SelfTestCheck::from).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<SelfTestReport> for opendut_types::peer::self_test::SelfTestReport {
+        type Error = ConversionError;
+        fn try_from(report: SelfTestReport) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SelfTestReport, opendut_types::peer::self_test::SelfTestReport>;
+            let peer_id: PeerId = report.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let checks = report.checks.into_iter()
+                .map(opendut_types::peer::self_test::SelfTestCheck::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(opendut_types::peer::self_test::SelfTestReport::new(peer_id, from_unix_millis(report.checked_at_unix_millis), checks))
+        }
+    }
+
+    impl From<opendut_types::peer::self_test::SelfTestCheck> for SelfTestCheck {
+        fn from(check: opendut_types::peer::self_test::SelfTestCheck) -> Self {
+            let outcome = match check.outcome {
+                CheckOutcome::Passed => self_test_check::Outcome::Passed(SelfTestCheckPassed {}),
+                CheckOutcome::Failed { cause } => self_test_check::Outcome::Failed(SelfTestCheckFailed { cause }),
+                CheckOutcome::Skipped { reason } => self_test_check::Outcome::Skipped(SelfTestCheckSkipped { reason }),
+            };
+            SelfTestCheck {
+                stage: check.stage,
+                subject: check.subject,
+                outcome: Some(outcome),
+            }
+        }
+    }
+
+    impl TryFrom<SelfTestCheck> for opendut_types::peer::self_test::SelfTestCheck {
+        type Error = ConversionError;
+        fn try_from(check: SelfTestCheck) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SelfTestCheck, opendut_types::peer::self_test::SelfTestCheck>;
+            let outcome = match check.outcome.ok_or_else(|| ErrorBuilder::field_not_set("outcome"))? {
+                self_test_check::Outcome::Passed(_) => CheckOutcome::Passed,
+                self_test_check::Outcome::Failed(failed) => CheckOutcome::Failed { cause: failed.cause },
+                self_test_check::Outcome::Skipped(skipped) => CheckOutcome::Skipped { reason: skipped.reason },
+            };
+            Ok(opendut_types::peer::self_test::SelfTestCheck {
+                stage: check.stage,
+                subject: check.subject,
+                outcome,
+            })
+        }
+    }
+
+    impl From<UploadSelfTestReportError> for UploadSelfTestReportFailure {
+        fn from(error: UploadSelfTestReportError) -> Self {
+            let proto_error = match error {
+                UploadSelfTestReportError::PeerNotFound { peer_id } => {
+                    upload_self_test_report_failure::Error::PeerNotFound(UploadSelfTestReportFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                UploadSelfTestReportError::Internal { peer_id, cause } => {
+                    upload_self_test_report_failure::Error::Internal(UploadSelfTestReportFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            UploadSelfTestReportFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<UploadSelfTestReportFailure> for UploadSelfTestReportError {
+        type Error = ConversionError;
+        fn try_from(failure: UploadSelfTestReportFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<UploadSelfTestReportFailure, UploadSelfTestReportError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                upload_self_test_report_failure::Error::PeerNotFound(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    UploadSelfTestReportError::PeerNotFound { peer_id }
+                }
+                upload_self_test_report_failure::Error::Internal(error) => {
+                    let peer_id: PeerId = error.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    UploadSelfTestReportError::Internal { peer_id, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod policy {
+    tonic::include_proto!("opendut.carl.services.policy");
+
+    impl From<crate::carl::policy::PolicyViolation> for PolicyViolation {
+        fn from(violation: crate::carl::policy::PolicyViolation) -> Self {
+            PolicyViolation {
+                rule: violation.rule,
+                message: violation.message,
+            }
+        }
+    }
+
+    impl From<PolicyViolation> for crate::carl::policy::PolicyViolation {
+        fn from(violation: PolicyViolation) -> Self {
+            crate::carl::policy::PolicyViolation {
+                rule: violation.rule,
+                message: violation.message,
+            }
+        }
+    }
+}
+
+pub mod project_manager {
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::peer::PeerId;
+    use opendut_types::project;
+    use opendut_types::project::{ProjectMember, ProjectName};
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::project::{DeleteProjectError, ListProjectsError, StoreProjectError};
+
+    tonic::include_proto!("opendut.carl.services.project_manager");
+
+    impl From<project::ProjectId> for ProjectId {
+        fn from(value: project::ProjectId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<ProjectId> for project::ProjectId {
+        type Error = ConversionError;
+
+        fn try_from(value: ProjectId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ProjectId, project::ProjectId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
+        }
+    }
+
+    impl From<project::Project> for Project {
+        fn from(value: project::Project) -> Self {
+            Self {
+                id: Some(value.id.into()),
+                name: value.name.into(),
+                members: value.members.into_iter().map(String::from).collect(),
+                peers: value.peers.into_iter().map(Into::into).collect(),
+                clusters: value.clusters.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<Project> for project::Project {
+        type Error = ConversionError;
+
+        fn try_from(value: Project) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Project, project::Project>;
+
+            let id: project::ProjectId = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+            let name = ProjectName::try_from(value.name)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+            let members = value.members.into_iter()
+                .map(ProjectMember::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+            let peers = value.peers.into_iter()
+                .map(PeerId::try_from)
+                .collect::<Result<_, _>>()?;
+            let clusters = value.clusters.into_iter()
+                .map(ClusterId::try_from)
+                .collect::<Result<_, _>>()?;
+
+            Ok(Self {
+                id,
+                name,
+                members,
+                peers,
+                clusters,
+            })
+        }
+    }
+
+    impl From<StoreProjectError> for StoreProjectFailure {
+        fn from(error: StoreProjectError) -> Self {
+            let proto_error = match error {
+                StoreProjectError::NameAlreadyExists { project_id, name } => {
+                    store_project_failure::Error::NameAlreadyExists(StoreProjectFailureNameAlreadyExists {
+                        project_id: Some(project_id.into()),
+                        name: name.into(),
+                    })
+                }
+                StoreProjectError::Internal { project_id, cause } => {
+                    store_project_failure::Error::Internal(StoreProjectFailureInternal {
+                        project_id: Some(project_id.into()),
+                        cause,
+                    })
+                }
+            };
+            StoreProjectFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StoreProjectFailure> for StoreProjectError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreProjectFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreProjectFailure, StoreProjectError>;
+
+            fn extract_project_id(project_id: Option<ProjectId>) -> Result<project::ProjectId, ConversionError> {
+                project_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("project_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_project_failure::Error::NameAlreadyExists(error) => {
+                    StoreProjectError::NameAlreadyExists {
+                        project_id: extract_project_id(error.project_id)?,
+                        name: ProjectName::try_from(error.name)
+                            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?,
+                    }
+                }
+                store_project_failure::Error::Internal(error) => {
+                    StoreProjectError::Internal {
+                        project_id: extract_project_id(error.project_id)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteProjectError> for DeleteProjectFailure {
+        fn from(error: DeleteProjectError) -> Self {
+            let proto_error = match error {
+                DeleteProjectError::NotFound { project_id } => {
+                    delete_project_failure::Error::NotFound(DeleteProjectFailureNotFound {
+                        project_id: Some(project_id.into()),
+                    })
+                }
+                DeleteProjectError::Internal { project_id, cause } => {
+                    delete_project_failure::Error::Internal(DeleteProjectFailureInternal {
+                        project_id: Some(project_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteProjectFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteProjectFailure> for DeleteProjectError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteProjectFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteProjectFailure, DeleteProjectError>;
+
+            fn extract_project_id(project_id: Option<ProjectId>) -> Result<project::ProjectId, ConversionError> {
+                project_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("project_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_project_failure::Error::NotFound(error) => {
+                    DeleteProjectError::NotFound {
+                        project_id: extract_project_id(error.project_id)?,
+                    }
+                }
+                delete_project_failure::Error::Internal(error) => {
+                    DeleteProjectError::Internal {
+                        project_id: extract_project_id(error.project_id)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListProjectsError> for ListProjectsFailure {
+        fn from(error: ListProjectsError) -> Self {
+            let proto_error = match error {
+                ListProjectsError::Internal { cause } => {
+                    list_projects_failure::Error::Internal(ListProjectsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListProjectsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListProjectsFailure> for ListProjectsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListProjectsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListProjectsFailure, ListProjectsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_projects_failure::Error::Internal(error) => {
+                    ListProjectsError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod results_manager {
+    use std::time::{Duration, SystemTime};
+
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::PeerId;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::results::{DownloadExecutorResultError, ListExecutorResultsError};
+
+    tonic::include_proto!("opendut.carl.services.results_manager");
+
+    fn to_unix_millis(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn from_unix_millis(millis: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    impl From<crate::carl::results::ExecutorResultFile> for ExecutorResultFile {
+        fn from(value: crate::carl::results::ExecutorResultFile) -> Self {
+            Self {
+                name: value.name,
+                size_bytes: value.size_bytes,
+                modified_at_unix_millis: value.modified_at.map(to_unix_millis),
+            }
+        }
+    }
+
+    impl From<ExecutorResultFile> for crate::carl::results::ExecutorResultFile {
+        fn from(value: ExecutorResultFile) -> Self {
+            Self {
+                name: value.name,
+                size_bytes: value.size_bytes,
+                modified_at: value.modified_at_unix_millis.map(from_unix_millis),
+            }
+        }
+    }
+
+    fn extract_ids(peer_id: Option<opendut_types::proto::peer::PeerId>, executor_id: Option<opendut_types::proto::peer::executor::ExecutorId>) -> Result<(PeerId, ExecutorId), ConversionError> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorResultsFailure, (PeerId, ExecutorId)>;
+
+        let peer_id = peer_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let executor_id = executor_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        Ok((peer_id, executor_id))
+    }
+
+    impl From<ListExecutorResultsError> for ExecutorResultsFailure {
+        fn from(error: ListExecutorResultsError) -> Self {
+            let proto_error = match error {
+                ListExecutorResultsError::PeerNotFound { peer_id, executor_id } => {
+                    executor_results_failure::Error::PeerNotFound(ExecutorResultsFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                ListExecutorResultsError::ExecutorNotFound { peer_id, executor_id } => {
+                    executor_results_failure::Error::ExecutorNotFound(ExecutorResultsFailureExecutorNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                ListExecutorResultsError::ResultsUrlNotConfigured { peer_id, executor_id } => {
+                    executor_results_failure::Error::ResultsUrlNotConfigured(ExecutorResultsFailureResultsUrlNotConfigured {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                ListExecutorResultsError::Storage { peer_id, executor_id, cause } => {
+                    executor_results_failure::Error::Storage(ExecutorResultsFailureStorage {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                        cause,
+                    })
+                }
+            };
+            ExecutorResultsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ExecutorResultsFailure> for ListExecutorResultsError {
+        type Error = ConversionError;
+        fn try_from(failure: ExecutorResultsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ExecutorResultsFailure, ListExecutorResultsError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                executor_results_failure::Error::PeerNotFound(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    ListExecutorResultsError::PeerNotFound { peer_id, executor_id }
+                }
+                executor_results_failure::Error::ExecutorNotFound(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    ListExecutorResultsError::ExecutorNotFound { peer_id, executor_id }
+                }
+                executor_results_failure::Error::ResultsUrlNotConfigured(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    ListExecutorResultsError::ResultsUrlNotConfigured { peer_id, executor_id }
+                }
+                executor_results_failure::Error::Storage(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    ListExecutorResultsError::Storage { peer_id, executor_id, cause: error.cause }
+                }
+                executor_results_failure::Error::ResultNotFound(_) => {
+                    return Err(ErrorBuilder::message("Listing results cannot fail, because a single result does not exist."));
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DownloadExecutorResultError> for ExecutorResultsFailure {
+        fn from(error: DownloadExecutorResultError) -> Self {
+            let proto_error = match error {
+                DownloadExecutorResultError::PeerNotFound { peer_id, executor_id } => {
+                    executor_results_failure::Error::PeerNotFound(ExecutorResultsFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                DownloadExecutorResultError::ExecutorNotFound { peer_id, executor_id } => {
+                    executor_results_failure::Error::ExecutorNotFound(ExecutorResultsFailureExecutorNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                DownloadExecutorResultError::ResultsUrlNotConfigured { peer_id, executor_id } => {
+                    executor_results_failure::Error::ResultsUrlNotConfigured(ExecutorResultsFailureResultsUrlNotConfigured {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                DownloadExecutorResultError::ResultNotFound { peer_id, executor_id, name } => {
+                    executor_results_failure::Error::ResultNotFound(ExecutorResultsFailureResultNotFound {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                        name,
+                    })
+                }
+                DownloadExecutorResultError::Storage { peer_id, executor_id, cause } => {
+                    executor_results_failure::Error::Storage(ExecutorResultsFailureStorage {
+                        peer_id: Some(peer_id.into()),
+                        executor_id: Some(executor_id.into()),
+                        cause,
+                    })
+                }
+            };
+            ExecutorResultsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ExecutorResultsFailure> for DownloadExecutorResultError {
+        type Error = ConversionError;
+        fn try_from(failure: ExecutorResultsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ExecutorResultsFailure, DownloadExecutorResultError>;
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                executor_results_failure::Error::PeerNotFound(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    DownloadExecutorResultError::PeerNotFound { peer_id, executor_id }
+                }
+                executor_results_failure::Error::ExecutorNotFound(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    DownloadExecutorResultError::ExecutorNotFound { peer_id, executor_id }
+                }
+                executor_results_failure::Error::ResultsUrlNotConfigured(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    DownloadExecutorResultError::ResultsUrlNotConfigured { peer_id, executor_id }
+                }
+                executor_results_failure::Error::ResultNotFound(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    DownloadExecutorResultError::ResultNotFound { peer_id, executor_id, name: error.name }
+                }
+                executor_results_failure::Error::Storage(error) => {
+                    let (peer_id, executor_id) = extract_ids(error.peer_id, error.executor_id)?;
+                    DownloadExecutorResultError::Storage { peer_id, executor_id, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod resource_observer {
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::observer::{ResourceChange, ResourceEvent};
+
+    tonic::include_proto!("opendut.carl.services.resource_observer");
+
+    impl From<crate::carl::observer::ResourceKind> for ResourceKind {
+        fn from(value: crate::carl::observer::ResourceKind) -> Self {
+            let kind = match value {
+                crate::carl::observer::ResourceKind::PeerDescriptor => resource_kind::Kind::PeerDescriptor(ResourceKindPeerDescriptor {}),
+                crate::carl::observer::ResourceKind::PeerState => resource_kind::Kind::PeerState(ResourceKindPeerState {}),
+                crate::carl::observer::ResourceKind::ClusterConfiguration => resource_kind::Kind::ClusterConfiguration(ResourceKindClusterConfiguration {}),
+                crate::carl::observer::ResourceKind::ClusterDeployment => resource_kind::Kind::ClusterDeployment(ResourceKindClusterDeployment {}),
+                crate::carl::observer::ResourceKind::ClusterDeploymentStatus => resource_kind::Kind::ClusterDeploymentStatus(ResourceKindClusterDeploymentStatus {}),
+                crate::carl::observer::ResourceKind::PeerConfiguration => resource_kind::Kind::PeerConfiguration(ResourceKindPeerConfiguration {}),
+                crate::carl::observer::ResourceKind::PeerGroup => resource_kind::Kind::PeerGroup(ResourceKindPeerGroup {}),
+                crate::carl::observer::ResourceKind::HierarchyNode => resource_kind::Kind::HierarchyNode(ResourceKindHierarchyNode {}),
+                crate::carl::observer::ResourceKind::ResourceSnapshot => resource_kind::Kind::ResourceSnapshot(ResourceKindResourceSnapshot {}),
+            };
+            Self {
+                kind: Some(kind)
+            }
+        }
+    }
+
+    impl TryFrom<ResourceKind> for crate::carl::observer::ResourceKind {
+        type Error = ConversionError;
+
+        fn try_from(value: ResourceKind) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ResourceKind, crate::carl::observer::ResourceKind>;
+
+            let kind = match value.kind.ok_or_else(|| ErrorBuilder::field_not_set("kind"))? {
+                resource_kind::Kind::PeerDescriptor(_) => crate::carl::observer::ResourceKind::PeerDescriptor,
+                resource_kind::Kind::PeerState(_) => crate::carl::observer::ResourceKind::PeerState,
+                resource_kind::Kind::ClusterConfiguration(_) => crate::carl::observer::ResourceKind::ClusterConfiguration,
+                resource_kind::Kind::ClusterDeployment(_) => crate::carl::observer::ResourceKind::ClusterDeployment,
+                resource_kind::Kind::ClusterDeploymentStatus(_) => crate::carl::observer::ResourceKind::ClusterDeploymentStatus,
+                resource_kind::Kind::PeerConfiguration(_) => crate::carl::observer::ResourceKind::PeerConfiguration,
+                resource_kind::Kind::PeerGroup(_) => crate::carl::observer::ResourceKind::PeerGroup,
+                resource_kind::Kind::HierarchyNode(_) => crate::carl::observer::ResourceKind::HierarchyNode,
+                resource_kind::Kind::ResourceSnapshot(_) => crate::carl::observer::ResourceKind::ResourceSnapshot,
+            };
+            Ok(kind)
+        }
+    }
+
+    impl From<crate::carl::observer::ResourceFilter> for ResourceFilter {
+        fn from(value: crate::carl::observer::ResourceFilter) -> Self {
+            Self {
+                kind: Some(value.kind.into()),
+                ids: value.ids.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<ResourceFilter> for crate::carl::observer::ResourceFilter {
+        type Error = ConversionError;
+
+        fn try_from(value: ResourceFilter) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ResourceFilter, crate::carl::observer::ResourceFilter>;
+
+            let kind = value.kind
+                .ok_or_else(|| ErrorBuilder::field_not_set("kind"))?
+                .try_into()?;
+            let ids = value.ids.into_iter().map(Into::into).collect();
+
+            Ok(Self { kind, ids })
+        }
+    }
+
+    impl From<ResourceEvent> for subscribe_resource_events_response::Event {
+        fn from(value: ResourceEvent) -> Self {
+            match value {
+                ResourceEvent::Changed { kind, id, change } => {
+                    let change = match change {
+                        ResourceChange::Inserted => resource_changed::Change::Inserted(ResourceInserted {}),
+                        ResourceChange::Removed => resource_changed::Change::Removed(ResourceRemoved {}),
+                    };
+                    subscribe_resource_events_response::Event::Changed(ResourceChanged {
+                        kind: Some(kind.into()),
+                        id: Some(id.into()),
+                        change: Some(change),
+                    })
+                }
+                ResourceEvent::Lagged { skipped } => {
+                    subscribe_resource_events_response::Event::Lagged(ResourceEventsLagged { skipped })
+                }
+            }
+        }
+    }
+
+    impl TryFrom<subscribe_resource_events_response::Event> for ResourceEvent {
+        type Error = ConversionError;
+
+        fn try_from(value: subscribe_resource_events_response::Event) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<subscribe_resource_events_response::Event, ResourceEvent>;
+
+            let event = match value {
+                subscribe_resource_events_response::Event::Changed(changed) => {
+                    let kind = changed.kind
+                        .ok_or_else(|| ErrorBuilder::field_not_set("kind"))?
+                        .try_into()?;
+                    let id = changed.id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                        .into();
+                    let change = match changed.change.ok_or_else(|| ErrorBuilder::field_not_set("change"))? {
+                        resource_changed::Change::Inserted(_) => ResourceChange::Inserted,
+                        resource_changed::Change::Removed(_) => ResourceChange::Removed,
+                    };
+                    ResourceEvent::Changed { kind, id, change }
+                }
+                subscribe_resource_events_response::Event::Lagged(lagged) => {
+                    ResourceEvent::Lagged { skipped: lagged.skipped }
+                }
+            };
+            Ok(event)
+        }
+    }
+}
+
+pub mod service_account_manager {
+    use std::time::{Duration, SystemTime};
+
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::service_account;
+    use opendut_types::service_account::ServiceAccountName;
+
+    use crate::carl::service_account::{CreateServiceAccountError, DeleteServiceAccountError, IssueServiceAccountTokenError, ListServiceAccountsError, RevokeServiceAccountTokenError};
+
+    tonic::include_proto!("opendut.carl.services.service_account_manager");
+
+    fn unix_millis(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    impl From<service_account::ServiceAccountId> for ServiceAccountId {
+        fn from(value: service_account::ServiceAccountId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<ServiceAccountId> for service_account::ServiceAccountId {
+        type Error = ConversionError;
+
+        fn try_from(value: ServiceAccountId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ServiceAccountId, service_account::ServiceAccountId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
+        }
+    }
+
+    impl From<service_account::ServiceAccountTokenId> for ServiceAccountTokenId {
+        fn from(value: service_account::ServiceAccountTokenId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<ServiceAccountTokenId> for service_account::ServiceAccountTokenId {
+        type Error = ConversionError;
+
+        fn try_from(value: ServiceAccountTokenId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ServiceAccountTokenId, service_account::ServiceAccountTokenId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
+        }
+    }
+
+    impl From<service_account::ServiceAccountScope> for ServiceAccountScope {
+        fn from(value: service_account::ServiceAccountScope) -> Self {
+            let scope = match value {
+                service_account::ServiceAccountScope::Read => service_account_scope::Scope::Read(ServiceAccountScopeRead {}),
+                service_account::ServiceAccountScope::Peers => service_account_scope::Scope::Peers(ServiceAccountScopePeers {}),
+                service_account::ServiceAccountScope::Clusters => service_account_scope::Scope::Clusters(ServiceAccountScopeClusters {}),
+                service_account::ServiceAccountScope::Snapshots => service_account_scope::Scope::Snapshots(ServiceAccountScopeSnapshots {}),
+                service_account::ServiceAccountScope::Diagnostics => service_account_scope::Scope::Diagnostics(ServiceAccountScopeDiagnostics {}),
+            };
+            Self {
+                scope: Some(scope)
+            }
+        }
+    }
+
+    impl TryFrom<ServiceAccountScope> for service_account::ServiceAccountScope {
+        type Error = ConversionError;
+
+        fn try_from(value: ServiceAccountScope) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ServiceAccountScope, service_account::ServiceAccountScope>;
+
+            let scope = match value.scope.ok_or_else(|| ErrorBuilder::field_not_set("scope"))? {
+                service_account_scope::Scope::Read(_) => service_account::ServiceAccountScope::Read,
+                service_account_scope::Scope::Peers(_) => service_account::ServiceAccountScope::Peers,
+                service_account_scope::Scope::Clusters(_) => service_account::ServiceAccountScope::Clusters,
+                service_account_scope::Scope::Snapshots(_) => service_account::ServiceAccountScope::Snapshots,
+                service_account_scope::Scope::Diagnostics(_) => service_account::ServiceAccountScope::Diagnostics,
+            };
+            Ok(scope)
+        }
+    }
+
+    impl From<crate::carl::service_account::ServiceAccountTokenInfo> for ServiceAccountTokenInfo {
+        fn from(value: crate::carl::service_account::ServiceAccountTokenInfo) -> Self {
+            Self {
+                id: Some(value.id.into()),
+                created_at_unix_millis: unix_millis(value.created_at),
+                expires_at_unix_millis: unix_millis(value.expires_at),
+                read_only: value.read_only,
+            }
+        }
+    }
+
+    impl TryFrom<ServiceAccountTokenInfo> for crate::carl::service_account::ServiceAccountTokenInfo {
+        type Error = ConversionError;
+
+        fn try_from(value: ServiceAccountTokenInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ServiceAccountTokenInfo, crate::carl::service_account::ServiceAccountTokenInfo>;
+
+            let id: service_account::ServiceAccountTokenId = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+
+            Ok(Self {
+                id,
+                created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.created_at_unix_millis),
+                expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.expires_at_unix_millis),
+                read_only: value.read_only,
+            })
+        }
+    }
+
+    impl From<crate::carl::service_account::ServiceAccountInfo> for ServiceAccountInfo {
+        fn from(value: crate::carl::service_account::ServiceAccountInfo) -> Self {
+            Self {
+                id: Some(value.id.into()),
+                name: value.name.into(),
+                scopes: value.scopes.into_iter().map(Into::into).collect(),
+                created_by: value.created_by,
+                created_at_unix_millis: unix_millis(value.created_at),
+                tokens: value.tokens.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<ServiceAccountInfo> for crate::carl::service_account::ServiceAccountInfo {
+        type Error = ConversionError;
+
+        fn try_from(value: ServiceAccountInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ServiceAccountInfo, crate::carl::service_account::ServiceAccountInfo>;
+
+            let id: service_account::ServiceAccountId = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .try_into()?;
+            let name = ServiceAccountName::try_from(value.name)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+            let scopes = value.scopes.into_iter()
+                .map(service_account::ServiceAccountScope::try_from)
+                .collect::<Result<_, _>>()?;
+            let tokens = value.tokens.into_iter()
+                .map(crate::carl::service_account::ServiceAccountTokenInfo::try_from)
+                .collect::<Result<_, _>>()?;
+
+            Ok(Self {
+                id,
+                name,
+                scopes,
+                created_by: value.created_by,
+                created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.created_at_unix_millis),
+                tokens,
+            })
+        }
+    }
+
+    impl From<CreateServiceAccountError> for CreateServiceAccountFailure {
+        fn from(error: CreateServiceAccountError) -> Self {
+            let proto_error = match error {
+                CreateServiceAccountError::NameAlreadyExists { name } => {
+                    create_service_account_failure::Error::NameAlreadyExists(CreateServiceAccountFailureNameAlreadyExists {
+                        name: name.into(),
+                    })
+                }
+                CreateServiceAccountError::Internal { name, cause } => {
+                    create_service_account_failure::Error::Internal(CreateServiceAccountFailureInternal {
+                        name: name.into(),
+                        cause,
+                    })
+                }
+            };
+            CreateServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateServiceAccountFailure> for CreateServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateServiceAccountFailure, CreateServiceAccountError>;
+
+            let name = |name: String| ServiceAccountName::try_from(name)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()));
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_service_account_failure::Error::NameAlreadyExists(error) => {
+                    CreateServiceAccountError::NameAlreadyExists { name: name(error.name)? }
+                }
+                create_service_account_failure::Error::Internal(error) => {
+                    CreateServiceAccountError::Internal { name: name(error.name)?, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListServiceAccountsError> for ListServiceAccountsFailure {
+        fn from(error: ListServiceAccountsError) -> Self {
+            let proto_error = match error {
+                ListServiceAccountsError::Internal { cause } => {
+                    list_service_accounts_failure::Error::Internal(ListServiceAccountsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListServiceAccountsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListServiceAccountsFailure> for ListServiceAccountsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListServiceAccountsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListServiceAccountsFailure, ListServiceAccountsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_service_accounts_failure::Error::Internal(error) => {
+                    ListServiceAccountsError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteServiceAccountError> for DeleteServiceAccountFailure {
+        fn from(error: DeleteServiceAccountError) -> Self {
+            let proto_error = match error {
+                DeleteServiceAccountError::NotFound { service_account_id } => {
+                    delete_service_account_failure::Error::NotFound(DeleteServiceAccountFailureNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                DeleteServiceAccountError::Internal { service_account_id, cause } => {
+                    delete_service_account_failure::Error::Internal(DeleteServiceAccountFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteServiceAccountFailure> for DeleteServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteServiceAccountFailure, DeleteServiceAccountError>;
+
+            fn extract_service_account_id(service_account_id: Option<ServiceAccountId>) -> Result<service_account::ServiceAccountId, ConversionError> {
+                service_account_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_service_account_failure::Error::NotFound(error) => {
+                    DeleteServiceAccountError::NotFound { service_account_id: extract_service_account_id(error.service_account_id)? }
+                }
+                delete_service_account_failure::Error::Internal(error) => {
+                    DeleteServiceAccountError::Internal { service_account_id: extract_service_account_id(error.service_account_id)?, cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<IssueServiceAccountTokenError> for IssueServiceAccountTokenFailure {
+        fn from(error: IssueServiceAccountTokenError) -> Self {
+            let proto_error = match error {
+                IssueServiceAccountTokenError::NotFound { service_account_id } => {
+                    issue_service_account_token_failure::Error::NotFound(IssueServiceAccountTokenFailureNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                IssueServiceAccountTokenError::IllegalValidity { service_account_id, validity_seconds, max_validity_seconds } => {
+                    issue_service_account_token_failure::Error::IllegalValidity(IssueServiceAccountTokenFailureIllegalValidity {
+                        service_account_id: Some(service_account_id.into()),
+                        validity_seconds,
+                        max_validity_seconds,
+                    })
+                }
+                IssueServiceAccountTokenError::Internal { service_account_id, cause } => {
+                    issue_service_account_token_failure::Error::Internal(IssueServiceAccountTokenFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause,
+                    })
+                }
+            };
+            IssueServiceAccountTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<IssueServiceAccountTokenFailure> for IssueServiceAccountTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: IssueServiceAccountTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<IssueServiceAccountTokenFailure, IssueServiceAccountTokenError>;
+
+            fn extract_service_account_id(service_account_id: Option<ServiceAccountId>) -> Result<service_account::ServiceAccountId, ConversionError> {
+                service_account_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                issue_service_account_token_failure::Error::NotFound(error) => {
+                    IssueServiceAccountTokenError::NotFound { service_account_id: extract_service_account_id(error.service_account_id)? }
+                }
+                issue_service_account_token_failure::Error::IllegalValidity(error) => {
+                    IssueServiceAccountTokenError::IllegalValidity {
+                        service_account_id: extract_service_account_id(error.service_account_id)?,
+                        validity_seconds: error.validity_seconds,
+                        max_validity_seconds: error.max_validity_seconds,
+                    }
+                }
+                issue_service_account_token_failure::Error::Internal(error) => {
+                    IssueServiceAccountTokenError::Internal { service_account_id: extract_service_account_id(error.service_account_id)?, cause: error.cause }
                 }
             };
             Ok(error)
         }
     }
 
-    impl TryFrom<DeletePeerDescriptorFailurePeerNotFound> for DeletePeerDescriptorError {
+    impl From<RevokeServiceAccountTokenError> for RevokeServiceAccountTokenFailure {
+        fn from(error: RevokeServiceAccountTokenError) -> Self {
+            let proto_error = match error {
+                RevokeServiceAccountTokenError::NotFound { service_account_id, token_id } => {
+                    revoke_service_account_token_failure::Error::NotFound(RevokeServiceAccountTokenFailureNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                        token_id: Some(token_id.into()),
+                    })
+                }
+                RevokeServiceAccountTokenError::Internal { service_account_id, token_id, cause } => {
+                    revoke_service_account_token_failure::Error::Internal(RevokeServiceAccountTokenFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        token_id: Some(token_id.into()),
+                        cause,
+                    })
+                }
+            };
+            RevokeServiceAccountTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokeServiceAccountTokenFailure> for RevokeServiceAccountTokenError {
         type Error = ConversionError;
-        fn try_from(failure: DeletePeerDescriptorFailurePeerNotFound) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailurePeerNotFound, DeletePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            Ok(DeletePeerDescriptorError::PeerNotFound { peer_id })
+        fn try_from(failure: RevokeServiceAccountTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokeServiceAccountTokenFailure, RevokeServiceAccountTokenError>;
+
+            fn extract_service_account_id(service_account_id: Option<ServiceAccountId>) -> Result<service_account::ServiceAccountId, ConversionError> {
+                service_account_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                    .try_into()
+            }
+            fn extract_token_id(token_id: Option<ServiceAccountTokenId>) -> Result<service_account::ServiceAccountTokenId, ConversionError> {
+                token_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("token_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_service_account_token_failure::Error::NotFound(error) => {
+                    RevokeServiceAccountTokenError::NotFound {
+                        service_account_id: extract_service_account_id(error.service_account_id)?,
+                        token_id: extract_token_id(error.token_id)?,
+                    }
+                }
+                revoke_service_account_token_failure::Error::Internal(error) => {
+                    RevokeServiceAccountTokenError::Internal {
+                        service_account_id: extract_service_account_id(error.service_account_id)?,
+                        token_id: extract_token_id(error.token_id)?,
+                        cause: error.cause,
+                    }
+                }
+            };
+            Ok(error)
         }
     }
+}
 
-    impl TryFrom<DeletePeerDescriptorFailureIllegalPeerState> for DeletePeerDescriptorError {
+pub mod snapshot_manager {
+    use std::time::{Duration, SystemTime};
+
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::snapshot;
+    use opendut_types::snapshot::{SnapshotName, SnapshotResourceKind};
+
+    use crate::carl::snapshot::{CreateSnapshotError, DeleteSnapshotError, DiffSnapshotsError, ListSnapshotsError, RestoreSnapshotError};
+
+    tonic::include_proto!("opendut.carl.services.snapshot_manager");
+
+    impl From<snapshot::SnapshotId> for SnapshotId {
+        fn from(value: snapshot::SnapshotId) -> Self {
+            Self {
+                uuid: Some(value.0.into())
+            }
+        }
+    }
+
+    impl TryFrom<SnapshotId> for snapshot::SnapshotId {
         type Error = ConversionError;
-        fn try_from(failure: DeletePeerDescriptorFailureIllegalPeerState) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailureIllegalPeerState, DeletePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            let peer_name: PeerName = failure.peer_name
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
-                .try_into()?;
-            let actual_state: PeerState = failure.actual_state
-                .ok_or_else(|| ErrorBuilder::field_not_set("actual_state"))?
-                .try_into()?;
-            let required_states = failure.required_states.into_iter()
-                .map(proto::peer::PeerState::try_into)
-                .collect::<Result<_, _>>()?;
-            Ok(DeletePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states })
+
+        fn try_from(value: SnapshotId) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SnapshotId, snapshot::SnapshotId>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Self(uuid.into()))
         }
     }
 
-    impl TryFrom<DeletePeerDescriptorFailureInternal> for DeletePeerDescriptorError {
+    impl From<crate::carl::snapshot::SnapshotInfo> for SnapshotInfo {
+        fn from(value: crate::carl::snapshot::SnapshotInfo) -> Self {
+            let created_at_unix_millis = value.created_at.duration_since(SystemTime::UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default();
+
+            Self {
+                id: Some(value.id.into()),
+                name: value.name.into(),
+                created_at_unix_millis,
+                resource_count: value.resource_count as u64,
+            }
+        }
+    }
+
+    impl TryFrom<SnapshotInfo> for crate::carl::snapshot::SnapshotInfo {
         type Error = ConversionError;
-        fn try_from(failure: DeletePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<DeletePeerDescriptorFailureInternal, DeletePeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+
+        fn try_from(value: SnapshotInfo) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SnapshotInfo, crate::carl::snapshot::SnapshotInfo>;
+
+            let id: snapshot::SnapshotId = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
                 .try_into()?;
-            let peer_name: Option<PeerName> = failure.peer_name
-                .map(TryInto::try_into)
-                .transpose()?;
-            Ok(DeletePeerDescriptorError::Internal { peer_id, peer_name, cause: failure.cause })
+            let name = SnapshotName::try_from(value.name)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+            Ok(Self {
+                id,
+                name,
+                created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.created_at_unix_millis),
+                resource_count: value.resource_count as usize,
+            })
         }
     }
 
-    impl From<crate::carl::peer::IllegalDevicesError> for IllegalDevicesError {
-        fn from(error: crate::carl::peer::IllegalDevicesError) -> Self {
-            match error {
-                crate::carl::peer::IllegalDevicesError::DeviceAlreadyExists { device_id } => {
-                    IllegalDevicesError {
-                        error: Some(illegal_devices_error::Error::DeviceAlreadyExists(IllegalDevicesErrorDeviceAlreadyExists {
-                            device_id: Some(device_id.into())
-                        })),
-                    }
-                }
+    impl From<snapshot::SnapshotResourceReference> for SnapshotResourceReference {
+        fn from(value: snapshot::SnapshotResourceReference) -> Self {
+            let kind = match value.kind {
+                SnapshotResourceKind::Peer => snapshot_resource_reference::Kind::Peer(SnapshotResourceKindPeer {}),
+                SnapshotResourceKind::PeerGroup => snapshot_resource_reference::Kind::PeerGroup(SnapshotResourceKindPeerGroup {}),
+                SnapshotResourceKind::ClusterConfiguration => snapshot_resource_reference::Kind::ClusterConfiguration(SnapshotResourceKindClusterConfiguration {}),
+                SnapshotResourceKind::ClusterDeployment => snapshot_resource_reference::Kind::ClusterDeployment(SnapshotResourceKindClusterDeployment {}),
+            };
+            Self {
+                kind: Some(kind),
+                id: Some(value.id.into()),
             }
         }
     }
 
-    impl TryFrom<IllegalDevicesError> for crate::carl::peer::IllegalDevicesError {
+    impl TryFrom<SnapshotResourceReference> for snapshot::SnapshotResourceReference {
         type Error = ConversionError;
-        fn try_from(error: IllegalDevicesError) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<IllegalDevicesError, crate::carl::peer::IllegalDevicesError>;
-            let inner = error.error
-                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
-            match inner {
-                illegal_devices_error::Error::DeviceAlreadyExists(error) => {
-                    error.try_into()
-                }
+
+        fn try_from(value: SnapshotResourceReference) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SnapshotResourceReference, snapshot::SnapshotResourceReference>;
+
+            let kind = match value.kind.ok_or_else(|| ErrorBuilder::field_not_set("kind"))? {
+                snapshot_resource_reference::Kind::Peer(_) => SnapshotResourceKind::Peer,
+                snapshot_resource_reference::Kind::PeerGroup(_) => SnapshotResourceKind::PeerGroup,
+                snapshot_resource_reference::Kind::ClusterConfiguration(_) => SnapshotResourceKind::ClusterConfiguration,
+                snapshot_resource_reference::Kind::ClusterDeployment(_) => SnapshotResourceKind::ClusterDeployment,
+            };
+            let id = value.id
+                .ok_or_else(|| ErrorBuilder::field_not_set("id"))?
+                .into();
+
+            Ok(Self { kind, id })
+        }
+    }
+
+    impl From<snapshot::SnapshotDiffEntry> for SnapshotDiffEntry {
+        fn from(value: snapshot::SnapshotDiffEntry) -> Self {
+            let change = match value.change {
+                snapshot::SnapshotChange::Added => snapshot_diff_entry::Change::Added(SnapshotChangeAdded {}),
+                snapshot::SnapshotChange::Removed => snapshot_diff_entry::Change::Removed(SnapshotChangeRemoved {}),
+                snapshot::SnapshotChange::Changed => snapshot_diff_entry::Change::Changed(SnapshotChangeChanged {}),
+            };
+            Self {
+                resource: Some(value.resource.into()),
+                name: value.name,
+                change: Some(change),
             }
         }
     }
 
-    impl TryFrom<IllegalDevicesErrorDeviceAlreadyExists> for crate::carl::peer::IllegalDevicesError {
+    impl TryFrom<SnapshotDiffEntry> for snapshot::SnapshotDiffEntry {
         type Error = ConversionError;
-        fn try_from(error: IllegalDevicesErrorDeviceAlreadyExists) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<IllegalDevicesErrorDeviceAlreadyExists, crate::carl::peer::IllegalDevicesError>;
-            let device_id: DeviceId = error.device_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+
+        fn try_from(value: SnapshotDiffEntry) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<SnapshotDiffEntry, snapshot::SnapshotDiffEntry>;
+
+            let resource: snapshot::SnapshotResourceReference = value.resource
+                .ok_or_else(|| ErrorBuilder::field_not_set("resource"))?
                 .try_into()?;
-            Ok(crate::carl::peer::IllegalDevicesError::DeviceAlreadyExists { device_id })
+            let change = match value.change.ok_or_else(|| ErrorBuilder::field_not_set("change"))? {
+                snapshot_diff_entry::Change::Added(_) => snapshot::SnapshotChange::Added,
+                snapshot_diff_entry::Change::Removed(_) => snapshot::SnapshotChange::Removed,
+                snapshot_diff_entry::Change::Changed(_) => snapshot::SnapshotChange::Changed,
+            };
+
+            Ok(Self { resource, name: value.name, change })
         }
     }
 
-    impl From<GetPeerDescriptorError> for GetPeerDescriptorFailure {
-        fn from(error: GetPeerDescriptorError) -> Self {
+    impl From<CreateSnapshotError> for CreateSnapshotFailure {
+        fn from(error: CreateSnapshotError) -> Self {
             let proto_error = match error {
-                GetPeerDescriptorError::PeerNotFound { peer_id } => {
-                    get_peer_descriptor_failure::Error::PeerNotFound(GetPeerDescriptorFailurePeerNotFound {
-                        peer_id: Some(peer_id.into()),
+                CreateSnapshotError::SnapshotNameAlreadyExists { snapshot_name } => {
+                    create_snapshot_failure::Error::SnapshotNameAlreadyExists(CreateSnapshotFailureSnapshotNameAlreadyExists {
+                        snapshot_name: snapshot_name.into(),
                     })
                 }
-                GetPeerDescriptorError::Internal { peer_id, cause } => {
-                    get_peer_descriptor_failure::Error::Internal(GetPeerDescriptorFailureInternal {
-                        peer_id: Some(peer_id.into()),
-                        cause
+                CreateSnapshotError::Internal { snapshot_name, cause } => {
+                    create_snapshot_failure::Error::Internal(CreateSnapshotFailureInternal {
+                        snapshot_name: snapshot_name.into(),
+                        cause,
                     })
                 }
             };
-            GetPeerDescriptorFailure {
+            CreateSnapshotFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<GetPeerDescriptorFailure> for GetPeerDescriptorError {
+    impl TryFrom<CreateSnapshotFailure> for CreateSnapshotError {
         type Error = ConversionError;
-        fn try_from(failure: GetPeerDescriptorFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailure, GetPeerDescriptorError>;
+        fn try_from(failure: CreateSnapshotFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateSnapshotFailure, CreateSnapshotError>;
+
+            let snapshot_name = |name: String| SnapshotName::try_from(name)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()));
+
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                get_peer_descriptor_failure::Error::PeerNotFound(error) => {
-                    error.try_into()?
+                create_snapshot_failure::Error::SnapshotNameAlreadyExists(error) => {
+                    CreateSnapshotError::SnapshotNameAlreadyExists { snapshot_name: snapshot_name(error.snapshot_name)? }
                 }
-                get_peer_descriptor_failure::Error::Internal(error) => {
-                    error.try_into()?
+                create_snapshot_failure::Error::Internal(error) => {
+                    CreateSnapshotError::Internal { snapshot_name: snapshot_name(error.snapshot_name)?, cause: error.cause }
                 }
             };
             Ok(error)
         }
     }
 
-    impl TryFrom<GetPeerDescriptorFailurePeerNotFound> for GetPeerDescriptorError {
-        type Error = ConversionError;
-        fn try_from(failure: GetPeerDescriptorFailurePeerNotFound) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailurePeerNotFound, GetPeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            Ok(GetPeerDescriptorError::PeerNotFound { peer_id })
+    impl From<ListSnapshotsError> for ListSnapshotsFailure {
+        fn from(error: ListSnapshotsError) -> Self {
+            let proto_error = match error {
+                ListSnapshotsError::Internal { cause } => {
+                    list_snapshots_failure::Error::Internal(ListSnapshotsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListSnapshotsFailure {
+                error: Some(proto_error)
+            }
         }
     }
 
-    impl TryFrom<GetPeerDescriptorFailureInternal> for GetPeerDescriptorError {
+    impl TryFrom<ListSnapshotsFailure> for ListSnapshotsError {
         type Error = ConversionError;
-        fn try_from(failure: GetPeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerDescriptorFailureInternal, GetPeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            Ok(GetPeerDescriptorError::Internal{ peer_id, cause: failure.cause})
+        fn try_from(failure: ListSnapshotsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListSnapshotsFailure, ListSnapshotsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_snapshots_failure::Error::Internal(error) => {
+                    ListSnapshotsError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
         }
     }
 
-    impl From<ListPeerDescriptorsError> for ListPeerDescriptorsFailure {
-        fn from(error: ListPeerDescriptorsError) -> Self {
+    impl From<DiffSnapshotsError> for DiffSnapshotsFailure {
+        fn from(error: DiffSnapshotsError) -> Self {
             let proto_error = match error {
-                ListPeerDescriptorsError::Internal { cause } => {
-                    list_peer_descriptors_failure::Error::Internal(ListPeerDescriptorsFailureInternal {
+                DiffSnapshotsError::SnapshotNotFound { snapshot_id } => {
+                    diff_snapshots_failure::Error::SnapshotNotFound(DiffSnapshotsFailureSnapshotNotFound {
+                        snapshot_id: Some(snapshot_id.into()),
+                    })
+                }
+                DiffSnapshotsError::Internal { cause } => {
+                    diff_snapshots_failure::Error::Internal(DiffSnapshotsFailureInternal {
                         cause
                     })
                 }
             };
-            ListPeerDescriptorsFailure {
+            DiffSnapshotsFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<ListPeerDescriptorsFailure> for ListPeerDescriptorsError {
+    impl TryFrom<DiffSnapshotsFailure> for DiffSnapshotsError {
         type Error = ConversionError;
-        fn try_from(failure: ListPeerDescriptorsFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<ListPeerDescriptorsFailure, ListPeerDescriptorsError>;
+        fn try_from(failure: DiffSnapshotsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiffSnapshotsFailure, DiffSnapshotsError>;
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                list_peer_descriptors_failure::Error::Internal(error) => {
-                    error.try_into()?
+                diff_snapshots_failure::Error::SnapshotNotFound(error) => {
+                    let snapshot_id: snapshot::SnapshotId = error.snapshot_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("snapshot_id"))?
+                        .try_into()?;
+                    DiffSnapshotsError::SnapshotNotFound { snapshot_id }
+                }
+                diff_snapshots_failure::Error::Internal(error) => {
+                    DiffSnapshotsError::Internal { cause: error.cause }
                 }
             };
             Ok(error)
         }
     }
 
-    impl TryFrom<ListPeerDescriptorsFailureInternal> for ListPeerDescriptorsError {
-        type Error = ConversionError;
-        fn try_from(failure: ListPeerDescriptorsFailureInternal) -> Result<Self, Self::Error> {
-            // type ErrorBuilder = ConversionErrorBuilder<ListPeersFailureInternal, ListPeersError>;
-            Ok(ListPeerDescriptorsError::Internal{ cause: failure.cause})
-        }
-    }
-
-    impl From<GetPeerStateError> for GetPeerStateFailure {
-        fn from(error: GetPeerStateError) -> Self {
+    impl From<RestoreSnapshotError> for RestoreSnapshotFailure {
+        fn from(error: RestoreSnapshotError) -> Self {
             let proto_error = match error {
-                GetPeerStateError::PeerNotFound { peer_id } => {
-                    get_peer_state_failure::Error::PeerNotFound(GetPeerStateFailurePeerNotFound {
-                        peer_id: Some(peer_id.into()),
+                RestoreSnapshotError::SnapshotNotFound { snapshot_id } => {
+                    restore_snapshot_failure::Error::SnapshotNotFound(RestoreSnapshotFailureSnapshotNotFound {
+                        snapshot_id: Some(snapshot_id.into()),
                     })
                 }
-                GetPeerStateError::Internal { peer_id, cause } => {
-                    get_peer_state_failure::Error::Internal(GetPeerStateFailureInternal {
-                        peer_id: Some(peer_id.into()),
-                        cause
+                RestoreSnapshotError::ResourcesNotInSnapshot { snapshot_id, resources } => {
+                    restore_snapshot_failure::Error::ResourcesNotInSnapshot(RestoreSnapshotFailureResourcesNotInSnapshot {
+                        snapshot_id: Some(snapshot_id.into()),
+                        resources: resources.into_iter().map(Into::into).collect(),
+                    })
+                }
+                RestoreSnapshotError::Internal { snapshot_id, cause } => {
+                    restore_snapshot_failure::Error::Internal(RestoreSnapshotFailureInternal {
+                        snapshot_id: Some(snapshot_id.into()),
+                        cause,
                     })
                 }
             };
-            GetPeerStateFailure {
+            RestoreSnapshotFailure {
                 error: Some(proto_error)
             }
         }
     }
 
-    impl TryFrom<GetPeerStateFailurePeerNotFound> for GetPeerStateError {
+    impl TryFrom<RestoreSnapshotFailure> for RestoreSnapshotError {
         type Error = ConversionError;
-        fn try_from(failure: GetPeerStateFailurePeerNotFound) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailurePeerNotFound, GetPeerDescriptorError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            Ok(GetPeerStateError::PeerNotFound { peer_id })
+        fn try_from(failure: RestoreSnapshotFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RestoreSnapshotFailure, RestoreSnapshotError>;
+
+            fn extract_snapshot_id(snapshot_id: Option<SnapshotId>) -> Result<snapshot::SnapshotId, ConversionError> {
+                snapshot_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("snapshot_id"))?
+                    .try_into()
+            }
+
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                restore_snapshot_failure::Error::SnapshotNotFound(error) => {
+                    RestoreSnapshotError::SnapshotNotFound { snapshot_id: extract_snapshot_id(error.snapshot_id)? }
+                }
+                restore_snapshot_failure::Error::ResourcesNotInSnapshot(error) => {
+                    let resources = error.resources.into_iter()
+                        .map(snapshot::SnapshotResourceReference::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    RestoreSnapshotError::ResourcesNotInSnapshot { snapshot_id: extract_snapshot_id(error.snapshot_id)?, resources }
+                }
+                restore_snapshot_failure::Error::Internal(error) => {
+                    RestoreSnapshotError::Internal { snapshot_id: extract_snapshot_id(error.snapshot_id)?, cause: error.cause }
+                }
+            };
+            Ok(error)
         }
     }
 
-    impl TryFrom<GetPeerStateFailureInternal> for GetPeerStateError {
-        type Error = ConversionError;
-        fn try_from(failure: GetPeerStateFailureInternal) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailureInternal, GetPeerStateError>;
-            let peer_id: PeerId = failure.peer_id
-                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
-                .try_into()?;
-            Ok(GetPeerStateError::Internal{ peer_id, cause: failure.cause})
+    impl From<DeleteSnapshotError> for DeleteSnapshotFailure {
+        fn from(error: DeleteSnapshotError) -> Self {
+            let proto_error = match error {
+                DeleteSnapshotError::SnapshotNotFound { snapshot_id } => {
+                    delete_snapshot_failure::Error::SnapshotNotFound(DeleteSnapshotFailureSnapshotNotFound {
+                        snapshot_id: Some(snapshot_id.into()),
+                    })
+                }
+                DeleteSnapshotError::Internal { snapshot_id, cause } => {
+                    delete_snapshot_failure::Error::Internal(DeleteSnapshotFailureInternal {
+                        snapshot_id: Some(snapshot_id.into()),
+                        cause,
+                    })
+                }
+            };
+            DeleteSnapshotFailure {
+                error: Some(proto_error)
+            }
         }
     }
 
-    impl TryFrom<GetPeerStateFailure> for GetPeerStateError {
+    impl TryFrom<DeleteSnapshotFailure> for DeleteSnapshotError {
         type Error = ConversionError;
-        fn try_from(failure: GetPeerStateFailure) -> Result<Self, Self::Error> {
-            type ErrorBuilder = ConversionErrorBuilder<GetPeerStateFailure, GetPeerStateError>;
+        fn try_from(failure: DeleteSnapshotFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteSnapshotFailure, DeleteSnapshotError>;
+
+            fn extract_snapshot_id(snapshot_id: Option<SnapshotId>) -> Result<snapshot::SnapshotId, ConversionError> {
+                snapshot_id
+                    .ok_or_else(|| ErrorBuilder::field_not_set("snapshot_id"))?
+                    .try_into()
+            }
+
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
-                get_peer_state_failure::Error::PeerNotFound(error) => {
-                    error.try_into()?
+                delete_snapshot_failure::Error::SnapshotNotFound(error) => {
+                    DeleteSnapshotError::SnapshotNotFound { snapshot_id: extract_snapshot_id(error.snapshot_id)? }
                 }
-                get_peer_state_failure::Error::Internal(error) => {
-                    error.try_into()?
+                delete_snapshot_failure::Error::Internal(error) => {
+                    DeleteSnapshotError::Internal { snapshot_id: extract_snapshot_id(error.snapshot_id)?, cause: error.cause }
                 }
             };
             Ok(error)
         }
     }
 }
-
-pub mod peer_messaging_broker {
-    tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
-}
@@ -1,11 +1,19 @@
+use std::ops::Not;
+
+use crate::actions::peer_groups::resolve_peer_group_devices;
+use crate::policy::PolicyEngineRef;
 use crate::resources::manager::ResourcesManagerRef;
 use opendut_carl_api::carl::cluster::CreateClusterConfigurationError;
+use opendut_types::cluster;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::PeerDescriptor;
 use tracing::{debug, error, info};
 use crate::resources::storage::ResourcesStorageApi;
 
 pub struct CreateClusterConfigurationParams {
     pub resources_manager: ResourcesManagerRef,
+    pub policy_engine: PolicyEngineRef,
     pub cluster_configuration: ClusterConfiguration,
 }
 
@@ -20,8 +28,37 @@ pub async fn create_cluster_configuration(params: CreateClusterConfigurationPara
 
         debug!("Creating cluster configuration '{cluster_name}' <{cluster_id}>.");
 
+        { //evaluate policies on the devices, which the cluster will contain after resolving its peer groups
+            let mut resolved_cluster_configuration = Clone::clone(&params.cluster_configuration);
+            let (peer_group_devices, hierarchy_nodes, peers) = resources_manager.resources(|resources| {
+                let peer_group_devices = resolve_peer_group_devices(resources, &resolved_cluster_configuration.peer_groups)?;
+                Ok((peer_group_devices, resources.list::<HierarchyNode>()?, resources.list::<PeerDescriptor>()?))
+            }).await
+            .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })?;
+            resolved_cluster_configuration.devices.extend(peer_group_devices.unwrap_or_default()); //missing peer groups are reported when storing
+
+            let mut violations = params.policy_engine.evaluate_cluster_configuration(&resolved_cluster_configuration).await
+                .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })?;
+            violations.extend(params.policy_engine.evaluate_cluster_placement(&resolved_cluster_configuration, &hierarchy_nodes, &peers));
+            if violations.is_empty().not() {
+                return Err(CreateClusterConfigurationError::PolicyViolation { cluster_id, cluster_name, violations });
+            }
+        }
+
         resources_manager.resources_mut(|resources| {
-            resources.insert(cluster_id, params.cluster_configuration)
+            let mut cluster_configuration = params.cluster_configuration;
+
+            let existing_clusters = resources.list::<ClusterConfiguration>()
+                .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })?;
+            cluster::validate_dependencies(&cluster_configuration, &existing_clusters)
+                .map_err(|cause| CreateClusterConfigurationError::InvalidDependencies { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })?;
+
+            let peer_group_devices = resolve_peer_group_devices(resources, &cluster_configuration.peer_groups)
+                .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })?
+                .map_err(|peer_group_id| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: format!("Referenced PeerGroup <{peer_group_id}> does not exist.") })?;
+            cluster_configuration.devices.extend(peer_group_devices);
+
+            resources.insert(cluster_id, cluster_configuration)
                 .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })
         }).await
         .map_err(|cause| CreateClusterConfigurationError::Internal { cluster_id, cluster_name: cluster_name.clone(), cause: cause.to_string() })??;
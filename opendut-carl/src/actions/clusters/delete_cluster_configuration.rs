@@ -1,6 +1,9 @@
 use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
 use opendut_carl_api::carl::cluster::DeleteClusterConfigurationError;
+use opendut_types::cluster;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use std::ops::Not;
 use tracing::{debug, error, info};
 
 pub struct DeleteClusterConfigurationParams {
@@ -18,9 +21,24 @@ pub async fn delete_cluster_configuration(params: DeleteClusterConfigurationPara
 
         debug!("Deleting cluster configuration <{cluster_id}>.");
 
-        let cluster_configuration = resources_manager.remove::<ClusterConfiguration>(cluster_id).await
-            .map_err(|cause| DeleteClusterConfigurationError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?
-            .ok_or_else(|| DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })?;
+        let cluster_configuration = resources_manager.resources_mut(|resources| {
+            let clusters = resources.list::<ClusterConfiguration>()
+                .map_err(|cause| DeleteClusterConfigurationError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?;
+
+            let dependents = cluster::dependents_of(cluster_id, &clusters);
+            if dependents.is_empty().not() {
+                let cluster_name = clusters.into_iter()
+                    .find(|cluster| cluster.id == cluster_id)
+                    .map(|cluster| cluster.name)
+                    .ok_or(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })?;
+                return Err(DeleteClusterConfigurationError::ReferencedAsDependency { cluster_id, cluster_name, dependents });
+            }
+
+            resources.remove::<ClusterConfiguration>(cluster_id)
+                .map_err(|cause| DeleteClusterConfigurationError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?
+                .ok_or(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
+        }).await
+        .map_err(|cause| DeleteClusterConfigurationError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })??;
 
         let cluster_name = Clone::clone(&cluster_configuration.name);
 
@@ -3,7 +3,7 @@ use crate::actions::UnassignClusterParams;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 use opendut_carl_api::carl::cluster::DeleteClusterDeploymentError;
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use std::ops::Not;
 use std::sync::Arc;
@@ -24,6 +24,9 @@ pub async fn delete_cluster_deployment(params: DeleteClusterDeploymentParams) ->
 
         let (deployment, cluster) = resources_manager
             .resources_mut(|resources| {
+                resources.remove::<ClusterDeploymentStatus>(cluster_id)
+                    .map_err(|cause| DeleteClusterDeploymentError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?;
+
                 resources.remove::<ClusterDeployment>(cluster_id)
                     .map_err(|cause| DeleteClusterDeploymentError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?
                     .map(|deployment| {
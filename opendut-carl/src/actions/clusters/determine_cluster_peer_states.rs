@@ -104,6 +104,9 @@ mod tests {
                     .chain(peer_b.topology.devices.iter())
                     .map(|device| device.id)
             ),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
         };
         resources_manager.insert(cluster.id, cluster.clone()).await?;
 
@@ -172,6 +175,10 @@ mod tests {
             executors: ExecutorDescriptors {
                 executors: vec![],
             },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         })
     }
 }
@@ -3,4 +3,5 @@ pub mod delete_cluster_configuration;
 pub mod delete_cluster_deployment;
 pub mod determine_cluster_peers;
 pub mod determine_cluster_peer_states;
+pub mod simulate_cluster_deployment;
 pub mod store_cluster_deployment;
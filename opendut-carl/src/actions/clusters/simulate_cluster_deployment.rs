@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Not;
+
+use crate::actions::peer_groups::resolve_peer_group_devices;
+use crate::cluster::manager::{determine_member_interface_mapping, selectable_devices};
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::policy::PolicyEngineRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::cluster::{ClusterDeploymentSimulation, SimulateClusterDeploymentError, SimulatedClusterMember};
+use opendut_types::cluster::{bind_devices, ClusterConfiguration};
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::state::{PeerState, PeerUpState};
+use opendut_types::peer::PeerDescriptor;
+use tracing::{debug, error};
+
+pub struct SimulateClusterDeploymentParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub policy_engine: PolicyEngineRef,
+    pub cluster_configuration: ClusterConfiguration,
+}
+
+/// Evaluates a hypothetical ClusterConfiguration against the current resources, like creating and deploying it would, without persisting anything.
+/// Problems, which would prevent the deployment, are collected into the result rather than returned as error, so that all of them can be shown at once.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn simulate_cluster_deployment(params: SimulateClusterDeploymentParams) -> Result<ClusterDeploymentSimulation, SimulateClusterDeploymentError> {
+
+    async fn inner(params: SimulateClusterDeploymentParams) -> Result<ClusterDeploymentSimulation, SimulateClusterDeploymentError> {
+
+        let SimulateClusterDeploymentParams { resources_manager, policy_engine, mut cluster_configuration } = params;
+        let cluster_id = cluster_configuration.id;
+
+        debug!("Simulating deployment of cluster '{}' <{cluster_id}>.", cluster_configuration.name);
+
+        let (peer_group_devices, hierarchy_nodes, peers, peer_states, candidates) = resources_manager.resources(|resources| {
+            let peer_group_devices = resolve_peer_group_devices(resources, &cluster_configuration.peer_groups)?;
+            let peers = resources.list::<PeerDescriptor>()?;
+            let peer_states = peers.iter()
+                .map(|peer| Ok::<_, PersistenceError>((peer.id, resources.get::<PeerState>(peer.id)?.unwrap_or_default())))
+                .collect::<PersistenceResult<HashMap<_, _>>>()?;
+            let candidates = selectable_devices(resources, &cluster_configuration.devices)?;
+            Ok((peer_group_devices, resources.list::<HierarchyNode>()?, peers, peer_states, candidates))
+        }).await
+        .map_err(|cause| SimulateClusterDeploymentError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        let mut problems = Vec::new();
+
+        match peer_group_devices {
+            Ok(peer_group_devices) => cluster_configuration.devices.extend(peer_group_devices),
+            Err(peer_group_id) => problems.push(format!("Referenced PeerGroup <{peer_group_id}> does not exist.")),
+        }
+
+        let mut violations = policy_engine.evaluate_cluster_configuration(&cluster_configuration).await
+            .map_err(|cause| SimulateClusterDeploymentError::Internal { cluster_id, cause: cause.to_string() })?;
+        violations.extend(policy_engine.evaluate_cluster_placement(&cluster_configuration, &hierarchy_nodes, &peers));
+
+        let bound_devices = match bind_devices(&cluster_configuration.device_selectors, &candidates, &cluster_configuration.devices) {
+            Ok(bound_devices) => bound_devices,
+            Err(cause) => {
+                problems.push(cause.to_string());
+                HashSet::new()
+            }
+        };
+
+        let mut cluster_devices = cluster_configuration.devices.clone();
+        cluster_devices.extend(&bound_devices);
+
+        let leader = cluster_configuration.leader;
+        if peers.iter().any(|peer| peer.id == leader).not() {
+            problems.push(format!("Peer <{leader}> designated as leader does not exist."));
+        }
+
+        let member_interface_mapping = determine_member_interface_mapping(cluster_devices.clone(), peers.clone(), leader)
+            .unwrap_or_else(|cause| {
+                problems.push(cause.to_string());
+                HashMap::new()
+            });
+
+        let mut members = Vec::new();
+        for (peer_id, device_interfaces) in member_interface_mapping {
+            let Some(peer) = peers.iter().find(|peer| peer.id == peer_id) else {
+                continue; //missing leader is reported above
+            };
+
+            let vpn_address = match peer_states.get(&peer_id) {
+                Some(PeerState::Up { inner: PeerUpState::Available, remote_host }) => Some(*remote_host),
+                Some(PeerState::Up { inner: PeerUpState::Blocked(_), remote_host }) => {
+                    problems.push(format!("Peer '{}' <{peer_id}> is blocked by another cluster.", peer.name));
+                    Some(*remote_host)
+                }
+                Some(PeerState::Unreachable { remote_host, .. }) => {
+                    problems.push(format!("Peer '{}' <{peer_id}> is unreachable.", peer.name));
+                    Some(*remote_host)
+                }
+                _ => {
+                    problems.push(format!("Peer '{}' <{peer_id}> is down.", peer.name));
+                    None
+                }
+            };
+
+            members.push(SimulatedClusterMember {
+                peer_id,
+                peer_name: Clone::clone(&peer.name),
+                leader: peer_id == leader,
+                vpn_address,
+                device_interfaces: device_interfaces.into_iter().map(|interface| interface.name).collect(),
+                devices: peer.topology.devices.iter()
+                    .map(|device| device.id)
+                    .filter(|device| cluster_devices.contains(device))
+                    .collect(),
+                executors: peer.executors.executors.iter()
+                    .map(|executor| executor.id)
+                    .collect(),
+            });
+        }
+        members.sort_by_key(|member| member.peer_name.to_string());
+
+        Ok(ClusterDeploymentSimulation {
+            cluster_id,
+            violations,
+            problems,
+            members,
+            bound_devices: bound_devices.into_iter().collect(),
+        })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn should_report_down_peers_without_persisting_the_cluster_configuration() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer_a = generate_peer_descriptor()?;
+        resources_manager.insert(peer_a.id, peer_a.clone()).await?;
+        let remote_host = IpAddr::from_str("10.0.0.1")?;
+        resources_manager.insert(peer_a.id, PeerState::Up { inner: PeerUpState::Available, remote_host }).await?;
+
+        let peer_b = generate_peer_descriptor()?;
+        resources_manager.insert(peer_b.id, peer_b.clone()).await?;
+
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: peer_a.id,
+            devices: HashSet::from([peer_a.topology.devices[0].id, peer_b.topology.devices[0].id]),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
+        };
+
+        let result = simulate_cluster_deployment(SimulateClusterDeploymentParams {
+            resources_manager: resources_manager.clone(),
+            policy_engine: Default::default(),
+            cluster_configuration: cluster.clone(),
+        }).await?;
+
+        assert!(result.violations.is_empty());
+        assert_eq!(result.problems, vec![format!("Peer 'peer' <{}> is down.", peer_b.id)]);
+        assert_eq!(result.members.len(), 2);
+
+        let leader = result.members.iter().find(|member| member.peer_id == peer_a.id).unwrap();
+        assert!(leader.leader);
+        assert_eq!(leader.vpn_address, Some(remote_host));
+        assert_eq!(leader.devices, vec![peer_a.topology.devices[0].id]);
+
+        assert!(result.is_deployable().not());
+        assert_eq!(resources_manager.get::<ClusterConfiguration>(cluster.id).await?, None);
+
+        Ok(())
+    }
+}
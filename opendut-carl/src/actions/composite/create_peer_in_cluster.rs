@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+use std::ops::Not;
+
+use crate::actions::peer_groups::resolve_peer_group_devices;
+use crate::persistence::error::PersistenceError;
+use crate::policy::PolicyEngineRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use crate::vpn::Vpn;
+use opendut_carl_api::carl::peer::CreatePeerInClusterError;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::Project;
+use opendut_types::topology::DeviceId;
+use tracing::{debug, error, info, warn};
+
+pub struct CreatePeerInClusterParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub policy_engine: PolicyEngineRef,
+    pub vpn: Vpn,
+    pub peer_descriptor: PeerDescriptor,
+    pub cluster_id: ClusterId,
+    /// Devices of the new peer, which are added to the cluster.
+    pub devices: HashSet<DeviceId>,
+}
+
+/// Creates a new peer and adds some of its devices to an existing cluster in one transaction,
+/// so that no peer remains, if it could not be added to the cluster.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_peer_in_cluster(params: CreatePeerInClusterParams) -> Result<PeerId, CreatePeerInClusterError> {
+
+    async fn inner(params: CreatePeerInClusterParams) -> Result<PeerId, CreatePeerInClusterError> {
+
+        let CreatePeerInClusterParams { resources_manager, policy_engine, vpn, peer_descriptor, cluster_id, devices } = params;
+        let peer_id = peer_descriptor.id;
+        let peer_name = Clone::clone(&peer_descriptor.name);
+
+        debug!("Creating peer '{peer_name}' <{peer_id}> in cluster <{cluster_id}>.");
+
+        let internal_error = |cause: PersistenceError| CreatePeerInClusterError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() };
+
+        let mut unknown_devices = devices.iter()
+            .filter(|device_id| peer_descriptor.topology.devices.iter().any(|device| device.id == **device_id).not())
+            .cloned()
+            .collect::<Vec<_>>();
+        if unknown_devices.is_empty().not() {
+            unknown_devices.sort_by_key(ToString::to_string);
+            return Err(CreatePeerInClusterError::UnknownDevices { peer_id, peer_name, device_ids: unknown_devices });
+        }
+
+        { //evaluate policies on the peer, as well as on the cluster as it will be after adding the devices
+            let (cluster_configuration, peer_group_devices, hierarchy_nodes, mut peers, projects) = resources_manager.resources(|resources| {
+                let cluster_configuration = resources.get::<ClusterConfiguration>(cluster_id)?;
+                let peer_group_devices = match &cluster_configuration {
+                    Some(cluster_configuration) => resolve_peer_group_devices(resources, &cluster_configuration.peer_groups)?,
+                    None => Ok(HashSet::new()),
+                };
+                Ok((cluster_configuration, peer_group_devices, resources.list::<HierarchyNode>()?, resources.list::<PeerDescriptor>()?, resources.list::<Project>()?))
+            }).await
+            .map_err(internal_error)?;
+
+            let mut resolved_cluster_configuration = cluster_configuration
+                .ok_or_else(|| CreatePeerInClusterError::ClusterNotFound { peer_id, peer_name: Clone::clone(&peer_name), cluster_id })?;
+            resolved_cluster_configuration.devices.extend(peer_group_devices.unwrap_or_default()); //missing peer groups are reported when storing the cluster
+            resolved_cluster_configuration.devices.extend(devices.iter().cloned());
+            peers.push(Clone::clone(&peer_descriptor));
+
+            //the peer becomes part of the projects of the cluster
+            let projects = projects.into_iter()
+                .filter(|project| project.peers.contains(&peer_id) || project.clusters.contains(&cluster_id))
+                .collect::<Vec<_>>();
+
+            let mut violations = policy_engine.evaluate_peer_descriptor(&peer_descriptor, &projects).await
+                .map_err(|cause| CreatePeerInClusterError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+            violations.extend(policy_engine.evaluate_cluster_configuration(&resolved_cluster_configuration).await
+                .map_err(|cause| CreatePeerInClusterError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?);
+            violations.extend(policy_engine.evaluate_cluster_placement(&resolved_cluster_configuration, &hierarchy_nodes, &peers));
+            if violations.is_empty().not() {
+                return Err(CreatePeerInClusterError::PolicyViolation { peer_id, peer_name, violations });
+            }
+        }
+
+        if let Vpn::Enabled { vpn_client } = &vpn {
+            debug!("Creating VPN peer <{peer_id}>.");
+            vpn_client.create_peer(peer_id).await
+                .map_err(|cause| CreatePeerInClusterError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+            info!("Successfully created VPN peer <{peer_id}>.");
+        } else {
+            warn!("VPN disabled. Skipping VPN peer creation!");
+        }
+
+        let transaction_result = resources_manager.resources_mut(|resources| {
+            //check everything before modifying anything, as the in-memory storage does not roll back
+            if resources.get::<PeerDescriptor>(peer_id).map_err(internal_error)?.is_some() {
+                return Err(CreatePeerInClusterError::PeerAlreadyExists { peer_id, peer_name: Clone::clone(&peer_name) });
+            }
+            let mut cluster_configuration = resources.get::<ClusterConfiguration>(cluster_id).map_err(internal_error)?
+                .ok_or_else(|| CreatePeerInClusterError::ClusterNotFound { peer_id, peer_name: Clone::clone(&peer_name), cluster_id })?;
+            if resources.get::<ClusterDeployment>(cluster_id).map_err(internal_error)?.is_some() {
+                return Err(CreatePeerInClusterError::ClusterDeployed { peer_id, peer_name: Clone::clone(&peer_name), cluster_id, cluster_name: cluster_configuration.name });
+            }
+
+            resources.insert(peer_id, peer_descriptor).map_err(internal_error)?;
+
+            cluster_configuration.devices.extend(devices);
+            resources.insert(cluster_id, cluster_configuration).map_err(internal_error)?;
+
+            Ok(peer_id)
+        }).await;
+        let result = match transaction_result {
+            Ok(result) => result,
+            Err(cause) => Err(internal_error(cause)),
+        };
+
+        if result.is_err() { //undo creating peer in VPN Management server, as the peer was not stored
+            if let Vpn::Enabled { vpn_client } = vpn {
+                debug!("Deleting previously created VPN peer <{peer_id}>, as the peer could not be created in cluster <{cluster_id}>.");
+                match vpn_client.delete_peer(peer_id).await {
+                    Ok(()) => info!("Successfully deleted previously created VPN peer <{peer_id}>."),
+                    Err(cause) => error!("Failed to delete previously created VPN peer <{peer_id}>: {cause}\n  Cannot recover automatically. Please remove the peer from the VPN management server manually."),
+                }
+            }
+        } else {
+            info!("Successfully created peer '{peer_name}' <{peer_id}> in cluster <{cluster_id}>.");
+        }
+
+        result
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::policy::{PolicyEngine, PolicyOptions};
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use opendut_types::cluster::ClusterName;
+    use regex::Regex;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    async fn store_cluster(resources_manager: &ResourcesManagerRef, fixture: &Fixture) -> anyhow::Result<ClusterConfiguration> {
+        let cluster_configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("MyCluster")?,
+            leader: fixture.peer_a_id,
+            devices: HashSet::new(),
+            peer_groups: HashSet::new(),
+            device_selectors: vec![],
+            dependencies: vec![],
+        };
+        resources_manager.insert(cluster_configuration.id, Clone::clone(&cluster_configuration)).await?;
+        Ok(cluster_configuration)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_create_peer_and_add_its_devices_to_cluster(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_configuration = store_cluster(&resources_manager, &fixture).await?;
+
+        let peer_id = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: cluster_configuration.id,
+            devices: HashSet::from([fixture.peer_a_device_1]),
+        }).await?;
+
+        assert_that!(peer_id, eq(fixture.peer_a_id));
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?.as_ref(), some(eq(&fixture.peer_a_descriptor)));
+        let stored_cluster = resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await?;
+        assert_that!(stored_cluster.map(|cluster| cluster.devices), some(eq(&HashSet::from([fixture.peer_a_device_1]))));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_create_peer_when_cluster_is_deployed(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_configuration = store_cluster(&resources_manager, &fixture).await?;
+        resources_manager.insert(cluster_configuration.id, ClusterDeployment { id: cluster_configuration.id }).await?;
+
+        let result = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: cluster_configuration.id,
+            devices: HashSet::from([fixture.peer_a_device_1]),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(CreatePeerInClusterError::ClusterDeployed { .. })));
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?, none());
+        assert_that!(resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await?, some(eq(&cluster_configuration)));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_create_peer_when_cluster_does_not_exist(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let result = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: ClusterId::random(),
+            devices: HashSet::from([fixture.peer_a_device_1]),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(CreatePeerInClusterError::ClusterNotFound { .. })));
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?, none());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_reject_devices_which_do_not_belong_to_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_configuration = store_cluster(&resources_manager, &fixture).await?;
+        let foreign_device = DeviceId::random();
+
+        let result = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: cluster_configuration.id,
+            devices: HashSet::from([fixture.peer_a_device_1, foreign_device]),
+        }).await;
+
+        let Err(CreatePeerInClusterError::UnknownDevices { device_ids, .. }) = result else {
+            panic!("Expected unknown devices to be rejected, but got: {result:?}");
+        };
+        assert_that!(device_ids, elements_are![eq(&foreign_device)]);
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?, none());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_create_peer_when_cluster_would_violate_policies(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_configuration = store_cluster(&resources_manager, &fixture).await?;
+        let policy_engine = Arc::new(PolicyEngine::new(PolicyOptions {
+            max_devices_per_cluster: Some(1),
+            ..Default::default()
+        }));
+
+        let result = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine,
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: cluster_configuration.id,
+            devices: HashSet::from([fixture.peer_a_device_1, fixture.peer_a_device_2]),
+        }).await;
+
+        let Err(CreatePeerInClusterError::PolicyViolation { violations, .. }) = result else {
+            panic!("Expected cluster to be rejected due to a policy violation, but got: {result:?}");
+        };
+        assert_that!(violations[0].rule, eq("max-devices-per-cluster"));
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?, none());
+        assert_that!(resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await?, some(eq(&cluster_configuration)));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_reject_peer_violating_naming_convention(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_configuration = store_cluster(&resources_manager, &fixture).await?;
+        let policy_engine = Arc::new(PolicyEngine::new(PolicyOptions {
+            peer_name_pattern: Some(Regex::new("^(?:site-.*)$")?),
+            ..Default::default()
+        }));
+
+        let result = create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine,
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+            cluster_id: cluster_configuration.id,
+            devices: HashSet::from([fixture.peer_a_device_1]),
+        }).await;
+
+        assert_that!(result, err(matches_pattern!(CreatePeerInClusterError::PolicyViolation { .. })));
+        assert_that!(resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await?, some(eq(&cluster_configuration)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1 @@
+pub mod create_peer_in_cluster;
@@ -0,0 +1,237 @@
+use opendut_carl_api::carl::diagnostics::{CheckCompatibilityError, CompatibilityFinding, CompatibilityProblem};
+use opendut_types::cluster::{validate_dependencies, ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId, IllegalClusterDependency};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::provisioning::ProvisioningToken;
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
+use opendut_types::proto;
+use opendut_types::service_account::ServiceAccount;
+use opendut_types::snapshot::ResourceSnapshot;
+use tracing::{debug, error, info, warn};
+
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::document::Document;
+use crate::persistence::resources::Persistable;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct CheckCompatibilityParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub transform: bool,
+}
+
+/// Checks that all persisted resources can be decoded and satisfy the invariants of the running version of CARL,
+/// so that incompatibilities show up right after an upgrade, rather than when the resources are accessed later on.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn check_compatibility(params: CheckCompatibilityParams) -> Result<Vec<CompatibilityFinding>, CheckCompatibilityError> {
+
+    async fn inner(params: CheckCompatibilityParams) -> Result<Vec<CompatibilityFinding>, CheckCompatibilityError> {
+
+        let CheckCompatibilityParams { resources_manager, transform } = params;
+
+        debug!("Checking compatibility of persisted resources.");
+
+        let findings = resources_manager.resources(|resources| {
+            Ok(determine_findings(resources))
+        }).await
+        .map_err(|cause| CheckCompatibilityError::Internal { cause: cause.to_string() })?;
+
+        let findings = if transform {
+            resources_manager.resources_mut(|resources| {
+                findings.into_iter()
+                    .map(|(mut finding, transform)| {
+                        if let Some(transform) = transform {
+                            apply_transform(resources, &transform)?;
+                            finding.transformed = true;
+                        }
+                        Ok(finding)
+                    })
+                    .collect::<PersistenceResult<Vec<_>>>()
+            }).await
+            .map_err(|cause| CheckCompatibilityError::Internal { cause: cause.to_string() })?
+            .map_err(|cause| CheckCompatibilityError::Internal { cause: cause.to_string() })?
+        } else {
+            findings.into_iter()
+                .map(|(finding, _)| finding)
+                .collect()
+        };
+
+        if findings.is_empty() {
+            info!("Successfully checked compatibility of persisted resources. No incompatibilities found.");
+        } else {
+            for finding in &findings {
+                let transformed = if finding.transformed { " (transformed)" } else { "" };
+                warn!("Incompatible resource{transformed}: {finding}");
+            }
+            info!("Successfully checked compatibility of persisted resources. Found {} incompatibilities.", findings.len());
+        }
+
+        Ok(findings)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Change of a persisted resource, which resolves a finding without losing data the operator might still need.
+enum Transform {
+    RemoveDanglingClusterDependencies { cluster_id: ClusterId },
+}
+impl Transform {
+    fn description(&self) -> String {
+        match self {
+            Transform::RemoveDanglingClusterDependencies { cluster_id } =>
+                format!("Remove the dependencies of cluster <{cluster_id}> on itself and on clusters, which do not exist."),
+        }
+    }
+}
+
+fn determine_findings(resources: &impl ResourcesStorageApi) -> Vec<(CompatibilityFinding, Option<Transform>)> {
+    let mut findings = Vec::new();
+
+    decode_all::<ClusterDeployment>(resources, &mut findings);
+    decode_all::<ClusterDeploymentStatus>(resources, &mut findings);
+    decode_all::<HierarchyNode>(resources, &mut findings);
+    decode_all::<PeerGroup>(resources, &mut findings);
+    decode_all::<PeerSetupIssuance>(resources, &mut findings);
+    decode_all::<Project>(resources, &mut findings);
+    decode_all::<ProvisioningToken>(resources, &mut findings);
+    decode_all::<ResourceSnapshot>(resources, &mut findings);
+    decode_all::<ServiceAccount>(resources, &mut findings);
+
+    // The API converts resources from and to their protobuf representation, which validates them, e.g. their names.
+    for peer in decode_all::<PeerDescriptor>(resources, &mut findings) {
+        let peer_id = peer.id;
+        if let Err(cause) = PeerDescriptor::try_from(proto::peer::PeerDescriptor::from(peer)) {
+            findings.push((invariant_violated::<PeerDescriptor>(peer_id.to_string(), cause.to_string(), None), None));
+        }
+    }
+
+    let clusters = decode_all::<ClusterConfiguration>(resources, &mut findings);
+    for cluster in &clusters {
+        if let Err(cause) = ClusterConfiguration::try_from(proto::cluster::ClusterConfiguration::from(Clone::clone(cluster))) {
+            findings.push((invariant_violated::<ClusterConfiguration>(cluster.id.to_string(), cause.to_string(), None), None));
+        }
+        if let Err(cause) = validate_dependencies(cluster, &clusters) {
+            let transform = match cause {
+                IllegalClusterDependency::SelfReference { .. }
+                | IllegalClusterDependency::UnknownCluster { .. } => Some(Transform::RemoveDanglingClusterDependencies { cluster_id: cluster.id }),
+                IllegalClusterDependency::Cycle { .. } => None,
+            };
+            let finding = invariant_violated::<ClusterConfiguration>(cluster.id.to_string(), cause.to_string(), transform.as_ref());
+            findings.push((finding, transform));
+        }
+    }
+
+    findings
+}
+
+/// Decodes all resources of a kind, reporting a finding, if they cannot be decoded.
+fn decode_all<R>(resources: &impl ResourcesStorageApi, findings: &mut Vec<(CompatibilityFinding, Option<Transform>)>) -> Vec<R>
+where R: Persistable + Document + Clone {
+    match resources.list::<R>() {
+        Ok(resources) => resources,
+        Err(cause) => {
+            findings.push((CompatibilityFinding {
+                resource_kind: R::KIND.to_owned(),
+                resource_id: None,
+                problem: CompatibilityProblem::Undecodable { cause: cause.to_string() },
+                transform: None,
+                transformed: false,
+            }, None));
+            Vec::new()
+        }
+    }
+}
+
+fn invariant_violated<R: Document>(resource_id: String, cause: String, transform: Option<&Transform>) -> CompatibilityFinding {
+    CompatibilityFinding {
+        resource_kind: R::KIND.to_owned(),
+        resource_id: Some(resource_id),
+        problem: CompatibilityProblem::InvariantViolated { cause },
+        transform: transform.map(Transform::description),
+        transformed: false,
+    }
+}
+
+fn apply_transform(resources: &mut impl ResourcesStorageApi, transform: &Transform) -> PersistenceResult<()> {
+    match transform {
+        Transform::RemoveDanglingClusterDependencies { cluster_id } => {
+            let cluster_ids = resources.list_ids::<ClusterConfiguration>()?;
+            if let Some(mut cluster) = resources.get::<ClusterConfiguration>(*cluster_id)? {
+                cluster.dependencies.retain(|dependency_id| dependency_id != cluster_id && cluster_ids.contains(dependency_id));
+                resources.insert(*cluster_id, cluster)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::cluster::ClusterName;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_report_and_transform_dangling_cluster_dependencies(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let cluster_id = ClusterId::random();
+        let unknown_cluster_id = ClusterId::random();
+        let cluster = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("cluster")?,
+            leader: fixture.peer_a_id,
+            devices: HashSet::from([fixture.peer_a_device_1, fixture.peer_a_device_2]),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: vec![unknown_cluster_id],
+        };
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(fixture.peer_a_id, fixture.peer_a_descriptor)?;
+            resources.insert(cluster_id, cluster)
+        }).await??;
+
+        let findings = check_compatibility(CheckCompatibilityParams {
+            resources_manager: Arc::clone(&resources_manager),
+            transform: false,
+        }).await?;
+
+        assert_that!(findings, len(eq(1)));
+        assert_that!(findings[0].resource_kind, eq("cluster_configuration"));
+        assert_that!(findings[0].resource_id, some(eq(&cluster_id.to_string())));
+        assert_that!(findings[0].transform, some(anything()));
+        assert_that!(findings[0].transformed, eq(false));
+
+        let findings = check_compatibility(CheckCompatibilityParams {
+            resources_manager: Arc::clone(&resources_manager),
+            transform: true,
+        }).await?;
+
+        assert_that!(findings, len(eq(1)));
+        assert_that!(findings[0].transformed, eq(true));
+        let cluster = resources_manager.get::<ClusterConfiguration>(cluster_id).await?;
+        assert_that!(cluster.map(|cluster| cluster.dependencies), some(empty()));
+
+        let findings = check_compatibility(CheckCompatibilityParams {
+            resources_manager: Arc::clone(&resources_manager),
+            transform: false,
+        }).await?;
+        assert_that!(findings, empty());
+
+        Ok(())
+    }
+}
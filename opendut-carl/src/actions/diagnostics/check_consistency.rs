@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+use std::ops::Not;
+
+use opendut_carl_api::carl::diagnostics::{CheckConsistencyError, ConsistencyFinding, ConsistencyFindingKind};
+use opendut_types::cluster::{ClusterDeployment, ClusterId};
+use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info, warn};
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::persistence::error::PersistenceResult;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct CheckConsistencyParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub repair: bool,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn check_consistency(params: CheckConsistencyParams) -> Result<Vec<ConsistencyFinding>, CheckConsistencyError> {
+
+    async fn inner(params: CheckConsistencyParams) -> Result<Vec<ConsistencyFinding>, CheckConsistencyError> {
+
+        let CheckConsistencyParams { resources_manager, peer_messaging_broker, repair } = params;
+
+        debug!("Checking consistency of resources.");
+
+        let connected_peers = peer_messaging_broker.connected_peers().await;
+
+        let findings = resources_manager.resources(|resources| {
+            determine_findings(resources, &connected_peers)
+        }).await
+        .map_err(|cause| CheckConsistencyError::Internal { cause: cause.to_string() })?;
+
+        // Peers may have connected or disconnected while the resources were checked,
+        // so only report findings about connections which are still valid afterwards.
+        let connected_peers = peer_messaging_broker.connected_peers().await;
+        let findings = findings.into_iter()
+            .filter(|finding| match finding {
+                ConsistencyFindingKind::PeerUpWithoutConnection { peer_id } => connected_peers.contains(peer_id).not(),
+                ConsistencyFindingKind::ConnectionWithoutPeerUp { peer_id } => connected_peers.contains(peer_id),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        let findings = if repair {
+            resources_manager.resources_mut(|resources| {
+                findings.into_iter()
+                    .map(|kind| {
+                        let repaired = kind.is_repairable() && repair_finding(resources, &kind)?;
+                        Ok(ConsistencyFinding { kind, repaired })
+                    })
+                    .collect::<PersistenceResult<Vec<_>>>()
+            }).await
+            .map_err(|cause| CheckConsistencyError::Internal { cause: cause.to_string() })?
+            .map_err(|cause| CheckConsistencyError::Internal { cause: cause.to_string() })?
+        } else {
+            findings.into_iter()
+                .map(|kind| ConsistencyFinding { kind, repaired: false })
+                .collect()
+        };
+
+        if findings.is_empty() {
+            info!("Successfully checked consistency of resources. No inconsistencies found.");
+        } else {
+            for finding in &findings {
+                let repaired = if finding.repaired { " (repaired)" } else { "" };
+                warn!("Inconsistency in resources{repaired}: {}", finding.kind);
+            }
+            info!("Successfully checked consistency of resources. Found {} inconsistencies.", findings.len());
+        }
+
+        Ok(findings)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+fn determine_findings(resources: &impl ResourcesStorageApi, connected_peers: &HashSet<PeerId>) -> PersistenceResult<Vec<ConsistencyFindingKind>> {
+    let mut findings = Vec::new();
+
+    let peer_ids = resources.list_ids::<PeerDescriptor>()?
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    for peer_id in resources.list_ids::<PeerState>()? {
+        if peer_ids.contains(&peer_id).not() {
+            findings.push(ConsistencyFindingKind::OrphanedPeerState { peer_id });
+        }
+    }
+    for peer_id in resources.list_ids::<PeerConfiguration>()? {
+        if peer_ids.contains(&peer_id).not() {
+            findings.push(ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id });
+        }
+    }
+
+    let deployed_clusters = resources.list_ids::<ClusterDeployment>()?
+        .into_iter()
+        .collect::<HashSet<ClusterId>>();
+
+    for peer_id in resources.list_ids::<OldPeerConfiguration>()? {
+        if peer_ids.contains(&peer_id).not() {
+            findings.push(ConsistencyFindingKind::OrphanedOldPeerConfiguration { peer_id });
+            continue;
+        }
+        let cluster_assignment = resources.get::<OldPeerConfiguration>(peer_id)?
+            .and_then(|old_peer_configuration| old_peer_configuration.cluster_assignment);
+
+        if let Some(cluster_assignment) = cluster_assignment {
+            if deployed_clusters.contains(&cluster_assignment.id).not() {
+                findings.push(ConsistencyFindingKind::AssignedClusterNotDeployed { peer_id, cluster_id: cluster_assignment.id });
+            }
+        }
+    }
+
+    for &peer_id in &peer_ids {
+        let is_up = matches!(resources.get::<PeerState>(peer_id)?, Some(PeerState::Up { .. } | PeerState::Unreachable { .. }));
+        let is_connected = connected_peers.contains(&peer_id);
+
+        if is_up && is_connected.not() {
+            findings.push(ConsistencyFindingKind::PeerUpWithoutConnection { peer_id });
+        }
+    }
+    for &peer_id in connected_peers {
+        let is_up = matches!(resources.get::<PeerState>(peer_id)?, Some(PeerState::Up { .. } | PeerState::Unreachable { .. }));
+
+        if is_up.not() {
+            findings.push(ConsistencyFindingKind::ConnectionWithoutPeerUp { peer_id });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Returns whether the inconsistency was resolved.
+fn repair_finding(resources: &mut impl ResourcesStorageApi, finding: &ConsistencyFindingKind) -> PersistenceResult<bool> {
+    match finding {
+        ConsistencyFindingKind::OrphanedPeerState { peer_id } => {
+            resources.remove::<PeerState>(*peer_id)?;
+            Ok(true)
+        }
+        ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id } => {
+            resources.remove::<PeerConfiguration>(*peer_id)?;
+            Ok(true)
+        }
+        ConsistencyFindingKind::OrphanedOldPeerConfiguration { peer_id } => {
+            resources.remove::<OldPeerConfiguration>(*peer_id)?;
+            Ok(true)
+        }
+        ConsistencyFindingKind::PeerUpWithoutConnection { peer_id } => {
+            resources.insert(*peer_id, PeerState::Down)?;
+            Ok(true)
+        }
+        ConsistencyFindingKind::ConnectionWithoutPeerUp { .. }
+        | ConsistencyFindingKind::AssignedClusterNotDeployed { .. } => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::cluster::ClusterAssignment;
+    use opendut_types::peer::state::PeerUpState;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_report_and_repair_orphaned_volatile_resources(fixture: Fixture) -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let orphaned_peer_id = PeerId::random();
+        let up_peer_id = fixture.peer_a_id;
+        let unknown_cluster_id = ClusterId::random();
+
+        let remote_host = IpAddr::from_str("127.0.0.1")?;
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(up_peer_id, fixture.peer_a_descriptor)?;
+            resources.insert(up_peer_id, PeerState::Up { inner: PeerUpState::Available, remote_host })?;
+            resources.insert(up_peer_id, OldPeerConfiguration {
+                cluster_assignment: Some(ClusterAssignment { id: unknown_cluster_id, leader: up_peer_id, assignments: vec![] }),
+            })?;
+            resources.insert(orphaned_peer_id, PeerState::Down)?;
+            resources.insert(orphaned_peer_id, PeerConfiguration::default())
+        }).await??;
+
+        let findings = check_consistency(CheckConsistencyParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            repair: true,
+        }).await?;
+
+        assert_that!(findings, unordered_elements_are![
+            eq(&ConsistencyFinding { kind: ConsistencyFindingKind::OrphanedPeerState { peer_id: orphaned_peer_id }, repaired: true }),
+            eq(&ConsistencyFinding { kind: ConsistencyFindingKind::OrphanedPeerConfiguration { peer_id: orphaned_peer_id }, repaired: true }),
+            eq(&ConsistencyFinding { kind: ConsistencyFindingKind::PeerUpWithoutConnection { peer_id: up_peer_id }, repaired: true }),
+            eq(&ConsistencyFinding { kind: ConsistencyFindingKind::AssignedClusterNotDeployed { peer_id: up_peer_id, cluster_id: unknown_cluster_id }, repaired: false }),
+        ]);
+
+        assert_that!(resources_manager.get::<PeerState>(orphaned_peer_id).await?, none());
+        assert_that!(resources_manager.get::<PeerConfiguration>(orphaned_peer_id).await?, none());
+        assert_that!(resources_manager.get::<PeerState>(up_peer_id).await?, some(eq(&PeerState::Down)));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_modify_resources_without_repair(fixture: Fixture) -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let orphaned_peer_id = PeerId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(fixture.peer_a_id, fixture.peer_a_descriptor)?;
+            resources.insert(orphaned_peer_id, PeerState::Down)
+        }).await??;
+
+        let findings = check_consistency(CheckConsistencyParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            repair: false,
+        }).await?;
+
+        assert_that!(findings, elements_are![
+            eq(&ConsistencyFinding { kind: ConsistencyFindingKind::OrphanedPeerState { peer_id: orphaned_peer_id }, repaired: false }),
+        ]);
+        assert_that!(resources_manager.get::<PeerState>(orphaned_peer_id).await?, some(eq(&PeerState::Down)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod check_compatibility;
+pub mod check_consistency;
+pub mod peer_streams;
+pub mod reconcile_oidc_clients;
+pub mod trace_captures;
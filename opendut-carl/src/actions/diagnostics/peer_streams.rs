@@ -0,0 +1,45 @@
+use opendut_carl_api::carl::diagnostics::{PeerStreamInfo, TerminatePeerStreamError};
+use opendut_types::peer::PeerId;
+use tracing::{debug, error, info};
+
+use crate::peer::broker::{PeerMessagingBrokerRef, TerminateError};
+
+pub struct ListPeerStreamsParams {
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_peer_streams(params: ListPeerStreamsParams) -> Vec<PeerStreamInfo> {
+
+    debug!("Listing streams of connected peers.");
+
+    let mut streams = params.peer_messaging_broker.list_streams().await;
+    streams.sort_by_key(|stream| stream.opened_at);
+    streams
+}
+
+pub struct TerminatePeerStreamParams {
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub peer_id: PeerId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn terminate_peer_stream(params: TerminatePeerStreamParams) -> Result<PeerId, TerminatePeerStreamError> {
+
+    async fn inner(params: TerminatePeerStreamParams) -> Result<PeerId, TerminatePeerStreamError> {
+
+        let TerminatePeerStreamParams { peer_messaging_broker, peer_id } = params;
+
+        peer_messaging_broker.terminate_stream(peer_id).await
+            .map_err(|cause| match cause {
+                TerminateError::PeerNotConnected { peer_id } => TerminatePeerStreamError::PeerNotConnected { peer_id },
+            })?;
+
+        info!("Terminating stream of peer <{peer_id}>.");
+
+        Ok(peer_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
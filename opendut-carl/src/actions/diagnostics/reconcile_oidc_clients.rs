@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Not;
+
+use opendut_auth::registration::client::{Client, RegistrationClientRef};
+use opendut_auth::registration::resources::ResourceKind;
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct ReconcileOidcClientsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub registration_client: RegistrationClientRef,
+    pub delete_orphans: bool,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReconcileOidcClientsError {
+    #[error("An internal error occurred while reconciling the OIDC clients:\n  {cause}")]
+    Internal {
+        cause: String,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OidcClientFinding {
+    pub kind: OidcClientFindingKind,
+    pub deleted: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OidcClientFindingKind {
+    /// The client was registered for a peer, which does not exist anymore.
+    OrphanedPeerClient { client_id: String, peer_id: PeerId },
+    /// The client was registered before the resource kind was stored in its home URL and does not belong to any peer.
+    /// It may belong to a deleted peer or to a CLEO instance, so it is never deleted automatically.
+    UnclassifiedClient { client_id: String },
+    /// The peer is connected, but has no OIDC client anymore, so it will fail to authenticate once its token expires.
+    /// A new client is only of use together with a new PeerSetup, so it is not re-created automatically.
+    ConnectedPeerWithoutClient { peer_id: PeerId },
+}
+
+impl fmt::Display for OidcClientFindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OidcClientFindingKind::OrphanedPeerClient { client_id, peer_id } => write!(f, "OIDC client '{client_id}' belongs to peer <{peer_id}>, which does not exist."),
+            OidcClientFindingKind::UnclassifiedClient { client_id } => write!(f, "OIDC client '{client_id}' does not belong to any peer and was registered without resource kind."),
+            OidcClientFindingKind::ConnectedPeerWithoutClient { peer_id } => write!(f, "Peer <{peer_id}> is connected, but has no OIDC client. Generate a new PeerSetup for it."),
+        }
+    }
+}
+
+/// Compares the OIDC clients registered at the identity provider with the peers known to CARL.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn reconcile_oidc_clients(params: ReconcileOidcClientsParams) -> Result<Vec<OidcClientFinding>, ReconcileOidcClientsError> {
+
+    async fn inner(params: ReconcileOidcClientsParams) -> Result<Vec<OidcClientFinding>, ReconcileOidcClientsError> {
+
+        let ReconcileOidcClientsParams { resources_manager, registration_client, delete_orphans } = params;
+
+        debug!("Reconciling OIDC clients with peers.");
+
+        // Clients have to be listed before the peers, because a client is only registered for an existing peer.
+        // Otherwise, a peer created in between would cause its client to be considered orphaned.
+        let clients = registration_client.list_clients().await
+            .map_err(|cause| ReconcileOidcClientsError::Internal { cause: cause.to_string() })?
+            .filter_carl_clients(&registration_client.config.client_home_base_url);
+
+        let (peer_ids, connected_peer_ids) = resources_manager.resources(|resources| {
+            let peer_ids = resources.list_ids::<PeerDescriptor>()?
+                .into_iter()
+                .collect::<HashSet<_>>();
+
+            let mut connected_peer_ids = HashSet::new();
+            for &peer_id in &peer_ids {
+                if let Some(PeerState::Up { .. } | PeerState::Unreachable { .. }) = resources.get::<PeerState>(peer_id)? {
+                    connected_peer_ids.insert(peer_id);
+                }
+            }
+            Ok((peer_ids, connected_peer_ids))
+        }).await
+        .map_err(|cause| ReconcileOidcClientsError::Internal { cause: cause.to_string() })?;
+
+        let findings = determine_findings(&clients, &peer_ids, &connected_peer_ids);
+
+        let mut reconciled = Vec::with_capacity(findings.len());
+        for kind in findings {
+            let deleted = match &kind {
+                OidcClientFindingKind::OrphanedPeerClient { client_id, peer_id } if delete_orphans => {
+                    delete_orphaned_client(&resources_manager, &registration_client, client_id, *peer_id).await?
+                }
+                _ => false,
+            };
+            reconciled.push(OidcClientFinding { kind, deleted });
+        }
+
+        if reconciled.is_empty() {
+            info!("Successfully reconciled {} OIDC client(s) with peers. No findings.", clients.len());
+        } else {
+            for finding in &reconciled {
+                let deleted = if finding.deleted { " (deleted)" } else { "" };
+                warn!("Finding while reconciling OIDC clients{deleted}: {}", finding.kind);
+            }
+            info!("Successfully reconciled {} OIDC client(s) with peers. Found {} findings.", clients.len(), reconciled.len());
+        }
+
+        Ok(reconciled)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Returns whether the client was deleted.
+async fn delete_orphaned_client(resources_manager: &ResourcesManagerRef, registration_client: &RegistrationClientRef, client_id: &String, peer_id: PeerId) -> Result<bool, ReconcileOidcClientsError> {
+    // The peer may have been re-created with the same ID in the meantime.
+    let peer_exists = resources_manager.get::<PeerDescriptor>(peer_id).await
+        .map_err(|cause| ReconcileOidcClientsError::Internal { cause: cause.to_string() })?
+        .is_some();
+    if peer_exists {
+        return Ok(false);
+    }
+
+    let response = registration_client.delete_client(client_id).await
+        .map_err(|cause| ReconcileOidcClientsError::Internal { cause: cause.to_string() })?;
+
+    if response.status_code.is_success() {
+        debug!("Deleted orphaned OIDC client '{client_id}' of peer <{peer_id}>.");
+        Ok(true)
+    } else {
+        warn!("Failed to delete orphaned OIDC client '{client_id}' of peer <{peer_id}>. Status: {}", response.status_code);
+        Ok(false)
+    }
+}
+
+fn determine_findings(clients: &[Client], peer_ids: &HashSet<PeerId>, connected_peer_ids: &HashSet<PeerId>) -> Vec<OidcClientFindingKind> {
+    let mut findings = Vec::new();
+
+    let peer_uuids = peer_ids.iter()
+        .map(|peer_id| peer_id.uuid)
+        .collect::<HashSet<Uuid>>();
+
+    let mut peers_with_client = HashSet::new();
+
+    for client in clients {
+        let Some(resource_id) = client.resource_id() else {
+            continue;
+        };
+        let belongs_to_peer = peer_uuids.contains(&resource_id.value());
+
+        match client.resource_kind() {
+            Some(ResourceKind::Cleo) => {}
+            Some(ResourceKind::Peer) | None if belongs_to_peer => {
+                peers_with_client.insert(PeerId::from(resource_id.value()));
+            }
+            Some(ResourceKind::Peer) => {
+                findings.push(OidcClientFindingKind::OrphanedPeerClient {
+                    client_id: Clone::clone(&client.client_id),
+                    peer_id: PeerId::from(resource_id.value()),
+                });
+            }
+            None => {
+                findings.push(OidcClientFindingKind::UnclassifiedClient {
+                    client_id: Clone::clone(&client.client_id),
+                });
+            }
+        }
+    }
+
+    for &peer_id in connected_peer_ids {
+        if peers_with_client.contains(&peer_id).not() {
+            findings.push(OidcClientFindingKind::ConnectedPeerWithoutClient { peer_id });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn client(client_id: &str, base_url: String) -> anyhow::Result<Client> {
+        let client = serde_json::from_value(serde_json::json!({
+            "clientId": client_id,
+            "baseUrl": base_url,
+        }))?;
+        Ok(client)
+    }
+
+    #[test]
+    fn should_determine_orphaned_clients_and_peers_without_client() -> anyhow::Result<()> {
+        let existing_peer = PeerId::random();
+        let deleted_peer = PeerId::random();
+        let connected_peer_without_client = PeerId::random();
+        let cleo = Uuid::new_v4();
+        let legacy_resource = Uuid::new_v4();
+
+        let clients = vec![
+            client("existing", format!("https://carl/resources/user/peer/{}", existing_peer.uuid))?,
+            client("orphaned", format!("https://carl/resources/user/peer/{}", deleted_peer.uuid))?,
+            client("cleo", format!("https://carl/resources/user/cleo/{cleo}"))?,
+            client("legacy", format!("https://carl/resources/user/{legacy_resource}"))?,
+        ];
+        let peer_ids = HashSet::from([existing_peer, connected_peer_without_client]);
+        let connected_peer_ids = HashSet::from([existing_peer, connected_peer_without_client]);
+
+        let findings = determine_findings(&clients, &peer_ids, &connected_peer_ids);
+
+        assert_that!(findings, unordered_elements_are![
+            eq(&OidcClientFindingKind::OrphanedPeerClient { client_id: String::from("orphaned"), peer_id: deleted_peer }),
+            eq(&OidcClientFindingKind::UnclassifiedClient { client_id: String::from("legacy") }),
+            eq(&OidcClientFindingKind::ConnectedPeerWithoutClient { peer_id: connected_peer_without_client }),
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use opendut_carl_api::carl::diagnostics::{CapturedRecord, DeleteTraceCaptureError, DownloadTraceCaptureError, StartTraceCaptureError, TraceCaptureBundle, TraceCaptureId, TraceCaptureInfo, TraceCaptureLevel, TraceCaptureScope};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_util::telemetry::capture;
+use opendut_util::telemetry::capture::TraceCapturesRef;
+use tracing::{debug, error, info};
+
+const PEER_LABEL: &str = "peer";
+const CLUSTER_LABEL: &str = "cluster";
+
+pub struct StartTraceCaptureParams {
+    pub trace_captures: TraceCapturesRef,
+    pub scope: TraceCaptureScope,
+    pub level: TraceCaptureLevel,
+    pub duration: Duration,
+    pub options: TraceCaptureOptions,
+}
+
+#[derive(Clone, Debug)]
+pub struct TraceCaptureOptions {
+    pub max_duration: Duration,
+    pub max_records: usize,
+}
+impl TraceCaptureOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let max_duration = Duration::from_millis(
+            config.get::<u64>("diagnostics.trace.capture.max.duration.ms")?
+        );
+        let max_records = config.get::<usize>("diagnostics.trace.capture.max.records")?;
+
+        Ok(TraceCaptureOptions { max_duration, max_records })
+    }
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn start_trace_capture(params: StartTraceCaptureParams) -> Result<TraceCaptureInfo, StartTraceCaptureError> {
+
+    async fn inner(params: StartTraceCaptureParams) -> Result<TraceCaptureInfo, StartTraceCaptureError> {
+
+        let StartTraceCaptureParams { trace_captures, scope, level, duration, options } = params;
+
+        if duration.is_zero() || duration > options.max_duration {
+            return Err(StartTraceCaptureError::IllegalDuration { duration, max_duration: options.max_duration });
+        }
+
+        let capture_scope = match &scope {
+            TraceCaptureScope::Peer { peer_id } => capture::TraceCaptureScope::Value { label: String::from(PEER_LABEL), value: peer_id.to_string() },
+            TraceCaptureScope::Cluster { cluster_id } => capture::TraceCaptureScope::Value { label: String::from(CLUSTER_LABEL), value: cluster_id.to_string() },
+            TraceCaptureScope::GrpcMethod { method } => capture::TraceCaptureScope::SpanName(Clone::clone(method)),
+        };
+        let capture_level = match level {
+            TraceCaptureLevel::Debug => tracing::Level::DEBUG,
+            TraceCaptureLevel::Trace => tracing::Level::TRACE,
+        };
+
+        let capture = trace_captures.start(capture_scope, capture_level, duration, options.max_records);
+
+        info!("Started trace capture <{}> for {scope} at level {level} for {} ms.", capture.id, duration.as_millis());
+
+        Ok(TraceCaptureInfo {
+            id: TraceCaptureId(capture.id),
+            scope,
+            level,
+            started_at: capture.started_at,
+            expires_at: capture.expires_at,
+            record_count: capture.record_count,
+            dropped_record_count: capture.dropped_record_count,
+        })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListTraceCapturesParams {
+    pub trace_captures: TraceCapturesRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_trace_captures(params: ListTraceCapturesParams) -> Vec<TraceCaptureInfo> {
+
+    debug!("Listing trace captures.");
+
+    params.trace_captures.list().into_iter()
+        .filter_map(to_trace_capture_info)
+        .collect()
+}
+
+pub struct DownloadTraceCaptureParams {
+    pub trace_captures: TraceCapturesRef,
+    pub capture_id: TraceCaptureId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn download_trace_capture(params: DownloadTraceCaptureParams) -> Result<TraceCaptureBundle, DownloadTraceCaptureError> {
+
+    async fn inner(params: DownloadTraceCaptureParams) -> Result<TraceCaptureBundle, DownloadTraceCaptureError> {
+
+        let DownloadTraceCaptureParams { trace_captures, capture_id } = params;
+
+        debug!("Downloading trace capture <{capture_id}>.");
+
+        let (capture, records) = trace_captures.records(capture_id.0)
+            .ok_or(DownloadTraceCaptureError::TraceCaptureNotFound { capture_id })?;
+        let capture = to_trace_capture_info(capture)
+            .ok_or(DownloadTraceCaptureError::TraceCaptureNotFound { capture_id })?;
+
+        let records = records.into_iter()
+            .map(|record| CapturedRecord {
+                timestamp: record.timestamp,
+                level: record.level.to_string(),
+                target: record.target,
+                spans: record.spans,
+                fields: record.fields,
+            })
+            .collect();
+
+        Ok(TraceCaptureBundle { capture, records })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteTraceCaptureParams {
+    pub trace_captures: TraceCapturesRef,
+    pub capture_id: TraceCaptureId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_trace_capture(params: DeleteTraceCaptureParams) -> Result<TraceCaptureId, DeleteTraceCaptureError> {
+
+    async fn inner(params: DeleteTraceCaptureParams) -> Result<TraceCaptureId, DeleteTraceCaptureError> {
+
+        let DeleteTraceCaptureParams { trace_captures, capture_id } = params;
+
+        trace_captures.remove(capture_id.0)
+            .ok_or(DeleteTraceCaptureError::TraceCaptureNotFound { capture_id })?;
+
+        info!("Deleted trace capture <{capture_id}>.");
+
+        Ok(capture_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Captures are only started via [`start_trace_capture`], so the scope can always be mapped back, unless another component started a capture of its own.
+fn to_trace_capture_info(capture: capture::TraceCaptureInfo) -> Option<TraceCaptureInfo> {
+    let scope = match capture.scope {
+        capture::TraceCaptureScope::Value { label, value } if label == PEER_LABEL => {
+            TraceCaptureScope::Peer { peer_id: PeerId::try_from(value.as_str()).ok()? }
+        }
+        capture::TraceCaptureScope::Value { label, value } if label == CLUSTER_LABEL => {
+            TraceCaptureScope::Cluster { cluster_id: ClusterId::try_from(value.as_str()).ok()? }
+        }
+        capture::TraceCaptureScope::Value { .. } => return None,
+        capture::TraceCaptureScope::SpanName(method) => TraceCaptureScope::GrpcMethod { method },
+    };
+    let level = if capture.level == tracing::Level::TRACE {
+        TraceCaptureLevel::Trace
+    } else {
+        TraceCaptureLevel::Debug
+    };
+
+    Some(TraceCaptureInfo {
+        id: TraceCaptureId(capture.id),
+        scope,
+        level,
+        started_at: capture.started_at,
+        expires_at: capture.expires_at,
+        record_count: capture.record_count,
+        dropped_record_count: capture.dropped_record_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use opendut_util::telemetry::capture::TraceCaptures;
+
+    use super::*;
+
+    fn options() -> TraceCaptureOptions {
+        TraceCaptureOptions {
+            max_duration: Duration::from_secs(60),
+            max_records: 100,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_start_list_and_delete_trace_capture() -> anyhow::Result<()> {
+        let trace_captures = Arc::new(TraceCaptures::default());
+        let peer_id = PeerId::random();
+
+        let capture = start_trace_capture(StartTraceCaptureParams {
+            trace_captures: Arc::clone(&trace_captures),
+            scope: TraceCaptureScope::Peer { peer_id },
+            level: TraceCaptureLevel::Trace,
+            duration: Duration::from_secs(10),
+            options: options(),
+        }).await?;
+
+        let captures = list_trace_captures(ListTraceCapturesParams {
+            trace_captures: Arc::clone(&trace_captures),
+        }).await;
+        assert_eq!(captures, vec![Clone::clone(&capture)]);
+
+        let bundle = download_trace_capture(DownloadTraceCaptureParams {
+            trace_captures: Arc::clone(&trace_captures),
+            capture_id: capture.id,
+        }).await?;
+        assert_eq!(bundle.capture.scope, TraceCaptureScope::Peer { peer_id });
+        assert!(bundle.records.is_empty());
+
+        let deleted = delete_trace_capture(DeleteTraceCaptureParams {
+            trace_captures: Arc::clone(&trace_captures),
+            capture_id: capture.id,
+        }).await?;
+        assert_eq!(deleted, capture.id);
+
+        let result = download_trace_capture(DownloadTraceCaptureParams {
+            trace_captures: Arc::clone(&trace_captures),
+            capture_id: capture.id,
+        }).await;
+        assert!(matches!(result, Err(DownloadTraceCaptureError::TraceCaptureNotFound { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_duration_exceeding_maximum() {
+        let trace_captures = Arc::new(TraceCaptures::default());
+
+        let result = start_trace_capture(StartTraceCaptureParams {
+            trace_captures: Arc::clone(&trace_captures),
+            scope: TraceCaptureScope::GrpcMethod { method: String::from("ListPeerDescriptors") },
+            level: TraceCaptureLevel::Debug,
+            duration: Duration::from_secs(3600),
+            options: options(),
+        }).await;
+
+        assert!(matches!(result, Err(StartTraceCaptureError::IllegalDuration { .. })));
+        assert!(trace_captures.list().is_empty());
+    }
+}
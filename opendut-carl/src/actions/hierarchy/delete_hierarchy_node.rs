@@ -0,0 +1,47 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::DeleteHierarchyNodeError;
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+use std::ops::Not;
+use tracing::{debug, error, info};
+
+pub struct DeleteHierarchyNodeParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub node_id: HierarchyNodeId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_hierarchy_node(params: DeleteHierarchyNodeParams) -> Result<HierarchyNode, DeleteHierarchyNodeError> {
+
+    async fn inner(params: DeleteHierarchyNodeParams) -> Result<HierarchyNode, DeleteHierarchyNodeError> {
+
+        let DeleteHierarchyNodeParams { resources_manager, node_id } = params;
+
+        debug!("Deleting hierarchy node <{node_id}>.");
+
+        let node = resources_manager.resources_mut(|resources| {
+            let child_ids = resources.list::<HierarchyNode>()
+                .map_err(|cause| DeleteHierarchyNodeError::Internal { node_id, cause: cause.to_string() })?
+                .into_iter()
+                .filter(|node| node.parent == Some(node_id))
+                .map(|node| node.id)
+                .collect::<Vec<_>>();
+
+            if child_ids.is_empty().not() {
+                return Err(DeleteHierarchyNodeError::HasChildren { node_id, child_ids });
+            }
+
+            resources.remove::<HierarchyNode>(node_id)
+                .map_err(|cause| DeleteHierarchyNodeError::Internal { node_id, cause: cause.to_string() })?
+                .ok_or(DeleteHierarchyNodeError::NodeNotFound { node_id })
+        }).await
+        .map_err(|cause| DeleteHierarchyNodeError::Internal { node_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted {} '{}' <{node_id}>.", node.level, node.name);
+
+        Ok(node)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use crate::actions::hierarchy::aggregate_health;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::{GetHierarchyHealthError, HierarchyNodeHealth};
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::state::PeerState;
+use tracing::{debug, error, info};
+
+pub struct GetHierarchyHealthParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+/// Determines, how many of the peers located in each site, room and rack are available, blocked or down.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_hierarchy_health(params: GetHierarchyHealthParams) -> Result<Vec<HierarchyNodeHealth>, GetHierarchyHealthError> {
+
+    async fn inner(params: GetHierarchyHealthParams) -> Result<Vec<HierarchyNodeHealth>, GetHierarchyHealthError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Aggregating health of the hierarchy.");
+
+        let health = resources_manager.resources(|resources| {
+            let nodes = resources.list::<HierarchyNode>()?;
+
+            let mut peer_states = HashMap::new();
+            for peer_id in nodes.iter().flat_map(|node| node.peers.iter()) {
+                if let Some(state) = resources.get::<PeerState>(*peer_id)? {
+                    peer_states.insert(*peer_id, state);
+                }
+            }
+
+            Ok(aggregate_health(&nodes, &peer_states))
+        }).await
+        .map_err(|cause| GetHierarchyHealthError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully aggregated health of {} hierarchy nodes.", health.len());
+
+        Ok(health)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
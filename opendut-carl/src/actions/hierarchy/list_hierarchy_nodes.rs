@@ -0,0 +1,30 @@
+use crate::resources::manager::ResourcesManagerRef;
+use opendut_carl_api::carl::peer::ListHierarchyNodesError;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use tracing::{debug, error, info};
+use crate::resources::storage::ReadConsistency;
+
+pub struct ListHierarchyNodesParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_hierarchy_nodes(params: ListHierarchyNodesParams) -> Result<Vec<HierarchyNode>, ListHierarchyNodesError> {
+
+    async fn inner(params: ListHierarchyNodesParams) -> Result<Vec<HierarchyNode>, ListHierarchyNodesError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all hierarchy nodes.");
+
+        let nodes = resources_manager.list_with::<HierarchyNode>(ReadConsistency::Eventual).await
+        .map_err(|cause| ListHierarchyNodesError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully queried all hierarchy nodes.");
+
+        Ok(nodes)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use opendut_carl_api::carl::peer::HierarchyNodeHealth;
+use opendut_types::peer::hierarchy::{ancestors, HierarchyNode};
+use opendut_types::peer::state::{PeerState, PeerUpState};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::persistence::error::PersistenceResult;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub mod delete_hierarchy_node;
+pub mod get_hierarchy_health;
+pub mod list_hierarchy_nodes;
+pub mod store_hierarchy_node;
+
+/// Checks whether `node` may be stored into the current hierarchy. Returns the reason, if the placement is illegal.
+pub(crate) fn check_placement(resources: &impl ResourcesStorageApi, node: &HierarchyNode) -> PersistenceResult<Result<(), String>> {
+    match (node.level.parent_level(), node.parent) {
+        (None, Some(_)) => {
+            return Ok(Err(format!("A {} must not have a parent.", node.level)));
+        }
+        (Some(parent_level), None) => {
+            return Ok(Err(format!("A {} must be placed in a {parent_level}.", node.level)));
+        }
+        (Some(parent_level), Some(parent_id)) => {
+            match resources.get::<HierarchyNode>(parent_id)? {
+                None => return Ok(Err(format!("Parent node <{parent_id}> does not exist."))),
+                Some(parent) if parent.level != parent_level => {
+                    return Ok(Err(format!("A {} must be placed in a {parent_level}, but parent '{}' <{parent_id}> is a {}.", node.level, parent.name, parent.level)));
+                }
+                Some(_) => {}
+            }
+        }
+        (None, None) => {}
+    }
+
+    let other_nodes = resources.list::<HierarchyNode>()?
+        .into_iter()
+        .filter(|other| other.id != node.id)
+        .collect::<Vec<_>>();
+
+    if let Some(previous) = resources.get::<HierarchyNode>(node.id)? {
+        if previous.level != node.level && other_nodes.iter().any(|other| other.parent == Some(node.id)) {
+            return Ok(Err(format!("The level of a node with children cannot be changed from {} to {}.", previous.level, node.level)));
+        }
+    }
+
+    for peer_id in &node.peers {
+        if resources.get::<PeerDescriptor>(*peer_id)?.is_none() {
+            return Ok(Err(format!("Peer <{peer_id}> does not exist.")));
+        }
+        if let Some(other) = other_nodes.iter().find(|other| other.peers.contains(peer_id)) {
+            return Ok(Err(format!("Peer <{peer_id}> is already placed in {} '{}' <{}>.", other.level, other.name, other.id)));
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+/// Aggregates the states of the peers in each node, including the peers placed in its descendants.
+pub(crate) fn aggregate_health(nodes: &[HierarchyNode], peer_states: &HashMap<PeerId, PeerState>) -> Vec<HierarchyNodeHealth> {
+    let mut health = nodes.iter()
+        .map(|node| HierarchyNodeHealth {
+            node_id: node.id,
+            node_name: Clone::clone(&node.name),
+            level: node.level,
+            available_peers: 0,
+            blocked_peers: 0,
+            down_peers: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for node in nodes {
+        for peer_id in &node.peers {
+            let state = peer_states.get(peer_id).cloned().unwrap_or_default();
+
+            for ancestor in ancestors(nodes, node.id) {
+                if let Some(health) = health.iter_mut().find(|health| health.node_id == ancestor.id) {
+                    match state {
+                        PeerState::Up { inner: PeerUpState::Available, .. } => health.available_peers += 1,
+                        PeerState::Up { inner: PeerUpState::Blocked(_), .. } => health.blocked_peers += 1,
+                        PeerState::Unreachable { .. } | PeerState::Down => health.down_peers += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    health
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNodeId, HierarchyNodeName};
+    use opendut_types::peer::state::PeerBlockedState;
+
+    use super::*;
+
+    #[test]
+    fn should_aggregate_health_of_descendants() -> anyhow::Result<()> {
+        let available = PeerId::random();
+        let blocked = PeerId::random();
+        let down = PeerId::random();
+
+        let site = HierarchyNode {
+            id: HierarchyNodeId::random(),
+            name: HierarchyNodeName::try_from("site-a")?,
+            level: HierarchyLevel::Site,
+            parent: None,
+            peers: HashSet::new(),
+        };
+        let room = HierarchyNode {
+            id: HierarchyNodeId::random(),
+            name: HierarchyNodeName::try_from("room-1")?,
+            level: HierarchyLevel::Room,
+            parent: Some(site.id),
+            peers: HashSet::from([down]),
+        };
+        let rack = HierarchyNode {
+            id: HierarchyNodeId::random(),
+            name: HierarchyNodeName::try_from("rack-1")?,
+            level: HierarchyLevel::Rack,
+            parent: Some(room.id),
+            peers: HashSet::from([available, blocked]),
+        };
+
+        let remote_host = IpAddr::from_str("127.0.0.1")?;
+        let peer_states = HashMap::from([
+            (available, PeerState::Up { inner: PeerUpState::Available, remote_host }),
+            (blocked, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), remote_host }),
+        ]);
+
+        let health = aggregate_health(&[site.clone(), room.clone(), rack.clone()], &peer_states);
+
+        let site_health = health.iter().find(|health| health.node_id == site.id).unwrap();
+        assert_eq!((site_health.available_peers, site_health.blocked_peers, site_health.down_peers), (1, 1, 1));
+
+        let rack_health = health.iter().find(|health| health.node_id == rack.id).unwrap();
+        assert_eq!((rack_health.available_peers, rack_health.blocked_peers, rack_health.down_peers), (1, 1, 0));
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::actions::hierarchy::check_placement;
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::StoreHierarchyNodeError;
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+use tracing::{debug, error, info};
+
+pub struct StoreHierarchyNodeParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub node: HierarchyNode,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_hierarchy_node(params: StoreHierarchyNodeParams) -> Result<HierarchyNodeId, StoreHierarchyNodeError> {
+
+    async fn inner(params: StoreHierarchyNodeParams) -> Result<HierarchyNodeId, StoreHierarchyNodeError> {
+
+        let StoreHierarchyNodeParams { resources_manager, node } = params;
+        let node_id = node.id;
+        let node_name = Clone::clone(&node.name);
+
+        debug!("Storing {} '{node_name}' <{node_id}>.", node.level);
+
+        let internal_error = |cause: PersistenceError| StoreHierarchyNodeError::Internal { node_id, node_name: Clone::clone(&node_name), cause: cause.to_string() };
+
+        resources_manager.resources_mut(|resources| {
+            check_placement(resources, &node)
+                .map_err(internal_error)?
+                .map_err(|reason| StoreHierarchyNodeError::IllegalPlacement { node_id, node_name: Clone::clone(&node_name), reason })?;
+
+            resources.insert(node_id, node)
+                .map_err(internal_error)
+        }).await
+        .map_err(internal_error)??;
+
+        info!("Successfully stored hierarchy node '{node_name}' <{node_id}>.");
+
+        Ok(node_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -5,6 +5,29 @@ pub use clusters::store_cluster_deployment::*;
 pub use clusters::determine_cluster_peers::*;
 pub use clusters::determine_cluster_peer_states::*;
 pub use clusters::delete_cluster_deployment::*;
+pub use clusters::simulate_cluster_deployment::*;
+
+mod composite;
+pub use composite::create_peer_in_cluster::*;
+
+mod diagnostics;
+pub use diagnostics::check_compatibility::*;
+pub use diagnostics::check_consistency::*;
+pub use diagnostics::peer_streams::*;
+pub use diagnostics::reconcile_oidc_clients::*;
+pub use diagnostics::trace_captures::*;
+
+mod hierarchy;
+pub use hierarchy::store_hierarchy_node::*;
+pub use hierarchy::delete_hierarchy_node::*;
+pub use hierarchy::list_hierarchy_nodes::*;
+pub use hierarchy::get_hierarchy_health::*;
+
+mod peer_groups;
+pub use peer_groups::store_peer_group::*;
+pub use peer_groups::preview_peer_group_changes::*;
+pub use peer_groups::delete_peer_group::*;
+pub use peer_groups::list_peer_groups::*;
 
 mod peers;
 pub use peers::store_peer_descriptor::*;
@@ -13,6 +36,45 @@ pub use peers::generate_cleo_setup::*;
 pub use peers::delete_peer_descriptor::*;
 pub use peers::list_peer_descriptors::*;
 pub use peers::get_peer_state::*;
+pub use peers::get_executor_secrets::*;
 pub use peers::list_devices::*;
 pub use peers::assign_cluster::*;
 pub use peers::unassign_cluster::*;
+pub use peers::edit_peer_labels::*;
+pub use peers::ping_peer::*;
+pub use peers::send_peer_command::*;
+pub use peers::rolling_restart::*;
+pub use peers::generate_provisioning_tokens::*;
+pub use peers::list_provisioning_tokens::*;
+pub use peers::claim_provisioning_token::*;
+pub use peers::revoke_peer_setup::*;
+pub use peers::upload_self_test_report::*;
+
+mod projects;
+pub use projects::ProjectOptions;
+pub use projects::assign_to_project::*;
+pub use projects::delete_project::*;
+pub use projects::list_projects::*;
+pub use projects::store_project::*;
+pub use projects::unassign_from_projects::*;
+
+mod results;
+pub use results::{ResultsStorage, ResultsStorageOptions};
+pub use results::download_executor_result::*;
+pub use results::list_executor_results::*;
+
+mod service_accounts;
+pub use service_accounts::ServiceAccountOptions;
+pub use service_accounts::authenticate_service_account::*;
+pub use service_accounts::create_service_account::*;
+pub use service_accounts::list_service_accounts::*;
+pub use service_accounts::delete_service_account::*;
+pub use service_accounts::issue_service_account_token::*;
+pub use service_accounts::revoke_service_account_token::*;
+
+mod snapshots;
+pub use snapshots::create_snapshot::*;
+pub use snapshots::list_snapshots::*;
+pub use snapshots::diff_snapshots::*;
+pub use snapshots::restore_snapshot::*;
+pub use snapshots::delete_snapshot::*;
@@ -0,0 +1,48 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::DeletePeerGroupError;
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+use std::ops::Not;
+use tracing::{debug, error, info};
+
+pub struct DeletePeerGroupParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_group_id: PeerGroupId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_peer_group(params: DeletePeerGroupParams) -> Result<PeerGroup, DeletePeerGroupError> {
+
+    async fn inner(params: DeletePeerGroupParams) -> Result<PeerGroup, DeletePeerGroupError> {
+
+        let DeletePeerGroupParams { resources_manager, peer_group_id } = params;
+
+        debug!("Deleting peer group <{peer_group_id}>.");
+
+        let peer_group = resources_manager.resources_mut(|resources| {
+            let cluster_ids = resources.list::<ClusterConfiguration>()
+                .map_err(|cause| DeletePeerGroupError::Internal { peer_group_id, cause: cause.to_string() })?
+                .into_iter()
+                .filter(|cluster| cluster.peer_groups.contains(&peer_group_id))
+                .map(|cluster| cluster.id)
+                .collect::<Vec<_>>();
+
+            if cluster_ids.is_empty().not() {
+                return Err(DeletePeerGroupError::ReferencedByClusters { peer_group_id, cluster_ids });
+            }
+
+            resources.remove::<PeerGroup>(peer_group_id)
+                .map_err(|cause| DeletePeerGroupError::Internal { peer_group_id, cause: cause.to_string() })?
+                .ok_or(DeletePeerGroupError::PeerGroupNotFound { peer_group_id })
+        }).await
+        .map_err(|cause| DeletePeerGroupError::Internal { peer_group_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted peer group '{}' <{peer_group_id}>.", peer_group.name);
+
+        Ok(peer_group)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,32 @@
+use crate::resources::manager::ResourcesManagerRef;
+use opendut_carl_api::carl::peer::ListPeerGroupsError;
+use opendut_types::peer::group::PeerGroup;
+use tracing::{debug, error, info};
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct ListPeerGroupsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_peer_groups(params: ListPeerGroupsParams) -> Result<Vec<PeerGroup>, ListPeerGroupsError> {
+
+    async fn inner(params: ListPeerGroupsParams) -> Result<Vec<PeerGroup>, ListPeerGroupsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all peer groups.");
+
+        let peer_groups = resources_manager.resources(|resources| {
+            resources.list::<PeerGroup>()
+        }).await
+        .map_err(|cause| ListPeerGroupsError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully queried all peer groups.");
+
+        Ok(peer_groups)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use std::ops::Not;
+
+use opendut_carl_api::carl::peer::PeerGroupClusterChange;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::topology::DeviceId;
+
+use crate::persistence::error::PersistenceResult;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub mod delete_peer_group;
+pub mod list_peer_groups;
+pub mod preview_peer_group_changes;
+pub mod store_peer_group;
+
+/// Determines how storing `peer_group` would change the devices of the ClusterConfigurations referencing it.
+pub(crate) fn determine_cluster_changes(resources: &impl ResourcesStorageApi, peer_group: &PeerGroup) -> PersistenceResult<Vec<PeerGroupClusterChange>> {
+    let previous_peers = resources.get::<PeerGroup>(peer_group.id)?
+        .map(|previous| previous.peers)
+        .unwrap_or_default();
+
+    let added_peers = peer_group.peers.difference(&previous_peers).cloned().collect::<HashSet<_>>();
+    let removed_peers = previous_peers.difference(&peer_group.peers).cloned().collect::<HashSet<_>>();
+
+    if added_peers.is_empty() && removed_peers.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let peers = resources.list::<PeerDescriptor>()?;
+    let other_peer_groups = resources.list::<PeerGroup>()?
+        .into_iter()
+        .filter(|other| other.id != peer_group.id)
+        .collect::<Vec<_>>();
+
+    let mut changes = Vec::new();
+
+    for cluster in resources.list::<ClusterConfiguration>()? {
+        if cluster.peer_groups.contains(&peer_group.id).not() {
+            continue;
+        }
+
+        //Peers which remain in the cluster via another referenced peer group should keep their devices.
+        let peers_of_other_groups = other_peer_groups.iter()
+            .filter(|other| cluster.peer_groups.contains(&other.id))
+            .flat_map(|other| other.peers.iter().cloned())
+            .collect::<HashSet<_>>();
+
+        let added_devices = devices_of_peers(&peers, &added_peers)
+            .difference(&cluster.devices).cloned()
+            .collect::<HashSet<_>>();
+
+        let removed_peers = removed_peers.difference(&peers_of_other_groups).cloned().collect::<HashSet<_>>();
+
+        let removed_devices = devices_of_peers(&peers, &removed_peers)
+            .intersection(&cluster.devices).cloned()
+            .collect::<HashSet<_>>();
+
+        let deployed = resources.get::<ClusterDeployment>(cluster.id)?.is_some();
+
+        let change = PeerGroupClusterChange {
+            cluster_id: cluster.id,
+            cluster_name: cluster.name,
+            deployed,
+            added_devices,
+            removed_devices,
+        };
+        if change.is_empty().not() {
+            changes.push(change);
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Returns the devices of all peers in the given peer groups.
+pub(crate) fn resolve_peer_group_devices(resources: &impl ResourcesStorageApi, peer_group_ids: &HashSet<PeerGroupId>) -> PersistenceResult<Result<HashSet<DeviceId>, PeerGroupId>> {
+    let mut group_peers = HashSet::new();
+
+    for peer_group_id in peer_group_ids {
+        match resources.get::<PeerGroup>(*peer_group_id)? {
+            Some(peer_group) => group_peers.extend(peer_group.peers),
+            None => return Ok(Err(*peer_group_id)),
+        }
+    }
+
+    let peers = resources.list::<PeerDescriptor>()?;
+
+    Ok(Ok(devices_of_peers(&peers, &group_peers)))
+}
+
+fn devices_of_peers(peers: &[PeerDescriptor], peer_ids: &HashSet<PeerId>) -> HashSet<DeviceId> {
+    peers.iter()
+        .filter(|peer| peer_ids.contains(&peer.id))
+        .flat_map(|peer| peer.topology.devices.iter().map(|device| device.id))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerNetworkDescriptor};
+    use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+    pub fn generate_peer_descriptor() -> anyhow::Result<PeerDescriptor> {
+        let network_interface_id = NetworkInterfaceId::random();
+
+        Ok(PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("peer")?,
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![
+                    NetworkInterfaceDescriptor {
+                        id: network_interface_id,
+                        name: NetworkInterfaceName::try_from("eth0")?,
+                        configuration: NetworkInterfaceConfiguration::Ethernet,
+                    },
+                ],
+                bridge_name: None,
+            },
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: DeviceId::random(),
+                        name: DeviceName::try_from("device")?,
+                        description: None,
+                        interface: network_interface_id,
+                        tags: vec![],
+                    }
+                ],
+            },
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use crate::actions::peer_groups::determine_cluster_changes;
+use crate::resources::manager::ResourcesManagerRef;
+use opendut_carl_api::carl::peer::{PeerGroupClusterChange, PreviewPeerGroupChangesError};
+use opendut_types::peer::group::PeerGroup;
+use tracing::{debug, error};
+
+pub struct PreviewPeerGroupChangesParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_group: PeerGroup,
+}
+
+/// Determines which ClusterConfigurations would be changed by storing the given PeerGroup, without storing it.
+/// Changes to deployed clusters are only applied by `store_peer_group` after they were confirmed.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn preview_peer_group_changes(params: PreviewPeerGroupChangesParams) -> Result<Vec<PeerGroupClusterChange>, PreviewPeerGroupChangesError> {
+
+    async fn inner(params: PreviewPeerGroupChangesParams) -> Result<Vec<PeerGroupClusterChange>, PreviewPeerGroupChangesError> {
+
+        let PreviewPeerGroupChangesParams { resources_manager, peer_group } = params;
+        let peer_group_id = peer_group.id;
+
+        debug!("Previewing changes of peer group '{}' <{peer_group_id}>.", peer_group.name);
+
+        let changes = resources_manager.resources(|resources| {
+            determine_cluster_changes(resources, &peer_group)
+        }).await
+        .map_err(|cause| PreviewPeerGroupChangesError::Internal { peer_group_id, cause: cause.to_string() })?;
+
+        Ok(changes)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
+    use opendut_types::peer::group::{PeerGroupId, PeerGroupName};
+    use std::collections::HashSet;
+
+    #[tokio::test]
+    async fn should_list_changes_to_deployed_clusters_without_applying_them() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer_a = generate_peer_descriptor()?;
+        resources_manager.insert(peer_a.id, peer_a.clone()).await?;
+        let peer_b = generate_peer_descriptor()?;
+        resources_manager.insert(peer_b.id, peer_b.clone()).await?;
+
+        let peer_group = PeerGroup {
+            id: PeerGroupId::random(),
+            name: PeerGroupName::try_from("rack-01")?,
+            labels: HashSet::new(),
+            peers: HashSet::from([peer_a.id]),
+        };
+        resources_manager.insert(peer_group.id, peer_group.clone()).await?;
+
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: peer_a.id,
+            devices: HashSet::from([peer_a.topology.devices[0].id]),
+            peer_groups: HashSet::from([peer_group.id]),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        resources_manager.insert(cluster.id, cluster.clone()).await?;
+        resources_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+
+        let changed_peer_group = PeerGroup { peers: HashSet::from([peer_a.id, peer_b.id]), ..peer_group.clone() };
+
+        let result = preview_peer_group_changes(PreviewPeerGroupChangesParams {
+            resources_manager: resources_manager.clone(),
+            peer_group: changed_peer_group,
+        }).await?;
+
+        assert_eq!(result, vec![
+            PeerGroupClusterChange {
+                cluster_id: cluster.id,
+                cluster_name: cluster.name.clone(),
+                deployed: true,
+                added_devices: HashSet::from([peer_b.topology.devices[0].id]),
+                removed_devices: HashSet::new(),
+            }
+        ]);
+        assert_eq!(resources_manager.get::<PeerGroup>(peer_group.id).await?, Some(peer_group));
+        assert_eq!(resources_manager.get::<ClusterConfiguration>(cluster.id).await?, Some(cluster));
+
+        Ok(())
+    }
+}
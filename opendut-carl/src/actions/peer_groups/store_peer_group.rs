@@ -0,0 +1,187 @@
+use crate::actions::peer_groups::determine_cluster_changes;
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::{StorePeerGroupError, StorePeerGroupOutcome};
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::PeerDescriptor;
+use std::ops::Not;
+use tracing::{debug, error, info, warn};
+
+pub struct StorePeerGroupParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_group: PeerGroup,
+    /// Whether membership changes should also be applied to ClusterConfigurations which are currently deployed.
+    /// Should only be set after the changes returned by `preview_peer_group_changes` were confirmed.
+    pub propagate_to_deployed_clusters: bool,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_peer_group(params: StorePeerGroupParams) -> Result<StorePeerGroupOutcome, StorePeerGroupError> {
+
+    async fn inner(params: StorePeerGroupParams) -> Result<StorePeerGroupOutcome, StorePeerGroupError> {
+
+        let StorePeerGroupParams { resources_manager, peer_group, propagate_to_deployed_clusters } = params;
+        let peer_group_id = peer_group.id;
+        let peer_group_name = Clone::clone(&peer_group.name);
+
+        debug!("Storing peer group '{peer_group_name}' <{peer_group_id}>.");
+
+        let internal_error = |cause: PersistenceError| StorePeerGroupError::Internal { peer_group_id, peer_group_name: Clone::clone(&peer_group_name), cause: cause.to_string() };
+
+        let outcome = resources_manager.resources_mut(|resources| {
+            for peer_id in &peer_group.peers {
+                if resources.get::<PeerDescriptor>(*peer_id).map_err(internal_error)?.is_none() {
+                    return Err(StorePeerGroupError::PeerNotFound { peer_group_id, peer_group_name: Clone::clone(&peer_group_name), peer_id: *peer_id });
+                }
+            }
+
+            let (applied_changes, skipped_changes): (Vec<_>, Vec<_>) = determine_cluster_changes(resources, &peer_group)
+                .map_err(internal_error)?
+                .into_iter()
+                .partition(|change| change.deployed.not() || propagate_to_deployed_clusters);
+
+            for change in &applied_changes {
+                let cluster = resources.get::<ClusterConfiguration>(change.cluster_id).map_err(internal_error)?;
+
+                if let Some(mut cluster) = cluster {
+                    cluster.devices.retain(|device| change.removed_devices.contains(device).not());
+                    cluster.devices.extend(change.added_devices.iter().cloned());
+                    resources.insert(cluster.id, cluster).map_err(internal_error)?;
+                }
+            }
+
+            resources.insert(peer_group_id, peer_group).map_err(internal_error)?;
+
+            Ok(StorePeerGroupOutcome { peer_group_id, applied_changes, skipped_changes })
+        }).await
+        .map_err(internal_error)??;
+
+        for change in &outcome.applied_changes {
+            info!("Updated devices of cluster '{}' <{}> due to membership changes of peer group '{peer_group_name}' <{peer_group_id}>.", change.cluster_name, change.cluster_id);
+        }
+        for change in &outcome.skipped_changes {
+            warn!("Not updating devices of deployed cluster '{}' <{}> for membership changes of peer group '{peer_group_name}' <{peer_group_id}>, as this was not confirmed.", change.cluster_name, change.cluster_id);
+        }
+
+        info!("Successfully stored peer group '{peer_group_name}' <{peer_group_id}>.");
+
+        Ok(outcome)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_types::cluster::{ClusterDeployment, ClusterId, ClusterName};
+    use opendut_types::peer::group::{PeerGroupId, PeerGroupLabel, PeerGroupName};
+    use opendut_types::peer::PeerId;
+    use std::collections::HashSet;
+
+    #[tokio::test]
+    async fn should_update_undeployed_clusters_when_peers_are_added_to_a_group() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer_a = generate_peer_descriptor()?;
+        resources_manager.insert(peer_a.id, peer_a.clone()).await?;
+        let peer_b = generate_peer_descriptor()?;
+        resources_manager.insert(peer_b.id, peer_b.clone()).await?;
+
+        let peer_group = peer_group(HashSet::from([peer_a.id]))?;
+        store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: peer_group.clone(), propagate_to_deployed_clusters: false }).await?;
+
+        let cluster = cluster_configuration(&peer_a, &peer_group)?;
+        resources_manager.insert(cluster.id, cluster.clone()).await?;
+
+        let peer_group = PeerGroup { peers: HashSet::from([peer_a.id, peer_b.id]), ..peer_group };
+        let outcome = store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: peer_group.clone(), propagate_to_deployed_clusters: false }).await?;
+
+        assert_eq!(outcome.applied_changes.len(), 1);
+        assert!(outcome.skipped_changes.is_empty());
+
+        let cluster = resources_manager.get::<ClusterConfiguration>(cluster.id).await?.unwrap();
+        assert_eq!(cluster.devices, HashSet::from([peer_a.topology.devices[0].id, peer_b.topology.devices[0].id]));
+
+        assert_eq!(resources_manager.get::<PeerGroup>(peer_group.id).await?, Some(peer_group));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_update_deployed_clusters_when_confirmed() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer_a = generate_peer_descriptor()?;
+        resources_manager.insert(peer_a.id, peer_a.clone()).await?;
+        let peer_b = generate_peer_descriptor()?;
+        resources_manager.insert(peer_b.id, peer_b.clone()).await?;
+
+        let peer_group = peer_group(HashSet::from([peer_a.id, peer_b.id]))?;
+        store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: peer_group.clone(), propagate_to_deployed_clusters: false }).await?;
+
+        let cluster = ClusterConfiguration {
+            devices: HashSet::from([peer_a.topology.devices[0].id, peer_b.topology.devices[0].id]),
+            ..cluster_configuration(&peer_a, &peer_group)?
+        };
+        resources_manager.insert(cluster.id, cluster.clone()).await?;
+        resources_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+
+        let changed_peer_group = PeerGroup { peers: HashSet::from([peer_a.id]), ..peer_group.clone() };
+
+        let outcome = store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: changed_peer_group.clone(), propagate_to_deployed_clusters: false }).await?;
+        assert!(outcome.applied_changes.is_empty());
+        assert_eq!(outcome.skipped_changes.len(), 1);
+        assert_eq!(resources_manager.get::<ClusterConfiguration>(cluster.id).await?, Some(cluster.clone()));
+
+        store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: peer_group.clone(), propagate_to_deployed_clusters: false }).await?;
+
+        let outcome = store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: changed_peer_group, propagate_to_deployed_clusters: true }).await?;
+        assert_eq!(outcome.applied_changes.len(), 1);
+        assert!(outcome.skipped_changes.is_empty());
+
+        let cluster = resources_manager.get::<ClusterConfiguration>(cluster.id).await?.unwrap();
+        assert_eq!(cluster.devices, HashSet::from([peer_a.topology.devices[0].id]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_unknown_peers() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer_group = peer_group(HashSet::from([PeerId::random()]))?;
+        let result = store_peer_group(StorePeerGroupParams { resources_manager: resources_manager.clone(), peer_group: peer_group.clone(), propagate_to_deployed_clusters: false }).await;
+
+        assert!(matches!(result, Err(StorePeerGroupError::PeerNotFound { .. })));
+        assert_eq!(resources_manager.get::<PeerGroup>(peer_group.id).await?, None);
+
+        Ok(())
+    }
+
+    fn peer_group(peers: HashSet<PeerId>) -> anyhow::Result<PeerGroup> {
+        Ok(PeerGroup {
+            id: PeerGroupId::random(),
+            name: PeerGroupName::try_from("rack-01")?,
+            labels: HashSet::from([PeerGroupLabel::try_from("hil")?]),
+            peers,
+        })
+    }
+
+    fn cluster_configuration(leader: &PeerDescriptor, peer_group: &PeerGroup) -> anyhow::Result<ClusterConfiguration> {
+        Ok(ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: leader.id,
+            devices: HashSet::from([leader.topology.devices[0].id]),
+            peer_groups: HashSet::from([peer_group.id]),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
+        })
+    }
+}
@@ -2,12 +2,12 @@ use crate::peer::broker::PeerMessagingBrokerRef;
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::resources::storage::ResourcesStorageApi;
-use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration};
 use opendut_types::cluster::ClusterAssignment;
 use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
 use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::peer::ethernet::EthernetBridge;
+use opendut_types::peer::restbus::RestbusChannel;
 use opendut_types::util::net::NetworkInterfaceName;
 
 pub struct AssignClusterParams {
@@ -65,6 +65,14 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
                 peer_configuration.insert(bridge, ParameterTarget::Present); //TODO not always Present
             }
 
+            for channel in RestbusChannel::from_devices(&peer_descriptor.topology.devices, &peer_descriptor.network.interfaces) {
+                peer_configuration.insert(channel, ParameterTarget::Present); //TODO not always Present
+            }
+
+            for hook in Clone::clone(&peer_descriptor.cluster_hooks) {
+                peer_configuration.insert(hook, ParameterTarget::Present); //TODO not always Present
+            }
+
             peer_configuration
         };
 
@@ -82,18 +90,23 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
                 })
                     .map_err(|source| AssignClusterError::Persistence { peer_id, source })?;
             }
+            PeerState::Unreachable { remote_host, .. } => {
+                resources.insert(peer_id, PeerState::Unreachable {
+                    inner: PeerUpState::Blocked(PeerBlockedState::Member),
+                    remote_host,
+                })
+                    .map_err(|source| AssignClusterError::Persistence { peer_id, source })?;
+            }
         }
 
         Ok((old_peer_configuration, peer_configuration))
     }).await
     .map_err(|source| AssignClusterError::Persistence { peer_id, source })??;
 
-    params.peer_messaging_broker.send_to_peer(
+    params.peer_messaging_broker.send_peer_configuration(
         peer_id,
-        downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
-            old_configuration: Some(old_peer_configuration.into()),
-            configuration: Some(peer_configuration.into()),
-        }),
+        old_peer_configuration,
+        peer_configuration,
     ).await
     .map_err(|cause| AssignClusterError::SendingToPeerFailed {
         peer_id,
@@ -107,7 +120,8 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
 mod tests {
     use super::*;
     use crate::actions::peers::testing::{fixture, Fixture};
-    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration};
+    use crate::peer::broker::{PeerConnection, PeerMessagingBroker, PeerMessagingBrokerOptions};
     use crate::resources::manager::ResourcesManager;
     use googletest::prelude::*;
     use opendut_types::cluster::{ClusterAssignment, ClusterId};
@@ -142,7 +156,7 @@ mod tests {
             resources.insert(peer_id, Clone::clone(&peer_configuration))
         }).await??;
 
-        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, PeerConnection::default()).await?;
         let received = receiver.recv().await.unwrap()
             .message.unwrap();
         assert_that!(
@@ -150,6 +164,7 @@ mod tests {
             eq(&downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
                 old_configuration: Some(Clone::clone(&old_peer_configuration).into()),
                 configuration: Some(Clone::clone(&peer_configuration).into()),
+                configuration_version: 1,
             }))
         );
 
@@ -183,6 +198,8 @@ mod tests {
         let mut peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            restbus_channels: vec![],
+            cluster_hooks: vec![],
         };
         peer_configuration.insert(EthernetBridge { name: NetworkInterfaceName::try_from("br-opendut-1")? }, ParameterTarget::Present);
 
@@ -190,7 +207,7 @@ mod tests {
             .message.unwrap();
 
         let downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
-            old_configuration, configuration
+            old_configuration, configuration, ..
         }) = received else { panic!() };
 
         assert_that!(OldPeerConfiguration::try_from(old_configuration.unwrap())?, eq(&old_peer_configuration));
@@ -213,7 +230,11 @@ mod tests {
             },
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         }
     }
 }
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use crate::actions::peers::generate_provisioning_tokens::hash_secret;
+use crate::actions::{generate_peer_setup, store_peer_descriptor, GeneratePeerSetupParams, PeerSetupOptions, StorePeerDescriptorParams};
+use crate::persistence::error::PersistenceError;
+use crate::policy::PolicyEngineRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use crate::vpn::Vpn;
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_auth::registration::resources::UserId;
+use opendut_carl_api::carl::peer::ClaimProvisioningTokenError;
+use opendut_types::peer::executor::ExecutorDescriptors;
+use opendut_types::peer::provisioning::{ProvisioningToken, ProvisioningTokenId};
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerNetworkDescriptor, PeerSetup};
+use opendut_types::topology::Topology;
+use pem::Pem;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+pub struct ClaimProvisioningTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub policy_engine: PolicyEngineRef,
+    pub vpn: Vpn,
+    pub carl_url: Url,
+    pub ca: Pem,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub setup_options: PeerSetupOptions,
+    pub token_id: ProvisioningTokenId,
+    pub secret: String,
+    /// Hostname of the device, which is used as name of the peer, if it is a valid peer name.
+    pub hostname: String,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn claim_provisioning_token(params: ClaimProvisioningTokenParams) -> Result<(PeerId, PeerSetup), ClaimProvisioningTokenError> {
+
+    async fn inner(params: ClaimProvisioningTokenParams) -> Result<(PeerId, PeerSetup), ClaimProvisioningTokenError> {
+
+        let ClaimProvisioningTokenParams { resources_manager, policy_engine, vpn, carl_url, ca, oidc_registration_client, setup_options, token_id, secret, hostname } = params;
+
+        debug!("Claiming provisioning token <{token_id}>.");
+
+        let peer_id = PeerId::random();
+        let internal_error = |cause: PersistenceError| ClaimProvisioningTokenError::Internal { token: token_id, cause: cause.to_string() };
+
+        //mark the token as claimed first, so that concurrent claims of the same token cannot create multiple peers
+        let token = resources_manager.resources_mut(|resources| {
+            let token = resources.get::<ProvisioningToken>(token_id)
+                .map_err(internal_error)?
+                .filter(|token| token.secret_hash == hash_secret(&secret))
+                .ok_or(ClaimProvisioningTokenError::InvalidToken { token: token_id })?;
+
+            if token.is_claimed() {
+                return Err(ClaimProvisioningTokenError::AlreadyClaimed { token: token_id });
+            }
+
+            let claimed = ProvisioningToken {
+                claimed_by: Some(peer_id),
+                ..token
+            };
+            resources.insert(token_id, Clone::clone(&claimed))
+                .map_err(internal_error)?;
+            Ok(claimed)
+        }).await
+        .map_err(internal_error)??;
+
+        let peer_name = PeerName::try_from(hostname.as_str())
+            .or_else(|cause| {
+                let fallback = PeerName::try_from(format!("peer-{}", &peer_id.uuid.simple().to_string()[..8]));
+                warn!("Hostname '{hostname}' cannot be used as name for the peer claiming provisioning token <{token_id}>: {cause}");
+                fallback
+            })
+            .map_err(|cause| ClaimProvisioningTokenError::Internal { token: token_id, cause: cause.to_string() })?;
+
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: peer_name,
+            location: Clone::clone(&token.template.location),
+            network: PeerNetworkDescriptor::default(),
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Clone::clone(&token.template.labels),
+            annotations: Default::default(),
+            hardware: None,
+            cluster_hooks: vec![],
+        };
+
+        let stored = store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine,
+            vpn: Clone::clone(&vpn),
+            peer_descriptor,
+        }).await;
+
+        if let Err(cause) = stored {
+            debug!("Releasing provisioning token <{token_id}>, since the peer could not be created.");
+            let released = ProvisioningToken {
+                claimed_by: None,
+                ..Clone::clone(&token)
+            };
+            if let Err(cause) = resources_manager.insert(token_id, released).await {
+                error!("Failed to release provisioning token <{token_id}> after the peer could not be created: {cause}");
+            }
+            return Err(ClaimProvisioningTokenError::Internal { token: token_id, cause: cause.to_string() });
+        }
+
+        let setup = generate_peer_setup(GeneratePeerSetupParams {
+            resources_manager,
+            peer: peer_id,
+            carl_url,
+            ca,
+            vpn,
+            oidc_registration_client,
+            user_id: UserId { value: token.created_by },
+            setup_options,
+        }).await
+        .map_err(|cause| ClaimProvisioningTokenError::Internal { token: token_id, cause: cause.to_string() })?;
+
+        info!("Successfully claimed provisioning token <{token_id}> of pool '{}' by peer <{peer_id}>.", token.pool);
+
+        Ok((peer_id, setup))
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use opendut_types::peer::label::{LabelKey, LabelValue};
+    use opendut_types::peer::provisioning::{PeerProvisioning, PeerTemplate};
+    use opendut_types::peer::PeerLocation;
+    use opendut_types::util::net::AuthConfig;
+
+    use super::*;
+    use crate::actions::{generate_provisioning_tokens, GenerateProvisioningTokensParams};
+    use crate::resources::manager::ResourcesManager;
+
+    #[tokio::test]
+    async fn should_create_peer_from_template_when_claiming_a_provisioning_token() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let template = PeerTemplate {
+            location: Some(PeerLocation::try_from("Plant Ingolstadt")?),
+            labels: [(LabelKey::try_from("fleet")?, LabelValue::try_from("test-vehicles")?)].into(),
+        };
+        let provisioning = generate_token(Arc::clone(&resources_manager), Clone::clone(&template)).await?;
+
+        let (peer_id, setup) = claim_provisioning_token(claim_params(Arc::clone(&resources_manager), &provisioning, "ecu-4711")).await?;
+
+        assert_that!(setup.id, eq(peer_id));
+        assert_that!(setup.auth_config, eq(&AuthConfig::Disabled));
+
+        let peer = resources_manager.get::<PeerDescriptor>(peer_id).await?
+            .expect("Peer should have been created.");
+        assert_that!(peer.name, eq(&PeerName::try_from("ecu-4711")?));
+        assert_that!(peer.location, eq(&template.location));
+        assert_that!(peer.labels, eq(&template.labels));
+
+        let token = resources_manager.get::<ProvisioningToken>(provisioning.token).await?
+            .expect("Token should still exist.");
+        assert_that!(token.claimed_by, some(eq(peer_id)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_claim_a_provisioning_token_twice() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let provisioning = generate_token(Arc::clone(&resources_manager), PeerTemplate::default()).await?;
+
+        claim_provisioning_token(claim_params(Arc::clone(&resources_manager), &provisioning, "ecu-4711")).await?;
+        let result = claim_provisioning_token(claim_params(Arc::clone(&resources_manager), &provisioning, "ecu-4712")).await;
+
+        assert!(matches!(result, Err(ClaimProvisioningTokenError::AlreadyClaimed { .. })));
+        assert_that!(resources_manager.list::<PeerDescriptor>().await?.len(), eq(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_provisioning_token_with_wrong_secret() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let provisioning = generate_token(Arc::clone(&resources_manager), PeerTemplate::default()).await?;
+        let provisioning = PeerProvisioning {
+            secret: String::from("guessed"),
+            ..provisioning
+        };
+
+        let result = claim_provisioning_token(claim_params(Arc::clone(&resources_manager), &provisioning, "ecu-4711")).await;
+
+        assert!(matches!(result, Err(ClaimProvisioningTokenError::InvalidToken { .. })));
+        assert!(resources_manager.list::<PeerDescriptor>().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_generated_peer_name_for_invalid_hostnames() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let provisioning = generate_token(Arc::clone(&resources_manager), PeerTemplate::default()).await?;
+
+        let (peer_id, _) = claim_provisioning_token(claim_params(Arc::clone(&resources_manager), &provisioning, "")).await?;
+
+        let peer = resources_manager.get::<PeerDescriptor>(peer_id).await?
+            .expect("Peer should have been created.");
+        assert_that!(peer.name.to_string(), starts_with("peer-"));
+
+        Ok(())
+    }
+
+    async fn generate_token(resources_manager: ResourcesManagerRef, template: PeerTemplate) -> anyhow::Result<PeerProvisioning> {
+        let mut provisionings = generate_provisioning_tokens(GenerateProvisioningTokensParams {
+            resources_manager,
+            pool: String::from("batch-2024-10"),
+            count: 1,
+            template,
+            carl_url: Url::parse("https://example.com:1234")?,
+            ca: Pem::new("Test Tag", vec![]),
+            user_id: String::from("testUser"),
+        }).await?;
+        Ok(provisionings.remove(0))
+    }
+
+    fn claim_params(resources_manager: ResourcesManagerRef, provisioning: &PeerProvisioning, hostname: &str) -> ClaimProvisioningTokenParams {
+        ClaimProvisioningTokenParams {
+            resources_manager,
+            policy_engine: Default::default(),
+            vpn: Vpn::Disabled,
+            carl_url: Clone::clone(&provisioning.carl),
+            ca: Clone::clone(&provisioning.ca.0),
+            oidc_registration_client: None,
+            setup_options: Default::default(),
+            token_id: provisioning.token,
+            secret: Clone::clone(&provisioning.secret),
+            hostname: String::from(hostname),
+        }
+    }
+}
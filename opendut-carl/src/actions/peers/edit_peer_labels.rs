@@ -0,0 +1,180 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::{EditPeerLabelsError, EditedPeerLabels};
+use opendut_types::peer::label::{AnnotationChange, LabelChange, LabelSelector};
+use opendut_types::peer::PeerDescriptor;
+use tracing::{debug, error, info};
+
+pub struct EditPeerLabelsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub selector: LabelSelector,
+    pub label_changes: Vec<LabelChange>,
+    pub annotation_changes: Vec<AnnotationChange>,
+    pub preview: bool,
+}
+
+/// Applies the label and annotation changes to all peers matching the selector within one transaction.
+/// In preview mode, the resulting labels are only computed and returned, without storing them.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn edit_peer_labels(params: EditPeerLabelsParams) -> Result<Vec<EditedPeerLabels>, EditPeerLabelsError> {
+
+    async fn inner(params: EditPeerLabelsParams) -> Result<Vec<EditedPeerLabels>, EditPeerLabelsError> {
+
+        let EditPeerLabelsParams { resources_manager, selector, label_changes, annotation_changes, preview } = params;
+
+        debug!("Editing labels of peers matching selector '{selector}'.");
+
+        let edited_peers = resources_manager.resources_mut(|resources| {
+            let peers = resources.list::<PeerDescriptor>()
+                .map_err(|cause| EditPeerLabelsError::Internal { cause: cause.to_string() })?
+                .into_iter()
+                .filter(|peer| selector.matches(&peer.labels))
+                .collect::<Vec<_>>();
+
+            let mut edited_peers = Vec::with_capacity(peers.len());
+
+            for mut peer in peers {
+                let mut labels = Clone::clone(&peer.labels);
+                for change in &label_changes {
+                    change.apply(&mut labels);
+                }
+                let mut annotations = Clone::clone(&peer.annotations);
+                for change in &annotation_changes {
+                    change.apply(&mut annotations);
+                }
+
+                let changed = labels != peer.labels || annotations != peer.annotations;
+
+                edited_peers.push(EditedPeerLabels {
+                    peer_id: peer.id,
+                    peer_name: Clone::clone(&peer.name),
+                    labels: Clone::clone(&labels),
+                    annotations: Clone::clone(&annotations),
+                    changed,
+                });
+
+                if changed && !preview {
+                    peer.labels = labels;
+                    peer.annotations = annotations;
+                    resources.insert(peer.id, peer)
+                        .map_err(|cause| EditPeerLabelsError::Internal { cause: cause.to_string() })?;
+                }
+            }
+            Ok(edited_peers)
+        }).await
+        .map_err(|cause| EditPeerLabelsError::Internal { cause: cause.to_string() })??;
+
+        if !preview {
+            let changed_count = edited_peers.iter().filter(|peer| peer.changed).count();
+            info!("Successfully edited labels of {changed_count} of {} peer(s) matching selector '{selector}'.", edited_peers.len());
+        }
+
+        Ok(edited_peers)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::peer::label::{LabelKey, LabelValue, PeerLabels};
+    use opendut_types::peer::{PeerId, PeerNetworkDescriptor};
+
+    use super::*;
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::resources::manager::ResourcesManager;
+
+    async fn store_peer(resources_manager: &ResourcesManagerRef, fixture: &Fixture, location: &str) -> anyhow::Result<PeerId> {
+        let peer_descriptor = PeerDescriptor {
+            id: PeerId::random(),
+            labels: PeerLabels::from([
+                (LabelKey::try_from("location")?, LabelValue::try_from(location)?),
+            ]),
+            topology: Default::default(),
+            network: PeerNetworkDescriptor { interfaces: vec![], bridge_name: None },
+            ..Clone::clone(&fixture.peer_a_descriptor)
+        };
+        let peer_id = peer_descriptor.id;
+
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor,
+        }).await?;
+        Ok(peer_id)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_add_label_to_matching_peers_in_memory(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        should_add_label_to_matching_peers(resources_manager, fixture).await
+    }
+
+    #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_add_label_to_matching_peers_in_database(fixture: Fixture) -> anyhow::Result<()> {
+        let db = crate::persistence::database::testing::spawn_and_connect_resources_manager().await?;
+        should_add_label_to_matching_peers(db.resources_manager, fixture).await
+    }
+
+    async fn should_add_label_to_matching_peers(resources_manager: ResourcesManagerRef, fixture: Fixture) -> anyhow::Result<()> {
+        let peer_in_hall3 = store_peer(&resources_manager, &fixture, "hall3").await?;
+        let peer_in_hall4 = store_peer(&resources_manager, &fixture, "hall4").await?;
+
+        let rack = LabelKey::try_from("rack")?;
+        let seven = LabelValue::try_from("7")?;
+
+        let edited_peers = edit_peer_labels(EditPeerLabelsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            selector: LabelSelector::from_str("location=hall3")?,
+            label_changes: vec![LabelChange::Set { key: Clone::clone(&rack), value: Clone::clone(&seven) }],
+            annotation_changes: vec![],
+            preview: false,
+        }).await?;
+
+        assert_that!(edited_peers.len(), eq(1));
+        assert_that!(edited_peers[0].peer_id, eq(peer_in_hall3));
+        assert_that!(edited_peers[0].changed, eq(true));
+
+        let labels_in_hall3 = resources_manager.get::<PeerDescriptor>(peer_in_hall3).await?.unwrap().labels;
+        assert_that!(labels_in_hall3.get(&rack), some(eq(&seven)));
+        let labels_in_hall4 = resources_manager.get::<PeerDescriptor>(peer_in_hall4).await?.unwrap().labels;
+        assert_that!(labels_in_hall4.get(&rack), none());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_store_changes_in_preview_mode(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_id = store_peer(&resources_manager, &fixture, "hall3").await?;
+
+        let edited_peers = edit_peer_labels(EditPeerLabelsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            selector: LabelSelector::from_str("location")?,
+            label_changes: vec![LabelChange::Remove { key: LabelKey::try_from("location")? }],
+            annotation_changes: vec![],
+            preview: true,
+        }).await?;
+
+        assert_that!(edited_peers.len(), eq(1));
+        assert_that!(edited_peers[0].labels.is_empty(), eq(true));
+        assert_that!(edited_peers[0].changed, eq(true));
+
+        let labels = resources_manager.get::<PeerDescriptor>(peer_id).await?.unwrap().labels;
+        assert_that!(labels.len(), eq(1));
+        Ok(())
+    }
+}
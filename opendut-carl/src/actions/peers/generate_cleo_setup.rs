@@ -1,5 +1,5 @@
 use opendut_auth::registration::client::RegistrationClientRef;
-use opendut_auth::registration::resources::UserId;
+use opendut_auth::registration::resources::{ResourceKind, UserId};
 use opendut_types::cleo::{CleoId, CleoSetup};
 use opendut_types::util::net::{AuthConfig, Certificate};
 use pem::Pem;
@@ -38,7 +38,7 @@ pub async fn generate_cleo_setup(params: GenerateCleoSetupParams) -> Result<Cleo
                 let resource_id = cleo_id.into();
                 debug!("Generating OIDC client for CLEO: <{cleo_id}>.");
                 let issuer_url = registration_client.config.issuer_remote_url.clone();
-                let client_credentials = registration_client.register_new_client_for_user(resource_id, params.user_id)
+                let client_credentials = registration_client.register_new_client_for_user(resource_id, ResourceKind::Cleo, params.user_id)
                     .await
                     .map_err(|cause| GenerateCleoSetupError::Internal { cause: cause.to_string() })?;
                 debug!("Successfully generated cleo setup with id <{cleo_id}>. OIDC client_id='{}'.", client_credentials.client_id.clone().value());
@@ -1,8 +1,11 @@
+use std::time::{Duration, SystemTime};
+
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 use opendut_auth::registration::client::RegistrationClientRef;
-use opendut_auth::registration::resources::UserId;
+use opendut_auth::registration::resources::{ResourceKind, UserId};
+use opendut_types::peer::setup::PeerSetupIssuance;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerSetup};
 use opendut_types::util::net::{AuthConfig, Certificate};
 use opendut_types::vpn::VpnPeerConfiguration;
@@ -18,6 +21,21 @@ pub struct GeneratePeerSetupParams {
     pub vpn: Vpn,
     pub oidc_registration_client: Option<RegistrationClientRef>,
     pub user_id: UserId,
+    pub setup_options: PeerSetupOptions,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PeerSetupOptions {
+    /// Time after which a Setup-String is rejected, unless the peer connected with it before. `None`, if Setup-Strings do not expire.
+    pub ttl: Option<Duration>,
+}
+impl PeerSetupOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let ttl = config.get::<u64>("peer.setup.ttl.ms")?;
+        Ok(Self {
+            ttl: (ttl > 0).then_some(Duration::from_millis(ttl)),
+        })
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -40,6 +58,7 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
     async fn inner(params: GeneratePeerSetupParams) -> Result<PeerSetup, GeneratePeerSetupError> {
 
         let peer_id = params.peer;
+        let issued_by = Clone::clone(&params.user_id.value);
 
         debug!("Generating PeerSetup for peer <{peer_id}>");
 
@@ -67,9 +86,15 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
             }
             Some(registration_client) => {
                 let resource_id = peer_id.into();
+                if registration_client.config.peer_credentials.is_none() {
+                    debug!("Deleting previous OIDC clients of peer '{peer_name}' <{peer_id}>, so that the credentials of previous Setup-Strings become invalid.");
+                    registration_client.delete_client_by_resource_id(resource_id)
+                        .await
+                        .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+                }
                 debug!("Generating OIDC client for peer '{peer_name}' <{peer_id}>.");
                 let issuer_url = registration_client.config.issuer_remote_url.clone();
-                let client_credentials = registration_client.register_new_client_for_user(resource_id, params.user_id)
+                let client_credentials = registration_client.register_new_client_for_user(resource_id, ResourceKind::Peer, params.user_id)
                     .await
                     .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
                 debug!("Successfully generated peer setup for peer '{peer_name}' <{peer_id}>. OIDC client_id='{}'.", client_credentials.client_id.clone().value());
@@ -77,12 +102,27 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
             }
         };
 
+        let issued_at = SystemTime::now();
+        let expires_at = params.setup_options.ttl.map(|ttl| issued_at + ttl);
+
+        let issuance = PeerSetupIssuance {
+            peer_id,
+            issued_by,
+            issued_at,
+            expires_at,
+            used_at: None,
+            revoked_at: None,
+        };
+        params.resources_manager.insert(peer_id, issuance).await
+            .map_err(|source| GeneratePeerSetupError::Persistance { peer_id, source })?;
+
         Ok(PeerSetup {
             id: peer_id,
             carl: params.carl_url,
             ca: Certificate(params.ca),
             auth_config,
             vpn: vpn_config,
+            expires_at,
         })
     }
 
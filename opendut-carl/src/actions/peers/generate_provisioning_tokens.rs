@@ -0,0 +1,86 @@
+use std::time::SystemTime;
+
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::GenerateProvisioningTokensError;
+use opendut_types::peer::provisioning::{PeerProvisioning, PeerTemplate, ProvisioningToken, ProvisioningTokenId};
+use opendut_types::util::net::Certificate;
+use pem::Pem;
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info};
+use url::Url;
+use uuid::Uuid;
+
+/// Upper limit of tokens per request, to keep a single transaction and response reasonably small.
+pub const MAX_PROVISIONING_TOKENS_PER_REQUEST: u32 = 1000;
+
+pub struct GenerateProvisioningTokensParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub pool: String,
+    pub count: u32,
+    pub template: PeerTemplate,
+    pub carl_url: Url,
+    pub ca: Pem,
+    pub user_id: String,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn generate_provisioning_tokens(params: GenerateProvisioningTokensParams) -> Result<Vec<PeerProvisioning>, GenerateProvisioningTokensError> {
+
+    async fn inner(params: GenerateProvisioningTokensParams) -> Result<Vec<PeerProvisioning>, GenerateProvisioningTokensError> {
+
+        let GenerateProvisioningTokensParams { resources_manager, pool, count, template, carl_url, ca, user_id } = params;
+
+        if count == 0 || count > MAX_PROVISIONING_TOKENS_PER_REQUEST {
+            return Err(GenerateProvisioningTokensError::IllegalCount { pool, count, max_count: MAX_PROVISIONING_TOKENS_PER_REQUEST });
+        }
+
+        debug!("Generating {count} provisioning tokens for pool '{pool}'.");
+
+        let created_at = SystemTime::now();
+        let (tokens, provisionings): (Vec<_>, Vec<_>) = (0..count)
+            .map(|_| {
+                let id = ProvisioningTokenId::random();
+                let secret = Uuid::new_v4().simple().to_string();
+                let token = ProvisioningToken {
+                    id,
+                    pool: Clone::clone(&pool),
+                    template: Clone::clone(&template),
+                    secret_hash: hash_secret(&secret),
+                    created_by: Clone::clone(&user_id),
+                    created_at,
+                    claimed_by: None,
+                };
+                let provisioning = PeerProvisioning {
+                    token: id,
+                    secret,
+                    carl: Clone::clone(&carl_url),
+                    ca: Certificate(Clone::clone(&ca)),
+                };
+                (token, provisioning)
+            })
+            .unzip();
+
+        let internal_error = |cause: PersistenceError| GenerateProvisioningTokensError::Internal { pool: Clone::clone(&pool), cause: cause.to_string() };
+
+        resources_manager.resources_mut(|resources| {
+            tokens.into_iter()
+                .try_for_each(|token| resources.insert(token.id, token))
+                .map_err(internal_error)
+        }).await
+        .map_err(internal_error)??;
+
+        info!("Successfully generated {count} provisioning tokens for pool '{pool}'.");
+
+        Ok(provisionings)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Only the hash of a secret is stored, so that the tokens cannot be redeemed by someone who obtains a copy of the database.
+pub(crate) fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,188 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::broker::{ExecutorSecret, GetExecutorSecretsError};
+use opendut_types::peer::executor::{ExecutorId, ExecutorKind};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct GetExecutorSecretsParams {
+    pub peer_id: PeerId,
+    pub executor_id: ExecutorId,
+    pub resources_manager: ResourcesManagerRef,
+    pub options: ExecutorSecretsOptions,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_executor_secrets(params: GetExecutorSecretsParams) -> Result<Vec<ExecutorSecret>, GetExecutorSecretsError> {
+
+    async fn inner(params: GetExecutorSecretsParams) -> Result<Vec<ExecutorSecret>, GetExecutorSecretsError> {
+
+        let GetExecutorSecretsParams { peer_id, executor_id, resources_manager, options } = params;
+
+        debug!("Retrieving secrets of executor <{executor_id}> for peer <{peer_id}>.");
+
+        let peer_descriptor = resources_manager.get::<PeerDescriptor>(peer_id).await
+            .map_err(|cause| GetExecutorSecretsError::Internal { peer_id, cause: cause.to_string() })?
+            .ok_or(GetExecutorSecretsError::ExecutorNotFound { peer_id, executor_id })?;
+
+        let executor = peer_descriptor.executors.executors.into_iter()
+            .find(|executor| executor.id == executor_id)
+            .ok_or(GetExecutorSecretsError::ExecutorNotFound { peer_id, executor_id })?;
+
+        let secret_names = match executor.kind {
+            ExecutorKind::Executable => Vec::new(),
+            ExecutorKind::Container { secrets, .. } => secrets,
+        };
+
+        if secret_names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let directory = options.directory
+            .ok_or_else(|| GetExecutorSecretsError::Internal {
+                peer_id,
+                cause: format!("Executor <{executor_id}> references secrets, but no directory for executor secrets is configured."),
+            })?;
+
+        let mut secrets = Vec::with_capacity(secret_names.len());
+        for name in secret_names {
+            let path = directory.join(name.value());
+            let value = tokio::fs::read(&path).await
+                .map_err(|cause| match cause.kind() {
+                    ErrorKind::NotFound => GetExecutorSecretsError::SecretNotFound { executor_id, secret: Clone::clone(&name) },
+                    _ => GetExecutorSecretsError::Internal { peer_id, cause: format!("Failed to read secret file '{}': {cause}", path.display()) },
+                })?;
+            secrets.push(ExecutorSecret { name, value });
+        }
+
+        info!("Successfully retrieved {} secret(s) of executor <{executor_id}> for peer <{peer_id}>.", secrets.len());
+
+        Ok(secrets)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[derive(Clone, Debug)]
+pub struct ExecutorSecretsOptions {
+    /// Directory containing one file per secret, named like the secret.
+    pub directory: Option<PathBuf>,
+}
+impl ExecutorSecretsOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let directory = config.get_string("executor.secrets.directory")?;
+        let directory = (!directory.is_empty()).then(|| PathBuf::from(directory));
+
+        Ok(ExecutorSecretsOptions { directory })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_fs::fixture::{FileWriteStr, PathChild};
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerImage, ContainerName, ContainerSecret, Engine};
+    use opendut_types::peer::executor::ExecutorDescriptor;
+
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn container_executor(secrets: Vec<ContainerSecret>) -> ExecutorDescriptor {
+        ExecutorDescriptor {
+            id: ExecutorId::random(),
+            kind: ExecutorKind::Container {
+                engine: Engine::Docker,
+                name: ContainerName::Empty,
+                image: ContainerImage::try_from("testUrl").unwrap(),
+                volumes: vec![],
+                devices: vec![],
+                envs: vec![],
+                ports: vec![],
+                command: ContainerCommand::Default,
+                args: vec![],
+                secrets,
+                can_gateways: vec![],
+                network_attachment: Default::default(),
+            },
+            results_url: None,
+            sandbox: Default::default(),
+        }
+    }
+
+    async fn store_peer_with_executor(resources_manager: ResourcesManagerRef, fixture: Fixture, executor: ExecutorDescriptor) -> anyhow::Result<()> {
+        let mut peer_descriptor = fixture.peer_a_descriptor;
+        peer_descriptor.executors.executors.push(executor);
+
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager,
+            policy_engine: Default::default(),
+            vpn: fixture.vpn,
+            peer_descriptor,
+        }).await?;
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_read_the_referenced_secrets_from_the_configured_directory(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let secrets_directory = TempDir::new()?;
+        secrets_directory.child("db-password").write_str("hunter2")?;
+        secrets_directory.child("unreferenced").write_str("not-for-this-executor")?;
+
+        let peer_id = fixture.peer_a_id;
+        let executor = container_executor(vec![ContainerSecret::try_from("db-password")?]);
+        let executor_id = executor.id;
+        store_peer_with_executor(Arc::clone(&resources_manager), fixture, executor).await?;
+
+        let secrets = get_executor_secrets(GetExecutorSecretsParams {
+            peer_id,
+            executor_id,
+            resources_manager,
+            options: ExecutorSecretsOptions { directory: Some(secrets_directory.to_path_buf()) },
+        }).await?;
+
+        assert_that!(secrets, elements_are![eq(&ExecutorSecret {
+            name: ContainerSecret::try_from("db-password")?,
+            value: b"hunter2".to_vec(),
+        })]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_refuse_secrets_of_an_executor_which_belongs_to_another_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let secrets_directory = TempDir::new()?;
+        secrets_directory.child("db-password").write_str("hunter2")?;
+
+        let executor = container_executor(vec![ContainerSecret::try_from("db-password")?]);
+        let executor_id = executor.id;
+        store_peer_with_executor(Arc::clone(&resources_manager), fixture, executor).await?;
+
+        let other_peer_id = PeerId::random();
+        let result = get_executor_secrets(GetExecutorSecretsParams {
+            peer_id: other_peer_id,
+            executor_id,
+            resources_manager,
+            options: ExecutorSecretsOptions { directory: Some(secrets_directory.to_path_buf()) },
+        }).await;
+
+        assert_that!(result, err(eq(&GetExecutorSecretsError::ExecutorNotFound { peer_id: other_peer_id, executor_id })));
+        Ok(())
+    }
+}
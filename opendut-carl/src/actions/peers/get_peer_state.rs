@@ -77,6 +77,7 @@ mod tests {
     async fn should_get_peer_state(resources_manager: ResourcesManagerRef, fixture: Fixture) -> anyhow::Result<()> {
         actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
             vpn: fixture.vpn,
             peer_descriptor: fixture.peer_a_descriptor,
         }).await?;
@@ -111,6 +112,7 @@ mod tests {
     async fn should_throw_error_if_peer_not_found(resources_manager: ResourcesManagerRef, fixture: Fixture) -> anyhow::Result<()> {
         actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
             vpn: fixture.vpn,
             peer_descriptor: fixture.peer_a_descriptor,
         }).await?;
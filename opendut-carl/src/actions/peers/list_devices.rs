@@ -61,6 +61,7 @@ mod tests {
 
         actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
             vpn: fixture.vpn,
             peer_descriptor: fixture.peer_a_descriptor,
         }).await?;
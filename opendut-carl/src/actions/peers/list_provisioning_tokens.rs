@@ -0,0 +1,34 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::{ListProvisioningTokensError, ProvisioningTokenInfo};
+use opendut_types::peer::provisioning::ProvisioningToken;
+use tracing::{debug, error, info};
+
+pub struct ListProvisioningTokensParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_provisioning_tokens(params: ListProvisioningTokensParams) -> Result<Vec<ProvisioningTokenInfo>, ListProvisioningTokensError> {
+
+    async fn inner(params: ListProvisioningTokensParams) -> Result<Vec<ProvisioningTokenInfo>, ListProvisioningTokensError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all provisioning tokens.");
+
+        let tokens = resources_manager.resources(|resources| {
+            resources.list::<ProvisioningToken>()
+        }).await
+        .map_err(|cause| ListProvisioningTokensError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully queried all provisioning tokens.");
+
+        Ok(tokens.into_iter()
+            .map(ProvisioningTokenInfo::from)
+            .collect())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -1,15 +1,25 @@
 pub mod assign_cluster;
+pub mod claim_provisioning_token;
 pub mod delete_peer_descriptor;
+pub mod edit_peer_labels;
 pub mod generate_cleo_setup;
 pub mod generate_peer_setup;
+pub mod generate_provisioning_tokens;
+pub mod get_executor_secrets;
 pub mod get_peer_state;
 pub mod list_devices;
 pub mod list_peer_descriptors;
+pub mod list_provisioning_tokens;
+pub mod ping_peer;
+pub mod revoke_peer_setup;
+pub mod rolling_restart;
+pub mod send_peer_command;
 pub mod store_peer_descriptor;
 pub mod unassign_cluster;
+pub mod upload_self_test_report;
 
 #[cfg(test)]
-mod testing {
+pub(crate) mod testing {
     use rstest::*;
 
     use opendut_types::peer::executor::ExecutorDescriptors;
@@ -73,7 +83,11 @@ mod testing {
             },
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
         Fixture {
             vpn: Vpn::Disabled,
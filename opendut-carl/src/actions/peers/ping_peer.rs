@@ -0,0 +1,117 @@
+use std::time::{Duration, SystemTime};
+
+use opendut_carl_api::carl::peer::{PeerPing, PingPeerError};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info};
+
+use crate::peer::broker::{PeerMessagingBrokerRef, ProbeError};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct PingPeerParams {
+    pub peer_id: PeerId,
+    pub timeout: Duration,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn ping_peer(params: PingPeerParams) -> Result<PeerPing, PingPeerError> {
+
+    async fn inner(params: PingPeerParams) -> Result<PeerPing, PingPeerError> {
+
+        let PingPeerParams { peer_id, timeout, resources_manager, peer_messaging_broker } = params;
+
+        debug!("Pinging peer <{peer_id}>.");
+
+        resources_manager.get::<PeerDescriptor>(peer_id).await
+            .map_err(|cause| PingPeerError::Internal { peer_id, cause: cause.to_string() })?
+            .ok_or(PingPeerError::PeerNotFound { peer_id })?;
+
+        let result = peer_messaging_broker.probe(peer_id, timeout).await;
+        let last_seen = peer_messaging_broker.last_seen(peer_id).await;
+
+        let round_trip_time = result.map_err(|cause| match cause {
+            ProbeError::PeerNotConnected { peer_id } => PingPeerError::PeerNotConnected { peer_id, last_seen },
+            ProbeError::Timeout { peer_id, timeout } => PingPeerError::PeerNotResponding { peer_id, timeout, last_seen },
+            ProbeError::Send { peer_id, cause } => PingPeerError::Internal { peer_id, cause },
+        })?;
+
+        info!("Peer <{peer_id}> responded to ping within {} ms.", round_trip_time.as_millis());
+
+        Ok(PeerPing {
+            peer_id,
+            round_trip_time,
+            last_seen: last_seen.unwrap_or_else(SystemTime::now),
+        })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, upstream, Probe, ProbeResponse};
+
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::peer::broker::{PeerConnection, PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_distinguish_unknown_disconnected_and_responding_peers(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 },
+        );
+        let peer_id = fixture.peer_a_id;
+
+        let ping = |peer_id: PeerId| ping_peer(PingPeerParams {
+            peer_id,
+            timeout: Duration::from_secs(5),
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+        });
+
+        assert_that!(ping(peer_id).await, err(eq(&PingPeerError::PeerNotFound { peer_id })));
+
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: fixture.vpn,
+            peer_descriptor: fixture.peer_a_descriptor,
+        }).await?;
+
+        assert_that!(ping(peer_id).await, err(eq(&PingPeerError::PeerNotConnected { peer_id, last_seen: None })));
+
+        let (sender, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, PeerConnection::default()).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+
+        tokio::spawn(async move {
+            while let Some(received) = receiver.recv().await {
+                if let Some(downstream::Message::Probe(Probe { id })) = received.message {
+                    sender.send(upstream::Message::ProbeResponse(ProbeResponse { id })).await.unwrap();
+                }
+            }
+        });
+
+        let result = ping(peer_id).await?;
+        assert_that!(result.peer_id, eq(peer_id));
+        assert!(result.round_trip_time < Duration::from_secs(5));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use std::time::SystemTime;
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_carl_api::carl::peer::RevokePeerSetupError;
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info};
+
+use crate::persistence::error::PersistenceError;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct RevokePeerSetupParams {
+    pub peer_id: PeerId,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    /// Name of the user, who revokes the Setup-String, in case no Setup-String was recorded for the peer.
+    pub user_id: String,
+}
+
+/// Marks the Setup-String of the peer as revoked, so that CARL rejects the peer, until it is set up with a newly generated Setup-String.
+/// Also deletes the OIDC clients of the peer, which invalidates the credentials contained in the Setup-String, and disconnects the peer.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_peer_setup(params: RevokePeerSetupParams) -> Result<PeerId, RevokePeerSetupError> {
+
+    async fn inner(params: RevokePeerSetupParams) -> Result<PeerId, RevokePeerSetupError> {
+
+        let RevokePeerSetupParams { peer_id, resources_manager, peer_messaging_broker, oidc_registration_client, user_id } = params;
+
+        debug!("Revoking Setup-String of peer <{peer_id}>.");
+
+        let internal_error = |cause: PersistenceError| RevokePeerSetupError::Internal { peer_id, cause: cause.to_string() };
+
+        resources_manager.resources_mut(|resources| {
+            resources.get::<PeerDescriptor>(peer_id)
+                .map_err(internal_error)?
+                .ok_or(RevokePeerSetupError::PeerNotFound { peer_id })?;
+
+            let now = SystemTime::now();
+            let revoked = match resources.get::<PeerSetupIssuance>(peer_id).map_err(internal_error)? {
+                Some(issuance) => PeerSetupIssuance {
+                    revoked_at: Some(now),
+                    ..issuance
+                },
+                None => PeerSetupIssuance { //Setup-String was generated before they were recorded
+                    peer_id,
+                    issued_by: user_id,
+                    issued_at: now,
+                    expires_at: None,
+                    used_at: None,
+                    revoked_at: Some(now),
+                },
+            };
+            resources.insert(peer_id, revoked)
+                .map_err(internal_error)
+        }).await
+        .map_err(internal_error)??;
+
+        if let Some(registration_client) = oidc_registration_client {
+            debug!("Deleting OIDC clients of peer <{peer_id}>.");
+            registration_client.delete_client_by_resource_id(peer_id.into())
+                .await
+                .map_err(|cause| RevokePeerSetupError::Internal { peer_id, cause: cause.to_string() })?;
+        }
+
+        if peer_messaging_broker.terminate_stream(peer_id).await.is_ok() {
+            debug!("Terminated stream of peer <{peer_id}>, whose Setup-String was revoked.");
+        }
+
+        info!("Successfully revoked Setup-String of peer <{peer_id}>.");
+
+        Ok(peer_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_revoke_the_setup_string_of_a_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 },
+        );
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let params = |peer_id| RevokePeerSetupParams {
+            peer_id,
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            oidc_registration_client: None,
+            user_id: String::from("alice"),
+        };
+
+        revoke_peer_setup(params(fixture.peer_a_id)).await?;
+
+        let issuance = resources_manager.get::<PeerSetupIssuance>(fixture.peer_a_id).await?;
+        assert_that!(issuance.map(|issuance| issuance.is_revoked()), some(eq(true)));
+
+        let result = revoke_peer_setup(params(PeerId::random())).await;
+        assert_that!(result, err(matches_pattern!(RevokePeerSetupError::PeerNotFound { .. })));
+
+        Ok(())
+    }
+}
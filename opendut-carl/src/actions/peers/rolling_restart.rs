@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use opendut_carl_api::carl::peer::{GetMaintenanceOperationError, MaintenanceOperation, MaintenanceOperationId, MaintenancePeerState, MaintenancePeerStatus, RollingRestartSpec, StartRollingRestartError};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::peer::command::{CommandRequester, PeerCommandAuthorization, AUDIT_LOG_TARGET};
+use crate::peer::maintenance;
+use crate::peer::maintenance::{MaintenanceOperationsRef, RollingRestart};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct StartRollingRestartParams {
+    pub spec: RollingRestartSpec,
+    /// User, who started the rolling restart, or `None` if authentication is disabled.
+    pub requester: Option<CommandRequester>,
+    pub authorization: PeerCommandAuthorization,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub maintenance_operations: MaintenanceOperationsRef,
+}
+
+/// Starts restarting the given peers in the background. The progress can be queried with the returned operation's id.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn start_rolling_restart(params: StartRollingRestartParams) -> Result<MaintenanceOperation, StartRollingRestartError> {
+
+    async fn inner(params: StartRollingRestartParams) -> Result<MaintenanceOperation, StartRollingRestartError> {
+
+        let StartRollingRestartParams { spec, requester, authorization, resources_manager, peer_messaging_broker, maintenance_operations } = params;
+        let RollingRestartSpec { peers, command, max_unavailable_per_cluster, peer_timeout } = spec;
+
+        let user = requester.as_ref()
+            .map(|requester| requester.name.clone())
+            .unwrap_or_else(|| String::from("<unauthenticated>"));
+
+        if peers.is_empty() {
+            return Err(StartRollingRestartError::NoPeers);
+        }
+        if RollingRestartSpec::COMMANDS.contains(&command).not() {
+            return Err(StartRollingRestartError::IllegalCommand { command });
+        }
+        if max_unavailable_per_cluster == 0 {
+            return Err(StartRollingRestartError::IllegalMaxUnavailable { max_unavailable_per_cluster });
+        }
+
+        if let Err(required_roles) = authorization.authorize(command, requester.as_ref()) {
+            warn!(target: AUDIT_LOG_TARGET, "Rejected rolling restart with command '{command}' by user '{user}', who lacks one of the roles: {}", required_roles.join(", "));
+            return Err(StartRollingRestartError::Unauthorized { command, user, required_roles });
+        }
+
+        let mut peer_ids = Vec::<PeerId>::new();
+        for peer_id in peers {
+            if peer_ids.contains(&peer_id).not() {
+                peer_ids.push(peer_id);
+            }
+        }
+
+        let peer_clusters = resources_manager.resources(|resources| {
+            let mut peer_descriptors = Vec::new();
+            for &peer_id in &peer_ids {
+                match resources.get::<PeerDescriptor>(peer_id)? {
+                    Some(peer_descriptor) => peer_descriptors.push(peer_descriptor),
+                    None => return Ok(Err(StartRollingRestartError::PeerNotFound { peer_id })),
+                }
+            }
+
+            let mut peer_clusters = HashMap::<PeerId, Vec<ClusterId>>::new();
+            for cluster_id in resources.list_ids::<ClusterDeployment>()? {
+                let Some(cluster_configuration) = resources.get::<ClusterConfiguration>(cluster_id)? else {
+                    continue;
+                };
+                for peer in &peer_descriptors {
+                    if peer.topology.devices.iter().any(|device| cluster_configuration.devices.contains(&device.id)) {
+                        peer_clusters.entry(peer.id).or_default().push(cluster_id);
+                    }
+                }
+            }
+            Ok(Ok(peer_clusters))
+        }).await
+        .map_err(|cause| StartRollingRestartError::Internal { cause: cause.to_string() })??;
+
+        let operation = MaintenanceOperation {
+            id: MaintenanceOperationId(Uuid::new_v4()),
+            command,
+            started_at: SystemTime::now(),
+            peers: peer_ids.iter()
+                .map(|&peer_id| MaintenancePeerStatus { peer_id, state: MaintenancePeerState::Waiting })
+                .collect(),
+        };
+
+        info!(target: AUDIT_LOG_TARGET, "User '{user}' started rolling restart <{}> with command '{command}' of {} peers, restarting at most {max_unavailable_per_cluster} members of a cluster at once.", operation.id, peer_ids.len());
+
+        maintenance::spawn_rolling_restart(
+            Arc::clone(&maintenance_operations),
+            peer_messaging_broker,
+            RollingRestart {
+                operation: Clone::clone(&operation),
+                peer_clusters,
+                max_unavailable_per_cluster,
+                peer_timeout,
+                user,
+            },
+        ).await;
+
+        Ok(operation)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct GetMaintenanceOperationParams {
+    pub operation_id: MaintenanceOperationId,
+    pub maintenance_operations: MaintenanceOperationsRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_maintenance_operation(params: GetMaintenanceOperationParams) -> Result<MaintenanceOperation, GetMaintenanceOperationError> {
+    let GetMaintenanceOperationParams { operation_id, maintenance_operations } = params;
+
+    debug!("Querying progress of maintenance operation <{operation_id}>.");
+
+    maintenance_operations.get(operation_id).await
+        .ok_or(GetMaintenanceOperationError::OperationNotFound { operation_id })
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_carl_api::carl::peer::PeerCommand;
+
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::peer::maintenance::MaintenanceOperations;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_validate_rolling_restart_before_starting_it(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 },
+        );
+        let maintenance_operations = MaintenanceOperations::new();
+        let peer_id = fixture.peer_a_id;
+
+        let start = |peers: Vec<PeerId>, command: PeerCommand, max_unavailable_per_cluster: u32| start_rolling_restart(StartRollingRestartParams {
+            spec: RollingRestartSpec { peers, command, max_unavailable_per_cluster, peer_timeout: Duration::from_secs(1) },
+            requester: None,
+            authorization: Default::default(),
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            maintenance_operations: Arc::clone(&maintenance_operations),
+        });
+
+        assert_that!(start(vec![], PeerCommand::RestartService, 1).await, err(eq(&StartRollingRestartError::NoPeers)));
+        assert_that!(start(vec![peer_id], PeerCommand::CollectDiagnostics, 1).await, err(eq(&StartRollingRestartError::IllegalCommand { command: PeerCommand::CollectDiagnostics })));
+        assert_that!(start(vec![peer_id], PeerCommand::RestartService, 0).await, err(eq(&StartRollingRestartError::IllegalMaxUnavailable { max_unavailable_per_cluster: 0 })));
+        assert_that!(start(vec![peer_id], PeerCommand::RestartService, 1).await, err(eq(&StartRollingRestartError::PeerNotFound { peer_id })));
+
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: fixture.vpn,
+            peer_descriptor: fixture.peer_a_descriptor,
+        }).await?;
+
+        let operation = start(vec![peer_id, peer_id], PeerCommand::RestartService, 1).await?;
+        assert_that!(operation.peers, len(eq(1)));
+
+        let operation_id = operation.id;
+        let progress = get_maintenance_operation(GetMaintenanceOperationParams { operation_id, maintenance_operations: Arc::clone(&maintenance_operations) }).await?;
+        assert_that!(progress.id, eq(operation_id));
+
+        let unknown = MaintenanceOperationId(Uuid::new_v4());
+        assert_that!(
+            get_maintenance_operation(GetMaintenanceOperationParams { operation_id: unknown, maintenance_operations }).await,
+            err(eq(&GetMaintenanceOperationError::OperationNotFound { operation_id: unknown }))
+        );
+
+        Ok(())
+    }
+}
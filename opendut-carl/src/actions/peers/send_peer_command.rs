@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use opendut_carl_api::carl::peer::{PeerCommand, PeerCommandResult, SendPeerCommandError};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info, warn};
+
+use crate::peer::broker::{CommandError, CommandOutput, PeerMessagingBrokerRef};
+use crate::peer::command::{CommandRequester, PeerCommandAuthorization, AUDIT_LOG_TARGET};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct SendPeerCommandParams {
+    pub peer_id: PeerId,
+    pub command: PeerCommand,
+    pub timeout: Duration,
+    /// User, who sent the command, or `None` if authentication is disabled.
+    pub requester: Option<CommandRequester>,
+    pub authorization: PeerCommandAuthorization,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn send_peer_command(params: SendPeerCommandParams) -> Result<PeerCommandResult, SendPeerCommandError> {
+
+    async fn inner(params: SendPeerCommandParams) -> Result<PeerCommandResult, SendPeerCommandError> {
+
+        let SendPeerCommandParams { peer_id, command, timeout, requester, authorization, resources_manager, peer_messaging_broker } = params;
+
+        let user = requester.as_ref()
+            .map(|requester| requester.name.clone())
+            .unwrap_or_else(|| String::from("<unauthenticated>"));
+
+        debug!("Sending command '{command}' to peer <{peer_id}>.");
+
+        resources_manager.get::<PeerDescriptor>(peer_id).await
+            .map_err(|cause| SendPeerCommandError::Internal { peer_id, cause: cause.to_string() })?
+            .ok_or(SendPeerCommandError::PeerNotFound { peer_id })?;
+
+        if let Err(required_roles) = authorization.authorize(command, requester.as_ref()) {
+            warn!(target: AUDIT_LOG_TARGET, "Rejected command '{command}' to peer <{peer_id}> by user '{user}', who lacks one of the roles: {}", required_roles.join(", "));
+            return Err(SendPeerCommandError::Unauthorized { peer_id, command, user, required_roles });
+        }
+        info!(target: AUDIT_LOG_TARGET, "User '{user}' sent command '{command}' to peer <{peer_id}>.");
+
+        let start = Instant::now();
+        let result = peer_messaging_broker.send_command(peer_id, command, timeout).await;
+        let duration = start.elapsed();
+
+        match result {
+            Ok(CommandOutput { output, attachment }) => {
+                info!(target: AUDIT_LOG_TARGET, "Peer <{peer_id}> executed command '{command}' of user '{user}' within {} ms.", duration.as_millis());
+                Ok(PeerCommandResult { peer_id, command, output, attachment, duration })
+            }
+            Err(cause) => {
+                warn!(target: AUDIT_LOG_TARGET, "Command '{command}' of user '{user}' to peer <{peer_id}> failed: {cause}");
+                Err(match cause {
+                    CommandError::PeerNotConnected { peer_id } => SendPeerCommandError::PeerNotConnected { peer_id, command },
+                    CommandError::Timeout { peer_id, timeout } => SendPeerCommandError::PeerNotResponding { peer_id, command, timeout },
+                    CommandError::Failed { peer_id, cause } => SendPeerCommandError::CommandFailed { peer_id, command, cause },
+                    CommandError::Send { peer_id, cause } => SendPeerCommandError::Internal { peer_id, cause },
+                })
+            }
+        }
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, peer_command_result, upstream, PeerCommand as PeerCommandMessage, PeerCommandResult as PeerCommandResultMessage, PeerCommandSucceeded};
+
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::peer::broker::{PeerConnection, PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_only_send_authorized_commands_to_connected_peers(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 },
+        );
+        let peer_id = fixture.peer_a_id;
+
+        let mut config = config::Config::builder();
+        for command in PeerCommand::ALL {
+            config = config.set_default(format!("peer.command.roles.{}", command.name().replace('-', "_")), Vec::<String>::new())?;
+        }
+        let authorization = PeerCommandAuthorization::load(&config.set_override("peer.command.roles.reboot_host", vec!["lab-admin"])?.build()?)?;
+
+        let send = |command: PeerCommand, roles: Vec<&str>| send_peer_command(SendPeerCommandParams {
+            peer_id,
+            command,
+            timeout: Duration::from_secs(5),
+            requester: Some(CommandRequester {
+                name: String::from("alice"),
+                roles: roles.into_iter().map(String::from).collect(),
+            }),
+            authorization: Clone::clone(&authorization),
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+        });
+
+        assert_that!(send(PeerCommand::RestartService, vec![]).await, err(eq(&SendPeerCommandError::PeerNotFound { peer_id })));
+
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: fixture.vpn,
+            peer_descriptor: fixture.peer_a_descriptor,
+        }).await?;
+
+        assert_that!(
+            send(PeerCommand::RebootHost, vec!["viewer"]).await,
+            err(eq(&SendPeerCommandError::Unauthorized { peer_id, command: PeerCommand::RebootHost, user: String::from("alice"), required_roles: vec![String::from("lab-admin")] }))
+        );
+        assert_that!(send(PeerCommand::RestartService, vec![]).await, err(eq(&SendPeerCommandError::PeerNotConnected { peer_id, command: PeerCommand::RestartService })));
+
+        let (sender, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, PeerConnection::default()).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+
+        tokio::spawn(async move {
+            while let Some(received) = receiver.recv().await {
+                if let Some(downstream::Message::PeerCommand(PeerCommandMessage { id, .. })) = received.message {
+                    let outcome = peer_command_result::Outcome::Succeeded(PeerCommandSucceeded { output: String::from("rebooting"), attachment: vec![] });
+                    sender.send(upstream::Message::PeerCommandResult(PeerCommandResultMessage { id, outcome: Some(outcome) })).await.unwrap();
+                }
+            }
+        });
+
+        let result = send(PeerCommand::RebootHost, vec!["lab-admin"]).await?;
+        assert_that!(result.command, eq(PeerCommand::RebootHost));
+        assert_that!(result.output, eq("rebooting"));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
+use std::ops::Not;
+
 use crate::persistence::error::PersistenceError;
+use crate::policy::PolicyEngineRef;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 use opendut_carl_api::carl::peer::StorePeerDescriptorError;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::Project;
 use tracing::{debug, error, info, warn};
 
 pub struct StorePeerDescriptorParams {
     pub resources_manager: ResourcesManagerRef,
+    pub policy_engine: PolicyEngineRef,
     pub vpn: Vpn,
     pub peer_descriptor: PeerDescriptor,
 }
@@ -18,12 +23,28 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
 
         let peer_id = params.peer_descriptor.id;
         let peer_name = Clone::clone(&params.peer_descriptor.name);
-        let peer_descriptor = params.peer_descriptor;
+        let mut peer_descriptor = params.peer_descriptor;
         let resources_manager = params.resources_manager;
 
-        let is_new_peer = resources_manager.get::<PeerDescriptor>(peer_id).await
+        let projects = resources_manager.list::<Project>().await
             .map_err(|cause| StorePeerDescriptorError::Internal { peer_id, peer_name: peer_name.clone(), cause: cause.to_string() })?
-            .is_none();
+            .into_iter()
+            .filter(|project| project.peers.contains(&peer_id))
+            .collect::<Vec<_>>();
+
+        let violations = params.policy_engine.evaluate_peer_descriptor(&peer_descriptor, &projects).await
+            .map_err(|cause| StorePeerDescriptorError::Internal { peer_id, peer_name: peer_name.clone(), cause: cause.to_string() })?;
+        if violations.is_empty().not() {
+            return Err(StorePeerDescriptorError::PolicyViolation { peer_id, peer_name, violations });
+        }
+
+        let existing_peer_descriptor = resources_manager.get::<PeerDescriptor>(peer_id).await
+            .map_err(|cause| StorePeerDescriptorError::Internal { peer_id, peer_name: peer_name.clone(), cause: cause.to_string() })?;
+        let is_new_peer = existing_peer_descriptor.is_none();
+
+        if peer_descriptor.hardware.is_none() { //hardware capabilities are only reported by EDGAR, so keep them when the peer is edited via CLEO or LEA
+            peer_descriptor.hardware = existing_peer_descriptor.and_then(|existing| existing.hardware);
+        }
 
         if is_new_peer {
             if let Vpn::Enabled { vpn_client } = &params.vpn {
@@ -68,8 +89,11 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
 mod tests {
     use super::*;
     use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::policy::{PolicyEngine, PolicyOptions};
     use crate::resources::manager::ResourcesManager;
     use googletest::prelude::*;
+    use regex::Regex;
+    use opendut_types::peer::hardware::HardwareCapabilities;
     use opendut_types::peer::state::PeerState;
     use opendut_types::peer::PeerNetworkDescriptor;
     use opendut_types::topology::DeviceDescriptor;
@@ -97,6 +121,7 @@ mod tests {
 
         store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
             vpn: Clone::clone(&fixture.vpn),
             peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
         }).await?;
@@ -138,6 +163,7 @@ mod tests {
 
         store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
             vpn: Clone::clone(&fixture.vpn),
             peer_descriptor: Clone::clone(&changed_descriptor),
         }).await?;
@@ -145,6 +171,68 @@ mod tests {
         assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?.as_ref(), some(eq(&changed_descriptor)));
         assert_that!(resources_manager.get::<PeerState>(fixture.peer_a_id).await?.as_ref(), none());
 
+        Ok(())
+    }
+    #[rstest]
+    #[tokio::test]
+    async fn should_keep_reported_hardware_capabilities_when_storing_peer_without_them(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let hardware = HardwareCapabilities {
+            cpu_architecture: String::from("x86_64"),
+            cpu_cores: 8,
+            ..Default::default()
+        };
+        let reported_descriptor = PeerDescriptor {
+            hardware: Some(Clone::clone(&hardware)),
+            ..Clone::clone(&fixture.peer_a_descriptor)
+        };
+        store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: reported_descriptor,
+        }).await?;
+
+        let edited_descriptor = PeerDescriptor {
+            hardware: None,
+            ..Clone::clone(&fixture.peer_a_descriptor)
+        };
+        store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: edited_descriptor,
+        }).await?;
+
+        let stored_descriptor = resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?;
+        assert_that!(stored_descriptor.and_then(|descriptor| descriptor.hardware), some(eq(&hardware)));
+
+        Ok(())
+    }
+    #[rstest]
+    #[tokio::test]
+    async fn should_reject_peer_violating_naming_convention(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let policy_engine = Arc::new(PolicyEngine::new(PolicyOptions {
+            peer_name_pattern: Some(Regex::new("^(?:site-.*)$")?),
+            ..Default::default()
+        }));
+
+        let result = store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine,
+            vpn: Clone::clone(&fixture.vpn),
+            peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+        }).await;
+
+        let Err(StorePeerDescriptorError::PolicyViolation { violations, .. }) = result else {
+            panic!("Expected peer to be rejected due to a policy violation, but got: {result:?}");
+        };
+        assert_that!(violations.len(), eq(1));
+        assert_that!(violations[0].rule, eq("naming-convention"));
+        assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await?, none());
+
         Ok(())
     }
 }
@@ -36,6 +36,13 @@ pub async fn unassign_cluster(params: UnassignClusterParams) -> Result<(), Unass
                 })
                 .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?;
             }
+            PeerState::Unreachable { remote_host, .. } => {
+                resources.insert(peer_id, PeerState::Unreachable {
+                    inner: PeerUpState::Available,
+                    remote_host,
+                })
+                .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,115 @@
+use opendut_carl_api::carl::broker::UploadSelfTestReportError;
+use opendut_types::peer::configuration::validation::CheckOutcome;
+use opendut_types::peer::self_test::SelfTestReport;
+use opendut_types::peer::PeerDescriptor;
+use tracing::{debug, error, info, warn};
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct UploadSelfTestReportParams {
+    pub report: SelfTestReport,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn upload_self_test_report(params: UploadSelfTestReportParams) -> Result<(), UploadSelfTestReportError> {
+
+    async fn inner(params: UploadSelfTestReportParams) -> Result<(), UploadSelfTestReportError> {
+
+        let UploadSelfTestReportParams { report, resources_manager, peer_messaging_broker } = params;
+        let peer_id = report.peer_id;
+
+        debug!("Storing self-test report of peer <{peer_id}>.");
+
+        resources_manager.get::<PeerDescriptor>(peer_id).await
+            .map_err(|cause| UploadSelfTestReportError::Internal { peer_id, cause: cause.to_string() })?
+            .ok_or(UploadSelfTestReportError::PeerNotFound { peer_id })?;
+
+        let failed = report.checks.iter()
+            .filter(|check| matches!(check.outcome, CheckOutcome::Failed { .. }))
+            .count();
+
+        if report.passed {
+            info!("Peer <{peer_id}> passed its self-test with {} check(s).", report.checks.len());
+        } else {
+            warn!("Peer <{peer_id}> failed {failed} of {} check(s) of its self-test.", report.checks.len());
+        }
+
+        peer_messaging_broker.store_self_test_report(report).await;
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::peer::self_test::SelfTestCheck;
+    use opendut_types::peer::PeerId;
+
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::StorePeerDescriptorParams;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn report(peer_id: PeerId) -> SelfTestReport {
+        SelfTestReport::new(peer_id, SystemTime::now(), vec![
+            SelfTestCheck { stage: String::from("carl"), subject: String::from("peer is registered"), outcome: CheckOutcome::Passed },
+            SelfTestCheck { stage: String::from("devices"), subject: String::from("interface 'eth0' is up"), outcome: CheckOutcome::Failed { cause: String::from("Interface is down.") } },
+        ])
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_store_the_report_of_a_registered_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 });
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            policy_engine: Default::default(),
+            vpn: fixture.vpn,
+            peer_descriptor: fixture.peer_a_descriptor,
+        }).await?;
+
+        upload_self_test_report(UploadSelfTestReportParams {
+            report: report(fixture.peer_a_id),
+            resources_manager,
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+        }).await?;
+
+        let stored = peer_messaging_broker.self_test_report(fixture.peer_a_id).await;
+        assert_that!(stored.map(|report| report.passed), some(eq(false)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_the_report_of_an_unknown_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 });
+        let peer_id = PeerId::random();
+
+        let result = upload_self_test_report(UploadSelfTestReportParams {
+            report: report(peer_id),
+            resources_manager,
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+        }).await;
+
+        assert_that!(result, err(eq(&UploadSelfTestReportError::PeerNotFound { peer_id })));
+        assert_that!(peer_messaging_broker.self_test_report(peer_id).await, none());
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::project::{Project, ProjectId};
+use tracing::{debug, error, info};
+
+/// A peer or cluster, which can be assigned to projects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectResourceId {
+    Peer(PeerId),
+    Cluster(ClusterId),
+}
+
+impl std::fmt::Display for ProjectResourceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectResourceId::Peer(peer_id) => write!(f, "peer <{peer_id}>"),
+            ProjectResourceId::Cluster(cluster_id) => write!(f, "cluster <{cluster_id}>"),
+        }
+    }
+}
+
+pub struct AssignToProjectParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub project_id: ProjectId,
+    pub resource: ProjectResourceId,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AssignToProjectError {
+    #[error("Project <{project_id}> could not be found.")]
+    ProjectNotFound { project_id: ProjectId },
+    #[error("Assigning {resource} to project <{project_id}> failed, due to internal error:\n  {cause}")]
+    Internal { project_id: ProjectId, resource: ProjectResourceId, cause: String },
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn assign_to_project(params: AssignToProjectParams) -> Result<(), AssignToProjectError> {
+
+    async fn inner(params: AssignToProjectParams) -> Result<(), AssignToProjectError> {
+
+        let AssignToProjectParams { resources_manager, project_id, resource } = params;
+
+        debug!("Assigning {resource} to project <{project_id}>.");
+
+        let internal_error = |cause: String| AssignToProjectError::Internal { project_id, resource, cause };
+
+        resources_manager.resources_mut(|resources| {
+            let mut project = resources.get::<Project>(project_id)
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .ok_or(AssignToProjectError::ProjectNotFound { project_id })?;
+
+            match resource {
+                ProjectResourceId::Peer(peer_id) => project.peers.insert(peer_id),
+                ProjectResourceId::Cluster(cluster_id) => project.clusters.insert(cluster_id),
+            };
+
+            resources.insert(project_id, project)
+                .map_err(|cause| internal_error(cause.to_string()))
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully assigned {resource} to project <{project_id}>.");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
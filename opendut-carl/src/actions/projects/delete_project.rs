@@ -0,0 +1,36 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::project::DeleteProjectError;
+use opendut_types::project::{Project, ProjectId};
+use tracing::{debug, error, info};
+
+pub struct DeleteProjectParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub project_id: ProjectId,
+}
+
+/// Deletes the project, but keeps its peers and clusters. These become accessible to all users, unless they are assigned to another project.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_project(params: DeleteProjectParams) -> Result<Project, DeleteProjectError> {
+
+    async fn inner(params: DeleteProjectParams) -> Result<Project, DeleteProjectError> {
+
+        let DeleteProjectParams { resources_manager, project_id } = params;
+
+        debug!("Deleting project <{project_id}>.");
+
+        let project = resources_manager.resources_mut(|resources| {
+            resources.remove::<Project>(project_id)
+                .map_err(|cause| DeleteProjectError::Internal { project_id, cause: cause.to_string() })?
+                .ok_or(DeleteProjectError::NotFound { project_id })
+        }).await
+        .map_err(|cause| DeleteProjectError::Internal { project_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted project '{}' <{project_id}>.", project.name);
+
+        Ok(project)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
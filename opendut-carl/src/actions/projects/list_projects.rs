@@ -0,0 +1,32 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::project::ListProjectsError;
+use opendut_types::project::Project;
+use tracing::{debug, error, info};
+
+pub struct ListProjectsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_projects(params: ListProjectsParams) -> Result<Vec<Project>, ListProjectsError> {
+
+    async fn inner(params: ListProjectsParams) -> Result<Vec<Project>, ListProjectsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all projects.");
+
+        let projects = resources_manager.resources(|resources| {
+            resources.list::<Project>()
+        }).await
+        .map_err(|cause| ListProjectsError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully queried all projects.");
+
+        Ok(projects)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,24 @@
+pub mod assign_to_project;
+pub mod delete_project;
+pub mod list_projects;
+pub mod store_project;
+pub mod unassign_from_projects;
+
+/// Access to projects, configured in the `projects` section.
+#[derive(Clone, Debug, Default)]
+pub struct ProjectOptions {
+    /// Roles, one of which a user needs to manage projects and to access the peers and clusters of all projects. An empty list allows all users.
+    pub admin_roles: Vec<String>,
+}
+impl ProjectOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let admin_roles = config.get::<Vec<String>>("projects.admin.roles")?;
+
+        Ok(ProjectOptions { admin_roles })
+    }
+
+    pub fn is_admin(&self, roles: &[String]) -> bool {
+        self.admin_roles.is_empty()
+            || self.admin_roles.iter().any(|role| roles.contains(role))
+    }
+}
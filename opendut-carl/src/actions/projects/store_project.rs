@@ -0,0 +1,45 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::project::StoreProjectError;
+use opendut_types::project::{Project, ProjectId};
+use tracing::{debug, error, info};
+
+pub struct StoreProjectParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub project: Project,
+}
+
+/// Creates the project or replaces the project with the same ID. Names have to be unique, as users refer to projects by their name.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_project(params: StoreProjectParams) -> Result<ProjectId, StoreProjectError> {
+
+    async fn inner(params: StoreProjectParams) -> Result<ProjectId, StoreProjectError> {
+
+        let StoreProjectParams { resources_manager, project } = params;
+        let project_id = project.id;
+        let project_name = Clone::clone(&project.name);
+
+        debug!("Storing project '{project_name}' <{project_id}>.");
+
+        resources_manager.resources_mut(|resources| {
+            let name_exists = resources.list::<Project>()
+                .map_err(|cause| StoreProjectError::Internal { project_id, cause: cause.to_string() })?
+                .into_iter()
+                .any(|other| other.id != project_id && other.name == project.name);
+            if name_exists {
+                return Err(StoreProjectError::NameAlreadyExists { project_id, name: Clone::clone(&project.name) });
+            }
+
+            resources.insert(project_id, project)
+                .map_err(|cause| StoreProjectError::Internal { project_id, cause: cause.to_string() })
+        }).await
+        .map_err(|cause| StoreProjectError::Internal { project_id, cause: cause.to_string() })??;
+
+        info!("Successfully stored project '{project_name}' <{project_id}>.");
+
+        Ok(project_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
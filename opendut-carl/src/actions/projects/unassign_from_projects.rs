@@ -0,0 +1,55 @@
+use crate::actions::projects::assign_to_project::ProjectResourceId;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_types::project::Project;
+use tracing::{debug, error, info};
+
+pub struct UnassignFromProjectsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub resource: ProjectResourceId,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnassignFromProjectsError {
+    #[error("Unassigning {resource} from its projects failed, due to internal error:\n  {cause}")]
+    Internal { resource: ProjectResourceId, cause: String },
+}
+
+/// Removes a deleted peer or cluster from all projects, which it is assigned to.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn unassign_from_projects(params: UnassignFromProjectsParams) -> Result<(), UnassignFromProjectsError> {
+
+    async fn inner(params: UnassignFromProjectsParams) -> Result<(), UnassignFromProjectsError> {
+
+        let UnassignFromProjectsParams { resources_manager, resource } = params;
+
+        debug!("Unassigning {resource} from its projects.");
+
+        let internal_error = |cause: String| UnassignFromProjectsError::Internal { resource, cause };
+
+        resources_manager.resources_mut(|resources| {
+            let projects = resources.list::<Project>()
+                .map_err(|cause| internal_error(cause.to_string()))?;
+
+            for mut project in projects {
+                let removed = match resource {
+                    ProjectResourceId::Peer(peer_id) => project.peers.remove(&peer_id),
+                    ProjectResourceId::Cluster(cluster_id) => project.clusters.remove(&cluster_id),
+                };
+                if removed {
+                    resources.insert(project.id, project)
+                        .map_err(|cause| internal_error(cause.to_string()))?;
+                }
+            }
+            Ok(())
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully unassigned {resource} from its projects.");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,48 @@
+use tracing::{debug, error, info};
+
+use opendut_carl_api::carl::results::{DownloadExecutorResultError, ExecutorResultFile};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+use crate::actions::results::{results_url, ResultsStorage, ResultsStorageError, ResultsUrlLookupError};
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct DownloadExecutorResultParams {
+    pub peer_id: PeerId,
+    pub executor_id: ExecutorId,
+    pub name: String,
+    pub resources_manager: ResourcesManagerRef,
+    pub results_storage: ResultsStorage,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn download_executor_result(params: DownloadExecutorResultParams) -> Result<(ExecutorResultFile, Vec<u8>), DownloadExecutorResultError> {
+
+    async fn inner(params: DownloadExecutorResultParams) -> Result<(ExecutorResultFile, Vec<u8>), DownloadExecutorResultError> {
+
+        let DownloadExecutorResultParams { peer_id, executor_id, name, resources_manager, results_storage } = params;
+
+        debug!("Downloading result '{name}' of executor <{executor_id}> of peer <{peer_id}>.");
+
+        let results_url = results_url(&resources_manager, peer_id, executor_id).await
+            .map_err(|error| match error {
+                ResultsUrlLookupError::PeerNotFound => DownloadExecutorResultError::PeerNotFound { peer_id, executor_id },
+                ResultsUrlLookupError::ExecutorNotFound => DownloadExecutorResultError::ExecutorNotFound { peer_id, executor_id },
+                ResultsUrlLookupError::ResultsUrlNotConfigured => DownloadExecutorResultError::ResultsUrlNotConfigured { peer_id, executor_id },
+                ResultsUrlLookupError::Persistence(cause) => DownloadExecutorResultError::Storage { peer_id, executor_id, cause: cause.to_string() },
+            })?;
+
+        let (file, content) = results_storage.download(results_url.value(), &name).await
+            .map_err(|error| match error {
+                ResultsStorageError::NotFound { .. } => DownloadExecutorResultError::ResultNotFound { peer_id, executor_id, name: Clone::clone(&name) },
+                ResultsStorageError::Request { .. } => DownloadExecutorResultError::Storage { peer_id, executor_id, cause: error.to_string() },
+            })?;
+
+        info!("Successfully downloaded result '{name}' with {} bytes of executor <{executor_id}> of peer <{peer_id}>.", content.len());
+
+        Ok((file, content))
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,44 @@
+use tracing::{debug, error, info};
+
+use opendut_carl_api::carl::results::{ExecutorResultFile, ListExecutorResultsError};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+use crate::actions::results::{results_url, ResultsStorage, ResultsUrlLookupError};
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ListExecutorResultsParams {
+    pub peer_id: PeerId,
+    pub executor_id: ExecutorId,
+    pub resources_manager: ResourcesManagerRef,
+    pub results_storage: ResultsStorage,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_executor_results(params: ListExecutorResultsParams) -> Result<Vec<ExecutorResultFile>, ListExecutorResultsError> {
+
+    async fn inner(params: ListExecutorResultsParams) -> Result<Vec<ExecutorResultFile>, ListExecutorResultsError> {
+
+        let ListExecutorResultsParams { peer_id, executor_id, resources_manager, results_storage } = params;
+
+        debug!("Listing results of executor <{executor_id}> of peer <{peer_id}>.");
+
+        let results_url = results_url(&resources_manager, peer_id, executor_id).await
+            .map_err(|error| match error {
+                ResultsUrlLookupError::PeerNotFound => ListExecutorResultsError::PeerNotFound { peer_id, executor_id },
+                ResultsUrlLookupError::ExecutorNotFound => ListExecutorResultsError::ExecutorNotFound { peer_id, executor_id },
+                ResultsUrlLookupError::ResultsUrlNotConfigured => ListExecutorResultsError::ResultsUrlNotConfigured { peer_id, executor_id },
+                ResultsUrlLookupError::Persistence(cause) => ListExecutorResultsError::Storage { peer_id, executor_id, cause: cause.to_string() },
+            })?;
+
+        let files = results_storage.list(results_url.value()).await
+            .map_err(|cause| ListExecutorResultsError::Storage { peer_id, executor_id, cause: cause.to_string() })?;
+
+        info!("Successfully listed {} result file(s) of executor <{executor_id}> of peer <{peer_id}>.", files.len());
+
+        Ok(files)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
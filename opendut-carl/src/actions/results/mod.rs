@@ -0,0 +1,317 @@
+use std::time::{Duration, SystemTime};
+
+use chrono::DateTime;
+use regex::Regex;
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+use opendut_carl_api::carl::results::ExecutorResultFile;
+use opendut_types::peer::executor::{ExecutorId, ResultsUrl};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub mod download_executor_result;
+pub mod list_executor_results;
+
+/// Access of CARL to the storage, which executors upload their results to, configured in the `results.storage` section.
+#[derive(Clone, Debug)]
+pub struct ResultsStorageOptions {
+    /// Bearer token for the results storage. No authentication, if not set.
+    pub token: Option<String>,
+    pub timeout: Duration,
+}
+impl ResultsStorageOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let token = config.get_string("results.storage.token")?;
+        let token = (!token.is_empty()).then_some(token);
+        let timeout = Duration::from_millis(config.get::<u64>("results.storage.timeout.ms")?);
+
+        Ok(ResultsStorageOptions { token, timeout })
+    }
+}
+
+/// Lists and downloads the files in the results URLs of executors, which are WebDAV collections.
+#[derive(Clone, Debug)]
+pub struct ResultsStorage {
+    client: reqwest::Client,
+    token: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ResultsStorageError {
+    #[error("File '{name}' does not exist in <{collection}>.")]
+    NotFound { collection: Url, name: String },
+    #[error("Request to <{url}> failed: {cause}")]
+    Request { url: Url, cause: String },
+}
+
+impl ResultsStorage {
+    pub fn new(options: ResultsStorageOptions) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .build()?;
+
+        Ok(Self { client, token: options.token })
+    }
+
+    fn request(&self, method: Method, url: Url) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Lists the files directly within the collection. Sub-collections are skipped.
+    pub async fn list(&self, collection: &Url) -> Result<Vec<ExecutorResultFile>, ResultsStorageError> {
+        let collection = as_collection(collection);
+        let request_error = |cause: String| ResultsStorageError::Request { url: Clone::clone(&collection), cause };
+
+        let method = Method::from_bytes(b"PROPFIND").expect("PROPFIND should be a valid HTTP method.");
+        let response = self.request(method, Clone::clone(&collection))
+            .header("Depth", "1")
+            .header(reqwest::header::CONTENT_TYPE, "application/xml")
+            .body(PROPFIND_BODY)
+            .send().await
+            .map_err(|cause| request_error(cause.to_string()))?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            //nothing was uploaded yet
+            return Ok(Vec::new());
+        }
+        let body = response.error_for_status()
+            .map_err(|cause| request_error(cause.to_string()))?
+            .text().await
+            .map_err(|cause| request_error(cause.to_string()))?;
+
+        let mut files = parse_multistatus(&body, &collection)
+            .map_err(request_error)?;
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    pub async fn download(&self, collection: &Url, name: &str) -> Result<(ExecutorResultFile, Vec<u8>), ResultsStorageError> {
+        let collection = as_collection(collection);
+        let url = file_url(&collection, name)
+            .ok_or_else(|| ResultsStorageError::NotFound { collection: Clone::clone(&collection), name: name.to_owned() })?;
+
+        let response = self.request(Method::GET, Clone::clone(&url))
+            .send().await
+            .map_err(|cause| ResultsStorageError::Request { url: Clone::clone(&url), cause: cause.to_string() })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ResultsStorageError::NotFound { collection, name: name.to_owned() });
+        }
+        let response = response.error_for_status()
+            .map_err(|cause| ResultsStorageError::Request { url: Clone::clone(&url), cause: cause.to_string() })?;
+        let modified_at = response.headers().get(reqwest::header::LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(SystemTime::from);
+        let content = response.bytes().await
+            .map_err(|cause| ResultsStorageError::Request { url: Clone::clone(&url), cause: cause.to_string() })?;
+
+        let file = ExecutorResultFile {
+            name: name.to_owned(),
+            size_bytes: content.len() as u64,
+            modified_at,
+        };
+        Ok((file, content.to_vec()))
+    }
+}
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<propfind xmlns="DAV:"><prop><resourcetype/><getcontentlength/><getlastmodified/></prop></propfind>"#;
+
+pub(super) enum ResultsUrlLookupError {
+    PeerNotFound,
+    ExecutorNotFound,
+    ResultsUrlNotConfigured,
+    Persistence(PersistenceError),
+}
+
+/// Determines the results URL of the executor from the descriptor of its peer.
+pub(super) async fn results_url(resources_manager: &ResourcesManagerRef, peer_id: PeerId, executor_id: ExecutorId) -> Result<ResultsUrl, ResultsUrlLookupError> {
+    let peer_descriptor = resources_manager.get::<PeerDescriptor>(peer_id).await
+        .map_err(ResultsUrlLookupError::Persistence)?
+        .ok_or(ResultsUrlLookupError::PeerNotFound)?;
+
+    peer_descriptor.executors.executors.into_iter()
+        .find(|executor| executor.id == executor_id)
+        .ok_or(ResultsUrlLookupError::ExecutorNotFound)?
+        .results_url
+        .ok_or(ResultsUrlLookupError::ResultsUrlNotConfigured)
+}
+
+/// Ensures the trailing slash, so that the files are resolved within the collection.
+fn as_collection(url: &Url) -> Url {
+    let mut collection = Clone::clone(url);
+    if !collection.path().ends_with('/') {
+        collection.set_path(&format!("{}/", collection.path()));
+    }
+    collection
+}
+
+/// URL of a file directly within the collection. `None` for names, which would point elsewhere, like "../secret".
+fn file_url(collection: &Url, name: &str) -> Option<Url> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        return None;
+    }
+    let mut url = Clone::clone(collection);
+    url.path_segments_mut().ok()?
+        .pop_if_empty()
+        .push(name);
+    Some(url)
+}
+
+/// Extracts the files from the response of a PROPFIND request with depth 1, skipping the collection itself and sub-collections.
+/// Namespace prefixes are ignored, since servers differ in how they declare the DAV namespace.
+fn parse_multistatus(body: &str, collection: &Url) -> Result<Vec<ExecutorResultFile>, String> {
+    let element = |name: &str| Regex::new(&format!(r"(?s)<(?:[\w.-]+:)?{name}(?:\s[^>]*)?>(.*?)</(?:[\w.-]+:)?{name}\s*>"))
+        .expect("Regex for WebDAV element should be valid.");
+    let response_element = element("response");
+    let href_element = element("href");
+    let length_element = element("getcontentlength");
+    let modified_element = element("getlastmodified");
+    let collection_element = Regex::new(r"<(?:[\w.-]+:)?collection\s*/?>")
+        .expect("Regex for WebDAV collection should be valid.");
+
+    if !body.contains("multistatus") {
+        return Err(String::from("Expected a WebDAV multistatus response."));
+    }
+
+    let mut files = Vec::new();
+    for response in response_element.captures_iter(body) {
+        let response = &response[1];
+
+        if collection_element.is_match(response) {
+            continue;
+        }
+        let Some(href) = href_element.captures(response) else {
+            return Err(String::from("Response of the WebDAV server contains an entry without href."));
+        };
+        let href = unescape_xml(href[1].trim());
+        let url = collection.join(&href)
+            .map_err(|cause| format!("Invalid href '{href}' in response of the WebDAV server: {cause}"))?;
+
+        let Some(name) = url.path_segments().and_then(|mut segments| segments.next_back()).map(percent_decode) else {
+            continue;
+        };
+        if name.is_empty() {
+            continue;
+        }
+
+        let size_bytes = length_element.captures(response)
+            .and_then(|length| length[1].trim().parse::<u64>().ok())
+            .unwrap_or_default();
+        let modified_at = modified_element.captures(response)
+            .and_then(|modified| DateTime::parse_from_rfc2822(modified[1].trim()).ok())
+            .map(SystemTime::from);
+
+        files.push(ExecutorResultFile { name, size_bytes, modified_at });
+    }
+    Ok(files)
+}
+
+fn unescape_xml(value: &str) -> String {
+    value.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes.get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_list_the_files_of_a_multistatus_response() -> anyhow::Result<()> {
+        let collection = Url::parse("https://webdav.example/results/executor-1/")?;
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+            <d:multistatus xmlns:d="DAV:">
+              <d:response>
+                <d:href>/results/executor-1/</d:href>
+                <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+              </d:response>
+              <d:response>
+                <d:href>/results/executor-1/2024-11-08_10-00-00_test%20run.zip</d:href>
+                <d:propstat><d:prop>
+                  <d:resourcetype/>
+                  <d:getcontentlength>2048</d:getcontentlength>
+                  <d:getlastmodified>Fri, 08 Nov 2024 10:00:05 GMT</d:getlastmodified>
+                </d:prop></d:propstat>
+              </d:response>
+              <d:response>
+                <d:href>https://webdav.example/results/executor-1/archive/</d:href>
+                <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+              </d:response>
+              <D:response xmlns:D="DAV:">
+                <D:href>/results/executor-1/2024-11-08_10-00-00_test.log</D:href>
+                <D:propstat><D:prop><D:getcontentlength>12</D:getcontentlength></D:prop></D:propstat>
+              </D:response>
+            </d:multistatus>"#;
+
+        let files = parse_multistatus(body, &collection).map_err(anyhow::Error::msg)?;
+
+        assert_that!(files, elements_are![
+            eq(&ExecutorResultFile {
+                name: String::from("2024-11-08_10-00-00_test run.zip"),
+                size_bytes: 2048,
+                modified_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_731_060_005)),
+            }),
+            eq(&ExecutorResultFile {
+                name: String::from("2024-11-08_10-00-00_test.log"),
+                size_bytes: 12,
+                modified_at: None,
+            }),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_responses_which_are_not_multistatus() -> anyhow::Result<()> {
+        let collection = Url::parse("https://webdav.example/results/")?;
+
+        assert_that!(parse_multistatus("<html>Login</html>", &collection), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_resolve_files_within_the_collection() -> anyhow::Result<()> {
+        let collection = as_collection(&Url::parse("https://webdav.example/results/executor-1")?);
+
+        assert_that!(file_url(&collection, "result.zip").map(String::from), some(eq("https://webdav.example/results/executor-1/result.zip")));
+        assert_that!(file_url(&collection, "test run.zip").map(String::from), some(eq("https://webdav.example/results/executor-1/test%20run.zip")));
+        assert_that!(file_url(&collection, "../secret"), none());
+        assert_that!(file_url(&collection, ".."), none());
+        assert_that!(file_url(&collection, ""), none());
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::SystemTime;
+
+use crate::actions::peers::generate_provisioning_tokens::hash_secret;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountCredentials, ServiceAccountId, ServiceAccountTokenId};
+use tracing::trace;
+
+pub struct AuthenticateServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub credentials: ServiceAccountCredentials,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthenticateServiceAccountError {
+    #[error("No service account has a token <{token_id}> with a matching secret.")]
+    InvalidCredentials {
+        token_id: ServiceAccountTokenId,
+    },
+    #[error("Token <{token_id}> of service account <{service_account_id}> is expired.")]
+    Expired {
+        service_account_id: ServiceAccountId,
+        token_id: ServiceAccountTokenId,
+    },
+    #[error("Token <{token_id}> could not be checked, due to internal errors:\n  {cause}")]
+    Internal {
+        token_id: ServiceAccountTokenId,
+        cause: String,
+    },
+}
+
+/// Determines the service account, which the credentials belong to, if the token exists, the secret matches and the token is not expired.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn authenticate_service_account(params: AuthenticateServiceAccountParams) -> Result<ServiceAccount, AuthenticateServiceAccountError> {
+
+    let AuthenticateServiceAccountParams { resources_manager, credentials } = params;
+    let token_id = credentials.token;
+
+    let service_accounts = resources_manager.resources(|resources| {
+        resources.list::<ServiceAccount>()
+    }).await
+    .map_err(|cause| AuthenticateServiceAccountError::Internal { token_id, cause: cause.to_string() })?;
+
+    let secret_hash = hash_secret(&credentials.secret);
+
+    let (service_account, token) = service_accounts.into_iter()
+        .find_map(|service_account| {
+            let token = service_account.tokens.iter()
+                .find(|token| token.id == token_id && token.secret_hash == secret_hash)
+                .cloned()?;
+            Some((service_account, token))
+        })
+        .ok_or(AuthenticateServiceAccountError::InvalidCredentials { token_id })?;
+
+    if token.is_expired(SystemTime::now()) {
+        return Err(AuthenticateServiceAccountError::Expired { service_account_id: service_account.id, token_id });
+    }
+
+    trace!("Authenticated service account '{}' <{}> with token <{token_id}>.", service_account.name, service_account.id);
+
+    Ok(service_account)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::actions::service_accounts::create_service_account::{create_service_account, CreateServiceAccountParams};
+    use crate::actions::service_accounts::issue_service_account_token::{issue_service_account_token, IssueServiceAccountTokenParams};
+    use crate::actions::service_accounts::revoke_service_account_token::{revoke_service_account_token, RevokeServiceAccountTokenParams};
+    use crate::actions::service_accounts::ServiceAccountOptions;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_carl_api::carl::service_account::IssueServiceAccountTokenError;
+    use opendut_types::service_account::{ServiceAccountName, ServiceAccountScope};
+
+    #[tokio::test]
+    async fn should_authenticate_with_issued_token_until_it_is_revoked() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let options = ServiceAccountOptions {
+            admin_roles: Vec::new(),
+            max_token_validity: Duration::from_secs(24 * 60 * 60),
+        };
+
+        let service_account = create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            scopes: BTreeSet::from([ServiceAccountScope::Read]),
+            user_id: String::from("alice"),
+        }).await?;
+
+        let result = issue_service_account_token(IssueServiceAccountTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+            validity: Duration::from_secs(2 * 24 * 60 * 60),
+            read_only: false,
+            options: Clone::clone(&options),
+        }).await;
+        assert!(matches!(result, Err(IssueServiceAccountTokenError::IllegalValidity { .. })));
+
+        let issued = issue_service_account_token(IssueServiceAccountTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+            validity: Duration::from_secs(60 * 60),
+            read_only: false,
+            options,
+        }).await?;
+
+        let credentials = ServiceAccountCredentials::decode(&issued.credentials).unwrap();
+        let authenticated = authenticate_service_account(AuthenticateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            credentials: Clone::clone(&credentials),
+        }).await?;
+        assert_eq!(authenticated.id, service_account.id);
+
+        let wrong_secret = ServiceAccountCredentials { secret: String::from("guessed"), ..Clone::clone(&credentials) };
+        let result = authenticate_service_account(AuthenticateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            credentials: wrong_secret,
+        }).await;
+        assert!(matches!(result, Err(AuthenticateServiceAccountError::InvalidCredentials { .. })));
+
+        revoke_service_account_token(RevokeServiceAccountTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+            token_id: issued.token.id,
+        }).await?;
+
+        let result = authenticate_service_account(AuthenticateServiceAccountParams {
+            resources_manager,
+            credentials,
+        }).await;
+        assert!(matches!(result, Err(AuthenticateServiceAccountError::InvalidCredentials { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeSet;
+
+use crate::actions::service_accounts::now_in_millis;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::service_account::{CreateServiceAccountError, ServiceAccountInfo};
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName, ServiceAccountScope};
+use tracing::{debug, error, info};
+
+pub struct CreateServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub name: ServiceAccountName,
+    pub scopes: BTreeSet<ServiceAccountScope>,
+    pub user_id: String,
+}
+
+/// Creates the service account without any tokens. Tokens are issued separately, so that they can be rotated.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_service_account(params: CreateServiceAccountParams) -> Result<ServiceAccountInfo, CreateServiceAccountError> {
+
+    async fn inner(params: CreateServiceAccountParams) -> Result<ServiceAccountInfo, CreateServiceAccountError> {
+
+        let CreateServiceAccountParams { resources_manager, name, scopes, user_id } = params;
+
+        debug!("Creating service account '{name}'.");
+
+        let internal_error = |cause: String| CreateServiceAccountError::Internal { name: Clone::clone(&name), cause };
+
+        let service_account = resources_manager.resources_mut(|resources| {
+            let name_exists = resources.list::<ServiceAccount>()
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .into_iter()
+                .any(|service_account| service_account.name == name);
+            if name_exists {
+                return Err(CreateServiceAccountError::NameAlreadyExists { name: Clone::clone(&name) });
+            }
+
+            let service_account = ServiceAccount {
+                id: ServiceAccountId::random(),
+                name: Clone::clone(&name),
+                scopes,
+                created_by: user_id,
+                created_at: now_in_millis(),
+                tokens: Vec::new(),
+            };
+            resources.insert(service_account.id, Clone::clone(&service_account))
+                .map_err(|cause| internal_error(cause.to_string()))?;
+
+            Ok(service_account)
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully created service account '{name}' <{}>.", service_account.id);
+
+        Ok(ServiceAccountInfo::from(service_account))
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
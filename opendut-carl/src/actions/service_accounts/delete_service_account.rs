@@ -0,0 +1,36 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::service_account::DeleteServiceAccountError;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId};
+use tracing::{debug, error, info};
+
+pub struct DeleteServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+}
+
+/// Deletes the service account together with its tokens, so that they cannot be used anymore.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_service_account(params: DeleteServiceAccountParams) -> Result<ServiceAccount, DeleteServiceAccountError> {
+
+    async fn inner(params: DeleteServiceAccountParams) -> Result<ServiceAccount, DeleteServiceAccountError> {
+
+        let DeleteServiceAccountParams { resources_manager, service_account_id } = params;
+
+        debug!("Deleting service account <{service_account_id}>.");
+
+        let service_account = resources_manager.resources_mut(|resources| {
+            resources.remove::<ServiceAccount>(service_account_id)
+                .map_err(|cause| DeleteServiceAccountError::Internal { service_account_id, cause: cause.to_string() })?
+                .ok_or(DeleteServiceAccountError::NotFound { service_account_id })
+        }).await
+        .map_err(|cause| DeleteServiceAccountError::Internal { service_account_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted service account '{}' <{service_account_id}>.", service_account.name);
+
+        Ok(service_account)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,74 @@
+use crate::actions::peers::generate_provisioning_tokens::hash_secret;
+use crate::actions::service_accounts::{now_in_millis, ServiceAccountOptions};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::service_account::{IssuedServiceAccountToken, IssueServiceAccountTokenError, ServiceAccountTokenInfo};
+use opendut_types::service_account::{ServiceAccount, ServiceAccountCredentials, ServiceAccountId, ServiceAccountToken, ServiceAccountTokenId};
+use std::time::Duration;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+pub struct IssueServiceAccountTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+    pub validity: Duration,
+    pub read_only: bool,
+    pub options: ServiceAccountOptions,
+}
+
+/// Issues a further token for the service account. Expired tokens of the service account are removed along the way.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn issue_service_account_token(params: IssueServiceAccountTokenParams) -> Result<IssuedServiceAccountToken, IssueServiceAccountTokenError> {
+
+    async fn inner(params: IssueServiceAccountTokenParams) -> Result<IssuedServiceAccountToken, IssueServiceAccountTokenError> {
+
+        let IssueServiceAccountTokenParams { resources_manager, service_account_id, validity, read_only, options } = params;
+
+        if validity.is_zero() || validity > options.max_token_validity {
+            return Err(IssueServiceAccountTokenError::IllegalValidity {
+                service_account_id,
+                validity_seconds: validity.as_secs(),
+                max_validity_seconds: options.max_token_validity.as_secs(),
+            });
+        }
+
+        debug!("Issuing token for service account <{service_account_id}>, which is valid for {} seconds.", validity.as_secs());
+
+        let internal_error = |cause: String| IssueServiceAccountTokenError::Internal { service_account_id, cause };
+
+        let secret = Uuid::new_v4().simple().to_string();
+        let created_at = now_in_millis();
+        let token = ServiceAccountToken {
+            id: ServiceAccountTokenId::random(),
+            secret_hash: hash_secret(&secret),
+            created_at,
+            expires_at: created_at + validity,
+            read_only,
+        };
+
+        resources_manager.resources_mut(|resources| {
+            let mut service_account = resources.get::<ServiceAccount>(service_account_id)
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .ok_or(IssueServiceAccountTokenError::NotFound { service_account_id })?;
+
+            service_account.tokens.retain(|token| !token.is_expired(created_at));
+            service_account.tokens.push(Clone::clone(&token));
+
+            resources.insert(service_account_id, service_account)
+                .map_err(|cause| internal_error(cause.to_string()))
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully issued token <{}> for service account <{service_account_id}>.", token.id);
+
+        let credentials = ServiceAccountCredentials { token: token.id, secret }.encode();
+
+        Ok(IssuedServiceAccountToken {
+            token: ServiceAccountTokenInfo::from(token),
+            credentials,
+        })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
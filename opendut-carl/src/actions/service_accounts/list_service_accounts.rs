@@ -0,0 +1,34 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::service_account::{ListServiceAccountsError, ServiceAccountInfo};
+use opendut_types::service_account::ServiceAccount;
+use tracing::{debug, error, info};
+
+pub struct ListServiceAccountsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_service_accounts(params: ListServiceAccountsParams) -> Result<Vec<ServiceAccountInfo>, ListServiceAccountsError> {
+
+    async fn inner(params: ListServiceAccountsParams) -> Result<Vec<ServiceAccountInfo>, ListServiceAccountsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all service accounts.");
+
+        let service_accounts = resources_manager.resources(|resources| {
+            resources.list::<ServiceAccount>()
+        }).await
+        .map_err(|cause| ListServiceAccountsError::Internal { cause: cause.to_string() })?;
+
+        info!("Successfully queried all service accounts.");
+
+        Ok(service_accounts.into_iter()
+            .map(ServiceAccountInfo::from)
+            .collect())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
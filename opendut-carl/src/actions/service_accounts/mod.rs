@@ -0,0 +1,34 @@
+use std::time::{Duration, SystemTime};
+
+pub mod authenticate_service_account;
+pub mod create_service_account;
+pub mod delete_service_account;
+pub mod issue_service_account_token;
+pub mod list_service_accounts;
+pub mod revoke_service_account_token;
+
+/// Limits for service accounts, configured in the `service.accounts` section.
+#[derive(Clone, Debug)]
+pub struct ServiceAccountOptions {
+    /// Roles, one of which a user needs to manage service accounts. An empty list allows all users.
+    pub admin_roles: Vec<String>,
+    pub max_token_validity: Duration,
+}
+impl ServiceAccountOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let admin_roles = config.get::<Vec<String>>("service.accounts.admin.roles")?;
+        let max_token_validity = Duration::from_secs(
+            config.get::<u64>("service.accounts.token.validity.max.days")?.saturating_mul(24 * 60 * 60)
+        );
+
+        Ok(ServiceAccountOptions { admin_roles, max_token_validity })
+    }
+}
+
+/// Times are persisted with millisecond precision, so they are truncated to be the same before and after persisting.
+fn now_in_millis() -> SystemTime {
+    let millis = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default();
+    SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+}
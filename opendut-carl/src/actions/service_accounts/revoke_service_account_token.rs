@@ -0,0 +1,48 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::service_account::RevokeServiceAccountTokenError;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountTokenId};
+use tracing::{debug, error, info};
+
+pub struct RevokeServiceAccountTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+    pub token_id: ServiceAccountTokenId,
+}
+
+/// Removes the token from the service account, so that requests authenticating with it are rejected from now on.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_service_account_token(params: RevokeServiceAccountTokenParams) -> Result<ServiceAccountTokenId, RevokeServiceAccountTokenError> {
+
+    async fn inner(params: RevokeServiceAccountTokenParams) -> Result<ServiceAccountTokenId, RevokeServiceAccountTokenError> {
+
+        let RevokeServiceAccountTokenParams { resources_manager, service_account_id, token_id } = params;
+
+        debug!("Revoking token <{token_id}> of service account <{service_account_id}>.");
+
+        let internal_error = |cause: String| RevokeServiceAccountTokenError::Internal { service_account_id, token_id, cause };
+
+        resources_manager.resources_mut(|resources| {
+            let mut service_account = resources.get::<ServiceAccount>(service_account_id)
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .ok_or(RevokeServiceAccountTokenError::NotFound { service_account_id, token_id })?;
+
+            let token_count = service_account.tokens.len();
+            service_account.tokens.retain(|token| token.id != token_id);
+            if service_account.tokens.len() == token_count {
+                return Err(RevokeServiceAccountTokenError::NotFound { service_account_id, token_id });
+            }
+
+            resources.insert(service_account_id, service_account)
+                .map_err(|cause| internal_error(cause.to_string()))
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully revoked token <{token_id}> of service account <{service_account_id}>.");
+
+        Ok(token_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
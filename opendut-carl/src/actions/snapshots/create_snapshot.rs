@@ -0,0 +1,113 @@
+use std::time::{Duration, SystemTime};
+
+use crate::actions::snapshots::current_content;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::snapshot::{CreateSnapshotError, SnapshotInfo};
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId, SnapshotName};
+use tracing::{debug, error, info};
+
+pub struct CreateSnapshotParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub name: SnapshotName,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_snapshot(params: CreateSnapshotParams) -> Result<SnapshotInfo, CreateSnapshotError> {
+
+    async fn inner(params: CreateSnapshotParams) -> Result<SnapshotInfo, CreateSnapshotError> {
+
+        let CreateSnapshotParams { resources_manager, name } = params;
+
+        debug!("Creating snapshot '{name}'.");
+
+        let internal_error = |cause: String| CreateSnapshotError::Internal { snapshot_name: Clone::clone(&name), cause };
+
+        let snapshot = resources_manager.resources_mut(|resources| {
+            let name_exists = resources.list::<ResourceSnapshot>()
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .into_iter()
+                .any(|snapshot| snapshot.name == name);
+            if name_exists {
+                return Err(CreateSnapshotError::SnapshotNameAlreadyExists { snapshot_name: Clone::clone(&name) });
+            }
+
+            let content = current_content(resources)
+                .map_err(|cause| internal_error(cause.to_string()))?;
+
+            let snapshot = ResourceSnapshot {
+                id: SnapshotId::random(),
+                name: Clone::clone(&name),
+                created_at: now_in_millis(),
+                content,
+            };
+            resources.insert(snapshot.id, Clone::clone(&snapshot))
+                .map_err(|cause| internal_error(cause.to_string()))?;
+
+            Ok(snapshot)
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        let snapshot = SnapshotInfo::from(&snapshot);
+
+        info!("Successfully created snapshot '{name}' <{}> with {} resources.", snapshot.id, snapshot.resource_count);
+
+        Ok(snapshot)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// The creation time is persisted with millisecond precision, so it is truncated to be the same before and after persisting.
+fn now_in_millis() -> SystemTime {
+    let millis = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default();
+    SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::persistence::database;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_types::peer::PeerDescriptor;
+
+    #[tokio::test]
+    async fn should_create_snapshot_in_memory() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        should_create_snapshot(resources_manager).await
+    }
+
+    #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+    #[tokio::test]
+    async fn should_create_snapshot_in_database() -> anyhow::Result<()> {
+        let db = database::testing::spawn_and_connect_resources_manager().await?;
+        should_create_snapshot(db.resources_manager).await
+    }
+
+    async fn should_create_snapshot(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+        let peer = generate_peer_descriptor()?;
+        resources_manager.insert(peer.id, Clone::clone(&peer)).await?;
+
+        let name = SnapshotName::try_from("before holiday shutdown")?;
+        let snapshot = create_snapshot(CreateSnapshotParams { resources_manager: Clone::clone(&resources_manager), name: Clone::clone(&name) }).await?;
+        assert_eq!(snapshot.resource_count, 1);
+
+        let persisted = resources_manager.get::<ResourceSnapshot>(snapshot.id).await?.unwrap();
+        assert_eq!(persisted.name, name);
+        assert_eq!(persisted.created_at, snapshot.created_at);
+        assert_eq!(persisted.content.peers, vec![peer.clone()]);
+
+        resources_manager.remove::<PeerDescriptor>(peer.id).await?;
+        let persisted = resources_manager.get::<ResourceSnapshot>(snapshot.id).await?.unwrap();
+        assert_eq!(persisted.content.peers, vec![peer], "Snapshot should not change when the resources change.");
+
+        let result = create_snapshot(CreateSnapshotParams { resources_manager: Clone::clone(&resources_manager), name }).await;
+        assert!(matches!(result, Err(CreateSnapshotError::SnapshotNameAlreadyExists { .. })));
+
+        Ok(())
+    }
+}
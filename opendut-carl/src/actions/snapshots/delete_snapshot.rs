@@ -0,0 +1,36 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::snapshot::DeleteSnapshotError;
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId};
+use tracing::{debug, error, info};
+
+pub struct DeleteSnapshotParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub snapshot_id: SnapshotId,
+}
+
+/// Deletes the snapshot itself. The resources contained in the snapshot are not affected.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_snapshot(params: DeleteSnapshotParams) -> Result<ResourceSnapshot, DeleteSnapshotError> {
+
+    async fn inner(params: DeleteSnapshotParams) -> Result<ResourceSnapshot, DeleteSnapshotError> {
+
+        let DeleteSnapshotParams { resources_manager, snapshot_id } = params;
+
+        debug!("Deleting snapshot <{snapshot_id}>.");
+
+        let snapshot = resources_manager.resources_mut(|resources| {
+            resources.remove::<ResourceSnapshot>(snapshot_id)
+                .map_err(|cause| DeleteSnapshotError::Internal { snapshot_id, cause: cause.to_string() })?
+                .ok_or(DeleteSnapshotError::SnapshotNotFound { snapshot_id })
+        }).await
+        .map_err(|cause| DeleteSnapshotError::Internal { snapshot_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted snapshot '{}' <{snapshot_id}>.", snapshot.name);
+
+        Ok(snapshot)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,58 @@
+use crate::actions::snapshots::current_content;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::snapshot::DiffSnapshotsError;
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotDiffEntry, SnapshotId};
+use tracing::{debug, error, info};
+
+pub struct DiffSnapshotsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub from: SnapshotId,
+    /// Snapshot to compare with. If not set, the snapshot is compared with the current resources.
+    pub to: Option<SnapshotId>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn diff_snapshots(params: DiffSnapshotsParams) -> Result<Vec<SnapshotDiffEntry>, DiffSnapshotsError> {
+
+    async fn inner(params: DiffSnapshotsParams) -> Result<Vec<SnapshotDiffEntry>, DiffSnapshotsError> {
+
+        let DiffSnapshotsParams { resources_manager, from, to } = params;
+
+        match to {
+            Some(to) => debug!("Comparing snapshot <{from}> with snapshot <{to}>."),
+            None => debug!("Comparing snapshot <{from}> with the current resources."),
+        }
+
+        let diff = resources_manager.resources(|resources| {
+            let get_content = |snapshot_id: SnapshotId| {
+                resources.get::<ResourceSnapshot>(snapshot_id)
+                    .map(|snapshot| snapshot.map(|snapshot| snapshot.content)
+                        .ok_or(DiffSnapshotsError::SnapshotNotFound { snapshot_id })
+                    )
+            };
+
+            let older = match get_content(from)? {
+                Ok(older) => older,
+                Err(error) => return Ok(Err(error)),
+            };
+            let newer = match to {
+                Some(to) => match get_content(to)? {
+                    Ok(newer) => newer,
+                    Err(error) => return Ok(Err(error)),
+                },
+                None => current_content(resources)?,
+            };
+
+            Ok(Ok(older.diff(&newer)))
+        }).await
+        .map_err(|cause| DiffSnapshotsError::Internal { cause: cause.to_string() })??;
+
+        info!("Successfully compared snapshot <{from}>. Found {} differences.", diff.len());
+
+        Ok(diff)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,37 @@
+use crate::resources::manager::ResourcesManagerRef;
+use opendut_carl_api::carl::snapshot::{ListSnapshotsError, SnapshotInfo};
+use opendut_types::snapshot::ResourceSnapshot;
+use tracing::{debug, error, info};
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct ListSnapshotsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_snapshots(params: ListSnapshotsParams) -> Result<Vec<SnapshotInfo>, ListSnapshotsError> {
+
+    async fn inner(params: ListSnapshotsParams) -> Result<Vec<SnapshotInfo>, ListSnapshotsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all snapshots.");
+
+        let mut snapshots = resources_manager.resources(|resources| {
+            resources.list::<ResourceSnapshot>()
+        }).await
+        .map_err(|cause| ListSnapshotsError::Internal { cause: cause.to_string() })?
+        .iter()
+        .map(SnapshotInfo::from)
+        .collect::<Vec<_>>();
+
+        snapshots.sort_by_key(|snapshot| snapshot.created_at);
+
+        info!("Successfully queried all snapshots.");
+
+        Ok(snapshots)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
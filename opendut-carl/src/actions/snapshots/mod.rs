@@ -0,0 +1,23 @@
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::snapshot::SnapshotContent;
+
+use crate::persistence::error::PersistenceResult;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub mod create_snapshot;
+pub mod delete_snapshot;
+pub mod diff_snapshots;
+pub mod list_snapshots;
+pub mod restore_snapshot;
+
+/// Collects the resources, which are part of a snapshot, from the current state.
+pub(crate) fn current_content(resources: &impl ResourcesStorageApi) -> PersistenceResult<SnapshotContent> {
+    Ok(SnapshotContent {
+        peers: resources.list::<PeerDescriptor>()?,
+        peer_groups: resources.list::<PeerGroup>()?,
+        cluster_configurations: resources.list::<ClusterConfiguration>()?,
+        cluster_deployments: resources.list::<ClusterDeployment>()?,
+    })
+}
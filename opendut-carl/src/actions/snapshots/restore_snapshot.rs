@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::ops::Not;
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::snapshot::RestoreSnapshotError;
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId, SnapshotResourceKind, SnapshotResourceReference};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+pub struct RestoreSnapshotParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub snapshot_id: SnapshotId,
+    /// Resources to restore. If empty, all resources of the snapshot are restored.
+    pub resources: Vec<SnapshotResourceReference>,
+}
+
+/// Overwrites the current resources with their version from the snapshot, re-creating them if they were deleted.
+/// Resources which were created after the snapshot are kept, as they are not part of the snapshot.
+/// Returns the restored resources.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn restore_snapshot(params: RestoreSnapshotParams) -> Result<Vec<SnapshotResourceReference>, RestoreSnapshotError> {
+
+    async fn inner(params: RestoreSnapshotParams) -> Result<Vec<SnapshotResourceReference>, RestoreSnapshotError> {
+
+        let RestoreSnapshotParams { resources_manager, snapshot_id, resources: requested_resources } = params;
+
+        debug!("Restoring snapshot <{snapshot_id}>.");
+
+        let internal_error = |cause: String| RestoreSnapshotError::Internal { snapshot_id, cause };
+
+        let (snapshot_name, restored) = resources_manager.resources_mut(|resources| {
+            let snapshot = resources.get::<ResourceSnapshot>(snapshot_id)
+                .map_err(|cause| internal_error(cause.to_string()))?
+                .ok_or(RestoreSnapshotError::SnapshotNotFound { snapshot_id })?;
+            let ResourceSnapshot { name, content, .. } = snapshot;
+
+            let available = content.references().into_iter().collect::<HashSet<_>>();
+
+            let selected = if requested_resources.is_empty() {
+                available
+            } else {
+                let missing = requested_resources.iter()
+                    .filter(|resource| available.contains(resource).not())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if missing.is_empty().not() {
+                    return Err(RestoreSnapshotError::ResourcesNotInSnapshot { snapshot_id, resources: missing });
+                }
+                requested_resources.into_iter().collect::<HashSet<_>>()
+            };
+            let is_selected = |kind: SnapshotResourceKind, id: Uuid| selected.contains(&SnapshotResourceReference { kind, id });
+
+            // Inserted in order of their dependencies, as resources may reference each other.
+            for peer in content.peers {
+                if is_selected(SnapshotResourceKind::Peer, peer.id.uuid) {
+                    resources.insert(peer.id, peer).map_err(|cause| internal_error(cause.to_string()))?;
+                }
+            }
+            for peer_group in content.peer_groups {
+                if is_selected(SnapshotResourceKind::PeerGroup, peer_group.id.0) {
+                    resources.insert(peer_group.id, peer_group).map_err(|cause| internal_error(cause.to_string()))?;
+                }
+            }
+            for cluster_configuration in content.cluster_configurations {
+                if is_selected(SnapshotResourceKind::ClusterConfiguration, cluster_configuration.id.0) {
+                    resources.insert(cluster_configuration.id, cluster_configuration).map_err(|cause| internal_error(cause.to_string()))?;
+                }
+            }
+            for cluster_deployment in content.cluster_deployments {
+                if is_selected(SnapshotResourceKind::ClusterDeployment, cluster_deployment.id.0) {
+                    resources.insert(cluster_deployment.id, cluster_deployment).map_err(|cause| internal_error(cause.to_string()))?;
+                }
+            }
+
+            let mut restored = selected.into_iter().collect::<Vec<_>>();
+            restored.sort();
+            Ok((name, restored))
+        }).await
+        .map_err(|cause| internal_error(cause.to_string()))??;
+
+        info!("Successfully restored {} resources from snapshot '{snapshot_name}' <{snapshot_id}>.", restored.len());
+
+        Ok(restored)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::actions::{create_snapshot, diff_snapshots, CreateSnapshotParams, DiffSnapshotsParams};
+    use crate::persistence::database;
+    use crate::resources::manager::ResourcesManager;
+    use opendut_types::peer::group::{PeerGroup, PeerGroupId, PeerGroupName};
+    use opendut_types::peer::PeerDescriptor;
+    use opendut_types::snapshot::{SnapshotChange, SnapshotName};
+
+    #[tokio::test]
+    async fn should_restore_selected_resources_in_memory() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        should_restore_selected_resources(resources_manager).await
+    }
+
+    #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+    #[tokio::test]
+    async fn should_restore_selected_resources_in_database() -> anyhow::Result<()> {
+        let db = database::testing::spawn_and_connect_resources_manager().await?;
+        should_restore_selected_resources(db.resources_manager).await
+    }
+
+    async fn should_restore_selected_resources(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+        let peer = generate_peer_descriptor()?;
+        resources_manager.insert(peer.id, Clone::clone(&peer)).await?;
+        let peer_group = peer_group(&peer, "rack-01")?;
+        resources_manager.insert(peer_group.id, Clone::clone(&peer_group)).await?;
+
+        let snapshot = create_snapshot(CreateSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            name: SnapshotName::try_from("before changes")?,
+        }).await?;
+
+        let renamed_peer_group = PeerGroup { name: PeerGroupName::try_from("rack-02")?, ..Clone::clone(&peer_group) };
+        resources_manager.insert(renamed_peer_group.id, renamed_peer_group).await?;
+        let added_peer = generate_peer_descriptor()?;
+        resources_manager.insert(added_peer.id, Clone::clone(&added_peer)).await?;
+
+        let diff = diff_snapshots(DiffSnapshotsParams { resources_manager: Clone::clone(&resources_manager), from: snapshot.id, to: None }).await?;
+        let changes = diff.iter().map(|entry| (entry.resource, entry.change)).collect::<HashSet<_>>();
+        let peer_group_reference = SnapshotResourceReference { kind: SnapshotResourceKind::PeerGroup, id: peer_group.id.0 };
+        assert_eq!(changes, HashSet::from([
+            (peer_group_reference, SnapshotChange::Changed),
+            (SnapshotResourceReference { kind: SnapshotResourceKind::Peer, id: added_peer.id.uuid }, SnapshotChange::Added),
+        ]));
+
+        let restored = restore_snapshot(RestoreSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            snapshot_id: snapshot.id,
+            resources: vec![peer_group_reference],
+        }).await?;
+        assert_eq!(restored, vec![peer_group_reference]);
+
+        assert_eq!(resources_manager.get::<PeerGroup>(peer_group.id).await?, Some(peer_group));
+        assert_eq!(resources_manager.get::<PeerDescriptor>(added_peer.id).await?, Some(added_peer), "Resources created after the snapshot should be kept.");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_restore_deleted_resources() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let peer = generate_peer_descriptor()?;
+        resources_manager.insert(peer.id, Clone::clone(&peer)).await?;
+        let peer_group = peer_group(&peer, "rack-01")?;
+        resources_manager.insert(peer_group.id, Clone::clone(&peer_group)).await?;
+
+        let snapshot = create_snapshot(CreateSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            name: SnapshotName::try_from("before deletion")?,
+        }).await?;
+
+        resources_manager.remove::<PeerGroup>(peer_group.id).await?;
+        resources_manager.remove::<PeerDescriptor>(peer.id).await?;
+
+        let restored = restore_snapshot(RestoreSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            snapshot_id: snapshot.id,
+            resources: vec![],
+        }).await?;
+        assert_eq!(restored.len(), 2);
+
+        assert_eq!(resources_manager.get::<PeerDescriptor>(peer.id).await?, Some(peer));
+        assert_eq!(resources_manager.get::<PeerGroup>(peer_group.id).await?, Some(peer_group));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_resources_not_in_snapshot() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let snapshot = create_snapshot(CreateSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            name: SnapshotName::try_from("empty")?,
+        }).await?;
+
+        let unknown = SnapshotResourceReference { kind: SnapshotResourceKind::Peer, id: Uuid::new_v4() };
+        let result = restore_snapshot(RestoreSnapshotParams {
+            resources_manager: Clone::clone(&resources_manager),
+            snapshot_id: snapshot.id,
+            resources: vec![unknown],
+        }).await;
+
+        assert!(matches!(result, Err(RestoreSnapshotError::ResourcesNotInSnapshot { resources, .. }) if resources == vec![unknown]));
+
+        Ok(())
+    }
+
+    fn peer_group(peer: &PeerDescriptor, name: &str) -> anyhow::Result<PeerGroup> {
+        Ok(PeerGroup {
+            id: PeerGroupId::random(),
+            name: PeerGroupName::try_from(name)?,
+            labels: HashSet::new(),
+            peers: HashSet::from([peer.id]),
+        })
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use pem::Pem;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::PeerDescriptor;
+use opendut_util::settings::LoadError;
+
+use crate::cluster::scheduler::DeploymentSchedulerRef;
+use crate::leadership::LeadershipRef;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ReadConsistency;
+
+pub type AlertsRef = Arc<Alerts>;
+
+/// Number of resolved alerts, which are kept for retrieval.
+const RESOLVED_ALERTS_RETAINED: usize = 100;
+
+/// Evaluates built-in alert rules against the resources and the state of CARL, to cover basic monitoring without an external monitoring stack.
+/// An alert fires, once the condition of its rule held for the configured duration, and resolves as soon as the condition no longer holds.
+pub struct Alerts {
+    options: AlertsOptions,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Since when the condition of each rule and subject holds, regardless of whether the alert fires already.
+    pending: HashMap<AlertKey, SystemTime>,
+    firing: HashMap<AlertKey, Alert>,
+    resolved: VecDeque<Alert>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct AlertKey {
+    rule: AlertRule,
+    subject: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertRule {
+    PeerOffline,
+    DeploymentStuck,
+    DiskUsage,
+}
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertRule::PeerOffline => write!(f, "peer-offline"),
+            AlertRule::DeploymentStuck => write!(f, "deployment-stuck"),
+            AlertRule::DiskUsage => write!(f, "disk-usage"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Alert {
+    pub rule: AlertRule,
+    /// What the alert is about, e.g. the ID of a peer.
+    pub subject: String,
+    pub message: String,
+    /// Since when the condition of the rule holds.
+    pub since_unix_millis: u64,
+    pub fired_unix_millis: u64,
+    pub resolved_unix_millis: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AlertsStatus {
+    pub firing: Vec<Alert>,
+    /// Most recently resolved alerts first.
+    pub resolved: Vec<Alert>,
+}
+
+/// Condition of a rule, which currently holds for a subject.
+#[derive(Clone, Debug)]
+struct Finding {
+    rule: AlertRule,
+    subject: String,
+    message: String,
+    /// Since when the condition holds, if known. Otherwise, it is assumed to hold since it was first found.
+    since: Option<SystemTime>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Transition {
+    Fired(Alert),
+    Resolved(Alert),
+}
+
+impl Alerts {
+    pub fn new(options: AlertsOptions) -> AlertsRef {
+        Arc::new(Self { options, state: Mutex::default() })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self.options, AlertsOptions::Enabled { .. })
+    }
+
+    pub fn status(&self) -> AlertsStatus {
+        let state = self.state.lock().unwrap();
+
+        let mut firing = state.firing.values().cloned().collect::<Vec<_>>();
+        firing.sort_by_key(|alert| (alert.since_unix_millis, alert.rule.to_string(), alert.subject.clone()));
+
+        AlertsStatus {
+            firing,
+            resolved: state.resolved.iter().cloned().collect(),
+        }
+    }
+
+    fn evaluate_at(&self, findings: Vec<Finding>, now: SystemTime) -> Vec<Transition> {
+        let AlertsOptions::Enabled { rules, .. } = &self.options else {
+            return vec![];
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let mut transitions = Vec::new();
+
+        let mut found = HashSet::new();
+        for finding in findings {
+            let key = AlertKey { rule: finding.rule, subject: finding.subject.clone() };
+            let since = match (finding.since, state.pending.get(&key)) {
+                (Some(since), _) => since,
+                (None, Some(pending)) => *pending,
+                (None, None) => now,
+            };
+            state.pending.insert(key.clone(), since);
+
+            let duration = rules.duration(finding.rule);
+            let holds_long_enough = now.duration_since(since).unwrap_or_default() >= duration;
+
+            match state.firing.get_mut(&key) {
+                Some(alert) => alert.message = finding.message,
+                None if holds_long_enough => {
+                    let alert = Alert {
+                        rule: finding.rule,
+                        subject: finding.subject,
+                        message: finding.message,
+                        since_unix_millis: unix_millis(since),
+                        fired_unix_millis: unix_millis(now),
+                        resolved_unix_millis: None,
+                    };
+                    state.firing.insert(key.clone(), alert.clone());
+                    transitions.push(Transition::Fired(alert));
+                }
+                None => {}
+            }
+            found.insert(key);
+        }
+
+        state.pending.retain(|key, _| found.contains(key));
+
+        let resolved_keys = state.firing.keys()
+            .filter(|key| found.contains(*key).not())
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in resolved_keys {
+            if let Some(mut alert) = state.firing.remove(&key) {
+                alert.resolved_unix_millis = Some(unix_millis(now));
+                state.resolved.push_front(alert.clone());
+                transitions.push(Transition::Resolved(alert));
+            }
+        }
+        state.resolved.truncate(RESOLVED_ALERTS_RETAINED);
+
+        transitions
+    }
+}
+
+/// Periodically evaluates the alert rules and notifies about alerts, which fired or resolved.
+/// Only the leader evaluates, as the connected peers and the running deployments are only known to it.
+pub fn spawn_evaluation(
+    alerts: AlertsRef,
+    resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    deployment_scheduler: DeploymentSchedulerRef,
+    leadership: LeadershipRef,
+    ca: Pem,
+) -> anyhow::Result<()> {
+    let AlertsOptions::Enabled { interval, rules, webhook } = Clone::clone(&alerts.options) else {
+        return Ok(());
+    };
+
+    info!("Evaluating alert rules every {} ms.", interval.as_millis());
+
+    let client = reqwest::Client::builder()
+        .add_root_certificate(reqwest::Certificate::from_pem(pem::encode(&ca).as_bytes())?)
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if leadership.is_leader().not() {
+                continue;
+            }
+
+            let mut findings = Vec::new();
+
+            if rules.peer_offline.is_some() {
+                match find_offline_peers(&resources_manager, &peer_messaging_broker).await {
+                    Ok(offline_peers) => findings.extend(offline_peers),
+                    Err(cause) => {
+                        warn!("Failed to evaluate alert rule '{}'. Skipping this evaluation:\n  {cause}", AlertRule::PeerOffline);
+                        continue; //otherwise, firing alerts would be resolved
+                    }
+                }
+            }
+            if rules.deployment_stuck.is_some() {
+                findings.extend(find_stuck_deployments(&deployment_scheduler));
+            }
+            if let Some(disk_usage) = &rules.disk_usage {
+                findings.extend(find_disk_usage(disk_usage));
+            }
+
+            for transition in alerts.evaluate_at(findings, SystemTime::now()) {
+                match &transition {
+                    Transition::Fired(alert) => warn!("Alert '{}' fired for <{}>: {}", alert.rule, alert.subject, alert.message),
+                    Transition::Resolved(alert) => info!("Alert '{}' resolved for <{}>.", alert.rule, alert.subject),
+                }
+                if let Some(webhook) = &webhook {
+                    notify(&client, webhook, &transition).await;
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Peers, which are down, but did not announce to go offline, e.g. when their host is shut down.
+/// Only peers, which were connected since CARL started, are considered, as it is not known for others, since when they are offline.
+async fn find_offline_peers(
+    resources_manager: &ResourcesManagerRef,
+    peer_messaging_broker: &PeerMessagingBrokerRef,
+) -> Result<Vec<Finding>, PersistenceError> {
+    let peers = resources_manager.list_with::<PeerDescriptor>(ReadConsistency::Eventual).await?;
+
+    let mut findings = Vec::new();
+    for peer in peers {
+        let state = resources_manager.get::<PeerState>(peer.id).await?.unwrap_or_default();
+        if matches!(state, PeerState::Down).not() {
+            continue;
+        }
+        let Some(last_seen) = peer_messaging_broker.last_seen(peer.id).await else {
+            continue;
+        };
+        if let Some(reason) = peer_messaging_broker.offline_announcement(peer.id).await {
+            debug!("Peer <{}> is offline as announced due to {reason}. Not alerting.", peer.id);
+            continue;
+        }
+        findings.push(Finding {
+            rule: AlertRule::PeerOffline,
+            subject: peer.id.to_string(),
+            message: format!("Peer '{}' is offline.", peer.name),
+            since: Some(last_seen),
+        });
+    }
+    Ok(findings)
+}
+
+fn find_stuck_deployments(deployment_scheduler: &DeploymentSchedulerRef) -> Vec<Finding> {
+    let now = SystemTime::now();
+
+    deployment_scheduler.status().in_progress.into_iter()
+        .map(|deployment| {
+            let pending_peers = deployment.pending_peers.iter()
+                .map(|peer_id| peer_id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Finding {
+                rule: AlertRule::DeploymentStuck,
+                subject: deployment.cluster_id.to_string(),
+                message: format!("Deployment is in progress for {} ms. Waiting for peers: {pending_peers}", deployment.running_ms),
+                since: now.checked_sub(Duration::from_millis(deployment.running_ms)),
+            }
+        })
+        .collect()
+}
+
+fn find_disk_usage(rule: &DiskUsageRule) -> Option<Finding> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+
+    // The disk mounted at the longest prefix of the path is the one containing it.
+    let disk = disks.list().iter()
+        .filter(|disk| rule.path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count());
+
+    let Some(disk) = disk else {
+        warn!("Could not determine the disk containing '{}'. Not evaluating alert rule '{}'.", rule.path.display(), AlertRule::DiskUsage);
+        return None;
+    };
+    let usage_percent = usage_percent(disk.total_space(), disk.available_space())?;
+
+    (usage_percent >= rule.threshold_percent).then(|| Finding {
+        rule: AlertRule::DiskUsage,
+        subject: rule.path.display().to_string(),
+        message: format!("Disk mounted at '{}' is {usage_percent:.1}% full.", disk.mount_point().display()),
+        since: None,
+    })
+}
+
+fn usage_percent(total: u64, available: u64) -> Option<f64> {
+    (total > 0).then(|| (total.saturating_sub(available) as f64 / total as f64) * 100.0)
+}
+
+#[derive(Serialize)]
+struct Notification<'a> {
+    state: &'static str,
+    alert: &'a Alert,
+}
+
+async fn notify(client: &reqwest::Client, webhook: &Url, transition: &Transition) {
+    let notification = match transition {
+        Transition::Fired(alert) => Notification { state: "firing", alert },
+        Transition::Resolved(alert) => Notification { state: "resolved", alert },
+    };
+
+    let result = client.post(Clone::clone(webhook))
+        .json(&notification)
+        .send().await
+        .and_then(reqwest::Response::error_for_status);
+
+    if let Err(cause) = result {
+        warn!("Failed to send notification about alert '{}' for <{}> to webhook <{webhook}>: {cause}", notification.alert.rule, notification.alert.subject);
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[derive(Clone, Debug)]
+pub enum AlertsOptions {
+    Enabled {
+        interval: Duration,
+        rules: AlertRules,
+        webhook: Option<Url>,
+    },
+    Disabled,
+}
+
+/// Rules, which are `None`, are disabled.
+#[derive(Clone, Debug, Default)]
+pub struct AlertRules {
+    pub peer_offline: Option<Duration>,
+    pub deployment_stuck: Option<Duration>,
+    pub disk_usage: Option<DiskUsageRule>,
+}
+impl AlertRules {
+    /// How long the condition of the rule has to hold, before the alert fires.
+    fn duration(&self, rule: AlertRule) -> Duration {
+        match rule {
+            AlertRule::PeerOffline => self.peer_offline,
+            AlertRule::DeploymentStuck => self.deployment_stuck,
+            AlertRule::DiskUsage => self.disk_usage.as_ref().map(|rule| rule.duration),
+        }.unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiskUsageRule {
+    pub path: PathBuf,
+    pub threshold_percent: f64,
+    pub duration: Duration,
+}
+
+impl AlertsOptions {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        if config.get_bool("alerts.enabled")?.not() {
+            return Ok(AlertsOptions::Disabled);
+        }
+
+        let interval = Duration::from_millis(config.get::<u64>("alerts.evaluation.interval.ms")?);
+
+        let rules = AlertRules {
+            peer_offline: config.get_bool("alerts.peer.offline.enabled")?
+                .then(|| config.get::<u64>("alerts.peer.offline.for.ms")).transpose()?
+                .map(Duration::from_millis),
+            deployment_stuck: config.get_bool("alerts.deployment.stuck.enabled")?
+                .then(|| config.get::<u64>("alerts.deployment.stuck.for.ms")).transpose()?
+                .map(Duration::from_millis),
+            disk_usage: if config.get_bool("alerts.disk.usage.enabled")? {
+                let field = "alerts.disk.usage.threshold.percent";
+                let threshold_percent = config.get::<f64>(field)?;
+                if (0.0..=100.0).contains(&threshold_percent).not() {
+                    return Err(LoadError::ParseValue { field, value: threshold_percent.to_string(), source: "Expected a percentage between 0 and 100.".into() });
+                }
+                Some(DiskUsageRule {
+                    path: PathBuf::from(config.get_string("alerts.disk.usage.path")?),
+                    threshold_percent,
+                    duration: Duration::from_millis(config.get::<u64>("alerts.disk.usage.for.ms")?),
+                })
+            } else {
+                None
+            },
+        };
+
+        let webhook = {
+            let field = "alerts.notification.webhook.url";
+            let value = config.get_string(field)?;
+            if value.is_empty() {
+                None
+            } else {
+                let url = Url::parse(&value)
+                    .map_err(|cause| LoadError::ParseValue { field, value: value.clone(), source: cause.into() })?;
+                Some(url)
+            }
+        };
+
+        Ok(AlertsOptions::Enabled { interval, rules, webhook })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testee() -> AlertsRef {
+        Alerts::new(AlertsOptions::Enabled {
+            interval: Duration::from_secs(60),
+            rules: AlertRules {
+                peer_offline: Some(Duration::from_secs(600)),
+                deployment_stuck: Some(Duration::from_secs(1800)),
+                disk_usage: None,
+            },
+            webhook: None,
+        })
+    }
+
+    fn peer_offline(since: Option<SystemTime>) -> Finding {
+        Finding {
+            rule: AlertRule::PeerOffline,
+            subject: String::from("peer-a"),
+            message: String::from("Peer 'a' is offline."),
+            since,
+        }
+    }
+
+    #[test]
+    fn should_fire_once_the_condition_held_for_the_duration_of_the_rule() {
+        let alerts = testee();
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        assert!(alerts.evaluate_at(vec![peer_offline(None)], start).is_empty());
+        assert!(alerts.evaluate_at(vec![peer_offline(None)], start + Duration::from_secs(300)).is_empty());
+
+        let transitions = alerts.evaluate_at(vec![peer_offline(None)], start + Duration::from_secs(600));
+        assert!(matches!(transitions.as_slice(), [Transition::Fired(alert)] if alert.since_unix_millis == unix_millis(start)));
+
+        assert!(alerts.evaluate_at(vec![peer_offline(None)], start + Duration::from_secs(900)).is_empty(), "Fired alerts should not fire again.");
+        assert_eq!(alerts.status().firing.len(), 1);
+    }
+
+    #[test]
+    fn should_fire_immediately_when_the_condition_is_known_to_hold_long_enough() {
+        let alerts = testee();
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        let transitions = alerts.evaluate_at(vec![peer_offline(Some(now - Duration::from_secs(601)))], now);
+
+        assert!(matches!(transitions.as_slice(), [Transition::Fired(_)]));
+    }
+
+    #[test]
+    fn should_resolve_when_the_condition_no_longer_holds() {
+        let alerts = testee();
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        alerts.evaluate_at(vec![peer_offline(Some(now - Duration::from_secs(601)))], now);
+
+        let transitions = alerts.evaluate_at(vec![], now + Duration::from_secs(60));
+
+        assert!(matches!(transitions.as_slice(), [Transition::Resolved(alert)] if alert.resolved_unix_millis.is_some()));
+        let status = alerts.status();
+        assert!(status.firing.is_empty());
+        assert_eq!(status.resolved.len(), 1);
+
+        assert!(alerts.evaluate_at(vec![peer_offline(None)], now + Duration::from_secs(120)).is_empty(), "Condition should be pending anew.");
+    }
+
+    #[test]
+    fn should_calculate_the_disk_usage() {
+        assert_eq!(usage_percent(200, 20), Some(90.0));
+        assert_eq!(usage_percent(0, 0), None);
+    }
+}
@@ -1,12 +1,102 @@
 use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer::GrpcAuthLayerEnabled;
 use crate::auth::json_web_key::JwkCacheValue;
 use crate::auth::validation::{authorize_user, Jwk, ValidationError};
-use crate::auth::CurrentUser;
+use crate::auth::service_account::ServiceAccountAuthenticator;
+use crate::auth::{AnonymousReader, CurrentUser, HttpCaller};
 use crate::util::in_memory_cache::CustomInMemoryCache;
+use opendut_types::service_account::ServiceAccountScope;
 use tonic::Status;
 use tracing::debug;
 use url::Url;
 
+/// Methods, which may be called without credentials, when anonymous read access is enabled via `network.oidc.anonymous.read.enabled`.
+/// Only methods listing or retrieving resources are included, but none revealing secrets, like `GetExecutorSecrets` or the setup strings.
+const ANONYMOUS_READ_METHODS: [&str; 14] = [
+    "/opendut.carl.services.cluster_manager.ClusterManager/GetClusterConfiguration",
+    "/opendut.carl.services.cluster_manager.ClusterManager/ListClusterConfigurations",
+    "/opendut.carl.services.cluster_manager.ClusterManager/ListClusterDeployments",
+    "/opendut.carl.services.cluster_manager.ClusterManager/GetClusterDeploymentStatus",
+    "/opendut.carl.services.metadata_provider.MetadataProvider/Version",
+    "/opendut.carl.services.metadata_provider.MetadataProvider/ErrorCatalog",
+    "/opendut.carl.services.peer_manager.PeerManager/GetPeerDescriptor",
+    "/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors",
+    "/opendut.carl.services.peer_manager.PeerManager/GetPeerState",
+    "/opendut.carl.services.peer_manager.PeerManager/ListDevices",
+    "/opendut.carl.services.peer_manager.PeerManager/ListPeerGroups",
+    "/opendut.carl.services.peer_manager.PeerManager/GetMaintenanceOperation",
+    "/opendut.carl.services.resource_observer.ResourceObserver/SubscribeResourceEvents",
+    "/opendut.carl.services.snapshot_manager.SnapshotManager/ListSnapshots",
+];
+
+/// Methods, which are always allowed without credentials, as they authenticate the caller by other means.
+/// Claiming a provisioning token is the first contact of a device, which only knows the secret of its token.
+const UNAUTHENTICATED_METHODS: [&str; 1] = [
+    "/opendut.carl.services.peer_manager.PeerManager/ClaimProvisioningToken",
+];
+
+/// Services, of which all methods may be called by service accounts with the respective scope.
+/// Other services, like managing service accounts or the connections of peers, are not accessible to service accounts at all.
+const SERVICE_ACCOUNT_SCOPES: [(&str, ServiceAccountScope); 6] = [
+    ("/opendut.carl.services.metadata_provider.MetadataProvider/", ServiceAccountScope::Read),
+    ("/opendut.carl.services.peer_manager.PeerManager/", ServiceAccountScope::Peers),
+    ("/opendut.carl.services.cluster_manager.ClusterManager/", ServiceAccountScope::Clusters),
+    ("/opendut.carl.services.snapshot_manager.SnapshotManager/", ServiceAccountScope::Snapshots),
+    ("/opendut.carl.services.diagnostics.Diagnostics/", ServiceAccountScope::Diagnostics),
+    ("/opendut.carl.services.results_manager.ResultsManager/", ServiceAccountScope::Read),
+];
+
+/// Prefixes of the names of methods, which only list, retrieve or observe resources. Only these may be called in read-only mode (see [`crate::auth::read_only`]).
+/// Methods, which change resources, must therefore not be named with one of these prefixes.
+const READ_METHOD_PREFIXES: [&str; 7] = ["Get", "List", "Subscribe", "Download", "Preview", "Simulate", "Diff"];
+
+/// Further methods, which do not change resources.
+const READ_METHODS: [&str; 2] = [
+    "/opendut.carl.services.metadata_provider.MetadataProvider/Version",
+    "/opendut.carl.services.metadata_provider.MetadataProvider/ErrorCatalog",
+];
+
+/// Path of the called gRPC method, e.g. `/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors`.
+/// Inserted into the extensions of each request by [`GrpcMethod::layer`], as the interceptor only receives the metadata of a request.
+#[derive(Clone, Debug)]
+pub struct GrpcMethod(pub String);
+
+impl GrpcMethod {
+    pub fn layer<B>() -> tower::util::MapRequestLayer<fn(http::Request<B>) -> http::Request<B>> {
+        tower::util::MapRequestLayer::new(Self::insert_into::<B> as fn(_) -> _)
+    }
+
+    fn insert_into<B>(mut request: http::Request<B>) -> http::Request<B> {
+        let method = GrpcMethod(request.uri().path().to_owned());
+        request.extensions_mut().insert(method);
+        request
+    }
+
+    fn allows_anonymous_read(&self) -> bool {
+        ANONYMOUS_READ_METHODS.contains(&self.0.as_str())
+    }
+
+    /// Whether the method only lists, retrieves or observes resources.
+    pub fn is_read(&self) -> bool {
+        let name = self.0.rsplit('/').next().unwrap_or_default();
+        READ_METHODS.contains(&self.0.as_str())
+            || READ_METHOD_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+    }
+
+    fn requires_authentication(&self) -> bool {
+        !UNAUTHENTICATED_METHODS.contains(&self.0.as_str())
+    }
+
+    /// Scope, which a service account needs to call this method. Methods, which could be called anonymously, only require reading.
+    fn required_service_account_scope(&self) -> Option<ServiceAccountScope> {
+        if self.allows_anonymous_read() {
+            return Some(ServiceAccountScope::Read);
+        }
+        SERVICE_ACCOUNT_SCOPES.into_iter()
+            .find(|(service, _)| self.0.starts_with(service))
+            .map(|(_, scope)| scope)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum GrpcAuthenticationLayer {
@@ -15,6 +105,8 @@ pub enum GrpcAuthenticationLayer {
         issuer_url: Url,
         issuer_remote_url: Url,
         cache: CustomInMemoryCache<String, JwkCacheValue>,
+        anonymous_read: bool,
+        service_accounts: ServiceAccountAuthenticator,
     },
 }
 
@@ -25,10 +117,25 @@ impl GrpcAuthenticationLayer {
             GrpcAuthenticationLayer::AuthDisabled => {
                 Ok(request)
             }
-            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache } => {
+            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache, anonymous_read, service_accounts } => {
                 let auth_header = match request.metadata().get("authorization") {
                     None => {
-                        return Err(Status::unauthenticated("CARL says, you did not provide credentials!"))
+                        let method = request.extensions().get::<GrpcMethod>();
+                        return match method {
+                            Some(method) if !method.requires_authentication() => {
+                                debug!("Allowing unauthenticated access to {}.", method.0);
+                                Ok(request)
+                            }
+                            Some(method) if anonymous_read && method.allows_anonymous_read() => {
+                                debug!("Allowing anonymous read access to {}.", method.0);
+                                request.extensions_mut().insert(AnonymousReader);
+                                Ok(request)
+                            }
+                            Some(_) if anonymous_read => {
+                                Err(Status::unauthenticated("CARL says, you did not provide credentials! Only listing and retrieving resources is possible without credentials."))
+                            }
+                            _ => Err(Status::unauthenticated("CARL says, you did not provide credentials!")),
+                        };
                     }
                     Some(token) => {
                         token.to_str()
@@ -36,6 +143,22 @@ impl GrpcAuthenticationLayer {
                     }
                 };
 
+                if let Some(credentials) = ServiceAccountAuthenticator::credentials(auth_header) {
+                    let service_account = service_accounts.authenticate(credentials).await
+                        .map_err(|cause| {
+                            debug!("Blocking authentication attempt of service account: {cause}");
+                            Status::unauthenticated("CARL says, invalid credentials!")
+                        })?;
+                    let required_scope = request.extensions().get::<GrpcMethod>()
+                        .and_then(GrpcMethod::required_service_account_scope);
+                    service_account.authorize(required_scope)
+                        .map_err(Status::permission_denied)?;
+
+                    debug!("Service account '{}' <{}> authenticated.", service_account.name, service_account.id);
+                    request.extensions_mut().insert(service_account);
+                    return Ok(request);
+                }
+
                 match authorize_current_user(auth_header, issuer_url, issuer_remote_url, cache).await {
                     Ok(user) => {
                         request.extensions_mut().insert(user);
@@ -51,6 +174,44 @@ impl GrpcAuthenticationLayer {
     }
 }
 
+impl GrpcAuthenticationLayer {
+    /// Validates the credentials of a request to the REST gateway (see [`crate::http::rest`]) with the same rules as for gRPC methods.
+    /// Reading requests are allowed without credentials, if anonymous read access is enabled.
+    /// Service accounts need the given scope, which is [`ServiceAccountScope::Read`] for reading requests.
+    pub async fn authenticate_http_request(self, authorization: Option<&str>, required_scope: ServiceAccountScope) -> Result<HttpCaller, String> {
+        let is_read = required_scope == ServiceAccountScope::Read;
+        match self {
+            GrpcAuthenticationLayer::AuthDisabled => Ok(HttpCaller::Anonymous),
+            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache, anonymous_read, service_accounts } => {
+                match authorization {
+                    None if anonymous_read && is_read => Ok(HttpCaller::Anonymous),
+                    None => Err(String::from("CARL says, you did not provide credentials!")),
+                    Some(authorization) if !authorization.starts_with("Bearer ") => Err(String::from("CARL says, your credentials are malformed!")),
+                    Some(authorization) => match ServiceAccountAuthenticator::credentials(authorization) {
+                        Some(credentials) => {
+                            let service_account = service_accounts.authenticate(credentials).await
+                                .map_err(|cause| {
+                                    debug!("Blocking authentication attempt of service account: {cause}");
+                                    String::from("CARL says, invalid credentials!")
+                                })?;
+                            service_account.authorize(Some(required_scope))?;
+                            Ok(HttpCaller::ServiceAccount(service_account))
+                        }
+                        None => {
+                            authorize_current_user(authorization, issuer_url, issuer_remote_url, cache).await
+                                .map(HttpCaller::User)
+                                .map_err(|cause| {
+                                    debug!("Blocking authentication attempt due to error while validating credentials: {cause}");
+                                    String::from("CARL says, invalid credentials!")
+                                })
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 async fn authorize_current_user(auth_token: &str, issuer_url: Url, issuer_remote_url: Url, cache: CustomInMemoryCache<String, JwkCacheValue>) -> Result<CurrentUser, ValidationError> {
     // decode token
     let token_part: Vec<&str> = auth_token.split(' ').collect();
@@ -59,3 +220,76 @@ async fn authorize_current_user(auth_token: &str, issuer_url: Url, issuer_remote
     let jwk_requester = Jwk;
     authorize_user(issuer_url, issuer_remote_url, token_part, cache, jwk_requester, false).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::manager::ResourcesManager;
+
+    #[test]
+    fn should_allow_anonymous_read_only_for_listing_and_retrieving_resources() {
+        assert!(GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors")).allows_anonymous_read());
+        assert!(GrpcMethod(String::from("/opendut.carl.services.cluster_manager.ClusterManager/GetClusterConfiguration")).allows_anonymous_read());
+
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/StorePeerDescriptor")).allows_anonymous_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/GeneratePeerSetup")).allows_anonymous_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/GetExecutorSecrets")).allows_anonymous_read());
+    }
+
+    #[test]
+    fn should_only_consider_methods_reading_resources_as_read() {
+        for method in ANONYMOUS_READ_METHODS {
+            assert!(GrpcMethod(String::from(method)).is_read(), "{method}");
+        }
+        assert!(GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/ListProvisioningTokens")).is_read());
+        assert!(GrpcMethod(String::from("/opendut.carl.services.cluster_manager.ClusterManager/SimulateClusterDeployment")).is_read());
+
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/StorePeerDescriptor")).is_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.cluster_manager.ClusterManager/DeleteClusterDeployment")).is_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.snapshot_manager.SnapshotManager/RestoreSnapshot")).is_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.diagnostics.Diagnostics/CheckCompatibility")).is_read());
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/Open")).is_read());
+    }
+
+    #[test]
+    fn should_require_authentication_for_all_methods_except_claiming_provisioning_tokens() {
+        assert!(!GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/ClaimProvisioningToken")).requires_authentication());
+
+        assert!(GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/GenerateProvisioningTokens")).requires_authentication());
+        assert!(GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/ListProvisioningTokens")).requires_authentication());
+        assert!(GrpcMethod(String::from("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors")).requires_authentication());
+    }
+
+    #[tokio::test]
+    async fn should_allow_anonymous_http_requests_only_for_reading() {
+        let testee = GrpcAuthLayerEnabled {
+            issuer_url: Url::parse("https://keycloak.internal/realms/opendut/").unwrap(),
+            issuer_remote_url: Url::parse("https://keycloak.internal/realms/opendut/").unwrap(),
+            cache: CustomInMemoryCache::new(),
+            anonymous_read: true,
+            service_accounts: ServiceAccountAuthenticator::new(ResourcesManager::new_in_memory()),
+        };
+
+        assert!(Clone::clone(&testee).authenticate_http_request(None, ServiceAccountScope::Read).await.is_ok());
+        assert!(Clone::clone(&testee).authenticate_http_request(None, ServiceAccountScope::Peers).await.is_err());
+        assert!(Clone::clone(&testee).authenticate_http_request(Some("Basic dXNlcjpwYXNz"), ServiceAccountScope::Read).await.is_err());
+        assert!(testee.authenticate_http_request(Some("Bearer opendut-sa.6c1fd9a4d0a44f33a0a4a8dd2c2b4e7f.unknown"), ServiceAccountScope::Read).await.is_err());
+    }
+
+    #[test]
+    fn should_require_a_scope_for_service_accounts_depending_on_the_service() {
+        let scope_of = |method: &str| GrpcMethod(String::from(method)).required_service_account_scope();
+
+        assert_eq!(scope_of("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors"), Some(ServiceAccountScope::Read));
+        assert_eq!(scope_of("/opendut.carl.services.snapshot_manager.SnapshotManager/ListSnapshots"), Some(ServiceAccountScope::Read));
+        assert_eq!(scope_of("/opendut.carl.services.metadata_provider.MetadataProvider/DownloadCleo"), Some(ServiceAccountScope::Read));
+        assert_eq!(scope_of("/opendut.carl.services.peer_manager.PeerManager/StorePeerDescriptor"), Some(ServiceAccountScope::Peers));
+        assert_eq!(scope_of("/opendut.carl.services.cluster_manager.ClusterManager/StoreClusterDeployment"), Some(ServiceAccountScope::Clusters));
+        assert_eq!(scope_of("/opendut.carl.services.snapshot_manager.SnapshotManager/RestoreSnapshot"), Some(ServiceAccountScope::Snapshots));
+        assert_eq!(scope_of("/opendut.carl.services.diagnostics.Diagnostics/StartTraceCapture"), Some(ServiceAccountScope::Diagnostics));
+
+        assert_eq!(scope_of("/opendut.carl.services.service_account_manager.ServiceAccountManager/IssueServiceAccountToken"), None);
+        assert_eq!(scope_of("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/GetExecutorSecrets"), None);
+        assert_eq!(scope_of("/opendut.carl.services.bootstrap.Bootstrap/CreateAdmin"), None);
+    }
+}
@@ -1,20 +1,50 @@
 mod validation;
 pub(crate) mod json_web_key;
 pub(crate) mod grpc_auth_layer;
+pub(crate) mod permission;
+pub(crate) mod project;
+pub(crate) mod read_only;
+pub(crate) mod service_account;
+
+use std::collections::BTreeSet;
 
 use openidconnect::core::CoreGenderClaim;
 use openidconnect::{AdditionalClaims, IdTokenClaims};
+use opendut_types::service_account::{ServiceAccountId, ServiceAccountName, ServiceAccountScope};
 use serde::{Deserialize, Serialize};
 
 pub type Claims<AC> = IdTokenClaims<AC, CoreGenderClaim>;
 
-#[allow(unused)] //TODO implement authorization
 #[derive(Clone, Debug)]
 pub struct CurrentUser {
     pub name: String,
     pub claims: Claims<MyAdditionalClaims>,
 }
 
+/// Service account, which authenticated a request with one of its tokens. Inserted into the extensions of a request instead of a [`CurrentUser`].
+#[derive(Clone, Debug)]
+pub struct CurrentServiceAccount {
+    pub id: ServiceAccountId,
+    pub name: ServiceAccountName,
+    pub scopes: BTreeSet<ServiceAccountScope>,
+    /// Whether the token, which authenticated the request, only allows reading (see [`read_only`]).
+    pub read_only: bool,
+}
+
+/// Marks a request without credentials, which is allowed due to anonymous read access. Inserted into the extensions of a request instead of a [`CurrentUser`].
+#[derive(Clone, Debug)]
+pub struct AnonymousReader;
+
+/// Caller of a request to the REST gateway, as determined by [`grpc_auth_layer::GrpcAuthenticationLayer::authenticate_http_request`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum HttpCaller {
+    /// No credentials were provided, because authentication is disabled or due to anonymous read access.
+    Anonymous,
+    User(CurrentUser),
+    ServiceAccount(CurrentServiceAccount),
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MyAdditionalClaims {
     /// Roles the user belongs to (custom claim)
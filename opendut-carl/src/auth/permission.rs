@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use tonic::{Request, Status};
+
+use crate::auth::CurrentUser;
+
+/// Permission to call a group of gRPC methods, which is granted to users via the roles or groups in their claims.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    PeerRead,
+    PeerWrite,
+    PeerCommand,
+    PeerSetupStringGenerate,
+    CleoSetupStringGenerate,
+    ClusterRead,
+    ClusterWrite,
+    ClusterDeploy,
+    SnapshotRead,
+    SnapshotWrite,
+    Diagnostics,
+    ResultsRead,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 12] = [
+        Permission::PeerRead,
+        Permission::PeerWrite,
+        Permission::PeerCommand,
+        Permission::PeerSetupStringGenerate,
+        Permission::CleoSetupStringGenerate,
+        Permission::ClusterRead,
+        Permission::ClusterWrite,
+        Permission::ClusterDeploy,
+        Permission::SnapshotRead,
+        Permission::SnapshotWrite,
+        Permission::Diagnostics,
+        Permission::ResultsRead,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Permission::PeerRead => "peer.read",
+            Permission::PeerWrite => "peer.write",
+            Permission::PeerCommand => "peer.command",
+            Permission::PeerSetupStringGenerate => "peer.setup-string.generate",
+            Permission::CleoSetupStringGenerate => "cleo.setup-string.generate",
+            Permission::ClusterRead => "cluster.read",
+            Permission::ClusterWrite => "cluster.write",
+            Permission::ClusterDeploy => "cluster.deploy",
+            Permission::SnapshotRead => "snapshot.read",
+            Permission::SnapshotWrite => "snapshot.write",
+            Permission::Diagnostics => "diagnostics",
+            Permission::ResultsRead => "results.read",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Roles or groups, one of which a user needs for each permission, configured in the `authorization.permissions` section.
+/// An empty list grants the permission to all users.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionAuthorization {
+    required_roles: HashMap<Permission, Vec<String>>,
+}
+impl PermissionAuthorization {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let mut required_roles = HashMap::new();
+        for permission in Permission::ALL {
+            let field = format!("authorization.permissions.{}", permission.name().replace('-', "_"));
+            let roles = config.get::<Vec<String>>(&field)?;
+            required_roles.insert(permission, roles);
+        }
+        Ok(Self { required_roles })
+    }
+
+    /// Checks whether the user of the request has one of the roles or groups required for the permission.
+    /// Service accounts are authorized via their scopes and anonymous readers via the methods allowed for them, both by the authentication layer.
+    /// Without a user, authentication is disabled and all permissions are granted.
+    pub fn authorize<T>(&self, request: &Request<T>, permission: Permission) -> Result<(), Status> {
+        let Some(user) = request.extensions().get::<CurrentUser>() else {
+            return Ok(());
+        };
+        let claims = user.claims.additional_claims();
+        self.check(permission, claims.roles.iter().chain(claims.groups.iter()))
+            .map_err(|required_roles| Status::permission_denied(format!(
+                "CARL says, user '{}' lacks the permission '{permission}', which requires one of the roles: {}",
+                user.name,
+                required_roles.join(", "),
+            )))
+    }
+
+    fn check<'a>(&self, permission: Permission, mut roles: impl Iterator<Item=&'a String>) -> Result<(), &[String]> {
+        let required_roles = self.required_roles.get(&permission)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if required_roles.is_empty()
+            || roles.any(|role| required_roles.contains(role)) {
+            Ok(())
+        } else {
+            Err(required_roles)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_require_one_of_the_configured_roles_or_groups() {
+        let testee = PermissionAuthorization {
+            required_roles: HashMap::from([
+                (Permission::ClusterDeploy, vec![String::from("tester"), String::from("/lab/powertrain")]),
+            ]),
+        };
+        let roles = |roles: &[&str]| roles.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_that!(testee.check(Permission::ClusterDeploy, roles(&["viewer", "tester"]).iter()), ok(eq(&())));
+        assert_that!(testee.check(Permission::ClusterDeploy, roles(&["/lab/powertrain"]).iter()), ok(eq(&())));
+        assert_that!(testee.check(Permission::ClusterDeploy, roles(&["viewer"]).iter()), err(len(eq(2))));
+        assert_that!(testee.check(Permission::ClusterRead, roles(&[]).iter()), ok(eq(&())));
+    }
+
+    #[test]
+    fn should_grant_all_permissions_without_authentication() {
+        let testee = PermissionAuthorization {
+            required_roles: HashMap::from([
+                (Permission::PeerWrite, vec![String::from("admin")]),
+            ]),
+        };
+
+        assert_that!(testee.authorize(&Request::new(()), Permission::PeerWrite), ok(eq(&())));
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use tonic::{Request, Status};
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::project::{Project, ProjectId};
+
+use crate::actions::ProjectOptions;
+use crate::auth::{AnonymousReader, CurrentServiceAccount, CurrentUser};
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Peers and clusters, which the caller of a request may see and modify.
+/// Resources, which are assigned to a project, are only accessible to the members of that project, while unassigned resources are accessible to all.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProjectAccess {
+    Unrestricted,
+    Restricted {
+        member_of: HashSet<ProjectId>,
+        hidden_peers: HashSet<PeerId>,
+        hidden_clusters: HashSet<ClusterId>,
+    },
+}
+
+impl ProjectAccess {
+    /// Users with one of the admin roles and service accounts have unrestricted access.
+    /// Without a user, authentication is disabled and everyone has unrestricted access, except for anonymous readers.
+    pub async fn of<T>(request: &Request<T>, resources_manager: &ResourcesManagerRef, options: &ProjectOptions) -> Result<Self, Status> {
+        let extensions = request.extensions();
+        if extensions.get::<CurrentServiceAccount>().is_some() {
+            return Ok(ProjectAccess::Unrestricted);
+        }
+
+        let anonymous = Vec::new();
+        let (user_name, roles, groups) = match extensions.get::<CurrentUser>() {
+            Some(user) => {
+                let claims = user.claims.additional_claims();
+                (Some(user.name.as_str()), &claims.roles, &claims.groups)
+            }
+            None if extensions.get::<AnonymousReader>().is_some() => (None, &anonymous, &anonymous),
+            None => return Ok(ProjectAccess::Unrestricted),
+        };
+        if options.is_admin(roles) {
+            return Ok(ProjectAccess::Unrestricted);
+        }
+
+        let projects = resources_manager.list::<Project>().await
+            .map_err(|cause| Status::internal(cause.to_string()))?;
+
+        Ok(ProjectAccess::restricted(&projects, user_name, groups))
+    }
+
+    fn restricted(projects: &[Project], user_name: Option<&str>, groups: &[String]) -> Self {
+        let (member_projects, other_projects): (Vec<_>, Vec<_>) = projects.iter()
+            .partition(|project| user_name.is_some_and(|user_name| project.has_member(user_name, groups)));
+
+        let visible_peers = member_projects.iter().flat_map(|project| &project.peers).collect::<HashSet<_>>();
+        let visible_clusters = member_projects.iter().flat_map(|project| &project.clusters).collect::<HashSet<_>>();
+
+        ProjectAccess::Restricted {
+            member_of: member_projects.iter().map(|project| project.id).collect(),
+            hidden_peers: other_projects.iter()
+                .flat_map(|project| &project.peers)
+                .filter(|peer_id| !visible_peers.contains(peer_id))
+                .copied()
+                .collect(),
+            hidden_clusters: other_projects.iter()
+                .flat_map(|project| &project.clusters)
+                .filter(|cluster_id| !visible_clusters.contains(cluster_id))
+                .copied()
+                .collect(),
+        }
+    }
+
+    pub fn may_access_peer(&self, peer_id: &PeerId) -> bool {
+        match self {
+            ProjectAccess::Unrestricted => true,
+            ProjectAccess::Restricted { hidden_peers, .. } => !hidden_peers.contains(peer_id),
+        }
+    }
+
+    pub fn may_access_cluster(&self, cluster_id: &ClusterId) -> bool {
+        match self {
+            ProjectAccess::Unrestricted => true,
+            ProjectAccess::Restricted { hidden_clusters, .. } => !hidden_clusters.contains(cluster_id),
+        }
+    }
+
+    pub fn is_member_of(&self, project_id: &ProjectId) -> bool {
+        match self {
+            ProjectAccess::Unrestricted => true,
+            ProjectAccess::Restricted { member_of, .. } => member_of.contains(project_id),
+        }
+    }
+
+    pub fn authorize_peer(&self, peer_id: PeerId) -> Result<(), Status> {
+        if self.may_access_peer(&peer_id) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!("CARL says, peer <{peer_id}> belongs to a project you are not a member of.")))
+        }
+    }
+
+    pub fn authorize_cluster(&self, cluster_id: ClusterId) -> Result<(), Status> {
+        if self.may_access_cluster(&cluster_id) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!("CARL says, cluster <{cluster_id}> belongs to a project you are not a member of.")))
+        }
+    }
+
+    pub fn authorize_project(&self, project_id: ProjectId) -> Result<(), Status> {
+        if self.is_member_of(&project_id) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!("CARL says, you are not a member of project <{project_id}>.")))
+        }
+    }
+
+    /// Retrieves a project, which a peer or cluster is stored into or which resources are filtered by. Only its members may use it.
+    pub async fn project(&self, project_id: ProjectId, resources_manager: &ResourcesManagerRef) -> Result<Project, Status> {
+        self.authorize_project(project_id)?;
+        resources_manager.get::<Project>(project_id).await
+            .map_err(|cause| Status::internal(cause.to_string()))?
+            .ok_or_else(|| Status::not_found(format!("CARL says, project <{project_id}> does not exist.")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use opendut_types::project::{ProjectMember, ProjectName};
+
+    use super::*;
+
+    #[test]
+    fn should_hide_the_resources_of_projects_the_user_is_not_a_member_of() -> anyhow::Result<()> {
+        let shared_peer = PeerId::random();
+        let powertrain_peer = PeerId::random();
+        let chassis_peer = PeerId::random();
+        let chassis_cluster = ClusterId::random();
+        let unassigned_peer = PeerId::random();
+
+        let powertrain = Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from("powertrain")?,
+            members: [ProjectMember::from_str("group:/lab/powertrain")?].into(),
+            peers: [powertrain_peer, shared_peer].into(),
+            clusters: HashSet::new(),
+        };
+        let chassis = Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from("chassis")?,
+            members: [ProjectMember::from_str("user:bob")?].into(),
+            peers: [chassis_peer, shared_peer].into(),
+            clusters: [chassis_cluster].into(),
+        };
+        let projects = [Clone::clone(&powertrain), chassis];
+
+        let testee = ProjectAccess::restricted(&projects, Some("alice"), &[String::from("/lab/powertrain")]);
+        assert_that!(testee.is_member_of(&powertrain.id), eq(true));
+        assert_that!(testee.may_access_peer(&powertrain_peer), eq(true));
+        assert_that!(testee.may_access_peer(&shared_peer), eq(true));
+        assert_that!(testee.may_access_peer(&unassigned_peer), eq(true));
+        assert_that!(testee.may_access_peer(&chassis_peer), eq(false));
+        assert_that!(testee.may_access_cluster(&chassis_cluster), eq(false));
+
+        let anonymous = ProjectAccess::restricted(&projects, None, &[]);
+        assert_that!(anonymous.may_access_peer(&unassigned_peer), eq(true));
+        assert_that!(anonymous.may_access_peer(&shared_peer), eq(false));
+        assert_that!(anonymous.is_member_of(&powertrain.id), eq(false));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::ops::Not;
+
+use opendut_types::service_account::ServiceAccountName;
+use tonic::Status;
+
+use crate::auth::grpc_auth_layer::GrpcMethod;
+use crate::auth::{CurrentServiceAccount, CurrentUser, HttpCaller};
+use crate::leadership::LeadershipRef;
+
+/// Read-only observer mode, configured in the `readonly` section, e.g. for a standby replica or a demo system.
+/// Requests, which create, change or delete resources, are rejected, while listing, retrieving and observing resources is still possible.
+#[derive(Clone, Debug, Default)]
+pub struct ReadOnlyOptions {
+    /// Rejects changes by all callers.
+    pub enabled: bool,
+    /// Roles or groups, whose users may only read. Tokens of service accounts are marked as read-only, when they are issued.
+    pub roles: Vec<String>,
+    /// Rejects changes by all callers, while this instance is not the elected leader.
+    pub leadership: Option<LeadershipRef>,
+}
+
+impl ReadOnlyOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("readonly.enabled")?;
+        let roles = config.get::<Vec<String>>("readonly.roles")?;
+        Ok(Self { enabled, roles, leadership: None })
+    }
+
+    /// Rejects gRPC methods, which change resources, if CARL or the credentials of the caller are read-only.
+    /// Runs after the authentication layer, which inserts the caller into the extensions of the request.
+    pub async fn intercept(self, request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        let is_read = request.extensions().get::<GrpcMethod>()
+            .is_some_and(GrpcMethod::is_read);
+        if is_read {
+            return Ok(request);
+        }
+        self.check_change(
+            request.extensions().get::<CurrentUser>(),
+            request.extensions().get::<CurrentServiceAccount>(),
+        )?;
+        Ok(request)
+    }
+
+    /// Checks a request to the REST gateway, which changes resources.
+    pub fn check_http_change(&self, caller: &HttpCaller) -> Result<(), ReadOnlyError> {
+        match caller {
+            HttpCaller::Anonymous => self.check_change(None, None),
+            HttpCaller::User(user) => self.check_change(Some(user), None),
+            HttpCaller::ServiceAccount(service_account) => self.check_change(None, Some(service_account)),
+        }
+    }
+
+    fn check_change(&self, user: Option<&CurrentUser>, service_account: Option<&CurrentServiceAccount>) -> Result<(), ReadOnlyError> {
+        if self.enabled {
+            return Err(ReadOnlyError::Instance);
+        }
+        if self.leadership.as_ref().is_some_and(|leadership| leadership.is_leader().not()) {
+            return Err(ReadOnlyError::Follower);
+        }
+        if let Some(service_account) = service_account.filter(|service_account| service_account.read_only) {
+            return Err(ReadOnlyError::ServiceAccountToken { name: Clone::clone(&service_account.name) });
+        }
+        if let Some(user) = user {
+            let claims = user.claims.additional_claims();
+            let role = claims.roles.iter().chain(claims.groups.iter())
+                .find(|role| self.roles.contains(role));
+            if let Some(role) = role {
+                return Err(ReadOnlyError::User { name: Clone::clone(&user.name), role: Clone::clone(role) });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReadOnlyError {
+    #[error("CARL says, this instance is read-only! Only listing, retrieving and observing resources is possible.")]
+    Instance,
+    #[error("CARL says, this instance is not the leader! Only listing, retrieving and observing resources is possible. Retry with the leader.")]
+    Follower,
+    #[error("CARL says, user '{name}' may only list, retrieve and observe resources, due to the role '{role}'!")]
+    User { name: String, role: String },
+    #[error("CARL says, the token of service account '{name}' is read-only! Only listing, retrieving and observing resources is possible.")]
+    ServiceAccountToken { name: ServiceAccountName },
+}
+
+impl From<ReadOnlyError> for Status {
+    fn from(error: ReadOnlyError) -> Self {
+        match error {
+            ReadOnlyError::Instance
+            | ReadOnlyError::Follower => Status::failed_precondition(error.to_string()),
+            ReadOnlyError::User { .. }
+            | ReadOnlyError::ServiceAccountToken { .. } => Status::permission_denied(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use opendut_types::service_account::{ServiceAccountId, ServiceAccountScope};
+
+    use super::*;
+    use crate::leadership::{LeaderElectionOptions, Leadership};
+
+    fn request(method: &str, service_account: Option<CurrentServiceAccount>) -> tonic::Request<()> {
+        let mut request = tonic::Request::new(());
+        request.extensions_mut().insert(GrpcMethod(String::from(method)));
+        if let Some(service_account) = service_account {
+            request.extensions_mut().insert(service_account);
+        }
+        request
+    }
+
+    fn service_account(read_only: bool) -> anyhow::Result<CurrentServiceAccount> {
+        Ok(CurrentServiceAccount {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("dashboard")?,
+            scopes: BTreeSet::from([ServiceAccountScope::Read, ServiceAccountScope::Peers]),
+            read_only,
+        })
+    }
+
+    const LIST_PEERS: &str = "/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors";
+    const STORE_PEER: &str = "/opendut.carl.services.peer_manager.PeerManager/StorePeerDescriptor";
+
+    #[tokio::test]
+    async fn should_reject_only_changes_when_read_only() -> anyhow::Result<()> {
+        let testee = ReadOnlyOptions { enabled: true, roles: vec![], leadership: None };
+
+        assert_that!(Clone::clone(&testee).intercept(request(LIST_PEERS, None)).await.is_ok(), eq(true));
+
+        let status = Clone::clone(&testee).intercept(request(STORE_PEER, None)).await.err();
+        assert_that!(status.map(|status| status.code()), some(eq(tonic::Code::FailedPrecondition)));
+
+        let status = testee.intercept(request("/opendut.carl.services.peer_manager.PeerManager/ClaimProvisioningToken", None)).await.err();
+        assert_that!(status.map(|status| status.code()), some(eq(tonic::Code::FailedPrecondition)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_changes_with_read_only_tokens_of_service_accounts() -> anyhow::Result<()> {
+        let testee = ReadOnlyOptions::default();
+
+        assert_that!(Clone::clone(&testee).intercept(request(STORE_PEER, Some(service_account(false)?))).await.is_ok(), eq(true));
+        assert_that!(Clone::clone(&testee).intercept(request(LIST_PEERS, Some(service_account(true)?))).await.is_ok(), eq(true));
+
+        let status = testee.intercept(request(STORE_PEER, Some(service_account(true)?))).await.err();
+        assert_that!(status.map(|status| status.code()), some(eq(tonic::Code::PermissionDenied)));
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn should_reject_changes_on_followers() -> anyhow::Result<()> {
+        let options = LeaderElectionOptions::Enabled { lock_id: 1, interval: Duration::from_secs(1), synchronization_interval: Duration::from_secs(1) };
+        let testee = ReadOnlyOptions { leadership: Some(Leadership::new(&options)), ..ReadOnlyOptions::default() };
+
+        assert_that!(Clone::clone(&testee).intercept(request(LIST_PEERS, None)).await.is_ok(), eq(true));
+
+        let status = testee.intercept(request(STORE_PEER, None)).await.err();
+        assert_that!(status.map(|status| status.code()), some(eq(tonic::Code::FailedPrecondition)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+use std::sync::Arc;
+
+use opendut_types::service_account::{ServiceAccountCredentials, ServiceAccountScope};
+
+use crate::actions;
+use crate::actions::{AuthenticateServiceAccountError, AuthenticateServiceAccountParams};
+use crate::auth::CurrentServiceAccount;
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Validates the credentials of service accounts against the tokens stored by CARL, as these are not known to the identity provider.
+#[derive(Clone)]
+pub struct ServiceAccountAuthenticator {
+    resources_manager: ResourcesManagerRef,
+}
+
+impl ServiceAccountAuthenticator {
+    pub fn new(resources_manager: ResourcesManagerRef) -> Self {
+        Self { resources_manager }
+    }
+
+    /// Returns `None`, if the authorization header does not carry the credentials of a service account, e.g. for an access token of the identity provider.
+    pub fn credentials(authorization: &str) -> Option<ServiceAccountCredentials> {
+        authorization.strip_prefix("Bearer ")
+            .and_then(ServiceAccountCredentials::decode)
+    }
+
+    pub async fn authenticate(&self, credentials: ServiceAccountCredentials) -> Result<CurrentServiceAccount, AuthenticateServiceAccountError> {
+        let token_id = credentials.token;
+        let service_account = actions::authenticate_service_account(AuthenticateServiceAccountParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            credentials,
+        }).await?;
+
+        let read_only = service_account.tokens.iter()
+            .any(|token| token.id == token_id && token.read_only);
+
+        Ok(CurrentServiceAccount {
+            id: service_account.id,
+            name: service_account.name,
+            scopes: service_account.scopes,
+            read_only,
+        })
+    }
+}
+
+impl fmt::Debug for ServiceAccountAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceAccountAuthenticator").finish_non_exhaustive()
+    }
+}
+
+impl CurrentServiceAccount {
+    /// Checks whether the service account was granted the scope. A missing scope means, that service accounts may not do this at all.
+    pub fn authorize(&self, required_scope: Option<ServiceAccountScope>) -> Result<(), String> {
+        match required_scope {
+            Some(scope) if self.scopes.contains(&scope) => Ok(()),
+            Some(scope) => Err(format!("CARL says, service account '{}' lacks the scope '{scope}'!", self.name)),
+            None => Err(format!("CARL says, service account '{}' is not allowed to do this!", self.name)),
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::bootstrap::{BootstrapError, BootstrapStatus, BootstrapStepState, IdentityProviderConfiguration};
+
+use crate::vpn::Vpn;
+
+pub type BootstrapRef = Arc<Bootstrap>;
+
+const STATE_FILE: &str = "state.json";
+const TOKEN_FILE: &str = "token";
+const CA_FILE: &str = "ca.pem";
+const COMPLETED_FILE: &str = "completed";
+/// Configuration written when completing the bootstrap, which is loaded on top of the other configuration sources.
+pub const CONFIG_OVERLAY_FILE: &str = "carl.toml";
+
+#[derive(Clone, Debug)]
+pub struct BootstrapOptions {
+    pub enabled: bool,
+    /// Directory in which the progress of the bootstrap, the uploaded CA and the resulting configuration are stored.
+    pub directory: PathBuf,
+}
+impl BootstrapOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("bootstrap.enabled")?;
+        let directory = PathBuf::from(config.get_string("bootstrap.directory")?);
+
+        Ok(BootstrapOptions { enabled, directory })
+    }
+}
+
+/// Guides the initial setup of a CARL instance, which otherwise requires editing several configuration files in the right order.
+///
+/// Each step is persisted in the bootstrap directory. When completing the bootstrap, the collected configuration is written
+/// as a configuration overlay, which takes effect after restarting CARL. Modifications require the bootstrap token,
+/// which is generated into the bootstrap directory, so only someone with access to the host of CARL can perform the setup.
+pub struct Bootstrap {
+    options: BootstrapOptions,
+    vpn: Vpn,
+    token: Option<String>,
+    completed_at_startup: bool,
+    state: Mutex<BootstrapState>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct BootstrapState {
+    admin: Option<BootstrapAdmin>,
+    identity_provider: Option<StoredIdentityProvider>,
+    vpn: Option<VpnVerification>,
+    #[serde(skip)]
+    completed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct BootstrapAdmin {
+    name: String,
+    email: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum StoredIdentityProvider {
+    Disabled,
+    Oidc {
+        issuer_url: String,
+        issuer_remote_url: String,
+        issuer_admin_url: String,
+        client_id: String,
+        client_secret: String,
+        lea_client_id: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "kebab-case")]
+enum VpnVerification {
+    Succeeded { detail: String },
+    Failed { cause: String },
+}
+
+impl Bootstrap {
+    pub fn create(options: BootstrapOptions, vpn: Vpn) -> anyhow::Result<BootstrapRef> {
+        if !options.enabled {
+            debug!("Bootstrap is disabled.");
+            return Ok(Arc::new(Self { options, vpn, token: None, completed_at_startup: true, state: Mutex::default() }));
+        }
+
+        std::fs::create_dir_all(&options.directory)?;
+
+        let completed = options.directory.join(COMPLETED_FILE).exists();
+        let mut state = read_state(&options.directory)?;
+        state.completed = completed;
+
+        let token = if completed {
+            None
+        } else {
+            let token_file = options.directory.join(TOKEN_FILE);
+            let token = match std::fs::read_to_string(&token_file) {
+                Ok(token) => token.trim().to_owned(),
+                Err(_) => {
+                    let token = Uuid::new_v4().simple().to_string();
+                    write_private_file(&token_file, token.as_bytes())?;
+                    token
+                }
+            };
+            info!("CARL has not been set up yet. Use the bootstrap token in '{}' to perform the initial setup, e.g. via `opendut-cleo bootstrap`.", token_file.display());
+            Some(token)
+        };
+
+        Ok(Arc::new(Self {
+            options,
+            vpn,
+            token,
+            completed_at_startup: completed,
+            state: Mutex::new(state),
+        }))
+    }
+
+    pub async fn status(&self) -> BootstrapStatus {
+        if !self.options.enabled {
+            let skipped = || BootstrapStepState::Skipped { reason: String::from("Bootstrapping is disabled.") };
+            return BootstrapStatus {
+                completed: true,
+                admin: skipped(),
+                identity_provider: skipped(),
+                ca: skipped(),
+                vpn: skipped(),
+                restart_required: false,
+            };
+        }
+        let state = self.state.lock().await;
+        self.status_of(&state)
+    }
+
+    pub async fn create_admin(&self, token: &str, name: String, email: String) -> Result<BootstrapStatus, BootstrapError> {
+        let mut state = self.authorize(token).await?;
+
+        let name = name.trim().to_owned();
+        let email = email.trim().to_owned();
+        if name.is_empty() {
+            return Err(BootstrapError::IllegalInput { message: String::from("The name of the admin must not be empty.") });
+        }
+        if !email.contains('@') {
+            return Err(BootstrapError::IllegalInput { message: format!("'{email}' is not a valid email address.") });
+        }
+
+        state.admin = Some(BootstrapAdmin { name, email });
+        self.persist(&state)?;
+        Ok(self.status_of(&state))
+    }
+
+    pub async fn configure_identity_provider(&self, token: &str, identity_provider: IdentityProviderConfiguration) -> Result<BootstrapStatus, BootstrapError> {
+        let mut state = self.authorize(token).await?;
+
+        let identity_provider = match identity_provider {
+            IdentityProviderConfiguration::Disabled => StoredIdentityProvider::Disabled,
+            IdentityProviderConfiguration::Oidc { issuer_url, issuer_remote_url, issuer_admin_url, client_id, client_secret, lea_client_id } => {
+                if client_id.is_empty() || client_secret.is_empty() || lea_client_id.is_empty() {
+                    return Err(BootstrapError::IllegalInput { message: String::from("The client ID, the client secret and the client ID of LEA must not be empty.") });
+                }
+                StoredIdentityProvider::Oidc {
+                    issuer_remote_url: issuer_remote_url.unwrap_or_else(|| Clone::clone(&issuer_url)).to_string(),
+                    issuer_url: issuer_url.to_string(),
+                    issuer_admin_url: issuer_admin_url.to_string(),
+                    client_id,
+                    client_secret,
+                    lea_client_id,
+                }
+            }
+        };
+
+        state.identity_provider = Some(identity_provider);
+        self.persist(&state)?;
+        Ok(self.status_of(&state))
+    }
+
+    pub async fn upload_ca(&self, token: &str, certificate: String) -> Result<BootstrapStatus, BootstrapError> {
+        let state = self.authorize(token).await?;
+
+        let pem = pem::parse(&certificate)
+            .map_err(|cause| BootstrapError::IllegalInput { message: format!("The CA is not a valid PEM file: {cause}") })?;
+        if pem.tag() != "CERTIFICATE" {
+            return Err(BootstrapError::IllegalInput { message: format!("Expected a PEM file with tag 'CERTIFICATE', but found '{}'.", pem.tag()) });
+        }
+
+        write_private_file(&self.options.directory.join(CA_FILE), pem::encode(&pem).as_bytes())
+            .map_err(internal_error)?;
+        Ok(self.status_of(&state))
+    }
+
+    pub async fn verify_vpn(&self, token: &str) -> Result<BootstrapStatus, BootstrapError> {
+        let mut state = self.authorize(token).await?;
+
+        match &self.vpn {
+            Vpn::Disabled => {}
+            Vpn::Enabled { vpn_client } => {
+                let verification = match vpn_client.check_connection().await {
+                    Ok(()) => VpnVerification::Succeeded { detail: String::from("VPN management service is reachable.") },
+                    Err(cause) => {
+                        warn!("Verifying the connection to the VPN management service failed: {cause}");
+                        VpnVerification::Failed { cause: cause.to_string() }
+                    }
+                };
+                state.vpn = Some(verification);
+                self.persist(&state)?;
+            }
+        }
+        Ok(self.status_of(&state))
+    }
+
+    pub async fn complete(&self, token: &str) -> Result<BootstrapStatus, BootstrapError> {
+        let mut state = self.authorize(token).await?;
+
+        let status = self.status_of(&state);
+        let incomplete_steps = status.steps().into_iter()
+            .filter(|(_, state)| !state.is_finished())
+            .map(|(step, _)| step.to_string())
+            .collect::<Vec<_>>();
+        if !incomplete_steps.is_empty() {
+            return Err(BootstrapError::StepsIncomplete { steps: incomplete_steps });
+        }
+
+        let overlay = config_overlay(&state, &self.options.directory.join(CA_FILE));
+        write_private_file(&self.options.directory.join(CONFIG_OVERLAY_FILE), overlay.as_bytes())
+            .map_err(internal_error)?;
+        write_private_file(&self.options.directory.join(COMPLETED_FILE), chrono::Utc::now().to_rfc3339().as_bytes())
+            .map_err(internal_error)?;
+        if let Err(cause) = std::fs::remove_file(self.options.directory.join(TOKEN_FILE)) {
+            warn!("Failed to remove bootstrap token after completing the bootstrap: {cause}");
+        }
+
+        state.completed = true;
+        info!("Bootstrap completed. Restart CARL to apply the configuration written to '{}'.", self.options.directory.join(CONFIG_OVERLAY_FILE).display());
+        Ok(self.status_of(&state))
+    }
+
+    async fn authorize(&self, token: &str) -> Result<tokio::sync::MutexGuard<'_, BootstrapState>, BootstrapError> {
+        if !self.options.enabled {
+            return Err(BootstrapError::Disabled);
+        }
+        let state = self.state.lock().await;
+        if state.completed {
+            return Err(BootstrapError::AlreadyCompleted);
+        }
+        match &self.token {
+            Some(expected) if expected == token.trim() => Ok(state),
+            _ => Err(BootstrapError::InvalidToken),
+        }
+    }
+
+    fn persist(&self, state: &BootstrapState) -> Result<(), BootstrapError> {
+        let json = serde_json::to_vec_pretty(state)
+            .map_err(|cause| internal_error(cause.into()))?;
+        write_private_file(&self.options.directory.join(STATE_FILE), &json)
+            .map_err(internal_error)
+    }
+
+    fn status_of(&self, state: &BootstrapState) -> BootstrapStatus {
+        let admin = match &state.admin {
+            None => BootstrapStepState::Pending,
+            Some(admin) => BootstrapStepState::Done { detail: format!("{} <{}>", admin.name, admin.email) },
+        };
+        let identity_provider = match &state.identity_provider {
+            None => BootstrapStepState::Pending,
+            Some(StoredIdentityProvider::Disabled) => BootstrapStepState::Skipped { reason: String::from("Authentication is disabled.") },
+            Some(StoredIdentityProvider::Oidc { issuer_url, client_id, .. }) => BootstrapStepState::Done { detail: format!("Issuer '{issuer_url}' with client '{client_id}'") },
+        };
+        let ca = if self.options.directory.join(CA_FILE).exists() {
+            BootstrapStepState::Done { detail: String::from("CA uploaded.") }
+        } else {
+            BootstrapStepState::Pending
+        };
+        let vpn = match (&self.vpn, &state.vpn) {
+            (Vpn::Disabled, _) => BootstrapStepState::Skipped { reason: String::from("VPN is disabled via 'vpn.enabled'.") },
+            (Vpn::Enabled { .. }, None) => BootstrapStepState::Pending,
+            (Vpn::Enabled { .. }, Some(VpnVerification::Succeeded { detail })) => BootstrapStepState::Done { detail: Clone::clone(detail) },
+            (Vpn::Enabled { .. }, Some(VpnVerification::Failed { cause })) => BootstrapStepState::Failed { cause: Clone::clone(cause) },
+        };
+
+        BootstrapStatus {
+            completed: state.completed,
+            admin,
+            identity_provider,
+            ca,
+            vpn,
+            restart_required: state.completed && !self.completed_at_startup,
+        }
+    }
+}
+
+fn read_state(directory: &Path) -> anyhow::Result<BootstrapState> {
+    let state_file = directory.join(STATE_FILE);
+    if state_file.exists() {
+        let json = std::fs::read(&state_file)?;
+        Ok(serde_json::from_slice(&json)?)
+    } else {
+        Ok(BootstrapState::default())
+    }
+}
+
+fn config_overlay(state: &BootstrapState, ca_file: &Path) -> String {
+    let mut overlay = toml_edit::DocumentMut::new();
+    overlay["network"] = toml_edit::table();
+    overlay["network"]["tls"] = toml_edit::table();
+    overlay["network"]["tls"]["ca"] = toml_edit::value(ca_file.display().to_string());
+    overlay["network"]["oidc"] = toml_edit::table();
+
+    match &state.identity_provider {
+        None | Some(StoredIdentityProvider::Disabled) => {
+            overlay["network"]["oidc"]["enabled"] = toml_edit::value(false);
+        }
+        Some(StoredIdentityProvider::Oidc { issuer_url, issuer_remote_url, issuer_admin_url, client_id, client_secret, lea_client_id }) => {
+            overlay["network"]["oidc"]["enabled"] = toml_edit::value(true);
+            overlay["network"]["oidc"]["client"] = toml_edit::table();
+            overlay["network"]["oidc"]["client"]["id"] = toml_edit::value(client_id);
+            overlay["network"]["oidc"]["client"]["secret"] = toml_edit::value(client_secret);
+            overlay["network"]["oidc"]["client"]["issuer"]["url"] = toml_edit::value(issuer_url);
+            overlay["network"]["oidc"]["client"]["issuer"]["remote"]["url"] = toml_edit::value(issuer_remote_url);
+            overlay["network"]["oidc"]["client"]["issuer"]["admin"]["url"] = toml_edit::value(issuer_admin_url);
+            overlay["network"]["oidc"]["lea"] = toml_edit::table();
+            overlay["network"]["oidc"]["lea"]["client"]["id"] = toml_edit::value(lea_client_id);
+            overlay["network"]["oidc"]["lea"]["issuer"]["url"] = toml_edit::value(issuer_remote_url);
+        }
+    }
+    overlay.to_string()
+}
+
+/// Only readable by the user running CARL, since the files may contain the client secret or the bootstrap token.
+fn write_private_file(path: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    Ok(())
+}
+
+fn internal_error(cause: anyhow::Error) -> BootstrapError {
+    BootstrapError::Internal { cause: cause.to_string() }
+}
+
+/// Path of the configuration overlay written by a completed bootstrap, if there is one.
+pub fn config_overlay_file(config: &config::Config) -> Option<PathBuf> {
+    let options = BootstrapOptions::load(config).ok()?;
+    let overlay_file = options.directory.join(CONFIG_OVERLAY_FILE);
+    (options.enabled && overlay_file.is_file()).then_some(overlay_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    const CA: &str = "-----BEGIN CERTIFICATE-----\nMIIBszCCAVmgAwIBAgIUQ2F0YXN0cm9waGljYWxseVNpbXBsZTAKBggqhkjOPQQD\n-----END CERTIFICATE-----\n";
+
+    fn fixture(directory: &TempDir) -> anyhow::Result<(BootstrapRef, String)> {
+        let bootstrap = Bootstrap::create(BootstrapOptions { enabled: true, directory: directory.to_path_buf() }, Vpn::Disabled)?;
+        let token = std::fs::read_to_string(directory.join(TOKEN_FILE))?;
+        Ok((bootstrap, token))
+    }
+
+    #[tokio::test]
+    async fn should_reject_modifications_with_an_invalid_token() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let (bootstrap, _) = fixture(&directory)?;
+
+        let result = bootstrap.create_admin("invalid", String::from("Admin"), String::from("admin@example.com")).await;
+
+        assert_that!(result, err(eq(BootstrapError::InvalidToken)));
+        assert_that!(bootstrap.status().await.admin, eq(BootstrapStepState::Pending));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refuse_completion_until_all_steps_are_finished() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let (bootstrap, token) = fixture(&directory)?;
+
+        bootstrap.create_admin(&token, String::from("Admin"), String::from("admin@example.com")).await?;
+        let result = bootstrap.complete(&token).await;
+        assert_that!(result, err(eq(BootstrapError::StepsIncomplete { steps: vec![String::from("identity-provider"), String::from("ca")] })));
+
+        bootstrap.configure_identity_provider(&token, IdentityProviderConfiguration::Disabled).await?;
+        bootstrap.upload_ca(&token, String::from(CA)).await?;
+        let status = bootstrap.complete(&token).await?;
+
+        assert_that!(status.completed, eq(true));
+        assert_that!(status.restart_required, eq(true));
+        assert_that!(directory.join(COMPLETED_FILE).exists(), eq(true));
+        assert_that!(directory.join(TOKEN_FILE).exists(), eq(false));
+
+        let overlay = std::fs::read_to_string(directory.join(CONFIG_OVERLAY_FILE))?;
+        assert_that!(overlay, contains_substring("enabled = false"));
+        assert_that!(overlay, contains_substring(CA_FILE));
+
+        let result = bootstrap.create_admin(&token, String::from("Admin"), String::from("admin@example.com")).await;
+        assert_that!(result, err(eq(BootstrapError::AlreadyCompleted)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_resume_from_persisted_progress() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let (bootstrap, token) = fixture(&directory)?;
+        bootstrap.create_admin(&token, String::from("Admin"), String::from("admin@example.com")).await?;
+        drop(bootstrap);
+
+        let (bootstrap, resumed_token) = fixture(&directory)?;
+
+        assert_that!(resumed_token, eq(token.as_str()));
+        assert_that!(bootstrap.status().await.admin, eq(BootstrapStepState::Done { detail: String::from("Admin <admin@example.com>") }));
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::ops::Not;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use clap::{Parser, Subcommand};
+
+use crate::disaster_recovery::{Database, DisasterRecoveryOptions};
+use crate::disaster_recovery::artifacts::ArtifactBackupOptions;
+use crate::persistence::database;
+use crate::resources::storage::PersistenceOptions;
+use crate::secrets::SecretsBackend;
+use crate::{disaster_recovery, secrets, settings};
+
+#[derive(Debug, Parser)]
+#[command(name = "opendut-carl")]
+#[command(about = "Manage the peers and clusters of openDuT.")]
+#[command(long_version = crate::app_info::formatted())]
+struct Cli {
+    /// Without a command, the CARL server is started
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Disaster recovery via the dumps of the persistence database, which are shipped to the standby location
+    Dr {
+        #[command(subcommand)]
+        command: DrCommand,
+    },
+    /// Apply or revert migrations of the persistence database, for a controlled upgrade or downgrade of its schema
+    Migrate {
+        /// Version of the schema to migrate to, e.g. '2024-11-11-090000'. Defaults to the latest version known to this binary.
+        /// Migrating to an older version reverts the newer migrations, which is needed before running an older binary of CARL.
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum DrCommand {
+    /// Check that a complete and recent dump is available at the standby location, without changing anything
+    Verify,
+    /// Restore a dump into the database of this standby instance, so that it can take over once started
+    Activate {
+        /// File name of the dump to restore, as listed in the manifest of the standby location. Defaults to the latest dump.
+        #[arg(long)]
+        artifact: Option<String>,
+
+        /// Restore even though the verification failed, e.g. because the primary instance stopped shipping a while ago
+        #[arg(long)]
+        force: bool,
+    },
+    /// Incremental backups of the files in the artifact directories, e.g. recorded traces or diagnostic bundles
+    Artifacts {
+        #[command(subcommand)]
+        command: ArtifactsCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ArtifactsCommand {
+    /// Back up the files of the artifact directories, storing only the chunks which are not contained in the backup directory yet
+    Backup,
+    /// Check the signature of the latest manifest and that all of its chunks are present and intact, without changing anything
+    Verify,
+    /// Restore the files of a backup into the artifact directories
+    Restore {
+        /// File name of the manifest to restore, as listed in the backup directory. Defaults to the latest backup.
+        #[arg(long)]
+        manifest: Option<String>,
+
+        /// Restore into this directory instead, with one subdirectory per artifact directory
+        #[arg(long)]
+        target: Option<PathBuf>,
+    },
+}
+
+pub async fn cli(banner: &str) -> anyhow::Result<()> {
+    let args = Cli::parse();
+
+    match args.command {
+        None => {
+            println!("{}", crate::app_info::formatted_with_banner(banner));
+            crate::create_with_telemetry(opendut_util::settings::Config::default()).await
+        }
+        Some(Commands::Dr { command }) => disaster_recovery(command).await,
+        Some(Commands::Migrate { to }) => migrate(to).await,
+    }
+}
+
+async fn migrate(version: Option<String>) -> anyhow::Result<()> {
+    let settings = settings::load_with_overrides(opendut_util::settings::Config::default())?;
+
+    let secrets_backend = SecretsBackend::load(&settings.config)?;
+    let (settings, _) = secrets::resolve(settings, &secrets_backend).await
+        .context("Error while retrieving secrets from backend.")?;
+
+    let PersistenceOptions::Enabled { database_connect_info, .. } = PersistenceOptions::load(&settings.config)? else {
+        return Err(anyhow!("Persistence is disabled, so there is no database to migrate."));
+    };
+
+    let summary = database::migrate(&database_connect_info, version.as_deref()).await
+        .context("Failed to migrate the database.")?;
+
+    for version in &summary.reverted {
+        println!("Reverted migration {version}.");
+    }
+    for version in &summary.applied {
+        println!("Applied migration {version}.");
+    }
+    println!("The database schema is at version {}.", summary.schema_version);
+    Ok(())
+}
+
+async fn disaster_recovery(command: DrCommand) -> anyhow::Result<()> {
+    let settings = settings::load_with_overrides(opendut_util::settings::Config::default())?;
+
+    let secrets_backend = SecretsBackend::load(&settings.config)?;
+    let (settings, _) = secrets::resolve(settings, &secrets_backend).await
+        .context("Error while retrieving secrets from backend.")?;
+
+    if let DrCommand::Artifacts { command } = command {
+        return artifacts(command, ArtifactBackupOptions::load(&settings.config)?).await;
+    }
+
+    let database = Database::try_from(PersistenceOptions::load(&settings.config)?)?;
+    let options = DisasterRecoveryOptions::load(&settings.config)?;
+
+    let report = disaster_recovery::verify(&database, &options);
+    print!("{report}");
+
+    match command {
+        DrCommand::Verify => {
+            if report.is_ok() {
+                println!("The standby location is ready for activation.");
+                Ok(())
+            } else {
+                Err(anyhow!("The standby location is not ready for activation."))
+            }
+        }
+        DrCommand::Activate { artifact, force } => {
+            if report.is_ok().not() && force.not() {
+                return Err(anyhow!("Not activating, because the verification failed. Pass --force to activate nonetheless."));
+            }
+
+            let artifact = disaster_recovery::activate(&database, &options, artifact.as_deref()).await?;
+            println!("Restored the database from dump '{}', which was created at {}.", artifact.file, artifact.created_at);
+            println!("Start CARL on this instance to take over, and point DNS or the load balancer of CARL to it.");
+            Ok(())
+        }
+        DrCommand::Artifacts { .. } => unreachable!("Backups of artifacts do not require the database."),
+    }
+}
+
+async fn artifacts(command: ArtifactsCommand, options: ArtifactBackupOptions) -> anyhow::Result<()> {
+    match command {
+        ArtifactsCommand::Backup => {
+            let summary = disaster_recovery::artifacts::backup(&options).await?;
+            println!(
+                "Backed up {} files into '{}', writing {} new chunks with {} bytes.",
+                summary.files, summary.manifest_file, summary.written_chunks, summary.written_bytes,
+            );
+            Ok(())
+        }
+        ArtifactsCommand::Verify => {
+            let report = disaster_recovery::artifacts::verify(&options);
+            print!("{report}");
+            if report.is_ok() {
+                println!("The latest backup of the artifacts can be restored.");
+                Ok(())
+            } else {
+                Err(anyhow!("The latest backup of the artifacts cannot be restored."))
+            }
+        }
+        ArtifactsCommand::Restore { manifest, target } => {
+            let summary = disaster_recovery::artifacts::restore(&options, manifest, target).await?;
+            println!("Restored {} files from backup '{}', which was created at {}.", summary.files, summary.manifest_file, summary.created_at);
+            Ok(())
+        }
+    }
+}
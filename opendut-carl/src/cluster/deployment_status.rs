@@ -0,0 +1,49 @@
+use tracing::{info, warn};
+
+use opendut_types::cluster::{ClusterDeploymentStatus, ClusterId, DeploymentState};
+use opendut_types::peer::PeerId;
+
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+/// Applies a transition to the deployment status of a cluster, starting with an empty status, if none was stored yet.
+pub async fn update(
+    resources_manager: &ResourcesManagerRef,
+    cluster_id: ClusterId,
+    transition: impl FnOnce(&mut ClusterDeploymentStatus),
+) -> PersistenceResult<()> {
+    resources_manager.resources_mut(|resources| {
+        let mut status = resources.get::<ClusterDeploymentStatus>(cluster_id)?
+            .unwrap_or_else(|| ClusterDeploymentStatus::pending(cluster_id, []));
+        transition(&mut status);
+        resources.insert(cluster_id, status)
+    }).await?
+}
+
+/// Records the report of a peer about applying its configuration in the deployment status of each cluster, which was rolled out to it.
+pub async fn record_peer_report(
+    resources_manager: &ResourcesManagerRef,
+    peer_id: PeerId,
+    outcome: Result<(), String>,
+) -> PersistenceResult<()> {
+    let transitions = resources_manager.resources_mut(|resources| {
+        let mut transitions = Vec::new();
+        for mut status in resources.list::<ClusterDeploymentStatus>()? {
+            if status.report(peer_id, Clone::clone(&outcome)) {
+                let state = status.get(peer_id).cloned().unwrap_or(DeploymentState::Pending);
+                transitions.push((status.id, state, status.state()));
+                resources.insert(status.id, status)?;
+            }
+        }
+        Ok::<_, PersistenceError>(transitions)
+    }).await??;
+
+    for (cluster_id, peer_state, cluster_state) in transitions {
+        match peer_state {
+            DeploymentState::Failed { cause } => warn!("Peer <{peer_id}> failed to roll out the deployment of cluster <{cluster_id}>, which is now {cluster_state}:\n  {cause}"),
+            _ => info!("Peer <{peer_id}> is {peer_state} in the deployment of cluster <{cluster_id}>, which is now {cluster_state}."),
+        }
+    }
+    Ok(())
+}
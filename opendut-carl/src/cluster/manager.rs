@@ -1,30 +1,41 @@
 use std::collections::{HashMap, HashSet};
+use std::ops::Not;
 use std::sync::Arc;
+use std::time::Duration;
 use anyhow::Context;
 use tokio::sync::Mutex;
 
 use futures::future::join_all;
 use futures::FutureExt;
-use tracing::{debug, error, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
-use opendut_carl_api::carl::cluster::{DeleteClusterDeploymentError, GetClusterConfigurationError, GetClusterDeploymentError, ListClusterConfigurationsError, ListClusterDeploymentsError, StoreClusterDeploymentError};
-use opendut_types::cluster::{ClusterAssignment, ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName, PeerClusterAssignment};
-use opendut_types::peer::state::{PeerState, PeerUpState};
+use opendut_carl_api::carl::cluster::{DeleteClusterDeploymentError, GetClusterConfigurationError, GetClusterDeploymentError, GetClusterDeploymentStatusError, ListClusterConfigurationsError, ListClusterDeploymentsError, StoreClusterDeploymentError};
+use opendut_types::cluster::{bind_devices, ClusterAssignment, ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId, ClusterName, DeploymentState, DeviceInterfaceKind, PeerClusterAssignment, SelectableDevice};
+use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::Project;
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
 use opendut_types::util::Port;
 
 use crate::actions;
+use crate::cluster::deployment_status;
+use crate::cluster::scheduler::{Admission, DeploymentSchedulerRef};
 use crate::actions::{AssignClusterOptions, AssignClusterParams, DeleteClusterDeploymentParams, DetermineClusterPeerStatesParams, GetPeerStateParams, ListPeerDescriptorsParams, StoreClusterConfigurationParams};
+use crate::leadership::LeadershipRef;
 use crate::peer::broker::PeerMessagingBrokerRef;
 use crate::persistence::error::PersistenceResult;
+use crate::policy::ImagePolicy;
 use crate::resources::manager::{ResourcesManagerRef, SubscriptionEvent};
+use crate::resources::subscription::ReceiveError;
 use crate::resources::storage::ResourcesStorageApi;
+use crate::statistics::{DeploymentUsage, StatisticsRef};
 use crate::vpn::Vpn;
 
 pub type ClusterManagerRef = Arc<Mutex<ClusterManager>>;
 
+const QUEUED_DEPLOYMENTS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum DeployClusterError {
     #[error("Cluster <{0}> not found!")]
@@ -41,6 +52,12 @@ pub enum DeployClusterError {
         cluster_id: ClusterId,
         cluster_name: ClusterName,
     },
+    #[error("Cluster '{cluster_name}' <{cluster_id}> contains executors with container images, which are not allowed:\n  {}", violations.join("\n  "))]
+    ImageNotAllowed {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        violations: Vec<String>,
+    },
     #[error("An error occurred while deploying cluster <{cluster_id}>:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -52,8 +69,12 @@ pub struct ClusterManager {
     resources_manager: ResourcesManagerRef,
     peer_messaging_broker: PeerMessagingBrokerRef,
     vpn: Vpn,
+    statistics: StatisticsRef,
+    deployment_scheduler: DeploymentSchedulerRef,
     options: ClusterManagerOptions,
     can_server_port_counter: u16,
+    /// Devices bound by the device selectors of a cluster during its latest deployment.
+    bound_devices: HashMap<ClusterId, HashSet<DeviceId>>,
 }
 
 impl ClusterManager {
@@ -61,19 +82,27 @@ impl ClusterManager {
         resources_manager: ResourcesManagerRef,
         peer_messaging_broker: PeerMessagingBrokerRef,
         vpn: Vpn,
+        statistics: StatisticsRef,
+        deployment_scheduler: DeploymentSchedulerRef,
+        leadership: LeadershipRef,
         options: ClusterManagerOptions,
     ) -> ClusterManagerRef {
         let can_server_port_counter = options.can_server_port_range_start;
 
         let self_ref = Arc::new(Mutex::new(Self {
             resources_manager: Arc::clone(&resources_manager),
-            peer_messaging_broker,
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
             vpn,
+            statistics,
+            deployment_scheduler: Arc::clone(&deployment_scheduler),
             options,
-            can_server_port_counter
+            can_server_port_counter,
+            bound_devices: HashMap::new(),
         }));
 
-        Self::schedule_redeploying_clusters_when_all_peers_become_available(resources_manager, Arc::clone(&self_ref)).await;
+        Self::schedule_redeploying_clusters_when_all_peers_become_available(Arc::clone(&resources_manager), Arc::clone(&self_ref)).await;
+        Self::schedule_reevaluating_device_selectors_when_peer_labels_change(resources_manager, leadership, Arc::clone(&self_ref)).await;
+        Self::schedule_queued_deployments(peer_messaging_broker, deployment_scheduler, Arc::clone(&self_ref));
 
         self_ref
     }
@@ -105,13 +134,12 @@ impl ClusterManager {
         let peer_descriptors = actions::list_peer_descriptors(ListPeerDescriptorsParams { resources_manager: self.resources_manager.clone() }).await
             .map_err(|cause| StoreClusterDeploymentError::Internal { cluster_id, cluster_name: None, cause: cause.to_string() })?;
 
-        let cluster_peer_ids = peer_descriptors.into_iter()
+        let cluster_peers = peer_descriptors.into_iter()
             .filter(|peer| peer.topology.devices.iter().any(|device| cluster_config.devices.contains(&device.id)))
-            .map(|peer| peer.id)
-            .collect::<Vec<PeerId>>();
+            .collect::<Vec<PeerDescriptor>>();
 
         let mut blocked_peers_by_id: Vec<PeerId> = Vec::new();
-        for peer_id in cluster_peer_ids {
+        for peer_id in cluster_peers.iter().map(|peer| peer.id) {
             let get_peer_state_params = GetPeerStateParams {
                 peer: peer_id,
                 resources_manager: self.resources_manager.clone(),
@@ -120,7 +148,7 @@ impl ClusterManager {
                 .await
                 .map_err(|get_peer_state_error| StoreClusterDeploymentError::Internal { cluster_id, cluster_name: None, cause: get_peer_state_error.to_string() })?;
 
-            if let PeerState::Up { inner: PeerUpState::Blocked(_), .. } = peer_state {
+            if let PeerState::Up { inner: PeerUpState::Blocked(_), .. } | PeerState::Unreachable { inner: PeerUpState::Blocked(_), .. } = peer_state {
                 blocked_peers_by_id.push(peer_id);
             }
         }
@@ -135,6 +163,19 @@ impl ClusterManager {
         };
         actions::store_cluster_deployment(store_cluster_deployment_params).await?;
 
+        let mut pending_peers = cluster_peers.iter().map(|peer| peer.id).collect::<Vec<_>>();
+        if pending_peers.contains(&cluster_config.leader).not() {
+            pending_peers.push(cluster_config.leader);
+        }
+        self.resources_manager.insert(cluster_id, ClusterDeploymentStatus::pending(cluster_id, pending_peers)).await
+            .map_err(|cause| StoreClusterDeploymentError::Internal { cluster_id, cluster_name: Some(Clone::clone(&cluster_config.name)), cause: cause.to_string() })?;
+
+        self.statistics.record_deployment(cluster_id, DeploymentUsage {
+            peers: cluster_peers.len(),
+            devices: cluster_config.devices.len(),
+            executors: cluster_peers.iter().map(|peer| peer.executors.executors.len()).sum(),
+        });
+
         if let Err(error) = self.deploy_cluster_if_all_peers_available(cluster_id).await {
             error!("Failed to deploy cluster <{cluster_id}> after storing cluster deployment, despite all peers being available, due to:\n  {error}");
         }
@@ -142,13 +183,18 @@ impl ClusterManager {
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn delete_cluster_deployment(&self, cluster_id: ClusterId) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
+    pub async fn delete_cluster_deployment(&mut self, cluster_id: ClusterId) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
         let delete_cluster_deployment_params = DeleteClusterDeploymentParams {
             resources_manager: Arc::clone(&self.resources_manager),
             vpn: Clone::clone(&self.vpn),
             cluster_id
         };
-        actions::delete_cluster_deployment(delete_cluster_deployment_params).await
+        let deployment = actions::delete_cluster_deployment(delete_cluster_deployment_params).await?;
+
+        self.deployment_scheduler.withdraw(cluster_id);
+        self.statistics.record_undeployment(cluster_id);
+        self.bound_devices.remove(&cluster_id);
+        Ok(deployment)
     }
 
     pub async fn get_deployment(&self, cluster_id: ClusterId) -> Result<Option<ClusterDeployment>, GetClusterDeploymentError> {
@@ -158,6 +204,24 @@ impl ClusterManager {
         .map_err(|cause| GetClusterDeploymentError::Internal { cluster_id, cause: cause.to_string() })
     }
 
+    /// Progress of rolling out the deployment of a cluster to its peers.
+    /// Deployments stored before their status was tracked are reported as pending without peers, until they are deployed anew.
+    #[tracing::instrument(skip(self), level="trace")]
+    pub async fn get_deployment_status(&self, cluster_id: ClusterId) -> Result<ClusterDeploymentStatus, GetClusterDeploymentStatusError> {
+        let (deployment, status) = self.resources_manager.resources(|resources| {
+            Ok((
+                resources.get::<ClusterDeployment>(cluster_id)?,
+                resources.get::<ClusterDeploymentStatus>(cluster_id)?,
+            ))
+        }).await
+        .map_err(|cause| GetClusterDeploymentStatusError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        if deployment.is_none() {
+            return Err(GetClusterDeploymentStatusError::ClusterDeploymentNotFound { cluster_id });
+        }
+        Ok(status.unwrap_or_else(|| ClusterDeploymentStatus::pending(cluster_id, [])))
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     pub async fn list_deployment(&self) -> Result<Vec<ClusterDeployment>, ListClusterDeploymentsError> {
         self.resources_manager.resources(|resources| {
@@ -174,13 +238,111 @@ impl ClusterManager {
             loop {
                 let peer_state = peer_state_subscription.receive().await;
 
-                if let Ok(SubscriptionEvent::Inserted { id: peer_id, value: PeerState::Up { inner: PeerUpState::Available, .. } }) = peer_state {
-                    trace!("Peer <{peer_id}> is now available. Checking if any clusters can now be deployed...");
+                match peer_state {
+                    Ok(SubscriptionEvent::Inserted { id: peer_id, value: PeerState::Up { inner: PeerUpState::Available, .. } }) => {
+                        trace!("Peer <{peer_id}> is now available. Checking if any clusters can now be deployed...");
 
+                        let mut self_ref = self_ref.lock().await;
+                        let result = self_ref.deploy_clusters_with_new_available_peer(peer_id).await;
+                        if let Err(error) = result {
+                            error!("Error while attempting deployment of clusters in which newly available peer <{peer_id}> is contained:  \n{error}");
+                        }
+                    }
+                    Ok(SubscriptionEvent::Inserted { id: peer_id, value: PeerState::Down }) => {
+                        let mut self_ref = self_ref.lock().await;
+                        let result = self_ref.rebind_clusters_with_devices_of_lost_peer(peer_id).await;
+                        if let Err(error) = result {
+                            error!("Error while re-evaluating device selectors of clusters, which bound devices of peer <{peer_id}> that went down:  \n{error}");
+                        }
+                    }
+                    Err(ReceiveError::Stale) => {
+                        warn!("Subscription to peer states was closed as stale. Subscribing anew.");
+                        peer_state_subscription = resources_manager.subscribe::<PeerState>().await;
+                    }
+                    Err(ReceiveError::Closed) => break,
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    /// Device selectors can select devices by the labels of their peers, so they are re-evaluated, whenever the labels of a peer change.
+    /// Followers only keep track of the labels, as the leader re-evaluates the device selectors.
+    async fn schedule_reevaluating_device_selectors_when_peer_labels_change(resources_manager: ResourcesManagerRef, leadership: LeadershipRef, self_ref: ClusterManagerRef) {
+        let mut peer_descriptor_subscription = resources_manager.subscribe::<PeerDescriptor>().await;
+
+        let mut known_labels = match resources_manager.list::<PeerDescriptor>().await {
+            Ok(peers) => peers.into_iter()
+                .map(|peer| (peer.id, peer.labels))
+                .collect::<HashMap<_, _>>(),
+            Err(cause) => {
+                warn!("Failed to determine the labels of the peers. Device selectors are re-evaluated only after the second change of a peer's labels:\n  {cause}");
+                HashMap::new()
+            }
+        };
+
+        tokio::spawn(async move {
+            loop {
+                let peer_descriptor = peer_descriptor_subscription.receive().await;
+
+                match peer_descriptor {
+                    Ok(SubscriptionEvent::Inserted { id: peer_id, value: peer }) => {
+                        //devices of new peers are selectable only once the peer becomes available, which is handled via its state
+                        let previous_labels = known_labels.insert(peer_id, Clone::clone(&peer.labels));
+                        if previous_labels.is_some_and(|previous_labels| previous_labels != peer.labels) && leadership.is_leader() {
+                            let mut self_ref = self_ref.lock().await;
+                            let result = self_ref.reevaluate_device_selectors_for_relabeled_peer(peer).await;
+                            if let Err(error) = result {
+                                error!("Error while re-evaluating device selectors of clusters after the labels of peer <{peer_id}> changed:  \n{error}");
+                            }
+                        }
+                    }
+                    Ok(SubscriptionEvent::Removed { id: peer_id, .. }) => {
+                        known_labels.remove(&peer_id);
+                    }
+                    Err(ReceiveError::Stale) => {
+                        warn!("Subscription to peer descriptors was closed as stale. Subscribing anew.");
+                        peer_descriptor_subscription = resources_manager.subscribe::<PeerDescriptor>().await;
+                    }
+                    Err(ReceiveError::Closed) => break,
+                    Err(ReceiveError::Lagged { .. }) => {}
+                }
+            }
+        });
+    }
+
+    /// Starts queued deployments, whenever a progressing deployment completed, because all of its peers reported back, or timed out.
+    /// Clusters waiting for the clusters they depend on are re-evaluated, whenever a peer reported back.
+    fn schedule_queued_deployments(peer_messaging_broker: PeerMessagingBrokerRef, deployment_scheduler: DeploymentSchedulerRef, self_ref: ClusterManagerRef) {
+        let mut configuration_reports = peer_messaging_broker.subscribe_configuration_reports();
+
+        tokio::spawn(async move {
+            let mut expiry_interval = tokio::time::interval(QUEUED_DEPLOYMENTS_CHECK_INTERVAL);
+            loop {
+                let (slots_freed, peer_reported) = tokio::select! {
+                    report = configuration_reports.recv() => match report {
+                        Ok(peer_id) => (deployment_scheduler.peer_reported(peer_id), true),
+                        Err(cause) => {
+                            warn!("Missed reports of peers about applying their configuration:\n  {cause}");
+                            (false, true)
+                        }
+                    },
+                    _ = expiry_interval.tick() => (deployment_scheduler.expire(), false),
+                };
+
+                if peer_reported {
                     let mut self_ref = self_ref.lock().await;
-                    let result = self_ref.deploy_clusters_with_new_available_peer(peer_id).await;
-                    if let Err(error) = result {
-                        error!("Error while attempting deployment of clusters in which newly available peer <{peer_id}> is contained:  \n{error}");
+                    if let Err(error) = self_ref.deploy_clusters_awaiting_dependencies().await {
+                        error!("Error while attempting deployment of clusters, which wait for the clusters they depend on:\n  {error}");
+                    }
+                }
+
+                if slots_freed {
+                    let mut self_ref = self_ref.lock().await;
+                    for cluster_id in deployment_scheduler.admissible() {
+                        if let Err(error) = self_ref.deploy_cluster_if_all_peers_available(cluster_id).await {
+                            error!("Error while attempting queued deployment of cluster <{cluster_id}>:\n  {error}");
+                        }
                     }
                 }
             }
@@ -198,11 +360,23 @@ impl ClusterManager {
 
         let cluster_configurations = self.resources_manager.list::<ClusterConfiguration>().await?;
 
+        let deployed_cluster_ids = self.resources_manager.list::<ClusterDeployment>().await?
+            .into_iter()
+            .map(|deployment| deployment.id)
+            .collect::<HashSet<_>>();
+
         let clusters_containing_devices_of_upped_peer = cluster_configurations.into_iter()
-            .filter(|cluster_configuration|
-                cluster_configuration.devices.iter()
-                    .any(|device| peer_devices.contains(device))
-            ).collect::<Vec<_>>();
+            .filter(|cluster_configuration| {
+                let contains_devices_of_upped_peer = cluster_configuration.devices.iter()
+                    .any(|device| peer_devices.contains(device));
+
+                // Devices of the upped peer might satisfy the selectors of a cluster, which is still waiting for devices.
+                let awaits_selectable_devices = cluster_configuration.device_selectors.is_empty().not()
+                    && deployed_cluster_ids.contains(&cluster_configuration.id)
+                    && self.bound_devices.contains_key(&cluster_configuration.id).not();
+
+                contains_devices_of_upped_peer || awaits_selectable_devices
+            }).collect::<Vec<_>>();
 
         for cluster in clusters_containing_devices_of_upped_peer {
             self.deploy_cluster_if_all_peers_available(cluster.id).await?;
@@ -210,6 +384,101 @@ impl ClusterManager {
         Ok(())
     }
 
+    /// Re-evaluates the device selectors of deployed clusters, which bound a device of a peer that went down,
+    /// and redeploys them with a replacement device, if one is available.
+    async fn rebind_clusters_with_devices_of_lost_peer(&mut self, peer_id: PeerId) -> anyhow::Result<()> {
+        let Some(peer_descriptor) = self.resources_manager.get::<PeerDescriptor>(peer_id).await? else {
+            return Ok(());
+        };
+        let peer_devices = peer_descriptor.topology.devices.iter()
+            .map(|device| device.id)
+            .collect::<HashSet<_>>();
+
+        let affected_clusters = self.bound_devices.iter()
+            .filter(|(_, bound_devices)| bound_devices.is_disjoint(&peer_devices).not())
+            .map(|(cluster_id, _)| *cluster_id)
+            .collect::<Vec<_>>();
+
+        for cluster_id in affected_clusters {
+            let Some(cluster_config) = self.resources_manager.get::<ClusterConfiguration>(cluster_id).await? else {
+                self.bound_devices.remove(&cluster_id);
+                continue;
+            };
+
+            if cluster_config.devices.is_disjoint(&peer_devices).not() {
+                debug!("Peer <{peer_id}> contains devices explicitly configured for cluster <{cluster_id}>, which cannot be replaced. Not re-evaluating device selectors.");
+                continue;
+            }
+
+            if let Some(previously_bound) = self.bound_devices.get_mut(&cluster_id) {
+                previously_bound.retain(|device| peer_devices.contains(device).not());
+            }
+
+            match self.bind_selected_devices(&cluster_config).await? {
+                Some(bound_devices) => {
+                    info!("Devices bound to cluster <{cluster_id}> were lost with peer <{peer_id}>. Redeploying with replacement devices...");
+                    self.bound_devices.insert(cluster_id, bound_devices);
+                    self.deploy_cluster(cluster_id).await?;
+                }
+                None => {
+                    warn!("Devices bound to cluster <{cluster_id}> were lost with peer <{peer_id}> and no replacement devices are available. Cluster will be redeployed, once the device selectors can be satisfied again.");
+                    self.bound_devices.remove(&cluster_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deploys clusters, which wait for devices, in case the devices of the relabeled peer satisfy their selectors now,
+    /// and redeploys clusters, whose bound devices change, because the devices of the peer are selected now or not anymore.
+    async fn reevaluate_device_selectors_for_relabeled_peer(&mut self, peer: PeerDescriptor) -> anyhow::Result<()> {
+        let peer_id = peer.id;
+        let peer_devices = peer.topology.devices.iter()
+            .map(|device| device.id)
+            .collect::<HashSet<_>>();
+
+        let clusters_with_device_selectors = self.resources_manager.resources(|resources| {
+            let mut clusters = Vec::new();
+            for deployment in resources.list::<ClusterDeployment>()? {
+                if let Some(cluster_config) = resources.get::<ClusterConfiguration>(deployment.id)? {
+                    if cluster_config.device_selectors.is_empty().not() {
+                        clusters.push(cluster_config);
+                    }
+                }
+            }
+            Ok(clusters)
+        }).await?;
+
+        for cluster_config in clusters_with_device_selectors {
+            let cluster_id = cluster_config.id;
+
+            let Some(previously_bound) = self.bound_devices.get(&cluster_id).cloned() else {
+                debug!("Labels of peer <{peer_id}> changed. Checking if cluster <{cluster_id}>, which waits for devices, can now be deployed...");
+                self.deploy_cluster_if_all_peers_available(cluster_id).await?;
+                continue;
+            };
+
+            match self.bind_selected_devices(&cluster_config).await? {
+                Some(bound_devices) if bound_devices == previously_bound => {}
+                Some(bound_devices) => {
+                    let affects_peer = bound_devices.is_disjoint(&peer_devices).not() || previously_bound.is_disjoint(&peer_devices).not();
+                    if affects_peer {
+                        info!("Labels of peer <{peer_id}> changed, which changes the devices bound to cluster <{cluster_id}>. Redeploying...");
+                        self.bound_devices.insert(cluster_id, bound_devices);
+                        self.deploy_cluster(cluster_id).await?;
+                    }
+                }
+                None => {
+                    if previously_bound.is_disjoint(&peer_devices).not() {
+                        warn!("Labels of peer <{peer_id}> changed, so that the device selectors of cluster <{cluster_id}> cannot be satisfied anymore. Cluster will be redeployed, once the device selectors can be satisfied again.");
+                        self.bound_devices.remove(&cluster_id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     pub async fn deploy_cluster_if_all_peers_available(&mut self, cluster_id: ClusterId) -> Result<(), DeployClusterError> {
         let cluster_peer_states = actions::determine_cluster_peer_states(DetermineClusterPeerStatesParams {
@@ -220,9 +489,49 @@ impl ClusterManager {
 
         let unavailable_peers = cluster_peer_states.filter_unavailable_peers();
         if unavailable_peers.is_empty() {
-            debug!("All peers of cluster <{cluster_id}> are now available. Deploying...");
-            self.deploy_cluster(cluster_id).await?;
+            let cluster_config = self.resources_manager.get::<ClusterConfiguration>(cluster_id).await
+                .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?
+                .ok_or(DeployClusterError::ClusterConfigurationNotFound(cluster_id))?;
+
+            let unready_dependencies = self.unready_dependencies(&cluster_config).await?;
+            if unready_dependencies.is_empty().not() {
+                self.deployment_scheduler.withdraw(cluster_id);
+                debug!(
+                    "Not deploying cluster <{cluster_id}> yet, because the clusters it depends on are not ready: {}",
+                    unready_dependencies.iter()
+                        .map(|dependency_id| dependency_id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                return Ok(());
+            }
+
+            if cluster_config.device_selectors.is_empty().not() {
+                match self.bind_selected_devices(&cluster_config).await? {
+                    Some(bound_devices) => {
+                        self.bound_devices.insert(cluster_id, bound_devices);
+                    }
+                    None => {
+                        self.deployment_scheduler.withdraw(cluster_id);
+                        return Ok(());
+                    }
+                }
+            }
+
+            let project = self.determine_project(cluster_id).await?;
+
+            match self.deployment_scheduler.admit(cluster_id, project) {
+                Admission::Admitted => {
+                    debug!("All peers of cluster <{cluster_id}> are now available. Deploying...");
+                    self.deploy_cluster(cluster_id).await
+                        .inspect_err(|_| self.deployment_scheduler.withdraw(cluster_id))?;
+                }
+                Admission::Queued { position } => {
+                    info!("All peers of cluster <{cluster_id}> are now available, but too many deployments are in progress. Queued deployment at position {position}.");
+                }
+            }
         } else {
+            self.deployment_scheduler.withdraw(cluster_id);
             trace!(
                 "Not all peers of cluster <{cluster_id}> are available, so not deploying. Unavailable peers: {}",
                 unavailable_peers.iter()
@@ -235,6 +544,116 @@ impl ClusterManager {
     }
 
 
+    /// Dependencies of a cluster, which are not deployed or whose deployment is not ready yet.
+    async fn unready_dependencies(&self, cluster_config: &ClusterConfiguration) -> Result<Vec<ClusterId>, DeployClusterError> {
+        let cluster_id = cluster_config.id;
+
+        self.resources_manager.resources(|resources| {
+            let mut unready_dependencies = Vec::new();
+            for dependency_id in &cluster_config.dependencies {
+                let deployed = resources.get::<ClusterDeployment>(*dependency_id)?.is_some();
+                let ready = resources.get::<ClusterDeploymentStatus>(*dependency_id)?
+                    .is_some_and(|status| status.state() == DeploymentState::Ready);
+
+                if (deployed && ready).not() {
+                    unready_dependencies.push(*dependency_id);
+                }
+            }
+            Ok(unready_dependencies)
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })
+    }
+
+    /// Attempts the deployment of clusters, which were deployed before the clusters they depend on were ready.
+    async fn deploy_clusters_awaiting_dependencies(&mut self) -> anyhow::Result<()> {
+        let awaiting_clusters = self.resources_manager.resources(|resources| {
+            let mut awaiting_clusters = Vec::new();
+            for deployment in resources.list::<ClusterDeployment>()? {
+                let has_dependencies = resources.get::<ClusterConfiguration>(deployment.id)?
+                    .is_some_and(|cluster| cluster.dependencies.is_empty().not());
+                let pending = resources.get::<ClusterDeploymentStatus>(deployment.id)?
+                    .map_or(true, |status| status.state() == DeploymentState::Pending);
+
+                if has_dependencies && pending {
+                    awaiting_clusters.push(deployment.id);
+                }
+            }
+            Ok(awaiting_clusters)
+        }).await?;
+
+        for cluster_id in awaiting_clusters {
+            self.deploy_cluster_if_all_peers_available(cluster_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Binds devices of available peers to the device selectors of a cluster.
+    /// Devices of peers, which are already members of the cluster, stay selectable, so that a re-evaluation keeps its devices where possible.
+    /// Returns `None`, if the selectors cannot be satisfied by the currently available devices.
+    async fn bind_selected_devices(&self, cluster_config: &ClusterConfiguration) -> Result<Option<HashSet<DeviceId>>, DeployClusterError> {
+        let cluster_id = cluster_config.id;
+        let mut member_devices = self.bound_devices.get(&cluster_id).cloned().unwrap_or_default();
+        member_devices.extend(&cluster_config.devices);
+
+        let candidates = self.resources_manager.resources(|resources| {
+            selectable_devices(resources, &member_devices)
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        match bind_devices(&cluster_config.device_selectors, &candidates, &cluster_config.devices) {
+            Ok(bound_devices) => {
+                debug!("Bound devices to cluster <{cluster_id}> via device selectors: {}",
+                    bound_devices.iter().map(|device| device.to_string()).collect::<Vec<_>>().join(", ")
+                );
+                Ok(Some(bound_devices))
+            }
+            Err(cause) => {
+                debug!("Not deploying cluster <{cluster_id}> yet, because its device selectors cannot be satisfied:\n  {cause}");
+                Ok(None)
+            }
+        }
+    }
+
+    /// The project of a cluster is designated by a label of its leader, which allows limiting the concurrent deployments per project.
+    async fn determine_project(&self, cluster_id: ClusterId) -> Result<Option<String>, DeployClusterError> {
+        let project_label = self.deployment_scheduler.project_label();
+
+        self.resources_manager.resources(|resources| {
+            let Some(cluster_config) = resources.get::<ClusterConfiguration>(cluster_id)? else {
+                return Ok(None);
+            };
+            let project = resources.get::<PeerDescriptor>(cluster_config.leader)?
+                .and_then(|leader| leader.labels.get(project_label).map(|project| project.value().to_owned()));
+            Ok(project)
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })
+    }
+
+    /// Checks the container images of the members' executors again, as their peers may have been assigned to projects with a stricter policy since they were stored.
+    async fn image_policy_violations(&self, cluster_id: ClusterId, member_ids: &[PeerId]) -> Result<Vec<String>, DeployClusterError> {
+        let (members, projects) = self.resources_manager.resources(|resources| {
+            let members = resources.list::<PeerDescriptor>()?.into_iter()
+                .filter(|peer| member_ids.contains(&peer.id))
+                .collect::<Vec<_>>();
+            Ok((members, resources.list::<Project>()?))
+        }).await
+        .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        let violations = members.iter()
+            .flat_map(|peer| {
+                let peer_projects = projects.iter()
+                    .filter(|project| project.peers.contains(&peer.id) || project.clusters.contains(&cluster_id))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                peer.executors.executors.iter()
+                    .flat_map(move |executor| self.options.images.evaluate(executor, &peer_projects))
+                    .collect::<Vec<_>>()
+            })
+            .map(|violation| violation.message)
+            .collect();
+        Ok(violations)
+    }
+
     #[tracing::instrument(skip(self), level="debug")]
     async fn deploy_cluster(&mut self, cluster_id: ClusterId) -> Result<(), DeployClusterError> {
 
@@ -249,13 +668,31 @@ impl ClusterManager {
         }).await.map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
 
 
-        let member_interface_mapping = determine_member_interface_mapping(cluster_config.devices, all_peers, cluster_config.leader)
+        let mut cluster_devices = cluster_config.devices;
+        if let Some(bound_devices) = self.bound_devices.get(&cluster_id) {
+            cluster_devices.extend(bound_devices);
+        }
+
+        let member_interface_mapping = determine_member_interface_mapping(cluster_devices, all_peers, cluster_config.leader)
             .map_err(|cause| match cause {
-                DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id } => DeployClusterError::PeerForDeviceNotFound { device_id, cluster_id, cluster_name },
+                DetermineMemberInterfaceMappingError::PeerForDeviceNotFound { device_id } => DeployClusterError::PeerForDeviceNotFound { device_id, cluster_id, cluster_name: Clone::clone(&cluster_name) },
             })?;
 
         let member_ids = member_interface_mapping.keys().cloned().collect::<Vec<_>>();
 
+        let violations = self.image_policy_violations(cluster_id, &member_ids).await?;
+        if violations.is_empty().not() {
+            let cause = format!("Container images of executors are not allowed: {}", violations.join(" "));
+            self.update_deployment_status(cluster_id, |status| {
+                for member_id in &member_ids {
+                    status.set(*member_id, DeploymentState::Failed { cause: Clone::clone(&cause) });
+                }
+            }).await;
+            return Err(DeployClusterError::ImageNotAllowed { cluster_id, cluster_name, violations });
+        }
+
+        self.deployment_scheduler.started(cluster_id, member_ids.clone());
+
         if let Vpn::Enabled { vpn_client } = &self.vpn {
             vpn_client.create_cluster(cluster_id, &member_ids).await
                 .map_err(|cause| {
@@ -334,8 +771,11 @@ impl ClusterManager {
             bridge_name_default: self.options.bridge_name_default.clone(),
         };
 
-        for member_id in member_ids {
-            actions::assign_cluster(AssignClusterParams {
+        self.update_deployment_status(cluster_id, |status| status.configuring(&member_ids)).await;
+
+        for member_id in &member_ids {
+            let member_id = *member_id;
+            let result = actions::assign_cluster(AssignClusterParams {
                 resources_manager: Arc::clone(&self.resources_manager),
                 peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
                 peer_id: member_id,
@@ -350,14 +790,58 @@ impl ClusterManager {
                 let message = format!("Failure while assigning cluster <{cluster_id}> to peer <{member_id}>.");
                 error!("{}\n  {cause}", message);
                 DeployClusterError::Internal { cluster_id, cause: message }
-            })?;
+            });
+
+            if let Err(error) = result {
+                let cause = error.to_string();
+                self.update_deployment_status(cluster_id, |status| status.set(member_id, DeploymentState::Failed { cause })).await;
+                return Err(error);
+            }
         }
 
         Ok(())
     }
+
+    /// Failing to track the rollout must not fail the deployment itself, so errors are only logged.
+    async fn update_deployment_status(&self, cluster_id: ClusterId, transition: impl FnOnce(&mut ClusterDeploymentStatus)) {
+        if let Err(cause) = deployment_status::update(&self.resources_manager, cluster_id, transition).await {
+            warn!("Failed to update the deployment status of cluster <{cluster_id}>:\n  {cause}");
+        }
+    }
+}
+
+/// Devices, which device selectors can bind: the devices of available peers
+/// and the devices of peers, which are members of a cluster via one of the `member_devices`.
+pub(crate) fn selectable_devices(resources: &impl ResourcesStorageApi, member_devices: &HashSet<DeviceId>) -> PersistenceResult<Vec<SelectableDevice>> {
+    let mut candidates = Vec::new();
+    for peer in resources.list::<PeerDescriptor>()? {
+        let peer_state = resources.get::<PeerState>(peer.id)?.unwrap_or_default();
+
+        let is_selectable = match peer_state {
+            PeerState::Up { inner: PeerUpState::Available, .. } => true,
+            PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), .. } => {
+                peer.topology.devices.iter()
+                    .any(|device| member_devices.contains(&device.id))
+            }
+            _ => false,
+        };
+
+        if is_selectable {
+            candidates.extend(
+                peer.network.interfaces_zipped_with_devices(&peer.topology.devices)
+                    .into_iter()
+                    .map(|(interface, device)| SelectableDevice {
+                        interface_kind: DeviceInterfaceKind::from(&interface.configuration),
+                        device,
+                        peer_labels: Clone::clone(&peer.labels),
+                    })
+            );
+        }
+    }
+    Ok(candidates)
 }
 
-fn determine_member_interface_mapping(
+pub(crate) fn determine_member_interface_mapping(
     cluster_devices: HashSet<DeviceId>,
     all_peers: Vec<PeerDescriptor>,
     leader: PeerId,
@@ -403,6 +887,7 @@ pub struct ClusterManagerOptions {
     pub can_server_port_range_start: u16,
     pub can_server_port_range_end: u16,
     pub bridge_name_default: NetworkInterfaceName,
+    pub images: ImagePolicy,
 }
 impl ClusterManagerOptions {
     pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
@@ -420,11 +905,12 @@ impl ClusterManagerOptions {
             can_server_port_range_start,
             can_server_port_range_end,
             bridge_name_default,
+            images: ImagePolicy::load(config)?,
         })
     }
 }
 #[derive(Debug, thiserror::Error)]
-enum DetermineMemberInterfaceMappingError {
+pub(crate) enum DetermineMemberInterfaceMappingError {
     #[error("Peer for device <{device_id}> not found.")]
     PeerForDeviceNotFound { device_id: DeviceId },
 }
@@ -450,9 +936,12 @@ mod test {
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceId, NetworkInterfaceName};
 
     use crate::actions::{CreateClusterConfigurationParams, StorePeerDescriptorParams};
+    use crate::leadership::{LeaderElectionOptions, Leadership};
     use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
     use crate::resources::manager::ResourcesManager;
     use crate::settings;
+    use crate::cluster::scheduler::{DeploymentScheduler, DeploymentSchedulerOptions};
+    use crate::statistics::{Statistics, StatisticsOptions};
 
     use super::*;
 
@@ -477,16 +966,21 @@ mod test {
                 name: ClusterName::try_from("MyAwesomeCluster").unwrap(),
                 leader: leader_id,
                 devices: HashSet::from([peer_a.device, peer_b.device]),
+                peer_groups: HashSet::new(),
+                device_selectors: Vec::new(),
+                dependencies: Vec::new(),
             };
 
             actions::store_peer_descriptor(StorePeerDescriptorParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
+                policy_engine: Default::default(),
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer_a.descriptor),
             }).await?;
 
             actions::store_peer_descriptor(StorePeerDescriptorParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
+                policy_engine: Default::default(),
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer_b.descriptor),
             }).await?;
@@ -498,6 +992,7 @@ mod test {
 
             actions::create_cluster_configuration(CreateClusterConfigurationParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
+                policy_engine: Default::default(),
                 cluster_configuration,
             }).await?;
 
@@ -567,6 +1062,7 @@ mod test {
             if let downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
                 old_configuration: Some(old_peer_configuration),
                 configuration: Some(peer_configuration),
+                ..
             }) = message {
                 (
                     old_peer_configuration.try_into().unwrap(),
@@ -630,6 +1126,10 @@ mod test {
                     devices,
                 },
                 executors: ExecutorDescriptors { executors: vec![] },
+                labels: Default::default(),
+                annotations: Default::default(),
+                hardware: Default::default(),
+                cluster_hooks: Default::default(),
             }
         }
 
@@ -676,6 +1176,9 @@ mod test {
                 Arc::clone(&resources_manager),
                 Arc::clone(&peer_messaging_broker),
                 Vpn::Disabled,
+                Statistics::new(StatisticsOptions::load(&settings.config).unwrap()),
+                DeploymentScheduler::new(DeploymentSchedulerOptions::load(&settings.config).unwrap()),
+                Leadership::new(&LeaderElectionOptions::Disabled),
                 cluster_manager_options.clone(),
             ).await;
             Fixture {
@@ -750,11 +1253,19 @@ mod test {
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            secrets: vec![],
+                            can_gateways: vec![],
+                            network_attachment: Default::default(),
                         },
                         results_url: None,
+                        sandbox: Default::default(),
                     }
                 ],
             },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
         PeerFixture {
             id,
@@ -1 +1,3 @@
+pub mod deployment_status;
 pub mod manager;
+pub mod scheduler;
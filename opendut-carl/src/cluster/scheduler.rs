@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Not;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::label::LabelKey;
+use opendut_types::peer::PeerId;
+use opendut_util::settings::LoadError;
+
+pub type DeploymentSchedulerRef = Arc<DeploymentScheduler>;
+
+/// Number of completed deployments, whose duration is averaged to estimate when a queued deployment starts.
+const DURATION_HISTORY: usize = 20;
+
+/// Limits the number of cluster deployments, which are progressing at the same time, and queues the rest in order of arrival.
+/// A deployment progresses from being handed to the peers until all of its peers reported the outcome of applying their configuration,
+/// which protects the control plane and the VPN management from a thundering herd of deployments, e.g. after a restart of CARL.
+pub struct DeploymentScheduler {
+    options: DeploymentSchedulerOptions,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    in_progress: HashMap<ClusterId, InProgress>,
+    queue: VecDeque<Queued>,
+    durations: VecDeque<Duration>,
+}
+
+struct InProgress {
+    project: Option<String>,
+    started: Instant,
+    pending_peers: HashSet<PeerId>,
+}
+
+struct Queued {
+    cluster_id: ClusterId,
+    project: Option<String>,
+    enqueued: Instant,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Admission {
+    Admitted,
+    Queued { position: usize },
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DeploymentQueueStatus {
+    pub in_progress: Vec<InProgressDeployment>,
+    pub queued: Vec<QueuedDeployment>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InProgressDeployment {
+    pub cluster_id: ClusterId,
+    pub project: Option<String>,
+    pub running_ms: u64,
+    pub pending_peers: Vec<PeerId>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct QueuedDeployment {
+    pub cluster_id: ClusterId,
+    pub project: Option<String>,
+    /// Position in the queue, starting at 1.
+    pub position: usize,
+    pub waiting_ms: u64,
+    /// Estimated time until the deployment starts. `None`, if no deployment completed yet to base the estimate on.
+    pub estimated_start_in_ms: Option<u64>,
+}
+
+impl DeploymentScheduler {
+    pub fn new(options: DeploymentSchedulerOptions) -> DeploymentSchedulerRef {
+        Arc::new(Self { options, state: Mutex::default() })
+    }
+
+    pub fn project_label(&self) -> &LabelKey {
+        &self.options.project_label
+    }
+
+    /// Decides whether the deployment of a cluster may start now or has to wait in the queue.
+    /// Deployments, which are queued already, keep their position.
+    pub fn admit(&self, cluster_id: ClusterId, project: Option<String>) -> Admission {
+        self.admit_at(cluster_id, project, Instant::now())
+    }
+
+    fn admit_at(&self, cluster_id: ClusterId, project: Option<String>, now: Instant) -> Admission {
+        let mut state = self.state.lock().unwrap();
+
+        if state.in_progress.contains_key(&cluster_id) {
+            debug!("Deployment of cluster <{cluster_id}> is already in progress. Deploying again.");
+            state.in_progress.remove(&cluster_id);
+        }
+
+        let index = state.queue.iter()
+            .position(|queued| queued.cluster_id == cluster_id);
+
+        let ahead = index.unwrap_or(state.queue.len());
+        let admitted = state.has_capacity(&self.options, &project)
+            && state.queue.iter().take(ahead).all(|queued| state.has_capacity(&self.options, &queued.project).not());
+
+        if admitted {
+            if let Some(index) = index {
+                state.queue.remove(index);
+            }
+            state.in_progress.insert(cluster_id, InProgress { project, started: now, pending_peers: HashSet::new() });
+            Admission::Admitted
+        } else {
+            if index.is_none() {
+                state.queue.push_back(Queued { cluster_id, project, enqueued: now });
+            }
+            Admission::Queued { position: ahead + 1 }
+        }
+    }
+
+    /// Registers the peers of an admitted deployment, which have to report the outcome of applying their configuration for the deployment to complete.
+    pub fn started(&self, cluster_id: ClusterId, peers: impl IntoIterator<Item=PeerId>) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(in_progress) = state.in_progress.get_mut(&cluster_id) {
+            in_progress.pending_peers.extend(peers);
+        }
+    }
+
+    /// Notes that a peer reported the outcome of applying its configuration.
+    /// Returns whether deployments completed, which makes room for queued deployments.
+    pub fn peer_reported(&self, peer_id: PeerId) -> bool {
+        self.peer_reported_at(peer_id, Instant::now())
+    }
+
+    fn peer_reported_at(&self, peer_id: PeerId, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        let completed = state.in_progress.iter_mut()
+            .filter_map(|(cluster_id, in_progress)| {
+                let was_pending = in_progress.pending_peers.remove(&peer_id);
+                (was_pending && in_progress.pending_peers.is_empty()).then_some(*cluster_id)
+            })
+            .collect::<Vec<_>>();
+
+        for cluster_id in &completed {
+            if let Some(in_progress) = state.in_progress.remove(cluster_id) {
+                let duration = now.saturating_duration_since(in_progress.started);
+                debug!("Deployment of cluster <{cluster_id}> completed after {} ms.", duration.as_millis());
+                state.record_duration(duration);
+            }
+        }
+        completed.is_empty().not()
+    }
+
+    /// Removes a deployment from the queue and frees its slot, e.g. when it failed or was deleted.
+    pub fn withdraw(&self, cluster_id: ClusterId) {
+        let mut state = self.state.lock().unwrap();
+        state.in_progress.remove(&cluster_id);
+        state.queue.retain(|queued| queued.cluster_id != cluster_id);
+    }
+
+    /// Frees the slots of deployments, which did not complete within the configured timeout.
+    /// Returns whether deployments were expired, which makes room for queued deployments.
+    pub fn expire(&self) -> bool {
+        self.expire_at(Instant::now())
+    }
+
+    fn expire_at(&self, now: Instant) -> bool {
+        let timeout = self.options.progress_timeout;
+        let mut state = self.state.lock().unwrap();
+
+        let expired = state.in_progress.iter()
+            .filter(|(_, in_progress)| now.saturating_duration_since(in_progress.started) >= timeout)
+            .map(|(cluster_id, _)| *cluster_id)
+            .collect::<Vec<_>>();
+
+        for cluster_id in &expired {
+            warn!("Deployment of cluster <{cluster_id}> did not complete within {} ms. Freeing its slot for queued deployments.", timeout.as_millis());
+            state.in_progress.remove(cluster_id);
+            state.record_duration(timeout);
+        }
+        expired.is_empty().not()
+    }
+
+    /// Queued deployments, which may start now, in the order they should be attempted.
+    pub fn admissible(&self) -> Vec<ClusterId> {
+        let state = self.state.lock().unwrap();
+
+        let mut in_progress_total = state.in_progress.len();
+        let mut in_progress_by_project = state.in_progress_by_project();
+
+        let mut admissible = Vec::new();
+        for queued in &state.queue {
+            let in_project = queued.project.as_ref()
+                .map(|project| in_progress_by_project.get(project).copied().unwrap_or(0));
+
+            if self.options.fits(in_progress_total, in_project) {
+                admissible.push(queued.cluster_id);
+                in_progress_total += 1;
+                if let Some(project) = &queued.project {
+                    *in_progress_by_project.entry(project.clone()).or_default() += 1;
+                }
+            }
+        }
+        admissible
+    }
+
+    pub fn status(&self) -> DeploymentQueueStatus {
+        self.status_at(Instant::now())
+    }
+
+    fn status_at(&self, now: Instant) -> DeploymentQueueStatus {
+        let state = self.state.lock().unwrap();
+
+        let mut in_progress = state.in_progress.iter()
+            .map(|(cluster_id, in_progress)| InProgressDeployment {
+                cluster_id: *cluster_id,
+                project: in_progress.project.clone(),
+                running_ms: millis(now.saturating_duration_since(in_progress.started)),
+                pending_peers: in_progress.pending_peers.iter().cloned().collect(),
+            })
+            .collect::<Vec<_>>();
+        in_progress.sort_by_key(|deployment| std::cmp::Reverse(deployment.running_ms));
+
+        let average_duration = state.average_duration();
+        let parallelism = self.options.max_concurrent
+            .or(self.options.max_concurrent_per_project)
+            .unwrap_or(usize::MAX)
+            .max(1);
+
+        let queued = state.queue.iter()
+            .enumerate()
+            .map(|(index, queued)| {
+                let rounds = u32::try_from(index / parallelism + 1).unwrap_or(u32::MAX);
+                QueuedDeployment {
+                    cluster_id: queued.cluster_id,
+                    project: queued.project.clone(),
+                    position: index + 1,
+                    waiting_ms: millis(now.saturating_duration_since(queued.enqueued)),
+                    estimated_start_in_ms: average_duration.map(|duration| millis(duration.saturating_mul(rounds))),
+                }
+            })
+            .collect();
+
+        DeploymentQueueStatus { in_progress, queued }
+    }
+}
+
+impl State {
+    fn in_progress_by_project(&self) -> HashMap<String, usize> {
+        let mut result = HashMap::new();
+        for project in self.in_progress.values().filter_map(|in_progress| in_progress.project.as_ref()) {
+            *result.entry(project.clone()).or_default() += 1;
+        }
+        result
+    }
+
+    fn has_capacity(&self, options: &DeploymentSchedulerOptions, project: &Option<String>) -> bool {
+        let in_project = project.as_ref()
+            .map(|project| self.in_progress.values()
+                .filter(|in_progress| in_progress.project.as_ref() == Some(project))
+                .count()
+            );
+        options.fits(self.in_progress.len(), in_project)
+    }
+
+    fn record_duration(&mut self, duration: Duration) {
+        self.durations.push_back(duration);
+        while self.durations.len() > DURATION_HISTORY {
+            self.durations.pop_front();
+        }
+    }
+
+    fn average_duration(&self) -> Option<Duration> {
+        let count = u32::try_from(self.durations.len()).ok()
+            .filter(|count| *count > 0)?;
+        let total = self.durations.iter().sum::<Duration>();
+        Some(total / count)
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[derive(Clone)]
+pub struct DeploymentSchedulerOptions {
+    /// `None`, if the number of concurrently progressing deployments is unlimited.
+    pub max_concurrent: Option<usize>,
+    /// `None`, if the number of concurrently progressing deployments of a project is unlimited.
+    pub max_concurrent_per_project: Option<usize>,
+    /// Label of the leader peer, which designates the project a cluster belongs to.
+    pub project_label: LabelKey,
+    pub progress_timeout: Duration,
+}
+impl DeploymentSchedulerOptions {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        fn load_limit(config: &config::Config, field: &'static str) -> Result<Option<usize>, LoadError> {
+            let value = config.get_int(field)?;
+            let value = usize::try_from(value)
+                .map_err(|cause| LoadError::ParseValue { field, value: value.to_string(), source: Box::new(cause) })?;
+            Ok((value > 0).then_some(value))
+        }
+
+        let max_concurrent = load_limit(config, "cluster.deployment.concurrency.max")?;
+        let max_concurrent_per_project = load_limit(config, "cluster.deployment.concurrency.per.project.max")?;
+
+        let project_label = {
+            let field = "cluster.deployment.project.label";
+            let value = config.get_string(field)?;
+            LabelKey::try_from(value.clone())
+                .map_err(|cause| LoadError::ParseValue { field, value, source: Box::new(cause) })?
+        };
+
+        let progress_timeout = Duration::from_millis(config.get::<u64>("cluster.deployment.progress.timeout.ms")?);
+
+        Ok(Self { max_concurrent, max_concurrent_per_project, project_label, progress_timeout })
+    }
+
+    fn fits(&self, in_progress_total: usize, in_progress_in_project: Option<usize>) -> bool {
+        let fits_total = self.max_concurrent
+            .map_or(true, |max| in_progress_total < max);
+        let fits_project = self.max_concurrent_per_project
+            .zip(in_progress_in_project)
+            .map_or(true, |(max, in_project)| in_project < max);
+        fits_total && fits_project
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(max_concurrent: Option<usize>, max_concurrent_per_project: Option<usize>) -> DeploymentSchedulerRef {
+        DeploymentScheduler::new(DeploymentSchedulerOptions {
+            max_concurrent,
+            max_concurrent_per_project,
+            project_label: LabelKey::try_from("project").unwrap(),
+            progress_timeout: Duration::from_secs(60),
+        })
+    }
+
+    #[test]
+    fn should_queue_deployments_beyond_the_global_limit_and_admit_them_in_order() {
+        let scheduler = scheduler(Some(1), None);
+        let now = Instant::now();
+        let (first, second, third) = (ClusterId::random(), ClusterId::random(), ClusterId::random());
+        let peer = PeerId::random();
+
+        assert_eq!(scheduler.admit_at(first, None, now), Admission::Admitted);
+        scheduler.started(first, [peer]);
+        assert_eq!(scheduler.admit_at(second, None, now), Admission::Queued { position: 1 });
+        assert_eq!(scheduler.admit_at(third, None, now), Admission::Queued { position: 2 });
+        assert!(scheduler.admissible().is_empty());
+
+        assert!(scheduler.peer_reported_at(peer, now + Duration::from_secs(10)));
+        assert_eq!(scheduler.admissible(), vec![second]);
+        assert_eq!(scheduler.admit_at(third, None, now), Admission::Queued { position: 2 });
+        assert_eq!(scheduler.admit_at(second, None, now), Admission::Admitted);
+
+        let status = scheduler.status_at(now + Duration::from_secs(10));
+        assert_eq!(status.in_progress.len(), 1);
+        assert_eq!(status.queued.len(), 1);
+        assert_eq!(status.queued[0].cluster_id, third);
+        assert_eq!(status.queued[0].position, 1);
+        assert_eq!(status.queued[0].estimated_start_in_ms, Some(10_000));
+    }
+
+    #[test]
+    fn should_limit_deployments_per_project() {
+        let scheduler = scheduler(None, Some(1));
+        let now = Instant::now();
+        let project = |name: &str| Some(String::from(name));
+        let (first, second, third) = (ClusterId::random(), ClusterId::random(), ClusterId::random());
+
+        assert_eq!(scheduler.admit_at(first, project("a"), now), Admission::Admitted);
+        assert_eq!(scheduler.admit_at(second, project("a"), now), Admission::Queued { position: 1 });
+        assert_eq!(scheduler.admit_at(third, project("b"), now), Admission::Admitted);
+
+        assert!(scheduler.expire_at(now + Duration::from_secs(60)));
+        assert_eq!(scheduler.admissible(), vec![second]);
+
+        scheduler.withdraw(second);
+        assert!(scheduler.status_at(now).queued.is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use opendut_auth::registration::client::RegistrationClientRef;
+
+use crate::actions;
+use crate::actions::{CheckCompatibilityParams, CheckConsistencyParams, ReconcileOidcClientsParams};
+use crate::leadership::LeadershipRef;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Periodically checks the persisted resources, the in-memory resources and the connected peers for inconsistencies.
+/// Findings are logged by the check itself. Only the leader checks, as the connected peers are only known to it.
+pub fn spawn_consistency_check(
+    resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    leadership: LeadershipRef,
+    options: ConsistencyCheckOptions,
+) {
+    let ConsistencyCheckOptions::Enabled { interval, repair } = options else {
+        return;
+    };
+
+    info!("Checking consistency of resources every {} ms (repair: {repair}).", interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if leadership.is_leader().not() {
+                continue;
+            }
+
+            let _ = actions::check_consistency(CheckConsistencyParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                repair,
+            }).await;
+        }
+    });
+}
+
+#[derive(Clone)]
+pub enum ConsistencyCheckOptions {
+    Enabled { interval: Duration, repair: bool },
+    Disabled,
+}
+impl ConsistencyCheckOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("diagnostics.consistency.check.enabled")?;
+
+        if enabled {
+            let interval = Duration::from_millis(
+                config.get::<u64>("diagnostics.consistency.check.interval.ms")?
+            );
+            let repair = config.get_bool("diagnostics.consistency.check.repair")?;
+
+            Ok(ConsistencyCheckOptions::Enabled { interval, repair })
+        } else {
+            Ok(ConsistencyCheckOptions::Disabled)
+        }
+    }
+}
+
+/// Checks once on startup, that the persisted resources are compatible with this version of CARL,
+/// so that incompatibilities introduced by an upgrade are reported right away. Findings are logged by the check itself.
+pub async fn check_compatibility_on_startup(
+    resources_manager: ResourcesManagerRef,
+    options: CompatibilityCheckOptions,
+) {
+    let CompatibilityCheckOptions::Enabled { transform } = options else {
+        return;
+    };
+
+    let _ = actions::check_compatibility(CheckCompatibilityParams {
+        resources_manager,
+        transform,
+    }).await;
+}
+
+#[derive(Clone)]
+pub enum CompatibilityCheckOptions {
+    Enabled { transform: bool },
+    Disabled,
+}
+impl CompatibilityCheckOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("diagnostics.compatibility.check.startup.enabled")?;
+
+        if enabled {
+            let transform = config.get_bool("diagnostics.compatibility.check.transform")?;
+
+            Ok(CompatibilityCheckOptions::Enabled { transform })
+        } else {
+            Ok(CompatibilityCheckOptions::Disabled)
+        }
+    }
+}
+
+/// Periodically compares the OIDC clients registered at the identity provider with the peers.
+/// Findings are logged by the reconciliation itself. Only the leader reconciles, as it may delete clients.
+pub fn spawn_oidc_client_reconciliation(
+    resources_manager: ResourcesManagerRef,
+    registration_client: Option<RegistrationClientRef>,
+    leadership: LeadershipRef,
+    options: OidcClientReconciliationOptions,
+) {
+    let OidcClientReconciliationOptions::Enabled { interval, delete_orphans } = options else {
+        return;
+    };
+    let Some(registration_client) = registration_client else {
+        warn!("Reconciliation of OIDC clients is enabled, but authentication is disabled. Not reconciling OIDC clients.");
+        return;
+    };
+
+    info!("Reconciling OIDC clients every {} ms (delete orphans: {delete_orphans}).", interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if leadership.is_leader().not() {
+                continue;
+            }
+
+            let _ = actions::reconcile_oidc_clients(ReconcileOidcClientsParams {
+                resources_manager: Arc::clone(&resources_manager),
+                registration_client: Arc::clone(&registration_client),
+                delete_orphans,
+            }).await;
+        }
+    });
+}
+
+#[derive(Clone)]
+pub enum OidcClientReconciliationOptions {
+    Enabled { interval: Duration, delete_orphans: bool },
+    Disabled,
+}
+impl OidcClientReconciliationOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("diagnostics.oidc.clients.reconcile.enabled")?;
+
+        if enabled {
+            let interval = Duration::from_millis(
+                config.get::<u64>("diagnostics.oidc.clients.reconcile.interval.ms")?
+            );
+            let delete_orphans = config.get_bool("diagnostics.oidc.clients.reconcile.delete.orphans")?;
+
+            Ok(OidcClientReconciliationOptions::Enabled { interval, delete_orphans })
+        } else {
+            Ok(OidcClientReconciliationOptions::Disabled)
+        }
+    }
+}
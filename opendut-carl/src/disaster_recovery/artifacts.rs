@@ -0,0 +1,663 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::ops::Not;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::disaster_recovery::VerifyReport;
+
+/// Files are split into chunks of this size, so that a changed file only adds the chunks, which actually changed.
+const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+const CHUNKS_DIRECTORY: &str = "chunks";
+const MANIFESTS_DIRECTORY: &str = "manifests";
+
+/// Periodically backs up the files in the artifact directories, e.g. stored setup bundles, recorded traces or diagnostic bundles.
+/// Each backup only writes the chunks, which are not contained in the backup directory yet, and lists all files with their chunks in a signed manifest.
+pub fn spawn_backups(options: ArtifactBackupOptions) {
+    let ArtifactBackupSchedule::Enabled { interval } = options.schedule else {
+        return;
+    };
+
+    info!("Backing up {} artifact directories to '{}' every {} ms, retaining {} backups.", options.sources.len(), options.backup_directory.display(), interval.as_millis(), options.retained);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+
+            match backup(&options).await {
+                Ok(summary) => info!("Backed up {} artifact files into '{}', writing {} new chunks with {} bytes.", summary.files, summary.manifest_file, summary.written_chunks, summary.written_bytes),
+                Err(cause) => warn!("Failed to back up the artifact directories:\n  {cause}"),
+            }
+        }
+    });
+}
+
+pub async fn backup(options: &ArtifactBackupOptions) -> Result<BackupSummary, ArtifactBackupError> {
+    let options = Clone::clone(options);
+    tokio::task::spawn_blocking(move || backup_blocking(&options)).await?
+}
+
+fn backup_blocking(options: &ArtifactBackupOptions) -> Result<BackupSummary, ArtifactBackupError> {
+    let signing_key = options.signing_key()?;
+    let chunks_directory = options.backup_directory.join(CHUNKS_DIRECTORY);
+    let manifests_directory = options.backup_directory.join(MANIFESTS_DIRECTORY);
+    create_dir_all(&chunks_directory)?;
+    create_dir_all(&manifests_directory)?;
+
+    let created_at = Utc::now();
+    let mut manifest = ArtifactManifest { created_at: created_at.to_rfc3339(), files: Vec::new() };
+    let mut written_chunks = 0;
+    let mut written_bytes = 0;
+
+    for source in &options.sources {
+        for path in files_of(&source.directory)? {
+            let relative_path = relative_path(&source.directory, &path);
+
+            let mut file = fs::File::open(&path)
+                .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+            let mut file_hasher = Sha256::new();
+            let mut size_bytes = 0;
+            let mut chunks = Vec::new();
+
+            let mut buffer = vec![0; CHUNK_SIZE];
+            loop {
+                let length = read_chunk(&mut file, &mut buffer)
+                    .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+                if length == 0 {
+                    break;
+                }
+                let content = &buffer[..length];
+                file_hasher.update(content);
+                size_bytes += length as u64;
+
+                let chunk = sha256_of(content);
+                if write_chunk(&chunks_directory, &chunk, content)? {
+                    written_chunks += 1;
+                    written_bytes += length as u64;
+                }
+                chunks.push(chunk);
+            }
+
+            manifest.files.push(BackedUpFile {
+                source: Clone::clone(&source.name),
+                path: relative_path,
+                size_bytes,
+                sha256: format!("{:x}", file_hasher.finalize()),
+                chunks,
+            });
+        }
+    }
+
+    let manifest_file = format!("artifacts-{}.json", created_at.format("%Y%m%dT%H%M%S%3fZ"));
+    SignedArtifactManifest::sign(Clone::clone(&manifest), &signing_key)?
+        .write(&manifests_directory.join(&manifest_file))?;
+
+    remove_outdated_backups(&options.backup_directory, options.retained)?;
+
+    Ok(BackupSummary {
+        manifest_file,
+        files: manifest.files.len(),
+        written_chunks,
+        written_bytes,
+    })
+}
+
+/// Keeps the newest `retained` manifests and deletes the chunks, which are only referenced by the removed ones.
+fn remove_outdated_backups(backup_directory: &Path, retained: usize) -> Result<(), ArtifactBackupError> {
+    let manifest_files = manifest_files(backup_directory)?;
+    let excess = manifest_files.len().saturating_sub(retained);
+    let (outdated, retained) = manifest_files.split_at(excess);
+
+    for file in outdated {
+        let path = backup_directory.join(MANIFESTS_DIRECTORY).join(file);
+        fs::remove_file(&path)
+            .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+    }
+    if outdated.is_empty() {
+        return Ok(());
+    }
+
+    let mut referenced = HashSet::new();
+    for file in retained {
+        let manifest = SignedArtifactManifest::read(&backup_directory.join(MANIFESTS_DIRECTORY).join(file))?.manifest;
+        referenced.extend(manifest.files.into_iter().flat_map(|file| file.chunks));
+    }
+
+    for path in files_of(&backup_directory.join(CHUNKS_DIRECTORY))? {
+        let is_referenced = path.file_name()
+            .map(|chunk| referenced.contains(chunk.to_string_lossy().as_ref()))
+            .unwrap_or(false);
+        if is_referenced.not() {
+            if let Err(cause) = fs::remove_file(&path) {
+                warn!("Failed to delete artifact chunk '{}', which is no longer referenced: {cause}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the signature of the latest manifest and that all of its chunks are present and intact, without changing anything.
+pub fn verify(options: &ArtifactBackupOptions) -> VerifyReport {
+    let mut report = VerifyReport { checks: Vec::new() };
+
+    let manifest = options.signing_key()
+        .and_then(|signing_key| read_verified_manifest(&options.backup_directory, None, &signing_key));
+    let (manifest_file, manifest) = match manifest {
+        Ok((manifest_file, manifest)) => {
+            report.passed("artifact manifest", format!("'{manifest_file}' lists {} files and matches its signature", manifest.files.len()));
+            (manifest_file, manifest)
+        }
+        Err(cause) => {
+            report.failed("artifact manifest", cause.to_string());
+            return report;
+        }
+    };
+
+    let chunks_directory = options.backup_directory.join(CHUNKS_DIRECTORY);
+    let chunks = manifest.files.iter()
+        .flat_map(|file| &file.chunks)
+        .collect::<HashSet<_>>();
+    let damaged = chunks.iter()
+        .filter(|chunk| read_chunk_checked(&chunks_directory, chunk).is_err())
+        .count();
+
+    if damaged == 0 {
+        report.passed("artifact chunks", format!("All {} chunks of '{manifest_file}' are intact", chunks.len()));
+    } else {
+        report.failed("artifact chunks", format!("{damaged} of {} chunks of '{manifest_file}' are missing or altered", chunks.len()));
+    }
+    report
+}
+
+/// Restores the files of a backup into their artifact directories, or into one subdirectory per source of the given target directory.
+/// Without a given manifest file, the latest backup is restored.
+pub async fn restore(options: &ArtifactBackupOptions, manifest_file: Option<String>, target_directory: Option<PathBuf>) -> Result<RestoreSummary, ArtifactBackupError> {
+    let options = Clone::clone(options);
+    tokio::task::spawn_blocking(move || restore_blocking(&options, manifest_file.as_deref(), target_directory.as_deref())).await?
+}
+
+fn restore_blocking(options: &ArtifactBackupOptions, manifest_file: Option<&str>, target_directory: Option<&Path>) -> Result<RestoreSummary, ArtifactBackupError> {
+    let signing_key = options.signing_key()?;
+    let (manifest_file, manifest) = read_verified_manifest(&options.backup_directory, manifest_file, &signing_key)?;
+    let chunks_directory = options.backup_directory.join(CHUNKS_DIRECTORY);
+
+    for file in &manifest.files {
+        let source_directory = match target_directory {
+            Some(target_directory) => target_directory.join(&file.source),
+            None => options.sources.iter()
+                .find(|source| source.name == file.source)
+                .map(|source| Clone::clone(&source.directory))
+                .ok_or_else(|| ArtifactBackupError::UnknownSource { source_name: Clone::clone(&file.source) })?,
+        };
+        let path = source_directory.join(safe_relative_path(&file.path)?);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        //restore under a temporary name, so that an incomplete file never replaces an intact one
+        let temporary_path = path.with_file_name(format!(".{}.partial", path.file_name().unwrap_or_default().to_string_lossy()));
+        let sha256 = restore_file(&chunks_directory, &file.chunks, &temporary_path)
+            .inspect_err(|_| { let _ = fs::remove_file(&temporary_path); })?;
+        if sha256 != file.sha256 {
+            let _ = fs::remove_file(&temporary_path);
+            return Err(ArtifactBackupError::FileMismatch { path, expected: Clone::clone(&file.sha256), actual: sha256 });
+        }
+        fs::rename(&temporary_path, &path)
+            .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+    }
+
+    Ok(RestoreSummary { manifest_file, created_at: manifest.created_at, files: manifest.files.len() })
+}
+
+/// Writes the chunks one after another into the file and returns the SHA-256 checksum of its content.
+fn restore_file(chunks_directory: &Path, chunks: &[String], path: &Path) -> Result<String, ArtifactBackupError> {
+    let mut output = fs::File::create(path)
+        .map_err(|cause| ArtifactBackupError::io(path, cause))?;
+    let mut file_hasher = Sha256::new();
+    for chunk in chunks {
+        let content = read_chunk_checked(chunks_directory, chunk)?;
+        file_hasher.update(&content);
+        output.write_all(&content)
+            .map_err(|cause| ArtifactBackupError::io(path, cause))?;
+    }
+    Ok(format!("{:x}", file_hasher.finalize()))
+}
+
+fn read_verified_manifest(backup_directory: &Path, manifest_file: Option<&str>, signing_key: &SigningKey) -> Result<(String, ArtifactManifest), ArtifactBackupError> {
+    let manifest_files = manifest_files(backup_directory)?;
+    let manifest_file = match manifest_file {
+        Some(manifest_file) => manifest_files.into_iter()
+            .find(|file| file == manifest_file)
+            .ok_or_else(|| ArtifactBackupError::UnknownManifest { file: manifest_file.to_owned() })?,
+        None => manifest_files.into_iter()
+            .last()
+            .ok_or_else(|| ArtifactBackupError::NoManifest { backup_directory: backup_directory.to_owned() })?,
+    };
+
+    let signed = SignedArtifactManifest::read(&backup_directory.join(MANIFESTS_DIRECTORY).join(&manifest_file))?;
+    let manifest = signed.verify(signing_key)
+        .map_err(|_| ArtifactBackupError::InvalidSignature { file: Clone::clone(&manifest_file) })?;
+    Ok((manifest_file, manifest))
+}
+
+/// Names of the manifests in the backup directory, oldest first, as their names start with the time of the backup.
+fn manifest_files(backup_directory: &Path) -> Result<Vec<String>, ArtifactBackupError> {
+    let manifests_directory = backup_directory.join(MANIFESTS_DIRECTORY);
+    let entries = match fs::read_dir(&manifests_directory) {
+        Ok(entries) => entries,
+        Err(cause) if cause.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(cause) => return Err(ArtifactBackupError::io(&manifests_directory, cause)),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let file = entry.map_err(|cause| ArtifactBackupError::io(&manifests_directory, cause))?
+            .file_name()
+            .to_string_lossy()
+            .into_owned();
+        if file.starts_with("artifacts-") && file.ends_with(".json") {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Regular files below the directory, in a stable order. A missing directory contains no files.
+fn files_of(directory: &Path) -> Result<Vec<PathBuf>, ArtifactBackupError> {
+    if directory.exists().not() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(directory).sort_by_file_name() {
+        let entry = entry.map_err(|cause| ArtifactBackupError::io(directory, cause.into()))?;
+        let is_partial = entry.file_name().to_string_lossy().ends_with(".partial");
+        if entry.file_type().is_file() && is_partial.not() {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn relative_path(directory: &Path, path: &Path) -> String {
+    path.strip_prefix(directory)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Rejects paths from a manifest, which would lead outside of the directory they are restored into.
+fn safe_relative_path(path: &str) -> Result<PathBuf, ArtifactBackupError> {
+    let relative = PathBuf::from(path);
+    let is_safe = relative.components().all(|component| matches!(component, Component::Normal(_)));
+    if is_safe && path.is_empty().not() {
+        Ok(relative)
+    } else {
+        Err(ArtifactBackupError::IllegalPath { path: path.to_owned() })
+    }
+}
+
+/// Fills the buffer as far as possible, so that the chunk boundaries do not depend on how the file is read.
+fn read_chunk(file: &mut fs::File, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut length = 0;
+    while length < buffer.len() {
+        match file.read(&mut buffer[length..])? {
+            0 => break,
+            read => length += read,
+        }
+    }
+    Ok(length)
+}
+
+fn chunk_path(chunks_directory: &Path, chunk: &str) -> PathBuf {
+    chunks_directory.join(&chunk[..2]).join(chunk)
+}
+
+/// Writes the chunk, unless it is contained in the backup directory already. Returns whether it was written.
+fn write_chunk(chunks_directory: &Path, chunk: &str, content: &[u8]) -> Result<bool, ArtifactBackupError> {
+    let path = chunk_path(chunks_directory, chunk);
+    if path.exists() {
+        return Ok(false);
+    }
+    let directory = path.parent().unwrap_or(chunks_directory);
+    create_dir_all(directory)?;
+
+    let temporary_path = directory.join(format!(".{chunk}.partial"));
+    fs::write(&temporary_path, content)
+        .and_then(|()| fs::rename(&temporary_path, &path))
+        .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+    Ok(true)
+}
+
+fn read_chunk_checked(chunks_directory: &Path, chunk: &str) -> Result<Vec<u8>, ArtifactBackupError> {
+    let is_sha256 = chunk.len() == 64 && chunk.chars().all(|character| character.is_ascii_hexdigit());
+    if is_sha256.not() {
+        return Err(ArtifactBackupError::IllegalChunk { chunk: chunk.to_owned() });
+    }
+    let path = chunk_path(chunks_directory, chunk);
+    let content = fs::read(&path)
+        .map_err(|cause| ArtifactBackupError::io(&path, cause))?;
+    let actual = sha256_of(&content);
+    if actual != chunk {
+        return Err(ArtifactBackupError::ChunkMismatch { chunk: chunk.to_owned(), actual });
+    }
+    Ok(content)
+}
+
+fn sha256_of(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn create_dir_all(directory: &Path) -> Result<(), ArtifactBackupError> {
+    fs::create_dir_all(directory)
+        .map_err(|cause| ArtifactBackupError::io(directory, cause))
+}
+
+/// Lists the files of one backup with the chunks they consist of, in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    /// RFC 3339 timestamp of when the backup was started.
+    pub created_at: String,
+    pub files: Vec<BackedUpFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackedUpFile {
+    /// Name of the artifact directory, which the file was backed up from.
+    pub source: String,
+    /// Path of the file within the artifact directory, separated by slashes.
+    pub path: String,
+    pub size_bytes: u64,
+    /// Hex-encoded SHA-256 checksum of the whole file.
+    pub sha256: String,
+    /// Hex-encoded SHA-256 checksums of the chunks, which are also their names in the backup directory.
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignedArtifactManifest {
+    manifest: ArtifactManifest,
+    /// Base64-encoded Ed25519 signature of the manifest serialized as JSON.
+    signature: String,
+}
+
+impl SignedArtifactManifest {
+    fn sign(manifest: ArtifactManifest, signing_key: &SigningKey) -> Result<Self, ArtifactBackupError> {
+        let content = serde_json::to_vec(&manifest)
+            .map_err(|source| ArtifactBackupError::InvalidManifest { path: PathBuf::new(), source })?;
+        let signature = BASE64_STANDARD.encode(signing_key.sign(&content).to_bytes());
+        Ok(Self { manifest, signature })
+    }
+
+    fn verify(self, signing_key: &SigningKey) -> Result<ArtifactManifest, ()> {
+        let content = serde_json::to_vec(&self.manifest).map_err(|_| ())?;
+        let signature = BASE64_STANDARD.decode(&self.signature).map_err(|_| ())?;
+        let signature = Signature::from_slice(&signature).map_err(|_| ())?;
+        signing_key.verifying_key().verify(&content, &signature).map_err(|_| ())?;
+        Ok(self.manifest)
+    }
+
+    fn read(path: &Path) -> Result<Self, ArtifactBackupError> {
+        let content = fs::read_to_string(path)
+            .map_err(|cause| ArtifactBackupError::io(path, cause))?;
+        serde_json::from_str(&content)
+            .map_err(|source| ArtifactBackupError::InvalidManifest { path: path.to_owned(), source })
+    }
+
+    /// Writes the manifest via a temporary file, so that a partially written manifest is never picked up.
+    fn write(&self, path: &Path) -> Result<(), ArtifactBackupError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|source| ArtifactBackupError::InvalidManifest { path: path.to_owned(), source })?;
+        let temporary_path = path.with_extension("json.partial");
+        fs::write(&temporary_path, content)
+            .and_then(|()| fs::rename(&temporary_path, path))
+            .map_err(|cause| ArtifactBackupError::io(path, cause))
+    }
+}
+
+pub struct BackupSummary {
+    pub manifest_file: String,
+    pub files: usize,
+    /// Chunks, which were not contained in the backup directory yet.
+    pub written_chunks: usize,
+    pub written_bytes: u64,
+}
+
+pub struct RestoreSummary {
+    pub manifest_file: String,
+    pub created_at: String,
+    pub files: usize,
+}
+
+#[derive(Clone)]
+pub struct ArtifactBackupOptions {
+    pub schedule: ArtifactBackupSchedule,
+    /// Directories, whose files are backed up, each with a name to restore them by.
+    pub sources: Vec<ArtifactSource>,
+    /// Directory, which the chunks and manifests are stored in, e.g. on a network share.
+    pub backup_directory: PathBuf,
+    /// Number of backups kept. Chunks, which are only referenced by older backups, are deleted.
+    pub retained: usize,
+    /// Key, which the manifests are signed with and verified against. Required for backing up, verifying and restoring.
+    pub signing_key: Option<SigningKey>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArtifactSource {
+    pub name: String,
+    pub directory: PathBuf,
+}
+
+#[derive(Clone)]
+pub enum ArtifactBackupSchedule {
+    Enabled { interval: Duration },
+    Disabled,
+}
+
+impl ArtifactBackupOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        use opendut_util::settings::LoadError;
+
+        let signing_key = {
+            let field = "disaster.recovery.artifacts.signing.key";
+            let value = config.get_string(field)?;
+            if value.trim().is_empty() {
+                None
+            } else {
+                let key_bytes = BASE64_STANDARD.decode(value.trim())
+                    .map_err(|cause| LoadError::ParseValue { field, value: String::from("<redacted>"), source: cause.into() })?;
+                let key_bytes: [u8; ed25519_dalek::SECRET_KEY_LENGTH] = key_bytes.try_into()
+                    .map_err(|_| LoadError::ParseValue { field, value: String::from("<redacted>"), source: format!("Expected a Base64-encoded key of {} bytes.", ed25519_dalek::SECRET_KEY_LENGTH).into() })?;
+                Some(SigningKey::from_bytes(&key_bytes))
+            }
+        };
+
+        let schedule = if config.get_bool("disaster.recovery.artifacts.backup.enabled")? {
+            if signing_key.is_none() {
+                return Err(LoadError::ParseValue {
+                    field: "disaster.recovery.artifacts.signing.key",
+                    value: String::new(),
+                    source: "A signing key is required for backing up artifacts.".into(),
+                });
+            }
+            let interval = Duration::from_millis(
+                config.get::<u64>("disaster.recovery.artifacts.backup.interval.ms")?
+            );
+            ArtifactBackupSchedule::Enabled { interval }
+        } else {
+            ArtifactBackupSchedule::Disabled
+        };
+
+        let retained = {
+            let field = "disaster.recovery.artifacts.backup.retained";
+            let value = config.get::<usize>(field)?;
+            if value == 0 {
+                return Err(LoadError::ParseValue { field, value: value.to_string(), source: "At least one backup must be retained.".into() });
+            }
+            value
+        };
+
+        let sources = {
+            let field = "disaster.recovery.artifacts.sources";
+            let value = config.get_string(field)?;
+            parse_sources(&value)
+                .map_err(|cause| LoadError::ParseValue { field, value: Clone::clone(&value), source: cause.into() })?
+        };
+
+        let backup_directory = PathBuf::from(config.get_string("disaster.recovery.artifacts.directory")?);
+
+        Ok(ArtifactBackupOptions { schedule, sources, backup_directory, retained, signing_key })
+    }
+
+    fn signing_key(&self) -> Result<SigningKey, ArtifactBackupError> {
+        Clone::clone(&self.signing_key)
+            .ok_or(ArtifactBackupError::SigningKeyMissing)
+    }
+}
+
+/// Parses comma-separated sources like `traces=/var/lib/opendut/carl/traces`.
+fn parse_sources(value: &str) -> Result<Vec<ArtifactSource>, String> {
+    let mut sources = Vec::<ArtifactSource>::new();
+    for source in value.split(',').map(str::trim).filter(|source| source.is_empty().not()) {
+        let (name, directory) = source.split_once('=')
+            .ok_or_else(|| format!("Expected '<name>=<directory>', but got '{source}'."))?;
+        let name = name.trim();
+        if safe_relative_path(name).is_err() || name.contains('/') {
+            return Err(format!("Name '{name}' of artifact directory must not be empty or contain path separators."));
+        }
+        if sources.iter().any(|source| source.name == name) {
+            return Err(format!("Name '{name}' of artifact directory is used more than once."));
+        }
+        sources.push(ArtifactSource { name: name.to_owned(), directory: PathBuf::from(directory.trim()) });
+    }
+    Ok(sources)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArtifactBackupError {
+    #[error("Failed to access '{path}': {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Invalid artifact manifest '{path}': {source}")]
+    InvalidManifest { path: PathBuf, source: serde_json::Error },
+    #[error("No signing key for artifact manifests configured. Set it via 'disaster.recovery.artifacts.signing.key'.")]
+    SigningKeyMissing,
+    #[error("Artifact manifest '{file}' does not match its signature. It was altered or signed with a different key.")]
+    InvalidSignature { file: String },
+    #[error("No artifacts were backed up to '{backup_directory}' yet.")]
+    NoManifest { backup_directory: PathBuf },
+    #[error("Artifact manifest '{file}' does not exist in the backup directory.")]
+    UnknownManifest { file: String },
+    #[error("Artifact directory '{source_name}' of the backup is not configured. Restore into a target directory instead.")]
+    UnknownSource { source_name: String },
+    #[error("Path '{path}' in the artifact manifest leads outside of the artifact directory.")]
+    IllegalPath { path: String },
+    #[error("Name '{chunk}' of artifact chunk is not a SHA-256 checksum.")]
+    IllegalChunk { chunk: String },
+    #[error("Artifact chunk '{chunk}' has the SHA-256 checksum '{actual}', so it was altered.")]
+    ChunkMismatch { chunk: String, actual: String },
+    #[error("Restored file '{path}' has the SHA-256 checksum {actual}, but {expected} was backed up.")]
+    FileMismatch { path: PathBuf, expected: String, actual: String },
+    #[error("Background task failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+impl ArtifactBackupError {
+    fn io(path: &Path, source: io::Error) -> Self {
+        ArtifactBackupError::Io { path: path.to_owned(), source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn options(source_directory: &Path, backup_directory: &Path) -> ArtifactBackupOptions {
+        ArtifactBackupOptions {
+            schedule: ArtifactBackupSchedule::Disabled,
+            sources: vec![ArtifactSource { name: String::from("traces"), directory: source_directory.to_owned() }],
+            backup_directory: backup_directory.to_owned(),
+            retained: 2,
+            signing_key: Some(SigningKey::from_bytes(&[7; ed25519_dalek::SECRET_KEY_LENGTH])),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_only_write_new_chunks_and_restore_the_files() -> anyhow::Result<()> {
+        let source_directory = tempfile::tempdir()?;
+        let backup_directory = tempfile::tempdir()?;
+        let options = options(source_directory.path(), backup_directory.path());
+
+        fs::create_dir(source_directory.path().join("peer-a"))?;
+        fs::write(source_directory.path().join("peer-a/trace.json"), "first trace")?;
+        fs::write(source_directory.path().join("bundle.tar"), "setup bundle")?;
+
+        let first = backup(&options).await?;
+        assert_that!(first.files, eq(2));
+        assert_that!(first.written_chunks, eq(2));
+
+        fs::write(source_directory.path().join("peer-a/trace.json"), "second trace")?;
+        let second = backup(&options).await?;
+        assert_that!(second.files, eq(2));
+        assert_that!(second.written_chunks, eq(1));
+
+        let target_directory = tempfile::tempdir()?;
+        let restored = restore(&options, Some(first.manifest_file), Some(target_directory.path().to_owned())).await?;
+        assert_that!(restored.files, eq(2));
+        assert_that!(fs::read_to_string(target_directory.path().join("traces/peer-a/trace.json"))?, eq("first trace"));
+        assert_that!(fs::read_to_string(target_directory.path().join("traces/bundle.tar"))?, eq("setup bundle"));
+
+        assert!(verify(&options).is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_detect_altered_chunks_and_manifests() -> anyhow::Result<()> {
+        let source_directory = tempfile::tempdir()?;
+        let backup_directory = tempfile::tempdir()?;
+        let options = options(source_directory.path(), backup_directory.path());
+
+        fs::write(source_directory.path().join("diagnostics.tar"), "diagnostic bundle")?;
+        let summary = backup(&options).await?;
+
+        let chunk = sha256_of(b"diagnostic bundle");
+        fs::write(chunk_path(&backup_directory.path().join(CHUNKS_DIRECTORY), &chunk), "altered")?;
+        assert!(verify(&options).is_ok().not());
+        assert!(matches!(restore(&options, None, None).await, Err(ArtifactBackupError::ChunkMismatch { .. })));
+
+        let manifest_path = backup_directory.path().join(MANIFESTS_DIRECTORY).join(&summary.manifest_file);
+        let altered = fs::read_to_string(&manifest_path)?.replace("diagnostics.tar", "../escaped.tar");
+        fs::write(&manifest_path, altered)?;
+        assert!(matches!(restore(&options, None, None).await, Err(ArtifactBackupError::InvalidSignature { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_artifact_sources() {
+        assert_that!(
+            parse_sources("traces=/var/lib/traces, bundles = /var/lib/bundles"),
+            ok(elements_are![
+                eq(&ArtifactSource { name: String::from("traces"), directory: PathBuf::from("/var/lib/traces") }),
+                eq(&ArtifactSource { name: String::from("bundles"), directory: PathBuf::from("/var/lib/bundles") }),
+            ])
+        );
+        assert_that!(parse_sources(""), ok(empty()));
+        assert_that!(parse_sources("/var/lib/traces"), err(anything()));
+        assert_that!(parse_sources("../traces=/var/lib/traces"), err(anything()));
+        assert_that!(parse_sources("traces=/a,traces=/b"), err(anything()));
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use diesel::connection::SimpleConnection;
+use diesel::{Connection as _, SqliteConnection};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use url::Url;
+
+use crate::disaster_recovery::DisasterRecoveryError;
+use crate::persistence::database::{sqlite_path, SQLITE_SCHEME};
+use crate::resources::storage::{DatabaseConnectInfo, PersistenceOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackend {
+    Postgres,
+    Sqlite,
+}
+impl fmt::Display for DatabaseBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseBackend::Postgres => write!(f, "PostgreSQL"),
+            DatabaseBackend::Sqlite => write!(f, "SQLite"),
+        }
+    }
+}
+
+/// The persistence database of this instance, which is dumped on the primary instance and restored on the standby instance.
+pub enum Database {
+    /// Dumped and restored via `pg_dump` and `pg_restore`, which have to be installed on the host of CARL.
+    Postgres { database_connect_info: DatabaseConnectInfo },
+    /// Dumped via `VACUUM INTO`, which yields a consistent copy while CARL keeps writing, and restored by replacing the database file.
+    Sqlite { path: PathBuf },
+}
+
+impl TryFrom<PersistenceOptions> for Database {
+    type Error = DisasterRecoveryError;
+
+    fn try_from(options: PersistenceOptions) -> Result<Self, Self::Error> {
+        let PersistenceOptions::Enabled { database_connect_info, .. } = options else {
+            return Err(DisasterRecoveryError::PersistenceDisabled);
+        };
+
+        if database_connect_info.url.scheme() == SQLITE_SCHEME {
+            let path = sqlite_path(&database_connect_info.url)
+                .ok_or_else(|| DisasterRecoveryError::InvalidDatabaseUrl { url: Clone::clone(&database_connect_info.url) })?;
+            Ok(Database::Sqlite { path: PathBuf::from(path) })
+        } else {
+            Ok(Database::Postgres { database_connect_info })
+        }
+    }
+}
+
+impl Database {
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Database::Postgres { .. } => DatabaseBackend::Postgres,
+            Database::Sqlite { .. } => DatabaseBackend::Sqlite,
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Database::Postgres { .. } => "dump",
+            Database::Sqlite { .. } => "db",
+        }
+    }
+
+    pub async fn dump(&self, destination: &Path) -> Result<(), DisasterRecoveryError> {
+        match self {
+            Database::Postgres { database_connect_info } => {
+                let (url, password) = url_and_password(database_connect_info)?;
+                let mut command = Command::new("pg_dump");
+                command
+                    .arg("--format=custom")
+                    .arg("--file").arg(destination)
+                    .arg("--dbname").arg(url.as_str())
+                    .env("PGPASSWORD", password);
+                run(command, "pg_dump").await
+            }
+            Database::Sqlite { path } => {
+                let path = Clone::clone(path);
+                let destination = destination.to_owned();
+
+                tokio::task::spawn_blocking(move || {
+                    let path = path.to_string_lossy();
+                    let mut connection = SqliteConnection::establish(&path)
+                        .map_err(|cause| DisasterRecoveryError::Sqlite { cause: cause.to_string() })?;
+
+                    let destination = destination.to_string_lossy().replace('\'', "''");
+                    connection.batch_execute(&format!("VACUUM INTO '{destination}';"))
+                        .map_err(|cause| DisasterRecoveryError::Sqlite { cause: cause.to_string() })
+                }).await?
+            }
+        }
+    }
+
+    /// Replaces the content of the database with the dump. CARL must not be running on this database meanwhile.
+    pub async fn restore(&self, source: &Path) -> Result<(), DisasterRecoveryError> {
+        match self {
+            Database::Postgres { database_connect_info } => {
+                let (url, password) = url_and_password(database_connect_info)?;
+                let mut command = Command::new("pg_restore");
+                command
+                    .arg("--clean")
+                    .arg("--if-exists")
+                    .arg("--no-owner")
+                    .arg("--single-transaction")
+                    .arg("--exit-on-error")
+                    .arg("--dbname").arg(url.as_str())
+                    .arg(source)
+                    .env("PGPASSWORD", password);
+                run(command, "pg_restore").await
+            }
+            Database::Sqlite { path } => {
+                //copy next to the database file first, so that the database is replaced atomically
+                let temporary_path = path.with_extension("restoring");
+                tokio::fs::copy(source, &temporary_path).await
+                    .map_err(|source| DisasterRecoveryError::Io { path: Clone::clone(&temporary_path), source })?;
+                tokio::fs::rename(&temporary_path, path).await
+                    .map_err(|source| DisasterRecoveryError::Io { path: Clone::clone(path), source })
+            }
+        }
+    }
+}
+
+/// The password is handed over via environment variable, so that it does not show up in the process list.
+fn url_and_password(database_connect_info: &DatabaseConnectInfo) -> Result<(Url, &str), DisasterRecoveryError> {
+    let DatabaseConnectInfo { url, username, password } = database_connect_info;
+
+    let mut url = Clone::clone(url);
+    url.set_username(username)
+        .map_err(|()| DisasterRecoveryError::InvalidDatabaseUrl { url: Clone::clone(&url) })?;
+
+    Ok((url, password.secret()))
+}
+
+async fn run(mut command: Command, program: &'static str) -> Result<(), DisasterRecoveryError> {
+    let output = command.output().await
+        .map_err(|cause| DisasterRecoveryError::Command { program, cause: cause.to_string() })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(DisasterRecoveryError::Command { program, cause: format!("{}: {}", output.status, stderr.trim()) })
+    }
+}
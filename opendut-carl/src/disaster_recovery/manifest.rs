@@ -0,0 +1,173 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::disaster_recovery::DatabaseBackend;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Lists the dumps in the standby directory, oldest first, so that the standby can check them without access to the primary instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Name of the file within the standby directory.
+    pub file: String,
+    pub backend: DatabaseBackend,
+    /// RFC 3339 timestamp of when the dump was started.
+    pub created_at: String,
+    pub size_bytes: u64,
+    /// Hex-encoded SHA-256 checksum of the file.
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn path(standby_directory: &Path) -> PathBuf {
+        standby_directory.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Reads the manifest from the standby directory. Without a manifest, no dump has been shipped yet.
+    pub fn read(standby_directory: &Path) -> Result<Self, ManifestError> {
+        let path = Self::path(standby_directory);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|source| ManifestError::Invalid { path, source }),
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(source) => Err(ManifestError::Io { path, source }),
+        }
+    }
+
+    /// Writes the manifest via a temporary file, so that a standby never reads a partially written manifest.
+    pub fn write(&self, standby_directory: &Path) -> Result<(), ManifestError> {
+        let path = Self::path(standby_directory);
+        let temporary_path = standby_directory.join(format!(".{MANIFEST_FILE_NAME}.partial"));
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|source| ManifestError::Invalid { path: Clone::clone(&path), source })?;
+        fs::write(&temporary_path, content)
+            .and_then(|()| fs::rename(&temporary_path, &path))
+            .map_err(|source| ManifestError::Io { path, source })
+    }
+
+    pub fn latest(&self) -> Option<&Artifact> {
+        self.artifacts.last()
+    }
+
+    pub fn find(&self, file: &str) -> Option<&Artifact> {
+        self.artifacts.iter().find(|artifact| artifact.file == file)
+    }
+
+    /// Keeps the newest `retained` artifacts and returns the removed ones, whose files can then be deleted.
+    pub fn retain_latest(&mut self, retained: usize) -> Vec<Artifact> {
+        let excess = self.artifacts.len().saturating_sub(retained);
+        self.artifacts.drain(..excess).collect()
+    }
+}
+
+impl Artifact {
+    pub fn created_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .map(|created_at| created_at.with_timezone(&Utc))
+    }
+
+    /// Checks that the file exists in the standby directory and was not truncated or altered since it was shipped.
+    pub fn check_integrity(&self, standby_directory: &Path) -> Result<(), ManifestError> {
+        let path = standby_directory.join(&self.file);
+
+        let size_bytes = fs::metadata(&path)
+            .map_err(|source| ManifestError::Io { path: Clone::clone(&path), source })?
+            .len();
+        if size_bytes != self.size_bytes {
+            return Err(ManifestError::SizeMismatch { path, expected: self.size_bytes, actual: size_bytes });
+        }
+
+        let sha256 = sha256_of_file(&path)?;
+        if sha256 != self.sha256 {
+            return Err(ManifestError::ChecksumMismatch { path, expected: Clone::clone(&self.sha256), actual: sha256 });
+        }
+        Ok(())
+    }
+}
+
+pub fn sha256_of_file(path: &Path) -> Result<String, ManifestError> {
+    let mut file = fs::File::open(path)
+        .map_err(|source| ManifestError::Io { path: path.to_owned(), source })?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)
+        .map_err(|source| ManifestError::Io { path: path.to_owned(), source })?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    #[error("Failed to access '{path}': {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Invalid manifest '{path}': {source}")]
+    Invalid { path: PathBuf, source: serde_json::Error },
+    #[error("Dump '{path}' has {actual} bytes, but {expected} bytes were shipped.")]
+    SizeMismatch { path: PathBuf, expected: u64, actual: u64 },
+    #[error("Dump '{path}' has the SHA-256 checksum {actual}, but {expected} was shipped.")]
+    ChecksumMismatch { path: PathBuf, expected: String, actual: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn artifact(standby_directory: &Path, file: &str, content: &str) -> anyhow::Result<Artifact> {
+        let path = standby_directory.join(file);
+        fs::write(&path, content)?;
+        Ok(Artifact {
+            file: file.to_owned(),
+            backend: DatabaseBackend::Sqlite,
+            created_at: Utc::now().to_rfc3339(),
+            size_bytes: content.len() as u64,
+            sha256: sha256_of_file(&path)?,
+        })
+    }
+
+    #[test]
+    fn should_keep_the_latest_artifacts_and_return_the_removed_ones() -> anyhow::Result<()> {
+        let standby_directory = tempfile::tempdir()?;
+        let mut manifest = Manifest {
+            artifacts: vec![
+                artifact(standby_directory.path(), "carl-1.db", "first")?,
+                artifact(standby_directory.path(), "carl-2.db", "second")?,
+                artifact(standby_directory.path(), "carl-3.db", "third")?,
+            ],
+        };
+
+        let removed = manifest.retain_latest(2);
+
+        assert_that!(removed.iter().map(|artifact| artifact.file.as_str()).collect::<Vec<_>>(), elements_are![eq("carl-1.db")]);
+        assert_that!(manifest.latest().map(|artifact| artifact.file.as_str()), some(eq("carl-3.db")));
+
+        manifest.write(standby_directory.path())?;
+        assert_that!(Manifest::read(standby_directory.path())?, eq(&manifest));
+        Ok(())
+    }
+
+    #[test]
+    fn should_detect_an_altered_artifact() -> anyhow::Result<()> {
+        let standby_directory = tempfile::tempdir()?;
+        let artifact = artifact(standby_directory.path(), "carl-1.db", "shipped")?;
+
+        artifact.check_integrity(standby_directory.path())?;
+
+        fs::write(standby_directory.path().join("carl-1.db"), "altered")?;
+        assert!(matches!(artifact.check_integrity(standby_directory.path()), Err(ManifestError::ChecksumMismatch { .. })));
+
+        fs::remove_file(standby_directory.path().join("carl-1.db"))?;
+        assert!(matches!(artifact.check_integrity(standby_directory.path()), Err(ManifestError::Io { .. })));
+        Ok(())
+    }
+}
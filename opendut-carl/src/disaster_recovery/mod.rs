@@ -0,0 +1,315 @@
+use std::fmt;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::Utc;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::disaster_recovery::manifest::{Artifact, Manifest, ManifestError};
+use crate::leadership::LeadershipRef;
+use crate::resources::storage::PersistenceOptions;
+
+pub use database::{Database, DatabaseBackend};
+
+pub mod artifacts;
+mod database;
+mod manifest;
+
+/// Periodically dumps the persistence database into the standby directory, e.g. a network share mounted on the host of the standby instance,
+/// where `opendut-carl dr verify` checks the dumps and `opendut-carl dr activate` restores the latest one into the database of the standby instance.
+/// Each dump is listed with its checksum in a manifest, so that the standby can detect incomplete or altered dumps.
+/// With leader election, only the leader ships the database, which is shared by all instances.
+pub fn spawn_shipping(
+    persistence_options: PersistenceOptions,
+    leadership: LeadershipRef,
+    options: DisasterRecoveryOptions,
+) {
+    let ShippingOptions::Enabled { interval, retained } = options.shipping else {
+        return;
+    };
+    let database = match Database::try_from(persistence_options) {
+        Ok(database) => database,
+        Err(cause) => {
+            warn!("Not shipping the database to the standby location: {cause}");
+            return;
+        }
+    };
+
+    info!("Shipping the {} database to '{}' every {} ms, retaining {retained} dumps.", database.backend(), options.standby_directory.display(), interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if leadership.is_leader().not() {
+                continue;
+            }
+
+            match ship(&database, &options.standby_directory, retained).await {
+                Ok(artifact) => info!("Shipped database dump '{}' with {} bytes to the standby location.", artifact.file, artifact.size_bytes),
+                Err(cause) => warn!("Failed to ship the database to the standby location:\n  {cause}"),
+            }
+        }
+    });
+}
+
+async fn ship(database: &Database, standby_directory: &Path, retained: usize) -> Result<Artifact, DisasterRecoveryError> {
+    tokio::fs::create_dir_all(standby_directory).await
+        .map_err(|source| DisasterRecoveryError::Io { path: standby_directory.to_owned(), source })?;
+
+    let created_at = Utc::now();
+    let file = format!("carl-{}.{}", created_at.format("%Y%m%dT%H%M%SZ"), database.file_extension());
+    let path = standby_directory.join(&file);
+    let temporary_path = standby_directory.join(format!(".{file}.partial"));
+
+    //dump under a temporary name, so that a standby never picks up an incomplete dump
+    database.dump(&temporary_path).await?;
+
+    let sha256 = {
+        let temporary_path = Clone::clone(&temporary_path);
+        tokio::task::spawn_blocking(move || manifest::sha256_of_file(&temporary_path)).await??
+    };
+    let size_bytes = tokio::fs::metadata(&temporary_path).await
+        .map_err(|source| DisasterRecoveryError::Io { path: Clone::clone(&temporary_path), source })?
+        .len();
+    tokio::fs::rename(&temporary_path, &path).await
+        .map_err(|source| DisasterRecoveryError::Io { path: Clone::clone(&path), source })?;
+
+    let artifact = Artifact {
+        file,
+        backend: database.backend(),
+        created_at: created_at.to_rfc3339(),
+        size_bytes,
+        sha256,
+    };
+
+    let mut manifest = Manifest::read(standby_directory)?;
+    manifest.artifacts.push(Clone::clone(&artifact));
+    let removed = manifest.retain_latest(retained);
+    manifest.write(standby_directory)?;
+
+    for removed in removed {
+        let path = standby_directory.join(&removed.file);
+        if let Err(cause) = tokio::fs::remove_file(&path).await {
+            warn!("Failed to delete database dump '{}', which is no longer retained: {cause}", path.display());
+        }
+    }
+
+    Ok(artifact)
+}
+
+/// Checks whether the standby location holds what is needed to activate the standby instance,
+/// without changing anything, so that it can be run regularly, e.g. as a cron job, to detect a broken shipping early.
+pub fn verify(database: &Database, options: &DisasterRecoveryOptions) -> VerifyReport {
+    let mut report = VerifyReport { checks: Vec::new() };
+    let now = SystemTime::now();
+
+    let manifest = Manifest::read(&options.standby_directory);
+    let latest = match &manifest {
+        Ok(manifest) => match manifest.latest() {
+            Some(latest) => {
+                report.passed("manifest", format!("{} dumps available, latest is '{}'", manifest.artifacts.len(), latest.file));
+                Some(latest)
+            }
+            None => {
+                report.failed("manifest", format!("No dumps were shipped to '{}' yet", options.standby_directory.display()));
+                None
+            }
+        },
+        Err(cause) => {
+            report.failed("manifest", cause.to_string());
+            None
+        }
+    };
+
+    if let Some(latest) = latest {
+        match latest.check_integrity(&options.standby_directory) {
+            Ok(()) => report.passed("integrity", format!("'{}' matches its checksum", latest.file)),
+            Err(cause) => report.failed("integrity", cause.to_string()),
+        }
+
+        match latest.created_at() {
+            Ok(created_at) => {
+                let age = now.duration_since(SystemTime::from(created_at)).unwrap_or_default();
+                report.check_age("age", &format!("'{}'", latest.file), age, options.max_age);
+            }
+            Err(cause) => report.failed("age", format!("Invalid creation time '{}' of '{}': {cause}", latest.created_at, latest.file)),
+        }
+
+        if latest.backend == database.backend() {
+            report.passed("backend", format!("Dump and database of this instance use {}", latest.backend));
+        } else {
+            report.failed("backend", format!("Dump was taken from {}, but this instance uses {}", latest.backend, database.backend()));
+        }
+    }
+
+    if let Some(wal_archive_directory) = &options.wal_archive_directory {
+        match newest_modification(wal_archive_directory) {
+            Ok(Some(modified)) => {
+                let age = now.duration_since(modified).unwrap_or_default();
+                report.check_age("wal archive", &format!("Newest WAL segment in '{}'", wal_archive_directory.display()), age, options.max_age);
+            }
+            Ok(None) => report.failed("wal archive", format!("No WAL segments were archived to '{}' yet", wal_archive_directory.display())),
+            Err(cause) => report.failed("wal archive", format!("Failed to read '{}': {cause}", wal_archive_directory.display())),
+        }
+    }
+
+    report
+}
+
+fn newest_modification(directory: &Path) -> std::io::Result<Option<SystemTime>> {
+    let mut newest = None;
+    for entry in std::fs::read_dir(directory)? {
+        let metadata = entry?.metadata()?;
+        if metadata.is_file() {
+            let modified = metadata.modified()?;
+            newest = newest.max(Some(modified));
+        }
+    }
+    Ok(newest)
+}
+
+/// Restores a dump from the standby location into the database of this instance, so that it can take over as primary instance once started.
+/// Without a given file name, the latest dump is restored.
+pub async fn activate(database: &Database, options: &DisasterRecoveryOptions, file: Option<&str>) -> Result<Artifact, DisasterRecoveryError> {
+    let manifest = Manifest::read(&options.standby_directory)?;
+
+    let artifact = match file {
+        Some(file) => manifest.find(file)
+            .ok_or_else(|| DisasterRecoveryError::UnknownArtifact { file: file.to_owned() })?,
+        None => manifest.latest()
+            .ok_or_else(|| DisasterRecoveryError::NoArtifact { standby_directory: Clone::clone(&options.standby_directory) })?,
+    };
+
+    if artifact.backend != database.backend() {
+        return Err(DisasterRecoveryError::BackendMismatch { artifact: artifact.backend, database: database.backend() });
+    }
+    artifact.check_integrity(&options.standby_directory)?;
+
+    info!("Restoring the {} database from '{}'.", database.backend(), artifact.file);
+    database.restore(&options.standby_directory.join(&artifact.file)).await?;
+
+    Ok(Clone::clone(artifact))
+}
+
+pub struct VerifyReport {
+    checks: Vec<VerifyCheck>,
+}
+struct VerifyCheck {
+    name: &'static str,
+    outcome: Result<String, String>,
+}
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|check| check.outcome.is_ok())
+    }
+
+    fn passed(&mut self, name: &'static str, message: String) {
+        self.checks.push(VerifyCheck { name, outcome: Ok(message) });
+    }
+
+    fn failed(&mut self, name: &'static str, message: String) {
+        self.checks.push(VerifyCheck { name, outcome: Err(message) });
+    }
+
+    fn check_age(&mut self, name: &'static str, subject: &str, age: Duration, max_age: Duration) {
+        let message = format!("{subject} is {} s old, at most {} s are tolerated", age.as_secs(), max_age.as_secs());
+        if age > max_age {
+            self.failed(name, message);
+        } else {
+            self.passed(name, message);
+        }
+    }
+}
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.outcome {
+                Ok(message) => writeln!(f, "[ OK ] {}: {message}", check.name)?,
+                Err(message) => writeln!(f, "[FAIL] {}: {message}", check.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct DisasterRecoveryOptions {
+    pub shipping: ShippingOptions,
+    /// Directory, which the dumps are shipped to and restored from.
+    pub standby_directory: PathBuf,
+    /// Directory, which PostgreSQL archives its write-ahead log to via `archive_command`, for point-in-time recovery beyond the latest dump.
+    pub wal_archive_directory: Option<PathBuf>,
+    /// Age of the latest dump or WAL segment, beyond which the standby is not considered ready.
+    pub max_age: Duration,
+}
+
+#[derive(Clone)]
+pub enum ShippingOptions {
+    Enabled {
+        interval: Duration,
+        retained: usize,
+    },
+    Disabled,
+}
+
+impl DisasterRecoveryOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        use opendut_util::settings::LoadError;
+
+        let shipping = if config.get_bool("disaster.recovery.shipping.enabled")? {
+            let interval = Duration::from_millis(
+                config.get::<u64>("disaster.recovery.shipping.interval.ms")?
+            );
+            let retained = {
+                let field = "disaster.recovery.shipping.retained";
+                let value = config.get::<usize>(field)?;
+                if value == 0 {
+                    return Err(LoadError::ParseValue { field, value: value.to_string(), source: "At least one dump must be retained.".into() });
+                }
+                value
+            };
+            ShippingOptions::Enabled { interval, retained }
+        } else {
+            ShippingOptions::Disabled
+        };
+
+        let standby_directory = PathBuf::from(config.get_string("disaster.recovery.standby.directory")?);
+
+        let wal_archive_directory = Some(config.get_string("disaster.recovery.wal.archive.directory")?)
+            .filter(|directory| directory.is_empty().not())
+            .map(PathBuf::from);
+
+        let max_age = Duration::from_millis(
+            config.get::<u64>("disaster.recovery.verify.max.age.ms")?
+        );
+
+        Ok(DisasterRecoveryOptions { shipping, standby_directory, wal_archive_directory, max_age })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DisasterRecoveryError {
+    #[error("Persistence is disabled, so there is no database to ship or restore.")]
+    PersistenceDisabled,
+    #[error("Invalid database URL '{url}'.")]
+    InvalidDatabaseUrl { url: Url },
+    #[error("Failed to access '{path}': {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error("Running '{program}' failed: {cause}")]
+    Command { program: &'static str, cause: String },
+    #[error("Dumping the SQLite database failed: {cause}")]
+    Sqlite { cause: String },
+    #[error("No dumps were shipped to '{standby_directory}' yet.")]
+    NoArtifact { standby_directory: PathBuf },
+    #[error("Dump '{file}' is not listed in the manifest of the standby location.")]
+    UnknownArtifact { file: String },
+    #[error("Dump was taken from {artifact}, but this instance uses {database}.")]
+    BackendMismatch { artifact: DatabaseBackend, database: DatabaseBackend },
+    #[error("Background task failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
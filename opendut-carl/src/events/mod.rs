@@ -0,0 +1,345 @@
+use std::ops::Not;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::label::LabelKey;
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_util::settings::LoadError;
+
+use crate::events::broker::{Broker, BrokerClient};
+use crate::leadership::LeadershipRef;
+use crate::persistence::error::PersistenceResult;
+use crate::resources::ids::IntoId;
+use crate::resources::manager::{ResourcesManagerRef, SubscriptionEvent};
+use crate::resources::resource::Resource;
+use crate::resources::storage::ResourcesStorageApi;
+use crate::resources::subscription::{ReceiveError, Subscribable};
+use crate::resources::Resources;
+
+mod broker;
+
+/// Prefix of the `type` attribute of the published events, e.g. `org.eclipse.opendut.cluster-deployment.stored`.
+pub const EVENT_TYPE_PREFIX: &str = "org.eclipse.opendut";
+/// Media type of events in the structured content mode of CloudEvents.
+pub const CLOUD_EVENTS_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Number of events, which are buffered for publishing, while the brokers are slow or unavailable. Further events are dropped.
+const PUBLISH_QUEUE_CAPACITY: usize = 1000;
+
+/// Event in the JSON format of the CloudEvents specification 1.0.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CloudEvent {
+    pub specversion: &'static str,
+    pub id: String,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// ID of the resource, which the event is about.
+    pub subject: String,
+    pub time: String,
+    pub datacontenttype: &'static str,
+    pub data: serde_json::Value,
+    /// Extension attribute with the project of the resource, as designated by the project label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opendutproject: Option<String>,
+}
+
+impl CloudEvent {
+    fn new<R: Publishable>(source: &str, event: SubscriptionEvent<R>, project: Option<String>) -> Result<Self, serde_json::Error> {
+        let (action, id, value) = match event {
+            SubscriptionEvent::Inserted { id, value } => ("stored", id, value),
+            SubscriptionEvent::Removed { id, value } => ("deleted", id, value),
+        };
+
+        Ok(Self {
+            specversion: "1.0",
+            id: Uuid::new_v4().to_string(),
+            source: source.to_owned(),
+            event_type: format!("{EVENT_TYPE_PREFIX}.{}.{action}", R::EVENT_NAME),
+            subject: IntoId::<R>::into_id(id).to_string(),
+            time: chrono::Utc::now().to_rfc3339(),
+            datacontenttype: "application/json",
+            data: serde_json::to_value(value)?,
+            opendutproject: project,
+        })
+    }
+}
+
+/// Resource, whose lifecycle is published as events.
+trait Publishable: Resource + Subscribable + Serialize {
+    /// Name of the resource within the type of its events.
+    const EVENT_NAME: &'static str;
+
+    /// Determines the project of the resource. The value may already be removed from the resources.
+    fn project(id: &Self::Id, value: &Self, resources: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>>;
+}
+
+fn project_of_peer(peer_id: PeerId, resources: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>> {
+    let project = resources.get::<PeerDescriptor>(peer_id)?
+        .and_then(|peer| peer.labels.get(project_label).map(|project| project.value().to_owned()));
+    Ok(project)
+}
+
+impl Publishable for ClusterConfiguration {
+    const EVENT_NAME: &'static str = "cluster-configuration";
+
+    fn project(_: &Self::Id, value: &Self, resources: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>> {
+        project_of_peer(value.leader, resources, project_label)
+    }
+}
+impl Publishable for ClusterDeployment {
+    const EVENT_NAME: &'static str = "cluster-deployment";
+
+    fn project(id: &Self::Id, _: &Self, resources: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>> {
+        match resources.get::<ClusterConfiguration>(*id)? {
+            Some(configuration) => project_of_peer(configuration.leader, resources, project_label),
+            None => Ok(None),
+        }
+    }
+}
+impl Publishable for PeerDescriptor {
+    const EVENT_NAME: &'static str = "peer-descriptor";
+
+    fn project(_: &Self::Id, value: &Self, _: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>> {
+        Ok(value.labels.get(project_label).map(|project| project.value().to_owned()))
+    }
+}
+impl Publishable for PeerState {
+    const EVENT_NAME: &'static str = "peer-state";
+
+    fn project(id: &Self::Id, _: &Self, resources: &Resources, project_label: &LabelKey) -> PersistenceResult<Option<String>> {
+        project_of_peer(*id, resources, project_label)
+    }
+}
+impl Publishable for PeerGroup {
+    const EVENT_NAME: &'static str = "peer-group";
+
+    fn project(_: &Self::Id, _: &Self, _: &Resources, _: &LabelKey) -> PersistenceResult<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Publishes the lifecycle of clusters, deployments, peers and peer groups as CloudEvents to the configured message brokers,
+/// so that event pipelines can consume the activity of openDuT without polling the APIs of CARL.
+///
+/// Events are delivered at most once: Events, which a broker does not accept, are logged and dropped.
+/// With leader election, only the leader publishes events, as followers observe the same changes.
+pub fn spawn_publishing(resources_manager: ResourcesManagerRef, leadership: LeadershipRef, options: EventsOptions, project_label: LabelKey) {
+    let EventsOptions::Enabled { brokers, source } = options else {
+        return;
+    };
+    info!("Publishing events to {} message broker(s).", brokers.len());
+
+    let (sender, receiver) = mpsc::channel(PUBLISH_QUEUE_CAPACITY);
+    let source = Arc::new(source.to_string());
+    let project_label = Arc::new(project_label);
+
+    forward::<ClusterConfiguration>(Arc::clone(&resources_manager), Arc::clone(&leadership), Arc::clone(&source), Arc::clone(&project_label), sender.clone());
+    forward::<ClusterDeployment>(Arc::clone(&resources_manager), Arc::clone(&leadership), Arc::clone(&source), Arc::clone(&project_label), sender.clone());
+    forward::<PeerDescriptor>(Arc::clone(&resources_manager), Arc::clone(&leadership), Arc::clone(&source), Arc::clone(&project_label), sender.clone());
+    forward::<PeerState>(Arc::clone(&resources_manager), Arc::clone(&leadership), Arc::clone(&source), Arc::clone(&project_label), sender.clone());
+    forward::<PeerGroup>(resources_manager, leadership, source, project_label, sender);
+
+    tokio::spawn(publish(brokers, receiver));
+}
+
+/// Converts the subscription events of one resource type into CloudEvents and queues them for publishing.
+fn forward<R: Publishable>(resources_manager: ResourcesManagerRef, leadership: LeadershipRef, source: Arc<String>, project_label: Arc<LabelKey>, sender: mpsc::Sender<CloudEvent>) {
+    tokio::spawn(async move {
+        let mut subscription = resources_manager.subscribe::<R>().await;
+        loop {
+            let event = match subscription.receive().await {
+                Ok(event) => event,
+                Err(ReceiveError::Lagged { skipped }) => {
+                    warn!("Publishing of {} events lagged behind. Dropped {skipped} events.", R::EVENT_NAME);
+                    continue;
+                }
+                Err(ReceiveError::Stale) => {
+                    warn!("Subscription to {} events was closed as stale. Subscribing anew.", R::EVENT_NAME);
+                    subscription = resources_manager.subscribe::<R>().await;
+                    continue;
+                }
+                Err(ReceiveError::Closed) => break,
+            };
+            if leadership.is_leader().not() {
+                continue;
+            }
+
+            let (id, value) = match &event {
+                SubscriptionEvent::Inserted { id, value } | SubscriptionEvent::Removed { id, value } => (id, value),
+            };
+            let project = resources_manager.resources(|resources| R::project(id, value, resources, &project_label)).await
+                .unwrap_or_else(|cause| {
+                    warn!("Failed to determine the project of a {} event. Publishing it without project:\n  {cause}", R::EVENT_NAME);
+                    None
+                });
+
+            match CloudEvent::new(&source, event, project) {
+                Ok(cloud_event) => {
+                    if let Err(cause) = sender.try_send(cloud_event) {
+                        warn!("Dropping {} event, because it could not be queued for publishing: {cause}", R::EVENT_NAME);
+                    }
+                }
+                Err(cause) => warn!("Failed to serialize {} event: {cause}", R::EVENT_NAME),
+            }
+        }
+    });
+}
+
+async fn publish(brokers: Vec<ProjectBroker>, mut receiver: mpsc::Receiver<CloudEvent>) {
+    let mut clients = brokers.into_iter()
+        .map(|ProjectBroker { project, broker }| (project, BrokerClient::new(broker)))
+        .collect::<Vec<_>>();
+
+    while let Some(event) = receiver.recv().await {
+        for (project, client) in clients.iter_mut() {
+            if project.matches(event.opendutproject.as_deref()).not() {
+                continue;
+            }
+            match client.publish(&event).await {
+                Ok(()) => debug!("Published event '{}' about <{}> to {}.", event.event_type, event.subject, client.broker()),
+                Err(cause) => warn!("Failed to publish event '{}' about <{}> to {}. Dropping the event:\n  {cause}", event.event_type, event.subject, client.broker()),
+            }
+        }
+    }
+}
+
+/// Projects, whose events are published to a broker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProjectFilter {
+    All,
+    Project(String),
+}
+impl ProjectFilter {
+    fn matches(&self, project: Option<&str>) -> bool {
+        match self {
+            ProjectFilter::All => true,
+            ProjectFilter::Project(expected) => project == Some(expected.as_str()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectBroker {
+    pub project: ProjectFilter,
+    pub broker: Broker,
+}
+
+#[derive(Clone, Debug)]
+pub enum EventsOptions {
+    Disabled,
+    Enabled {
+        brokers: Vec<ProjectBroker>,
+        /// `source` attribute of the events, which identifies this instance of CARL.
+        source: Url,
+    },
+}
+
+impl EventsOptions {
+    pub fn load(config: &config::Config, carl_url: Url) -> Result<Self, LoadError> {
+        if config.get_bool("events.enabled")?.not() {
+            return Ok(EventsOptions::Disabled);
+        }
+
+        let brokers = {
+            let field = "events.brokers";
+            let value = config.get_string(field)?;
+            let brokers = parse_brokers(&value)
+                .map_err(|cause| LoadError::ParseValue { field, value: value.clone(), source: cause.into() })?;
+            if brokers.is_empty() {
+                return Err(LoadError::ParseValue { field, value, source: "At least one broker is required for publishing events.".into() });
+            }
+            brokers
+        };
+
+        let source = {
+            let field = "events.source";
+            let value = config.get_string(field)?;
+            if value.is_empty() {
+                carl_url
+            } else {
+                Url::parse(&value)
+                    .map_err(|cause| LoadError::ParseValue { field, value: value.clone(), source: cause.into() })?
+            }
+        };
+
+        Ok(EventsOptions::Enabled { brokers, source })
+    }
+}
+
+/// Parses a comma-separated list of brokers in the format `project=url`, where `*` designates all projects.
+fn parse_brokers(value: &str) -> Result<Vec<ProjectBroker>, String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|broker| broker.is_empty().not())
+        .map(|broker| {
+            let (project, url) = broker.split_once('=')
+                .ok_or_else(|| format!("Broker '{broker}' is not in the format 'project=url'."))?;
+            let project = match project.trim() {
+                "" => return Err(format!("Broker '{broker}' has no project. Use '*' for all projects.")),
+                "*" => ProjectFilter::All,
+                project => ProjectFilter::Project(project.to_owned()),
+            };
+            let url = Url::parse(url.trim())
+                .map_err(|cause| format!("Invalid URL of broker '{broker}': {cause}"))?;
+            let broker = Broker::try_from(&url)?;
+            Ok(ProjectBroker { project, broker })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterId;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_brokers_per_project() -> anyhow::Result<()> {
+        let brokers = parse_brokers("*=nats://nats.internal:4222/opendut.events, powertrain=kafka://kafka.internal:9092/powertrain-events")
+            .map_err(anyhow::Error::msg)?;
+
+        assert_that!(brokers.len(), eq(2));
+        assert_that!(brokers[0].project, eq(&ProjectFilter::All));
+        assert_that!(brokers[1].project, eq(&ProjectFilter::Project(String::from("powertrain"))));
+
+        assert!(parse_brokers("nats://nats.internal:4222/opendut.events").is_err());
+        assert!(parse_brokers("=nats://nats.internal:4222/opendut.events").is_err());
+        assert!(parse_brokers("*=mqtt://mqtt.internal/opendut").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_match_events_of_the_filtered_project() {
+        assert!(ProjectFilter::All.matches(None));
+        assert!(ProjectFilter::Project(String::from("powertrain")).matches(Some("powertrain")));
+        assert!(ProjectFilter::Project(String::from("powertrain")).matches(Some("chassis")).not());
+        assert!(ProjectFilter::Project(String::from("powertrain")).matches(None).not());
+    }
+
+    #[test]
+    fn should_describe_a_deployment_as_cloud_event() -> anyhow::Result<()> {
+        let id = ClusterId::random();
+        let event = SubscriptionEvent::Removed { id, value: ClusterDeployment { id } };
+
+        let cloud_event = CloudEvent::new("https://carl.opendut.local/", event, Some(String::from("powertrain")))?;
+        let json = serde_json::to_value(&cloud_event)?;
+
+        assert_that!(json["specversion"].as_str(), some(eq("1.0")));
+        assert_that!(json["type"].as_str(), some(eq("org.eclipse.opendut.cluster-deployment.deleted")));
+        assert_that!(json["subject"].as_str(), some(eq(id.to_string().as_str())));
+        assert_that!(json["opendutproject"].as_str(), some(eq("powertrain")));
+        assert_that!(json["data"]["id"].as_str(), some(eq(id.to_string().as_str())));
+        Ok(())
+    }
+}
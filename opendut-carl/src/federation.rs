@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use pem::Pem;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use url::Url;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::label::PeerLabels;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
+use opendut_types::topology::DeviceName;
+use opendut_util::settings::LoadError;
+
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub type FederationRef = Arc<Federation>;
+
+pub const VIEW_PATH: &str = "/api/federation/view";
+
+/// Federation of CARL instances across regions, e.g. plants.
+/// Each regional instance owns and controls its peers and clusters and exports a read-only view of them.
+/// A central instance retrieves these views periodically and serves them aggregated, without being able to change them.
+pub struct Federation {
+    options: FederationOptions,
+    regions: Mutex<HashMap<String, RegionView>>,
+}
+
+impl Federation {
+    pub fn new(options: FederationOptions) -> FederationRef {
+        Arc::new(Self { options, regions: Mutex::default() })
+    }
+
+    /// Whether a central instance presenting the given bearer token may retrieve the view of this instance.
+    pub fn is_export_authorized(&self, bearer_token: Option<&str>) -> bool {
+        match &self.options.export {
+            ExportOptions::Disabled => false,
+            ExportOptions::Enabled { token } => bearer_token == Some(token.as_str()),
+        }
+    }
+
+    pub fn is_export_enabled(&self) -> bool {
+        matches!(self.options.export, ExportOptions::Enabled { .. })
+    }
+
+    pub fn is_aggregating(&self) -> bool {
+        self.options.regions.is_empty().not()
+    }
+
+    /// Views of the regional instances, ordered by name, as last retrieved.
+    pub fn regions(&self) -> Vec<RegionView> {
+        let regions = self.regions.lock().unwrap();
+        self.options.regions.iter()
+            .map(|region| regions.get(&region.name).cloned()
+                .unwrap_or_else(|| RegionView::pending(region)))
+            .collect()
+    }
+
+    fn record(&self, region: &Region, result: Result<FederationView, String>) {
+        let mut regions = self.regions.lock().unwrap();
+        let previous = regions.remove(&region.name);
+
+        let view = match result {
+            Ok(view) => RegionView {
+                name: region.name.clone(),
+                url: region.url.clone(),
+                retrieved_unix_millis: Some(unix_millis(SystemTime::now())),
+                error: None,
+                view: Some(view),
+            },
+            Err(cause) => {
+                // Keep showing the last retrieved view, marked with the error, rather than nothing at all.
+                let previous = previous.unwrap_or_else(|| RegionView::pending(region));
+                RegionView { error: Some(cause), ..previous }
+            }
+        };
+        regions.insert(region.name.clone(), view);
+    }
+}
+
+/// Periodically retrieves the views of the configured regional instances.
+pub fn spawn_region_polling(federation: FederationRef, ca: Pem) -> anyhow::Result<()> {
+    if federation.is_aggregating().not() {
+        return Ok(());
+    }
+
+    let client = reqwest::Client::builder()
+        .add_root_certificate(reqwest::Certificate::from_pem(pem::encode(&ca).as_bytes())?)
+        .timeout(federation.options.poll_timeout)
+        .build()?;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(federation.options.poll_interval);
+        loop {
+            interval.tick().await;
+
+            for region in &federation.options.regions {
+                let result = retrieve_view(&client, region, &federation.options.regions_token).await;
+                match &result {
+                    Ok(view) => debug!("Retrieved view of region '{}' with {} peers and {} clusters.", region.name, view.peers.len(), view.clusters.len()),
+                    Err(cause) => warn!("Failed to retrieve view of region '{}' from <{}>: {cause}", region.name, region.url),
+                }
+                federation.record(region, result);
+            }
+        }
+    });
+    Ok(())
+}
+
+async fn retrieve_view(client: &reqwest::Client, region: &Region, token: &str) -> Result<FederationView, String> {
+    let url = region.url.join(VIEW_PATH)
+        .map_err(|cause| cause.to_string())?;
+
+    let response = client.get(url)
+        .bearer_auth(token)
+        .send().await
+        .map_err(|cause| cause.to_string())?;
+
+    let status = response.status();
+    if status.is_success().not() {
+        return Err(format!("Regional instance responded with HTTP status {status}."));
+    }
+    response.json::<FederationView>().await
+        .map_err(|cause| cause.to_string())
+}
+
+/// Read-only view of the peers and clusters of a CARL instance.
+/// Only contains what is needed for an overview, e.g. no executors, as their configuration may contain sensitive values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FederationView {
+    pub peers: Vec<FederatedPeer>,
+    pub clusters: Vec<FederatedCluster>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FederatedPeer {
+    pub id: PeerId,
+    pub name: PeerName,
+    pub location: Option<PeerLocation>,
+    pub labels: PeerLabels,
+    pub state: PeerState,
+    pub devices: Vec<DeviceName>,
+    pub executors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FederatedCluster {
+    pub id: ClusterId,
+    pub name: ClusterName,
+    pub leader: PeerId,
+    pub devices: usize,
+    pub deployed: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RegionView {
+    pub name: String,
+    pub url: Url,
+    /// `None`, if the view was not retrieved successfully yet.
+    pub retrieved_unix_millis: Option<u64>,
+    /// Error of the last attempt to retrieve the view, if it failed.
+    pub error: Option<String>,
+    pub view: Option<FederationView>,
+}
+
+impl RegionView {
+    fn pending(region: &Region) -> Self {
+        Self { name: region.name.clone(), url: region.url.clone(), retrieved_unix_millis: None, error: None, view: None }
+    }
+}
+
+pub async fn export_view(resources_manager: &ResourcesManagerRef) -> PersistenceResult<FederationView> {
+    resources_manager.resources(|resources| {
+        let mut peers = resources.list::<PeerDescriptor>()?.into_iter()
+            .map(|peer| {
+                let state = resources.get::<PeerState>(peer.id)?.unwrap_or_default();
+                Ok::<_, PersistenceError>(FederatedPeer {
+                    id: peer.id,
+                    name: peer.name,
+                    location: peer.location,
+                    labels: peer.labels,
+                    state,
+                    devices: peer.topology.devices.into_iter().map(|device| device.name).collect(),
+                    executors: peer.executors.executors.len(),
+                })
+            })
+            .collect::<PersistenceResult<Vec<_>>>()?;
+        peers.sort_by_key(|peer| peer.name.to_string());
+
+        let mut clusters = resources.list::<ClusterConfiguration>()?.into_iter()
+            .map(|cluster| {
+                let deployed = resources.get::<ClusterDeployment>(cluster.id)?.is_some();
+                Ok::<_, PersistenceError>(FederatedCluster {
+                    id: cluster.id,
+                    name: cluster.name,
+                    leader: cluster.leader,
+                    devices: cluster.devices.len(),
+                    deployed,
+                })
+            })
+            .collect::<PersistenceResult<Vec<_>>>()?;
+        clusters.sort_by_key(|cluster| cluster.name.to_string());
+
+        Ok(FederationView { peers, clusters })
+    }).await
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    pub name: String,
+    pub url: Url,
+}
+
+#[derive(Clone, Debug)]
+pub enum ExportOptions {
+    Disabled,
+    Enabled { token: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct FederationOptions {
+    pub export: ExportOptions,
+    pub regions: Vec<Region>,
+    pub regions_token: String,
+    pub poll_interval: Duration,
+    pub poll_timeout: Duration,
+}
+impl FederationOptions {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        let export = if config.get_bool("federation.export.enabled")? {
+            let field = "federation.export.token";
+            let token = config.get_string(field)?;
+            if token.is_empty() {
+                return Err(LoadError::ParseValue { field, value: token, source: "A token is required for exporting the view of this instance.".into() });
+            }
+            ExportOptions::Enabled { token }
+        } else {
+            ExportOptions::Disabled
+        };
+
+        let regions = {
+            let field = "federation.region.urls";
+            let value = config.get_string(field)?;
+            parse_regions(&value)
+                .map_err(|cause| LoadError::ParseValue { field, value, source: cause.into() })?
+        };
+
+        Ok(Self {
+            export,
+            regions,
+            regions_token: config.get_string("federation.region.token")?,
+            poll_interval: Duration::from_millis(config.get::<u64>("federation.region.poll.interval.ms")?),
+            poll_timeout: Duration::from_millis(config.get::<u64>("federation.region.poll.timeout.ms")?),
+        })
+    }
+}
+
+/// Parses a comma-separated list of regions in the format `name=url`.
+fn parse_regions(value: &str) -> Result<Vec<Region>, String> {
+    let mut regions = value.split(',')
+        .map(str::trim)
+        .filter(|region| region.is_empty().not())
+        .map(|region| {
+            let (name, url) = region.split_once('=')
+                .ok_or_else(|| format!("Region '{region}' is not in the format 'name=url'."))?;
+            let url = Url::parse(url.trim())
+                .map_err(|cause| format!("Invalid URL of region '{name}': {cause}"))?;
+            Ok(Region { name: name.trim().to_owned(), url })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    regions.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Some(duplicate) = regions.windows(2).find(|pair| pair[0].name == pair[1].name) {
+        return Err(format!("Region '{}' is configured more than once.", duplicate[0].name));
+    }
+    Ok(regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::actions::peer_groups::testing::generate_peer_descriptor;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_regions() -> anyhow::Result<()> {
+        let regions = parse_regions(" plant-b=https://carl.plant-b.internal/, plant-a = https://carl.plant-a.internal/ ,")
+            .map_err(|cause| anyhow::anyhow!(cause))?;
+
+        assert_eq!(regions, vec![
+            Region { name: String::from("plant-a"), url: Url::parse("https://carl.plant-a.internal/")? },
+            Region { name: String::from("plant-b"), url: Url::parse("https://carl.plant-b.internal/")? },
+        ]);
+
+        assert!(parse_regions("https://carl.plant-a.internal/").is_err());
+        assert!(parse_regions("a=https://one.internal/,a=https://two.internal/").is_err());
+        assert_eq!(parse_regions(""), Ok(vec![]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_export_peers_with_their_state() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer = generate_peer_descriptor()?;
+        resources_manager.insert(peer.id, peer.clone()).await?;
+
+        let view = export_view(&resources_manager).await?;
+
+        assert_that!(view.peers.len(), eq(1));
+        assert_that!(view.peers[0].id, eq(peer.id));
+        assert_eq!(view.peers[0].state, PeerState::Down);
+        assert_that!(view.peers[0].devices.len(), eq(peer.topology.devices.len()));
+        assert!(view.clusters.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_the_last_view_of_an_unreachable_region() -> anyhow::Result<()> {
+        let region = Region { name: String::from("plant-a"), url: Url::parse("https://carl.plant-a.internal/")? };
+        let federation = Federation::new(FederationOptions {
+            export: ExportOptions::Disabled,
+            regions: vec![region.clone()],
+            regions_token: String::new(),
+            poll_interval: Duration::from_secs(30),
+            poll_timeout: Duration::from_secs(10),
+        });
+        assert!(federation.regions()[0].view.is_none());
+
+        federation.record(&region, Ok(FederationView { peers: vec![], clusters: vec![] }));
+        federation.record(&region, Err(String::from("connection refused")));
+
+        let regions = federation.regions();
+        assert!(regions[0].view.is_some());
+        assert_eq!(regions[0].error.as_deref(), Some("connection refused"));
+        Ok(())
+    }
+}
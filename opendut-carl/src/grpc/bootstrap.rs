@@ -0,0 +1,113 @@
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::carl::bootstrap::{BootstrapError, BootstrapStatus, IdentityProviderConfiguration};
+use opendut_carl_api::proto::services::bootstrap::*;
+use opendut_carl_api::proto::services::bootstrap::bootstrap_server::{Bootstrap as BootstrapService, BootstrapServer};
+
+use crate::bootstrap::BootstrapRef;
+use crate::grpc::extract;
+
+pub struct BootstrapFacade {
+    bootstrap: BootstrapRef,
+}
+
+impl BootstrapFacade {
+
+    pub fn new(bootstrap: BootstrapRef) -> Self {
+        Self {
+            bootstrap,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<BootstrapServer<Self>> {
+        tonic_web::enable(BootstrapServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl BootstrapService for BootstrapFacade {
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_bootstrap_status(&self, _: Request<GetBootstrapStatusRequest>) -> Result<Response<GetBootstrapStatusResponse>, Status> {
+
+        trace!("Received request to get the bootstrap status.");
+
+        let status = self.bootstrap.status().await;
+
+        Ok(Response::new(GetBootstrapStatusResponse {
+            status: Some(status.into()),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_admin(&self, request: Request<CreateAdminRequest>) -> Result<Response<BootstrapResponse>, Status> {
+
+        let request = request.into_inner();
+
+        trace!("Received request to create the admin '{}' during the bootstrap.", request.name);
+
+        let result = self.bootstrap.create_admin(&request.token, request.name, request.email).await;
+
+        Ok(Response::new(into_response(result)))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn configure_identity_provider(&self, request: Request<ConfigureIdentityProviderRequest>) -> Result<Response<BootstrapResponse>, Status> {
+
+        let request = request.into_inner();
+        let identity_provider: IdentityProviderConfiguration = extract!(request.identity_provider)?;
+
+        trace!("Received request to configure the identity provider during the bootstrap.");
+
+        let result = self.bootstrap.configure_identity_provider(&request.token, identity_provider).await;
+
+        Ok(Response::new(into_response(result)))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn upload_ca(&self, request: Request<UploadCaRequest>) -> Result<Response<BootstrapResponse>, Status> {
+
+        let request = request.into_inner();
+
+        trace!("Received request to upload the CA during the bootstrap.");
+
+        let result = self.bootstrap.upload_ca(&request.token, request.certificate).await;
+
+        Ok(Response::new(into_response(result)))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn verify_vpn(&self, request: Request<VerifyVpnRequest>) -> Result<Response<BootstrapResponse>, Status> {
+
+        let request = request.into_inner();
+
+        trace!("Received request to verify the VPN during the bootstrap.");
+
+        let result = self.bootstrap.verify_vpn(&request.token).await;
+
+        Ok(Response::new(into_response(result)))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn complete_bootstrap(&self, request: Request<CompleteBootstrapRequest>) -> Result<Response<BootstrapResponse>, Status> {
+
+        let request = request.into_inner();
+
+        trace!("Received request to complete the bootstrap.");
+
+        let result = self.bootstrap.complete(&request.token).await;
+
+        Ok(Response::new(into_response(result)))
+    }
+}
+
+fn into_response(result: Result<BootstrapStatus, BootstrapError>) -> BootstrapResponse {
+    let reply = match result {
+        Ok(status) => bootstrap_response::Reply::Success(status.into()),
+        Err(error) => bootstrap_response::Reply::Failure(error.into()),
+    };
+    BootstrapResponse {
+        reply: Some(reply)
+    }
+}
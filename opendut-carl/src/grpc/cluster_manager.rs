@@ -6,30 +6,44 @@ use tracing::trace;
 use opendut_carl_api::proto::services::cluster_manager::*;
 use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::{ClusterManager as ClusterManagerService, ClusterManagerServer};
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::project::ProjectId;
 
 use crate::actions;
-use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams};
+use crate::actions::{AssignToProjectParams, CreateClusterConfigurationParams, DeleteClusterConfigurationParams, ProjectOptions, ProjectResourceId, SimulateClusterDeploymentParams, UnassignFromProjectsParams};
+use crate::auth::permission::{Permission, PermissionAuthorization};
+use crate::auth::project::ProjectAccess;
 use crate::cluster::manager::ClusterManagerRef;
 use crate::grpc::extract;
+use crate::policy::PolicyEngineRef;
 use crate::resources::manager::ResourcesManagerRef;
 
 pub struct ClusterManagerFacade {
     cluster_manager: ClusterManagerRef,
     resources_manager: ResourcesManagerRef,
+    policy_engine: PolicyEngineRef,
+    authorization: PermissionAuthorization,
+    project_options: ProjectOptions,
 }
 
 impl ClusterManagerFacade {
 
-    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef) -> Self {
+    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef, policy_engine: PolicyEngineRef, authorization: PermissionAuthorization, project_options: ProjectOptions) -> Self {
         Self {
             cluster_manager,
-            resources_manager
+            resources_manager,
+            policy_engine,
+            authorization,
+            project_options,
         }
     }
 
     pub fn into_grpc_service(self) -> CorsGrpcWeb<ClusterManagerServer<Self>> {
         tonic_web::enable(ClusterManagerServer::new(self))
     }
+
+    async fn project_access<T>(&self, request: &Request<T>) -> Result<ProjectAccess, Status> {
+        ProjectAccess::of(request, &self.resources_manager, &self.project_options).await
+    }
 }
 
 #[tonic::async_trait]
@@ -37,16 +51,38 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn create_cluster_configuration(&self, request: Request<CreateClusterConfigurationRequest>) -> Result<Response<CreateClusterConfigurationResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::ClusterWrite)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let cluster_configuration: ClusterConfiguration = extract!(request.cluster_configuration)?;
+        let project_id = request.project_id
+            .map(ProjectId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.project_id' is not valid: {cause}")))?;
+
+        access.authorize_cluster(cluster_configuration.id)?;
+        access.authorize_peer(cluster_configuration.leader)?;
+        if let Some(project_id) = project_id {
+            access.project(project_id, &self.resources_manager).await?;
+        }
 
         trace!("Received request to create cluster configuration: {cluster_configuration:?}");
 
         let result = actions::create_cluster_configuration(CreateClusterConfigurationParams {
             resources_manager: Arc::clone(&self.resources_manager),
+            policy_engine: Arc::clone(&self.policy_engine),
             cluster_configuration,
         }).await;
 
+        if let (Ok(cluster_id), Some(project_id)) = (&result, project_id) {
+            actions::assign_to_project(AssignToProjectParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                project_id,
+                resource: ProjectResourceId::Cluster(*cluster_id),
+            }).await
+            .map_err(|cause| Status::internal(cause.to_string()))?;
+        }
+
         match result {
             Err(error) => {
                 Ok(Response::new(CreateClusterConfigurationResponse {
@@ -67,8 +103,11 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_cluster_configuration(&self, request: Request<DeleteClusterConfigurationRequest>) -> Result<Response<DeleteClusterConfigurationResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::ClusterWrite)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        access.authorize_cluster(cluster_id)?;
 
         trace!("Received request to delete cluster configuration for cluster <{cluster_id}>.");
 
@@ -78,6 +117,14 @@ impl ClusterManagerService for ClusterManagerFacade {
                 cluster_id,
             }).await;
 
+        if result.is_ok() {
+            // failures are logged by the action and a remaining assignment only refers to a cluster, which does not exist anymore
+            let _ignore_error = actions::unassign_from_projects(UnassignFromProjectsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                resource: ProjectResourceId::Cluster(cluster_id),
+            }).await;
+        }
+
         match result {
             Err(error) => {
                 Ok(Response::new(DeleteClusterConfigurationResponse {
@@ -98,8 +145,11 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_cluster_configuration(&self, request: Request<GetClusterConfigurationRequest>) -> Result<Response<GetClusterConfigurationResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::ClusterRead)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.id)?;
+        access.authorize_cluster(cluster_id)?;
 
         trace!("Received request to get cluster configuration for cluster <{cluster_id}>.");
 
@@ -126,7 +176,19 @@ impl ClusterManagerService for ClusterManagerFacade {
         }
     }
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_cluster_configurations(&self, _: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
+    async fn list_cluster_configurations(&self, request: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
+        self.authorization.authorize(&request, Permission::ClusterRead)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let project = match request.project_id {
+            Some(project_id) => {
+                let project_id = ProjectId::try_from(project_id)
+                    .map_err(|cause| Status::invalid_argument(format!("Field 'request.project_id' is not valid: {cause}")))?;
+                Some(access.project(project_id, &self.resources_manager).await?)
+            }
+            None => None,
+        };
+
         trace!("Received request to list cluster configurations.");
 
         let configurations = self.cluster_manager.lock().await.list_configuration().await
@@ -135,7 +197,11 @@ impl ClusterManagerService for ClusterManagerFacade {
         Ok(Response::new(ListClusterConfigurationsResponse {
             result: Some(list_cluster_configurations_response::Result::Success(
                 ListClusterConfigurationsSuccess {
-                    configurations: configurations.into_iter().map(|configuration| configuration.into()).collect::<Vec<_>>()
+                    configurations: configurations.into_iter()
+                        .filter(|configuration| access.may_access_cluster(&configuration.id))
+                        .filter(|configuration| project.as_ref().map_or(true, |project| project.clusters.contains(&configuration.id)))
+                        .map(|configuration| configuration.into())
+                        .collect::<Vec<_>>()
                 }
             ))
         }))
@@ -144,8 +210,11 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn store_cluster_deployment(&self, request: Request<StoreClusterDeploymentRequest>) -> Result<Response<StoreClusterDeploymentResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::ClusterDeploy)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let cluster_deployment: ClusterDeployment = extract!(request.cluster_deployment)?;
+        access.authorize_cluster(cluster_deployment.id)?;
 
         trace!("Received request to store cluster deployment: {cluster_deployment:?}");
 
@@ -170,8 +239,11 @@ impl ClusterManagerService for ClusterManagerFacade {
     }
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_cluster_deployment(&self, request: Request<DeleteClusterDeploymentRequest>) -> Result<Response<DeleteClusterDeploymentResponse>, Status> {
+        self.authorization.authorize(&request, Permission::ClusterDeploy)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        access.authorize_cluster(cluster_id)?;
 
         trace!("Received request to delete cluster deployment for cluster <{cluster_id}>.");
 
@@ -196,7 +268,10 @@ impl ClusterManagerService for ClusterManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_cluster_deployments(&self, _: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
+    async fn list_cluster_deployments(&self, request: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
+        self.authorization.authorize(&request, Permission::ClusterRead)?;
+        let access = self.project_access(&request).await?;
+
         trace!("Received request to list cluster deployments.");
 
         let deployments = self.cluster_manager.lock().await.list_deployment().await
@@ -205,9 +280,76 @@ impl ClusterManagerService for ClusterManagerFacade {
         Ok(Response::new(ListClusterDeploymentsResponse {
             result: Some(list_cluster_deployments_response::Result::Success(
                 ListClusterDeploymentsSuccess {
-                    deployments: deployments.into_iter().map(|deployment| deployment.into()).collect::<Vec<_>>()
+                    deployments: deployments.into_iter()
+                        .filter(|deployment| access.may_access_cluster(&deployment.id))
+                        .map(|deployment| deployment.into())
+                        .collect::<Vec<_>>()
                 }
             ))
         }))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_cluster_deployment_status(&self, request: Request<GetClusterDeploymentStatusRequest>) -> Result<Response<GetClusterDeploymentStatusResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::ClusterRead)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        access.authorize_cluster(cluster_id)?;
+
+        trace!("Received request to get the deployment status of cluster <{cluster_id}>.");
+
+        let result = self.cluster_manager.lock().await.get_deployment_status(cluster_id).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetClusterDeploymentStatusResponse {
+                    reply: Some(get_cluster_deployment_status_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(status) => {
+                Ok(Response::new(GetClusterDeploymentStatusResponse {
+                    reply: Some(get_cluster_deployment_status_response::Reply::Success(
+                        GetClusterDeploymentStatusSuccess {
+                            status: Some(status.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn simulate_cluster_deployment(&self, request: Request<SimulateClusterDeploymentRequest>) -> Result<Response<SimulateClusterDeploymentResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::ClusterRead)?;
+        let request = request.into_inner();
+        let cluster_configuration: ClusterConfiguration = extract!(request.cluster_configuration)?;
+
+        trace!("Received request to simulate deployment of cluster configuration: {cluster_configuration:?}");
+
+        let result = actions::simulate_cluster_deployment(SimulateClusterDeploymentParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            policy_engine: Arc::clone(&self.policy_engine),
+            cluster_configuration,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(SimulateClusterDeploymentResponse {
+                    reply: Some(simulate_cluster_deployment_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(simulation) => {
+                Ok(Response::new(SimulateClusterDeploymentResponse {
+                    reply: Some(simulate_cluster_deployment_response::Reply::Success(
+                        SimulateClusterDeploymentSuccess {
+                            simulation: Some(simulation.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
 }
@@ -0,0 +1,276 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::diagnostics::*;
+use opendut_carl_api::proto::services::diagnostics::diagnostics_server::{Diagnostics as DiagnosticsService, DiagnosticsServer};
+
+use opendut_carl_api::carl::diagnostics::{TraceCaptureId, TraceCaptureLevel, TraceCaptureScope};
+use opendut_types::peer::PeerId;
+use opendut_util::telemetry::capture::TraceCapturesRef;
+
+use crate::actions;
+use crate::actions::{CheckCompatibilityParams, CheckConsistencyParams, DeleteTraceCaptureParams, DownloadTraceCaptureParams, ListPeerStreamsParams, ListTraceCapturesParams, StartTraceCaptureParams, TerminatePeerStreamParams, TraceCaptureOptions};
+use crate::auth::permission::{Permission, PermissionAuthorization};
+use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct DiagnosticsFacade {
+    resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    trace_captures: TraceCapturesRef,
+    trace_capture_options: TraceCaptureOptions,
+    authorization: PermissionAuthorization,
+}
+
+impl DiagnosticsFacade {
+
+    pub fn new(
+        resources_manager: ResourcesManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
+        trace_captures: TraceCapturesRef,
+        trace_capture_options: TraceCaptureOptions,
+        authorization: PermissionAuthorization,
+    ) -> Self {
+        Self {
+            resources_manager,
+            peer_messaging_broker,
+            trace_captures,
+            trace_capture_options,
+            authorization,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<DiagnosticsServer<Self>> {
+        tonic_web::enable(DiagnosticsServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl DiagnosticsService for DiagnosticsFacade {
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn check_consistency(&self, request: Request<CheckConsistencyRequest>) -> Result<Response<CheckConsistencyResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+
+        trace!("Received request to check consistency of resources (repair: {}).", request.repair);
+
+        let result = actions::check_consistency(CheckConsistencyParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            repair: request.repair,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CheckConsistencyResponse {
+                    reply: Some(check_consistency_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(findings) => {
+                Ok(Response::new(CheckConsistencyResponse {
+                    reply: Some(check_consistency_response::Reply::Success(
+                        CheckConsistencySuccess {
+                            findings: findings.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn check_compatibility(&self, request: Request<CheckCompatibilityRequest>) -> Result<Response<CheckCompatibilityResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+
+        trace!("Received request to check compatibility of resources (transform: {}).", request.transform);
+
+        let result = actions::check_compatibility(CheckCompatibilityParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            transform: request.transform,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CheckCompatibilityResponse {
+                    reply: Some(check_compatibility_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(findings) => {
+                Ok(Response::new(CheckCompatibilityResponse {
+                    reply: Some(check_compatibility_response::Reply::Success(
+                        CheckCompatibilitySuccess {
+                            findings: findings.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn start_trace_capture(&self, request: Request<StartTraceCaptureRequest>) -> Result<Response<StartTraceCaptureResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+        let scope: TraceCaptureScope = extract!(request.scope)?;
+        let level: TraceCaptureLevel = extract!(request.level)?;
+        let duration = Duration::from_millis(request.duration_millis);
+
+        trace!("Received request to start trace capture for {scope} at level {level}.");
+
+        let result = actions::start_trace_capture(StartTraceCaptureParams {
+            trace_captures: Arc::clone(&self.trace_captures),
+            scope,
+            level,
+            duration,
+            options: Clone::clone(&self.trace_capture_options),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StartTraceCaptureResponse {
+                    reply: Some(start_trace_capture_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(capture) => {
+                Ok(Response::new(StartTraceCaptureResponse {
+                    reply: Some(start_trace_capture_response::Reply::Success(
+                        StartTraceCaptureSuccess {
+                            capture: Some(capture.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_trace_captures(&self, request: Request<ListTraceCapturesRequest>) -> Result<Response<ListTraceCapturesResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        trace!("Received request to list trace captures.");
+
+        let captures = actions::list_trace_captures(ListTraceCapturesParams {
+            trace_captures: Arc::clone(&self.trace_captures),
+        }).await;
+
+        Ok(Response::new(ListTraceCapturesResponse {
+            captures: captures.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn download_trace_capture(&self, request: Request<DownloadTraceCaptureRequest>) -> Result<Response<DownloadTraceCaptureResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+        let capture_id: TraceCaptureId = extract!(request.capture_id)?;
+
+        trace!("Received request to download trace capture <{capture_id}>.");
+
+        let result = actions::download_trace_capture(DownloadTraceCaptureParams {
+            trace_captures: Arc::clone(&self.trace_captures),
+            capture_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DownloadTraceCaptureResponse {
+                    reply: Some(download_trace_capture_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(bundle) => {
+                Ok(Response::new(DownloadTraceCaptureResponse {
+                    reply: Some(download_trace_capture_response::Reply::Success(bundle.into()))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_trace_capture(&self, request: Request<DeleteTraceCaptureRequest>) -> Result<Response<DeleteTraceCaptureResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+        let capture_id: TraceCaptureId = extract!(request.capture_id)?;
+
+        trace!("Received request to delete trace capture <{capture_id}>.");
+
+        let result = actions::delete_trace_capture(DeleteTraceCaptureParams {
+            trace_captures: Arc::clone(&self.trace_captures),
+            capture_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteTraceCaptureResponse {
+                    reply: Some(delete_trace_capture_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(capture_id) => {
+                Ok(Response::new(DeleteTraceCaptureResponse {
+                    reply: Some(delete_trace_capture_response::Reply::Success(
+                        DeleteTraceCaptureSuccess {
+                            capture_id: Some(capture_id.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_peer_streams(&self, request: Request<ListPeerStreamsRequest>) -> Result<Response<ListPeerStreamsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        trace!("Received request to list peer streams.");
+
+        let streams = actions::list_peer_streams(ListPeerStreamsParams {
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+        }).await;
+
+        Ok(Response::new(ListPeerStreamsResponse {
+            streams: streams.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn terminate_peer_stream(&self, request: Request<TerminatePeerStreamRequest>) -> Result<Response<TerminatePeerStreamResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::Diagnostics)?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+
+        trace!("Received request to terminate stream of peer <{peer_id}>.");
+
+        let result = actions::terminate_peer_stream(TerminatePeerStreamParams {
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            peer_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(TerminatePeerStreamResponse {
+                    reply: Some(terminate_peer_stream_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_id) => {
+                Ok(Response::new(TerminatePeerStreamResponse {
+                    reply: Some(terminate_peer_stream_response::Reply::Success(
+                        TerminatePeerStreamSuccess {
+                            peer_id: Some(peer_id.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
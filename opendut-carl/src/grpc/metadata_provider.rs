@@ -1,18 +1,30 @@
+use std::pin::Pin;
+
+use tokio_stream::Stream;
 use tonic::{Request, Response, Status};
 use tonic_web::CorsGrpcWeb;
-use tracing::trace;
+use tracing::{debug, trace};
 
-use opendut_carl_api::proto::services::metadata_provider::{VersionRequest, VersionResponse};
+use opendut_carl_api::carl::error_code::{ErrorCode, ErrorRemediation};
+use opendut_carl_api::proto::services::metadata_provider::{download_cleo_response, DownloadCleoHeader, DownloadCleoRequest, DownloadCleoResponse, ErrorCatalogRequest, ErrorCatalogResponse, VersionRequest, VersionResponse};
 use opendut_carl_api::proto::services::metadata_provider::metadata_provider_server::{MetadataProvider, MetadataProviderServer};
 use opendut_types::proto::util::VersionInfo;
 
-#[derive(Debug, Default)]
-pub struct MetadataProviderFacade {}
+use crate::http::state::CarlInstallDirectory;
+use crate::util::{CLEO_IDENTIFIER, CleoArch};
+
+/// Size of the parts in which the CLEO archive is streamed, to stay below the gRPC message size limit.
+const DOWNLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct MetadataProviderFacade {
+    carl_installation_directory: CarlInstallDirectory,
+}
 
 impl MetadataProviderFacade {
 
-    pub fn new() -> Self {
-        Self { }
+    pub fn new(carl_installation_directory: CarlInstallDirectory) -> Self {
+        Self { carl_installation_directory }
     }
 
     pub fn into_grpc_service(self) -> CorsGrpcWeb<MetadataProviderServer<Self>> {
@@ -29,14 +41,240 @@ impl MetadataProvider for MetadataProviderFacade {
         trace!("Received request to get version information.");
 
         let reply = VersionResponse {
-            version_info: Some(VersionInfo {
-                name: String::from(crate::app_info::CRATE_VERSION),
-                revision: String::from(crate::app_info::REVISION),
-                revision_date: String::from(crate::app_info::REVISION_DATE),
-                build_date: String::from(crate::app_info::BUILD_DATE),
-            })
+            version_info: Some(version_info())
         };
 
         Ok(Response::new(reply))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn error_catalog(&self, _: Request<ErrorCatalogRequest>) -> Result<Response<ErrorCatalogResponse>, Status> {
+
+        trace!("Received request to get the error catalog.");
+
+        let reply = ErrorCatalogResponse {
+            entries: error_catalog().into_iter()
+                .map(From::from)
+                .collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    type DownloadCleoStream = Pin<Box<dyn Stream<Item = Result<DownloadCleoResponse, Status>> + Send>>;
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn download_cleo(&self, request: Request<DownloadCleoRequest>) -> Result<Response<Self::DownloadCleoStream>, Status> {
+
+        let request = request.into_inner();
+
+        trace!("Received request to download CLEO for architecture '{}'.", request.architecture);
+
+        let architecture = CleoArch::from_target_triple(&request.architecture)
+            .ok_or_else(|| Status::invalid_argument(format!("No CLEO distribution available for architecture '{}'.", request.architecture)))?;
+
+        let cleo_file_name = format!("{}-{}.tar.gz", architecture.distribution_name(), crate::app_info::CRATE_VERSION);
+        let cleo_dir = self.carl_installation_directory.path.join(CLEO_IDENTIFIER);
+
+        let archive = tokio::fs::read(cleo_dir.join(&cleo_file_name)).await
+            .map_err(|cause| Status::not_found(format!("CLEO distribution '{cleo_file_name}' could not be read: {cause}")))?;
+
+        let signature_file_name = format!("{cleo_file_name}.sig");
+        let signature = tokio::fs::read(cleo_dir.join(&signature_file_name)).await
+            .map_err(|cause| Status::not_found(format!("Signature '{signature_file_name}' of CLEO distribution could not be read: {cause}")))?;
+
+        debug!("Sending CLEO distribution '{cleo_file_name}' with {} bytes.", archive.len());
+
+        let header = DownloadCleoResponse {
+            part: Some(download_cleo_response::Part::Header(DownloadCleoHeader {
+                version_info: Some(version_info()),
+                signature,
+            }))
+        };
+        let chunks = archive.chunks(DOWNLOAD_CHUNK_SIZE)
+            .map(|chunk| DownloadCleoResponse {
+                part: Some(download_cleo_response::Part::Chunk(chunk.to_vec()))
+            })
+            .collect::<Vec<_>>();
+
+        let stream = tokio_stream::iter(
+            std::iter::once(header)
+                .chain(chunks)
+                .map(Ok)
+        );
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn version_info() -> VersionInfo {
+    VersionInfo {
+        name: String::from(crate::app_info::CRATE_VERSION),
+        revision: String::from(crate::app_info::REVISION),
+        revision_date: String::from(crate::app_info::REVISION_DATE),
+        build_date: String::from(crate::app_info::BUILD_DATE),
+    }
+}
+
+/// Remediation hints for all known error codes. Served to clients, so that the hints match this version of CARL.
+fn error_catalog() -> Vec<ErrorRemediation> {
+    ErrorCode::ALL.into_iter()
+        .map(|code| {
+            let (summary, remediation) = match code {
+                ErrorCode::CarlUnreachable => (
+                    "CARL could not be reached.",
+                    "Check that CARL is running and that 'network.carl.host' and 'network.carl.port' point to it. If authentication is enabled, check that the OIDC client credentials are still valid.",
+                ),
+                ErrorCode::InvalidRequest => (
+                    "CARL rejected the request as invalid.",
+                    "Check the given arguments. If they are correct, the client may be incompatible with this version of CARL. Run 'opendut-cleo self-update --check'.",
+                ),
+                ErrorCode::Internal => (
+                    "An internal error occurred in CARL.",
+                    "Check the logs of CARL for details. Running 'opendut-cleo check-consistency' or 'opendut-cleo check-compatibility' may reveal inconsistent or incompatible resources.",
+                ),
+                ErrorCode::PeerNotFound => (
+                    "The peer does not exist.",
+                    "Check the PeerID with 'opendut-cleo list peers'.",
+                ),
+                ErrorCode::PeerAlreadyExists => (
+                    "A peer with the same PeerID already exists.",
+                    "Use a new PeerID to create a peer, or check the existing peer with 'opendut-cleo describe peer'.",
+                ),
+                ErrorCode::PeerIllegalState => (
+                    "The peer cannot be changed in its current state.",
+                    "A peer which is part of a deployed cluster cannot be changed. Delete the cluster deployment first with 'opendut-cleo delete cluster-deployment'.",
+                ),
+                ErrorCode::PeerIllegalDevices => (
+                    "The devices of the peer are invalid.",
+                    "Each device may only be registered once. Check the DeviceIDs with 'opendut-cleo list devices'.",
+                ),
+                ErrorCode::PeersUnavailable => (
+                    "Peers of the cluster are offline or already in use.",
+                    "Check with 'opendut-cleo list peers' that all peers of the cluster are connected. For disconnected peers, check that the EDGAR service is running on the peer ('systemctl status opendut-edgar') and can reach CARL. Peers can only be part of one deployed cluster at a time.",
+                ),
+                ErrorCode::PeerUnreachable => (
+                    "CARL is reachable, but the peer did not answer over its connection to CARL.",
+                    "Check that the EDGAR service is running on the peer ('systemctl status opendut-edgar') and that the peer's network allows the connection to CARL. Check 'journalctl -u opendut-edgar' on the peer for connection errors.",
+                ),
+                ErrorCode::PeerCommandUnauthorized => (
+                    "You lack the role required to send this command to peers.",
+                    "Ask your administrator to assign you one of the roles listed in the error, which are configured via the 'peer.command.roles' settings of CARL.",
+                ),
+                ErrorCode::PeerCommandFailed => (
+                    "The peer received the command, but could not execute it.",
+                    "Check the cause in the error message and 'journalctl -u opendut-edgar' on the peer for details.",
+                ),
+                ErrorCode::PolicyViolation => (
+                    "The resource was rejected, as it violates the admission policies configured for CARL.",
+                    "Change the resource as described by the listed violations, e.g. rename it or use an allowed container image. The policies are configured by the operator of CARL in the '[policy]' section of its configuration.",
+                ),
+                ErrorCode::PeerGroupNotFound => (
+                    "The PeerGroup does not exist.",
+                    "Check the PeerGroupID.",
+                ),
+                ErrorCode::PeerGroupReferenced => (
+                    "The PeerGroup is still referenced by cluster configurations.",
+                    "Remove the PeerGroup from the listed cluster configurations or delete them first.",
+                ),
+                ErrorCode::ClusterConfigurationAlreadyExists => (
+                    "A cluster configuration with the same ClusterID already exists.",
+                    "Choose a different ClusterID or delete the existing cluster configuration first.",
+                ),
+                ErrorCode::ClusterConfigurationNotFound => (
+                    "The cluster configuration does not exist.",
+                    "Check the ClusterID with 'opendut-cleo list cluster-configurations'.",
+                ),
+                ErrorCode::ClusterIllegalState => (
+                    "The cluster cannot be changed in its current state.",
+                    "A deployed cluster cannot be changed or deleted. Delete the cluster deployment first with 'opendut-cleo delete cluster-deployment'.",
+                ),
+                ErrorCode::ClusterDeploymentNotFound => (
+                    "The cluster is not deployed.",
+                    "Check the deployed clusters with 'opendut-cleo list cluster-deployments'.",
+                ),
+                ErrorCode::ClusterDependencyInvalid => (
+                    "The dependencies of the cluster configuration reference unknown clusters or form a cycle.",
+                    "Check the ClusterIDs passed via '--depends-on' with 'opendut-cleo list cluster-configurations' and remove the dependency, which closes the cycle.",
+                ),
+                ErrorCode::ClusterReferencedAsDependency => (
+                    "Other cluster configurations depend on the cluster.",
+                    "Remove the dependency from the listed cluster configurations or delete them first.",
+                ),
+                ErrorCode::SnapshotNotFound => (
+                    "The snapshot does not exist.",
+                    "Check the SnapshotID with 'opendut-cleo list snapshots'.",
+                ),
+                ErrorCode::SnapshotNameAlreadyExists => (
+                    "A snapshot with the same name already exists.",
+                    "Choose a different name or delete the existing snapshot first with 'opendut-cleo delete snapshot'.",
+                ),
+                ErrorCode::SnapshotResourcesMissing => (
+                    "The snapshot does not contain the requested resources.",
+                    "Check which resources the snapshot contains with 'opendut-cleo diff snapshots'.",
+                ),
+                ErrorCode::TraceCaptureNotFound => (
+                    "The trace capture does not exist.",
+                    "Check the TraceCaptureID with 'opendut-cleo list trace-captures'. Captures are kept in memory, so they are gone after CARL restarted.",
+                ),
+                ErrorCode::ExecutorNotFound => (
+                    "The peer has no executor with this ID.",
+                    "Check the executors of the peer with 'opendut-cleo list container-executor'.",
+                ),
+                ErrorCode::ExecutorResultsNotConfigured => (
+                    "The executor has no results URL, so its results are not uploaded anywhere.",
+                    "Configure a results URL for the executor with 'opendut-cleo create container-executor --results-url'.",
+                ),
+                ErrorCode::ExecutorResultNotFound => (
+                    "The results storage of the executor does not contain a file with this name.",
+                    "Check the available files with 'opendut-cleo results list'.",
+                ),
+                ErrorCode::ExecutorResultsUnavailable => (
+                    "CARL could not access the results storage of the executor.",
+                    "Check that the results storage is reachable from CARL and that the token configured in CARL's 'results.storage.token' is valid.",
+                ),
+                ErrorCode::MaintenanceOperationNotFound => (
+                    "The maintenance operation does not exist.",
+                    "Check the operation ID printed by 'opendut-cleo peer rolling-restart'. Operations are kept in memory, so they are gone after CARL restarted.",
+                ),
+                ErrorCode::ProvisioningTokenInvalid => (
+                    "The provisioning token does not exist or its secret does not match.",
+                    "Check that the provisioning string flashed onto the device was copied completely. Existing tokens can be listed with 'opendut-cleo list provisioning-tokens'.",
+                ),
+                ErrorCode::ProvisioningTokenClaimed => (
+                    "The provisioning token was already used by another device.",
+                    "Each provisioning token registers exactly one peer. Generate further tokens with 'opendut-cleo generate-provisioning-tokens' and flash a new one onto the device.",
+                ),
+                ErrorCode::ProjectNameAlreadyExists => (
+                    "A different project with the same name already exists.",
+                    "Choose a different name, or update the existing project by passing its ID with 'opendut-cleo create project --id'.",
+                ),
+                ErrorCode::ProjectNotFound => (
+                    "The project does not exist or you are not a member of it.",
+                    "Check the ProjectID with 'opendut-cleo list projects'.",
+                ),
+                ErrorCode::ServiceAccountNameAlreadyExists => (
+                    "A service account with the same name already exists.",
+                    "Choose a different name, or issue a further token for the existing service account with 'opendut-cleo create service-account-token'.",
+                ),
+                ErrorCode::ServiceAccountNotFound => (
+                    "The service account does not exist.",
+                    "Check the ServiceAccountID with 'opendut-cleo list service-accounts'.",
+                ),
+                ErrorCode::ServiceAccountTokenNotFound => (
+                    "The service account does not have a token with this ID.",
+                    "Check the tokens of the service account with 'opendut-cleo list service-accounts'.",
+                ),
+                ErrorCode::SelfTestReportNotFound => (
+                    "The peer did not upload a self-test report since CARL started.",
+                    "Run 'opendut-edgar self-test' on the peer. Reports are kept in memory, so they are gone after CARL restarted.",
+                ),
+            };
+            ErrorRemediation {
+                code: code.value().to_owned(),
+                summary: summary.to_owned(),
+                remediation: remediation.to_owned(),
+            }
+        })
+        .collect()
 }
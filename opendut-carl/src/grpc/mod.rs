@@ -1,14 +1,28 @@
 use std::fmt::Display;
 
+pub use bootstrap::BootstrapFacade;
 pub use cluster_manager::ClusterManagerFacade;
+pub use diagnostics::DiagnosticsFacade;
 pub use metadata_provider::MetadataProviderFacade;
 pub use peer_manager::PeerManagerFacade;
 pub use peer_messaging_broker::PeerMessagingBrokerFacade;
+pub use project_manager::ProjectManagerFacade;
+pub use resource_observer::ResourceObserverFacade;
+pub use results_manager::ResultsManagerFacade;
+pub use service_account_manager::ServiceAccountManagerFacade;
+pub use snapshot_manager::SnapshotManagerFacade;
 
+mod bootstrap;
 mod cluster_manager;
+mod diagnostics;
 mod peer_manager;
 mod peer_messaging_broker;
 mod metadata_provider;
+mod project_manager;
+mod resource_observer;
+mod results_manager;
+mod service_account_manager;
+mod snapshot_manager;
 
 pub trait ExtractOrInvalidArgument<A, B>
 where
@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 use pem::Pem;
 
 use tonic::{Request, Response, Status};
@@ -7,48 +9,86 @@ use tracing::trace;
 use url::Url;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_auth::registration::resources::UserId;
-use opendut_carl_api::carl::peer::{GetPeerDescriptorError, GetPeerStateError};
+use opendut_carl_api::carl::peer::{GetPeerDescriptorError, GetPeerStateError, MaintenanceOperationId, PeerCommand, RollingRestartSpec};
 use opendut_carl_api::proto::services::peer_manager;
 use opendut_carl_api::proto::services::peer_manager::*;
 use opendut_carl_api::proto::services::peer_manager::peer_manager_server::{PeerManager as PeerManagerService, PeerManagerServer};
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+use opendut_types::peer::label::{AnnotationChange, LabelChange, LabelSelector};
+use opendut_types::peer::provisioning::{PeerTemplate, ProvisioningTokenId};
 use opendut_types::cleo::{CleoId};
+use opendut_types::cluster::ClusterId;
+use opendut_types::topology::DeviceId;
+use opendut_types::project::ProjectId;
 
 use crate::actions;
-use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, GetPeerStateParams, ListDevicesParams, ListPeerDescriptorsParams, StorePeerDescriptorParams};
+use crate::actions::{AssignToProjectParams, ProjectOptions, ProjectResourceId, UnassignFromProjectsParams, ClaimProvisioningTokenParams, CreatePeerInClusterParams, DeleteHierarchyNodeParams, DeletePeerDescriptorParams, EditPeerLabelsParams, DeletePeerGroupParams, GetHierarchyHealthParams, ListHierarchyNodesParams, StoreHierarchyNodeParams, GenerateCleoSetupParams, GeneratePeerSetupParams, GenerateProvisioningTokensParams, PeerSetupOptions, RevokePeerSetupParams, GetPeerStateParams, ListDevicesParams, ListPeerDescriptorsParams, ListPeerGroupsParams, ListProvisioningTokensParams, PingPeerParams, PreviewPeerGroupChangesParams, SendPeerCommandParams, StartRollingRestartParams, GetMaintenanceOperationParams, StorePeerDescriptorParams, StorePeerGroupParams};
+use crate::auth::permission::{Permission, PermissionAuthorization};
+use crate::auth::project::ProjectAccess;
 use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::peer::command::{CommandRequester, PeerCommandAuthorization};
+use crate::peer::maintenance::{MaintenanceOperations, MaintenanceOperationsRef};
+use crate::policy::PolicyEngineRef;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 
 pub struct PeerManagerFacade {
     resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    policy_engine: PolicyEngineRef,
+    peer_command_authorization: PeerCommandAuthorization,
+    authorization: PermissionAuthorization,
+    project_options: ProjectOptions,
+    maintenance_operations: MaintenanceOperationsRef,
     vpn: Vpn,
     carl_url: Url,
     ca: Pem,
     oidc_registration_client: Option<RegistrationClientRef>,
+    setup_options: PeerSetupOptions,
 }
 
 impl PeerManagerFacade {
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         resources_manager: ResourcesManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
+        policy_engine: PolicyEngineRef,
+        peer_command_authorization: PeerCommandAuthorization,
+        authorization: PermissionAuthorization,
+        project_options: ProjectOptions,
         vpn: Vpn,
         carl_url: Url,
         ca: Pem,
         oidc_registration_client: Option<RegistrationClientRef>,
+        setup_options: PeerSetupOptions,
     ) -> Self {
         PeerManagerFacade {
             resources_manager,
+            peer_messaging_broker,
+            policy_engine,
+            peer_command_authorization,
+            authorization,
+            project_options,
+            maintenance_operations: MaintenanceOperations::new(),
             vpn,
             carl_url,
             ca,
             oidc_registration_client,
+            setup_options,
         }
     }
 
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerManagerServer<Self>> {
         tonic_web::enable(PeerManagerServer::new(self))
     }
+
+    async fn project_access<T>(&self, request: &Request<T>) -> Result<ProjectAccess, Status> {
+        ProjectAccess::of(request, &self.resources_manager, &self.project_options).await
+    }
 }
 
 #[tonic::async_trait]
@@ -57,17 +97,38 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn store_peer_descriptor(&self, request: Request<StorePeerDescriptorRequest>) -> Result<Response<StorePeerDescriptorResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let peer_descriptor: PeerDescriptor = extract!(request.peer)?;
+        let project_id = request.project_id
+            .map(ProjectId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.project_id' is not valid: {cause}")))?;
+
+        access.authorize_peer(peer_descriptor.id)?;
+        if let Some(project_id) = project_id {
+            access.project(project_id, &self.resources_manager).await?;
+        }
 
         trace!("Received request to store peer descriptor: {peer_descriptor:?}");
 
         let result = actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&self.resources_manager),
+            policy_engine: Arc::clone(&self.policy_engine),
             vpn: Clone::clone(&self.vpn),
             peer_descriptor: Clone::clone(&peer_descriptor),
         }).await;
 
+        if let (Ok(peer_id), Some(project_id)) = (&result, project_id) {
+            actions::assign_to_project(AssignToProjectParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                project_id,
+                resource: ProjectResourceId::Peer(*peer_id),
+            }).await
+            .map_err(|cause| Status::internal(cause.to_string()))?;
+        }
+
         match result {
             Err(error) => {
                 Ok(Response::new(StorePeerDescriptorResponse {
@@ -86,11 +147,58 @@ impl PeerManagerService for PeerManagerFacade {
         }
     }
 
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_peer_in_cluster(&self, request: Request<CreatePeerInClusterRequest>) -> Result<Response<CreatePeerInClusterResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let peer_descriptor: PeerDescriptor = extract!(request.peer)?;
+        let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        access.authorize_peer(peer_descriptor.id)?;
+        access.authorize_cluster(cluster_id)?;
+        let devices = request.devices.into_iter()
+            .map(DeviceId::try_from)
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.devices' is not valid: {cause}")))?;
+
+        trace!("Received request to create peer descriptor in cluster <{cluster_id}>: {peer_descriptor:?}");
+
+        let result = actions::create_peer_in_cluster(CreatePeerInClusterParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            policy_engine: Arc::clone(&self.policy_engine),
+            vpn: Clone::clone(&self.vpn),
+            peer_descriptor,
+            cluster_id,
+            devices,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreatePeerInClusterResponse {
+                    reply: Some(create_peer_in_cluster_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_id) => {
+                Ok(Response::new(CreatePeerInClusterResponse {
+                    reply: Some(create_peer_in_cluster_response::Reply::Success(
+                        CreatePeerInClusterSuccess {
+                            peer_id: Some(peer_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_peer_descriptor(&self, request: Request<DeletePeerDescriptorRequest>) -> Result<Response<DeletePeerDescriptorResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
 
         trace!("Received request to delete peer descriptor for peer <{peer_id}>.");
 
@@ -102,6 +210,14 @@ impl PeerManagerService for PeerManagerFacade {
                 oidc_registration_client: self.oidc_registration_client.clone(),
             }).await;
 
+        if result.is_ok() {
+            // failures are logged by the action and a remaining assignment only refers to a peer, which does not exist anymore
+            let _ignore_error = actions::unassign_from_projects(UnassignFromProjectsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                resource: ProjectResourceId::Peer(peer_id),
+            }).await;
+        }
+
         match result {
             Err(error) => {
                 Ok(Response::new(DeletePeerDescriptorResponse {
@@ -123,8 +239,11 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_peer_descriptor(&self, request: Request<GetPeerDescriptorRequest>) -> Result<Response<GetPeerDescriptorResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
 
         trace!("Received request to get peer descriptor for peer <{peer_id}>.");
 
@@ -158,7 +277,19 @@ impl PeerManagerService for PeerManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_peer_descriptors(&self, _: Request<ListPeerDescriptorsRequest>) -> Result<Response<ListPeerDescriptorsResponse>, Status> {
+    async fn list_peer_descriptors(&self, request: Request<ListPeerDescriptorsRequest>) -> Result<Response<ListPeerDescriptorsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let project = match request.project_id {
+            Some(project_id) => {
+                let project_id = ProjectId::try_from(project_id)
+                    .map_err(|cause| Status::invalid_argument(format!("Field 'request.project_id' is not valid: {cause}")))?;
+                Some(access.project(project_id, &self.resources_manager).await?)
+            }
+            None => None,
+        };
 
         trace!("Received request to list peer descriptors.");
 
@@ -167,6 +298,8 @@ impl PeerManagerService for PeerManagerFacade {
                 resources_manager: Arc::clone(&self.resources_manager),
             }).await
             .map(|peers| peers.into_iter()
+                .filter(|peer| access.may_access_peer(&peer.id))
+                .filter(|peer| project.as_ref().map_or(true, |project| project.peers.contains(&peer.id)))
                 .map(From::from)
                 .collect::<Vec<_>>()
             );
@@ -192,8 +325,11 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_peer_state(&self, request: Request<GetPeerStateRequest>) -> Result<Response<GetPeerStateResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
 
         trace!("Received request to get peer state for peer <{peer_id}>.");
 
@@ -223,8 +359,9 @@ impl PeerManagerService for PeerManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_devices(&self, _: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
+    async fn list_devices(&self, request: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
 
+        self.authorization.authorize(&request, Permission::PeerRead)?;
         trace!("Received request to list devices.");
 
         let devices = actions::list_devices(ListDevicesParams {
@@ -240,10 +377,13 @@ impl PeerManagerService for PeerManagerFacade {
 
     #[tracing::instrument(skip_all, level="trace")]
     async fn generate_peer_setup(&self, request: Request<GeneratePeerSetupRequest>) -> Result<Response<GeneratePeerSetupResponse>, Status> { // TODO: Refactor error types.
+        self.authorization.authorize(&request, Permission::PeerSetupStringGenerate)?;
         trace!("Received request to generate peer setup.");
 
+        let access = self.project_access(&request).await?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer)?;
+        access.authorize_peer(peer_id)?;
         let user_id = UserId { value: request.user_id };
 
         let setup = actions::generate_peer_setup(GeneratePeerSetupParams {
@@ -254,6 +394,7 @@ impl PeerManagerService for PeerManagerFacade {
             vpn: Clone::clone(&self.vpn),
             oidc_registration_client: self.oidc_registration_client.clone(),
             user_id,
+            setup_options: Clone::clone(&self.setup_options),
         }).await.map_err(|cause| Status::internal(format!("Peer setup could not be created: {}", cause)))?;
 
         let response = peer_manager::generate_peer_setup_response::Reply::Success(peer_manager::GeneratePeerSetupSuccess {
@@ -264,7 +405,45 @@ impl PeerManagerService for PeerManagerFacade {
         Ok(Response::new(GeneratePeerSetupResponse { reply: Some(response) }))
     }
 
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn revoke_peer_setup(&self, request: Request<RevokePeerSetupRequest>) -> Result<Response<RevokePeerSetupResponse>, Status> {
+        self.authorization.authorize(&request, Permission::PeerSetupStringGenerate)?;
+        let access = self.project_access(&request).await?;
+        let requester = CommandRequester::of(&request);
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
+
+        trace!("Received request to revoke the setup of peer <{peer_id}>.");
+
+        let result = actions::revoke_peer_setup(RevokePeerSetupParams {
+            peer_id,
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            oidc_registration_client: self.oidc_registration_client.clone(),
+            user_id: requester.map(|requester| requester.name).unwrap_or_else(|| String::from("unknown")),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokePeerSetupResponse {
+                    reply: Some(revoke_peer_setup_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_id) => {
+                Ok(Response::new(RevokePeerSetupResponse {
+                    reply: Some(revoke_peer_setup_response::Reply::Success(
+                        RevokePeerSetupSuccess {
+                            peer_id: Some(peer_id.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     async fn generate_cleo_setup(&self, request: Request<GenerateCleoSetupRequest>) -> Result<Response<GenerateCleoSetupResponse>, Status> {
+        self.authorization.authorize(&request, Permission::CleoSetupStringGenerate)?;
         trace!("Received request to generate CLEO Setup information.");
 
         let request = request.into_inner();
@@ -285,6 +464,585 @@ impl PeerManagerService for PeerManagerFacade {
 
         Ok(Response::new(GenerateCleoSetupResponse { reply: Some(response) }))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn store_peer_group(&self, request: Request<StorePeerGroupRequest>) -> Result<Response<StorePeerGroupResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let request = request.into_inner();
+        let peer_group: PeerGroup = extract!(request.peer_group)?;
+
+        trace!("Received request to store peer group: {peer_group:?}");
+
+        let result = actions::store_peer_group(StorePeerGroupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_group,
+            propagate_to_deployed_clusters: request.propagate_to_deployed_clusters,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StorePeerGroupResponse {
+                    reply: Some(store_peer_group_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(outcome) => {
+                Ok(Response::new(StorePeerGroupResponse {
+                    reply: Some(store_peer_group_response::Reply::Success(
+                        StorePeerGroupSuccess {
+                            peer_group_id: Some(outcome.peer_group_id.into()),
+                            applied_changes: outcome.applied_changes.into_iter().map(From::from).collect(),
+                            skipped_changes: outcome.skipped_changes.into_iter().map(From::from).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn preview_peer_group_changes(&self, request: Request<PreviewPeerGroupChangesRequest>) -> Result<Response<PreviewPeerGroupChangesResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        let request = request.into_inner();
+        let peer_group: PeerGroup = extract!(request.peer_group)?;
+
+        trace!("Received request to preview changes of peer group: {peer_group:?}");
+
+        let result = actions::preview_peer_group_changes(PreviewPeerGroupChangesParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_group,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(PreviewPeerGroupChangesResponse {
+                    reply: Some(preview_peer_group_changes_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(changes) => {
+                Ok(Response::new(PreviewPeerGroupChangesResponse {
+                    reply: Some(preview_peer_group_changes_response::Reply::Success(
+                        PreviewPeerGroupChangesSuccess {
+                            changes: changes.into_iter().map(From::from).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_peer_group(&self, request: Request<DeletePeerGroupRequest>) -> Result<Response<DeletePeerGroupResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let request = request.into_inner();
+        let peer_group_id: PeerGroupId = extract!(request.peer_group_id)?;
+
+        trace!("Received request to delete peer group <{peer_group_id}>.");
+
+        let result = actions::delete_peer_group(DeletePeerGroupParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_group_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeletePeerGroupResponse {
+                    reply: Some(delete_peer_group_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_group) => {
+                Ok(Response::new(DeletePeerGroupResponse {
+                    reply: Some(delete_peer_group_response::Reply::Success(
+                        DeletePeerGroupSuccess {
+                            peer_group_id: Some(peer_group.id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_peer_groups(&self, request: Request<ListPeerGroupsRequest>) -> Result<Response<ListPeerGroupsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        trace!("Received request to list peer groups.");
+
+        let result =
+            actions::list_peer_groups(ListPeerGroupsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|peer_groups| peer_groups.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListPeerGroupsResponse {
+                    reply: Some(list_peer_groups_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_groups) => {
+                Ok(Response::new(ListPeerGroupsResponse {
+                    reply: Some(list_peer_groups_response::Reply::Success(
+                        ListPeerGroupsSuccess {
+                            peer_groups
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn store_hierarchy_node(&self, request: Request<StoreHierarchyNodeRequest>) -> Result<Response<StoreHierarchyNodeResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let request = request.into_inner();
+        let node: HierarchyNode = extract!(request.node)?;
+
+        trace!("Received request to store hierarchy node: {node:?}");
+
+        let result = actions::store_hierarchy_node(StoreHierarchyNodeParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            node,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StoreHierarchyNodeResponse {
+                    reply: Some(store_hierarchy_node_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(node_id) => {
+                Ok(Response::new(StoreHierarchyNodeResponse {
+                    reply: Some(store_hierarchy_node_response::Reply::Success(
+                        StoreHierarchyNodeSuccess {
+                            node_id: Some(node_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_hierarchy_node(&self, request: Request<DeleteHierarchyNodeRequest>) -> Result<Response<DeleteHierarchyNodeResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let request = request.into_inner();
+        let node_id: HierarchyNodeId = extract!(request.node_id)?;
+
+        trace!("Received request to delete hierarchy node <{node_id}>.");
+
+        let result = actions::delete_hierarchy_node(DeleteHierarchyNodeParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            node_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteHierarchyNodeResponse {
+                    reply: Some(delete_hierarchy_node_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(node) => {
+                Ok(Response::new(DeleteHierarchyNodeResponse {
+                    reply: Some(delete_hierarchy_node_response::Reply::Success(
+                        DeleteHierarchyNodeSuccess {
+                            node_id: Some(node.id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_hierarchy_nodes(&self, request: Request<ListHierarchyNodesRequest>) -> Result<Response<ListHierarchyNodesResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        trace!("Received request to list hierarchy nodes.");
+
+        let result =
+            actions::list_hierarchy_nodes(ListHierarchyNodesParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|nodes| nodes.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListHierarchyNodesResponse {
+                    reply: Some(list_hierarchy_nodes_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(nodes) => {
+                Ok(Response::new(ListHierarchyNodesResponse {
+                    reply: Some(list_hierarchy_nodes_response::Reply::Success(
+                        ListHierarchyNodesSuccess {
+                            nodes
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_hierarchy_health(&self, request: Request<GetHierarchyHealthRequest>) -> Result<Response<GetHierarchyHealthResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        trace!("Received request to get the health of the hierarchy.");
+
+        let result =
+            actions::get_hierarchy_health(GetHierarchyHealthParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|nodes| nodes.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetHierarchyHealthResponse {
+                    reply: Some(get_hierarchy_health_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(nodes) => {
+                Ok(Response::new(GetHierarchyHealthResponse {
+                    reply: Some(get_hierarchy_health_response::Reply::Success(
+                        GetHierarchyHealthSuccess {
+                            nodes
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn edit_peer_labels(&self, request: Request<EditPeerLabelsRequest>) -> Result<Response<EditPeerLabelsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerWrite)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let selector: LabelSelector = extract!(request.selector)?;
+        let label_changes = request.label_changes.into_iter()
+            .map(LabelChange::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.label_changes' is not valid: {cause}")))?;
+        let annotation_changes = request.annotation_changes.into_iter()
+            .map(AnnotationChange::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.annotation_changes' is not valid: {cause}")))?;
+
+        trace!("Received request to edit labels of peers matching selector '{selector}'.");
+
+        if let ProjectAccess::Restricted { .. } = access {
+            let matching_peers = actions::edit_peer_labels(EditPeerLabelsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                selector: Clone::clone(&selector),
+                label_changes: Clone::clone(&label_changes),
+                annotation_changes: Clone::clone(&annotation_changes),
+                preview: true,
+            }).await
+            .unwrap_or_default();
+            for peer in matching_peers {
+                access.authorize_peer(peer.peer_id)?;
+            }
+        }
+
+        let result = actions::edit_peer_labels(EditPeerLabelsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            selector,
+            label_changes,
+            annotation_changes,
+            preview: request.preview,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(EditPeerLabelsResponse {
+                    reply: Some(edit_peer_labels_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peers) => {
+                Ok(Response::new(EditPeerLabelsResponse {
+                    reply: Some(edit_peer_labels_response::Reply::Success(
+                        EditPeerLabelsSuccess {
+                            peers: peers.into_iter().map(From::from).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn ping_peer(&self, request: Request<PingPeerRequest>) -> Result<Response<PingPeerResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerCommand)?;
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
+        let timeout = Duration::from_millis(request.timeout_ms);
+
+        trace!("Received request to ping peer <{peer_id}>.");
+
+        let result = actions::ping_peer(PingPeerParams {
+            peer_id,
+            timeout,
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(PingPeerResponse {
+                    reply: Some(ping_peer_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(ping) => {
+                Ok(Response::new(PingPeerResponse {
+                    reply: Some(ping_peer_response::Reply::Success(ping.into()))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn send_peer_command(&self, request: Request<SendPeerCommandRequest>) -> Result<Response<SendPeerCommandResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerCommand)?;
+        let requester = CommandRequester::of(&request);
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        access.authorize_peer(peer_id)?;
+        let command: PeerCommand = extract!(request.command)?;
+        let timeout = Duration::from_millis(request.timeout_ms);
+
+        trace!("Received request to send command '{command}' to peer <{peer_id}>.");
+
+        let result = actions::send_peer_command(SendPeerCommandParams {
+            peer_id,
+            command,
+            timeout,
+            requester,
+            authorization: Clone::clone(&self.peer_command_authorization),
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(SendPeerCommandResponse {
+                    reply: Some(send_peer_command_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(result) => {
+                Ok(Response::new(SendPeerCommandResponse {
+                    reply: Some(send_peer_command_response::Reply::Success(result.into()))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn start_rolling_restart(&self, request: Request<StartRollingRestartRequest>) -> Result<Response<StartRollingRestartResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerCommand)?;
+        let requester = CommandRequester::of(&request);
+        let access = self.project_access(&request).await?;
+        let request = request.into_inner();
+        let peers = request.peers.into_iter()
+            .map(PeerId::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.peers' is not valid: {cause}")))?;
+        for peer_id in &peers {
+            access.authorize_peer(*peer_id)?;
+        }
+        let command: PeerCommand = extract!(request.command)?;
+
+        trace!("Received request to start rolling restart with command '{command}' of {} peers.", peers.len());
+
+        let result = actions::start_rolling_restart(StartRollingRestartParams {
+            spec: RollingRestartSpec {
+                peers,
+                command,
+                max_unavailable_per_cluster: request.max_unavailable_per_cluster,
+                peer_timeout: Duration::from_millis(request.peer_timeout_ms),
+            },
+            requester,
+            authorization: Clone::clone(&self.peer_command_authorization),
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            maintenance_operations: Arc::clone(&self.maintenance_operations),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StartRollingRestartResponse {
+                    reply: Some(start_rolling_restart_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(operation) => {
+                Ok(Response::new(StartRollingRestartResponse {
+                    reply: Some(start_rolling_restart_response::Reply::Success(
+                        StartRollingRestartSuccess {
+                            operation: Some(operation.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_maintenance_operation(&self, request: Request<GetMaintenanceOperationRequest>) -> Result<Response<GetMaintenanceOperationResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerRead)?;
+        let request = request.into_inner();
+        let operation_id: MaintenanceOperationId = extract!(request.operation_id)?;
+
+        trace!("Received request to get maintenance operation <{operation_id}>.");
+
+        let result = actions::get_maintenance_operation(GetMaintenanceOperationParams {
+            operation_id,
+            maintenance_operations: Arc::clone(&self.maintenance_operations),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetMaintenanceOperationResponse {
+                    reply: Some(get_maintenance_operation_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(operation) => {
+                Ok(Response::new(GetMaintenanceOperationResponse {
+                    reply: Some(get_maintenance_operation_response::Reply::Success(
+                        GetMaintenanceOperationSuccess {
+                            operation: Some(operation.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn generate_provisioning_tokens(&self, request: Request<GenerateProvisioningTokensRequest>) -> Result<Response<GenerateProvisioningTokensResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerSetupStringGenerate)?;
+        let request = request.into_inner();
+        let template: PeerTemplate = extract!(request.template)?;
+
+        trace!("Received request to generate {} provisioning tokens for pool '{}'.", request.count, request.pool);
+
+        let result = actions::generate_provisioning_tokens(GenerateProvisioningTokensParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            pool: request.pool,
+            count: request.count,
+            template,
+            carl_url: Clone::clone(&self.carl_url),
+            ca: Clone::clone(&self.ca),
+            user_id: request.user_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GenerateProvisioningTokensResponse {
+                    reply: Some(generate_provisioning_tokens_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(provisionings) => {
+                Ok(Response::new(GenerateProvisioningTokensResponse {
+                    reply: Some(generate_provisioning_tokens_response::Reply::Success(
+                        GenerateProvisioningTokensSuccess {
+                            provisionings: provisionings.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_provisioning_tokens(&self, request: Request<ListProvisioningTokensRequest>) -> Result<Response<ListProvisioningTokensResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::PeerSetupStringGenerate)?;
+        trace!("Received request to list provisioning tokens.");
+
+        let result = actions::list_provisioning_tokens(ListProvisioningTokensParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListProvisioningTokensResponse {
+                    reply: Some(list_provisioning_tokens_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(tokens) => {
+                Ok(Response::new(ListProvisioningTokensResponse {
+                    reply: Some(list_provisioning_tokens_response::Reply::Success(
+                        ListProvisioningTokensSuccess {
+                            tokens: tokens.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    /// Called by devices on first contact, before they have credentials. The secret of the token authenticates the request instead.
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn claim_provisioning_token(&self, request: Request<ClaimProvisioningTokenRequest>) -> Result<Response<ClaimProvisioningTokenResponse>, Status> {
+
+        let request = request.into_inner();
+        let token_id: ProvisioningTokenId = extract!(request.token)?;
+
+        trace!("Received request to claim provisioning token <{token_id}>.");
+
+        let result = actions::claim_provisioning_token(ClaimProvisioningTokenParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            policy_engine: Arc::clone(&self.policy_engine),
+            vpn: Clone::clone(&self.vpn),
+            carl_url: Clone::clone(&self.carl_url),
+            ca: Clone::clone(&self.ca),
+            oidc_registration_client: self.oidc_registration_client.clone(),
+            setup_options: Clone::clone(&self.setup_options),
+            token_id,
+            secret: request.secret,
+            hostname: request.hostname,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ClaimProvisioningTokenResponse {
+                    reply: Some(claim_provisioning_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok((peer_id, setup)) => {
+                Ok(Response::new(ClaimProvisioningTokenResponse {
+                    reply: Some(claim_provisioning_token_response::Reply::Success(
+                        ClaimProvisioningTokenSuccess {
+                            peer_id: Some(peer_id.into()),
+                            setup: Some(setup.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -309,6 +1067,7 @@ mod tests {
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
     use opendut_auth_tests::registration_client;
 
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
     use crate::resources::manager::ResourcesManager;
     use crate::vpn::Vpn;
 
@@ -328,12 +1087,22 @@ mod tests {
     async fn test_successful_create_delete(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        });
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            Default::default(),
         );
 
         let peer_id = PeerId::random();
@@ -366,16 +1135,25 @@ mod tests {
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            secrets: vec![],
+                            can_gateways: vec![],
+                            network_attachment: Default::default(),
                         },
                         results_url: None,
+                        sandbox: Default::default(),
                     }
                 ],
             },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
 
         let create_peer_reply = testee.store_peer_descriptor(Request::new(
             StorePeerDescriptorRequest {
                 peer: Some(Clone::clone(&peer_descriptor).into()),
+                project_id: None,
             }
         )).await?;
 
@@ -391,7 +1169,7 @@ mod tests {
         )?;
 
         let list_reply = testee.list_peer_descriptors(Request::new(
-            ListPeerDescriptorsRequest {}
+            ListPeerDescriptorsRequest { project_id: None }
         )).await?;
 
         verify_that!(
@@ -410,7 +1188,7 @@ mod tests {
         )).await?;
 
         let list_reply = testee.list_peer_descriptors(Request::new(
-            peer_manager::ListPeerDescriptorsRequest {}
+            peer_manager::ListPeerDescriptorsRequest { project_id: None }
         )).await?;
 
         verify_that!(list_reply.get_ref().reply,
@@ -429,17 +1207,28 @@ mod tests {
     async fn register_fails_when_no_id_specified(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        });
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            Default::default(),
         );
 
         let create_peer_reply = testee.store_peer_descriptor(Request::new(
             StorePeerDescriptorRequest {
-                peer: None
+                peer: None,
+                project_id: None,
             }
         )).await;
 
@@ -449,7 +1238,7 @@ mod tests {
         )?;
 
         let list_reply = testee.list_peer_descriptors(Request::new(
-            peer_manager::ListPeerDescriptorsRequest {}
+            peer_manager::ListPeerDescriptorsRequest { project_id: None }
         )).await?;
 
         verify_that!(
@@ -469,12 +1258,22 @@ mod tests {
     async fn unregister_fails_when_no_id_specified(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resources_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        });
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            Default::default(),
         );
 
         let delete_peer_reply = testee.delete_peer_descriptor(Request::new(
@@ -489,7 +1288,7 @@ mod tests {
         )?;
 
         let list_reply = testee.list_peer_descriptors(Request::new(
-            peer_manager::ListPeerDescriptorsRequest {}
+            peer_manager::ListPeerDescriptorsRequest { project_id: None }
         )).await?;
 
         verify_that!(
@@ -2,6 +2,7 @@ use std::net::IpAddr;
 use std::ops::Not;
 use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use futures::StreamExt;
 use tokio_stream::Stream;
@@ -12,19 +13,28 @@ use tonic_web::CorsGrpcWeb;
 use tracing::{error, trace, warn};
 use uuid::Uuid;
 
-use opendut_carl_api::proto::services::peer_messaging_broker::{Downstream, Upstream};
+use opendut_carl_api::proto::services::peer_messaging_broker::{get_executor_secrets_response, Downstream, GetExecutorSecretsRequest, GetExecutorSecretsResponse, GetExecutorSecretsSuccess, Upstream};
+use opendut_carl_api::proto::services::peer_messaging_broker::{upload_self_test_report_response, UploadSelfTestReportRequest, UploadSelfTestReportResponse, UploadSelfTestReportSuccess};
 use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_broker_server::PeerMessagingBrokerServer;
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::self_test::SelfTestReport;
 use opendut_types::peer::PeerId;
-use crate::peer::broker::{OpenError, PeerMessagingBrokerRef};
+use crate::actions;
+use crate::actions::{ExecutorSecretsOptions, GetExecutorSecretsParams, UploadSelfTestReportParams};
+use crate::grpc::extract;
+use crate::peer::broker::{OpenError, PeerConnection, PeerMessagingBrokerRef};
+use crate::resources::manager::ResourcesManagerRef;
 
 pub struct PeerMessagingBrokerFacade {
     peer_messaging_broker: PeerMessagingBrokerRef,
+    resources_manager: ResourcesManagerRef,
+    executor_secrets_options: ExecutorSecretsOptions,
 }
 
 impl PeerMessagingBrokerFacade {
-    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef) -> Self {
-        Self { peer_messaging_broker }
+    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef, resources_manager: ResourcesManagerRef, executor_secrets_options: ExecutorSecretsOptions) -> Self {
+        Self { peer_messaging_broker, resources_manager, executor_secrets_options }
     }
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerMessagingBrokerServer<Self>> {
         tonic_web::enable(PeerMessagingBrokerServer::new(self))
@@ -51,10 +61,16 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                 Status::invalid_argument(message)
             })?;
 
+        let connection = PeerConnection {
+            remote_address: request.remote_addr(),
+            version: extract_version(request.metadata()),
+        };
 
-        let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host).await
+        let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host, connection).await
             .map_err(|cause| match cause {
                 OpenError::PeerAlreadyConnected { .. } => Status::aborted(cause.to_string()),
+                OpenError::ShuttingDown { .. } => Status::unavailable(cause.to_string()),
+                OpenError::SetupRevoked { .. } | OpenError::SetupExpired { .. } => Status::permission_denied(cause.to_string()),
                 OpenError::SendApplyPeerConfiguration { .. } => Status::unavailable(cause.to_string()),
                 OpenError::Persistence { .. } => Status::internal(cause.to_string()),
             })?;
@@ -68,7 +84,10 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                             if matches!(message, upstream::Message::Ping(_)).not() {
                                 trace!("Received message from client <{}>: {:?}", peer_id, message);
                             }
-                            tx_inbound.send(message).await.unwrap();
+                            if tx_inbound.send(message).await.is_err() {
+                                trace!("Stream of client <{}> was closed by CARL. Dropping further messages.", peer_id);
+                                break;
+                            }
                         } else {
                             warn!("Ignoring empty message from client <{}>: {:?}", peer_id, upstream);
                         }
@@ -87,6 +106,68 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
 
         Ok(Response::new(Box::pin(outbound)))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_executor_secrets(&self, request: Request<GetExecutorSecretsRequest>) -> Result<Response<GetExecutorSecretsResponse>, Status> {
+
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+
+        trace!("Received request to get secrets of executor <{executor_id}> for peer <{peer_id}>.");
+
+        let result = actions::get_executor_secrets(GetExecutorSecretsParams {
+            peer_id,
+            executor_id,
+            resources_manager: Arc::clone(&self.resources_manager),
+            options: Clone::clone(&self.executor_secrets_options),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetExecutorSecretsResponse {
+                    reply: Some(get_executor_secrets_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(secrets) => {
+                Ok(Response::new(GetExecutorSecretsResponse {
+                    reply: Some(get_executor_secrets_response::Reply::Success(
+                        GetExecutorSecretsSuccess {
+                            secrets: secrets.into_iter().map(From::from).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn upload_self_test_report(&self, request: Request<UploadSelfTestReportRequest>) -> Result<Response<UploadSelfTestReportResponse>, Status> {
+
+        let request = request.into_inner();
+        let report: SelfTestReport = extract!(request.report)?;
+
+        trace!("Received self-test report of peer <{}>.", report.peer_id);
+
+        let result = actions::upload_self_test_report(UploadSelfTestReportParams {
+            report,
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(UploadSelfTestReportResponse {
+                    reply: Some(upload_self_test_report_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(()) => {
+                Ok(Response::new(UploadSelfTestReportResponse {
+                    reply: Some(upload_self_test_report_response::Reply::Success(UploadSelfTestReportSuccess {}))
+                }))
+            }
+        }
+    }
 }
 
 
@@ -115,5 +196,13 @@ fn extract_remote_host(metadata: &MetadataMap) -> Result<IpAddr, UserError> {
     Ok(remote_host)
 }
 
+/// Version of EDGAR, which is not sent by older versions.
+fn extract_version(metadata: &MetadataMap) -> Option<String> {
+    metadata
+        .get("version")
+        .and_then(|version| version.to_str().ok())
+        .map(ToString::to_string)
+}
+
 
 type UserError = String;
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::project_manager::*;
+use opendut_carl_api::proto::services::project_manager::project_manager_server::{ProjectManager as ProjectManagerService, ProjectManagerServer};
+use opendut_types::project;
+
+use crate::actions;
+use crate::actions::{DeleteProjectParams, ListProjectsParams, ProjectOptions, StoreProjectParams};
+use crate::auth::project::ProjectAccess;
+use crate::auth::CurrentUser;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ProjectManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    options: ProjectOptions,
+}
+
+impl ProjectManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, options: ProjectOptions) -> Self {
+        Self {
+            resources_manager,
+            options,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ProjectManagerServer<Self>> {
+        tonic_web::enable(ProjectManagerServer::new(self))
+    }
+
+    /// Only users with one of the configured admin roles may manage projects. Service accounts are already rejected by the authentication layer.
+    /// Without a user, authentication is disabled and everyone may manage projects.
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let Some(user) = request.extensions().get::<CurrentUser>() else {
+            return Ok(());
+        };
+        if self.options.is_admin(&user.claims.additional_claims().roles) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!(
+                "CARL says, managing projects requires one of the roles: {}",
+                self.options.admin_roles.join(", ")
+            )))
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ProjectManagerService for ProjectManagerFacade {
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn store_project(&self, request: Request<StoreProjectRequest>) -> Result<Response<StoreProjectResponse>, Status> {
+
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let project: project::Project = extract!(request.project)?;
+
+        trace!("Received request to store project '{}' <{}>.", project.name, project.id);
+
+        let result = actions::store_project(StoreProjectParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            project,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StoreProjectResponse {
+                    reply: Some(store_project_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(project_id) => {
+                Ok(Response::new(StoreProjectResponse {
+                    reply: Some(store_project_response::Reply::Success(
+                        StoreProjectSuccess {
+                            project_id: Some(project_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_project(&self, request: Request<DeleteProjectRequest>) -> Result<Response<DeleteProjectResponse>, Status> {
+
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let project_id: project::ProjectId = extract!(request.project_id)?;
+
+        trace!("Received request to delete project <{project_id}>.");
+
+        let result = actions::delete_project(DeleteProjectParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            project_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteProjectResponse {
+                    reply: Some(delete_project_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(project) => {
+                Ok(Response::new(DeleteProjectResponse {
+                    reply: Some(delete_project_response::Reply::Success(
+                        DeleteProjectSuccess {
+                            project_id: Some(project.id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    /// Users without an admin role only see the projects they are members of.
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_projects(&self, request: Request<ListProjectsRequest>) -> Result<Response<ListProjectsResponse>, Status> {
+
+        let access = ProjectAccess::of(&request, &self.resources_manager, &self.options).await?;
+
+        trace!("Received request to list projects.");
+
+        let result = actions::list_projects(ListProjectsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListProjectsResponse {
+                    reply: Some(list_projects_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(projects) => {
+                Ok(Response::new(ListProjectsResponse {
+                    reply: Some(list_projects_response::Reply::Success(
+                        ListProjectsSuccess {
+                            projects: projects.into_iter()
+                                .filter(|project| access.is_member_of(&project.id))
+                                .map(Into::into)
+                                .collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::pin::Pin;
+
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::{debug, trace};
+
+use opendut_carl_api::carl::observer::{ResourceChange, ResourceEvent, ResourceFilter, ResourceKind};
+use opendut_carl_api::proto::services::resource_observer::{SubscribeResourceEventsRequest, SubscribeResourceEventsResponse};
+use opendut_carl_api::proto::services::resource_observer::resource_observer_server::{ResourceObserver, ResourceObserverServer};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::state::PeerState;
+use opendut_types::snapshot::ResourceSnapshot;
+
+use crate::resources::ids::IntoId;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::resource::Resource;
+use crate::resources::subscription::{ReceiveError, Subscribable, SubscriptionEvent, SUBSCRIPTION_CAPACITY};
+
+type EventSender = mpsc::Sender<Result<SubscribeResourceEventsResponse, Status>>;
+
+pub struct ResourceObserverFacade {
+    resources_manager: ResourcesManagerRef,
+}
+
+impl ResourceObserverFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef) -> Self {
+        Self { resources_manager }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ResourceObserverServer<Self>> {
+        tonic_web::enable(ResourceObserverServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl ResourceObserver for ResourceObserverFacade {
+
+    type SubscribeResourceEventsStream = Pin<Box<dyn Stream<Item = Result<SubscribeResourceEventsResponse, Status>> + Send>>;
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn subscribe_resource_events(&self, request: Request<SubscribeResourceEventsRequest>) -> Result<Response<Self::SubscribeResourceEventsStream>, Status> {
+
+        let request = request.into_inner();
+        let filters = request.filters.into_iter()
+            .map(ResourceFilter::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.filters' is not valid: {cause}")))?;
+
+        if filters.is_empty() {
+            return Err(Status::invalid_argument("Field 'request.filters' must contain at least one filter."));
+        }
+        let filters = merge_filters(filters);
+
+        trace!("Received request to subscribe to events of resources: {}", filters.keys().map(ToString::to_string).collect::<Vec<_>>().join(", "));
+
+        let (sender, receiver) = mpsc::channel(SUBSCRIPTION_CAPACITY);
+        for (kind, filter) in filters {
+            let sender = Clone::clone(&sender);
+            match kind {
+                ResourceKind::PeerDescriptor => forward_events::<PeerDescriptor>(&self.resources_manager, filter, sender).await,
+                ResourceKind::PeerState => forward_events::<PeerState>(&self.resources_manager, filter, sender).await,
+                ResourceKind::ClusterConfiguration => forward_events::<ClusterConfiguration>(&self.resources_manager, filter, sender).await,
+                ResourceKind::ClusterDeployment => forward_events::<ClusterDeployment>(&self.resources_manager, filter, sender).await,
+                ResourceKind::ClusterDeploymentStatus => forward_events::<ClusterDeploymentStatus>(&self.resources_manager, filter, sender).await,
+                ResourceKind::PeerConfiguration => forward_events::<PeerConfiguration>(&self.resources_manager, filter, sender).await,
+                ResourceKind::PeerGroup => forward_events::<PeerGroup>(&self.resources_manager, filter, sender).await,
+                ResourceKind::HierarchyNode => forward_events::<HierarchyNode>(&self.resources_manager, filter, sender).await,
+                ResourceKind::ResourceSnapshot => forward_events::<ResourceSnapshot>(&self.resources_manager, filter, sender).await,
+            }
+        }
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+}
+
+/// Combines the filters of the same kind of resource. A filter without ids selects all resources of its kind.
+fn merge_filters(filters: Vec<ResourceFilter>) -> HashMap<ResourceKind, ResourceFilter> {
+    let mut merged = HashMap::<ResourceKind, ResourceFilter>::new();
+    for filter in filters {
+        match merged.get_mut(&filter.kind) {
+            None => {
+                merged.insert(filter.kind, filter);
+            }
+            Some(existing) => {
+                if existing.ids.is_empty() || filter.ids.is_empty() {
+                    existing.ids.clear();
+                } else {
+                    existing.ids.extend(filter.ids);
+                }
+            }
+        }
+    }
+    merged
+}
+
+/// Subscribes to the resources of type `R` and forwards the events matching the filter to the client, until the client closes the stream.
+async fn forward_events<R>(resources_manager: &ResourcesManagerRef, filter: ResourceFilter, sender: EventSender)
+where R: Resource + Subscribable {
+    let kind = filter.kind;
+    let mut subscription = resources_manager.subscribe::<R>().await;
+
+    tokio::spawn(async move {
+        loop {
+            let received = tokio::select! {
+                received = subscription.receive() => received,
+                _ = sender.closed() => break,
+            };
+
+            let event = match received {
+                Ok(SubscriptionEvent::Inserted { id, .. }) => ResourceEvent::Changed { kind, id: IntoId::<R>::into_id(id).value(), change: ResourceChange::Inserted },
+                Ok(SubscriptionEvent::Removed { id, .. }) => ResourceEvent::Changed { kind, id: IntoId::<R>::into_id(id).value(), change: ResourceChange::Removed },
+                Err(ReceiveError::Lagged { skipped }) => ResourceEvent::Lagged { skipped },
+                Err(cause @ (ReceiveError::Stale | ReceiveError::Closed)) => {
+                    let _ignore_closed_stream = sender.send(Err(Status::aborted(cause.to_string()))).await;
+                    break;
+                }
+            };
+
+            if let ResourceEvent::Changed { id, .. } = &event {
+                if filter.matches(id).not() {
+                    continue;
+                }
+            }
+
+            let response = SubscribeResourceEventsResponse { event: Some(event.into()) };
+            if sender.send(Ok(response)).await.is_err() {
+                break;
+            }
+        }
+        debug!("Stopped forwarding events of {kind} resources to subscriber.");
+    });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use tokio_stream::StreamExt;
+
+    use opendut_carl_api::proto::services::resource_observer::subscribe_resource_events_response;
+    use opendut_types::peer::PeerId;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    async fn next_event(stream: &mut <ResourceObserverFacade as ResourceObserver>::SubscribeResourceEventsStream) -> anyhow::Result<ResourceEvent> {
+        let response = tokio::time::timeout(Duration::from_secs(10), stream.next()).await?
+            .expect("stream should not end")?;
+        let event: subscribe_resource_events_response::Event = response.event.expect("event should be set");
+        Ok(ResourceEvent::try_from(event)?)
+    }
+
+    #[tokio::test]
+    async fn should_stream_events_of_the_resources_selected_by_the_filters() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let testee = ResourceObserverFacade::new(std::sync::Arc::clone(&resources_manager));
+
+        let observed_peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+
+        let request = Request::new(SubscribeResourceEventsRequest {
+            filters: vec![ResourceFilter { kind: ResourceKind::PeerState, ids: vec![observed_peer_id.uuid] }.into()],
+        });
+        let mut stream = testee.subscribe_resource_events(request).await?.into_inner();
+
+        resources_manager.insert(other_peer_id, PeerState::Down).await?;
+        resources_manager.insert(observed_peer_id, PeerState::Down).await?;
+        resources_manager.remove::<PeerState>(observed_peer_id).await?;
+
+        assert_that!(next_event(&mut stream).await?, eq(&ResourceEvent::Changed { kind: ResourceKind::PeerState, id: observed_peer_id.uuid, change: ResourceChange::Inserted }));
+        assert_that!(next_event(&mut stream).await?, eq(&ResourceEvent::Changed { kind: ResourceKind::PeerState, id: observed_peer_id.uuid, change: ResourceChange::Removed }));
+
+        let request = Request::new(SubscribeResourceEventsRequest { filters: vec![] });
+        assert_that!(testee.subscribe_resource_events(request).await.err().map(|status| status.code()), some(eq(tonic::Code::InvalidArgument)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_merge_filters_of_the_same_kind_of_resource() {
+        let id_a = uuid::Uuid::new_v4();
+        let id_b = uuid::Uuid::new_v4();
+
+        let merged = merge_filters(vec![
+            ResourceFilter { kind: ResourceKind::PeerState, ids: vec![id_a] },
+            ResourceFilter { kind: ResourceKind::PeerState, ids: vec![id_b] },
+            ResourceFilter { kind: ResourceKind::PeerDescriptor, ids: vec![id_a] },
+            ResourceFilter::all(ResourceKind::PeerDescriptor),
+        ]);
+
+        assert_that!(merged.len(), eq(2));
+        assert_that!(merged[&ResourceKind::PeerState].ids, elements_are![eq(&id_a), eq(&id_b)]);
+        assert_that!(merged[&ResourceKind::PeerDescriptor].ids, empty());
+    }
+}
@@ -0,0 +1,132 @@
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::results_manager::*;
+use opendut_carl_api::proto::services::results_manager::results_manager_server::{ResultsManager as ResultsManagerService, ResultsManagerServer};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+use crate::actions;
+use crate::actions::{DownloadExecutorResultParams, ListExecutorResultsParams, ProjectOptions, ResultsStorage};
+use crate::auth::permission::{Permission, PermissionAuthorization};
+use crate::auth::project::ProjectAccess;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+const DOWNLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+
+pub struct ResultsManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    results_storage: ResultsStorage,
+    authorization: PermissionAuthorization,
+    project_options: ProjectOptions,
+}
+
+impl ResultsManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, results_storage: ResultsStorage, authorization: PermissionAuthorization, project_options: ProjectOptions) -> Self {
+        Self {
+            resources_manager,
+            results_storage,
+            authorization,
+            project_options,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ResultsManagerServer<Self>> {
+        tonic_web::enable(ResultsManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl ResultsManagerService for ResultsManagerFacade {
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_executor_results(&self, request: Request<ListExecutorResultsRequest>) -> Result<Response<ListExecutorResultsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::ResultsRead)?;
+        let access = ProjectAccess::of(&request, &self.resources_manager, &self.project_options).await?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+        access.authorize_peer(peer_id)?;
+
+        trace!("Received request to list the results of executor <{executor_id}> of peer <{peer_id}>.");
+
+        let result = actions::list_executor_results(ListExecutorResultsParams {
+            peer_id,
+            executor_id,
+            resources_manager: Arc::clone(&self.resources_manager),
+            results_storage: Clone::clone(&self.results_storage),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListExecutorResultsResponse {
+                    reply: Some(list_executor_results_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(files) => {
+                Ok(Response::new(ListExecutorResultsResponse {
+                    reply: Some(list_executor_results_response::Reply::Success(
+                        ListExecutorResultsSuccess {
+                            files: files.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    type DownloadExecutorResultStream = Pin<Box<dyn Stream<Item = Result<DownloadExecutorResultResponse, Status>> + Send>>;
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn download_executor_result(&self, request: Request<DownloadExecutorResultRequest>) -> Result<Response<Self::DownloadExecutorResultStream>, Status> {
+
+        self.authorization.authorize(&request, Permission::ResultsRead)?;
+        let access = ProjectAccess::of(&request, &self.resources_manager, &self.project_options).await?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+        access.authorize_peer(peer_id)?;
+
+        trace!("Received request to download result '{}' of executor <{executor_id}> of peer <{peer_id}>.", request.name);
+
+        let result = actions::download_executor_result(DownloadExecutorResultParams {
+            peer_id,
+            executor_id,
+            name: request.name,
+            resources_manager: Arc::clone(&self.resources_manager),
+            results_storage: Clone::clone(&self.results_storage),
+        }).await;
+
+        let responses = match result {
+            Err(error) => {
+                vec![DownloadExecutorResultResponse {
+                    part: Some(download_executor_result_response::Part::Failure(error.into()))
+                }]
+            }
+            Ok((file, content)) => {
+                let header = DownloadExecutorResultResponse {
+                    part: Some(download_executor_result_response::Part::Header(file.into()))
+                };
+                let chunks = content.chunks(DOWNLOAD_CHUNK_SIZE)
+                    .map(|chunk| DownloadExecutorResultResponse {
+                        part: Some(download_executor_result_response::Part::Chunk(chunk.to_vec()))
+                    });
+                std::iter::once(header)
+                    .chain(chunks)
+                    .collect()
+            }
+        };
+
+        let stream = tokio_stream::iter(responses.into_iter().map(Ok));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
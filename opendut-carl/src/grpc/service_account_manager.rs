@@ -0,0 +1,231 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::service_account_manager::*;
+use opendut_carl_api::proto::services::service_account_manager::service_account_manager_server::{ServiceAccountManager as ServiceAccountManagerService, ServiceAccountManagerServer};
+use opendut_types::service_account;
+use opendut_types::service_account::ServiceAccountName;
+
+use crate::actions;
+use crate::actions::{CreateServiceAccountParams, DeleteServiceAccountParams, IssueServiceAccountTokenParams, ListServiceAccountsParams, RevokeServiceAccountTokenParams, ServiceAccountOptions};
+use crate::auth::CurrentUser;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ServiceAccountManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    options: ServiceAccountOptions,
+}
+
+impl ServiceAccountManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, options: ServiceAccountOptions) -> Self {
+        Self {
+            resources_manager,
+            options,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ServiceAccountManagerServer<Self>> {
+        tonic_web::enable(ServiceAccountManagerServer::new(self))
+    }
+
+    /// Only users with one of the configured admin roles may manage service accounts. Service accounts are already rejected by the authentication layer.
+    /// Without a user, authentication is disabled and everyone may manage service accounts.
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let Some(user) = request.extensions().get::<CurrentUser>() else {
+            return Ok(());
+        };
+        let roles = &user.claims.additional_claims().roles;
+        if self.options.admin_roles.is_empty()
+            || self.options.admin_roles.iter().any(|role| roles.contains(role)) {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!(
+                "CARL says, managing service accounts requires one of the roles: {}",
+                self.options.admin_roles.join(", ")
+            )))
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ServiceAccountManagerService for ServiceAccountManagerFacade {
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_service_account(&self, request: Request<CreateServiceAccountRequest>) -> Result<Response<CreateServiceAccountResponse>, Status> {
+
+        self.authorize(&request)?;
+        let user_name = request.extensions().get::<CurrentUser>()
+            .map(|user| Clone::clone(&user.name));
+        let request = request.into_inner();
+        let name = ServiceAccountName::try_from(request.name)
+            .map_err(|cause| Status::invalid_argument(format!("Field 'name' is not valid: {cause}")))?;
+        let scopes = request.scopes.into_iter()
+            .map(service_account::ServiceAccountScope::try_from)
+            .collect::<Result<BTreeSet<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'scopes' is not valid: {cause}")))?;
+
+        trace!("Received request to create service account '{name}'.");
+
+        let result = actions::create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            name,
+            scopes,
+            user_id: user_name.unwrap_or(request.user_id),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_account) => {
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Success(
+                        CreateServiceAccountSuccess {
+                            service_account: Some(service_account.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_service_accounts(&self, request: Request<ListServiceAccountsRequest>) -> Result<Response<ListServiceAccountsResponse>, Status> {
+
+        self.authorize(&request)?;
+
+        trace!("Received request to list service accounts.");
+
+        let result = actions::list_service_accounts(ListServiceAccountsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListServiceAccountsResponse {
+                    reply: Some(list_service_accounts_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_accounts) => {
+                Ok(Response::new(ListServiceAccountsResponse {
+                    reply: Some(list_service_accounts_response::Reply::Success(
+                        ListServiceAccountsSuccess {
+                            service_accounts: service_accounts.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_service_account(&self, request: Request<DeleteServiceAccountRequest>) -> Result<Response<DeleteServiceAccountResponse>, Status> {
+
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let service_account_id: service_account::ServiceAccountId = extract!(request.service_account_id)?;
+
+        trace!("Received request to delete service account <{service_account_id}>.");
+
+        let result = actions::delete_service_account(DeleteServiceAccountParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_account) => {
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Success(
+                        DeleteServiceAccountSuccess {
+                            service_account_id: Some(service_account.id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn issue_service_account_token(&self, request: Request<IssueServiceAccountTokenRequest>) -> Result<Response<IssueServiceAccountTokenResponse>, Status> {
+
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let service_account_id: service_account::ServiceAccountId = extract!(request.service_account_id)?;
+        let validity = Duration::from_secs(request.validity_seconds);
+
+        trace!("Received request to issue a token for service account <{service_account_id}>.");
+
+        let result = actions::issue_service_account_token(IssueServiceAccountTokenParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account_id,
+            validity,
+            read_only: request.read_only,
+            options: Clone::clone(&self.options),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(IssueServiceAccountTokenResponse {
+                    reply: Some(issue_service_account_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(issued) => {
+                Ok(Response::new(IssueServiceAccountTokenResponse {
+                    reply: Some(issue_service_account_token_response::Reply::Success(
+                        IssueServiceAccountTokenSuccess {
+                            token: Some(issued.token.into()),
+                            credentials: issued.credentials,
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn revoke_service_account_token(&self, request: Request<RevokeServiceAccountTokenRequest>) -> Result<Response<RevokeServiceAccountTokenResponse>, Status> {
+
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let service_account_id: service_account::ServiceAccountId = extract!(request.service_account_id)?;
+        let token_id: service_account::ServiceAccountTokenId = extract!(request.token_id)?;
+
+        trace!("Received request to revoke token <{token_id}> of service account <{service_account_id}>.");
+
+        let result = actions::revoke_service_account_token(RevokeServiceAccountTokenParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            service_account_id,
+            token_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokeServiceAccountTokenResponse {
+                    reply: Some(revoke_service_account_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(token_id) => {
+                Ok(Response::new(RevokeServiceAccountTokenResponse {
+                    reply: Some(revoke_service_account_token_response::Reply::Success(
+                        RevokeServiceAccountTokenSuccess {
+                            token_id: Some(token_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::{error, trace};
+
+use opendut_carl_api::proto::services::snapshot_manager::*;
+use opendut_carl_api::proto::services::snapshot_manager::snapshot_manager_server::{SnapshotManager as SnapshotManagerService, SnapshotManagerServer};
+use opendut_types::cluster::ClusterId;
+use opendut_types::snapshot;
+use opendut_types::snapshot::{SnapshotName, SnapshotResourceKind};
+
+use crate::actions;
+use crate::actions::{CreateSnapshotParams, DeleteSnapshotParams, DiffSnapshotsParams, ListSnapshotsParams, RestoreSnapshotParams};
+use crate::auth::permission::{Permission, PermissionAuthorization};
+use crate::cluster::manager::ClusterManagerRef;
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct SnapshotManagerFacade {
+    cluster_manager: ClusterManagerRef,
+    resources_manager: ResourcesManagerRef,
+    authorization: PermissionAuthorization,
+}
+
+impl SnapshotManagerFacade {
+
+    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef, authorization: PermissionAuthorization) -> Self {
+        Self {
+            cluster_manager,
+            resources_manager,
+            authorization,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<SnapshotManagerServer<Self>> {
+        tonic_web::enable(SnapshotManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl SnapshotManagerService for SnapshotManagerFacade {
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_snapshot(&self, request: Request<CreateSnapshotRequest>) -> Result<Response<CreateSnapshotResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::SnapshotWrite)?;
+        let request = request.into_inner();
+        let name = SnapshotName::try_from(request.name)
+            .map_err(|cause| Status::invalid_argument(format!("Field 'name' is not valid: {cause}")))?;
+
+        trace!("Received request to create snapshot '{name}'.");
+
+        let result = actions::create_snapshot(CreateSnapshotParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            name,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateSnapshotResponse {
+                    reply: Some(create_snapshot_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(snapshot) => {
+                Ok(Response::new(CreateSnapshotResponse {
+                    reply: Some(create_snapshot_response::Reply::Success(
+                        CreateSnapshotSuccess {
+                            snapshot: Some(snapshot.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_snapshots(&self, request: Request<ListSnapshotsRequest>) -> Result<Response<ListSnapshotsResponse>, Status> {
+        self.authorization.authorize(&request, Permission::SnapshotRead)?;
+        trace!("Received request to list snapshots.");
+
+        let result = actions::list_snapshots(ListSnapshotsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListSnapshotsResponse {
+                    reply: Some(list_snapshots_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(snapshots) => {
+                Ok(Response::new(ListSnapshotsResponse {
+                    reply: Some(list_snapshots_response::Reply::Success(
+                        ListSnapshotsSuccess {
+                            snapshots: snapshots.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn diff_snapshots(&self, request: Request<DiffSnapshotsRequest>) -> Result<Response<DiffSnapshotsResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::SnapshotRead)?;
+        let request = request.into_inner();
+        let from: snapshot::SnapshotId = extract!(request.from)?;
+        let to = request.to
+            .map(snapshot::SnapshotId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'to' is not valid: {cause}")))?;
+
+        trace!("Received request to compare snapshot <{from}> with {to:?}.");
+
+        let result = actions::diff_snapshots(DiffSnapshotsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            from,
+            to,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DiffSnapshotsResponse {
+                    reply: Some(diff_snapshots_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(entries) => {
+                Ok(Response::new(DiffSnapshotsResponse {
+                    reply: Some(diff_snapshots_response::Reply::Success(
+                        DiffSnapshotsSuccess {
+                            entries: entries.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn restore_snapshot(&self, request: Request<RestoreSnapshotRequest>) -> Result<Response<RestoreSnapshotResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::SnapshotWrite)?;
+        let request = request.into_inner();
+        let snapshot_id: snapshot::SnapshotId = extract!(request.snapshot_id)?;
+        let resources = request.resources.into_iter()
+            .map(snapshot::SnapshotResourceReference::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'resources' is not valid: {cause}")))?;
+
+        trace!("Received request to restore snapshot <{snapshot_id}> for resources: {resources:?}");
+
+        let result = actions::restore_snapshot(RestoreSnapshotParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            snapshot_id,
+            resources,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RestoreSnapshotResponse {
+                    reply: Some(restore_snapshot_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(restored_resources) => {
+                // Restored deployments are rolled out like newly stored deployments, once all of their peers are available.
+                let restored_deployments = restored_resources.iter()
+                    .filter(|resource| resource.kind == SnapshotResourceKind::ClusterDeployment)
+                    .map(|resource| ClusterId::from(resource.id));
+                for cluster_id in restored_deployments {
+                    if let Err(error) = self.cluster_manager.lock().await.deploy_cluster_if_all_peers_available(cluster_id).await {
+                        error!("Failed to deploy cluster <{cluster_id}> after restoring it from snapshot <{snapshot_id}>, due to:\n  {error}");
+                    }
+                }
+
+                Ok(Response::new(RestoreSnapshotResponse {
+                    reply: Some(restore_snapshot_response::Reply::Success(
+                        RestoreSnapshotSuccess {
+                            restored_resources: restored_resources.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_snapshot(&self, request: Request<DeleteSnapshotRequest>) -> Result<Response<DeleteSnapshotResponse>, Status> {
+
+        self.authorization.authorize(&request, Permission::SnapshotWrite)?;
+        let request = request.into_inner();
+        let snapshot_id: snapshot::SnapshotId = extract!(request.snapshot_id)?;
+
+        trace!("Received request to delete snapshot <{snapshot_id}>.");
+
+        let result = actions::delete_snapshot(DeleteSnapshotParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            snapshot_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteSnapshotResponse {
+                    reply: Some(delete_snapshot_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(snapshot) => {
+                Ok(Response::new(DeleteSnapshotResponse {
+                    reply: Some(delete_snapshot_response::Reply::Success(
+                        DeleteSnapshotSuccess {
+                            snapshot_id: Some(snapshot.id.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod state;
+pub mod rest;
 pub mod router;
 mod tests;
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use http::StatusCode;
+use serde::Serialize;
+
+use opendut_carl_api::carl::error_code::ErrorCode;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+
+use crate::actions;
+use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams};
+use crate::http::rest::{RestError, RestState};
+
+#[derive(Serialize)]
+pub struct ClusterIdResponse {
+    pub id: ClusterId,
+}
+
+fn parse_cluster_id(id: &str) -> Result<ClusterId, RestError> {
+    ClusterId::try_from(id)
+        .map_err(|cause| RestError::invalid_request(cause.to_string()))
+}
+
+pub async fn list_cluster_configurations(
+    State(state): State<RestState>,
+) -> Result<Json<Vec<ClusterConfiguration>>, RestError> {
+    let configurations = state.cluster_manager.lock().await.list_configuration().await
+        .map_err(RestError::internal)?;
+    Ok(Json(configurations))
+}
+
+pub async fn get_cluster_configuration(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<ClusterConfiguration>, RestError> {
+    let cluster_id = parse_cluster_id(&id)?;
+
+    let configuration = state.cluster_manager.lock().await.get_configuration(cluster_id).await
+        .map_err(RestError::internal)?
+        .ok_or_else(|| RestError::not_found(ErrorCode::ClusterConfigurationNotFound, format!("ClusterConfiguration <{cluster_id}> does not exist.")))?;
+    Ok(Json(configuration))
+}
+
+pub async fn create_cluster_configuration(
+    State(state): State<RestState>,
+    Json(cluster_configuration): Json<ClusterConfiguration>,
+) -> Result<impl IntoResponse, RestError> {
+    let id = actions::create_cluster_configuration(CreateClusterConfigurationParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+        policy_engine: Arc::clone(&state.policy_engine),
+        cluster_configuration,
+    }).await
+    .map_err(RestError::from_error)?;
+
+    Ok((StatusCode::CREATED, Json(ClusterIdResponse { id })))
+}
+
+pub async fn delete_cluster_configuration(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<ClusterConfiguration>, RestError> {
+    let cluster_id = parse_cluster_id(&id)?;
+
+    let configuration = actions::delete_cluster_configuration(DeleteClusterConfigurationParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+        cluster_id,
+    }).await
+    .map_err(RestError::from_error)?;
+    Ok(Json(configuration))
+}
+
+pub async fn list_cluster_deployments(
+    State(state): State<RestState>,
+) -> Result<Json<Vec<ClusterDeployment>>, RestError> {
+    let deployments = state.cluster_manager.lock().await.list_deployment().await
+        .map_err(RestError::internal)?;
+    Ok(Json(deployments))
+}
+
+pub async fn store_cluster_deployment(
+    State(state): State<RestState>,
+    Json(cluster_deployment): Json<ClusterDeployment>,
+) -> Result<impl IntoResponse, RestError> {
+    let id = state.cluster_manager.lock().await.store_cluster_deployment(cluster_deployment).await
+        .map_err(RestError::from_error)?;
+
+    Ok((StatusCode::CREATED, Json(ClusterIdResponse { id })))
+}
+
+pub async fn delete_cluster_deployment(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<ClusterDeployment>, RestError> {
+    let cluster_id = parse_cluster_id(&id)?;
+
+    let deployment = state.cluster_manager.lock().await.delete_cluster_deployment(cluster_id).await
+        .map_err(RestError::from_error)?;
+    Ok(Json(deployment))
+}
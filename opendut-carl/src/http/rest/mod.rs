@@ -0,0 +1,202 @@
+//! REST/JSON gateway to the cluster and peer management, for integrations which cannot use gRPC or gRPC-web.
+//! Mounted under [`PATH`], when enabled via `serve.rest.enabled`. Resources are (de-)serialized like the types of `opendut-types`.
+
+use std::fmt::Display;
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use http::{header, Method, Request, StatusCode};
+use serde::Serialize;
+use tracing::debug;
+
+use opendut_carl_api::carl::error_code::{ErrorCode, HasErrorCode};
+
+use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer;
+use crate::auth::read_only::ReadOnlyOptions;
+use crate::cluster::manager::ClusterManagerRef;
+use crate::http::state::HttpState;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::policy::PolicyEngineRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::vpn::Vpn;
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_types::service_account::ServiceAccountScope;
+
+pub mod cluster_manager;
+pub mod openapi;
+pub mod peer_manager;
+
+pub const PATH: &str = "/api/v1";
+
+#[derive(Clone)]
+pub struct RestState {
+    pub cluster_manager: ClusterManagerRef,
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub policy_engine: PolicyEngineRef,
+    pub vpn: Vpn,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub auth: GrpcAuthenticationLayer,
+    pub read_only: ReadOnlyOptions,
+}
+
+pub fn router(state: HttpState) -> Router<HttpState> {
+    let api = Router::new()
+        .route("/cluster-configurations", get(cluster_manager::list_cluster_configurations).post(cluster_manager::create_cluster_configuration))
+        .route("/cluster-configurations/:id", get(cluster_manager::get_cluster_configuration).delete(cluster_manager::delete_cluster_configuration))
+        .route("/cluster-deployments", get(cluster_manager::list_cluster_deployments).post(cluster_manager::store_cluster_deployment))
+        .route("/cluster-deployments/:id", axum::routing::delete(cluster_manager::delete_cluster_deployment))
+        .route("/peers", get(peer_manager::list_peer_descriptors).post(peer_manager::store_peer_descriptor))
+        .route("/peers/:id", get(peer_manager::get_peer_descriptor).delete(peer_manager::delete_peer_descriptor))
+        .route("/peers/:id/self-test", get(peer_manager::get_self_test_report))
+        .route("/devices", get(peer_manager::list_devices))
+        .route_layer(middleware::from_fn_with_state(state, authenticate))
+        .route(openapi::PATH, get(openapi::specification));
+
+    Router::new().nest(PATH, api)
+}
+
+/// Applies the same rules as for the gRPC methods: reading requests are allowed without credentials, if anonymous read access is enabled.
+/// Service accounts need the scope for reading, or for changing clusters or peers respectively.
+/// Changes are rejected, if CARL or the credentials of the caller are read-only.
+async fn authenticate(
+    State(state): State<RestState>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let authorization = request.headers().get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    let required_scope = if request.method() == Method::GET {
+        ServiceAccountScope::Read
+    } else if request.uri().path().contains("/cluster-") {
+        ServiceAccountScope::Clusters
+    } else {
+        ServiceAccountScope::Peers
+    };
+
+    let caller = match state.auth.authenticate_http_request(authorization, required_scope).await {
+        Ok(caller) => caller,
+        Err(message) => {
+            debug!("Rejected request to REST gateway for {} {}: {message}", request.method(), request.uri().path());
+            return RestError { status: StatusCode::UNAUTHORIZED, code: None, message }.into_response();
+        }
+    };
+
+    if request.method() != Method::GET {
+        if let Err(cause) = state.read_only.check_http_change(&caller) {
+            debug!("Rejected request to REST gateway for {} {}: {cause}", request.method(), request.uri().path());
+            return RestError { status: StatusCode::FORBIDDEN, code: None, message: cause.to_string() }.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestError {
+    #[serde(skip)]
+    status: StatusCode,
+    /// Stable identifier of the error, as listed in the error catalog of CARL.
+    code: Option<&'static str>,
+    message: String,
+}
+
+impl RestError {
+    pub fn from_error<E: HasErrorCode + Display>(error: E) -> Self {
+        let code = error.error_code();
+        Self {
+            status: code.map(status_of).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            code: code.map(|code| code.value()),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn not_found(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self { status: status_of(code), code: Some(code.value()), message: message.into() }
+    }
+
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self { status: StatusCode::BAD_REQUEST, code: Some(ErrorCode::InvalidRequest.value()), message: message.into() }
+    }
+
+    pub fn internal(cause: impl Display) -> Self {
+        Self { status: StatusCode::INTERNAL_SERVER_ERROR, code: Some(ErrorCode::Internal.value()), message: cause.to_string() }
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self)).into_response()
+    }
+}
+
+fn status_of(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::InvalidRequest
+        | ErrorCode::PeerIllegalDevices => StatusCode::BAD_REQUEST,
+        ErrorCode::PeerNotFound
+        | ErrorCode::PeerGroupNotFound
+        | ErrorCode::ClusterConfigurationNotFound
+        | ErrorCode::ClusterDeploymentNotFound
+        | ErrorCode::SnapshotNotFound
+        | ErrorCode::TraceCaptureNotFound
+        | ErrorCode::ExecutorNotFound
+        | ErrorCode::ExecutorResultNotFound
+        | ErrorCode::MaintenanceOperationNotFound
+        | ErrorCode::ProjectNotFound
+        | ErrorCode::ServiceAccountNotFound
+        | ErrorCode::ServiceAccountTokenNotFound
+        | ErrorCode::SelfTestReportNotFound => StatusCode::NOT_FOUND,
+        ErrorCode::PeerAlreadyExists
+        | ErrorCode::PeerIllegalState
+        | ErrorCode::PeerGroupReferenced
+        | ErrorCode::ClusterConfigurationAlreadyExists
+        | ErrorCode::ClusterReferencedAsDependency
+        | ErrorCode::ClusterIllegalState
+        | ErrorCode::SnapshotNameAlreadyExists
+        | ErrorCode::ProvisioningTokenClaimed
+        | ErrorCode::ProjectNameAlreadyExists
+        | ErrorCode::ServiceAccountNameAlreadyExists => StatusCode::CONFLICT,
+        ErrorCode::ExecutorResultsNotConfigured
+        | ErrorCode::PolicyViolation
+        | ErrorCode::ClusterDependencyInvalid
+        | ErrorCode::SnapshotResourcesMissing => StatusCode::UNPROCESSABLE_ENTITY,
+        ErrorCode::PeerCommandUnauthorized
+        | ErrorCode::ProvisioningTokenInvalid => StatusCode::FORBIDDEN,
+        ErrorCode::PeersUnavailable
+        | ErrorCode::ExecutorResultsUnavailable
+        | ErrorCode::PeerUnreachable
+        | ErrorCode::CarlUnreachable => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCode::PeerCommandFailed
+        | ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use opendut_carl_api::carl::cluster::DeleteClusterDeploymentError;
+    use opendut_types::cluster::ClusterId;
+
+    use super::*;
+
+    #[test]
+    fn should_respond_with_the_status_and_code_of_the_error() {
+        let error = RestError::from_error(DeleteClusterDeploymentError::ClusterDeploymentNotFound { cluster_id: ClusterId::random() });
+
+        assert_that!(error.status, eq(StatusCode::NOT_FOUND));
+        assert_that!(error.code, some(eq("cluster-deployment-not-found")));
+    }
+
+    #[test]
+    fn should_map_every_error_code_to_an_error_status() {
+        for code in ErrorCode::ALL {
+            let status = status_of(code);
+            assert!(status.is_client_error() || status.is_server_error(), "Status {status} of error code '{code}' is no error status.");
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use axum::Json;
+use serde_json::{json, Map, Value};
+
+use crate::http::rest;
+
+/// Path of the OpenAPI specification, relative to [`rest::PATH`]. It is served without credentials.
+pub const PATH: &str = "/openapi.json";
+
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    operation_id: &'static str,
+    summary: &'static str,
+    tag: &'static str,
+    request_schema: Option<&'static str>,
+    response_status: &'static str,
+    response_schema: Value,
+}
+
+fn reference(schema: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{schema}") })
+}
+
+fn list_of(schema: &str) -> Value {
+    json!({ "type": "array", "items": reference(schema) })
+}
+
+fn operations() -> Vec<Operation> {
+    vec![
+        Operation { method: "get", path: "/cluster-configurations", operation_id: "listClusterConfigurations", summary: "List all cluster configurations.", tag: "ClusterManager", request_schema: None, response_status: "200", response_schema: list_of("ClusterConfiguration") },
+        Operation { method: "post", path: "/cluster-configurations", operation_id: "createClusterConfiguration", summary: "Create a cluster configuration.", tag: "ClusterManager", request_schema: Some("ClusterConfiguration"), response_status: "201", response_schema: reference("ClusterIdResponse") },
+        Operation { method: "get", path: "/cluster-configurations/{id}", operation_id: "getClusterConfiguration", summary: "Retrieve a cluster configuration.", tag: "ClusterManager", request_schema: None, response_status: "200", response_schema: reference("ClusterConfiguration") },
+        Operation { method: "delete", path: "/cluster-configurations/{id}", operation_id: "deleteClusterConfiguration", summary: "Delete a cluster configuration, which is not deployed.", tag: "ClusterManager", request_schema: None, response_status: "200", response_schema: reference("ClusterConfiguration") },
+        Operation { method: "get", path: "/cluster-deployments", operation_id: "listClusterDeployments", summary: "List all cluster deployments.", tag: "ClusterManager", request_schema: None, response_status: "200", response_schema: list_of("ClusterDeployment") },
+        Operation { method: "post", path: "/cluster-deployments", operation_id: "storeClusterDeployment", summary: "Deploy a cluster.", tag: "ClusterManager", request_schema: Some("ClusterDeployment"), response_status: "201", response_schema: reference("ClusterIdResponse") },
+        Operation { method: "delete", path: "/cluster-deployments/{id}", operation_id: "deleteClusterDeployment", summary: "Undeploy a cluster.", tag: "ClusterManager", request_schema: None, response_status: "200", response_schema: reference("ClusterDeployment") },
+        Operation { method: "get", path: "/peers", operation_id: "listPeerDescriptors", summary: "List all peers.", tag: "PeerManager", request_schema: None, response_status: "200", response_schema: list_of("PeerDescriptor") },
+        Operation { method: "post", path: "/peers", operation_id: "storePeerDescriptor", summary: "Create or update a peer.", tag: "PeerManager", request_schema: Some("PeerDescriptor"), response_status: "201", response_schema: reference("PeerIdResponse") },
+        Operation { method: "get", path: "/peers/{id}", operation_id: "getPeerDescriptor", summary: "Retrieve a peer.", tag: "PeerManager", request_schema: None, response_status: "200", response_schema: reference("PeerDescriptor") },
+        Operation { method: "delete", path: "/peers/{id}", operation_id: "deletePeerDescriptor", summary: "Delete a peer, which is not part of a deployed cluster.", tag: "PeerManager", request_schema: None, response_status: "200", response_schema: reference("PeerDescriptor") },
+        Operation { method: "get", path: "/peers/{id}/self-test", operation_id: "getSelfTestReport", summary: "Retrieve the latest self-test report, which the peer uploaded.", tag: "PeerManager", request_schema: None, response_status: "200", response_schema: reference("SelfTestReport") },
+        Operation { method: "get", path: "/devices", operation_id: "listDevices", summary: "List the devices of all peers.", tag: "PeerManager", request_schema: None, response_status: "200", response_schema: list_of("DeviceDescriptor") },
+    ]
+}
+
+/// Schema of a resource, which is (de-)serialized like the type of the same name in `opendut-types`.
+fn resource_schema(name: &str) -> Value {
+    json!({
+        "type": "object",
+        "description": format!("JSON representation of `opendut_types::{name}`."),
+        "additionalProperties": true,
+    })
+}
+
+pub fn specification_document() -> Value {
+    let mut paths = Map::new();
+
+    for operation in operations() {
+        let mut definition = json!({
+            "operationId": operation.operation_id,
+            "summary": operation.summary,
+            "tags": [operation.tag],
+            "responses": {
+                (operation.response_status): {
+                    "description": "Success",
+                    "content": { "application/json": { "schema": operation.response_schema } },
+                },
+                "default": {
+                    "description": "Failure",
+                    "content": { "application/json": { "schema": reference("Error") } },
+                },
+            },
+        });
+        if operation.path.contains("{id}") {
+            definition["parameters"] = json!([{
+                "name": "id",
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string", "format": "uuid" },
+            }]);
+        }
+        if let Some(schema) = operation.request_schema {
+            definition["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": reference(schema) } },
+            });
+        }
+
+        let path = paths.entry(operation.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        path[operation.method] = definition;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "openDuT CARL",
+            "description": "REST/JSON gateway to the cluster and peer management of CARL.",
+            "version": crate::app_info::CRATE_VERSION,
+        },
+        "servers": [{ "url": rest::PATH }],
+        "security": [{ "bearerAuth": [] }],
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+            },
+            "schemas": {
+                "ClusterConfiguration": resource_schema("cluster::ClusterConfiguration"),
+                "ClusterDeployment": resource_schema("cluster::ClusterDeployment"),
+                "PeerDescriptor": resource_schema("peer::PeerDescriptor"),
+                "DeviceDescriptor": resource_schema("topology::DeviceDescriptor"),
+                "SelfTestReport": resource_schema("peer::self_test::SelfTestReport"),
+                "ClusterIdResponse": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": { "id": { "type": "string", "format": "uuid" } },
+                },
+                "PeerIdResponse": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": { "id": resource_schema("peer::PeerId") },
+                },
+                "Error": {
+                    "type": "object",
+                    "required": ["message"],
+                    "properties": {
+                        "code": { "type": "string", "nullable": true, "description": "Stable identifier of the error, as listed in the error catalog of CARL." },
+                        "message": { "type": "string" },
+                    },
+                },
+            },
+        },
+    })
+}
+
+pub async fn specification() -> Json<Value> {
+    Json(specification_document())
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_describe_every_operation_in_the_specification() {
+        let document = specification_document();
+
+        for operation in operations() {
+            let definition = &document["paths"][operation.path][operation.method];
+            assert_that!(definition["operationId"].as_str(), some(eq(operation.operation_id)));
+        }
+        assert_that!(document["paths"]["/peers/{id}"]["get"]["parameters"][0]["name"].as_str(), some(eq("id")));
+        assert_that!(document["paths"]["/peers"]["post"]["requestBody"].is_object(), eq(true));
+    }
+}
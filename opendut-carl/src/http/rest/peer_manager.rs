@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use http::StatusCode;
+use serde::Serialize;
+
+use opendut_carl_api::carl::error_code::ErrorCode;
+use opendut_carl_api::carl::peer::GetPeerDescriptorError;
+use opendut_types::peer::self_test::SelfTestReport;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::actions;
+use crate::actions::{DeletePeerDescriptorParams, ListDevicesParams, ListPeerDescriptorsParams, StorePeerDescriptorParams};
+use crate::http::rest::{RestError, RestState};
+
+#[derive(Serialize)]
+pub struct PeerIdResponse {
+    pub id: PeerId,
+}
+
+fn parse_peer_id(id: &str) -> Result<PeerId, RestError> {
+    PeerId::try_from(id)
+        .map_err(|cause| RestError::invalid_request(cause.to_string()))
+}
+
+pub async fn list_peer_descriptors(
+    State(state): State<RestState>,
+) -> Result<Json<Vec<PeerDescriptor>>, RestError> {
+    let peers = actions::list_peer_descriptors(ListPeerDescriptorsParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+    }).await
+    .map_err(RestError::internal)?;
+    Ok(Json(peers))
+}
+
+pub async fn get_peer_descriptor(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<PeerDescriptor>, RestError> {
+    let peer_id = parse_peer_id(&id)?;
+
+    let peer = actions::list_peer_descriptors(ListPeerDescriptorsParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+    }).await
+    .map_err(|error| GetPeerDescriptorError::Internal { peer_id, cause: error.to_string() })
+    .and_then(|peers| peers.into_iter()
+        .find(|peer| peer.id == peer_id)
+        .ok_or(GetPeerDescriptorError::PeerNotFound { peer_id })
+    )
+    .map_err(RestError::from_error)?;
+    Ok(Json(peer))
+}
+
+/// Latest self-test report, which the peer uploaded via `opendut-edgar self-test`.
+pub async fn get_self_test_report(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<SelfTestReport>, RestError> {
+    let peer_id = parse_peer_id(&id)?;
+
+    let report = state.peer_messaging_broker.self_test_report(peer_id).await
+        .ok_or_else(|| RestError::not_found(ErrorCode::SelfTestReportNotFound, format!("Peer <{peer_id}> did not upload a self-test report.")))?;
+    Ok(Json(report))
+}
+
+pub async fn store_peer_descriptor(
+    State(state): State<RestState>,
+    Json(peer_descriptor): Json<PeerDescriptor>,
+) -> Result<impl IntoResponse, RestError> {
+    let id = actions::store_peer_descriptor(StorePeerDescriptorParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+        policy_engine: Arc::clone(&state.policy_engine),
+        vpn: Clone::clone(&state.vpn),
+        peer_descriptor,
+    }).await
+    .map_err(RestError::from_error)?;
+
+    Ok((StatusCode::CREATED, Json(PeerIdResponse { id })))
+}
+
+pub async fn delete_peer_descriptor(
+    State(state): State<RestState>,
+    Path(id): Path<String>,
+) -> Result<Json<PeerDescriptor>, RestError> {
+    let peer_id = parse_peer_id(&id)?;
+
+    let peer = actions::delete_peer_descriptor(DeletePeerDescriptorParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+        vpn: Clone::clone(&state.vpn),
+        peer: peer_id,
+        oidc_registration_client: state.oidc_registration_client.clone(),
+    }).await
+    .map_err(RestError::from_error)?;
+    Ok(Json(peer))
+}
+
+pub async fn list_devices(
+    State(state): State<RestState>,
+) -> Result<Json<Vec<DeviceDescriptor>>, RestError> {
+    let devices = actions::list_devices(ListDevicesParams {
+        resources_manager: Arc::clone(&state.resources_manager),
+    }).await
+    .map_err(RestError::internal)?;
+    Ok(Json(devices))
+}
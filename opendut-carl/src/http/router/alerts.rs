@@ -0,0 +1,19 @@
+use std::ops::Not;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use http::StatusCode;
+
+use crate::alerts::AlertsRef;
+
+pub async fn alerts(
+    State(alerts): State<AlertsRef>,
+) -> impl IntoResponse {
+
+    if alerts.is_enabled().not() {
+        return (StatusCode::NOT_FOUND, "Alerts are disabled. Enable them via the configuration field 'alerts.enabled'.").into_response();
+    }
+
+    Json(alerts.status()).into_response()
+}
@@ -0,0 +1,10 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::cluster::scheduler::{DeploymentQueueStatus, DeploymentSchedulerRef};
+
+pub async fn deployment_queue_status(
+    State(deployment_scheduler): State<DeploymentSchedulerRef>,
+) -> Json<DeploymentQueueStatus> {
+    Json(deployment_scheduler.status())
+}
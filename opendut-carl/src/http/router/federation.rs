@@ -0,0 +1,49 @@
+use std::ops::Not;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use http::{header, HeaderMap, StatusCode};
+use tracing::{error, warn};
+
+use crate::federation;
+use crate::http::state::FederationState;
+
+/// Read-only view of this instance's peers and clusters, retrieved by a central instance.
+pub async fn federation_view(
+    State(FederationState { federation, resources_manager }): State<FederationState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+
+    if federation.is_export_enabled().not() {
+        return (StatusCode::NOT_FOUND, "Federation is disabled. Enable exporting the view of this instance via the configuration field 'federation.export.enabled'.").into_response();
+    }
+
+    let bearer_token = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if federation.is_export_authorized(bearer_token).not() {
+        warn!("Rejected request for the federation view with missing or invalid token.");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    match federation::export_view(&resources_manager).await {
+        Ok(view) => Json(view).into_response(),
+        Err(cause) => {
+            error!("Failed to export federation view:\n  {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Views of the regional instances, as last retrieved by this central instance.
+pub async fn federation_regions(
+    State(FederationState { federation, .. }): State<FederationState>,
+) -> impl IntoResponse {
+
+    if federation.is_aggregating().not() {
+        return (StatusCode::NOT_FOUND, "No regional instances are configured. Configure them via the configuration field 'federation.region.urls'.").into_response();
+    }
+
+    Json(federation.regions()).into_response()
+}
@@ -2,8 +2,12 @@ use axum::extract::State;
 use axum::Json;
 use crate::http::state::LeaConfig;
 
+pub mod alerts;
 pub mod cleo;
+pub mod deployment_queue;
 pub mod edgar;
+pub mod federation;
+pub mod statistics;
 
 pub async fn lea_config(State(config): State<LeaConfig>) -> Json<LeaConfig> {
     Json(Clone::clone(&config))
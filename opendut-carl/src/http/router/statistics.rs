@@ -0,0 +1,26 @@
+use std::ops::Not;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use http::StatusCode;
+use tracing::error;
+
+use crate::http::state::StatisticsState;
+
+pub async fn usage_statistics(
+    State(StatisticsState { statistics, resources_manager }): State<StatisticsState>,
+) -> impl IntoResponse {
+
+    if statistics.is_enabled().not() {
+        return (StatusCode::NOT_FOUND, "Usage statistics are disabled. Enable them via the configuration field 'statistics.enabled'.").into_response();
+    }
+
+    match statistics.usage(&resources_manager).await {
+        Ok(usage) => Json(usage).into_response(),
+        Err(cause) => {
+            error!("Failed to aggregate usage statistics:\n  {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -8,11 +8,23 @@ use shadow_rs::formatcp;
 use url::Url;
 use opendut_auth::confidential::config::ConfidentialClientConfigData;
 
+use crate::alerts::AlertsRef;
+use crate::cluster::scheduler::DeploymentSchedulerRef;
+use crate::federation::FederationRef;
+use crate::http::rest::RestState;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::statistics::StatisticsRef;
+
 
 #[derive(Clone)]
 pub struct HttpState {
     pub lea_config: LeaConfig,
     pub carl_installation_directory: CarlInstallDirectory,
+    pub statistics: StatisticsState,
+    pub deployment_scheduler: DeploymentSchedulerRef,
+    pub federation: FederationState,
+    pub alerts: AlertsRef,
+    pub rest: RestState,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -65,7 +77,7 @@ impl FromRef<HttpState> for LeaConfig {
     }
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CarlInstallDirectory {
     pub path: PathBuf,
 }
@@ -84,3 +96,45 @@ impl FromRef<HttpState> for CarlInstallDirectory {
         Clone::clone(&app_state.carl_installation_directory)
     }
 }
+
+#[derive(Clone)]
+pub struct StatisticsState {
+    pub statistics: StatisticsRef,
+    pub resources_manager: ResourcesManagerRef,
+}
+
+impl FromRef<HttpState> for StatisticsState {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.statistics)
+    }
+}
+
+impl FromRef<HttpState> for DeploymentSchedulerRef {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.deployment_scheduler)
+    }
+}
+
+#[derive(Clone)]
+pub struct FederationState {
+    pub federation: FederationRef,
+    pub resources_manager: ResourcesManagerRef,
+}
+
+impl FromRef<HttpState> for FederationState {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.federation)
+    }
+}
+
+impl FromRef<HttpState> for AlertsRef {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.alerts)
+    }
+}
+
+impl FromRef<HttpState> for RestState {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.rest)
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use diesel::PgConnection;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::persistence::database;
+use crate::persistence::database::leader_lock;
+use crate::resources::storage::PersistenceOptions;
+use crate::shutdown::ShutdownOptions;
+
+/// Election of a leader among multiple CARL instances, which share a Postgres database, configured in the `leader.election` section.
+/// Only the leader changes resources, accepts peers and runs the periodic jobs, while followers serve reads and observe the resources.
+/// When the leader fails, a follower takes over within the election interval.
+#[derive(Clone)]
+pub enum LeaderElectionOptions {
+    Enabled {
+        /// Key of the advisory lock in Postgres. Instances with the same key compete for leadership.
+        lock_id: i64,
+        /// How often followers try to become the leader and the leader checks that it still holds the lock.
+        interval: Duration,
+        /// How often the resources are read from the database, for publishing changes made by the leader to the subscribers of followers.
+        synchronization_interval: Duration,
+    },
+    Disabled,
+}
+impl LeaderElectionOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("leader.election.enabled")?;
+
+        if enabled {
+            let lock_id = config.get::<i64>("leader.election.lock.id")?;
+            let interval = Duration::from_millis(
+                config.get::<u64>("leader.election.interval.ms")?
+            );
+            let synchronization_interval = Duration::from_millis(
+                config.get::<u64>("leader.election.synchronization.interval.ms")?
+            );
+            Ok(LeaderElectionOptions::Enabled { lock_id, interval, synchronization_interval })
+        } else {
+            Ok(LeaderElectionOptions::Disabled)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Leader,
+    Follower,
+}
+
+pub type LeadershipRef = Arc<Leadership>;
+
+/// The current role of this instance. Without leader election, the instance is always the leader.
+#[derive(Debug)]
+pub struct Leadership {
+    role: watch::Sender<Role>,
+}
+impl Leadership {
+    pub fn new(options: &LeaderElectionOptions) -> LeadershipRef {
+        let role = match options {
+            LeaderElectionOptions::Enabled { .. } => Role::Follower,
+            LeaderElectionOptions::Disabled => Role::Leader,
+        };
+        Arc::new(Self { role: watch::Sender::new(role) })
+    }
+
+    pub fn role(&self) -> Role {
+        *self.role.borrow()
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role() == Role::Leader
+    }
+
+    /// Notifies about changes of the role, e.g. for catching up with the changes of the previous leader after a promotion.
+    pub fn subscribe(&self) -> watch::Receiver<Role> {
+        self.role.subscribe()
+    }
+
+    /// Returns whether the role changed.
+    fn change(&self, role: Role) -> bool {
+        self.role.send_if_modified(|current| {
+            let changed = *current != role;
+            *current = role;
+            changed
+        })
+    }
+}
+
+/// Periodically competes for the advisory lock in the database, which designates the leader, if leader election is enabled.
+/// The lock is bound to a dedicated session, so it is released by Postgres, as soon as the leader stops or loses its connection.
+/// When demoted, the instance asks its peers to reconnect, so that they connect to the new leader, e.g. via the load balancer or the alternate endpoint of the shutdown options.
+pub fn spawn_election(
+    leadership: LeadershipRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    persistence_options: PersistenceOptions,
+    shutdown_options: ShutdownOptions,
+    options: LeaderElectionOptions,
+) -> Result<(), ElectionError> {
+    let LeaderElectionOptions::Enabled { lock_id, interval, .. } = options else {
+        return Ok(());
+    };
+    let PersistenceOptions::Enabled { database_connect_info, .. } = persistence_options else {
+        return Err(ElectionError::PersistenceDisabled);
+    };
+    if database_connect_info.url.scheme() == database::SQLITE_SCHEME {
+        return Err(ElectionError::Connect(database::ConnectError::LeaderElectionNotSupported { backend: database::SQLITE_SCHEME }));
+    }
+
+    info!("Competing for leadership via lock {lock_id} every {} ms. Serving reads only, until elected.", interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        let mut session: Option<PgConnection> = None;
+        loop {
+            interval.tick().await;
+
+            let mut connection = match session.take() {
+                Some(connection) => connection,
+                None => match database::connect_leader_session(&database_connect_info).await {
+                    Ok(connection) => connection,
+                    Err(cause) => {
+                        warn!("Failed to open session for leader election:\n  {cause}");
+                        continue;
+                    }
+                },
+            };
+
+            let holds_lock = if leadership.is_leader() {
+                leader_lock::check_session(&mut connection).map(|()| true)
+            } else {
+                leader_lock::try_acquire(&mut connection, lock_id)
+            };
+
+            match holds_lock {
+                Ok(true) => {
+                    if leadership.change(Role::Leader) {
+                        info!("Elected as leader. Accepting changes and peers.");
+                    }
+                    session = Some(connection);
+                }
+                Ok(false) => {
+                    session = Some(connection);
+                }
+                Err(cause) => {
+                    warn!("Lost session for leader election. The lock is released by the database:\n  {cause}");
+                    drop(connection);
+                    if leadership.change(Role::Follower) {
+                        warn!("No longer the leader. Serving reads only and asking peers to reconnect.");
+                        peer_messaging_broker.disconnect_connected(
+                            Clone::clone(&shutdown_options.reconnect_hint),
+                            shutdown_options.timeout,
+                        ).await;
+                    }
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ElectionError {
+    #[error("Leader election requires persistence to be enabled, as the leader is elected via the shared database")]
+    PersistenceDisabled,
+    #[error(transparent)]
+    Connect(#[from] database::ConnectError),
+}
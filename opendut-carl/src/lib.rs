@@ -12,6 +12,7 @@ use futures::future::BoxFuture;
 use futures::TryFutureExt;
 use ::http::{header::CONTENT_TYPE, Request};
 use pem::Pem;
+use tokio::sync::Notify;
 use tonic::transport::Server;
 use tonic_async_interceptor::async_interceptor;
 use tower::{make::Shared, steer::Steer, BoxError, ServiceExt};
@@ -28,24 +29,54 @@ use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 use opendut_util::{project, telemetry};
 use util::in_memory_cache::CustomInMemoryCache;
 
-use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer;
+use crate::actions::{ExecutorSecretsOptions, PeerSetupOptions, ProjectOptions, ResultsStorage, ResultsStorageOptions, ServiceAccountOptions, TraceCaptureOptions};
+use crate::alerts::{Alerts, AlertsOptions, AlertsRef};
+use crate::auth::grpc_auth_layer::{GrpcAuthenticationLayer, GrpcMethod};
+use crate::auth::read_only::ReadOnlyOptions;
+use crate::auth::permission::PermissionAuthorization;
 use crate::auth::json_web_key::JwkCacheValue;
+use crate::auth::service_account::ServiceAccountAuthenticator;
+use crate::bootstrap::{Bootstrap, BootstrapOptions, BootstrapRef};
 use crate::cluster::manager::{ClusterManager, ClusterManagerOptions, ClusterManagerRef};
-use crate::grpc::{ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerMessagingBrokerFacade};
-use crate::http::router;
-use crate::http::state::{CarlInstallDirectory, HttpState, LeaConfig, LeaIdentityProviderConfig};
+use crate::cluster::scheduler::{DeploymentScheduler, DeploymentSchedulerOptions, DeploymentSchedulerRef};
+use crate::diagnostics::{CompatibilityCheckOptions, ConsistencyCheckOptions, OidcClientReconciliationOptions};
+use crate::disaster_recovery::DisasterRecoveryOptions;
+use crate::disaster_recovery::artifacts::ArtifactBackupOptions;
+use crate::events::EventsOptions;
+use crate::federation::{Federation, FederationOptions, FederationRef};
+use crate::grpc::{BootstrapFacade, ClusterManagerFacade, DiagnosticsFacade, MetadataProviderFacade, PeerManagerFacade, PeerMessagingBrokerFacade, ProjectManagerFacade, ResourceObserverFacade, ResultsManagerFacade, ServiceAccountManagerFacade, SnapshotManagerFacade};
+use crate::http::{rest, router};
+use crate::http::rest::RestState;
+use crate::http::state::{CarlInstallDirectory, FederationState, HttpState, LeaConfig, LeaIdentityProviderConfig, StatisticsState};
+use crate::leadership::{LeaderElectionOptions, Leadership};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions, PeerMessagingBrokerRef};
+use crate::peer::command::PeerCommandAuthorization;
+use crate::peer::heartbeat::HeartbeatOptions;
+use crate::policy::{PolicyEngine, PolicyEngineRef, PolicyOptions};
 use crate::provisioning::cleo_script::CleoScript;
 use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
 use crate::resources::storage::PersistenceOptions;
+use crate::secrets::{SecretsBackend, SecretsRotationOptions};
+use crate::shutdown::ShutdownOptions;
+use crate::statistics::{Statistics, StatisticsOptions, StatisticsRef};
 use crate::vpn::Vpn;
 
 pub mod grpc;
 pub mod util;
 opendut_util::app_info!();
 
+pub use cli::cli;
+
 mod actions;
+mod alerts;
+mod bootstrap;
+mod cli;
 mod cluster;
+mod diagnostics;
+mod disaster_recovery;
+mod events;
+mod federation;
+mod leadership;
 mod metrics;
 pub mod persistence;
 mod peer;
@@ -54,6 +85,10 @@ pub mod settings;
 mod vpn;
 mod http;
 mod provisioning;
+mod shutdown;
+mod secrets;
+mod policy;
+mod statistics;
 mod auth;
 
 #[tracing::instrument]
@@ -82,6 +117,10 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
 pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
     info!("Started with configuration: {settings:?}");
 
+    let secrets_backend = SecretsBackend::load(&settings.config)?;
+    let (settings, secrets) = secrets::resolve(settings, &secrets_backend).await
+        .context("Error while retrieving secrets from backend.")?;
+
     let address: SocketAddr = {
         let host = settings.config.get_string("network.bind.host")?;
         let port = settings.config.get_int("network.bind.port")?;
@@ -115,12 +154,41 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
     let vpn = vpn::create(&settings.config)
         .context("Error while parsing VPN configuration.")?;
 
+    let bootstrap = Bootstrap::create(BootstrapOptions::load(&settings.config)?, Clone::clone(&vpn))
+        .context("Error while loading the state of the bootstrap.")?;
+
+    let leader_election_options = LeaderElectionOptions::load(&settings.config)?;
+    let leadership = Leadership::new(&leader_election_options);
+
+    disaster_recovery::spawn_shipping(
+        PersistenceOptions::load(&settings.config)?,
+        Arc::clone(&leadership),
+        DisasterRecoveryOptions::load(&settings.config)?,
+    );
+    disaster_recovery::artifacts::spawn_backups(
+        ArtifactBackupOptions::load(&settings.config)?,
+    );
+
     let resources_manager = {
         let resources_storage_options = PersistenceOptions::load(&settings.config)?;
 
         ResourcesManager::create(resources_storage_options).await
             .context("Creating ResourcesManager failed")?
     };
+    resources::storage::snapshot::spawn_writing(
+        Arc::clone(&resources_manager),
+        PersistenceOptions::load(&settings.config)?,
+    );
+    resources::synchronization::spawn_synchronization(
+        Arc::clone(&resources_manager),
+        Arc::clone(&leadership),
+        Clone::clone(&leader_election_options),
+    );
+
+    diagnostics::check_compatibility_on_startup(
+        Arc::clone(&resources_manager),
+        CompatibilityCheckOptions::load(&settings.config)?,
+    ).await;
 
     metrics::initialize_metrics_collection(Arc::clone(&resources_manager));
 
@@ -128,13 +196,83 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
         Arc::clone(&resources_manager),
         PeerMessagingBrokerOptions::load(&settings.config)?,
     );
+    leadership::spawn_election(
+        Arc::clone(&leadership),
+        Arc::clone(&peer_messaging_broker),
+        PersistenceOptions::load(&settings.config)?,
+        ShutdownOptions::load(&settings.config)?,
+        leader_election_options,
+    ).context("Error while setting up the leader election.")?;
+    peer::heartbeat::spawn_heartbeat_monitoring(
+        Arc::clone(&resources_manager),
+        Arc::clone(&peer_messaging_broker),
+        HeartbeatOptions::load(&settings.config)?,
+    );
+    diagnostics::spawn_consistency_check(
+        Arc::clone(&resources_manager),
+        Arc::clone(&peer_messaging_broker),
+        Arc::clone(&leadership),
+        ConsistencyCheckOptions::load(&settings.config)?,
+    );
+    diagnostics::spawn_oidc_client_reconciliation(
+        Arc::clone(&resources_manager),
+        oidc_registration_client.clone(),
+        Arc::clone(&leadership),
+        OidcClientReconciliationOptions::load(&settings.config)?,
+    );
+
+    let statistics = Statistics::new(StatisticsOptions::load(&settings.config)?);
+
+    let federation = Federation::new(FederationOptions::load(&settings.config)?);
+    federation::spawn_region_polling(Arc::clone(&federation), ca_certificate.clone())
+        .context("Error while setting up the retrieval of the views of regional instances.")?;
+    let deployment_scheduler = DeploymentScheduler::new(DeploymentSchedulerOptions::load(&settings.config)?);
+
+    let alerts = Alerts::new(AlertsOptions::load(&settings.config)?);
+    alerts::spawn_evaluation(
+        Arc::clone(&alerts),
+        Arc::clone(&resources_manager),
+        Arc::clone(&peer_messaging_broker),
+        Arc::clone(&deployment_scheduler),
+        Arc::clone(&leadership),
+        ca_certificate.clone(),
+    ).context("Error while setting up the evaluation of alert rules.")?;
+
+    events::spawn_publishing(
+        Arc::clone(&resources_manager),
+        Arc::clone(&leadership),
+        EventsOptions::load(&settings.config, carl_url.value())?,
+        deployment_scheduler.project_label().clone(),
+    );
+
     let cluster_manager = ClusterManager::create(
         Arc::clone(&resources_manager),
         Arc::clone(&peer_messaging_broker),
         Clone::clone(&vpn),
+        Arc::clone(&statistics),
+        Arc::clone(&deployment_scheduler),
+        Arc::clone(&leadership),
         ClusterManagerOptions::load(&settings.config)?,
     ).await;
 
+    let executor_secrets_options = ExecutorSecretsOptions::load(&settings.config)?;
+    let trace_capture_options = TraceCaptureOptions::load(&settings.config)?;
+    let service_account_options = ServiceAccountOptions::load(&settings.config)?;
+    let project_options = ProjectOptions::load(&settings.config)?;
+    let results_storage = ResultsStorage::new(ResultsStorageOptions::load(&settings.config)?)
+        .context("Failed to create client for the results storage.")?;
+    let policy_engine = Arc::new(PolicyEngine::new(PolicyOptions::load(&settings.config)?));
+    let peer_command_authorization = PeerCommandAuthorization::load(&settings.config)?;
+    let authorization = PermissionAuthorization::load(&settings.config)?;
+    let peer_setup_options = PeerSetupOptions::load(&settings.config)?;
+    let read_only_options = ReadOnlyOptions {
+        leadership: Some(Arc::clone(&leadership)),
+        ..ReadOnlyOptions::load(&settings.config)?
+    };
+    if read_only_options.enabled {
+        info!("Read-only mode is enabled. Requests, which create, change or delete resources, are rejected.");
+    }
+
     let grpc_auth_layer = match oidc_registration_client.clone() {
         None => GrpcAuthenticationLayer::AuthDisabled,
         Some(oidc_client_ref) => {
@@ -144,24 +282,61 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
                 issuer_url: oidc_client_ref.inner.config.issuer_url.clone(),
                 issuer_remote_url: oidc_client_ref.config.issuer_remote_url.clone(),
                 cache: jwk_cache,
+                anonymous_read: settings.config.get_bool("network.oidc.anonymous.read.enabled").unwrap_or(false),
+                service_accounts: ServiceAccountAuthenticator::new(Arc::clone(&resources_manager)),
             }
         }
     };
 
+    let server_handle = axum_server::Handle::new();
+    let shutdown_trigger = Arc::new(Notify::new());
+    let graceful_shutdown = shutdown::spawn_graceful_shutdown(
+        Clone::clone(&server_handle),
+        Arc::clone(&peer_messaging_broker),
+        Arc::clone(&resources_manager),
+        ShutdownOptions::load(&settings.config)?,
+        Arc::clone(&shutdown_trigger),
+    );
+    secrets::spawn_rotation_check(
+        secrets_backend,
+        secrets,
+        SecretsRotationOptions::load(&settings.config)?,
+        shutdown_trigger,
+    );
+
     info!("Server listening at {address}...");
-    spawn_server(
+    let server_result = spawn_server(
         address,
         tls_config,
+        server_handle,
         resources_manager,
         cluster_manager,
         peer_messaging_broker,
+        executor_secrets_options,
+        trace_capture_options,
+        service_account_options,
+        project_options,
+        results_storage,
+        policy_engine,
+        peer_command_authorization,
+        authorization,
+        peer_setup_options,
+        statistics,
+        deployment_scheduler,
+        federation,
+        alerts,
         vpn,
+        bootstrap,
         carl_url,
         settings.config,
         ca_certificate,
         oidc_registration_client,
         grpc_auth_layer,
-    ).await.unwrap();
+    ).await;
+
+    graceful_shutdown.complete().await;
+
+    server_result.unwrap();
 
     Ok(())
 }
@@ -172,10 +347,25 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
 fn spawn_server(
     address: SocketAddr,
     tls_config: TlsConfig,
+    server_handle: axum_server::Handle,
     resources_manager: ResourcesManagerRef,
     cluster_manager: ClusterManagerRef,
     peer_messaging_broker: PeerMessagingBrokerRef,
+    executor_secrets_options: ExecutorSecretsOptions,
+    trace_capture_options: TraceCaptureOptions,
+    service_account_options: ServiceAccountOptions,
+    project_options: ProjectOptions,
+    results_storage: ResultsStorage,
+    policy_engine: PolicyEngineRef,
+    peer_command_authorization: PeerCommandAuthorization,
+    authorization: PermissionAuthorization,
+    peer_setup_options: PeerSetupOptions,
+    statistics: StatisticsRef,
+    deployment_scheduler: DeploymentSchedulerRef,
+    federation: FederationRef,
+    alerts: AlertsRef,
     vpn: Vpn,
+    bootstrap: BootstrapRef,
     carl_url: ResourceHomeUrl,
     settings: config::Config,
     ca: Pem,
@@ -183,28 +373,76 @@ fn spawn_server(
     grpc_auth_layer: GrpcAuthenticationLayer,
 ) -> BoxFuture<'static, anyhow::Result<()>> {
     let oidc_enabled = settings.get_bool("network.oidc.enabled").unwrap_or(false);
+    let rest_gateway_enabled = settings.get_bool("serve.rest.enabled").unwrap_or(false);
+
+    let rest_state = RestState {
+        cluster_manager: Arc::clone(&cluster_manager),
+        resources_manager: Arc::clone(&resources_manager),
+        peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+        policy_engine: Arc::clone(&policy_engine),
+        vpn: Clone::clone(&vpn),
+        oidc_registration_client: oidc_registration_client.clone(),
+        auth: Clone::clone(&grpc_auth_layer),
+        read_only: Clone::clone(&read_only_options),
+    };
+
+    let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager), Arc::clone(&policy_engine), Clone::clone(&authorization), Clone::clone(&project_options));
+    let carl_installation_directory = CarlInstallDirectory::determine().expect("Could not determine installation directory.");
 
-    let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager));
-    let metadata_provider_facade = MetadataProviderFacade::new();
+    let metadata_provider_facade = MetadataProviderFacade::new(Clone::clone(&carl_installation_directory));
 
     let peer_manager_facade = PeerManagerFacade::new(
         Arc::clone(&resources_manager),
+        Arc::clone(&peer_messaging_broker),
+        policy_engine,
+        peer_command_authorization,
+        Clone::clone(&authorization),
+        Clone::clone(&project_options),
         vpn,
         Clone::clone(&carl_url.value()),
         ca.clone(),
         oidc_registration_client,
+        peer_setup_options,
     );
-    let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker));
+    let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(
+        Arc::clone(&peer_messaging_broker),
+        Arc::clone(&resources_manager),
+        executor_secrets_options,
+    );
+    let diagnostics_facade = DiagnosticsFacade::new(
+        Arc::clone(&resources_manager),
+        Arc::clone(&peer_messaging_broker),
+        opendut_util::telemetry::capture::trace_captures(),
+        trace_capture_options,
+        Clone::clone(&authorization),
+    );
+    let snapshot_manager_facade = SnapshotManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager), Clone::clone(&authorization));
+    let resource_observer_facade = ResourceObserverFacade::new(Arc::clone(&resources_manager));
+    let service_account_manager_facade = ServiceAccountManagerFacade::new(Arc::clone(&resources_manager), service_account_options);
+    let results_manager_facade = ResultsManagerFacade::new(Arc::clone(&resources_manager), results_storage, authorization, Clone::clone(&project_options));
+    let project_manager_facade = ProjectManagerFacade::new(Arc::clone(&resources_manager), project_options);
+    let bootstrap_facade = BootstrapFacade::new(bootstrap);
 
     let grpc = Server::builder()
+        .layer(GrpcMethod::layer())
         .layer(async_interceptor(move |request| {
             Clone::clone(&grpc_auth_layer).auth_interceptor(request)
         }))
+        .layer(async_interceptor(move |request| {
+            Clone::clone(&read_only_options).intercept(request)
+        }))
         .accept_http1(true) //gRPC-web uses HTTP1
+        .add_service(bootstrap_facade.into_grpc_service())
         .add_service(cluster_manager_facade.into_grpc_service())
+        .add_service(diagnostics_facade.into_grpc_service())
         .add_service(metadata_provider_facade.into_grpc_service())
         .add_service(peer_manager_facade.into_grpc_service())
         .add_service(peer_messaging_broker_facade.into_grpc_service())
+        .add_service(project_manager_facade.into_grpc_service())
+        .add_service(resource_observer_facade.into_grpc_service())
+        .add_service(results_manager_facade.into_grpc_service())
+        .add_service(service_account_manager_facade.into_grpc_service())
+        .add_service(snapshot_manager_facade.into_grpc_service())
         .into_service()
         .map_response(|response| response.map(axum::body::boxed))
         .boxed_clone();
@@ -226,14 +464,23 @@ fn spawn_server(
         None
     };
 
-    let carl_installation_directory = CarlInstallDirectory::determine().expect("Could not determine installation directory.");
-
     let app_state = HttpState {
         lea_config: LeaConfig {
             carl_url: carl_url.value(),
             idp_config: lea_idp_config,
         },
-        carl_installation_directory
+        carl_installation_directory,
+        statistics: StatisticsState {
+            statistics,
+            resources_manager: Arc::clone(&resources_manager),
+        },
+        deployment_scheduler,
+        federation: FederationState {
+            federation,
+            resources_manager: Arc::clone(&resources_manager),
+        },
+        alerts,
+        rest: rest_state,
     };
 
     let lea_index_html = lea_dir.join("index.html").clone();
@@ -257,9 +504,17 @@ fn spawn_server(
         ).expect("Could not create cleo install script.");
     }
 
+    let rest_gateway = if rest_gateway_enabled {
+        info!("REST gateway is enabled at {}.", rest::PATH);
+        rest::router(Clone::clone(&app_state))
+    } else {
+        axum::Router::new()
+    };
+
     let http = axum::Router::new()
         .fallback_service(
             axum::Router::new()
+                .merge(rest_gateway)
                 .nest_service(
                     "/api/licenses",
                     ServeDir::new(&licenses_dir)
@@ -268,6 +523,11 @@ fn spawn_server(
                 .route("/api/cleo/:architecture/download", get(router::cleo::download_cleo))
                 .route("/api/edgar/:architecture/download", get(router::edgar::download_edgar))
                 .route("/api/lea/config", get(router::lea_config))
+                .route("/api/statistics", get(router::statistics::usage_statistics))
+                .route("/api/cluster/deployments/queue", get(router::deployment_queue::deployment_queue_status))
+                .route(federation::VIEW_PATH, get(router::federation::federation_view))
+                .route("/api/federation/regions", get(router::federation::federation_regions))
+                .route("/api/alerts", get(router::alerts::alerts))
                 .nest_service(
                     "/",
                     ServeDir::new(&lea_dir)
@@ -296,12 +556,13 @@ fn spawn_server(
         TlsConfig::Enabled(tls_config) => {
             Box::pin(axum_server_dual_protocol::bind_dual_protocol(address, tls_config)
                 .set_upgrade(true) //http -> https
+                .handle(server_handle)
                 .serve(Shared::new(http_grpc))
                 .map_err(|cause| anyhow!(cause)))
         }
         TlsConfig::Disabled => {
             // Disable TLS in case a load balancer with TLS termination is present
-            Box::pin(axum_server::bind(address).serve(Shared::new(http_grpc)).map_err(From::from))
+            Box::pin(axum_server::bind(address).handle(server_handle).serve(Shared::new(http_grpc)).map_err(From::from))
         }
     }
 }
@@ -16,7 +16,5 @@ const BANNER: &str = r"
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    println!("{}", opendut_carl::app_info::formatted_with_banner(BANNER));
-
-    opendut_carl::create_with_telemetry(opendut_util::settings::Config::default()).await
+    opendut_carl::cli(BANNER).await
 }
@@ -1,16 +1,17 @@
-use opentelemetry::global;
+use opentelemetry::{global, KeyValue};
 use tracing::trace;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
 use opendut_types::peer::PeerDescriptor;
 use opendut_types::peer::state::PeerState;
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
-use crate::resources::storage::ResourcesStorageApi;
+use crate::resources::storage::ReadConsistency;
 
 pub fn initialize_metrics_collection(
     resources_manager: ResourcesManagerRef,
 ) {
     let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
+    let resources_manager_for_subscriptions = ResourcesManagerRef::clone(&resources_manager);
 
     let deployed_clusters_gauge = meter.u64_observable_gauge("deployed_clusters").init();
     let configured_clusters_gauge = meter.u64_observable_gauge("configured_clusters").init();
@@ -18,23 +19,21 @@ pub fn initialize_metrics_collection(
     let connected_peers_gauge = meter.u64_observable_gauge("connected_peers").init();
     meter.register_callback(&[deployed_clusters_gauge.as_any(), configured_clusters_gauge.as_any(), registered_peers_gauge.as_any(), connected_peers_gauge.as_any()], move |observer| {
 
-        let metrics: Result<Metrics, PersistenceError> = futures::executor::block_on(
-            resources_manager.resources(|resources| {
-                Ok(Metrics {
-                    deployed_clusters: resources.list::<ClusterDeployment>()?,
-                    configured_clusters: resources.list::<ClusterConfiguration>()?,
-                    registered_peers: resources.list::<PeerDescriptor>()?,
-                    connected_peers: {
-                        let registered_peers = resources.list::<PeerState>()?;
-                        let mut online_peers: Vec<PeerState> = vec![];
-                        registered_peers.iter().for_each(|state| {
-                            if let PeerState::Up { .. } = state { online_peers.push(state.clone()) }
-                        });
-                        online_peers
-                    },
-                })
+        let metrics: Result<Metrics, PersistenceError> = futures::executor::block_on(async {
+            Ok(Metrics {
+                deployed_clusters: resources_manager.list_with::<ClusterDeployment>(ReadConsistency::Eventual).await?,
+                configured_clusters: resources_manager.list_with::<ClusterConfiguration>(ReadConsistency::Eventual).await?,
+                registered_peers: resources_manager.list_with::<PeerDescriptor>(ReadConsistency::Eventual).await?,
+                connected_peers: {
+                    let registered_peers = resources_manager.list_with::<PeerState>(ReadConsistency::Eventual).await?;
+                    let mut online_peers: Vec<PeerState> = vec![];
+                    registered_peers.iter().for_each(|state| {
+                        if let PeerState::Up { .. } = state { online_peers.push(state.clone()) }
+                    });
+                    online_peers
+                },
             })
-        );
+        });
 
         match metrics {
             Ok(metrics) => match ObservableMetrics::try_from(metrics) {
@@ -50,6 +49,28 @@ pub fn initialize_metrics_collection(
         }
 
     }).expect("could not register metrics collection callback for CARL");
+
+    initialize_subscription_metrics_collection(&meter, resources_manager_for_subscriptions);
+}
+
+fn initialize_subscription_metrics_collection(meter: &opentelemetry::metrics::Meter, resources_manager: ResourcesManagerRef) {
+    let subscribers_gauge = meter.u64_observable_gauge("subscription_subscribers").init();
+    let queued_events_gauge = meter.u64_observable_gauge("subscription_queued_events").init();
+    let lagged_events_gauge = meter.u64_observable_gauge("subscription_lagged_events").init();
+    let stale_subscriptions_gauge = meter.u64_observable_gauge("subscription_stale_subscriptions").init();
+    meter.register_callback(&[subscribers_gauge.as_any(), queued_events_gauge.as_any(), lagged_events_gauge.as_any(), stale_subscriptions_gauge.as_any()], move |observer| {
+
+        let reports = futures::executor::block_on(resources_manager.subscription_health());
+
+        for report in reports {
+            let attributes = [KeyValue::new("resource", report.resource)];
+            observer.observe_u64(&subscribers_gauge, report.subscribers as u64, &attributes);
+            observer.observe_u64(&queued_events_gauge, report.queued_events as u64, &attributes);
+            observer.observe_u64(&lagged_events_gauge, report.lagged_events, &attributes);
+            observer.observe_u64(&stale_subscriptions_gauge, report.stale_subscriptions, &attributes);
+        }
+
+    }).expect("could not register subscription metrics collection callback for CARL");
 }
 
 struct Metrics {
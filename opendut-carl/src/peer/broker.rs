@@ -1,22 +1,40 @@
-use std::collections::HashMap;
-use std::net::IpAddr;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::ops::Not;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::mpsc::error::SendError;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex, Notify, RwLock};
 use tracing::{debug, error, info, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
-use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
-use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration, Downstream, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{Pong, Probe, ProbeResponse};
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration, ApplyPeerConfigurationDelta, Disconnect, Downstream, ReconnectHint, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{network_readiness, NetworkNotReady, NetworkReadiness};
+use opendut_carl_api::proto::services::peer_messaging_broker::{test_event, TestEvent, TestStopped};
+use opendut_carl_api::proto::services::peer_messaging_broker::{component_status, ComponentFailed, ComponentSkipped, ComponentStatus, PeerConfigurationStatus};
+use opendut_carl_api::proto::services::peer_messaging_broker::EnergyMeasurement;
+use opendut_carl_api::proto::services::peer_messaging_broker::GoingOffline;
+use opendut_carl_api::proto::services::peer_messaging_broker::{NetworkManagerFinding, NetworkManagerInterference};
+use opendut_carl_api::proto::services::peer_messaging_broker::{StorageCategoryUsage, StorageUsage};
+use opendut_carl_api::proto::services::peer_messaging_broker::{peer_command_result, PeerCommand as PeerCommandMessage, PeerCommandFailed, PeerCommandResult, PeerCommandSucceeded};
+use opendut_carl_api::carl::diagnostics::PeerStreamInfo;
+use opendut_carl_api::carl::peer::PeerCommand;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::configuration::delta::{PeerConfigurationDelta, PeerConfigurationVersion};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::self_test::SelfTestReport;
+use opendut_types::peer::setup::PeerSetupIssuance;
 use opendut_types::peer::state::{PeerState, PeerUpState};
 use opendut_types::peer::PeerId;
 
+use crate::cluster::deployment_status;
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::resources::storage::ResourcesStorageApi;
@@ -28,9 +46,59 @@ pub struct PeerMessagingBroker {
     resources_manager: ResourcesManagerRef,
     peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>,
     options: PeerMessagingBrokerOptions,
+    shutting_down: AtomicBool,
+    /// When a message was last received from a peer. Kept after the peer disconnected, to tell when it was last reachable.
+    last_seen: Arc<RwLock<HashMap<PeerId, SystemTime>>>,
+    /// Reasons, which peers announced for going offline. Removed, when the peer connects again.
+    offline_announcements: Arc<RwLock<HashMap<PeerId, String>>>,
+    pending_probes: PendingProbesRef,
+    next_probe_id: AtomicU64,
+    pending_commands: PendingCommandsRef,
+    next_command_id: AtomicU64,
+    configuration_reports: broadcast::Sender<PeerId>,
+    /// Last PeerConfiguration sent to each peer, which the next delta is computed from.
+    sent_configurations: SentConfigurationsRef,
+    /// Latest self-test report uploaded by each peer. Kept in memory only, as peers upload a new one with each self-test.
+    self_test_reports: Arc<RwLock<HashMap<PeerId, SelfTestReport>>>,
+}
+type SentConfigurationsRef = Arc<Mutex<HashMap<PeerId, SentPeerConfiguration>>>;
+struct SentPeerConfiguration {
+    version: PeerConfigurationVersion,
+    configuration: PeerConfiguration,
+}
+type PendingProbesRef = Arc<Mutex<HashMap<u64, PendingProbe>>>;
+struct PendingProbe {
+    peer_id: PeerId,
+    response: oneshot::Sender<()>,
+}
+type PendingCommandsRef = Arc<Mutex<HashMap<u64, PendingCommand>>>;
+struct PendingCommand {
+    peer_id: PeerId,
+    response: oneshot::Sender<peer_command_result::Outcome>,
 }
 struct PeerMessagingRef {
     downstream: mpsc::Sender<Downstream>,
+    stream: PeerStreamRef,
+}
+type PeerStreamRef = Arc<PeerStream>;
+/// Connection metadata and message counters of the messaging stream of one peer.
+struct PeerStream {
+    remote_host: IpAddr,
+    connection: PeerConnection,
+    opened_at: SystemTime,
+    messages_received: AtomicU64,
+    messages_sent: AtomicU64,
+    /// Notified to end the stream from CARL's side.
+    terminate: Notify,
+}
+
+/// Metadata of the connection, which a peer opens its messaging stream over.
+#[derive(Clone, Debug, Default)]
+pub struct PeerConnection {
+    /// Address of the peer as seen by CARL, which may differ from the remote host the peer reports, e.g. behind a NAT.
+    pub remote_address: Option<SocketAddr>,
+    /// Version of EDGAR, which the peer reported when opening the stream. Not reported by older versions.
+    pub version: Option<String>,
 }
 
 impl PeerMessagingBroker {
@@ -39,18 +107,27 @@ impl PeerMessagingBroker {
             resources_manager,
             peers: Default::default(),
             options,
+            shutting_down: AtomicBool::new(false),
+            last_seen: Default::default(),
+            offline_announcements: Default::default(),
+            pending_probes: Default::default(),
+            next_probe_id: AtomicU64::new(0),
+            pending_commands: Default::default(),
+            next_command_id: AtomicU64::new(0),
+            configuration_reports: broadcast::channel(1024).0,
+            sent_configurations: Default::default(),
+            self_test_reports: Default::default(),
         })
     }
 
     #[tracing::instrument(skip(self), level="trace")]
     pub async fn send_to_peer(&self, peer_id: PeerId, message: downstream::Message) -> Result<(), Error> {
-        let downstream = {
+        let (downstream, stream) = {
             let peers = self.peers.read().await;
             peers.get(&peer_id)
-                .map(|peer| &peer.downstream)
-                .cloned()
+                .map(|peer| (Clone::clone(&peer.downstream), Arc::clone(&peer.stream)))
+                .ok_or(Error::PeerNotFound(peer_id))?
         };
-        let downstream = downstream.ok_or(Error::PeerNotFound(peer_id))?;
 
         let context = {
             let mut context = TracingContext { values: Default::default() };
@@ -64,6 +141,7 @@ impl PeerMessagingBroker {
             context,
             message: Some(message)
         }).await.map_err(Error::DownstreamSend)?;
+        stream.messages_sent.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
 
@@ -71,16 +149,35 @@ impl PeerMessagingBroker {
         &self,
         peer_id: PeerId,
         remote_host: IpAddr,
+        connection: PeerConnection,
     ) -> Result<(mpsc::Sender<upstream::Message>, mpsc::Receiver<Downstream>), OpenError> {
 
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(OpenError::ShuttingDown { peer_id });
+        }
+
+        self.check_setup(peer_id).await?;
+
         let (tx_inbound, mut rx_inbound) = mpsc::channel::<upstream::Message>(1024);
         let (tx_outbound, rx_outbound) = mpsc::channel::<Downstream>(1024);
 
+        let stream = Arc::new(PeerStream {
+            remote_host,
+            connection,
+            opened_at: SystemTime::now(),
+            messages_received: AtomicU64::new(0),
+            messages_sent: AtomicU64::new(0),
+            terminate: Notify::new(),
+        });
+
         let peer_messaging_ref = PeerMessagingRef {
             downstream: Clone::clone(&tx_outbound),
+            stream: Arc::clone(&stream),
         };
 
         self.peers.write().await.insert(peer_id, peer_messaging_ref);
+        self.last_seen.write().await.insert(peer_id, SystemTime::now());
+        self.offline_announcements.write().await.remove(&peer_id);
 
         fn new_peer_up_state(remote_host: IpAddr) -> PeerState {
             PeerState::Up { inner: PeerUpState::Available, remote_host }
@@ -100,7 +197,7 @@ impl PeerMessagingBroker {
                         debug!("Peer <{peer_id}> opened stream which was previously down.");
                         Ok(new_peer_up_state(remote_host))
                     }
-                    PeerState::Up { .. } => {
+                    PeerState::Up { .. } | PeerState::Unreachable { .. } => {
                         error!("Peer <{peer_id}> opened stream which was already connected.");
                         Err(OpenError::PeerAlreadyConnected { peer_id })
                     }
@@ -121,13 +218,12 @@ impl PeerMessagingBroker {
             .map_err(|source| OpenError::Persistence { peer_id, source })?
             .unwrap_or_default(); //PeerConfiguration is not persisted across restarts
 
-        self.send_to_peer(peer_id, downstream::Message::ApplyPeerConfiguration(
-            ApplyPeerConfiguration {
-                old_configuration: Some(old_peer_configuration.into()),
-                configuration: Some(peer_configuration.into()),
-            }
-        )).await
-        .map_err(|cause| OpenError::SendApplyPeerConfiguration { peer_id, cause: cause.to_string() })?;
+        let message = { //always send the full configuration, since the peer may have lost its previous configuration
+            let mut sent_configurations = self.sent_configurations.lock().await;
+            full_peer_configuration_message(peer_id, old_peer_configuration, peer_configuration, &mut sent_configurations)
+        };
+        self.send_to_peer(peer_id, message).await
+            .map_err(|cause| OpenError::SendApplyPeerConfiguration { peer_id, cause: cause.to_string() })?;
 
 
         let timeout_duration = self.options.peer_disconnect_timeout;
@@ -135,19 +231,44 @@ impl PeerMessagingBroker {
         {
             let peers = Arc::clone(&self.peers);
             let resources_manager = Arc::clone(&self.resources_manager);
+            let last_seen = Arc::clone(&self.last_seen);
+            let offline_announcements = Arc::clone(&self.offline_announcements);
+            let pending_probes = Arc::clone(&self.pending_probes);
+            let pending_commands = Arc::clone(&self.pending_commands);
+            let configuration_reports = Clone::clone(&self.configuration_reports);
+            let sent_configurations = Arc::clone(&self.sent_configurations);
 
             tokio::spawn(async move {
                 loop {
-                    let received = tokio::time::timeout(timeout_duration, rx_inbound.recv()).await;
+                    let received = tokio::select! {
+                        received = tokio::time::timeout(timeout_duration, rx_inbound.recv()) => received,
+                        _ = stream.terminate.notified() => {
+                            warn!("Terminating stream of peer <{peer_id}> on request.");
+                            break;
+                        }
+                    };
 
                     match received {
-                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound).await,
+                        Ok(Some(message)) => {
+                            stream.messages_received.fetch_add(1, Ordering::Relaxed);
+                            last_seen.write().await.insert(peer_id, SystemTime::now());
+                            if let upstream::Message::GoingOffline(GoingOffline { reason }) = &message {
+                                offline_announcements.write().await.insert(peer_id, Clone::clone(reason));
+                            }
+                            handle_stream_message(message, peer_id, &tx_outbound, &stream, &pending_probes, &pending_commands, &configuration_reports, &resources_manager, &sent_configurations).await
+                        }
                         Ok(None) => {
-                            info!("Peer <{peer_id}> disconnected!");
+                            match offline_announcements.read().await.get(&peer_id) {
+                                Some(reason) => info!("Peer <{peer_id}> disconnected after announcing to go offline due to {reason}."),
+                                None => info!("Peer <{peer_id}> disconnected!"),
+                            }
                             break;
                         }
                         Err(cause) => {
-                            error!("No message from peer <{peer_id}> within {} ms:\n  {cause}", timeout_duration.as_millis());
+                            match offline_announcements.read().await.get(&peer_id) {
+                                Some(reason) => info!("Peer <{peer_id}> stopped sending messages after announcing to go offline due to {reason}."),
+                                None => error!("No message from peer <{peer_id}> within {} ms:\n  {cause}", timeout_duration.as_millis()),
+                            }
                             break;
                         }
                     }
@@ -160,6 +281,236 @@ impl PeerMessagingBroker {
         Ok((tx_inbound, rx_outbound))
     }
 
+    /// Sends the configuration to the peer. For large configurations, only the changes since the previously sent configuration are sent,
+    /// if they comprise fewer parameters than the whole configuration.
+    #[tracing::instrument(skip_all, fields(%peer_id), level="trace")]
+    pub async fn send_peer_configuration(
+        &self,
+        peer_id: PeerId,
+        old_peer_configuration: OldPeerConfiguration,
+        peer_configuration: PeerConfiguration,
+    ) -> Result<(), Error> {
+        let message = {
+            let mut sent_configurations = self.sent_configurations.lock().await;
+
+            let threshold = self.options.configuration_delta_threshold;
+            let parameter_count = parameter_count(&peer_configuration);
+
+            let delta = sent_configurations.get(&peer_id)
+                .filter(|_| threshold > 0 && parameter_count >= threshold)
+                .and_then(|previous| PeerConfigurationDelta::between(&previous.configuration, &peer_configuration, previous.version, previous.version + 1))
+                .filter(|delta| delta.len() < parameter_count);
+
+            match delta {
+                Some(delta) => {
+                    debug!("Sending delta of {} parameters for configuration version {} to peer <{peer_id}>.", delta.len(), delta.version);
+                    sent_configurations.insert(peer_id, SentPeerConfiguration { version: delta.version, configuration: peer_configuration });
+
+                    downstream::Message::ApplyPeerConfigurationDelta(ApplyPeerConfigurationDelta {
+                        old_configuration: Some(old_peer_configuration.into()),
+                        delta: Some(delta.into()),
+                    })
+                }
+                None => full_peer_configuration_message(peer_id, old_peer_configuration, peer_configuration, &mut sent_configurations),
+            }
+        };
+
+        self.send_to_peer(peer_id, message).await
+    }
+
+    /// Connection metadata and message counters of the streams of all connected peers.
+    pub async fn list_streams(&self) -> Vec<PeerStreamInfo> {
+        let last_seen = self.last_seen.read().await;
+        self.peers.read().await
+            .iter()
+            .map(|(peer_id, peer)| {
+                let stream = &peer.stream;
+                PeerStreamInfo {
+                    peer_id: *peer_id,
+                    remote_host: stream.remote_host,
+                    remote_address: stream.connection.remote_address,
+                    version: Clone::clone(&stream.connection.version),
+                    opened_at: stream.opened_at,
+                    last_seen: last_seen.get(peer_id).cloned(),
+                    messages_received: stream.messages_received.load(Ordering::Relaxed),
+                    messages_sent: stream.messages_sent.load(Ordering::Relaxed),
+                }
+            })
+            .collect()
+    }
+
+    /// Ends the stream of the peer from CARL's side and marks the peer as down, e.g. to get rid of a half-open connection.
+    /// The peer notices the end of its stream and connects again.
+    /// Rejects peers, whose Setup-String was revoked or expired before it was used. Otherwise, records the first use of the Setup-String.
+    async fn check_setup(&self, peer_id: PeerId) -> Result<(), OpenError> {
+        self.resources_manager.resources_mut(|resources| {
+            let issuance = resources.get::<PeerSetupIssuance>(peer_id)
+                .map_err(|source| OpenError::Persistence { peer_id, source })?;
+
+            let Some(issuance) = issuance else {
+                return Ok(()); //Setup-String was generated before they were recorded
+            };
+
+            let now = SystemTime::now();
+            if issuance.is_revoked() {
+                return Err(OpenError::SetupRevoked { peer_id });
+            }
+            if issuance.is_expired(now) {
+                return Err(OpenError::SetupExpired { peer_id });
+            }
+            if issuance.used_at.is_none() {
+                debug!("Peer <{peer_id}> connected with its Setup-String for the first time.");
+                resources.insert(peer_id, PeerSetupIssuance { used_at: Some(now), ..issuance })
+                    .map_err(|source| OpenError::Persistence { peer_id, source })?;
+            }
+            Ok(())
+        }).await
+        .map_err(|source| OpenError::Persistence { peer_id, source })?
+    }
+
+    pub async fn terminate_stream(&self, peer_id: PeerId) -> Result<(), TerminateError> {
+        let peers = self.peers.read().await;
+        let peer = peers.get(&peer_id)
+            .ok_or(TerminateError::PeerNotConnected { peer_id })?;
+        peer.stream.terminate.notify_one();
+        Ok(())
+    }
+
+    pub async fn connected_peers(&self) -> HashSet<PeerId> {
+        self.peers.read().await
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Notifies about each peer, which reported the outcome of applying its configuration.
+    pub fn subscribe_configuration_reports(&self) -> broadcast::Receiver<PeerId> {
+        self.configuration_reports.subscribe()
+    }
+
+    pub async fn last_seen(&self, peer_id: PeerId) -> Option<SystemTime> {
+        self.last_seen.read().await
+            .get(&peer_id)
+            .cloned()
+    }
+
+    pub async fn store_self_test_report(&self, report: SelfTestReport) {
+        self.self_test_reports.write().await
+            .insert(report.peer_id, report);
+    }
+
+    pub async fn self_test_report(&self, peer_id: PeerId) -> Option<SelfTestReport> {
+        self.self_test_reports.read().await
+            .get(&peer_id)
+            .cloned()
+    }
+
+    /// Reason, which the peer announced for going offline, if it did so before its stream ended and did not connect again since.
+    pub async fn offline_announcement(&self, peer_id: PeerId) -> Option<String> {
+        self.offline_announcements.read().await
+            .get(&peer_id)
+            .cloned()
+    }
+
+    /// Sends a probe to the peer over its messaging stream and waits for the response.
+    /// Returns the round-trip time, which includes the time the peer needed to process the probe.
+    pub async fn probe(&self, peer_id: PeerId, timeout: Duration) -> Result<Duration, ProbeError> {
+        let probe_id = self.next_probe_id.fetch_add(1, Ordering::SeqCst);
+        let (tx_response, rx_response) = oneshot::channel();
+
+        self.pending_probes.lock().await
+            .insert(probe_id, PendingProbe { peer_id, response: tx_response });
+
+        let started = Instant::now();
+        let result = async {
+            self.send_to_peer(peer_id, downstream::Message::Probe(Probe { id: probe_id })).await
+                .map_err(|cause| match cause {
+                    Error::PeerNotFound(peer_id) => ProbeError::PeerNotConnected { peer_id },
+                    cause => ProbeError::Send { peer_id, cause: cause.to_string() },
+                })?;
+
+            tokio::time::timeout(timeout, rx_response).await
+                .map_err(|_| ProbeError::Timeout { peer_id, timeout })?
+                .map_err(|_| ProbeError::PeerNotConnected { peer_id })?;
+
+            Ok(started.elapsed())
+        }.await;
+
+        self.pending_probes.lock().await
+            .remove(&probe_id);
+
+        result
+    }
+
+    /// Sends a command to the peer over its messaging stream and waits for the peer to report the result.
+    pub async fn send_command(&self, peer_id: PeerId, command: PeerCommand, timeout: Duration) -> Result<CommandOutput, CommandError> {
+        let command_id = self.next_command_id.fetch_add(1, Ordering::SeqCst);
+        let (tx_response, rx_response) = oneshot::channel();
+
+        self.pending_commands.lock().await
+            .insert(command_id, PendingCommand { peer_id, response: tx_response });
+
+        let result = async {
+            let message = downstream::Message::PeerCommand(PeerCommandMessage { id: command_id, kind: Some(command.into()) });
+            self.send_to_peer(peer_id, message).await
+                .map_err(|cause| match cause {
+                    Error::PeerNotFound(peer_id) => CommandError::PeerNotConnected { peer_id },
+                    cause => CommandError::Send { peer_id, cause: cause.to_string() },
+                })?;
+
+            let outcome = tokio::time::timeout(timeout, rx_response).await
+                .map_err(|_| CommandError::Timeout { peer_id, timeout })?
+                .map_err(|_| CommandError::PeerNotConnected { peer_id })?;
+
+            match outcome {
+                peer_command_result::Outcome::Succeeded(PeerCommandSucceeded { output, attachment }) => Ok(CommandOutput { output, attachment }),
+                peer_command_result::Outcome::Failed(PeerCommandFailed { cause }) => Err(CommandError::Failed { peer_id, cause }),
+            }
+        }.await;
+
+        self.pending_commands.lock().await
+            .remove(&command_id);
+
+        result
+    }
+
+    /// Rejects new streams and asks all connected peers to disconnect, handing them the hint when and where to reconnect.
+    /// Peers, which did not close their stream within the timeout, are removed nonetheless, so that no peer remains marked as up.
+    pub async fn disconnect_all(&self, reconnect_hint: ReconnectHint, timeout: Duration) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.disconnect_connected(reconnect_hint, timeout).await;
+    }
+
+    /// Asks the connected peers to disconnect, without rejecting new streams, e.g. when this instance is no longer the leader and peers should reconnect to the new one.
+    pub async fn disconnect_connected(&self, reconnect_hint: ReconnectHint, timeout: Duration) {
+        let connected_peers = self.connected_peers().await;
+        info!("Asking {} connected peers to disconnect.", connected_peers.len());
+
+        for peer_id in &connected_peers {
+            let message = downstream::Message::Disconnect(Disconnect {
+                reconnect_hint: Some(Clone::clone(&reconnect_hint)),
+            });
+            let _ignore_result = self.send_to_peer(*peer_id, message).await
+                .inspect_err(|cause| warn!("Failed to ask peer <{peer_id}> to disconnect:\n  {cause}"));
+        }
+
+        let disconnected = tokio::time::timeout(timeout, async {
+            while self.peers.read().await.is_empty().not() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }).await;
+
+        if disconnected.is_err() {
+            let remaining_peers = self.connected_peers().await;
+            warn!("{} peers did not disconnect within {} ms. Removing them.", remaining_peers.len(), timeout.as_millis());
+
+            for peer_id in remaining_peers {
+                self.remove_peer(peer_id).await
+                    .unwrap_or_else(|cause| error!("Error while removing peer <{peer_id}> after asking it to disconnect:\n  {cause}"));
+            }
+        }
+    }
+
     pub async fn remove_peer(&self, peer_id: PeerId) -> Result<(), RemovePeerError> {
         Self::remove_peer_impl(peer_id, Arc::clone(&self.resources_manager), Arc::clone(&self.peers)).await
     }
@@ -182,10 +533,66 @@ impl PeerMessagingBroker {
     }
 }
 
+fn parameter_count(peer_configuration: &PeerConfiguration) -> usize {
+    peer_configuration.executors.len() + peer_configuration.ethernet_bridges.len() + peer_configuration.restbus_channels.len() + peer_configuration.cluster_hooks.len()
+}
+
+/// Remembers the configuration as the base for subsequent deltas and wraps it for sending it in full.
+fn full_peer_configuration_message(
+    peer_id: PeerId,
+    old_peer_configuration: OldPeerConfiguration,
+    peer_configuration: PeerConfiguration,
+    sent_configurations: &mut HashMap<PeerId, SentPeerConfiguration>,
+) -> downstream::Message {
+    let version = sent_configurations.get(&peer_id)
+        .map(|previous| previous.version + 1)
+        .unwrap_or(1);
+
+    sent_configurations.insert(peer_id, SentPeerConfiguration { version, configuration: Clone::clone(&peer_configuration) });
+
+    downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
+        old_configuration: Some(old_peer_configuration.into()),
+        configuration: Some(peer_configuration.into()),
+        configuration_version: version,
+    })
+}
+
+async fn resend_peer_configuration(
+    peer_id: PeerId,
+    tx_outbound: &mpsc::Sender<Downstream>,
+    stream: &PeerStream,
+    resources_manager: &ResourcesManagerRef,
+    sent_configurations: &SentConfigurationsRef,
+) -> Result<(), Error> {
+    let (old_peer_configuration, peer_configuration) = resources_manager.resources(|resources| {
+        Ok((
+            resources.get::<OldPeerConfiguration>(peer_id)?.unwrap_or_default(),
+            resources.get::<PeerConfiguration>(peer_id)?.unwrap_or_default(),
+        ))
+    }).await
+    .map_err(|cause| Error::Other { message: format!("Failed to load configuration of peer <{peer_id}>: {cause}") })?;
+
+    let message = {
+        let mut sent_configurations = sent_configurations.lock().await;
+        full_peer_configuration_message(peer_id, old_peer_configuration, peer_configuration, &mut sent_configurations)
+    };
+    tx_outbound.send(Downstream { message: Some(message), context: None }).await
+        .map_err(Error::DownstreamSend)?;
+    stream.messages_sent.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_stream_message(
     message: upstream::Message,
     peer_id: PeerId,
     tx_outbound: &mpsc::Sender<Downstream>,
+    stream: &PeerStream,
+    pending_probes: &PendingProbesRef,
+    pending_commands: &PendingCommandsRef,
+    configuration_reports: &broadcast::Sender<PeerId>,
+    resources_manager: &ResourcesManagerRef,
+    sent_configurations: &SentConfigurationsRef,
 ) {
     match message {
         upstream::Message::Ping(_) => {
@@ -193,8 +600,225 @@ async fn handle_stream_message(
             let context = None;
             let _ignore_result =
                 tx_outbound.send(Downstream{message:Some(message), context}).await
+                    .inspect(|_| { stream.messages_sent.fetch_add(1, Ordering::Relaxed); })
                     .inspect_err(|cause| warn!("Failed to send ping to peer <{peer_id}>:\n  {cause}"));
         },
+        upstream::Message::NetworkReadiness(readiness) => {
+            log_network_readiness(readiness, peer_id);
+        }
+        upstream::Message::TestEvent(test_event) => {
+            log_test_event(test_event, peer_id);
+        }
+        upstream::Message::PeerConfigurationStatus(status) => {
+            let outcome = configuration_outcome(&status);
+            log_peer_configuration_status(status, peer_id);
+            let _ignore_result = deployment_status::record_peer_report(resources_manager, peer_id, outcome).await
+                .inspect_err(|cause| warn!("Failed to record the configuration report of peer <{peer_id}> in the deployment status of its clusters:\n  {cause}"));
+            let _ignore_result = configuration_reports.send(peer_id); //no one may be subscribed
+        }
+        upstream::Message::EnergyMeasurement(measurement) => {
+            log_energy_measurement(measurement, peer_id);
+        }
+        upstream::Message::NetworkManagerInterference(interference) => {
+            log_network_manager_interference(interference, peer_id);
+        }
+        upstream::Message::StorageUsage(usage) => {
+            log_storage_usage(usage, peer_id);
+        }
+        upstream::Message::GoingOffline(GoingOffline { reason }) => {
+            info!("Peer <{peer_id}> announced to go offline due to {reason}.");
+        }
+        upstream::Message::RequestPeerConfiguration(_) => {
+            info!("Peer <{peer_id}> could not apply configuration delta. Sending full configuration.");
+            let _ignore_result = resend_peer_configuration(peer_id, tx_outbound, stream, resources_manager, sent_configurations).await
+                .inspect_err(|cause| warn!("Failed to send full configuration to peer <{peer_id}>:\n  {cause}"));
+        }
+        upstream::Message::ProbeResponse(ProbeResponse { id }) => {
+            let mut pending_probes = pending_probes.lock().await;
+            match pending_probes.get(&id) {
+                Some(probe) if probe.peer_id == peer_id => {
+                    if let Some(probe) = pending_probes.remove(&id) {
+                        let _ignore_result = probe.response.send(()); //the prober may have timed out already
+                    }
+                }
+                Some(_) => warn!("Peer <{peer_id}> responded to probe <{id}>, which was sent to another peer. Ignoring."),
+                None => debug!("Peer <{peer_id}> responded to probe <{id}>, which is not pending anymore. Ignoring."),
+            }
+        }
+        upstream::Message::PeerCommandResult(PeerCommandResult { id, outcome }) => {
+            let Some(outcome) = outcome else {
+                warn!("Peer <{peer_id}> reported the result of command <{id}> without outcome. Ignoring.");
+                return;
+            };
+            let mut pending_commands = pending_commands.lock().await;
+            match pending_commands.get(&id) {
+                Some(command) if command.peer_id == peer_id => {
+                    if let Some(command) = pending_commands.remove(&id) {
+                        let _ignore_result = command.response.send(outcome); //the sender may have timed out already
+                    }
+                }
+                Some(_) => warn!("Peer <{peer_id}> reported the result of command <{id}>, which was sent to another peer. Ignoring."),
+                None => debug!("Peer <{peer_id}> reported the result of command <{id}>, which is not pending anymore. Ignoring."),
+            }
+        }
+    }
+}
+
+fn log_network_readiness(readiness: NetworkReadiness, peer_id: PeerId) {
+    let cluster_id = readiness.cluster_id
+        .and_then(|cluster_id| ClusterId::try_from(cluster_id).ok())
+        .map(|cluster_id| cluster_id.to_string())
+        .unwrap_or_else(|| String::from("unknown"));
+
+    match readiness.state {
+        Some(network_readiness::State::Ready(_)) => {
+            info!("Peer <{peer_id}> verified the network of cluster <{cluster_id}> and started its executors.");
+        }
+        Some(network_readiness::State::NotReady(NetworkNotReady { unreachable_peers, failed_checks })) => {
+            let unreachable_peers = unreachable_peers.into_iter()
+                .filter_map(|peer_id| PeerId::try_from(peer_id).ok())
+                .map(|peer_id| peer_id.to_string())
+                .collect::<Vec<_>>();
+
+            warn!(
+                "Peer <{peer_id}> reported the network of cluster <{cluster_id}> as not ready and did not start its executors.\n  Unreachable peers: [{}]\n  Failed checks: [{}]",
+                unreachable_peers.join(", "),
+                failed_checks.join("; "),
+            );
+        }
+        None => {
+            warn!("Peer <{peer_id}> reported the network readiness of cluster <{cluster_id}> without state.");
+        }
+    }
+}
+
+fn log_test_event(test_event: TestEvent, peer_id: PeerId) {
+    let TestEvent { cluster_id, name, timestamp_unix_millis, kind } = test_event;
+
+    let cluster_id = cluster_id
+        .and_then(|cluster_id| ClusterId::try_from(cluster_id).ok())
+        .map(|cluster_id| cluster_id.to_string())
+        .unwrap_or_else(|| String::from("none"));
+
+    match kind {
+        Some(test_event::Kind::Started(_)) => {
+            info!("Test '{name}' started on peer <{peer_id}> in cluster <{cluster_id}> at {timestamp_unix_millis} (unix millis).");
+        }
+        Some(test_event::Kind::Stopped(TestStopped { verdict })) => {
+            let verdict = verdict.unwrap_or_else(|| String::from("none"));
+            info!("Test '{name}' stopped on peer <{peer_id}> in cluster <{cluster_id}> at {timestamp_unix_millis} (unix millis) with verdict: {verdict}");
+        }
+        None => {
+            warn!("Peer <{peer_id}> reported test event for test '{name}' without kind.");
+        }
+    }
+}
+
+fn log_peer_configuration_status(status: PeerConfigurationStatus, peer_id: PeerId) {
+    let mut failed = false;
+
+    let components = status.components.into_iter()
+        .map(|ComponentStatus { component, duration_ms, state }| {
+            match state {
+                Some(component_status::State::Applied(_)) => format!("{component}: applied in {duration_ms} ms"),
+                Some(component_status::State::Failed(ComponentFailed { cause })) => {
+                    failed = true;
+                    format!("{component}: failed after {duration_ms} ms: {cause}")
+                }
+                Some(component_status::State::Skipped(ComponentSkipped { reason })) => {
+                    failed = true;
+                    format!("{component}: skipped: {reason}")
+                }
+                None => format!("{component}: unknown"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n  ");
+
+    if failed {
+        warn!("Peer <{peer_id}> could not apply all components of its configuration:\n  {components}");
+    } else {
+        info!("Peer <{peer_id}> applied its configuration:\n  {components}");
+    }
+}
+
+/// Summarizes the components, which a peer failed to apply or skipped, so that they can be shown in the deployment status of a cluster.
+fn configuration_outcome(status: &PeerConfigurationStatus) -> Result<(), String> {
+    let problems = status.components.iter()
+        .filter_map(|ComponentStatus { component, state, .. }| match state {
+            Some(component_status::State::Failed(ComponentFailed { cause })) => Some(format!("{component}: failed: {cause}")),
+            Some(component_status::State::Skipped(ComponentSkipped { reason })) => Some(format!("{component}: skipped: {reason}")),
+            Some(component_status::State::Applied(_)) | None => None,
+        })
+        .collect::<Vec<_>>();
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+fn log_energy_measurement(measurement: EnergyMeasurement, peer_id: PeerId) {
+    let EnergyMeasurement { executor_id, meter, started_unix_millis, duration_ms, samples, average_power_watts, peak_power_watts, energy_watt_hours, completed } = measurement;
+
+    let executor_id = executor_id
+        .and_then(|executor_id| ExecutorId::try_from(executor_id).ok())
+        .map(|executor_id| executor_id.to_string())
+        .unwrap_or_else(|| String::from("unknown"));
+
+    let summary = format!("{energy_watt_hours:.3} Wh over {duration_ms} ms, average {average_power_watts:.2} W, peak {peak_power_watts:.2} W ({samples} samples from '{meter}', started at {started_unix_millis} (unix millis))");
+
+    if completed {
+        info!("Peer <{peer_id}> measured the energy consumption of executor <{executor_id}>: {summary}");
+    } else {
+        debug!("Peer <{peer_id}> is measuring the energy consumption of executor <{executor_id}>: {summary}");
+    }
+}
+
+fn log_network_manager_interference(interference: NetworkManagerInterference, peer_id: PeerId) {
+    if interference.findings.is_empty() {
+        info!("Peer <{peer_id}> reported that third-party network managers no longer interfere with its interfaces.");
+        return;
+    }
+
+    let findings = interference.findings.into_iter()
+        .map(|NetworkManagerFinding { manager, interface, problem, remediation }| {
+            format!("{manager} on '{interface}': {problem}\n    Remediation: {remediation}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n  ");
+
+    warn!("Peer <{peer_id}> reported third-party network managers interfering with its interfaces:\n  {findings}");
+}
+
+fn log_storage_usage(usage: StorageUsage, peer_id: PeerId) {
+    let mut exceeded = false;
+
+    let categories = usage.categories.into_iter()
+        .map(|StorageCategoryUsage { category, directory, eviction_policy, used_bytes, quota_bytes, evicted_files, evicted_bytes, filesystem_available_bytes }| {
+            let filesystem_available = filesystem_available_bytes
+                .map(|available_bytes| format!("{available_bytes} bytes"))
+                .unwrap_or_else(|| String::from("unknown"));
+            let mut line = format!("{category} in '{directory}': {used_bytes} of {quota_bytes} bytes used, {filesystem_available} available on filesystem (eviction: {eviction_policy})");
+
+            if used_bytes > quota_bytes {
+                exceeded = true;
+                line.push_str(" -- QUOTA EXCEEDED");
+            }
+            if evicted_files > 0 {
+                exceeded = true;
+                line.push_str(&format!(" -- evicted {evicted_files} files with {evicted_bytes} bytes"));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n  ");
+
+    if exceeded {
+        warn!("Peer <{peer_id}> reached the storage quota of some categories:\n  {categories}");
+    } else {
+        debug!("Peer <{peer_id}> reported its storage usage:\n  {categories}");
     }
 }
 
@@ -217,6 +841,15 @@ pub enum OpenError {
     )]
     PeerAlreadyConnected { peer_id: PeerId },
 
+    #[error("Peer <{peer_id}> opened stream, but CARL is shutting down. Rejecting connection.")]
+    ShuttingDown { peer_id: PeerId },
+
+    #[error("Peer <{peer_id}> opened stream, but its Setup-String was revoked. Rejecting connection. Set up the peer with a newly generated Setup-String.")]
+    SetupRevoked { peer_id: PeerId },
+
+    #[error("Peer <{peer_id}> opened stream, but its Setup-String expired before it was used. Rejecting connection. Set up the peer with a newly generated Setup-String.")]
+    SetupExpired { peer_id: PeerId },
+
     #[error("Error while sending peer configuration to peer:\n  {cause}")]
     SendApplyPeerConfiguration { peer_id: PeerId, cause: String },
 
@@ -224,6 +857,41 @@ pub enum OpenError {
     Persistence { peer_id: PeerId, #[source] source: PersistenceError },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ProbeError {
+    #[error("Peer <{peer_id}> is not connected.")]
+    PeerNotConnected { peer_id: PeerId },
+    #[error("Peer <{peer_id}> did not respond to probe within {} ms.", timeout.as_millis())]
+    Timeout { peer_id: PeerId, timeout: Duration },
+    #[error("Error while sending probe to peer <{peer_id}>:\n  {cause}")]
+    Send { peer_id: PeerId, cause: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TerminateError {
+    #[error("Peer <{peer_id}> is not connected.")]
+    PeerNotConnected { peer_id: PeerId },
+}
+
+/// Output of a command, which the peer executed successfully.
+#[derive(Debug)]
+pub struct CommandOutput {
+    pub output: String,
+    pub attachment: Vec<u8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("Peer <{peer_id}> is not connected.")]
+    PeerNotConnected { peer_id: PeerId },
+    #[error("Peer <{peer_id}> did not report the result of the command within {} ms.", timeout.as_millis())]
+    Timeout { peer_id: PeerId, timeout: Duration },
+    #[error("Peer <{peer_id}> failed to execute the command:\n  {cause}")]
+    Failed { peer_id: PeerId, cause: String },
+    #[error("Error while sending command to peer <{peer_id}>:\n  {cause}")]
+    Send { peer_id: PeerId, cause: String },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RemovePeerError {
     #[error("PeerNotFound Error while removing peer: {0}")]
@@ -235,6 +903,8 @@ pub enum RemovePeerError {
 #[derive(Clone)]
 pub struct PeerMessagingBrokerOptions {
     pub peer_disconnect_timeout: Duration,
+    /// Number of parameters from which on only the changes of a PeerConfiguration are sent. Zero always sends the full configuration.
+    pub configuration_delta_threshold: usize,
 }
 impl PeerMessagingBrokerOptions {
     pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
@@ -242,8 +912,11 @@ impl PeerMessagingBrokerOptions {
             config.get::<u64>("peer.disconnect.timeout.ms")?
         );
 
+        let configuration_delta_threshold = config.get::<usize>("peer.configuration.delta.threshold")?;
+
         Ok(PeerMessagingBrokerOptions {
             peer_disconnect_timeout,
+            configuration_delta_threshold,
         })
     }
 }
@@ -258,6 +931,9 @@ mod tests {
     use tokio::sync::mpsc::Receiver;
 
     use opendut_carl_api::proto::services::peer_messaging_broker::Ping;
+    use opendut_types::peer::configuration::ParameterTarget;
+    use opendut_types::peer::ethernet::EthernetBridge;
+    use opendut_types::util::net::NetworkInterfaceName;
 
     use crate::resources::manager::ResourcesManager;
     use crate::resources::storage::ResourcesStorageApi;
@@ -269,12 +945,13 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            configuration_delta_threshold: 0,
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
 
         let remote_host = IpAddr::from_str("1.2.3.4")?;
 
-        let (sender, mut receiver) = testee.open(peer_id, remote_host).await?;
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, PeerConnection::default()).await?;
 
         { //assert state contains peer connected and up
             let peers = testee.peers.read().await;
@@ -346,15 +1023,16 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            configuration_delta_threshold: 0,
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
 
         let remote_host = IpAddr::from_str("1.2.3.4")?;
 
-        let result = testee.open(peer_id, remote_host).await;
+        let result = testee.open(peer_id, remote_host, PeerConnection::default()).await;
         assert!(result.is_ok());
 
-        let result = testee.open(peer_id, remote_host).await;
+        let result = testee.open(peer_id, remote_host, PeerConnection::default()).await;
         assert_that!(
             result.unwrap_err(),
             matches_pattern!(OpenError::PeerAlreadyConnected { peer_id: eq(&peer_id) })
@@ -363,6 +1041,257 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_list_and_terminate_peer_streams() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let connection = PeerConnection {
+            remote_address: Some(SocketAddr::from_str("10.0.0.1:45678")?),
+            version: Some(String::from("0.3.0")),
+        };
+
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, connection).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+        do_ping(&sender, &mut receiver).await;
+
+        let streams = testee.list_streams().await;
+        assert_that!(streams.len(), eq(1));
+        let stream = &streams[0];
+        assert_that!(stream.peer_id, eq(peer_id));
+        assert_that!(stream.remote_address, some(eq(SocketAddr::from_str("10.0.0.1:45678")?)));
+        assert_that!(stream.version, some(eq("0.3.0")));
+        assert_that!(stream.messages_received, eq(1));
+        assert_that!(stream.messages_sent, eq(2));
+
+        testee.terminate_stream(peer_id).await?;
+
+        let ended = tokio::time::timeout(Duration::from_secs(5), async {
+            while receiver.recv().await.is_some() {}
+        }).await;
+        assert!(ended.is_ok(), "Stream should end after it was terminated.");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while testee.connected_peers().await.contains(&peer_id) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await?;
+        assert_that!(resources_manager.get::<PeerState>(peer_id).await?.as_ref(), some(eq(&PeerState::Down)));
+
+        assert_that!(testee.terminate_stream(peer_id).await, err(matches_pattern!(TerminateError::PeerNotConnected { peer_id: eq(&peer_id) })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_ask_peers_to_disconnect_and_reject_new_streams_when_shutting_down() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+
+        let (_sender, mut receiver) = testee.open(peer_id, remote_host, PeerConnection::default()).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+
+        let reconnect_hint = ReconnectHint {
+            retry_after_ms: 5000,
+            alternate_endpoint: Some(String::from("carl-standby:443")),
+        };
+        testee.disconnect_all(Clone::clone(&reconnect_hint), Duration::from_millis(200)).await;
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_eq!(received, downstream::Message::Disconnect(Disconnect { reconnect_hint: Some(reconnect_hint) }));
+
+        assert!(testee.connected_peers().await.is_empty());
+        let peer_state = resources_manager.get::<PeerState>(peer_id).await?;
+        assert_eq!(peer_state, Some(PeerState::Down));
+
+        let result = testee.open(PeerId::random(), remote_host, PeerConnection::default()).await;
+        assert_that!(
+            result.unwrap_err(),
+            matches_pattern!(OpenError::ShuttingDown { .. })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_round_trip_probe_to_connected_peer() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let result = testee.probe(peer_id, Duration::from_millis(100)).await;
+        assert_that!(result.unwrap_err(), matches_pattern!(ProbeError::PeerNotConnected { peer_id: eq(&peer_id) }));
+        assert!(testee.last_seen(peer_id).await.is_none());
+
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, PeerConnection::default()).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+
+        let responder = tokio::spawn(async move {
+            let received = receiver.recv().await.unwrap().message.unwrap();
+            let downstream::Message::Probe(Probe { id }) = received else {
+                panic!("Expected a probe, but received: {received:?}");
+            };
+            sender.send(upstream::Message::ProbeResponse(ProbeResponse { id })).await.unwrap();
+            (sender, receiver)
+        });
+
+        testee.probe(peer_id, Duration::from_secs(5)).await?;
+        let (_sender, mut receiver) = responder.await?;
+        assert!(testee.last_seen(peer_id).await.is_some());
+
+        let result = testee.probe(peer_id, Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(ProbeError::Timeout { .. })));
+        assert!(receiver.recv().await.is_some());
+        assert!(testee.pending_probes.lock().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_relay_command_results_reported_by_the_peer() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let result = testee.send_command(peer_id, PeerCommand::CollectDiagnostics, Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(CommandError::PeerNotConnected { .. })));
+
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, PeerConnection::default()).await?;
+        let _initial_configuration = receiver.recv().await.unwrap();
+
+        tokio::spawn(async move {
+            while let Some(received) = receiver.recv().await {
+                if let Some(downstream::Message::PeerCommand(PeerCommandMessage { id, kind })) = received.message {
+                    let outcome = match PeerCommand::try_from(kind.unwrap()).unwrap() {
+                        PeerCommand::CollectDiagnostics => peer_command_result::Outcome::Succeeded(PeerCommandSucceeded { output: String::from("collected"), attachment: vec![1, 2, 3] }),
+                        _ => peer_command_result::Outcome::Failed(PeerCommandFailed { cause: String::from("not supported") }),
+                    };
+                    sender.send(upstream::Message::PeerCommandResult(PeerCommandResult { id, outcome: Some(outcome) })).await.unwrap();
+                }
+            }
+        });
+
+        let output = testee.send_command(peer_id, PeerCommand::CollectDiagnostics, Duration::from_secs(5)).await?;
+        assert_that!(output.output, eq("collected"));
+        assert_that!(output.attachment, eq(&vec![1, 2, 3]));
+
+        let result = testee.send_command(peer_id, PeerCommand::RebootHost, Duration::from_secs(5)).await;
+        let Err(CommandError::Failed { cause, .. }) = result else {
+            panic!("Expected the command to fail, but got: {result:?}");
+        };
+        assert_that!(cause, eq("not supported"));
+        assert!(testee.pending_commands.lock().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_send_only_the_changes_of_large_configurations() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 2,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, mut receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, PeerConnection::default()).await?;
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_that!(received, matches_pattern!(downstream::Message::ApplyPeerConfiguration(
+            matches_pattern!(ApplyPeerConfiguration { configuration_version: eq(1), .. })
+        )));
+
+        let mut peer_configuration = PeerConfiguration::default();
+        for name in ["br-opendut-1", "br-opendut-2", "br-opendut-3"] {
+            peer_configuration.insert(EthernetBridge { name: NetworkInterfaceName::try_from(name)? }, ParameterTarget::Present);
+        }
+        testee.send_peer_configuration(peer_id, OldPeerConfiguration::default(), Clone::clone(&peer_configuration)).await?;
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_that!(received, matches_pattern!(downstream::Message::ApplyPeerConfiguration( //every parameter changed
+            matches_pattern!(ApplyPeerConfiguration { configuration_version: eq(2), .. })
+        )));
+
+        peer_configuration.insert(EthernetBridge { name: NetworkInterfaceName::try_from("br-opendut-4")? }, ParameterTarget::Present);
+        testee.send_peer_configuration(peer_id, OldPeerConfiguration::default(), Clone::clone(&peer_configuration)).await?;
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        let downstream::Message::ApplyPeerConfigurationDelta(ApplyPeerConfigurationDelta { delta: Some(delta), .. }) = received else {
+            panic!("Expected a configuration delta, but received: {received:?}");
+        };
+        let delta = PeerConfigurationDelta::try_from(delta)?;
+        assert_eq!(delta.base_version, 2);
+        assert_eq!(delta.version, 3);
+        assert_eq!(delta.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_peers_with_revoked_or_expired_setup_strings() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(60),
+            configuration_delta_threshold: 0,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+
+        let issued_at = SystemTime::now() - Duration::from_secs(60);
+        let issuance = PeerSetupIssuance {
+            peer_id,
+            issued_by: String::from("alice"),
+            issued_at,
+            expires_at: Some(issued_at + Duration::from_secs(30)),
+            used_at: None,
+            revoked_at: None,
+        };
+        resources_manager.insert(peer_id, Clone::clone(&issuance)).await?;
+
+        let result = testee.open(peer_id, remote_host, PeerConnection::default()).await;
+        assert_that!(result, err(matches_pattern!(OpenError::SetupExpired { .. })));
+
+        let revoked = PeerSetupIssuance { expires_at: None, revoked_at: Some(SystemTime::now()), ..Clone::clone(&issuance) };
+        resources_manager.insert(peer_id, revoked).await?;
+
+        let result = testee.open(peer_id, remote_host, PeerConnection::default()).await;
+        assert_that!(result, err(matches_pattern!(OpenError::SetupRevoked { .. })));
+
+        let valid = PeerSetupIssuance { expires_at: Some(SystemTime::now() + Duration::from_secs(60)), ..issuance };
+        resources_manager.insert(peer_id, valid).await?;
+
+        let _connection = testee.open(peer_id, remote_host, PeerConnection::default()).await?;
+        let used_at = resources_manager.get::<PeerSetupIssuance>(peer_id).await?
+            .and_then(|issuance| issuance.used_at);
+        assert!(used_at.is_some());
+
+        Ok(())
+    }
+
     async fn do_ping(sender: &mpsc::Sender<upstream::Message>, receiver: &mut Receiver<Downstream>) {
         sender.send(upstream::Message::Ping(Ping {})).await
             .unwrap();
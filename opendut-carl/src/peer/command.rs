@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use opendut_carl_api::carl::peer::PeerCommand;
+
+use crate::auth::{CurrentServiceAccount, CurrentUser};
+
+/// Target of the log records, which document who sent which command to which peer.
+pub const AUDIT_LOG_TARGET: &str = "opendut_carl::audit";
+
+/// Roles, which are allowed to send a command to peers. An empty list allows all users.
+#[derive(Clone, Debug, Default)]
+pub struct PeerCommandAuthorization {
+    required_roles: HashMap<PeerCommand, Vec<String>>,
+}
+impl PeerCommandAuthorization {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let mut required_roles = HashMap::new();
+        for command in PeerCommand::ALL {
+            let field = format!("peer.command.roles.{}", command.name().replace('-', "_"));
+            let roles = config.get::<Vec<String>>(&field)?;
+            required_roles.insert(command, roles);
+        }
+        Ok(Self { required_roles })
+    }
+
+    /// Checks whether the requester has one of the roles required for the command.
+    /// Without a requester, authentication is disabled and all commands are allowed.
+    pub fn authorize(&self, command: PeerCommand, requester: Option<&CommandRequester>) -> Result<(), Vec<String>> {
+        let required_roles = self.required_roles(command);
+        let Some(requester) = requester else {
+            return Ok(());
+        };
+        if required_roles.is_empty()
+            || required_roles.iter().any(|role| requester.roles.contains(role)) {
+            Ok(())
+        } else {
+            Err(required_roles.to_vec())
+        }
+    }
+
+    pub fn required_roles(&self, command: PeerCommand) -> &[String] {
+        self.required_roles.get(&command)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// User, who sent a command to a peer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandRequester {
+    pub name: String,
+    pub roles: Vec<String>,
+}
+impl From<&CurrentUser> for CommandRequester {
+    fn from(user: &CurrentUser) -> Self {
+        Self {
+            name: user.name.clone(),
+            roles: user.claims.additional_claims().roles.clone(),
+        }
+    }
+}
+/// Service accounts have no roles, so they may only send commands, which do not require a role.
+impl From<&CurrentServiceAccount> for CommandRequester {
+    fn from(service_account: &CurrentServiceAccount) -> Self {
+        Self {
+            name: format!("service-account/{}", service_account.name),
+            roles: Vec::new(),
+        }
+    }
+}
+
+impl CommandRequester {
+    /// Determines the requester from the authenticated user or service account. Without either, authentication is disabled.
+    pub fn of<T>(request: &tonic::Request<T>) -> Option<Self> {
+        request.extensions().get::<CurrentUser>().map(CommandRequester::from)
+            .or_else(|| request.extensions().get::<CurrentServiceAccount>().map(CommandRequester::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_require_one_of_the_configured_roles() {
+        let testee = PeerCommandAuthorization {
+            required_roles: HashMap::from([
+                (PeerCommand::RebootHost, vec![String::from("lab-admin"), String::from("operator")]),
+            ]),
+        };
+        let requester = |roles: &[&str]| CommandRequester {
+            name: String::from("alice"),
+            roles: roles.iter().map(ToString::to_string).collect(),
+        };
+
+        assert_that!(testee.authorize(PeerCommand::RebootHost, Some(&requester(&["operator"]))), ok(eq(&())));
+        assert_that!(testee.authorize(PeerCommand::RebootHost, Some(&requester(&["viewer"]))), err(len(eq(2))));
+        assert_that!(testee.authorize(PeerCommand::RebootHost, None), ok(eq(&())));
+        assert_that!(testee.authorize(PeerCommand::CollectDiagnostics, Some(&requester(&[]))), ok(eq(&())));
+    }
+}
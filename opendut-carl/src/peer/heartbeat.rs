@@ -0,0 +1,143 @@
+use std::ops::Not;
+use std::time::{Duration, SystemTime};
+
+use tracing::{info, warn};
+
+use opendut_types::peer::state::PeerState;
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+/// Periodically checks when each connected peer last sent a message, e.g. its regular ping.
+/// Peers, which stayed silent for longer than the heartbeat timeout, are marked as [`PeerState::Unreachable`],
+/// so that subscribers like LEA and CLEO can show them as stale, before their stream times out and they are marked as down.
+/// Once they send a message again, they are marked as up with their previous state.
+pub fn spawn_heartbeat_monitoring(
+    resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    options: HeartbeatOptions,
+) {
+    let HeartbeatOptions::Enabled { interval, timeout } = options else {
+        return;
+    };
+
+    info!("Checking heartbeats of peers every {} ms with a timeout of {} ms.", interval.as_millis(), timeout.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+
+            check_heartbeats(&resources_manager, &peer_messaging_broker, timeout, SystemTime::now()).await
+                .unwrap_or_else(|cause| warn!("Failed to check heartbeats of peers:\n  {cause}"));
+        }
+    });
+}
+
+async fn check_heartbeats(
+    resources_manager: &ResourcesManagerRef,
+    peer_messaging_broker: &PeerMessagingBrokerRef,
+    timeout: Duration,
+    now: SystemTime,
+) -> PersistenceResult<()> {
+    for peer_id in peer_messaging_broker.connected_peers().await {
+        let silent_for = peer_messaging_broker.last_seen(peer_id).await
+            .and_then(|last_seen| now.duration_since(last_seen).ok())
+            .unwrap_or_default();
+
+        let is_silent = silent_for > timeout;
+
+        resources_manager.resources_mut(|resources| {
+            //the state is read within the transaction, so that a peer, which disconnected in the meantime, is not marked as unreachable
+            match resources.get::<PeerState>(peer_id)? {
+                Some(PeerState::Up { inner, remote_host }) if is_silent => {
+                    warn!("No message from peer <{peer_id}> for {} ms. Marking it as unreachable.", silent_for.as_millis());
+                    resources.insert(peer_id, PeerState::Unreachable { inner, remote_host })?;
+                }
+                Some(PeerState::Unreachable { inner, remote_host }) if is_silent.not() => {
+                    info!("Peer <{peer_id}> is sending messages again. Marking it as up.");
+                    resources.insert(peer_id, PeerState::Up { inner, remote_host })?;
+                }
+                _ => {}
+            }
+            Ok::<_, PersistenceError>(())
+        }).await??;
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub enum HeartbeatOptions {
+    Enabled {
+        interval: Duration,
+        /// Duration without any message from a peer, after which it is marked as unreachable.
+        /// Should be shorter than `peer.disconnect.timeout.ms`, after which its stream is closed and it is marked as down.
+        timeout: Duration,
+    },
+    Disabled,
+}
+impl HeartbeatOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let enabled = config.get_bool("peer.heartbeat.enabled")?;
+
+        if enabled {
+            let interval = Duration::from_millis(
+                config.get::<u64>("peer.heartbeat.check.interval.ms")?
+            );
+            let timeout = Duration::from_millis(
+                config.get::<u64>("peer.heartbeat.timeout.ms")?
+            );
+
+            Ok(HeartbeatOptions::Enabled { interval, timeout })
+        } else {
+            Ok(HeartbeatOptions::Disabled)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::state::{PeerBlockedState, PeerUpState};
+    use opendut_types::peer::PeerId;
+
+    use crate::peer::broker::{PeerConnection, PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_mark_silent_peers_as_unreachable_and_restore_their_state_when_they_are_heard_again() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions { peer_disconnect_timeout: Duration::from_secs(60), configuration_delta_threshold: 0 },
+        );
+        let timeout = Duration::from_secs(10);
+
+        let peer_id = PeerId::random();
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let (_sender, _receiver) = peer_messaging_broker.open(peer_id, remote_host, PeerConnection::default()).await?;
+
+        let inner = PeerUpState::Blocked(PeerBlockedState::Member);
+        resources_manager.insert(peer_id, PeerState::Up { inner: Clone::clone(&inner), remote_host }).await?;
+
+        check_heartbeats(&resources_manager, &peer_messaging_broker, timeout, SystemTime::now()).await?;
+        assert_that!(resources_manager.get::<PeerState>(peer_id).await?, some(eq(&PeerState::Up { inner: Clone::clone(&inner), remote_host })));
+
+        check_heartbeats(&resources_manager, &peer_messaging_broker, timeout, SystemTime::now() + timeout * 2).await?;
+        assert_that!(resources_manager.get::<PeerState>(peer_id).await?, some(eq(&PeerState::Unreachable { inner: Clone::clone(&inner), remote_host })));
+
+        check_heartbeats(&resources_manager, &peer_messaging_broker, timeout, SystemTime::now()).await?;
+        assert_that!(resources_manager.get::<PeerState>(peer_id).await?, some(eq(&PeerState::Up { inner, remote_host })));
+
+        Ok(())
+    }
+}
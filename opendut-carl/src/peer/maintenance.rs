@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use opendut_carl_api::carl::peer::{MaintenanceOperation, MaintenanceOperationId, MaintenancePeerState, MaintenancePeerStatus, PeerCommand};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::peer::command::AUDIT_LOG_TARGET;
+
+pub type MaintenanceOperationsRef = Arc<MaintenanceOperations>;
+
+const CONNECTION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maintenance operations, which were started via CARL, like rolling restarts of peers.
+/// Only kept in memory, so the progress of operations is lost, when CARL restarts.
+#[derive(Default)]
+pub struct MaintenanceOperations {
+    operations: RwLock<HashMap<MaintenanceOperationId, MaintenanceOperation>>,
+}
+
+impl MaintenanceOperations {
+    pub fn new() -> MaintenanceOperationsRef {
+        Arc::new(Self::default())
+    }
+
+    pub async fn get(&self, operation_id: MaintenanceOperationId) -> Option<MaintenanceOperation> {
+        self.operations.read().await
+            .get(&operation_id)
+            .cloned()
+    }
+
+    async fn insert(&self, operation: MaintenanceOperation) {
+        self.operations.write().await
+            .insert(operation.id, operation);
+    }
+
+    async fn update(&self, operation_id: MaintenanceOperationId, transition: impl FnOnce(&mut MaintenanceOperation)) {
+        if let Some(operation) = self.operations.write().await.get_mut(&operation_id) {
+            transition(operation);
+        }
+    }
+}
+
+pub struct RollingRestart {
+    pub operation: MaintenanceOperation,
+    /// Deployed clusters, which each peer is a member of.
+    pub peer_clusters: HashMap<PeerId, Vec<ClusterId>>,
+    pub max_unavailable_per_cluster: u32,
+    pub peer_timeout: Duration,
+    pub user: String,
+}
+
+/// Restarts the peers of the operation in batches, so that no more than `max_unavailable_per_cluster` members of a deployed cluster are restarting at once.
+/// Peers, which are not a member of any deployed cluster, are restarted under the same limit.
+/// A peer counts as restarted, once it connected again and reported the outcome of applying its configuration.
+/// When a peer fails to restart, the peers still waiting are skipped.
+pub async fn spawn_rolling_restart(
+    operations: MaintenanceOperationsRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    rolling_restart: RollingRestart,
+) {
+    let RollingRestart { operation, peer_clusters, max_unavailable_per_cluster, peer_timeout, user } = rolling_restart;
+    let operation_id = operation.id;
+    let command = operation.command;
+
+    operations.insert(operation).await;
+
+    tokio::spawn(async move {
+        let mut restarts = JoinSet::new();
+
+        loop {
+            let mut batch = Vec::new();
+            operations.update(operation_id, |operation| {
+                batch = next_batch(&operation.peers, &peer_clusters, max_unavailable_per_cluster);
+                for &peer_id in &batch {
+                    set_state(operation, peer_id, MaintenancePeerState::Restarting);
+                }
+            }).await;
+
+            for peer_id in batch {
+                info!(target: AUDIT_LOG_TARGET, "Rolling restart <{operation_id}> of user '{user}' sends command '{command}' to peer <{peer_id}>.");
+                let peer_messaging_broker = Arc::clone(&peer_messaging_broker);
+                restarts.spawn(async move {
+                    let result = restart_peer(&peer_messaging_broker, peer_id, command, peer_timeout).await;
+                    (peer_id, result)
+                });
+            }
+
+            let Some(finished) = restarts.join_next().await else {
+                break;
+            };
+            let (peer_id, result) = match finished {
+                Ok(finished) => finished,
+                Err(cause) => {
+                    warn!("Task of rolling restart <{operation_id}> ended unexpectedly: {cause}");
+                    continue;
+                }
+            };
+
+            operations.update(operation_id, |operation| {
+                match result {
+                    Ok(()) => {
+                        info!("Peer <{peer_id}> completed the restart of rolling restart <{operation_id}>.");
+                        set_state(operation, peer_id, MaintenancePeerState::Completed);
+                    }
+                    Err(cause) => {
+                        warn!(target: AUDIT_LOG_TARGET, "Peer <{peer_id}> failed to restart during rolling restart <{operation_id}>, skipping the remaining peers:\n  {cause}");
+                        set_state(operation, peer_id, MaintenancePeerState::Failed { cause });
+                        for peer in &mut operation.peers {
+                            if peer.state == MaintenancePeerState::Waiting {
+                                peer.state = MaintenancePeerState::Skipped;
+                            }
+                        }
+                    }
+                }
+            }).await;
+        }
+
+        info!(target: AUDIT_LOG_TARGET, "Rolling restart <{operation_id}> of user '{user}' finished.");
+    });
+}
+
+fn set_state(operation: &mut MaintenanceOperation, peer_id: PeerId, state: MaintenancePeerState) {
+    if let Some(peer) = operation.peers.iter_mut().find(|peer| peer.peer_id == peer_id) {
+        peer.state = state;
+    }
+}
+
+/// Selects the waiting peers, which can be restarted now without exceeding `max_unavailable_per_cluster` restarting members in any cluster.
+/// Peers without a deployed cluster are treated like members of one further cluster.
+fn next_batch(
+    peers: &[MaintenancePeerStatus],
+    peer_clusters: &HashMap<PeerId, Vec<ClusterId>>,
+    max_unavailable_per_cluster: u32,
+) -> Vec<PeerId> {
+    let clusters_of = |peer_id: PeerId| -> Vec<Option<ClusterId>> {
+        match peer_clusters.get(&peer_id) {
+            Some(clusters) if clusters.is_empty().not() => clusters.iter().copied().map(Some).collect(),
+            _ => vec![None],
+        }
+    };
+
+    let mut unavailable = HashMap::<Option<ClusterId>, u32>::new();
+    for peer in peers.iter().filter(|peer| peer.state == MaintenancePeerState::Restarting) {
+        for cluster in clusters_of(peer.peer_id) {
+            *unavailable.entry(cluster).or_default() += 1;
+        }
+    }
+
+    let mut batch = Vec::new();
+    for peer in peers.iter().filter(|peer| peer.state == MaintenancePeerState::Waiting) {
+        let clusters = clusters_of(peer.peer_id);
+        let available = clusters.iter()
+            .all(|cluster| unavailable.get(cluster).copied().unwrap_or_default() < max_unavailable_per_cluster);
+
+        if available {
+            for cluster in clusters {
+                *unavailable.entry(cluster).or_default() += 1;
+            }
+            batch.push(peer.peer_id);
+        }
+    }
+    batch
+}
+
+/// Sends the command to the peer and waits until it disconnected, connected again and reported the outcome of applying its configuration.
+async fn restart_peer(
+    peer_messaging_broker: &PeerMessagingBrokerRef,
+    peer_id: PeerId,
+    command: PeerCommand,
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut configuration_reports = peer_messaging_broker.subscribe_configuration_reports();
+
+    peer_messaging_broker.send_command(peer_id, command, timeout).await
+        .map_err(|cause| cause.to_string())?;
+
+    let restarted = async {
+        while peer_messaging_broker.connected_peers().await.contains(&peer_id) {
+            tokio::time::sleep(CONNECTION_POLL_INTERVAL).await;
+        }
+        loop {
+            match configuration_reports.recv().await {
+                Ok(reporting_peer) if reporting_peer == peer_id => return Ok(()),
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Err(String::from("CARL stopped receiving reports of peers.")),
+            }
+        }
+    };
+
+    tokio::time::timeout(timeout, restarted).await
+        .map_err(|_| format!("Peer did not connect again and report its configuration within {} seconds.", timeout.as_secs()))?
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn status(peer_id: PeerId, state: MaintenancePeerState) -> MaintenancePeerStatus {
+        MaintenancePeerStatus { peer_id, state }
+    }
+
+    #[test]
+    fn should_not_restart_more_than_the_allowed_members_of_a_cluster_at_once() {
+        let cluster_a = ClusterId::random();
+        let cluster_b = ClusterId::random();
+        let [peer_1, peer_2, peer_3, peer_4, peer_5] = [(); 5].map(|_| PeerId::random());
+
+        let peer_clusters = HashMap::from([
+            (peer_1, vec![cluster_a]),
+            (peer_2, vec![cluster_a, cluster_b]),
+            (peer_3, vec![cluster_b]),
+            (peer_4, vec![cluster_a]),
+        ]);
+
+        let peers = vec![
+            status(peer_1, MaintenancePeerState::Waiting),
+            status(peer_2, MaintenancePeerState::Waiting),
+            status(peer_3, MaintenancePeerState::Waiting),
+            status(peer_4, MaintenancePeerState::Waiting),
+            status(peer_5, MaintenancePeerState::Waiting),
+        ];
+        assert_that!(next_batch(&peers, &peer_clusters, 1), elements_are![eq(&peer_1), eq(&peer_3), eq(&peer_5)]);
+        assert_that!(next_batch(&peers, &peer_clusters, 2), elements_are![eq(&peer_1), eq(&peer_2), eq(&peer_3), eq(&peer_5)]);
+
+        let peers = vec![
+            status(peer_1, MaintenancePeerState::Completed),
+            status(peer_2, MaintenancePeerState::Waiting),
+            status(peer_3, MaintenancePeerState::Restarting),
+            status(peer_4, MaintenancePeerState::Waiting),
+            status(peer_5, MaintenancePeerState::Skipped),
+        ];
+        assert_that!(next_batch(&peers, &peer_clusters, 1), elements_are![eq(&peer_4)]);
+    }
+}
@@ -1 +1,4 @@
 pub mod broker;
+pub mod command;
+pub mod heartbeat;
+pub mod maintenance;
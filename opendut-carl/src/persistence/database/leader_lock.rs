@@ -0,0 +1,31 @@
+use diesel::sql_types::{BigInt, Bool, Integer};
+use diesel::{PgConnection, QueryResult, RunQueryDsl};
+
+/// Tries to acquire the session-level advisory lock, which designates the leader among the CARL instances sharing the database.
+/// The lock is held until the session ends, e.g. because the instance stopped or its connection broke, after which another instance can acquire it.
+pub fn try_acquire(connection: &mut PgConnection, lock_id: i64) -> QueryResult<bool> {
+    #[derive(diesel::QueryableByName)]
+    struct Acquired {
+        #[diesel(sql_type = Bool)]
+        acquired: bool,
+    }
+
+    let result = diesel::sql_query("SELECT pg_try_advisory_lock($1) AS acquired")
+        .bind::<BigInt, _>(lock_id)
+        .get_result::<Acquired>(connection)?;
+    Ok(result.acquired)
+}
+
+/// Checks that the session is still alive. As long as it is, the lock acquired via [`try_acquire`] is held.
+pub fn check_session(connection: &mut PgConnection) -> QueryResult<()> {
+    #[derive(diesel::QueryableByName)]
+    struct Alive {
+        #[diesel(sql_type = Integer)]
+        #[allow(unused)]
+        alive: i32,
+    }
+
+    diesel::sql_query("SELECT 1 AS alive")
+        .get_result::<Alive>(connection)?;
+    Ok(())
+}
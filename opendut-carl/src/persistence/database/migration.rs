@@ -0,0 +1,178 @@
+use std::ops::Not;
+
+use diesel::backend::Backend;
+use diesel::migration::{Migration, MigrationSource};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use tracing::{debug, info};
+
+pub(super) const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/persistence/database/migrations/");
+pub(super) const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/persistence/database/migrations-sqlite/");
+
+/// Migrations, which were applied or reverted by [`migrate_to`].
+#[derive(Debug, Default)]
+pub struct MigrationSummary {
+    pub applied: Vec<String>,
+    pub reverted: Vec<String>,
+    /// Version of the latest migration, which is applied to the database now.
+    pub schema_version: String,
+}
+
+/// Runs the migrations, which were not applied yet.
+/// Refuses to do so, if the database schema is newer than this binary, e.g. after a newer version of CARL ran against the database.
+pub(super) fn run_pending_migrations<DB: Backend>(connection: &mut impl MigrationHarness<DB>, migrations: EmbeddedMigrations) -> Result<(), MigrationError> {
+    let available = available_migrations::<DB>(&migrations)?;
+    ensure_schema_not_newer(connection, &available)?;
+
+    let migrated_versions = connection.run_pending_migrations(migrations)?;
+
+    if migrated_versions.is_empty() {
+        debug!("No database migrations had to be applied.");
+    } else {
+        let migrated_versions = migrated_versions.into_iter()
+            .map(|version| version.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        info!("Completed running pending database migrations: {migrated_versions}");
+    }
+    Ok(())
+}
+
+/// Applies or reverts migrations, until the given version is the latest migration applied to the database. Without a version, all migrations are applied.
+/// Versions are the dates in the names of the migration directories, e.g. `2024-11-11-090000` or `20241111090000`.
+pub(super) fn migrate_to<DB: Backend>(connection: &mut impl MigrationHarness<DB>, migrations: EmbeddedMigrations, target: Option<&str>) -> Result<MigrationSummary, MigrationError> {
+    let available = available_migrations::<DB>(&migrations)?;
+    ensure_schema_not_newer(connection, &available)?;
+
+    let target = match target {
+        None => latest_version(&available),
+        Some(target) => {
+            let target = target.replace('-', "");
+            if available.iter().any(|migration| version_of(migration.as_ref()) == target).not() {
+                return Err(MigrationError::UnknownVersion {
+                    version: target,
+                    known: available.iter().map(|migration| version_of(migration.as_ref())).collect::<Vec<_>>().join(", "),
+                });
+            }
+            target
+        }
+    };
+
+    let applied_versions = applied_versions(connection)?;
+    let mut summary = MigrationSummary { schema_version: Clone::clone(&target), ..Default::default() };
+
+    for migration in available.iter().rev() {
+        let version = version_of(migration.as_ref());
+        if version > target && applied_versions.contains(&version) {
+            connection.revert_migration(migration.as_ref())?;
+            info!("Reverted database migration: {}", migration.name());
+            summary.reverted.push(version);
+        }
+    }
+    for migration in &available {
+        let version = version_of(migration.as_ref());
+        if version <= target && applied_versions.contains(&version).not() {
+            connection.run_migration(migration.as_ref())?;
+            info!("Applied database migration: {}", migration.name());
+            summary.applied.push(version);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Fails, if the database contains migrations, which this binary does not know.
+/// These were applied by a newer binary, whose code may rely on them, and only that binary knows how to revert them.
+fn ensure_schema_not_newer<DB: Backend>(connection: &mut impl MigrationHarness<DB>, available: &[Box<dyn Migration<DB>>]) -> Result<(), MigrationError> {
+    let known_versions = available.iter()
+        .map(|migration| version_of(migration.as_ref()))
+        .collect::<Vec<_>>();
+
+    let newest_unknown_version = applied_versions(connection)?
+        .into_iter()
+        .filter(|version| known_versions.contains(version).not())
+        .max();
+
+    match newest_unknown_version {
+        None => Ok(()),
+        Some(database_version) => Err(MigrationError::SchemaNewerThanBinary {
+            database_version,
+            binary_version: latest_version(available),
+        }),
+    }
+}
+
+fn available_migrations<DB: Backend>(migrations: &EmbeddedMigrations) -> Result<Vec<Box<dyn Migration<DB>>>, MigrationError> {
+    let mut available = MigrationSource::<DB>::migrations(migrations)?;
+    available.sort_by_key(|migration| version_of(migration.as_ref()));
+    Ok(available)
+}
+
+fn applied_versions<DB: Backend>(connection: &mut impl MigrationHarness<DB>) -> Result<Vec<String>, MigrationError> {
+    let versions = connection.applied_migrations()?
+        .into_iter()
+        .map(|version| version.to_string())
+        .collect();
+    Ok(versions)
+}
+
+fn latest_version<DB: Backend>(available: &[Box<dyn Migration<DB>>]) -> String {
+    available.last()
+        .map(|migration| version_of(migration.as_ref()))
+        .unwrap_or_default()
+}
+
+fn version_of<DB: Backend>(migration: &dyn Migration<DB>) -> String {
+    migration.name().version().to_string()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("The database schema has version {database_version}, which is newer than version {binary_version} known to this binary of CARL. Update CARL or revert the schema with `opendut-carl migrate --to {binary_version}` of the newer binary.")]
+    SchemaNewerThanBinary { database_version: String, binary_version: String },
+    #[error("Unknown database schema version '{version}'. Known versions: {known}")]
+    UnknownVersion { version: String, known: String },
+    #[error("Error while running database migrations")]
+    Diesel(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::SimpleConnection;
+    use diesel::{Connection, SqliteConnection};
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_revert_and_reapply_migrations() -> anyhow::Result<()> {
+        let mut connection = SqliteConnection::establish(":memory:")?;
+        run_pending_migrations(&mut connection, SQLITE_MIGRATIONS)?;
+        let latest = applied_versions(&mut connection)?.into_iter().max().unwrap_or_default();
+
+        let summary = migrate_to(&mut connection, SQLITE_MIGRATIONS, None)?;
+        assert_that!(summary.applied, empty());
+        assert_that!(summary.reverted, empty());
+        assert_that!(summary.schema_version, eq(&latest));
+
+        let result = migrate_to(&mut connection, SQLITE_MIGRATIONS, Some("1999-01-01-000000"));
+        assert_that!(result, err(matches_pattern!(MigrationError::UnknownVersion { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_to_migrate_a_schema_newer_than_the_binary() -> anyhow::Result<()> {
+        let mut connection = SqliteConnection::establish(":memory:")?;
+        run_pending_migrations(&mut connection, SQLITE_MIGRATIONS)?;
+
+        connection.batch_execute("INSERT INTO __diesel_schema_migrations (version) VALUES ('99991231000000');")?;
+
+        let result = run_pending_migrations(&mut connection, SQLITE_MIGRATIONS);
+        assert_that!(result, err(displays_as(contains_substring("99991231000000"))));
+
+        let result = migrate_to(&mut connection, SQLITE_MIGRATIONS, None);
+        assert_that!(result, err(matches_pattern!(MigrationError::SchemaNewerThanBinary { .. })));
+
+        Ok(())
+    }
+}
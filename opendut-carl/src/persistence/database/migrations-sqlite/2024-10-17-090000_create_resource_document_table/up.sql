@@ -0,0 +1,6 @@
+CREATE TABLE resource_document (
+    kind TEXT NOT NULL,
+    id TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (kind, id)
+);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS cluster_peer_group;
+DROP TABLE IF EXISTS peer_group_member;
+DROP INDEX IF EXISTS peer_group_label_name_index;
+DROP TABLE IF EXISTS peer_group_label;
+DROP TABLE IF EXISTS peer_group;
@@ -0,0 +1,23 @@
+CREATE TABLE peer_group (
+    peer_group_id uuid PRIMARY KEY,
+    name text NOT NULL
+);
+
+CREATE TABLE peer_group_label (
+    peer_group_id uuid REFERENCES peer_group(peer_group_id) ON DELETE CASCADE,
+    name text NOT NULL,
+    PRIMARY KEY(peer_group_id, name)
+);
+CREATE INDEX peer_group_label_name_index ON peer_group_label(name);
+
+CREATE TABLE peer_group_member (
+    peer_group_id uuid REFERENCES peer_group(peer_group_id) ON DELETE CASCADE,
+    peer_id uuid NOT NULL, -- no foreign key, as peer descriptors are deleted and re-inserted on every update
+    PRIMARY KEY(peer_group_id, peer_id)
+);
+
+CREATE TABLE cluster_peer_group (
+    cluster_id uuid REFERENCES cluster_configuration(cluster_id) ON DELETE CASCADE,
+    peer_group_id uuid REFERENCES peer_group(peer_group_id) ON DELETE CASCADE,
+    PRIMARY KEY(cluster_id, peer_group_id)
+);
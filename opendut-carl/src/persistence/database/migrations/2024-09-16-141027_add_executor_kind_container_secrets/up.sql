@@ -0,0 +1 @@
+ALTER TABLE executor_kind_container ADD COLUMN secrets text[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS peer_annotation;
+DROP INDEX IF EXISTS peer_label_key_value_index;
+DROP TABLE IF EXISTS peer_label;
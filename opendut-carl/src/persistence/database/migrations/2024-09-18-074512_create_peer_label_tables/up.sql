@@ -0,0 +1,14 @@
+CREATE TABLE peer_label (
+    peer_id uuid REFERENCES peer_descriptor(peer_id) ON DELETE CASCADE,
+    key text NOT NULL,
+    value text NOT NULL,
+    PRIMARY KEY(peer_id, key)
+);
+CREATE INDEX peer_label_key_value_index ON peer_label(key, value);
+
+CREATE TABLE peer_annotation (
+    peer_id uuid REFERENCES peer_descriptor(peer_id) ON DELETE CASCADE,
+    key text NOT NULL,
+    value text NOT NULL,
+    PRIMARY KEY(peer_id, key)
+);
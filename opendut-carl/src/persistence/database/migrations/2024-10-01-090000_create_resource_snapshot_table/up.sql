@@ -0,0 +1,6 @@
+CREATE TABLE resource_snapshot (
+    snapshot_id uuid PRIMARY KEY,
+    name text NOT NULL UNIQUE,
+    created_at_unix_millis bigint NOT NULL,
+    content jsonb NOT NULL
+);
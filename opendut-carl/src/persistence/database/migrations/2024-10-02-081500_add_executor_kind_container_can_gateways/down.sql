@@ -0,0 +1 @@
+ALTER TABLE executor_kind_container DROP COLUMN can_gateways;
@@ -0,0 +1 @@
+ALTER TABLE executor_kind_container ADD COLUMN can_gateways text[] NOT NULL DEFAULT '{}';
@@ -0,0 +1 @@
+ALTER TABLE peer_descriptor ADD COLUMN hardware jsonb;
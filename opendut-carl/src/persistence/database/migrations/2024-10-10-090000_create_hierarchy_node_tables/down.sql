@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS hierarchy_node_peer;
+DROP TABLE IF EXISTS hierarchy_node;
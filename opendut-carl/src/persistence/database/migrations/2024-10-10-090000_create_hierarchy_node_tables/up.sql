@@ -0,0 +1,12 @@
+CREATE TABLE hierarchy_node (
+    node_id uuid PRIMARY KEY,
+    name text NOT NULL,
+    level text NOT NULL,
+    parent_id uuid REFERENCES hierarchy_node(node_id) -- deleting a node with children is refused by CARL
+);
+
+CREATE TABLE hierarchy_node_peer (
+    node_id uuid REFERENCES hierarchy_node(node_id) ON DELETE CASCADE,
+    peer_id uuid NOT NULL, -- no foreign key, as peer descriptors are deleted and re-inserted on every update
+    PRIMARY KEY(node_id, peer_id)
+);
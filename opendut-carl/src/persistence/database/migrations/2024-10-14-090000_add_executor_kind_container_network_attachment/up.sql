@@ -0,0 +1 @@
+ALTER TABLE executor_kind_container ADD COLUMN network_attachment text NOT NULL DEFAULT 'host';
@@ -0,0 +1,9 @@
+CREATE TABLE cluster_device_selector (
+    cluster_id uuid REFERENCES cluster_configuration(cluster_id) ON DELETE CASCADE,
+    position integer NOT NULL, -- selectors bind devices in the order of their position
+    tags text[] NOT NULL,
+    interface_kind text, -- NULL matches any kind of interface
+    min_count integer NOT NULL,
+    max_count integer, -- NULL for an unlimited number of devices
+    PRIMARY KEY(cluster_id, position)
+);
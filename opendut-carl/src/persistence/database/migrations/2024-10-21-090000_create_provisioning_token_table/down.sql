@@ -0,0 +1 @@
+DROP TABLE provisioning_token;
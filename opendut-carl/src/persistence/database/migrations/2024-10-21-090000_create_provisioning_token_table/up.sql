@@ -0,0 +1,9 @@
+CREATE TABLE provisioning_token (
+    token_id uuid PRIMARY KEY,
+    pool text NOT NULL,
+    template jsonb NOT NULL,
+    secret_hash text NOT NULL,
+    created_by text NOT NULL,
+    created_at_unix_millis bigint NOT NULL,
+    claimed_by uuid
+);
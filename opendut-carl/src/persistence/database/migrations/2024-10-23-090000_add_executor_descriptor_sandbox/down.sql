@@ -0,0 +1,3 @@
+ALTER TABLE executor_descriptor DROP COLUMN sandbox_read_only_filesystem;
+ALTER TABLE executor_descriptor DROP COLUMN sandbox_seccomp_profile;
+ALTER TABLE executor_descriptor DROP COLUMN sandbox_user;
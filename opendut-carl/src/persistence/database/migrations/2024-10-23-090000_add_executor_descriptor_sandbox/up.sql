@@ -0,0 +1,3 @@
+ALTER TABLE executor_descriptor ADD COLUMN sandbox_user text;
+ALTER TABLE executor_descriptor ADD COLUMN sandbox_seccomp_profile text NOT NULL DEFAULT 'default';
+ALTER TABLE executor_descriptor ADD COLUMN sandbox_read_only_filesystem boolean NOT NULL DEFAULT false;
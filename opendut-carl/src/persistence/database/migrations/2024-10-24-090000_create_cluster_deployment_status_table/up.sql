@@ -0,0 +1,4 @@
+CREATE TABLE cluster_deployment_status (
+    cluster_id uuid PRIMARY KEY REFERENCES cluster_configuration(cluster_id) ON DELETE CASCADE,
+    peers jsonb NOT NULL
+);
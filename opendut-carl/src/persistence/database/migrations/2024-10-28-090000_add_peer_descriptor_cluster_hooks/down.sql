@@ -0,0 +1 @@
+ALTER TABLE peer_descriptor DROP COLUMN cluster_hooks;
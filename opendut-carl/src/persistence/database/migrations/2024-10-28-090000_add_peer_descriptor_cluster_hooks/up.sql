@@ -0,0 +1 @@
+ALTER TABLE peer_descriptor ADD COLUMN cluster_hooks jsonb NOT NULL DEFAULT '[]';
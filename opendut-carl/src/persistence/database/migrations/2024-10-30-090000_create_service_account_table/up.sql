@@ -0,0 +1,8 @@
+CREATE TABLE service_account (
+    service_account_id uuid PRIMARY KEY,
+    name text NOT NULL UNIQUE,
+    scopes jsonb NOT NULL,
+    created_by text NOT NULL,
+    created_at_unix_millis bigint NOT NULL,
+    tokens jsonb NOT NULL
+);
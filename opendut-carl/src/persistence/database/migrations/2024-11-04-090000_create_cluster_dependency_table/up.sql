@@ -0,0 +1,6 @@
+CREATE TABLE cluster_dependency (
+    cluster_id uuid REFERENCES cluster_configuration(cluster_id) ON DELETE CASCADE,
+    dependency_id uuid NOT NULL, -- no foreign key, as updating a cluster configuration removes and re-inserts it
+    position integer NOT NULL, -- keeps the order, in which the dependencies were configured
+    PRIMARY KEY(cluster_id, dependency_id)
+);
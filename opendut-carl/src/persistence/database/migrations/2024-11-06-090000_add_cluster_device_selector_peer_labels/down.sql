@@ -0,0 +1 @@
+ALTER TABLE cluster_device_selector DROP COLUMN peer_labels;
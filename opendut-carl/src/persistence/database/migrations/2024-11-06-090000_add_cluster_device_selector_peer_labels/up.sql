@@ -0,0 +1 @@
+ALTER TABLE cluster_device_selector ADD COLUMN peer_labels text NOT NULL DEFAULT ''; -- label selector on the peers of the devices, empty matches any peer
@@ -0,0 +1,7 @@
+CREATE TABLE project (
+    project_id uuid PRIMARY KEY,
+    name text NOT NULL UNIQUE,
+    members jsonb NOT NULL,
+    peers jsonb NOT NULL,
+    clusters jsonb NOT NULL
+);
@@ -0,0 +1,8 @@
+CREATE TABLE peer_setup_issuance (
+    peer_id uuid PRIMARY KEY,
+    issued_by text NOT NULL,
+    issued_at_unix_millis bigint NOT NULL,
+    expires_at_unix_millis bigint,
+    used_at_unix_millis bigint,
+    revoked_at_unix_millis bigint
+);
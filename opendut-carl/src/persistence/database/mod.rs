@@ -1,12 +1,69 @@
-use diesel::{Connection as _, ConnectionError, PgConnection};
-use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use diesel::connection::SimpleConnection;
+use diesel::{Connection as _, ConnectionError, PgConnection, SqliteConnection};
 use backoff::ExponentialBackoff;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
+use url::Url;
+use crate::persistence::DbConnection;
 use crate::resources::storage::DatabaseConnectInfo;
+use self::migration::{MigrationError, MigrationSummary, MIGRATIONS, SQLITE_MIGRATIONS};
 
+pub mod leader_lock;
+pub mod migration;
 pub mod schema;
+pub mod sqlite_schema;
 
-pub async fn connect(database_connect_info: &DatabaseConnectInfo) -> Result<PgConnection, ConnectError> {
+pub const SQLITE_SCHEME: &str = "sqlite";
+
+pub async fn connect(database_connect_info: &DatabaseConnectInfo) -> Result<DbConnection, ConnectError> {
+    let mut connection = connect_without_migrations(database_connect_info).await?;
+
+    match &mut connection {
+        DbConnection::Sqlite(connection) => migration::run_pending_migrations(connection, SQLITE_MIGRATIONS)?,
+        DbConnection::Postgres(connection) => migration::run_pending_migrations(connection, MIGRATIONS)?,
+    }
+    Ok(connection)
+}
+
+/// Applies or reverts migrations for a controlled upgrade or downgrade of the database schema via `opendut-carl migrate`.
+/// Without a version, all migrations known to this binary are applied.
+pub async fn migrate(database_connect_info: &DatabaseConnectInfo, version: Option<&str>) -> Result<MigrationSummary, ConnectError> {
+    let summary = match connect_without_migrations(database_connect_info).await? {
+        DbConnection::Sqlite(mut connection) => migration::migrate_to(&mut connection, SQLITE_MIGRATIONS, version)?,
+        DbConnection::Postgres(mut connection) => migration::migrate_to(&mut connection, MIGRATIONS, version)?,
+    };
+    Ok(summary)
+}
+
+async fn connect_without_migrations(database_connect_info: &DatabaseConnectInfo) -> Result<DbConnection, ConnectError> {
+    if database_connect_info.url.scheme() == SQLITE_SCHEME {
+        let connection = establish_sqlite(database_connect_info)?;
+        Ok(DbConnection::Sqlite(connection))
+    } else {
+        let connection = establish(database_connect_info).await?;
+        Ok(DbConnection::Postgres(connection))
+    }
+}
+
+/// Connects to a read replica of the database, which is read-only, so no migrations are run.
+/// Read replicas are only supported for Postgres, as an SQLite database is a local file.
+pub async fn connect_read_replica(database_connect_info: &DatabaseConnectInfo) -> Result<DbConnection, ConnectError> {
+    if database_connect_info.url.scheme() == SQLITE_SCHEME {
+        return Err(ConnectError::ReadReplicaNotSupported { backend: SQLITE_SCHEME });
+    }
+    let connection = establish(database_connect_info).await?;
+    Ok(DbConnection::Postgres(connection))
+}
+
+/// Opens a further session on the Postgres database for holding the lock of the leader election (see [`leader_lock`]).
+/// No migrations are run, as the session does not access any tables. Leader election is only supported for Postgres, as an SQLite database is not shared.
+pub async fn connect_leader_session(database_connect_info: &DatabaseConnectInfo) -> Result<PgConnection, ConnectError> {
+    if database_connect_info.url.scheme() == SQLITE_SCHEME {
+        return Err(ConnectError::LeaderElectionNotSupported { backend: SQLITE_SCHEME });
+    }
+    establish(database_connect_info).await
+}
+
+async fn establish(database_connect_info: &DatabaseConnectInfo) -> Result<PgConnection, ConnectError> {
     let DatabaseConnectInfo { url, username, password } = database_connect_info;
 
     let confidential_url = {
@@ -18,7 +75,7 @@ pub async fn connect(database_connect_info: &DatabaseConnectInfo) -> Result<PgCo
         url
     };
 
-    let mut connection = backoff::future::retry(ExponentialBackoff::default(), || async {
+    let connection = backoff::future::retry(ExponentialBackoff::default(), || async {
         PgConnection::establish(confidential_url.as_str())
             .map_err(|cause| match &cause {
                 ConnectionError::BadConnection(_) => {
@@ -38,27 +95,33 @@ pub async fn connect(database_connect_info: &DatabaseConnectInfo) -> Result<PgCo
     }).await?;
     info!("Connection to database at {url} established!");
 
-    run_pending_migrations(&mut connection)
-        .map_err(|cause| ConnectError::Migration { source: cause })?;
-
     Ok(connection)
 }
 
-const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/persistence/database/migrations/");
+/// Opens the SQLite database file, creating it if it does not exist.
+/// The path follows the scheme, i.e. `sqlite:///var/lib/opendut/carl.db` for an absolute path or `sqlite://carl.db` for a relative one.
+fn establish_sqlite(database_connect_info: &DatabaseConnectInfo) -> Result<SqliteConnection, ConnectError> {
+    let url = &database_connect_info.url;
 
-fn run_pending_migrations(connection: &mut PgConnection) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let migrated_versions = connection.run_pending_migrations(MIGRATIONS)?;
+    let path = sqlite_path(url)
+        .ok_or_else(|| ConnectError::Diesel(ConnectionError::InvalidConnectionUrl(format!("Missing path to SQLite database file in '{url}'."))))?;
 
-    if migrated_versions.is_empty() {
-        debug!("No database migrations had to be applied.");
-    } else {
-        let migrated_versions = migrated_versions.into_iter()
-            .map(|version| version.to_string())
-            .collect::<Vec<String>>()
-            .join(", ");
-        info!("Completed running pending database migrations: {migrated_versions}");
-    }
-    Ok(())
+    let mut connection = SqliteConnection::establish(path)
+        .map_err(ConnectError::Diesel)?;
+
+    //SQLite does not enforce foreign keys by default and fails right away when another connection holds a lock
+    connection.batch_execute("PRAGMA foreign_keys = ON; PRAGMA busy_timeout = 5000;")
+        .map_err(|cause| ConnectError::Diesel(ConnectionError::CouldntSetupConfiguration(cause)))?;
+
+    info!("Connection to SQLite database at {path} established!");
+    Ok(connection)
+}
+
+/// Path of the SQLite database file, if the URL designates one, e.g. `/var/lib/opendut/carl/carl.db` for `sqlite:///var/lib/opendut/carl/carl.db`.
+pub fn sqlite_path(url: &Url) -> Option<&str> {
+    url.as_str()
+        .strip_prefix("sqlite://")
+        .filter(|path| !path.is_empty())
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -66,13 +129,17 @@ pub enum ConnectError {
     #[error("Connection error from Diesel")]
     Diesel(#[source] diesel::ConnectionError),
     #[error("Error while applying migrations")]
-    Migration { #[source] source: Box<dyn std::error::Error + Send + Sync> },
+    Migration(#[from] MigrationError),
+    #[error("Read replicas are not supported for {backend} databases")]
+    ReadReplicaNotSupported { backend: &'static str },
+    #[error("Leader election is not supported for {backend} databases")]
+    LeaderElectionNotSupported { backend: &'static str },
 }
 
 
 #[cfg(any(test, doc))] //needed for doctests to compile
 pub mod testing {
-    use crate::persistence::database;
+    use crate::persistence::{database, DbConnection};
     use diesel::{Connection, PgConnection};
     use testcontainers_modules::testcontainers::ContainerAsync;
     use testcontainers_modules::{postgres, testcontainers::runners::AsyncRunner};
@@ -97,7 +164,9 @@ pub mod testing {
     pub async fn spawn_and_connect() -> anyhow::Result<PostgresConnection> {
         let (container, connect_info) = spawn().await?;
 
-        let mut connection = database::connect(&connect_info).await?;
+        let DbConnection::Postgres(mut connection) = database::connect(&connect_info).await? else {
+            anyhow::bail!("Expected connection to Postgres database.");
+        };
         connection.begin_test_transaction()?;
         Ok(PostgresConnection { container, connection })
     }
@@ -126,6 +195,7 @@ pub mod testing {
 
         let resources_manager = ResourcesManager::create(PersistenceOptions::Enabled {
             database_connect_info: connect_info.clone(),
+            read_replicas: Vec::new(),
         }).await?;
 
         Ok(PostgresResources { container, resources_manager })
@@ -136,6 +206,28 @@ pub mod testing {
         pub resources_manager: ResourcesManagerRef,
     }
 
+    /// Creates an SQLite database in a temporary directory and returns a ResourcesManager using it for testing.
+    pub async fn connect_sqlite_resources_manager() -> anyhow::Result<SqliteResources> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("carl.db");
+
+        let resources_manager = ResourcesManager::create(PersistenceOptions::Enabled {
+            database_connect_info: DatabaseConnectInfo {
+                url: Url::parse(&format!("sqlite://{}", path.display()))?,
+                username: String::new(),
+                password: Password::new_static(""),
+            },
+            read_replicas: Vec::new(),
+        }).await?;
+
+        Ok(SqliteResources { directory, resources_manager })
+    }
+    pub struct SqliteResources {
+        #[allow(unused)] //primarily carried along to extend its lifetime until the end of the test (directory is deleted when variable is dropped)
+        pub directory: tempfile::TempDir,
+        pub resources_manager: ResourcesManagerRef,
+    }
+
     async fn spawn() -> anyhow::Result<(ContainerAsync<postgres::Postgres>, DatabaseConnectInfo)> {
         let container = postgres::Postgres::default().start().await?;
         let host = container.get_host().await?;
@@ -9,6 +9,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    cluster_dependency (cluster_id, dependency_id) {
+        cluster_id -> Uuid,
+        dependency_id -> Uuid,
+        position -> Int4,
+    }
+}
+
 diesel::table! {
     cluster_device (cluster_id, device_id) {
         cluster_id -> Uuid,
@@ -16,6 +24,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    cluster_deployment_status (cluster_id) {
+        cluster_id -> Uuid,
+        peers -> Jsonb,
+    }
+}
+
+diesel::table! {
+    cluster_device_selector (cluster_id, position) {
+        cluster_id -> Uuid,
+        position -> Int4,
+        tags -> Array<Nullable<Text>>,
+        interface_kind -> Nullable<Text>,
+        min_count -> Int4,
+        max_count -> Nullable<Int4>,
+        peer_labels -> Text,
+    }
+}
+
+diesel::table! {
+    cluster_peer_group (cluster_id, peer_group_id) {
+        cluster_id -> Uuid,
+        peer_group_id -> Uuid,
+    }
+}
+
 diesel::table! {
     device_descriptor (device_id) {
         device_id -> Uuid,
@@ -38,6 +72,9 @@ diesel::table! {
         kind -> Text,
         results_url -> Nullable<Text>,
         peer_id -> Uuid,
+        sandbox_user -> Nullable<Text>,
+        sandbox_seccomp_profile -> Text,
+        sandbox_read_only_filesystem -> Bool,
     }
 }
 
@@ -53,6 +90,25 @@ diesel::table! {
         ports -> Array<Nullable<Text>>,
         command -> Nullable<Text>,
         args -> Array<Nullable<Text>>,
+        secrets -> Array<Nullable<Text>>,
+        can_gateways -> Array<Nullable<Text>>,
+        network_attachment -> Text,
+    }
+}
+
+diesel::table! {
+    hierarchy_node (node_id) {
+        node_id -> Uuid,
+        name -> Text,
+        level -> Text,
+        parent_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    hierarchy_node_peer (node_id, peer_id) {
+        node_id -> Uuid,
+        peer_id -> Uuid,
     }
 }
 
@@ -76,33 +132,150 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    peer_annotation (peer_id, key) {
+        peer_id -> Uuid,
+        key -> Text,
+        value -> Text,
+    }
+}
+
 diesel::table! {
     peer_descriptor (peer_id) {
         peer_id -> Uuid,
         name -> Text,
         location -> Nullable<Text>,
         network_bridge_name -> Nullable<Text>,
+        hardware -> Nullable<Jsonb>,
+        cluster_hooks -> Jsonb,
+    }
+}
+
+diesel::table! {
+    peer_group (peer_group_id) {
+        peer_group_id -> Uuid,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    peer_group_label (peer_group_id, name) {
+        peer_group_id -> Uuid,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    peer_group_member (peer_group_id, peer_id) {
+        peer_group_id -> Uuid,
+        peer_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    peer_label (peer_id, key) {
+        peer_id -> Uuid,
+        key -> Text,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    peer_setup_issuance (peer_id) {
+        peer_id -> Uuid,
+        issued_by -> Text,
+        issued_at_unix_millis -> Int8,
+        expires_at_unix_millis -> Nullable<Int8>,
+        used_at_unix_millis -> Nullable<Int8>,
+        revoked_at_unix_millis -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    project (project_id) {
+        project_id -> Uuid,
+        name -> Text,
+        members -> Jsonb,
+        peers -> Jsonb,
+        clusters -> Jsonb,
+    }
+}
+
+diesel::table! {
+    provisioning_token (token_id) {
+        token_id -> Uuid,
+        pool -> Text,
+        template -> Jsonb,
+        secret_hash -> Text,
+        created_by -> Text,
+        created_at_unix_millis -> Int8,
+        claimed_by -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    resource_snapshot (snapshot_id) {
+        snapshot_id -> Uuid,
+        name -> Text,
+        created_at_unix_millis -> Int8,
+        content -> Jsonb,
+    }
+}
+
+diesel::table! {
+    service_account (service_account_id) {
+        service_account_id -> Uuid,
+        name -> Text,
+        scopes -> Jsonb,
+        created_by -> Text,
+        created_at_unix_millis -> Int8,
+        tokens -> Jsonb,
     }
 }
 
 diesel::joinable!(cluster_configuration -> peer_descriptor (leader_id));
+diesel::joinable!(cluster_deployment_status -> cluster_configuration (cluster_id));
 diesel::joinable!(cluster_device -> cluster_configuration (cluster_id));
 diesel::joinable!(cluster_device -> device_descriptor (device_id));
+diesel::joinable!(cluster_device_selector -> cluster_configuration (cluster_id));
+diesel::joinable!(cluster_peer_group -> cluster_configuration (cluster_id));
+diesel::joinable!(cluster_peer_group -> peer_group (peer_group_id));
 diesel::joinable!(device_descriptor -> network_interface_descriptor (network_interface_id));
 diesel::joinable!(device_tag -> device_descriptor (device_id));
 diesel::joinable!(executor_descriptor -> peer_descriptor (peer_id));
 diesel::joinable!(executor_kind_container -> executor_descriptor (executor_id));
+diesel::joinable!(hierarchy_node_peer -> hierarchy_node (node_id));
 diesel::joinable!(network_interface_descriptor -> peer_descriptor (peer_id));
 diesel::joinable!(network_interface_kind_can -> network_interface_descriptor (network_interface_id));
+diesel::joinable!(peer_annotation -> peer_descriptor (peer_id));
+diesel::joinable!(peer_group_label -> peer_group (peer_group_id));
+diesel::joinable!(peer_group_member -> peer_group (peer_group_id));
+diesel::joinable!(peer_label -> peer_descriptor (peer_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     cluster_configuration,
+    cluster_dependency,
+    cluster_deployment_status,
     cluster_device,
+    cluster_device_selector,
+    cluster_peer_group,
     device_descriptor,
     device_tag,
     executor_descriptor,
     executor_kind_container,
+    hierarchy_node,
+    hierarchy_node_peer,
     network_interface_descriptor,
     network_interface_kind_can,
+    peer_annotation,
     peer_descriptor,
+    peer_group,
+    peer_group_label,
+    peer_group_member,
+    peer_label,
+    peer_setup_issuance,
+    project,
+    provisioning_token,
+    resource_snapshot,
+    service_account,
 );
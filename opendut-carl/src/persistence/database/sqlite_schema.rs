@@ -0,0 +1,9 @@
+// Schema of the SQLite backend, which stores each resource as a JSON document.
+
+diesel::table! {
+    resource_document (kind, id) {
+        kind -> Text,
+        id -> Text,
+        value -> Text,
+    }
+}
@@ -1,27 +1,68 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::resources::storage::volatile::VolatileResourcesStorage;
-use diesel::PgConnection;
+use diesel::connection::{AnsiTransactionManager, TransactionManager};
+use diesel::{PgConnection, SqliteConnection};
 
 pub mod database;
 pub(crate) mod resources;
-mod query;
+pub(crate) mod query;
 
 pub struct Storage<'a> {
     pub db: Db<'a>,
     pub memory: &'a mut Memory,
 }
 pub struct Db<'a> {
-    pub inner: Mutex<&'a mut PgConnection>, //Mutex rather than RwLock, because we share this between threads (i.e. we need it to implement `Sync`)
+    pub inner: Mutex<&'a mut DbConnection>, //Mutex rather than RwLock, because we share this between threads (i.e. we need it to implement `Sync`)
 }
 impl<'a> Db<'a> {
-    pub fn from_connection(connection: &'a mut PgConnection) -> Db {
+    pub fn from_connection(connection: &'a mut DbConnection) -> Db {
         Self { inner: Mutex::new(connection) }
     }
-    pub fn connection(&self) -> MutexGuard<&'a mut PgConnection> {
+    pub fn connection(&self) -> MutexGuard<&'a mut DbConnection> {
         self.inner.lock().expect("error while locking mutex for database connection")
     }
 }
+
+/// Connection to one of the supported database backends, selected by the scheme of `persistence.database.url`.
+/// Postgres stores the resources in relational tables, whereas SQLite stores each resource as a JSON document,
+/// which allows small deployments to persist resources without operating a Postgres server.
+pub enum DbConnection {
+    Postgres(PgConnection),
+    Sqlite(SqliteConnection),
+}
+impl DbConnection {
+    /// Runs the code in a transaction, which is rolled back, if the code returns an error. Works like [`diesel::Connection::transaction`].
+    pub fn transaction<T, E, F>(&mut self, code: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut DbConnection) -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        match self {
+            DbConnection::Postgres(connection) => AnsiTransactionManager::begin_transaction(connection)?,
+            DbConnection::Sqlite(connection) => AnsiTransactionManager::begin_transaction(connection)?,
+        }
+
+        match code(self) {
+            Ok(value) => {
+                match self {
+                    DbConnection::Postgres(connection) => AnsiTransactionManager::commit_transaction(connection)?,
+                    DbConnection::Sqlite(connection) => AnsiTransactionManager::commit_transaction(connection)?,
+                }
+                Ok(value)
+            }
+            Err(error) => {
+                let rollback = match self {
+                    DbConnection::Postgres(connection) => AnsiTransactionManager::rollback_transaction(connection),
+                    DbConnection::Sqlite(connection) => AnsiTransactionManager::rollback_transaction(connection),
+                };
+                rollback?;
+                Err(error)
+            }
+        }
+    }
+}
+
 pub type Memory = VolatileResourcesStorage;
 
 pub(crate) mod error {
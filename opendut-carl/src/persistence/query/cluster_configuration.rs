@@ -2,16 +2,18 @@ use crate::persistence::database::schema;
 use crate::persistence::error::{PersistenceError, PersistenceResult};
 use crate::persistence::query;
 use crate::persistence::query::cluster_device::PersistableClusterDevice;
+use crate::persistence::query::cluster_peer_group::PersistableClusterPeerGroup;
 use crate::persistence::query::Filter;
 use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
 use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::peer::group::PeerGroupId;
 use opendut_types::peer::PeerId;
 use opendut_types::topology::DeviceId;
 use std::collections::HashSet;
 use uuid::Uuid;
 
 pub fn insert(cluster_configuration: ClusterConfiguration, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let ClusterConfiguration { id, name, leader, devices } = cluster_configuration;
+    let ClusterConfiguration { id, name, leader, devices, peer_groups, device_selectors, dependencies } = cluster_configuration;
 
     insert_persistable(PersistableClusterConfiguration {
         cluster_id: id.0,
@@ -26,6 +28,17 @@ pub fn insert(cluster_configuration: ClusterConfiguration, connection: &mut PgCo
         }, connection)?
     }
 
+    for peer_group in peer_groups {
+        query::cluster_peer_group::insert(PersistableClusterPeerGroup {
+            cluster_id: id.0,
+            peer_group_id: peer_group.0,
+        }, connection)?
+    }
+
+    query::cluster_device_selector::insert(id, device_selectors, connection)?;
+
+    query::cluster_dependency::insert(id, dependencies, connection)?;
+
     Ok(())
 }
 
@@ -93,11 +106,23 @@ pub fn list(filter_by_cluster_id: Filter<ClusterId>, connection: &mut PgConnecti
             .map(|cluster_device| DeviceId::from(cluster_device.device_id))
             .collect::<HashSet<_>>();
 
+        let peer_groups = query::cluster_peer_group::list_filtered_by_cluster_id(cluster_id, connection)?
+            .into_iter()
+            .map(|cluster_peer_group| PeerGroupId::from(cluster_peer_group.peer_group_id))
+            .collect::<HashSet<_>>();
+
+        let device_selectors = query::cluster_device_selector::list_filtered_by_cluster_id(cluster_id, connection)?;
+
+        let dependencies = query::cluster_dependency::list_filtered_by_cluster_id(cluster_id, connection)?;
+
         Ok(ClusterConfiguration {
             id: cluster_id,
             name,
             leader: leader_id,
             devices,
+            peer_groups,
+            device_selectors,
+            dependencies,
         })
     })
     .collect::<PersistenceResult<Vec<_>>>()
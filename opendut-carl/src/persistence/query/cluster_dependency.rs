@@ -0,0 +1,50 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::cluster::ClusterId;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::cluster_dependency)]
+#[diesel(belongs_to(PersistableClusterConfiguration, foreign_key = cluster_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PersistableClusterDependency {
+    pub cluster_id: Uuid,
+    pub dependency_id: Uuid,
+    pub position: i32,
+}
+
+pub fn insert(cluster_id: ClusterId, dependencies: Vec<ClusterId>, connection: &mut PgConnection) -> PersistenceResult<()> {
+    for (position, dependency_id) in dependencies.into_iter().enumerate() {
+        let position = i32::try_from(position)
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDependency>(cluster_id.0, cause))?;
+
+        let persistable = PersistableClusterDependency {
+            cluster_id: cluster_id.0,
+            dependency_id: dependency_id.0,
+            position,
+        };
+
+        diesel::insert_into(schema::cluster_dependency::table)
+            .values(&persistable)
+            .on_conflict((schema::cluster_dependency::cluster_id, schema::cluster_dependency::dependency_id))
+            .do_update()
+            .set(&persistable)
+            .execute(connection)
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDependency>(cluster_id.0, cause))?;
+    }
+    Ok(())
+}
+
+pub fn list_filtered_by_cluster_id(cluster_id: ClusterId, connection: &mut PgConnection) -> PersistenceResult<Vec<ClusterId>> {
+    let persistables = schema::cluster_dependency::table
+        .filter(schema::cluster_dependency::cluster_id.eq(cluster_id.0))
+        .order(schema::cluster_dependency::position.asc())
+        .select(PersistableClusterDependency::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PersistableClusterDependency>)?;
+
+    Ok(persistables.into_iter()
+        .map(|persistable| ClusterId::from(persistable.dependency_id))
+        .collect())
+}
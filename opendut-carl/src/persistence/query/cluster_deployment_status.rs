@@ -0,0 +1,82 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::cluster::{ClusterDeploymentStatus, ClusterId, PeerDeploymentStatus};
+use uuid::Uuid;
+
+pub fn insert(status: ClusterDeploymentStatus, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ClusterDeploymentStatus { id, peers } = status;
+
+    let peers = serde_json::to_value(peers)
+        .map_err(|cause| PersistenceError::insert::<ClusterDeploymentStatus>(id.0, cause))?;
+
+    insert_persistable(PersistableClusterDeploymentStatus {
+        cluster_id: id.0,
+        peers,
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::cluster_deployment_status)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableClusterDeploymentStatus {
+    pub cluster_id: Uuid,
+    pub peers: serde_json::Value,
+}
+fn insert_persistable(persistable: PersistableClusterDeploymentStatus, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::cluster_deployment_status::table)
+        .values(&persistable)
+        .on_conflict(schema::cluster_deployment_status::cluster_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ClusterDeploymentStatus>(persistable.cluster_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(cluster_id: ClusterId, connection: &mut PgConnection) -> PersistenceResult<Option<ClusterDeploymentStatus>> {
+    let result = list(Filter::By(cluster_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::cluster_deployment_status::table
+            .filter(schema::cluster_deployment_status::cluster_id.eq(cluster_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ClusterDeploymentStatus>(cluster_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_cluster_id: Filter<ClusterId>, connection: &mut PgConnection) -> PersistenceResult<Vec<ClusterDeploymentStatus>> {
+    let persistable_statuses = {
+        let mut query = schema::cluster_deployment_status::table.into_boxed();
+
+        if let Filter::By(cluster_id) = filter_by_cluster_id {
+            query = query.filter(schema::cluster_deployment_status::cluster_id.eq(cluster_id.0));
+        }
+
+        query
+            .select(PersistableClusterDeploymentStatus::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<ClusterDeploymentStatus>)?
+    };
+
+    persistable_statuses.into_iter().map(|persistable| {
+        let PersistableClusterDeploymentStatus { cluster_id, peers } = persistable;
+
+        let peers = serde_json::from_value::<Vec<PeerDeploymentStatus>>(peers)
+            .map_err(|cause| PersistenceError::get::<ClusterDeploymentStatus>(cluster_id, cause))?;
+
+        Ok(ClusterDeploymentStatus {
+            id: ClusterId::from(cluster_id),
+            peers,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<ClusterDeploymentStatus>(cause)
+            .context("Failed to convert from database values to ClusterDeploymentStatus.")
+    )
+}
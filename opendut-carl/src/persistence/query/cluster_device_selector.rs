@@ -0,0 +1,92 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::types::null_removing_text_array::NullRemovingTextArray;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::cluster::{ClusterId, DeviceCount, DeviceInterfaceKind, DeviceSelector};
+use opendut_types::peer::label::LabelSelector;
+use opendut_types::topology::DeviceTag;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::cluster_device_selector)]
+#[diesel(belongs_to(PersistableClusterConfiguration, foreign_key = cluster_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PersistableClusterDeviceSelector {
+    pub cluster_id: Uuid,
+    pub position: i32,
+    pub tags: NullRemovingTextArray,
+    pub interface_kind: Option<String>,
+    pub min_count: i32,
+    pub max_count: Option<i32>,
+    pub peer_labels: String,
+}
+
+pub fn insert(cluster_id: ClusterId, device_selectors: Vec<DeviceSelector>, connection: &mut PgConnection) -> PersistenceResult<()> {
+    for (position, device_selector) in device_selectors.into_iter().enumerate() {
+        let DeviceSelector { tags, interface_kind, peer_labels, count } = device_selector;
+
+        let position = i32::try_from(position)
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDeviceSelector>(cluster_id.0, cause))?;
+        let min_count = i32::try_from(count.min())
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDeviceSelector>(cluster_id.0, cause))?;
+        let max_count = count.max()
+            .map(i32::try_from)
+            .transpose()
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDeviceSelector>(cluster_id.0, cause))?;
+
+        let persistable = PersistableClusterDeviceSelector {
+            cluster_id: cluster_id.0,
+            position,
+            tags: tags.into_iter().map(String::from).collect(),
+            interface_kind: interface_kind.map(|kind| kind.to_string()),
+            min_count,
+            max_count,
+            peer_labels: peer_labels.to_string(),
+        };
+
+        diesel::insert_into(schema::cluster_device_selector::table)
+            .values(&persistable)
+            .execute(connection)
+            .map_err(|cause| PersistenceError::insert::<PersistableClusterDeviceSelector>(cluster_id.0, cause))?;
+    }
+    Ok(())
+}
+
+pub fn list_filtered_by_cluster_id(cluster_id: ClusterId, connection: &mut PgConnection) -> PersistenceResult<Vec<DeviceSelector>> {
+    let persistables = schema::cluster_device_selector::table
+        .filter(schema::cluster_device_selector::cluster_id.eq(cluster_id.0))
+        .order(schema::cluster_device_selector::position.asc())
+        .select(PersistableClusterDeviceSelector::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PersistableClusterDeviceSelector>)?;
+
+    persistables.into_iter().map(|persistable| {
+        let PersistableClusterDeviceSelector { cluster_id, position: _, tags, interface_kind, min_count, max_count, peer_labels } = persistable;
+
+        let tags = tags.into_iter()
+            .map(DeviceTag::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+
+        let interface_kind = interface_kind
+            .map(|kind| DeviceInterfaceKind::from_str(&kind))
+            .transpose()
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+
+        let peer_labels = LabelSelector::from_str(&peer_labels)
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+
+        let min_count = u32::try_from(min_count)
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+        let max_count = max_count
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+        let count = DeviceCount::new(min_count, max_count)
+            .map_err(|cause| PersistenceError::get::<PersistableClusterDeviceSelector>(cluster_id, cause))?;
+
+        Ok(DeviceSelector { tags, interface_kind, peer_labels, count })
+    })
+    .collect()
+}
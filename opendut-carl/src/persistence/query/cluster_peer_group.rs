@@ -0,0 +1,33 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::cluster::ClusterId;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::cluster_peer_group)]
+#[diesel(belongs_to(PersistableClusterConfiguration, foreign_key = cluster_id))]
+#[diesel(belongs_to(PersistablePeerGroup, foreign_key = peer_group_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PersistableClusterPeerGroup {
+    pub cluster_id: Uuid,
+    pub peer_group_id: Uuid,
+}
+pub fn insert(persistable: PersistableClusterPeerGroup, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::cluster_peer_group::table)
+        .values(&persistable)
+        .on_conflict((schema::cluster_peer_group::cluster_id, schema::cluster_peer_group::peer_group_id))
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PersistableClusterPeerGroup>(persistable.peer_group_id, cause))?;
+    Ok(())
+}
+
+pub fn list_filtered_by_cluster_id(cluster_id: ClusterId, connection: &mut PgConnection) -> PersistenceResult<Vec<PersistableClusterPeerGroup>> {
+    schema::cluster_peer_group::table
+        .filter(schema::cluster_peer_group::cluster_id.eq(cluster_id.0))
+        .select(PersistableClusterPeerGroup::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PersistableClusterPeerGroup>)
+}
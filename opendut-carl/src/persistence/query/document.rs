@@ -0,0 +1,118 @@
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::persistence::database::sqlite_schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use crate::resources::ids::IntoId;
+use crate::resources::resource::Resource;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::provisioning::ProvisioningToken;
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
+use opendut_types::service_account::ServiceAccount;
+use opendut_types::snapshot::ResourceSnapshot;
+
+/// Resource, which is stored as a JSON document in the SQLite backend.
+pub trait Document: Resource + Serialize + DeserializeOwned {
+    /// Distinguishes the documents of the different resources. Must not change, as it is stored alongside the documents.
+    const KIND: &'static str;
+}
+macro_rules! impl_document {
+    ($resource:ty, $kind:literal) => {
+        impl Document for $resource {
+            const KIND: &'static str = $kind;
+        }
+    }
+}
+impl_document!(ClusterConfiguration, "cluster_configuration");
+impl_document!(ClusterDeployment, "cluster_deployment");
+impl_document!(ClusterDeploymentStatus, "cluster_deployment_status");
+impl_document!(HierarchyNode, "hierarchy_node");
+impl_document!(PeerDescriptor, "peer_descriptor");
+impl_document!(PeerGroup, "peer_group");
+impl_document!(PeerSetupIssuance, "peer_setup_issuance");
+impl_document!(Project, "project");
+impl_document!(ProvisioningToken, "provisioning_token");
+impl_document!(ResourceSnapshot, "resource_snapshot");
+impl_document!(ServiceAccount, "service_account");
+
+fn uuid_of<R: Document>(id: R::Id) -> Uuid {
+    IntoId::<R>::into_id(id).value()
+}
+
+pub fn insert<R: Document>(id: R::Id, resource: &R, connection: &mut SqliteConnection) -> PersistenceResult<()> {
+    let id = uuid_of::<R>(id);
+
+    let value = serde_json::to_string(resource)
+        .map_err(|cause| PersistenceError::insert::<R>(id, cause))?;
+
+    let persistable = PersistableResourceDocument {
+        kind: R::KIND.to_owned(),
+        id: id.to_string(),
+        value,
+    };
+
+    diesel::insert_into(sqlite_schema::resource_document::table)
+        .values(&persistable)
+        .on_conflict((sqlite_schema::resource_document::kind, sqlite_schema::resource_document::id))
+        .do_update()
+        .set(sqlite_schema::resource_document::value.eq(&persistable.value))
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<R>(id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable)]
+#[diesel(table_name = sqlite_schema::resource_document)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+struct PersistableResourceDocument {
+    pub kind: String,
+    pub id: String,
+    pub value: String,
+}
+
+pub fn remove<R: Document>(id: R::Id, connection: &mut SqliteConnection) -> PersistenceResult<Option<R>> {
+    let result = list::<R>(Filter::By(id.clone()), connection)?
+        .into_iter().next();
+
+    let id = uuid_of::<R>(id);
+
+    diesel::delete(
+        sqlite_schema::resource_document::table
+            .filter(sqlite_schema::resource_document::kind.eq(R::KIND))
+            .filter(sqlite_schema::resource_document::id.eq(id.to_string()))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<R>(id, cause))?;
+
+    Ok(result)
+}
+
+pub fn list<R: Document>(filter_by_id: Filter<R::Id>, connection: &mut SqliteConnection) -> PersistenceResult<Vec<R>> {
+    let mut query = sqlite_schema::resource_document::table
+        .filter(sqlite_schema::resource_document::kind.eq(R::KIND))
+        .into_boxed();
+
+    if let Filter::By(id) = filter_by_id {
+        query = query.filter(sqlite_schema::resource_document::id.eq(uuid_of::<R>(id).to_string()));
+    }
+
+    let values = query
+        .select(sqlite_schema::resource_document::value)
+        .get_results::<String>(connection)
+        .map_err(PersistenceError::list::<R>)?;
+
+    values.into_iter()
+        .map(|value| serde_json::from_str::<R>(&value))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|cause|
+            PersistenceError::list::<R>(cause)
+                .context(format!("Failed to convert from database values to {}.", R::KIND))
+        )
+}
@@ -1,5 +1,6 @@
 use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
-use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume};
+use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, ContainerVolume};
+use opendut_types::peer::executor::sandbox::{ExecutorSandbox, ExecutorUser, SeccompProfile};
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId, ExecutorKind, ResultsUrl};
 use opendut_types::peer::PeerId;
 use tracing::warn;
@@ -21,6 +22,9 @@ pub(in crate::persistence) struct PersistableExecutorDescriptor {
     pub kind: PersistableExecutorKind,
     pub results_url: Option<String>,
     pub peer_id: Uuid,
+    pub sandbox_user: Option<String>,
+    pub sandbox_seccomp_profile: String,
+    pub sandbox_read_only_filesystem: bool,
 }
 
 #[derive(diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::Identifiable, diesel::Associations, diesel::AsChangeset, Debug, PartialEq)]
@@ -39,10 +43,13 @@ pub(in crate::persistence) struct PersistableExecutorKindContainer {
     ports: NullRemovingTextArray,
     command: Option<String>,
     args: NullRemovingTextArray,
+    secrets: NullRemovingTextArray,
+    can_gateways: NullRemovingTextArray,
+    network_attachment: String,
 }
 
 pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let ExecutorDescriptor { id, kind, results_url } = executor;
+    let ExecutorDescriptor { id, kind, results_url, sandbox } = executor;
 
     let executor_id = id.uuid;
 
@@ -50,7 +57,7 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
         ExecutorKind::Executable => {
             (PersistableExecutorKind::Executable, None)
         }
-        ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
+        ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args, secrets, can_gateways, network_attachment } => {
 
             let engine = engine.into();
             let name = match name {
@@ -67,6 +74,9 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
                 ContainerCommand::Value(value) => Some(value),
             };
             let args = args.into_iter().map(|arg| arg.value().to_owned()).collect();
+            let secrets = secrets.into_iter().map(|secret| secret.value().to_owned()).collect();
+            let can_gateways = can_gateways.into_iter().map(|can_gateway| can_gateway.to_string()).collect();
+            let network_attachment = network_attachment.to_string();
 
             let executor_kind_container = PersistableExecutorKindContainer {
                 executor_id,
@@ -79,6 +89,9 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
                 ports,
                 command,
                 args,
+                secrets,
+                can_gateways,
+                network_attachment,
             };
             (PersistableExecutorKind::Container, Some(executor_kind_container))
         }
@@ -86,11 +99,16 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
 
     let results_url = results_url.map(|url| url.to_string());
 
+    let ExecutorSandbox { user, seccomp_profile, read_only_filesystem } = sandbox;
+
     let executor_descriptor = PersistableExecutorDescriptor {
         executor_id,
         kind,
         results_url,
         peer_id: peer_id.uuid,
+        sandbox_user: user.map(|user| user.to_string()),
+        sandbox_seccomp_profile: seccomp_profile.to_string(),
+        sandbox_read_only_filesystem: read_only_filesystem,
     };
 
     insert_persistable(executor_descriptor, executor_kind_container, executor.id, connection)
@@ -138,7 +156,7 @@ pub fn list_filtered_by_peer(
     let persistables = list_filtered_by_peer_id_persistable(peer_id, connection)?;
 
     let result = persistables.into_iter().map(|(persistable_executable_descriptor, persistable_executable_kind_container)| {
-        let PersistableExecutorDescriptor { executor_id, kind, results_url, peer_id: _ } = persistable_executable_descriptor;
+        let PersistableExecutorDescriptor { executor_id, kind, results_url, peer_id: _, sandbox_user, sandbox_seccomp_profile, sandbox_read_only_filesystem } = persistable_executable_descriptor;
 
         let id = ExecutorId::from(executor_id);
 
@@ -147,7 +165,15 @@ pub fn list_filtered_by_peer(
         let results_url = results_url.map(ResultsUrl::try_from).transpose()
             .map_err(PersistenceError::list::<ExecutorDescriptor>)?;
 
-        Ok(ExecutorDescriptor { id, kind, results_url })
+        let sandbox = ExecutorSandbox {
+            user: sandbox_user.map(ExecutorUser::try_from).transpose()
+                .map_err(PersistenceError::list::<ExecutorDescriptor>)?,
+            seccomp_profile: SeccompProfile::try_from(sandbox_seccomp_profile)
+                .map_err(PersistenceError::list::<ExecutorDescriptor>)?,
+            read_only_filesystem: sandbox_read_only_filesystem,
+        };
+
+        Ok(ExecutorDescriptor { id, kind, results_url, sandbox })
     }).collect::<PersistenceResult<_>>()?;
 
     Ok(result)
@@ -179,7 +205,7 @@ fn executor_kind_from_persistable(
             let persistable_executor_kind_container = persistable_executor_kind_container
                 .ok_or(PersistenceError::new::<ExecutorKind>(None::<Uuid>, PersistenceOperation::List, Option::<PersistenceError>::None))?;
 
-            let PersistableExecutorKindContainer { executor_id: _, engine, name, image, volumes, devices, envs, ports, command, args } = persistable_executor_kind_container;
+            let PersistableExecutorKindContainer { executor_id: _, engine, name, image, volumes, devices, envs, ports, command, args, secrets, can_gateways, network_attachment } = persistable_executor_kind_container;
 
             let engine = engine.into();
 
@@ -233,6 +259,19 @@ fn executor_kind_from_persistable(
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(PersistenceError::list::<ExecutorKind>)?;
 
+            let secrets = secrets.into_iter()
+                .map(ContainerSecret::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            let can_gateways = can_gateways.into_iter()
+                .map(ContainerCanGateway::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            let network_attachment = ContainerNetworkAttachment::try_from(network_attachment)
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
             ExecutorKind::Container {
                 engine,
                 name,
@@ -243,6 +282,9 @@ fn executor_kind_from_persistable(
                 ports,
                 command,
                 args,
+                secrets,
+                can_gateways,
+                network_attachment,
             }
         }
     };
@@ -0,0 +1,148 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNode, HierarchyNodeId, HierarchyNodeName};
+use opendut_types::peer::PeerId;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub fn insert(node: HierarchyNode, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let HierarchyNode { id, name, level, parent, peers } = node;
+
+    insert_persistable(PersistableHierarchyNode {
+        node_id: id.0,
+        name: name.value(),
+        level: level.to_string(),
+        parent_id: parent.map(|parent| parent.0),
+    }, connection)?;
+
+    //Only delete the peers rather than the whole node,
+    //as a deletion would be refused while the node has children.
+    remove_peers(id, connection)?;
+
+    for peer in peers {
+        insert_peer(PersistableHierarchyNodePeer {
+            node_id: id.0,
+            peer_id: peer.uuid,
+        }, connection)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::hierarchy_node)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableHierarchyNode {
+    pub node_id: Uuid,
+    pub name: String,
+    pub level: String,
+    pub parent_id: Option<Uuid>,
+}
+fn insert_persistable(persistable: PersistableHierarchyNode, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::hierarchy_node::table)
+        .values(&persistable)
+        .on_conflict(schema::hierarchy_node::node_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<HierarchyNode>(persistable.node_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::hierarchy_node_peer)]
+#[diesel(belongs_to(PersistableHierarchyNode, foreign_key = node_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableHierarchyNodePeer {
+    pub node_id: Uuid,
+    pub peer_id: Uuid,
+}
+fn insert_peer(persistable: PersistableHierarchyNodePeer, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::hierarchy_node_peer::table)
+        .values(&persistable)
+        .on_conflict((schema::hierarchy_node_peer::node_id, schema::hierarchy_node_peer::peer_id))
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PersistableHierarchyNodePeer>(persistable.peer_id, cause))?;
+    Ok(())
+}
+
+fn remove_peers(node_id: HierarchyNodeId, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::delete(
+        schema::hierarchy_node_peer::table
+            .filter(schema::hierarchy_node_peer::node_id.eq(node_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PersistableHierarchyNodePeer>(node_id.0, cause))?;
+
+    Ok(())
+}
+
+pub fn remove(node_id: HierarchyNodeId, connection: &mut PgConnection) -> PersistenceResult<Option<HierarchyNode>> {
+    let result = list(Filter::By(node_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::hierarchy_node::table
+            .filter(schema::hierarchy_node::node_id.eq(node_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<HierarchyNode>(node_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_node_id: Filter<HierarchyNodeId>, connection: &mut PgConnection) -> PersistenceResult<Vec<HierarchyNode>> {
+    let persistable_nodes = {
+        let mut query = schema::hierarchy_node::table.into_boxed();
+
+        if let Filter::By(node_id) = filter_by_node_id {
+            query = query.filter(schema::hierarchy_node::node_id.eq(node_id.0));
+        }
+
+        query
+            .select(PersistableHierarchyNode::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<HierarchyNode>)?
+    };
+
+    persistable_nodes.into_iter().map(|persistable| {
+        let PersistableHierarchyNode { node_id, name, level, parent_id } = persistable;
+
+        let name = HierarchyNodeName::try_from(name)
+            .map_err(|cause| PersistenceError::get::<HierarchyNode>(node_id, cause))?;
+
+        let level = match level.as_str() {
+            "site" => HierarchyLevel::Site,
+            "room" => HierarchyLevel::Room,
+            "rack" => HierarchyLevel::Rack,
+            other => return Err(PersistenceError::get::<HierarchyNode>(node_id, format!("Unknown hierarchy level '{other}'."))),
+        };
+
+        let peers = schema::hierarchy_node_peer::table
+            .filter(schema::hierarchy_node_peer::node_id.eq(node_id))
+            .select(PersistableHierarchyNodePeer::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<PersistableHierarchyNodePeer>)?
+            .into_iter()
+            .map(|peer| PeerId::from(peer.peer_id))
+            .collect::<HashSet<_>>();
+
+        Ok(HierarchyNode {
+            id: HierarchyNodeId::from(node_id),
+            name,
+            level,
+            parent: parent_id.map(HierarchyNodeId::from),
+            peers,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<HierarchyNode>(cause)
+            .context("Failed to convert from database values to HierarchyNode.")
+    )
+}
@@ -1,11 +1,25 @@
 pub mod cluster_configuration;
+pub mod cluster_dependency;
 pub mod cluster_deployment;
+pub mod cluster_deployment_status;
 pub mod cluster_device;
+pub mod cluster_device_selector;
+pub mod cluster_peer_group;
 pub mod device_descriptor;
 pub mod device_tag;
+pub mod document;
 pub mod executor_descriptor;
+pub mod hierarchy_node;
 pub mod network_interface_descriptor;
+pub mod peer_annotation;
 pub mod peer_descriptor;
+pub mod peer_group;
+pub mod peer_label;
+pub mod peer_setup_issuance;
+pub mod project;
+pub mod provisioning_token;
+pub mod resource_snapshot;
+pub mod service_account;
 
 mod types;
 
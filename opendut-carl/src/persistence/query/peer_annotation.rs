@@ -0,0 +1,52 @@
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use opendut_types::peer::label::{AnnotationValue, LabelKey, PeerAnnotations};
+use opendut_types::peer::PeerId;
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_annotation)]
+#[diesel(belongs_to(PersistablePeerDescriptor, foreign_key = peer_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerAnnotation {
+    pub peer_id: Uuid,
+    pub key: String,
+    pub value: String,
+}
+
+pub fn insert(annotations: PeerAnnotations, peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<()> {
+    for (key, value) in annotations {
+        let persistable = PersistablePeerAnnotation {
+            peer_id: peer_id.uuid,
+            key: key.into(),
+            value: value.into(),
+        };
+        diesel::insert_into(schema::peer_annotation::table)
+            .values(&persistable)
+            .on_conflict((schema::peer_annotation::peer_id, schema::peer_annotation::key))
+            .do_update()
+            .set(&persistable)
+            .execute(connection)
+            .map_err(|cause| PersistenceError::insert::<PeerAnnotations>(persistable.peer_id, cause))?;
+    }
+    Ok(())
+}
+
+pub fn list_filtered_by_peer(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<PeerAnnotations> {
+    schema::peer_annotation::table
+        .filter(schema::peer_annotation::peer_id.eq(peer_id.uuid))
+        .select(PersistablePeerAnnotation::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PeerAnnotations>)?
+        .into_iter()
+        .map(|PersistablePeerAnnotation { peer_id, key, value }| {
+            let key = LabelKey::try_from(key)
+                .map_err(|cause| PersistenceError::get::<PeerAnnotations>(peer_id, cause))?;
+            let value = AnnotationValue::try_from(value)
+                .map_err(|cause| PersistenceError::get::<PeerAnnotations>(peer_id, cause))?;
+            Ok((key, value))
+        })
+        .collect::<PersistenceResult<_>>()
+}
@@ -6,19 +6,29 @@ use crate::persistence::error::{PersistenceError, PersistenceResult};
 use crate::persistence::query;
 use crate::persistence::query::Filter;
 use opendut_types::peer::executor::ExecutorDescriptors;
+use opendut_types::peer::hardware::HardwareCapabilities;
+use opendut_types::peer::hook::ClusterHook;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::topology::Topology;
 use opendut_types::util::net::NetworkInterfaceName;
 
 pub fn insert(peer_descriptor: PeerDescriptor, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let PeerDescriptor { id: peer_id, name, location, network, topology, executors } = peer_descriptor;
+    let PeerDescriptor { id: peer_id, name, location, network, topology, executors, labels, annotations, hardware, cluster_hooks } = peer_descriptor;
     let PeerNetworkDescriptor { interfaces, bridge_name } = network;
 
+    let hardware = hardware.map(serde_json::to_value).transpose()
+        .map_err(|cause| PersistenceError::insert::<PeerDescriptor>(peer_id.uuid, cause))?;
+
+    let cluster_hooks = serde_json::to_value(cluster_hooks)
+        .map_err(|cause| PersistenceError::insert::<PeerDescriptor>(peer_id.uuid, cause))?;
+
     insert_persistable(PersistablePeerDescriptor {
         peer_id: peer_id.uuid,
         name: name.value(),
         location: location.map(|location| location.value()),
         network_bridge_name: bridge_name.map(|name| name.name()),
+        hardware,
+        cluster_hooks,
     }, connection)?;
 
     for interface in interfaces {
@@ -35,6 +45,10 @@ pub fn insert(peer_descriptor: PeerDescriptor, connection: &mut PgConnection) ->
         query::executor_descriptor::insert_into_database(executor, peer_id, connection)?;
     }
 
+    query::peer_label::insert(labels, peer_id, connection)?;
+
+    query::peer_annotation::insert(annotations, peer_id, connection)?;
+
     Ok(())
 }
 
@@ -46,6 +60,8 @@ struct PersistablePeerDescriptor {
     pub name: String,
     pub location: Option<String>,
     pub network_bridge_name: Option<String>,
+    pub hardware: Option<serde_json::Value>,
+    pub cluster_hooks: serde_json::Value,
 }
 fn insert_persistable(persistable: PersistablePeerDescriptor, connection: &mut PgConnection) -> PersistenceResult<()> {
     diesel::insert_into(schema::peer_descriptor::table)
@@ -85,7 +101,7 @@ pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) ->
         .map_err(PersistenceError::list::<PeerDescriptor>)?;
 
     persistable_peer_descriptors.into_iter().map(|persistable| {
-        let PersistablePeerDescriptor { peer_id, name, location, network_bridge_name } = persistable;
+        let PersistablePeerDescriptor { peer_id, name, location, network_bridge_name, hardware, cluster_hooks } = persistable;
 
         let peer_id = PeerId::from(peer_id);
 
@@ -98,12 +114,22 @@ pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) ->
         let network_bridge_name = network_bridge_name.map(NetworkInterfaceName::try_from).transpose()
             .map_err(|cause| PersistenceError::get::<PeerDescriptor>(peer_id.uuid, cause))?;
 
+        let hardware = hardware.map(serde_json::from_value::<HardwareCapabilities>).transpose()
+            .map_err(|cause| PersistenceError::get::<PeerDescriptor>(peer_id.uuid, cause))?;
+
+        let cluster_hooks = serde_json::from_value::<Vec<ClusterHook>>(cluster_hooks)
+            .map_err(|cause| PersistenceError::get::<PeerDescriptor>(peer_id.uuid, cause))?;
+
         let network_interfaces = query::network_interface_descriptor::list_filtered_by_peer(peer_id, connection)?;
 
         let devices = query::device_descriptor::list_filtered_by_peer(peer_id, connection)?;
 
         let executors = query::executor_descriptor::list_filtered_by_peer(peer_id, connection)?;
 
+        let labels = query::peer_label::list_filtered_by_peer(peer_id, connection)?;
+
+        let annotations = query::peer_annotation::list_filtered_by_peer(peer_id, connection)?;
+
         Ok(PeerDescriptor {
             id: peer_id,
             name,
@@ -116,6 +142,10 @@ pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) ->
                 devices,
             },
             executors: ExecutorDescriptors { executors },
+            labels,
+            annotations,
+            hardware,
+            cluster_hooks,
         })
     })
     .collect::<PersistenceResult<Vec<_>>>()
@@ -0,0 +1,179 @@
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::peer::group::{PeerGroup, PeerGroupId, PeerGroupLabel, PeerGroupName};
+use opendut_types::peer::PeerId;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub fn insert(peer_group: PeerGroup, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PeerGroup { id, name, labels, peers } = peer_group;
+
+    insert_persistable(PersistablePeerGroup {
+        peer_group_id: id.0,
+        name: name.value(),
+    }, connection)?;
+
+    //Only delete the list elements rather than the whole peer group,
+    //as a deletion would cascade to the references held by cluster configurations.
+    remove_labels_and_members(id, connection)?;
+
+    for label in labels {
+        insert_label(PersistablePeerGroupLabel {
+            peer_group_id: id.0,
+            name: String::from(label),
+        }, connection)?;
+    }
+
+    for peer in peers {
+        insert_member(PersistablePeerGroupMember {
+            peer_group_id: id.0,
+            peer_id: peer.uuid,
+        }, connection)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_group)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerGroup {
+    pub peer_group_id: Uuid,
+    pub name: String,
+}
+fn insert_persistable(persistable: PersistablePeerGroup, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::peer_group::table)
+        .values(&persistable)
+        .on_conflict(schema::peer_group::peer_group_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PeerGroup>(persistable.peer_group_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_group_label)]
+#[diesel(belongs_to(PersistablePeerGroup, foreign_key = peer_group_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerGroupLabel {
+    pub peer_group_id: Uuid,
+    pub name: String,
+}
+fn insert_label(persistable: PersistablePeerGroupLabel, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::peer_group_label::table)
+        .values(&persistable)
+        .on_conflict((schema::peer_group_label::peer_group_id, schema::peer_group_label::name))
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PeerGroupLabel>(persistable.peer_group_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_group_member)]
+#[diesel(belongs_to(PersistablePeerGroup, foreign_key = peer_group_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerGroupMember {
+    pub peer_group_id: Uuid,
+    pub peer_id: Uuid,
+}
+fn insert_member(persistable: PersistablePeerGroupMember, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::peer_group_member::table)
+        .values(&persistable)
+        .on_conflict((schema::peer_group_member::peer_group_id, schema::peer_group_member::peer_id))
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PersistablePeerGroupMember>(persistable.peer_id, cause))?;
+    Ok(())
+}
+
+fn remove_labels_and_members(peer_group_id: PeerGroupId, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::delete(
+        schema::peer_group_label::table
+            .filter(schema::peer_group_label::peer_group_id.eq(peer_group_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerGroupLabel>(peer_group_id.0, cause))?;
+
+    diesel::delete(
+        schema::peer_group_member::table
+            .filter(schema::peer_group_member::peer_group_id.eq(peer_group_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PersistablePeerGroupMember>(peer_group_id.0, cause))?;
+
+    Ok(())
+}
+
+pub fn remove(peer_group_id: PeerGroupId, connection: &mut PgConnection) -> PersistenceResult<Option<PeerGroup>> {
+    let result = list(Filter::By(peer_group_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::peer_group::table
+            .filter(schema::peer_group::peer_group_id.eq(peer_group_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerGroup>(peer_group_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_peer_group_id: Filter<PeerGroupId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PeerGroup>> {
+    let persistable_peer_groups = {
+        let mut query = schema::peer_group::table.into_boxed();
+
+        if let Filter::By(peer_group_id) = filter_by_peer_group_id {
+            query = query.filter(schema::peer_group::peer_group_id.eq(peer_group_id.0));
+        }
+
+        query
+            .select(PersistablePeerGroup::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<PeerGroup>)?
+    };
+
+    persistable_peer_groups.into_iter().map(|persistable| {
+        let PersistablePeerGroup { peer_group_id, name } = persistable;
+
+        let name = PeerGroupName::try_from(name)
+            .map_err(|cause| PersistenceError::get::<PeerGroup>(peer_group_id, cause))?;
+
+        let labels = schema::peer_group_label::table
+            .filter(schema::peer_group_label::peer_group_id.eq(peer_group_id))
+            .select(PersistablePeerGroupLabel::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<PeerGroupLabel>)?
+            .into_iter()
+            .map(|label| PeerGroupLabel::try_from(label.name)
+                .map_err(|cause| PersistenceError::get::<PeerGroupLabel>(peer_group_id, cause))
+            )
+            .collect::<PersistenceResult<HashSet<_>>>()?;
+
+        let peers = schema::peer_group_member::table
+            .filter(schema::peer_group_member::peer_group_id.eq(peer_group_id))
+            .select(PersistablePeerGroupMember::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<PersistablePeerGroupMember>)?
+            .into_iter()
+            .map(|member| PeerId::from(member.peer_id))
+            .collect::<HashSet<_>>();
+
+        Ok(PeerGroup {
+            id: PeerGroupId::from(peer_group_id),
+            name,
+            labels,
+            peers,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<PeerGroup>(cause)
+            .context("Failed to convert from database values to PeerGroup.")
+    )
+}
@@ -0,0 +1,52 @@
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use opendut_types::peer::label::{LabelKey, LabelValue, PeerLabels};
+use opendut_types::peer::PeerId;
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_label)]
+#[diesel(belongs_to(PersistablePeerDescriptor, foreign_key = peer_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerLabel {
+    pub peer_id: Uuid,
+    pub key: String,
+    pub value: String,
+}
+
+pub fn insert(labels: PeerLabels, peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<()> {
+    for (key, value) in labels {
+        let persistable = PersistablePeerLabel {
+            peer_id: peer_id.uuid,
+            key: key.into(),
+            value: value.into(),
+        };
+        diesel::insert_into(schema::peer_label::table)
+            .values(&persistable)
+            .on_conflict((schema::peer_label::peer_id, schema::peer_label::key))
+            .do_update()
+            .set(&persistable)
+            .execute(connection)
+            .map_err(|cause| PersistenceError::insert::<PeerLabels>(persistable.peer_id, cause))?;
+    }
+    Ok(())
+}
+
+pub fn list_filtered_by_peer(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<PeerLabels> {
+    schema::peer_label::table
+        .filter(schema::peer_label::peer_id.eq(peer_id.uuid))
+        .select(PersistablePeerLabel::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PeerLabels>)?
+        .into_iter()
+        .map(|PersistablePeerLabel { peer_id, key, value }| {
+            let key = LabelKey::try_from(key)
+                .map_err(|cause| PersistenceError::get::<PeerLabels>(peer_id, cause))?;
+            let value = LabelValue::try_from(value)
+                .map_err(|cause| PersistenceError::get::<PeerLabels>(peer_id, cause))?;
+            Ok((key, value))
+        })
+        .collect::<PersistenceResult<_>>()
+}
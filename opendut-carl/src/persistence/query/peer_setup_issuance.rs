@@ -0,0 +1,97 @@
+use std::time::{Duration, SystemTime};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerId;
+use uuid::Uuid;
+
+pub fn insert(issuance: PeerSetupIssuance, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PeerSetupIssuance { peer_id, issued_by, issued_at, expires_at, used_at, revoked_at } = issuance;
+
+    let to_unix_millis = |time: SystemTime| time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .map_err(|cause| PersistenceError::insert::<PeerSetupIssuance>(peer_id.uuid, cause));
+
+    insert_persistable(PersistablePeerSetupIssuance {
+        peer_id: peer_id.uuid,
+        issued_by,
+        issued_at_unix_millis: to_unix_millis(issued_at)?,
+        expires_at_unix_millis: expires_at.map(to_unix_millis).transpose()?,
+        used_at_unix_millis: used_at.map(to_unix_millis).transpose()?,
+        revoked_at_unix_millis: revoked_at.map(to_unix_millis).transpose()?,
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_setup_issuance)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+struct PersistablePeerSetupIssuance {
+    pub peer_id: Uuid,
+    pub issued_by: String,
+    pub issued_at_unix_millis: i64,
+    pub expires_at_unix_millis: Option<i64>,
+    pub used_at_unix_millis: Option<i64>,
+    pub revoked_at_unix_millis: Option<i64>,
+}
+fn insert_persistable(persistable: PersistablePeerSetupIssuance, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::peer_setup_issuance::table)
+        .values(&persistable)
+        .on_conflict(schema::peer_setup_issuance::peer_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PeerSetupIssuance>(persistable.peer_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<Option<PeerSetupIssuance>> {
+    let result = list(Filter::By(peer_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::peer_setup_issuance::table
+            .filter(schema::peer_setup_issuance::peer_id.eq(peer_id.uuid))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerSetupIssuance>(peer_id.uuid, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PeerSetupIssuance>> {
+    let persistable_issuances = {
+        let mut query = schema::peer_setup_issuance::table.into_boxed();
+
+        if let Filter::By(peer_id) = filter_by_peer_id {
+            query = query.filter(schema::peer_setup_issuance::peer_id.eq(peer_id.uuid));
+        }
+
+        query
+            .select(PersistablePeerSetupIssuance::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<PeerSetupIssuance>)?
+    };
+
+    let from_unix_millis = |millis: i64| SystemTime::UNIX_EPOCH + Duration::from_millis(u64::try_from(millis).unwrap_or_default());
+
+    let issuances = persistable_issuances.into_iter()
+        .map(|persistable| {
+            let PersistablePeerSetupIssuance { peer_id, issued_by, issued_at_unix_millis, expires_at_unix_millis, used_at_unix_millis, revoked_at_unix_millis } = persistable;
+
+            PeerSetupIssuance {
+                peer_id: PeerId::from(peer_id),
+                issued_by,
+                issued_at: from_unix_millis(issued_at_unix_millis),
+                expires_at: expires_at_unix_millis.map(from_unix_millis),
+                used_at: used_at_unix_millis.map(from_unix_millis),
+                revoked_at: revoked_at_unix_millis.map(from_unix_millis),
+            }
+        })
+        .collect();
+
+    Ok(issuances)
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::project::{Project, ProjectId, ProjectMember, ProjectName};
+use uuid::Uuid;
+
+pub fn insert(project: Project, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let Project { id, name, members, peers, clusters } = project;
+
+    let members = serde_json::to_value(members)
+        .map_err(|cause| PersistenceError::insert::<Project>(id.0, cause))?;
+
+    let peers = serde_json::to_value(peers)
+        .map_err(|cause| PersistenceError::insert::<Project>(id.0, cause))?;
+
+    let clusters = serde_json::to_value(clusters)
+        .map_err(|cause| PersistenceError::insert::<Project>(id.0, cause))?;
+
+    insert_persistable(PersistableProject {
+        project_id: id.0,
+        name: name.into(),
+        members,
+        peers,
+        clusters,
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::project)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableProject {
+    pub project_id: Uuid,
+    pub name: String,
+    pub members: serde_json::Value,
+    pub peers: serde_json::Value,
+    pub clusters: serde_json::Value,
+}
+fn insert_persistable(persistable: PersistableProject, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::project::table)
+        .values(&persistable)
+        .on_conflict(schema::project::project_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<Project>(persistable.project_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(project_id: ProjectId, connection: &mut PgConnection) -> PersistenceResult<Option<Project>> {
+    let result = list(Filter::By(project_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::project::table
+            .filter(schema::project::project_id.eq(project_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<Project>(project_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_project_id: Filter<ProjectId>, connection: &mut PgConnection) -> PersistenceResult<Vec<Project>> {
+    let persistable_projects = {
+        let mut query = schema::project::table.into_boxed();
+
+        if let Filter::By(project_id) = filter_by_project_id {
+            query = query.filter(schema::project::project_id.eq(project_id.0));
+        }
+
+        query
+            .select(PersistableProject::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<Project>)?
+    };
+
+    persistable_projects.into_iter().map(|persistable| {
+        let PersistableProject { project_id, name, members, peers, clusters } = persistable;
+
+        let name = ProjectName::try_from(name)
+            .map_err(|cause| PersistenceError::get::<Project>(project_id, cause))?;
+
+        let members = serde_json::from_value::<BTreeSet<ProjectMember>>(members)
+            .map_err(|cause| PersistenceError::get::<Project>(project_id, cause))?;
+
+        let peers = serde_json::from_value::<HashSet<PeerId>>(peers)
+            .map_err(|cause| PersistenceError::get::<Project>(project_id, cause))?;
+
+        let clusters = serde_json::from_value::<HashSet<ClusterId>>(clusters)
+            .map_err(|cause| PersistenceError::get::<Project>(project_id, cause))?;
+
+        Ok(Project {
+            id: ProjectId::from(project_id),
+            name,
+            members,
+            peers,
+            clusters,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<Project>(cause)
+            .context("Failed to convert from database values to Project.")
+    )
+}
@@ -0,0 +1,107 @@
+use std::time::{Duration, SystemTime};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::peer::provisioning::{PeerTemplate, ProvisioningToken, ProvisioningTokenId};
+use opendut_types::peer::PeerId;
+use uuid::Uuid;
+
+pub fn insert(token: ProvisioningToken, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ProvisioningToken { id, pool, template, secret_hash, created_by, created_at, claimed_by } = token;
+
+    let created_at_unix_millis = created_at.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .map_err(|cause| PersistenceError::insert::<ProvisioningToken>(id.0, cause))?;
+
+    let template = serde_json::to_value(template)
+        .map_err(|cause| PersistenceError::insert::<ProvisioningToken>(id.0, cause))?;
+
+    insert_persistable(PersistableProvisioningToken {
+        token_id: id.0,
+        pool,
+        template,
+        secret_hash,
+        created_by,
+        created_at_unix_millis,
+        claimed_by: claimed_by.map(|peer_id| peer_id.uuid),
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::provisioning_token)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+struct PersistableProvisioningToken {
+    pub token_id: Uuid,
+    pub pool: String,
+    pub template: serde_json::Value,
+    pub secret_hash: String,
+    pub created_by: String,
+    pub created_at_unix_millis: i64,
+    pub claimed_by: Option<Uuid>,
+}
+fn insert_persistable(persistable: PersistableProvisioningToken, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::provisioning_token::table)
+        .values(&persistable)
+        .on_conflict(schema::provisioning_token::token_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ProvisioningToken>(persistable.token_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(token_id: ProvisioningTokenId, connection: &mut PgConnection) -> PersistenceResult<Option<ProvisioningToken>> {
+    let result = list(Filter::By(token_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::provisioning_token::table
+            .filter(schema::provisioning_token::token_id.eq(token_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ProvisioningToken>(token_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_token_id: Filter<ProvisioningTokenId>, connection: &mut PgConnection) -> PersistenceResult<Vec<ProvisioningToken>> {
+    let persistable_tokens = {
+        let mut query = schema::provisioning_token::table.into_boxed();
+
+        if let Filter::By(token_id) = filter_by_token_id {
+            query = query.filter(schema::provisioning_token::token_id.eq(token_id.0));
+        }
+
+        query
+            .select(PersistableProvisioningToken::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<ProvisioningToken>)?
+    };
+
+    persistable_tokens.into_iter().map(|persistable| {
+        let PersistableProvisioningToken { token_id, pool, template, secret_hash, created_by, created_at_unix_millis, claimed_by } = persistable;
+
+        let template = serde_json::from_value::<PeerTemplate>(template)
+            .map_err(|cause| PersistenceError::get::<ProvisioningToken>(token_id, cause))?;
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_millis(u64::try_from(created_at_unix_millis).unwrap_or_default());
+
+        Ok(ProvisioningToken {
+            id: ProvisioningTokenId::from(token_id),
+            pool,
+            template,
+            secret_hash,
+            created_by,
+            created_at,
+            claimed_by: claimed_by.map(PeerId::from),
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<ProvisioningToken>(cause)
+            .context("Failed to convert from database values to ProvisioningToken.")
+    )
+}
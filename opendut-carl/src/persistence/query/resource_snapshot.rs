@@ -0,0 +1,99 @@
+use std::time::{Duration, SystemTime};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotContent, SnapshotId, SnapshotName};
+use uuid::Uuid;
+
+pub fn insert(snapshot: ResourceSnapshot, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ResourceSnapshot { id, name, created_at, content } = snapshot;
+
+    let created_at_unix_millis = created_at.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .map_err(|cause| PersistenceError::insert::<ResourceSnapshot>(id.0, cause))?;
+
+    let content = serde_json::to_value(content)
+        .map_err(|cause| PersistenceError::insert::<ResourceSnapshot>(id.0, cause))?;
+
+    insert_persistable(PersistableResourceSnapshot {
+        snapshot_id: id.0,
+        name: name.value(),
+        created_at_unix_millis,
+        content,
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::resource_snapshot)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableResourceSnapshot {
+    pub snapshot_id: Uuid,
+    pub name: String,
+    pub created_at_unix_millis: i64,
+    pub content: serde_json::Value,
+}
+fn insert_persistable(persistable: PersistableResourceSnapshot, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::resource_snapshot::table)
+        .values(&persistable)
+        .on_conflict(schema::resource_snapshot::snapshot_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ResourceSnapshot>(persistable.snapshot_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(snapshot_id: SnapshotId, connection: &mut PgConnection) -> PersistenceResult<Option<ResourceSnapshot>> {
+    let result = list(Filter::By(snapshot_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::resource_snapshot::table
+            .filter(schema::resource_snapshot::snapshot_id.eq(snapshot_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ResourceSnapshot>(snapshot_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_snapshot_id: Filter<SnapshotId>, connection: &mut PgConnection) -> PersistenceResult<Vec<ResourceSnapshot>> {
+    let persistable_snapshots = {
+        let mut query = schema::resource_snapshot::table.into_boxed();
+
+        if let Filter::By(snapshot_id) = filter_by_snapshot_id {
+            query = query.filter(schema::resource_snapshot::snapshot_id.eq(snapshot_id.0));
+        }
+
+        query
+            .select(PersistableResourceSnapshot::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<ResourceSnapshot>)?
+    };
+
+    persistable_snapshots.into_iter().map(|persistable| {
+        let PersistableResourceSnapshot { snapshot_id, name, created_at_unix_millis, content } = persistable;
+
+        let name = SnapshotName::try_from(name)
+            .map_err(|cause| PersistenceError::get::<ResourceSnapshot>(snapshot_id, cause))?;
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_millis(u64::try_from(created_at_unix_millis).unwrap_or_default());
+
+        let content = serde_json::from_value::<SnapshotContent>(content)
+            .map_err(|cause| PersistenceError::get::<ResourceSnapshot>(snapshot_id, cause))?;
+
+        Ok(ResourceSnapshot {
+            id: SnapshotId::from(snapshot_id),
+            name,
+            created_at,
+            content,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<ResourceSnapshot>(cause)
+            .context("Failed to convert from database values to ResourceSnapshot.")
+    )
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName, ServiceAccountScope, ServiceAccountToken};
+use uuid::Uuid;
+
+pub fn insert(service_account: ServiceAccount, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ServiceAccount { id, name, scopes, created_by, created_at, tokens } = service_account;
+
+    let created_at_unix_millis = created_at.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .map_err(|cause| PersistenceError::insert::<ServiceAccount>(id.0, cause))?;
+
+    let scopes = serde_json::to_value(scopes)
+        .map_err(|cause| PersistenceError::insert::<ServiceAccount>(id.0, cause))?;
+
+    let tokens = serde_json::to_value(tokens)
+        .map_err(|cause| PersistenceError::insert::<ServiceAccount>(id.0, cause))?;
+
+    insert_persistable(PersistableServiceAccount {
+        service_account_id: id.0,
+        name: name.into(),
+        scopes,
+        created_by,
+        created_at_unix_millis,
+        tokens,
+    }, connection)
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::service_account)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableServiceAccount {
+    pub service_account_id: Uuid,
+    pub name: String,
+    pub scopes: serde_json::Value,
+    pub created_by: String,
+    pub created_at_unix_millis: i64,
+    pub tokens: serde_json::Value,
+}
+fn insert_persistable(persistable: PersistableServiceAccount, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::insert_into(schema::service_account::table)
+        .values(&persistable)
+        .on_conflict(schema::service_account::service_account_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ServiceAccount>(persistable.service_account_id, cause))?;
+    Ok(())
+}
+
+pub fn remove(service_account_id: ServiceAccountId, connection: &mut PgConnection) -> PersistenceResult<Option<ServiceAccount>> {
+    let result = list(Filter::By(service_account_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::service_account::table
+            .filter(schema::service_account::service_account_id.eq(service_account_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ServiceAccount>(service_account_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_service_account_id: Filter<ServiceAccountId>, connection: &mut PgConnection) -> PersistenceResult<Vec<ServiceAccount>> {
+    let persistable_service_accounts = {
+        let mut query = schema::service_account::table.into_boxed();
+
+        if let Filter::By(service_account_id) = filter_by_service_account_id {
+            query = query.filter(schema::service_account::service_account_id.eq(service_account_id.0));
+        }
+
+        query
+            .select(PersistableServiceAccount::as_select())
+            .get_results(connection)
+            .map_err(PersistenceError::list::<ServiceAccount>)?
+    };
+
+    persistable_service_accounts.into_iter().map(|persistable| {
+        let PersistableServiceAccount { service_account_id, name, scopes, created_by, created_at_unix_millis, tokens } = persistable;
+
+        let name = ServiceAccountName::try_from(name)
+            .map_err(|cause| PersistenceError::get::<ServiceAccount>(service_account_id, cause))?;
+
+        let scopes = serde_json::from_value::<BTreeSet<ServiceAccountScope>>(scopes)
+            .map_err(|cause| PersistenceError::get::<ServiceAccount>(service_account_id, cause))?;
+
+        let tokens = serde_json::from_value::<Vec<ServiceAccountToken>>(tokens)
+            .map_err(|cause| PersistenceError::get::<ServiceAccount>(service_account_id, cause))?;
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_millis(u64::try_from(created_at_unix_millis).unwrap_or_default());
+
+        Ok(ServiceAccount {
+            id: ServiceAccountId::from(service_account_id),
+            name,
+            scopes,
+            created_by,
+            created_at,
+            tokens,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<ServiceAccount>(cause)
+            .context("Failed to convert from database values to ServiceAccount.")
+    )
+}
@@ -1,34 +1,52 @@
 use opendut_types::cluster::{ClusterDeployment, ClusterId};
 
+use super::Persistable;
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::query::Filter;
-use crate::persistence::{query, Storage};
-
-use super::Persistable;
+use crate::persistence::{query, DbConnection, Storage};
 
 impl Persistable for ClusterDeployment {
-    fn insert(self, _id: ClusterId, storage: &mut Storage) -> PersistenceResult<()> {
-        let mut connection = storage.db.connection();
-
-        //Delete before inserting to ensure that when an update removes
-        //list elements we don't leave those elements behind in the database.
-        //TODO more efficient solution
-        query::cluster_deployment::remove(self.id, &mut connection)?;
-
-        query::cluster_deployment::insert(self, &mut connection)
+    fn insert(self, cluster_id: ClusterId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => {
+                //Delete before inserting to ensure that when an update removes
+                //list elements we don't leave those elements behind in the database.
+                //TODO more efficient solution
+                query::cluster_deployment::remove(self.id, connection)?;
+
+                query::cluster_deployment::insert(self, connection)
+            }
+            DbConnection::Sqlite(connection) => query::document::insert(cluster_id, &self, connection),
+        }
     }
 
     fn remove(cluster_id: ClusterId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
-        query::cluster_deployment::remove(cluster_id, &mut storage.db.connection())
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment::remove(cluster_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(cluster_id, connection),
+        }
     }
 
     fn get(cluster_id: ClusterId, storage: &Storage) -> PersistenceResult<Option<Self>> {
-        let result = query::cluster_deployment::list(Filter::By(cluster_id), &mut storage.db.connection())?
-            .first().cloned();
-        Ok(result)
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment::list(Filter::By(cluster_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(cluster_id), connection)?,
+        };
+        Ok(result.first().cloned())
     }
 
     fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
-        query::cluster_deployment::list(Filter::Not, &mut storage.db.connection())
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<ClusterId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
     }
 }
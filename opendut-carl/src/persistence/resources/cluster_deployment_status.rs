@@ -0,0 +1,45 @@
+use opendut_types::cluster::{ClusterDeploymentStatus, ClusterId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for ClusterDeploymentStatus {
+    fn insert(self, cluster_id: ClusterId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment_status::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(cluster_id, &self, connection),
+        }
+    }
+
+    fn remove(cluster_id: ClusterId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment_status::remove(cluster_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(cluster_id, connection),
+        }
+    }
+
+    fn get(cluster_id: ClusterId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment_status::list(Filter::By(cluster_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(cluster_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::cluster_deployment_status::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<ClusterId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
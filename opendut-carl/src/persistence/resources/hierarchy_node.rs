@@ -0,0 +1,45 @@
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for HierarchyNode {
+    fn insert(self, node_id: HierarchyNodeId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::hierarchy_node::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(node_id, &self, connection),
+        }
+    }
+
+    fn remove(node_id: HierarchyNodeId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::hierarchy_node::remove(node_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(node_id, connection),
+        }
+    }
+
+    fn get(node_id: HierarchyNodeId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::hierarchy_node::list(Filter::By(node_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(node_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::hierarchy_node::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<HierarchyNodeId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
@@ -1,16 +1,25 @@
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::Storage;
 use crate::resources::resource::Resource;
+use crate::resources::subscription::Subscribable;
 use std::fmt::Debug;
 
 pub mod cluster_configuration;
 pub mod cluster_deployment;
+pub mod cluster_deployment_status;
+pub mod hierarchy_node;
 pub mod old_peer_configuration;
 pub mod peer_configuration;
 pub mod peer_descriptor;
+pub mod peer_group;
+pub mod peer_setup_issuance;
 pub mod peer_state;
+pub mod project;
+pub mod provisioning_token;
+pub mod resource_snapshot;
+pub mod service_account;
 
-pub trait Persistable: Send + Sync + Sized + Debug + Resource {
+pub trait Persistable: Send + Sync + Sized + Debug + Resource + Subscribable {
     fn insert(self, id: Self::Id, storage: &mut Storage) -> PersistenceResult<()>;
 
     fn remove(id: Self::Id, storage: &mut Storage) -> PersistenceResult<Option<Self>>;
@@ -18,4 +27,6 @@ pub trait Persistable: Send + Sync + Sized + Debug + Resource {
     fn get(id: Self::Id, storage: &Storage) -> PersistenceResult<Option<Self>>;
 
     fn list(storage: &Storage) -> PersistenceResult<Vec<Self>>;
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<Self::Id>>;
 }
@@ -22,4 +22,8 @@ impl Persistable for PeerConfiguration {
     fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
         storage.memory.list()
     }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<PeerId>> {
+        storage.memory.list_ids::<Self>()
+    }
 }
@@ -3,31 +3,50 @@ use opendut_types::peer::{PeerDescriptor, PeerId};
 use super::Persistable;
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::query::Filter;
-use crate::persistence::{query, Storage};
+use crate::persistence::{query, DbConnection, Storage};
 
 impl Persistable for PeerDescriptor {
-    fn insert(self, _peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<()> {
-        let mut connection = storage.db.connection();
+    fn insert(self, peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => {
+                //Delete before inserting to ensure that when an update removes
+                //list elements we don't leave those elements behind in the database.
+                //TODO more efficient solution
+                query::peer_descriptor::remove(self.id, connection)?;
 
-        //Delete before inserting to ensure that when an update removes
-        //list elements we don't leave those elements behind in the database.
-        //TODO more efficient solution
-        query::peer_descriptor::remove(self.id, &mut connection)?;
-
-        query::peer_descriptor::insert(self, &mut connection)
+                query::peer_descriptor::insert(self, connection)
+            }
+            DbConnection::Sqlite(connection) => query::document::insert(peer_id, &self, connection),
+        }
     }
 
     fn remove(peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
-        query::peer_descriptor::remove(peer_id, &mut storage.db.connection())
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_descriptor::remove(peer_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(peer_id, connection),
+        }
     }
 
     fn get(peer_id: PeerId, storage: &Storage) -> PersistenceResult<Option<Self>> {
-        let result = query::peer_descriptor::list(Filter::By(peer_id), &mut storage.db.connection())?
-            .first().cloned();
-        Ok(result)
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_descriptor::list(Filter::By(peer_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(peer_id), connection)?,
+        };
+        Ok(result.first().cloned())
     }
 
     fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
-        query::peer_descriptor::list(Filter::Not, &mut storage.db.connection())
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_descriptor::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<PeerId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
     }
 }
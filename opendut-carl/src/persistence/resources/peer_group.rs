@@ -0,0 +1,45 @@
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for PeerGroup {
+    fn insert(self, peer_group_id: PeerGroupId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_group::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(peer_group_id, &self, connection),
+        }
+    }
+
+    fn remove(peer_group_id: PeerGroupId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_group::remove(peer_group_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(peer_group_id, connection),
+        }
+    }
+
+    fn get(peer_group_id: PeerGroupId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_group::list(Filter::By(peer_group_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(peer_group_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_group::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<PeerGroupId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
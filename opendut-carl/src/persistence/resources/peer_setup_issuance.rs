@@ -0,0 +1,46 @@
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerId;
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for PeerSetupIssuance {
+    fn insert(self, peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_setup_issuance::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(peer_id, &self, connection),
+        }
+    }
+
+    fn remove(peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_setup_issuance::remove(peer_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(peer_id, connection),
+        }
+    }
+
+    fn get(peer_id: PeerId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_setup_issuance::list(Filter::By(peer_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(peer_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::peer_setup_issuance::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<PeerId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.peer_id)
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,45 @@
+use opendut_types::project::{Project, ProjectId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for Project {
+    fn insert(self, project_id: ProjectId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::project::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(project_id, &self, connection),
+        }
+    }
+
+    fn remove(project_id: ProjectId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::project::remove(project_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(project_id, connection),
+        }
+    }
+
+    fn get(project_id: ProjectId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::project::list(Filter::By(project_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(project_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::project::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<ProjectId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
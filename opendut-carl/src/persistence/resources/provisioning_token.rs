@@ -0,0 +1,45 @@
+use opendut_types::peer::provisioning::{ProvisioningToken, ProvisioningTokenId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for ProvisioningToken {
+    fn insert(self, token_id: ProvisioningTokenId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::provisioning_token::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(token_id, &self, connection),
+        }
+    }
+
+    fn remove(token_id: ProvisioningTokenId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::provisioning_token::remove(token_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(token_id, connection),
+        }
+    }
+
+    fn get(token_id: ProvisioningTokenId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::provisioning_token::list(Filter::By(token_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(token_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::provisioning_token::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<ProvisioningTokenId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,45 @@
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for ResourceSnapshot {
+    fn insert(self, snapshot_id: SnapshotId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::resource_snapshot::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(snapshot_id, &self, connection),
+        }
+    }
+
+    fn remove(snapshot_id: SnapshotId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::resource_snapshot::remove(snapshot_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(snapshot_id, connection),
+        }
+    }
+
+    fn get(snapshot_id: SnapshotId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::resource_snapshot::list(Filter::By(snapshot_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(snapshot_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::resource_snapshot::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<SnapshotId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,45 @@
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, DbConnection, Storage};
+
+impl Persistable for ServiceAccount {
+    fn insert(self, service_account_id: ServiceAccountId, storage: &mut Storage) -> PersistenceResult<()> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::service_account::insert(self, connection),
+            DbConnection::Sqlite(connection) => query::document::insert(service_account_id, &self, connection),
+        }
+    }
+
+    fn remove(service_account_id: ServiceAccountId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::service_account::remove(service_account_id, connection),
+            DbConnection::Sqlite(connection) => query::document::remove(service_account_id, connection),
+        }
+    }
+
+    fn get(service_account_id: ServiceAccountId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::service_account::list(Filter::By(service_account_id), connection)?,
+            DbConnection::Sqlite(connection) => query::document::list(Filter::By(service_account_id), connection)?,
+        };
+        Ok(result.first().cloned())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        match &mut **storage.db.connection() {
+            DbConnection::Postgres(connection) => query::service_account::list(Filter::Not, connection),
+            DbConnection::Sqlite(connection) => query::document::list(Filter::Not, connection),
+        }
+    }
+
+    fn list_ids(storage: &Storage) -> PersistenceResult<Vec<ServiceAccountId>> {
+        let result = Self::list(storage)?
+            .into_iter()
+            .map(|resource| resource.id)
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,529 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::Arc;
+
+use anyhow::Context;
+use regex::Regex;
+use tracing::debug;
+use url::Url;
+
+use opendut_carl_api::carl::policy::PolicyViolation;
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use opendut_types::peer::hierarchy::{site_of_peer, HierarchyNode};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
+
+pub type PolicyEngineRef = Arc<PolicyEngine>;
+
+/// Admission control for resources, which are created or updated via the API.
+/// Evaluates the built-in rules and, if configured, an external policy served by Open Policy Agent.
+#[derive(Default)]
+pub struct PolicyEngine {
+    options: PolicyOptions,
+    client: reqwest::Client,
+}
+
+#[derive(Clone, Default)]
+pub struct PolicyOptions {
+    pub peer_name_pattern: Option<Regex>,
+    pub cluster_name_pattern: Option<Regex>,
+    pub images: ImagePolicy,
+    pub max_devices_per_cluster: Option<usize>,
+    pub cluster_same_site: bool,
+    pub opa_url: Option<Url>,
+}
+impl PolicyOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let name_pattern = |field: &str| -> anyhow::Result<Option<Regex>> {
+            let pattern = config.get_string(field)?;
+            if pattern.is_empty() {
+                Ok(None)
+            } else {
+                let regex = Regex::new(&format!("^(?:{pattern})$"))
+                    .context(format!("Invalid regular expression for '{field}'"))?;
+                Ok(Some(regex))
+            }
+        };
+        let peer_name_pattern = name_pattern("policy.naming.peer.pattern")?;
+        let cluster_name_pattern = name_pattern("policy.naming.cluster.pattern")?;
+
+        let images = ImagePolicy::load(config)?;
+
+        let max_devices_per_cluster = config.get::<usize>("policy.cluster.devices.max")?;
+        let max_devices_per_cluster = (max_devices_per_cluster > 0).then_some(max_devices_per_cluster);
+
+        let cluster_same_site = config.get_bool("policy.cluster.same.site")?;
+
+        let opa_url = config.get_string("policy.opa.url")?;
+        let opa_url = if opa_url.is_empty() {
+            None
+        } else {
+            Some(Url::parse(&opa_url).context("Invalid URL for 'policy.opa.url'")?)
+        };
+
+        Ok(PolicyOptions { peer_name_pattern, cluster_name_pattern, images, max_devices_per_cluster, cluster_same_site, opa_url })
+    }
+}
+
+/// Container images, which executors may use, matched by patterns for their registry, namespace or digest.
+/// Enforced when peers are stored and again when clusters are deployed, as peers may have been assigned to a project in the meantime.
+#[derive(Clone, Default)]
+pub struct ImagePolicy {
+    /// Images allowed by default. Empty allows all images, which are not forbidden.
+    pub allowed: Vec<Regex>,
+    /// Images forbidden for all projects.
+    pub forbidden: Vec<Regex>,
+    /// Policies of the projects, by project name, which apply to the peers assigned to them.
+    pub projects: HashMap<String, ProjectImagePolicy>,
+}
+
+#[derive(Clone, Default)]
+pub struct ProjectImagePolicy {
+    /// Replaces the images allowed by default, unless empty.
+    pub allowed: Vec<Regex>,
+    /// Forbidden in addition to the images forbidden for all projects.
+    pub forbidden: Vec<Regex>,
+}
+
+impl ImagePolicy {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        use opendut_util::settings::LoadError;
+
+        let patterns = |field: &'static str| -> Result<Vec<String>, LoadError> {
+            config.get::<Vec<String>>(field)
+                .map_err(|cause| LoadError::ReadField { field, source: Box::new(cause) })
+        };
+        let regex = |field: &'static str, pattern: &str| -> Result<Regex, LoadError> {
+            wildcard_regex(pattern)
+                .map_err(|cause| LoadError::ParseValue { field, value: pattern.to_owned(), source: Box::new(cause) })
+        };
+
+        let allowed = patterns("policy.images.allowed")?.iter()
+            .map(|pattern| regex("policy.images.allowed", pattern))
+            .collect::<Result<_, _>>()?;
+        let forbidden = patterns("policy.images.forbidden")?.iter()
+            .map(|pattern| regex("policy.images.forbidden", pattern))
+            .collect::<Result<_, _>>()?;
+
+        let mut projects = HashMap::<String, ProjectImagePolicy>::new();
+        for field in ["policy.images.project.allowed", "policy.images.project.forbidden"] {
+            for entry in patterns(field)? {
+                let (project, pattern) = entry.split_once('=')
+                    .map(|(project, pattern)| (project.trim(), pattern.trim()))
+                    .filter(|(project, pattern)| project.is_empty().not() && pattern.is_empty().not())
+                    .ok_or_else(|| LoadError::ParseValue { field, value: Clone::clone(&entry), source: "Expected an entry formatted as 'project=pattern'.".into() })?;
+
+                let policy = projects.entry(project.to_owned()).or_default();
+                let patterns = if field.ends_with("allowed") { &mut policy.allowed } else { &mut policy.forbidden };
+                patterns.push(regex(field, pattern)?);
+            }
+        }
+
+        Ok(Self { allowed, forbidden, projects })
+    }
+
+    /// Checks the container image of the executor against the default policy and the policies of the projects, which the peer of the executor is assigned to.
+    pub fn evaluate(&self, executor: &ExecutorDescriptor, projects: &[Project]) -> Vec<PolicyViolation> {
+        let ExecutorKind::Container { image, .. } = &executor.kind else {
+            return vec![];
+        };
+        let image = image.value();
+        let normalized_image = normalize_image(image);
+        let matches = |patterns: &[Regex]| patterns.iter().any(|pattern| pattern.is_match(image) || pattern.is_match(&normalized_image));
+
+        let mut violations = vec![];
+
+        if matches(&self.forbidden) {
+            violations.push(PolicyViolation {
+                rule: String::from(RULE_FORBIDDEN_IMAGE),
+                message: format!("Container image '{image}' of executor <{}> is forbidden.", executor.id),
+            });
+        }
+
+        if projects.is_empty() {
+            if self.allowed.is_empty().not() && matches(&self.allowed).not() {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_IMAGE_NOT_ALLOWED),
+                    message: format!("Container image '{image}' of executor <{}> is not allowed.", executor.id),
+                });
+            }
+            return violations;
+        }
+
+        for project in projects {
+            let project_name = project.name.value();
+            let project_policy = self.projects.get(project_name);
+
+            if project_policy.is_some_and(|policy| matches(&policy.forbidden)) {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_FORBIDDEN_IMAGE),
+                    message: format!("Container image '{image}' of executor <{}> is forbidden in project '{project_name}'.", executor.id),
+                });
+            }
+
+            let allowed = project_policy
+                .map(|policy| &policy.allowed)
+                .filter(|allowed| allowed.is_empty().not())
+                .unwrap_or(&self.allowed);
+            if allowed.is_empty().not() && matches(allowed).not() {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_IMAGE_NOT_ALLOWED),
+                    message: format!("Container image '{image}' of executor <{}> is not allowed in project '{project_name}'.", executor.id),
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+/// Expands references to Docker Hub, which omit the registry or the namespace, e.g. `alpine:3.20` to `docker.io/library/alpine:3.20`,
+/// so that patterns for a registry cannot be circumvented by omitting it.
+fn normalize_image(image: &str) -> String {
+    match image.split_once('/') {
+        None => format!("docker.io/library/{image}"),
+        Some((registry, _)) if registry.contains(['.', ':']) || registry == "localhost" => image.to_owned(),
+        Some(_) => format!("docker.io/{image}"),
+    }
+}
+
+/// Converts a pattern like `docker.io/*:latest` into a regular expression, where `*` matches any characters.
+fn wildcard_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    let pattern = regex::escape(pattern).replace(r"\*", ".*");
+    Regex::new(&format!("^{pattern}$"))
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to evaluate policy at '{url}':\n  {cause}")]
+pub struct PolicyError {
+    url: Url,
+    cause: String,
+}
+
+const RULE_NAMING_CONVENTION: &str = "naming-convention";
+const RULE_FORBIDDEN_IMAGE: &str = "forbidden-image";
+const RULE_IMAGE_NOT_ALLOWED: &str = "image-not-allowed";
+const RULE_MAX_DEVICES_PER_CLUSTER: &str = "max-devices-per-cluster";
+const RULE_SAME_SITE: &str = "same-site";
+
+impl PolicyEngine {
+    pub fn new(options: PolicyOptions) -> Self {
+        Self { options, client: reqwest::Client::new() }
+    }
+
+    /// Expects the projects, which the peer is assigned to.
+    pub async fn evaluate_peer_descriptor(&self, peer_descriptor: &PeerDescriptor, projects: &[Project]) -> Result<Vec<PolicyViolation>, PolicyError> {
+        let mut violations = vec![];
+
+        let peer_name = Clone::clone(&peer_descriptor.name).value();
+        if let Some(pattern) = &self.options.peer_name_pattern {
+            if pattern.is_match(&peer_name).not() {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_NAMING_CONVENTION),
+                    message: format!("Peer name '{peer_name}' does not match the pattern '{pattern}'."),
+                });
+            }
+        }
+
+        for executor in &peer_descriptor.executors.executors {
+            violations.extend(self.options.images.evaluate(executor, projects));
+        }
+
+        violations.extend(self.evaluate_external("PeerDescriptor", peer_descriptor).await?);
+
+        Ok(violations)
+    }
+
+    /// Expects the devices of the cluster's peer groups to be resolved into its devices.
+    pub async fn evaluate_cluster_configuration(&self, cluster_configuration: &ClusterConfiguration) -> Result<Vec<PolicyViolation>, PolicyError> {
+        let mut violations = vec![];
+
+        let cluster_name = Clone::clone(&cluster_configuration.name).value();
+        if let Some(pattern) = &self.options.cluster_name_pattern {
+            if pattern.is_match(&cluster_name).not() {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_NAMING_CONVENTION),
+                    message: format!("Cluster name '{cluster_name}' does not match the pattern '{pattern}'."),
+                });
+            }
+        }
+
+        if let Some(max_devices) = self.options.max_devices_per_cluster {
+            let devices = cluster_configuration.devices.len();
+            if devices > max_devices {
+                violations.push(PolicyViolation {
+                    rule: String::from(RULE_MAX_DEVICES_PER_CLUSTER),
+                    message: format!("Cluster contains {devices} devices, but at most {max_devices} are allowed."),
+                });
+            }
+        }
+
+        violations.extend(self.evaluate_external("ClusterConfiguration", cluster_configuration).await?);
+
+        Ok(violations)
+    }
+
+    /// Checks the placement of the cluster's peers in the hierarchy of sites, rooms and racks.
+    /// Expects the devices of the cluster's peer groups to be resolved into its devices. Peers, which are not placed in the hierarchy, are not restricted.
+    pub fn evaluate_cluster_placement(&self, cluster_configuration: &ClusterConfiguration, hierarchy_nodes: &[HierarchyNode], peers: &[PeerDescriptor]) -> Vec<PolicyViolation> {
+        let mut violations = vec![];
+
+        if self.options.cluster_same_site {
+            let Some(leader_site) = site_of_peer(hierarchy_nodes, cluster_configuration.leader) else {
+                return violations;
+            };
+
+            let cluster_peers = peers.iter()
+                .filter(|peer| peer.topology.devices.iter().any(|device| cluster_configuration.devices.contains(&device.id)));
+
+            for peer in cluster_peers {
+                if let Some(site) = site_of_peer(hierarchy_nodes, peer.id) {
+                    if site.id != leader_site.id {
+                        violations.push(PolicyViolation {
+                            rule: String::from(RULE_SAME_SITE),
+                            message: format!("Peer '{}' <{}> is located in site '{}', but the leader is located in site '{}'.", peer.name, peer.id, site.name, leader_site.name),
+                        });
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Queries the configured Open Policy Agent decision, which is expected to evaluate to a list of violations,
+    /// e.g. `[{"rule": "cluster-leader-location", "message": "..."}]`. An undefined decision counts as no violations.
+    async fn evaluate_external(&self, kind: &str, resource: &impl serde::Serialize) -> Result<Vec<PolicyViolation>, PolicyError> {
+        let Some(url) = &self.options.opa_url else {
+            return Ok(vec![]);
+        };
+        let error = |cause: String| PolicyError { url: Clone::clone(url), cause };
+
+        let input = serde_json::json!({
+            "input": {
+                "kind": kind,
+                "resource": resource,
+            }
+        });
+
+        let response = self.client.post(Clone::clone(url))
+            .json(&input)
+            .send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|cause| error(cause.to_string()))?;
+
+        let decision = response.json::<OpaDecision>().await
+            .map_err(|cause| error(cause.to_string()))?;
+
+        let violations = decision.result.unwrap_or_default().into_iter()
+            .map(|violation| PolicyViolation {
+                rule: violation.rule.unwrap_or_else(|| String::from("opa")),
+                message: violation.message,
+            })
+            .collect::<Vec<_>>();
+        debug!("Policy at '{url}' reported {} violation(s) for {kind}.", violations.len());
+
+        Ok(violations)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct OpaDecision {
+    result: Option<Vec<OpaViolation>>,
+}
+
+#[derive(serde::Deserialize)]
+struct OpaViolation {
+    rule: Option<String>,
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerImage, ContainerName, Engine};
+    use opendut_types::peer::executor::{ExecutorDescriptors, ExecutorId};
+    use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNodeId, HierarchyNodeName};
+    use opendut_types::peer::{PeerId, PeerName, PeerNetworkDescriptor};
+    use opendut_types::project::{ProjectId, ProjectName};
+    use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_report_violations_of_built_in_rules_for_cluster_configuration() -> anyhow::Result<()> {
+        let testee = PolicyEngine::new(PolicyOptions {
+            cluster_name_pattern: Some(Regex::new("^(?:lab-.*)$")?),
+            max_devices_per_cluster: Some(1),
+            ..Default::default()
+        });
+
+        let cluster_configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("MyCluster")?,
+            leader: PeerId::random(),
+            devices: HashSet::from([DeviceId::random(), DeviceId::random()]),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
+        };
+
+        let violations = testee.evaluate_cluster_configuration(&cluster_configuration).await?;
+
+        let rules = violations.into_iter().map(|violation| violation.rule).collect::<Vec<_>>();
+        assert_that!(rules, eq(&vec![String::from(RULE_NAMING_CONVENTION), String::from(RULE_MAX_DEVICES_PER_CLUSTER)]));
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_peers_outside_of_the_leader_site() -> anyhow::Result<()> {
+        let testee = PolicyEngine::new(PolicyOptions {
+            cluster_same_site: true,
+            ..Default::default()
+        });
+
+        let leader = generate_peer_descriptor()?;
+        let remote = generate_peer_descriptor()?;
+        let unplaced = generate_peer_descriptor()?;
+
+        let site = |name: &str, peers: Vec<PeerId>| -> anyhow::Result<HierarchyNode> {
+            Ok(HierarchyNode {
+                id: HierarchyNodeId::random(),
+                name: HierarchyNodeName::try_from(name)?,
+                level: HierarchyLevel::Site,
+                parent: None,
+                peers: HashSet::from_iter(peers),
+            })
+        };
+        let hierarchy_nodes = vec![site("site-a", vec![leader.id])?, site("site-b", vec![remote.id])?];
+
+        let peers = vec![leader.clone(), remote.clone(), unplaced.clone()];
+        let cluster_configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("MyCluster")?,
+            leader: leader.id,
+            devices: peers.iter().flat_map(|peer| peer.topology.devices.iter().map(|device| device.id)).collect(),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
+        };
+
+        let violations = testee.evaluate_cluster_placement(&cluster_configuration, &hierarchy_nodes, &peers);
+
+        assert_that!(violations.len(), eq(1));
+        assert_that!(violations[0].rule, eq(RULE_SAME_SITE));
+        assert!(violations[0].message.contains(&remote.id.to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_match_forbidden_images_with_wildcards() -> anyhow::Result<()> {
+        let pattern = wildcard_regex("docker.io/*:latest")?;
+
+        assert!(pattern.is_match("docker.io/library/alpine:latest"));
+        assert!(pattern.is_match("docker.io/library/alpine:3.20").not());
+        assert!(pattern.is_match("registry.example.com/alpine:latest").not());
+        Ok(())
+    }
+
+    #[test]
+    fn should_apply_the_image_policy_of_the_projects_of_the_peer() -> anyhow::Result<()> {
+        let testee = ImagePolicy {
+            allowed: vec![wildcard_regex("registry.example.com/*")?],
+            forbidden: vec![wildcard_regex("*:latest")?],
+            projects: HashMap::from([
+                (String::from("powertrain"), ProjectImagePolicy {
+                    allowed: vec![wildcard_regex("registry.example.com/powertrain/*")?],
+                    forbidden: vec![],
+                }),
+            ]),
+        };
+        let project = Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from("powertrain")?,
+            members: Default::default(),
+            peers: HashSet::new(),
+            clusters: HashSet::new(),
+        };
+        let executor = |image: &str| -> anyhow::Result<ExecutorDescriptor> {
+            Ok(ExecutorDescriptor {
+                id: ExecutorId::random(),
+                kind: ExecutorKind::Container {
+                    engine: Engine::Docker,
+                    name: ContainerName::Empty,
+                    image: ContainerImage::try_from(image)?,
+                    volumes: vec![],
+                    devices: vec![],
+                    envs: vec![],
+                    ports: vec![],
+                    command: ContainerCommand::Default,
+                    args: vec![],
+                    secrets: vec![],
+                    can_gateways: vec![],
+                    network_attachment: Default::default(),
+                },
+                results_url: None,
+                sandbox: Default::default(),
+            })
+        };
+        let rules = |violations: Vec<PolicyViolation>| violations.into_iter().map(|violation| violation.rule).collect::<Vec<_>>();
+
+        assert_that!(rules(testee.evaluate(&executor("registry.example.com/chassis/test:1.0")?, &[])), empty());
+        assert_that!(rules(testee.evaluate(&executor("alpine:latest")?, &[])), eq(&vec![String::from(RULE_FORBIDDEN_IMAGE), String::from(RULE_IMAGE_NOT_ALLOWED)]));
+        assert_that!(rules(testee.evaluate(&executor("registry.example.com/chassis/test:1.0")?, &[Clone::clone(&project)])), eq(&vec![String::from(RULE_IMAGE_NOT_ALLOWED)]));
+        assert_that!(rules(testee.evaluate(&executor("registry.example.com/powertrain/test:1.0")?, &[project])), empty());
+        Ok(())
+    }
+
+    #[test]
+    fn should_normalize_images_of_docker_hub() {
+        assert_that!(normalize_image("alpine:3.20"), eq("docker.io/library/alpine:3.20"));
+        assert_that!(normalize_image("grafana/grafana"), eq("docker.io/grafana/grafana"));
+        assert_that!(normalize_image("localhost:5000/test"), eq("localhost:5000/test"));
+        assert_that!(normalize_image("registry.example.com/test@sha256:abc"), eq("registry.example.com/test@sha256:abc"));
+    }
+
+    fn generate_peer_descriptor() -> anyhow::Result<PeerDescriptor> {
+        let network_interface_id = NetworkInterfaceId::random();
+
+        Ok(PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("peer")?,
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![
+                    NetworkInterfaceDescriptor {
+                        id: network_interface_id,
+                        name: NetworkInterfaceName::try_from("eth0")?,
+                        configuration: NetworkInterfaceConfiguration::Ethernet,
+                    },
+                ],
+                bridge_name: None,
+            },
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: DeviceId::random(),
+                        name: DeviceName::try_from("device")?,
+                        description: None,
+                        interface: network_interface_id,
+                        tags: vec![],
+                    }
+                ],
+            },
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
+        })
+    }
+}
@@ -1,8 +1,15 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+use opendut_types::peer::provisioning::{ProvisioningToken, ProvisioningTokenId};
+use opendut_types::peer::setup::PeerSetupIssuance;
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::{Project, ProjectId};
 use opendut_types::resources::Id;
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId};
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId};
 
 use crate::resources::resource::Resource;
 
@@ -21,11 +28,26 @@ impl IntoId<ClusterDeployment> for ClusterId {
         Id::from(self.0)
     }
 }
+impl IntoId<ClusterDeploymentStatus> for ClusterId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 impl IntoId<PeerDescriptor> for PeerId {
     fn into_id(self) -> Id {
         Id::from(self.uuid)
     }
 }
+impl IntoId<PeerGroup> for PeerGroupId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<HierarchyNode> for HierarchyNodeId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 impl IntoId<PeerState> for PeerId {
     fn into_id(self) -> Id {
         Id::from(self.uuid)
@@ -41,3 +63,28 @@ impl IntoId<PeerConfiguration> for PeerId {
         Id::from(self.uuid)
     }
 }
+impl IntoId<PeerSetupIssuance> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+impl IntoId<Project> for ProjectId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ProvisioningToken> for ProvisioningTokenId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ResourceSnapshot> for SnapshotId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ServiceAccount> for ServiceAccountId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
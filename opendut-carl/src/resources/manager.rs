@@ -2,8 +2,9 @@ pub use crate::resources::subscription::SubscriptionEvent;
 
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::resources::Persistable;
-use crate::resources::storage::{PersistenceOptions, ResourcesStorageApi};
-use crate::resources::subscription::{ResourceSubscriptionChannel, ResourceSubscriptionChannels, Subscribable, Subscription};
+use crate::resources::storage::snapshot::{SnapshotError, VolatileSnapshot};
+use crate::resources::storage::{PersistenceOptions, ReadConsistency, ResourcesStorageApi};
+use crate::resources::subscription::{ResourceSubscriptionChannel, ResourceSubscriptionChannels, Subscribable, Subscription, SubscriptionHealthReport};
 use crate::resources::transaction::RelayedSubscriptionEvents;
 use crate::resources::{storage, Resource, Resources, ResourcesTransaction};
 use std::sync::Arc;
@@ -64,6 +65,20 @@ impl ResourcesManager {
         state.resources.list()
     }
 
+    /// Like [get](Self::get), but may be served by a read replica, if the call site tolerates stale data.
+    pub async fn get_with<R>(&self, consistency: ReadConsistency, id: R::Id) -> PersistenceResult<Option<R>>
+    where R: Resource + Persistable + Clone {
+        let state = self.state.read().await;
+        state.resources.get_with(consistency, id)
+    }
+
+    /// Like [list](Self::list), but may be served by a read replica, if the call site tolerates stale data.
+    pub async fn list_with<R>(&self, consistency: ReadConsistency) -> PersistenceResult<Vec<R>>
+    where R: Resource + Persistable + Clone {
+        let state = self.state.read().await;
+        state.resources.list_with(consistency)
+    }
+
     pub async fn resources<F, T>(&self, f: F) -> PersistenceResult<T>
     where F: FnOnce(&Resources) -> PersistenceResult<T> {
         let state = self.state.read().await;
@@ -87,12 +102,51 @@ impl ResourcesManager {
         Ok(result)
     }
 
+    /// Waits for running transactions to complete, including notifying subscribers about their changes.
+    /// Transactions are committed synchronously, so all modifications are persisted afterwards.
+    pub async fn flush(&self) {
+        let _state = self.state.write().await;
+    }
+
+    /// Serializes the resources of the volatile storage, if a snapshot path is configured for it. Returns `None` otherwise.
+    pub async fn volatile_snapshot(&self) -> Option<Result<VolatileSnapshot, SnapshotError>> {
+        let state = self.state.read().await;
+        state.resources.volatile_snapshot()
+    }
+
+    /// Writes the resources of the volatile storage to its snapshot file, if a snapshot path is configured for it, e.g. when shutting down.
+    pub async fn write_volatile_snapshot(&self) -> Result<(), SnapshotError> {
+        let snapshot = {
+            let state = self.state.read().await;
+            state.resources.volatile_snapshot_path().zip(state.resources.volatile_snapshot())
+        };
+        if let Some((path, snapshot)) = snapshot {
+            snapshot?.write(&path).await?;
+        }
+        Ok(())
+    }
+
     pub async fn subscribe<R>(&self) -> Subscription<R>
     where R: Resource + Subscribable {
         let mut state = self.state.write().await;
         state.subscribers.subscribe()
     }
 
+    /// Publishes an event, which was not caused by a transaction of this instance, e.g. a change by the leader, which a follower observed in the database.
+    pub async fn publish<R>(&self, event: SubscriptionEvent<R>)
+    where R: Resource + Subscribable {
+        let mut state = self.state.write().await;
+        state.subscribers
+            .publish(event)
+            .expect("should successfully send notification about event observed in the database");
+    }
+
+    /// Reports the number of subscribers and their backlog per resource type.
+    pub async fn subscription_health(&self) -> Vec<SubscriptionHealthReport> {
+        let mut state = self.state.write().await;
+        state.subscribers.health_reports()
+    }
+
     async fn send_relayed_subscription_events(
         relayed_subscription_events: RelayedSubscriptionEvents,
         state: &mut RwLockWriteGuard<'_, State>,
@@ -100,10 +154,19 @@ impl ResourcesManager {
         let ResourceSubscriptionChannels {
             cluster_configuration,
             cluster_deployment,
+            cluster_deployment_status,
+            hierarchy_node,
             old_peer_configuration,
             peer_configuration,
             peer_descriptor,
-            peer_state
+            peer_group,
+            peer_setup_issuance,
+            peer_state,
+            project,
+            provisioning_token,
+            resource_snapshot,
+            service_account,
+            health: _,
         } = relayed_subscription_events;
 
         async fn notify_for_relayed_subscription_events_on_channel<R: Resource + Subscribable + Clone>(
@@ -113,17 +176,25 @@ impl ResourcesManager {
             let (_, mut receiver) = channel;
             while let Ok(event) = receiver.try_recv() {
                 state.subscribers
-                    .notify(event)
+                    .publish(event)
                     .expect("should successfully send notification about event during resource transaction");
             }
         }
 
         notify_for_relayed_subscription_events_on_channel(cluster_configuration, state).await;
         notify_for_relayed_subscription_events_on_channel(cluster_deployment, state).await;
+        notify_for_relayed_subscription_events_on_channel(cluster_deployment_status, state).await;
+        notify_for_relayed_subscription_events_on_channel(hierarchy_node, state).await;
         notify_for_relayed_subscription_events_on_channel(old_peer_configuration, state).await;
         notify_for_relayed_subscription_events_on_channel(peer_configuration, state).await;
         notify_for_relayed_subscription_events_on_channel(peer_descriptor, state).await;
+        notify_for_relayed_subscription_events_on_channel(peer_group, state).await;
+        notify_for_relayed_subscription_events_on_channel(peer_setup_issuance, state).await;
         notify_for_relayed_subscription_events_on_channel(peer_state, state).await;
+        notify_for_relayed_subscription_events_on_channel(project, state).await;
+        notify_for_relayed_subscription_events_on_channel(provisioning_token, state).await;
+        notify_for_relayed_subscription_events_on_channel(resource_snapshot, state).await;
+        notify_for_relayed_subscription_events_on_channel(service_account, state).await;
     }
 }
 
@@ -132,7 +203,7 @@ impl ResourcesManager {
 impl ResourcesManager {
     pub fn new_in_memory() -> ResourcesManagerRef {
         let resources = futures::executor::block_on(
-            Resources::connect(PersistenceOptions::Disabled)
+            Resources::connect(PersistenceOptions::Disabled { snapshot: None })
         )
         .expect("Creating in-memory storage for tests should not fail");
 
@@ -205,11 +276,19 @@ mod test {
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            secrets: vec![],
+                            can_gateways: vec![],
+                            network_attachment: Default::default(),
                         },
                         results_url: None,
+                        sandbox: Default::default(),
                     }
                 ],
-            }
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
 
         let cluster_resource_id = ClusterId::random();
@@ -218,6 +297,9 @@ mod test {
             name: ClusterName::try_from("ClusterX032")?,
             leader: peer.id,
             devices: HashSet::new(),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies: Vec::new(),
         };
 
         assert!(testee.is_empty().await);
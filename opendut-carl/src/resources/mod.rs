@@ -1,6 +1,9 @@
+use std::path::{Path, PathBuf};
+
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::resources::Persistable;
-use crate::resources::storage::{PersistenceOptions, ResourcesStorage, ResourcesStorageApi};
+use crate::resources::storage::snapshot::{SnapshotError, VolatileSnapshot};
+use crate::resources::storage::{PersistenceOptions, ReadConsistency, ResourcesStorage, ResourcesStorageApi};
 use crate::resources::subscription::Subscribable;
 use crate::resources::transaction::{RelayedSubscriptionEvents, ResourcesTransaction};
 use resource::Resource;
@@ -10,6 +13,7 @@ pub mod ids;
 pub mod resource;
 pub(crate) mod storage;
 pub(crate) mod subscription;
+pub(crate) mod synchronization;
 mod transaction;
 
 pub struct Resources {
@@ -38,6 +42,39 @@ impl Resources {
             }),
         }
     }
+
+    pub fn get_with<R>(&self, consistency: ReadConsistency, id: R::Id) -> PersistenceResult<Option<R>>
+    where R: Resource + Persistable + Clone {
+        match &self.storage {
+            ResourcesStorage::Persistent(storage) => storage.get_with(consistency, id),
+            ResourcesStorage::Volatile(storage) => storage.get(id),
+        }
+    }
+
+    pub fn list_with<R>(&self, consistency: ReadConsistency) -> PersistenceResult<Vec<R>>
+    where R: Resource + Persistable + Clone {
+        match &self.storage {
+            ResourcesStorage::Persistent(storage) => storage.list_with(consistency),
+            ResourcesStorage::Volatile(storage) => storage.list(),
+        }
+    }
+}
+
+impl Resources {
+    /// Serializes the resources of the volatile storage, if a snapshot path is configured for it.
+    pub fn volatile_snapshot(&self) -> Option<Result<VolatileSnapshot, SnapshotError>> {
+        match &self.storage {
+            ResourcesStorage::Persistent(_) => None,
+            ResourcesStorage::Volatile(storage) => storage.snapshot_path().map(|_| storage.snapshot()),
+        }
+    }
+
+    pub fn volatile_snapshot_path(&self) -> Option<PathBuf> {
+        match &self.storage {
+            ResourcesStorage::Persistent(_) => None,
+            ResourcesStorage::Volatile(storage) => storage.snapshot_path().map(Path::to_path_buf),
+        }
+    }
 }
 
 impl ResourcesStorageApi for Resources {
@@ -73,6 +110,14 @@ impl ResourcesStorageApi for Resources {
             ResourcesStorage::Volatile(storage) => storage.list(),
         }
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable {
+        match &self.storage {
+            ResourcesStorage::Persistent(storage) => storage.list_ids::<R>(),
+            ResourcesStorage::Volatile(storage) => storage.list_ids::<R>(),
+        }
+    }
 }
 
 #[cfg(test)]
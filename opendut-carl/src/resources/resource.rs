@@ -1,15 +1,24 @@
 use std::any::Any;
 use std::fmt::Debug;
 
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus, ClusterId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::group::{PeerGroup, PeerGroupId};
+use opendut_types::peer::hierarchy::{HierarchyNode, HierarchyNodeId};
+use opendut_types::peer::provisioning::{ProvisioningToken, ProvisioningTokenId};
+use opendut_types::peer::setup::PeerSetupIssuance;
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::{Project, ProjectId};
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId};
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotId};
+
+use uuid::Uuid;
 
 use crate::resources::ids::IntoId;
 
 pub trait Resource: Any + Send + Sync + Debug + Clone {
-    type Id: IntoId<Self> + Clone + Debug;
+    type Id: IntoId<Self> + From<Uuid> + Clone + Debug;
 }
 
 impl Resource for ClusterConfiguration {
@@ -18,6 +27,9 @@ impl Resource for ClusterConfiguration {
 impl Resource for ClusterDeployment {
     type Id = ClusterId;
 }
+impl Resource for ClusterDeploymentStatus {
+    type Id = ClusterId;
+}
 impl Resource for OldPeerConfiguration {
     type Id = PeerId;
 }
@@ -27,6 +39,27 @@ impl Resource for PeerConfiguration {
 impl Resource for PeerDescriptor {
     type Id = PeerId;
 }
+impl Resource for PeerGroup {
+    type Id = PeerGroupId;
+}
+impl Resource for HierarchyNode {
+    type Id = HierarchyNodeId;
+}
 impl Resource for PeerState {
     type Id = PeerId;
 }
+impl Resource for PeerSetupIssuance {
+    type Id = PeerId;
+}
+impl Resource for Project {
+    type Id = ProjectId;
+}
+impl Resource for ProvisioningToken {
+    type Id = ProvisioningTokenId;
+}
+impl Resource for ResourceSnapshot {
+    type Id = SnapshotId;
+}
+impl Resource for ServiceAccount {
+    type Id = ServiceAccountId;
+}
@@ -1,15 +1,19 @@
+use std::ops::Not;
+
 use url::Url;
 
 use crate::persistence::database::ConnectError;
 use crate::persistence::error::PersistenceResult;
 use crate::persistence::resources::Persistable;
 use crate::resources::storage::persistent::PersistentResourcesStorage;
+use crate::resources::storage::snapshot::{SnapshotError, VolatileSnapshotOptions};
 use crate::resources::storage::volatile::VolatileResourcesStorage;
 use crate::resources::Resource;
 use crate::resources::subscription::Subscribable;
 
 pub mod volatile;
 pub mod persistent;
+pub mod snapshot;
 
 #[cfg(test)]
 mod tests;
@@ -21,14 +25,17 @@ pub enum ResourcesStorage {
 impl ResourcesStorage {
     pub async fn connect(options: PersistenceOptions) -> Result<Self, ConnectionError> {
         let storage = match options {
-            PersistenceOptions::Enabled { database_connect_info } => {
-                let storage = PersistentResourcesStorage::connect(&database_connect_info).await
-                    .map_err(|cause| ConnectionError::Database { url: database_connect_info.url, source: cause })?;
+            PersistenceOptions::Enabled { database_connect_info, read_replicas } => {
+                let storage = PersistentResourcesStorage::connect(&database_connect_info, &read_replicas).await?;
                 ResourcesStorage::Persistent(storage)
             }
-            PersistenceOptions::Disabled => {
+            PersistenceOptions::Disabled { snapshot: None } => {
                 ResourcesStorage::Volatile(VolatileResourcesStorage::default())
             }
+            PersistenceOptions::Disabled { snapshot: Some(snapshot) } => {
+                let storage = VolatileResourcesStorage::load_snapshot(&snapshot.path)?;
+                ResourcesStorage::Volatile(storage)
+            }
         };
         Ok(storage)
     }
@@ -38,11 +45,31 @@ impl ResourcesStorage {
 pub enum ConnectionError {
     #[error("Failed to connect to database at '{url}'")]
     Database { url: Url, #[source] source: ConnectError },
+    #[error("Failed to connect to read replica of database at '{url}'")]
+    ReadReplica { url: Url, #[source] source: ConnectError },
+    #[error("Failed to load the snapshot of the volatile storage")]
+    Snapshot(#[from] SnapshotError),
+}
+
+/// How up-to-date the data of a read has to be, which decides whether it may be served by a read replica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// Reads from the primary database, so all committed writes are visible. Required, when the data is used to decide on further writes.
+    Strong,
+    /// May read from a read replica, which lags behind the primary database, e.g. for dashboards and reports.
+    Eventual,
 }
 
 pub enum PersistenceOptions {
-    Enabled { database_connect_info: DatabaseConnectInfo },
-    Disabled,
+    Enabled {
+        database_connect_info: DatabaseConnectInfo,
+        /// Read-only copies of the database, which serve reads that tolerate stale data. Writes always go to the primary database.
+        read_replicas: Vec<DatabaseConnectInfo>,
+    },
+    Disabled {
+        /// File, which the resources are periodically written to and restored from on startup.
+        snapshot: Option<VolatileSnapshotOptions>,
+    },
 }
 impl PersistenceOptions {
     pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
@@ -73,15 +100,34 @@ impl PersistenceOptions {
                 Password { secret: value }
             };
 
+            let read_replicas = {
+                let field = "persistence.database.replica.urls";
+                let value = config.get_string(field)
+                    .map_err(|cause| LoadError::ReadField { field, source: Box::new(cause) })?;
+
+                value.split(',')
+                    .map(str::trim)
+                    .filter(|url| url.is_empty().not())
+                    .map(|url| {
+                        let url = Url::parse(url)
+                            .map_err(|cause| LoadError::ParseValue { field, value: url.to_owned(), source: Box::new(cause) })?;
+                        Ok(DatabaseConnectInfo { url, username: username.clone(), password: password.clone() })
+                    })
+                    .collect::<Result<Vec<_>, LoadError>>()?
+            };
+
             Ok(PersistenceOptions::Enabled {
                 database_connect_info: DatabaseConnectInfo {
                     url,
                     username,
                     password,
-                }
+                },
+                read_replicas,
             })
         } else {
-            Ok(PersistenceOptions::Disabled)
+            Ok(PersistenceOptions::Disabled {
+                snapshot: VolatileSnapshotOptions::load(config)?,
+            })
         }
     }
 }
@@ -117,4 +163,7 @@ pub trait ResourcesStorageApi {
 
     fn list<R>(&self) -> PersistenceResult<Vec<R>>
     where R: Resource + Persistable + Clone;
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable;
 }
@@ -1,25 +1,64 @@
-use crate::persistence::database::ConnectError;
 use crate::persistence::error::{PersistenceError, PersistenceResult};
 use crate::persistence::resources::Persistable;
-use crate::persistence::{Db, Storage};
+use crate::persistence::{Db, DbConnection, Storage};
 use crate::resources::storage::volatile::VolatileResourcesStorage;
-use crate::resources::storage::{DatabaseConnectInfo, Resource, ResourcesStorageApi};
-use diesel::{Connection, PgConnection};
+use crate::resources::storage::{ConnectionError, DatabaseConnectInfo, ReadConsistency, Resource, ResourcesStorageApi};
 use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
+use tracing::warn;
 use crate::resources::transaction::RelayedSubscriptionEvents;
 
 pub struct PersistentResourcesStorage {
-    db_connection: Mutex<PgConnection>,
+    db_connection: Mutex<DbConnection>,
+    read_replicas: ReadReplicas,
     memory: Mutex<VolatileResourcesStorage>,
 }
 impl PersistentResourcesStorage {
-    pub async fn connect(database_connect_info: &DatabaseConnectInfo) -> Result<Self, ConnectError> {
-        let db_connection = crate::persistence::database::connect(database_connect_info).await?;
+    pub async fn connect(database_connect_info: &DatabaseConnectInfo, read_replicas: &[DatabaseConnectInfo]) -> Result<Self, ConnectionError> {
+        let db_connection = crate::persistence::database::connect(database_connect_info).await
+            .map_err(|cause| ConnectionError::Database { url: database_connect_info.url.clone(), source: cause })?;
         let db_connection = Mutex::new(db_connection);
+
+        let mut read_replica_connections = Vec::new();
+        for read_replica in read_replicas {
+            let connection = crate::persistence::database::connect_read_replica(read_replica).await
+                .map_err(|cause| ConnectionError::ReadReplica { url: read_replica.url.clone(), source: cause })?;
+            read_replica_connections.push(Mutex::new(connection));
+        }
+        let read_replicas = ReadReplicas { connections: read_replica_connections, next: AtomicUsize::new(0) };
+
         let memory = VolatileResourcesStorage::default();
         let memory = Mutex::new(memory);
-        Ok(Self { db_connection, memory })
+        Ok(Self { db_connection, read_replicas, memory })
+    }
+
+    pub fn get_with<R>(&self, consistency: ReadConsistency, id: R::Id) -> PersistenceResult<Option<R>>
+    where R: Resource + Persistable + Clone {
+        self.read_with(consistency, |storage| R::get(id.clone(), storage))
+    }
+
+    pub fn list_with<R>(&self, consistency: ReadConsistency) -> PersistenceResult<Vec<R>>
+    where R: Resource + Persistable + Clone {
+        self.read_with(consistency, |storage| R::list(storage))
+    }
+
+    /// Reads from a read replica, if the consistency allows it, falling back to the primary database, if the read replica fails.
+    fn read_with<T>(&self, consistency: ReadConsistency, read: impl Fn(&Storage) -> PersistenceResult<T>) -> PersistenceResult<T> {
+        if let (ReadConsistency::Eventual, Some(read_replica)) = (consistency, self.read_replicas.next()) {
+            let mut db = read_replica.lock().unwrap();
+            let db = Db::from_connection(&mut db);
+            let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
+            match read(&storage) {
+                Ok(result) => return Ok(result),
+                Err(cause) => warn!("Reading from read replica failed. Reading from primary database instead:\n  {cause}"),
+            }
+        }
+
+        let mut db = self.db_connection.lock().unwrap();
+        let db = Db::from_connection(&mut db);
+        let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
+        read(&storage)
     }
 
     pub fn transaction<T, E, F>(&mut self, code: F) -> PersistenceResult<(Result<T, E>, RelayedSubscriptionEvents)>
@@ -87,11 +126,19 @@ impl ResourcesStorageApi for PersistentResourcesStorage {
         let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
         R::list(&storage)
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable {
+        let mut db = self.db_connection.lock().unwrap();
+        let db = Db::from_connection(&mut db);
+        let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
+        R::list_ids(&storage)
+    }
 }
 
 
 pub struct PersistentResourcesTransaction<'transaction> {
-    db_connection: Mutex<&'transaction mut PgConnection>,
+    db_connection: Mutex<&'transaction mut DbConnection>,
     memory: Mutex<&'transaction mut VolatileResourcesStorage>,
     pub relayed_subscription_events: &'transaction mut RelayedSubscriptionEvents,
 }
@@ -131,6 +178,30 @@ impl ResourcesStorageApi for PersistentResourcesTransaction<'_> {
         let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
         R::list(&storage)
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable {
+        let mut db = self.db_connection.lock().unwrap();
+        let db = Db::from_connection(&mut db);
+        let storage = Storage { db, memory: &mut self.memory.lock().unwrap() };
+        R::list_ids(&storage)
+    }
+}
+
+/// Connections to the read replicas, which take turns in serving reads.
+struct ReadReplicas {
+    connections: Vec<Mutex<DbConnection>>,
+    next: AtomicUsize,
+}
+impl ReadReplicas {
+    fn next(&self) -> Option<&Mutex<DbConnection>> {
+        if self.connections.is_empty() {
+            None
+        } else {
+            let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+            self.connections.get(index)
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
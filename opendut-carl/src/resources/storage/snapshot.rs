@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::PersistenceOptions;
+
+/// Where and how often the resources of the volatile storage are written to a JSON file, configured via `persistence.snapshot`.
+/// The file is loaded on startup, so that small test setups without a database survive a restart of CARL.
+#[derive(Clone, Debug)]
+pub struct VolatileSnapshotOptions {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+impl VolatileSnapshotOptions {
+    pub fn load(config: &config::Config) -> Result<Option<Self>, opendut_util::settings::LoadError> {
+        let path = config.get_string("persistence.snapshot.path")?;
+        if path.trim().is_empty() {
+            return Ok(None);
+        }
+        let interval = Duration::from_millis(config.get::<u64>("persistence.snapshot.interval.ms")?);
+
+        Ok(Some(Self { path: PathBuf::from(path), interval }))
+    }
+}
+
+/// Serialized resources of the volatile storage, by kind of resource and ID.
+/// Only the resources, which are persisted in a database, are contained. The others, e.g. the state of peers, are rebuilt at runtime.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VolatileSnapshot {
+    pub(super) documents: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+}
+impl VolatileSnapshot {
+    pub fn read(path: &Path) -> Result<Option<Self>, SnapshotError> {
+        if path.exists().not() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| SnapshotError::Io { path: path.to_owned(), source })?;
+        let snapshot = serde_json::from_str(&content)
+            .map_err(|source| SnapshotError::Parse { path: path.to_owned(), source })?;
+        Ok(Some(snapshot))
+    }
+
+    /// Writes under a temporary name first, so that a crash while writing does not leave an incomplete snapshot behind.
+    pub async fn write(&self, path: &Path) -> Result<(), SnapshotError> {
+        let content = serde_json::to_string_pretty(self)
+            .expect("Serializing a snapshot, which consists of JSON values, should not fail.");
+
+        if let Some(directory) = path.parent().filter(|directory| directory.as_os_str().is_empty().not()) {
+            tokio::fs::create_dir_all(directory).await
+                .map_err(|source| SnapshotError::Io { path: directory.to_owned(), source })?;
+        }
+        let temporary_path = path.with_extension("partial");
+        tokio::fs::write(&temporary_path, content).await
+            .map_err(|source| SnapshotError::Io { path: Clone::clone(&temporary_path), source })?;
+        tokio::fs::rename(&temporary_path, path).await
+            .map_err(|source| SnapshotError::Io { path: path.to_owned(), source })?;
+        Ok(())
+    }
+}
+
+/// Periodically writes the resources of the volatile storage to the snapshot file, if persistence is disabled and a snapshot path is configured.
+/// Unchanged resources are not written again.
+pub fn spawn_writing(
+    resources_manager: ResourcesManagerRef,
+    persistence_options: PersistenceOptions,
+) {
+    let PersistenceOptions::Disabled { snapshot: Some(options) } = persistence_options else {
+        return;
+    };
+
+    info!("Writing snapshots of the volatile storage to '{}' every {} ms.", options.path.display(), options.interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(options.interval);
+        let mut previous = None;
+        loop {
+            interval.tick().await;
+
+            let snapshot = match resources_manager.volatile_snapshot().await {
+                Some(Ok(snapshot)) => snapshot,
+                Some(Err(cause)) => {
+                    warn!("Failed to create snapshot of the volatile storage:\n  {cause}");
+                    continue;
+                }
+                None => return,
+            };
+            if previous.as_ref() == Some(&snapshot) {
+                continue;
+            }
+            match snapshot.write(&options.path).await {
+                Ok(()) => {
+                    debug!("Wrote snapshot of the volatile storage to '{}'.", options.path.display());
+                    previous = Some(snapshot);
+                }
+                Err(cause) => warn!("Failed to write snapshot of the volatile storage:\n  {cause}"),
+            }
+        }
+    });
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("Failed to access snapshot file '{path}'")]
+    Io { path: PathBuf, #[source] source: std::io::Error },
+    #[error("Snapshot file '{path}' is not valid JSON")]
+    Parse { path: PathBuf, #[source] source: serde_json::Error },
+    #[error("Failed to encode {kind} <{id}> for the snapshot")]
+    Encode { kind: &'static str, id: String, #[source] source: serde_json::Error },
+    #[error("Failed to decode {kind} <{id}> from the snapshot")]
+    Decode { kind: &'static str, id: String, #[source] source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+    use opendut_types::peer::PeerId;
+    use opendut_types::project::{Project, ProjectId, ProjectMember, ProjectName};
+
+    use super::*;
+    use crate::resources::storage::volatile::VolatileResourcesStorage;
+    use crate::resources::storage::ResourcesStorageApi;
+
+    #[tokio::test]
+    async fn should_restore_resources_from_the_written_snapshot() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("snapshot.json");
+
+        let project = Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from("powertrain")?,
+            members: [ProjectMember::from_str("user:alice")?].into(),
+            peers: HashSet::from([PeerId::random()]),
+            clusters: HashSet::new(),
+        };
+
+        let mut storage = VolatileResourcesStorage::load_snapshot(&path)?;
+        assert_that!(storage.list::<Project>()?, empty());
+        storage.insert(project.id, Clone::clone(&project))?;
+        storage.snapshot()?.write(&path).await?;
+
+        let restored = VolatileResourcesStorage::load_snapshot(&path)?;
+        assert_that!(restored.get::<Project>(project.id)?, some(eq(&project)));
+        assert_that!(restored.snapshot()?, eq(&storage.snapshot()?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_to_load_an_invalid_snapshot() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("snapshot.json");
+        std::fs::write(&path, "{ \"documents\": ")?;
+
+        let error = VolatileResourcesStorage::load_snapshot(&path).err();
+        assert_that!(error.map(|error| error.to_string()), some(contains_substring("is not valid JSON")));
+
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use crate::persistence::database;
-use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName, DeviceCount, DeviceInterfaceKind, DeviceSelector};
+use opendut_types::peer::label::LabelSelector;
 use opendut_types::peer::PeerId;
-use opendut_types::topology::DeviceId;
+use opendut_types::topology::{DeviceId, DeviceTag};
 use std::collections::HashSet;
+use std::str::FromStr;
 use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
 
 #[tokio::test]
@@ -18,6 +20,12 @@ async fn should_persist_cluster_configuration_in_database() -> anyhow::Result<()
     should_persist_cluster_configuration(db.resources_manager).await
 }
 
+#[tokio::test]
+async fn should_persist_cluster_configuration_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_cluster_configuration(db.resources_manager).await
+}
+
 async fn should_persist_cluster_configuration(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
 
     let peer = super::peer_descriptor::peer_descriptor()?;
@@ -48,10 +56,18 @@ async fn should_persist_cluster_configuration(resources_manager: ResourcesManage
     let testee = {
         let mut testee = testee.clone();
         testee.devices.remove(&cluster_devices[0]);
+        testee.device_selectors = vec![
+            DeviceSelector { tags: vec![DeviceTag::try_from("ecu")?], interface_kind: Some(DeviceInterfaceKind::Can), peer_labels: LabelSelector::default(), count: DeviceCount::new(1, Some(2))? },
+            DeviceSelector { tags: vec![], interface_kind: None, peer_labels: LabelSelector::from_str("location=hall3,!decommissioned")?, count: DeviceCount::new(1, None)? },
+        ];
+        testee.dependencies = vec![ClusterId::random(), ClusterId::random()];
         testee
     };
     resources_manager.insert(testee.id, testee.clone()).await?;
 
+    let result = resources_manager.get::<ClusterConfiguration>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+
     let result = resources_manager.remove::<ClusterConfiguration>(testee.id).await?;
     assert_eq!(result, Some(testee.clone()));
 
@@ -72,5 +88,8 @@ pub fn cluster_configuration(leader_id: PeerId, devices: Vec<DeviceId>) -> anyho
         name: ClusterName::try_from("cluster-name")?,
         leader: leader_id,
         devices: HashSet::from_iter(devices),
+        peer_groups: HashSet::new(),
+        device_selectors: Vec::new(),
+        dependencies: Vec::new(),
     })
 }
@@ -16,6 +16,12 @@ async fn should_persist_cluster_deployment_in_database() -> anyhow::Result<()> {
     should_persist_cluster_deployment(db.resources_manager).await
 }
 
+#[tokio::test]
+async fn should_persist_cluster_deployment_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_cluster_deployment(db.resources_manager).await
+}
+
 async fn should_persist_cluster_deployment(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
 
     let peer_descriptor = super::peer_descriptor::peer_descriptor()?;
@@ -0,0 +1,69 @@
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeploymentStatus, DeploymentState};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+
+#[tokio::test]
+async fn should_persist_cluster_deployment_status_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_cluster_deployment_status(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_cluster_deployment_status_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_cluster_deployment_status(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_cluster_deployment_status_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_cluster_deployment_status(db.resources_manager).await
+}
+
+async fn should_persist_cluster_deployment_status(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let peer_descriptor = super::peer_descriptor::peer_descriptor()?;
+    resources_manager.insert::<PeerDescriptor>(peer_descriptor.id, peer_descriptor.clone()).await?;
+
+    let cluster_configuration = super::cluster_configuration::cluster_configuration(
+        peer_descriptor.id,
+        peer_descriptor.topology.devices.into_iter().map(|device| device.id).collect()
+    )?;
+    resources_manager.insert::<ClusterConfiguration>(cluster_configuration.id, cluster_configuration.clone()).await?;
+
+    let other_peer = PeerId::random();
+    let testee = ClusterDeploymentStatus::pending(cluster_configuration.id, [peer_descriptor.id, other_peer]);
+
+    let result = resources_manager.get::<ClusterDeploymentStatus>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<ClusterDeploymentStatus>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert::<ClusterDeploymentStatus>(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<ClusterDeploymentStatus>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<ClusterDeploymentStatus>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let mut progressed = testee.clone();
+    progressed.configuring(&[peer_descriptor.id, other_peer]);
+    progressed.report(peer_descriptor.id, Ok(()));
+    progressed.report(other_peer, Err(String::from("cluster-network: failed after 20 ms: GRE interface could not be created")));
+    resources_manager.insert::<ClusterDeploymentStatus>(progressed.id, progressed.clone()).await?;
+
+    let result = resources_manager.get::<ClusterDeploymentStatus>(testee.id).await?;
+    assert_eq!(result, Some(progressed.clone()));
+    assert_eq!(result.and_then(|status| status.get(peer_descriptor.id).cloned()), Some(DeploymentState::Ready));
+
+    let result = resources_manager.remove::<ClusterDeploymentStatus>(testee.id).await?;
+    assert_eq!(result, Some(progressed));
+
+    let result = resources_manager.get::<ClusterDeploymentStatus>(testee.id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::peer::hierarchy::{HierarchyLevel, HierarchyNode, HierarchyNodeId, HierarchyNodeName};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn should_persist_hierarchy_node_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_hierarchy_node(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_hierarchy_node_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_hierarchy_node(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_hierarchy_node_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_hierarchy_node(db.resources_manager).await
+}
+
+async fn should_persist_hierarchy_node(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let peer = super::peer_descriptor::peer_descriptor()?;
+    resources_manager.insert::<PeerDescriptor>(peer.id, peer.clone()).await?;
+
+    let site = hierarchy_node("site-a", HierarchyLevel::Site, None, vec![])?;
+    let testee = hierarchy_node("room-1", HierarchyLevel::Room, Some(site.id), vec![peer.id])?;
+
+    let result = resources_manager.get::<HierarchyNode>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<HierarchyNode>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(site.id, site.clone()).await?;
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<HierarchyNode>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<HierarchyNode>().await?;
+    assert_eq!(result.len(), 2);
+
+    let testee = {
+        let mut testee = testee.clone();
+        testee.peers.clear();
+        testee
+    };
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<HierarchyNode>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+
+    let result = resources_manager.remove::<HierarchyNode>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+
+    let result = resources_manager.get::<HierarchyNode>(testee.id).await?;
+    assert!(result.is_none());
+
+    let result = resources_manager.remove::<HierarchyNode>(site.id).await?;
+    assert_eq!(result, Some(site.clone()));
+
+    let result = resources_manager.list::<HierarchyNode>().await?;
+    assert!(result.is_empty());
+
+    Ok(())
+}
+
+pub fn hierarchy_node(name: &str, level: HierarchyLevel, parent: Option<HierarchyNodeId>, peers: Vec<PeerId>) -> anyhow::Result<HierarchyNode> {
+    Ok(HierarchyNode {
+        id: HierarchyNodeId::random(),
+        name: HierarchyNodeName::try_from(name)?,
+        level,
+        parent,
+        peers: HashSet::from_iter(peers),
+    })
+}
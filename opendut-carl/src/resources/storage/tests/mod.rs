@@ -3,4 +3,12 @@
 mod peer_descriptor;
 mod cluster_configuration;
 mod cluster_deployment;
+mod cluster_deployment_status;
+mod peer_group;
+mod peer_setup_issuance;
+mod hierarchy_node;
+mod project;
+mod provisioning_token;
+mod resource_snapshot;
+mod service_account;
 mod transaction;
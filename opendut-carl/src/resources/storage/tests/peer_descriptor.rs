@@ -1,5 +1,9 @@
-use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine};
+use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, ContainerVolume, Engine};
+use opendut_types::peer::executor::sandbox::{ExecutorSandbox, ExecutorUser, SeccompProfile};
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind, ResultsUrl};
+use opendut_types::peer::hardware::{CanControllerCapabilities, HardwareCapabilities, NetworkInterfaceCapabilities};
+use opendut_types::peer::hook::{ClusterHook, ClusterHookFailurePolicy, ClusterHookName, ClusterHookStage};
+use opendut_types::peer::label::{AnnotationValue, LabelKey, LabelValue, PeerAnnotations, PeerLabels};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
 use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
@@ -20,6 +24,12 @@ async fn should_persist_peer_descriptor_in_database() -> anyhow::Result<()> {
     should_persist_peer_descriptor_implementation(db.resources_manager).await
 }
 
+#[tokio::test]
+async fn should_persist_peer_descriptor_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_peer_descriptor_implementation(db.resources_manager).await
+}
+
 async fn should_persist_peer_descriptor_implementation(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
 
     let testee = peer_descriptor()?;
@@ -42,6 +52,7 @@ async fn should_persist_peer_descriptor_implementation(resources_manager: Resour
         let mut testee = testee.clone();
         let removed_device = testee.topology.devices.remove(0);
         testee.network.interfaces.retain(|interface| interface.id != removed_device.interface);
+        testee.labels.clear();
         testee
     };
     resources_manager.insert(testee.id, testee.clone()).await?;
@@ -138,15 +149,61 @@ pub fn peer_descriptor() -> anyhow::Result<PeerDescriptor> {
                         args: vec![
                             ContainerCommandArgument::try_from("-la")?,
                         ],
+                        secrets: vec![
+                            ContainerSecret::try_from("container-secret")?,
+                        ],
+                        can_gateways: vec![
+                            ContainerCanGateway::try_from("can0:isotp:0x7E0:0x7E8")?,
+                        ],
+                        network_attachment: ContainerNetworkAttachment::try_from("sriov:enp1s0f0")?,
                     },
                     results_url: None,
+                    sandbox: ExecutorSandbox {
+                        user: Some(ExecutorUser::try_from("tester:dialout")?),
+                        seccomp_profile: SeccompProfile::try_from("/etc/opendut/seccomp/strict.json")?,
+                        read_only_filesystem: true,
+                    },
                 },
                 ExecutorDescriptor {
                     id: ExecutorId::random(),
                     kind: ExecutorKind::Executable,
                     results_url: Some(ResultsUrl::try_from("https://example.com/")?),
+                    sandbox: Default::default(),
                 },
             ]
         },
+        labels: PeerLabels::from([
+            (LabelKey::try_from("rack")?, LabelValue::try_from("7")?),
+        ]),
+        annotations: PeerAnnotations::from([
+            (LabelKey::try_from("note")?, AnnotationValue::try_from("Located next to the window.")?),
+        ]),
+        hardware: Some(HardwareCapabilities {
+            cpu_architecture: String::from("aarch64"),
+            cpu_cores: 4,
+            memory_bytes: 8 * 1024 * 1024 * 1024,
+            virtualization: None,
+            can_controllers: vec![
+                CanControllerCapabilities {
+                    interface: NetworkInterfaceName::try_from("can0")?,
+                    fd_supported: true,
+                },
+            ],
+            network_interfaces: vec![
+                NetworkInterfaceCapabilities {
+                    interface: NetworkInterfaceName::try_from("eth0")?,
+                    speed_mbits: Some(1000),
+                },
+            ],
+        }),
+        cluster_hooks: vec![
+            ClusterHook {
+                name: ClusterHookName::try_from("relay-on")?,
+                stage: ClusterHookStage::BeforeJoin,
+                script: String::from("relayctl --channel 3 on"),
+                timeout: std::time::Duration::from_secs(10),
+                failure_policy: ClusterHookFailurePolicy::Abort,
+            },
+        ],
     })
 }
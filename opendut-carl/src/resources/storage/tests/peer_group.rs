@@ -0,0 +1,91 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::group::{PeerGroup, PeerGroupId, PeerGroupLabel, PeerGroupName};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn should_persist_peer_group_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_peer_group(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_peer_group_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_peer_group(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_peer_group_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_peer_group(db.resources_manager).await
+}
+
+async fn should_persist_peer_group(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let peer = super::peer_descriptor::peer_descriptor()?;
+    resources_manager.insert::<PeerDescriptor>(peer.id, peer.clone()).await?;
+
+    let testee = peer_group(vec![peer.id])?;
+
+    let result = resources_manager.get::<PeerGroup>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<PeerGroup>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<PeerGroup>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<PeerGroup>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let cluster_configuration = {
+        let mut cluster_configuration = super::cluster_configuration::cluster_configuration(
+            peer.id,
+            peer.topology.devices.iter().map(|device| device.id).collect(),
+        )?;
+        cluster_configuration.peer_groups = HashSet::from([testee.id]);
+        cluster_configuration
+    };
+    resources_manager.insert(cluster_configuration.id, cluster_configuration.clone()).await?;
+
+    let testee = {
+        let mut testee = testee.clone();
+        testee.peers.clear();
+        testee.labels.clear();
+        testee
+    };
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<PeerGroup>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.get::<ClusterConfiguration>(cluster_configuration.id).await?;
+    assert_eq!(result, Some(cluster_configuration.clone()), "Updating a peer group should keep references from cluster configurations.");
+
+    let result = resources_manager.remove::<PeerGroup>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+
+    let result = resources_manager.get::<PeerGroup>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<PeerGroup>().await?;
+    assert!(result.is_empty());
+
+    let result = resources_manager.remove::<PeerGroup>(testee.id).await?;
+    assert_eq!(result, None);
+
+    Ok(())
+}
+
+pub fn peer_group(peers: Vec<PeerId>) -> anyhow::Result<PeerGroup> {
+    Ok(PeerGroup {
+        id: PeerGroupId::random(),
+        name: PeerGroupName::try_from("rack-01")?,
+        labels: HashSet::from([PeerGroupLabel::try_from("hil")?]),
+        peers: HashSet::from_iter(peers),
+    })
+}
@@ -0,0 +1,68 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerId;
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn should_persist_peer_setup_issuance_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_peer_setup_issuance(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_peer_setup_issuance_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_peer_setup_issuance(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_peer_setup_issuance_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_peer_setup_issuance(db.resources_manager).await
+}
+
+async fn should_persist_peer_setup_issuance(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let issued_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_731_315_600_000); //millisecond precision, as stored in the database
+
+    let testee = PeerSetupIssuance {
+        peer_id: PeerId::random(),
+        issued_by: String::from("alice"),
+        issued_at,
+        expires_at: Some(issued_at + Duration::from_secs(24 * 60 * 60)),
+        used_at: None,
+        revoked_at: None,
+    };
+
+    let result = resources_manager.get::<PeerSetupIssuance>(testee.peer_id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<PeerSetupIssuance>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.peer_id, testee.clone()).await?;
+
+    let result = resources_manager.get::<PeerSetupIssuance>(testee.peer_id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<PeerSetupIssuance>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let used = PeerSetupIssuance {
+        used_at: Some(issued_at + Duration::from_secs(60)),
+        ..testee.clone()
+    };
+    resources_manager.insert(used.peer_id, used.clone()).await?;
+
+    let result = resources_manager.get::<PeerSetupIssuance>(testee.peer_id).await?;
+    assert_eq!(result, Some(used.clone()));
+
+    let result = resources_manager.remove::<PeerSetupIssuance>(testee.peer_id).await?;
+    assert_eq!(result, Some(used));
+
+    let result = resources_manager.get::<PeerSetupIssuance>(testee.peer_id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
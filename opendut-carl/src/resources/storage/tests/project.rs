@@ -0,0 +1,70 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::project::{Project, ProjectId, ProjectMember, ProjectName};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[tokio::test]
+async fn should_persist_project_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_project(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_project_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_project(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_project_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_project(db.resources_manager).await
+}
+
+async fn should_persist_project(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let testee = Project {
+        id: ProjectId::random(),
+        name: ProjectName::try_from("powertrain")?,
+        members: [
+            ProjectMember::from_str("user:alice")?,
+            ProjectMember::from_str("group:/lab/powertrain")?,
+        ].into(),
+        peers: HashSet::from([PeerId::random()]),
+        clusters: HashSet::new(),
+    };
+
+    let result = resources_manager.get::<Project>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<Project>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<Project>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<Project>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let with_cluster = Project {
+        clusters: HashSet::from([ClusterId::random()]),
+        ..testee.clone()
+    };
+    resources_manager.insert(with_cluster.id, with_cluster.clone()).await?;
+
+    let result = resources_manager.get::<Project>(testee.id).await?;
+    assert_eq!(result, Some(with_cluster.clone()));
+
+    let result = resources_manager.remove::<Project>(testee.id).await?;
+    assert_eq!(result, Some(with_cluster));
+
+    let result = resources_manager.get::<Project>(testee.id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::peer::label::{LabelKey, LabelValue};
+use opendut_types::peer::provisioning::{PeerTemplate, ProvisioningToken, ProvisioningTokenId};
+use opendut_types::peer::{PeerId, PeerLocation};
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn should_persist_provisioning_token_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_provisioning_token(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_provisioning_token_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_provisioning_token(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_provisioning_token_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_provisioning_token(db.resources_manager).await
+}
+
+async fn should_persist_provisioning_token(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let testee = ProvisioningToken {
+        id: ProvisioningTokenId::random(),
+        pool: String::from("batch-2024-10"),
+        template: PeerTemplate {
+            location: Some(PeerLocation::try_from("Plant Ingolstadt")?),
+            labels: [(LabelKey::try_from("fleet")?, LabelValue::try_from("test-vehicles")?)].into(),
+        },
+        secret_hash: String::from("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+        created_by: String::from("manufacturing"),
+        created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_729_497_600_000), //millisecond precision, as stored in the database
+        claimed_by: None,
+    };
+
+    let result = resources_manager.get::<ProvisioningToken>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<ProvisioningToken>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<ProvisioningToken>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<ProvisioningToken>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let claimed = ProvisioningToken {
+        claimed_by: Some(PeerId::random()),
+        ..testee.clone()
+    };
+    resources_manager.insert(claimed.id, claimed.clone()).await?;
+
+    let result = resources_manager.get::<ProvisioningToken>(testee.id).await?;
+    assert_eq!(result, Some(claimed.clone()));
+
+    let result = resources_manager.remove::<ProvisioningToken>(testee.id).await?;
+    assert_eq!(result, Some(claimed));
+
+    let result = resources_manager.get::<ProvisioningToken>(testee.id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::snapshot::{ResourceSnapshot, SnapshotContent, SnapshotId, SnapshotName};
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn should_persist_resource_snapshot_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_resource_snapshot(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_resource_snapshot_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_resource_snapshot(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_resource_snapshot_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_resource_snapshot(db.resources_manager).await
+}
+
+async fn should_persist_resource_snapshot(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let peer = super::peer_descriptor::peer_descriptor()?;
+    let peer_group = super::peer_group::peer_group(vec![peer.id])?;
+
+    let testee = ResourceSnapshot {
+        id: SnapshotId::random(),
+        name: SnapshotName::try_from("before holiday shutdown")?,
+        created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_727_773_200_000), //millisecond precision, as stored in the database
+        content: SnapshotContent {
+            peers: vec![peer],
+            peer_groups: vec![peer_group],
+            ..Default::default()
+        },
+    };
+
+    let result = resources_manager.get::<ResourceSnapshot>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<ResourceSnapshot>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<ResourceSnapshot>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<ResourceSnapshot>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let result = resources_manager.remove::<ResourceSnapshot>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+
+    let result = resources_manager.get::<ResourceSnapshot>(testee.id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
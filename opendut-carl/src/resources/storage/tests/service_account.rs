@@ -0,0 +1,73 @@
+use crate::persistence::database;
+use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use opendut_types::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountName, ServiceAccountScope, ServiceAccountToken, ServiceAccountTokenId};
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn should_persist_service_account_in_memory() -> anyhow::Result<()> {
+    let resources_manager = ResourcesManager::new_in_memory();
+    should_persist_service_account(resources_manager).await
+}
+
+#[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+#[tokio::test]
+async fn should_persist_service_account_in_database() -> anyhow::Result<()> {
+    let db = database::testing::spawn_and_connect_resources_manager().await?;
+    should_persist_service_account(db.resources_manager).await
+}
+
+#[tokio::test]
+async fn should_persist_service_account_in_sqlite() -> anyhow::Result<()> {
+    let db = database::testing::connect_sqlite_resources_manager().await?;
+    should_persist_service_account(db.resources_manager).await
+}
+
+async fn should_persist_service_account(resources_manager: ResourcesManagerRef) -> anyhow::Result<()> {
+
+    let created_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_730_278_800_000); //millisecond precision, as stored in the database
+
+    let testee = ServiceAccount {
+        id: ServiceAccountId::random(),
+        name: ServiceAccountName::try_from("ci-pipeline")?,
+        scopes: [ServiceAccountScope::Read, ServiceAccountScope::Clusters].into(),
+        created_by: String::from("alice"),
+        created_at,
+        tokens: vec![],
+    };
+
+    let result = resources_manager.get::<ServiceAccount>(testee.id).await?;
+    assert!(result.is_none());
+    let result = resources_manager.list::<ServiceAccount>().await?;
+    assert!(result.is_empty());
+
+    resources_manager.insert(testee.id, testee.clone()).await?;
+
+    let result = resources_manager.get::<ServiceAccount>(testee.id).await?;
+    assert_eq!(result, Some(testee.clone()));
+    let result = resources_manager.list::<ServiceAccount>().await?;
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.first(), Some(&testee));
+
+    let with_token = ServiceAccount {
+        tokens: vec![ServiceAccountToken {
+            id: ServiceAccountTokenId::random(),
+            secret_hash: String::from("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+            created_at,
+            expires_at: created_at + Duration::from_secs(30 * 24 * 60 * 60),
+            read_only: true,
+        }],
+        ..testee.clone()
+    };
+    resources_manager.insert(with_token.id, with_token.clone()).await?;
+
+    let result = resources_manager.get::<ServiceAccount>(testee.id).await?;
+    assert_eq!(result, Some(with_token.clone()));
+
+    let result = resources_manager.remove::<ServiceAccount>(testee.id).await?;
+    assert_eq!(result, Some(with_token));
+
+    let result = resources_manager.get::<ServiceAccount>(testee.id).await?;
+    assert!(result.is_none());
+
+    Ok(())
+}
@@ -1,11 +1,24 @@
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::provisioning::ProvisioningToken;
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
 use opendut_types::resources::Id;
+use opendut_types::service_account::ServiceAccount;
+use opendut_types::snapshot::ResourceSnapshot;
+use uuid::Uuid;
 
 use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::document::Document;
 use crate::persistence::resources::Persistable;
 use crate::resources::ids::IntoId;
+use crate::resources::storage::snapshot::{SnapshotError, VolatileSnapshot};
 use crate::resources::storage::ResourcesStorageApi;
 use crate::resources::Resource;
 use crate::resources::subscription::Subscribable;
@@ -14,8 +27,83 @@ use crate::resources::transaction::RelayedSubscriptionEvents;
 #[derive(Default)]
 pub struct VolatileResourcesStorage {
     storage: HashMap<TypeId, HashMap<Id, Box<dyn Any + Send + Sync>>>,
+    snapshot_path: Option<PathBuf>,
 }
 impl VolatileResourcesStorage {
+    /// Restores the resources from the snapshot file, if it exists, and keeps its path for writing further snapshots.
+    pub fn load_snapshot(path: &Path) -> Result<Self, SnapshotError> {
+        let mut storage = Self { snapshot_path: Some(path.to_owned()), ..Default::default() };
+
+        if let Some(snapshot) = VolatileSnapshot::read(path)? {
+            storage.import_documents::<ClusterConfiguration>(&snapshot)?;
+            storage.import_documents::<ClusterDeployment>(&snapshot)?;
+            storage.import_documents::<ClusterDeploymentStatus>(&snapshot)?;
+            storage.import_documents::<HierarchyNode>(&snapshot)?;
+            storage.import_documents::<PeerDescriptor>(&snapshot)?;
+            storage.import_documents::<PeerGroup>(&snapshot)?;
+            storage.import_documents::<PeerSetupIssuance>(&snapshot)?;
+            storage.import_documents::<Project>(&snapshot)?;
+            storage.import_documents::<ProvisioningToken>(&snapshot)?;
+            storage.import_documents::<ResourceSnapshot>(&snapshot)?;
+            storage.import_documents::<ServiceAccount>(&snapshot)?;
+        }
+        Ok(storage)
+    }
+
+    pub fn snapshot_path(&self) -> Option<&Path> {
+        self.snapshot_path.as_deref()
+    }
+
+    /// Serializes the resources, which would be persisted in a database, for writing them to the snapshot file.
+    pub fn snapshot(&self) -> Result<VolatileSnapshot, SnapshotError> {
+        let mut snapshot = VolatileSnapshot::default();
+        self.export_documents::<ClusterConfiguration>(&mut snapshot)?;
+        self.export_documents::<ClusterDeployment>(&mut snapshot)?;
+        self.export_documents::<ClusterDeploymentStatus>(&mut snapshot)?;
+        self.export_documents::<HierarchyNode>(&mut snapshot)?;
+        self.export_documents::<PeerDescriptor>(&mut snapshot)?;
+        self.export_documents::<PeerGroup>(&mut snapshot)?;
+        self.export_documents::<PeerSetupIssuance>(&mut snapshot)?;
+        self.export_documents::<Project>(&mut snapshot)?;
+        self.export_documents::<ProvisioningToken>(&mut snapshot)?;
+        self.export_documents::<ResourceSnapshot>(&mut snapshot)?;
+        self.export_documents::<ServiceAccount>(&mut snapshot)?;
+        Ok(snapshot)
+    }
+
+    fn export_documents<R: Document>(&self, snapshot: &mut VolatileSnapshot) -> Result<(), SnapshotError> {
+        let Some(column) = self.column_of::<R>() else {
+            return Ok(());
+        };
+        let mut documents = BTreeMap::new();
+        for (id, resource) in column {
+            let resource = resource.downcast_ref::<R>()
+                .expect("It should always be possible to cast the stored data back to its own type.");
+            let value = serde_json::to_value(resource)
+                .map_err(|source| SnapshotError::Encode { kind: R::KIND, id: id.to_string(), source })?;
+            documents.insert(id.value().to_string(), value);
+        }
+        snapshot.documents.insert(R::KIND.to_owned(), documents);
+        Ok(())
+    }
+
+    fn import_documents<R: Document>(&mut self, snapshot: &VolatileSnapshot) -> Result<(), SnapshotError> {
+        let Some(documents) = snapshot.documents.get(R::KIND) else {
+            return Ok(());
+        };
+        for (id, value) in documents {
+            let uuid = Uuid::parse_str(id)
+                .map_err(|cause| SnapshotError::Decode { kind: R::KIND, id: id.clone(), source: serde::de::Error::custom(cause) })?;
+            let resource = serde_json::from_value::<R>(Clone::clone(value))
+                .map_err(|source| SnapshotError::Decode { kind: R::KIND, id: id.clone(), source })?;
+            self.storage
+                .entry(TypeId::of::<R>())
+                .or_default()
+                .insert(R::Id::from(uuid).into_id(), Box::new(resource));
+        }
+        Ok(())
+    }
+
     pub fn noop_transaction<T, E, F>(&mut self, code: F) -> PersistenceResult<(Result<T, E>, RelayedSubscriptionEvents)>
     where
         F: FnOnce(VolatileResourcesTransaction) -> Result<T, E>,
@@ -89,6 +177,19 @@ impl ResourcesStorageApi for VolatileResourcesStorage {
         };
         Ok(result)
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource {
+        let result = match self.column_of::<R>() {
+            Some(column) => {
+                column.keys()
+                    .map(|id| R::Id::from(id.value()))
+                    .collect()
+            }
+            None => Vec::new()
+        };
+        Ok(result)
+    }
 }
 impl VolatileResourcesStorage {
     fn column_of<R>(&self) -> Option<&HashMap<Id, Box<dyn Any + Send + Sync>>>
@@ -144,4 +245,9 @@ impl ResourcesStorageApi for VolatileResourcesTransaction<'_> {
     where R: Resource + Persistable + Clone {
         self.inner.list()
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable {
+        self.inner.list_ids::<R>()
+    }
 }
@@ -1,52 +1,135 @@
 use crate::resources::resource::Resource;
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::provisioning::ProvisioningToken;
+use opendut_types::peer::setup::PeerSetupIssuance;
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
+use opendut_types::service_account::ServiceAccount;
+use opendut_types::snapshot::ResourceSnapshot;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Number of events buffered per resource type. This caps the backlog of each subscriber:
+/// a subscriber, which falls behind by more events, misses the oldest ones and is informed via [`ReceiveError::Lagged`].
+/// It then continues with the oldest event still buffered.
+pub const SUBSCRIPTION_CAPACITY: usize = 100;
+
+/// Number of times a subscriber may lag behind without catching up in between, before its subscription is considered stale and closed.
+/// Its consumer is then informed via [`ReceiveError::Stale`] and has to subscribe anew.
+pub const MAX_CONSECUTIVE_LAGS: u32 = 3;
 
 
 pub struct Subscription<R: Resource> {
-    receiver: broadcast::Receiver<SubscriptionEvent<R>>,
+    receiver: Option<broadcast::Receiver<SubscriptionEvent<R>>>,
+    consecutive_lags: u32,
+    health: Arc<SubscriberHealth>,
 }
 impl<R: Resource> Subscription<R> {
     pub async fn receive(&mut self) -> Result<SubscriptionEvent<R>, ReceiveError> {
-        self.receiver.recv().await
-            .map_err(ReceiveError::Broadcast)
+        let receiver = self.receiver.as_mut()
+            .ok_or(ReceiveError::Stale)?;
+
+        match receiver.recv().await {
+            Ok(event) => {
+                if receiver.is_empty() { //caught up with the sender
+                    self.consecutive_lags = 0;
+                }
+                Ok(event)
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                self.health.lagged_events.fetch_add(skipped, Ordering::Relaxed);
+                self.consecutive_lags += 1;
+
+                if self.consecutive_lags >= MAX_CONSECUTIVE_LAGS {
+                    warn!("Subscriber lagged behind {} times without catching up. Closing its subscription as stale.", self.consecutive_lags);
+                    self.receiver = None;
+                    self.health.stale_subscriptions.fetch_add(1, Ordering::Relaxed);
+                    Err(ReceiveError::Stale)
+                } else {
+                    Err(ReceiveError::Lagged { skipped })
+                }
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                self.receiver = None;
+                Err(ReceiveError::Closed)
+            }
+        }
     }
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SubscriptionEvent<R: Resource> {
     Inserted { id: R::Id, value: R },
+    /// The resource was removed. Contains its last value.
+    Removed { id: R::Id, value: R },
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum ReceiveError {
-    #[error("Error while receiving Broadcast")]
-    Broadcast(#[from] broadcast::error::RecvError),
+    #[error("Subscriber lagged behind and missed {skipped} events")]
+    Lagged { skipped: u64 },
+    #[error("Subscription was closed as stale, because the subscriber lagged behind repeatedly")]
+    Stale,
+    #[error("Subscription was closed, because no more events will be sent")]
+    Closed,
+}
+
+/// Accounting of the subscribers of one resource type, which outlives the individual subscriptions.
+#[derive(Debug, Default)]
+pub struct SubscriberHealth {
+    lagged_events: AtomicU64,
+    stale_subscriptions: AtomicU64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubscriptionHealthReport {
+    pub resource: &'static str,
+    /// Number of live subscriptions. Subscriptions are dropped together with their consumer.
+    pub subscribers: usize,
+    /// Number of events not yet received by the slowest subscriber.
+    pub queued_events: usize,
+    pub lagged_events: u64,
+    pub stale_subscriptions: u64,
 }
 
 pub trait Subscribable: Resource {
-    fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<SubscriptionEvent<Self>>
+    const SUBSCRIPTION_NAME: &'static str;
+
+    fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &mut ResourceSubscriptionChannel<Self>
     where Self: Sized;
 }
 macro_rules! impl_subscribable {
     ($resource:ty, $field:ident) => {
         impl Subscribable for $resource {
-            fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<SubscriptionEvent<Self>>
+            const SUBSCRIPTION_NAME: &'static str = stringify!($field);
+
+            fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &mut ResourceSubscriptionChannel<Self>
             where Self: Sized {
-                let (sender, _) = &resource_subscribers.$field;
-                sender
+                &mut resource_subscribers.$field
             }
         }
     }
 }
 impl_subscribable!(ClusterConfiguration, cluster_configuration);
 impl_subscribable!(ClusterDeployment, cluster_deployment);
+impl_subscribable!(ClusterDeploymentStatus, cluster_deployment_status);
+impl_subscribable!(HierarchyNode, hierarchy_node);
 impl_subscribable!(OldPeerConfiguration, old_peer_configuration);
 impl_subscribable!(PeerConfiguration, peer_configuration);
 impl_subscribable!(PeerDescriptor, peer_descriptor);
+impl_subscribable!(PeerGroup, peer_group);
+impl_subscribable!(PeerSetupIssuance, peer_setup_issuance);
 impl_subscribable!(PeerState, peer_state);
+impl_subscribable!(Project, project);
+impl_subscribable!(ProvisioningToken, provisioning_token);
+impl_subscribable!(ResourceSnapshot, resource_snapshot);
+impl_subscribable!(ServiceAccount, service_account);
 
 
 pub type ResourceSubscriptionChannel<R> = (broadcast::Sender<SubscriptionEvent<R>>, broadcast::Receiver<SubscriptionEvent<R>>); //store both the sender and initial receiver, to prevent channel from closing
@@ -54,42 +137,118 @@ pub type ResourceSubscriptionChannel<R> = (broadcast::Sender<SubscriptionEvent<R
 pub struct ResourceSubscriptionChannels {
     pub cluster_configuration: ResourceSubscriptionChannel<ClusterConfiguration>,
     pub cluster_deployment: ResourceSubscriptionChannel<ClusterDeployment>,
+    pub cluster_deployment_status: ResourceSubscriptionChannel<ClusterDeploymentStatus>,
+    pub hierarchy_node: ResourceSubscriptionChannel<HierarchyNode>,
     pub old_peer_configuration: ResourceSubscriptionChannel<OldPeerConfiguration>,
     pub peer_configuration: ResourceSubscriptionChannel<PeerConfiguration>,
     pub peer_descriptor: ResourceSubscriptionChannel<PeerDescriptor>,
+    pub peer_group: ResourceSubscriptionChannel<PeerGroup>,
+    pub peer_setup_issuance: ResourceSubscriptionChannel<PeerSetupIssuance>,
     pub peer_state: ResourceSubscriptionChannel<PeerState>,
+    pub project: ResourceSubscriptionChannel<Project>,
+    pub provisioning_token: ResourceSubscriptionChannel<ProvisioningToken>,
+    pub resource_snapshot: ResourceSubscriptionChannel<ResourceSnapshot>,
+    pub service_account: ResourceSubscriptionChannel<ServiceAccount>,
+    pub health: HashMap<&'static str, Arc<SubscriberHealth>>,
 }
 impl ResourceSubscriptionChannels {
     pub fn subscribe<R: Resource + Subscribable>(&mut self) -> Subscription<R> {
-        let receiver = R::resource_subscribers_field(self)
-            .subscribe();
-        Subscription { receiver }
+        let health = self.health_of::<R>();
+        let (sender, _) = R::resource_subscribers_field(self);
+        Subscription {
+            receiver: Some(sender.subscribe()),
+            consecutive_lags: 0,
+            health,
+        }
     }
 
+    /// Queues the event, e.g. for relaying it after a transaction. The event stays available to the initial receiver.
     pub fn notify<R: Resource + Subscribable + Sized>(&mut self, event: SubscriptionEvent<R>) -> Result<(), broadcast::error::SendError<SubscriptionEvent<R>>> {
-        R::resource_subscribers_field(self)
-            .send(event)?;
+        let (sender, _) = R::resource_subscribers_field(self);
+        sender.send(event)?;
         Ok(())
     }
+
+    /// Sends the event to the subscribers. The initial receiver is drained right away,
+    /// so that events are released as soon as all subscribers received them, rather than staying buffered for a receiver no one reads from.
+    pub fn publish<R: Resource + Subscribable + Sized>(&mut self, event: SubscriptionEvent<R>) -> Result<(), broadcast::error::SendError<SubscriptionEvent<R>>> {
+        let (sender, initial_receiver) = R::resource_subscribers_field(self);
+        sender.send(event)?;
+        while initial_receiver.try_recv().is_ok() {}
+        Ok(())
+    }
+
+    pub fn health_reports(&mut self) -> Vec<SubscriptionHealthReport> {
+        vec![
+            self.health_report::<ClusterConfiguration>(),
+            self.health_report::<ClusterDeployment>(),
+            self.health_report::<ClusterDeploymentStatus>(),
+            self.health_report::<HierarchyNode>(),
+            self.health_report::<OldPeerConfiguration>(),
+            self.health_report::<PeerConfiguration>(),
+            self.health_report::<PeerDescriptor>(),
+            self.health_report::<PeerGroup>(),
+            self.health_report::<PeerSetupIssuance>(),
+            self.health_report::<PeerState>(),
+            self.health_report::<Project>(),
+            self.health_report::<ProvisioningToken>(),
+            self.health_report::<ResourceSnapshot>(),
+            self.health_report::<ServiceAccount>(),
+        ]
+    }
+
+    fn health_report<R: Resource + Subscribable>(&mut self) -> SubscriptionHealthReport {
+        let health = self.health_of::<R>();
+        let (sender, _) = R::resource_subscribers_field(self);
+
+        SubscriptionHealthReport {
+            resource: R::SUBSCRIPTION_NAME,
+            subscribers: sender.receiver_count().saturating_sub(1), //without the initial receiver
+            queued_events: sender.len(),
+            lagged_events: health.lagged_events.load(Ordering::Relaxed),
+            stale_subscriptions: health.stale_subscriptions.load(Ordering::Relaxed),
+        }
+    }
+
+    fn health_of<R: Resource + Subscribable>(&mut self) -> Arc<SubscriberHealth> {
+        Arc::clone(self.health.entry(R::SUBSCRIPTION_NAME).or_default())
+    }
 }
 impl Default for ResourceSubscriptionChannels {
     fn default() -> Self {
-        let capacity = 100;
+        let capacity = SUBSCRIPTION_CAPACITY;
 
         let cluster_configuration = broadcast::channel(capacity);
         let cluster_deployment = broadcast::channel(capacity);
+        let cluster_deployment_status = broadcast::channel(capacity);
+        let hierarchy_node = broadcast::channel(capacity);
         let old_peer_configuration = broadcast::channel(capacity);
         let peer_configuration = broadcast::channel(capacity);
         let peer_descriptor = broadcast::channel(capacity);
+        let peer_group = broadcast::channel(capacity);
+        let peer_setup_issuance = broadcast::channel(capacity);
         let peer_state = broadcast::channel(capacity);
+        let project = broadcast::channel(capacity);
+        let provisioning_token = broadcast::channel(capacity);
+        let resource_snapshot = broadcast::channel(capacity);
+        let service_account = broadcast::channel(capacity);
 
         Self {
             cluster_configuration,
             cluster_deployment,
+            cluster_deployment_status,
+            hierarchy_node,
             old_peer_configuration,
             peer_configuration,
             peer_descriptor,
+            peer_group,
+            peer_setup_issuance,
             peer_state,
+            project,
+            provisioning_token,
+            resource_snapshot,
+            service_account,
+            health: HashMap::new(),
         }
     }
 }
@@ -125,4 +284,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_notify_about_resource_removals() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let id = PeerId::random();
+        let value = PeerState::Down;
+        resources_manager.insert(id, value.clone()).await?;
+
+        let mut subscription = resources_manager.subscribe::<PeerState>().await;
+        resources_manager.remove::<PeerState>(id).await?;
+        assert_eq!(timeout(Duration::from_secs(10), subscription.receive()).await??, SubscriptionEvent::Removed { id, value });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_account_for_subscribers_and_drop_stale_subscriptions() -> anyhow::Result<()> {
+        let mut channels = ResourceSubscriptionChannels::default();
+
+        let mut subscription = channels.subscribe::<PeerState>();
+        let dropped_subscription = channels.subscribe::<PeerState>();
+        drop(dropped_subscription);
+
+        let report = |channels: &mut ResourceSubscriptionChannels| channels.health_reports().into_iter()
+            .find(|report| report.resource == PeerState::SUBSCRIPTION_NAME)
+            .expect("should contain report for PeerState");
+
+        assert_eq!(report(&mut channels).subscribers, 1);
+
+        let id = PeerId::random();
+        for lag in 1..=MAX_CONSECUTIVE_LAGS {
+            for _ in 0..=SUBSCRIPTION_CAPACITY {
+                channels.publish(SubscriptionEvent::Inserted { id, value: PeerState::Down })?;
+            }
+            assert_eq!(report(&mut channels).queued_events, SUBSCRIPTION_CAPACITY);
+
+            let result = subscription.receive().await;
+            if lag < MAX_CONSECUTIVE_LAGS {
+                assert!(matches!(result, Err(ReceiveError::Lagged { .. })));
+            } else {
+                assert!(matches!(result, Err(ReceiveError::Stale)));
+            }
+        }
+
+        let report = report(&mut channels);
+        assert_eq!(report.subscribers, 0);
+        assert_eq!(report.queued_events, 0);
+        assert!(report.lagged_events > 0);
+        assert_eq!(report.stale_subscriptions, 1);
+        assert!(matches!(subscription.receive().await, Err(ReceiveError::Stale)));
+
+        Ok(())
+    }
 }
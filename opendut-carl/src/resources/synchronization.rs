@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterDeploymentStatus};
+use opendut_types::peer::group::PeerGroup;
+use opendut_types::peer::hierarchy::HierarchyNode;
+use opendut_types::peer::provisioning::ProvisioningToken;
+use opendut_types::peer::setup::PeerSetupIssuance;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::Project;
+use opendut_types::service_account::ServiceAccount;
+use opendut_types::snapshot::ResourceSnapshot;
+
+use crate::leadership::{LeaderElectionOptions, LeadershipRef, Role};
+use crate::persistence::error::PersistenceError;
+use crate::persistence::query::document::Document;
+use crate::persistence::resources::Persistable;
+use crate::resources::ids::IntoId;
+use crate::resources::manager::{ResourcesManagerRef, SubscriptionEvent};
+use crate::resources::storage::ResourcesStorageApi;
+
+/// Turns changes in the shared database into subscription events on instances, which did not make the changes themselves.
+/// Only the leader writes, so a follower would not notice changes otherwise, e.g. for streaming them to clients via the resource observer.
+///
+/// Remembers the last seen value of each persisted resource and publishes the differences to the subscribers of the [`ResourcesManager`](crate::resources::manager::ResourcesManager).
+/// Resources, which are only held in memory, like the state of peers, are not synchronized, as they are only known to the leader.
+pub struct ResourceSynchronizer {
+    resources_manager: ResourcesManagerRef,
+    known: HashMap<&'static str, HashMap<Uuid, serde_json::Value>>,
+}
+
+impl ResourceSynchronizer {
+    pub fn new(resources_manager: ResourcesManagerRef) -> Self {
+        Self { resources_manager, known: HashMap::new() }
+    }
+
+    /// Compares the resources in the database with the last seen values and returns the number of changes.
+    /// The changes are published as events, if `publish` is set. Otherwise, only the last seen values are updated, e.g. for the baseline on startup.
+    pub async fn synchronize(&mut self, publish: bool) -> Result<usize, SynchronizationError> {
+        let mut changes = 0;
+        changes += self.synchronize_documents::<ClusterConfiguration>(publish).await?;
+        changes += self.synchronize_documents::<ClusterDeployment>(publish).await?;
+        changes += self.synchronize_documents::<ClusterDeploymentStatus>(publish).await?;
+        changes += self.synchronize_documents::<HierarchyNode>(publish).await?;
+        changes += self.synchronize_documents::<PeerDescriptor>(publish).await?;
+        changes += self.synchronize_documents::<PeerGroup>(publish).await?;
+        changes += self.synchronize_documents::<PeerSetupIssuance>(publish).await?;
+        changes += self.synchronize_documents::<Project>(publish).await?;
+        changes += self.synchronize_documents::<ProvisioningToken>(publish).await?;
+        changes += self.synchronize_documents::<ResourceSnapshot>(publish).await?;
+        changes += self.synchronize_documents::<ServiceAccount>(publish).await?;
+        Ok(changes)
+    }
+
+    async fn synchronize_documents<R>(&mut self, publish: bool) -> Result<usize, SynchronizationError>
+    where R: Document + Persistable {
+        let resources = self.resources_manager.resources(|resources| {
+            let mut current = Vec::new();
+            for id in resources.list_ids::<R>()? {
+                if let Some(resource) = resources.get::<R>(Clone::clone(&id))? {
+                    current.push((id, resource));
+                }
+            }
+            Ok(current)
+        }).await?;
+
+        let known = self.known.entry(R::KIND).or_default();
+        let mut current = HashMap::with_capacity(resources.len());
+        let mut events = Vec::new();
+
+        for (id, resource) in resources {
+            let uuid = IntoId::<R>::into_id(Clone::clone(&id)).value();
+            let value = serde_json::to_value(&resource)
+                .map_err(|source| SynchronizationError::Encode { kind: R::KIND, id: uuid, source })?;
+            if known.get(&uuid) != Some(&value) {
+                events.push(SubscriptionEvent::Inserted { id, value: resource });
+            }
+            current.insert(uuid, value);
+        }
+        for (uuid, value) in known.drain() {
+            if current.contains_key(&uuid) {
+                continue;
+            }
+            match serde_json::from_value::<R>(value) {
+                Ok(resource) => events.push(SubscriptionEvent::Removed { id: R::Id::from(uuid), value: resource }),
+                Err(cause) => warn!("Could not decode last seen value of removed {} <{uuid}>. Not publishing its removal:\n  {cause}", R::KIND),
+            }
+        }
+        *known = current;
+
+        let changes = events.len();
+        if publish {
+            for event in events {
+                self.resources_manager.publish(event).await;
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// Synchronizes the resources periodically, if leader election is enabled.
+/// Followers publish the observed changes. The leader publishes its changes via its own transactions, so it only keeps the last seen values up-to-date,
+/// except for the first synchronization after its promotion, which publishes the changes made by the previous leader in the meantime.
+pub fn spawn_synchronization(
+    resources_manager: ResourcesManagerRef,
+    leadership: LeadershipRef,
+    options: LeaderElectionOptions,
+) {
+    let LeaderElectionOptions::Enabled { synchronization_interval, .. } = options else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let mut synchronizer = ResourceSynchronizer::new(resources_manager);
+        let mut roles = leadership.subscribe();
+        let mut interval = tokio::time::interval(synchronization_interval);
+        let mut previous_role = None;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                changed = roles.changed() => if changed.is_err() { return },
+            }
+            let role = *roles.borrow_and_update();
+            let publish = match previous_role {
+                None => false,
+                Some(previous_role) => role == Role::Follower || previous_role == Role::Follower,
+            };
+
+            match synchronizer.synchronize(publish).await {
+                Ok(changes) => {
+                    if publish && changes > 0 {
+                        debug!("Published {changes} changes of resources, which were observed in the database.");
+                    }
+                    previous_role = Some(role);
+                }
+                Err(cause) => warn!("Failed to synchronize resources with the database:\n  {cause}"),
+            }
+        }
+    });
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SynchronizationError {
+    #[error("Failed to read resources for synchronization")]
+    Persistence(#[from] PersistenceError),
+    #[error("Failed to encode {kind} <{id}> for comparing it with its last seen value")]
+    Encode { kind: &'static str, id: Uuid, #[source] source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use opendut_types::project::{ProjectId, ProjectName};
+
+    use super::*;
+    use crate::resources::manager::ResourcesManager;
+
+    fn project(name: &str) -> anyhow::Result<Project> {
+        Ok(Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from(name)?,
+            members: Default::default(),
+            peers: HashSet::new(),
+            clusters: HashSet::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn should_publish_the_changes_since_the_last_synchronization() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let powertrain = project("powertrain")?;
+        let chassis = project("chassis")?;
+        resources_manager.insert(powertrain.id, Clone::clone(&powertrain)).await?;
+
+        let mut testee = ResourceSynchronizer::new(Arc::clone(&resources_manager));
+        assert_that!(testee.synchronize(false).await?, eq(1));
+        assert_that!(testee.synchronize(true).await?, eq(0));
+
+        resources_manager.insert(chassis.id, Clone::clone(&chassis)).await?;
+        resources_manager.remove::<Project>(powertrain.id).await?;
+
+        let mut subscription = resources_manager.subscribe::<Project>().await;
+        assert_that!(testee.synchronize(true).await?, eq(2));
+
+        assert_that!(subscription.receive().await?, eq(&SubscriptionEvent::Inserted { id: chassis.id, value: Clone::clone(&chassis) }));
+        assert_that!(subscription.receive().await?, eq(&SubscriptionEvent::Removed { id: powertrain.id, value: powertrain }));
+
+        Ok(())
+    }
+}
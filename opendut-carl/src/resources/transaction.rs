@@ -55,8 +55,24 @@ impl<'transaction> ResourcesStorageApi for ResourcesTransaction<'transaction> {
     fn remove<R>(&mut self, id: R::Id) -> PersistenceResult<Option<R>>
     where R: Resource + Persistable {
         match self {
-            ResourcesTransaction::Persistent(transaction) => transaction.remove(id),
-            ResourcesTransaction::Volatile(transaction) => transaction.remove(id),
+            ResourcesTransaction::Persistent(transaction) => {
+                let result = transaction.remove(id.clone());
+                if let Ok(Some(resource)) = &result {
+                    transaction.relayed_subscription_events
+                        .notify(SubscriptionEvent::Removed { id, value: Clone::clone(resource) })
+                        .expect("should successfully queue notification about resource removal during transaction");
+                }
+                result
+            }
+            ResourcesTransaction::Volatile(transaction) => {
+                let result = transaction.remove(id.clone());
+                if let Ok(Some(resource)) = &result {
+                    transaction.relayed_subscription_events
+                        .notify(SubscriptionEvent::Removed { id, value: Clone::clone(resource) })
+                        .expect("should successfully queue notification about resource removal during transaction");
+                }
+                result
+            }
         }
     }
 
@@ -75,4 +91,12 @@ impl<'transaction> ResourcesStorageApi for ResourcesTransaction<'transaction> {
             ResourcesTransaction::Volatile(transaction) => transaction.list(),
         }
     }
+
+    fn list_ids<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable {
+        match &self {
+            ResourcesTransaction::Persistent(transaction) => transaction.list_ids::<R>(),
+            ResourcesTransaction::Volatile(transaction) => transaction.list_ids::<R>(),
+        }
+    }
 }
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use opendut_util::settings::LoadedConfig;
+
+use crate::settings::SECRET_FIELDS;
+
+/// Backend, from which the secret fields of the configuration are retrieved, instead of only from the configuration files.
+/// A secret is looked up under the name of its configuration field, e.g. `persistence.database.password`.
+/// Secrets, which the backend does not provide, keep the value from the configuration files.
+pub enum SecretsBackend {
+    Configuration,
+    Vault(VaultBackend),
+    Kubernetes(KubernetesBackend),
+}
+
+/// Reads the secrets from a key-value secrets engine (version 2) of HashiCorp Vault, with all fields stored in one secret.
+pub struct VaultBackend {
+    url: Url,
+    token: String,
+    mount: String,
+    path: String,
+    client: reqwest::Client,
+}
+
+/// Reads the secrets from a directory, into which a Kubernetes secret is mounted, with one file per field.
+pub struct KubernetesBackend {
+    directory: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("Failed to retrieve secrets from Vault at '{url}':\n  {cause}")]
+    Vault { url: Url, cause: String },
+    #[error("Failed to read secret from file '{path}'")]
+    Kubernetes { path: PathBuf, #[source] source: std::io::Error },
+}
+
+impl SecretsBackend {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let backend = config.get_string("secrets.backend")?;
+
+        match backend.as_str() {
+            "" => Ok(SecretsBackend::Configuration),
+            "vault" => {
+                let url = Url::parse(&config.get_string("secrets.vault.url")?)?;
+                let token = config.get_string("secrets.vault.token")?;
+                let mount = config.get_string("secrets.vault.mount")?;
+                let path = config.get_string("secrets.vault.path")?;
+
+                Ok(SecretsBackend::Vault(VaultBackend { url, token, mount, path, client: reqwest::Client::new() }))
+            }
+            "kubernetes" => {
+                let directory = PathBuf::from(config.get_string("secrets.kubernetes.directory")?);
+                Ok(SecretsBackend::Kubernetes(KubernetesBackend { directory }))
+            }
+            other => Err(anyhow!("Unknown secrets backend '{other}'. Expected one of: '', 'vault', 'kubernetes'.")),
+        }
+    }
+
+    /// Returns the secrets, which the backend provides for the secret fields of the configuration.
+    pub async fn fetch(&self) -> Result<HashMap<&'static str, String>, SecretsError> {
+        match self {
+            SecretsBackend::Configuration => Ok(HashMap::new()),
+            SecretsBackend::Vault(backend) => backend.fetch().await,
+            SecretsBackend::Kubernetes(backend) => backend.fetch().await,
+        }
+    }
+}
+
+impl VaultBackend {
+    async fn fetch(&self) -> Result<HashMap<&'static str, String>, SecretsError> {
+        let error = |cause: String| SecretsError::Vault { url: Clone::clone(&self.url), cause };
+
+        let url = self.url.join(&format!("v1/{}/data/{}", self.mount, self.path))
+            .map_err(|cause| error(cause.to_string()))?;
+
+        let response = self.client.get(url)
+            .header("X-Vault-Token", &self.token)
+            .send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|cause| error(cause.to_string()))?;
+
+        let body = response.json::<serde_json::Value>().await
+            .map_err(|cause| error(cause.to_string()))?;
+
+        let data = body.pointer("/data/data")
+            .and_then(serde_json::Value::as_object)
+            .ok_or_else(|| error(String::from("Response does not contain the data of a key-value secret.")))?;
+
+        let secrets = SECRET_FIELDS.into_iter()
+            .filter_map(|field| {
+                data.get(field)
+                    .and_then(serde_json::Value::as_str)
+                    .map(|value| (field, value.to_owned()))
+            })
+            .collect();
+
+        Ok(secrets)
+    }
+}
+
+impl KubernetesBackend {
+    async fn fetch(&self) -> Result<HashMap<&'static str, String>, SecretsError> {
+        let mut secrets = HashMap::new();
+
+        for field in SECRET_FIELDS {
+            let path = self.directory.join(field);
+
+            match tokio::fs::read_to_string(&path).await {
+                Ok(value) => {
+                    secrets.insert(field, value.trim_end_matches(['\r', '\n']).to_owned());
+                }
+                Err(cause) if cause.kind() == std::io::ErrorKind::NotFound => {
+                    debug!("No secret for '{field}' in '{}'.", self.directory.display());
+                }
+                Err(source) => return Err(SecretsError::Kubernetes { path, source }),
+            }
+        }
+
+        Ok(secrets)
+    }
+}
+
+/// Overrides the secret fields of the configuration with the values from the backend.
+pub async fn resolve(settings: LoadedConfig, backend: &SecretsBackend) -> anyhow::Result<(LoadedConfig, HashMap<&'static str, String>)> {
+    let secrets = backend.fetch().await?;
+
+    if secrets.is_empty() {
+        return Ok((settings, secrets));
+    }
+
+    let mut fields = secrets.keys().copied().collect::<Vec<_>>();
+    fields.sort();
+    info!("Retrieved secrets for the configuration fields: {}", fields.join(", "));
+
+    let mut builder = config::Config::builder()
+        .add_source(settings.config);
+    for (field, value) in &secrets {
+        builder = builder.set_override(*field, Clone::clone(value))?;
+    }
+
+    let settings = LoadedConfig {
+        config: builder.build()?,
+        ..settings
+    };
+    Ok((settings, secrets))
+}
+
+#[derive(Clone)]
+pub struct SecretsRotationOptions {
+    pub check_interval: Option<Duration>,
+}
+impl SecretsRotationOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let check_interval_ms = config.get::<u64>("secrets.rotation.check.interval.ms")?;
+        let check_interval = (check_interval_ms > 0).then(|| Duration::from_millis(check_interval_ms));
+
+        Ok(SecretsRotationOptions { check_interval })
+    }
+}
+
+/// Periodically checks the backend for rotated secrets. As the secrets are only read when CARL starts,
+/// e.g. to connect to the database, a rotation triggers a graceful shutdown, after which the service manager restarts CARL.
+pub fn spawn_rotation_check(
+    backend: SecretsBackend,
+    mut current: HashMap<&'static str, String>,
+    options: SecretsRotationOptions,
+    shutdown_trigger: Arc<Notify>,
+) {
+    let Some(check_interval) = options.check_interval else {
+        debug!("Check for rotated secrets is disabled.");
+        return;
+    };
+    if matches!(backend, SecretsBackend::Configuration) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(check_interval);
+        interval.tick().await; //first tick completes immediately
+
+        loop {
+            interval.tick().await;
+
+            match backend.fetch().await {
+                Ok(secrets) => {
+                    let rotated = rotated_fields(&current, &secrets);
+                    if rotated.is_empty().not() {
+                        info!("Secrets were rotated for the configuration fields: {}. Shutting down gracefully to apply them.", rotated.join(", "));
+                        current = secrets;
+                        shutdown_trigger.notify_one();
+                    }
+                }
+                Err(cause) => warn!("Checking for rotated secrets failed. Retrying in {} ms.\n  {cause}", check_interval.as_millis()),
+            }
+        }
+    });
+}
+
+fn rotated_fields(current: &HashMap<&'static str, String>, fetched: &HashMap<&'static str, String>) -> Vec<&'static str> {
+    let mut rotated = SECRET_FIELDS.into_iter()
+        .filter(|field| fetched.get(field).is_some_and(|value| current.get(field) != Some(value)))
+        .collect::<Vec<_>>();
+    rotated.sort();
+    rotated
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_override_configured_secrets_with_files_of_mounted_kubernetes_secret() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        directory.child("persistence.database.password").write_str("rotated-password\n")?;
+
+        let backend = SecretsBackend::Kubernetes(KubernetesBackend { directory: directory.to_path_buf() });
+        let settings = crate::settings::load_defaults()?;
+
+        let (settings, secrets) = resolve(settings, &backend).await?;
+
+        assert_that!(settings.config.get_string("persistence.database.password")?, eq("rotated-password"));
+        assert_that!(settings.config.get_string("network.oidc.client.secret")?, eq("tbd"));
+        assert_that!(secrets.len(), eq(1));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_report_secrets_with_changed_values_as_rotated() {
+        let current = HashMap::from([
+            ("persistence.database.password", String::from("old")),
+            ("network.oidc.client.secret", String::from("unchanged")),
+        ]);
+        let fetched = HashMap::from([
+            ("persistence.database.password", String::from("new")),
+            ("network.oidc.client.secret", String::from("unchanged")),
+            ("vpn.netbird.auth.secret", String::from("added")),
+        ]);
+
+        assert_that!(rotated_fields(&current, &fetched), eq(&vec!["persistence.database.password", "vpn.netbird.auth.secret"]));
+    }
+}
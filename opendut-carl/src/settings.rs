@@ -1,13 +1,34 @@
 use opendut_util::settings::{LoadedConfig, LoadError};
 
+/// Configuration fields holding secrets, which are hidden when logging the configuration and can be retrieved from a secrets backend.
+pub const SECRET_FIELDS: [&str; 4] = [
+    "vpn.netbird.auth.secret",
+    "network.oidc.client.secret",
+    "persistence.database.password",
+    "disaster.recovery.artifacts.signing.key",
+];
+
 pub fn load_with_overrides(overrides: config::Config) -> Result<LoadedConfig, LoadError> {
-    let carl_config_hide_secrets_override = config::Config::builder()
-        .set_override("vpn.netbird.auth.secret", "redacted")?
-        .set_override("network.oidc.client.secret", "redacted")?
-        .set_override("persistence.database.password", "redacted")?
-        .build()?;
+    let mut carl_config_hide_secrets_override = config::Config::builder()
+        .set_override("secrets.vault.token", "redacted")?;
+    for field in SECRET_FIELDS {
+        carl_config_hide_secrets_override = carl_config_hide_secrets_override.set_override(field, "redacted")?;
+    }
+    let carl_config_hide_secrets_override = carl_config_hide_secrets_override.build()?;
+
+    let loaded = opendut_util::settings::load_config("carl", include_str!("../carl.toml"), config::FileFormat::Toml, Clone::clone(&overrides), Clone::clone(&carl_config_hide_secrets_override))?;
 
-    opendut_util::settings::load_config("carl", include_str!("../carl.toml"), config::FileFormat::Toml, overrides, carl_config_hide_secrets_override)
+    match crate::bootstrap::config_overlay_file(&loaded.config) {
+        None => Ok(loaded),
+        Some(overlay_file) => {
+            //the configuration written by the bootstrap takes precedence over configuration files and environment variables, but not over explicit overrides
+            let overrides = config::Config::builder()
+                .add_source(config::File::from(overlay_file))
+                .add_source(overrides)
+                .build()?;
+            opendut_util::settings::load_config("carl", include_str!("../carl.toml"), config::FileFormat::Toml, overrides, carl_config_hide_secrets_override)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,116 @@
+use std::ops::Not;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum_server::Handle;
+use tokio::signal::unix::SignalKind;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use opendut_carl_api::proto::services::peer_messaging_broker::ReconnectHint;
+
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Waits for SIGINT, SIGTERM or the trigger, e.g. when secrets were rotated, and then shuts CARL down within a bounded time:
+/// - stops accepting new connections,
+/// - asks connected peers to disconnect, handing them a hint when and where to reconnect,
+/// - waits for running transactions and their subscription events to be flushed,
+/// - writes the snapshot of the volatile storage, if one is configured.
+///
+/// Afterwards, the server ends once the remaining connections are closed or the timeout elapsed.
+pub fn spawn_graceful_shutdown(
+    server_handle: Handle,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    resources_manager: ResourcesManagerRef,
+    options: ShutdownOptions,
+    trigger: Arc<Notify>,
+) -> GracefulShutdown {
+    let started = Arc::new(AtomicBool::new(false));
+
+    let task = {
+        let started = Arc::clone(&started);
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = wait_for_shutdown_signal() => {}
+                _ = trigger.notified() => info!("Shutdown was triggered."),
+            }
+            started.store(true, Ordering::SeqCst);
+
+            info!("Shutting down within {} ms...", options.timeout.as_millis());
+            server_handle.graceful_shutdown(Some(options.timeout));
+
+            peer_messaging_broker.disconnect_all(options.reconnect_hint, options.timeout).await;
+
+            resources_manager.flush().await;
+            if let Err(cause) = resources_manager.write_volatile_snapshot().await {
+                warn!("Failed to write snapshot of the volatile storage:\n  {cause}");
+            }
+            info!("Flushed resources. Waiting for remaining connections to close.");
+        })
+    };
+
+    GracefulShutdown { started, task }
+}
+
+pub struct GracefulShutdown {
+    started: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+impl GracefulShutdown {
+    /// Waits for the shutdown to complete, if it was started. Otherwise, e.g. when the server failed, there is nothing to wait for.
+    pub async fn complete(self) {
+        if self.started.load(Ordering::SeqCst).not() {
+            self.task.abort();
+            return;
+        }
+        let _ = self.task.await;
+        info!("Shutdown complete.");
+    }
+}
+
+async fn wait_for_shutdown_signal() {
+    let terminate = async {
+        match tokio::signal::unix::signal(SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(cause) => {
+                warn!("Could not listen for SIGTERM. Only shutting down gracefully on SIGINT.\n  {cause}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => info!("Received SIGINT."),
+        _ = terminate => info!("Received SIGTERM."),
+    }
+}
+
+#[derive(Clone)]
+pub struct ShutdownOptions {
+    pub timeout: Duration,
+    pub reconnect_hint: ReconnectHint,
+}
+impl ShutdownOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let timeout = Duration::from_millis(
+            config.get::<u64>("shutdown.timeout.ms")?
+        );
+        let retry_after_ms = config.get::<u64>("shutdown.reconnect.retry.after.ms")?;
+        let alternate_endpoint = config.get_string("shutdown.reconnect.alternate.endpoint")?;
+        let alternate_endpoint = alternate_endpoint.is_empty().not().then_some(alternate_endpoint);
+
+        Ok(ShutdownOptions {
+            timeout,
+            reconnect_hint: ReconnectHint {
+                retry_after_ms,
+                alternate_endpoint,
+            },
+        })
+    }
+}
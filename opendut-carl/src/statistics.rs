@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::PeerDescriptor;
+use opendut_util::settings::LoadError;
+
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ReadConsistency;
+
+pub type StatisticsRef = Arc<Statistics>;
+
+const WEEK: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// The unix epoch started on a Thursday. Weeks are aligned to start on Mondays instead, the first one being four days later.
+const WEEK_ALIGNMENT: Duration = Duration::from_secs(4 * 24 * 60 * 60);
+
+/// Collects anonymized usage of CARL, which is aggregated into weekly statistics for planning the capacity of a lab.
+/// Identifiers of clusters are only kept while a deployment is running, to measure its runtime, and are never reported.
+pub struct Statistics {
+    options: StatisticsOptions,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    running: HashMap<ClusterId, DeploymentRecord>,
+    deployments: Vec<DeploymentRecord>,
+}
+
+#[derive(Clone, Debug)]
+struct DeploymentRecord {
+    deployed_at: SystemTime,
+    undeployed_at: Option<SystemTime>,
+    peers: usize,
+    devices: usize,
+    executors: usize,
+}
+
+pub struct DeploymentUsage {
+    pub peers: usize,
+    pub devices: usize,
+    pub executors: usize,
+}
+
+impl Statistics {
+    pub fn new(options: StatisticsOptions) -> StatisticsRef {
+        Arc::new(Self { options, state: Mutex::default() })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.options.enabled
+    }
+
+    pub fn record_deployment(&self, cluster_id: ClusterId, usage: DeploymentUsage) {
+        self.record_deployment_at(cluster_id, usage, SystemTime::now());
+    }
+
+    pub fn record_undeployment(&self, cluster_id: ClusterId) {
+        self.record_undeployment_at(cluster_id, SystemTime::now());
+    }
+
+    fn record_deployment_at(&self, cluster_id: ClusterId, usage: DeploymentUsage, now: SystemTime) {
+        if self.options.enabled.not() { return }
+
+        let DeploymentUsage { peers, devices, executors } = usage;
+        let mut state = self.state.lock().unwrap();
+        state.running.insert(cluster_id, DeploymentRecord { deployed_at: now, undeployed_at: None, peers, devices, executors });
+        state.prune(now, self.options.retention);
+        trace!("Recorded deployment of a cluster with {peers} peers, {devices} devices and {executors} executors for usage statistics.");
+    }
+
+    fn record_undeployment_at(&self, cluster_id: ClusterId, now: SystemTime) {
+        if self.options.enabled.not() { return }
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(mut record) = state.running.remove(&cluster_id) {
+            record.undeployed_at = Some(now);
+            state.deployments.push(record);
+        }
+        state.prune(now, self.options.retention);
+    }
+
+    /// Aggregates the recorded usage into weekly statistics, combined with the current utilization of the resources.
+    pub async fn usage(&self, resources_manager: &ResourcesManagerRef) -> Result<UsageStatistics, PersistenceError> {
+        let capacity = Capacity {
+            registered_peers: resources_manager.list_with::<PeerDescriptor>(ReadConsistency::Eventual).await?.len(),
+            connected_peers: resources_manager.list_with::<PeerState>(ReadConsistency::Eventual).await?.into_iter()
+                .filter(|state| matches!(state, PeerState::Up { .. }))
+                .count(),
+            configured_clusters: resources_manager.list_with::<ClusterConfiguration>(ReadConsistency::Eventual).await?.len(),
+            deployed_clusters: resources_manager.list_with::<ClusterDeployment>(ReadConsistency::Eventual).await?.len(),
+        };
+
+        let usage = self.aggregate_at(SystemTime::now(), capacity);
+        debug!("Aggregated usage statistics over {} weeks.", usage.weeks.len());
+        Ok(usage)
+    }
+
+    fn aggregate_at(&self, now: SystemTime, capacity: Capacity) -> UsageStatistics {
+        let state = self.state.lock().unwrap();
+
+        let records = state.deployments.iter()
+            .chain(state.running.values())
+            .collect::<Vec<_>>();
+
+        let mut weeks = Vec::<WeeklyUsage>::new();
+        let mut week_start = week_start(now.checked_sub(self.options.retention).unwrap_or(UNIX_EPOCH));
+        while week_start <= now {
+            let week_end = week_start + WEEK;
+            let deployed = records.iter()
+                .filter(|record| record.deployed_at >= week_start && record.deployed_at < week_end)
+                .collect::<Vec<_>>();
+
+            weeks.push(WeeklyUsage {
+                week_start_unix_millis: unix_millis(week_start),
+                deployments: deployed.len(),
+                average_cluster_peers: average(deployed.iter().map(|record| record.peers)),
+                average_cluster_devices: average(deployed.iter().map(|record| record.devices)),
+            });
+            week_start = week_end;
+        }
+
+        let executor_runtimes = records.iter()
+            .flat_map(|record| {
+                let runtime = record.undeployed_at.unwrap_or(now)
+                    .duration_since(record.deployed_at)
+                    .unwrap_or_default();
+                std::iter::repeat(runtime).take(record.executors)
+            })
+            .collect::<Vec<_>>();
+
+        let executor_runtime = ExecutorRuntime {
+            executors: executor_runtimes.len(),
+            average_seconds: average(executor_runtimes.iter().map(|runtime| runtime.as_secs() as usize)),
+            total_hours: executor_runtimes.iter().map(Duration::as_secs_f64).sum::<f64>() / 3600.0,
+        };
+
+        UsageStatistics { weeks, executor_runtime, capacity }
+    }
+}
+
+impl State {
+    fn prune(&mut self, now: SystemTime, retention: Duration) {
+        let oldest = now.checked_sub(retention).unwrap_or(UNIX_EPOCH);
+        self.deployments.retain(|record| record.undeployed_at.unwrap_or(now) >= oldest);
+    }
+}
+
+fn week_start(time: SystemTime) -> SystemTime {
+    let since_first_monday = time.duration_since(UNIX_EPOCH).unwrap_or_default().saturating_sub(WEEK_ALIGNMENT);
+    let weeks = since_first_monday.as_secs() / WEEK.as_secs();
+    UNIX_EPOCH + WEEK_ALIGNMENT + WEEK * weeks as u32
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+fn average(values: impl Iterator<Item=usize>) -> f64 {
+    let (count, sum) = values.fold((0usize, 0usize), |(count, sum), value| (count + 1, sum + value));
+    if count == 0 { 0.0 } else { sum as f64 / count as f64 }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct UsageStatistics {
+    pub weeks: Vec<WeeklyUsage>,
+    pub executor_runtime: ExecutorRuntime,
+    pub capacity: Capacity,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct WeeklyUsage {
+    pub week_start_unix_millis: u64,
+    pub deployments: usize,
+    pub average_cluster_peers: f64,
+    pub average_cluster_devices: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExecutorRuntime {
+    pub executors: usize,
+    pub average_seconds: f64,
+    pub total_hours: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Capacity {
+    pub registered_peers: usize,
+    pub connected_peers: usize,
+    pub configured_clusters: usize,
+    pub deployed_clusters: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct StatisticsOptions {
+    pub enabled: bool,
+    pub retention: Duration,
+}
+impl StatisticsOptions {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        let enabled = config.get_bool("statistics.enabled")?;
+
+        let retention = {
+            let field = "statistics.retention.weeks";
+            let weeks = config.get_int(field)?;
+            let weeks = u32::try_from(weeks)
+                .map_err(|cause| LoadError::ParseValue { field, value: weeks.to_string(), source: Box::new(cause) })?;
+            WEEK * weeks
+        };
+
+        Ok(Self { enabled, retention })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn testee() -> Statistics {
+        Statistics {
+            options: StatisticsOptions { enabled: true, retention: WEEK * 4 },
+            state: Mutex::default(),
+        }
+    }
+
+    fn capacity() -> Capacity {
+        Capacity { registered_peers: 0, connected_peers: 0, configured_clusters: 0, deployed_clusters: 0 }
+    }
+
+    #[test]
+    fn should_aggregate_deployments_per_week_and_executor_runtimes() -> anyhow::Result<()> {
+        let testee = testee();
+        let now = UNIX_EPOCH + WEEK * 100;
+
+        let finished = ClusterId::random();
+        testee.record_deployment_at(finished, DeploymentUsage { peers: 2, devices: 4, executors: 1 }, now - WEEK);
+        testee.record_undeployment_at(finished, now - WEEK + Duration::from_secs(3600));
+
+        testee.record_deployment_at(ClusterId::random(), DeploymentUsage { peers: 4, devices: 2, executors: 2 }, now - Duration::from_secs(1800));
+
+        let statistics = testee.aggregate_at(now, capacity());
+
+        let deployments = statistics.weeks.iter().map(|week| week.deployments).sum::<usize>();
+        assert_that!(deployments, eq(2));
+        assert_that!(statistics.weeks.last().unwrap().average_cluster_peers, eq(4.0));
+
+        assert_that!(statistics.executor_runtime.executors, eq(3));
+        assert_that!(statistics.executor_runtime.total_hours, eq(2.0));
+        assert_that!(statistics.executor_runtime.average_seconds, eq(2400.0));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_record_when_disabled() -> anyhow::Result<()> {
+        let testee = Statistics {
+            options: StatisticsOptions { enabled: false, retention: WEEK },
+            state: Mutex::default(),
+        };
+        let now = UNIX_EPOCH + WEEK * 100;
+
+        testee.record_deployment_at(ClusterId::random(), DeploymentUsage { peers: 1, devices: 1, executors: 1 }, now);
+
+        let statistics = testee.aggregate_at(now, capacity());
+        assert!(statistics.weeks.iter().all(|week| week.deployments == 0));
+        assert_that!(statistics.executor_runtime.executors, eq(0));
+        Ok(())
+    }
+
+    #[test]
+    fn should_align_weeks_to_mondays() {
+        let monday_1970_01_05 = UNIX_EPOCH + Duration::from_secs(4 * 24 * 60 * 60);
+        assert_eq!(week_start(monday_1970_01_05 + Duration::from_secs(3 * 24 * 60 * 60)), monday_1970_01_05);
+        assert_eq!(week_start(monday_1970_01_05 + WEEK), monday_1970_01_05 + WEEK);
+    }
+}
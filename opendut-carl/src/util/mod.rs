@@ -28,6 +28,15 @@ impl CleoArch {
         static CLEO_ARCH: [CleoArch; 3] = [CleoArch::X86_64, CleoArch::Armhf, CleoArch::Arm64];
         CLEO_ARCH.iter()
     }
+
+    pub fn from_target_triple(target_triple: &str) -> Option<CleoArch> {
+        match target_triple {
+            "x86_64-unknown-linux-gnu" => Some(CleoArch::X86_64),
+            "armv7-unknown-linux-gnueabihf" => Some(CleoArch::Armhf),
+            "aarch64-unknown-linux-gnu" => Some(CleoArch::Arm64),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
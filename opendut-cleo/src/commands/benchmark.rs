@@ -0,0 +1,288 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::peer::executor::ExecutorDescriptors;
+
+use crate::CreateOutputFormat;
+use crate::parse::cluster::ParseableClusterId;
+
+/// Measure the latency and throughput of CARL's API, e.g. to validate the sizing of CARL or to spot regressions after an upgrade
+#[derive(clap::Parser)]
+pub struct BenchmarkCli {
+    ///Number of requests sent at the same time
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
+    ///Number of requests per operation
+    #[arg(long, default_value_t = 100)]
+    requests: usize,
+    ///Operations to measure, all by default. Creating peers is followed by deleting them, so no test resources remain
+    #[arg(long, value_enum, value_delimiter = ',')]
+    operations: Vec<BenchmarkOperation>,
+    ///Cluster configuration to retrieve, defaults to the first one listed
+    #[arg(long)]
+    cluster_id: Option<ParseableClusterId>,
+    ///Table, JSON or prettified JSON as output format
+    #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+    output: CreateOutputFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq)]
+pub enum BenchmarkOperation {
+    ListPeers,
+    GetCluster,
+    CreateDeletePeer,
+}
+
+#[derive(Table, Debug, Serialize)]
+struct BenchmarkTable {
+    #[table(title = "Operation")]
+    operation: &'static str,
+    #[table(title = "Requests")]
+    requests: usize,
+    #[table(title = "Failures")]
+    failures: usize,
+    #[table(title = "Throughput [req/s]")]
+    throughput: String,
+    #[table(title = "p50 [ms]")]
+    p50: String,
+    #[table(title = "p90 [ms]")]
+    p90: String,
+    #[table(title = "p99 [ms]")]
+    p99: String,
+    #[table(title = "Max [ms]")]
+    max: String,
+}
+
+impl BenchmarkCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        if self.concurrency == 0 || self.requests == 0 {
+            Err("Concurrency and number of requests must be at least 1.")?
+        }
+
+        let operations = if self.operations.is_empty() {
+            vec![BenchmarkOperation::ListPeers, BenchmarkOperation::GetCluster, BenchmarkOperation::CreateDeletePeer]
+        } else {
+            Clone::clone(&self.operations)
+        };
+
+        let mut results = vec![];
+        let mut first_failures = vec![];
+        let mut record = |operation: &'static str, measurement: Measurement| {
+            if let Some(first_failure) = measurement.failures.first() {
+                first_failures.push(format!("First failure of '{operation}': {first_failure}"));
+            }
+            results.push(measurement.into_table(operation));
+        };
+
+        for operation in operations {
+            match operation {
+                BenchmarkOperation::ListPeers => {
+                    let measurement = measure(carl, self.concurrency, self.requests, |mut carl, _| async move {
+                        carl.peers.list_peer_descriptors().await
+                            .map(|_| ())
+                            .map_err(|error| error.to_string())
+                    }).await;
+                    record("list peers", measurement);
+                }
+                BenchmarkOperation::GetCluster => {
+                    let Some(cluster_id) = self.cluster_id(carl).await? else {
+                        eprintln!("Skipping 'get cluster', as there is no cluster configuration to retrieve.");
+                        continue;
+                    };
+                    let measurement = measure(carl, self.concurrency, self.requests, move |mut carl, _| async move {
+                        carl.cluster.get_cluster_configuration(cluster_id).await
+                            .map(|_| ())
+                            .map_err(|error| error.to_string())
+                    }).await;
+                    record("get cluster", measurement);
+                }
+                BenchmarkOperation::CreateDeletePeer => {
+                    let peer_ids: Arc<Vec<PeerId>> = Arc::new((0..self.requests).map(|_| PeerId::random()).collect());
+
+                    let creation = {
+                        let peer_ids = Arc::clone(&peer_ids);
+                        measure(carl, self.concurrency, self.requests, move |mut carl, index| {
+                            let peer_id = peer_ids[index];
+                            async move {
+                                carl.peers.store_peer_descriptor(benchmark_peer(peer_id)?).await
+                                    .map(|_| ())
+                                    .map_err(|error| error.to_string())
+                            }
+                        }).await
+                    };
+                    record("create peer", creation);
+
+                    //deletes all peers, also after failed creations, so that no test resources remain, if the creation timed out on our side only
+                    let deletion = {
+                        let peer_ids = Arc::clone(&peer_ids);
+                        measure(carl, self.concurrency, self.requests, move |mut carl, index| {
+                            let peer_id = peer_ids[index];
+                            async move {
+                                carl.peers.delete_peer_descriptor(peer_id).await
+                                    .map(|_| ())
+                                    .map_err(|error| error.to_string())
+                            }
+                        }).await
+                    };
+                    record("delete peer", deletion);
+                }
+            }
+        }
+
+        for first_failure in first_failures {
+            eprintln!("{first_failure}");
+        }
+        match self.output {
+            CreateOutputFormat::Text => {
+                print_stdout(results.with_title())
+                    .expect("Benchmark results should be printable as table.");
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&results).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&results).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+
+    async fn cluster_id(&self, carl: &mut CarlClient) -> crate::Result<Option<ClusterId>> {
+        if let Some(ParseableClusterId(cluster_id)) = &self.cluster_id {
+            return Ok(Some(*cluster_id));
+        }
+        let clusters = carl.cluster.list_cluster_configurations().await
+            .map_err(|error| format!("Could not list cluster configurations.\n  {}", error))?;
+        Ok(clusters.first().map(|cluster| cluster.id))
+    }
+}
+
+fn benchmark_peer(peer_id: PeerId) -> Result<PeerDescriptor, String> {
+    let name = PeerName::try_from(format!("benchmark-{}", peer_id.uuid.simple()))
+        .map_err(|error| error.to_string())?;
+
+    Ok(PeerDescriptor {
+        id: peer_id,
+        name,
+        location: None,
+        network: Default::default(),
+        topology: Default::default(),
+        executors: ExecutorDescriptors { executors: vec![] },
+        labels: Default::default(),
+        annotations: Default::default(),
+        hardware: Default::default(),
+        cluster_hooks: Default::default(),
+    })
+}
+
+struct Measurement {
+    latencies: Vec<Duration>,
+    failures: Vec<String>,
+    elapsed: Duration,
+}
+
+/// Sends the requests from `concurrency` tasks, each sending its next request once the previous one was answered.
+async fn measure<F, Fut>(carl: &CarlClient, concurrency: usize, requests: usize, request: F) -> Measurement
+where
+    F: Fn(CarlClient, usize) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output=Result<(), String>> + Send + 'static,
+{
+    let next_index = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..concurrency.min(requests) {
+        let carl = Clone::clone(carl);
+        let next_index = Arc::clone(&next_index);
+        let request = Clone::clone(&request);
+
+        tasks.spawn(async move {
+            let mut latencies = vec![];
+            let mut failures = vec![];
+            loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                if index >= requests {
+                    break;
+                }
+                let sent = Instant::now();
+                match request(Clone::clone(&carl), index).await {
+                    Ok(()) => latencies.push(sent.elapsed()),
+                    Err(failure) => failures.push(failure),
+                }
+            }
+            (latencies, failures)
+        });
+    }
+
+    let mut measurement = Measurement { latencies: vec![], failures: vec![], elapsed: Duration::ZERO };
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((latencies, failures)) => {
+                measurement.latencies.extend(latencies);
+                measurement.failures.extend(failures);
+            }
+            Err(cause) => measurement.failures.push(format!("Benchmark task failed: {cause}")),
+        }
+    }
+    measurement.elapsed = started.elapsed();
+    measurement.latencies.sort();
+    measurement
+}
+
+impl Measurement {
+    fn into_table(self, operation: &'static str) -> BenchmarkTable {
+        let milliseconds = |latency: Option<Duration>| latency
+            .map(|latency| format!("{:.1}", latency.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| String::from("-"));
+
+        let throughput = self.latencies.len() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON);
+
+        BenchmarkTable {
+            operation,
+            requests: self.latencies.len() + self.failures.len(),
+            failures: self.failures.len(),
+            throughput: format!("{throughput:.1}"),
+            p50: milliseconds(percentile(&self.latencies, 50)),
+            p90: milliseconds(percentile(&self.latencies, 90)),
+            p99: milliseconds(percentile(&self.latencies, 99)),
+            max: milliseconds(self.latencies.last().copied()),
+        }
+    }
+}
+
+/// Nearest-rank percentile of the sorted latencies.
+fn percentile(sorted_latencies: &[Duration], percent: usize) -> Option<Duration> {
+    if sorted_latencies.is_empty() {
+        return None;
+    }
+    let rank = (percent * sorted_latencies.len()).div_ceil(100).max(1);
+    sorted_latencies.get(rank - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_determine_the_nearest_rank_percentile() {
+        let latencies = (1..=10).map(Duration::from_millis).collect::<Vec<_>>();
+
+        assert_that!(percentile(&latencies, 50), some(eq(Duration::from_millis(5))));
+        assert_that!(percentile(&latencies, 90), some(eq(Duration::from_millis(9))));
+        assert_that!(percentile(&latencies, 99), some(eq(Duration::from_millis(10))));
+        assert_that!(percentile(&[Duration::from_millis(7)], 50), some(eq(Duration::from_millis(7))));
+        assert_that!(percentile(&[], 50), none());
+    }
+}
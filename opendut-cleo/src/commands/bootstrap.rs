@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use url::Url;
+
+use opendut_carl_api::carl::bootstrap::{BootstrapStatus, IdentityProviderConfiguration};
+use opendut_carl_api::carl::CarlClient;
+
+/// Perform the initial setup of a new CARL instance
+#[derive(clap::Parser)]
+pub struct BootstrapCli {
+    #[command(subcommand)]
+    command: BootstrapCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum BootstrapCommand {
+    ///Show the progress of the bootstrap
+    Status,
+    ///Create the first admin
+    CreateAdmin {
+        #[command(flatten)]
+        token: BootstrapToken,
+        ///Name of the admin
+        #[arg(long)]
+        name: String,
+        ///Email address of the admin
+        #[arg(long)]
+        email: String,
+    },
+    ///Configure the OIDC identity provider, which CARL and LEA authenticate against
+    ConfigureIdentityProvider {
+        #[command(flatten)]
+        token: BootstrapToken,
+        ///Run CARL without authentication
+        #[arg(long, conflicts_with_all = ["issuer_url", "issuer_remote_url", "issuer_admin_url", "client_id", "client_secret", "lea_client_id"])]
+        disabled: bool,
+        ///Issuer URL, e.g. https://keycloak.internal/realms/opendut/
+        #[arg(long, required_unless_present = "disabled")]
+        issuer_url: Option<Url>,
+        ///Issuer URL which CARL tells the clients to use, if it differs from the issuer URL
+        #[arg(long)]
+        issuer_remote_url: Option<Url>,
+        ///Admin URL of the issuer, e.g. https://keycloak.internal/admin/realms/opendut/
+        #[arg(long, required_unless_present = "disabled")]
+        issuer_admin_url: Option<Url>,
+        ///ID of the client used by CARL
+        #[arg(long, required_unless_present = "disabled")]
+        client_id: Option<String>,
+        ///Secret of the client used by CARL
+        #[arg(long, required_unless_present = "disabled")]
+        client_secret: Option<String>,
+        ///ID of the client used by LEA
+        #[arg(long, default_value = "opendut-lea-client")]
+        lea_client_id: String,
+    },
+    ///Upload the certificate of the CA, which signed the certificate of CARL
+    UploadCa {
+        #[command(flatten)]
+        token: BootstrapToken,
+        ///Path to the PEM-encoded certificate
+        #[arg(long)]
+        file: PathBuf,
+    },
+    ///Verify that CARL can reach the VPN management service
+    VerifyVpn {
+        #[command(flatten)]
+        token: BootstrapToken,
+    },
+    ///Complete the bootstrap, after which CARL needs to be restarted
+    Complete {
+        #[command(flatten)]
+        token: BootstrapToken,
+    },
+}
+
+#[derive(clap::Args)]
+struct BootstrapToken {
+    ///Bootstrap token, otherwise read from the token file
+    #[arg(long)]
+    token: Option<String>,
+    ///File containing the bootstrap token, which CARL generates into its bootstrap directory
+    #[arg(long, default_value = "/var/lib/opendut/carl/bootstrap/token")]
+    token_file: PathBuf,
+}
+
+impl BootstrapToken {
+    fn resolve(self) -> crate::Result<String> {
+        match self.token {
+            Some(token) => Ok(token),
+            None => std::fs::read_to_string(&self.token_file)
+                .map(|token| token.trim().to_owned())
+                .map_err(|cause| format!("Could not read bootstrap token from '{}'. Specify it via --token instead.\n  {cause}", self.token_file.display())),
+        }
+    }
+}
+
+impl BootstrapCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let status = match self.command {
+            BootstrapCommand::Status => {
+                carl.bootstrap.get_status().await
+                    .map_err(|error| format!("Could not get bootstrap status.\n  {error}"))?
+            }
+            BootstrapCommand::CreateAdmin { token, name, email } => {
+                carl.bootstrap.create_admin(token.resolve()?, name, email).await
+                    .map_err(|error| format!("Could not create admin.\n  {error}"))?
+            }
+            BootstrapCommand::ConfigureIdentityProvider { token, disabled, issuer_url, issuer_remote_url, issuer_admin_url, client_id, client_secret, lea_client_id } => {
+                let identity_provider = match (disabled, issuer_url, issuer_admin_url, client_id, client_secret) {
+                    (true, ..) => IdentityProviderConfiguration::Disabled,
+                    (false, Some(issuer_url), Some(issuer_admin_url), Some(client_id), Some(client_secret)) => {
+                        IdentityProviderConfiguration::Oidc { issuer_url, issuer_remote_url, issuer_admin_url, client_id, client_secret, lea_client_id }
+                    }
+                    _ => return Err(String::from("Either specify --disabled or the issuer and client of the identity provider.")),
+                };
+                carl.bootstrap.configure_identity_provider(token.resolve()?, identity_provider).await
+                    .map_err(|error| format!("Could not configure identity provider.\n  {error}"))?
+            }
+            BootstrapCommand::UploadCa { token, file } => {
+                let certificate = std::fs::read_to_string(&file)
+                    .map_err(|cause| format!("Could not read CA from '{}'.\n  {cause}", file.display()))?;
+                carl.bootstrap.upload_ca(token.resolve()?, certificate).await
+                    .map_err(|error| format!("Could not upload CA.\n  {error}"))?
+            }
+            BootstrapCommand::VerifyVpn { token } => {
+                carl.bootstrap.verify_vpn(token.resolve()?).await
+                    .map_err(|error| format!("Could not verify VPN.\n  {error}"))?
+            }
+            BootstrapCommand::Complete { token } => {
+                carl.bootstrap.complete(token.resolve()?).await
+                    .map_err(|error| format!("Could not complete bootstrap.\n  {error}"))?
+            }
+        };
+
+        print_status(&status);
+        Ok(())
+    }
+}
+
+fn print_status(status: &BootstrapStatus) {
+    for (step, state) in status.steps() {
+        println!("  {step:<20}{state}");
+    }
+    if status.completed {
+        println!("Bootstrap completed.");
+    }
+    if status.restart_required {
+        println!("Restart CARL to apply the configuration.");
+    }
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::bundle::{BundleFormat, ResourceBundle};
+
+/// Export all peers, cluster configurations and cluster deployments, e.g. for a backup or to migrate them to another CARL
+#[derive(clap::Parser)]
+pub struct ExportCli {
+    ///File to write the resources to. Prints them to stdout, if not specified
+    #[arg(long, short)]
+    file: Option<PathBuf>,
+    ///Format of the file. Derived from the file extension, if not specified, otherwise defaults to YAML
+    #[arg(value_enum, long)]
+    format: Option<BundleFormat>,
+}
+
+impl ExportCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let peers = carl.peers.list_peer_descriptors().await
+            .map_err(|error| format!("Could not list peers for export.\n  {error}"))?;
+        let cluster_configurations = carl.cluster.list_cluster_configurations().await
+            .map_err(|error| format!("Could not list cluster configurations for export.\n  {error}"))?;
+        let cluster_deployments = carl.cluster.list_cluster_deployments().await
+            .map_err(|error| format!("Could not list cluster deployments for export.\n  {error}"))?;
+
+        let bundle = ResourceBundle::new(peers, cluster_configurations, cluster_deployments);
+        let format = BundleFormat::resolve(self.format, self.file.as_deref());
+        let content = bundle.serialize(format)?;
+
+        match self.file {
+            Some(file) => {
+                std::fs::write(&file, content)
+                    .map_err(|cause| format!("Failed to write resources to '{}': {cause}", file.display()))?;
+                println!(
+                    "Exported {} peers, {} cluster configurations and {} cluster deployments to '{}'.",
+                    bundle.peers.len(),
+                    bundle.cluster_configurations.len(),
+                    bundle.cluster_deployments.len(),
+                    file.display(),
+                );
+            }
+            None => {
+                println!("{}", content.trim_end());
+            }
+        }
+        Ok(())
+    }
+}
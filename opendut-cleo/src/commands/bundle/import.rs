@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::PeerDescriptor;
+
+use crate::commands::bundle::{BundleFormat, ResourceBundle};
+
+/// Import peers, cluster configurations and cluster deployments from a file written by `export`
+#[derive(clap::Parser)]
+pub struct ImportCli {
+    ///File to read the resources from
+    file: PathBuf,
+    ///Format of the file. Derived from the file extension, if not specified, otherwise defaults to YAML
+    #[arg(value_enum, long)]
+    format: Option<BundleFormat>,
+    ///How to handle resources, which already exist with the same ID
+    #[arg(value_enum, long, default_value_t = ConflictStrategy::Skip)]
+    on_conflict: ConflictStrategy,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ConflictStrategy {
+    ///Keep the existing resource
+    Skip,
+    ///Replace the existing resource with the imported one
+    Overwrite,
+    ///Keep the existing resource, but add the devices, network interfaces, executors, labels and peer groups, which only the imported one contains
+    Merge,
+}
+
+impl ImportCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let content = std::fs::read_to_string(&self.file)
+            .map_err(|cause| format!("Failed to open file '{}': {}", self.file.display(), cause))?;
+        let format = BundleFormat::resolve(self.format, Some(&self.file));
+        let bundle = ResourceBundle::deserialize(&content, format)
+            .map_err(|cause| format!("Failed to read resources from '{}':\n  {cause}", self.file.display()))?;
+
+        let existing_peers = carl.peers.list_peer_descriptors().await
+            .map_err(|error| format!("Could not list existing peers.\n  {error}"))?
+            .into_iter()
+            .map(|peer| (peer.id, peer))
+            .collect::<HashMap<_, _>>();
+        let existing_cluster_configurations = carl.cluster.list_cluster_configurations().await
+            .map_err(|error| format!("Could not list existing cluster configurations.\n  {error}"))?
+            .into_iter()
+            .map(|configuration| (configuration.id, configuration))
+            .collect::<HashMap<_, _>>();
+        let existing_cluster_deployments = carl.cluster.list_cluster_deployments().await
+            .map_err(|error| format!("Could not list existing cluster deployments.\n  {error}"))?
+            .into_iter()
+            .map(|deployment| (deployment.id, deployment))
+            .collect::<HashMap<_, _>>();
+
+        println!("Importing resources from '{}':", self.file.display());
+        let mut failures = Vec::new();
+
+        //Peers first, as clusters reference them, and deployments last, as they require their cluster configuration.
+        for peer in bundle.peers {
+            let label = format!("peer '{}' <{}>", peer.name, peer.id);
+            let resolution = resolve(peer.clone(), existing_peers.get(&peer.id), self.on_conflict);
+            let result = match resolution.resource() {
+                Some(peer) => carl.peers.store_peer_descriptor(peer.clone()).await
+                    .map(|_| ())
+                    .map_err(|error| error.to_string()),
+                None => Ok(()),
+            };
+            report(&label, &resolution, result, &mut failures);
+        }
+
+        for configuration in bundle.cluster_configurations {
+            let label = format!("cluster configuration '{}' <{}>", configuration.name, configuration.id);
+            let resolution = resolve(configuration.clone(), existing_cluster_configurations.get(&configuration.id), self.on_conflict);
+            let result = match resolution.resource() {
+                Some(configuration) => carl.cluster.store_cluster_configuration(configuration.clone()).await
+                    .map(|_| ())
+                    .map_err(|error| error.to_string()),
+                None => Ok(()),
+            };
+            report(&label, &resolution, result, &mut failures);
+        }
+
+        for deployment in bundle.cluster_deployments {
+            let label = format!("cluster deployment <{}>", deployment.id);
+            let resolution = resolve(deployment.clone(), existing_cluster_deployments.get(&deployment.id), self.on_conflict);
+            let result = match resolution.resource() {
+                Some(deployment) => carl.cluster.store_cluster_deployment(deployment.clone()).await
+                    .map(|_| ())
+                    .map_err(|error| error.to_string()),
+                None => Ok(()),
+            };
+            report(&label, &resolution, result, &mut failures);
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Failed to import {} resources:\n  {}", failures.len(), failures.join("\n  ")).into())
+        }
+    }
+}
+
+fn report<R>(label: &str, resolution: &Resolution<R>, result: Result<(), String>, failures: &mut Vec<String>) {
+    match result {
+        Ok(()) => {
+            let action = resolution.to_string();
+            println!("  {action:<10} {label}");
+        }
+        Err(cause) => {
+            println!("  {:<10} {label}", "Failed");
+            failures.push(format!("{label}: {cause}"));
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Resolution<R> {
+    Create(R),
+    Overwrite(R),
+    Merge(R),
+    Skip,
+    Unchanged,
+}
+
+impl<R> Resolution<R> {
+    /// The resource to store in CARL, if any.
+    fn resource(&self) -> Option<&R> {
+        match self {
+            Resolution::Create(resource)
+            | Resolution::Overwrite(resource)
+            | Resolution::Merge(resource) => Some(resource),
+            Resolution::Skip
+            | Resolution::Unchanged => None,
+        }
+    }
+}
+
+impl<R> Display for Resolution<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Resolution::Create(_) => "Created",
+            Resolution::Overwrite(_) => "Overwrote",
+            Resolution::Merge(_) => "Merged",
+            Resolution::Skip => "Skipped",
+            Resolution::Unchanged => "Unchanged",
+        };
+        write!(f, "{text}")
+    }
+}
+
+fn resolve<R: Mergeable>(imported: R, existing: Option<&R>, strategy: ConflictStrategy) -> Resolution<R> {
+    match existing {
+        None => Resolution::Create(imported),
+        Some(existing) if *existing == imported => Resolution::Unchanged,
+        Some(existing) => match strategy {
+            ConflictStrategy::Skip => Resolution::Skip,
+            ConflictStrategy::Overwrite => Resolution::Overwrite(imported),
+            ConflictStrategy::Merge => {
+                let merged = R::merge(existing, imported);
+                if merged == *existing {
+                    Resolution::Unchanged
+                } else {
+                    Resolution::Merge(merged)
+                }
+            }
+        }
+    }
+}
+
+/// Combines an existing resource with an imported one of the same ID. The values of the existing resource take precedence.
+trait Mergeable: Clone + PartialEq {
+    fn merge(existing: &Self, imported: Self) -> Self;
+}
+
+impl Mergeable for PeerDescriptor {
+    fn merge(existing: &Self, imported: Self) -> Self {
+        let mut merged = existing.clone();
+
+        for interface in imported.network.interfaces {
+            if !merged.network.interfaces.iter().any(|existing| existing.id == interface.id) {
+                merged.network.interfaces.push(interface);
+            }
+        }
+        for device in imported.topology.devices {
+            if !merged.topology.devices.iter().any(|existing| existing.id == device.id) {
+                merged.topology.devices.push(device);
+            }
+        }
+        for executor in imported.executors.executors {
+            if !merged.executors.executors.iter().any(|existing| existing.id == executor.id) {
+                merged.executors.executors.push(executor);
+            }
+        }
+        for hook in imported.cluster_hooks {
+            if !merged.cluster_hooks.iter().any(|existing| existing.name == hook.name) {
+                merged.cluster_hooks.push(hook);
+            }
+        }
+        for (key, value) in imported.labels {
+            merged.labels.entry(key).or_insert(value);
+        }
+        for (key, value) in imported.annotations {
+            merged.annotations.entry(key).or_insert(value);
+        }
+        merged.location = merged.location.or(imported.location);
+        merged.network.bridge_name = merged.network.bridge_name.or(imported.network.bridge_name);
+        merged.hardware = merged.hardware.or(imported.hardware);
+
+        merged
+    }
+}
+
+impl Mergeable for ClusterConfiguration {
+    fn merge(existing: &Self, imported: Self) -> Self {
+        let mut merged = existing.clone();
+
+        merged.devices.extend(imported.devices);
+        merged.peer_groups.extend(imported.peer_groups);
+        for selector in imported.device_selectors {
+            if !merged.device_selectors.contains(&selector) {
+                merged.device_selectors.push(selector);
+            }
+        }
+        for dependency in imported.dependencies {
+            if !merged.dependencies.contains(&dependency) {
+                merged.dependencies.push(dependency);
+            }
+        }
+
+        merged
+    }
+}
+
+impl Mergeable for ClusterDeployment {
+    fn merge(existing: &Self, _imported: Self) -> Self {
+        existing.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::NetworkInterfaceId;
+
+    use super::*;
+
+    fn peer() -> PeerDescriptor {
+        PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("MyPeer").unwrap(),
+            location: None,
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
+        }
+    }
+
+    fn device(name: &str) -> DeviceDescriptor {
+        DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from(name).unwrap(),
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn should_create_resources_which_do_not_exist() {
+        let imported = peer();
+
+        let resolution = resolve(imported.clone(), None, ConflictStrategy::Skip);
+
+        assert_that!(resolution, eq(Resolution::Create(imported)));
+    }
+
+    #[test]
+    fn should_leave_identical_resources_unchanged() {
+        let existing = peer();
+
+        for strategy in [ConflictStrategy::Skip, ConflictStrategy::Overwrite, ConflictStrategy::Merge] {
+            let resolution = resolve(existing.clone(), Some(&existing), strategy);
+            assert_that!(resolution, eq(Resolution::Unchanged));
+        }
+    }
+
+    #[test]
+    fn should_resolve_conflicts_according_to_strategy() {
+        let existing = peer();
+        let imported = PeerDescriptor {
+            name: PeerName::try_from("RenamedPeer").unwrap(),
+            ..existing.clone()
+        };
+
+        assert_that!(resolve(imported.clone(), Some(&existing), ConflictStrategy::Skip), eq(Resolution::Skip));
+        assert_that!(resolve(imported.clone(), Some(&existing), ConflictStrategy::Overwrite), eq(Resolution::Overwrite(imported.clone())));
+        assert_that!(resolve(imported, Some(&existing), ConflictStrategy::Merge), eq(Resolution::Unchanged));
+    }
+
+    #[test]
+    fn should_merge_devices_of_peers_keeping_existing_values() {
+        let shared_device = device("shared");
+        let existing = PeerDescriptor {
+            topology: Topology { devices: vec![shared_device.clone()] },
+            ..peer()
+        };
+        let added_device = device("added");
+        let imported = PeerDescriptor {
+            name: PeerName::try_from("RenamedPeer").unwrap(),
+            topology: Topology { devices: vec![shared_device.clone(), added_device.clone()] },
+            ..existing.clone()
+        };
+
+        let resolution = resolve(imported, Some(&existing), ConflictStrategy::Merge);
+
+        let expected = PeerDescriptor {
+            topology: Topology { devices: vec![shared_device, added_device] },
+            ..existing
+        };
+        assert_that!(resolution, eq(Resolution::Merge(expected)));
+    }
+
+    #[test]
+    fn should_merge_devices_of_cluster_configurations() {
+        let first_device = DeviceId::random();
+        let second_device = DeviceId::random();
+        let existing = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("MyCluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::from([first_device]),
+            peer_groups: HashSet::new(),
+            device_selectors: vec![],
+            dependencies: vec![],
+        };
+        let imported = ClusterConfiguration {
+            devices: HashSet::from([second_device]),
+            ..existing.clone()
+        };
+
+        let resolution = resolve(imported, Some(&existing), ConflictStrategy::Merge);
+
+        let expected = ClusterConfiguration {
+            devices: HashSet::from([first_device, second_device]),
+            ..existing
+        };
+        assert_that!(resolution, eq(Resolution::Merge(expected)));
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::PeerDescriptor;
+
+pub mod export;
+pub mod import;
+
+/// Version of the bundle format. Incremented on incompatible changes, so that older bundles can be rejected with a clear message.
+const BUNDLE_VERSION: u32 = 1;
+
+/// Peers, cluster configurations and cluster deployments of a CARL instance, as written by `export` and read by `import`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResourceBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub peers: Vec<PeerDescriptor>,
+    #[serde(default)]
+    pub cluster_configurations: Vec<ClusterConfiguration>,
+    #[serde(default)]
+    pub cluster_deployments: Vec<ClusterDeployment>,
+}
+
+impl ResourceBundle {
+    pub fn new(peers: Vec<PeerDescriptor>, cluster_configurations: Vec<ClusterConfiguration>, cluster_deployments: Vec<ClusterDeployment>) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            peers,
+            cluster_configurations,
+            cluster_deployments,
+        }
+    }
+
+    pub fn serialize(&self, format: BundleFormat) -> crate::Result<String> {
+        match format {
+            BundleFormat::Yaml => serde_yaml::to_string(self)
+                .map_err(|cause| format!("Failed to serialize resources as YAML: {cause}").into()),
+            BundleFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|cause| format!("Failed to serialize resources as JSON: {cause}").into()),
+        }
+    }
+
+    pub fn deserialize(content: &str, format: BundleFormat) -> crate::Result<Self> {
+        let bundle: ResourceBundle = match format {
+            BundleFormat::Yaml => serde_yaml::from_str(content)
+                .map_err(|cause| format!("Failed to parse resources from YAML: {cause}"))?,
+            BundleFormat::Json => serde_json::from_str(content)
+                .map_err(|cause| format!("Failed to parse resources from JSON: {cause}"))?,
+        };
+
+        if bundle.version != BUNDLE_VERSION {
+            return Err(format!("Unsupported bundle version {}. This version of CLEO supports version {BUNDLE_VERSION}.", bundle.version).into());
+        }
+        Ok(bundle)
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum BundleFormat {
+    Yaml,
+    Json,
+}
+
+impl BundleFormat {
+    /// Uses the explicitly specified format, otherwise derives it from the file extension. Defaults to YAML.
+    pub fn resolve(format: Option<BundleFormat>, path: Option<&Path>) -> Self {
+        format
+            .or_else(|| path.and_then(Self::from_extension))
+            .unwrap_or(BundleFormat::Yaml)
+    }
+
+    fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "yaml" | "yml" => Some(BundleFormat::Yaml),
+            "json" => Some(BundleFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+
+    use super::*;
+
+    fn bundle() -> ResourceBundle {
+        let peer = PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("MyPeer").unwrap(),
+            location: None,
+            network: Default::default(),
+            topology: Default::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
+        };
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("MyCluster").unwrap(),
+            leader: peer.id,
+            devices: HashSet::new(),
+            peer_groups: HashSet::new(),
+            device_selectors: vec![],
+            dependencies: vec![],
+        };
+        let deployment = ClusterDeployment { id: cluster.id };
+        ResourceBundle::new(vec![peer], vec![cluster], vec![deployment])
+    }
+
+    #[test]
+    fn should_roundtrip_bundle_in_both_formats() -> anyhow::Result<()> {
+        let bundle = bundle();
+
+        for format in [BundleFormat::Yaml, BundleFormat::Json] {
+            let serialized = bundle.serialize(format).map_err(|error| anyhow::anyhow!("{error}"))?;
+            let deserialized = ResourceBundle::deserialize(&serialized, format).map_err(|error| anyhow::anyhow!("{error}"))?;
+            assert_that!(deserialized, eq(&bundle));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unsupported_bundle_version() {
+        let content = r#"{ "version": 99, "peers": [] }"#;
+
+        let result = ResourceBundle::deserialize(content, BundleFormat::Json);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_resolve_format_from_file_extension() {
+        assert_that!(BundleFormat::resolve(None, Some(&PathBuf::from("backup.json"))), eq(BundleFormat::Json));
+        assert_that!(BundleFormat::resolve(None, Some(&PathBuf::from("backup.yml"))), eq(BundleFormat::Yaml));
+        assert_that!(BundleFormat::resolve(Some(BundleFormat::Json), Some(&PathBuf::from("backup.yaml"))), eq(BundleFormat::Json));
+        assert_that!(BundleFormat::resolve(None, None), eq(BundleFormat::Yaml));
+    }
+}
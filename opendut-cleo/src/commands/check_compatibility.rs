@@ -0,0 +1,42 @@
+use std::ops::Not;
+
+use opendut_carl_api::carl::CarlClient;
+
+/// Check that CARL's persisted resources are compatible with its version, e.g. after an upgrade
+#[derive(clap::Parser)]
+pub struct CheckCompatibilityCli {
+    ///Let CARL apply the transforms it offers for resolving incompatibilities
+    #[arg(long)]
+    transform: bool,
+}
+
+impl CheckCompatibilityCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let findings = carl.diagnostics.check_compatibility(self.transform).await
+            .map_err(|error| format!("Could not check compatibility of resources.\n  {error}"))?;
+
+        if findings.is_empty() {
+            println!("No incompatibilities found.");
+            return Ok(());
+        }
+
+        println!("Found {} incompatibilities:", findings.len());
+        for finding in &findings {
+            let status = if finding.transformed {
+                "transformed"
+            } else if finding.transform.is_some() {
+                "transformable"
+            } else {
+                "manual fix required"
+            };
+            println!("  - [{status}] {finding}");
+            if let (Some(transform), false) = (&finding.transform, finding.transformed) {
+                println!("      Transform: {transform}");
+            }
+        }
+        if self.transform.not() && findings.iter().any(|finding| finding.transform.is_some()) {
+            println!("Run with --transform to apply the offered transforms.");
+        }
+        Ok(())
+    }
+}
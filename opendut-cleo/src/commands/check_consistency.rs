@@ -0,0 +1,36 @@
+use std::ops::Not;
+
+use opendut_carl_api::carl::CarlClient;
+
+/// Check CARL's resources for inconsistencies, e.g. after a crash
+#[derive(clap::Parser)]
+pub struct CheckConsistencyCli {
+    ///Let CARL resolve the inconsistencies it knows how to repair
+    #[arg(long)]
+    repair: bool,
+}
+
+impl CheckConsistencyCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let findings = carl.diagnostics.check_consistency(self.repair).await
+            .map_err(|error| format!("Could not check consistency of resources.\n  {error}"))?;
+
+        if findings.is_empty() {
+            println!("No inconsistencies found.");
+            return Ok(());
+        }
+
+        println!("Found {} inconsistencies:", findings.len());
+        for finding in &findings {
+            let status = if finding.repaired {
+                "repaired"
+            } else if self.repair && finding.kind.is_repairable().not() {
+                "not repairable"
+            } else {
+                "not repaired"
+            };
+            println!("  - [{status}] {}", finding.kind);
+        }
+        Ok(())
+    }
+}
@@ -6,10 +6,12 @@ use uuid::Uuid;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
 use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectId;
 use opendut_types::topology::{DeviceDescriptor, DeviceName};
 
 use crate::{ClusterConfigurationDevices, CreateOutputFormat};
-use crate::parse::cluster::{ParseableClusterId, ParseableClusterName};
+use crate::i18n::{tr, trf};
+use crate::parse::cluster::{ParseableClusterId, ParseableClusterName, ParseableDeviceSelector};
 
 /// Create a cluster configuration
 #[derive(clap::Parser)]
@@ -26,6 +28,12 @@ pub struct CreateClusterConfigurationCli {
     ///List of devices in cluster
     #[clap(flatten)]
     devices: ClusterConfigurationDevices,
+    ///ClusterID of a cluster, which has to be deployed and ready before this cluster is deployed
+    #[arg(long = "depends-on")]
+    dependencies: Vec<ParseableClusterId>,
+    ///ProjectID of the project to assign the cluster to, so that only its members can access the cluster
+    #[arg(long)]
+    project: Option<Uuid>,
 }
 
 impl CreateClusterConfigurationCli {
@@ -43,7 +51,7 @@ impl CreateClusterConfigurationCli {
         let cluster_deployments = carl.cluster.list_cluster_deployments().await
             .map_err(|_| String::from("Failed to get list of cluster deployments!"))?;
         if cluster_deployments.into_iter().any(|cluster_deployment| cluster_deployment.id == cluster_id) {
-            Err(format!("Cluster <{}> can not be updated while it is deployed.", cluster_id))?
+            Err(trf("cluster-configuration.store.deployed", &[("cluster_id", &cluster_id)]))?
         };
 
         let leader = PeerId::from(self.leader_id); //TODO: check if peer exists
@@ -65,24 +73,46 @@ impl CreateClusterConfigurationCli {
         if !errors.is_empty() {
             Err(format!("Could not create cluster configuration:\n  {}", errors.join("\n  ")))?
         }
-        if devices.len() < 2 {
-            Err("Specify at least 2 devices per cluster configuration.".to_string())?
+        let device_selectors = self.devices.device_selectors.into_iter()
+            .map(|ParseableDeviceSelector(selector)| selector)
+            .collect::<Vec<_>>();
+        let selected_devices = device_selectors.iter()
+            .map(|selector| selector.count.min() as usize)
+            .sum::<usize>();
+        if devices.len() + selected_devices < 2 {
+            Err("Specify at least 2 devices per cluster configuration, either directly or via device selectors.".to_string())?
         }
 
-        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids };
-        carl.cluster.store_cluster_configuration(configuration.clone()).await
-            .map_err(|err| format!("Could not store cluster configuration. Make sure the application is running. Error: {}", err))?;
+        let dependencies = self.dependencies.into_iter()
+            .map(|ParseableClusterId(id)| id)
+            .collect::<Vec<_>>();
+
+        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids, peer_groups: HashSet::new(), device_selectors: Clone::clone(&device_selectors), dependencies: Clone::clone(&dependencies) };
+        carl.cluster.store_cluster_configuration_in_project(configuration.clone(), self.project.map(ProjectId::from)).await
+            .map_err(|error| crate::Error::carl(tr("cluster-configuration.store.failed"), error))?;
 
         match output {
             CreateOutputFormat::Text => {
-                println!("Successfully stored new cluster configuration.");
+                println!("{}", tr("cluster-configuration.store.success"));
 
-                println!("ClusterID: {}", cluster_id);
-                println!("Name of the Cluster: {}", cluster_name);
-                println!("The following devices are part of the cluster configuration:");
+                println!("{}", trf("cluster-configuration.cluster-id", &[("cluster_id", &cluster_id)]));
+                println!("{}", trf("cluster-configuration.cluster-name", &[("cluster_name", &cluster_name)]));
+                println!("{}", tr("cluster-configuration.devices"));
                 for device_name in device_names.iter() {
                     println!("\x09{}", device_name);
                 };
+                if device_selectors.is_empty().not() {
+                    println!("{}", tr("cluster-configuration.device-selectors"));
+                    for device_selector in device_selectors.iter() {
+                        println!("\x09{}", device_selector);
+                    };
+                }
+                if dependencies.is_empty().not() {
+                    println!("{}", tr("cluster-configuration.dependencies"));
+                    for dependency in dependencies.iter() {
+                        println!("\x09{}", dependency);
+                    };
+                }
             }
             CreateOutputFormat::Json => {
                 let json = serde_json::to_string(&configuration).unwrap();
@@ -98,7 +128,7 @@ impl CreateClusterConfigurationCli {
     }
 }
 
-fn check_devices(all_devices: &[DeviceDescriptor], device_names: &[DeviceName], device_ids: &[String]) -> Vec<Result<DeviceDescriptor, crate::Error>> {
+fn check_devices(all_devices: &[DeviceDescriptor], device_names: &[DeviceName], device_ids: &[String]) -> Vec<Result<DeviceDescriptor, String>> {
     let mut checked_devices_ids = device_ids.iter().map(|device_id| {
         let maybe_device = all_devices.iter().find(|device| device.id.to_string().starts_with(device_id));
         if let Some(device) = maybe_device {
@@ -2,6 +2,8 @@ use uuid::Uuid;
 use opendut_carl_api::carl::{CarlClient};
 use opendut_types::cluster::ClusterId;
 
+use crate::i18n::trf;
+
 /// Delete a cluster configuration
 #[derive(clap::Parser)]
 pub struct DeleteClusterConfigurationCli {
@@ -19,13 +21,13 @@ impl DeleteClusterConfigurationCli {
 
         if cluster_deployments.into_iter()
             .any(|cluster_deployment| cluster_deployment.id == id) {
-            Err(format!("Cluster <{}> can not be deleted while it is deployed.", id))?
+            Err(trf("cluster-configuration.delete.deployed", &[("cluster_id", &id)]))?
         };
         
         let cluster_configuration = carl.cluster.delete_cluster_configuration(id).await
-            .map_err(|error| format!("Failed to delete ClusterConfiguration with id <{id}>.\n  {error}"))?;
+            .map_err(|error| crate::Error::carl(trf("cluster-configuration.delete.failed", &[("cluster_id", &id)]), error))?;
 
-        println!("Deleted ClusterConfiguration {} <{}> successfully.", cluster_configuration.name, cluster_configuration.id);
+        println!("{}", trf("cluster-configuration.delete.success", &[("cluster_name", &cluster_configuration.name), ("cluster_id", &cluster_configuration.id)]));
 
         Ok(())
     }
@@ -25,6 +25,7 @@ struct ClusterTable {
     leader: PeerId,
     peers: Vec<PeerName>,
     devices: Vec<DeviceName>,
+    dependencies: Vec<ClusterId>,
 }
 
 impl DescribeClusterConfigurationCli {
@@ -64,6 +65,7 @@ impl DescribeClusterConfigurationCli {
             leader: cluster_configuration.leader,
             peers: cluster_peers,
             devices: cluster_devices,
+            dependencies: cluster_configuration.dependencies,
         };
 
         let text = match output {
@@ -74,7 +76,8 @@ impl DescribeClusterConfigurationCli {
                   Leader: {}
                   Peers: [{:?}]
                   Devices: [{:?}]
-            "), table.name, table.id, table.leader, table.peers, table.devices)
+                  Dependencies: [{:?}]
+            "), table.name, table.id, table.leader, table.peers, table.devices, table.dependencies)
             }
             DescribeOutputFormat::Json => {
                 serde_json::to_string(&table).unwrap()
@@ -1,13 +1,25 @@
 use cli_table::{print_stdout, Table, WithTitle};
 
 use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::ResourceKind;
 use opendut_types::cluster::{ClusterId, ClusterName};
+use opendut_types::project::ProjectId;
 
+use crate::commands::export::{self, ExportColumnsArgs, ExportFormat};
+use crate::commands::watch::WatchArgs;
 use crate::ListOutputFormat;
 
 /// List all cluster configurations
 #[derive(clap::Parser)]
-pub struct ListClusterConfigurationsCli;
+pub struct ListClusterConfigurationsCli {
+    ///Only list the cluster configurations of the project with this ProjectID
+    #[arg(long)]
+    project: Option<uuid::Uuid>,
+    #[command(flatten)]
+    export: ExportColumnsArgs,
+    #[command(flatten)]
+    watch: WatchArgs,
+}
 
 #[derive(Table)]
 struct ClusterTable {
@@ -19,29 +31,39 @@ struct ClusterTable {
 
 impl ListClusterConfigurationsCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let clusters = carl.cluster.list_cluster_configurations().await
-            .map_err(|error| format!("Could not list any cluster configurations.\n  {error}"))?;
+        let mut watch = self.watch.subscribe(carl, &[ResourceKind::ClusterConfiguration], &output).await?;
 
-        match output {
-            ListOutputFormat::Table => {
-                let cluster_table = clusters.into_iter()
-                    .map(|cluster| {
-                        ClusterTable {
-                            name: cluster.name,
-                            id: cluster.id,
-                        }
-                    })
-                    .collect::<Vec<_>>();
-                print_stdout(cluster_table.with_title())
-                    .expect("List of cluster configurations should be printable as table.");
-            }
-            ListOutputFormat::Json => {
-                let json = serde_json::to_string(&clusters).unwrap();
-                println!("{}", json);
-            }
-            ListOutputFormat::PrettyJson => {
-                let json = serde_json::to_string_pretty(&clusters).unwrap();
-                println!("{}", json);
+        while watch.next_render().await? {
+            let clusters = carl.cluster.list_cluster_configurations_in_project(self.project.map(ProjectId::from)).await
+                .map_err(|error| format!("Could not list any cluster configurations.\n  {error}"))?;
+
+            match output {
+                ListOutputFormat::Table => {
+                    let cluster_table = clusters.into_iter()
+                        .map(|cluster| {
+                            ClusterTable {
+                                name: cluster.name,
+                                id: cluster.id,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    print_stdout(cluster_table.with_title())
+                        .expect("List of cluster configurations should be printable as table.");
+                }
+                ListOutputFormat::Json => {
+                    let json = serde_json::to_string(&clusters).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::PrettyJson => {
+                    let json = serde_json::to_string_pretty(&clusters).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::Csv => {
+                    export::print(&clusters, ExportFormat::Csv, &self.export.columns)?;
+                }
+                ListOutputFormat::Xlsx => {
+                    export::print(&clusters, ExportFormat::Xlsx, &self.export.columns)?;
+                }
             }
         }
         Ok(())
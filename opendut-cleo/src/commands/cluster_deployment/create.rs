@@ -2,6 +2,7 @@ use uuid::Uuid;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterDeployment, ClusterId};
 use crate::CreateOutputFormat;
+use crate::i18n::trf;
 
 /// Create a cluster deployment
 #[derive(clap::Parser)]
@@ -17,10 +18,10 @@ impl CreateClusterDeploymentCli {
 
         let deployment = ClusterDeployment { id };
         carl.cluster.store_cluster_deployment(deployment).await
-            .map_err(|error| format!("Could not create cluster deployment for ClusterID: '{}'.\n  {}", id, error))?;
+            .map_err(|error| crate::Error::carl(trf("cluster-deployment.create.failed", &[("cluster_id", &id)]), error))?;
         match output {
             CreateOutputFormat::Text => {
-                println!("Successfully created cluster deployment for cluster <{}>.", id);
+                println!("{}", trf("cluster-deployment.create.success", &[("cluster_id", &id)]));
             }
             CreateOutputFormat::Json => {
                 let json = serde_json::to_string(&id).unwrap();
@@ -2,6 +2,8 @@ use uuid::Uuid;
 use opendut_carl_api::carl::{CarlClient};
 use opendut_types::cluster::ClusterId;
 
+use crate::i18n::trf;
+
 /// Delete a cluster deployment
 #[derive(clap::Parser)]
 pub struct DeleteClusterDeploymentCli {
@@ -15,9 +17,9 @@ impl DeleteClusterDeploymentCli {
         let id = ClusterId::from(self.id);
         carl.cluster.delete_cluster_deployment(id).await
             .map_err(|error| {
-                format!("Could not delete cluster deployment for ClusterID '{}'.\n  {}", id, error)
+                crate::Error::carl(trf("cluster-deployment.delete.failed", &[("cluster_id", &id)]), error)
             })?;
-        println!("Deleted cluster deployment for ClusterID '{}'.", id);
+        println!("{}", trf("cluster-deployment.delete.success", &[("cluster_id", &id)]));
 
         Ok(())
     }
@@ -1,11 +1,19 @@
 use cli_table::{print_stdout, Table, WithTitle};
 use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::ResourceKind;
 use opendut_types::cluster::{ClusterId};
+use crate::commands::export::{self, ExportColumnsArgs, ExportFormat};
+use crate::commands::watch::WatchArgs;
 use crate::ListOutputFormat;
 
 /// List all cluster deployments
 #[derive(clap::Parser)]
-pub struct ListClusterDeploymentsCli;
+pub struct ListClusterDeploymentsCli {
+    #[command(flatten)]
+    export: ExportColumnsArgs,
+    #[command(flatten)]
+    watch: WatchArgs,
+}
 
 #[derive(Table)]
 struct ClusterTable {
@@ -15,28 +23,38 @@ struct ClusterTable {
 
 impl ListClusterDeploymentsCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let clusters = carl.cluster.list_cluster_deployments().await
-            .map_err(|error| format!("Error while listing cluster deployments: {}", error))?;
+        let mut watch = self.watch.subscribe(carl, &[ResourceKind::ClusterDeployment], &output).await?;
 
-        match output {
-            ListOutputFormat::Table => {
-                let cluster_table = clusters.into_iter()
-                    .map(|cluster_deployment| {
-                        ClusterTable {
-                            id: cluster_deployment.id,
-                        }
-                    })
-                    .collect::<Vec<_>>();
-                print_stdout(cluster_table.with_title())
-                    .expect("List of clusters should be printable as table.");
-            }
-            ListOutputFormat::Json => {
-                let json = serde_json::to_string(&clusters).unwrap();
-                println!("{}", json);
-            }
-            ListOutputFormat::PrettyJson => {
-                let json = serde_json::to_string_pretty(&clusters).unwrap();
-                println!("{}", json);
+        while watch.next_render().await? {
+            let clusters = carl.cluster.list_cluster_deployments().await
+                .map_err(|error| format!("Error while listing cluster deployments: {}", error))?;
+
+            match output {
+                ListOutputFormat::Table => {
+                    let cluster_table = clusters.into_iter()
+                        .map(|cluster_deployment| {
+                            ClusterTable {
+                                id: cluster_deployment.id,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    print_stdout(cluster_table.with_title())
+                        .expect("List of clusters should be printable as table.");
+                }
+                ListOutputFormat::Json => {
+                    let json = serde_json::to_string(&clusters).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::PrettyJson => {
+                    let json = serde_json::to_string_pretty(&clusters).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::Csv => {
+                    export::print(&clusters, ExportFormat::Csv, &self.export.columns)?;
+                }
+                ListOutputFormat::Xlsx => {
+                    export::print(&clusters, ExportFormat::Xlsx, &self.export.columns)?;
+                }
             }
         }
 
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Not;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::PeerCommand;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+
+use crate::CreateOutputFormat;
+
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Show the recent logs of the EDGAR service of all peers of a deployed cluster, including the output of their executors, interleaved by time
+#[derive(clap::Parser)]
+pub struct ClusterDeploymentLogsCli {
+    ///ClusterID
+    #[arg()]
+    id: Uuid,
+    ///Number of most recent lines to show across all peers. Each peer provides its last 1000 lines at most
+    #[arg(long, default_value_t=100)]
+    lines: usize,
+    ///Keep running and print the lines logged by the peers from now on
+    #[arg(long)]
+    follow: bool,
+    ///Seconds to wait for each peer to send its logs
+    #[arg(long, default_value_t=30)]
+    timeout: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct LogLine {
+    timestamp: DateTime<FixedOffset>,
+    peer_id: PeerId,
+    message: String,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableLogLine {
+    timestamp: String,
+    peer_id: String,
+    peer_name: String,
+    message: String,
+}
+
+impl ClusterDeploymentLogsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let cluster_id = ClusterId::from(self.id);
+        let timeout = Duration::from_secs(self.timeout);
+
+        let status = carl.cluster.get_cluster_deployment_status(cluster_id).await
+            .map_err(|error| crate::Error::carl(format!("Could not retrieve the peers of the deployed cluster <{cluster_id}>."), error))?;
+        let peer_ids = status.peers.into_iter()
+            .map(|peer| peer.peer_id)
+            .collect::<Vec<_>>();
+        if peer_ids.is_empty() {
+            Err(format!("Cluster <{cluster_id}> has no deployed peers."))?
+        }
+        let peer_names = peer_names(carl, &peer_ids).await;
+
+        let mut unreachable_peers = HashSet::new();
+        let lines = collect(carl, &peer_ids, timeout, &peer_names, &mut unreachable_peers).await;
+        if unreachable_peers.len() == peer_ids.len() && self.follow.not() {
+            Err(format!("Could not retrieve the logs of any peer of cluster <{cluster_id}>."))?
+        }
+
+        let skipped = lines.len().saturating_sub(self.lines);
+        print_lines(&lines[skipped..], &peer_names, &output);
+
+        // Lines of each peer up to this timestamp were already collected, so only newer lines are printed when following.
+        let mut latest = HashMap::<PeerId, DateTime<FixedOffset>>::new();
+        remember_latest(&lines, &mut latest);
+
+        while self.follow {
+            tokio::time::sleep(FOLLOW_INTERVAL).await;
+
+            let lines = collect(carl, &peer_ids, timeout, &peer_names, &mut unreachable_peers).await.into_iter()
+                .filter(|line| latest.get(&line.peer_id).map_or(true, |latest| line.timestamp > *latest))
+                .collect::<Vec<_>>();
+            print_lines(&lines, &peer_names, &output);
+            remember_latest(&lines, &mut latest);
+        }
+        Ok(())
+    }
+}
+
+/// Names of the peers, to prefix their lines with. Falls back to the ID, if the name cannot be retrieved.
+async fn peer_names(carl: &mut CarlClient, peer_ids: &[PeerId]) -> HashMap<PeerId, String> {
+    let mut names = HashMap::new();
+    for peer_id in peer_ids {
+        let name = match carl.peers.get_peer_descriptor(*peer_id).await {
+            Ok(descriptor) => descriptor.name.to_string(),
+            Err(_) => peer_id.to_string(),
+        };
+        names.insert(*peer_id, name);
+    }
+    names
+}
+
+/// Requests the logs from all peers at once and interleaves their lines by time.
+/// Peers, which cannot be reached, are reported on stderr once, until they can be reached again.
+async fn collect(carl: &CarlClient, peer_ids: &[PeerId], timeout: Duration, peer_names: &HashMap<PeerId, String>, unreachable_peers: &mut HashSet<PeerId>) -> Vec<LogLine> {
+    let mut tasks = tokio::task::JoinSet::new();
+    for peer_id in peer_ids {
+        let peer_id = *peer_id;
+        let mut carl = Clone::clone(carl);
+        tasks.spawn(async move {
+            let result = carl.peers.send_peer_command(peer_id, PeerCommand::CollectLogs, timeout).await;
+            (peer_id, result)
+        });
+    }
+
+    let mut lines = vec![];
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((peer_id, result)) = joined else { continue };
+        match result {
+            Ok(result) => {
+                unreachable_peers.remove(&peer_id);
+                lines.extend(parse_journal(peer_id, &result.output));
+            }
+            Err(error) => {
+                if unreachable_peers.insert(peer_id) {
+                    eprintln!("Could not retrieve the logs of peer '{}' <{peer_id}>:\n  {error}", peer_names[&peer_id]);
+                }
+            }
+        }
+    }
+    lines.sort_by_key(|line| line.timestamp);
+    lines
+}
+
+/// Parses the lines of the journal in the format 'short-iso-precise', e.g. `2024-05-02T10:15:30.123456+02:00 host opendut-edgar[1234]: message`.
+/// Lines without a timestamp, like the continuation of a multi-line message, are assigned the timestamp of the line before.
+fn parse_journal(peer_id: PeerId, journal: &str) -> Vec<LogLine> {
+    let mut lines = vec![];
+    let mut previous_timestamp = None;
+
+    for line in journal.lines() {
+        let (timestamp, message) = match line.split_once(' ').and_then(|(timestamp, rest)| Some((parse_timestamp(timestamp)?, rest))) {
+            Some((timestamp, rest)) => {
+                let message = rest.split_once("]: ").map(|(_, message)| message).unwrap_or(rest);
+                (timestamp, message)
+            }
+            None => match previous_timestamp {
+                Some(timestamp) => (timestamp, line),
+                None => continue,
+            }
+        };
+        previous_timestamp = Some(timestamp);
+        lines.push(LogLine { timestamp, peer_id, message: message.to_owned() });
+    }
+    lines
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+}
+
+fn remember_latest(lines: &[LogLine], latest: &mut HashMap<PeerId, DateTime<FixedOffset>>) {
+    for line in lines {
+        let timestamp = latest.entry(line.peer_id).or_insert(line.timestamp);
+        *timestamp = line.timestamp.max(*timestamp);
+    }
+}
+
+fn print_lines(lines: &[LogLine], peer_names: &HashMap<PeerId, String>, output: &CreateOutputFormat) {
+    let name_width = peer_names.values().map(|name| name.len()).max().unwrap_or_default();
+
+    for line in lines {
+        let peer_name = &peer_names[&line.peer_id];
+        match output {
+            CreateOutputFormat::Text => {
+                println!("{} {peer_name:>name_width$} | {}", line.timestamp.format("%Y-%m-%dT%H:%M:%S%.3f%:z"), line.message);
+            }
+            CreateOutputFormat::Json | CreateOutputFormat::PrettyJson => {
+                let serializable = SerializableLogLine {
+                    timestamp: line.timestamp.to_rfc3339(),
+                    peer_id: line.peer_id.to_string(),
+                    peer_name: peer_name.clone(),
+                    message: line.message.clone(),
+                };
+                let json = if let CreateOutputFormat::PrettyJson = output {
+                    serde_json::to_string_pretty(&serializable).unwrap()
+                } else {
+                    serde_json::to_string(&serializable).unwrap()
+                };
+                println!("{}", json);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_journal_lines_and_continue_multi_line_messages() {
+        let peer_id = PeerId::random();
+        let journal = "\
+            2024-05-02T10:15:30.123456+02:00 host opendut-edgar[1234]: Applying configuration\n\
+            \x20   with 2 executors\n\
+            2024-05-02T08:15:31.000001+0000 host opendut-edgar[1234]: Received line: \"test started\"\n";
+
+        let lines = parse_journal(peer_id, journal);
+
+        assert_that!(lines.iter().map(|line| line.message.as_str()).collect::<Vec<_>>(), elements_are![
+            eq("Applying configuration"),
+            eq("    with 2 executors"),
+            eq("Received line: \"test started\""),
+        ]);
+        assert_that!(lines[1].timestamp, eq(lines[0].timestamp));
+        assert_that!((lines[2].timestamp - lines[0].timestamp).num_microseconds(), some(eq(876545)));
+    }
+}
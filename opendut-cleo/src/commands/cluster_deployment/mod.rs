@@ -1,3 +1,5 @@
 pub mod create;
 pub mod list;
 pub mod delete;
+pub mod status;
+pub mod logs;
@@ -0,0 +1,129 @@
+use std::ops::Not;
+use std::time::{Duration, Instant};
+
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::{ClusterDeploymentStatus, ClusterId, DeploymentState};
+use opendut_types::ShortName;
+
+use crate::CreateOutputFormat;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Show whether the peers of a deployed cluster applied their configuration, e.g. their bridges and GRE tunnels
+#[derive(clap::Parser)]
+pub struct ClusterDeploymentStatusCli {
+    ///ClusterID
+    #[arg()]
+    id: Uuid,
+    ///Wait until all peers are ready or one of them failed. Fails, if the rollout failed or did not complete in time
+    #[arg(long)]
+    wait: bool,
+    ///Seconds to wait for the rollout to complete
+    #[arg(long, default_value_t=120)]
+    timeout: u64,
+}
+
+#[derive(Table)]
+struct PeerDeploymentTable {
+    #[table(title = "PeerID")]
+    peer_id: String,
+    #[table(title = "State")]
+    state: &'static str,
+    #[table(title = "Cause")]
+    cause: String,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableClusterDeploymentStatus {
+    cluster_id: String,
+    state: String,
+    peers: Vec<SerializablePeerDeploymentStatus>,
+}
+
+#[derive(serde::Serialize)]
+struct SerializablePeerDeploymentStatus {
+    peer_id: String,
+    state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cause: Option<String>,
+}
+
+impl ClusterDeploymentStatusCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let cluster_id = ClusterId::from(self.id);
+
+        let started = Instant::now();
+        let status = loop {
+            let status = carl.cluster.get_cluster_deployment_status(cluster_id).await
+                .map_err(|error| crate::Error::carl(format!("Could not retrieve the deployment status of cluster <{cluster_id}>."), error))?;
+
+            let completed = matches!(status.state(), DeploymentState::Ready | DeploymentState::Failed { .. });
+            if self.wait.not() || completed || started.elapsed() >= Duration::from_secs(self.timeout) {
+                break status;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        let state = status.state();
+        print_status(status, output);
+
+        if self.wait {
+            match state {
+                DeploymentState::Ready => {}
+                DeploymentState::Failed { cause } => Err(format!("Deployment of cluster <{cluster_id}> failed: {cause}"))?,
+                DeploymentState::Pending | DeploymentState::Configuring => {
+                    Err(format!("Deployment of cluster <{cluster_id}> did not complete within {} seconds.", self.timeout))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_status(status: ClusterDeploymentStatus, output: CreateOutputFormat) {
+    let cluster_id = status.id;
+    let state = status.state();
+
+    match output {
+        CreateOutputFormat::Text => {
+            println!("Deployment of cluster <{cluster_id}>: {state}");
+            let table = status.peers.into_iter()
+                .map(|peer| PeerDeploymentTable {
+                    peer_id: peer.peer_id.to_string(),
+                    state: peer.state.short_name(),
+                    cause: match peer.state {
+                        DeploymentState::Failed { cause } => cause,
+                        _ => String::new(),
+                    },
+                })
+                .collect::<Vec<_>>();
+            print_stdout(table.with_title())
+                .expect("Deployment status should be printable as table.");
+        }
+        CreateOutputFormat::Json | CreateOutputFormat::PrettyJson => {
+            let serializable = SerializableClusterDeploymentStatus {
+                cluster_id: cluster_id.to_string(),
+                state: state.short_name().to_owned(),
+                peers: status.peers.into_iter()
+                    .map(|peer| SerializablePeerDeploymentStatus {
+                        peer_id: peer.peer_id.to_string(),
+                        state: peer.state.short_name().to_owned(),
+                        cause: match peer.state {
+                            DeploymentState::Failed { cause } => Some(cause),
+                            _ => None,
+                        },
+                    })
+                    .collect(),
+            };
+            let json = if let CreateOutputFormat::PrettyJson = output {
+                serde_json::to_string_pretty(&serializable).unwrap()
+            } else {
+                serde_json::to_string(&serializable).unwrap()
+            };
+            println!("{}", json);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::hook::{ClusterHook, ClusterHookFailurePolicy, ClusterHookName, ClusterHookStage};
+use opendut_types::peer::PeerId;
+
+use crate::{CreateOutputFormat, DescribeOutputFormat};
+
+/// Create a hook script, which EDGAR runs on the peer right before joining or right after leaving the network of a cluster
+#[derive(clap::Parser)]
+pub struct CreateClusterHookCli {
+    ///ID of the peer to add the hook to
+    #[arg(long)]
+    peer_id: Uuid,
+    ///Name of the hook, unique per peer
+    #[arg(short, long)]
+    name: ClusterHookName,
+    ///When to run the hook
+    #[arg(long)]
+    stage: ClusterHookStageVariants,
+    ///Path to the script, which is transferred to the peer and run with '/bin/sh'
+    #[arg(long)]
+    script: PathBuf,
+    ///Seconds after which the hook is terminated and considered failed
+    #[arg(long, default_value_t=ClusterHook::DEFAULT_TIMEOUT.as_secs())]
+    timeout: u64,
+    ///What to do, when the hook fails: 'abort' skips the remaining hooks and does not join the cluster, 'ignore' continues as if the hook succeeded
+    #[arg(long, default_value="abort")]
+    on_failure: ClusterHookFailurePolicyVariants,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+pub enum ClusterHookStageVariants {
+    BeforeJoin,
+    AfterLeave,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+pub enum ClusterHookFailurePolicyVariants {
+    Abort,
+    Ignore,
+}
+
+impl CreateClusterHookCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+
+        let script = std::fs::read_to_string(&self.script)
+            .map_err(|cause| format!("Failed to open file '{}': {}", self.script.display(), cause))?;
+
+        let cluster_hook = ClusterHook {
+            name: self.name,
+            stage: match self.stage {
+                ClusterHookStageVariants::BeforeJoin => ClusterHookStage::BeforeJoin,
+                ClusterHookStageVariants::AfterLeave => ClusterHookStage::AfterLeave,
+            },
+            script,
+            timeout: Duration::from_secs(self.timeout),
+            failure_policy: match self.on_failure {
+                ClusterHookFailurePolicyVariants::Abort => ClusterHookFailurePolicy::Abort,
+                ClusterHookFailurePolicyVariants::Ignore => ClusterHookFailurePolicy::Ignore,
+            },
+        };
+
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
+
+        if peer_descriptor.cluster_hooks.iter().any(|hook| hook.name == cluster_hook.name) {
+            Err(format!("Peer <{peer_id}> already has a cluster hook named '{}'. Delete it first to replace it.", cluster_hook.name))?
+        }
+        peer_descriptor.cluster_hooks.push(cluster_hook);
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| crate::Error::carl(format!("Failed to update peer <{}>.", peer_id), error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+}
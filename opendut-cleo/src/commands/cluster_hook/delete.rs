@@ -0,0 +1,41 @@
+use std::ops::Not;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::hook::ClusterHookName;
+use opendut_types::peer::PeerId;
+
+/// Delete hook scripts, which EDGAR runs when joining or leaving the network of a cluster
+#[derive(clap::Parser)]
+pub struct DeleteClusterHookCli {
+    ///ID of the peer to delete the hooks from
+    #[arg()]
+    peer_id: Uuid,
+    ///Names of the hooks to delete
+    #[arg(short, long, num_args = 1.., required = true)]
+    names: Vec<ClusterHookName>,
+}
+
+impl DeleteClusterHookCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PeerId::from(self.peer_id);
+
+        let mut peer = carl.peers
+            .get_peer_descriptor(id)
+            .await
+            .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", id, error))?;
+
+        for name in &self.names {
+            if peer.cluster_hooks.iter().all(|hook| &hook.name != name) {
+                Err(format!("Peer <{id}> has no cluster hook named '{name}'."))?
+            }
+        }
+        peer.cluster_hooks.retain(|hook| self.names.contains(&hook.name).not());
+
+        carl.peers.store_peer_descriptor(peer).await
+            .map_err(|error| crate::Error::carl("Failed to delete cluster hooks of peer.", error))?;
+
+        Ok(())
+    }
+}
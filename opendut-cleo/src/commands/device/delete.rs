@@ -46,7 +46,7 @@ impl DeleteDeviceCli {
         peer.topology.devices.retain(|device| device.id != device_to_delete);
 
         carl.peers.store_peer_descriptor(Clone::clone(peer)).await
-            .map_err(|error| format!("Failed to delete peer.\n  {}", error))?;
+            .map_err(|error| crate::Error::carl("Failed to delete peer.", error))?;
 
         Ok(())
     }
@@ -1,6 +1,7 @@
 use opendut_carl_api::carl::CarlClient;
 
-use crate::commands::device::{DeviceTable, render_devices};
+use crate::commands::device::{DeviceTable, print_devices};
+use crate::commands::export::ExportColumnsArgs;
 use crate::ListOutputFormat;
 
 /// Find a device
@@ -9,6 +10,8 @@ pub struct FindDeviceCli {
     ///Criteria for search
     #[arg(required = true, value_delimiter = ' ', num_args = 1..)]
     criteria: Vec<String>,
+    #[command(flatten)]
+    export: ExportColumnsArgs,
 }
 
 impl FindDeviceCli {
@@ -30,8 +33,6 @@ impl FindDeviceCli {
                 .map(DeviceTable::from)
                 .collect::<Vec<_>>()
         };
-        let text = render_devices(devices, output);
-        println!("{text}");
-        Ok(())
+        print_devices(devices, output, &self.export.columns)
     }
 }
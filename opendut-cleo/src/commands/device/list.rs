@@ -1,10 +1,14 @@
 use opendut_carl_api::carl::CarlClient;
-use crate::commands::device::{DeviceTable, render_devices};
+use crate::commands::device::{DeviceTable, print_devices};
+use crate::commands::export::ExportColumnsArgs;
 use crate::ListOutputFormat;
 
 /// List all devices
 #[derive(clap::Parser)]
-pub struct ListDevicesCli;
+pub struct ListDevicesCli {
+    #[command(flatten)]
+    export: ExportColumnsArgs,
+}
 
 impl ListDevicesCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
@@ -14,8 +18,6 @@ impl ListDevicesCli {
             .map(DeviceTable::from)
             .collect::<Vec<_>>();
 
-        let text = render_devices(devices, output);
-        println!("{text}");
-        Ok(())
+        print_devices(devices, output, &self.export.columns)
     }
 }
\ No newline at end of file
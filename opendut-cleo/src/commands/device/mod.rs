@@ -9,6 +9,7 @@ use serde::Serialize;
 
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName};
 
+use crate::commands::export::{self, ExportFormat};
 use crate::ListOutputFormat;
 
 #[derive(Table, Serialize)]
@@ -23,7 +24,7 @@ struct DeviceTable {
     tags: String,
 }
 
-fn render_devices(devices: Vec<DeviceTable>, output: ListOutputFormat) -> String {
+fn print_devices(devices: Vec<DeviceTable>, output: ListOutputFormat, columns: &[String]) -> crate::Result<()> {
     match output {
         ListOutputFormat::Table => {
             let table = devices
@@ -31,15 +32,22 @@ fn render_devices(devices: Vec<DeviceTable>, output: ListOutputFormat) -> String
                 .table()
                 .display()
                 .unwrap();
-            format!("{table}")
+            println!("{table}");
         }
         ListOutputFormat::Json => {
-            serde_json::to_string(&devices).unwrap()
+            println!("{}", serde_json::to_string(&devices).unwrap());
         }
         ListOutputFormat::PrettyJson => {
-            serde_json::to_string_pretty(&devices).unwrap()
+            println!("{}", serde_json::to_string_pretty(&devices).unwrap());
+        }
+        ListOutputFormat::Csv => {
+            export::print(&devices, ExportFormat::Csv, columns)?;
+        }
+        ListOutputFormat::Xlsx => {
+            export::print(&devices, ExportFormat::Xlsx, columns)?;
         }
     }
+    Ok(())
 }
 
 impl From<DeviceDescriptor> for DeviceTable {
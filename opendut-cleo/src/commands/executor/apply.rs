@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId, ExecutorKind, ResultsUrl};
+use opendut_types::peer::executor::sandbox::ExecutorSandbox;
 use serde::{Deserialize, Serialize};
 
 use opendut_carl_api::carl::CarlClient;
@@ -22,6 +23,8 @@ struct ExecutorConfiguration {
     #[serde(flatten)]
     pub kind: ExecutorKind,
     pub results_url: Option<ResultsUrl>,
+    #[serde(default)]
+    pub sandbox: ExecutorSandbox,
 }
 
 impl ApplyContainerExecutorCli {
@@ -33,11 +36,12 @@ impl ApplyContainerExecutorCli {
         let executor_configuration: ExecutorConfiguration = serde_json::from_str(&config_str)
             .map_err(|cause| format!("Failed to parse '{}' as executor configuration: {}", self.config_file.display(), cause))?;
 
-        let ExecutorConfiguration { peer_id, kind, results_url } = executor_configuration;
+        let ExecutorConfiguration { peer_id, kind, results_url, sandbox } = executor_configuration;
         let executor_descriptor = ExecutorDescriptor {
             id: ExecutorId::random(), //Currently, we create a new ExecutorDescriptor every time. It might be better to put the IDs into the configuration, to keep them stable.
             kind,
             results_url,
+            sandbox,
         };
 
         let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
@@ -46,7 +50,7 @@ impl ApplyContainerExecutorCli {
         peer_descriptor.executors.executors.push(executor_descriptor);
 
         carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
-            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+            .map_err(|error| crate::Error::carl(format!("Failed to update peer <{}>.", peer_id), error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
 
@@ -1,9 +1,10 @@
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId};
+use opendut_types::peer::executor::sandbox::{ExecutorSandbox, ExecutorUser, SeccompProfile};
 use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ResultsUrl};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, ContainerVolume, Engine}, ExecutorKind, ResultsUrl};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, EngineVariants};
 
@@ -40,6 +41,24 @@ pub struct CreateContainerExecutorCli {
     ///Container arguments
     #[arg(short, long, num_args = 1..)]
     args: Option<Vec<ContainerCommandArgument>>,
+    ///Names of secrets, which are provided by CARL and mounted as files into the container
+    #[arg(long, num_args = 1..)]
+    secrets: Option<Vec<ContainerSecret>>,
+    ///CAN interfaces provided to the container via a socket of EDGAR, e.g. 'can0:raw:0x100,0x200-0x2FF' or 'can0:isotp:0x7E0:0x7E8'
+    #[arg(long, num_args = 1..)]
+    can_gateways: Option<Vec<ContainerCanGateway>>,
+    ///How the container is connected to the cluster network: 'host', 'macvlan' or 'sriov:<physical-function>' (default: host)
+    #[arg(long)]
+    network_attachment: Option<ContainerNetworkAttachment>,
+    ///Unix user and optionally group, which the container runs as, e.g. 'tester' or '1000:1000' (default: user of the image)
+    #[arg(long)]
+    user: Option<ExecutorUser>,
+    ///Seccomp profile of the container: 'default', 'unconfined' or an absolute path to a profile on the peer (default: default)
+    #[arg(long)]
+    seccomp_profile: Option<SeccompProfile>,
+    ///Mount the root filesystem of the container read-only
+    #[arg(long)]
+    read_only_filesystem: bool,
     ///URL to which results will be uploaded
     #[arg(short, long)]
     results_url: Option<ResultsUrl>,
@@ -60,6 +79,8 @@ impl CreateContainerExecutorCli {
         let devices = self.devices.unwrap_or_default();
         let ports = self.ports.unwrap_or_default();
         let args = self.args.unwrap_or_default();
+        let secrets = self.secrets.unwrap_or_default();
+        let can_gateways = self.can_gateways.unwrap_or_default();
 
         let mut environment_variables = vec![];
 
@@ -83,8 +104,16 @@ impl CreateContainerExecutorCli {
                 ports,
                 command: self.command.unwrap_or_default(),
                 args,
+                secrets,
+                can_gateways,
+                network_attachment: self.network_attachment.unwrap_or_default(),
             },
             results_url: self.results_url,
+            sandbox: ExecutorSandbox {
+                user: self.user,
+                seccomp_profile: self.seccomp_profile.unwrap_or_default(),
+                read_only_filesystem: self.read_only_filesystem,
+            },
         };
 
         let peer_id = PeerId::from(self.peer_id);
@@ -96,7 +125,7 @@ impl CreateContainerExecutorCli {
         peer_descriptor.executors.executors.push(executor_descriptor);
 
         carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
-            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+            .map_err(|error| crate::Error::carl(format!("Failed to update peer <{}>.", peer_id), error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
 
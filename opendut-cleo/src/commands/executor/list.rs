@@ -5,8 +5,9 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId};
-use opendut_types::peer::executor::{container::{ContainerImage, Engine}, ExecutorKind};
+use opendut_types::peer::executor::{container::{ContainerImage, Engine}, sandbox::ExecutorSandbox, ExecutorKind};
 
+use crate::commands::export::{self, ExportFormat};
 use crate::{ListOutputFormat};
 
 /// List all container executors for one peer
@@ -39,6 +40,12 @@ impl ListContainerExecutorCli {
                 let json = serde_json::to_string_pretty(&executor_table).unwrap();
                 println!("{}", json);
             }
+            ListOutputFormat::Csv => {
+                export::print(&executor_table, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                export::print(&executor_table, ExportFormat::Xlsx, &[])?;
+            }
         }
         Ok(())
     }
@@ -49,7 +56,7 @@ fn filter_connected_peers(
 ) -> Vec<ContainerExecutorTable> {
     let mut executor_table = vec![];
     for executor in &peer.executors.executors {
-        let ExecutorDescriptor { id: _, kind, results_url } = executor;
+        let ExecutorDescriptor { id: _, kind, results_url, sandbox } = executor;
         
         if let ExecutorKind::Container {
             engine,
@@ -61,11 +68,16 @@ fn filter_connected_peers(
             ports,
             command,
             args,
+            secrets,
+            can_gateways,
+            network_attachment,
         } = kind {
             let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
             let volumes = volumes.iter().map(|volume| volume.to_string()).collect::<Vec<_>>();
             let devices = devices.iter().map(|device| device.to_string()).collect::<Vec<_>>();
             let ports = ports.iter().map(|port| port.to_string()).collect::<Vec<_>>();
+            let secrets = secrets.iter().map(|secret| secret.to_string()).collect::<Vec<_>>();
+            let can_gateways = can_gateways.iter().map(|can_gateway| can_gateway.to_string()).collect::<Vec<_>>();
             let envs = envs.iter().map(|env|
                 format!("{}={}", env.name(), env.value())).collect::<Vec<_>>();
             executor_table.push(ContainerExecutorTable {
@@ -78,13 +90,32 @@ fn filter_connected_peers(
                 ports: ports.join(", "),
                 command: command.into(),
                 args: args.join(", "),
+                secrets: secrets.join(", "),
+                can_gateways: can_gateways.join(", "),
+                network_attachment: network_attachment.to_string(),
                 results_url: results_url.clone().map_or("None".to_string(), |results_url| results_url.into()),
+                sandbox: format_sandbox(sandbox),
             });
         }
     };
     executor_table
 }
 
+fn format_sandbox(sandbox: &ExecutorSandbox) -> String {
+    if sandbox.is_unrestricted() {
+        return String::from("None");
+    }
+    let mut restrictions = vec![];
+    if let Some(user) = &sandbox.user {
+        restrictions.push(format!("user={user}"));
+    }
+    restrictions.push(format!("seccomp={}", sandbox.seccomp_profile));
+    if sandbox.read_only_filesystem {
+        restrictions.push(String::from("read-only"));
+    }
+    restrictions.join(", ")
+}
+
 #[derive(Table, Debug, Serialize)]
 struct ContainerExecutorTable {
     #[table(title = "Engine")]
@@ -105,6 +136,14 @@ struct ContainerExecutorTable {
     command: String,
     #[table(title = "Args")]
     args: String,
+    #[table(title = "Secrets")]
+    secrets: String,
+    #[table(title = "CAN Gateways")]
+    can_gateways: String,
+    #[table(title = "Network Attachment")]
+    network_attachment: String,
     #[table(title = "Results URL")]
     results_url: String,
+    #[table(title = "Sandbox")]
+    sandbox: String,
 }
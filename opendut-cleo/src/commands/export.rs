@@ -0,0 +1,204 @@
+use std::io::Write;
+use std::ops::Not;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Spreadsheet formats for exporting the output of list commands, e.g. for inventory extracts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+#[derive(clap::Args, Default)]
+pub struct ExportColumnsArgs {
+    ///Comma-separated columns to include in CSV or Excel output, in this order (default: all columns)
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+}
+
+/// Prints the rows as CSV or Excel workbook to stdout. Each field of a row becomes a column, nested fields are joined with a dot, e.g. `location.name`.
+pub fn print<T: Serialize>(rows: &[T], format: ExportFormat, columns: &[String]) -> crate::Result<()> {
+    let sheet = Sheet::from_rows(rows)?
+        .select(columns)?;
+
+    match format {
+        ExportFormat::Csv => {
+            print!("{}", sheet.to_csv());
+        }
+        ExportFormat::Xlsx => {
+            if console::Term::stdout().is_term() {
+                return Err("Excel output is binary. Redirect it into a file, e.g. `> inventory.xlsx`.".into());
+            }
+            let workbook = sheet.to_xlsx()?;
+            std::io::stdout().write_all(&workbook)
+                .map_err(|cause| format!("Failed to write Excel workbook: {cause}"))?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct Sheet {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Sheet {
+    fn from_rows<T: Serialize>(rows: &[T]) -> crate::Result<Self> {
+        let flattened = rows.iter()
+            .map(|row| {
+                let value = serde_json::to_value(row)
+                    .map_err(|cause| format!("Failed to convert row for export: {cause}"))?;
+                let mut cells = Vec::new();
+                flatten(String::new(), &value, &mut cells);
+                Ok(cells)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let mut columns: Vec<String> = Vec::new();
+        for cells in &flattened {
+            for (column, _) in cells {
+                if columns.contains(column).not() {
+                    columns.push(Clone::clone(column));
+                }
+            }
+        }
+
+        let rows = flattened.into_iter()
+            .map(|cells| {
+                columns.iter()
+                    .map(|column| {
+                        cells.iter()
+                            .find(|(name, _)| name == column)
+                            .map(|(_, cell)| Clone::clone(cell))
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self { columns, rows })
+    }
+
+    fn select(self, columns: &[String]) -> crate::Result<Self> {
+        if columns.is_empty() {
+            return Ok(self);
+        }
+
+        let indices = columns.iter()
+            .map(|column| {
+                self.columns.iter().position(|candidate| candidate == column)
+                    .ok_or_else(|| format!("Unknown column '{column}'. Available columns are: {}", self.columns.join(", ")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rows = self.rows.into_iter()
+            .map(|row| indices.iter().map(|index| Clone::clone(&row[*index])).collect())
+            .collect();
+
+        Ok(Self { columns: columns.to_vec(), rows })
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for line in std::iter::once(&self.columns).chain(&self.rows) {
+            let line = line.iter()
+                .map(|cell| escape_csv(cell))
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push_str(&line);
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+
+    fn to_xlsx(&self) -> crate::Result<Vec<u8>> {
+        let to_error = |cause: rust_xlsxwriter::XlsxError| format!("Failed to create Excel workbook: {cause}");
+
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let bold = rust_xlsxwriter::Format::new().set_bold();
+
+        for (column, title) in self.columns.iter().enumerate() {
+            worksheet.write_string_with_format(0, column as u16, title, &bold).map_err(to_error)?;
+        }
+        for (row, cells) in self.rows.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                worksheet.write_string(row as u32 + 1, column as u16, cell).map_err(to_error)?;
+            }
+        }
+        worksheet.autofit();
+
+        let workbook = workbook.save_to_buffer().map_err(to_error)?;
+        Ok(workbook)
+    }
+}
+
+fn flatten(prefix: String, value: &Value, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) if fields.is_empty().not() => {
+            for (name, value) in fields {
+                let column = if prefix.is_empty() { Clone::clone(name) } else { format!("{prefix}.{name}") };
+                flatten(column, value, cells);
+            }
+        }
+        value => cells.push((prefix, cell_text(value))),
+    }
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => Clone::clone(text),
+        Value::Array(values) => values.iter().map(cell_text).collect::<Vec<_>>().join(", "),
+        Value::Object(_) => value.to_string(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+    }
+}
+
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        location: Location,
+        tags: Vec<String>,
+    }
+    #[derive(Serialize)]
+    struct Location {
+        site: String,
+    }
+
+    #[test]
+    fn should_flatten_nested_fields_and_select_columns() -> Result<()> {
+        let rows = vec![
+            Row { name: String::from("peer-1"), location: Location { site: String::from("Lab, Building 1") }, tags: vec![String::from("a"), String::from("b")] },
+            Row { name: String::from("peer-2"), location: Location { site: String::from("Lab \"2\"") }, tags: vec![] },
+        ];
+
+        let sheet = Sheet::from_rows(&rows).unwrap();
+        assert_that!(sheet.columns, elements_are![eq("location.site"), eq("name"), eq("tags")]);
+
+        let sheet = sheet.select(&[String::from("name"), String::from("location.site")]).unwrap();
+        assert_that!(sheet.to_csv(), eq("name,location.site\r\npeer-1,\"Lab, Building 1\"\r\npeer-2,\"Lab \"\"2\"\"\"\r\n"));
+
+        let sheet = Sheet::from_rows(&rows).unwrap();
+        assert!(sheet.select(&[String::from("unknown")]).is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, SecondsFormat, Utc};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
 use uuid::Uuid;
@@ -24,6 +25,10 @@ impl GenerateSetupStringCli {
             Ok(setup_string) => {
                 println!("{}", setup_string);
                 eprintln!("Setup-Strings may only be used to set up one host. For setting up multiple hosts, you should create a peer for each host.");
+                if let Some(expires_at) = created_setup.expires_at {
+                    let expires_at = DateTime::<Utc>::from(expires_at).to_rfc3339_opts(SecondsFormat::Secs, true);
+                    eprintln!("The Setup-String expires at {expires_at}, unless the peer connects with it before. Run 'opendut-cleo peer revoke-setup-string {}', if it was leaked.", self.id);
+                }
             }
             Err(_) => {
                 println!("Could not configure setup string...")
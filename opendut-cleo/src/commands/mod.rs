@@ -1,10 +1,28 @@
+pub mod benchmark;
+pub mod bootstrap;
+pub mod bundle;
+pub mod check_compatibility;
+pub mod check_consistency;
 pub mod cluster_configuration;
 pub mod cluster_deployment;
+pub mod cluster_hook;
 pub mod device;
 pub mod peer;
+pub mod peer_stream;
+pub mod provisioning_token;
+pub mod plugin;
+pub mod project;
+pub mod results;
 pub mod network_interface;
 pub mod executor;
+pub mod export;
 pub mod decode_setup_string;
 pub mod generate_setup_string;
 pub mod completions;
+pub mod self_update;
+pub mod service_account;
 pub mod setup;
+pub mod snapshot;
+pub mod topology;
+pub mod trace_capture;
+pub mod watch;
@@ -58,7 +58,7 @@ impl CreateNetworkInterfaceCli {
         }
 
         carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
-            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+            .map_err(|error| crate::Error::carl(format!("Failed to update peer <{}>.", peer_id), error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
 
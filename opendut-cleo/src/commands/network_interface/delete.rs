@@ -55,7 +55,7 @@ impl DeleteNetworkInterfaceCli {
         };
 
         carl.peers.store_peer_descriptor(peer).await
-            .map_err(|error| format!("Failed to delete network interfaces for peer.\n  {}", error))?;
+            .map_err(|error| crate::Error::carl("Failed to delete network interfaces for peer.", error))?;
 
         Ok(())
     }
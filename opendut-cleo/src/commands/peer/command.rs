@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::{CarlClient, ClientError};
+use opendut_carl_api::carl::peer::{PeerCommand, PeerCommandResult, SendPeerCommandError};
+use opendut_types::peer::PeerId;
+
+use crate::CreateOutputFormat;
+
+/// Send an administrative command to a connected peer and report its result
+#[derive(clap::Parser)]
+pub struct SendPeerCommandCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+    ///Command to execute on the peer: restart-service, reapply-configuration, collect-diagnostics, reboot-host, collect-logs, start-packet-capture or stop-packet-capture
+    #[arg()]
+    command: PeerCommand,
+    ///Seconds to wait for the peer to report the result
+    #[arg(long, default_value_t=30)]
+    timeout: u64,
+    ///File to write the archive collected by the command to, e.g. the diagnostics bundle. Defaults to 'diagnostics-<PeerID>.tar.gz'
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+struct SerializablePeerCommandResult {
+    peer_id: String,
+    command: String,
+    output: String,
+    duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+}
+
+impl SendPeerCommandCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.id);
+        let command = self.command;
+
+        let result = carl.peers.send_peer_command(peer_id, command, Duration::from_secs(self.timeout)).await
+            .map_err(|error| {
+                let context = match &error {
+                    ClientError::TransportError(_) => {
+                        format!("Could not send command '{command}' to peer <{peer_id}>, because CARL is unreachable.")
+                    }
+                    ClientError::UsageError(SendPeerCommandError::PeerNotConnected { .. })
+                    | ClientError::UsageError(SendPeerCommandError::PeerNotResponding { .. }) => {
+                        format!("CARL is reachable, but peer <{peer_id}> is not.")
+                    }
+                    _ => format!("Could not execute command '{command}' on peer <{peer_id}>."),
+                };
+                crate::Error::carl(context, error)
+            })?;
+
+        let file = self.write_attachment(&result)?;
+
+        let PeerCommandResult { peer_id, command, output: command_output, duration, .. } = result;
+        let serializable = SerializablePeerCommandResult {
+            peer_id: peer_id.to_string(),
+            command: command.to_string(),
+            output: command_output,
+            duration_ms: duration.as_millis(),
+            file: file.map(|file| file.display().to_string()),
+        };
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Peer <{}> executed command '{}' in {} ms:\n  {}", serializable.peer_id, serializable.command, serializable.duration_ms, serializable.output);
+                if let Some(file) = serializable.file {
+                    println!("Wrote attachment to '{file}'.");
+                }
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&serializable).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&serializable).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_attachment(&self, result: &PeerCommandResult) -> crate::Result<Option<PathBuf>> {
+        if result.attachment.is_empty() {
+            return Ok(None);
+        }
+        let file = self.file.clone()
+            .unwrap_or_else(|| PathBuf::from(format!("diagnostics-{}.tar.gz", result.peer_id)));
+
+        std::fs::write(&file, &result.attachment)
+            .map_err(|cause| format!("Failed to write attachment to '{}': {}", file.display(), cause))?;
+        Ok(Some(file))
+    }
+}
@@ -5,6 +5,7 @@ use crate::{CreateOutputFormat};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::peer::executor::{ExecutorDescriptors};
+use opendut_types::project::ProjectId;
 use opendut_types::util::net::NetworkInterfaceName;
 
 /// Create a peer
@@ -24,6 +25,9 @@ pub struct CreatePeerCli {
     /// Not removing the bridge could lead to network traffic being misdirected!
     #[arg(long)]
     bridge_name: Option<NetworkInterfaceName>,
+    ///ProjectID of the project to assign the peer to, so that only its members can access the peer
+    #[arg(long)]
+    project: Option<Uuid>,
 }
 
 impl CreatePeerCli {
@@ -51,12 +55,16 @@ impl CreatePeerCli {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
         carl.peers
-            .store_peer_descriptor(descriptor.clone())
+            .store_peer_descriptor_in_project(descriptor.clone(), self.project.map(ProjectId::from))
             .await
-            .map_err(|error| format!("Failed to create new peer.\n  {error}"))?;
+            .map_err(|error| crate::Error::carl("Failed to create new peer.", error))?;
         let bold = Style::new().bold();
         match output {
             CreateOutputFormat::Text => {
@@ -4,6 +4,8 @@ use uuid::Uuid;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
 
+use crate::i18n::trf;
+
 /// Delete a peer
 #[derive(clap::Parser)]
 pub struct DeletePeerCli {
@@ -18,7 +20,7 @@ impl DeletePeerCli {
 
         { //block deleting, if device is used in cluster
             let peer_descriptor = carl.peers.get_peer_descriptor(id).await
-                .map_err(|error| format!("Failed to get peer descriptor for peer: {}.\n {}", id, error))?;
+                .map_err(|error| crate::Error::carl(format!("Failed to get peer descriptor for peer: {}.", id), error))?;
 
             let peer_device_ids = peer_descriptor.topology.devices.into_iter().map(|descriptor| descriptor.id).collect::<Vec<_>>();
 
@@ -36,15 +38,15 @@ impl DeletePeerCli {
                 }
             }
             if clusters_with_configured_devices.is_empty().not() {
-                Err(format!("Cannot delete peer because it is used in following clusters: {}", clusters_with_configured_devices.join(", ")))?
+                Err(trf("peer.delete.in-use", &[("clusters", &clusters_with_configured_devices.join(", "))]))?
             }
         }
         
         carl.peers
             .delete_peer_descriptor(id)
             .await
-            .map_err(|error| format!("Failed to delete peer with the id '{}'.\n  {}", id, error))?;
-        println!("Deleted peer with the PeerID: {}", id);
+            .map_err(|error| crate::Error::carl(trf("peer.delete.failed", &[("peer_id", &id)]), error))?;
+        println!("{}", trf("peer.delete.success", &[("peer_id", &id)]));
 
         Ok(())
     }
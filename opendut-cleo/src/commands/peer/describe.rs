@@ -35,6 +35,42 @@ pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeO
         .map(|device| device.name.value())
         .collect::<Vec<_>>()
         .join(", ");
+    let peer_labels = peer_descriptor
+        .labels
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let peer_hardware = peer_descriptor
+        .hardware
+        .as_ref()
+        .map(|hardware| {
+            let can_controllers = hardware.can_controllers.iter()
+                .map(|controller| if controller.fd_supported { format!("{} (FD)", controller.interface) } else { controller.interface.to_string() })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let network_interfaces = hardware.network_interfaces.iter()
+                .map(|network_interface| match network_interface.speed_mbits {
+                    Some(speed) => format!("{} ({speed} Mbit/s)", network_interface.interface),
+                    None => network_interface.interface.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "{} with {} cores and {} MiB memory, virtualization: {}, CAN controllers: [{can_controllers}], network interfaces: [{network_interfaces}]",
+                hardware.cpu_architecture,
+                hardware.cpu_cores,
+                hardware.memory_bytes / (1024 * 1024),
+                hardware.virtualization.as_deref().unwrap_or("none"),
+            )
+        })
+        .unwrap_or_else(|| String::from("not reported"));
+    let peer_cluster_hooks = peer_descriptor
+        .cluster_hooks
+        .iter()
+        .map(|hook| format!("{} ({}, on failure: {})", hook.name, hook.stage, hook.failure_policy))
+        .collect::<Vec<_>>()
+        .join(", ");
     let text = match output {
         DescribeOutputFormat::Text => {
             format!(
@@ -42,10 +78,13 @@ pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeO
                     "
                 Peer: {}
                   Id: {}
-                  Devices: [{}]\
+                  Devices: [{}]
+                  Labels: [{}]
+                  Hardware: {}
+                  Cluster hooks: [{}]\
             "
                 ),
-                peer_descriptor.name, peer_descriptor.id, peer_devices
+                peer_descriptor.name, peer_descriptor.id, peer_devices, peer_labels, peer_hardware, peer_cluster_hooks
             )
         }
         DescribeOutputFormat::Json => serde_json::to_string(&peer_descriptor).unwrap(),
@@ -0,0 +1,112 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::peer::EditedPeerLabels;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::label::LabelSelector;
+use opendut_types::peer::{PeerId, PeerName};
+
+use crate::parse::label::{ParseableAnnotationChange, ParseableLabelChange};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// Add, change or remove labels and annotations of all peers matching a selector
+#[derive(clap::Parser)]
+pub struct LabelPeersCli {
+    ///Selector of the peers to label, e.g. 'location=hall3,rack!=7,!decommissioned'
+    #[arg(short='l', long)]
+    selector: LabelSelector,
+    ///Labels to set as 'key=value' or to remove as 'key-'
+    #[arg(num_args = 0..)]
+    labels: Vec<ParseableLabelChange>,
+    ///Annotation to set as 'key=value' or to remove as 'key-'
+    #[arg(long)]
+    annotate: Vec<ParseableAnnotationChange>,
+    ///Only show the resulting labels, without storing them
+    #[arg(long)]
+    preview: bool,
+}
+
+#[derive(Table, Debug, Serialize)]
+struct LabeledPeerTable {
+    #[table(title = "Name")]
+    name: PeerName,
+    #[table(title = "PeerID")]
+    id: PeerId,
+    #[table(title = "Labels")]
+    labels: String,
+    #[table(title = "Annotations")]
+    annotations: String,
+    #[table(title = "Changed")]
+    changed: bool,
+}
+
+impl LabelPeersCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let label_changes = self.labels.into_iter()
+            .map(|ParseableLabelChange(change)| change)
+            .collect::<Vec<_>>();
+        let annotation_changes = self.annotate.into_iter()
+            .map(|ParseableAnnotationChange(change)| change)
+            .collect::<Vec<_>>();
+
+        if label_changes.is_empty() && annotation_changes.is_empty() {
+            return Err("Could not label peers.\n  Specify at least one label or annotation to change.".into());
+        }
+
+        let edited_peers = carl.peers.edit_peer_labels(self.selector, label_changes, annotation_changes, self.preview).await
+            .map_err(|error| format!("Could not label peers.\n  {error}"))?;
+
+        let peers_table = edited_peers.into_iter()
+            .map(LabeledPeerTable::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                if peers_table.is_empty() {
+                    println!("No peers match the selector.");
+                } else {
+                    print_stdout(peers_table.with_title())
+                        .expect("List of labeled peers should be printable as table.");
+                }
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&peers_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&peers_table).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                export::print(&peers_table, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                export::print(&peers_table, ExportFormat::Xlsx, &[])?;
+            }
+        }
+        if self.preview {
+            eprintln!("Preview only, no labels were stored.");
+        }
+        Ok(())
+    }
+}
+
+impl From<EditedPeerLabels> for LabeledPeerTable {
+    fn from(peer: EditedPeerLabels) -> Self {
+        let labels = peer.labels.iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>();
+        let annotations = peer.annotations.iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>();
+
+        LabeledPeerTable {
+            name: peer.peer_name,
+            id: peer.peer_id,
+            labels: labels.join(", "),
+            annotations: annotations.join(", "),
+            changed: peer.changed,
+        }
+    }
+}
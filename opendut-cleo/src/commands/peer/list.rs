@@ -4,13 +4,25 @@ use cli_table::{print_stdout, Table, WithTitle};
 use serde::Serialize;
 
 use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::ResourceKind;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
 use opendut_types::peer::state::PeerState;
+use opendut_types::project::ProjectId;
+use crate::commands::export::{self, ExportColumnsArgs, ExportFormat};
+use crate::commands::watch::WatchArgs;
 use crate::ListOutputFormat;
 
 /// List all peers
 #[derive(clap::Parser)]
-pub struct ListPeersCli;
+pub struct ListPeersCli {
+    ///Only list the peers of the project with this ProjectID
+    #[arg(long)]
+    project: Option<uuid::Uuid>,
+    #[command(flatten)]
+    export: ExportColumnsArgs,
+    #[command(flatten)]
+    watch: WatchArgs,
+}
 
 #[derive(Table, Debug, Serialize)]
 struct PeerTable {
@@ -29,6 +41,7 @@ struct PeerTable {
 #[derive(Debug, PartialEq, Serialize)]
 enum PeerStatus {
     Connected,
+    Unreachable,
     Disconnected,
 }
 
@@ -36,6 +49,7 @@ impl Display for PeerStatus {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             PeerStatus::Connected => write!(f, "Connected"),
+            PeerStatus::Unreachable => write!(f, "Unreachable"),
             PeerStatus::Disconnected => write!(f, "Disconnected"),
         }
     }
@@ -43,31 +57,41 @@ impl Display for PeerStatus {
 
 impl ListPeersCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let all_peers = carl
-            .peers
-            .list_peer_descriptors()
-            .await
-            .map_err(|error| format!("Could not list peers.\n  {}", error))?;
-        
-        let mut peers_table = vec![];
-        for peer in all_peers {
-            let peer_state = carl.peers.get_peer_state(peer.id).await.map_err(|_| {
-                format!("Failed to retrieve state for peer <{}>", peer.id)
-            })?;
-            peers_table.push(add_peer_status(peer, peer_state));
-        };
-        match output {
-            ListOutputFormat::Table => {
-                print_stdout(peers_table.with_title())
-                    .expect("List of clusters should be printable as table.");
-            }
-            ListOutputFormat::Json => {
-                let json = serde_json::to_string(&peers_table).unwrap();
-                println!("{}", json);
-            }
-            ListOutputFormat::PrettyJson => {
-                let json = serde_json::to_string_pretty(&peers_table).unwrap();
-                println!("{}", json);
+        let mut watch = self.watch.subscribe(carl, &[ResourceKind::PeerDescriptor, ResourceKind::PeerState], &output).await?;
+
+        while watch.next_render().await? {
+            let all_peers = carl
+                .peers
+                .list_peer_descriptors_in_project(self.project.map(ProjectId::from))
+                .await
+                .map_err(|error| format!("Could not list peers.\n  {}", error))?;
+
+            let mut peers_table = vec![];
+            for peer in all_peers {
+                let peer_state = carl.peers.get_peer_state(peer.id).await.map_err(|_| {
+                    format!("Failed to retrieve state for peer <{}>", peer.id)
+                })?;
+                peers_table.push(add_peer_status(peer, peer_state));
+            };
+            match output {
+                ListOutputFormat::Table => {
+                    print_stdout(peers_table.with_title())
+                        .expect("List of clusters should be printable as table.");
+                }
+                ListOutputFormat::Json => {
+                    let json = serde_json::to_string(&peers_table).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::PrettyJson => {
+                    let json = serde_json::to_string_pretty(&peers_table).unwrap();
+                    println!("{}", json);
+                }
+                ListOutputFormat::Csv => {
+                    export::print(&peers_table, ExportFormat::Csv, &self.export.columns)?;
+                }
+                ListOutputFormat::Xlsx => {
+                    export::print(&peers_table, ExportFormat::Xlsx, &self.export.columns)?;
+                }
             }
         }
         Ok(())
@@ -81,6 +105,7 @@ fn add_peer_status(
     let status = match peer_state {
         PeerState::Down => { PeerStatus::Disconnected }
         PeerState::Up { .. } => { PeerStatus::Connected }
+        PeerState::Unreachable { .. } => { PeerStatus::Unreachable }
     };
     let network_interfaces = Clone::clone(&peer.network.interfaces);
     let interfaces = network_interfaces.into_iter().map(|interface| interface.name.to_string()).collect::<Vec<_>>();
@@ -120,7 +145,11 @@ mod test {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![]
-            }
+            },
+            labels: Default::default(),
+            annotations: Default::default(),
+            hardware: Default::default(),
+            cluster_hooks: Default::default(),
         };
         assert_that!(
             add_peer_status(peer.clone(), PeerState::Down),
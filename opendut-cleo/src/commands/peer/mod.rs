@@ -2,6 +2,11 @@ pub mod list;
 pub mod describe;
 pub mod create;
 pub mod delete;
+pub mod label;
+pub mod ping;
 
 
 
+pub mod command;
+pub mod rolling_restart;
+pub mod revoke_setup_string;
@@ -0,0 +1,81 @@
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::{CarlClient, ClientError};
+use opendut_carl_api::carl::peer::{PeerPing, PingPeerError};
+use opendut_types::peer::PeerId;
+
+use crate::CreateOutputFormat;
+
+/// Round-trip a probe from CARL to a peer over its connection to CARL and report the latency
+#[derive(clap::Parser)]
+pub struct PingPeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+    ///Seconds to wait for the peer to respond
+    #[arg(long, default_value_t=5)]
+    timeout: u64,
+}
+
+#[derive(serde::Serialize)]
+struct SerializablePeerPing {
+    peer_id: String,
+    round_trip_time_ms: u128,
+    last_seen: String,
+}
+
+impl From<PeerPing> for SerializablePeerPing {
+    fn from(ping: PeerPing) -> Self {
+        SerializablePeerPing {
+            peer_id: ping.peer_id.to_string(),
+            round_trip_time_ms: ping.round_trip_time.as_millis(),
+            last_seen: format_timestamp(ping.last_seen),
+        }
+    }
+}
+
+impl PingPeerCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.id);
+
+        let ping = carl.peers.ping_peer(peer_id, Duration::from_secs(self.timeout)).await
+            .map_err(|error| {
+                let context = match &error {
+                    ClientError::TransportError(_) => {
+                        format!("Could not ping peer <{peer_id}>, because CARL is unreachable.")
+                    }
+                    ClientError::UsageError(PingPeerError::PeerNotConnected { last_seen, .. })
+                    | ClientError::UsageError(PingPeerError::PeerNotResponding { last_seen, .. }) => {
+                        let last_seen = last_seen.map(format_timestamp)
+                            .unwrap_or_else(|| String::from("never since CARL started"));
+                        format!("CARL is reachable, but peer <{peer_id}> is not. Last seen: {last_seen}")
+                    }
+                    _ => format!("Could not ping peer <{peer_id}>."),
+                };
+                crate::Error::carl(context, error)
+            })?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Peer <{}> responded via CARL in {} ms. Last seen: {}", ping.peer_id, ping.round_trip_time.as_millis(), format_timestamp(ping.last_seen));
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializablePeerPing::from(ping)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializablePeerPing::from(ping)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_timestamp(timestamp: SystemTime) -> String {
+    DateTime::<Utc>::from(timestamp).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+
+use crate::CreateOutputFormat;
+
+/// Revoke the Setup-String and credentials of a peer, e.g. when the Setup-String was leaked. Generate a new Setup-String to set up the peer again
+#[derive(clap::Parser)]
+pub struct RevokeSetupStringCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableRevokedSetup {
+    peer_id: String,
+    revoked: bool,
+}
+
+impl RevokeSetupStringCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.id);
+
+        let peer_id = carl.peers.revoke_peer_setup(peer_id).await
+            .map_err(|error| crate::Error::carl(format!("Could not revoke the Setup-String of peer <{peer_id}>."), error))?;
+
+        let serializable = SerializableRevokedSetup {
+            peer_id: peer_id.to_string(),
+            revoked: true,
+        };
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Revoked the Setup-String of peer <{}>. The peer was disconnected and needs a new Setup-String to connect again.", serializable.peer_id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&serializable).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&serializable).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Not;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::{MaintenanceOperation, MaintenanceOperationId, MaintenancePeerState, PeerCommand, RollingRestartSpec};
+use opendut_types::peer::PeerId;
+use opendut_types::ShortName;
+
+use crate::CreateOutputFormat;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Restart the given peers a few at a time, without taking down more than the allowed members of a deployed cluster at once
+#[derive(clap::Parser)]
+pub struct RollingRestartCli {
+    ///PeerIDs
+    #[arg(required = true)]
+    ids: Vec<Uuid>,
+    ///Command to restart each peer with: restart-service or reboot-host
+    #[arg(long, default_value = "restart-service", value_parser = parse_command)]
+    command: PeerCommand,
+    ///Maximum number of members of a deployed cluster, which are restarted at once
+    #[arg(long, default_value_t=1)]
+    max_unavailable: u32,
+    ///Seconds to wait for each peer to execute the command and to connect to CARL again
+    #[arg(long, default_value_t=300)]
+    peer_timeout: u64,
+    ///Wait until all peers were restarted or one of them failed. Fails, if a peer failed to restart
+    #[arg(long)]
+    wait: bool,
+}
+
+/// Show the progress of a rolling restart of peers
+#[derive(clap::Parser)]
+pub struct RollingRestartStatusCli {
+    ///OperationID, as printed when starting the rolling restart
+    #[arg()]
+    id: Uuid,
+    ///Wait until all peers were restarted or one of them failed. Fails, if a peer failed to restart
+    #[arg(long)]
+    wait: bool,
+}
+
+fn parse_command(value: &str) -> Result<PeerCommand, String> {
+    let command = PeerCommand::from_str(value)?;
+    if RollingRestartSpec::COMMANDS.contains(&command) {
+        Ok(command)
+    } else {
+        Err(format!("Expected one of: {}", RollingRestartSpec::COMMANDS.map(|command| command.name()).join(", ")))
+    }
+}
+
+#[derive(Table)]
+struct MaintenancePeerTable {
+    #[table(title = "PeerID")]
+    peer_id: String,
+    #[table(title = "State")]
+    state: &'static str,
+    #[table(title = "Cause")]
+    cause: String,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableMaintenanceOperation {
+    operation_id: String,
+    command: String,
+    finished: bool,
+    peers: Vec<SerializableMaintenancePeerStatus>,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableMaintenancePeerStatus {
+    peer_id: String,
+    state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cause: Option<String>,
+}
+
+impl RollingRestartCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let spec = RollingRestartSpec {
+            peers: self.ids.into_iter().map(PeerId::from).collect(),
+            command: self.command,
+            max_unavailable_per_cluster: self.max_unavailable,
+            peer_timeout: Duration::from_secs(self.peer_timeout),
+        };
+        let peer_count = spec.peers.len() as u64;
+
+        let operation = carl.peers.start_rolling_restart(spec).await
+            .map_err(|error| crate::Error::carl("Could not start the rolling restart of peers.", error))?;
+
+        if self.wait.not() {
+            print_operation(operation, output);
+            return Ok(());
+        }
+
+        // Each peer may take up to the peer timeout, in the worst case one after another.
+        let timeout = Duration::from_secs(self.peer_timeout.saturating_mul(peer_count)).saturating_add(POLL_INTERVAL);
+        wait_for(carl, operation.id, timeout, output).await
+    }
+}
+
+impl RollingRestartStatusCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let operation_id = MaintenanceOperationId(self.id);
+
+        if self.wait {
+            wait_for(carl, operation_id, Duration::MAX, output).await
+        } else {
+            let operation = get(carl, operation_id).await?;
+            print_operation(operation, output);
+            Ok(())
+        }
+    }
+}
+
+async fn get(carl: &mut CarlClient, operation_id: MaintenanceOperationId) -> crate::Result<MaintenanceOperation> {
+    carl.peers.get_maintenance_operation(operation_id).await
+        .map_err(|error| crate::Error::carl(format!("Could not retrieve the progress of rolling restart <{operation_id}>."), error))
+}
+
+async fn wait_for(carl: &mut CarlClient, operation_id: MaintenanceOperationId, timeout: Duration, output: CreateOutputFormat) -> crate::Result<()> {
+    let started = Instant::now();
+    let operation = loop {
+        let operation = get(carl, operation_id).await?;
+        if operation.is_finished() || started.elapsed() >= timeout {
+            break operation;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    };
+
+    let finished = operation.is_finished();
+    let failed = operation.is_failed();
+    print_operation(operation, output);
+
+    if failed {
+        Err(format!("Rolling restart <{operation_id}> failed."))?
+    } else if finished.not() {
+        Err(format!("Rolling restart <{operation_id}> did not complete within {} seconds.", timeout.as_secs()))?
+    }
+    Ok(())
+}
+
+fn print_operation(operation: MaintenanceOperation, output: CreateOutputFormat) {
+    let operation_id = operation.id;
+    let finished = operation.is_finished();
+
+    match output {
+        CreateOutputFormat::Text => {
+            let progress = if finished { "finished" } else { "running" };
+            println!(
+                "Rolling restart <{operation_id}> with command '{}' is {progress}: {} of {} peers completed.",
+                operation.command,
+                operation.count(&MaintenancePeerState::Completed),
+                operation.peers.len(),
+            );
+            let table = operation.peers.into_iter()
+                .map(|peer| MaintenancePeerTable {
+                    peer_id: peer.peer_id.to_string(),
+                    state: peer.state.short_name(),
+                    cause: match peer.state {
+                        MaintenancePeerState::Failed { cause } => cause,
+                        _ => String::new(),
+                    },
+                })
+                .collect::<Vec<_>>();
+            print_stdout(table.with_title())
+                .expect("Progress of rolling restart should be printable as table.");
+        }
+        CreateOutputFormat::Json | CreateOutputFormat::PrettyJson => {
+            let serializable = SerializableMaintenanceOperation {
+                operation_id: operation_id.to_string(),
+                command: operation.command.to_string(),
+                finished,
+                peers: operation.peers.into_iter()
+                    .map(|peer| SerializableMaintenancePeerStatus {
+                        peer_id: peer.peer_id.to_string(),
+                        state: peer.state.short_name().to_owned(),
+                        cause: match peer.state {
+                            MaintenancePeerState::Failed { cause } => Some(cause),
+                            _ => None,
+                        },
+                    })
+                    .collect(),
+            };
+            let json = if let CreateOutputFormat::PrettyJson = output {
+                serde_json::to_string_pretty(&serializable).unwrap()
+            } else {
+                serde_json::to_string(&serializable).unwrap()
+            };
+            println!("{}", json);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+
+/// Terminate the messaging stream of a connected peer. CARL marks the peer as down and the peer connects again, once it notices the end of its stream.
+#[derive(clap::Parser)]
+pub struct DeletePeerStreamCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeletePeerStreamCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PeerId::from(self.id);
+        carl.diagnostics.terminate_peer_stream(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not terminate stream of peer <{id}>."), error))?;
+        println!("Terminated stream of peer <{id}>.");
+
+        Ok(())
+    }
+}
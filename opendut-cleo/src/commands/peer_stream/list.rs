@@ -0,0 +1,78 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+
+use crate::commands::peer_stream::{format_timestamp, SerializablePeerStreamInfo};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// List the messaging streams of the peers currently connected to CARL, e.g. to spot half-open connections
+#[derive(clap::Parser)]
+pub struct ListPeerStreamsCli;
+
+#[derive(Table)]
+struct PeerStreamTable {
+    #[table(title = "PeerID")]
+    peer_id: PeerId,
+    #[table(title = "Remote Address")]
+    remote_address: String,
+    #[table(title = "Version")]
+    version: String,
+    #[table(title = "Uptime (s)")]
+    uptime: u64,
+    #[table(title = "Last Seen")]
+    last_seen: String,
+    #[table(title = "Received")]
+    messages_received: u64,
+    #[table(title = "Sent")]
+    messages_sent: u64,
+}
+
+impl ListPeerStreamsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let streams = carl.diagnostics.list_peer_streams().await
+            .map_err(|error| format!("Could not list peer streams.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let stream_table = streams.into_iter()
+                    .map(|stream| {
+                        PeerStreamTable {
+                            peer_id: stream.peer_id,
+                            remote_address: stream.remote_address
+                                .map(|address| address.to_string())
+                                .unwrap_or_else(|| stream.remote_host.to_string()),
+                            uptime: stream.uptime().as_secs(),
+                            version: stream.version.unwrap_or_else(|| String::from("unknown")),
+                            last_seen: stream.last_seen.map(format_timestamp).unwrap_or_default(),
+                            messages_received: stream.messages_received,
+                            messages_sent: stream.messages_sent,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(stream_table.with_title())
+                    .expect("List of peer streams should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let streams = streams.into_iter().map(SerializablePeerStreamInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&streams).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let streams = streams.into_iter().map(SerializablePeerStreamInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&streams).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let streams = streams.into_iter().map(SerializablePeerStreamInfo::from).collect::<Vec<_>>();
+                export::print(&streams, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let streams = streams.into_iter().map(SerializablePeerStreamInfo::from).collect::<Vec<_>>();
+                export::print(&streams, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
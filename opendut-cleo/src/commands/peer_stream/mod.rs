@@ -0,0 +1,40 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use opendut_carl_api::carl::diagnostics::PeerStreamInfo;
+
+pub mod delete;
+pub mod list;
+
+fn format_timestamp(timestamp: SystemTime) -> String {
+    DateTime::<Utc>::from(timestamp).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[derive(serde::Serialize)]
+struct SerializablePeerStreamInfo {
+    peer_id: String,
+    remote_host: String,
+    remote_address: Option<String>,
+    version: Option<String>,
+    opened_at: String,
+    uptime_seconds: u64,
+    last_seen: Option<String>,
+    messages_received: u64,
+    messages_sent: u64,
+}
+
+impl From<PeerStreamInfo> for SerializablePeerStreamInfo {
+    fn from(stream: PeerStreamInfo) -> Self {
+        Self {
+            peer_id: stream.peer_id.to_string(),
+            remote_host: stream.remote_host.to_string(),
+            remote_address: stream.remote_address.map(|address| address.to_string()),
+            uptime_seconds: stream.uptime().as_secs(),
+            version: stream.version,
+            opened_at: format_timestamp(stream.opened_at),
+            last_seen: stream.last_seen.map(format_timestamp),
+            messages_received: stream.messages_received,
+            messages_sent: stream.messages_sent,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::ffi::{OsStr, OsString};
+use std::ops::Not;
+use std::path::PathBuf;
+
+use opendut_auth::confidential::client::ConfidentialClient;
+
+/// Executables on the PATH named with this prefix are available as subcommands, e.g. `cleo-foo` as `cleo foo`.
+pub const PLUGIN_PREFIX: &str = "cleo-";
+
+pub const ENV_CARL_URL: &str = "OPENDUT_CLEO_CARL_URL";
+pub const ENV_CARL_TOKEN: &str = "OPENDUT_CLEO_CARL_TOKEN";
+pub const ENV_CA_PATH: &str = "OPENDUT_CLEO_CA_PATH";
+pub const ENV_DOMAIN_NAME_OVERRIDE: &str = "OPENDUT_CLEO_DOMAIN_NAME_OVERRIDE";
+
+/// Runs the external subcommand with the remaining arguments. The plugin receives the context for connecting
+/// to CARL via environment variables, so it does not have to read CLEO's configuration itself.
+pub async fn execute(args: Vec<OsString>, config: &config::Config) -> crate::Result<()> {
+    let (name, arguments) = args.split_first()
+        .ok_or_else(|| String::from("No subcommand specified."))?;
+    let name = name.to_string_lossy();
+
+    let executable = find_plugin(&name, std::env::var_os("PATH").as_deref())
+        .ok_or_else(|| format!("Unknown subcommand '{name}'. No executable named '{PLUGIN_PREFIX}{name}' was found on the PATH."))?;
+
+    let environment = plugin_environment(config).await?;
+
+    let status = std::process::Command::new(&executable)
+        .args(arguments)
+        .envs(environment)
+        .status()
+        .map_err(|cause| format!("Could not run plugin '{}'.\n  {cause}", executable.display()))?;
+
+    if status.success().not() {
+        return Err(format!("Plugin '{}' failed with {status}.", executable.display()).into());
+    }
+    Ok(())
+}
+
+fn find_plugin(name: &str, path: Option<&OsStr>) -> Option<PathBuf> {
+    let file_name = format!("{PLUGIN_PREFIX}{name}{}", std::env::consts::EXE_SUFFIX);
+
+    std::env::split_paths(path?)
+        .map(|directory| directory.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+async fn plugin_environment(config: &config::Config) -> crate::Result<Vec<(&'static str, String)>> {
+    let mut environment = Vec::new();
+
+    let host = config.get_string("network.carl.host")
+        .map_err(|cause| format!("Configuration should contain a valid host name to connect to CARL.\n  {cause}"))?;
+    let port = config.get_int("network.carl.port")
+        .map_err(|cause| format!("Configuration should contain a valid port number to connect to CARL.\n  {cause}"))?;
+    environment.push((ENV_CARL_URL, format!("https://{host}:{port}")));
+
+    let ca_path = match config.get_string("network.tls.ca.content") {
+        Ok(content) => {
+            //plugins expect a file, so the CA certificate embedded into the configuration is written to a temporary location
+            let path = std::env::temp_dir().join("opendut-cleo-plugin-ca.pem");
+            std::fs::write(&path, content)
+                .map_err(|cause| format!("Could not write CA certificate for plugin to '{}'.\n  {cause}", path.display()))?;
+            path.display().to_string()
+        }
+        Err(_) => config.get_string("network.tls.ca")
+            .map_err(|cause| format!("Configuration should contain a valid path to a CA certificate to connect to CARL.\n  {cause}"))?,
+    };
+    environment.push((ENV_CA_PATH, ca_path));
+
+    if let Ok(domain_name_override) = config.get_string("network.tls.domain.name.override") {
+        if domain_name_override.is_empty().not() {
+            environment.push((ENV_DOMAIN_NAME_OVERRIDE, domain_name_override));
+        }
+    }
+
+    let service_account_token = config.get_string("network.service.account.token").ok()
+        .filter(|token| token.is_empty().not());
+    if let Some(token) = service_account_token {
+        environment.push((ENV_CARL_TOKEN, token));
+        return Ok(environment);
+    }
+
+    let oidc_client = ConfidentialClient::from_settings(config).await
+        .map_err(|cause| format!("Could not initialize OIDC authentication for plugin.\n  {cause}"))?;
+    if let Some(oidc_client) = oidc_client {
+        let token = oidc_client.get_token().await
+            .map_err(|cause| format!("Could not retrieve token to authenticate plugin at CARL.\n  {cause}"))?;
+        environment.push((ENV_CARL_TOKEN, token.value));
+    }
+
+    Ok(environment)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_find_plugin_in_first_matching_directory_of_path() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let empty = temp.child("empty");
+        empty.create_dir_all()?;
+        let first = temp.child("first");
+        let second = temp.child("second");
+        let file_name = format!("cleo-foo{}", std::env::consts::EXE_SUFFIX);
+        first.child(&file_name).touch()?;
+        second.child(&file_name).touch()?;
+
+        let path = std::env::join_paths([empty.path(), first.path(), second.path()])?;
+
+        assert_eq!(find_plugin("foo", Some(path.as_os_str())), Some(first.child(&file_name).to_path_buf()));
+        assert_eq!(find_plugin("bar", Some(path.as_os_str())), None);
+        assert_eq!(find_plugin("foo", None), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::{BTreeSet, HashSet};
+
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::project::{Project, ProjectId, ProjectMember, ProjectName};
+
+use crate::commands::project::{format_members, SerializableProject};
+use crate::CreateOutputFormat;
+
+/// Create a project or update the name and members of an existing project. Requires an admin role
+#[derive(clap::Parser)]
+pub struct CreateProjectCli {
+    ///Name of the project, e.g. "powertrain"
+    #[arg(short, long)]
+    name: ProjectName,
+    ///Member of the project, either a user like "user:alice" or all users of a group like "group:/lab/powertrain"
+    #[arg(short, long = "member")]
+    members: Vec<ProjectMember>,
+    ///ProjectID of an existing project to update
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+impl CreateProjectCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let (id, peers, clusters) = match self.id {
+            Some(id) => {
+                let id = ProjectId::from(id);
+                let existing = carl.projects.list_projects().await
+                    .map_err(|error| format!("Could not list projects.\n  {}", error))?
+                    .into_iter()
+                    .find(|project| project.id == id)
+                    .ok_or(format!("Project <{id}> could not be found."))?;
+                (id, existing.peers, existing.clusters)
+            }
+            None => (ProjectId::random(), HashSet::new(), HashSet::new()),
+        };
+
+        let project = Project {
+            id,
+            name: self.name,
+            members: self.members.into_iter().collect::<BTreeSet<_>>(),
+            peers,
+            clusters,
+        };
+        let name = Clone::clone(&project.name);
+
+        carl.projects.store_project(Clone::clone(&project)).await
+            .map_err(|error| crate::Error::carl(format!("Could not store project '{name}'."), error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully stored project '{}' <{}> with members: {}", project.name, project.id, format_members(&project));
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializableProject::from(project)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializableProject::from(project)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::project::ProjectId;
+
+/// Delete a project. Its peers and clusters are kept and become accessible to all users, unless they belong to another project
+#[derive(clap::Parser)]
+pub struct DeleteProjectCli {
+    ///ProjectID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteProjectCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ProjectId::from(self.id);
+        carl.projects.delete_project(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not delete project <{id}>."), error))?;
+        println!("Deleted project <{id}>.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::project::{ProjectId, ProjectName};
+
+use crate::commands::export::{self, ExportFormat};
+use crate::commands::project::{format_members, SerializableProject};
+use crate::ListOutputFormat;
+
+/// List the projects, which you are a member of, or all projects with an admin role
+#[derive(clap::Parser)]
+pub struct ListProjectsCli;
+
+#[derive(Table)]
+struct ProjectTable {
+    #[table(title = "Name")]
+    name: ProjectName,
+    #[table(title = "ProjectID")]
+    id: ProjectId,
+    #[table(title = "Members")]
+    members: String,
+    #[table(title = "Peers")]
+    peers: usize,
+    #[table(title = "Clusters")]
+    clusters: usize,
+}
+
+impl ListProjectsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let mut projects = carl.projects.list_projects().await
+            .map_err(|error| format!("Could not list projects.\n  {}", error))?;
+        projects.sort_by(|a, b| a.name.value().cmp(b.name.value()));
+
+        match output {
+            ListOutputFormat::Table => {
+                let project_table = projects.into_iter()
+                    .map(|project| ProjectTable {
+                        members: format_members(&project),
+                        peers: project.peers.len(),
+                        clusters: project.clusters.len(),
+                        name: project.name,
+                        id: project.id,
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(project_table.with_title())
+                    .expect("List of projects should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let projects = projects.into_iter().map(SerializableProject::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&projects).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let projects = projects.into_iter().map(SerializableProject::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&projects).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let projects = projects.into_iter().map(SerializableProject::from).collect::<Vec<_>>();
+                export::print(&projects, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let projects = projects.into_iter().map(SerializableProject::from).collect::<Vec<_>>();
+                export::print(&projects, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use opendut_types::project::Project;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+fn format_members(project: &Project) -> String {
+    project.members.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(serde::Serialize)]
+struct SerializableProject {
+    id: String,
+    name: String,
+    members: Vec<String>,
+    peers: Vec<String>,
+    clusters: Vec<String>,
+}
+
+impl From<Project> for SerializableProject {
+    fn from(project: Project) -> Self {
+        let mut peers = project.peers.iter().map(ToString::to_string).collect::<Vec<_>>();
+        peers.sort();
+        let mut clusters = project.clusters.iter().map(ToString::to_string).collect::<Vec<_>>();
+        clusters.sort();
+
+        Self {
+            id: project.id.to_string(),
+            name: project.name.to_string(),
+            members: project.members.iter().map(ToString::to_string).collect(),
+            peers,
+            clusters,
+        }
+    }
+}
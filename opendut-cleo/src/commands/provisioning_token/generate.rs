@@ -0,0 +1,53 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerLocation;
+use opendut_types::peer::label::PeerLabels;
+use opendut_types::peer::provisioning::PeerTemplate;
+
+use crate::parse::label::ParseableLabel;
+
+/// Generate a pool of single-use provisioning strings, with which devices register themselves as peers on first contact
+#[derive(clap::Parser)]
+pub struct GenerateProvisioningTokensCli {
+    ///Name of the pool, e.g. the production batch
+    #[arg(long)]
+    pool: String,
+    ///Number of provisioning strings to generate
+    #[arg(long, default_value_t = 1)]
+    count: u32,
+    ///Location of the peers, which are registered with these provisioning strings
+    #[arg(long)]
+    location: Option<String>,
+    ///Label of the peers, which are registered with these provisioning strings, as 'key=value'
+    #[arg(long)]
+    label: Vec<ParseableLabel>,
+}
+
+impl GenerateProvisioningTokensCli {
+    pub async fn execute(self, carl: &mut CarlClient, cleo_oidc_client_id: String) -> crate::Result<()> {
+        let location = self.location
+            .map(PeerLocation::try_from)
+            .transpose()
+            .map_err(|error| format!("Could not generate provisioning strings.\n  {}", error))?;
+
+        let labels = self.label.into_iter()
+            .map(|ParseableLabel(key, value)| (key, value))
+            .collect::<PeerLabels>();
+
+        let template = PeerTemplate { location, labels };
+
+        let provisionings = carl
+            .peers
+            .generate_provisioning_tokens(self.pool, self.count, template, cleo_oidc_client_id)
+            .await
+            .map_err(|error| format!("Could not generate provisioning strings.\n  {}", error))?;
+
+        for provisioning in provisionings {
+            let provisioning_string = provisioning.encode()
+                .map_err(|error| format!("Could not encode provisioning string.\n  {}", error))?;
+            println!("{}", provisioning_string);
+        }
+        eprintln!("Each provisioning string can only be claimed once. The secrets cannot be retrieved from CARL again, so store them safely.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::provisioning_token::{format_created_at, format_labels, SerializableProvisioningTokenInfo};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// List all provisioning tokens, without their secrets
+#[derive(clap::Parser)]
+pub struct ListProvisioningTokensCli;
+
+#[derive(Table)]
+struct ProvisioningTokenTable {
+    #[table(title = "TokenID")]
+    id: String,
+    #[table(title = "Pool")]
+    pool: String,
+    #[table(title = "Location")]
+    location: String,
+    #[table(title = "Labels")]
+    labels: String,
+    #[table(title = "Created")]
+    created_at: String,
+    #[table(title = "Claimed by")]
+    claimed_by: String,
+}
+
+impl ListProvisioningTokensCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let tokens = carl.peers.list_provisioning_tokens().await
+            .map_err(|error| format!("Could not list provisioning tokens.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let token_table = tokens.into_iter()
+                    .map(|token| {
+                        ProvisioningTokenTable {
+                            id: token.id.to_string(),
+                            pool: token.pool,
+                            location: token.template.location.map(|location| location.value()).unwrap_or_default(),
+                            labels: format_labels(&token.template.labels),
+                            created_at: format_created_at(token.created_at),
+                            claimed_by: token.claimed_by.map(|peer_id| peer_id.to_string()).unwrap_or_default(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(token_table.with_title())
+                    .expect("List of provisioning tokens should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let tokens = tokens.into_iter().map(SerializableProvisioningTokenInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&tokens).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let tokens = tokens.into_iter().map(SerializableProvisioningTokenInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&tokens).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let tokens = tokens.into_iter().map(SerializableProvisioningTokenInfo::from).collect::<Vec<_>>();
+                export::print(&tokens, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let tokens = tokens.into_iter().map(SerializableProvisioningTokenInfo::from).collect::<Vec<_>>();
+                export::print(&tokens, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use opendut_types::peer::label::PeerLabels;
+
+pub mod generate;
+pub mod list;
+
+fn format_created_at(created_at: SystemTime) -> String {
+    DateTime::<Utc>::from(created_at).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn format_labels(labels: &PeerLabels) -> String {
+    labels.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(serde::Serialize)]
+struct SerializableProvisioningTokenInfo {
+    id: String,
+    pool: String,
+    location: Option<String>,
+    labels: String,
+    created_by: String,
+    created_at: String,
+    claimed_by: Option<String>,
+}
+
+impl From<opendut_carl_api::carl::peer::ProvisioningTokenInfo> for SerializableProvisioningTokenInfo {
+    fn from(token: opendut_carl_api::carl::peer::ProvisioningTokenInfo) -> Self {
+        Self {
+            id: token.id.to_string(),
+            pool: token.pool,
+            location: token.template.location.map(|location| location.value()),
+            labels: format_labels(&token.template.labels),
+            created_by: token.created_by,
+            created_at: format_created_at(token.created_at),
+            claimed_by: token.claimed_by.map(|peer_id| peer_id.to_string()),
+        }
+    }
+}
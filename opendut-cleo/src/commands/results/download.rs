@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+/// Download a result, which an executor uploaded to its results URL, through CARL
+#[derive(clap::Parser)]
+pub struct DownloadExecutorResultCli {
+    ///ID of the peer
+    #[arg(long)]
+    peer: Uuid,
+    ///ID of the executor
+    #[arg(long)]
+    executor: Uuid,
+    ///Name of the result file, as shown by 'results list'
+    #[arg()]
+    name: String,
+    ///File to write the result to. Written to a file with the name of the result in the current directory, if not specified.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+impl DownloadExecutorResultCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer);
+        let executor_id = ExecutorId::from(self.executor);
+
+        let (file, content) = carl.results.download_executor_result(peer_id, executor_id, &self.name).await
+            .map_err(|error| crate::Error::carl(format!("Could not download result '{}' of executor <{executor_id}> of peer <{peer_id}>.", self.name), error))?;
+
+        let path = self.file.unwrap_or_else(|| PathBuf::from(&file.name));
+        std::fs::write(&path, &content)
+            .map_err(|cause| format!("Could not write result '{}' to '{}'.\n  {cause}", file.name, path.display()))?;
+        println!("Wrote result '{}' with {} bytes to '{}'.", file.name, content.len(), path.display());
+
+        Ok(())
+    }
+}
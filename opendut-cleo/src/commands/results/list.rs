@@ -0,0 +1,74 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+use crate::commands::export::{self, ExportFormat};
+use crate::commands::results::SerializableExecutorResultFile;
+use crate::ListOutputFormat;
+
+/// List the results, which an executor uploaded to its results URL
+#[derive(clap::Parser)]
+pub struct ListExecutorResultsCli {
+    ///ID of the peer
+    #[arg(long)]
+    peer: Uuid,
+    ///ID of the executor
+    #[arg(long)]
+    executor: Uuid,
+}
+
+#[derive(Table)]
+struct ExecutorResultTable {
+    #[table(title = "Name")]
+    name: String,
+    #[table(title = "Size (bytes)")]
+    size_bytes: u64,
+    #[table(title = "Modified")]
+    modified_at: String,
+}
+
+impl ListExecutorResultsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer);
+        let executor_id = ExecutorId::from(self.executor);
+
+        let files = carl.results.list_executor_results(peer_id, executor_id).await
+            .map_err(|error| crate::Error::carl(format!("Could not list the results of executor <{executor_id}> of peer <{peer_id}>."), error))?;
+        let files = files.into_iter()
+            .map(SerializableExecutorResultFile::from)
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                let table = files.into_iter()
+                    .map(|file| ExecutorResultTable {
+                        name: file.name,
+                        size_bytes: file.size_bytes,
+                        modified_at: file.modified_at.unwrap_or_default(),
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of executor results should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&files).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&files).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                export::print(&files, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                export::print(&files, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use opendut_carl_api::carl::results::ExecutorResultFile;
+
+pub mod download;
+pub mod list;
+
+#[derive(serde::Serialize)]
+struct SerializableExecutorResultFile {
+    name: String,
+    size_bytes: u64,
+    modified_at: Option<String>,
+}
+
+impl From<ExecutorResultFile> for SerializableExecutorResultFile {
+    fn from(file: ExecutorResultFile) -> Self {
+        Self {
+            name: file.name,
+            size_bytes: file.size_bytes,
+            modified_at: file.modified_at
+                .map(|modified_at| DateTime::<Utc>::from(modified_at).to_rfc3339_opts(SecondsFormat::Secs, true)),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use flate2::read::GzDecoder;
+
+use opendut_carl_api::carl::CarlClient;
+
+const CLEO_IDENTIFIER: &str = "opendut-cleo";
+
+/// Update CLEO to the version which is compatible with CARL
+#[derive(clap::Parser)]
+pub struct SelfUpdateCli {
+    ///Only check whether an update is available, without installing it
+    #[arg(long)]
+    check: bool,
+    ///Install the version provided by CARL, even if it matches the current version
+    #[arg(long)]
+    force: bool,
+}
+
+impl SelfUpdateCli {
+    pub async fn execute(self, carl: &mut CarlClient, signature_public_key: Option<String>) -> crate::Result<()> {
+        let current_version = crate::app_info::CRATE_VERSION;
+
+        let compatible_version = carl.metadata.version().await
+            .map_err(|error| format!("Could not determine CLEO version compatible with CARL.\n  {error}"))?
+            .name;
+
+        if compatible_version == current_version && !self.force {
+            println!("CLEO is up-to-date with version {current_version}.");
+            return Ok(());
+        }
+        if self.check {
+            println!("CLEO version {compatible_version} is available. Currently installed is version {current_version}.");
+            return Ok(());
+        }
+
+        let verifying_key = parse_verifying_key(signature_public_key)?;
+
+        let download = carl.metadata.download_cleo(crate::app_info::BUILD_TARGET).await
+            .map_err(|error| format!("Could not download CLEO version {compatible_version}.\n  {error}"))?;
+
+        let executable = extract_executable(&download.archive)?;
+
+        verify_signature(&verifying_key, &executable, &download.signature)?;
+
+        let current_executable = std::env::current_exe()
+            .map_err(|cause| format!("Could not determine path of the current CLEO executable: {cause}"))?;
+
+        replace_executable(&current_executable, &executable)?;
+
+        println!("Updated CLEO from version {current_version} to version {}.", download.version_info.name);
+        Ok(())
+    }
+}
+
+fn parse_verifying_key(signature_public_key: Option<String>) -> Result<VerifyingKey, String> {
+    let signature_public_key = signature_public_key
+        .filter(|key| !key.is_empty())
+        .ok_or("No public key configured for verifying the signature of CLEO updates. Set it via 'update.signature.public.key' in the CLEO configuration.")?;
+
+    let key_bytes = BASE64_STANDARD.decode(signature_public_key.trim())
+        .map_err(|cause| format!("Public key for verifying CLEO updates is not valid Base64: {cause}"))?;
+    let key_bytes: [u8; ed25519_dalek::PUBLIC_KEY_LENGTH] = key_bytes.try_into()
+        .map_err(|_| format!("Public key for verifying CLEO updates must be {} bytes long.", ed25519_dalek::PUBLIC_KEY_LENGTH))?;
+
+    VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|cause| format!("Public key for verifying CLEO updates is invalid: {cause}"))
+}
+
+fn extract_executable(archive: &[u8]) -> Result<Vec<u8>, String> {
+    let executable_path = Path::new(CLEO_IDENTIFIER).join(CLEO_IDENTIFIER);
+
+    let mut archive = tar::Archive::new(GzDecoder::new(archive));
+    let entries = archive.entries()
+        .map_err(|cause| format!("Could not read downloaded CLEO archive: {cause}"))?;
+
+    for entry in entries {
+        let mut entry = entry
+            .map_err(|cause| format!("Could not read entry of downloaded CLEO archive: {cause}"))?;
+
+        let is_executable = entry.path()
+            .map(|path| path == executable_path)
+            .unwrap_or(false);
+
+        if is_executable {
+            let mut executable = Vec::new();
+            entry.read_to_end(&mut executable)
+                .map_err(|cause| format!("Could not extract CLEO executable from downloaded archive: {cause}"))?;
+            return Ok(executable);
+        }
+    }
+
+    Err(format!("Downloaded CLEO archive does not contain the executable '{}'.", executable_path.display()))
+}
+
+fn verify_signature(verifying_key: &VerifyingKey, executable: &[u8], signature: &[u8]) -> Result<(), String> {
+    let signature = Signature::from_slice(signature)
+        .map_err(|cause| format!("Signature of downloaded CLEO executable is malformed: {cause}"))?;
+
+    verifying_key.verify(executable, &signature)
+        .map_err(|_| String::from("Signature of downloaded CLEO executable is invalid! Not installing the update."))
+}
+
+/// Writes the new executable next to the current one and renames it into place,
+/// so that the executable is never left in a partially written state.
+fn replace_executable(current_executable: &Path, executable: &[u8]) -> Result<(), String> {
+    let temporary_executable = {
+        let mut file_name = current_executable.file_name()
+            .ok_or_else(|| format!("Path of the current CLEO executable '{}' has no file name.", current_executable.display()))?
+            .to_owned();
+        file_name.push(".update");
+        PathBuf::from(current_executable).with_file_name(file_name)
+    };
+
+    let write_temporary_executable = || -> std::io::Result<()> {
+        fs::write(&temporary_executable, executable)?;
+        fs::set_permissions(&temporary_executable, fs::Permissions::from_mode(0o755))?;
+        fs::rename(&temporary_executable, current_executable)
+    };
+
+    write_temporary_executable()
+        .map_err(|cause| {
+            let _ = fs::remove_file(&temporary_executable);
+            format!("Could not replace CLEO executable at '{}': {cause}", current_executable.display())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    #[test]
+    fn should_extract_executable_from_distribution_archive() -> anyhow::Result<()> {
+        let executable = b"cleo-executable".to_vec();
+        let archive = archive_with_executable(&executable)?;
+
+        let result = extract_executable(&archive).map_err(anyhow::Error::msg)?;
+
+        assert_eq!(result, executable);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_executable_with_invalid_signature() -> anyhow::Result<()> {
+        let signing_key = SigningKey::from_bytes(&[7; ed25519_dalek::SECRET_KEY_LENGTH]);
+        let executable = b"cleo-executable".to_vec();
+        let signature = signing_key.sign(&executable).to_bytes();
+
+        let verifying_key = parse_verifying_key(Some(BASE64_STANDARD.encode(signing_key.verifying_key().to_bytes())))
+            .map_err(anyhow::Error::msg)?;
+
+        assert!(verify_signature(&verifying_key, &executable, &signature).is_ok());
+        assert!(verify_signature(&verifying_key, b"tampered-executable", &signature).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_require_a_configured_public_key() {
+        assert!(parse_verifying_key(None).is_err());
+        assert!(parse_verifying_key(Some(String::new())).is_err());
+    }
+
+    fn archive_with_executable(executable: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(executable.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, Path::new(CLEO_IDENTIFIER).join(CLEO_IDENTIFIER), executable)?;
+
+        Ok(builder.into_inner()?.finish()?)
+    }
+}
@@ -0,0 +1,42 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccountName, ServiceAccountScope};
+
+use crate::commands::service_account::{format_scopes, SerializableServiceAccountInfo};
+use crate::CreateOutputFormat;
+
+/// Create a service account for automation, like CI pipelines, which authenticates with tokens issued by CARL
+#[derive(clap::Parser)]
+pub struct CreateServiceAccountCli {
+    ///Name of the service account, e.g. "ci-pipeline"
+    #[arg(short, long)]
+    name: ServiceAccountName,
+    ///Scope granted to the service account, one of: read, peers, clusters, snapshots, diagnostics
+    #[arg(short, long = "scope", required = true)]
+    scopes: Vec<ServiceAccountScope>,
+}
+
+impl CreateServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient, cleo_oidc_client_id: String, output: CreateOutputFormat) -> crate::Result<()> {
+        let name = self.name;
+
+        let service_account = carl.service_accounts.create_service_account(Clone::clone(&name), self.scopes, cleo_oidc_client_id).await
+            .map_err(|error| crate::Error::carl(format!("Could not create service account '{name}'."), error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created service account '{}' <{}> with scopes: {}", service_account.name, service_account.id, format_scopes(&service_account.scopes));
+                println!("Issue a token for it with 'opendut-cleo create service-account-token {}'.", service_account.id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializableServiceAccountInfo::from(service_account)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializableServiceAccountInfo::from(service_account)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ServiceAccountId;
+
+use crate::commands::service_account::{format_time, SerializableServiceAccountTokenInfo};
+use crate::CreateOutputFormat;
+
+/// Issue a token for a service account. Multiple tokens may be valid at the same time, so that they can be rotated.
+#[derive(clap::Parser)]
+pub struct CreateServiceAccountTokenCli {
+    ///ServiceAccountID
+    #[arg()]
+    id: Uuid,
+    ///Number of days, after which the token expires
+    #[arg(long, default_value_t = 90)]
+    validity_days: u64,
+    ///Only allow listing, retrieving and observing resources with the token, regardless of the scopes of the service account
+    #[arg(long)]
+    read_only: bool,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableIssuedServiceAccountToken {
+    #[serde(flatten)]
+    token: SerializableServiceAccountTokenInfo,
+    credentials: String,
+}
+
+impl CreateServiceAccountTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id);
+        let validity = Duration::from_secs(self.validity_days.saturating_mul(24 * 60 * 60));
+
+        let issued = carl.service_accounts.issue_service_account_token(id, validity, self.read_only).await
+            .map_err(|error| crate::Error::carl(format!("Could not issue token for service account <{id}>."), error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("{}", issued.credentials);
+                eprintln!("Issued token <{}> for service account <{id}>, which expires at {}.", issued.token.id, format_time(issued.token.expires_at));
+                eprintln!("The token cannot be retrieved from CARL again, so store it safely, e.g. as secret of the CI pipeline.");
+            }
+            CreateOutputFormat::Json => {
+                let issued = SerializableIssuedServiceAccountToken { token: issued.token.into(), credentials: issued.credentials };
+                let json = serde_json::to_string(&issued).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let issued = SerializableIssuedServiceAccountToken { token: issued.token.into(), credentials: issued.credentials };
+                let json = serde_json::to_string_pretty(&issued).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ServiceAccountId;
+
+/// Delete a service account, which also revokes all of its tokens
+#[derive(clap::Parser)]
+pub struct DeleteServiceAccountCli {
+    ///ServiceAccountID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id);
+        carl.service_accounts.delete_service_account(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not delete service account <{id}>."), error))?;
+        println!("Deleted service account <{id}>.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccountId, ServiceAccountTokenId};
+
+/// Revoke a token of a service account, so that it cannot be used anymore
+#[derive(clap::Parser)]
+pub struct DeleteServiceAccountTokenCli {
+    ///ServiceAccountID
+    #[arg()]
+    id: Uuid,
+    ///ID of the token
+    #[arg()]
+    token_id: Uuid,
+}
+
+impl DeleteServiceAccountTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id);
+        let token_id = ServiceAccountTokenId::from(self.token_id);
+        carl.service_accounts.revoke_service_account_token(id, token_id).await
+            .map_err(|error| crate::Error::carl(format!("Could not revoke token <{token_id}> of service account <{id}>."), error))?;
+        println!("Revoked token <{token_id}> of service account <{id}>.");
+
+        Ok(())
+    }
+}
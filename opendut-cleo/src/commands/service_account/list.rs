@@ -0,0 +1,77 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccountId, ServiceAccountName};
+
+use crate::commands::service_account::{format_scopes, format_time, SerializableServiceAccountInfo};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// List all service accounts with their tokens
+#[derive(clap::Parser)]
+pub struct ListServiceAccountsCli;
+
+#[derive(Table)]
+struct ServiceAccountTable {
+    #[table(title = "Name")]
+    name: ServiceAccountName,
+    #[table(title = "ServiceAccountID")]
+    id: ServiceAccountId,
+    #[table(title = "Scopes")]
+    scopes: String,
+    #[table(title = "Created By")]
+    created_by: String,
+    #[table(title = "Tokens")]
+    tokens: String,
+}
+
+impl ListServiceAccountsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let service_accounts = carl.service_accounts.list_service_accounts().await
+            .map_err(|error| format!("Could not list service accounts.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let service_account_table = service_accounts.into_iter()
+                    .map(|service_account| {
+                        let tokens = service_account.tokens.iter()
+                            .map(|token| {
+                                let read_only = if token.read_only { ", read-only" } else { "" };
+                                format!("{} (expires {}{read_only})", token.id, format_time(token.expires_at))
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        ServiceAccountTable {
+                            name: service_account.name,
+                            id: service_account.id,
+                            scopes: format_scopes(&service_account.scopes),
+                            created_by: service_account.created_by,
+                            tokens,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(service_account_table.with_title())
+                    .expect("List of service accounts should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let service_accounts = service_accounts.into_iter().map(SerializableServiceAccountInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&service_accounts).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let service_accounts = service_accounts.into_iter().map(SerializableServiceAccountInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&service_accounts).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let service_accounts = service_accounts.into_iter().map(SerializableServiceAccountInfo::from).collect::<Vec<_>>();
+                export::print(&service_accounts, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let service_accounts = service_accounts.into_iter().map(SerializableServiceAccountInfo::from).collect::<Vec<_>>();
+                export::print(&service_accounts, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use opendut_types::service_account::ServiceAccountScope;
+
+pub mod create;
+pub mod create_token;
+pub mod delete;
+pub mod delete_token;
+pub mod list;
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn format_scopes<'a>(scopes: impl IntoIterator<Item=&'a ServiceAccountScope>) -> String {
+    scopes.into_iter()
+        .map(ServiceAccountScope::name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(serde::Serialize)]
+struct SerializableServiceAccountInfo {
+    id: String,
+    name: String,
+    scopes: String,
+    created_by: String,
+    created_at: String,
+    tokens: Vec<SerializableServiceAccountTokenInfo>,
+}
+
+impl From<opendut_carl_api::carl::service_account::ServiceAccountInfo> for SerializableServiceAccountInfo {
+    fn from(service_account: opendut_carl_api::carl::service_account::ServiceAccountInfo) -> Self {
+        Self {
+            id: service_account.id.to_string(),
+            name: service_account.name.to_string(),
+            scopes: format_scopes(&service_account.scopes),
+            created_by: service_account.created_by,
+            created_at: format_time(service_account.created_at),
+            tokens: service_account.tokens.into_iter().map(SerializableServiceAccountTokenInfo::from).collect(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SerializableServiceAccountTokenInfo {
+    id: String,
+    created_at: String,
+    expires_at: String,
+    read_only: bool,
+}
+
+impl From<opendut_carl_api::carl::service_account::ServiceAccountTokenInfo> for SerializableServiceAccountTokenInfo {
+    fn from(token: opendut_carl_api::carl::service_account::ServiceAccountTokenInfo) -> Self {
+        Self {
+            id: token.id.to_string(),
+            created_at: format_time(token.created_at),
+            expires_at: format_time(token.expires_at),
+            read_only: token.read_only,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::snapshot::SerializableSnapshotInfo;
+use crate::parse::snapshot::ParseableSnapshotName;
+use crate::CreateOutputFormat;
+
+/// Create a snapshot of all peers, peer groups, cluster configurations and cluster deployments
+#[derive(clap::Parser)]
+pub struct CreateSnapshotCli {
+    ///Name of the snapshot, e.g. "before holiday shutdown"
+    #[arg(short, long)]
+    name: ParseableSnapshotName,
+}
+
+impl CreateSnapshotCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let ParseableSnapshotName(name) = self.name;
+
+        let snapshot = carl.snapshots.create_snapshot(Clone::clone(&name)).await
+            .map_err(|error| crate::Error::carl(format!("Could not create snapshot '{name}'."), error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created snapshot '{}' <{}> with {} resources.", snapshot.name, snapshot.id, snapshot.resource_count);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializableSnapshotInfo::from(snapshot)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializableSnapshotInfo::from(snapshot)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
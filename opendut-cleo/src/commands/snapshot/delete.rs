@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::snapshot::SnapshotId;
+
+/// Delete a snapshot. The resources contained in the snapshot are not affected.
+#[derive(clap::Parser)]
+pub struct DeleteSnapshotCli {
+    ///SnapshotID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteSnapshotCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = SnapshotId::from(self.id);
+        carl.snapshots.delete_snapshot(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not delete snapshot <{id}>."), error))?;
+        println!("Deleted snapshot <{id}>.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::snapshot::{SnapshotChange, SnapshotId, SnapshotResourceKind};
+
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// Compare a snapshot with another snapshot or with the current resources
+#[derive(clap::Parser)]
+pub struct DiffSnapshotsCli {
+    ///SnapshotID of the older snapshot
+    #[arg()]
+    from: Uuid,
+    ///SnapshotID of the newer snapshot. Compares with the current resources, if not specified.
+    #[arg(long)]
+    to: Option<Uuid>,
+}
+
+#[derive(Table)]
+struct SnapshotDiffTable {
+    #[table(title = "Change")]
+    change: SnapshotChange,
+    #[table(title = "Kind")]
+    kind: SnapshotResourceKind,
+    #[table(title = "Name")]
+    name: String,
+    #[table(title = "ID")]
+    id: Uuid,
+}
+
+impl DiffSnapshotsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let from = SnapshotId::from(self.from);
+        let to = self.to.map(SnapshotId::from);
+
+        let entries = carl.snapshots.diff_snapshots(from, to).await
+            .map_err(|error| crate::Error::carl(format!("Could not compare snapshot <{from}>."), error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                if entries.is_empty() {
+                    println!("No differences found.");
+                    return Ok(());
+                }
+                let diff_table = entries.into_iter()
+                    .map(|entry| {
+                        SnapshotDiffTable {
+                            change: entry.change,
+                            kind: entry.resource.kind,
+                            name: entry.name,
+                            id: entry.resource.id,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(diff_table.with_title())
+                    .expect("Differences of snapshots should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&entries).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&entries).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                export::print(&entries, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                export::print(&entries, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
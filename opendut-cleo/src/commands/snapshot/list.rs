@@ -0,0 +1,67 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::snapshot::{SnapshotId, SnapshotName};
+
+use crate::commands::snapshot::{format_created_at, SerializableSnapshotInfo};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// List all snapshots
+#[derive(clap::Parser)]
+pub struct ListSnapshotsCli;
+
+#[derive(Table)]
+struct SnapshotTable {
+    #[table(title = "Name")]
+    name: SnapshotName,
+    #[table(title = "SnapshotID")]
+    id: SnapshotId,
+    #[table(title = "Created")]
+    created_at: String,
+    #[table(title = "Resources")]
+    resource_count: usize,
+}
+
+impl ListSnapshotsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let snapshots = carl.snapshots.list_snapshots().await
+            .map_err(|error| format!("Could not list snapshots.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let snapshot_table = snapshots.into_iter()
+                    .map(|snapshot| {
+                        SnapshotTable {
+                            name: snapshot.name,
+                            id: snapshot.id,
+                            created_at: format_created_at(snapshot.created_at),
+                            resource_count: snapshot.resource_count,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(snapshot_table.with_title())
+                    .expect("List of snapshots should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let snapshots = snapshots.into_iter().map(SerializableSnapshotInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&snapshots).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let snapshots = snapshots.into_iter().map(SerializableSnapshotInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&snapshots).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let snapshots = snapshots.into_iter().map(SerializableSnapshotInfo::from).collect::<Vec<_>>();
+                export::print(&snapshots, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let snapshots = snapshots.into_iter().map(SerializableSnapshotInfo::from).collect::<Vec<_>>();
+                export::print(&snapshots, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+pub mod create;
+pub mod delete;
+pub mod diff;
+pub mod list;
+pub mod restore;
+
+fn format_created_at(created_at: SystemTime) -> String {
+    DateTime::<Utc>::from(created_at).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(serde::Serialize)]
+struct SerializableSnapshotInfo {
+    id: String,
+    name: String,
+    created_at: String,
+    resource_count: usize,
+}
+
+impl From<opendut_carl_api::carl::snapshot::SnapshotInfo> for SerializableSnapshotInfo {
+    fn from(snapshot: opendut_carl_api::carl::snapshot::SnapshotInfo) -> Self {
+        Self {
+            id: snapshot.id.to_string(),
+            name: snapshot.name.value(),
+            created_at: format_created_at(snapshot.created_at),
+            resource_count: snapshot.resource_count,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::snapshot::SnapshotId;
+
+use crate::parse::snapshot::ParseableSnapshotResourceReference;
+
+/// Restore resources from a snapshot. Resources created after the snapshot are not deleted.
+#[derive(clap::Parser)]
+pub struct RestoreSnapshotCli {
+    ///SnapshotID
+    #[arg()]
+    id: Uuid,
+    ///Resource to restore as 'kind:id', e.g. 'peer-group:<PeerGroupID>'. Restores all resources of the snapshot, if not specified.
+    ///Kinds: peer, peer-group, cluster-configuration, cluster-deployment
+    #[arg(long = "resource", num_args = 0..)]
+    resources: Vec<ParseableSnapshotResourceReference>,
+}
+
+impl RestoreSnapshotCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = SnapshotId::from(self.id);
+        let resources = self.resources.into_iter()
+            .map(|ParseableSnapshotResourceReference(resource)| resource)
+            .collect::<Vec<_>>();
+
+        let restored = carl.snapshots.restore_snapshot(id, resources).await
+            .map_err(|error| crate::Error::carl(format!("Could not restore snapshot <{id}>."), error))?;
+
+        println!("Restored {} resources from snapshot <{id}>:", restored.len());
+        for resource in restored {
+            println!("  - {resource}");
+        }
+
+        Ok(())
+    }
+}
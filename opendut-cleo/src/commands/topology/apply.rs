@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::topology::plan::{Change, CurrentState, Plan};
+use crate::commands::topology::TopologyFile;
+
+/// Apply a declarative topology of peers, devices and clusters from a YAML or JSON file
+#[derive(clap::Args)]
+pub struct ApplyTopologyCli {
+    ///Path to the topology file. Only the peers, devices and clusters, which differ from CARL, are changed
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    ///Only print the planned changes, without applying them
+    #[arg(long, requires = "file")]
+    dry_run: bool,
+    ///Delete peers and clusters, which are not described in the topology file
+    #[arg(long, requires = "file")]
+    prune: bool,
+}
+
+impl ApplyTopologyCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let file = self.file
+            .ok_or("Specify a topology file via --file.")?;
+        let content = std::fs::read_to_string(&file)
+            .map_err(|cause| format!("Failed to open file '{}': {}", file.display(), cause))?;
+        let topology = TopologyFile::parse(&content)
+            .map_err(|cause| format!("Failed to read topology from '{}':\n  {cause}", file.display()))?;
+
+        let current = CurrentState {
+            peers: carl.peers.list_peer_descriptors().await
+                .map_err(|error| format!("Could not list peers.\n  {error}"))?,
+            clusters: carl.cluster.list_cluster_configurations().await
+                .map_err(|error| format!("Could not list cluster configurations.\n  {error}"))?,
+            deployed_clusters: carl.cluster.list_cluster_deployments().await
+                .map_err(|error| format!("Could not list cluster deployments.\n  {error}"))?
+                .into_iter()
+                .map(|deployment| deployment.id)
+                .collect(),
+        };
+
+        let plan = Plan::create(topology, &current, self.prune)?;
+        println!("{plan}");
+
+        if self.dry_run || plan.is_empty() {
+            return Ok(());
+        }
+
+        println!();
+        for change in plan.changes {
+            apply_change(carl, change).await?;
+        }
+        println!("Applied the topology from '{}'.", file.display());
+        Ok(())
+    }
+}
+
+/// Applies a single change and stops at the first failure, as later changes may depend on it.
+async fn apply_change(carl: &mut CarlClient, change: Change) -> crate::Result<()> {
+    match change {
+        Change::CreatePeer(peer) | Change::UpdatePeer { desired: peer, .. } => {
+            let label = format!("peer '{}' <{}>", peer.name, peer.id);
+            carl.peers.store_peer_descriptor(peer).await
+                .map_err(|error| crate::Error::carl(format!("Failed to store {label}."), error))?;
+            println!("  Stored {label}");
+        }
+        Change::DeletePeer(peer) => {
+            let label = format!("peer '{}' <{}>", peer.name, peer.id);
+            carl.peers.delete_peer_descriptor(peer.id).await
+                .map_err(|error| crate::Error::carl(format!("Failed to delete {label}."), error))?;
+            println!("  Deleted {label}");
+        }
+        Change::CreateCluster(cluster) | Change::UpdateCluster { desired: cluster, .. } => {
+            let label = format!("cluster '{}' <{}>", cluster.name, cluster.id);
+            carl.cluster.store_cluster_configuration(cluster).await
+                .map_err(|error| crate::Error::carl(format!("Failed to store {label}."), error))?;
+            println!("  Stored {label}");
+        }
+        Change::DeleteCluster(cluster) => {
+            let label = format!("cluster '{}' <{}>", cluster.name, cluster.id);
+            carl.cluster.delete_cluster_configuration(cluster.id).await
+                .map_err(|error| crate::Error::carl(format!("Failed to delete {label}."), error))?;
+            println!("  Deleted {label}");
+        }
+    }
+    Ok(())
+}
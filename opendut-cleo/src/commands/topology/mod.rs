@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+pub mod apply;
+pub mod plan;
+
+/// Declarative description of peers, their devices and clusters, as read by `apply --file`.
+/// Resources are identified by their names, so that no IDs need to be written by hand.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TopologyFile {
+    #[serde(default)]
+    pub peers: Vec<PeerSpec>,
+    #[serde(default)]
+    pub clusters: Vec<ClusterSpec>,
+}
+
+impl TopologyFile {
+    /// Parses YAML, which includes JSON.
+    pub fn parse(content: &str) -> crate::Result<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|cause| format!("Failed to parse topology: {cause}").into())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PeerSpec {
+    pub name: String,
+    pub location: Option<String>,
+    pub bridge_name: Option<String>,
+    #[serde(default)]
+    pub network_interfaces: Vec<NetworkInterfaceSpec>,
+    #[serde(default)]
+    pub devices: Vec<DeviceSpec>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkInterfaceSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: NetworkInterfaceKindSpec,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum NetworkInterfaceKindSpec {
+    Ethernet,
+    #[serde(rename_all = "kebab-case")]
+    Can {
+        bitrate: u32,
+        sample_point: f32,
+        fd: bool,
+        data_bitrate: u32,
+        data_sample_point: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DeviceSpec {
+    pub name: String,
+    ///Name of the network interface of the peer, via which the device is connected
+    pub interface: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ClusterSpec {
+    pub name: String,
+    ///Name of the leading peer
+    pub leader: String,
+    ///Names of the devices
+    #[serde(default)]
+    pub devices: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_topology() -> anyhow::Result<()> {
+        let content = r#"
+peers:
+  - name: peer-a
+    location: Ulm
+    network-interfaces:
+      - name: eth0
+        kind: ethernet
+      - name: can0
+        kind: can
+        bitrate: 500000
+        sample-point: 0.7
+        fd: true
+        data-bitrate: 2000000
+        data-sample-point: 0.7
+    devices:
+      - name: ecu-a
+        interface: can0
+        tags: [ecu]
+    labels:
+      project: braking
+clusters:
+  - name: cluster-a
+    leader: peer-a
+    devices: [ecu-a]
+"#;
+
+        let topology = TopologyFile::parse(content).map_err(|error| anyhow::anyhow!("{error}"))?;
+
+        assert_that!(topology.peers.len(), eq(1));
+        let NetworkInterfaceKindSpec::Can { bitrate, .. } = topology.peers[0].network_interfaces[1].kind else {
+            panic!("Expected a CAN interface, but got: {:?}", topology.peers[0].network_interfaces[1].kind);
+        };
+        assert_that!(bitrate, eq(500000));
+        assert_that!(topology.peers[0].devices[0].interface, eq("can0"));
+        assert_that!(topology.clusters[0].devices, elements_are![eq("ecu-a")]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_fields() {
+        let content = "peers:\n  - name: peer-a\n    colour: blue\n";
+
+        assert!(TopologyFile::parse(content).is_err());
+    }
+}
@@ -0,0 +1,562 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::peer::executor::ExecutorDescriptors;
+use opendut_types::peer::label::{LabelKey, LabelValue, PeerLabels};
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
+use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+use crate::commands::topology::{ClusterSpec, DeviceSpec, NetworkInterfaceKindSpec, PeerSpec, TopologyFile};
+
+/// Resources as currently stored in CARL.
+#[derive(Clone, Debug, Default)]
+pub struct CurrentState {
+    pub peers: Vec<PeerDescriptor>,
+    pub clusters: Vec<ClusterConfiguration>,
+    pub deployed_clusters: HashSet<ClusterId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    CreatePeer(PeerDescriptor),
+    UpdatePeer { current: PeerDescriptor, desired: PeerDescriptor },
+    DeletePeer(PeerDescriptor),
+    CreateCluster(ClusterConfiguration),
+    UpdateCluster { current: ClusterConfiguration, desired: ClusterConfiguration },
+    DeleteCluster(ClusterConfiguration),
+}
+
+/// Changes required to bring CARL to the state described in a topology file, in the order in which they need to be applied.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Plan {
+    pub changes: Vec<Change>,
+    /// Names of devices, to describe the changes of clusters.
+    device_names: HashMap<DeviceId, DeviceName>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Determines the changes from the current state to the topology.
+    /// Peers and clusters, which are not described in the topology, are only deleted when `prune` is set.
+    pub fn create(topology: TopologyFile, current: &CurrentState, prune: bool) -> crate::Result<Self> {
+        let mut errors = Vec::new();
+
+        let mut desired_peers = Vec::new();
+        for spec in topology.peers {
+            if desired_peers.iter().any(|(_, peer): &(Option<PeerDescriptor>, PeerDescriptor)| peer.name.to_string() == spec.name) {
+                errors.push(format!("Peer '{}' is described more than once.", spec.name));
+                continue;
+            }
+            let existing = match find_by_name(&current.peers, |peer| peer.name.to_string() == spec.name) {
+                Ok(existing) => existing,
+                Err(count) => {
+                    errors.push(format!("Peer '{}' is ambiguous, as {count} peers with that name exist in CARL.", spec.name));
+                    continue;
+                }
+            };
+            match desired_peer(spec, existing) {
+                Ok(desired) => desired_peers.push((existing.cloned(), desired)),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        let undescribed_peers = current.peers.iter()
+            .filter(|peer| desired_peers.iter().any(|(_, desired)| desired.id == peer.id).not())
+            .collect::<Vec<_>>();
+        let referenceable_peers = desired_peers.iter()
+            .map(|(_, desired)| desired)
+            .chain(undescribed_peers.iter().cloned().filter(|_| prune.not()))
+            .collect::<Vec<_>>();
+
+        let mut desired_clusters = Vec::new();
+        for spec in topology.clusters {
+            if desired_clusters.iter().any(|(_, cluster): &(Option<ClusterConfiguration>, ClusterConfiguration)| cluster.name.to_string() == spec.name) {
+                errors.push(format!("Cluster '{}' is described more than once.", spec.name));
+                continue;
+            }
+            let existing = match find_by_name(&current.clusters, |cluster| cluster.name.to_string() == spec.name) {
+                Ok(existing) => existing,
+                Err(count) => {
+                    errors.push(format!("Cluster '{}' is ambiguous, as {count} clusters with that name exist in CARL.", spec.name));
+                    continue;
+                }
+            };
+            match desired_cluster(spec, existing, &referenceable_peers) {
+                Ok(desired) => desired_clusters.push((existing.cloned(), desired)),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        let described_clusters = desired_clusters.iter()
+            .map(|(_, desired)| desired.id)
+            .collect::<HashSet<_>>();
+
+        let mut changes = Vec::new();
+
+        for (existing, desired) in desired_peers {
+            match existing {
+                None => changes.push(Change::CreatePeer(desired)),
+                Some(current) if peer_details(Some(&current), &desired).is_empty() => {}
+                Some(current) => changes.push(Change::UpdatePeer { current, desired }),
+            }
+        }
+        for (existing, desired) in desired_clusters {
+            match existing {
+                None => changes.push(Change::CreateCluster(desired)),
+                Some(current) if current == desired => {}
+                Some(current) => changes.push(Change::UpdateCluster { current, desired }),
+            }
+        }
+        if prune {
+            for cluster in &current.clusters {
+                if described_clusters.contains(&cluster.id).not() {
+                    changes.push(Change::DeleteCluster(Clone::clone(cluster)));
+                }
+            }
+            for peer in undescribed_peers {
+                changes.push(Change::DeletePeer(Clone::clone(peer)));
+            }
+        }
+
+        for change in &changes {
+            if let Change::UpdateCluster { current: cluster, .. } | Change::DeleteCluster(cluster) = change {
+                if current.deployed_clusters.contains(&cluster.id) {
+                    errors.push(format!("Cluster '{}' <{}> is deployed and cannot be changed. Delete its deployment first.", cluster.name, cluster.id));
+                }
+            }
+        }
+
+        if errors.is_empty().not() {
+            return Err(format!("The topology cannot be applied:\n  {}", errors.join("\n  ")).into());
+        }
+
+        let device_names = current.peers.iter()
+            .chain(changes.iter().filter_map(|change| match change {
+                Change::CreatePeer(peer) | Change::UpdatePeer { desired: peer, .. } => Some(peer),
+                _ => None,
+            }))
+            .flat_map(|peer| peer.topology.devices.iter())
+            .map(|device| (device.id, Clone::clone(&device.name)))
+            .collect();
+
+        Ok(Plan { changes, device_names })
+    }
+
+    pub fn count(&self) -> (usize, usize, usize) {
+        let created = self.changes.iter().filter(|change| matches!(change, Change::CreatePeer(_) | Change::CreateCluster(_))).count();
+        let updated = self.changes.iter().filter(|change| matches!(change, Change::UpdatePeer { .. } | Change::UpdateCluster { .. })).count();
+        let deleted = self.changes.len() - created - updated;
+        (created, updated, deleted)
+    }
+
+    fn device_name(&self, device_id: &DeviceId) -> String {
+        self.device_names.get(device_id)
+            .map(|name| format!("'{name}'"))
+            .unwrap_or_else(|| format!("<{device_id}>"))
+    }
+}
+
+/// Returns the single element matching the predicate, or the number of matching elements, if there are more than one.
+fn find_by_name<T>(elements: &[T], predicate: impl Fn(&T) -> bool) -> Result<Option<&T>, usize> {
+    let matching = elements.iter().filter(|element| predicate(element)).collect::<Vec<_>>();
+    match matching.as_slice() {
+        [] => Ok(None),
+        [element] => Ok(Some(element)),
+        _ => Err(matching.len()),
+    }
+}
+
+fn desired_peer(spec: PeerSpec, existing: Option<&PeerDescriptor>) -> Result<PeerDescriptor, String> {
+    let PeerSpec { name, location, bridge_name, network_interfaces, devices, labels } = spec;
+    let invalid = |cause: String| format!("Peer '{name}' is invalid: {cause}");
+
+    let peer_name = PeerName::try_from(Clone::clone(&name))
+        .map_err(|cause| invalid(cause.to_string()))?;
+    let location = location
+        .map(PeerLocation::try_from)
+        .transpose()
+        .map_err(|cause| invalid(cause.to_string()))?;
+    let bridge_name = bridge_name
+        .map(NetworkInterfaceName::try_from)
+        .transpose()
+        .map_err(|cause| invalid(cause.to_string()))?;
+
+    let mut interfaces: Vec<NetworkInterfaceDescriptor> = Vec::new();
+    for spec in network_interfaces {
+        let interface_name = NetworkInterfaceName::try_from(spec.name)
+            .map_err(|cause| invalid(cause.to_string()))?;
+        if interfaces.iter().any(|interface| interface.name == interface_name) {
+            return Err(invalid(format!("Network interface '{interface_name}' is described more than once.")));
+        }
+        let id = existing
+            .and_then(|peer| peer.network.interfaces.iter().find(|interface| interface.name == interface_name))
+            .map(|interface| interface.id)
+            .unwrap_or_else(NetworkInterfaceId::random);
+        let configuration = network_interface_configuration(spec.kind)
+            .map_err(|cause| invalid(format!("Network interface '{interface_name}' is invalid: {cause}")))?;
+        interfaces.push(NetworkInterfaceDescriptor { id, name: interface_name, configuration });
+    }
+
+    let mut topology_devices: Vec<DeviceDescriptor> = Vec::new();
+    for spec in devices {
+        let DeviceSpec { name: device_name, interface, description, tags } = spec;
+        let device_name = DeviceName::try_from(device_name)
+            .map_err(|cause| invalid(cause.to_string()))?;
+        if topology_devices.iter().any(|device| device.name == device_name) {
+            return Err(invalid(format!("Device '{device_name}' is described more than once.")));
+        }
+        let interface = interfaces.iter()
+            .find(|candidate| candidate.name.name() == interface)
+            .ok_or_else(|| invalid(format!("Device '{device_name}' refers to network interface '{interface}', which is not described for the peer.")))?;
+        let id = existing
+            .and_then(|peer| peer.topology.devices.iter().find(|device| device.name == device_name))
+            .map(|device| device.id)
+            .unwrap_or_else(DeviceId::random);
+        let description = description
+            .map(DeviceDescription::try_from)
+            .transpose()
+            .map_err(|cause| invalid(cause.to_string()))?;
+        let tags = tags.into_iter()
+            .map(DeviceTag::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| invalid(cause.to_string()))?;
+        topology_devices.push(DeviceDescriptor { id, name: device_name, description, interface: interface.id, tags });
+    }
+
+    let labels = labels.into_iter()
+        .map(|(key, value)| {
+            let key = LabelKey::try_from(key).map_err(|cause| invalid(cause.to_string()))?;
+            let value = LabelValue::try_from(value).map_err(|cause| invalid(cause.to_string()))?;
+            Ok((key, value))
+        })
+        .collect::<Result<PeerLabels, String>>()?;
+
+    //executors, annotations, hardware capabilities and cluster hooks are not described in the topology, so keep them
+    Ok(PeerDescriptor {
+        id: existing.map(|peer| peer.id).unwrap_or_else(PeerId::random),
+        name: peer_name,
+        location,
+        network: PeerNetworkDescriptor {
+            interfaces,
+            bridge_name,
+        },
+        topology: Topology { devices: topology_devices },
+        executors: existing.map(|peer| Clone::clone(&peer.executors)).unwrap_or(ExecutorDescriptors { executors: vec![] }),
+        labels,
+        annotations: existing.map(|peer| Clone::clone(&peer.annotations)).unwrap_or_default(),
+        hardware: existing.and_then(|peer| Clone::clone(&peer.hardware)),
+        cluster_hooks: existing.map(|peer| Clone::clone(&peer.cluster_hooks)).unwrap_or_default(),
+    })
+}
+
+fn network_interface_configuration(kind: NetworkInterfaceKindSpec) -> Result<NetworkInterfaceConfiguration, String> {
+    match kind {
+        NetworkInterfaceKindSpec::Ethernet => Ok(NetworkInterfaceConfiguration::Ethernet),
+        NetworkInterfaceKindSpec::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point } => {
+            Ok(NetworkInterfaceConfiguration::Can {
+                bitrate,
+                sample_point: CanSamplePoint::try_from(sample_point).map_err(|cause| cause.to_string())?,
+                fd,
+                data_bitrate,
+                data_sample_point: CanSamplePoint::try_from(data_sample_point).map_err(|cause| cause.to_string())?,
+            })
+        }
+    }
+}
+
+fn desired_cluster(spec: ClusterSpec, existing: Option<&ClusterConfiguration>, peers: &[&PeerDescriptor]) -> Result<ClusterConfiguration, String> {
+    let ClusterSpec { name, leader, devices } = spec;
+    let invalid = |cause: String| format!("Cluster '{name}' is invalid: {cause}");
+
+    let cluster_name = ClusterName::try_from(Clone::clone(&name))
+        .map_err(|cause| invalid(cause.to_string()))?;
+
+    let leader = match find_by_name(peers, |peer| peer.name.to_string() == leader) {
+        Ok(Some(peer)) => peer.id,
+        Ok(None) => return Err(invalid(format!("Leader '{leader}' is neither described in the topology nor exists in CARL."))),
+        Err(count) => return Err(invalid(format!("Leader '{leader}' is ambiguous, as {count} peers with that name exist."))),
+    };
+
+    let all_devices = peers.iter()
+        .flat_map(|peer| peer.topology.devices.iter())
+        .collect::<Vec<_>>();
+    let mut device_ids = HashSet::new();
+    for device in devices {
+        match find_by_name(&all_devices, |candidate| candidate.name.value() == device) {
+            Ok(Some(found)) => { device_ids.insert(found.id); }
+            Ok(None) => return Err(invalid(format!("Device '{device}' is neither described in the topology nor exists in CARL."))),
+            Err(count) => return Err(invalid(format!("Device '{device}' is ambiguous, as {count} devices with that name exist. Use unique device names."))),
+        }
+    }
+
+    //peer groups and device selectors are not described in the topology, so keep them
+    Ok(ClusterConfiguration {
+        id: existing.map(|cluster| cluster.id).unwrap_or_else(ClusterId::random),
+        name: cluster_name,
+        leader,
+        devices: device_ids,
+        peer_groups: existing.map(|cluster| Clone::clone(&cluster.peer_groups)).unwrap_or_default(),
+        device_selectors: existing.map(|cluster| Clone::clone(&cluster.device_selectors)).unwrap_or_default(),
+        dependencies: existing.map(|cluster| Clone::clone(&cluster.dependencies)).unwrap_or_default(),
+    })
+}
+
+/// Describes how the desired peer differs from the current one, one line per difference.
+fn peer_details(current: Option<&PeerDescriptor>, desired: &PeerDescriptor) -> Vec<String> {
+    let mut details = Vec::new();
+
+    let current_location = current.and_then(|peer| peer.location.as_ref());
+    if current_location != desired.location.as_ref() {
+        details.push(changed_value("location", current_location.map(ToString::to_string), desired.location.as_ref().map(ToString::to_string)));
+    }
+    let current_bridge_name = current.and_then(|peer| peer.network.bridge_name.as_ref());
+    if current_bridge_name != desired.network.bridge_name.as_ref() {
+        details.push(changed_value("bridge name", current_bridge_name.map(ToString::to_string), desired.network.bridge_name.as_ref().map(ToString::to_string)));
+    }
+
+    let current_interfaces = current.map(|peer| peer.network.interfaces.as_slice()).unwrap_or_default();
+    for interface in &desired.network.interfaces {
+        match current_interfaces.iter().find(|candidate| candidate.name == interface.name) {
+            None => details.push(format!("+ network interface '{}' ({})", interface.name, interface.configuration)),
+            Some(existing) if existing.configuration != interface.configuration => {
+                details.push(format!("~ network interface '{}': {} -> {}", interface.name, existing.configuration, interface.configuration));
+            }
+            Some(_) => {}
+        }
+    }
+    for interface in current_interfaces {
+        if desired.network.interfaces.iter().any(|candidate| candidate.name == interface.name).not() {
+            details.push(format!("- network interface '{}'", interface.name));
+        }
+    }
+
+    let interface_name = |peer: &PeerDescriptor, device: &DeviceDescriptor| peer.network.interfaces.iter()
+        .find(|interface| interface.id == device.interface)
+        .map(|interface| interface.name.name())
+        .unwrap_or_default();
+    let current_devices = current.map(|peer| peer.topology.devices.as_slice()).unwrap_or_default();
+    for device in &desired.topology.devices {
+        let desired_interface = interface_name(desired, device);
+        match current.zip(current_devices.iter().find(|candidate| candidate.name == device.name)) {
+            None => details.push(format!("+ device '{}' via '{desired_interface}'", device.name)),
+            Some((current, existing)) => {
+                let current_interface = interface_name(current, existing);
+                if current_interface != desired_interface || existing.description != device.description || existing.tags != device.tags {
+                    details.push(format!("~ device '{}'", device.name));
+                }
+            }
+        }
+    }
+    for device in current_devices {
+        if desired.topology.devices.iter().any(|candidate| candidate.name == device.name).not() {
+            details.push(format!("- device '{}'", device.name));
+        }
+    }
+
+    let current_labels = current.map(|peer| Clone::clone(&peer.labels)).unwrap_or_default();
+    for (key, value) in &desired.labels {
+        match current_labels.get(key) {
+            None => details.push(format!("+ label '{}={}'", key.value(), value.value())),
+            Some(existing) if existing != value => details.push(format!("~ label '{}': '{}' -> '{}'", key.value(), existing.value(), value.value())),
+            Some(_) => {}
+        }
+    }
+    for key in current_labels.keys() {
+        if desired.labels.contains_key(key).not() {
+            details.push(format!("- label '{}'", key.value()));
+        }
+    }
+
+    details
+}
+
+fn changed_value(field: &str, current: Option<String>, desired: Option<String>) -> String {
+    let format = |value: Option<String>| value.map(|value| format!("'{value}'")).unwrap_or_else(|| String::from("none"));
+    format!("~ {field}: {} -> {}", format(current), format(desired))
+}
+
+impl Plan {
+    fn cluster_details(&self, current: Option<&ClusterConfiguration>, desired: &ClusterConfiguration) -> Vec<String> {
+        let mut details = Vec::new();
+        if let Some(current) = current {
+            if current.leader != desired.leader {
+                details.push(format!("~ leader: <{}> -> <{}>", current.leader, desired.leader));
+            }
+        }
+        let current_devices = current.map(|cluster| Clone::clone(&cluster.devices)).unwrap_or_default();
+        let mut added = desired.devices.difference(&current_devices).map(|device| self.device_name(device)).collect::<Vec<_>>();
+        let mut removed = current_devices.difference(&desired.devices).map(|device| self.device_name(device)).collect::<Vec<_>>();
+        added.sort();
+        removed.sort();
+        details.extend(added.into_iter().map(|device| format!("+ device {device}")));
+        details.extend(removed.into_iter().map(|device| format!("- device {device}")));
+        details
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes. CARL matches the topology.");
+        }
+
+        writeln!(f, "The following changes will be applied:")?;
+        for change in &self.changes {
+            let (headline, details) = match change {
+                Change::CreatePeer(peer) => (format!("+ create peer '{}'", peer.name), peer_details(None, peer)),
+                Change::UpdatePeer { current, desired } => (format!("~ update peer '{}' <{}>", desired.name, desired.id), peer_details(Some(current), desired)),
+                Change::DeletePeer(peer) => (format!("- delete peer '{}' <{}>", peer.name, peer.id), Vec::new()),
+                Change::CreateCluster(cluster) => (format!("+ create cluster '{}'", cluster.name), self.cluster_details(None, cluster)),
+                Change::UpdateCluster { current, desired } => (format!("~ update cluster '{}' <{}>", desired.name, desired.id), self.cluster_details(Some(current), desired)),
+                Change::DeleteCluster(cluster) => (format!("- delete cluster '{}' <{}>", cluster.name, cluster.id), Vec::new()),
+            };
+            writeln!(f, "  {headline}")?;
+            for detail in details {
+                writeln!(f, "      {detail}")?;
+            }
+        }
+        let (created, updated, deleted) = self.count();
+        writeln!(f)?;
+        write!(f, "Plan: {created} to create, {updated} to update, {deleted} to delete.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn topology() -> TopologyFile {
+        TopologyFile::parse(r#"
+peers:
+  - name: peer-a
+    location: Ulm
+    network-interfaces:
+      - name: eth0
+        kind: ethernet
+    devices:
+      - name: ecu-a
+        interface: eth0
+  - name: peer-b
+    network-interfaces:
+      - name: eth0
+        kind: ethernet
+    devices:
+      - name: ecu-b
+        interface: eth0
+clusters:
+  - name: cluster-a
+    leader: peer-a
+    devices: [ecu-a, ecu-b]
+"#).unwrap()
+    }
+
+    /// The state of CARL after applying the plan.
+    fn applied(plan: Plan) -> CurrentState {
+        let mut state = CurrentState::default();
+        for change in plan.changes {
+            match change {
+                Change::CreatePeer(peer) | Change::UpdatePeer { desired: peer, .. } => state.peers.push(peer),
+                Change::CreateCluster(cluster) | Change::UpdateCluster { desired: cluster, .. } => state.clusters.push(cluster),
+                Change::DeletePeer(_) | Change::DeleteCluster(_) => {}
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn should_create_all_resources_in_empty_carl() -> anyhow::Result<()> {
+        let plan = Plan::create(topology(), &CurrentState::default(), false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+
+        assert_that!(plan.count(), eq((3, 0, 0)));
+        let Some(Change::CreateCluster(cluster)) = plan.changes.last() else {
+            panic!("Expected the cluster to be created last, but got: {:?}", plan.changes);
+        };
+        assert_that!(cluster.devices.len(), eq(2));
+        Ok(())
+    }
+
+    #[test]
+    fn should_plan_no_changes_when_carl_matches_topology() -> anyhow::Result<()> {
+        let plan = Plan::create(topology(), &CurrentState::default(), false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+        let state = applied(plan);
+
+        let plan = Plan::create(topology(), &state, true)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+
+        assert!(plan.is_empty(), "Expected no changes, but got: {plan}");
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_update_what_differs_and_keep_ids() -> anyhow::Result<()> {
+        let state = applied(Plan::create(topology(), &CurrentState::default(), false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?);
+        let mut changed_topology = topology();
+        changed_topology.peers[0].location = Some(String::from("Berlin"));
+        changed_topology.clusters[0].devices.pop();
+
+        let plan = Plan::create(changed_topology, &state, false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+
+        assert_that!(plan.count(), eq((0, 2, 0)));
+        let Change::UpdatePeer { current, desired } = &plan.changes[0] else {
+            panic!("Expected peer to be updated, but got: {:?}", plan.changes[0]);
+        };
+        assert_that!(desired.id, eq(current.id));
+        assert_that!(desired.topology.devices[0].id, eq(current.topology.devices[0].id));
+        assert_that!(plan.to_string(), contains_substring("~ location: 'Ulm' -> 'Berlin'"));
+        assert_that!(plan.to_string(), contains_substring("- device 'ecu-b'"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_delete_undescribed_resources_when_pruning() -> anyhow::Result<()> {
+        let state = applied(Plan::create(topology(), &CurrentState::default(), false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?);
+        let mut reduced_topology = topology();
+        reduced_topology.clusters.clear();
+
+        let plan = Plan::create(reduced_topology.clone(), &state, false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+        assert!(plan.is_empty(), "Expected no changes, but got: {plan}");
+
+        let plan = Plan::create(reduced_topology, &state, true)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+        assert!(matches!(plan.changes.as_slice(), [Change::DeleteCluster(_)]), "Expected the cluster to be deleted, but got: {plan}");
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_changes_to_deployed_clusters() -> anyhow::Result<()> {
+        let mut state = applied(Plan::create(topology(), &CurrentState::default(), false)
+            .map_err(|error| anyhow::anyhow!("{error}"))?);
+        state.deployed_clusters.insert(state.clusters[0].id);
+        let mut changed_topology = topology();
+        changed_topology.clusters[0].devices.pop();
+
+        let result = Plan::create(changed_topology, &state, false);
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_references_to_unknown_devices() {
+        let mut topology = topology();
+        topology.clusters[0].devices.push(String::from("ecu-unknown"));
+
+        let result = Plan::create(topology, &CurrentState::default(), false);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::diagnostics::{TraceCaptureLevel, TraceCaptureScope};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+
+use crate::commands::trace_capture::{format_timestamp, SerializableTraceCaptureInfo};
+use crate::CreateOutputFormat;
+
+/// Temporarily raise CARL's log verbosity for a single peer, cluster or gRPC method and capture the resulting records
+#[derive(clap::Parser)]
+pub struct CreateTraceCaptureCli {
+    #[command(flatten)]
+    scope: TraceCaptureScopeArgs,
+    ///Level up to which records are captured
+    #[arg(long, value_enum, default_value_t=TraceCaptureLevelArg::Debug)]
+    level: TraceCaptureLevelArg,
+    ///Duration of the capture in seconds, after which the verbosity is lowered again
+    #[arg(long, default_value_t=300)]
+    duration: u64,
+}
+
+#[derive(clap::Args)]
+#[group(required=true, multiple=false)]
+struct TraceCaptureScopeArgs {
+    ///Capture records mentioning this peer
+    #[arg(long)]
+    peer_id: Option<Uuid>,
+    ///Capture records mentioning this cluster
+    #[arg(long)]
+    cluster_id: Option<Uuid>,
+    ///Capture records of requests to this gRPC method, e.g. "ListPeerDescriptors"
+    #[arg(long)]
+    grpc_method: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum TraceCaptureLevelArg {
+    Debug,
+    Trace,
+}
+
+impl CreateTraceCaptureCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let TraceCaptureScopeArgs { peer_id, cluster_id, grpc_method } = self.scope;
+
+        let scope = if let Some(peer_id) = peer_id {
+            TraceCaptureScope::Peer { peer_id: PeerId::from(peer_id) }
+        } else if let Some(cluster_id) = cluster_id {
+            TraceCaptureScope::Cluster { cluster_id: ClusterId::from(cluster_id) }
+        } else if let Some(method) = grpc_method {
+            TraceCaptureScope::GrpcMethod { method }
+        } else {
+            unreachable!("Clap should require one of the scope arguments.")
+        };
+        let level = match self.level {
+            TraceCaptureLevelArg::Debug => TraceCaptureLevel::Debug,
+            TraceCaptureLevelArg::Trace => TraceCaptureLevel::Trace,
+        };
+
+        let capture = carl.diagnostics.start_trace_capture(Clone::clone(&scope), level, Duration::from_secs(self.duration)).await
+            .map_err(|error| crate::Error::carl(format!("Could not start trace capture for {scope}."), error))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully started trace capture <{}> for {} at level {} until {}.", capture.id, capture.scope, capture.level, format_timestamp(capture.expires_at));
+                println!("Download the captured records with 'opendut-cleo download trace-capture {}'.", capture.id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializableTraceCaptureInfo::from(capture)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializableTraceCaptureInfo::from(capture)).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::diagnostics::TraceCaptureId;
+
+/// Delete a trace capture. Stops the capture, if it is still active, and discards the captured records.
+#[derive(clap::Parser)]
+pub struct DeleteTraceCaptureCli {
+    ///TraceCaptureID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteTraceCaptureCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = TraceCaptureId(self.id);
+        carl.diagnostics.delete_trace_capture(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not delete trace capture <{id}>."), error))?;
+        println!("Deleted trace capture <{id}>.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::diagnostics::TraceCaptureId;
+
+use crate::commands::trace_capture::{SerializableCapturedRecord, SerializableTraceCaptureInfo};
+
+/// Download the records captured so far as JSON bundle. Possible while the capture is still active.
+#[derive(clap::Parser)]
+pub struct DownloadTraceCaptureCli {
+    ///TraceCaptureID
+    #[arg()]
+    id: Uuid,
+    ///File to write the bundle to. Printed to stdout, if not specified.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+struct SerializableTraceCaptureBundle {
+    capture: SerializableTraceCaptureInfo,
+    records: Vec<SerializableCapturedRecord>,
+}
+
+impl DownloadTraceCaptureCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = TraceCaptureId(self.id);
+        let bundle = carl.diagnostics.download_trace_capture(id).await
+            .map_err(|error| crate::Error::carl(format!("Could not download trace capture <{id}>."), error))?;
+
+        let record_count = bundle.records.len();
+        let bundle = SerializableTraceCaptureBundle {
+            capture: SerializableTraceCaptureInfo::from(bundle.capture),
+            records: bundle.records.into_iter().map(SerializableCapturedRecord::from).collect(),
+        };
+        let json = serde_json::to_string_pretty(&bundle).unwrap();
+
+        match self.file {
+            Some(file) => {
+                std::fs::write(&file, json)
+                    .map_err(|cause| format!("Could not write trace capture <{id}> to '{}'.\n  {cause}", file.display()))?;
+                println!("Wrote {record_count} records of trace capture <{id}> to '{}'.", file.display());
+            }
+            None => {
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
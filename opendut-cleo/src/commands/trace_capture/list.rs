@@ -0,0 +1,76 @@
+use std::time::SystemTime;
+
+use cli_table::{print_stdout, Table, WithTitle};
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::diagnostics::TraceCaptureId;
+
+use crate::commands::trace_capture::{format_timestamp, SerializableTraceCaptureInfo};
+use crate::commands::export::{self, ExportFormat};
+use crate::ListOutputFormat;
+
+/// List all trace captures, including expired ones, which were not yet deleted
+#[derive(clap::Parser)]
+pub struct ListTraceCapturesCli;
+
+#[derive(Table)]
+struct TraceCaptureTable {
+    #[table(title = "TraceCaptureID")]
+    id: TraceCaptureId,
+    #[table(title = "Scope")]
+    scope: String,
+    #[table(title = "Level")]
+    level: String,
+    #[table(title = "Expires")]
+    expires_at: String,
+    #[table(title = "Active")]
+    active: bool,
+    #[table(title = "Records")]
+    record_count: usize,
+}
+
+impl ListTraceCapturesCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let captures = carl.diagnostics.list_trace_captures().await
+            .map_err(|error| format!("Could not list trace captures.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let now = SystemTime::now();
+                let capture_table = captures.into_iter()
+                    .map(|capture| {
+                        TraceCaptureTable {
+                            id: capture.id,
+                            scope: capture.scope.to_string(),
+                            level: capture.level.to_string(),
+                            expires_at: format_timestamp(capture.expires_at),
+                            active: capture.expires_at > now,
+                            record_count: capture.record_count,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(capture_table.with_title())
+                    .expect("List of trace captures should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let captures = captures.into_iter().map(SerializableTraceCaptureInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string(&captures).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let captures = captures.into_iter().map(SerializableTraceCaptureInfo::from).collect::<Vec<_>>();
+                let json = serde_json::to_string_pretty(&captures).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::Csv => {
+                let captures = captures.into_iter().map(SerializableTraceCaptureInfo::from).collect::<Vec<_>>();
+                export::print(&captures, ExportFormat::Csv, &[])?;
+            }
+            ListOutputFormat::Xlsx => {
+                let captures = captures.into_iter().map(SerializableTraceCaptureInfo::from).collect::<Vec<_>>();
+                export::print(&captures, ExportFormat::Xlsx, &[])?;
+            }
+        }
+
+        Ok(())
+    }
+}
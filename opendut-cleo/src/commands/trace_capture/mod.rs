@@ -0,0 +1,59 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use opendut_carl_api::carl::diagnostics::{CapturedRecord, TraceCaptureInfo};
+
+pub mod create;
+pub mod delete;
+pub mod download;
+pub mod list;
+
+fn format_timestamp(timestamp: SystemTime) -> String {
+    DateTime::<Utc>::from(timestamp).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[derive(serde::Serialize)]
+struct SerializableTraceCaptureInfo {
+    id: String,
+    scope: String,
+    level: String,
+    started_at: String,
+    expires_at: String,
+    record_count: usize,
+    dropped_record_count: usize,
+}
+
+impl From<TraceCaptureInfo> for SerializableTraceCaptureInfo {
+    fn from(capture: TraceCaptureInfo) -> Self {
+        Self {
+            id: capture.id.to_string(),
+            scope: capture.scope.to_string(),
+            level: capture.level.to_string(),
+            started_at: format_timestamp(capture.started_at),
+            expires_at: format_timestamp(capture.expires_at),
+            record_count: capture.record_count,
+            dropped_record_count: capture.dropped_record_count,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SerializableCapturedRecord {
+    timestamp: String,
+    level: String,
+    target: String,
+    spans: Vec<String>,
+    fields: String,
+}
+
+impl From<CapturedRecord> for SerializableCapturedRecord {
+    fn from(record: CapturedRecord) -> Self {
+        Self {
+            timestamp: format_timestamp(record.timestamp),
+            level: record.level,
+            target: record.target,
+            spans: record.spans,
+            fields: record.fields,
+        }
+    }
+}
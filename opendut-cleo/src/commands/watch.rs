@@ -0,0 +1,92 @@
+use std::io::Write;
+use std::ops::Not;
+use std::time::Duration;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::{ResourceEvents, ResourceFilter, ResourceKind};
+
+use crate::ListOutputFormat;
+
+/// Events arriving within this duration after another are collected into a single re-render.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(clap::Args, Default)]
+pub struct WatchArgs {
+    ///Keep running and print the list anew, whenever the listed resources change
+    #[arg(long)]
+    pub watch: bool,
+}
+
+impl WatchArgs {
+    /// Subscribes to the events of the given kinds of resources, if `--watch` was specified.
+    /// Subscribing happens before the list is retrieved for the first time, so no change in between gets lost.
+    pub async fn subscribe(&self, carl: &mut CarlClient, kinds: &[ResourceKind], output: &ListOutputFormat) -> crate::Result<Watch> {
+        if self.watch.not() {
+            return Ok(Watch { events: None, clear_screen: false, rendered: false });
+        }
+
+        let clear_screen = match output {
+            ListOutputFormat::Table => true,
+            ListOutputFormat::Json | ListOutputFormat::PrettyJson => false,
+            ListOutputFormat::Csv | ListOutputFormat::Xlsx => {
+                return Err("Option '--watch' cannot be combined with CSV or Excel output.".into());
+            }
+        };
+
+        let filters = kinds.iter()
+            .map(|kind| ResourceFilter::all(*kind))
+            .collect();
+        let events = carl.observer.subscribe_resource_events(filters).await
+            .map_err(|error| format!("Could not subscribe to changes of {}.\n  {error}", kinds.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")))?;
+
+        Ok(Watch { events: Some(events), clear_screen, rendered: false })
+    }
+}
+
+pub struct Watch {
+    events: Option<ResourceEvents>,
+    clear_screen: bool,
+    rendered: bool,
+}
+
+impl Watch {
+    /// Returns `true`, when the list should be printed (again).
+    /// The first call returns immediately. Without `--watch`, every further call returns `false`,
+    /// otherwise it waits for the next change and returns `false`, when CARL closed the stream.
+    pub async fn next_render(&mut self) -> crate::Result<bool> {
+        if self.rendered.not() {
+            self.rendered = true;
+            self.clear();
+            return Ok(true);
+        }
+
+        let Some(events) = &mut self.events else {
+            return Ok(false);
+        };
+
+        if events.next().await.map_err(stream_error)?.is_none() {
+            return Ok(false);
+        }
+
+        while let Ok(received) = tokio::time::timeout(DEBOUNCE, events.next()).await {
+            if received.map_err(stream_error)?.is_none() {
+                self.events = None;
+                break;
+            }
+        }
+
+        self.clear();
+        Ok(true)
+    }
+
+    fn clear(&self) {
+        if self.clear_screen {
+            print!("\x1B[2J\x1B[H");
+            let _ = std::io::stdout().flush();
+        }
+    }
+}
+
+fn stream_error(error: impl std::fmt::Display) -> crate::Error {
+    format!("Lost the subscription to changes of resources.\n  {error}").into()
+}
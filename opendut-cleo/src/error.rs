@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+use opendut_carl_api::carl::error_code::{ErrorCode, ErrorRemediation, HasErrorCode};
+
+use crate::i18n::{tr, trf};
+
+/// Error of a command. Errors returned by CARL carry an error code,
+/// for which remediation hints can be retrieved from CARL and displayed with `--explain`.
+#[derive(Debug)]
+pub struct Error {
+    message: String,
+    code: Option<ErrorCode>,
+    remediation: Option<Remediation>,
+}
+
+#[derive(Debug)]
+enum Remediation {
+    Known(ErrorRemediation),
+    Unavailable { cause: String },
+}
+
+impl Error {
+    /// Prefixes the error returned by CARL with the context, in the same format as the other errors of CLEO.
+    pub fn carl<E>(context: impl Display, error: E) -> Self
+    where E: Display + HasErrorCode {
+        Self {
+            message: format!("{context}\n  {error}"),
+            code: error.error_code(),
+            remediation: None,
+        }
+    }
+
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.code
+    }
+
+    /// Attaches the remediation hint for this error's code from the catalog, if there is one.
+    pub fn explained(mut self, catalog: &[ErrorRemediation]) -> Self {
+        if let Some(code) = self.code {
+            self.remediation = catalog.iter()
+                .find(|entry| entry.code == code.value())
+                .cloned()
+                .map(Remediation::Known);
+        }
+        self
+    }
+
+    pub fn explanation_unavailable(mut self, cause: impl Display) -> Self {
+        self.remediation = Some(Remediation::Unavailable { cause: cause.to_string() });
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message.trim_end())?;
+
+        if let Some(code) = self.code {
+            write!(f, "\n\n{}", trf("error.code", &[("code", &code)]))?;
+
+            match &self.remediation {
+                Some(Remediation::Known(remediation)) => {
+                    write!(f, "\n  {}\n  {}", remediation.summary, trf("error.hint", &[("hint", &remediation.remediation)]))?;
+                }
+                Some(Remediation::Unavailable { cause }) => {
+                    write!(f, "\n  {}", trf("error.remediation-unavailable", &[("cause", cause)]))?;
+                }
+                None => {
+                    write!(f, "\n  {}", tr("error.explain"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self { message, code: None, remediation: None }
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_carl_api::carl::peer::GetPeerStateError;
+    use opendut_carl_api::carl::ClientError;
+    use opendut_types::peer::PeerId;
+
+    use super::*;
+
+    #[test]
+    fn should_render_the_remediation_hint_of_a_carl_error() {
+        crate::i18n::init(Some(crate::i18n::Locale::English));
+        let peer_id = PeerId::random();
+        let error = Error::carl("Could not get peer state.", ClientError::UsageError(GetPeerStateError::PeerNotFound { peer_id }));
+        assert_that!(error.code(), some(eq(ErrorCode::PeerNotFound)));
+
+        let catalog = vec![ErrorRemediation {
+            code: String::from("peer-not-found"),
+            summary: String::from("The peer does not exist."),
+            remediation: String::from("Check the PeerID."),
+        }];
+        let explained = error.explained(&catalog).to_string();
+
+        assert_that!(explained, starts_with(format!("Could not get peer state.\n  A peer with id <{peer_id}> could not be found!")));
+        assert_that!(explained, ends_with("Error code: peer-not-found\n  The peer does not exist.\n  Hint: Check the PeerID."));
+    }
+
+    #[test]
+    fn should_render_errors_without_code_unchanged() {
+        let error = Error::from("Failed to hide cleo secrets.");
+        assert_that!(error.to_string(), eq("Failed to hide cleo secrets."));
+    }
+}
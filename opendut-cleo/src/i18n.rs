@@ -0,0 +1,25 @@
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+pub use opendut_util_core::i18n::Locale;
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Sets the language of CLEO's output. Defaults to the locale of the environment, if not called.
+pub fn init(locale: Option<Locale>) {
+    let _ = LOCALE.set(locale.unwrap_or_else(Locale::from_env));
+}
+
+fn locale() -> Locale {
+    *LOCALE.get_or_init(Locale::from_env)
+}
+
+/// Returns the message for the key in the language of CLEO's output.
+pub fn tr(key: &str) -> String {
+    opendut_util_core::i18n::message(locale(), key)
+}
+
+/// Returns the message for the key in the language of CLEO's output, with its placeholders replaced by the arguments.
+pub fn trf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    opendut_util_core::i18n::format(locale(), key, args)
+}
@@ -1,3 +1,4 @@
+use std::ffi::OsString;
 use std::ops::Not;
 use std::path::PathBuf;
 use std::process::ExitCode;
@@ -6,14 +7,16 @@ use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use console::Style;
 
-use opendut_carl_api::carl::{CaCertInfo, CarlClient};
+use opendut_carl_api::carl::{CaCertInfo, CarlClient, InitializationError};
 use opendut_types::topology::DeviceName;
 use opendut_util::settings::{FileFormat, load_config, LoadedConfig};
 
 mod commands;
+mod error;
+mod i18n;
 pub mod parse;
 
-type Error = String;
+use error::Error;
 type Result<T> = std::result::Result<T, Error>;
 
 opendut_util::app_info!();
@@ -25,6 +28,15 @@ opendut_util::app_info!();
 struct Args {
     #[command(subcommand)]
     command: Commands,
+    ///Explain errors with remediation hints provided by CARL
+    #[arg(long, global = true)]
+    explain: bool,
+    ///Language of the output, e.g. 'en' or 'de'. Defaults to the locale of the environment (LC_ALL, LC_MESSAGES, LANG)
+    #[arg(long, global = true)]
+    language: Option<i18n::Locale>,
+    ///Connect to CARL without credentials, for quick status checks. Only list, describe, find, diff, export and cluster-deployment status commands are available. Requires CARL to allow anonymous read access
+    #[arg(long, global = true)]
+    read_only: bool,
 }
 
 #[derive(Subcommand)]
@@ -35,14 +47,16 @@ enum Commands {
     List {
         #[command(subcommand)]
         resource: ListResource,
-        ///JSON, prettified JSON or table as output format
+        ///JSON, prettified JSON, table, CSV or Excel workbook as output format
         #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
         output: ListOutputFormat,
     },
-    ///Create openDuT resource from configuration file
+    ///Create openDuT resource from configuration file, or apply a topology of peers, devices and clusters via --file
     Apply {
         #[command(subcommand)]
-        resource: ApplyResource,
+        resource: Option<ApplyResource>,
+        #[command(flatten)]
+        topology: commands::topology::apply::ApplyTopologyCli,
         ///Text, JSON or prettified JSON as output format
         #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
         output: CreateOutputFormat,
@@ -56,6 +70,7 @@ enum Commands {
         output: CreateOutputFormat,
     },
     GenerateSetupString(commands::generate_setup_string::GenerateSetupStringCli),
+    GenerateProvisioningTokens(commands::provisioning_token::generate::GenerateProvisioningTokensCli),
     DecodeSetupString(commands::decode_setup_string::DecodeSetupStringCli),
     ///Describe openDuT resource
     Describe {
@@ -71,7 +86,7 @@ enum Commands {
         ///Name of openDuT resource
         #[command(subcommand)]
         resource: FindResource,
-        ///JSON, prettified JSON or table as output format
+        ///JSON, prettified JSON, table, CSV or Excel workbook as output format
         #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
         output: ListOutputFormat,
     },
@@ -81,13 +96,81 @@ enum Commands {
         #[command(subcommand)]
         resource: DeleteResource,
     },
+    ///Compare openDuT resources
+    Diff {
+        #[command(subcommand)]
+        resource: DiffResource,
+        ///JSON, prettified JSON, table, CSV or Excel workbook as output format
+        #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+        output: ListOutputFormat,
+    },
+    ///Download openDuT resources
+    Download {
+        #[command(subcommand)]
+        resource: DownloadResource,
+    },
+    ///Check the connectivity of openDuT resources
+    Ping {
+        #[command(subcommand)]
+        resource: PingResource,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
+    ///Administer connected peers
+    Peer {
+        #[command(subcommand)]
+        action: PeerAction,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
+    ///Track the rollout of cluster deployments and collect the logs of their peers
+    ClusterDeployment {
+        #[command(subcommand)]
+        action: ClusterDeploymentAction,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
+    ///List and download the results, which executors uploaded to their results URL
+    Results {
+        #[command(subcommand)]
+        action: ResultsAction,
+        ///JSON, prettified JSON, table, CSV or Excel workbook as output format of the listed results
+        #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+        output: ListOutputFormat,
+    },
+    ///Restore openDuT resources
+    Restore {
+        #[command(subcommand)]
+        resource: RestoreResource,
+    },
+    ///Label openDuT resources matching a selector
+    Label {
+        #[command(subcommand)]
+        resource: LabelResource,
+        ///JSON, prettified JSON, table, CSV or Excel workbook as output format
+        #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+        output: ListOutputFormat,
+    },
+    Export(commands::bundle::export::ExportCli),
+    Import(commands::bundle::import::ImportCli),
     Config,
+    SelfUpdate(commands::self_update::SelfUpdateCli),
+    CheckConsistency(commands::check_consistency::CheckConsistencyCli),
+    CheckCompatibility(commands::check_compatibility::CheckCompatibilityCli),
+    Bootstrap(commands::bootstrap::BootstrapCli),
+    Benchmark(commands::benchmark::BenchmarkCli),
     /// Generates shell completion
     Completions {
         /// Shell to generate completions for
         #[arg(value_enum)]
         shell: Shell
     },
+    /// Runs an executable named `cleo-<subcommand>` from the PATH, passing the connection to CARL via environment variables
+    #[command(external_subcommand)]
+    Plugin(Vec<OsString>),
 }
 
 #[derive(Subcommand)]
@@ -95,8 +178,14 @@ enum ListResource {
     ClusterConfigurations(commands::cluster_configuration::list::ListClusterConfigurationsCli),
     ClusterDeployments(commands::cluster_deployment::list::ListClusterDeploymentsCli),
     Peers(commands::peer::list::ListPeersCli),
+    PeerStreams(commands::peer_stream::list::ListPeerStreamsCli),
     Devices(commands::device::list::ListDevicesCli),
     ContainerExecutor(commands::executor::list::ListContainerExecutorCli),
+    Snapshots(commands::snapshot::list::ListSnapshotsCli),
+    ProvisioningTokens(commands::provisioning_token::list::ListProvisioningTokensCli),
+    Projects(commands::project::list::ListProjectsCli),
+    ServiceAccounts(commands::service_account::list::ListServiceAccountsCli),
+    TraceCaptures(commands::trace_capture::list::ListTraceCapturesCli),
 }
 
 #[derive(clap::Args)]
@@ -106,6 +195,9 @@ struct ClusterConfigurationDevices {
     device_names: Vec<DeviceName>,
     #[arg(long, num_args = 0..)]
     device_ids: Vec<String>,
+    ///Selector for devices, which CARL binds to the cluster at deployment time, e.g. "tags=ecu,front;kind=can;peers=location=hall3,hw=raspberrypi;count=1..2"
+    #[arg(long = "device-selector")]
+    device_selectors: Vec<crate::parse::cluster::ParseableDeviceSelector>,
 }
 
 #[derive(ValueEnum, Clone)]
@@ -131,8 +223,14 @@ enum CreateResource {
     ClusterDeployment(commands::cluster_deployment::create::CreateClusterDeploymentCli),
     Peer(commands::peer::create::CreatePeerCli),
     ContainerExecutor(commands::executor::create::CreateContainerExecutorCli),
+    ClusterHook(commands::cluster_hook::create::CreateClusterHookCli),
     NetworkInterface(commands::network_interface::create::CreateNetworkInterfaceCli),
-    Device(commands::device::create::CreateDeviceCli)
+    Device(commands::device::create::CreateDeviceCli),
+    Snapshot(commands::snapshot::create::CreateSnapshotCli),
+    TraceCapture(commands::trace_capture::create::CreateTraceCaptureCli),
+    Project(commands::project::create::CreateProjectCli),
+    ServiceAccount(commands::service_account::create::CreateServiceAccountCli),
+    ServiceAccountToken(commands::service_account::create_token::CreateServiceAccountTokenCli),
 }
 
 #[derive(Subcommand)]
@@ -152,9 +250,61 @@ enum DeleteResource {
     ClusterConfiguration(commands::cluster_configuration::delete::DeleteClusterConfigurationCli),
     ClusterDeployment(commands::cluster_deployment::delete::DeleteClusterDeploymentCli),
     Peer(commands::peer::delete::DeletePeerCli),
+    PeerStream(commands::peer_stream::delete::DeletePeerStreamCli),
     ContainerExecutor(commands::executor::delete::DeleteContainerExecutorCli),
+    ClusterHook(commands::cluster_hook::delete::DeleteClusterHookCli),
     NetworkInterface(commands::network_interface::delete::DeleteNetworkInterfaceCli),
     Device(commands::device::delete::DeleteDeviceCli),
+    Snapshot(commands::snapshot::delete::DeleteSnapshotCli),
+    TraceCapture(commands::trace_capture::delete::DeleteTraceCaptureCli),
+    Project(commands::project::delete::DeleteProjectCli),
+    ServiceAccount(commands::service_account::delete::DeleteServiceAccountCli),
+    ServiceAccountToken(commands::service_account::delete_token::DeleteServiceAccountTokenCli),
+}
+
+#[derive(Subcommand)]
+enum DiffResource {
+    Snapshots(commands::snapshot::diff::DiffSnapshotsCli),
+}
+
+#[derive(Subcommand)]
+enum DownloadResource {
+    TraceCapture(commands::trace_capture::download::DownloadTraceCaptureCli),
+}
+
+#[derive(Subcommand)]
+enum PingResource {
+    Peer(commands::peer::ping::PingPeerCli),
+}
+
+#[derive(Subcommand)]
+enum PeerAction {
+    Command(commands::peer::command::SendPeerCommandCli),
+    RollingRestart(commands::peer::rolling_restart::RollingRestartCli),
+    RollingRestartStatus(commands::peer::rolling_restart::RollingRestartStatusCli),
+    RevokeSetupString(commands::peer::revoke_setup_string::RevokeSetupStringCli),
+}
+
+#[derive(Subcommand)]
+enum ClusterDeploymentAction {
+    Status(commands::cluster_deployment::status::ClusterDeploymentStatusCli),
+    Logs(commands::cluster_deployment::logs::ClusterDeploymentLogsCli),
+}
+
+#[derive(Subcommand)]
+enum ResultsAction {
+    List(commands::results::list::ListExecutorResultsCli),
+    Download(commands::results::download::DownloadExecutorResultCli),
+}
+
+#[derive(Subcommand)]
+enum RestoreResource {
+    Snapshot(commands::snapshot::restore::RestoreSnapshotCli),
+}
+
+#[derive(Subcommand)]
+enum LabelResource {
+    Peers(commands::peer::label::LabelPeersCli),
 }
 
 #[derive(ValueEnum, Clone)]
@@ -169,6 +319,8 @@ enum ListOutputFormat {
     Table,
     Json,
     PrettyJson,
+    Csv,
+    Xlsx,
 }
 
 #[derive(ValueEnum, Clone)]
@@ -212,6 +364,8 @@ async fn execute() -> Result<()> {
     let cleo_config_hide_secrets_override = config::Config::builder()
         .set_override("network.oidc.client.secret", "redacted")
         .map_err(|_error| "Failed to hide cleo secrets.")?
+        .set_override("network.service.account.token", "redacted")
+        .map_err(|_error| "Failed to hide cleo secrets.")?
         .build()
         .map_err(|_error| "Failed to hide cleo secrets.")?;
 
@@ -219,9 +373,87 @@ async fn execute() -> Result<()> {
         .expect("Failed to load config"); // TODO: Point the user to the source of the error.
 
     let args = Args::parse();
+    i18n::init(args.language);
 
-    execute_command(args.command, &settings).await?;
-    Ok(())
+    let settings = if args.read_only {
+        args.command.ensure_read_only()?;
+        read_only_settings(settings)?
+    } else {
+        settings
+    };
+
+    match execute_command(args.command, &settings).await {
+        Err(error) if args.explain && error.code().is_some() => {
+            Err(explain(error, &settings.config).await)
+        }
+        result => result,
+    }
+}
+
+impl Commands {
+    /// Fails for commands, which change resources or require credentials for other reasons, like the setup strings.
+    fn ensure_read_only(&self) -> Result<()> {
+        let command = match self {
+            Commands::List { .. }
+            | Commands::Describe { .. }
+            | Commands::Find { .. }
+            | Commands::Diff { .. }
+            | Commands::ClusterDeployment { action: ClusterDeploymentAction::Status(_), .. }
+            | Commands::Peer { action: PeerAction::RollingRestartStatus(_), .. }
+            | Commands::DecodeSetupString(_)
+            | Commands::Export(_)
+            | Commands::Config
+            | Commands::Completions { .. } => return Ok(()),
+            Commands::Setup(_) => "setup",
+            Commands::Apply { .. } => "apply",
+            Commands::Create { .. } => "create",
+            Commands::GenerateSetupString(_) => "generate-setup-string",
+            Commands::GenerateProvisioningTokens(_) => "generate-provisioning-tokens",
+            Commands::Delete { .. } => "delete",
+            Commands::Download { .. } => "download",
+            Commands::Ping { .. } => "ping",
+            Commands::Peer { .. } => "peer",
+            Commands::ClusterDeployment { .. } => "cluster-deployment",
+            Commands::Results { .. } => "results",
+            Commands::Restore { .. } => "restore",
+            Commands::Label { .. } => "label",
+            Commands::Import(_) => "import",
+            Commands::SelfUpdate(_) => "self-update",
+            Commands::CheckConsistency(_) => "check-consistency",
+            Commands::CheckCompatibility(_) => "check-compatibility",
+            Commands::Bootstrap(_) => "bootstrap",
+            Commands::Benchmark(_) => "benchmark",
+            Commands::Plugin(_) => "plugin",
+        };
+        Err(Error::from(i18n::trf("error.read-only", &[("command", &command)])))
+    }
+}
+
+/// Disables OIDC, so that no credentials need to be configured. CARL only answers reading requests then.
+fn read_only_settings(settings: LoadedConfig) -> Result<LoadedConfig> {
+    let disable_oidc = |config: config::Config| config::Config::builder()
+        .add_source(config)
+        .set_override("network.oidc.enabled", false)
+        .and_then(|builder| builder.set_override("network.service.account.token", ""))
+        .and_then(|builder| builder.build())
+        .map_err(|cause| Error::from(format!("Failed to disable authentication for read-only mode: {cause}")));
+
+    Ok(LoadedConfig {
+        config: disable_oidc(settings.config)?,
+        redacted_config: disable_oidc(settings.redacted_config)?,
+        ..settings
+    })
+}
+
+async fn explain(error: Error, config: &config::Config) -> Error {
+    let mut carl = match try_create_carl_client(config).await {
+        Ok(carl) => carl,
+        Err(cause) => return error.explanation_unavailable(cause),
+    };
+    match carl.metadata.error_catalog().await {
+        Ok(catalog) => error.explained(&catalog),
+        Err(cause) => error.explanation_unavailable(cause),
+    }
 }
 
 async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<()>{
@@ -241,20 +473,44 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 ListResource::Peers(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::PeerStreams(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 ListResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
                 ListResource::Devices(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::Snapshots(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::ProvisioningTokens(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::Projects(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::ServiceAccounts(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::TraceCaptures(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
-        Commands::Apply { resource, output } => {
+        Commands::Apply { resource, topology, output } => {
+            if resource.is_some() && topology.file.is_some() {
+                Err("Specify either a resource to apply or a topology file via --file, not both.")?
+            }
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
-                ApplyResource::ContainerExecutor(implementation) => {
+                Some(ApplyResource::ContainerExecutor(implementation)) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                None => {
+                    topology.execute(&mut carl).await?;
+                }
             }
         }
         Commands::Create { resource, output } => {
@@ -272,12 +528,31 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 CreateResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::ClusterHook(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 CreateResource::NetworkInterface(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
                 CreateResource::Device(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::Snapshot(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::TraceCapture(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::Project(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::ServiceAccount(implementation) => {
+                    let cleo_oidc_client_id = get_cleo_oidc_client_id(&settings.config).await;
+                    implementation.execute(&mut carl, cleo_oidc_client_id, output).await?;
+                }
+                CreateResource::ServiceAccountToken(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::GenerateSetupString(implementation) => {
@@ -285,6 +560,11 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
             let cleo_oidc_client_id = get_cleo_oidc_client_id(&settings.config).await;
             implementation.execute(&mut carl, cleo_oidc_client_id).await?;
         }
+        Commands::GenerateProvisioningTokens(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            let cleo_oidc_client_id = get_cleo_oidc_client_id(&settings.config).await;
+            implementation.execute(&mut carl, cleo_oidc_client_id).await?;
+        }
         Commands::DecodeSetupString(implementation) => {
             implementation.execute().await?;
         }
@@ -314,15 +594,107 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DeleteResource::Peer(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::PeerStream(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
                 DeleteResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::ClusterHook(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
                 DeleteResource::NetworkInterface(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
                 DeleteResource::Device(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::Snapshot(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::TraceCapture(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::Project(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::ServiceAccount(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::ServiceAccountToken(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Diff { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                DiffResource::Snapshots(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Download { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                DownloadResource::TraceCapture(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Ping { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                PingResource::Peer(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Peer { action, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match action {
+                PeerAction::Command(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                PeerAction::RollingRestart(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                PeerAction::RollingRestartStatus(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                PeerAction::RevokeSetupString(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::ClusterDeployment { action, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match action {
+                ClusterDeploymentAction::Status(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ClusterDeploymentAction::Logs(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Results { action, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match action {
+                ResultsAction::List(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ResultsAction::Download(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Restore { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                RestoreResource::Snapshot(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
             }
         }
         Commands::Find { resource, output } => {
@@ -333,18 +705,63 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 }
             }
         }
+        Commands::Label { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                LabelResource::Peers(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Export(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Import(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
         Commands::Config => {
             println!("Active CLEO configuration: {:?}", settings);
         }
+        Commands::CheckConsistency(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::CheckCompatibility(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Bootstrap(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Benchmark(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::SelfUpdate(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            let signature_public_key = settings.config.get_string("update.signature.public.key").ok();
+            implementation.execute(&mut carl, signature_public_key).await?;
+        }
         Commands::Completions { shell } => {
             let mut cmd = Args::command();
             commands::completions::print_completions(shell, &mut cmd);
         }
+        Commands::Plugin(args) => {
+            commands::plugin::execute(args, &settings.config).await?;
+        }
     }
     Ok(())
 }
 
 pub async fn create_carl_client(config: &config::Config) -> CarlClient {
+    try_create_carl_client(config).await
+        .expect("Failed to create CARL client")
+}
+
+async fn try_create_carl_client(config: &config::Config) -> std::result::Result<CarlClient, InitializationError> {
     let host = config.get_string("network.carl.host")
         .expect("Configuration should contain a valid host name to connect to CARL");
 
@@ -365,7 +782,6 @@ pub async fn create_carl_client(config: &config::Config) -> CarlClient {
     let domain_name_override = domain_name_override.is_empty().not().then_some(domain_name_override);
 
     CarlClient::create(host, port as u16, &ca_cert_info, &domain_name_override, config).await
-        .expect("Failed to create CARL client")
 }
 
 pub async fn get_cleo_oidc_client_id(config: &config::Config) -> String {
@@ -1,4 +1,8 @@
+use std::ops::Not;
+
 use opendut_types::cluster::*;
+use opendut_types::peer::label::LabelSelector;
+use opendut_types::topology::DeviceTag;
 
 use super::*;
 
@@ -25,3 +29,85 @@ impl FromStr for ParseableClusterName {
         Ok(Self(inner))
     }
 }
+
+/// Parses `;`-separated settings of a device selector, e.g. `tags=ecu,front;kind=can;peers=location=hall3,hw=raspberrypi;count=1..2`.
+/// All settings are optional, the count defaults to exactly one device.
+#[derive(Clone)]
+pub struct ParseableDeviceSelector(pub DeviceSelector);
+impl FromStr for ParseableDeviceSelector {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut selector = DeviceSelector {
+            tags: Vec::new(),
+            interface_kind: None,
+            peer_labels: LabelSelector::default(),
+            count: DeviceCount::new(1, Some(1))
+                .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?,
+        };
+
+        for setting in value.split(';').filter(|setting| setting.trim().is_empty().not()) {
+            let (key, setting_value) = setting.split_once('=')
+                .ok_or_else(|| ParseError::new::<Self>(value, format!("Expected 'key=value', got '{setting}'.")))?;
+
+            match key.trim() {
+                "tags" => {
+                    selector.tags = setting_value.split(',')
+                        .filter(|tag| tag.trim().is_empty().not())
+                        .map(|tag| DeviceTag::try_from(tag.trim()))
+                        .collect::<Result<_, _>>()
+                        .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+                }
+                "kind" => {
+                    selector.interface_kind = Some(DeviceInterfaceKind::from_str(setting_value.trim())
+                        .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?);
+                }
+                "peers" => {
+                    selector.peer_labels = LabelSelector::from_str(setting_value)
+                        .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+                }
+                "count" => {
+                    selector.count = DeviceCount::from_str(setting_value.trim())
+                        .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+                }
+                other => return Err(ParseError::new::<Self>(value, format!("Unknown setting '{other}'. Expected 'tags', 'kind', 'peers' or 'count'."))),
+            }
+        }
+        Ok(Self(selector))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_device_selector() -> anyhow::Result<()> {
+        let ParseableDeviceSelector(selector) = ParseableDeviceSelector::from_str("tags=ecu,front;kind=can;peers=location=hall3, hw=raspberrypi;count=1..2")?;
+
+        assert_that!(selector, eq(DeviceSelector {
+            tags: vec![DeviceTag::try_from("ecu")?, DeviceTag::try_from("front")?],
+            interface_kind: Some(DeviceInterfaceKind::Can),
+            peer_labels: LabelSelector::from_str("location=hall3,hw=raspberrypi")?,
+            count: DeviceCount::new(1, Some(2))?,
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn should_default_to_exactly_one_device_of_any_kind() -> anyhow::Result<()> {
+        let ParseableDeviceSelector(selector) = ParseableDeviceSelector::from_str("tags=ecu")?;
+
+        assert_that!(selector.interface_kind, none());
+        assert_that!(selector.count, eq(DeviceCount::new(1, Some(1))?));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_settings() {
+        assert_that!(ParseableDeviceSelector::from_str("color=red"), err(anything()));
+        assert_that!(ParseableDeviceSelector::from_str("kind=lin"), err(anything()));
+    }
+}
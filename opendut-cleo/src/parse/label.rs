@@ -0,0 +1,100 @@
+use opendut_types::peer::label::*;
+
+use super::*;
+
+/// Parses `key=value` as a label.
+#[derive(Clone)]
+pub struct ParseableLabel(pub LabelKey, pub LabelValue);
+impl FromStr for ParseableLabel {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (key, label_value) = value.split_once('=')
+            .ok_or_else(|| ParseError::new::<Self>(value, "Expected 'key=value'."))?;
+        let key = LabelKey::try_from(key)
+            .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+        let label_value = LabelValue::try_from(label_value)
+            .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+        Ok(Self(key, label_value))
+    }
+}
+
+/// Parses `key=value` as setting a label and `key-` as removing it.
+#[derive(Clone)]
+pub struct ParseableLabelChange(pub LabelChange);
+impl FromStr for ParseableLabelChange {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let inner = match value.split_once('=') {
+            Some((key, label_value)) => LabelChange::Set {
+                key: LabelKey::try_from(key)
+                    .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?,
+                value: LabelValue::try_from(label_value)
+                    .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?,
+            },
+            None => LabelChange::Remove {
+                key: parse_removed_key::<Self>(value)?,
+            },
+        };
+        Ok(Self(inner))
+    }
+}
+
+/// Parses `key=value` as setting an annotation and `key-` as removing it.
+#[derive(Clone)]
+pub struct ParseableAnnotationChange(pub AnnotationChange);
+impl FromStr for ParseableAnnotationChange {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let inner = match value.split_once('=') {
+            Some((key, annotation_value)) => AnnotationChange::Set {
+                key: LabelKey::try_from(key)
+                    .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?,
+                value: AnnotationValue::try_from(annotation_value)
+                    .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?,
+            },
+            None => AnnotationChange::Remove {
+                key: parse_removed_key::<Self>(value)?,
+            },
+        };
+        Ok(Self(inner))
+    }
+}
+
+fn parse_removed_key<To>(value: &str) -> Result<LabelKey, ParseError> {
+    let key = value.strip_suffix('-')
+        .ok_or_else(|| ParseError::new::<To>(value, "Expected 'key=value' to set or 'key-' to remove."))?;
+    LabelKey::try_from(key)
+        .map_err(|cause| ParseError::new::<To>(value, cause.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_label_changes() -> anyhow::Result<()> {
+        let ParseableLabelChange(set) = ParseableLabelChange::from_str("rack=7")?;
+        assert_that!(set, eq(&LabelChange::Set { key: LabelKey::try_from("rack")?, value: LabelValue::try_from("7")? }));
+
+        let ParseableLabelChange(remove) = ParseableLabelChange::from_str("rack-")?;
+        assert_that!(remove, eq(&LabelChange::Remove { key: LabelKey::try_from("rack")? }));
+
+        assert!(ParseableLabelChange::from_str("rack").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_labels() -> anyhow::Result<()> {
+        let ParseableLabel(key, value) = ParseableLabel::from_str("batch=2024-10")?;
+        assert_that!(key, eq(&LabelKey::try_from("batch")?));
+        assert_that!(value, eq(&LabelValue::try_from("2024-10")?));
+
+        assert!(ParseableLabel::from_str("batch-").is_err());
+        Ok(())
+    }
+}
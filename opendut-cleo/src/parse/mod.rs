@@ -1,4 +1,6 @@
 pub mod cluster;
+pub mod label;
+pub mod snapshot;
 
 use std::str::FromStr;
 
@@ -0,0 +1,53 @@
+use opendut_types::snapshot::*;
+use uuid::Uuid;
+
+use super::*;
+
+#[derive(Clone)]
+pub struct ParseableSnapshotName(pub SnapshotName);
+impl FromStr for ParseableSnapshotName {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let inner = SnapshotName::try_from(value)
+            .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+        Ok(Self(inner))
+    }
+}
+
+/// Parses `kind:id`, e.g. `peer-group:9a1c...`, as a reference to a resource within a snapshot.
+#[derive(Clone)]
+pub struct ParseableSnapshotResourceReference(pub SnapshotResourceReference);
+impl FromStr for ParseableSnapshotResourceReference {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = value.split_once(':')
+            .ok_or_else(|| ParseError::new::<Self>(value, "Expected format 'kind:id'."))?;
+
+        let kind = SnapshotResourceKind::try_from(kind)
+            .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+        let id = Uuid::parse_str(id)
+            .map_err(|cause| ParseError::new::<Self>(value, cause.to_string()))?;
+
+        Ok(Self(SnapshotResourceReference { kind, id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_snapshot_resource_references() -> anyhow::Result<()> {
+        let id = Uuid::new_v4();
+        let ParseableSnapshotResourceReference(reference) = ParseableSnapshotResourceReference::from_str(&format!("peer-group:{id}"))?;
+        assert_that!(reference, eq(SnapshotResourceReference { kind: SnapshotResourceKind::PeerGroup, id }));
+
+        assert!(ParseableSnapshotResourceReference::from_str(&id.to_string()).is_err());
+        assert!(ParseableSnapshotResourceReference::from_str(&format!("device:{id}")).is_err());
+        Ok(())
+    }
+}
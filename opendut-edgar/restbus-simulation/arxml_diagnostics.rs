@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fs;
+
+use autosar_data::Element;
+use serde::Serialize;
+
+use crate::arxml_structs::*;
+
+/*
+- Diagnostics collected while extracting the restbus simulation data from an ARXML file.
+- Instead of only logging skipped elements, every problem is recorded with the path of the offending element,
+  so that the ARXML file can be fixed systematically. The collection can be exported as JSON for further processing.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    // The element was extracted, but possibly not as intended, e.g. a frame replaced another one with the same CAN ID
+    Warning,
+    // The element was skipped and is not part of the restbus simulation
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseDiagnostic {
+    // AUTOSAR path of the element or, for elements without a short name, its path in the XML hierarchy
+    pub path: String,
+    pub reason: String,
+    pub severity: DiagnosticSeverity,
+}
+
+impl ParseDiagnostic {
+    pub fn warning(element: &Element, reason: String) -> ParseDiagnostic {
+        return ParseDiagnostic { path: element_path(element), reason, severity: DiagnosticSeverity::Warning };
+    }
+
+    pub fn error(element: &Element, reason: String) -> ParseDiagnostic {
+        return ParseDiagnostic { path: element_path(element), reason, severity: DiagnosticSeverity::Error };
+    }
+}
+
+fn element_path(element: &Element) -> String {
+    return element.path().unwrap_or_else(|_| element.xml_path());
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ParseDiagnostics {
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseDiagnostics {
+    pub fn push(&mut self, diagnostic: ParseDiagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.diagnostics.is_empty();
+    }
+
+    pub fn count(&self, severity: DiagnosticSeverity) -> usize {
+        return self.diagnostics.iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count();
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        return serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize parse diagnostics: {}", err));
+    }
+
+    pub fn write_json(&self, file_name: &str) -> Result<(), String> {
+        let json = self.to_json()?;
+        return fs::write(file_name, json)
+            .map_err(|err| format!("Failed to write parse diagnostics to {}: {}", file_name, err));
+    }
+
+    pub fn print(&self) {
+        for diagnostic in &self.diagnostics {
+            let prefix = match diagnostic.severity {
+                DiagnosticSeverity::Warning => "WARNING",
+                DiagnosticSeverity::Error => "ERROR",
+            };
+            println!("[-] {}: {} ({})", prefix, diagnostic.reason, diagnostic.path);
+        }
+    }
+}
+
+// Result of ArxmlParser::parse_file. The CanClusters only contain the elements, which could be extracted.
+// All skipped elements are listed in the diagnostics.
+#[derive(Debug)]
+pub struct ArxmlParseResult {
+    pub can_clusters: HashMap<String, CanCluster>,
+    pub diagnostics: ParseDiagnostics,
+}
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 
 use autosar_data::{AutosarModel, CharacterData, Element, ElementName, EnumItem};
 
+use crate::arxml_diagnostics::*;
 use crate::arxml_structs::*;
 use crate::arxml_utils::*;
 
@@ -42,7 +43,7 @@ pub struct ArxmlParser {
 // Do I have to add license to this file or is project license enough?
 impl ArxmlParser {
     fn handle_isignal_to_pdu_mappings(&self, mapping: &Element, 
-        signals: &mut HashMap<String, (String, String, i64, i64, InitValues)>, 
+        signals: &mut HashMap<String, (String, String, i64, i64, InitValues, Option<CompuMethod>)>, 
         signal_groups: &mut Vec<Element>) 
         {
         if let Some(signal) = mapping
@@ -66,8 +67,11 @@ impl ArxmlParser {
                 
             if let Some(init_value_elem) = signal.get_sub_element(ElementName::InitValue) {
                 process_init_value(&init_value_elem, &mut init_values, &name);
-            }                     
-            signals.insert(refpath, (name, byte_order, start_pos, length, init_values));
+            }
+
+            let compu_method = get_compu_method(&signal);
+
+            signals.insert(refpath, (name, byte_order, start_pos, length, init_values, compu_method));
         } else if let Some(signal_group) = mapping
             .get_sub_element(ElementName::ISignalGroupRef)
             .and_then(|elem| elem.get_reference_target().ok())
@@ -79,7 +83,7 @@ impl ArxmlParser {
 
     fn handle_isignals(&self, pdu: &Element, grouped_signals: &mut Vec<ISignalGroup>, ungrouped_signals: &mut Vec<ISignal>) -> Option<()> {
         //let mut signals: HashMap<String, (String, Option<i64>, Option<i64>)> = HashMap::new();
-        let mut signals: HashMap<String, (String, String, i64, i64, InitValues)> = HashMap::new();
+        let mut signals: HashMap<String, (String, String, i64, i64, InitValues, Option<CompuMethod>)> = HashMap::new();
         let mut signal_groups = Vec::new();
 
 
@@ -94,15 +98,16 @@ impl ArxmlParser {
             process_signal_group(signal_group, &mut signals, grouped_signals);
         }
 
-        let remaining_signals: Vec<(String, String, i64, i64, InitValues)> = signals.values().cloned().collect();
+        let remaining_signals: Vec<(String, String, i64, i64, InitValues, Option<CompuMethod>)> = signals.values().cloned().collect();
         if remaining_signals.len() > 0 {
-            for (name, byte_order, start_pos, length, init_values) in remaining_signals {
+            for (name, byte_order, start_pos, length, init_values, compu_method) in remaining_signals {
                 let isignal_struct: ISignal = ISignal {
                     name: name,
                     byte_order: get_byte_order(&byte_order),
                     start_pos: start_pos,
                     length: length,
-                    init_values: init_values
+                    init_values: init_values,
+                    compu_method: compu_method
                 };
                 ungrouped_signals.push(isignal_struct);
             }
@@ -227,7 +232,7 @@ impl ArxmlParser {
 
     }*/
 
-    fn handle_pdu_mapping(&self, pdu_mapping: &Element) -> Result<PDUMapping, String> {
+    fn handle_pdu_mapping(&self, pdu_mapping: &Element) -> Result<PDUMapping, ParseDiagnostic> {
         let pdu = get_required_reference(
             pdu_mapping,
             ElementName::PduRef);
@@ -283,8 +288,8 @@ impl ArxmlParser {
             }*/
             // Handle more?
             _ => {
-                let error = format!("PDU type {} not supported. Will skip it.", pdu.element_name().to_string());
-                return Err(error)
+                let reason = format!("PDU type {} not supported. Skipping the frame containing it.", pdu.element_name().to_string());
+                return Err(ParseDiagnostic::error(&pdu, reason))
             }
         }
 
@@ -303,7 +308,7 @@ impl ArxmlParser {
         return Ok(pdu_mapping);     
     }
     
-    fn handle_can_frame_triggering(&self, can_frame_triggering: &Element) -> Result<CanFrameTriggering, String> {
+    fn handle_can_frame_triggering(&self, can_frame_triggering: &Element) -> Result<CanFrameTriggering, ParseDiagnostic> {
         let can_frame_triggering_name= get_required_item_name(
             can_frame_triggering, "CanFrameTriggering");
 
@@ -346,7 +351,7 @@ impl ArxmlParser {
         let mut tx_ecus: Vec<String> = Vec::new();
 
         match process_frame_ports(can_frame_triggering, &can_frame_triggering_name, &mut rx_ecus, &mut tx_ecus) {
-            Err(err) => return Err(err),
+            Err(err) => return Err(ParseDiagnostic::error(can_frame_triggering, err)),
             _ => {}
         }
 
@@ -354,6 +359,10 @@ impl ArxmlParser {
             &frame,
             ElementName::FrameLength);
 
+        let can_xl_props = get_can_xl_frame_props(can_frame_triggering);
+
+        let frame_format = get_can_frame_format(&frame_tx_behavior, &can_xl_props);
+
         let mut pdu_mappings_vec: Vec<PDUMapping> = Vec::new();
 
         // assign here and other similar variable?
@@ -378,13 +387,15 @@ impl ArxmlParser {
             receiver_ecus: rx_ecus,
             sender_ecus: tx_ecus,
             frame_length: frame_length,
+            frame_format: frame_format,
+            can_xl_props: can_xl_props,
             pdu_mappings: pdu_mappings_vec 
         };
 
         return Ok(can_frame_triggering_struct);
     }
 
-    fn handle_can_cluster(&self, can_cluster: &Element) -> Result<CanCluster, String> {
+    fn handle_can_cluster(&self, can_cluster: &Element, diagnostics: &mut ParseDiagnostics) -> Result<CanCluster, ParseDiagnostic> {
         let can_cluster_name = get_required_item_name(
             can_cluster, "CanCluster");
 
@@ -404,7 +415,7 @@ impl ArxmlParser {
 
         if can_cluster_baudrate == 0 && can_cluster_fd_baudrate == 0 {
             let msg = format!("Baudrate and FD Baudrate of CanCluster {} do not exist or are 0. Skipping this CanCluster.", can_cluster_name);
-            return Err(msg.to_string());
+            return Err(ParseDiagnostic::error(can_cluster, msg));
         }
 
         // iterate over PhysicalChannels and handle the CanFrameTriggerings inside them
@@ -416,8 +427,8 @@ impl ArxmlParser {
         {
             physical_channels = value;
         } else {
-            let msg = format!("Cannot handle physical channels of CanCluster {}", can_cluster_name);
-            return Err(msg.to_string());
+            let msg = format!("Cannot handle physical channels of CanCluster {}. Skipping this CanCluster.", can_cluster_name);
+            return Err(ParseDiagnostic::error(can_cluster, msg));
         }
 
         let mut can_frame_triggerings: HashMap<i64, CanFrameTriggering> = HashMap::new(); 
@@ -426,9 +437,14 @@ impl ArxmlParser {
                 for can_frame_triggering in frame_triggerings.sub_elements() {
                     match self.handle_can_frame_triggering(&can_frame_triggering) {
                         Ok(value) => {
-                            can_frame_triggerings.insert(value.can_id.clone(), value);
+                            let frame_triggering_name = value.frame_triggering_name.clone();
+                            if let Some(replaced) = can_frame_triggerings.insert(value.can_id.clone(), value) {
+                                let msg = format!("CanFrameTriggering {} replaces CanFrameTriggering {} with the same CAN ID {:#x} in CanCluster {}",
+                                    frame_triggering_name, replaced.frame_triggering_name, replaced.can_id, can_cluster_name);
+                                diagnostics.push(ParseDiagnostic::warning(&can_frame_triggering, msg));
+                            }
                         }
-                        Err(error) => println!("[-] WARNING: {}", error),
+                        Err(diagnostic) => diagnostics.push(diagnostic),
                     }
                 }
             }
@@ -446,8 +462,8 @@ impl ArxmlParser {
 
     // Main parsing method. Uses autosar-data libray for parsing ARXML 
     // In the future, it might be extended to support Etherneth, Flexray, ...
-    // Returns now a vector of CanCluster
-    pub fn parse_file(&self, file_name: String) -> Option<HashMap<String, CanCluster>> {
+    // Returns the CanClusters together with diagnostics about all elements, which were skipped
+    pub fn parse_file(&self, file_name: String) -> Option<ArxmlParseResult> {
         let start = Instant::now();
 
         let model = AutosarModel::new();
@@ -461,6 +477,7 @@ impl ArxmlParser {
         // DEBUG END
 
         let mut can_clusters: HashMap<String, CanCluster> = HashMap::new();
+        let mut diagnostics = ParseDiagnostics::default();
 
         // Iterate over Autosar elements and handle CanCluster elements
         for element in model
@@ -470,12 +487,12 @@ impl ArxmlParser {
         {
             match element.element_name() {
                 ElementName::CanCluster => {
-                    let result: Result<CanCluster, String> = self.handle_can_cluster(&element);
+                    let result: Result<CanCluster, ParseDiagnostic> = self.handle_can_cluster(&element, &mut diagnostics);
                     match result {
                         Ok(value) => {
                             can_clusters.insert(value.name.clone(), value);
                         }
-                        Err(error) => println!("[-] WARNING: {}", error)
+                        Err(diagnostic) => diagnostics.push(diagnostic)
                     }
                 }
                 _ => {}
@@ -484,6 +501,11 @@ impl ArxmlParser {
 
         println!("[+] Duration of parsing: {:?}", start.elapsed());
 
-        return Some(can_clusters);
+        if !diagnostics.is_empty() {
+            println!("[-] Parsing finished with {} errors and {} warnings. Export the diagnostics for details.",
+                diagnostics.count(DiagnosticSeverity::Error), diagnostics.count(DiagnosticSeverity::Warning));
+        }
+
+        return Some(ArxmlParseResult { can_clusters, diagnostics });
     }
 }
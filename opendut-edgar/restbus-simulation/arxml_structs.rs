@@ -1,123 +1,169 @@
-use std::collections::HashMap;
-
-#[derive(Debug)]
-pub struct CanCluster {
-    pub name: String,
-    pub baudrate: i64,
-    pub canfd_baudrate: i64,
-    pub can_frame_triggerings: HashMap<i64, CanFrameTriggering>
-}
-
-#[derive(Debug)]
-pub struct CanFrameTriggering {
-    pub frame_triggering_name: String,
-    pub frame_name: String,
-    pub can_id: i64,
-    pub addressing_mode: String,
-    pub frame_rx_behavior: String,
-    pub frame_tx_behavior: String,
-    pub rx_range_lower: i64,
-    pub rx_range_upper: i64,
-    pub sender_ecus: Vec<String>,
-    pub receiver_ecus: Vec<String>,
-    pub frame_length: i64,
-    pub pdu_mappings: Vec<PDUMapping>
-}
-
-#[derive(Debug)]
-pub struct PDUMapping {
-    pub name: String,
-    pub byte_order: bool,
-    pub start_position: i64,
-    pub length: i64,
-    pub dynamic_length: String,
-    pub category: String,
-    pub contained_header_id_short: String,
-    pub contained_header_id_long: String,
-    pub pdu: PDU
-}
-
-#[derive(Debug)]
-pub enum PDU {
-    ISignalIPDU(ISignalIPDU),
-    NMPDU(NMPDU),
-//     DCMIPDU(DCMIPDU),
-//    NMPDU(NMPDU),
-//     ContaineredPDU(XY),
-//    Temp(i64)
-}
-
-/*pub struct DCMIPDU {  // Seems to be only DoIP relevant
-    diag_pdu_type: String
-}*/
-
-/*pub struct NMPDU { // Seems to be only needed for Ethernet, not CAN
-    nm_signal: String,
-    start_pos: i64,
-    length: i64
-}*/
-
-#[derive(Debug)]
-pub struct ISignalIPDU {
-    pub cyclic_timing_period_value: f64,
-    pub cyclic_timing_period_tolerance: Option<TimeRangeTolerance>,
-    pub cyclic_timing_offset_value: f64,
-    pub cyclic_timing_offset_tolerance: Option<TimeRangeTolerance>,
-    pub number_of_repetitions: i64,
-    pub repetition_period_value: f64,
-    pub repetition_period_tolerance: Option<TimeRangeTolerance>,
-    pub unused_bit_pattern: bool,
-    pub ungrouped_signals: Vec<ISignal>,
-    pub grouped_signals: Vec<ISignalGroup>,
-}
-
-#[derive(Debug)]
-pub struct NMPDU {
-    pub unused_bit_pattern: bool,
-    pub ungrouped_signals: Vec<ISignal>,
-    pub grouped_signals: Vec<ISignalGroup>,
-}
-
-#[derive(Debug)]
-pub struct ISignal {
-    pub name: String,
-    pub byte_order: bool,
-    pub start_pos: i64,
-    pub length: i64,
-    pub init_values: InitValues
-}
-
-#[derive(Debug)]
-#[derive(Clone)]
-pub enum InitValues {
-    Single(i64),
-    Array(Vec<i64>),
-    NotExist(bool),
-}
-
-#[derive(Debug)]
-pub struct E2EDataTransformationProps {
-    pub transformer_name: String,
-    pub data_id: i64,
-    pub data_length: i64
-}
-
-#[derive(Debug)]
-pub struct ISignalGroup {
-    pub name: String,
-    pub isignals: Vec<ISignal>,
-    pub data_transformations: Vec<String>,
-    pub transformation_props: Vec<E2EDataTransformationProps>
-}
-
-#[derive(Debug)]
-pub enum TimeRangeTolerance {
-    Relative(i64),
-    Absolute(f64),
-}
-
-#[derive(Debug)]
-pub struct TimeRange {
-    pub tolerance: Option<TimeRangeTolerance>,
-    pub value: f64,
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct CanCluster {
+    pub name: String,
+    pub baudrate: i64,
+    pub canfd_baudrate: i64,
+    pub can_frame_triggerings: HashMap<i64, CanFrameTriggering>
+}
+
+#[derive(Debug)]
+pub struct CanFrameTriggering {
+    pub frame_triggering_name: String,
+    pub frame_name: String,
+    pub can_id: i64,
+    pub addressing_mode: String,
+    pub frame_rx_behavior: String,
+    pub frame_tx_behavior: String,
+    pub rx_range_lower: i64,
+    pub rx_range_upper: i64,
+    pub sender_ecus: Vec<String>,
+    pub receiver_ecus: Vec<String>,
+    pub frame_length: i64,
+    pub frame_format: CanFrameFormat,
+    pub can_xl_props: Option<CanXlFrameProps>,
+    pub pdu_mappings: Vec<PDUMapping>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CanFrameFormat {
+    Classic,
+    Fd,
+    Xl,
+}
+
+// CAN XL specific fields of a CanFrameTriggering. Values not given in the ARXML are 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanXlFrameProps {
+    // 11 bit priority, which replaces the CAN ID in the arbitration
+    pub priority_id: i64,
+    // SDU type, describing the content of the payload, e.g. 0x03 for classic/FD frame tunneling
+    pub sdu_type: i64,
+    pub vcid: i64,
+    pub acceptance_field: i64,
+}
+
+#[derive(Debug)]
+pub struct PDUMapping {
+    pub name: String,
+    pub byte_order: bool,
+    pub start_position: i64,
+    pub length: i64,
+    pub dynamic_length: String,
+    pub category: String,
+    pub contained_header_id_short: String,
+    pub contained_header_id_long: String,
+    pub pdu: PDU
+}
+
+#[derive(Debug)]
+pub enum PDU {
+    ISignalIPDU(ISignalIPDU),
+    NMPDU(NMPDU),
+//     DCMIPDU(DCMIPDU),
+//    NMPDU(NMPDU),
+//     ContaineredPDU(XY),
+//    Temp(i64)
+}
+
+/*pub struct DCMIPDU {  // Seems to be only DoIP relevant
+    diag_pdu_type: String
+}*/
+
+/*pub struct NMPDU { // Seems to be only needed for Ethernet, not CAN
+    nm_signal: String,
+    start_pos: i64,
+    length: i64
+}*/
+
+#[derive(Debug)]
+pub struct ISignalIPDU {
+    pub cyclic_timing_period_value: f64,
+    pub cyclic_timing_period_tolerance: Option<TimeRangeTolerance>,
+    pub cyclic_timing_offset_value: f64,
+    pub cyclic_timing_offset_tolerance: Option<TimeRangeTolerance>,
+    pub number_of_repetitions: i64,
+    pub repetition_period_value: f64,
+    pub repetition_period_tolerance: Option<TimeRangeTolerance>,
+    pub unused_bit_pattern: bool,
+    pub ungrouped_signals: Vec<ISignal>,
+    pub grouped_signals: Vec<ISignalGroup>,
+}
+
+#[derive(Debug)]
+pub struct NMPDU {
+    pub unused_bit_pattern: bool,
+    pub ungrouped_signals: Vec<ISignal>,
+    pub grouped_signals: Vec<ISignalGroup>,
+}
+
+#[derive(Debug)]
+pub struct ISignal {
+    pub name: String,
+    pub byte_order: bool,
+    pub start_pos: i64,
+    pub length: i64,
+    pub init_values: InitValues,
+    pub compu_method: Option<CompuMethod>
+}
+
+// Conversion of the raw value of a signal into its physical value, e.g. an engine speed in rpm
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompuMethod {
+    pub name: String,
+    // e.g. IDENTICAL, LINEAR, SCALE_LINEAR, TEXTTABLE or SCALE_LINEAR_AND_TEXTTABLE
+    pub category: String,
+    pub scales: Vec<CompuScale>,
+}
+
+// Limits are given as raw values. None, if the limit is not given in the ARXML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompuScale {
+    pub lower_limit: Option<f64>,
+    pub upper_limit: Option<f64>,
+    pub content: CompuScaleContent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompuScaleContent {
+    // physical value = (numerator[0] + numerator[1] * raw value + ...) / (denominator[0] + denominator[1] * raw value + ...)
+    Rational { numerator: Vec<f64>, denominator: Vec<f64> },
+    // Text of a TEXTTABLE entry, e.g. "Reverse" for a gear position
+    Text(String),
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub enum InitValues {
+    Single(i64),
+    Array(Vec<i64>),
+    NotExist(bool),
+}
+
+#[derive(Debug)]
+pub struct E2EDataTransformationProps {
+    pub transformer_name: String,
+    pub data_id: i64,
+    pub data_length: i64
+}
+
+#[derive(Debug)]
+pub struct ISignalGroup {
+    pub name: String,
+    pub isignals: Vec<ISignal>,
+    pub data_transformations: Vec<String>,
+    pub transformation_props: Vec<E2EDataTransformationProps>
+}
+
+#[derive(Debug)]
+pub enum TimeRangeTolerance {
+    Relative(i64),
+    Absolute(f64),
+}
+
+#[derive(Debug)]
+pub struct TimeRange {
+    pub tolerance: Option<TimeRangeTolerance>,
+    pub value: f64,
 }    
\ No newline at end of file
@@ -0,0 +1,303 @@
+use std::io;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/*
+- Error handling for the CAN interface a bus is transmitted on. When the frames of the simulation are not acknowledged
+  (e.g. unplugged cable, missing termination, no other node on the bus), the CAN controller becomes error passive and finally bus-off,
+  after which it does not send anything anymore. Without handling, the simulation keeps scheduling frames, which never reach the bus.
+- Each bus has a BusErrorSupervisor, which outlives restarts of the bus, like its RestbusControl. The transmitting thread
+  passes failed transmissions to BusErrorSupervisor::transmission_failed() and calls BusErrorSupervisor::poll() before each chunk of transmissions.
+  The state of the controller is read every STATE_POLL_INTERVAL and right after a failed transmission (see parse_bus_state()).
+- What happens when the controller is error passive or bus-off, is defined by the BusErrorPolicy:
+    Restart   restarts a bus-off controller via "ip link set <interface> type can restart", waiting an exponentially growing backoff
+              before each attempt. An error passive controller is only reported, as it still transmits.
+    Alert     keeps transmitting and reports each change of the state, so that the test run can be marked as inconclusive.
+    Halt      stops transmitting on the bus, until it is resumed via the control socket.
+- The state of each bus is surfaced via the control socket (see restbus_control.rs):
+    health CAN_Powertrain    ->  OK state=BusOff failed-transmissions=42 restarts=3 last-error="No buffer space available (os error 105)"
+    resume CAN_Powertrain    resumes a halted bus, restarting its controller if it is bus-off
+- Virtual CAN interfaces (vcan) have no controller state, so only failed transmissions are counted for them.
+*/
+
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BusState {
+    ErrorActive,
+    ErrorWarning,
+    ErrorPassive,
+    BusOff,
+    Stopped,
+}
+
+impl BusState {
+    fn is_degraded(&self) -> bool {
+        return matches!(self, BusState::ErrorPassive | BusState::BusOff);
+    }
+}
+
+// Defines what happens, when the controller of a bus is error passive or bus-off
+#[derive(Debug, Clone, PartialEq)]
+pub enum BusErrorPolicy {
+    // Restarts a bus-off controller. The backoff doubles with each attempt up to max_backoff and is reset, once the bus stayed error active for max_backoff.
+    // After max_attempts restarts without recovery, the bus is halted. None restarts indefinitely.
+    Restart { initial_backoff: Duration, max_backoff: Duration, max_attempts: Option<u32> },
+    // Only reports the state, keeps transmitting
+    Alert,
+    // Stops transmitting on the bus
+    Halt,
+}
+
+impl Default for BusErrorPolicy {
+    fn default() -> BusErrorPolicy {
+        return BusErrorPolicy::Restart {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            max_attempts: None,
+        };
+    }
+}
+
+// Reads and restarts the CAN controller of an interface
+pub trait CanController: Send + Sync {
+    // Returns None for interfaces without a controller state, e.g. vcan
+    fn state(&self, interface: &str) -> Result<Option<BusState>, String>;
+    fn restart(&self, interface: &str) -> Result<(), String>;
+}
+
+// Uses "ip" (iproute2), like EDGAR's CanManager does for configuring CAN interfaces
+pub struct IpLinkController;
+
+impl CanController for IpLinkController {
+    fn state(&self, interface: &str) -> Result<Option<BusState>, String> {
+        let output = Command::new("ip")
+            .args(["-details", "link", "show", interface])
+            .output()
+            .map_err(|cause| format!("Failed to execute ip for reading the state of interface '{interface}': {cause}"))?;
+        if !output.status.success() {
+            return Err(format!("Failed to read the state of interface '{interface}': {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        return Ok(parse_bus_state(&String::from_utf8_lossy(&output.stdout)));
+    }
+
+    fn restart(&self, interface: &str) -> Result<(), String> {
+        let output = Command::new("ip")
+            .args(["link", "set", interface, "type", "can", "restart"])
+            .output()
+            .map_err(|cause| format!("Failed to execute ip for restarting interface '{interface}': {cause}"))?;
+        if !output.status.success() {
+            return Err(format!("Failed to restart interface '{interface}': {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        return Ok(());
+    }
+}
+
+// Parses the controller state from the output of "ip -details link show", which contains a line like
+// "    can <FD> state ERROR-PASSIVE (berr-counter tx 128 rx 0) restart-ms 0"
+pub fn parse_bus_state(output: &str) -> Option<BusState> {
+    for line in output.lines() {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() != Some("can") {
+            continue;
+        }
+        let state = tokens.skip_while(|token| *token != "state").nth(1)?;
+        return match state {
+            "ERROR-ACTIVE" => Some(BusState::ErrorActive),
+            "ERROR-WARNING" => Some(BusState::ErrorWarning),
+            "ERROR-PASSIVE" => Some(BusState::ErrorPassive),
+            "BUS-OFF" => Some(BusState::BusOff),
+            "STOPPED" => Some(BusState::Stopped),
+            _ => None,
+        };
+    }
+    return None;
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BusErrorStatus {
+    pub state: Option<BusState>,
+    pub halted: bool,
+    pub failed_transmissions: u64,
+    pub restarts: u64,
+    pub last_error: Option<String>,
+}
+
+impl BusErrorStatus {
+    pub fn describe(&self) -> String {
+        let state = match self.state {
+            Some(state) => format!("{state:?}"),
+            None => String::from("Unknown"),
+        };
+        let mut description = format!("state={state}");
+        if self.halted {
+            description += " halted";
+        }
+        description += &format!(" failed-transmissions={} restarts={}", self.failed_transmissions, self.restarts);
+        if let Some(last_error) = &self.last_error {
+            description += &format!(" last-error=\"{last_error}\"");
+        }
+        return description;
+    }
+}
+
+#[derive(Debug, Default)]
+struct SupervisorState {
+    status: BusErrorStatus,
+    next_poll: Duration,
+    next_restart: Option<Duration>,
+    backoff: Option<Duration>,
+    attempts: u32,
+    // Time since which the bus is error active again after a restart, to reset the backoff once it is stable
+    recovered_since: Option<Duration>,
+}
+
+pub struct BusErrorSupervisor {
+    bus: String,
+    interface: Option<String>,
+    policy: BusErrorPolicy,
+    controller: Arc<dyn CanController>,
+    state: Mutex<SupervisorState>,
+}
+
+impl BusErrorSupervisor {
+    // Without an interface, e.g. when the bus is not mapped to one, only failed transmissions are counted
+    pub fn new(bus: String, interface: Option<String>, policy: BusErrorPolicy, controller: Arc<dyn CanController>) -> BusErrorSupervisor {
+        return BusErrorSupervisor { bus, interface, policy, controller, state: Mutex::new(SupervisorState::default()) };
+    }
+
+    pub fn bus(&self) -> &str {
+        return &self.bus;
+    }
+
+    pub fn status(&self) -> BusErrorStatus {
+        return self.state.lock().unwrap().status.clone();
+    }
+
+    // Halted buses drop their transmissions
+    pub fn may_transmit(&self) -> bool {
+        return !self.state.lock().unwrap().status.halted;
+    }
+
+    // Counts the failed transmission and reads the state of the controller with the next poll
+    pub fn transmission_failed(&self, cause: &io::Error, now: Duration) {
+        let mut state = self.state.lock().unwrap();
+        if state.status.failed_transmissions == 0 || state.status.last_error.as_deref() != Some(&cause.to_string()) {
+            println!("[-] WARNING: Failed to transmit frame on bus '{}': {cause}", self.bus);
+        }
+        state.status.failed_transmissions += 1;
+        state.status.last_error = Some(cause.to_string());
+        state.next_poll = state.next_poll.min(now);
+    }
+
+    // Reads the state of the controller, if due, and handles it according to the policy. The time is taken from the Clock of the scheduler.
+    pub fn poll(&self, now: Duration) {
+        let Some(interface) = &self.interface else { return };
+        let mut state = self.state.lock().unwrap();
+        if now < state.next_poll {
+            return;
+        }
+        state.next_poll = now + STATE_POLL_INTERVAL;
+
+        let bus_state = match self.controller.state(interface) {
+            Ok(bus_state) => bus_state,
+            Err(cause) => {
+                println!("[-] WARNING: Could not read the state of bus '{}': {cause}", self.bus);
+                return;
+            }
+        };
+        if bus_state != state.status.state {
+            self.report_transition(state.status.state, bus_state, interface);
+            state.status.state = bus_state;
+        }
+        if state.status.halted {
+            return;
+        }
+
+        match bus_state {
+            Some(bus_state) if bus_state.is_degraded() => {
+                state.recovered_since = None;
+                match &self.policy {
+                    BusErrorPolicy::Restart { initial_backoff, max_backoff, max_attempts } => {
+                        if bus_state == BusState::BusOff {
+                            self.restart_when_due(&mut state, interface, now, *initial_backoff, *max_backoff, *max_attempts);
+                        }
+                    }
+                    BusErrorPolicy::Alert => {}
+                    BusErrorPolicy::Halt => self.halt(&mut state, format!("Interface '{interface}' is {bus_state:?}.")),
+                }
+            }
+            _ => {
+                state.next_restart = None;
+                if let BusErrorPolicy::Restart { max_backoff, .. } = &self.policy {
+                    let recovered_since = *state.recovered_since.get_or_insert(now);
+                    if now.saturating_sub(recovered_since) >= *max_backoff {
+                        state.backoff = None;
+                        state.attempts = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    // Resumes a halted bus. Restarts its controller, if it is bus-off. Returns false, if the bus was not halted.
+    pub fn resume(&self) -> Result<bool, String> {
+        let mut state = self.state.lock().unwrap();
+        if !state.status.halted {
+            return Ok(false);
+        }
+        if let (Some(interface), Some(BusState::BusOff)) = (&self.interface, state.status.state) {
+            self.controller.restart(interface)?;
+            state.status.restarts += 1;
+        }
+        state.status.halted = false;
+        state.next_restart = None;
+        state.backoff = None;
+        state.attempts = 0;
+        state.next_poll = Duration::ZERO;
+        println!("[+] Resumed transmitting on bus '{}'", self.bus);
+        return Ok(true);
+    }
+
+    fn restart_when_due(&self, state: &mut SupervisorState, interface: &str, now: Duration, initial_backoff: Duration, max_backoff: Duration, max_attempts: Option<u32>) {
+        let backoff = *state.backoff.get_or_insert(initial_backoff);
+        let next_restart = *state.next_restart.get_or_insert(now + backoff);
+        if now < next_restart {
+            return;
+        }
+
+        if max_attempts.is_some_and(|max_attempts| state.attempts >= max_attempts) {
+            self.halt(state, format!("Interface '{interface}' is still bus-off after {} restarts.", state.attempts));
+            return;
+        }
+        state.attempts += 1;
+        state.backoff = Some((backoff * 2).min(max_backoff));
+        state.next_restart = None;
+
+        match self.controller.restart(interface) {
+            Ok(()) => {
+                state.status.restarts += 1;
+                state.next_poll = now;
+                println!("[+] Restarted interface '{interface}' of bus '{}' after bus-off (attempt {}, waited {}ms)", self.bus, state.attempts, backoff.as_millis());
+            }
+            Err(cause) => println!("[-] WARNING: Could not restart bus '{}' after bus-off: {cause}", self.bus),
+        }
+    }
+
+    fn halt(&self, state: &mut SupervisorState, reason: String) {
+        state.status.halted = true;
+        state.next_restart = None;
+        println!("[-] WARNING: Halted transmitting on bus '{}': {reason} Resume it via the control socket, once the bus is fixed.", self.bus);
+    }
+
+    fn report_transition(&self, previous: Option<BusState>, current: Option<BusState>, interface: &str) {
+        let describe = |state: Option<BusState>| state.map(|state| format!("{state:?}")).unwrap_or_else(|| String::from("Unknown"));
+        let message = format!("Interface '{interface}' of bus '{}' changed from {} to {}", self.bus, describe(previous), describe(current));
+        if current.is_some_and(|state| state.is_degraded()) {
+            println!("[-] WARNING: {message}");
+        } else {
+            println!("[+] {message}");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use opendut_types::peer::configuration::{ParameterTarget, PeerConfiguration};
+use opendut_types::peer::restbus::RestbusChannel;
+
+use crate::arxml_structs::*;
+use crate::restbus_lifecycle::*;
+use crate::restbus_scheduler::*;
+
+/*
+- Binds the buses (CanClusters) of the parsed communication database to the SocketCAN interfaces of the peer.
+- The channels are delivered via the PeerConfiguration (see RestbusChannel). CARL derives them from devices tagged with
+  "restbus:<CanCluster>", so the bus is sent on the interface the device is connected to.
+- Multi-channel CAN adapters show up as one interface per channel (e.g. can0 and can1 for the two channels of a PCAN-USB Pro),
+  so several buses of the same adapter are bound by referencing the respective interfaces.
+- Before the simulation starts, RestbusChannelMapping::validate() checks that every channel references a known bus and an
+  existing interface. All problems are reported at once, so a misconfigured peer can be fixed in one go.
+*/
+
+const NETWORK_INTERFACES_PATH: &str = "/sys/class/net";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelMappingError {
+    UnknownBus { bus: String, interface: String },
+    MissingInterface { bus: String, interface: String },
+    AmbiguousBus { bus: String, interfaces: Vec<String> },
+}
+
+impl ChannelMappingError {
+    pub fn message(&self) -> String {
+        return match self {
+            ChannelMappingError::UnknownBus { bus, interface } =>
+                format!("Channel on interface '{interface}' references bus '{bus}', which is not contained in the communication database."),
+            ChannelMappingError::MissingInterface { bus, interface } =>
+                format!("Bus '{bus}' is mapped to interface '{interface}', which does not exist on this host."),
+            ChannelMappingError::AmbiguousBus { bus, interfaces } =>
+                format!("Bus '{bus}' is mapped to multiple interfaces: {}. Each bus can only be sent on one interface.", interfaces.join(", ")),
+        };
+    }
+}
+
+pub fn format_channel_mapping_errors(errors: &[ChannelMappingError]) -> String {
+    let messages = errors.iter()
+        .map(|error| format!("  - {}", error.message()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    return format!("Invalid restbus channel mapping:\n{messages}");
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RestbusChannelMapping {
+    // Maps the name of a CanCluster to the name of the SocketCAN interface(s) it is bound to. Sorted by bus for stable error reports.
+    channels: BTreeMap<String, Vec<String>>,
+}
+
+impl RestbusChannelMapping {
+    pub fn new(channels: &[RestbusChannel]) -> RestbusChannelMapping {
+        let mut mapping = RestbusChannelMapping::default();
+        for channel in channels {
+            let interfaces = mapping.channels.entry(channel.bus.clone()).or_default();
+            let interface = channel.interface.name();
+            if !interfaces.contains(&interface) {
+                interfaces.push(interface);
+            }
+        }
+        return mapping;
+    }
+
+    // Only channels, which should be Present, are mapped. Absent channels are released by not mapping them anymore.
+    pub fn from_peer_configuration(peer_configuration: &PeerConfiguration) -> RestbusChannelMapping {
+        let channels = peer_configuration.restbus_channels.iter()
+            .filter(|parameter| parameter.target == ParameterTarget::Present)
+            .map(|parameter| parameter.value.clone())
+            .collect::<Vec<_>>();
+        return RestbusChannelMapping::new(&channels);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.channels.is_empty();
+    }
+
+    pub fn interface_of(&self, bus: &str) -> Option<&String> {
+        return self.channels.get(bus).and_then(|interfaces| interfaces.first());
+    }
+
+    pub fn validate(&self, can_clusters: &HashMap<String, CanCluster>) -> Result<(), Vec<ChannelMappingError>> {
+        return self.validate_with(can_clusters, |interface| Path::new(NETWORK_INTERFACES_PATH).join(interface).exists());
+    }
+
+    // Checks the mapping against the parsed CanClusters and the interfaces, for which interface_exists returns true
+    pub fn validate_with(&self, can_clusters: &HashMap<String, CanCluster>, interface_exists: impl Fn(&str) -> bool) -> Result<(), Vec<ChannelMappingError>> {
+        let mut errors = Vec::new();
+
+        for (bus, interfaces) in &self.channels {
+            if interfaces.len() > 1 {
+                errors.push(ChannelMappingError::AmbiguousBus { bus: bus.clone(), interfaces: interfaces.clone() });
+            }
+            for interface in interfaces {
+                if !can_clusters.contains_key(bus) {
+                    errors.push(ChannelMappingError::UnknownBus { bus: bus.clone(), interface: interface.clone() });
+                }
+                if !interface_exists(interface) {
+                    errors.push(ChannelMappingError::MissingInterface { bus: bus.clone(), interface: interface.clone() });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        return Err(errors);
+    }
+}
+
+// Sends the frames of each bus via the interface the bus is mapped to.
+// Errors of the socket are returned, so that the bus can react to an error passive or bus-off controller (see restbus_bus_errors.rs).
+pub trait InterfaceTransmitter: Send + Sync {
+    fn transmit_on(&self, interface: &str, transmission: &ScheduledTransmission) -> io::Result<()>;
+}
+
+pub struct MappedFrameTransmitter {
+    mapping: RestbusChannelMapping,
+    transmitter: Arc<dyn InterfaceTransmitter>,
+}
+
+impl MappedFrameTransmitter {
+    // Fails with all problems of the mapping, so that the simulation is not started with a partially working mapping
+    pub fn new(mapping: RestbusChannelMapping, can_clusters: &HashMap<String, CanCluster>, transmitter: Arc<dyn InterfaceTransmitter>) -> Result<MappedFrameTransmitter, String> {
+        mapping.validate(can_clusters)
+            .map_err(|errors| format_channel_mapping_errors(&errors))?;
+        return Ok(MappedFrameTransmitter { mapping, transmitter });
+    }
+}
+
+impl FrameTransmitter for MappedFrameTransmitter {
+    fn transmit(&self, bus: &str, transmission: &ScheduledTransmission) -> io::Result<()> {
+        match self.mapping.interface_of(bus) {
+            Some(interface) => self.transmitter.transmit_on(interface, transmission),
+            None => {
+                println!("[-] WARNING: Bus '{bus}' is not mapped to an interface. Not sending frame.");
+                Ok(())
+            }
+        }
+    }
+
+    fn interface_of(&self, bus: &str) -> Option<String> {
+        return self.mapping.interface_of(bus).cloned();
+    }
+}
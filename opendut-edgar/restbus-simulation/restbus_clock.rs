@@ -0,0 +1,152 @@
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/*
+- Clock abstraction for the restbus simulation's scheduler.
+- The scheduler only knows the simulated time since the start of the simulation and asks the clock to wait until the next transmission is due.
+- Besides the real-time clock, this allows running the simulation against a virtual clock:
+    - ScaledClock runs faster (or slower) than real-time, e.g. to check long-duration scenarios within minutes.
+    - VirtualClock jumps to the next deadline immediately, so that a whole schedule is computed without waiting at all.
+    - SteppedClock only advances when step() is called, e.g. from a test which checks the transmissions after every step.
+*/
+
+pub trait Clock: Send + Sync {
+    // Simulated time since the start of the simulation
+    fn now(&self) -> Duration;
+
+    // Blocks until the simulated time reached the deadline. Returns immediately, if the deadline already passed.
+    fn sleep_until(&self, deadline: Duration);
+}
+
+// Follows the wall clock, used when transmitting on a real bus
+pub struct RealTimeClock {
+    start: Instant,
+}
+
+impl RealTimeClock {
+    pub fn new() -> RealTimeClock {
+        return RealTimeClock { start: Instant::now() };
+    }
+}
+
+impl Default for RealTimeClock {
+    fn default() -> Self {
+        return RealTimeClock::new();
+    }
+}
+
+impl Clock for RealTimeClock {
+    fn now(&self) -> Duration {
+        return self.start.elapsed();
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let now = self.now();
+        if deadline > now {
+            thread::sleep(deadline - now);
+        }
+    }
+}
+
+// Follows the wall clock, but multiplied by a factor. A factor of 60.0 simulates one hour per minute.
+pub struct ScaledClock {
+    start: Instant,
+    factor: f64,
+}
+
+impl ScaledClock {
+    pub fn new(factor: f64) -> Result<ScaledClock, String> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(format!("Clock factor must be a positive number, but was {}", factor));
+        }
+        return Ok(ScaledClock { start: Instant::now(), factor });
+    }
+}
+
+impl Clock for ScaledClock {
+    fn now(&self) -> Duration {
+        return self.start.elapsed().mul_f64(self.factor);
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let now = self.now();
+        if deadline > now {
+            thread::sleep((deadline - now).div_f64(self.factor));
+        }
+    }
+}
+
+// Never waits. Sleeping moves the simulated time directly to the deadline.
+pub struct VirtualClock {
+    now: Mutex<Duration>,
+}
+
+impl VirtualClock {
+    pub fn new() -> VirtualClock {
+        return VirtualClock { now: Mutex::new(Duration::ZERO) };
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        return VirtualClock::new();
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        return *self.now.lock().unwrap();
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let mut now = self.now.lock().unwrap();
+        if deadline > *now {
+            *now = deadline;
+        }
+    }
+}
+
+// Only advances when step() or advance_to() is called. Sleeping blocks until another thread advanced the time far enough.
+pub struct SteppedClock {
+    now: Mutex<Duration>,
+    advanced: Condvar,
+}
+
+impl SteppedClock {
+    pub fn new() -> SteppedClock {
+        return SteppedClock { now: Mutex::new(Duration::ZERO), advanced: Condvar::new() };
+    }
+
+    pub fn step(&self, step: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += step;
+        self.advanced.notify_all();
+    }
+
+    // Time never runs backwards, so earlier points in time are ignored
+    pub fn advance_to(&self, time: Duration) {
+        let mut now = self.now.lock().unwrap();
+        if time > *now {
+            *now = time;
+            self.advanced.notify_all();
+        }
+    }
+}
+
+impl Default for SteppedClock {
+    fn default() -> Self {
+        return SteppedClock::new();
+    }
+}
+
+impl Clock for SteppedClock {
+    fn now(&self) -> Duration {
+        return *self.now.lock().unwrap();
+    }
+
+    fn sleep_until(&self, deadline: Duration) {
+        let now = self.now.lock().unwrap();
+        let _now = self.advanced.wait_while(now, |now| *now < deadline).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::arxml_structs::*;
+
+/*
+- Detection of CAN IDs that are already sent by real ECUs on the bus the restbus simulation is going to transmit on.
+- Sending the same CAN ID from the simulation and a real ECU leads to intermittent, hard to debug errors (wrong signal values,
+  error frames when two nodes win arbitration with different payloads, ...).
+- Before starting the transmission, the bus can optionally be observed for a while. Every observed CAN ID that is also part of the
+  simulation's transmit schedule is a conflict. Depending on the ConflictPolicy, conflicts are only reported or excluded from the schedule.
+- Uses candump (can-utils) for listening, like cangw is used in EDGAR's CanManager.
+*/
+
+// Defines what happens with CAN IDs of the simulation that are already sent by someone else on the bus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    // Only print a warning, keep sending the conflicting CAN IDs
+    Warn,
+    // Print a warning and remove the conflicting CAN IDs from the transmit schedule
+    Exclude,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConflictDetectionConfig {
+    pub interface: String,
+    pub listen_duration: Duration,
+    pub policy: ConflictPolicy,
+}
+
+// Returns the CAN IDs of all frames that the restbus simulation would transmit for the given CanCluster.
+// A frame is transmitted when at least one of its PDUs is an ISignalIPDU with cyclic or repeated timing.
+pub fn scheduled_can_ids(can_cluster: &CanCluster) -> HashSet<i64> {
+    let mut can_ids: HashSet<i64> = HashSet::new();
+
+    for (can_id, can_frame_triggering) in &can_cluster.can_frame_triggerings {
+        for pdu_mapping in &can_frame_triggering.pdu_mappings {
+            if let PDU::ISignalIPDU(isignal_ipdu) = &pdu_mapping.pdu {
+                if isignal_ipdu.cyclic_timing_period_value > 0.0 || isignal_ipdu.number_of_repetitions > 0 {
+                    can_ids.insert(*can_id);
+                }
+            }
+        }
+    }
+
+    return can_ids;
+}
+
+// Parses a single line of "candump -L" output, e.g. "(1436509052.249713) vcan0 44C#44C0000000000000" or "(...) can0 1234ABCD##1DEAD" (CAN FD)
+pub fn parse_candump_line(line: &str) -> Option<i64> {
+    let frame = line.split_whitespace().nth(2)?;
+    let (can_id, _) = frame.split_once('#')?;
+
+    return i64::from_str_radix(can_id, 16).ok();
+}
+
+// Listens on the given interface for the given duration and returns all CAN IDs that were observed
+pub fn listen_for_can_ids(interface: &String, listen_duration: Duration) -> Result<HashSet<i64>, String> {
+    let mut child = Command::new("candump")
+        .arg("-L")
+        .arg(interface)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to execute candump on interface {}: {}", interface, err))?;
+
+    thread::sleep(listen_duration);
+
+    // candump exiting before the listen duration passed means it failed, e.g. because the interface does not exist
+    if let Ok(Some(status)) = child.try_wait() {
+        let mut stderr = String::new();
+        if let Some(mut child_stderr) = child.stderr.take() {
+            let _ = child_stderr.read_to_string(&mut stderr);
+        }
+        return Err(format!("candump on interface {} exited early with {}: {}", interface, status, stderr.trim()));
+    }
+
+    let _ = child.kill();
+
+    let output = child.wait_with_output()
+        .map_err(|err| format!("Failed to read output of candump on interface {}: {}", interface, err))?;
+
+    let observed_can_ids: HashSet<i64> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_candump_line)
+        .collect();
+
+    return Ok(observed_can_ids);
+}
+
+// Returns the scheduled CAN IDs that were also observed on the bus, sorted for stable output
+pub fn find_conflicts(scheduled_can_ids: &HashSet<i64>, observed_can_ids: &HashSet<i64>) -> Vec<i64> {
+    let mut conflicts: Vec<i64> = scheduled_can_ids.intersection(observed_can_ids).cloned().collect();
+    conflicts.sort();
+    return conflicts;
+}
+
+// Applies the ConflictPolicy to the CanCluster. Returns the conflicting CAN IDs.
+pub fn apply_conflict_policy(can_cluster: &mut CanCluster, conflicts: &Vec<i64>, policy: ConflictPolicy) -> Vec<i64> {
+    for can_id in conflicts {
+        let frame_name = can_cluster.can_frame_triggerings.get(can_id)
+            .map(|can_frame_triggering| can_frame_triggering.frame_name.clone())
+            .unwrap_or_default();
+
+        match policy {
+            ConflictPolicy::Warn => {
+                println!("[-] WARNING: CAN ID 0x{:X} (frame {}) of CanCluster {} is already sent by another node on the bus. Sending it anyway.",
+                    can_id, frame_name, can_cluster.name);
+            }
+            ConflictPolicy::Exclude => {
+                println!("[-] WARNING: CAN ID 0x{:X} (frame {}) of CanCluster {} is already sent by another node on the bus. Excluding it from the simulation.",
+                    can_id, frame_name, can_cluster.name);
+                can_cluster.can_frame_triggerings.remove(can_id);
+            }
+        }
+    }
+
+    return conflicts.clone();
+}
+
+// Observes the bus and handles conflicts between the simulation's transmit schedule and the real ECUs on the bus.
+// Should be called right before starting the transmission. Returns the conflicting CAN IDs.
+pub fn detect_tx_conflicts(can_cluster: &mut CanCluster, config: &ConflictDetectionConfig) -> Result<Vec<i64>, String> {
+    let scheduled = scheduled_can_ids(can_cluster);
+
+    println!("[+] Listening on {} for {:?} to detect conflicting CAN IDs", config.interface, config.listen_duration);
+
+    let observed = listen_for_can_ids(&config.interface, config.listen_duration)?;
+
+    let conflicts = find_conflicts(&scheduled, &observed);
+
+    if conflicts.is_empty() {
+        println!("[+] No conflicting CAN IDs detected on {}", config.interface);
+    }
+
+    return Ok(apply_conflict_policy(can_cluster, &conflicts, config.policy));
+}
+
+// Same as detect_tx_conflicts, but for all CanClusters of the ArxmlParseResult returned by ArxmlParser::parse_file
+pub fn detect_tx_conflicts_for_clusters(can_clusters: &mut HashMap<String, CanCluster>, configs: &HashMap<String, ConflictDetectionConfig>) -> Result<HashMap<String, Vec<i64>>, String> {
+    let mut conflicts_per_cluster: HashMap<String, Vec<i64>> = HashMap::new();
+
+    for (cluster_name, can_cluster) in can_clusters.iter_mut() {
+        if let Some(config) = configs.get(cluster_name) {
+            let conflicts = detect_tx_conflicts(can_cluster, config)?;
+            conflicts_per_cluster.insert(cluster_name.clone(), conflicts);
+        }
+    }
+
+    return Ok(conflicts_per_cluster);
+}
@@ -0,0 +1,447 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::arxml_structs::*;
+use crate::restbus_bus_errors::*;
+use crate::restbus_clock::*;
+use crate::restbus_encoder::*;
+use crate::restbus_faults::*;
+use crate::restbus_scheduler::*;
+use crate::restbus_stimulation::*;
+
+/*
+- Runtime control of the restbus simulation, so that test engineers can manipulate the simulated ECUs during a test run,
+  without restarting EDGAR.
+- Each bus (CanCluster) has a RestbusControl, which is shared between the transmitting thread and the control interface:
+    - The scheduler thread calls RestbusControl::update_schedule() before each chunk of transmissions, to apply stopped/started frames
+      and changed cycle times to its TransmissionScheduler.
+    - The transmit function passes each frame's payload to RestbusControl::apply(), which writes the overridden signal values
+      and drops the transmissions of paused frames. It can be combined with the FaultInjector (see restbus_faults.rs).
+- The RestbusControlServer accepts commands on a Unix socket, one per line, and answers each with a line starting with "OK" or "ERROR":
+    stop CAN_Powertrain 0x1A0                    removes the frame from the schedule
+    start CAN_Powertrain 0x1A0                   restarts a stopped frame right away or resumes a paused frame
+    pause CAN_Powertrain 0x1A0                   keeps the schedule running, but does not send the frame
+    cycle CAN_Powertrain 0x1A0 50                changes the cycle time to 50 ms, "cycle ... default" restores the ARXML cycle time
+    set CAN_Powertrain 0x1A0 EngineSpeed=3000    overrides the raw value of one or more signals of the frame
+    reset CAN_Powertrain 0x1A0                   removes all signal overrides of the frame
+    stimulate CAN_Powertrain drive.csv loop      drives signals of the bus from a stimulation profile (see restbus_stimulation.rs),
+                                                 optionally looping and with "step" instead of linear interpolation
+    unstimulate CAN_Powertrain                   stops driving the signals from the stimulation profile
+    status CAN_Powertrain                        lists the frames which are not running unaltered
+    health CAN_Powertrain                        shows the state of the CAN controller and failed transmissions (see restbus_bus_errors.rs)
+    resume CAN_Powertrain                        resumes transmitting on a bus halted due to bus errors
+  The socket can be used e.g. with: echo "pause CAN_Powertrain 0x1A0" | socat - UNIX-CONNECT:/run/opendut/restbus-control.sock
+- Signal values are raw values, written into the payload with the same bit layout as the init values (see write_signal_value()).
+  To set physical values, e.g. for building payloads in tests, use the SignalEncoder (see restbus_encoder.rs).
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameState {
+    Running,
+    Paused,
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScheduleChange {
+    Stop(i64),
+    Start(i64),
+    SetPeriod(i64, Duration),
+    ResetPeriod(i64),
+}
+
+#[derive(Debug, Default)]
+struct ControlState {
+    frames: BTreeMap<i64, FrameState>,
+    cycle_times: BTreeMap<i64, Duration>,
+    overrides: BTreeMap<i64, BTreeMap<String, i64>>,
+    pending: Vec<ScheduleChange>,
+}
+
+pub struct RestbusControl {
+    bus: String,
+    signals: HashMap<i64, HashMap<String, SignalLayout>>,
+    state: Mutex<ControlState>,
+    stimulation: Mutex<Option<SignalStimulation>>,
+}
+
+fn parse_can_id(value: &str) -> Result<i64, String> {
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => value.parse::<i64>(),
+    };
+    return parsed.map_err(|cause| format!("Invalid CAN ID '{value}': {cause}"));
+}
+
+impl RestbusControl {
+    pub fn new(can_cluster: &CanCluster) -> RestbusControl {
+        let signals = can_cluster.can_frame_triggerings.iter()
+            .map(|(can_id, can_frame_triggering)| (*can_id, signal_layouts(can_frame_triggering)))
+            .collect();
+
+        return RestbusControl { bus: can_cluster.name.clone(), signals, state: Mutex::new(ControlState::default()), stimulation: Mutex::new(None) };
+    }
+
+    pub fn bus(&self) -> &str {
+        return &self.bus;
+    }
+
+    fn check_frame(&self, can_id: i64) -> Result<(), String> {
+        if !self.signals.contains_key(&can_id) {
+            return Err(format!("Bus '{}' has no frame with CAN ID {can_id:#X}.", self.bus));
+        }
+        return Ok(());
+    }
+
+    pub fn frame_state(&self, can_id: i64) -> FrameState {
+        return self.state.lock().unwrap().frames.get(&can_id).copied().unwrap_or(FrameState::Running);
+    }
+
+    pub fn stop(&self, can_id: i64) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        let mut state = self.state.lock().unwrap();
+        if state.frames.insert(can_id, FrameState::Stopped) != Some(FrameState::Stopped) {
+            state.pending.push(ScheduleChange::Stop(can_id));
+        }
+        return Ok(());
+    }
+
+    // Restarts a stopped frame or resumes a paused frame
+    pub fn start(&self, can_id: i64) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        let mut state = self.state.lock().unwrap();
+        if state.frames.remove(&can_id) == Some(FrameState::Stopped) {
+            state.pending.push(ScheduleChange::Start(can_id));
+        }
+        return Ok(());
+    }
+
+    pub fn pause(&self, can_id: i64) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        let mut state = self.state.lock().unwrap();
+        match state.frames.get(&can_id) {
+            Some(FrameState::Stopped) => return Err(format!("Frame with CAN ID {can_id:#X} is stopped. Start it, before pausing it.")),
+            _ => { state.frames.insert(can_id, FrameState::Paused); }
+        }
+        return Ok(());
+    }
+
+    // Changes the cycle time of the frame. None restores the cycle time from the ARXML.
+    pub fn set_cycle_time(&self, can_id: i64, cycle_time: Option<Duration>) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        let mut state = self.state.lock().unwrap();
+        match cycle_time {
+            Some(cycle_time) => {
+                if cycle_time == Duration::ZERO {
+                    return Err(format!("Cycle time of frame with CAN ID {can_id:#X} has to be greater than zero."));
+                }
+                state.cycle_times.insert(can_id, cycle_time);
+                state.pending.push(ScheduleChange::SetPeriod(can_id, cycle_time));
+            }
+            None => {
+                state.cycle_times.remove(&can_id);
+                state.pending.push(ScheduleChange::ResetPeriod(can_id));
+            }
+        }
+        return Ok(());
+    }
+
+    // Overrides the raw value of a signal in every following transmission of its frame
+    pub fn set_signal(&self, can_id: i64, signal: &str, value: i64) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        let layout = self.signals.get(&can_id)
+            .and_then(|signals| signals.get(signal))
+            .ok_or_else(|| format!("Frame with CAN ID {can_id:#X} on bus '{}' has no signal '{signal}'.", self.bus))?;
+        if layout.length > 64 {
+            return Err(format!("Signal '{signal}' has {} bits, values can only be set for signals with up to 64 bits.", layout.length));
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.overrides.entry(can_id).or_default().insert(signal.to_string(), value);
+        return Ok(());
+    }
+
+    pub fn reset_signals(&self, can_id: i64) -> Result<(), String> {
+        self.check_frame(can_id)?;
+        self.state.lock().unwrap().overrides.remove(&can_id);
+        return Ok(());
+    }
+
+    // Drives the signals of the profile from now on, replacing a previous stimulation
+    pub fn stimulate(&self, profile: StimulationProfile, options: StimulationOptions, clock: Arc<dyn Clock>) -> Result<(), String> {
+        let stimulation = SignalStimulation::new(profile, options, &self.signals, clock)?;
+        *self.stimulation.lock().unwrap() = Some(stimulation);
+        return Ok(());
+    }
+
+    // Returns false, if no stimulation was running
+    pub fn remove_stimulation(&self) -> bool {
+        return self.stimulation.lock().unwrap().take().is_some();
+    }
+
+    // Applies all stopped frames and changed cycle times to a newly created scheduler, e.g. when a bus is restarted
+    pub fn restore_schedule(&self, scheduler: &mut TransmissionScheduler) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.clear();
+
+        for (can_id, frame_state) in &state.frames {
+            if *frame_state == FrameState::Stopped {
+                scheduler.stop_frame(*can_id);
+            }
+        }
+        for (can_id, cycle_time) in &state.cycle_times {
+            if let Err(cause) = scheduler.set_period(*can_id, *cycle_time) {
+                println!("[-] WARNING: Could not change schedule of bus '{}': {}", self.bus, cause);
+            }
+        }
+    }
+
+    // Applies the stopped/started frames and changed cycle times since the last call to the scheduler
+    pub fn update_schedule(&self, scheduler: &mut TransmissionScheduler) {
+        let pending = std::mem::take(&mut self.state.lock().unwrap().pending);
+
+        for change in pending {
+            let result = match change {
+                ScheduleChange::Stop(can_id) => {
+                    scheduler.stop_frame(can_id);
+                    Ok(())
+                }
+                ScheduleChange::Start(can_id) => {
+                    scheduler.start_frame(can_id);
+                    Ok(())
+                }
+                ScheduleChange::SetPeriod(can_id, period) => scheduler.set_period(can_id, period),
+                ScheduleChange::ResetPeriod(can_id) => scheduler.reset_period(can_id),
+            };
+            if let Err(cause) = result {
+                println!("[-] WARNING: Could not change schedule of bus '{}': {}", self.bus, cause);
+            }
+        }
+    }
+
+    // Writes the stimulated and overridden signal values into the payload of one transmission. Paused and stopped frames are dropped.
+    pub fn apply(&self, can_id: i64, payload: &mut Vec<u8>) -> FaultOutcome {
+        let state = self.state.lock().unwrap();
+
+        if state.frames.get(&can_id).is_some_and(|frame_state| *frame_state != FrameState::Running) {
+            return FaultOutcome::Drop;
+        }
+
+        if let Some(stimulation) = self.stimulation.lock().unwrap().as_ref() {
+            stimulation.apply(can_id, payload);
+        }
+
+        if let (Some(overrides), Some(signals)) = (state.overrides.get(&can_id), self.signals.get(&can_id)) {
+            for (signal, value) in overrides {
+                if let Some(layout) = signals.get(signal) {
+                    if let Err(cause) = write_signal_value(payload, signal, layout, *value) {
+                        println!("[-] WARNING: Could not override signal of frame with CAN ID {can_id:#X}: {cause}");
+                    }
+                }
+            }
+        }
+
+        return FaultOutcome::Transmit;
+    }
+
+    // Describes each frame, which is not running unaltered
+    pub fn status(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+
+        let mut can_ids: Vec<i64> = state.frames.keys()
+            .chain(state.cycle_times.keys())
+            .chain(state.overrides.keys())
+            .copied()
+            .collect();
+        can_ids.sort();
+        can_ids.dedup();
+
+        let stimulation = self.stimulation.lock().unwrap().as_ref()
+            .map(|stimulation| stimulation.status());
+
+        return stimulation.into_iter().chain(can_ids.into_iter().map(|can_id| {
+            let frame_state = state.frames.get(&can_id).copied().unwrap_or(FrameState::Running);
+            let mut description = format!("{can_id:#X} {frame_state:?}");
+            if let Some(cycle_time) = state.cycle_times.get(&can_id) {
+                description += &format!(" cycle={}ms", cycle_time.as_millis());
+            }
+            if let Some(overrides) = state.overrides.get(&can_id) {
+                for (signal, value) in overrides {
+                    description += &format!(" {signal}={value}");
+                }
+            }
+            description
+        })).collect();
+    }
+}
+
+// Dispatches the commands of the control socket to the RestbusControl of the addressed bus
+pub struct RestbusControlServer {
+    controls: HashMap<String, Arc<RestbusControl>>,
+    bus_errors: HashMap<String, Arc<BusErrorSupervisor>>,
+}
+
+impl RestbusControlServer {
+    pub fn new(controls: Vec<Arc<RestbusControl>>) -> RestbusControlServer {
+        let controls = controls.into_iter()
+            .map(|control| (control.bus().to_string(), control))
+            .collect();
+        return RestbusControlServer { controls, bus_errors: HashMap::new() };
+    }
+
+    // Answers the commands "health" and "resume" with the given supervisors
+    pub fn with_bus_errors(mut self, bus_errors: Vec<Arc<BusErrorSupervisor>>) -> RestbusControlServer {
+        self.bus_errors = bus_errors.into_iter()
+            .map(|supervisor| (supervisor.bus().to_string(), supervisor))
+            .collect();
+        return self;
+    }
+
+    fn bus_errors_of(&self, bus: &str) -> Result<&Arc<BusErrorSupervisor>, String> {
+        return self.bus_errors.get(bus)
+            .ok_or_else(|| format!("Bus errors of bus '{bus}' are not supervised"));
+    }
+
+    // Handles one command and returns the answer without the leading "OK"
+    pub fn handle_command(&self, line: &str) -> Result<String, String> {
+        let mut tokens = line.split_whitespace();
+        let command = tokens.next()
+            .ok_or_else(|| String::from("Empty command"))?;
+        let bus = tokens.next()
+            .ok_or_else(|| format!("Command '{command}' is missing the name of the bus"))?;
+        let control = self.controls.get(bus)
+            .ok_or_else(|| format!("Bus '{bus}' is not simulated"))?;
+
+        match command {
+            "status" => return Ok(control.status().join("; ")),
+            "health" => return Ok(self.bus_errors_of(bus)?.status().describe()),
+            "resume" => {
+                if !self.bus_errors_of(bus)?.resume()? {
+                    return Err(format!("Bus '{bus}' is not halted"));
+                }
+                return Ok(String::new());
+            }
+            "stimulate" => {
+                let path = tokens.next()
+                    .ok_or_else(|| String::from("Command 'stimulate' is missing the path of the stimulation profile"))?;
+                let mut options = StimulationOptions::default();
+                for option in tokens {
+                    match option {
+                        "loop" => options.looping = true,
+                        "step" => options.interpolation = StimulationInterpolation::Step,
+                        "linear" => options.interpolation = StimulationInterpolation::Linear,
+                        _ => return Err(format!("Unknown option '{option}' of command 'stimulate', expected one of: loop, step, linear")),
+                    }
+                }
+                let profile = StimulationProfile::load(Path::new(path))?;
+                control.stimulate(profile, options, Arc::new(RealTimeClock::new()))?;
+                return Ok(String::new());
+            }
+            "unstimulate" => {
+                if !control.remove_stimulation() {
+                    return Err(format!("No stimulation profile is running on bus '{bus}'"));
+                }
+                return Ok(String::new());
+            }
+            _ => {}
+        }
+
+        let can_id = tokens.next()
+            .ok_or_else(|| format!("Command '{command}' is missing the CAN ID of the frame"))
+            .and_then(parse_can_id)?;
+
+        match command {
+            "stop" => control.stop(can_id)?,
+            "start" => control.start(can_id)?,
+            "pause" => control.pause(can_id)?,
+            "cycle" => {
+                let cycle_time = tokens.next()
+                    .ok_or_else(|| String::from("Command 'cycle' is missing the cycle time in milliseconds or 'default'"))?;
+                let cycle_time = match cycle_time {
+                    "default" => None,
+                    milliseconds => Some(Duration::from_millis(
+                        milliseconds.parse::<u64>()
+                            .map_err(|cause| format!("Invalid cycle time '{milliseconds}': {cause}"))?
+                    )),
+                };
+                control.set_cycle_time(can_id, cycle_time)?;
+            }
+            "set" => {
+                let mut assignments = tokens.peekable();
+                if assignments.peek().is_none() {
+                    return Err(String::from("Command 'set' expects at least one signal as name=value"));
+                }
+                for assignment in assignments {
+                    let (signal, value) = assignment.split_once('=')
+                        .ok_or_else(|| format!("Expected signal as name=value, but got '{assignment}'"))?;
+                    let value = value.parse::<i64>()
+                        .map_err(|cause| format!("Invalid value for signal '{signal}': {cause}"))?;
+                    control.set_signal(can_id, signal, value)?;
+                }
+            }
+            "reset" => control.reset_signals(can_id)?,
+            _ => return Err(format!("Unknown command '{command}', expected one of: start, stop, pause, cycle, set, reset, stimulate, unstimulate, status, health, resume")),
+        }
+        return Ok(String::new());
+    }
+
+    // Listens on the Unix socket in a background thread. An existing socket file is replaced.
+    pub fn serve(self, socket_path: &Path) -> Result<thread::JoinHandle<()>, String> {
+        if socket_path.exists() {
+            fs::remove_file(socket_path)
+                .map_err(|cause| format!("Could not remove existing control socket {}: {cause}", socket_path.display()))?;
+        }
+        let listener = UnixListener::bind(socket_path)
+            .map_err(|cause| format!("Could not listen on control socket {}: {cause}", socket_path.display()))?;
+        println!("[+] Listening for restbus control commands on {}", socket_path.display());
+
+        let server = Arc::new(self);
+        let socket_path = PathBuf::from(socket_path);
+
+        return Ok(thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let server = Arc::clone(&server);
+                        thread::spawn(move || server.handle_connection(stream));
+                    }
+                    Err(cause) => println!("[-] Failed to accept connection on control socket {}: {}", socket_path.display(), cause),
+                }
+            }
+        }));
+    }
+
+    fn handle_connection(&self, stream: UnixStream) {
+        let mut writer = match stream.try_clone() {
+            Ok(writer) => writer,
+            Err(cause) => {
+                println!("[-] Failed to set up connection on control socket: {}", cause);
+                return;
+            }
+        };
+
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let answer = match self.handle_command(&line) {
+                Ok(message) if message.is_empty() => String::from("OK"),
+                Ok(message) => format!("OK {message}"),
+                Err(cause) => {
+                    println!("[-] Invalid restbus control command '{}': {}", line.trim(), cause);
+                    format!("ERROR {cause}")
+                }
+            };
+            if writeln!(writer, "{answer}").is_err() {
+                break;
+            }
+        }
+    }
+}
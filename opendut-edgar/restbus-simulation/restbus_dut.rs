@@ -0,0 +1,199 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+use serde::Serialize;
+
+use crate::arxml_structs::*;
+use crate::restbus_lifecycle::*;
+
+/*
+- Identifies the communication of the device under test (DUT) from the parsed model, given the name of its ECU.
+- The DUT expects to receive every frame, which lists its ECU as receiver and which is sent by another ECU. These frames have to be
+  simulated by the restbus, so they are the default frames of the simulation for an executor testing this DUT.
+- The DUT transmits every frame, which lists its ECU as sender. These frames must not be simulated, even if the DUT also receives them,
+  but a test can check them against the expected timing.
+- Each frame is reported with the timing of its ISignalIPDU in milliseconds. Frames without timing (e.g. event-triggered frames) have no cycle time.
+- The communication can be exported as JSON, e.g. to be attached to test reports.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DutFrameDirection {
+    // Sent by other ECUs and received by the DUT
+    Rx,
+    // Sent by the DUT
+    Tx,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DutFrame {
+    // Name of the CanCluster
+    pub bus: String,
+    pub can_id: i64,
+    pub frame_name: String,
+    pub direction: DutFrameDirection,
+    pub frame_length: i64,
+    pub cycle_time_ms: Option<f64>,
+    pub offset_ms: f64,
+    pub number_of_repetitions: i64,
+    pub repetition_period_ms: f64,
+    // The other ECUs sending (for RX frames) or receiving (for TX frames) the frame
+    pub peer_ecus: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DutCommunication {
+    pub ecu: String,
+    // Sorted by bus and CAN ID
+    pub rx_frames: Vec<DutFrame>,
+    pub tx_frames: Vec<DutFrame>,
+}
+
+// ARXML timing values are given in seconds. Values, which are not given, are 0.
+fn seconds_to_millis(seconds: f64) -> f64 {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return 0.0;
+    }
+    return seconds * 1000.0;
+}
+
+fn dut_frame(bus: &String, can_id: i64, can_frame_triggering: &CanFrameTriggering, direction: DutFrameDirection, ecu: &str) -> DutFrame {
+    let mut frame = DutFrame {
+        bus: bus.clone(),
+        can_id,
+        frame_name: can_frame_triggering.frame_name.clone(),
+        direction,
+        frame_length: can_frame_triggering.frame_length,
+        cycle_time_ms: None,
+        offset_ms: 0.0,
+        number_of_repetitions: 0,
+        repetition_period_ms: 0.0,
+        peer_ecus: Vec::new(),
+    };
+
+    for pdu_mapping in &can_frame_triggering.pdu_mappings {
+        if let PDU::ISignalIPDU(isignal_ipdu) = &pdu_mapping.pdu {
+            let cycle_time = seconds_to_millis(isignal_ipdu.cyclic_timing_period_value);
+            frame.cycle_time_ms = if cycle_time > 0.0 { Some(cycle_time) } else { None };
+            frame.offset_ms = seconds_to_millis(isignal_ipdu.cyclic_timing_offset_value);
+            frame.number_of_repetitions = isignal_ipdu.number_of_repetitions;
+            frame.repetition_period_ms = seconds_to_millis(isignal_ipdu.repetition_period_value);
+            break;
+        }
+    }
+
+    let peer_ecus = match direction {
+        DutFrameDirection::Rx => &can_frame_triggering.sender_ecus,
+        DutFrameDirection::Tx => &can_frame_triggering.receiver_ecus,
+    };
+    frame.peer_ecus = peer_ecus.iter()
+        .filter(|peer_ecu| peer_ecu.as_str() != ecu)
+        .cloned()
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect();
+
+    return frame;
+}
+
+// Computes the frames the ECU receives and transmits on the given CanClusters.
+// Fails, if the ECU neither sends nor receives any frame, which usually means its name is misspelled.
+pub fn identify_dut_communication<'a>(can_clusters: impl IntoIterator<Item = &'a CanCluster>, ecu: &str) -> Result<DutCommunication, String> {
+    let mut communication = DutCommunication { ecu: ecu.to_string(), ..Default::default() };
+    let mut known_ecus: BTreeSet<&String> = BTreeSet::new();
+
+    for can_cluster in can_clusters {
+        for (can_id, can_frame_triggering) in &can_cluster.can_frame_triggerings {
+            known_ecus.extend(can_frame_triggering.sender_ecus.iter());
+            known_ecus.extend(can_frame_triggering.receiver_ecus.iter());
+
+            let is_sender = can_frame_triggering.sender_ecus.iter().any(|sender| sender == ecu);
+            let is_receiver = can_frame_triggering.receiver_ecus.iter().any(|receiver| receiver == ecu);
+
+            if is_sender {
+                communication.tx_frames.push(dut_frame(&can_cluster.name, *can_id, can_frame_triggering, DutFrameDirection::Tx, ecu));
+            } else if is_receiver {
+                communication.rx_frames.push(dut_frame(&can_cluster.name, *can_id, can_frame_triggering, DutFrameDirection::Rx, ecu));
+            }
+        }
+    }
+
+    if communication.rx_frames.is_empty() && communication.tx_frames.is_empty() {
+        let known_ecus: Vec<&str> = known_ecus.into_iter().map(String::as_str).collect();
+        return Err(format!("ECU '{}' neither sends nor receives any frame. Known ECUs: {}", ecu, known_ecus.join(", ")));
+    }
+
+    communication.rx_frames.sort_by(|a, b| (&a.bus, a.can_id).cmp(&(&b.bus, b.can_id)));
+    communication.tx_frames.sort_by(|a, b| (&a.bus, a.can_id).cmp(&(&b.bus, b.can_id)));
+
+    return Ok(communication);
+}
+
+impl DutCommunication {
+    // Buses, on which the DUT receives or transmits frames, sorted by name
+    pub fn buses(&self) -> Vec<String> {
+        return self.rx_frames.iter().chain(self.tx_frames.iter())
+            .map(|frame| frame.bus.clone())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+    }
+
+    pub fn expected_rx_frame(&self, bus: &str, can_id: i64) -> Option<&DutFrame> {
+        return self.rx_frames.iter().find(|frame| frame.bus == bus && frame.can_id == can_id);
+    }
+
+    // Default configuration of the restbus simulation for this DUT: Only the frames it expects to receive are transmitted.
+    // Buses, on which the DUT only transmits, are not simulated.
+    pub fn restbus_dependencies(&self) -> Vec<RestbusDependency> {
+        let mut dependencies: Vec<RestbusDependency> = Vec::new();
+
+        for frame in &self.rx_frames {
+            match dependencies.iter_mut().find(|dependency| dependency.bus == frame.bus) {
+                Some(dependency) => dependency.can_ids.push(frame.can_id),
+                None => dependencies.push(RestbusDependency { bus: frame.bus.clone(), can_ids: vec![frame.can_id] }),
+            }
+        }
+
+        return dependencies;
+    }
+
+    // Same as restbus_dependencies(), in the format of the OPENDUT_RESTBUS environment variable, e.g. "CAN_Powertrain:0x1a0,0x2b0"
+    pub fn restbus_declaration(&self) -> String {
+        return self.restbus_dependencies().iter()
+            .map(|dependency| {
+                let can_ids: Vec<String> = dependency.can_ids.iter().map(|can_id| format!("{:#x}", can_id)).collect();
+                format!("{}:{}", dependency.bus, can_ids.join(","))
+            })
+            .collect::<Vec<String>>()
+            .join(";");
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        return serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize communication of ECU {}: {}", self.ecu, err));
+    }
+
+    pub fn write_json(&self, file_name: &str) -> Result<(), String> {
+        let json = self.to_json()?;
+        return fs::write(file_name, json)
+            .map_err(|err| format!("Failed to write communication of ECU {} to {}: {}", self.ecu, file_name, err));
+    }
+
+    pub fn print(&self) {
+        println!("[+] ECU {} receives {} and transmits {} frames.", self.ecu, self.rx_frames.len(), self.tx_frames.len());
+
+        for frame in self.rx_frames.iter().chain(self.tx_frames.iter()) {
+            let direction = match frame.direction {
+                DutFrameDirection::Rx => "RX",
+                DutFrameDirection::Tx => "TX",
+            };
+            let timing = match frame.cycle_time_ms {
+                Some(cycle_time) => format!("every {} ms, offset {} ms", cycle_time, frame.offset_ms),
+                None if frame.number_of_repetitions > 0 => format!("{} repetitions every {} ms", frame.number_of_repetitions, frame.repetition_period_ms),
+                None => String::from("not cyclic"),
+            };
+            println!("      {} {} {:#x} {} ({})", direction, frame.bus, frame.can_id, frame.frame_name, timing);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use crate::arxml_structs::*;
+
+/*
+- Encodes the payload of a frame from signal values, so that callers do not have to provide raw byte arrays:
+    let mut encoder = SignalEncoder::new(can_frame_triggering)?;
+    encoder.set_signal("EngineSpeed", 3000)?;
+    encoder.set_signal_text("GearPosition", "Reverse")?;
+    let payload = encoder.encode();
+- Values are physical values, which are converted into raw values with the COMPU-METHOD of the signal (see CompuMethod::to_raw()).
+  Signals without COMPU-METHOD are written with the value rounded to an integer.
+- Signals, which are not set, keep their init value. Bits of the PDUs, which do not belong to a signal, are set to the unused bit pattern.
+- The raw values are written with the start position, length and byte order from the ARXML, with the same bit layout as
+  the init values (see extract_init_values()).
+*/
+
+// Position of a signal within the payload of a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalLayout {
+    // Start of the signal in bits, including the start position of its PDU
+    pub start: usize,
+    pub length: usize,
+    // Byte order of the signal, true for big endian
+    pub byte_order: bool,
+    // Byte order of the PDU
+    pub pdu_byte_order: bool,
+    pub compu_method: Option<CompuMethod>,
+}
+
+pub fn signal_layouts(can_frame_triggering: &CanFrameTriggering) -> HashMap<String, SignalLayout> {
+    let mut layouts = HashMap::new();
+
+    for pdu_mapping in &can_frame_triggering.pdu_mappings {
+        let (ungrouped_signals, grouped_signals) = match &pdu_mapping.pdu {
+            PDU::ISignalIPDU(pdu) => (&pdu.ungrouped_signals, &pdu.grouped_signals),
+            PDU::NMPDU(pdu) => (&pdu.ungrouped_signals, &pdu.grouped_signals),
+        };
+        let isignals = ungrouped_signals.iter()
+            .chain(grouped_signals.iter().flat_map(|group| group.isignals.iter()));
+
+        for isignal in isignals {
+            if isignal.start_pos < 0 || isignal.length <= 0 || pdu_mapping.start_position < 0 {
+                continue;
+            }
+            layouts.insert(isignal.name.clone(), SignalLayout {
+                start: (pdu_mapping.start_position + isignal.start_pos) as usize,
+                length: isignal.length as usize,
+                byte_order: isignal.byte_order,
+                pdu_byte_order: pdu_mapping.byte_order,
+                compu_method: isignal.compu_method.clone(),
+            });
+        }
+    }
+
+    return layouts;
+}
+
+fn write_bit(payload: &mut [u8], index: usize, pdu_byte_order: bool, is_set: bool) {
+    let shift = if pdu_byte_order { 7 - index % 8 } else { index % 8 };
+
+    if is_set {
+        payload[index / 8] |= 1 << shift;
+    } else {
+        payload[index / 8] &= !(1 << shift);
+    }
+}
+
+// Writes the raw value into the payload. The value is truncated to the length of the signal.
+pub fn write_signal_value(payload: &mut [u8], name: &str, layout: &SignalLayout, value: i64) -> Result<(), String> {
+    if layout.length > 64 {
+        return Err(format!("Signal '{name}' has {} bits, values can only be set for signals with up to 64 bits.", layout.length));
+    }
+    let last_byte = (layout.start + layout.length - 1) / 8;
+    if last_byte >= payload.len() {
+        return Err(format!("Signal '{name}' does not fit into the payload of {} bytes.", payload.len()));
+    }
+
+    for bit in 0..layout.length {
+        let is_set = (value >> bit) & 1 != 0;
+        // Big endian signals are written starting with their most significant bit
+        let index = if layout.byte_order { layout.start + layout.length - 1 - bit } else { layout.start + bit };
+        write_bit(payload, index, layout.pdu_byte_order, is_set);
+    }
+    return Ok(());
+}
+
+// Checks that the raw value can be represented with the length of the signal, either unsigned or as two's complement
+fn check_raw_range(name: &str, layout: &SignalLayout, raw: i64) -> Result<(), String> {
+    if layout.length >= 64 {
+        return Ok(());
+    }
+    let min = -(1_i64 << (layout.length - 1));
+    let max = (1_i64 << layout.length) - 1;
+
+    if raw < min || raw > max {
+        return Err(format!("Raw value {raw} of signal '{name}' does not fit into {} bits.", layout.length));
+    }
+    return Ok(());
+}
+
+impl CompuMethod {
+    // Converts a physical value into the raw value, by inverting the first linear scale whose limits contain the result
+    pub fn to_raw(&self, physical: f64) -> Result<i64, String> {
+        if self.category == "IDENTICAL" || self.scales.is_empty() {
+            return Ok(physical.round() as i64);
+        }
+
+        let mut has_linear_scale = false;
+
+        for scale in &self.scales {
+            let CompuScaleContent::Rational { numerator, denominator } = &scale.content else {
+                continue;
+            };
+            if numerator.len() > 2 || denominator.len() > 1 {
+                return Err(format!("COMPU-METHOD '{}' is a rational function, only linear scales are supported.", self.name));
+            }
+            let offset = numerator.first().copied().unwrap_or(0_f64);
+            let factor = numerator.get(1).copied().unwrap_or(0_f64);
+            let divisor = denominator.first().copied().unwrap_or(1_f64);
+            if factor == 0_f64 || divisor == 0_f64 {
+                continue;
+            }
+            has_linear_scale = true;
+
+            let raw = ((physical * divisor - offset) / factor).round();
+
+            let above_lower_limit = scale.lower_limit.map_or(true, |lower_limit| raw >= lower_limit);
+            let below_upper_limit = scale.upper_limit.map_or(true, |upper_limit| raw <= upper_limit);
+            if above_lower_limit && below_upper_limit {
+                return Ok(raw as i64);
+            }
+        }
+
+        if !has_linear_scale {
+            return Err(format!("COMPU-METHOD '{}' of category {} has no linear scale. Set the signal by its text instead.", self.name, self.category));
+        }
+        return Err(format!("Physical value {physical} is outside of the limits of COMPU-METHOD '{}'.", self.name));
+    }
+
+    // Looks up the raw value of a TEXTTABLE entry, which is the lower limit of its scale
+    pub fn text_to_raw(&self, text: &str) -> Result<i64, String> {
+        for scale in &self.scales {
+            if let CompuScaleContent::Text(scale_text) = &scale.content {
+                if scale_text == text {
+                    return scale.lower_limit
+                        .map(|lower_limit| lower_limit as i64)
+                        .ok_or_else(|| format!("Text '{text}' of COMPU-METHOD '{}' has no lower limit.", self.name));
+                }
+            }
+        }
+        return Err(format!("COMPU-METHOD '{}' has no text '{text}'.", self.name));
+    }
+}
+
+pub struct SignalEncoder {
+    signals: HashMap<String, SignalLayout>,
+    payload: Vec<u8>,
+}
+
+impl SignalEncoder {
+    // Creates the payload of the frame from the unused bit patterns and the init values of its signals
+    pub fn new(can_frame_triggering: &CanFrameTriggering) -> Result<SignalEncoder, String> {
+        let frame_length: usize = can_frame_triggering.frame_length.try_into()
+            .map_err(|_| format!("Frame '{}' has an invalid length of {}.", can_frame_triggering.frame_name, can_frame_triggering.frame_length))?;
+        let mut payload = vec![0_u8; frame_length];
+
+        for pdu_mapping in &can_frame_triggering.pdu_mappings {
+            let (unused_bit_pattern, ungrouped_signals, grouped_signals) = match &pdu_mapping.pdu {
+                PDU::ISignalIPDU(pdu) => (pdu.unused_bit_pattern, &pdu.ungrouped_signals, &pdu.grouped_signals),
+                PDU::NMPDU(pdu) => (pdu.unused_bit_pattern, &pdu.ungrouped_signals, &pdu.grouped_signals),
+            };
+            if pdu_mapping.start_position < 0 || pdu_mapping.length < 0 {
+                continue;
+            }
+            let pdu_start = pdu_mapping.start_position as usize;
+            let pdu_end = pdu_start + pdu_mapping.length as usize * 8;
+            if pdu_end > frame_length * 8 {
+                return Err(format!("PDU '{}' does not fit into the payload of frame '{}'.", pdu_mapping.name, can_frame_triggering.frame_name));
+            }
+            for index in pdu_start..pdu_end {
+                write_bit(&mut payload, index, pdu_mapping.byte_order, unused_bit_pattern);
+            }
+
+            let isignals = ungrouped_signals.iter()
+                .chain(grouped_signals.iter().flat_map(|group| group.isignals.iter()));
+
+            for isignal in isignals {
+                if isignal.start_pos < 0 || isignal.length <= 0 {
+                    continue;
+                }
+                let layout = SignalLayout {
+                    start: pdu_start + isignal.start_pos as usize,
+                    length: isignal.length as usize,
+                    byte_order: isignal.byte_order,
+                    pdu_byte_order: pdu_mapping.byte_order,
+                    compu_method: None,
+                };
+                match &isignal.init_values {
+                    InitValues::Single(value) => write_signal_value(&mut payload, &isignal.name, &layout, *value)?,
+                    // Arrays are written byte by byte, starting with the first element
+                    InitValues::Array(values) => {
+                        for (index, value) in values.iter().enumerate() {
+                            let byte_layout = SignalLayout { start: layout.start + index * 8, length: 8, byte_order: true, ..layout.clone() };
+                            write_signal_value(&mut payload, &isignal.name, &byte_layout, *value)?;
+                        }
+                    }
+                    InitValues::NotExist(_) => {}
+                }
+            }
+        }
+
+        return Ok(SignalEncoder {
+            signals: signal_layouts(can_frame_triggering),
+            payload,
+        });
+    }
+
+    fn layout(&self, name: &str) -> Result<&SignalLayout, String> {
+        return self.signals.get(name)
+            .ok_or_else(|| format!("Frame has no signal '{name}'."));
+    }
+
+    // Sets the physical value of a signal, which is scaled with the COMPU-METHOD of the signal
+    pub fn set_signal(&mut self, name: &str, value: impl Into<f64>) -> Result<(), String> {
+        let value = value.into();
+        let layout = self.layout(name)?;
+        let raw = match &layout.compu_method {
+            Some(compu_method) => compu_method.to_raw(value)
+                .map_err(|cause| format!("Could not set signal '{name}' to {value}: {cause}"))?,
+            None => value.round() as i64,
+        };
+        return self.set_raw_signal(name, raw);
+    }
+
+    // Sets a signal with a TEXTTABLE, e.g. a gear position, by the text of its value
+    pub fn set_signal_text(&mut self, name: &str, text: &str) -> Result<(), String> {
+        let layout = self.layout(name)?;
+        let raw = layout.compu_method.as_ref()
+            .ok_or_else(|| format!("Signal '{name}' has no COMPU-METHOD, which could map the text '{text}' to a value."))?
+            .text_to_raw(text)
+            .map_err(|cause| format!("Could not set signal '{name}' to '{text}': {cause}"))?;
+        return self.set_raw_signal(name, raw);
+    }
+
+    // Sets the raw value of a signal without scaling
+    pub fn set_raw_signal(&mut self, name: &str, raw: i64) -> Result<(), String> {
+        let layout = self.layout(name)?.clone();
+        check_raw_range(name, &layout, raw)?;
+        return write_signal_value(&mut self.payload, name, &layout, raw);
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        return self.payload.clone();
+    }
+}
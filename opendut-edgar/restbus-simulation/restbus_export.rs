@@ -0,0 +1,224 @@
+use std::collections::{BTreeMap, HashMap};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag};
+use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+use crate::arxml_structs::*;
+
+/*
+- Export of the parsed communication database into openDuT's topology definition.
+- Every CanCluster becomes a CAN NetworkInterfaceDescriptor and every ECU sending or receiving frames on a CanCluster becomes a DeviceDescriptor
+  connected to that interface. This way, the ARXML file is the single source for both the restbus simulation and the lab topology.
+- The result is only a proposal. It can be printed for review and optionally be pushed to a peer in CARL, where already existing
+  interfaces and devices (same name) are kept unchanged.
+*/
+
+// Tag added to every exported device, to be able to find them in CARL
+pub const EXPORTED_DEVICE_TAG: &str = "restbus-export";
+
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    // Maps the name of a CanCluster to the name of the CAN interface on the peer, e.g. "CAN_Powertrain" -> "can0".
+    // CanClusters without mapping are not exported.
+    pub interface_names: HashMap<String, String>,
+    pub sample_point: f32,
+    pub data_sample_point: f32,
+}
+
+impl ExportConfig {
+    pub fn new(interface_names: HashMap<String, String>) -> ExportConfig {
+        return ExportConfig {
+            interface_names,
+            sample_point: 0.875,
+            data_sample_point: 0.8,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProposedTopology {
+    pub network_interfaces: Vec<NetworkInterfaceDescriptor>,
+    pub devices: Vec<DeviceDescriptor>,
+}
+
+// Number of frames an ECU sends and receives on a CanCluster
+#[derive(Debug, Default)]
+struct EcuFrameCount {
+    sent: usize,
+    received: usize,
+}
+
+// Collects all ECUs of a CanCluster, sorted by name for stable output
+fn ecus_of_cluster(can_cluster: &CanCluster) -> BTreeMap<String, EcuFrameCount> {
+    let mut ecus: BTreeMap<String, EcuFrameCount> = BTreeMap::new();
+
+    for can_frame_triggering in can_cluster.can_frame_triggerings.values() {
+        for ecu in &can_frame_triggering.sender_ecus {
+            ecus.entry(ecu.clone()).or_default().sent += 1;
+        }
+        for ecu in &can_frame_triggering.receiver_ecus {
+            ecus.entry(ecu.clone()).or_default().received += 1;
+        }
+    }
+
+    return ecus;
+}
+
+fn network_interface_for_cluster(can_cluster: &CanCluster, interface_name: &String, config: &ExportConfig) -> Result<NetworkInterfaceDescriptor, String> {
+    let name = NetworkInterfaceName::try_from(interface_name.as_str())
+        .map_err(|err| format!("Invalid interface name {} for CanCluster {}: {}", interface_name, can_cluster.name, err))?;
+
+    let bitrate = u32::try_from(can_cluster.baudrate)
+        .map_err(|_| format!("Invalid baudrate {} of CanCluster {}", can_cluster.baudrate, can_cluster.name))?;
+    let fd = can_cluster.canfd_baudrate > 0;
+    let data_bitrate = if fd {
+        u32::try_from(can_cluster.canfd_baudrate)
+            .map_err(|_| format!("Invalid CAN FD baudrate {} of CanCluster {}", can_cluster.canfd_baudrate, can_cluster.name))?
+    } else {
+        bitrate
+    };
+
+    let sample_point = CanSamplePoint::try_from(config.sample_point)
+        .map_err(|err| err.to_string())?;
+    let data_sample_point = CanSamplePoint::try_from(config.data_sample_point)
+        .map_err(|err| err.to_string())?;
+
+    return Ok(NetworkInterfaceDescriptor {
+        id: NetworkInterfaceId::random(),
+        name,
+        configuration: NetworkInterfaceConfiguration::Can {
+            bitrate,
+            sample_point,
+            fd,
+            data_bitrate,
+            data_sample_point,
+        },
+    });
+}
+
+// Device names must be unique per peer, so ECUs connected to multiple CanClusters get the name of the cluster appended
+fn device_name(ecu: &String, can_cluster: &CanCluster, ecu_on_multiple_clusters: bool) -> Result<DeviceName, String> {
+    let name = if ecu_on_multiple_clusters {
+        format!("{}_{}", ecu, can_cluster.name)
+    } else {
+        ecu.clone()
+    };
+    let name: String = name.chars().take(DeviceName::MAX_LENGTH).collect();
+
+    return DeviceName::try_from(name).map_err(|err| err.to_string());
+}
+
+// Maps the parsed CanClusters to proposed openDuT descriptors
+pub fn propose_topology(can_clusters: &HashMap<String, CanCluster>, config: &ExportConfig) -> Result<ProposedTopology, String> {
+    let mut topology = ProposedTopology::default();
+
+    let mut cluster_names: Vec<&String> = can_clusters.keys().collect();
+    cluster_names.sort();
+
+    let mut clusters_per_ecu: HashMap<String, usize> = HashMap::new();
+    for can_cluster in can_clusters.values() {
+        if config.interface_names.contains_key(&can_cluster.name) {
+            for ecu in ecus_of_cluster(can_cluster).keys() {
+                *clusters_per_ecu.entry(ecu.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    for cluster_name in cluster_names {
+        let can_cluster = &can_clusters[cluster_name];
+
+        let Some(interface_name) = config.interface_names.get(&can_cluster.name) else {
+            println!("[-] WARNING: No interface name configured for CanCluster {}. Not exporting it.", can_cluster.name);
+            continue;
+        };
+
+        let network_interface = network_interface_for_cluster(can_cluster, interface_name, config)?;
+
+        for (ecu, frame_count) in ecus_of_cluster(can_cluster) {
+            let ecu_on_multiple_clusters = clusters_per_ecu.get(&ecu).copied().unwrap_or_default() > 1;
+
+            let description = format!("ECU {} on CanCluster {}, sending {} and receiving {} frames.", ecu, can_cluster.name, frame_count.sent, frame_count.received);
+            let description: String = description.chars().take(DeviceDescription::MAX_LENGTH).collect();
+
+            let tags = vec![
+                DeviceTag::try_from(EXPORTED_DEVICE_TAG).map_err(|err| err.to_string())?,
+                DeviceTag::try_from(can_cluster.name.chars().take(DeviceTag::MAX_LENGTH).collect::<String>()).map_err(|err| err.to_string())?,
+            ];
+
+            topology.devices.push(DeviceDescriptor {
+                id: DeviceId::random(),
+                name: device_name(&ecu, can_cluster, ecu_on_multiple_clusters)?,
+                description: Some(DeviceDescription::try_from(description).map_err(|err| err.to_string())?),
+                interface: network_interface.id,
+                tags,
+            });
+        }
+
+        topology.network_interfaces.push(network_interface);
+    }
+
+    return Ok(topology);
+}
+
+pub fn print_topology(topology: &ProposedTopology) {
+    for network_interface in &topology.network_interfaces {
+        println!("[+] Network interface {}: {}", network_interface.name, network_interface.configuration);
+
+        for device in topology.devices.iter().filter(|device| device.interface == network_interface.id) {
+            println!("      Device {}", device.name);
+        }
+    }
+}
+
+// Adds the proposed interfaces and devices to the peer in CARL. Interfaces and devices with a name, which already exists on the peer,
+// are left unchanged, so the export can be repeated after the communication database changed.
+// Returns the number of added interfaces and devices.
+pub async fn push_topology_to_carl(carl: &mut CarlClient, peer_id: PeerId, topology: ProposedTopology) -> Result<(usize, usize), String> {
+    let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        .map_err(|err| format!("Failed to get peer with ID <{}>: {}", peer_id, err))?;
+
+    // Devices have to reference the ID of the interface on the peer, which differs from the proposed one, if the interface already existed
+    let mut interface_ids: HashMap<NetworkInterfaceId, NetworkInterfaceId> = HashMap::new();
+    let mut added_interfaces = 0;
+
+    for network_interface in topology.network_interfaces {
+        match peer_descriptor.network.interfaces.iter().find(|existing| existing.name == network_interface.name) {
+            Some(existing) => {
+                if existing.configuration != network_interface.configuration {
+                    println!("[-] WARNING: Network interface {} already exists on peer <{}> with a different configuration. Keeping {}.",
+                        existing.name, peer_id, existing.configuration);
+                }
+                interface_ids.insert(network_interface.id, existing.id);
+            }
+            None => {
+                interface_ids.insert(network_interface.id, network_interface.id);
+                peer_descriptor.network.interfaces.push(network_interface);
+                added_interfaces += 1;
+            }
+        }
+    }
+
+    let existing_device_names: Vec<DeviceName> = peer_descriptor.topology.devices.iter()
+        .map(|device| device.name.clone())
+        .collect();
+    let mut added_devices = 0;
+
+    for mut device in topology.devices {
+        if existing_device_names.contains(&device.name) {
+            println!("[-] Device {} already exists on peer <{}>. Keeping it unchanged.", device.name, peer_id);
+            continue;
+        }
+        device.interface = interface_ids[&device.interface];
+        peer_descriptor.topology.devices.push(device);
+        added_devices += 1;
+    }
+
+    carl.peers.store_peer_descriptor(peer_descriptor).await
+        .map_err(|err| format!("Failed to store peer with ID <{}>: {}", peer_id, err))?;
+
+    println!("[+] Added {} network interfaces and {} devices to peer <{}>", added_interfaces, added_devices, peer_id);
+
+    return Ok((added_interfaces, added_devices));
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/*
+- Fault injection for the restbus simulation, to test how the DUT handles erroneous frames.
+- A FaultScenario deliberately corrupts the transmissions of one CAN ID: corrupted E2E CRC, wrong E2E counter, shortened DLC or skipped cycles.
+- Each scenario can be delayed by a number of transmissions, limited to a number of faulty transmissions (a burst when applied to every
+  transmission) and applied only to every n-th transmission.
+- Scenarios are scriptable, one per line (see parse_fault_scenario()), and can be injected and cleared at runtime via the FaultInjector,
+  e.g. from another thread while the TransmissionScheduler is running:
+    inject brake_crc 0x1A0 crc byte=0 start=10 count=5
+    inject speed_gap 0x2B0 skip every=3
+    clear brake_crc
+    clear all
+- The transmit function of the scheduler passes each frame's payload to FaultInjector::apply() and only sends it,
+  if the outcome is FaultOutcome::Transmit.
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaultKind {
+    // Inverts the byte holding the E2E CRC
+    CorruptCrc { byte: usize },
+    // Adds step to the 4-bit E2E counter in the lower nibble of the given byte, so that the DUT sees a jump or repetition
+    WrongCounter { byte: usize, step: u8 },
+    // Truncates the payload, so that the frame is sent with a shorter DLC than expected
+    ShortenDlc { length: usize },
+    // Does not send the frame at all, so that the DUT misses a cycle
+    SkipCycle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaultScenario {
+    pub can_id: i64,
+    pub kind: FaultKind,
+    // Number of transmissions of the CAN ID which are sent unaltered, before the first fault is injected
+    pub start_after: u64,
+    // Number of faulty transmissions, after which the scenario is finished. None injects faults until the scenario is cleared.
+    pub count: Option<u64>,
+    // Only every n-th transmission is faulty. 1 makes every transmission faulty, i.e. a burst when combined with count.
+    pub every: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaultOutcome {
+    Transmit,
+    Drop,
+}
+
+#[derive(Debug)]
+struct ActiveScenario {
+    scenario: FaultScenario,
+    transmissions: u64,
+    injected: u64,
+}
+
+impl ActiveScenario {
+    fn is_finished(&self) -> bool {
+        return match self.scenario.count {
+            Some(count) => self.injected >= count,
+            None => false,
+        };
+    }
+
+    // Counts the transmission and returns whether it has to be faulty
+    fn is_due(&mut self) -> bool {
+        self.transmissions += 1;
+
+        if self.is_finished() || self.transmissions <= self.scenario.start_after {
+            return false;
+        }
+        let since_start = self.transmissions - self.scenario.start_after - 1;
+        if since_start % self.scenario.every.max(1) != 0 {
+            return false;
+        }
+
+        self.injected += 1;
+        return true;
+    }
+}
+
+// Shared between the transmitting thread and whoever controls the faults at runtime
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    scenarios: Mutex<BTreeMap<String, ActiveScenario>>,
+}
+
+impl FaultInjector {
+    pub fn new() -> FaultInjector {
+        return FaultInjector::default();
+    }
+
+    // Replaces an existing scenario with the same name
+    pub fn inject(&self, name: String, scenario: FaultScenario) {
+        let mut scenarios = self.scenarios.lock().unwrap();
+        scenarios.insert(name, ActiveScenario { scenario, transmissions: 0, injected: 0 });
+    }
+
+    pub fn clear(&self, name: &str) -> bool {
+        return self.scenarios.lock().unwrap().remove(name).is_some();
+    }
+
+    pub fn clear_all(&self) {
+        self.scenarios.lock().unwrap().clear();
+    }
+
+    // Returns the name of each scenario with the number of faults injected so far
+    pub fn status(&self) -> Vec<(String, u64, bool)> {
+        return self.scenarios.lock().unwrap().iter()
+            .map(|(name, active)| (name.clone(), active.injected, active.is_finished()))
+            .collect();
+    }
+
+    // Applies all scenarios of the CAN ID to the payload of one transmission
+    pub fn apply(&self, can_id: i64, payload: &mut Vec<u8>) -> FaultOutcome {
+        let mut scenarios = self.scenarios.lock().unwrap();
+        let mut outcome = FaultOutcome::Transmit;
+
+        for active in scenarios.values_mut().filter(|active| active.scenario.can_id == can_id) {
+            if !active.is_due() {
+                continue;
+            }
+            match active.scenario.kind {
+                FaultKind::CorruptCrc { byte } => {
+                    if let Some(value) = payload.get_mut(byte) {
+                        *value = !*value;
+                    }
+                }
+                FaultKind::WrongCounter { byte, step } => {
+                    if let Some(value) = payload.get_mut(byte) {
+                        let counter = (*value & 0x0F).wrapping_add(step) & 0x0F;
+                        *value = (*value & 0xF0) | counter;
+                    }
+                }
+                FaultKind::ShortenDlc { length } => {
+                    payload.truncate(length);
+                }
+                FaultKind::SkipCycle => {
+                    outcome = FaultOutcome::Drop;
+                }
+            }
+        }
+
+        return outcome;
+    }
+
+    // Handles one line of a fault script or a command given at runtime. Empty lines and lines starting with '#' are ignored.
+    pub fn handle_command(&self, line: &str) -> Result<(), String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(());
+        }
+
+        let (command, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let arguments = arguments.trim();
+
+        match command {
+            "inject" => {
+                let (name, scenario) = parse_fault_scenario(arguments)?;
+                self.inject(name, scenario);
+            }
+            "clear" if arguments == "all" => {
+                self.clear_all();
+            }
+            "clear" => {
+                if !self.clear(arguments) {
+                    return Err(format!("No fault scenario named '{}' to clear", arguments));
+                }
+            }
+            _ => return Err(format!("Unknown fault command '{}', expected 'inject' or 'clear'", command)),
+        }
+        return Ok(());
+    }
+
+    // Handles every line of a fault script, stopping at the first invalid line
+    pub fn run_script(&self, script: &str) -> Result<(), String> {
+        for (index, line) in script.lines().enumerate() {
+            self.handle_command(line)
+                .map_err(|err| format!("Line {} of fault script: {}", index + 1, err))?;
+        }
+        return Ok(());
+    }
+}
+
+// Parses a scenario like "brake_crc 0x1A0 crc byte=0 start=10 count=5 every=1", i.e. the name, the CAN ID (hexadecimal with 0x or decimal),
+// the kind (crc, counter, dlc, skip) and optional parameters
+pub fn parse_fault_scenario(definition: &str) -> Result<(String, FaultScenario), String> {
+    let mut tokens = definition.split_whitespace();
+
+    let name = tokens.next()
+        .ok_or_else(|| String::from("Fault scenario is missing a name"))?;
+    let can_id = tokens.next()
+        .ok_or_else(|| format!("Fault scenario '{}' is missing a CAN ID", name))?;
+    let can_id = match can_id.strip_prefix("0x").or_else(|| can_id.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => can_id.parse::<i64>(),
+    }.map_err(|err| format!("Invalid CAN ID '{}' in fault scenario '{}': {}", can_id, name, err))?;
+    let kind = tokens.next()
+        .ok_or_else(|| format!("Fault scenario '{}' is missing a kind", name))?;
+
+    let mut parameters: BTreeMap<&str, u64> = BTreeMap::new();
+    for token in tokens {
+        let (key, value) = token.split_once('=')
+            .ok_or_else(|| format!("Expected parameter as key=value in fault scenario '{}', but got '{}'", name, token))?;
+        let value = value.parse::<u64>()
+            .map_err(|err| format!("Invalid value for parameter '{}' in fault scenario '{}': {}", key, name, err))?;
+        parameters.insert(key, value);
+    }
+    let mut required = |key: &str| -> Result<u64, String> {
+        return parameters.remove(key)
+            .ok_or_else(|| format!("Fault scenario '{}' of kind '{}' requires parameter '{}'", name, kind, key));
+    };
+
+    let kind = match kind {
+        "crc" => FaultKind::CorruptCrc { byte: required("byte")? as usize },
+        "counter" => FaultKind::WrongCounter { byte: required("byte")? as usize, step: (required("step")? & 0x0F) as u8 },
+        "dlc" => FaultKind::ShortenDlc { length: required("length")? as usize },
+        "skip" => FaultKind::SkipCycle,
+        _ => return Err(format!("Unknown kind '{}' in fault scenario '{}', expected one of: crc, counter, dlc, skip", kind, name)),
+    };
+
+    let scenario = FaultScenario {
+        can_id,
+        kind,
+        start_after: parameters.remove("start").unwrap_or(0),
+        count: parameters.remove("count"),
+        every: parameters.remove("every").unwrap_or(1).max(1),
+    };
+
+    if let Some(unknown) = parameters.keys().next() {
+        return Err(format!("Unknown parameter '{}' in fault scenario '{}'", unknown, name));
+    }
+
+    return Ok((name.to_string(), scenario));
+}
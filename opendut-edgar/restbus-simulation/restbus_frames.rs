@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Mutex;
+
+use crate::arxml_structs::*;
+
+/*
+- Builds the frames of the restbus simulation in the format of their CanFrameTriggering (classic CAN, CAN FD or CAN XL)
+  and encodes them like the SocketCAN structs can_frame, canfd_frame and canxl_frame (see linux/can.h), for writing them onto a CAN_RAW socket.
+- CAN XL requires Linux 6.2 or later, a CAN XL capable controller and the socket option CAN_RAW_XL_FRAMES on the socket.
+  Whether an interface supports CAN FD or CAN XL, is detected from its MTU (see BusCapabilities::detect()).
+- If an interface does not support the format of a frame, the frame falls back to a supported format:
+  CAN XL frames with up to 64 bytes are sent as CAN FD frames, CAN FD frames with up to 8 bytes as classic frames.
+  Frames with larger payloads are not sent. Each fallback is reported once per CAN ID, to not flood the log with every transmission.
+*/
+
+const CAN_MTU: u32 = 16;
+const CANFD_MTU: u32 = 72;
+const CANXL_MIN_MTU: u32 = 76;
+
+const CAN_MAX_DLEN: usize = 8;
+const CANFD_MAX_DLEN: usize = 64;
+const CANXL_MAX_DLEN: usize = 2048;
+
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+const CAN_SFF_MASK: u32 = 0x0000_07FF;
+const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+const CANFD_BRS: u8 = 0x01;
+const CANFD_FDF: u8 = 0x04;
+const CANXL_XLF: u8 = 0x80;
+const CANXL_PRIO_MASK: u32 = CAN_SFF_MASK;
+const CANXL_VCID_OFFSET: u32 = 16;
+
+// Payload lengths a CAN FD frame can have, as the DLC is not linear above 8 bytes
+const CANFD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusCapabilities {
+    pub fd: bool,
+    pub xl: bool,
+}
+
+impl BusCapabilities {
+    pub fn from_mtu(mtu: u32) -> BusCapabilities {
+        return BusCapabilities {
+            fd: mtu >= CANFD_MTU,
+            xl: mtu >= CANXL_MIN_MTU,
+        };
+    }
+
+    // Reads the MTU of the CAN interface, which the kernel sets according to the frame formats the interface is configured for
+    pub fn detect(interface: &str) -> Result<BusCapabilities, String> {
+        let path = format!("/sys/class/net/{interface}/mtu");
+        let mtu = fs::read_to_string(&path)
+            .map_err(|cause| format!("Could not read MTU of CAN interface '{interface}' from {path}: {cause}"))?;
+        let mtu = mtu.trim().parse::<u32>()
+            .map_err(|cause| format!("Invalid MTU '{}' of CAN interface '{interface}': {cause}", mtu.trim()))?;
+
+        if mtu < CAN_MTU {
+            return Err(format!("Interface '{interface}' has an MTU of {mtu}, which is too small for a CAN interface."));
+        }
+        return Ok(BusCapabilities::from_mtu(mtu));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestbusFrame {
+    pub can_id: i64,
+    pub extended: bool,
+    pub format: CanFrameFormat,
+    pub can_xl_props: Option<CanXlFrameProps>,
+    pub payload: Vec<u8>,
+}
+
+impl RestbusFrame {
+    pub fn new(can_frame_triggering: &CanFrameTriggering, payload: Vec<u8>) -> RestbusFrame {
+        return RestbusFrame {
+            can_id: can_frame_triggering.can_id,
+            extended: can_frame_triggering.addressing_mode.to_uppercase() == "EXTENDED",
+            format: can_frame_triggering.frame_format,
+            can_xl_props: can_frame_triggering.can_xl_props.clone(),
+            payload,
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fallback {
+    Downgraded { can_id: i64, from: CanFrameFormat, to: CanFrameFormat },
+    Dropped { can_id: i64, format: CanFrameFormat, payload_length: usize },
+}
+
+impl Fallback {
+    pub fn message(&self) -> String {
+        return match self {
+            Fallback::Downgraded { can_id, from, to } =>
+                format!("Interface does not support {from:?} frames. Sending frame with CAN ID {can_id:#X} as {to:?} frame instead."),
+            Fallback::Dropped { can_id, format, payload_length } =>
+                format!("Interface does not support {format:?} frames and the payload of {payload_length} bytes is too large for a fallback. Not sending frame with CAN ID {can_id:#X}."),
+        };
+    }
+}
+
+// Determines the format a frame is sent with on a bus with the given capabilities
+pub fn transmission_format(frame: &RestbusFrame, capabilities: &BusCapabilities) -> Result<CanFrameFormat, Fallback> {
+    let length = frame.payload.len();
+
+    let format = match frame.format {
+        CanFrameFormat::Xl if capabilities.xl => CanFrameFormat::Xl,
+        CanFrameFormat::Xl | CanFrameFormat::Fd if capabilities.fd && length <= CANFD_MAX_DLEN => CanFrameFormat::Fd,
+        _ if length <= CAN_MAX_DLEN => CanFrameFormat::Classic,
+        _ => return Err(Fallback::Dropped { can_id: frame.can_id, format: frame.format, payload_length: length }),
+    };
+
+    if format != frame.format {
+        return Err(Fallback::Downgraded { can_id: frame.can_id, from: frame.format, to: format });
+    }
+    return Ok(format);
+}
+
+pub struct FrameEncoder {
+    capabilities: BusCapabilities,
+    reported: Mutex<HashSet<i64>>,
+}
+
+impl FrameEncoder {
+    pub fn new(capabilities: BusCapabilities) -> FrameEncoder {
+        return FrameEncoder { capabilities, reported: Mutex::new(HashSet::new()) };
+    }
+
+    pub fn capabilities(&self) -> &BusCapabilities {
+        return &self.capabilities;
+    }
+
+    // Returns the bytes to write onto the socket, or None, if the frame cannot be sent on this bus
+    pub fn encode(&self, frame: &RestbusFrame) -> Option<Vec<u8>> {
+        let format = match transmission_format(frame, &self.capabilities) {
+            Ok(format) => format,
+            Err(fallback) => {
+                self.report(&fallback);
+                match fallback {
+                    Fallback::Downgraded { to, .. } => to,
+                    Fallback::Dropped { .. } => return None,
+                }
+            }
+        };
+
+        return match format {
+            CanFrameFormat::Classic => Some(encode_classic_frame(frame)),
+            CanFrameFormat::Fd => Some(encode_fd_frame(frame)),
+            CanFrameFormat::Xl => encode_xl_frame(frame),
+        };
+    }
+
+    fn report(&self, fallback: &Fallback) {
+        let can_id = match fallback {
+            Fallback::Downgraded { can_id, .. } | Fallback::Dropped { can_id, .. } => *can_id,
+        };
+        if self.reported.lock().unwrap().insert(can_id) {
+            println!("[-] WARNING: {}", fallback.message());
+        }
+    }
+}
+
+fn raw_can_id(frame: &RestbusFrame) -> u32 {
+    let can_id = frame.can_id as u32;
+    if frame.extended {
+        return (can_id & CAN_EFF_MASK) | CAN_EFF_FLAG;
+    }
+    return can_id & CAN_SFF_MASK;
+}
+
+// struct can_frame: can_id (4), len (1), padding (1), reserved (1), len8_dlc (1), data (8)
+fn encode_classic_frame(frame: &RestbusFrame) -> Vec<u8> {
+    let length = frame.payload.len().min(CAN_MAX_DLEN);
+
+    let mut bytes = Vec::with_capacity(CAN_MTU as usize);
+    bytes.extend_from_slice(&raw_can_id(frame).to_ne_bytes());
+    bytes.extend_from_slice(&[length as u8, 0, 0, 0]);
+    bytes.extend_from_slice(&frame.payload[..length]);
+    bytes.resize(CAN_MTU as usize, 0);
+    return bytes;
+}
+
+// struct canfd_frame: can_id (4), len (1), flags (1), reserved (2), data (64)
+// The payload is padded to the next length, which can be expressed as DLC.
+fn encode_fd_frame(frame: &RestbusFrame) -> Vec<u8> {
+    let length = frame.payload.len().min(CANFD_MAX_DLEN);
+    let padded_length = CANFD_LENGTHS.iter()
+        .find(|fd_length| **fd_length >= length)
+        .copied()
+        .unwrap_or(CANFD_MAX_DLEN);
+
+    let mut bytes = Vec::with_capacity(CANFD_MTU as usize);
+    bytes.extend_from_slice(&raw_can_id(frame).to_ne_bytes());
+    bytes.extend_from_slice(&[padded_length as u8, CANFD_BRS | CANFD_FDF, 0, 0]);
+    bytes.extend_from_slice(&frame.payload[..length]);
+    bytes.resize(CANFD_MTU as usize, 0);
+    return bytes;
+}
+
+// struct canxl_frame: prio (4, including the VCID), flags (1), sdt (1), len (2), af (4), data (len)
+// Only the used part of the data is written, as CAN XL frames are written with their actual size.
+fn encode_xl_frame(frame: &RestbusFrame) -> Option<Vec<u8>> {
+    if frame.payload.len() > CANXL_MAX_DLEN {
+        println!("[-] WARNING: Payload of CAN XL frame with CAN ID {:#X} exceeds {} bytes. Not sending it.", frame.can_id, CANXL_MAX_DLEN);
+        return None;
+    }
+    // CAN XL frames carry at least one byte of payload
+    let mut payload = frame.payload.clone();
+    if payload.is_empty() {
+        payload.push(0);
+    }
+
+    let (priority_id, sdu_type, vcid, acceptance_field) = match &frame.can_xl_props {
+        Some(props) if props.priority_id != 0 => (props.priority_id, props.sdu_type, props.vcid, props.acceptance_field),
+        Some(props) => (frame.can_id, props.sdu_type, props.vcid, props.acceptance_field),
+        None => (frame.can_id, 0, 0, 0),
+    };
+    let prio = (priority_id as u32 & CANXL_PRIO_MASK) | ((vcid as u32 & 0xFF) << CANXL_VCID_OFFSET);
+
+    let mut bytes = Vec::with_capacity(12 + payload.len());
+    bytes.extend_from_slice(&prio.to_ne_bytes());
+    bytes.extend_from_slice(&[CANXL_XLF, sdu_type as u8]);
+    bytes.extend_from_slice(&(payload.len() as u16).to_ne_bytes());
+    bytes.extend_from_slice(&(acceptance_field as u32).to_ne_bytes());
+    bytes.extend_from_slice(&payload);
+    return Some(bytes);
+}
@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId, ExecutorKind};
+
+use crate::arxml_structs::*;
+use crate::restbus_bus_errors::*;
+use crate::restbus_clock::*;
+use crate::restbus_control::*;
+use crate::restbus_dut::*;
+use crate::restbus_scheduler::*;
+
+/*
+- Ties the restbus simulation into the lifecycle of executors, instead of configuring it separately.
+- An executor declares the buses (CanClusters) it needs simulated via the environment variable OPENDUT_RESTBUS of its container,
+  optionally restricted to some frames, separated by semicolons:
+    OPENDUT_RESTBUS=CAN_Powertrain;CAN_Chassis:0x1A0,0x2B0
+  The variable is passed into the container as well, so the test can tell which buses are simulated.
+- Alternatively, an executor names the ECU of its device under test via OPENDUT_RESTBUS_DUT, e.g. OPENDUT_RESTBUS_DUT=EngineControl.
+  Then the frames this ECU expects to receive are simulated (see restbus_dut.rs). OPENDUT_RESTBUS takes precedence, if both are set.
+- Before the executor is started, RestbusLifecycle::executor_starting() starts transmitting on the required buses.
+  After the executor stopped, RestbusLifecycle::executor_stopped() stops the buses no other executor still requires.
+- When several executors require the same bus, the union of their frames is transmitted. If one of them requires all frames,
+  all frames of the bus are transmitted.
+- Each bus has a RestbusControl, which outlives restarts of the bus, so that frames can be controlled at runtime (see restbus_control.rs).
+- Likewise, each bus has a BusErrorSupervisor, which handles failed transmissions and an error passive or bus-off controller
+  according to the BusErrorPolicy of the lifecycle (see restbus_bus_errors.rs).
+*/
+
+pub const RESTBUS_ENVIRONMENT_VARIABLE: &str = "OPENDUT_RESTBUS";
+pub const RESTBUS_DUT_ENVIRONMENT_VARIABLE: &str = "OPENDUT_RESTBUS_DUT";
+
+// Interval in which a running bus checks, whether it should stop
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestbusDependency {
+    // Name of the CanCluster
+    pub bus: String,
+    // CAN IDs of the frames to transmit. Empty transmits all frames of the bus.
+    pub can_ids: Vec<i64>,
+}
+
+// Sends the frames of the simulation onto the bus, e.g. via a SocketCAN interface
+pub trait FrameTransmitter: Send + Sync {
+    fn transmit(&self, bus: &str, transmission: &ScheduledTransmission) -> io::Result<()>;
+
+    // Interface the bus is sent on, to read and restart its controller. None, if the transmitter is not bound to interfaces.
+    fn interface_of(&self, _bus: &str) -> Option<String> {
+        return None;
+    }
+}
+
+fn parse_can_id(value: &str) -> Result<i64, String> {
+    let value = value.trim();
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => value.parse::<i64>(),
+    };
+    return parsed.map_err(|cause| format!("Invalid CAN ID '{value}': {cause}"));
+}
+
+pub fn parse_restbus_dependencies(declaration: &str) -> Result<Vec<RestbusDependency>, String> {
+    let mut dependencies = Vec::new();
+
+    for entry in declaration.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (bus, can_ids) = match entry.split_once(':') {
+            Some((bus, can_ids)) => {
+                let can_ids = can_ids.split(',')
+                    .filter(|can_id| !can_id.trim().is_empty())
+                    .map(parse_can_id)
+                    .collect::<Result<Vec<_>, _>>()?;
+                (bus.trim(), can_ids)
+            }
+            None => (entry, Vec::new()),
+        };
+
+        if bus.is_empty() {
+            return Err(format!("Missing bus name in restbus dependency '{entry}'."));
+        }
+        dependencies.push(RestbusDependency { bus: bus.to_string(), can_ids });
+    }
+
+    return Ok(dependencies);
+}
+
+// Reads the declared dependencies from the environment variables of a container executor
+pub fn restbus_dependencies_of(executor: &ExecutorDescriptor) -> Result<Vec<RestbusDependency>, String> {
+    return match &executor.kind {
+        ExecutorKind::Container { envs, .. } => {
+            match envs.iter().find(|env| env.name() == RESTBUS_ENVIRONMENT_VARIABLE) {
+                Some(env) => parse_restbus_dependencies(env.value())
+                    .map_err(|cause| format!("Executor <{}> declares invalid restbus dependencies: {cause}", executor.id)),
+                None => Ok(Vec::new()),
+            }
+        }
+        ExecutorKind::Executable => Ok(Vec::new()),
+    };
+}
+
+// Reads the name of the ECU of the device under test from the environment variables of a container executor
+pub fn restbus_dut_of(executor: &ExecutorDescriptor) -> Option<String> {
+    return match &executor.kind {
+        ExecutorKind::Container { envs, .. } => envs.iter()
+            .find(|env| env.name() == RESTBUS_DUT_ENVIRONMENT_VARIABLE)
+            .map(|env| env.value().trim().to_string())
+            .filter(|ecu| !ecu.is_empty()),
+        ExecutorKind::Executable => None,
+    };
+}
+
+// Frames to transmit on a bus. None transmits all frames.
+type FrameSelection = Option<HashSet<i64>>;
+
+struct RunningBus {
+    frames: FrameSelection,
+    stop: Arc<AtomicBool>,
+    handle: thread::JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct LifecycleState {
+    executors: HashMap<ExecutorId, Vec<RestbusDependency>>,
+    running: HashMap<String, RunningBus>,
+}
+
+pub struct RestbusLifecycle {
+    can_clusters: HashMap<String, Arc<CanCluster>>,
+    controls: HashMap<String, Arc<RestbusControl>>,
+    bus_errors: HashMap<String, Arc<BusErrorSupervisor>>,
+    transmitter: Arc<dyn FrameTransmitter>,
+    state: Mutex<LifecycleState>,
+}
+
+impl RestbusLifecycle {
+    pub fn new(can_clusters: HashMap<String, CanCluster>, transmitter: Arc<dyn FrameTransmitter>, bus_error_policy: BusErrorPolicy) -> RestbusLifecycle {
+        let controls = can_clusters.iter()
+            .map(|(name, can_cluster)| (name.clone(), Arc::new(RestbusControl::new(can_cluster))))
+            .collect();
+        let controller: Arc<dyn CanController> = Arc::new(IpLinkController);
+        let bus_errors = can_clusters.keys()
+            .map(|name| {
+                let supervisor = BusErrorSupervisor::new(name.clone(), transmitter.interface_of(name), bus_error_policy.clone(), Arc::clone(&controller));
+                (name.clone(), Arc::new(supervisor))
+            })
+            .collect();
+        let can_clusters = can_clusters.into_iter()
+            .map(|(name, can_cluster)| (name, Arc::new(can_cluster)))
+            .collect();
+
+        return RestbusLifecycle { can_clusters, controls, bus_errors, transmitter, state: Mutex::new(LifecycleState::default()) };
+    }
+
+    // Starts the buses required by the executor. Fails without starting anything, if a required bus is not known.
+    pub fn executor_starting(&self, executor: &ExecutorDescriptor) -> Result<(), String> {
+        let mut dependencies = restbus_dependencies_of(executor)?;
+        if dependencies.is_empty() {
+            if let Some(ecu) = restbus_dut_of(executor) {
+                dependencies = self.dut_communication(&ecu)
+                    .map_err(|cause| format!("Executor <{}> declares an invalid device under test: {cause}", executor.id))?
+                    .restbus_dependencies();
+            }
+        }
+        if dependencies.is_empty() {
+            return Ok(());
+        }
+
+        for dependency in &dependencies {
+            if !self.can_clusters.contains_key(&dependency.bus) {
+                return Err(format!("Executor <{}> requires bus '{}', which is not contained in the restbus simulation.", executor.id, dependency.bus));
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.executors.insert(executor.id, dependencies);
+        self.reconcile(&mut state);
+        return Ok(());
+    }
+
+    // Stops the buses, which are not required by any other executor anymore
+    pub fn executor_stopped(&self, executor_id: ExecutorId) {
+        let mut state = self.state.lock().unwrap();
+        if state.executors.remove(&executor_id).is_some() {
+            self.reconcile(&mut state);
+        }
+    }
+
+    // Frames the ECU expects to receive and transmits on all buses of the simulation, e.g. to attach them to a test report
+    pub fn dut_communication(&self, ecu: &str) -> Result<DutCommunication, String> {
+        return identify_dut_communication(self.can_clusters.values().map(|can_cluster| can_cluster.as_ref()), ecu);
+    }
+
+    // Controls of all buses, e.g. to pass them to the RestbusControlServer
+    pub fn controls(&self) -> Vec<Arc<RestbusControl>> {
+        return self.controls.values().cloned().collect();
+    }
+
+    // Error supervisors of all buses, e.g. to pass them to the RestbusControlServer
+    pub fn bus_errors(&self) -> Vec<Arc<BusErrorSupervisor>> {
+        return self.bus_errors.values().cloned().collect();
+    }
+
+    // Names of the buses currently transmitting
+    pub fn running_buses(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let mut buses: Vec<String> = state.running.keys().cloned().collect();
+        buses.sort();
+        return buses;
+    }
+
+    // Starts, restarts or stops buses, so that each required bus transmits the union of the required frames
+    fn reconcile(&self, state: &mut LifecycleState) {
+        let required = required_frames(&state.executors);
+
+        let outdated: Vec<String> = state.running.iter()
+            .filter(|(bus, running)| required.get(*bus) != Some(&running.frames))
+            .map(|(bus, _)| bus.clone())
+            .collect();
+
+        for bus in outdated {
+            if let Some(running) = state.running.remove(&bus) {
+                running.stop.store(true, Ordering::SeqCst);
+                let _ = running.handle.join();
+            }
+        }
+
+        for (bus, frames) in required {
+            if state.running.contains_key(&bus) {
+                continue;
+            }
+            if let (Some(can_cluster), Some(control), Some(bus_errors)) = (self.can_clusters.get(&bus), self.controls.get(&bus), self.bus_errors.get(&bus)) {
+                let running = spawn_bus(bus.clone(), Arc::clone(can_cluster), frames, Arc::clone(control), Arc::clone(bus_errors), Arc::clone(&self.transmitter));
+                state.running.insert(bus, running);
+            }
+        }
+    }
+}
+
+impl Drop for RestbusLifecycle {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        for (_, running) in state.running.drain() {
+            running.stop.store(true, Ordering::SeqCst);
+            let _ = running.handle.join();
+        }
+    }
+}
+
+fn required_frames(executors: &HashMap<ExecutorId, Vec<RestbusDependency>>) -> HashMap<String, FrameSelection> {
+    let mut required: HashMap<String, FrameSelection> = HashMap::new();
+
+    for dependency in executors.values().flatten() {
+        let frames = required.entry(dependency.bus.clone())
+            .or_insert_with(|| Some(HashSet::new()));
+
+        if dependency.can_ids.is_empty() {
+            *frames = None;
+        } else if let Some(frames) = frames {
+            frames.extend(dependency.can_ids.iter().cloned());
+        }
+    }
+
+    return required;
+}
+
+fn spawn_bus(bus: String, can_cluster: Arc<CanCluster>, frames: FrameSelection, control: Arc<RestbusControl>, bus_errors: Arc<BusErrorSupervisor>, transmitter: Arc<dyn FrameTransmitter>) -> RunningBus {
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let handle = {
+        let stop = Arc::clone(&stop);
+        let frames = frames.clone();
+        thread::spawn(move || {
+            let clock: Arc<dyn Clock> = Arc::new(RealTimeClock::new());
+            let mut scheduler = TransmissionScheduler::new(&can_cluster, clock);
+            // Frames stopped or with a changed cycle time before this bus was (re)started
+            control.restore_schedule(&mut scheduler);
+
+            while !stop.load(Ordering::SeqCst) {
+                control.update_schedule(&mut scheduler);
+                bus_errors.poll(scheduler.clock().now());
+                let end = scheduler.clock().now() + STOP_CHECK_INTERVAL;
+                scheduler.run_for(STOP_CHECK_INTERVAL, |transmission| {
+                    let selected = match &frames {
+                        Some(frames) => frames.contains(&transmission.can_id),
+                        None => true,
+                    };
+                    if selected && control.frame_state(transmission.can_id) == FrameState::Running && bus_errors.may_transmit() {
+                        if let Err(cause) = transmitter.transmit(&bus, transmission) {
+                            bus_errors.transmission_failed(&cause, transmission.time);
+                        }
+                    }
+                });
+                // run_for() returns early, when no further transmission is due within the interval
+                scheduler.clock().sleep_until(end);
+            }
+        })
+    };
+
+    return RunningBus { frames, stop, handle };
+}
@@ -0,0 +1,497 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use flate2::read::ZlibDecoder;
+
+use crate::arxml_structs::*;
+use crate::restbus_clock::*;
+use crate::restbus_frames::*;
+use crate::restbus_scheduler::*;
+
+/*
+- Besides the ARXML-driven cyclic sending, the restbus simulation can replay recorded bus traffic, e.g. to reproduce a drive on a test bench.
+- Supported are Vector ASC (text) and BLF (binary logging format) capture files. The format is detected from the file content.
+    - ASC: classic and CAN FD data frames, with absolute or relative timestamps and hexadecimal or decimal CAN IDs.
+    - BLF: CAN_MESSAGE, CAN_MESSAGE2, CAN_FD_MESSAGE and CAN_FD_MESSAGE_64 objects, uncompressed or zlib-compressed log containers.
+  Remote frames, error frames and all other events are skipped.
+- Frames are replayed with their original timing, relative to the first frame of the log. With looping enabled, the log starts over
+  after its last frame, optionally after a gap, until the replay is stopped.
+- The channels of the log (1-based, as in CANoe/CANalyzer) are mapped to SocketCAN interfaces, e.g. "1=can0,2=vcan1".
+  Frames on unmapped channels are not sent.
+- Timing is based on a Clock (see restbus_clock.rs), so that a replay can be checked with a VirtualClock without waiting.
+*/
+
+const BLF_FILE_SIGNATURE: &[u8] = b"LOGG";
+const BLF_OBJECT_SIGNATURE: &[u8] = b"LOBJ";
+const BLF_OBJECT_BASE_HEADER_SIZE: usize = 16;
+
+const BLF_CAN_MESSAGE: u32 = 1;
+const BLF_LOG_CONTAINER: u32 = 10;
+const BLF_CAN_MESSAGE2: u32 = 86;
+const BLF_CAN_FD_MESSAGE: u32 = 100;
+const BLF_CAN_FD_MESSAGE_64: u32 = 101;
+
+const BLF_NO_COMPRESSION: u16 = 0;
+const BLF_ZLIB_COMPRESSION: u16 = 2;
+
+const BLF_TIME_TEN_MICS: u32 = 0x1;
+const BLF_TIME_ONE_NANS: u32 = 0x2;
+
+const BLF_CAN_MESSAGE_RTR: u8 = 0x80;
+const BLF_CAN_FD_MESSAGE_EDL: u8 = 0x1;
+const BLF_CAN_FD_MESSAGE_64_RTR: u32 = 0x0010;
+const BLF_CAN_FD_MESSAGE_64_EDL: u32 = 0x1000;
+
+const CAN_ID_EXTENDED_FLAG: u32 = 0x8000_0000;
+
+// Interval in which a running replay checks, whether it should stop, while waiting for the next frame
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+const CANFD_MAX_DLEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayFrame {
+    // Time since the first frame of the log
+    pub time: Duration,
+    pub channel: u16,
+    pub frame: RestbusFrame,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayLog {
+    // Sorted by time
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl ReplayLog {
+    pub fn load(path: &Path) -> Result<ReplayLog, String> {
+        let content = fs::read(path)
+            .map_err(|cause| format!("Could not read replay log {}: {cause}", path.display()))?;
+
+        let log = if content.starts_with(BLF_FILE_SIGNATURE) {
+            parse_blf(&content)
+        } else {
+            let content = String::from_utf8_lossy(&content);
+            parse_asc(&content)
+        };
+        return log.map_err(|cause| format!("Invalid replay log {}: {cause}", path.display()));
+    }
+
+    // Sorts the frames and moves the start of the log to the first frame
+    fn from_frames(mut frames: Vec<ReplayFrame>) -> ReplayLog {
+        frames.sort_by_key(|frame| frame.time);
+        if let Some(start) = frames.first().map(|frame| frame.time) {
+            for frame in frames.iter_mut() {
+                frame.time -= start;
+            }
+        }
+        return ReplayLog { frames };
+    }
+
+    pub fn duration(&self) -> Duration {
+        return self.frames.last().map(|frame| frame.time).unwrap_or(Duration::ZERO);
+    }
+
+    pub fn channels(&self) -> Vec<u16> {
+        let mut channels: Vec<u16> = self.frames.iter().map(|frame| frame.channel).collect();
+        channels.sort();
+        channels.dedup();
+        return channels;
+    }
+}
+
+fn replay_frame(time: Duration, channel: u16, can_id: u32, extended: bool, format: CanFrameFormat, payload: Vec<u8>) -> ReplayFrame {
+    return ReplayFrame {
+        time,
+        channel,
+        frame: RestbusFrame { can_id: can_id as i64, extended, format, can_xl_props: None, payload },
+    };
+}
+
+fn parse_asc_can_id(value: &str, hexadecimal: bool) -> Result<(u32, bool), String> {
+    let (value, extended) = match value.strip_suffix(['x', 'X']) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let can_id = if hexadecimal {
+        u32::from_str_radix(value, 16)
+    } else {
+        value.parse::<u32>()
+    };
+    let can_id = can_id.map_err(|cause| format!("Invalid CAN ID '{value}': {cause}"))?;
+    return Ok((can_id, extended || can_id > 0x7FF));
+}
+
+fn parse_asc_payload(values: &[&str], length: usize) -> Result<Vec<u8>, String> {
+    if values.len() < length {
+        return Err(format!("Expected {length} data bytes, but found {}.", values.len()));
+    }
+    return values[..length].iter()
+        .map(|value| u8::from_str_radix(value, 16).map_err(|cause| format!("Invalid data byte '{value}': {cause}")))
+        .collect();
+}
+
+// Classic frame: <time> <channel> <id> <Rx|Tx> d <dlc> <data>...
+fn parse_asc_can_line(tokens: &[&str], hexadecimal: bool) -> Result<Option<(u16, u32, bool, CanFrameFormat, Vec<u8>)>, String> {
+    let channel = match tokens[1].parse::<u16>() {
+        Ok(channel) => channel,
+        Err(_) => return Ok(None),
+    };
+    if tokens.len() < 6 || tokens[4] != "d" {
+        return Ok(None);
+    }
+    let (can_id, extended) = parse_asc_can_id(tokens[2], hexadecimal)?;
+    let length = usize::from_str_radix(tokens[5], 16)
+        .map_err(|cause| format!("Invalid DLC '{}': {cause}", tokens[5]))?
+        .min(8);
+    let payload = parse_asc_payload(&tokens[6..], length)?;
+    return Ok(Some((channel, can_id, extended, CanFrameFormat::Classic, payload)));
+}
+
+// CAN FD frame: <time> CANFD <channel> <Rx|Tx> <id> [<symbolic name>] <brs> <esi> <dlc> <data length> <data>...
+fn parse_asc_canfd_line(tokens: &[&str], hexadecimal: bool) -> Result<Option<(u16, u32, bool, CanFrameFormat, Vec<u8>)>, String> {
+    if tokens.len() < 9 {
+        return Ok(None);
+    }
+    let channel = tokens[2].parse::<u16>()
+        .map_err(|cause| format!("Invalid channel '{}': {cause}", tokens[2]))?;
+    let (can_id, extended) = parse_asc_can_id(tokens[4], hexadecimal)?;
+
+    // The symbolic name is optional, so the position of the flags is found by their shape
+    let is_flag = |token: &str| token == "0" || token == "1";
+    let flags = (5..tokens.len().saturating_sub(3))
+        .find(|index| is_flag(tokens[*index]) && is_flag(tokens[index + 1]))
+        .ok_or_else(|| String::from("Missing BRS and ESI flags of CAN FD frame."))?;
+
+    let dlc = usize::from_str_radix(tokens[flags + 2], 16)
+        .map_err(|cause| format!("Invalid DLC '{}': {cause}", tokens[flags + 2]))?;
+    let length = tokens[flags + 3].parse::<usize>()
+        .map_err(|cause| format!("Invalid data length '{}': {cause}", tokens[flags + 3]))?;
+    if length == 0 && dlc > 0 {
+        // Remote frame
+        return Ok(None);
+    }
+    let payload = parse_asc_payload(&tokens[flags + 4..], length.min(CANFD_MAX_DLEN))?;
+    return Ok(Some((channel, can_id, extended, CanFrameFormat::Fd, payload)));
+}
+
+pub fn parse_asc(content: &str) -> Result<ReplayLog, String> {
+    let mut hexadecimal = true;
+    let mut relative_timestamps = false;
+    let mut previous_time = Duration::ZERO;
+    let mut frames = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() || tokens[0].starts_with("//") {
+            continue;
+        }
+
+        if tokens[0] == "base" {
+            hexadecimal = tokens.get(1) != Some(&"dec");
+            relative_timestamps = tokens.get(3) == Some(&"relative");
+            continue;
+        }
+
+        let time = match tokens[0].parse::<f64>() {
+            Ok(time) if tokens.len() > 2 => seconds_to_duration(time),
+            _ => continue,
+        };
+        let time = if relative_timestamps { previous_time + time } else { time };
+        previous_time = time;
+
+        let parsed = if tokens[1] == "CANFD" {
+            parse_asc_canfd_line(&tokens, hexadecimal)
+        } else {
+            parse_asc_can_line(&tokens, hexadecimal)
+        };
+        let parsed = parsed.map_err(|cause| format!("Line {}: {cause}", index + 1))?;
+
+        if let Some((channel, can_id, extended, format, payload)) = parsed {
+            frames.push(replay_frame(time, channel, can_id, extended, format, payload));
+        }
+    }
+
+    return Ok(ReplayLog::from_frames(frames));
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, String> {
+    return bytes.get(offset).copied()
+        .ok_or_else(|| format!("Unexpected end of data at offset {offset}."));
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, String> {
+    return bytes.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| format!("Unexpected end of data at offset {offset}."));
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    return bytes.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| format!("Unexpected end of data at offset {offset}."));
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    return Ok(read_u32(bytes, offset)? as u64 | (read_u32(bytes, offset + 4)? as u64) << 32);
+}
+
+fn read_payload(bytes: &[u8], offset: usize, length: usize) -> Result<Vec<u8>, String> {
+    return bytes.get(offset..offset + length)
+        .map(|payload| payload.to_vec())
+        .ok_or_else(|| format!("Unexpected end of data at offset {offset}."));
+}
+
+// A BLF object: its type, the flags and timestamp from its header and the data following the header
+struct BlfObject<'a> {
+    object_type: u32,
+    flags: u32,
+    timestamp: u64,
+    data: &'a [u8],
+}
+
+impl BlfObject<'_> {
+    fn time(&self) -> Duration {
+        return match self.flags {
+            BLF_TIME_ONE_NANS => Duration::from_nanos(self.timestamp),
+            BLF_TIME_TEN_MICS => Duration::from_micros(self.timestamp.saturating_mul(10)),
+            _ => Duration::from_nanos(self.timestamp),
+        };
+    }
+}
+
+// Splits the bytes into objects. Returns the objects and the number of bytes consumed,
+// as objects may continue in the next log container.
+fn split_blf_objects(bytes: &[u8]) -> Result<(Vec<BlfObject<'_>>, usize), String> {
+    let mut objects = Vec::new();
+    let mut offset = 0;
+
+    while offset + BLF_OBJECT_BASE_HEADER_SIZE <= bytes.len() {
+        if &bytes[offset..offset + 4] != BLF_OBJECT_SIGNATURE {
+            // Padding between objects
+            offset += 1;
+            continue;
+        }
+        let header_size = read_u16(bytes, offset + 4)? as usize;
+        let object_size = read_u32(bytes, offset + 8)? as usize;
+        let object_type = read_u32(bytes, offset + 12)?;
+        if object_size < BLF_OBJECT_BASE_HEADER_SIZE {
+            return Err(format!("Invalid size {object_size} of object at offset {offset}."));
+        }
+        if offset + object_size > bytes.len() {
+            break;
+        }
+
+        let object = &bytes[offset..offset + object_size];
+        let (flags, timestamp) = if object_type == BLF_LOG_CONTAINER {
+            (0, 0)
+        } else {
+            (read_u32(object, 16)?, read_u64(object, 24)?)
+        };
+        let data_start = if object_type == BLF_LOG_CONTAINER { BLF_OBJECT_BASE_HEADER_SIZE } else { header_size.min(object_size) };
+        objects.push(BlfObject { object_type, flags, timestamp, data: &object[data_start..] });
+
+        offset += object_size + object_size % 4;
+    }
+
+    return Ok((objects, offset.min(bytes.len())));
+}
+
+fn decompress_blf_container(data: &[u8]) -> Result<Vec<u8>, String> {
+    let compression = read_u16(data, 0)?;
+    let uncompressed_size = read_u32(data, 8)? as usize;
+    let content = data.get(16..).unwrap_or_default();
+
+    return match compression {
+        BLF_NO_COMPRESSION => Ok(content.to_vec()),
+        BLF_ZLIB_COMPRESSION => {
+            let mut uncompressed = Vec::with_capacity(uncompressed_size);
+            ZlibDecoder::new(content).read_to_end(&mut uncompressed)
+                .map_err(|cause| format!("Could not decompress log container: {cause}"))?;
+            Ok(uncompressed)
+        }
+        other => Err(format!("Unsupported compression method {other} of log container.")),
+    };
+}
+
+fn parse_blf_frame(object: &BlfObject) -> Result<Option<ReplayFrame>, String> {
+    let data = object.data;
+    let frame = match object.object_type {
+        // channel (2), flags (1), dlc (1), id (4), data (8)
+        BLF_CAN_MESSAGE | BLF_CAN_MESSAGE2 => {
+            if read_u8(data, 2)? & BLF_CAN_MESSAGE_RTR != 0 {
+                return Ok(None);
+            }
+            let length = (read_u8(data, 3)? as usize).min(8);
+            let id = read_u32(data, 4)?;
+            let payload = read_payload(data, 8, length)?;
+            replay_frame(object.time(), read_u16(data, 0)?, id & !CAN_ID_EXTENDED_FLAG, id & CAN_ID_EXTENDED_FLAG != 0, CanFrameFormat::Classic, payload)
+        }
+        // channel (2), flags (1), dlc (1), id (4), frame length (4), bit count (1), fd flags (1), valid data bytes (1), reserved (5), data (64)
+        BLF_CAN_FD_MESSAGE => {
+            let fd_flags = read_u8(data, 13)?;
+            if fd_flags & BLF_CAN_FD_MESSAGE_EDL == 0 && read_u8(data, 2)? & BLF_CAN_MESSAGE_RTR != 0 {
+                return Ok(None);
+            }
+            let format = if fd_flags & BLF_CAN_FD_MESSAGE_EDL != 0 { CanFrameFormat::Fd } else { CanFrameFormat::Classic };
+            let length = read_u8(data, 14)? as usize;
+            let id = read_u32(data, 4)?;
+            let payload = read_payload(data, 20, length)?;
+            replay_frame(object.time(), read_u16(data, 0)?, id & !CAN_ID_EXTENDED_FLAG, id & CAN_ID_EXTENDED_FLAG != 0, format, payload)
+        }
+        // channel (1), dlc (1), valid data bytes (1), tx count (1), id (4), frame length (4), flags (4),
+        // arbitration and data bit timing (8), BRS and CRC offsets (8), bit count (2), direction (1), ext data offset (1), crc (4), data
+        BLF_CAN_FD_MESSAGE_64 => {
+            let flags = read_u32(data, 12)?;
+            if flags & BLF_CAN_FD_MESSAGE_64_RTR != 0 {
+                return Ok(None);
+            }
+            let format = if flags & BLF_CAN_FD_MESSAGE_64_EDL != 0 { CanFrameFormat::Fd } else { CanFrameFormat::Classic };
+            let length = read_u8(data, 2)? as usize;
+            let id = read_u32(data, 4)?;
+            let payload = read_payload(data, 40, length)?;
+            replay_frame(object.time(), read_u8(data, 0)? as u16, id & !CAN_ID_EXTENDED_FLAG, id & CAN_ID_EXTENDED_FLAG != 0, format, payload)
+        }
+        _ => return Ok(None),
+    };
+    return Ok(Some(frame));
+}
+
+pub fn parse_blf(content: &[u8]) -> Result<ReplayLog, String> {
+    if !content.starts_with(BLF_FILE_SIGNATURE) {
+        return Err(String::from("Missing BLF file signature."));
+    }
+    let header_size = read_u32(content, 4)? as usize;
+    let (objects, _) = split_blf_objects(content.get(header_size..).unwrap_or_default())?;
+
+    let mut frames = Vec::new();
+    // Objects within log containers may be split across consecutive containers
+    let mut pending: Vec<u8> = Vec::new();
+
+    for object in objects {
+        if object.object_type == BLF_LOG_CONTAINER {
+            pending.extend(decompress_blf_container(object.data)?);
+            let (contained, consumed) = split_blf_objects(&pending)?;
+            for contained in contained {
+                if let Some(frame) = parse_blf_frame(&contained)? {
+                    frames.push(frame);
+                }
+            }
+            pending.drain(..consumed);
+        } else if let Some(frame) = parse_blf_frame(&object)? {
+            frames.push(frame);
+        }
+    }
+
+    return Ok(ReplayLog::from_frames(frames));
+}
+
+// Maps the channels of the log to the SocketCAN interfaces the frames are sent on
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayChannelMapping {
+    channels: BTreeMap<u16, String>,
+}
+
+impl ReplayChannelMapping {
+    // Parses a declaration like "1=can0,2=vcan1"
+    pub fn parse(declaration: &str) -> Result<ReplayChannelMapping, String> {
+        let mut channels = BTreeMap::new();
+
+        for entry in declaration.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (channel, interface) = entry.split_once('=')
+                .ok_or_else(|| format!("Invalid replay channel mapping '{entry}'. Expected <channel>=<interface>."))?;
+            let channel = channel.trim().parse::<u16>()
+                .map_err(|cause| format!("Invalid channel '{}' in replay channel mapping: {cause}", channel.trim()))?;
+            let interface = interface.trim();
+            if interface.is_empty() {
+                return Err(format!("Missing interface for channel {channel} in replay channel mapping."));
+            }
+            if channels.insert(channel, interface.to_string()).is_some() {
+                return Err(format!("Channel {channel} is mapped to multiple interfaces."));
+            }
+        }
+
+        return Ok(ReplayChannelMapping { channels });
+    }
+
+    pub fn interface_of(&self, channel: u16) -> Option<&String> {
+        return self.channels.get(&channel);
+    }
+}
+
+// Writes a replayed frame onto the interface, e.g. encoded with a FrameEncoder onto a CAN_RAW socket
+pub trait ReplayTransmitter: Send + Sync {
+    fn transmit_frame(&self, interface: &str, frame: &RestbusFrame);
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayOptions {
+    pub looping: bool,
+    // Pause between the last frame of the log and the first frame of the next pass
+    pub loop_gap: Duration,
+}
+
+pub struct CanReplay {
+    log: ReplayLog,
+    mapping: ReplayChannelMapping,
+    options: ReplayOptions,
+    clock: Arc<dyn Clock>,
+}
+
+impl CanReplay {
+    pub fn new(log: ReplayLog, mapping: ReplayChannelMapping, options: ReplayOptions, clock: Arc<dyn Clock>) -> CanReplay {
+        for channel in log.channels() {
+            if mapping.interface_of(channel).is_none() {
+                println!("[-] WARNING: Channel {channel} of the replay log is not mapped to an interface. Not sending its frames.");
+            }
+        }
+        return CanReplay { log, mapping, options, clock };
+    }
+
+    // Waits until the deadline, but returns early with false, if the replay was stopped meanwhile
+    fn wait_until(&self, deadline: Duration, stop: &AtomicBool) -> bool {
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return false;
+            }
+            let now = self.clock.now();
+            if now >= deadline {
+                return true;
+            }
+            self.clock.sleep_until(deadline.min(now + STOP_CHECK_INTERVAL));
+        }
+    }
+
+    // Sends the frames of the log with their original timing, starting at the current time of the clock.
+    // Returns after the last frame, or when looping, once stopped. Returns the number of sent frames.
+    pub fn run(&self, stop: &AtomicBool, transmitter: &dyn ReplayTransmitter) -> usize {
+        if self.log.frames.is_empty() {
+            println!("[-] WARNING: Replay log contains no frames.");
+            return 0;
+        }
+        let mut start = self.clock.now();
+        let mut transmissions = 0;
+
+        loop {
+            for replay_frame in &self.log.frames {
+                if !self.wait_until(start + replay_frame.time, stop) {
+                    return transmissions;
+                }
+                if let Some(interface) = self.mapping.interface_of(replay_frame.channel) {
+                    transmitter.transmit_frame(interface, &replay_frame.frame);
+                    transmissions += 1;
+                }
+            }
+
+            if !self.options.looping {
+                return transmissions;
+            }
+            start += self.log.duration() + self.options.loop_gap;
+        }
+    }
+}
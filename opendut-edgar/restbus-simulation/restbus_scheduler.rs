@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::arxml_structs::*;
+use crate::restbus_clock::*;
+
+/*
+- Computes when the frames of a CanCluster have to be transmitted, based on the timing of their ISignalIPDUs.
+- Cyclic frames are sent first at their offset and then once per period.
+- Cyclic frames with the same period, but without an offset in the ARXML, would all be sent at once in every cycle.
+  Such bursts overload low-bitrate buses and distort latency measurements, so their first transmissions are spread evenly across the period.
+  Frames with an offset keep it and occupy the slot of the period closest to it.
+- Frames without a period, but with repetitions, are sent at their offset and then repeated number_of_repetitions times.
+- All timing is based on a Clock (see restbus_clock.rs). With a VirtualClock or SteppedClock, schedules can be checked
+  deterministically and long-duration scenarios run without waiting.
+- Transmissions due at the same time are ordered by CAN ID, so that the order is reproducible.
+- Frames can be stopped, restarted and get a different cycle time while the schedule is running (see restbus_control.rs).
+  A restarted frame is sent right away and then continues with its period.
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledTransmission {
+    pub can_id: i64,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ScheduleEntry {
+    // Order of the fields matters, as the heap is ordered by the derived Ord
+    next: Duration,
+    can_id: i64,
+    period: Option<Duration>,
+    remaining_repetitions: i64,
+    repetition_period: Duration,
+}
+
+pub struct TransmissionScheduler {
+    clock: Arc<dyn Clock>,
+    entries: BinaryHeap<Reverse<ScheduleEntry>>,
+    // Entries of the stopped frames, kept to restart them
+    stopped: HashMap<i64, ScheduleEntry>,
+    // Periods from the ARXML, to restore them after the cycle time was changed
+    default_periods: HashMap<i64, Option<Duration>>,
+}
+
+// ARXML timing values are given in seconds
+pub fn seconds_to_duration(seconds: f64) -> Duration {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Duration::ZERO;
+    }
+    return Duration::from_secs_f64(seconds);
+}
+
+fn schedule_entry(can_id: i64, can_frame_triggering: &CanFrameTriggering, start: Duration) -> Option<ScheduleEntry> {
+    for pdu_mapping in &can_frame_triggering.pdu_mappings {
+        if let PDU::ISignalIPDU(isignal_ipdu) = &pdu_mapping.pdu {
+            let period = seconds_to_duration(isignal_ipdu.cyclic_timing_period_value);
+
+            if period > Duration::ZERO || isignal_ipdu.number_of_repetitions > 0 {
+                return Some(ScheduleEntry {
+                    next: start + seconds_to_duration(isignal_ipdu.cyclic_timing_offset_value),
+                    can_id,
+                    period: if period > Duration::ZERO { Some(period) } else { None },
+                    remaining_repetitions: isignal_ipdu.number_of_repetitions,
+                    repetition_period: seconds_to_duration(isignal_ipdu.repetition_period_value),
+                });
+            }
+        }
+    }
+    return None;
+}
+
+// Divides the period into one slot per frame with that period. Frames with an offset occupy the slot closest to it,
+// frames without one are assigned the remaining slots in the order of their CAN IDs.
+fn spread_offsets(entries: &mut [ScheduleEntry], start: Duration) {
+    let mut frames_by_period: HashMap<Duration, Vec<&mut ScheduleEntry>> = HashMap::new();
+    for entry in entries.iter_mut() {
+        if let Some(period) = entry.period {
+            frames_by_period.entry(period).or_default().push(entry);
+        }
+    }
+
+    for (period, mut frames) in frames_by_period {
+        let slot_count = frames.len();
+        let slot = period / slot_count as u32;
+        if slot_count < 2 || slot == Duration::ZERO {
+            continue;
+        }
+        frames.sort_by_key(|entry| entry.can_id);
+
+        let mut occupied = vec![false; slot_count];
+        for entry in frames.iter().filter(|entry| entry.next > start) {
+            let offset = (entry.next - start).as_nanos() % period.as_nanos();
+            let index = ((offset + slot.as_nanos() / 2) / slot.as_nanos()) as usize % slot_count;
+            occupied[index] = true;
+        }
+
+        let mut free_slots = (0..slot_count).filter(|index| !occupied[*index]);
+        for entry in frames.iter_mut().filter(|entry| entry.next == start) {
+            if let Some(index) = free_slots.next() {
+                entry.next = start + slot * index as u32;
+            }
+        }
+    }
+}
+
+impl TransmissionScheduler {
+    // The schedule starts at the current time of the clock
+    pub fn new(can_cluster: &CanCluster, clock: Arc<dyn Clock>) -> TransmissionScheduler {
+        let start = clock.now();
+
+        let mut entries: Vec<ScheduleEntry> = can_cluster.can_frame_triggerings.iter()
+            .filter_map(|(can_id, can_frame_triggering)| schedule_entry(*can_id, can_frame_triggering, start))
+            .collect();
+        spread_offsets(&mut entries, start);
+
+        let entries: BinaryHeap<Reverse<ScheduleEntry>> = entries.into_iter()
+            .map(Reverse)
+            .collect();
+        let default_periods = entries.iter()
+            .map(|Reverse(entry)| (entry.can_id, entry.period))
+            .collect();
+
+        return TransmissionScheduler { clock, entries, stopped: HashMap::new(), default_periods };
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        return &self.clock;
+    }
+
+    pub fn is_scheduled(&self, can_id: i64) -> bool {
+        return self.default_periods.contains_key(&can_id);
+    }
+
+    // Applies the change to the entry of the CAN ID, whether the frame is running or stopped. Returns false, if the frame is not scheduled.
+    fn modify_entry<F>(&mut self, can_id: i64, mut modify: F) -> bool
+    where F: FnMut(&mut ScheduleEntry) {
+        if let Some(entry) = self.stopped.get_mut(&can_id) {
+            modify(entry);
+            return true;
+        }
+
+        let mut entries = std::mem::take(&mut self.entries).into_vec();
+        let mut found = false;
+        for Reverse(entry) in entries.iter_mut().filter(|Reverse(entry)| entry.can_id == can_id) {
+            modify(entry);
+            found = true;
+        }
+        self.entries = entries.into();
+        return found;
+    }
+
+    // Removes the frame from the schedule, until it is restarted. Returns false, if the frame is not running.
+    pub fn stop_frame(&mut self, can_id: i64) -> bool {
+        let mut entries = std::mem::take(&mut self.entries).into_vec();
+        let position = entries.iter().position(|Reverse(entry)| entry.can_id == can_id);
+        if let Some(position) = position {
+            let Reverse(entry) = entries.swap_remove(position);
+            self.stopped.insert(can_id, entry);
+        }
+        self.entries = entries.into();
+        return position.is_some();
+    }
+
+    // Sends the stopped frame at the current time and then continues with its period. Returns false, if the frame is not stopped.
+    pub fn start_frame(&mut self, can_id: i64) -> bool {
+        return match self.stopped.remove(&can_id) {
+            Some(mut entry) => {
+                entry.next = self.clock.now();
+                self.entries.push(Reverse(entry));
+                true
+            }
+            None => false,
+        };
+    }
+
+    // Changes the cycle time of a frame. A shorter period takes effect right away, a longer one after the next transmission.
+    pub fn set_period(&mut self, can_id: i64, period: Duration) -> Result<(), String> {
+        if period == Duration::ZERO {
+            return Err(format!("Cycle time of frame with CAN ID {can_id:#X} has to be greater than zero."));
+        }
+        let now = self.clock.now();
+        let found = self.modify_entry(can_id, |entry| {
+            entry.period = Some(period);
+            entry.next = entry.next.min(now + period);
+        });
+        if !found {
+            return Err(format!("Frame with CAN ID {can_id:#X} is not scheduled."));
+        }
+        return Ok(());
+    }
+
+    // Restores the cycle time from the ARXML
+    pub fn reset_period(&mut self, can_id: i64) -> Result<(), String> {
+        let default_period = *self.default_periods.get(&can_id)
+            .ok_or_else(|| format!("Frame with CAN ID {can_id:#X} is not scheduled."))?;
+        self.modify_entry(can_id, |entry| entry.period = default_period);
+        return Ok(());
+    }
+
+    // Returns the next due transmission without waiting for it and reschedules the frame
+    pub fn next_transmission(&mut self) -> Option<ScheduledTransmission> {
+        let Reverse(mut entry) = self.entries.pop()?;
+
+        let transmission = ScheduledTransmission { can_id: entry.can_id, time: entry.next };
+
+        if let Some(period) = entry.period {
+            entry.next += period;
+            self.entries.push(Reverse(entry));
+        } else if entry.remaining_repetitions > 0 {
+            entry.remaining_repetitions -= 1;
+            entry.next += entry.repetition_period;
+            self.entries.push(Reverse(entry));
+        }
+
+        return Some(transmission);
+    }
+
+    // Waits for each transmission on the clock and passes it to the transmit function, until the given duration passed.
+    // Returns the number of transmissions.
+    pub fn run_for<F>(&mut self, duration: Duration, mut transmit: F) -> usize
+    where F: FnMut(&ScheduledTransmission) {
+        let end = self.clock.now() + duration;
+        let mut transmissions = 0;
+
+        loop {
+            let next_is_due = match self.entries.peek() {
+                Some(Reverse(entry)) => entry.next <= end,
+                None => false,
+            };
+            if !next_is_due {
+                break;
+            }
+
+            let transmission = self.next_transmission().unwrap();
+            self.clock.sleep_until(transmission.time);
+            transmit(&transmission);
+            transmissions += 1;
+        }
+
+        return transmissions;
+    }
+}
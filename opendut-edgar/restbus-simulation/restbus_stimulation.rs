@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::restbus_clock::*;
+use crate::restbus_encoder::*;
+
+/*
+- Drives selected signals of the simulated frames from time-stamped value sequences (stimulation profiles), e.g. a recorded drive cycle,
+  so that realistic signal curves can be sent without writing scripts.
+- A profile is a CSV file. The first column is the time in seconds, every further column holds the physical values of one signal,
+  named by the CAN ID of its frame and the signal name:
+    time,0x1A0:EngineSpeed,0x1A0:EngineTemperature,0x2B0:VehicleSpeed
+    0.0,800,20,0
+    2.5,3000,,30.5
+    10.0,2000,90,80
+  Empty cells mean that the signal has no sample at that time. Lines starting with '#' are ignored.
+  Values can also be separated by semicolons, which is detected from the header.
+- Between two samples, the value is interpolated linearly, or held until the next sample with StimulationInterpolation::Step,
+  e.g. for gear positions. Before its first sample, a signal has the value of its first sample and after its last sample the value of its last sample.
+- With looping enabled, the profile starts over after the time of its last row, until the stimulation is removed.
+- The physical values are converted with the COMPU-METHOD of the signal (see CompuMethod::to_raw()) and written into the payload of each
+  transmission by SignalStimulation::apply(). The RestbusControl applies a stimulation before the overridden signal values,
+  so that a value set manually takes precedence (see restbus_control.rs).
+- Timing is based on a Clock (see restbus_clock.rs), so that a profile can be checked with a VirtualClock without waiting.
+*/
+
+const TIME_COLUMN: &str = "time";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StimulationInterpolation {
+    #[default]
+    Linear,
+    Step,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StimulationOptions {
+    pub interpolation: StimulationInterpolation,
+    pub looping: bool,
+}
+
+// Samples of one signal as (seconds since the start of the profile, physical value), sorted by time
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalSequence {
+    pub can_id: i64,
+    pub signal: String,
+    pub samples: Vec<(f64, f64)>,
+}
+
+impl SignalSequence {
+    pub fn value_at(&self, time: f64, interpolation: StimulationInterpolation) -> Option<f64> {
+        let (first_time, first_value) = *self.samples.first()?;
+        if time <= first_time {
+            return Some(first_value);
+        }
+
+        // Index of the first sample after the time
+        let next = self.samples.partition_point(|(sample_time, _)| *sample_time <= time);
+        if next == self.samples.len() {
+            return self.samples.last().map(|(_, value)| *value);
+        }
+        let (previous_time, previous_value) = self.samples[next - 1];
+        let (next_time, next_value) = self.samples[next];
+
+        return match interpolation {
+            StimulationInterpolation::Step => Some(previous_value),
+            StimulationInterpolation::Linear => {
+                let fraction = (time - previous_time) / (next_time - previous_time);
+                Some(previous_value + (next_value - previous_value) * fraction)
+            }
+        };
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StimulationProfile {
+    pub sequences: Vec<SignalSequence>,
+}
+
+impl StimulationProfile {
+    pub fn load(path: &Path) -> Result<StimulationProfile, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|cause| format!("Could not read stimulation profile {}: {cause}", path.display()))?;
+        return parse_stimulation_csv(&content)
+            .map_err(|cause| format!("Invalid stimulation profile {}: {cause}", path.display()));
+    }
+
+    // Time of the last sample of all signals
+    pub fn duration(&self) -> f64 {
+        return self.sequences.iter()
+            .filter_map(|sequence| sequence.samples.last().map(|(time, _)| *time))
+            .fold(0.0, f64::max);
+    }
+}
+
+fn parse_can_id(value: &str) -> Result<i64, String> {
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => value.parse::<i64>(),
+    };
+    return parsed.map_err(|cause| format!("Invalid CAN ID '{value}': {cause}"));
+}
+
+pub fn parse_stimulation_csv(content: &str) -> Result<StimulationProfile, String> {
+    let mut lines = content.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (_, header) = lines.next()
+        .ok_or_else(|| String::from("Missing header line."))?;
+    let separator = if header.contains(';') { ';' } else { ',' };
+
+    let mut columns = header.split(separator).map(str::trim);
+    if columns.next().map(|column| column.to_lowercase()) != Some(String::from(TIME_COLUMN)) {
+        return Err(format!("First column of the header has to be '{TIME_COLUMN}'."));
+    }
+
+    let mut sequences = Vec::new();
+    for column in columns {
+        let (can_id, signal) = column.split_once(':')
+            .ok_or_else(|| format!("Invalid column '{column}'. Expected <CAN ID>:<signal>, e.g. 0x1A0:EngineSpeed."))?;
+        let can_id = parse_can_id(can_id.trim())?;
+        let signal = signal.trim().to_string();
+        if sequences.iter().any(|sequence: &SignalSequence| sequence.can_id == can_id && sequence.signal == signal) {
+            return Err(format!("Signal '{signal}' of frame with CAN ID {can_id:#X} has multiple columns."));
+        }
+        sequences.push(SignalSequence { can_id, signal, samples: Vec::new() });
+    }
+    if sequences.is_empty() {
+        return Err(String::from("Header contains no signal columns."));
+    }
+
+    for (line_number, line) in lines {
+        let mut cells = line.split(separator).map(str::trim);
+        let time = cells.next().unwrap_or_default();
+        let time = time.parse::<f64>()
+            .ok()
+            .filter(|time| time.is_finite() && *time >= 0.0)
+            .ok_or_else(|| format!("Invalid time '{time}' in line {line_number}. Expected seconds as a non-negative number."))?;
+
+        let cells: Vec<&str> = cells.collect();
+        if cells.len() > sequences.len() {
+            return Err(format!("Line {line_number} has more values than the header has signals."));
+        }
+        for (sequence, cell) in sequences.iter_mut().zip(cells) {
+            if cell.is_empty() {
+                continue;
+            }
+            let value = cell.parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| format!("Invalid value '{cell}' for signal '{}' in line {line_number}.", sequence.signal))?;
+            if sequence.samples.last().is_some_and(|(previous_time, _)| *previous_time >= time) {
+                return Err(format!("Time {time} in line {line_number} is not after the previous sample of signal '{}'.", sequence.signal));
+            }
+            sequence.samples.push((time, value));
+        }
+    }
+
+    if let Some(sequence) = sequences.iter().find(|sequence| sequence.samples.is_empty()) {
+        return Err(format!("Signal '{}' of frame with CAN ID {:#X} has no samples.", sequence.signal, sequence.can_id));
+    }
+
+    return Ok(StimulationProfile { sequences });
+}
+
+// Stimulation of the signals of one bus, started when it is created
+pub struct SignalStimulation {
+    profile: StimulationProfile,
+    options: StimulationOptions,
+    // Layouts of the stimulated signals, by CAN ID and signal name
+    layouts: HashMap<i64, HashMap<String, SignalLayout>>,
+    clock: Arc<dyn Clock>,
+    start: Duration,
+}
+
+impl SignalStimulation {
+    // Checks that all signals of the profile exist in the given layouts of the frames of the bus
+    pub fn new(profile: StimulationProfile, options: StimulationOptions, signals: &HashMap<i64, HashMap<String, SignalLayout>>, clock: Arc<dyn Clock>) -> Result<SignalStimulation, String> {
+        let mut layouts: HashMap<i64, HashMap<String, SignalLayout>> = HashMap::new();
+
+        for sequence in &profile.sequences {
+            let layout = signals.get(&sequence.can_id)
+                .ok_or_else(|| format!("Stimulation profile references frame with CAN ID {:#X}, which is not simulated on this bus.", sequence.can_id))?
+                .get(&sequence.signal)
+                .ok_or_else(|| format!("Frame with CAN ID {:#X} has no signal '{}'.", sequence.can_id, sequence.signal))?;
+            if layout.length > 64 {
+                return Err(format!("Signal '{}' has {} bits, values can only be set for signals with up to 64 bits.", sequence.signal, layout.length));
+            }
+            layouts.entry(sequence.can_id).or_default().insert(sequence.signal.clone(), layout.clone());
+        }
+
+        let start = clock.now();
+        return Ok(SignalStimulation { profile, options, layouts, clock, start });
+    }
+
+    // Seconds into the profile, wrapped around with looping
+    fn profile_time(&self) -> f64 {
+        let elapsed = self.clock.now().saturating_sub(self.start).as_secs_f64();
+        let duration = self.profile.duration();
+        if self.options.looping && duration > 0.0 {
+            return elapsed % duration;
+        }
+        return elapsed;
+    }
+
+    // True, once the time of the last sample passed without looping
+    pub fn is_finished(&self) -> bool {
+        return !self.options.looping && self.profile_time() >= self.profile.duration();
+    }
+
+    // Physical values of the stimulated signals of the frame at the current time
+    pub fn values(&self, can_id: i64) -> Vec<(&str, f64)> {
+        let time = self.profile_time();
+        return self.profile.sequences.iter()
+            .filter(|sequence| sequence.can_id == can_id)
+            .filter_map(|sequence| {
+                sequence.value_at(time, self.options.interpolation)
+                    .map(|value| (sequence.signal.as_str(), value))
+            })
+            .collect();
+    }
+
+    // Writes the current values of the stimulated signals into the payload of one transmission of the frame
+    pub fn apply(&self, can_id: i64, payload: &mut [u8]) {
+        let Some(layouts) = self.layouts.get(&can_id) else {
+            return;
+        };
+        for (signal, value) in self.values(can_id) {
+            let Some(layout) = layouts.get(signal) else { continue };
+            let raw = match &layout.compu_method {
+                Some(compu_method) => compu_method.to_raw(value),
+                None => Ok(value.round() as i64),
+            };
+            let result = raw.and_then(|raw| write_signal_value(payload, signal, layout, raw));
+            if let Err(cause) = result {
+                println!("[-] WARNING: Could not stimulate signal '{signal}' of frame with CAN ID {can_id:#X}: {cause}");
+            }
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let signals = self.profile.sequences.len();
+        if self.is_finished() {
+            return format!("stimulation of {signals} signal(s) finished");
+        }
+        return format!("stimulation of {signals} signal(s) at {:.1}s of {:.1}s", self.profile_time(), self.profile.duration());
+    }
+}
@@ -1,12 +1,20 @@
 use std::collections::HashSet;
 use std::net::Ipv4Addr;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
+use anyhow::{anyhow, Context};
 use clap::{Parser, Subcommand};
 use tracing::info;
 use url::Url;
 use uuid::Uuid;
 
+use crate::common::settings;
+use crate::service::bus;
+use crate::service::bus::{BusFrame, BusKind};
+use crate::service::configuration_validation;
+use crate::service::self_test;
 use crate::setup;
 use opendut_types::peer::PeerId;
 use opendut_types::util::net::NetworkInterfaceName;
@@ -35,7 +43,7 @@ enum Commands {
         #[command(subcommand, name="mode")]
         setup_mode: SetupMode,
 
-        /// Run through all steps without changing the system
+        /// Run through all steps without changing the system, printing the changes which would be made
         #[arg(long, global=true)]
         dry_run: bool,
 
@@ -46,6 +54,73 @@ enum Commands {
         /// Specify the Maximum Transfer Unit for network packages in bytes.
         #[arg(long, global=true, default_value="1538")]
         mtu: u16,
+
+        /// Install configuration, which prevents NetworkManager and systemd-networkd from managing the interfaces of EDGAR.
+        #[arg(long, global=true)]
+        exclude_from_network_managers: bool,
+    },
+    /// Inspect the buses which EDGAR connects to a cluster
+    Bus {
+        #[command(subcommand)]
+        command: BusCommand,
+    },
+    /// Check whether a peer configuration could be applied on this host, without applying it, and print a report as JSON
+    ValidateConfiguration {
+        /// File containing a Protobuf-encoded ApplyPeerConfiguration message, as captured via 'peer.configuration.capture.file'
+        #[arg()]
+        file: PathBuf,
+
+        /// Validate for the provided ID instead of the configured one
+        #[arg(long)]
+        id: Option<Uuid>,
+    },
+    /// Verify the setup of this peer, from CARL over the VPN and the devices to the container engine, print a report and upload it to CARL
+    SelfTest {
+        /// Run a container from this image once, to verify that executors can be started, e.g. "busybox"
+        #[arg(long)]
+        executor_image: Option<String>,
+
+        /// Only print the report, without uploading it to CARL
+        #[arg(long)]
+        no_upload: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BusCommand {
+    /// Record the traffic on a bus into a file
+    Capture {
+        #[arg(value_enum)]
+        bus: BusKind,
+
+        /// File to write the recorded traffic to
+        #[arg(long)]
+        output: PathBuf,
+
+        /// How long to record the traffic for, in seconds
+        #[arg(long, default_value="10")]
+        duration: u64,
+
+        /// Interface to record, defaults to the bridge which is connected to the cluster
+        #[arg(long)]
+        interface: Option<NetworkInterfaceName>,
+    },
+    /// Send a single CAN frame onto a bus
+    Inject {
+        #[arg(value_enum)]
+        bus: BusKind,
+
+        /// CAN ID of the frame in hexadecimal notation, e.g. "1A3"
+        #[arg(long)]
+        can_id: ParseableCanId,
+
+        /// Payload of the frame in hexadecimal notation, e.g. "DEADBEEF"
+        #[arg(long, default_value="")]
+        data: ParseableHexData,
+
+        /// Interface to send the frame on, defaults to the bridge which is connected to the cluster
+        #[arg(long)]
+        interface: Option<NetworkInterfaceName>,
     },
 }
 
@@ -57,6 +132,20 @@ enum SetupMode {
         #[arg()]
         setup_string: String,
     },
+    /// Register this host as a new peer with a provisioning token and prepare your system for running EDGAR Service
+    Provisioned {
+        // Provisioning String generated via CLEO, e.g. flashed onto the device during manufacturing
+        #[arg()]
+        provisioning_string: String,
+    },
+    /// Replace the credentials of an already set up peer, e.g. after they were revoked or expired, while preserving its local configuration and data
+    ReEnroll {
+        // Setup String retrieved from LEA, for the same peer
+        #[arg()]
+        setup_string: String,
+    },
+    /// Revert the changes of a previous setup: stop and remove the service, uninstall NetBird, delete the bridge and GRE interfaces and remove the CA certificates
+    Uninstall,
     /// Setup your system for network routing without automatic management. This setup method will be removed in the future.
     Unmanaged {
         /// URL of the VPN management service
@@ -92,7 +181,7 @@ pub async fn cli() -> anyhow::Result<()> {
                 id_override,
             ).await
         },
-        Commands::Setup { setup_mode, dry_run, no_confirm, mtu } => {
+        Commands::Setup { setup_mode, dry_run, no_confirm, mtu, exclude_from_network_managers } => {
             setup::start::init_logging().await?;
 
             let command = std::env::args_os()
@@ -110,19 +199,89 @@ pub async fn cli() -> anyhow::Result<()> {
 
             match setup_mode {
                 SetupMode::Managed { setup_string } => {
-                    setup::start::managed(dry_run, no_confirm, setup_string, mtu).await?;
+                    setup::start::managed(dry_run, no_confirm, setup_string, mtu, exclude_from_network_managers).await?;
+                },
+                SetupMode::Provisioned { provisioning_string } => {
+                    setup::start::provisioned(dry_run, no_confirm, provisioning_string, mtu, exclude_from_network_managers).await?;
                 },
+                SetupMode::ReEnroll { setup_string } => {
+                    setup::start::re_enroll(dry_run, no_confirm, setup_string).await?;
+                },
+                SetupMode::Uninstall => {
+                    setup::start::uninstall(dry_run, no_confirm).await?;
+                }
                 SetupMode::Unmanaged { management_url, setup_key, leader, bridge, device_interfaces } => {
                     let setup_key = SetupKey { uuid: setup_key };
                     let ParseableLeader(leader) = leader;
                     let bridge = bridge.unwrap_or_else(crate::common::default_bridge_name);
                     let device_interfaces = HashSet::from_iter(device_interfaces);
-                    setup::start::unmanaged(dry_run, no_confirm, management_url, setup_key, bridge, device_interfaces, leader, mtu).await?;
+                    setup::start::unmanaged(dry_run, no_confirm, management_url, setup_key, bridge, device_interfaces, leader, mtu, exclude_from_network_managers).await?;
                 }
             };
             info!("EDGAR Setup finished!\n");
             Ok(())
         }
+        Commands::Bus { command } => {
+            match command {
+                BusCommand::Capture { bus, output, duration, interface } => {
+                    let interface = interface.unwrap_or_else(|| bus::default_interface(bus));
+                    println!("Capturing {bus} traffic on interface '{interface}' for {duration} seconds into '{}'.", output.display());
+                    bus::create(bus)?
+                        .capture(&interface, &output, Duration::from_secs(duration)).await?;
+                }
+                BusCommand::Inject { bus, can_id, data, interface } => {
+                    let interface = interface.unwrap_or_else(|| bus::default_interface(bus));
+                    let frame = BusFrame::Can { id: can_id.0, data: data.0 };
+                    bus::create(bus)?
+                        .inject(&interface, &frame).await?;
+                }
+            }
+            Ok(())
+        }
+        Commands::ValidateConfiguration { file, id } => {
+            let self_id = match id {
+                Some(id) => PeerId::from(id),
+                None => settings::load_with_overrides(config::Config::default())?
+                    .config.get::<PeerId>(settings::key::peer::id)
+                    .context("Failed to read ID from configuration. Specify it via --id instead.")?,
+            };
+
+            let report = configuration_validation::validate_configuration_file(&file, self_id).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+
+            if report.applicable {
+                Ok(())
+            } else {
+                Err(anyhow!("Configuration could not be applied on this host."))
+            }
+        }
+        Commands::SelfTest { executor_image, no_upload } => {
+            let settings = settings::load_with_overrides(config::Config::default())?;
+            let self_id = settings.config.get::<PeerId>(settings::key::peer::id)
+                .context("Failed to read ID from configuration. Run `edgar setup` first.")?;
+
+            let options = self_test::SelfTestOptions { executor_image };
+            let self_test::SelfTestOutcome { report, carl } = self_test::run(&settings, self_id, &options).await;
+            println!("{}", self_test::format_report(&report));
+
+            let passed = report.passed;
+            if !no_upload {
+                match carl {
+                    Some(mut carl) => {
+                        carl.broker.upload_self_test_report(report).await
+                            .context("Failed to upload the self-test report to CARL.")?;
+                        println!("Uploaded the report to CARL.");
+                    }
+                    None => println!("Could not upload the report, as CARL is not reachable."),
+                }
+            }
+
+            if passed {
+                Ok(())
+            } else {
+                Err(anyhow!("Self-test failed."))
+            }
+        }
     }
 }
 
@@ -143,6 +302,33 @@ impl FromStr for ParseableLeader {
     }
 }
 
+#[derive(Clone, Debug)]
+struct ParseableCanId(u32);
+impl FromStr for ParseableCanId {
+    type Err = String;
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        u32::from_str_radix(string.trim_start_matches("0x"), 16)
+            .map(ParseableCanId)
+            .map_err(|cause| format!("Specify the CAN ID in hexadecimal notation ({cause})."))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseableHexData(Vec<u8>);
+impl FromStr for ParseableHexData {
+    type Err = String;
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        if string.len() % 2 != 0 {
+            return Err(String::from("Specify the data in hexadecimal notation with two digits per byte."));
+        }
+        (0..string.len()).step_by(2)
+            .map(|index| u8::from_str_radix(&string[index..index + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map(ParseableHexData)
+            .map_err(|cause| format!("Specify the data in hexadecimal notation ({cause})."))
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum DryRun { Yes, No }
 impl DryRun {
@@ -2,23 +2,72 @@ use std::net::IpAddr;
 use std::ops::Not;
 use std::time::Duration;
 
-use anyhow::bail;
+use anyhow::{bail, Context};
 use config::Config;
 use tracing::{debug, info, warn};
 
 use opendut_carl_api::carl::{broker, CaCertInfo, CarlClient};
 use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::ReconnectHint;
 use opendut_types::peer::PeerId;
+use opendut_types::util::net::Certificate;
 use opendut_util::project;
+use url::Url;
 
 pub async fn connect(settings: &Config) -> anyhow::Result<CarlClient> {
-    debug!("Connecting to CARL...");
-
     let host = settings.get_string("network.carl.host")?;
     let port = u16::try_from(settings.get_int("network.carl.port")?)?;
+
+    connect_to(settings, host, port).await
+}
+
+/// Connects without credentials, before the peer is set up, e.g. to claim a provisioning token.
+/// The CA certificate is passed along with the token, as it is not yet written to the configured path.
+pub async fn connect_unauthenticated(settings: &Config, carl_url: &Url, ca: &Certificate) -> anyhow::Result<CarlClient> {
+    let host = carl_url.host_str()
+        .with_context(|| format!("URL of CARL '{carl_url}' does not contain a host."))?;
+    let port = carl_url.port().unwrap_or(443);
+
+    let settings = Config::builder()
+        .add_source(Clone::clone(settings))
+        .set_override("network.oidc.enabled", false)?
+        .build()?;
+
+    let ca_cert_info = CaCertInfo::Content(ca.encode_as_string());
+
+    connect_with_ca(&settings, host.to_owned(), port, &ca_cert_info).await
+}
+
+/// Connects again after CARL asked to disconnect, following its hint when and where to reconnect.
+pub async fn reconnect(settings: &Config, reconnect_hint: ReconnectHint) -> anyhow::Result<CarlClient> {
+    tokio::time::sleep(Duration::from_millis(reconnect_hint.retry_after_ms)).await;
+
+    match reconnect_hint.alternate_endpoint {
+        Some(alternate_endpoint) => {
+            let Some((host, port)) = alternate_endpoint.rsplit_once(':') else {
+                bail!("Alternate endpoint '{alternate_endpoint}' of CARL is not formatted as 'host:port'.");
+            };
+            let port = port.parse::<u16>()
+                .with_context(|| format!("Invalid port in alternate endpoint '{alternate_endpoint}' of CARL."))?;
+
+            info!("Reconnecting to CARL at alternate endpoint '{alternate_endpoint}'.");
+            connect_to(settings, host.to_owned(), port).await
+        }
+        None => connect(settings).await,
+    }
+}
+
+async fn connect_to(settings: &Config, host: String, port: u16) -> anyhow::Result<CarlClient> {
     let ca_cert_path = CaCertInfo::Path(
         project::make_path_absolute(settings.get_string("network.tls.ca")?)?
     );
+
+    connect_with_ca(settings, host, port, &ca_cert_path).await
+}
+
+async fn connect_with_ca(settings: &Config, host: String, port: u16, ca_cert_info: &CaCertInfo) -> anyhow::Result<CarlClient> {
+    debug!("Connecting to CARL...");
+
     let domain_name_override = settings.get_string("network.tls.domain.name.override")?;
     let domain_name_override = domain_name_override.is_empty().not().then_some(domain_name_override);
 
@@ -26,7 +75,7 @@ pub async fn connect(settings: &Config) -> anyhow::Result<CarlClient> {
     let interval = Duration::from_millis(u64::try_from(settings.get_int("network.connect.interval.ms")?)?);
 
     for retries_left in (0..retries).rev() {
-        match CarlClient::create(&host, port, &ca_cert_path, &domain_name_override, settings).await {
+        match CarlClient::create(&host, port, ca_cert_info, &domain_name_override, settings).await {
             Ok(carl) => {
                 info!("Connected to CARL.");
                 return Ok(carl);
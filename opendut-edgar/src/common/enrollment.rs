@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use opendut_carl_api::carl::broker;
+
+use crate::fs;
+
+/// OAuth error codes, with which the identity provider rejects a client, which was deleted or disabled, or whose secret was rotated or expired.
+const REJECTED_CREDENTIALS_OAUTH_ERRORS: [&str; 3] = ["invalid_client", "unauthorized_client", "invalid_grant"];
+
+/// Whether this peer may connect to CARL with its credentials.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnrollmentState {
+    Enrolled,
+    /// CARL or the identity provider rejected the credentials of this peer. It needs to be re-enrolled via `opendut-edgar setup re-enroll`.
+    ReEnrollmentRequired { reason: String },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("The credentials of this peer were rejected, possibly because they were revoked or expired:\n  {reason}\n\nRe-enroll this peer with a new Setup-String via `opendut-edgar setup re-enroll <Setup-String>`. The local configuration and data are preserved.")]
+pub struct ReEnrollmentRequired {
+    pub reason: String,
+}
+
+impl ReEnrollmentRequired {
+    /// Failures to retrieve a token from the identity provider surface as an error while opening the stream, before CARL is even contacted.
+    pub fn from_open_stream_error(error: &broker::error::OpenStream) -> Option<Self> {
+        indicates_rejected_credentials(&error.message)
+            .then(|| Self { reason: error.message.clone() })
+    }
+}
+
+fn indicates_rejected_credentials(message: &str) -> bool {
+    message.contains("status: Unauthenticated")
+        || message.contains("status: PermissionDenied")
+        || REJECTED_CREDENTIALS_OAUTH_ERRORS.iter().any(|error| message.contains(error))
+}
+
+/// Persists the enrollment state, so that EDGAR does not retry connecting with rejected credentials after a restart.
+#[derive(Clone, Debug)]
+pub struct EnrollmentStateFile {
+    path: PathBuf,
+}
+
+impl EnrollmentStateFile {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let path = PathBuf::from(config.get_string("peer.enrollment.state.file")?);
+        Ok(Self { path })
+    }
+
+    pub fn read(&self) -> anyhow::Result<EnrollmentState> {
+        if self.path.exists() {
+            let reason = fs::read_to_string(&self.path)
+                .context("Failed to read enrollment state.")?;
+            Ok(EnrollmentState::ReEnrollmentRequired { reason: reason.trim().to_owned() })
+        } else {
+            Ok(EnrollmentState::Enrolled)
+        }
+    }
+
+    pub fn mark_re_enrollment_required(&self, re_enrollment_required: &ReEnrollmentRequired) -> anyhow::Result<()> {
+        if let Some(parent_dir) = self.path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        fs::write(&self.path, &re_enrollment_required.reason)
+            .context("Failed to persist that re-enrollment is required.")?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> anyhow::Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)
+                .context("Failed to clear enrollment state.")?;
+        }
+        Ok(())
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_detect_rejected_credentials_from_identity_provider_error() {
+        let error = broker::error::OpenStream { message: String::from("Error while opening stream: status: Unknown, message: \"Failed to get token: invalid_client\"") };
+        assert!(ReEnrollmentRequired::from_open_stream_error(&error).is_some());
+
+        let error = broker::error::OpenStream { message: String::from("Error while opening stream: status: Unavailable, message: \"error trying to connect\"") };
+        assert!(ReEnrollmentRequired::from_open_stream_error(&error).is_none());
+    }
+
+    #[test]
+    fn should_persist_and_clear_re_enrollment_required() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let state_file = EnrollmentStateFile { path: temp_dir.path().join("enrollment/re-enrollment-required") };
+        assert_that!(state_file.read()?, eq(&EnrollmentState::Enrolled));
+
+        let re_enrollment_required = ReEnrollmentRequired { reason: String::from("Client was disabled.") };
+        state_file.mark_re_enrollment_required(&re_enrollment_required)?;
+        assert_that!(state_file.read()?, eq(&EnrollmentState::ReEnrollmentRequired { reason: String::from("Client was disabled.") }));
+
+        state_file.clear()?;
+        assert_that!(state_file.read()?, eq(&EnrollmentState::Enrolled));
+        Ok(())
+    }
+}
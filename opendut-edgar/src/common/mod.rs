@@ -3,6 +3,8 @@ use std::str::FromStr;
 use opendut_types::util::net::NetworkInterfaceName;
 
 pub mod carl;
+pub mod enrollment;
+pub mod network_managers;
 pub mod settings;
 pub mod task;
 
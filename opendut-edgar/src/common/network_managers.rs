@@ -0,0 +1,317 @@
+//! Detection of third-party network managers (NetworkManager, systemd-networkd, netplan),
+//! which interfere with the interfaces managed by EDGAR, e.g. by removing the addresses of its bridges.
+
+use std::fmt;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::network_interface::gre::GRE_INTERFACE_NAME_PREFIX;
+
+pub const NETWORK_MANAGER_EXCLUSION_FILE: &str = "/etc/NetworkManager/conf.d/90-opendut-edgar.conf";
+pub const SYSTEMD_NETWORKD_EXCLUSION_FILE: &str = "/etc/systemd/network/00-opendut-edgar.network";
+const NETPLAN_DIRECTORY: &str = "/etc/netplan";
+const SYSTEMD_NETWORKD_LINKS_DIRECTORY: &str = "/run/systemd/netif/links";
+const SYS_CLASS_NET_DIRECTORY: &str = "/sys/class/net";
+
+/// Names of the interfaces managed by EDGAR. A trailing `*` matches any suffix.
+pub fn managed_interface_patterns(bridge_names: &[NetworkInterfaceName]) -> Vec<String> {
+    let mut patterns = bridge_names.iter()
+        .map(|bridge_name| bridge_name.name())
+        .collect::<Vec<_>>();
+    patterns.push(format!("{GRE_INTERFACE_NAME_PREFIX}*"));
+    patterns.dedup();
+    patterns
+}
+
+fn matches_pattern(interface: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => interface.starts_with(prefix),
+        None => interface == pattern,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NetworkManagerKind {
+    NetworkManager,
+    SystemdNetworkd,
+    Netplan,
+}
+impl fmt::Display for NetworkManagerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkManagerKind::NetworkManager => write!(f, "NetworkManager"),
+            NetworkManagerKind::SystemdNetworkd => write!(f, "systemd-networkd"),
+            NetworkManagerKind::Netplan => write!(f, "netplan"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Finding {
+    pub manager: NetworkManagerKind,
+    pub interface: String,
+    pub problem: String,
+    pub remediation: String,
+}
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on '{}': {} Remediation: {}", self.manager, self.interface, self.problem, self.remediation)
+    }
+}
+
+/// Checks the existing interfaces, which match the given patterns, for interference by third-party network managers.
+pub async fn detect(interface_patterns: &[String]) -> Vec<Finding> {
+    let interfaces = existing_interfaces(interface_patterns).await;
+    if interfaces.is_empty() {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    findings.append(&mut detect_network_manager(&interfaces).await);
+    findings.append(&mut detect_systemd_networkd(&interfaces).await);
+    findings.append(&mut detect_netplan(&interfaces).await);
+    findings
+}
+
+async fn existing_interfaces(interface_patterns: &[String]) -> Vec<String> {
+    let mut interfaces = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(SYS_CLASS_NET_DIRECTORY).await else {
+        return interfaces;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if interface_patterns.iter().any(|pattern| matches_pattern(&name, pattern)) {
+            interfaces.push(name);
+        }
+    }
+    interfaces.sort();
+    interfaces
+}
+
+async fn detect_network_manager(interfaces: &[String]) -> Vec<Finding> {
+    if which::which("nmcli").is_err() {
+        return Vec::new();
+    }
+    let output = tokio::process::Command::new("nmcli")
+        .args(["--terse", "--fields", "DEVICE,STATE", "device", "status"])
+        .output().await;
+
+    let output = match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).into_owned(),
+        _ => return Vec::new(), //NetworkManager is installed, but not running
+    };
+
+    parse_nmcli_device_states(&output).into_iter()
+        .filter(|(device, state)| interfaces.contains(device) && state != "unmanaged")
+        .map(|(interface, state)| Finding {
+            manager: NetworkManagerKind::NetworkManager,
+            interface,
+            problem: format!("The interface is in state '{state}', so NetworkManager may reconfigure it or remove its addresses."),
+            remediation: format!("Exclude the interface via '{NETWORK_MANAGER_EXCLUSION_FILE}', e.g. by running `edgar setup` with `--exclude-from-network-managers`."),
+        })
+        .collect()
+}
+
+/// Parses the output of `nmcli --terse --fields DEVICE,STATE device status` into pairs of device and state.
+fn parse_nmcli_device_states(output: &str) -> Vec<(String, String)> {
+    output.lines()
+        .filter_map(|line| {
+            let (device, state) = line.rsplit_once(':')?;
+            Some((device.replace("\\:", ":"), state.trim().to_owned()))
+        })
+        .collect()
+}
+
+async fn detect_systemd_networkd(interfaces: &[String]) -> Vec<Finding> {
+    let links_directory = Path::new(SYSTEMD_NETWORKD_LINKS_DIRECTORY);
+    if links_directory.is_dir().not() {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for interface in interfaces {
+        let Ok(ifindex) = tokio::fs::read_to_string(Path::new(SYS_CLASS_NET_DIRECTORY).join(interface).join("ifindex")).await else {
+            continue;
+        };
+        let Ok(link_state) = tokio::fs::read_to_string(links_directory.join(ifindex.trim())).await else {
+            continue;
+        };
+        if let Some(admin_state) = parse_networkd_admin_state(&link_state) {
+            if admin_state != "unmanaged" && admin_state != "linger" {
+                findings.push(Finding {
+                    manager: NetworkManagerKind::SystemdNetworkd,
+                    interface: interface.clone(),
+                    problem: format!("The interface is managed by systemd-networkd (administrative state '{admin_state}'), which may remove addresses it did not configure itself."),
+                    remediation: format!("Mark the interface as unmanaged via '{SYSTEMD_NETWORKD_EXCLUSION_FILE}', e.g. by running `edgar setup` with `--exclude-from-network-managers`."),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Parses the `ADMIN_STATE` from a link state file of systemd-networkd.
+fn parse_networkd_admin_state(link_state: &str) -> Option<String> {
+    link_state.lines()
+        .find_map(|line| line.strip_prefix("ADMIN_STATE="))
+        .map(|state| state.trim().to_owned())
+}
+
+async fn detect_netplan(interfaces: &[String]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(NETPLAN_DIRECTORY).await else {
+        return findings;
+    };
+    let mut files = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if matches!(path.extension().and_then(|extension| extension.to_str()), Some("yaml" | "yml")) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    for file in files {
+        let Ok(content) = tokio::fs::read_to_string(&file).await else {
+            continue;
+        };
+        for interface in interfaces {
+            if netplan_references(&content, interface) {
+                findings.push(Finding {
+                    manager: NetworkManagerKind::Netplan,
+                    interface: interface.clone(),
+                    problem: format!("The interface is configured in '{}', so its configuration is overwritten, whenever netplan is applied.", file.display()),
+                    remediation: format!("Remove the interface from '{}' and run `netplan apply`.", file.display()),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Whether the netplan configuration mentions the interface as a whole word.
+fn netplan_references(content: &str, interface: &str) -> bool {
+    content.split(|c: char| c.is_whitespace() || matches!(c, ':' | ',' | '[' | ']' | '"' | '\''))
+        .any(|word| word == interface)
+}
+
+/// Configuration files, which exclude the managed interfaces, for each network manager installed on this host.
+pub fn exclusion_files(interface_patterns: &[String]) -> Vec<(PathBuf, String)> {
+    let mut files = Vec::new();
+    if Path::new("/etc/NetworkManager").is_dir() {
+        files.push((PathBuf::from(NETWORK_MANAGER_EXCLUSION_FILE), network_manager_exclusion(interface_patterns)));
+    }
+    if Path::new("/etc/systemd/network").is_dir() {
+        files.push((PathBuf::from(SYSTEMD_NETWORKD_EXCLUSION_FILE), systemd_networkd_exclusion(interface_patterns)));
+    }
+    files
+}
+
+fn network_manager_exclusion(interface_patterns: &[String]) -> String {
+    let devices = interface_patterns.iter()
+        .map(|pattern| format!("interface-name:{pattern}"))
+        .collect::<Vec<_>>()
+        .join(";");
+
+    format!("# Generated by openDuT EDGAR. Prevents NetworkManager from managing the interfaces of EDGAR.\n[keyfile]\nunmanaged-devices={devices}\n")
+}
+
+fn systemd_networkd_exclusion(interface_patterns: &[String]) -> String {
+    let names = interface_patterns.join(" ");
+
+    format!("# Generated by openDuT EDGAR. Prevents systemd-networkd from managing the interfaces of EDGAR.\n[Match]\nName={names}\n\n[Link]\nUnmanaged=yes\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use super::*;
+
+    fn patterns() -> Vec<String> {
+        managed_interface_patterns(&[
+            NetworkInterfaceName::from_str("br-opendut").unwrap(),
+            NetworkInterfaceName::from_str("br-vcan-opendut").unwrap(),
+        ])
+    }
+
+    #[test]
+    fn should_match_interface_patterns() {
+        let patterns = patterns();
+        assert_eq!(patterns, vec!["br-opendut", "br-vcan-opendut", "gre-opendut*"]);
+
+        let matches = |interface: &str| patterns.iter().any(|pattern| matches_pattern(interface, pattern));
+        assert!(matches("br-opendut"));
+        assert!(matches("gre-opendut0"));
+        assert!(matches("br-opendut2").not());
+        assert!(matches("eth0").not());
+    }
+
+    #[test]
+    fn should_parse_nmcli_device_states() {
+        let output = indoc!("
+            eth0:connected
+            br-opendut:connected (externally)
+            gre-opendut0:unmanaged
+            lo:unmanaged
+        ");
+
+        let states = parse_nmcli_device_states(output);
+
+        assert_eq!(states, vec![
+            (String::from("eth0"), String::from("connected")),
+            (String::from("br-opendut"), String::from("connected (externally)")),
+            (String::from("gre-opendut0"), String::from("unmanaged")),
+            (String::from("lo"), String::from("unmanaged")),
+        ]);
+    }
+
+    #[test]
+    fn should_parse_networkd_admin_state() {
+        let link_state = indoc!("
+            # This is private data. Do not parse.
+            ADMIN_STATE=configured
+            OPER_STATE=routable
+        ");
+
+        assert_eq!(parse_networkd_admin_state(link_state), Some(String::from("configured")));
+        assert_eq!(parse_networkd_admin_state("OPER_STATE=routable\n"), None);
+    }
+
+    #[test]
+    fn should_detect_netplan_references() {
+        let content = indoc!("
+            network:
+              bridges:
+                br-opendut:
+                  interfaces: [eth1]
+        ");
+
+        assert!(netplan_references(content, "br-opendut"));
+        assert!(netplan_references(content, "br-vcan-opendut").not());
+    }
+
+    #[test]
+    fn should_generate_exclusion_configuration() {
+        let patterns = patterns();
+
+        assert_eq!(network_manager_exclusion(&patterns), indoc!("
+            # Generated by openDuT EDGAR. Prevents NetworkManager from managing the interfaces of EDGAR.
+            [keyfile]
+            unmanaged-devices=interface-name:br-opendut;interface-name:br-vcan-opendut;interface-name:gre-opendut*
+        ").trim_start());
+
+        assert_eq!(systemd_networkd_exclusion(&patterns), indoc!("
+            # Generated by openDuT EDGAR. Prevents systemd-networkd from managing the interfaces of EDGAR.
+            [Match]
+            Name=br-opendut br-vcan-opendut gre-opendut*
+
+            [Link]
+            Unmanaged=yes
+        ").trim_start());
+    }
+}
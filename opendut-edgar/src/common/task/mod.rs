@@ -1,3 +1,6 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 
 pub mod runner;
@@ -13,6 +16,57 @@ pub trait Task: Send + Sync {
 
     /// Make changes to the host system.
     async fn execute(&self) -> anyhow::Result<Success>;
+
+    /// The changes to the host system, which `execute()` would make.
+    /// Only used to inform the user during a dry-run, so tasks without changes to declare can rely on the default.
+    fn planned_changes(&self) -> anyhow::Result<Vec<SystemChange>> {
+        Ok(Vec::new())
+    }
+
+    /// Whether `undo()` reverts the changes of this task, when uninstalling EDGAR.
+    /// Tasks without changes to revert, like checks, or whose changes are kept, like the service user, rely on the default.
+    fn undoable(&self) -> bool {
+        false
+    }
+
+    /// Revert the changes to the host system made by `execute()`.
+    /// Must succeed for parts, which were never set up, as it is called regardless of the previous setup.
+    async fn undo(&self) -> anyhow::Result<Success> {
+        Ok(Success::default())
+    }
+
+    /// Which tasks have to be completed, before this task is started. Tasks, whose dependencies are completed, run concurrently.
+    /// The default runs the task after all tasks listed before it, and all tasks listed after it wait for it,
+    /// so that tasks only run concurrently, if they declare their dependencies.
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::Sequential
+    }
+
+    /// Identifies the task in the dependencies of other tasks.
+    fn id(&self) -> TaskId {
+        TaskId(std::any::type_name::<Self>())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId(&'static str);
+impl TaskId {
+    pub fn of<T: Task>() -> Self {
+        Self(std::any::type_name::<T>())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Dependencies {
+    /// Started after all tasks listed before it were completed. The tasks listed after it are started after it was completed.
+    Sequential,
+    /// Started after the given tasks, as far as they are listed before this task, and the last sequential task listed before it were completed.
+    After(Vec<TaskId>),
+}
+impl Dependencies {
+    pub fn none() -> Self {
+        Dependencies::After(Vec::new())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -34,3 +88,37 @@ impl Success {
         Self { message: Some(message.into()) }
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemChange {
+    WriteFile(PathBuf),
+    RemoveFile(PathBuf),
+    Symlink { link: PathBuf, target: PathBuf },
+    ChangeOwnership { path: PathBuf, user: String },
+    CreateUser(String),
+    LoadKernelModule(String),
+    GrantCapability { executable: PathBuf, capability: String },
+    InstallService(String),
+    StopService(String),
+    RestartService(String),
+    NetworkInterface(String),
+    RunCommand(String),
+}
+impl Display for SystemChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SystemChange::WriteFile(path) => write!(f, "Write '{}'", path.display()),
+            SystemChange::RemoveFile(path) => write!(f, "Remove '{}'", path.display()),
+            SystemChange::Symlink { link, target } => write!(f, "Link '{}' to '{}'", link.display(), target.display()),
+            SystemChange::ChangeOwnership { path, user } => write!(f, "Change owner of '{}' and its contents to user '{user}'", path.display()),
+            SystemChange::CreateUser(name) => write!(f, "Create user '{name}'"),
+            SystemChange::LoadKernelModule(name) => write!(f, "Load kernel module '{name}'"),
+            SystemChange::GrantCapability { executable, capability } => write!(f, "Grant capability {capability} to '{}'", executable.display()),
+            SystemChange::InstallService(name) => write!(f, "Install service '{name}'"),
+            SystemChange::StopService(name) => write!(f, "Stop service '{name}'"),
+            SystemChange::RestartService(name) => write!(f, "(Re-)start service '{name}'"),
+            SystemChange::NetworkInterface(change) => write!(f, "Network interfaces: {change}"),
+            SystemChange::RunCommand(command) => write!(f, "Run `{command}`"),
+        }
+    }
+}
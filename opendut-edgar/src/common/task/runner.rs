@@ -1,11 +1,20 @@
+use std::ops::Not;
+use std::sync::Arc;
 use std::time::Duration;
 
-use indicatif::{ProgressBar, ProgressStyle};
+use anyhow::bail;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tokio::task::JoinSet;
 use tracing::{debug, error, info};
 
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 
-pub async fn run(run_mode: RunMode, tasks: &[Box<dyn Task>]) -> anyhow::Result<()> {
+/// Maximum number of tasks, which run at the same time.
+const MAX_CONCURRENT_TASKS: usize = 4;
+
+/// Runs the tasks concurrently, as far as their dependencies allow, and prints their outcomes in the order of the tasks.
+/// No further tasks are started after a task failed, except during a dry-run.
+pub async fn run(run_mode: RunMode, tasks: Vec<Box<dyn Task>>) -> anyhow::Result<()> {
     if tasks.is_empty() {
         debug!("No tasks to run. Skipping.");
         return Ok(())
@@ -21,69 +30,185 @@ pub async fn run(run_mode: RunMode, tasks: &[Box<dyn Task>]) -> anyhow::Result<(
     Ok(())
 }
 
+/// Reverts the changes of the tasks in reverse order, e.g. to uninstall EDGAR.
+/// Continues with the remaining tasks, if one of them fails, so that as much as possible is removed.
+pub async fn undo(run_mode: RunMode, tasks: &[Box<dyn Task>]) -> anyhow::Result<()> {
+    let tasks = tasks.iter()
+        .rev()
+        .filter(|task| task.undoable())
+        .collect::<Vec<_>>();
+
+    println!();
+    let mut failed_tasks = 0;
+    for task in &tasks {
+        let outcome = if run_mode == RunMode::SetupDryRun {
+            Outcome::DryRun(Vec::new())
+        } else {
+            match task.undo().await {
+                Ok(success) => Outcome::Changed(success),
+                Err(cause) => {
+                    failed_tasks += 1;
+                    print_outcome(format!("Undo {}", task.description()), Outcome::Failed);
+                    print_error("Error while undoing:", Some(cause));
+                    continue;
+                }
+            }
+        };
+        print_outcome(format!("Undo {}", task.description()), outcome);
+    }
+    println!();
+
+    if failed_tasks > 0 {
+        bail!("{failed_tasks} of {} task(s) could not be undone.", tasks.len());
+    }
+    Ok(())
+}
+
 async fn run_tasks(
-    tasks: &[Box<dyn Task>],
+    tasks: Vec<Box<dyn Task>>,
     run_mode: RunMode,
 ) {
     println!();
 
+    let dependencies = resolve_dependencies(&tasks);
+    let tasks = tasks.into_iter()
+        .map(Arc::<dyn Task>::from)
+        .collect::<Vec<_>>();
+
+    let progress = MultiProgress::new();
     let progress_style = ProgressStyle::with_template(" {spinner:.dim}  {msg}").unwrap()
         .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", ""]);
-    for task in tasks {
-        let spinner = ProgressBar::new_spinner();
-        spinner.enable_steady_tick(Duration::from_millis(120));
-        spinner.set_style(progress_style.clone());
-        spinner.set_message(task.description());
-
-        let is_fulfilled = match task.check_fulfilled().await {
-            Ok(is_fulfilled) => is_fulfilled,
-            Err(cause) => {
-                print_outcome(task.description(), Outcome::Failed);
-                print_error("Error while determining system state:", Some(cause));
-                return;
-            }
-        };
 
-        let outcome = match is_fulfilled {
-            TaskFulfilled::Yes => Outcome::Unchanged,
-            TaskFulfilled::No | TaskFulfilled::Unchecked => {
-                if run_mode == RunMode::SetupDryRun {
-                    Outcome::DryRun
-                } else {
-                    let result = task.execute().await;
-                    spinner.finish_and_clear();
-                    match result {
-                        Ok(success) => Outcome::Changed(success),
-                        Err(cause) => {
-                            print_outcome(task.description(), Outcome::Failed);
-                            print_error("Error while executing:", Some(cause));
-                            return;
-                        }
-                    }
-                }
-            }
-        };
-        spinner.finish_and_clear();
-
-        if let Outcome::Changed(_) = outcome {
-            match task.check_fulfilled().await {
-                Ok(fulfillment) => match fulfillment {
-                    TaskFulfilled::Yes | TaskFulfilled::Unchecked => {}, //do nothing
-                    TaskFulfilled::No => {
-                        print_outcome(task.description(), Outcome::Failed);
-                        print_error("Execution succeeded, but system state check indicated task still needing execution.", None);
-                        return;
-                    }
+    let mut started = vec![false; tasks.len()];
+    let mut completed = vec![false; tasks.len()];
+    let mut reports = tasks.iter().map(|_| None).collect::<Vec<Option<Report>>>();
+    let mut printed = 0;
+    let mut failed = false;
+    let mut tasks_needing_change = 0;
+    let mut running = JoinSet::new();
+
+    loop {
+        let may_start = run_mode == RunMode::SetupDryRun || failed.not(); //evaluate the remaining tasks during a dry-run, as they might not depend on the failed one
+        if may_start {
+            for index in 0..tasks.len() {
+                if running.len() >= MAX_CONCURRENT_TASKS {
+                    break;
                 }
-                Err(cause) => {
-                    print_outcome(task.description(), Outcome::Failed);
-                    print_error("Error while determining system state after execution:", Some(cause));
-                    return;
+                let ready = started[index].not() && dependencies[index].iter().all(|dependency| completed[*dependency]);
+                if ready {
+                    started[index] = true;
+
+                    let task = Arc::clone(&tasks[index]);
+                    let spinner = progress.add(ProgressBar::new_spinner());
+                    spinner.enable_steady_tick(Duration::from_millis(120));
+                    spinner.set_style(progress_style.clone());
+                    spinner.set_message(task.description());
+
+                    running.spawn(async move {
+                        let report = run_task(task.as_ref(), run_mode).await;
+                        spinner.finish_and_clear();
+                        (index, report)
+                    });
                 }
             }
-        };
+        }
+
+        let Some(joined) = running.join_next().await else { break };
+        let (index, report) = joined.unwrap_or_else(|cause| std::panic::resume_unwind(cause.into_panic()));
+
+        match report.outcome {
+            Outcome::Failed => failed = true,
+            Outcome::DryRun(_) => tasks_needing_change += 1,
+            Outcome::Changed(_) | Outcome::Unchanged => {}
+        }
+        completed[index] = true;
+        reports[index] = Some(report);
+
+        //print in the order of the tasks, so that the output does not depend on which task completed first
+        while let Some(report) = reports.get_mut(printed).and_then(Option::take) {
+            progress.suspend(|| print_report(report));
+            printed += 1;
+        }
+    }
+    for report in reports.into_iter().flatten() { //completed after a task, which was not started due to a failure
+        print_report(report);
+    }
+
+    if run_mode == RunMode::SetupDryRun {
+        println!();
+        println!("{tasks_needing_change} of {} task(s) would change the system. No changes were made.", tasks.len());
+        info!("Dry-run completed: {tasks_needing_change} of {} task(s) would change the system.", tasks.len());
+    }
+}
+
+/// Determines the indices of the tasks, which each task waits for.
+/// Only tasks listed before a task are considered, so the dependencies cannot form a cycle.
+fn resolve_dependencies(tasks: &[Box<dyn Task>]) -> Vec<Vec<usize>> {
+    let mut last_sequential = None;
+
+    tasks.iter().enumerate()
+        .map(|(index, task)| match task.dependencies() {
+            Dependencies::Sequential => {
+                last_sequential = Some(index);
+                (0..index).collect()
+            }
+            Dependencies::After(ids) => {
+                (0..index)
+                    .filter(|previous| Some(*previous) == last_sequential || ids.contains(&tasks[*previous].id()))
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+struct Report {
+    description: String,
+    outcome: Outcome,
+    error: Option<(&'static str, Option<anyhow::Error>)>,
+}
+
+async fn run_task(task: &dyn Task, run_mode: RunMode) -> Report {
+    let description = task.description();
+    let failed = |context, cause| Report { description: task.description(), outcome: Outcome::Failed, error: Some((context, cause)) };
+
+    let is_fulfilled = match task.check_fulfilled().await {
+        Ok(is_fulfilled) => is_fulfilled,
+        Err(cause) => return failed("Error while determining system state:", Some(cause)),
+    };
 
-        print_outcome(task.description(), outcome)
+    let outcome = match is_fulfilled {
+        TaskFulfilled::Yes => Outcome::Unchanged,
+        TaskFulfilled::No | TaskFulfilled::Unchecked => {
+            if run_mode == RunMode::SetupDryRun {
+                return match task.planned_changes() {
+                    Ok(planned_changes) => Report { description, outcome: Outcome::DryRun(planned_changes), error: None },
+                    Err(cause) => Report { description, outcome: Outcome::DryRun(Vec::new()), error: Some(("Error while determining planned changes:", Some(cause))) },
+                };
+            }
+            match task.execute().await {
+                Ok(success) => Outcome::Changed(success),
+                Err(cause) => return failed("Error while executing:", Some(cause)),
+            }
+        }
+    };
+
+    if let Outcome::Changed(_) = outcome {
+        match task.check_fulfilled().await {
+            Ok(fulfillment) => match fulfillment {
+                TaskFulfilled::Yes | TaskFulfilled::Unchecked => {}, //do nothing
+                TaskFulfilled::No => return failed("Execution succeeded, but system state check indicated task still needing execution.", None),
+            }
+            Err(cause) => return failed("Error while determining system state after execution:", Some(cause)),
+        }
+    }
+
+    Report { description, outcome, error: None }
+}
+
+fn print_report(report: Report) {
+    print_outcome(report.description, report.outcome);
+    if let Some((context, cause)) = report.error {
+        print_error(context, cause);
     }
 }
 
@@ -113,7 +238,7 @@ pub enum RunMode { Setup, SetupDryRun, Service }
 
 enum Outcome {
     Changed(Success),
-    DryRun,
+    DryRun(Vec<SystemChange>),
     Unchanged,
     Failed,
 }
@@ -132,8 +257,12 @@ fn print_outcome(task_name: String, outcome: Outcome) {
                 }
                 message
             }
-            Outcome::DryRun => {
-                format!("{tick}{task_name} (Needs Change)")
+            Outcome::DryRun(planned_changes) => {
+                let mut message = format!("{tick}{task_name} (Needs Change)");
+                for planned_change in planned_changes {
+                    message.push_str(&format!("\n       - {planned_change}"));
+                }
+                message
             }
             Outcome::Unchanged => {
                 let mut message = format!("{task_name} (Unchanged)");
@@ -151,3 +280,93 @@ fn print_outcome(task_name: String, outcome: Outcome) {
     println!("{}", message(&task_name, &outcome, console::user_attended()));
     info!("{}", message(&task_name, &outcome, false));
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use googletest::prelude::*;
+
+    use crate::common::task::TaskId;
+
+    use super::*;
+
+    type Events = Arc<Mutex<Vec<String>>>;
+
+    struct Recording<const N: usize> {
+        duration: Duration,
+        dependencies: Dependencies,
+        events: Events,
+    }
+
+    #[async_trait]
+    impl<const N: usize> Task for Recording<N> {
+        fn description(&self) -> String {
+            format!("Task {N}")
+        }
+        fn dependencies(&self) -> Dependencies {
+            Clone::clone(&self.dependencies)
+        }
+        async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+            Ok(TaskFulfilled::Unchecked)
+        }
+        async fn execute(&self) -> anyhow::Result<Success> {
+            self.events.lock().unwrap().push(format!("start {N}"));
+            tokio::time::sleep(self.duration).await;
+            self.events.lock().unwrap().push(format!("finish {N}"));
+            Ok(Success::default())
+        }
+    }
+
+    fn task<const N: usize>(duration_millis: u64, dependencies: Dependencies, events: &Events) -> Box<dyn Task> {
+        Box::new(Recording::<N> { duration: Duration::from_millis(duration_millis), dependencies, events: Arc::clone(events) })
+    }
+
+    #[tokio::test]
+    async fn should_run_independent_tasks_concurrently_and_dependent_tasks_after_their_dependencies() -> anyhow::Result<()> {
+        let events = Events::default();
+
+        let tasks = vec![
+            task::<1>(200, Dependencies::none(), &events),
+            task::<2>(10, Dependencies::none(), &events),
+            task::<3>(10, Dependencies::After(vec![TaskId::of::<Recording<1>>()]), &events),
+            task::<4>(10, Dependencies::Sequential, &events),
+        ];
+
+        run(RunMode::Setup, tasks).await?;
+
+        assert_that!(*events.lock().unwrap(), elements_are![
+            eq("start 1"),
+            eq("start 2"),
+            eq("finish 2"),
+            eq("finish 1"),
+            eq("start 3"),
+            eq("finish 3"),
+            eq("start 4"),
+            eq("finish 4"),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_wait_for_the_last_sequential_task_and_the_declared_dependencies() {
+        let events = Events::default();
+
+        let tasks = vec![
+            task::<1>(0, Dependencies::Sequential, &events),
+            task::<2>(0, Dependencies::none(), &events),
+            task::<3>(0, Dependencies::After(vec![TaskId::of::<Recording<2>>()]), &events),
+            task::<4>(0, Dependencies::Sequential, &events),
+            task::<5>(0, Dependencies::After(vec![TaskId::of::<Recording<2>>()]), &events),
+        ];
+
+        assert_that!(resolve_dependencies(&tasks), elements_are![
+            empty(),
+            elements_are![eq(0)],
+            elements_are![eq(0), eq(1)],
+            elements_are![eq(0), eq(1), eq(2)],
+            elements_are![eq(1), eq(3)],
+        ]);
+    }
+}
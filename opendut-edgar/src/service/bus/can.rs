@@ -0,0 +1,153 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::process::Command;
+use tracing::debug;
+
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::bus::{run_for_duration, Bus, BusFrame, BusKind, ClusterContext, Error};
+use crate::service::can_manager::CanManagerRef;
+use crate::service::cluster_assignment;
+
+pub struct CanBus {
+    can_manager: CanManagerRef,
+}
+
+impl CanBus {
+    pub fn new(can_manager: CanManagerRef) -> Self {
+        Self { can_manager }
+    }
+}
+
+#[async_trait]
+impl Bus for CanBus {
+    fn kind(&self) -> BusKind {
+        BusKind::Can
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn setup(&self, cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        let ClusterContext { cluster_assignment, self_id, .. } = cluster;
+
+        let own_can_interfaces = cluster_assignment::get_own_can_interfaces(cluster_assignment, *self_id)?;
+
+        if let sudo::RunningAs::User = sudo::check() {
+            if own_can_interfaces.is_empty() {
+                //Since we don't have the correct permissions to run the CAN setup code,
+                //no previous CAN interfaces exist which we might need to clean up,
+                //so we can safely skip this code, which allows us to run without root,
+                //when CAN is not used.
+                debug!("No CAN interfaces to set up. Skipping.");
+                return Ok(());
+            } else {
+                panic!("CARL requested to setup CAN interfaces, but EDGAR is not running with root permissions, which is currently required."); //TODO report problem to CARL
+            }
+        }
+
+        debug!("Setting up CAN interfaces.");
+
+        let can_bridge_name = crate::common::default_can_bridge_name();
+        self.can_manager.setup_local_routing(
+            &can_bridge_name,
+            own_can_interfaces,
+        ).await
+        .map_err(cluster_assignment::Error::LocalCanRoutingSetupFailed)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn join_cluster(&self, cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        let ClusterContext { cluster_assignment, self_id, .. } = cluster;
+
+        if let sudo::RunningAs::User = sudo::check() {
+            debug!("Not running with root permissions, so no CAN interfaces were set up. Not connecting CAN to remote peers.");
+            return Ok(());
+        }
+
+        let local_peer_assignment = cluster_assignment::determine_local_assignment(cluster_assignment, *self_id)?;
+
+        let is_leader = cluster_assignment.leader == *self_id;
+
+        let server_port = local_peer_assignment.can_server_port;
+
+        let can_bridge_name = crate::common::default_can_bridge_name();
+
+        if is_leader {
+
+            let remote_assignments = cluster_assignment::determine_remote_assignments(cluster_assignment, *self_id)?;
+            self.can_manager.setup_remote_routing_server(
+                &can_bridge_name,
+                &remote_assignments
+            ).await
+            .map_err(cluster_assignment::Error::RemoteCanRoutingSetupFailed)?;
+
+        } else {
+
+            let leader_assignment = cluster_assignment::determine_leader_assignment(cluster_assignment)?;
+            self.can_manager.setup_remote_routing_client(
+                &can_bridge_name,
+                &leader_assignment.vpn_address,
+                &server_port
+            ).await
+            .map_err(cluster_assignment::Error::RemoteCanRoutingSetupFailed)?;
+        }
+
+        Ok(())
+    }
+
+    async fn verify_cluster(&self, _cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        //Cannelloni reconnects on its own, once the remote peers are reachable, which is verified independent of the bus.
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn teardown(&self) -> Result<(), Error> {
+        if let sudo::RunningAs::User = sudo::check() {
+            debug!("Not running with root permissions, so no CAN routing was set up. Skipping teardown.");
+            return Ok(());
+        }
+
+        debug!("Removing CAN routing.");
+
+        self.can_manager.teardown_routing().await
+            .map_err(Error::CanRoutingTeardownFailed)
+    }
+
+    async fn capture(&self, interface: &NetworkInterfaceName, output: &Path, duration: Duration) -> Result<(), Error> {
+        let output_file = std::fs::File::create(output)
+            .map_err(|cause| Error::CaptureFileCreation { path: output.to_owned(), cause })?;
+
+        let mut command = Command::new("candump");
+        command
+            .arg("-L")
+            .arg(interface.name())
+            .stdout(Stdio::from(output_file));
+
+        run_for_duration(command, duration).await
+    }
+
+    async fn inject(&self, interface: &NetworkInterfaceName, frame: &BusFrame) -> Result<(), Error> {
+        let BusFrame::Can { id, data } = frame;
+
+        let data = data.iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<String>();
+
+        let output = Command::new("cansend")
+            .arg(interface.name())
+            .arg(format!("{id:03X}#{data}"))
+            .output()
+            .await
+            .map_err(|cause| Error::CommandLineProgramExecution { command: String::from("cansend"), cause })?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(Error::CommandLineProgramFailed { command: String::from("cansend"), cause: String::from_utf8_lossy(&output.stderr).trim().to_owned() })
+        }
+    }
+}
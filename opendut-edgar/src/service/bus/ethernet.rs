@@ -0,0 +1,113 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::process::Command;
+use tracing::debug;
+
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::bus::{run_for_duration, Bus, BusFrame, BusKind, ClusterContext, Error};
+use crate::service::cluster_assignment;
+use crate::service::network_interface::gre;
+use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+
+pub struct EthernetBus {
+    network_interface_manager: NetworkInterfaceManagerRef,
+}
+
+impl EthernetBus {
+    pub fn new(network_interface_manager: NetworkInterfaceManagerRef) -> Self {
+        Self { network_interface_manager }
+    }
+}
+
+#[async_trait]
+impl Bus for EthernetBus {
+    fn kind(&self) -> BusKind {
+        BusKind::Ethernet
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn setup(&self, cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        let ClusterContext { cluster_assignment, self_id, bridge_name } = cluster;
+
+        debug!("Joining Ethernet interfaces to bridge '{bridge_name}'.");
+
+        let own_ethernet_interfaces = cluster_assignment::get_own_ethernet_interfaces(cluster_assignment, *self_id)?;
+
+        cluster_assignment::join_device_interfaces_to_bridge(&own_ethernet_interfaces, bridge_name, Arc::clone(&self.network_interface_manager)).await
+            .map_err(cluster_assignment::Error::JoinDeviceInterfaceToBridgeFailed)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn join_cluster(&self, cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        let ClusterContext { cluster_assignment, self_id, bridge_name } = cluster;
+
+        debug!("Setting up Ethernet GRE interfaces.");
+
+        let local_peer_assignment = cluster_assignment::determine_local_assignment(cluster_assignment, *self_id)?;
+
+        let local_ip = cluster_assignment::require_ipv4_for_gre(local_peer_assignment.vpn_address)?;
+
+        let remote_ips = cluster_assignment::determine_remote_ips(cluster_assignment, *self_id)?;
+        let remote_ips = remote_ips.into_iter()
+            .map(cluster_assignment::require_ipv4_for_gre)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        gre::setup_interfaces(
+            &local_ip,
+            &remote_ips,
+            bridge_name,
+            Arc::clone(&self.network_interface_manager),
+        ).await
+        .map_err(cluster_assignment::Error::GreInterfaceSetupFailed)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn verify_cluster(&self, cluster: &ClusterContext<'_>) -> Result<(), Error> {
+        let ClusterContext { cluster_assignment, self_id, bridge_name } = cluster;
+
+        let bridge = self.network_interface_manager.find_interface(bridge_name).await
+            .map_err(|cause| Error::NetworkInterfaceVerificationFailed(gre::Error::from(cause)))?;
+        if bridge.is_none() {
+            return Err(Error::NetworkInterfaceMissing { name: Clone::clone(*bridge_name) });
+        }
+
+        let expected = cluster_assignment::determine_remote_ips(cluster_assignment, *self_id)?.len();
+        let found = gre::list_existing_interfaces(Arc::clone(&self.network_interface_manager)).await
+            .map_err(Error::NetworkInterfaceVerificationFailed)?
+            .len();
+        if found < expected {
+            return Err(Error::GreInterfacesMissing { expected, found });
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn teardown(&self) -> Result<(), Error> {
+        debug!("Removing Ethernet GRE interfaces.");
+
+        gre::remove_existing_interfaces(Arc::clone(&self.network_interface_manager)).await
+            .map_err(Error::GreInterfaceTeardownFailed)
+    }
+
+    async fn capture(&self, interface: &NetworkInterfaceName, output: &Path, duration: Duration) -> Result<(), Error> {
+        let mut command = Command::new("tcpdump");
+        command
+            .arg("-i").arg(interface.name())
+            .arg("-w").arg(output);
+
+        run_for_duration(command, duration).await
+    }
+
+    async fn inject(&self, _interface: &NetworkInterfaceName, _frame: &BusFrame) -> Result<(), Error> {
+        Err(Error::Unsupported { bus: self.kind(), operation: "inject" })
+    }
+}
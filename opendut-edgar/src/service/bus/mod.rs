@@ -0,0 +1,144 @@
+use std::fmt;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use opendut_types::cluster::ClusterAssignment;
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::{can_manager, cluster_assignment};
+use crate::service::can_manager::{CanManager, CanMirrorOptions};
+use crate::service::network_interface::gre;
+use crate::service::network_interface::manager::NetworkInterfaceManager;
+
+pub mod can;
+pub mod ethernet;
+
+pub type BusRef = Arc<dyn Bus>;
+
+/// A type of bus, which EDGAR can connect to the other peers of a cluster.
+///
+/// Implementations are responsible for everything specific to their bus type,
+/// so that the handling of ClusterAssignments does not need to change when further bus types are added.
+#[async_trait]
+pub trait Bus: Send + Sync {
+    fn kind(&self) -> BusKind;
+
+    /// Prepares the local interfaces of this bus type, e.g. by creating bridges and routes.
+    async fn setup(&self, cluster: &ClusterContext<'_>) -> Result<(), Error>;
+
+    /// Connects the local bus to the buses of the remote peers in the cluster.
+    async fn join_cluster(&self, cluster: &ClusterContext<'_>) -> Result<(), Error>;
+
+    /// Checks whether the connections to the remote peers, which were created when joining the cluster, are in place.
+    async fn verify_cluster(&self, cluster: &ClusterContext<'_>) -> Result<(), Error>;
+
+    /// Removes the connections to remote peers, which were created when joining a cluster.
+    async fn teardown(&self) -> Result<(), Error>;
+
+    /// Records the traffic on the given interface into the output file for the given duration.
+    async fn capture(&self, interface: &NetworkInterfaceName, output: &Path, duration: Duration) -> Result<(), Error>;
+
+    /// Sends a single frame onto the given interface.
+    async fn inject(&self, interface: &NetworkInterfaceName, frame: &BusFrame) -> Result<(), Error>;
+}
+
+pub struct ClusterContext<'a> {
+    pub cluster_assignment: &'a ClusterAssignment,
+    pub self_id: PeerId,
+    pub bridge_name: &'a NetworkInterfaceName,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum BusKind {
+    Can,
+    Ethernet,
+}
+impl fmt::Display for BusKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusKind::Can => write!(f, "CAN"),
+            BusKind::Ethernet => write!(f, "Ethernet"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum BusFrame {
+    Can { id: u32, data: Vec<u8> },
+}
+
+/// Creates a bus of the given kind for use outside the service, e.g. for diagnostics via the CLI.
+pub fn create(kind: BusKind) -> anyhow::Result<BusRef> {
+    let network_interface_manager = NetworkInterfaceManager::create()?;
+
+    let bus: BusRef = match kind {
+        BusKind::Can => Arc::new(can::CanBus::new(CanManager::create(network_interface_manager, CanMirrorOptions::default()))),
+        BusKind::Ethernet => Arc::new(ethernet::EthernetBus::new(network_interface_manager)),
+    };
+    Ok(bus)
+}
+
+/// Name of the interface, which EDGAR connects to the cluster for the given kind of bus.
+pub fn default_interface(kind: BusKind) -> NetworkInterfaceName {
+    match kind {
+        BusKind::Can => crate::common::default_can_bridge_name(),
+        BusKind::Ethernet => crate::common::default_bridge_name(),
+    }
+}
+
+async fn run_for_duration(mut command: Command, duration: Duration) -> Result<(), Error> {
+    let program = command.as_std().get_program().to_string_lossy().to_string();
+
+    let mut child = command
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|cause| Error::CommandLineProgramExecution { command: Clone::clone(&program), cause })?;
+
+    tokio::time::sleep(duration).await;
+
+    if let Some(status) = child.try_wait().map_err(|cause| Error::CommandLineProgramExecution { command: Clone::clone(&program), cause })? {
+        if status.success().not() {
+            let output = child.wait_with_output().await
+                .map_err(|cause| Error::CommandLineProgramExecution { command: Clone::clone(&program), cause })?;
+            return Err(Error::CommandLineProgramFailed { command: program, cause: String::from_utf8_lossy(&output.stderr).trim().to_owned() });
+        }
+        return Ok(());
+    }
+
+    child.kill().await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: program, cause })?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    ClusterAssignment(#[from] cluster_assignment::Error),
+    #[error("Removing GRE interfaces failed: {0}")]
+    GreInterfaceTeardownFailed(gre::Error),
+    #[error("CAN routing teardown failed: {0}")]
+    CanRoutingTeardownFailed(can_manager::Error),
+    #[error("Network interface '{name}' does not exist.")]
+    NetworkInterfaceMissing { name: NetworkInterfaceName },
+    #[error("Expected {expected} GRE interfaces, but found {found}.")]
+    GreInterfacesMissing { expected: usize, found: usize },
+    #[error("Verifying network interfaces failed: {0}")]
+    NetworkInterfaceVerificationFailed(gre::Error),
+    #[error("Failure while invoking command line program '{command}': {cause}")]
+    CommandLineProgramExecution { command: String, cause: std::io::Error },
+    #[error("Command line program '{command}' failed: {cause}")]
+    CommandLineProgramFailed { command: String, cause: String },
+    #[error("Could not create capture file '{path}': {cause}", path=path.display())]
+    CaptureFileCreation { path: PathBuf, cause: std::io::Error },
+    #[error("Operation '{operation}' is not supported for {bus} buses.")]
+    Unsupported { bus: BusKind, operation: &'static str },
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -28,13 +29,52 @@ pub struct CanManager{
      */
     cannelloni_termination_token: Mutex<Arc<AtomicBool>>,
     network_interface_manager: NetworkInterfaceManagerRef,
+    mirror_options: CanMirrorOptions,
+}
+
+/// Physical CAN interfaces, whose traffic is mirrored onto a vcan interface, so that analysis tools can observe it
+/// without contending for the socket of the physical interface or requiring elevated privileges.
+#[derive(Clone, Debug, Default)]
+pub struct CanMirrorOptions {
+    mirrors: HashMap<NetworkInterfaceName, NetworkInterfaceName>,
+}
+
+impl CanMirrorOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let mirrors = config.get::<Vec<String>>("can.mirror.interfaces")?
+            .iter()
+            .map(|mirror| parse_mirror(mirror))
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+
+        Ok(Self { mirrors })
+    }
+
+    fn mirror_of(&self, interface: &NetworkInterfaceName) -> Option<&NetworkInterfaceName> {
+        self.mirrors.get(interface)
+    }
+}
+
+/// Parses a mirror in the form `<physical interface>:<vcan interface>`, e.g. `can0:can0-mirror`.
+fn parse_mirror(mirror: &str) -> anyhow::Result<(NetworkInterfaceName, NetworkInterfaceName)> {
+    let (interface, mirror_interface) = mirror.split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("CAN mirror '{mirror}' is not in the form '<interface>:<mirror interface>'."))?;
+
+    let interface = NetworkInterfaceName::try_from(interface.trim())?;
+    let mirror_interface = NetworkInterfaceName::try_from(mirror_interface.trim())?;
+
+    if interface == mirror_interface || mirror_interface == crate::common::default_can_bridge_name() {
+        anyhow::bail!("CAN mirror '{mirror}' must not mirror onto the interface itself or the CAN bridge.");
+    }
+
+    Ok((interface, mirror_interface))
 }
 
 impl CanManager {
-    pub fn create(network_interface_manager: NetworkInterfaceManagerRef) -> CanManagerRef {
+    pub fn create(network_interface_manager: NetworkInterfaceManagerRef, mirror_options: CanMirrorOptions) -> CanManagerRef {
         Arc::new(Self {
             cannelloni_termination_token: Mutex::new(Arc::new(AtomicBool::new(false))),
-            network_interface_manager
+            network_interface_manager,
+            mirror_options,
         })
     }
 
@@ -128,10 +168,35 @@ impl CanManager {
             self.create_can_route(bridge_name, &interface.name, false, 2).await?;
             self.create_can_route(&interface.name, bridge_name, true, 2).await?;
             self.create_can_route(&interface.name, bridge_name, false, 2).await?;
+
+            if let Some(mirror_name) = self.mirror_options.mirror_of(&interface.name) {
+                self.create_can_mirror(&interface.name, mirror_name).await?;
+            }
         }
     
         Ok(())
     }
+
+    /// Routes the traffic of the interface onto the mirror, but not back, so that tools writing to the mirror do not affect the bus.
+    /// With the echo flag of the routes, the frames sent onto the interface from the cluster are mirrored as well.
+    async fn create_can_mirror(&self, interface_name: &NetworkInterfaceName, mirror_name: &NetworkInterfaceName) -> Result<(), Error> {
+
+        if self.network_interface_manager.find_interface(mirror_name).await
+            .map_err(|cause| Error::Other { message: format!("Error while looking up CAN mirror '{mirror_name}': {cause}") })?
+            .is_none() {
+            debug!("Creating CAN mirror '{mirror_name}' of '{interface_name}'.");
+            let mirror = self.network_interface_manager.create_vcan_interface(mirror_name).await
+                .map_err(|cause| Error::Other { message: format!("Error while creating CAN mirror '{mirror_name}': {cause}") })?;
+            self.network_interface_manager.set_interface_up(&mirror).await
+                .map_err(|cause| Error::Other { message: format!("Error while setting CAN mirror '{mirror_name}' up: {cause}") })?;
+        }
+
+        self.create_can_route(interface_name, mirror_name, true, 2).await?;
+        self.create_can_route(interface_name, mirror_name, false, 2).await?;
+
+        info!("Mirroring the traffic of CAN interface '{interface_name}' onto '{mirror_name}'.");
+        Ok(())
+    }
     
     async fn create_can_bridge(&self, bridge_name: &NetworkInterfaceName) -> anyhow::Result<()> {
     
@@ -191,6 +256,11 @@ impl CanManager {
         Ok(())
     }
     
+    pub async fn teardown_routing(&self) -> Result<(), Error> {
+        self.terminate_cannelloni_managers().await;
+        self.remove_all_can_routes().await
+    }
+
     pub async fn setup_remote_routing_server(&self, bridge_name: &NetworkInterfaceName, remote_assignments: &Vec<PeerClusterAssignment>) -> Result<(), Error>  {
 
         self.terminate_cannelloni_managers().await;
@@ -234,3 +304,27 @@ pub enum Error {
     #[error("{message}")]
     Other { message: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_mirror() -> anyhow::Result<()> {
+        let (interface, mirror) = parse_mirror("can0:can0-mirror")?;
+
+        assert_that!(interface, eq(&NetworkInterfaceName::try_from("can0")?));
+        assert_that!(mirror, eq(&NetworkInterfaceName::try_from("can0-mirror")?));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_mirrors() {
+        assert_that!(parse_mirror("can0"), err(anything()));
+        assert_that!(parse_mirror("can0:can0"), err(anything()));
+        assert_that!(parse_mirror("can0:br-vcan-opendut"), err(anything()));
+        assert_that!(parse_mirror("can0:a-name-which-is-too-long"), err(anything()));
+    }
+}
@@ -1,5 +1,4 @@
 use std::net::{IpAddr, Ipv4Addr};
-use std::sync::Arc;
 use tracing::debug;
 
 use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
@@ -9,126 +8,21 @@ use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDe
 use crate::service::network_interface;
 use crate::service::network_interface::gre;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
-use crate::service::can_manager::CanManagerRef;
 
-#[tracing::instrument(skip_all, level="trace")]
-pub async fn setup_ethernet_gre_interfaces(
-    cluster_assignment: &ClusterAssignment,
-    self_id: PeerId,
-    bridge_name: &NetworkInterfaceName,
-    network_interface_manager: NetworkInterfaceManagerRef,
-) -> Result<(), Error> {
-    debug!("Setting up Ethernet GRE interfaces.");
-
-    let local_peer_assignment = cluster_assignment.assignments.iter().find(|assignment| {
+pub(crate) fn determine_local_assignment(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> Result<&PeerClusterAssignment, Error> {
+    cluster_assignment.assignments.iter().find(|assignment| {
         assignment.peer_id == self_id
-    }).ok_or(Error::LocalPeerAssignmentNotFound { self_id })?;
-
-    let local_ip = local_peer_assignment.vpn_address;
-    let local_ip = require_ipv4_for_gre(local_ip)?;
-
-    let remote_ips = determine_remote_ips(cluster_assignment, self_id)?;
-    let remote_ips = remote_ips.into_iter()
-        .map(require_ipv4_for_gre)
-        .collect::<Result<Vec<_>, _>>()?;
-
-    gre::setup_interfaces(
-        &local_ip,
-        &remote_ips,
-        bridge_name,
-        Arc::clone(&network_interface_manager),
-    ).await
-    .map_err(Error::GreInterfaceSetupFailed)?;
-
-    Ok(())
-}
-
-#[tracing::instrument(skip_all, level="trace")]
-pub async fn join_ethernet_interfaces_to_bridge(
-    cluster_assignment: &ClusterAssignment,
-    self_id: PeerId,
-    bridge_name: &NetworkInterfaceName,
-    network_interface_manager: NetworkInterfaceManagerRef,
-) -> Result<(), Error> {
-    debug!("Joining Ethernet interfaces to bridge '{bridge_name}'.");
-
-    let own_ethernet_interfaces = get_own_ethernet_interfaces(cluster_assignment, self_id)?;
-
-    join_device_interfaces_to_bridge(&own_ethernet_interfaces, bridge_name, Arc::clone(&network_interface_manager)).await
-        .map_err(Error::JoinDeviceInterfaceToBridgeFailed)?;
-
-    Ok(())
-}
-
-#[tracing::instrument(skip_all, level="trace")]
-pub async fn setup_can_interfaces(
-    cluster_assignment: &ClusterAssignment,
-    self_id: PeerId,
-    can_manager: CanManagerRef
-) -> Result<(), Error> {
-    let own_can_interfaces = get_own_can_interfaces(cluster_assignment, self_id)?;
-
-    if let sudo::RunningAs::User = sudo::check() {
-        if own_can_interfaces.is_empty() {
-            //Since we don't have the correct permissions to run the CAN setup code,
-            //no previous CAN interfaces exist which we might need to clean up,
-            //so we can safely skip this code, which allows us to run without root,
-            //when CAN is not used.
-            debug!("No CAN interfaces to set up. Skipping.");
-            return Ok(());
-        } else {
-            panic!("CARL requested to setup CAN interfaces, but EDGAR is not running with root permissions, which is currently required."); //TODO report problem to CARL
-        }
-    }
-
-    debug!("Setting up CAN interfaces.");
-
-    let can_bridge_name = crate::common::default_can_bridge_name();
-    can_manager.setup_local_routing(
-        &can_bridge_name, 
-        own_can_interfaces,
-    ).await
-    .map_err(Error::LocalCanRoutingSetupFailed)?;
-
-    let local_peer_assignment = cluster_assignment.assignments.iter().find(|assignment| {
-        assignment.peer_id == self_id
-    }).ok_or(Error::LocalPeerAssignmentNotFound { self_id })?;
-
-    let is_leader = cluster_assignment.leader == self_id;
-
-    let server_port = local_peer_assignment.can_server_port;
-
-    if is_leader {
-
-        let remote_assignments = determine_remote_assignments(cluster_assignment, self_id)?;
-        can_manager.setup_remote_routing_server(
-            &can_bridge_name, 
-            &remote_assignments
-        ).await
-        .map_err(Error::RemoteCanRoutingSetupFailed)?;
-
-    } else {        
-
-        let leader_assignment = determine_leader_assignment(cluster_assignment)?;
-        can_manager.setup_remote_routing_client(
-            &can_bridge_name, 
-            &leader_assignment.vpn_address,
-            &server_port
-        ).await
-        .map_err(Error::RemoteCanRoutingSetupFailed)?;
-    }
-
-    Ok(())
+    }).ok_or(Error::LocalPeerAssignmentNotFound { self_id })
 }
 
-fn determine_remote_ips(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> Result<Vec<IpAddr>, Error> {
+pub(crate) fn determine_remote_ips(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> Result<Vec<IpAddr>, Error> {
     let remote_assignments = determine_remote_assignments(cluster_assignment, self_id);
     let remote_ips = remote_assignments?.iter().map(|remote_assignment| remote_assignment.vpn_address).collect();
 
     Ok(remote_ips)
 }
 
-fn determine_remote_assignments(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> Result<Vec<PeerClusterAssignment>, Error> {
+pub(crate) fn determine_remote_assignments(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> Result<Vec<PeerClusterAssignment>, Error> {
     let is_leader = cluster_assignment.leader == self_id;
 
     let remote_peer_cluster_assignments = if is_leader {
@@ -145,7 +39,7 @@ fn determine_remote_assignments(cluster_assignment: &ClusterAssignment, self_id:
     Ok(remote_peer_cluster_assignments)
 }
 
-fn determine_leader_assignment(cluster_assignment: &ClusterAssignment) -> Result<&PeerClusterAssignment, Error>{
+pub(crate) fn determine_leader_assignment(cluster_assignment: &ClusterAssignment) -> Result<&PeerClusterAssignment, Error>{
     let leader_assignment = cluster_assignment.assignments
         .iter().find(|peer_assignment| 
             peer_assignment.peer_id == cluster_assignment.leader
@@ -154,14 +48,14 @@ fn determine_leader_assignment(cluster_assignment: &ClusterAssignment) -> Result
     Ok(leader_assignment)
 }
 
-fn require_ipv4_for_gre(ip_address: IpAddr) -> Result<Ipv4Addr, Error> {
+pub(crate) fn require_ipv4_for_gre(ip_address: IpAddr) -> Result<Ipv4Addr, Error> {
     match ip_address {
         IpAddr::V4(ip_address) => Ok(ip_address),
         IpAddr::V6(_) => Err(Error::Ipv6NotSupported),
     }
 }
 
-fn get_own_ethernet_interfaces(
+pub(crate) fn get_own_ethernet_interfaces(
     cluster_assignment: &ClusterAssignment,
     self_id: PeerId,
 ) -> Result<Vec<NetworkInterfaceDescriptor>, Error> {
@@ -176,7 +70,7 @@ fn get_own_ethernet_interfaces(
     Ok(own_ethernet_interfaces)
 }
 
-fn get_own_can_interfaces(
+pub(crate) fn get_own_can_interfaces(
     cluster_assignment: &ClusterAssignment,
     self_id: PeerId,
 ) -> Result<Vec<NetworkInterfaceDescriptor>, Error> {
@@ -191,7 +85,7 @@ fn get_own_can_interfaces(
     Ok(own_can_interfaces)
 }
 
-async fn join_device_interfaces_to_bridge(
+pub(crate) async fn join_device_interfaces_to_bridge(
     device_interfaces: &Vec<NetworkInterfaceDescriptor>,
     bridge_name: &NetworkInterfaceName,
     network_interface_manager: NetworkInterfaceManagerRef
@@ -0,0 +1,131 @@
+use std::ops::Not;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::configuration::{Parameter, ParameterTarget};
+use opendut_types::peer::hook::{ClusterHook, ClusterHookFailurePolicy, ClusterHookName, ClusterHookStage};
+use opendut_types::peer::PeerId;
+
+const SHELL: &str = "/bin/sh";
+
+/// Runs the hooks of the given stage one after another, in the order they were configured.
+/// Their output is logged line by line. When a hook with the failure policy `Abort` fails,
+/// the remaining hooks are not run and the error is returned.
+pub async fn run(hooks: &[Parameter<ClusterHook>], stage: ClusterHookStage, cluster_id: ClusterId, self_id: PeerId) -> Result<(), Error> {
+    let hooks = hooks.iter()
+        .filter(|parameter| parameter.target == ParameterTarget::Present)
+        .map(|parameter| &parameter.value)
+        .filter(|hook| hook.stage == stage);
+
+    for hook in hooks {
+        info!("Running {stage} cluster hook '{}' for cluster <{cluster_id}>.", hook.name);
+
+        match run_hook(hook, cluster_id, self_id).await {
+            Ok(()) => info!("Cluster hook '{}' succeeded.", hook.name),
+            Err(cause) => match hook.failure_policy {
+                ClusterHookFailurePolicy::Abort => {
+                    error!("{cause}\n  Not running the remaining {stage} cluster hooks, as the failure policy of the hook is '{}'.", hook.failure_policy);
+                    return Err(cause);
+                }
+                ClusterHookFailurePolicy::Ignore => {
+                    warn!("{cause}\n  Continuing, as the failure policy of the hook is '{}'.", hook.failure_policy);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_hook(hook: &ClusterHook, cluster_id: ClusterId, self_id: PeerId) -> Result<(), Error> {
+    let name = Clone::clone(&hook.name);
+
+    let child = Command::new(SHELL)
+        .arg("-c")
+        .arg(&hook.script)
+        .env("OPENDUT_HOOK_NAME", hook.name.value())
+        .env("OPENDUT_HOOK_STAGE", hook.stage.to_string())
+        .env("OPENDUT_CLUSTER_ID", cluster_id.to_string())
+        .env("OPENDUT_PEER_ID", self_id.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true) //terminates the hook, when the timeout elapses
+        .spawn()
+        .map_err(|cause| Error::Start { name: Clone::clone(&name), cause })?;
+
+    let output = tokio::time::timeout(hook.timeout, child.wait_with_output()).await
+        .map_err(|_| Error::TimedOut { name: Clone::clone(&name), timeout: hook.timeout })?
+        .map_err(|cause| Error::Start { name: Clone::clone(&name), cause })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stdout.lines().chain(stderr.lines()) {
+        info!("Output of cluster hook '{name}': {line}");
+    }
+
+    if output.status.success().not() {
+        return Err(Error::Failed { name, status: output.status, stderr: stderr.trim().to_owned() });
+    }
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to run cluster hook '{name}': {cause}")]
+    Start { name: ClusterHookName, #[source] cause: std::io::Error },
+    #[error("Cluster hook '{name}' did not finish within {} seconds and was terminated.", timeout.as_secs_f32())]
+    TimedOut { name: ClusterHookName, timeout: Duration },
+    #[error("Cluster hook '{name}' failed with {status}:\n  {stderr}")]
+    Failed { name: ClusterHookName, status: ExitStatus, stderr: String },
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn hook(name: &str, script: &str, timeout: Duration, failure_policy: ClusterHookFailurePolicy) -> Parameter<ClusterHook> {
+        let hook = ClusterHook {
+            name: ClusterHookName::try_from(name).unwrap(),
+            stage: ClusterHookStage::BeforeJoin,
+            script: script.to_owned(),
+            timeout,
+            failure_policy,
+        };
+        Parameter {
+            id: opendut_types::peer::configuration::ParameterValue::parameter_identifier(&hook),
+            dependencies: vec![],
+            target: ParameterTarget::Present,
+            value: hook,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_apply_the_failure_policy_of_the_hooks() -> anyhow::Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        let marker = temp.path().join("marker");
+        let touch_marker = format!("touch '{}'", marker.display());
+
+        let hooks = vec![
+            hook("ignored", "exit 3", ClusterHook::DEFAULT_TIMEOUT, ClusterHookFailurePolicy::Ignore),
+            hook("slow", "sleep 5", Duration::from_millis(100), ClusterHookFailurePolicy::Abort),
+            hook("skipped", &touch_marker, ClusterHook::DEFAULT_TIMEOUT, ClusterHookFailurePolicy::Abort),
+        ];
+
+        let result = run(&hooks, ClusterHookStage::BeforeJoin, ClusterId::random(), PeerId::random()).await;
+
+        assert_that!(result, err(matches_pattern!(Error::TimedOut { .. })));
+        assert_that!(marker.exists(), eq(false));
+
+        let result = run(&hooks, ClusterHookStage::AfterLeave, ClusterId::random(), PeerId::random()).await;
+        assert_that!(result, ok(anything()));
+
+        Ok(())
+    }
+}
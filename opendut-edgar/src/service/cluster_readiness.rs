@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::ops::Not;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, info, trace, warn};
+
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::{network_readiness, NetworkNotReady, NetworkReady};
+use opendut_types::cluster::{ClusterAssignment, ClusterId};
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::bus::{BusRef, ClusterContext};
+
+/// Whether EDGAR verifies the network of a cluster after joining it, before starting the executors.
+/// Executors of a test usually fail immediately, when they are started while the network between the peers is still converging.
+#[derive(Clone, Debug)]
+pub enum ClusterReadinessOptions {
+    Enabled {
+        /// Time after which the network is reported as not ready, if the checks still fail.
+        timeout: Duration,
+        /// Time to wait between two rounds of checks.
+        probe_interval: Duration,
+        /// Time to wait for the reply of a remote peer.
+        probe_timeout: Duration,
+    },
+    Disabled,
+}
+
+impl ClusterReadinessOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("cluster.readiness.enabled")?;
+
+        if enabled {
+            let timeout = Duration::from_millis(config.get::<u64>("cluster.readiness.timeout.ms")?);
+            let probe_interval = Duration::from_millis(config.get::<u64>("cluster.readiness.probe.interval.ms")?);
+            let probe_timeout = Duration::from_millis(config.get::<u64>("cluster.readiness.probe.timeout.ms")?);
+            Ok(ClusterReadinessOptions::Enabled { timeout, probe_interval, probe_timeout })
+        } else {
+            Ok(ClusterReadinessOptions::Disabled)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkReadiness {
+    Ready,
+    NetworkNotReady {
+        unreachable_peers: Vec<PeerId>,
+        failed_checks: Vec<String>,
+    },
+}
+
+/// Checks whether a remote peer can be reached via its VPN address.
+#[async_trait]
+pub trait ReachabilityProbe: Send + Sync {
+    async fn is_reachable(&self, address: IpAddr, timeout: Duration) -> bool;
+}
+
+pub struct PingProbe;
+
+#[async_trait]
+impl ReachabilityProbe for PingProbe {
+    async fn is_reachable(&self, address: IpAddr, timeout: Duration) -> bool {
+        tokio::task::spawn_blocking(move || {
+            let data = [1, 2, 3, 4];
+            let options = ping_rs::PingOptions { ttl: 128, dont_fragment: true };
+            ping_rs::send_ping(&address, timeout, &data, Some(&options))
+                .inspect_err(|cause| trace!("Probing {address} failed: {cause:?}"))
+                .is_ok()
+        }).await
+        .unwrap_or(false)
+    }
+}
+
+pub struct AwaitNetworkReadinessParams<'a> {
+    pub cluster_assignment: &'a ClusterAssignment,
+    pub self_id: PeerId,
+    /// Buses, whose connections to the cluster are verified. Empty, if EDGAR does not manage the network interfaces.
+    pub buses: &'a [BusRef],
+    pub bridge_name: &'a NetworkInterfaceName,
+    pub timeout: Duration,
+    pub probe_interval: Duration,
+    pub probe_timeout: Duration,
+}
+
+/// Repeatedly checks the buses and the reachability of all other peers in the cluster,
+/// until all checks succeed or the timeout elapsed.
+#[tracing::instrument(skip_all)]
+pub async fn await_network_readiness(params: AwaitNetworkReadinessParams<'_>, probe: &dyn ReachabilityProbe) -> NetworkReadiness {
+    let AwaitNetworkReadinessParams { cluster_assignment, self_id, buses, bridge_name, timeout, probe_interval, probe_timeout } = params;
+
+    let deadline = Instant::now() + timeout;
+    let cluster = ClusterContext { cluster_assignment, self_id, bridge_name };
+
+    let remote_peers = cluster_assignment.assignments.iter()
+        .filter(|assignment| assignment.peer_id != self_id)
+        .collect::<Vec<_>>();
+
+    debug!("Verifying network of cluster <{}> with {} remote peer(s).", cluster_assignment.id, remote_peers.len());
+
+    let mut reached_peers = HashSet::new();
+
+    loop {
+        let mut failed_checks = Vec::new();
+        for bus in buses {
+            if let Err(cause) = bus.verify_cluster(&cluster).await {
+                failed_checks.push(format!("{} bus: {cause}", bus.kind()));
+            }
+        }
+
+        let pending_peers = remote_peers.iter()
+            .filter(|assignment| reached_peers.contains(&assignment.peer_id).not())
+            .collect::<Vec<_>>();
+
+        let probes = pending_peers.iter()
+            .map(|assignment| probe.is_reachable(assignment.vpn_address, probe_timeout));
+        let results = futures::future::join_all(probes).await;
+
+        let mut unreachable_peers = Vec::new();
+        for (assignment, reachable) in pending_peers.into_iter().zip(results) {
+            if reachable {
+                reached_peers.insert(assignment.peer_id);
+            } else {
+                unreachable_peers.push(assignment.peer_id);
+            }
+        }
+
+        if unreachable_peers.is_empty() && failed_checks.is_empty() {
+            info!("Network of cluster <{}> is ready.", cluster_assignment.id);
+            return NetworkReadiness::Ready;
+        }
+
+        if Instant::now() + probe_interval >= deadline {
+            warn!(
+                "Network of cluster <{}> is not ready after {} ms.\n  Unreachable peers: {unreachable_peers:?}\n  Failed checks: {failed_checks:?}",
+                cluster_assignment.id, timeout.as_millis(),
+            );
+            return NetworkReadiness::NetworkNotReady { unreachable_peers, failed_checks };
+        }
+
+        debug!("Network of cluster <{}> is not yet ready. Unreachable peers: {unreachable_peers:?}, failed checks: {failed_checks:?}", cluster_assignment.id);
+        tokio::time::sleep(probe_interval).await;
+    }
+}
+
+/// Informs CARL about the readiness, so that the reason for missing executors is visible outside of this peer.
+pub async fn report(readiness: &NetworkReadiness, cluster_id: ClusterId, tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>) {
+    let state = match Clone::clone(readiness) {
+        NetworkReadiness::Ready => network_readiness::State::Ready(NetworkReady {}),
+        NetworkReadiness::NetworkNotReady { unreachable_peers, failed_checks } => network_readiness::State::NotReady(NetworkNotReady {
+            unreachable_peers: unreachable_peers.into_iter().map(Into::into).collect(),
+            failed_checks,
+        }),
+    };
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::NetworkReadiness(peer_messaging_broker::NetworkReadiness {
+            cluster_id: Some(cluster_id.into()),
+            state: Some(state),
+        })),
+        context: None,
+    };
+    let _ignore_error =
+        tx_outbound.send(message).await
+            .inspect_err(|cause| warn!("Failed to report network readiness to CARL: {cause}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::PeerClusterAssignment;
+    use opendut_types::util::Port;
+
+    use super::*;
+
+    struct FakeProbe {
+        reachable: HashSet<IpAddr>,
+    }
+
+    #[async_trait]
+    impl ReachabilityProbe for FakeProbe {
+        async fn is_reachable(&self, address: IpAddr, _timeout: Duration) -> bool {
+            self.reachable.contains(&address)
+        }
+    }
+
+    fn assignment(peer_id: PeerId, vpn_address: [u8; 4]) -> PeerClusterAssignment {
+        PeerClusterAssignment {
+            peer_id,
+            vpn_address: IpAddr::V4(Ipv4Addr::from(vpn_address)),
+            can_server_port: Port(10000),
+            device_interfaces: vec![],
+        }
+    }
+
+    fn params<'a>(cluster_assignment: &'a ClusterAssignment, self_id: PeerId, bridge_name: &'a NetworkInterfaceName) -> AwaitNetworkReadinessParams<'a> {
+        AwaitNetworkReadinessParams {
+            cluster_assignment,
+            self_id,
+            buses: &[],
+            bridge_name,
+            timeout: Duration::from_millis(50),
+            probe_interval: Duration::from_millis(10),
+            probe_timeout: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_report_unreachable_peers_as_not_ready() -> anyhow::Result<()> {
+        let self_id = PeerId::random();
+        let reachable_peer = PeerId::random();
+        let unreachable_peer = PeerId::random();
+
+        let cluster_assignment = ClusterAssignment {
+            id: ClusterId::random(),
+            leader: self_id,
+            assignments: vec![
+                assignment(self_id, [10, 0, 0, 1]),
+                assignment(reachable_peer, [10, 0, 0, 2]),
+                assignment(unreachable_peer, [10, 0, 0, 3]),
+            ],
+        };
+        let probe = FakeProbe { reachable: HashSet::from([IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]) };
+        let bridge_name = NetworkInterfaceName::try_from("br-opendut")?;
+
+        let readiness = await_network_readiness(params(&cluster_assignment, self_id, &bridge_name), &probe).await;
+
+        assert_that!(readiness, eq(&NetworkReadiness::NetworkNotReady {
+            unreachable_peers: vec![unreachable_peer],
+            failed_checks: vec![],
+        }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_be_ready_when_all_remote_peers_are_reachable() -> anyhow::Result<()> {
+        let self_id = PeerId::random();
+        let remote_peer = PeerId::random();
+
+        let cluster_assignment = ClusterAssignment {
+            id: ClusterId::random(),
+            leader: remote_peer,
+            assignments: vec![
+                assignment(self_id, [10, 0, 0, 1]),
+                assignment(remote_peer, [10, 0, 0, 2]),
+            ],
+        };
+        let probe = FakeProbe { reachable: HashSet::from([IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]) };
+        let bridge_name = NetworkInterfaceName::try_from("br-opendut")?;
+
+        let readiness = await_network_readiness(params(&cluster_assignment, self_id, &bridge_name), &probe).await;
+
+        assert_that!(readiness, eq(&NetworkReadiness::Ready));
+        Ok(())
+    }
+}
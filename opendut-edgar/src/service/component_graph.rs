@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Not;
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::{component_status, ComponentApplied, ComponentFailed, ComponentSkipped, PeerConfigurationStatus};
+
+/// Part of the PeerConfiguration, which is applied as one unit, once the components it depends on were applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Component {
+    EthernetBridges,
+    ExecutorTermination,
+    ClusterNetwork,
+    Executors,
+    ClusterMetrics,
+}
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Component::EthernetBridges => "ethernet-bridges",
+            Component::ExecutorTermination => "executor-termination",
+            Component::ClusterNetwork => "cluster-network",
+            Component::Executors => "executors",
+            Component::ClusterMetrics => "cluster-metrics",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ComponentStatus {
+    Applied { duration: Duration },
+    Failed { duration: Duration, cause: String },
+    /// Not applied, because a component it depends on was not applied.
+    Skipped { reason: String },
+}
+
+struct Node<'a> {
+    component: Component,
+    depends_on: Vec<Component>,
+    apply: BoxFuture<'a, anyhow::Result<()>>,
+}
+
+/// Applies the components of a PeerConfiguration concurrently, as far as their dependencies allow.
+#[derive(Default)]
+pub struct ComponentGraph<'a> {
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> ComponentGraph<'a> {
+    pub fn add(&mut self, component: Component, depends_on: &[Component], apply: BoxFuture<'a, anyhow::Result<()>>) {
+        self.nodes.push(Node { component, depends_on: depends_on.to_vec(), apply });
+    }
+
+    /// Applies all components in waves, where each wave consists of the components, whose dependencies were applied by an earlier wave.
+    /// Returns the status of each component in the order they were added.
+    pub async fn apply(self) -> Vec<(Component, ComponentStatus)> {
+        let order = self.nodes.iter().map(|node| node.component).collect::<Vec<_>>();
+        let mut statuses = HashMap::<Component, ComponentStatus>::new();
+        let mut pending = self.nodes;
+
+        while pending.is_empty().not() {
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter()
+                .partition(|node| node.depends_on.iter().all(|dependency| statuses.contains_key(dependency)));
+
+            if ready.is_empty() {
+                for node in waiting {
+                    warn!("Cannot apply component '{}', as its dependencies form a cycle or are missing.", node.component);
+                    statuses.insert(node.component, ComponentStatus::Skipped { reason: String::from("Dependencies form a cycle or are missing.") });
+                }
+                break;
+            }
+
+            let mut runnable = vec![];
+            for node in ready {
+                let failed_dependencies = node.depends_on.iter()
+                    .filter(|dependency| matches!(statuses.get(dependency), Some(ComponentStatus::Applied { .. })).not())
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+
+                if failed_dependencies.is_empty() {
+                    runnable.push(node);
+                } else {
+                    let reason = format!("Depends on components, which were not applied: {}", failed_dependencies.join(", "));
+                    warn!("Skipping component '{}'. {reason}", node.component);
+                    statuses.insert(node.component, ComponentStatus::Skipped { reason });
+                }
+            }
+
+            debug!("Applying components concurrently: {}", runnable.iter().map(|node| node.component.to_string()).collect::<Vec<_>>().join(", "));
+
+            let results = futures::future::join_all(
+                runnable.into_iter().map(|node| async move {
+                    let start = Instant::now();
+                    let result = node.apply.await;
+                    (node.component, result, start.elapsed())
+                })
+            ).await;
+
+            for (component, result, duration) in results {
+                let status = match result {
+                    Ok(()) => {
+                        info!("Applied component '{component}' in {} ms.", duration.as_millis());
+                        ComponentStatus::Applied { duration }
+                    }
+                    Err(cause) => {
+                        warn!("Failed to apply component '{component}' after {} ms:\n  {cause}", duration.as_millis());
+                        ComponentStatus::Failed { duration, cause: cause.to_string() }
+                    }
+                };
+                statuses.insert(component, status);
+            }
+
+            pending = waiting;
+        }
+
+        order.into_iter()
+            .filter_map(|component| statuses.remove(&component).map(|status| (component, status)))
+            .collect()
+    }
+}
+
+pub async fn report(statuses: &[(Component, ComponentStatus)], tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>) {
+    let components = statuses.iter()
+        .map(|(component, status)| {
+            let (duration, state) = match Clone::clone(status) {
+                ComponentStatus::Applied { duration } => (duration, component_status::State::Applied(ComponentApplied {})),
+                ComponentStatus::Failed { duration, cause } => (duration, component_status::State::Failed(ComponentFailed { cause })),
+                ComponentStatus::Skipped { reason } => (Duration::ZERO, component_status::State::Skipped(ComponentSkipped { reason })),
+            };
+            peer_messaging_broker::ComponentStatus {
+                component: component.to_string(),
+                duration_ms: duration.as_millis() as u64,
+                state: Some(state),
+            }
+        })
+        .collect();
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::PeerConfigurationStatus(PeerConfigurationStatus { components })),
+        context: None,
+    };
+    let _ignore_error =
+        tx_outbound.send(message).await
+            .inspect_err(|cause| warn!("Failed to report status of peer configuration to CARL: {cause}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::anyhow;
+    use futures::FutureExt;
+    use googletest::prelude::*;
+    use tokio::sync::Barrier;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_apply_independent_components_concurrently() -> anyhow::Result<()> {
+        let barrier = Arc::new(Barrier::new(2)); //only released, when both components run at the same time
+
+        let mut testee = ComponentGraph::default();
+        testee.add(Component::EthernetBridges, &[], {
+            let barrier = Arc::clone(&barrier);
+            async move { barrier.wait().await; Ok(()) }.boxed()
+        });
+        testee.add(Component::ExecutorTermination, &[], {
+            let barrier = Arc::clone(&barrier);
+            async move { barrier.wait().await; Ok(()) }.boxed()
+        });
+
+        let statuses = tokio::time::timeout(Duration::from_secs(5), testee.apply()).await?;
+
+        assert_that!(statuses.len(), eq(2));
+        assert!(statuses.iter().all(|(_, status)| matches!(status, ComponentStatus::Applied { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_skip_components_depending_on_failed_component() -> anyhow::Result<()> {
+        let mut testee = ComponentGraph::default();
+        testee.add(Component::ClusterNetwork, &[], async { Err(anyhow!("Network not ready.")) }.boxed());
+        testee.add(Component::Executors, &[Component::ClusterNetwork], async { Ok(()) }.boxed());
+        testee.add(Component::ClusterMetrics, &[], async { Ok(()) }.boxed());
+
+        let statuses = testee.apply().await;
+
+        assert!(matches!(statuses[0], (Component::ClusterNetwork, ComponentStatus::Failed { .. })));
+        assert!(matches!(statuses[1], (Component::Executors, ComponentStatus::Skipped { .. })));
+        assert!(matches!(statuses[2], (Component::ClusterMetrics, ComponentStatus::Applied { .. })));
+        Ok(())
+    }
+}
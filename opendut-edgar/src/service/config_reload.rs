@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Not;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+use opendut_util::settings::LoadedConfig;
+use opendut_util::telemetry::LogFilterHandle;
+
+use crate::common::settings;
+use crate::service::data_plane_metrics::{DataPlaneMetricsOptions, PrometheusEndpoint};
+use crate::service::test_execution::upload_queue::{UploadQueue, UploadQueueOptions};
+
+const LOG_LEVEL: &str = "logging.level";
+const PROMETHEUS_ADDRESS: &str = "metrics.data.plane.prometheus.address";
+const UPLOAD_QUEUE_LIMITS: [&str; 3] = [
+    "executor.upload.queue.size.limit.bytes",
+    "executor.upload.queue.retry.interval.min.ms",
+    "executor.upload.queue.retry.interval.max.ms",
+];
+
+#[derive(Clone, Debug)]
+pub enum ConfigReloadOptions {
+    Enabled {
+        /// Time without further changes to the configuration files, before they are loaded, as editors often write in multiple steps.
+        debounce: Duration,
+    },
+    Disabled,
+}
+
+impl ConfigReloadOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("configuration.reload.enabled")?;
+
+        if enabled {
+            let debounce = Duration::from_millis(config.get::<u64>("configuration.reload.debounce.ms")?);
+            Ok(ConfigReloadOptions::Enabled { debounce })
+        } else {
+            Ok(ConfigReloadOptions::Disabled)
+        }
+    }
+}
+
+/// Components, whose settings can be changed while EDGAR is running.
+pub struct Reloadable {
+    pub log_filter: LogFilterHandle,
+    pub upload_queue: UploadQueue,
+    /// `None`, if data plane metrics are disabled.
+    pub prometheus_endpoint: Option<PrometheusEndpoint>,
+}
+
+/// Watches the configuration files of EDGAR via inotify and applies changes to the log level, the address of the data plane metrics endpoint
+/// and the limits of the upload queue right away, so that peers running long tests do not have to be restarted.
+/// Which changed settings were applied and which only take effect after a restart of the service, is logged.
+pub fn spawn(
+    options: ConfigReloadOptions,
+    settings: &LoadedConfig,
+    settings_override: config::Config,
+    reloadable: Reloadable,
+) -> anyhow::Result<()> {
+    let ConfigReloadOptions::Enabled { debounce } = options else {
+        debug!("Reloading the configuration files on changes is disabled.");
+        return Ok(());
+    };
+
+    let files = settings.config_files_declared.clone();
+    let (tx_changed, mut rx_changed) = mpsc::unbounded_channel::<()>();
+
+    let mut watcher = notify::recommended_watcher({
+        let files = files.clone();
+        move |event: notify::Result<notify::Event>| match event {
+            Ok(event) => {
+                if event.kind.is_access().not() && event.paths.iter().any(|path| files.contains(path)) {
+                    let _ignore_error = tx_changed.send(());
+                }
+            }
+            Err(cause) => warn!("Error while watching the configuration files:\n  {cause}"),
+        }
+    })?;
+
+    // Watch the directories rather than the files, as editors often replace a file instead of writing to it, which ends a watch on the file itself.
+    let directories = files.iter()
+        .filter_map(|file| file.parent().map(PathBuf::from))
+        .collect::<BTreeSet<_>>();
+    for directory in directories {
+        match watcher.watch(&directory, RecursiveMode::NonRecursive) {
+            Ok(()) => debug!("Watching configuration files in '{}' for changes.", directory.display()),
+            Err(cause) => debug!("Not watching '{}' for changes to configuration files: {cause}", directory.display()),
+        }
+    }
+
+    let started = flatten(&settings.config)?;
+
+    tokio::spawn(async move {
+        let _watcher = watcher; //stops watching, when dropped
+        let mut applied = started.clone();
+
+        while rx_changed.recv().await.is_some() {
+            while let Ok(Some(())) = tokio::time::timeout(debounce, rx_changed.recv()).await {}
+
+            let loaded = settings::load_with_overrides(Clone::clone(&settings_override))
+                .and_then(|loaded| {
+                    let values = flatten(&loaded.config)?;
+                    Ok((loaded, values))
+                });
+            let (loaded, values) = match loaded {
+                Ok(loaded) => loaded,
+                Err(cause) => {
+                    error!("Ignoring changed configuration files, which could not be loaded:\n  {cause:#}");
+                    continue;
+                }
+            };
+
+            let changes = Changes::between(&started, &applied, &values, reloadable.prometheus_endpoint.is_some());
+            if changes.reloadable.is_empty() && changes.restart_required.is_empty() {
+                continue;
+            }
+
+            match apply(&changes.reloadable, &loaded.config, &reloadable) {
+                Ok(()) => {
+                    if changes.reloadable.is_empty().not() {
+                        info!("Applied changed settings from the configuration files: {}", changes.reloadable.join(", "));
+                    }
+                    applied = values;
+                }
+                Err(cause) => error!("Failed to apply changed settings from the configuration files:\n  {cause:#}"),
+            }
+
+            if changes.restart_required.is_empty().not() {
+                warn!("Changed settings, which only take effect after restarting EDGAR: {}", changes.restart_required.join(", "));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn apply(keys: &[String], config: &config::Config, reloadable: &Reloadable) -> anyhow::Result<()> {
+    let changed = |key: &str| keys.iter().any(|changed| changed == key);
+
+    if changed(LOG_LEVEL) {
+        let level = config.get_string(LOG_LEVEL).ok()
+            .filter(|level| level.is_empty().not());
+        reloadable.log_filter.reload(level.as_deref())?;
+    }
+
+    if UPLOAD_QUEUE_LIMITS.iter().any(|key| changed(key)) {
+        reloadable.upload_queue.reconfigure(UploadQueueOptions::load(config)?);
+    }
+
+    if let Some(prometheus_endpoint) = &reloadable.prometheus_endpoint {
+        if changed(PROMETHEUS_ADDRESS) {
+            if let DataPlaneMetricsOptions::Enabled { prometheus_address, .. } = DataPlaneMetricsOptions::load(config)? {
+                prometheus_endpoint.serve(prometheus_address)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct Changes {
+    /// Settings, which changed since they were last applied and can be applied while running.
+    reloadable: Vec<String>,
+    /// Settings, which differ from the ones EDGAR was started with and only take effect after a restart.
+    restart_required: Vec<String>,
+}
+
+impl Changes {
+    fn between(
+        started: &BTreeMap<String, String>,
+        applied: &BTreeMap<String, String>,
+        loaded: &BTreeMap<String, String>,
+        prometheus_endpoint_running: bool,
+    ) -> Self {
+        let is_reloadable = |key: &str| {
+            key == LOG_LEVEL
+                || UPLOAD_QUEUE_LIMITS.contains(&key)
+                || (key == PROMETHEUS_ADDRESS && prometheus_endpoint_running)
+        };
+
+        let reloadable = changed_keys(applied, loaded)
+            .filter(|key| is_reloadable(key))
+            .collect();
+        let restart_required = changed_keys(started, loaded)
+            .filter(|key| is_reloadable(key).not())
+            .collect();
+
+        Self { reloadable, restart_required }
+    }
+}
+
+fn changed_keys<'a>(before: &'a BTreeMap<String, String>, after: &'a BTreeMap<String, String>) -> impl Iterator<Item=String> + 'a {
+    before.keys().chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+}
+
+/// Maps the dotted key of each setting, e.g. `logging.level`, to its value.
+fn flatten(config: &config::Config) -> anyhow::Result<BTreeMap<String, String>> {
+    fn flatten_into(prefix: &str, table: config::Map<String, config::Value>, values: &mut BTreeMap<String, String>) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() { key } else { format!("{prefix}.{key}") };
+            match value.kind {
+                config::ValueKind::Table(table) => flatten_into(&key, table, values),
+                _ => { values.insert(key, value.to_string()); }
+            }
+        }
+    }
+
+    let mut values = BTreeMap::new();
+    flatten_into("", config.clone().try_deserialize()?, &mut values);
+    Ok(values)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn should_flatten_nested_tables_into_dotted_keys() -> anyhow::Result<()> {
+        let config = config::Config::builder()
+            .add_source(config::File::from_str(indoc::indoc!(r#"
+                [logging]
+                level = "info"
+
+                [executor.upload.queue]
+                size.limit.bytes = 1024
+            "#), config::FileFormat::Toml))
+            .build()?;
+
+        assert_eq!(flatten(&config)?, values(&[
+            ("executor.upload.queue.size.limit.bytes", "1024"),
+            ("logging.level", "info"),
+        ]));
+        Ok(())
+    }
+
+    #[test]
+    fn should_separate_reloadable_settings_from_those_requiring_a_restart() {
+        let started = values(&[
+            (LOG_LEVEL, ""),
+            (PROMETHEUS_ADDRESS, ""),
+            ("network.carl.host", "carl"),
+        ]);
+        let loaded = values(&[
+            (LOG_LEVEL, "debug"),
+            (PROMETHEUS_ADDRESS, "0.0.0.0:9559"),
+            ("network.carl.host", "carl.example.com"),
+        ]);
+
+        assert_eq!(Changes::between(&started, &started, &loaded, true), Changes {
+            reloadable: vec![String::from(LOG_LEVEL), String::from(PROMETHEUS_ADDRESS)],
+            restart_required: vec![String::from("network.carl.host")],
+        });
+
+        assert_eq!(Changes::between(&started, &loaded, &loaded, false), Changes {
+            reloadable: vec![],
+            restart_required: vec![String::from(PROMETHEUS_ADDRESS), String::from("network.carl.host")],
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use prost::Message;
+
+use opendut_carl_api::proto::services::peer_messaging_broker::ApplyPeerConfiguration;
+use opendut_types::peer::configuration::validation::{CheckOutcome, ConfigurationCheck, ConfigurationValidationReport};
+use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
+use opendut_types::peer::executor::container::CommandName;
+use opendut_types::peer::executor::ExecutorKind;
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
+
+use crate::service::cluster_assignment;
+use crate::service::component_graph::Component;
+use crate::service::network_interface::manager::NetworkInterfaceManager;
+
+const CANNELLONI: &str = "cannelloni";
+/// Lists the listening SCTP endpoints, which is only present, when the SCTP kernel module is loaded.
+const PROC_NET_SCTP_ENDPOINTS: &str = "/proc/net/sctp/eps";
+
+/// Checks whether a captured `ApplyPeerConfiguration` message, as sent by CARL, could be applied on this host, without applying it.
+pub async fn validate_configuration_file(file: &Path, self_id: PeerId) -> anyhow::Result<ConfigurationValidationReport> {
+    let bytes = fs_err::read(file)?;
+
+    let message = ApplyPeerConfiguration::decode(bytes.as_slice())
+        .context(format!("File '{}' does not contain a Protobuf-encoded ApplyPeerConfiguration message.", file.display()))?;
+
+    let old_peer_configuration = message.old_configuration
+        .ok_or(anyhow!("ApplyPeerConfiguration message does not contain an OldPeerConfiguration."))?;
+    let old_peer_configuration = OldPeerConfiguration::try_from(old_peer_configuration)
+        .map_err(|cause| anyhow!("Illegal OldPeerConfiguration: {cause}"))?;
+
+    let peer_configuration = message.configuration
+        .ok_or(anyhow!("ApplyPeerConfiguration message does not contain a PeerConfiguration."))?;
+    let peer_configuration = PeerConfiguration::try_from(peer_configuration)
+        .map_err(|cause| anyhow!("Illegal PeerConfiguration: {cause}"))?;
+
+    let host = HostState::inspect().await?;
+
+    Ok(validate(&old_peer_configuration, &peer_configuration, self_id, &host))
+}
+
+/// What is present on the host, which the configuration relies on.
+#[derive(Debug, Default)]
+struct HostState {
+    interfaces: HashSet<NetworkInterfaceName>,
+    command_line_programs: HashSet<String>,
+    occupied_sctp_ports: HashSet<u16>,
+}
+impl HostState {
+    async fn inspect() -> anyhow::Result<Self> {
+        let interfaces = NetworkInterfaceManager::create()?
+            .list_interfaces().await?
+            .into_iter()
+            .map(|interface| interface.name)
+            .collect();
+
+        let command_line_programs = ["docker", "podman", CANNELLONI].into_iter()
+            .filter(|program| which::which(program).is_ok())
+            .map(String::from)
+            .collect();
+
+        let occupied_sctp_ports = match fs_err::read_to_string(PROC_NET_SCTP_ENDPOINTS) {
+            Ok(endpoints) => parse_sctp_endpoint_ports(&endpoints),
+            Err(_) => HashSet::new(), //SCTP module not loaded, so no port can be occupied
+        };
+
+        Ok(Self { interfaces, command_line_programs, occupied_sctp_ports })
+    }
+}
+
+fn parse_sctp_endpoint_ports(endpoints: &str) -> HashSet<u16> {
+    endpoints.lines()
+        .skip(1) //header
+        .filter_map(|line| line.split_whitespace().nth(5)) //LPORT
+        .filter_map(|port| port.parse::<u16>().ok())
+        .collect()
+}
+
+fn validate(
+    old_peer_configuration: &OldPeerConfiguration,
+    peer_configuration: &PeerConfiguration,
+    self_id: PeerId,
+    host: &HostState,
+) -> ConfigurationValidationReport {
+    let mut checks = Vec::new();
+
+    let mut check = |component: Component, subject: String, outcome: CheckOutcome| {
+        checks.push(ConfigurationCheck { component: component.to_string(), subject, outcome });
+    };
+
+    let program_available = |program: &str| {
+        if host.command_line_programs.contains(program) {
+            CheckOutcome::Passed
+        } else {
+            CheckOutcome::Failed { cause: format!("Command-line program `{program}` could not be found in the operating system PATH.") }
+        }
+    };
+
+    if let Some(cluster_assignment) = &old_peer_configuration.cluster_assignment {
+        match cluster_assignment::determine_local_assignment(cluster_assignment, self_id) {
+            Ok(local_assignment) => {
+                for interface in &local_assignment.device_interfaces {
+                    let outcome = if host.interfaces.contains(&interface.name) {
+                        CheckOutcome::Passed
+                    } else {
+                        CheckOutcome::Failed { cause: format!("Interface '{}' does not exist on this host.", interface.name) }
+                    };
+                    check(Component::ClusterNetwork, format!("device interface {interface} exists"), outcome);
+                }
+
+                let routes_can = cluster_assignment.assignments.len() > 1
+                    && cluster_assignment.assignments.iter()
+                        .flat_map(|assignment| &assignment.device_interfaces)
+                        .any(|interface| matches!(interface.configuration, NetworkInterfaceConfiguration::Can { .. }));
+
+                if routes_can {
+                    check(Component::ClusterNetwork, format!("{CANNELLONI} is available for routing CAN"), program_available(CANNELLONI));
+
+                    if cluster_assignment.leader == self_id {
+                        for remote_assignment in cluster_assignment.assignments.iter().filter(|assignment| assignment.peer_id != self_id) {
+                            let port = remote_assignment.can_server_port;
+                            let outcome = if host.occupied_sctp_ports.contains(&port.0) {
+                                CheckOutcome::Failed { cause: format!("SCTP port {port} is already in use on this host.") }
+                            } else {
+                                CheckOutcome::Passed
+                            };
+                            check(Component::ClusterNetwork, format!("CAN server port {port} for peer <{}> is free", remote_assignment.peer_id), outcome);
+                        }
+                    }
+                }
+            }
+            Err(cause) => {
+                check(Component::ClusterNetwork, format!("peer <{self_id}> is part of cluster <{}>", cluster_assignment.id), CheckOutcome::Failed { cause: cause.to_string() });
+            }
+        }
+    }
+
+    for executor in peer_configuration.executors.iter().filter(|executor| executor.target == ParameterTarget::Present) {
+        let executor = &executor.value;
+        match &executor.kind {
+            ExecutorKind::Container { engine, .. } => {
+                check(Component::Executors, format!("{engine} is available for executor <{}>", executor.id), program_available(engine.command_name()));
+            }
+            ExecutorKind::Executable => {
+                check(Component::Executors, format!("executor <{}>", executor.id), CheckOutcome::Skipped { reason: String::from("Executables are not checked.") });
+            }
+        }
+    }
+
+    ConfigurationValidationReport::new(self_id, checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    use opendut_types::cluster::{ClusterAssignment, ClusterId, PeerClusterAssignment};
+    use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceDescriptor, NetworkInterfaceId};
+    use opendut_types::util::Port;
+
+    use super::*;
+
+    #[test]
+    fn should_report_missing_interfaces_and_occupied_ports() -> anyhow::Result<()> {
+        let leader = PeerId::random();
+        let member = PeerId::random();
+
+        let can_interface = NetworkInterfaceDescriptor {
+            id: NetworkInterfaceId::random(),
+            name: NetworkInterfaceName::try_from("can0")?,
+            configuration: NetworkInterfaceConfiguration::Can {
+                bitrate: 500_000,
+                sample_point: CanSamplePoint::try_from(0.7)?,
+                fd: false,
+                data_bitrate: 2_000_000,
+                data_sample_point: CanSamplePoint::try_from(0.7)?,
+            },
+        };
+        let old_peer_configuration = OldPeerConfiguration {
+            cluster_assignment: Some(ClusterAssignment {
+                id: ClusterId::random(),
+                leader,
+                assignments: vec![
+                    PeerClusterAssignment { peer_id: leader, vpn_address: IpAddr::from_str("10.0.0.1")?, can_server_port: Port(10000), device_interfaces: vec![can_interface] },
+                    PeerClusterAssignment { peer_id: member, vpn_address: IpAddr::from_str("10.0.0.2")?, can_server_port: Port(10001), device_interfaces: vec![] },
+                ],
+            }),
+        };
+
+        let host = HostState {
+            interfaces: HashSet::new(),
+            command_line_programs: HashSet::from([String::from(CANNELLONI)]),
+            occupied_sctp_ports: parse_sctp_endpoint_ports(indoc::indoc!("
+                 ENDPT     SOCK   STY SST HBKT LPORT   UID INODE LADDRS
+                ffff8a5c 0000000 2   10  29   10001     0 12345 0.0.0.0
+            ")),
+        };
+
+        let report = validate(&old_peer_configuration, &PeerConfiguration::default(), leader, &host);
+
+        assert!(!report.applicable);
+        let outcomes = report.checks.iter()
+            .map(|check| &check.outcome)
+            .collect::<Vec<_>>();
+        assert!(matches!(outcomes[..], [CheckOutcome::Failed { .. }, CheckOutcome::Passed, CheckOutcome::Failed { .. }]));
+
+        let report = validate(&old_peer_configuration, &PeerConfiguration::default(), PeerId::random(), &host);
+        assert!(!report.applicable);
+        assert_eq!(report.checks.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::ops::Not;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use opentelemetry::{global, KeyValue};
+use tokio::sync::oneshot;
+use tracing::{debug, error, info, warn};
+
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::network_interface::gre::GRE_INTERFACE_NAME_PREFIX;
+use crate::service::test_execution::executor_status::{ExecutorHealth, ExecutorStatus, ExecutorStatuses};
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+/// Value of `/sys/class/net/<interface>/type` for CAN interfaces, including virtual ones.
+const ARPHRD_CAN: u32 = 280;
+
+/// Samples the health of the data plane of this peer, i.e. the byte and frame counters of the bridges, GRE interfaces and CAN interfaces,
+/// the latter carrying the frames of the restbus simulation, as well as the health of the executors.
+/// The samples are recorded as OpenTelemetry metrics, which are pushed along with the other metrics of EDGAR, when `opentelemetry` is enabled,
+/// and can additionally be served in the Prometheus text format, for scraping them directly from the peer.
+#[derive(Clone, Debug)]
+pub enum DataPlaneMetricsOptions {
+    Disabled,
+    Enabled {
+        sample_interval: Duration,
+        prometheus_address: Option<SocketAddr>,
+    },
+}
+impl DataPlaneMetricsOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("metrics.data.plane.enabled")?;
+
+        if enabled {
+            let sample_interval = Duration::from_millis(config.get::<u64>("metrics.data.plane.sample.interval.ms")?);
+            let prometheus_address = Some(config.get_string("metrics.data.plane.prometheus.address")?)
+                .filter(|address| address.is_empty().not())
+                .map(|address| SocketAddr::from_str(&address))
+                .transpose()?;
+
+            Ok(DataPlaneMetricsOptions::Enabled { sample_interval, prometheus_address })
+        } else {
+            Ok(DataPlaneMetricsOptions::Disabled)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InterfaceKind {
+    Bridge,
+    Gre,
+    Can,
+}
+impl InterfaceKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InterfaceKind::Bridge => "bridge",
+            InterfaceKind::Gre => "gre",
+            InterfaceKind::Can => "can",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct InterfaceCounters {
+    received_bytes: u64,
+    transmitted_bytes: u64,
+    received_packets: u64,
+    transmitted_packets: u64,
+    receive_errors: u64,
+    transmit_errors: u64,
+    receive_dropped: u64,
+    transmit_dropped: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct InterfaceSample {
+    name: String,
+    kind: InterfaceKind,
+    counters: InterfaceCounters,
+    /// Packets, i.e. frames on CAN interfaces, per second since the previous sample. Unknown for the first sample of an interface.
+    received_packets_per_second: Option<f64>,
+    transmitted_packets_per_second: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct DataPlaneSample {
+    interfaces: Vec<InterfaceSample>,
+    executors: Vec<(ExecutorId, ExecutorStatus)>,
+}
+
+type DataPlaneSampleRef = Arc<Mutex<DataPlaneSample>>;
+
+/// Serves the samples in the Prometheus text format. The address can be changed while running, e.g. after the configuration file was edited.
+#[derive(Clone)]
+pub struct PrometheusEndpoint {
+    sample: DataPlaneSampleRef,
+    server: Arc<Mutex<Option<(SocketAddr, oneshot::Sender<()>)>>>,
+}
+impl PrometheusEndpoint {
+    /// Stops serving on the previous address, if any, and starts serving on the given address, if any.
+    pub fn serve(&self, address: Option<SocketAddr>) -> anyhow::Result<()> {
+        let mut server = self.server.lock().unwrap();
+        if server.as_ref().map(|(previous_address, _)| *previous_address) == address {
+            return Ok(());
+        }
+
+        if let Some((previous_address, shutdown)) = server.take() {
+            let _ignore_error = shutdown.send(());
+            info!("Stopped serving data plane metrics on http://{previous_address}/metrics");
+        }
+
+        if let Some(address) = address {
+            let builder = axum::Server::try_bind(&address)?;
+            info!("Serving data plane metrics on http://{address}/metrics");
+
+            let router = Router::new()
+                .route("/metrics", get(get_prometheus_metrics))
+                .with_state(Arc::clone(&self.sample));
+            let (shutdown, shutdown_received) = oneshot::channel::<()>();
+            tokio::spawn(async move {
+                builder.serve(router.into_make_service())
+                    .with_graceful_shutdown(async { shutdown_received.await.ok(); })
+                    .await
+                    .unwrap_or_else(|cause| error!("Serving data plane metrics stopped with error:\n  {cause}"));
+            });
+            *server = Some((address, shutdown));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the Prometheus endpoint, unless data plane metrics are disabled.
+pub async fn spawn(options: DataPlaneMetricsOptions, bridge_names: Vec<NetworkInterfaceName>, executor_statuses: ExecutorStatuses) -> anyhow::Result<Option<PrometheusEndpoint>> {
+    let DataPlaneMetricsOptions::Enabled { sample_interval, prometheus_address } = options else {
+        debug!("Data plane metrics are disabled.");
+        return Ok(None);
+    };
+
+    let sample: DataPlaneSampleRef = Default::default();
+    register_instruments(Arc::clone(&sample));
+
+    let prometheus_endpoint = PrometheusEndpoint {
+        sample: Arc::clone(&sample),
+        server: Default::default(),
+    };
+    prometheus_endpoint.serve(prometheus_address)?;
+
+    tokio::spawn(async move {
+        let bridge_names = bridge_names.iter().map(NetworkInterfaceName::name).collect::<Vec<_>>();
+        let bridge_names = bridge_names.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut previous: HashMap<String, (InterfaceCounters, Instant)> = HashMap::new();
+        let mut interval = tokio::time::interval(sample_interval);
+
+        loop {
+            interval.tick().await;
+
+            let interfaces = match read_interfaces(Path::new(SYS_CLASS_NET), &bridge_names) {
+                Ok(interfaces) => interfaces,
+                Err(cause) => {
+                    warn!("Failed to sample the network interfaces for data plane metrics: {cause}");
+                    continue;
+                }
+            };
+            let now = Instant::now();
+
+            let interfaces = interfaces.into_iter()
+                .map(|(name, kind, counters)| {
+                    let rates = previous.get(&name)
+                        .map(|(previous_counters, sampled_at)| {
+                            let elapsed = now.duration_since(*sampled_at);
+                            (
+                                rate(previous_counters.received_packets, counters.received_packets, elapsed),
+                                rate(previous_counters.transmitted_packets, counters.transmitted_packets, elapsed),
+                            )
+                        });
+                    InterfaceSample {
+                        name,
+                        kind,
+                        counters,
+                        received_packets_per_second: rates.map(|(received, _)| received),
+                        transmitted_packets_per_second: rates.map(|(_, transmitted)| transmitted),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            previous = interfaces.iter()
+                .map(|interface| (Clone::clone(&interface.name), (interface.counters, now)))
+                .collect();
+
+            *sample.lock().unwrap() = DataPlaneSample {
+                interfaces,
+                executors: executor_statuses.snapshot(),
+            };
+        }
+    });
+
+    Ok(Some(prometheus_endpoint))
+}
+
+/// Reads the counters of the given bridges, the GRE interfaces of EDGAR and all CAN interfaces, sorted by name.
+fn read_interfaces(sys_class_net: &Path, bridge_names: &[&str]) -> std::io::Result<Vec<(String, InterfaceKind, InterfaceCounters)>> {
+    let mut interfaces = vec![];
+
+    for entry in std::fs::read_dir(sys_class_net)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+
+        let kind = if bridge_names.contains(&name.as_str()) {
+            InterfaceKind::Bridge
+        } else if name.starts_with(GRE_INTERFACE_NAME_PREFIX) {
+            InterfaceKind::Gre
+        } else if read_value(&path.join("type")).ok() == Some(u64::from(ARPHRD_CAN)) {
+            InterfaceKind::Can
+        } else {
+            continue;
+        };
+
+        let statistics = path.join("statistics");
+        let read = |counter: &str| read_value(&statistics.join(counter));
+        let counters = (|| Ok::<_, std::io::Error>(InterfaceCounters {
+            received_bytes: read("rx_bytes")?,
+            transmitted_bytes: read("tx_bytes")?,
+            received_packets: read("rx_packets")?,
+            transmitted_packets: read("tx_packets")?,
+            receive_errors: read("rx_errors")?,
+            transmit_errors: read("tx_errors")?,
+            receive_dropped: read("rx_dropped")?,
+            transmit_dropped: read("tx_dropped")?,
+        }))();
+        match counters {
+            Ok(counters) => interfaces.push((name, kind, counters)),
+            Err(cause) if cause.kind() == std::io::ErrorKind::NotFound => continue, //removed meanwhile, e.g. while a cluster is undeployed
+            Err(cause) => return Err(cause),
+        }
+    }
+
+    interfaces.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+    Ok(interfaces)
+}
+
+fn read_value(path: &Path) -> std::io::Result<u64> {
+    let value = std::fs::read_to_string(path)?;
+    value.trim().parse()
+        .map_err(|cause| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Invalid value in '{}': {cause}", path.display())))
+}
+
+/// Counters are reset, when an interface is recreated, e.g. when a new cluster is deployed, in which case no rate can be determined.
+fn rate(previous: u64, current: u64, elapsed: Duration) -> f64 {
+    if current < previous || elapsed.is_zero() {
+        return 0.0;
+    }
+    (current - previous) as f64 / elapsed.as_secs_f64()
+}
+
+fn register_instruments(sample: DataPlaneSampleRef) {
+    let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
+
+    let received_bytes = meter.u64_observable_counter("interface_received_bytes").init();
+    let transmitted_bytes = meter.u64_observable_counter("interface_transmitted_bytes").init();
+    let received_packets_rate = meter.f64_observable_gauge("interface_received_packets_per_second").init();
+    let transmitted_packets_rate = meter.f64_observable_gauge("interface_transmitted_packets_per_second").init();
+    let errors = meter.u64_observable_counter("interface_errors").init();
+    let dropped = meter.u64_observable_counter("interface_dropped").init();
+    let executor_health = meter.u64_observable_gauge("executor_health").init();
+
+    meter.register_callback(&[received_bytes.as_any(), transmitted_bytes.as_any(), received_packets_rate.as_any(), transmitted_packets_rate.as_any(), errors.as_any(), dropped.as_any(), executor_health.as_any()], move |observer| {
+        let sample = Clone::clone(&*sample.lock().unwrap());
+
+        for interface in &sample.interfaces {
+            let attributes = [KeyValue::new("interface", Clone::clone(&interface.name)), KeyValue::new("kind", interface.kind.as_str())];
+            let with_direction = |direction: &'static str| [attributes[0].clone(), attributes[1].clone(), KeyValue::new("direction", direction)];
+
+            observer.observe_u64(&received_bytes, interface.counters.received_bytes, &attributes);
+            observer.observe_u64(&transmitted_bytes, interface.counters.transmitted_bytes, &attributes);
+            if let Some(rate) = interface.received_packets_per_second {
+                observer.observe_f64(&received_packets_rate, rate, &attributes);
+            }
+            if let Some(rate) = interface.transmitted_packets_per_second {
+                observer.observe_f64(&transmitted_packets_rate, rate, &attributes);
+            }
+            observer.observe_u64(&errors, interface.counters.receive_errors, &with_direction("receive"));
+            observer.observe_u64(&errors, interface.counters.transmit_errors, &with_direction("transmit"));
+            observer.observe_u64(&dropped, interface.counters.receive_dropped, &with_direction("receive"));
+            observer.observe_u64(&dropped, interface.counters.transmit_dropped, &with_direction("transmit"));
+        }
+
+        for (executor_id, status) in &sample.executors {
+            for health in ExecutorHealth::ALL {
+                let attributes = [
+                    KeyValue::new("executor_id", executor_id.to_string()),
+                    KeyValue::new("executor_name", Clone::clone(&status.name)),
+                    KeyValue::new("health", health.to_string()),
+                ];
+                observer.observe_u64(&executor_health, u64::from(status.health == health), &attributes);
+            }
+        }
+    }).expect("could not register data plane metrics collection callback for EDGAR");
+}
+
+async fn get_prometheus_metrics(State(sample): State<DataPlaneSampleRef>) -> impl IntoResponse {
+    let sample = Clone::clone(&*sample.lock().unwrap());
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render_prometheus(&sample),
+    )
+}
+
+fn render_prometheus(sample: &DataPlaneSample) -> String {
+    let mut output = String::new();
+
+    let mut interface_metric = |name: &str, kind: &str, help: &str, value: &dyn Fn(&InterfaceSample) -> Option<String>| {
+        let _ = writeln!(output, "# HELP opendut_edgar_{name} {help}");
+        let _ = writeln!(output, "# TYPE opendut_edgar_{name} {kind}");
+        for interface in &sample.interfaces {
+            if let Some(value) = value(interface) {
+                let _ = writeln!(output, "opendut_edgar_{name}{{interface=\"{}\",kind=\"{}\"}} {value}", interface.name, interface.kind.as_str());
+            }
+        }
+    };
+    interface_metric("interface_received_bytes_total", "counter", "Bytes received on the interface.", &|interface| Some(interface.counters.received_bytes.to_string()));
+    interface_metric("interface_transmitted_bytes_total", "counter", "Bytes transmitted on the interface.", &|interface| Some(interface.counters.transmitted_bytes.to_string()));
+    interface_metric("interface_received_packets_total", "counter", "Packets, or frames on CAN interfaces, received on the interface.", &|interface| Some(interface.counters.received_packets.to_string()));
+    interface_metric("interface_transmitted_packets_total", "counter", "Packets, or frames on CAN interfaces, transmitted on the interface.", &|interface| Some(interface.counters.transmitted_packets.to_string()));
+    interface_metric("interface_received_packets_per_second", "gauge", "Packets, or frames on CAN interfaces, received per second since the previous sample.", &|interface| interface.received_packets_per_second.map(|rate| format!("{rate:.3}")));
+    interface_metric("interface_transmitted_packets_per_second", "gauge", "Packets, or frames on CAN interfaces, transmitted per second since the previous sample.", &|interface| interface.transmitted_packets_per_second.map(|rate| format!("{rate:.3}")));
+    interface_metric("interface_receive_errors_total", "counter", "Receive errors on the interface.", &|interface| Some(interface.counters.receive_errors.to_string()));
+    interface_metric("interface_transmit_errors_total", "counter", "Transmit errors on the interface.", &|interface| Some(interface.counters.transmit_errors.to_string()));
+    interface_metric("interface_receive_dropped_total", "counter", "Received packets dropped on the interface.", &|interface| Some(interface.counters.receive_dropped.to_string()));
+    interface_metric("interface_transmit_dropped_total", "counter", "Transmitted packets dropped on the interface.", &|interface| Some(interface.counters.transmit_dropped.to_string()));
+
+    let _ = writeln!(output, "# HELP opendut_edgar_executor_health Health of the executor, 1 for its current health and 0 for the others.");
+    let _ = writeln!(output, "# TYPE opendut_edgar_executor_health gauge");
+    for (executor_id, status) in &sample.executors {
+        for health in ExecutorHealth::ALL {
+            let _ = writeln!(output, "opendut_edgar_executor_health{{executor_id=\"{executor_id}\",executor_name=\"{}\",health=\"{health}\"}} {}", status.name, u64::from(status.health == health));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn create_interface(sys_class_net: &Path, name: &str, interface_type: u32, received_packets: u64) -> anyhow::Result<()> {
+        let statistics = sys_class_net.join(name).join("statistics");
+        std::fs::create_dir_all(&statistics)?;
+        std::fs::write(sys_class_net.join(name).join("type"), format!("{interface_type}\n"))?;
+        for counter in ["rx_bytes", "tx_bytes", "tx_packets", "rx_errors", "tx_errors", "rx_dropped", "tx_dropped"] {
+            std::fs::write(statistics.join(counter), "0\n")?;
+        }
+        std::fs::write(statistics.join("rx_packets"), format!("{received_packets}\n"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_read_the_counters_of_bridges_gre_and_can_interfaces_only() -> anyhow::Result<()> {
+        let sys_class_net = assert_fs::TempDir::new()?;
+        create_interface(sys_class_net.path(), "br-opendut", 1, 10)?;
+        create_interface(sys_class_net.path(), "gre-opendut0", 778, 20)?;
+        create_interface(sys_class_net.path(), "vcan0", ARPHRD_CAN, 30)?;
+        create_interface(sys_class_net.path(), "eth0", 1, 40)?;
+
+        let interfaces = read_interfaces(sys_class_net.path(), &["br-opendut"])?;
+
+        let interfaces = interfaces.into_iter()
+            .map(|(name, kind, counters)| (name, kind, counters.received_packets))
+            .collect::<Vec<_>>();
+        assert_that!(interfaces, elements_are![
+            eq(&(String::from("br-opendut"), InterfaceKind::Bridge, 10)),
+            eq(&(String::from("gre-opendut0"), InterfaceKind::Gre, 20)),
+            eq(&(String::from("vcan0"), InterfaceKind::Can, 30)),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_the_sample_in_the_prometheus_text_format() -> anyhow::Result<()> {
+        let executor_id = ExecutorId::random();
+        let sample = DataPlaneSample {
+            interfaces: vec![InterfaceSample {
+                name: String::from("vcan0"),
+                kind: InterfaceKind::Can,
+                counters: InterfaceCounters { received_packets: 500, ..Default::default() },
+                received_packets_per_second: Some(rate(400, 500, Duration::from_secs(10))),
+                transmitted_packets_per_second: None,
+            }],
+            executors: vec![(executor_id, ExecutorStatus { name: String::from("restbus"), health: ExecutorHealth::Running })],
+        };
+
+        let output = render_prometheus(&sample);
+
+        assert_that!(output, contains_substring("opendut_edgar_interface_received_packets_total{interface=\"vcan0\",kind=\"can\"} 500\n"));
+        assert_that!(output, contains_substring("opendut_edgar_interface_received_packets_per_second{interface=\"vcan0\",kind=\"can\"} 10.000\n"));
+        assert_that!(output, not(contains_substring("opendut_edgar_interface_transmitted_packets_per_second{")));
+        assert_that!(output, contains_substring(format!("opendut_edgar_executor_health{{executor_id=\"{executor_id}\",executor_name=\"restbus\",health=\"running\"}} 1\n")));
+        assert_that!(output, contains_substring(format!("opendut_edgar_executor_health{{executor_id=\"{executor_id}\",executor_name=\"restbus\",health=\"failed\"}} 0\n")));
+        Ok(())
+    }
+}
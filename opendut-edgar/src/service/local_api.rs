@@ -0,0 +1,291 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+use opendut_carl_api::carl::broker;
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::test_event;
+use opendut_types::cluster::{ClusterAssignment, ClusterId};
+use opendut_types::peer::PeerId;
+
+/// REST API on localhost, via which test tools running on the same host can query the cluster membership and devices of this peer,
+/// and signal the start and stop of tests, which are forwarded to CARL.
+#[derive(Clone, Debug)]
+pub enum LocalApiOptions {
+    Disabled,
+    Enabled { port: u16 },
+}
+impl LocalApiOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("local.api.enabled")?;
+
+        if enabled {
+            let port = config.get::<u16>("local.api.port")?;
+            Ok(LocalApiOptions::Enabled { port })
+        } else {
+            Ok(LocalApiOptions::Disabled)
+        }
+    }
+}
+
+pub type LocalApiStateRef = Arc<LocalApiState>;
+
+pub struct LocalApiState {
+    self_id: PeerId,
+    carl: CarlClient,
+    cluster_assignment: RwLock<Option<ClusterAssignment>>,
+    tx_outbound: RwLock<Option<broker::Upstream>>,
+}
+
+impl LocalApiState {
+    pub fn new(self_id: PeerId, carl: CarlClient) -> LocalApiStateRef {
+        Arc::new(Self {
+            self_id,
+            carl,
+            cluster_assignment: Default::default(),
+            tx_outbound: Default::default(),
+        })
+    }
+
+    pub async fn set_cluster_assignment(&self, cluster_assignment: Option<ClusterAssignment>) {
+        *self.cluster_assignment.write().await = cluster_assignment;
+    }
+
+    /// Sets the stream to CARL, via which test events are forwarded. `None` while disconnected.
+    pub async fn set_stream(&self, tx_outbound: Option<broker::Upstream>) {
+        *self.tx_outbound.write().await = tx_outbound;
+    }
+}
+
+pub async fn spawn(options: LocalApiOptions, state: LocalApiStateRef) -> anyhow::Result<()> {
+    match options {
+        LocalApiOptions::Disabled => {
+            debug!("Local API is disabled.");
+        }
+        LocalApiOptions::Enabled { port } => {
+            let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port); //only reachable from the same host, as the API is not authenticated
+            let server = axum::Server::try_bind(&address)?;
+
+            info!("Serving local API on http://{address}/api/v1/");
+
+            tokio::spawn(async move {
+                server.serve(router(state).into_make_service()).await
+                    .unwrap_or_else(|cause| error!("Local API stopped with error:\n  {cause}"));
+            });
+        }
+    }
+    Ok(())
+}
+
+fn router(state: LocalApiStateRef) -> Router {
+    Router::new()
+        .route("/api/v1/peer", get(get_peer))
+        .route("/api/v1/cluster", get(get_cluster))
+        .route("/api/v1/devices", get(list_devices))
+        .route("/api/v1/test-events", post(post_test_event))
+        .with_state(state)
+}
+
+#[derive(serde::Serialize)]
+struct PeerInfo {
+    peer_id: String,
+    connected_to_carl: bool,
+    cluster_id: Option<String>,
+}
+
+async fn get_peer(State(state): State<LocalApiStateRef>) -> Json<PeerInfo> {
+    let connected_to_carl = state.tx_outbound.read().await
+        .as_ref()
+        .is_some_and(|tx_outbound| tx_outbound.is_closed().not());
+    let cluster_id = state.cluster_assignment.read().await
+        .as_ref()
+        .map(|cluster_assignment| cluster_assignment.id.to_string());
+
+    Json(PeerInfo {
+        peer_id: state.self_id.to_string(),
+        connected_to_carl,
+        cluster_id,
+    })
+}
+
+#[derive(serde::Serialize)]
+struct ClusterInfo {
+    cluster_id: String,
+    leader: String,
+    peers: Vec<ClusterPeerInfo>,
+}
+
+#[derive(serde::Serialize)]
+struct ClusterPeerInfo {
+    peer_id: String,
+    vpn_address: String,
+    can_server_port: u16,
+    device_interfaces: Vec<String>,
+}
+
+impl From<&ClusterAssignment> for ClusterInfo {
+    fn from(cluster_assignment: &ClusterAssignment) -> Self {
+        let peers = cluster_assignment.assignments.iter()
+            .map(|assignment| ClusterPeerInfo {
+                peer_id: assignment.peer_id.to_string(),
+                vpn_address: assignment.vpn_address.to_string(),
+                can_server_port: assignment.can_server_port.0,
+                device_interfaces: assignment.device_interfaces.iter()
+                    .map(|interface| interface.name.name())
+                    .collect(),
+            })
+            .collect();
+
+        ClusterInfo {
+            cluster_id: cluster_assignment.id.to_string(),
+            leader: cluster_assignment.leader.to_string(),
+            peers,
+        }
+    }
+}
+
+async fn get_cluster(State(state): State<LocalApiStateRef>) -> Result<Json<ClusterInfo>, (StatusCode, String)> {
+    let cluster_assignment = state.cluster_assignment.read().await;
+
+    match cluster_assignment.as_ref() {
+        Some(cluster_assignment) => Ok(Json(ClusterInfo::from(cluster_assignment))),
+        None => Err((StatusCode::NOT_FOUND, String::from("Peer is not assigned to a cluster."))),
+    }
+}
+
+#[derive(serde::Serialize)]
+struct DeviceInfo {
+    id: String,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    interface: Option<InterfaceInfo>,
+}
+
+#[derive(serde::Serialize)]
+struct InterfaceInfo {
+    name: String,
+    configuration: String,
+}
+
+async fn list_devices(State(state): State<LocalApiStateRef>) -> Result<Json<Vec<DeviceInfo>>, (StatusCode, String)> {
+    let mut carl = Clone::clone(&state.carl);
+
+    let peer_descriptor = carl.peers.get_peer_descriptor(state.self_id).await
+        .map_err(|cause| {
+            warn!("Local API could not retrieve the devices of this peer from CARL:\n  {cause}");
+            (StatusCode::BAD_GATEWAY, format!("Could not retrieve the devices of this peer from CARL: {cause}"))
+        })?;
+
+    let devices = peer_descriptor.topology.devices.into_iter()
+        .map(|device| {
+            let interface = peer_descriptor.network.interfaces.iter()
+                .find(|interface| interface.id == device.interface)
+                .map(|interface| InterfaceInfo {
+                    name: interface.name.name(),
+                    configuration: interface.configuration.to_string(),
+                });
+
+            DeviceInfo {
+                id: device.id.to_string(),
+                name: device.name.to_string(),
+                description: device.description.map(|description| description.to_string()),
+                tags: device.tags.iter().map(ToString::to_string).collect(),
+                interface,
+            }
+        })
+        .collect();
+
+    Ok(Json(devices))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TestEventRequest {
+    kind: TestEventKind,
+    name: String,
+    verdict: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TestEventKind {
+    Start,
+    Stop,
+}
+
+async fn post_test_event(State(state): State<LocalApiStateRef>, Json(request): Json<TestEventRequest>) -> Result<StatusCode, (StatusCode, String)> {
+    let cluster_id = state.cluster_assignment.read().await
+        .as_ref()
+        .map(|cluster_assignment| cluster_assignment.id);
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::TestEvent(
+            to_test_event(request, cluster_id, SystemTime::now())
+        )),
+        context: None,
+    };
+
+    let tx_outbound = state.tx_outbound.read().await.clone()
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, String::from("Not connected to CARL.")))?;
+
+    tx_outbound.send(message).await
+        .map_err(|_| (StatusCode::SERVICE_UNAVAILABLE, String::from("Not connected to CARL.")))?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn to_test_event(request: TestEventRequest, cluster_id: Option<ClusterId>, timestamp: SystemTime) -> peer_messaging_broker::TestEvent {
+    let TestEventRequest { kind, name, verdict } = request;
+
+    let kind = match kind {
+        TestEventKind::Start => test_event::Kind::Started(peer_messaging_broker::TestStarted {}),
+        TestEventKind::Stop => test_event::Kind::Stopped(peer_messaging_broker::TestStopped { verdict }),
+    };
+    let timestamp_unix_millis = timestamp.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default();
+
+    peer_messaging_broker::TestEvent {
+        cluster_id: cluster_id.map(Into::into),
+        name,
+        timestamp_unix_millis,
+        kind: Some(kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_stop_request_to_test_event_of_the_current_cluster() {
+        let cluster_id = ClusterId::random();
+        let request = TestEventRequest {
+            kind: TestEventKind::Stop,
+            name: String::from("hil-smoke-test"),
+            verdict: Some(String::from("passed")),
+        };
+
+        let test_event = to_test_event(request, Some(cluster_id), SystemTime::UNIX_EPOCH + Duration::from_millis(1234));
+
+        assert_that!(test_event, eq(&peer_messaging_broker::TestEvent {
+            cluster_id: Some(cluster_id.into()),
+            name: String::from("hil-smoke-test"),
+            timestamp_unix_millis: 1234,
+            kind: Some(test_event::Kind::Stopped(peer_messaging_broker::TestStopped { verdict: Some(String::from("passed")) })),
+        }));
+    }
+}
@@ -1,11 +1,25 @@
 pub mod start;
 pub mod network_interface;
 pub mod peer_configuration;
+pub mod bus;
+pub mod configuration_validation;
+pub mod self_test;
 
 mod cluster_assignment;
+mod cluster_hooks;
+mod config_reload;
+mod component_graph;
+mod data_plane_metrics;
+mod cluster_readiness;
+mod local_api;
 mod cannelloni_manager;
 mod can_manager;
 mod vpn;
 mod test_execution;
 mod network_metrics;
+mod network_manager_check;
+mod packet_capture;
+mod peer_command;
+mod shutdown;
+mod storage_quota;
 mod tasks;
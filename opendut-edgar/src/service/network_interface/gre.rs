@@ -5,9 +5,9 @@ use tracing::debug;
 use opendut_types::util::net::NetworkInterfaceName;
 
 use crate::service::network_interface;
-use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+use crate::service::network_interface::manager::{Interface, NetworkInterfaceManagerRef};
 
-const GRE_INTERFACE_NAME_PREFIX: &str = "gre-opendut";
+pub const GRE_INTERFACE_NAME_PREFIX: &str = "gre-opendut";
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -33,11 +33,9 @@ pub async fn setup_interfaces(
     Ok(())
 }
 
-async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<(), Error> {
+pub async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<(), Error> {
 
-    let interfaces_to_remove = network_interface_manager.list_interfaces().await?
-        .into_iter()
-        .filter(|interface| interface.name.name().starts_with(GRE_INTERFACE_NAME_PREFIX));
+    let interfaces_to_remove = list_existing_interfaces(Arc::clone(&network_interface_manager)).await?;
 
     for interface in interfaces_to_remove {
         network_interface_manager.delete_interface(&interface).await?;
@@ -46,6 +44,16 @@ async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceM
     Ok(())
 }
 
+pub async fn list_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<Vec<Interface>, Error> {
+
+    let interfaces = network_interface_manager.list_interfaces().await?
+        .into_iter()
+        .filter(|interface| interface.name.name().starts_with(GRE_INTERFACE_NAME_PREFIX))
+        .collect();
+
+    Ok(interfaces)
+}
+
 async fn create_interface(
     local_ip: &Ipv4Addr,
     remote_ip: &Ipv4Addr,
@@ -0,0 +1,126 @@
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use opendut_carl_api::carl::broker;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+
+use crate::common::network_managers;
+use crate::common::network_managers::Finding;
+
+#[derive(Clone, Debug)]
+pub enum NetworkManagerCheckOptions {
+    Enabled {
+        /// Time to wait between two checks.
+        interval: Duration,
+    },
+    Disabled,
+}
+
+impl NetworkManagerCheckOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("network.managers.check.enabled")?;
+
+        if enabled {
+            let interval = Duration::from_millis(config.get::<u64>("network.managers.check.interval.ms")?);
+            Ok(NetworkManagerCheckOptions::Enabled { interval })
+        } else {
+            Ok(NetworkManagerCheckOptions::Disabled)
+        }
+    }
+}
+
+/// Periodically checks whether third-party network managers interfere with the interfaces managed by EDGAR.
+/// Findings are logged, when they first occur, and reported to CARL, whenever they change.
+#[derive(Clone)]
+pub struct NetworkManagerCheck {
+    tx_outbound: Arc<RwLock<Option<broker::Upstream>>>,
+    /// Findings last reported via the current stream, `None` if nothing was reported yet.
+    reported: Arc<Mutex<Option<Vec<Finding>>>>,
+}
+
+impl NetworkManagerCheck {
+    pub fn spawn(options: NetworkManagerCheckOptions, interface_patterns: Vec<String>) -> Self {
+        let check = Self {
+            tx_outbound: Default::default(),
+            reported: Default::default(),
+        };
+
+        if let NetworkManagerCheckOptions::Enabled { interval } = options {
+            let check = Clone::clone(&check);
+            tokio::spawn(async move {
+                let mut logged: Vec<Finding> = Vec::new();
+                let mut interval = tokio::time::interval(interval);
+                loop {
+                    interval.tick().await;
+
+                    let findings = network_managers::detect(&interface_patterns).await;
+                    log_changes(&logged, &findings);
+                    logged = Clone::clone(&findings);
+
+                    check.report(findings).await;
+                }
+            });
+        } else {
+            debug!("Checking for interfering network managers is disabled.");
+        }
+
+        check
+    }
+
+    pub async fn set_stream(&self, tx_outbound: Option<broker::Upstream>) {
+        *self.tx_outbound.write().await = tx_outbound;
+        *self.reported.lock().await = None; //report again via the new stream
+    }
+
+    async fn report(&self, findings: Vec<Finding>) {
+        let mut reported = self.reported.lock().await;
+
+        let changed = match reported.as_ref() {
+            Some(reported) => reported != &findings,
+            None => findings.is_empty().not(),
+        };
+        if changed.not() {
+            return;
+        }
+
+        let message = peer_messaging_broker::Upstream {
+            message: Some(peer_messaging_broker::upstream::Message::NetworkManagerInterference(peer_messaging_broker::NetworkManagerInterference {
+                findings: findings.iter()
+                    .map(|finding| peer_messaging_broker::NetworkManagerFinding {
+                        manager: finding.manager.to_string(),
+                        interface: Clone::clone(&finding.interface),
+                        problem: Clone::clone(&finding.problem),
+                        remediation: Clone::clone(&finding.remediation),
+                    })
+                    .collect(),
+            })),
+            context: None,
+        };
+
+        match self.tx_outbound.read().await.as_ref() {
+            Some(tx_outbound) => {
+                match tx_outbound.send(message).await {
+                    Ok(()) => *reported = Some(findings),
+                    Err(cause) => warn!("Failed to report interfering network managers to CARL: {cause}"),
+                }
+            }
+            None => debug!("Not connected to CARL. Not reporting interfering network managers."),
+        }
+    }
+}
+
+fn log_changes(previous: &[Finding], current: &[Finding]) {
+    for finding in current {
+        if previous.contains(finding).not() {
+            warn!("Detected third-party network manager interfering with EDGAR: {finding}");
+        }
+    }
+    if previous.is_empty().not() && current.is_empty() {
+        info!("Third-party network managers no longer interfere with EDGAR.");
+    }
+}
+
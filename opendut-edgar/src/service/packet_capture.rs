@@ -0,0 +1,278 @@
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::test_execution::upload_queue::{UploadKind, UploadQueue};
+
+/// Time to wait after starting tcpdump, before checking that it is still running, e.g. because an interface does not exist.
+const STARTUP_CHECK_DELAY: Duration = Duration::from_millis(500);
+
+const PCAP_FILE_EXTENSION: &str = "pcap";
+
+#[derive(Clone, Debug)]
+pub struct PacketCaptureOptions {
+    directory: PathBuf,
+    interfaces: Vec<NetworkInterfaceName>,
+    rotation_size_megabytes: u64,
+    rotation_files: u64,
+    /// WebDAV collection, which the files are uploaded to, when the capture is stopped.
+    upload_url: Option<Url>,
+}
+
+impl PacketCaptureOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let interfaces = config.get::<Vec<String>>("packet.capture.interfaces")?
+            .into_iter()
+            .map(NetworkInterfaceName::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let interfaces = if interfaces.is_empty() {
+            vec![crate::common::default_bridge_name(), crate::common::default_can_bridge_name()]
+        } else {
+            interfaces
+        };
+
+        let upload_url = config.get_string("packet.capture.upload.url")?;
+        let upload_url = if upload_url.is_empty() {
+            None
+        } else {
+            Some(Url::parse(&upload_url)?)
+        };
+
+        Ok(Self {
+            directory: PathBuf::from(config.get_string("packet.capture.directory")?),
+            interfaces,
+            rotation_size_megabytes: config.get::<u64>("packet.capture.rotation.size.megabytes")?,
+            rotation_files: config.get::<u64>("packet.capture.rotation.files")?,
+            upload_url,
+        })
+    }
+}
+
+/// Captures the packets on the bridge and the CAN interfaces of the peer, while started via a command from CARL.
+/// For each interface, tcpdump writes rotating pcap files into a directory per capture, which are uploaded via the upload queue, when the capture is stopped.
+#[derive(Clone)]
+pub struct PacketCapture {
+    self_id: PeerId,
+    options: PacketCaptureOptions,
+    upload_queue: UploadQueue,
+    running: Arc<Mutex<Option<RunningCapture>>>,
+}
+
+struct RunningCapture {
+    name: String,
+    directory: PathBuf,
+    started: Instant,
+    processes: Vec<(NetworkInterfaceName, Child)>,
+}
+
+impl PacketCapture {
+    pub fn new(self_id: PeerId, options: PacketCaptureOptions, upload_queue: UploadQueue) -> Self {
+        Self {
+            self_id,
+            options,
+            upload_queue,
+            running: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Starts tcpdump for each configured interface. Returns the output for CARL or the cause why the capture could not be started.
+    pub async fn start(&self) -> Result<String, String> {
+        let mut running = self.running.lock().await;
+        if let Some(capture) = running.as_ref() {
+            return Err(format!("Packet capture '{}' is already running since {} seconds. Stop it first.", capture.name, capture.started.elapsed().as_secs()));
+        }
+
+        let name = format!("capture-{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ"));
+        let directory = self.options.directory.join(&name);
+        fs::create_dir_all(&directory).await
+            .map_err(|cause| format!("Failed to create directory '{}' for packet capture: {cause}", directory.display()))?;
+
+        let mut processes = Vec::new();
+        for interface in &self.options.interfaces {
+            match self.spawn_tcpdump(interface, &directory) {
+                Ok(child) => processes.push((Clone::clone(interface), child)),
+                Err(cause) => {
+                    terminate(processes).await;
+                    return Err(cause);
+                }
+            }
+        }
+
+        tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+
+        let mut failure = None;
+        for (interface, child) in processes.iter_mut() {
+            if let Ok(Some(status)) = child.try_wait() {
+                let mut stderr = String::new();
+                if let Some(mut output) = child.stderr.take() {
+                    let _ignore_error = output.read_to_string(&mut stderr).await;
+                }
+                failure = Some(format!("Capturing packets on '{interface}' failed with {status}: {}", stderr.trim()));
+                break;
+            }
+        }
+        if let Some(cause) = failure {
+            terminate(processes).await;
+            return Err(cause);
+        }
+
+        let interfaces = self.options.interfaces.iter()
+            .map(|interface| interface.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        info!("Started packet capture '{name}' on {interfaces} into '{}'.", directory.display());
+        let output = format!("Started packet capture '{name}' on {interfaces} into '{}'.", directory.display());
+
+        *running = Some(RunningCapture { name, directory, started: Instant::now(), processes });
+        Ok(output)
+    }
+
+    /// Stops tcpdump and queues the pcap files for upload, if an upload URL is configured.
+    pub async fn stop(&self) -> Result<String, String> {
+        let RunningCapture { name, directory, started, processes } = self.running.lock().await.take()
+            .ok_or_else(|| String::from("No packet capture is running."))?;
+
+        terminate(processes).await;
+
+        let files = rename_capture_files(&directory).await
+            .map_err(|cause| format!("Failed to list the files of packet capture '{name}' in '{}': {cause}", directory.display()))?;
+        let size_bytes = files.iter().map(|(_, size_bytes)| size_bytes).sum::<u64>();
+
+        info!("Stopped packet capture '{name}' after {} seconds with {} file(s) and {size_bytes} bytes.", started.elapsed().as_secs(), files.len());
+        let mut output = format!("Stopped packet capture '{name}' after {} seconds. Wrote {} file(s) with {size_bytes} bytes to '{}'.", started.elapsed().as_secs(), files.len(), directory.display());
+
+        if let Some(upload_url) = &self.options.upload_url {
+            let collection = upload_collection(upload_url, self.self_id, &name)?;
+
+            let mut queued = 0;
+            for (path, _) in &files {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let result = match fs::read(path).await {
+                    Ok(data) => self.upload_queue.enqueue(UploadKind::PacketCaptures, Clone::clone(&collection), &file_name, data).await
+                        .map_err(|cause| cause.to_string()),
+                    Err(cause) => Err(cause.to_string()),
+                };
+                match result {
+                    Ok(()) => queued += 1,
+                    Err(cause) => warn!("Failed to queue '{}' of packet capture '{name}' for upload: {cause}", path.display()),
+                }
+            }
+            output.push_str(&format!(" Queued {queued} file(s) for upload to '{collection}'."));
+        }
+
+        Ok(output)
+    }
+
+    fn spawn_tcpdump(&self, interface: &NetworkInterfaceName, directory: &Path) -> Result<Child, String> {
+        let file = directory.join(format!("{interface}.{PCAP_FILE_EXTENSION}"));
+
+        let mut command = Command::new("tcpdump");
+        command
+            .arg("-i").arg(interface.name())
+            .arg("-w").arg(&file)
+            .arg("-C").arg(self.options.rotation_size_megabytes.to_string())
+            .arg("-W").arg(self.options.rotation_files.to_string())
+            .arg("-U") //write each packet immediately, so that the files are complete, when tcpdump is killed
+            .arg("-n")
+            .arg("-Z").arg("root") //keep writing into the directory of EDGAR, instead of dropping privileges to the tcpdump user
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        debug!("Starting packet capture: {command:?}");
+        command.spawn()
+            .map_err(|cause| format!("Failed to execute 'tcpdump' for capturing packets on '{interface}': {cause}"))
+    }
+}
+
+async fn terminate(processes: Vec<(NetworkInterfaceName, Child)>) {
+    for (interface, mut child) in processes {
+        if let Err(cause) = child.kill().await {
+            warn!("Failed to stop capturing packets on '{interface}': {cause}");
+        }
+    }
+}
+
+/// Renames the files, which tcpdump numbered by appending the number to the file name, so that they keep the pcap extension.
+/// Returns the files with their size.
+async fn rename_capture_files(directory: &Path) -> std::io::Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut read_dir = fs::read_dir(directory).await?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+
+        let path = match pcap_file_name(&file_name) {
+            Some(renamed) => {
+                let renamed = directory.join(renamed);
+                fs::rename(&path, &renamed).await?;
+                renamed
+            }
+            None => path,
+        };
+        let size_bytes = fs::metadata(&path).await?.len();
+        files.push((path, size_bytes));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Turns a file name like "br-opendut.pcap3" into "br-opendut-3.pcap". Returns `None` for other file names.
+fn pcap_file_name(file_name: &str) -> Option<String> {
+    let (stem, number) = file_name.rsplit_once(&format!(".{PCAP_FILE_EXTENSION}"))?;
+    if number.is_empty() || number.chars().any(|character| character.is_ascii_digit().not()) {
+        return None;
+    }
+    Some(format!("{stem}-{number}.{PCAP_FILE_EXTENSION}"))
+}
+
+/// Collection for the files of one capture, e.g. "https://webdav.example/captures/<PeerID>/capture-20241105T100000Z/".
+fn upload_collection(upload_url: &Url, peer_id: PeerId, capture_name: &str) -> Result<Url, String> {
+    let peer_id = peer_id.to_string();
+    let mut collection = Clone::clone(upload_url);
+    collection.path_segments_mut()
+        .map_err(|_| format!("Upload URL '{upload_url}' of packet captures cannot be used as a base URL."))?
+        .pop_if_empty()
+        .extend([peer_id.as_str(), capture_name, ""]);
+    Ok(collection)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_keep_the_pcap_extension_of_rotated_files() {
+        assert_that!(pcap_file_name("br-opendut.pcap0"), some(eq("br-opendut-0.pcap")));
+        assert_that!(pcap_file_name("br-vcan-opendut.pcap12"), some(eq("br-vcan-opendut-12.pcap")));
+        assert_that!(pcap_file_name("br-opendut.pcap"), none());
+        assert_that!(pcap_file_name("notes.txt"), none());
+    }
+
+    #[test]
+    fn should_upload_each_capture_into_its_own_collection() -> anyhow::Result<()> {
+        let peer_id = PeerId::random();
+
+        let collection = upload_collection(&Url::parse("https://webdav.example/captures")?, peer_id, "capture-1")?;
+        assert_that!(collection.as_str(), eq(&format!("https://webdav.example/captures/{peer_id}/capture-1/")));
+
+        let collection = upload_collection(&Url::parse("https://webdav.example/captures/")?, peer_id, "capture-1")?;
+        assert_that!(collection.as_str(), eq(&format!("https://webdav.example/captures/{peer_id}/capture-1/")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+use opendut_carl_api::carl::peer::PeerCommand;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::{peer_command_result, PeerCommandFailed, PeerCommandResult, PeerCommandSucceeded};
+
+use crate::service::packet_capture::PacketCapture;
+use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
+
+/// Time between reporting the result of a restart or reboot to CARL and executing it, so that the result is still delivered.
+const DISRUPTIVE_COMMAND_DELAY: Duration = Duration::from_secs(2);
+
+/// Number of journal lines of the EDGAR service, which are included in the diagnostics bundle.
+const DIAGNOSTICS_JOURNAL_LINES: &str = "5000";
+
+/// Number of journal lines of the EDGAR service, which are returned by the command to collect logs.
+const COLLECTED_JOURNAL_LINES: &str = "1000";
+
+/// Executes a command sent by CARL in the background and reports its result, so that other messages are not held up meanwhile.
+/// `applied_configuration` is the textual representation of the last configuration received from CARL, for the diagnostics bundle.
+pub fn handle(
+    message: peer_messaging_broker::PeerCommand,
+    applied_configuration: Option<String>,
+    packet_capture: &PacketCapture,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+) {
+    let packet_capture = Clone::clone(packet_capture);
+    let tx_outbound = Clone::clone(tx_outbound);
+    tokio::spawn(async move {
+        let peer_messaging_broker::PeerCommand { id, kind } = message;
+
+        let command = kind.ok_or_else(|| String::from("No command specified."))
+            .and_then(|kind| PeerCommand::try_from(kind).map_err(|cause| cause.to_string()));
+
+        let outcome = match command {
+            Ok(command) => {
+                info!("Executing command '{command}' sent by CARL.");
+                execute(command, applied_configuration, &packet_capture, &tx_outbound).await
+            }
+            Err(cause) => Err(format!("Received invalid command: {cause}")),
+        };
+
+        let outcome = match outcome {
+            Ok((output, attachment)) => peer_command_result::Outcome::Succeeded(PeerCommandSucceeded { output, attachment }),
+            Err(cause) => {
+                error!("Failed to execute command <{id}> sent by CARL: {cause}");
+                peer_command_result::Outcome::Failed(PeerCommandFailed { cause })
+            }
+        };
+
+        let message = peer_messaging_broker::Upstream {
+            message: Some(peer_messaging_broker::upstream::Message::PeerCommandResult(PeerCommandResult { id, outcome: Some(outcome) })),
+            context: None,
+        };
+        let _ignore_error =
+            tx_outbound.send(message).await
+                .inspect_err(|cause| warn!("Failed to report result of command <{id}> to CARL: {cause}"));
+    });
+}
+
+/// Returns the output of the command and an optional attachment, or the cause why it failed.
+async fn execute(
+    command: PeerCommand,
+    applied_configuration: Option<String>,
+    packet_capture: &PacketCapture,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+) -> Result<(String, Vec<u8>), String> {
+    match command {
+        PeerCommand::RestartService => {
+            schedule_disruptive("systemctl", &["restart", SYSTEMD_SERVICE_FILE_NAME]);
+            Ok((format!("Restarting '{SYSTEMD_SERVICE_FILE_NAME}' in {} seconds.", DISRUPTIVE_COMMAND_DELAY.as_secs()), vec![]))
+        }
+        PeerCommand::ReapplyConfiguration => {
+            let message = peer_messaging_broker::Upstream {
+                message: Some(peer_messaging_broker::upstream::Message::RequestPeerConfiguration(peer_messaging_broker::RequestPeerConfiguration {})),
+                context: None,
+            };
+            tx_outbound.send(message).await
+                .map_err(|cause| format!("Failed to request the configuration from CARL: {cause}"))?;
+            Ok((String::from("Requested the configuration from CARL, which is applied once received."), vec![]))
+        }
+        PeerCommand::CollectDiagnostics => {
+            let bundle = collect_diagnostics(applied_configuration).await?;
+            Ok((format!("Collected diagnostics bundle with {} bytes.", bundle.len()), bundle))
+        }
+        PeerCommand::RebootHost => {
+            schedule_disruptive("systemctl", &["reboot"]);
+            Ok((format!("Rebooting host in {} seconds.", DISRUPTIVE_COMMAND_DELAY.as_secs()), vec![]))
+        }
+        PeerCommand::CollectLogs => {
+            let logs = collect_logs().await?;
+            Ok((logs, vec![]))
+        }
+        PeerCommand::StartPacketCapture => {
+            let output = packet_capture.start().await?;
+            Ok((output, vec![]))
+        }
+        PeerCommand::StopPacketCapture => {
+            let output = packet_capture.stop().await?;
+            Ok((output, vec![]))
+        }
+    }
+}
+
+/// Runs the command after a delay, as it interrupts the connection to CARL, before the result could be reported otherwise.
+fn schedule_disruptive(program: &'static str, args: &'static [&'static str]) {
+    tokio::spawn(async move {
+        tokio::time::sleep(DISRUPTIVE_COMMAND_DELAY).await;
+        let mut command = Command::new(program);
+        command.args(args);
+        match command.status().await {
+            Ok(status) if status.success() => debug!("Executed {command:?}."),
+            Ok(status) => error!("Executing {command:?} failed with {status}."),
+            Err(cause) => error!("Failed to execute {command:?}: {cause}"),
+        }
+    });
+}
+
+/// Returns the recent lines of the journal of the EDGAR service, which also contains the output of the executors.
+/// Each line starts with its timestamp in ISO 8601 format with microseconds, so that the lines of multiple peers can be interleaved.
+async fn collect_logs() -> Result<String, String> {
+    let output = Command::new("journalctl")
+        .args(["--unit", SYSTEMD_SERVICE_FILE_NAME, "--lines", COLLECTED_JOURNAL_LINES, "--output", "short-iso-precise", "--no-pager", "--quiet"])
+        .output().await
+        .map_err(|cause| format!("Failed to execute 'journalctl': {cause}"))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(format!("Failed to read the journal with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Collects logs, network state and version information into an in-memory .tar.gz archive.
+/// Commands, which cannot be executed, are noted in their file instead of failing the whole bundle.
+async fn collect_diagnostics(applied_configuration: Option<String>) -> Result<Vec<u8>, String> {
+    let mut files = vec![
+        (String::from("version.txt"), String::from(crate::app_info::formatted())),
+        (String::from("peer-configuration.txt"), applied_configuration.unwrap_or_else(|| String::from("No configuration received from CARL yet."))),
+    ];
+
+    let commands: [(&str, &str, &[&str]); 4] = [
+        ("journal.txt", "journalctl", &["--unit", SYSTEMD_SERVICE_FILE_NAME, "--lines", DIAGNOSTICS_JOURNAL_LINES, "--no-pager"]),
+        ("ip-address.txt", "ip", &["address"]),
+        ("ip-link.txt", "ip", &["-details", "link"]),
+        ("service-status.txt", "systemctl", &["status", SYSTEMD_SERVICE_FILE_NAME, "--no-pager"]),
+    ];
+    for (file_name, program, args) in commands {
+        let content = match Command::new(program).args(args).output().await {
+            Ok(output) => format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+            Err(cause) => format!("Failed to execute '{program}': {cause}"),
+        };
+        files.push((String::from(file_name), content));
+    }
+
+    create_archive(files)
+        .map_err(|cause| format!("Failed to create diagnostics bundle: {cause}"))
+}
+
+fn create_archive(files: Vec<(String, String)>) -> std::io::Result<Vec<u8>> {
+    let modified = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (file_name, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(modified);
+        header.set_cksum();
+        archive.append_data(&mut header, format!("diagnostics/{file_name}"), content.as_bytes())?;
+    }
+    let mut encoder = archive.into_inner()?;
+    encoder.flush()?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn should_pack_the_files_into_a_tar_gz_archive() -> anyhow::Result<()> {
+        let bundle = create_archive(vec![
+            (String::from("version.txt"), String::from("1.2.3")),
+            (String::from("ip-address.txt"), String::from("1: lo: <LOOPBACK,UP>")),
+        ])?;
+
+        let mut archive = tar::Archive::new(GzDecoder::new(bundle.as_slice()));
+        let mut files = vec![];
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            files.push((entry.path()?.display().to_string(), content));
+        }
+
+        assert_eq!(files, vec![
+            (String::from("diagnostics/version.txt"), String::from("1.2.3")),
+            (String::from("diagnostics/ip-address.txt"), String::from("1: lo: <LOOPBACK,UP>")),
+        ]);
+        Ok(())
+    }
+}
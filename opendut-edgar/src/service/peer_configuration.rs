@@ -1,17 +1,23 @@
 use std::fmt::Formatter;
-use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
+use anyhow::anyhow;
+use futures::FutureExt;
+use opendut_types::cluster::{ClusterAssignment, ClusterId, PeerClusterAssignment};
 use opendut_types::util::net::NetworkInterfaceName;
-use tracing::{debug, error, info, trace, warn};
+use tracing::{debug, error, info, trace};
 use std::sync::Arc;
 use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
 use opendut_util::project;
 use opendut_types::peer::PeerId;
+use opendut_types::peer::hook::ClusterHookStage;
 use std::time::Duration;
 use std::ops::Not;
 use tokio::sync::mpsc;
 use crate::common::task::{runner, Task};
-use crate::service::{cluster_assignment, network_metrics, tasks};
-use crate::service::can_manager::CanManagerRef;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use crate::service::{cluster_assignment, cluster_hooks, cluster_readiness, component_graph, network_metrics, tasks};
+use crate::service::component_graph::{Component, ComponentGraph};
+use crate::service::bus::{BusRef, ClusterContext};
+use crate::service::cluster_readiness::{AwaitNetworkReadinessParams, ClusterReadinessOptions, NetworkReadiness, PingProbe};
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 use crate::service::test_execution::executor_manager::ExecutorManagerRef;
 use crate::setup::RunMode;
@@ -24,10 +30,12 @@ pub struct ApplyPeerConfigurationParams {
     pub network_interface_management: NetworkInterfaceManagement,
     pub executor_manager: ExecutorManagerRef,
     pub cluster_metrics_options: ClusterMetricsOptions,
+    pub cluster_readiness_options: ClusterReadinessOptions,
+    pub tx_outbound: mpsc::Sender<peer_messaging_broker::Upstream>,
 }
 #[derive(Clone)]
 pub enum NetworkInterfaceManagement {
-    Enabled { network_interface_manager: NetworkInterfaceManagerRef, can_manager: CanManagerRef },
+    Enabled { network_interface_manager: NetworkInterfaceManagerRef, buses: Vec<BusRef> },
     Disabled,
 }
 impl std::fmt::Debug for NetworkInterfaceManagement {
@@ -48,22 +56,37 @@ pub struct ClusterMetricsOptions {
 
 pub async fn spawn_peer_configurations_handler(mut rx_peer_configuration: mpsc::Receiver<ApplyPeerConfigurationParams>) -> anyhow::Result<()> {
     tokio::spawn(async move {
+        let mut joined_cluster = None;
         while let Some(apply_peer_configuration_params) = rx_peer_configuration.recv().await {
-            apply_peer_configuration(apply_peer_configuration_params).await
+            apply_peer_configuration(apply_peer_configuration_params, &mut joined_cluster).await
                 .expect("Error while applying peer configuration.");
         }
     });
     Ok(())
 }
 
+/// Applies the components of the PeerConfiguration concurrently, as far as they do not depend on each other:
+/// ```text
+/// ethernet-bridges ──> cluster-network ──┐
+///                                        ├──> executors
+/// executor-termination ──────────────────┘
+/// cluster-metrics
+/// ```
+/// `joined_cluster` remembers the cluster, whose network was joined by an earlier PeerConfiguration,
+/// so that the cluster hooks only run when the peer actually joins or leaves a cluster.
 #[tracing::instrument(skip_all)]
-async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyhow::Result<()> {
-    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, cluster_metrics_options } = params;
+async fn apply_peer_configuration(params: ApplyPeerConfigurationParams, joined_cluster: &mut Option<ClusterId>) -> anyhow::Result<()> {
+    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, cluster_metrics_options, cluster_readiness_options, tx_outbound } = params;
 
-    {
+    let maybe_bridge = peer_configuration.ethernet_bridges.iter()
+        .find(|bridge| bridge.target == ParameterTarget::Present); //we currently expect only one bridge to be Present (for one cluster)
+
+    let mut graph = ComponentGraph::default();
+
+    graph.add(Component::EthernetBridges, &[], async {
         let mut tasks: Vec<Box<dyn Task>> = vec![];
 
-        if let NetworkInterfaceManagement::Enabled { network_interface_manager, can_manager: _ } = &network_interface_management {
+        if let NetworkInterfaceManagement::Enabled { network_interface_manager, buses: _ } = &network_interface_management {
             for parameter in peer_configuration.ethernet_bridges.iter().cloned() {
                 tasks.push(Box::new(tasks::create_ethernet_bridge::CreateEthernetBridge {
                     parameter,
@@ -72,39 +95,85 @@ async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyho
             }
         }
 
-        runner::run(RunMode::Service, &tasks).await?;
-    }
+        runner::run(RunMode::Service, tasks).await
+    }.boxed());
 
-    {
-        let maybe_bridge = peer_configuration.ethernet_bridges.iter()
-            .find(|bridge| bridge.target == ParameterTarget::Present); //we currently expect only one bridge to be Present (for one cluster)
+    graph.add(Component::ExecutorTermination, &[], async {
+        executor_manager.lock().unwrap().terminate_executors();
+        Ok(())
+    }.boxed());
 
+    graph.add(Component::ClusterNetwork, &[Component::EthernetBridges], async {
         match maybe_bridge {
             Some(bridge) => {
+                let assigned_cluster = old_peer_configuration.cluster_assignment.as_ref()
+                    .map(|cluster_assignment| cluster_assignment.id);
+
+                if let Some(cluster_id) = assigned_cluster {
+                    if *joined_cluster != Some(cluster_id) {
+                        cluster_hooks::run(&peer_configuration.cluster_hooks, ClusterHookStage::BeforeJoin, cluster_id, self_id).await
+                            .map_err(|cause| anyhow!("Not joining cluster <{cluster_id}>, because a cluster hook failed: {cause}"))?;
+                    }
+                }
+
                 let _ = setup_cluster(
                     &old_peer_configuration.cluster_assignment,
                     self_id,
-                    network_interface_management,
+                    network_interface_management.clone(),
+                    &bridge.value.name,
+                ).await;
+
+                let left_cluster = std::mem::replace(joined_cluster, assigned_cluster)
+                    .filter(|_| assigned_cluster.is_none());
+
+                if let Some(cluster_id) = left_cluster {
+                    cluster_hooks::run(&peer_configuration.cluster_hooks, ClusterHookStage::AfterLeave, cluster_id, self_id).await
+                        .map_err(|cause| anyhow!("A cluster hook failed after leaving cluster <{cluster_id}>: {cause}"))?;
+                }
+
+                let network_readiness = verify_cluster_network(
+                    &old_peer_configuration.cluster_assignment,
+                    self_id,
+                    &network_interface_management,
                     &bridge.value.name,
+                    &cluster_readiness_options,
+                    &tx_outbound,
                 ).await;
+
+                match network_readiness {
+                    Some(NetworkReadiness::NetworkNotReady { .. }) => {
+                        Err(anyhow!("The network of the cluster is not ready, so executors are not started. Redeploy the cluster to try again."))
+                    }
+                    Some(NetworkReadiness::Ready) | None => Ok(()),
+                }
             }
             None => {
                 debug!("PeerConfiguration contained no info for bridge. Not setting up cluster.");
+                Ok(())
             }
         }
-    }
+    }.boxed());
+
+    graph.add(Component::Executors, &[Component::ExecutorTermination, Component::ClusterNetwork], async {
+        let bridge_name = maybe_bridge
+            .map(|bridge| Clone::clone(&bridge.value.name))
+            .unwrap_or_else(crate::common::default_bridge_name);
+        executor_manager.lock().unwrap().create_new_executors(Clone::clone(&peer_configuration.executors), &bridge_name);
+        Ok(())
+    }.boxed());
 
-    let mut executor_manager = executor_manager.lock().unwrap();
-    executor_manager.terminate_executors();
-    executor_manager.create_new_executors(peer_configuration.executors);
+    graph.add(Component::ClusterMetrics, &[], async {
+        setup_cluster_metrics(
+            &old_peer_configuration.cluster_assignment,
+            self_id,
+            cluster_metrics_options.clone(),
+        )
+    }.boxed());
 
-    setup_cluster_metrics(
-        &old_peer_configuration.cluster_assignment,
-        self_id,
-        cluster_metrics_options.clone(),
-    )?;
+    let statuses = graph.apply().await;
+    component_graph::report(&statuses, &tx_outbound).await;
 
-    debug!("Peer configuration has been successfully applied.");
+    debug!("Peer configuration has been applied.");
     Ok(())
 }
 
@@ -121,41 +190,80 @@ async fn setup_cluster( //TODO make idempotent
             trace!("Received ClusterAssignment: {cluster_assignment:?}");
             info!("Was assigned to cluster <{}>", cluster_assignment.id);
 
-            if let NetworkInterfaceManagement::Enabled { network_interface_manager, can_manager } = &network_interface_management {
-                cluster_assignment::setup_ethernet_gre_interfaces(
+            if let NetworkInterfaceManagement::Enabled { network_interface_manager: _, buses } = &network_interface_management {
+                let cluster = ClusterContext {
                     cluster_assignment,
                     self_id,
                     bridge_name,
-                    Arc::clone(network_interface_manager),
-                ).await
-                .inspect_err(|error| error!("Failed to configure Ethernet GRE interfaces: {error}"))?;
+                };
 
-                cluster_assignment::join_ethernet_interfaces_to_bridge(
-                    cluster_assignment,
-                    self_id,
-                    bridge_name,
-                    Arc::clone(network_interface_manager),
-                ).await
-                .inspect_err(|error| error!("Failed to join Ethernet interfaces to bridge: {error}"))?;
+                for bus in buses {
+                    bus.setup(&cluster).await
+                        .inspect_err(|error| error!("Failed to set up {} bus: {error}", bus.kind()))?;
 
-                cluster_assignment::setup_can_interfaces(
-                    cluster_assignment,
-                    self_id,
-                    Arc::clone(can_manager),
-                ).await
-                .inspect_err(|error| error!("Failed to configure CAN interfaces: {error}"))?;
+                    bus.join_cluster(&cluster).await
+                        .inspect_err(|error| error!("Failed to join {} bus to cluster: {error}", bus.kind()))?;
+                }
             } else {
                 debug!("Skipping changes to network interfaces after receiving ClusterAssignment, as this is disabled via configuration.");
             }
         }
         None => {
             debug!("No ClusterAssignment in peer configuration.");
-            //TODO teardown cluster, if configuration changed
+
+            if let NetworkInterfaceManagement::Enabled { network_interface_manager: _, buses } = &network_interface_management {
+                for bus in buses {
+                    bus.teardown().await
+                        .inspect_err(|error| error!("Failed to tear down {} bus: {error}", bus.kind()))?;
+                }
+            }
         }
     }
     Ok(())
 }
 
+/// Waits for the network of the assigned cluster to become usable, before executors are started.
+/// Returns `None`, if there is no cluster to verify or the verification is disabled.
+#[tracing::instrument(skip_all)]
+async fn verify_cluster_network(
+    cluster_assignment: &Option<ClusterAssignment>,
+    self_id: PeerId,
+    network_interface_management: &NetworkInterfaceManagement,
+    bridge_name: &NetworkInterfaceName,
+    cluster_readiness_options: &ClusterReadinessOptions,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+) -> Option<NetworkReadiness> {
+
+    let cluster_assignment = cluster_assignment.as_ref()?;
+
+    let ClusterReadinessOptions::Enabled { timeout, probe_interval, probe_timeout } = cluster_readiness_options else {
+        debug!("Verification of the cluster network is disabled via configuration.");
+        return None;
+    };
+
+    let buses = match network_interface_management {
+        NetworkInterfaceManagement::Enabled { network_interface_manager: _, buses } => buses.as_slice(),
+        NetworkInterfaceManagement::Disabled => &[],
+    };
+
+    let readiness = cluster_readiness::await_network_readiness(
+        AwaitNetworkReadinessParams {
+            cluster_assignment,
+            self_id,
+            buses,
+            bridge_name,
+            timeout: *timeout,
+            probe_interval: *probe_interval,
+            probe_timeout: *probe_timeout,
+        },
+        &PingProbe,
+    ).await;
+
+    cluster_readiness::report(&readiness, cluster_assignment.id, tx_outbound).await;
+
+    Some(readiness)
+}
+
 #[tracing::instrument(skip_all)]
 fn setup_cluster_metrics( //TODO make idempotent
     cluster_assignment: &Option<ClusterAssignment>,
@@ -0,0 +1,308 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_netbird_client_api::extension::LocalPeerStateExtension;
+use opendut_types::peer::configuration::validation::CheckOutcome;
+use opendut_types::peer::executor::container::{CommandName, Engine};
+use opendut_types::peer::executor::ExecutorKind;
+use opendut_types::peer::self_test::{SelfTestCheck, SelfTestReport};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
+use opendut_util::settings::LoadedConfig;
+use tokio::process::Command;
+
+use crate::common::{carl, settings};
+use crate::service::cluster_readiness::{PingProbe, ReachabilityProbe};
+use crate::service::network_interface::manager::NetworkInterfaceManager;
+use crate::service::vpn::VpnConfig;
+
+const STAGE_CARL: &str = "carl";
+const STAGE_VPN: &str = "vpn";
+const STAGE_DEVICES: &str = "devices";
+const STAGE_CONTAINER_ENGINE: &str = "container-engine";
+const STAGE_EXECUTOR: &str = "executor";
+
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+/// Printed by the test executor, to tell its output apart from output of the container engine.
+const EXECUTOR_MARKER: &str = "opendut-edgar-self-test";
+
+pub struct SelfTestOptions {
+    /// Image of a container, which is run once to verify that executors can be started, e.g. "busybox". Skipped, if not set.
+    pub executor_image: Option<String>,
+}
+
+pub struct SelfTestOutcome {
+    pub report: SelfTestReport,
+    /// Connection to CARL for uploading the report, if CARL could be reached.
+    pub carl: Option<CarlClient>,
+}
+
+/// Verifies the chain, which a cluster relies on, after the setup of this peer: CARL, the VPN, the devices, the container engine and optionally a test executor.
+/// Stages, which depend on a failed stage, are reported as skipped.
+pub async fn run(settings: &LoadedConfig, self_id: PeerId, options: &SelfTestOptions) -> SelfTestOutcome {
+    let mut checks = Checks::default();
+
+    let (carl, peer_descriptor) = check_carl(&mut checks, settings, self_id).await;
+
+    check_vpn(&mut checks, settings).await;
+
+    match &peer_descriptor {
+        Some(peer_descriptor) => check_devices(&mut checks, peer_descriptor).await,
+        None => checks.add(STAGE_DEVICES, "devices of this peer are accessible", CheckOutcome::Skipped { reason: String::from("The devices could not be retrieved from CARL.") }),
+    }
+
+    let engines = check_container_engines(&mut checks, peer_descriptor.as_ref()).await;
+
+    match (&options.executor_image, engines.first()) {
+        (Some(image), Some(engine)) => check_executor(&mut checks, engine, image).await,
+        (Some(image), None) => checks.add(STAGE_EXECUTOR, format!("container from image '{image}' runs"), CheckOutcome::Skipped { reason: String::from("No container engine is available.") }),
+        (None, _) => checks.add(STAGE_EXECUTOR, "test executor runs", CheckOutcome::Skipped { reason: String::from("No image for the test executor was specified.") }),
+    }
+
+    let report = SelfTestReport::new(self_id, SystemTime::now(), checks.0);
+    SelfTestOutcome { report, carl }
+}
+
+/// Renders the report for printing it on the console, one line per check.
+pub fn format_report(report: &SelfTestReport) -> String {
+    let mut lines = report.checks.iter()
+        .map(|check| match &check.outcome {
+            CheckOutcome::Passed => format!("PASS  [{}] {}", check.stage, check.subject),
+            CheckOutcome::Failed { cause } => format!("FAIL  [{}] {}: {cause}", check.stage, check.subject),
+            CheckOutcome::Skipped { reason } => format!("SKIP  [{}] {}: {reason}", check.stage, check.subject),
+        })
+        .collect::<Vec<_>>();
+
+    let verdict = if report.passed { "PASSED" } else { "FAILED" };
+    lines.push(format!("\nSelf-test of peer <{}> {verdict}.", report.peer_id));
+    lines.join("\n")
+}
+
+#[derive(Default)]
+struct Checks(Vec<SelfTestCheck>);
+impl Checks {
+    fn add(&mut self, stage: &str, subject: impl Into<String>, outcome: CheckOutcome) {
+        self.0.push(SelfTestCheck { stage: stage.to_owned(), subject: subject.into(), outcome });
+    }
+}
+
+async fn check_carl(checks: &mut Checks, settings: &LoadedConfig, self_id: PeerId) -> (Option<CarlClient>, Option<PeerDescriptor>) {
+    let mut carl = match carl::connect(&settings.config).await {
+        Ok(carl) => {
+            checks.add(STAGE_CARL, "CARL is reachable and accepts the credentials of this peer", CheckOutcome::Passed);
+            carl
+        }
+        Err(cause) => {
+            checks.add(STAGE_CARL, "CARL is reachable and accepts the credentials of this peer", CheckOutcome::Failed { cause: format!("{cause:#}") });
+            return (None, None);
+        }
+    };
+
+    let peer_descriptor = match carl.peers.get_peer_descriptor(self_id).await {
+        Ok(peer_descriptor) => {
+            checks.add(STAGE_CARL, format!("peer <{self_id}> is registered in CARL"), CheckOutcome::Passed);
+            Some(peer_descriptor)
+        }
+        Err(cause) => {
+            checks.add(STAGE_CARL, format!("peer <{self_id}> is registered in CARL"), CheckOutcome::Failed { cause: cause.to_string() });
+            None
+        }
+    };
+    (Some(carl), peer_descriptor)
+}
+
+async fn check_vpn(checks: &mut Checks, settings: &LoadedConfig) {
+    let vpn_enabled = settings.config.get::<VpnConfig>(settings::key::vpn::table)
+        .map(|vpn| vpn.enabled)
+        .unwrap_or(false);
+
+    if !vpn_enabled {
+        checks.add(STAGE_VPN, "VPN mesh is connected", CheckOutcome::Skipped { reason: String::from("The VPN is disabled in the configuration.") });
+        return;
+    }
+
+    let status = match opendut_netbird_client_api::client::Client::connect().await {
+        Ok(mut client) => client.full_status().await,
+        Err(cause) => Err(cause),
+    };
+    let status = match status {
+        Ok(status) => status,
+        Err(cause) => {
+            checks.add(STAGE_VPN, "NetBird client is running", CheckOutcome::Failed { cause: cause.to_string() });
+            return;
+        }
+    };
+    checks.add(STAGE_VPN, "NetBird client is running", CheckOutcome::Passed);
+
+    let connected = |connected: Option<bool>, service: &str| match connected {
+        Some(true) => CheckOutcome::Passed,
+        Some(false) => CheckOutcome::Failed { cause: format!("NetBird client is not connected to the {service} service. Re-run `edgar setup` to fix this.") },
+        None => CheckOutcome::Failed { cause: format!("NetBird client did not return the state of the {service} service.") },
+    };
+    checks.add(STAGE_VPN, "connected to NetBird management", connected(status.management_state.map(|state| state.connected), "management"));
+    checks.add(STAGE_VPN, "connected to NetBird signal", connected(status.signal_state.map(|state| state.connected), "signal"));
+
+    let local_ip = status.local_peer_state
+        .map(|state| state.local_ip().map_err(|cause| cause.to_string()))
+        .unwrap_or_else(|| Err(String::from("NetBird client did not return a local peer state. May not be logged in.")));
+    match local_ip {
+        Ok(local_ip) => checks.add(STAGE_VPN, format!("VPN address {local_ip} is assigned"), CheckOutcome::Passed),
+        Err(cause) => checks.add(STAGE_VPN, "VPN address is assigned", CheckOutcome::Failed { cause }),
+    }
+
+    if status.peers.is_empty() {
+        checks.add(STAGE_VPN, "remote peers are reachable", CheckOutcome::Skipped { reason: String::from("No other peers are part of the VPN mesh yet.") });
+    }
+    for peer in status.peers {
+        let subject = format!("remote peer '{}' is reachable via {}", peer.fqdn, peer.ip);
+        let outcome = match IpAddr::from_str(&peer.ip) {
+            Ok(address) => {
+                if PingProbe.is_reachable(address, PING_TIMEOUT).await {
+                    CheckOutcome::Passed
+                } else {
+                    CheckOutcome::Failed { cause: format!("No response to ping within {} ms. Connection status: {}.", PING_TIMEOUT.as_millis(), peer.conn_status) }
+                }
+            }
+            Err(cause) => CheckOutcome::Failed { cause: format!("NetBird returned an invalid address: {cause}") },
+        };
+        checks.add(STAGE_VPN, subject, outcome);
+    }
+}
+
+async fn check_devices(checks: &mut Checks, peer_descriptor: &PeerDescriptor) {
+    let present_interfaces = match NetworkInterfaceManager::create() {
+        Ok(manager) => manager.list_interfaces().await
+            .map(|interfaces| interfaces.into_iter().map(|interface| interface.name).collect::<HashSet<_>>())
+            .map_err(|cause| cause.to_string()),
+        Err(cause) => Err(cause.to_string()),
+    };
+    let present_interfaces = match present_interfaces {
+        Ok(interfaces) => interfaces,
+        Err(cause) => {
+            checks.add(STAGE_DEVICES, "network interfaces can be listed", CheckOutcome::Failed { cause });
+            return;
+        }
+    };
+
+    if peer_descriptor.network.interfaces.is_empty() {
+        checks.add(STAGE_DEVICES, "devices of this peer are accessible", CheckOutcome::Skipped { reason: String::from("No interfaces are configured for this peer.") });
+    }
+    for interface in &peer_descriptor.network.interfaces {
+        let kind = match interface.configuration {
+            NetworkInterfaceConfiguration::Ethernet => "Ethernet",
+            NetworkInterfaceConfiguration::Can { .. } => "CAN",
+        };
+        let subject = format!("{kind} interface '{}' is up", interface.name);
+        let outcome = if present_interfaces.contains(&interface.name) {
+            interface_up(&interface.name).await
+        } else {
+            CheckOutcome::Failed { cause: format!("Interface '{}' does not exist on this host.", interface.name) }
+        };
+        checks.add(STAGE_DEVICES, subject, outcome);
+    }
+}
+
+/// Virtual interfaces, like vcan, report their operational state as "unknown", even when they are up.
+async fn interface_up(name: &NetworkInterfaceName) -> CheckOutcome {
+    let path = format!("/sys/class/net/{name}/operstate");
+    match tokio::fs::read_to_string(&path).await {
+        Ok(state) => match state.trim() {
+            "up" | "unknown" => CheckOutcome::Passed,
+            state => CheckOutcome::Failed { cause: format!("Interface '{name}' is {state}.") },
+        },
+        Err(cause) => CheckOutcome::Failed { cause: format!("Failed to read state of interface '{name}' from '{path}': {cause}") },
+    }
+}
+
+/// Checks the engines used by the container executors of this peer or, without such executors, those installed on this host.
+/// Returns the engines, which are available.
+async fn check_container_engines(checks: &mut Checks, peer_descriptor: Option<&PeerDescriptor>) -> Vec<Engine> {
+    let configured_engines = peer_descriptor
+        .map(|peer_descriptor| peer_descriptor.executors.executors.iter()
+            .filter_map(|executor| match &executor.kind {
+                ExecutorKind::Container { engine, .. } => Some(Clone::clone(engine)),
+                ExecutorKind::Executable => None,
+            })
+            .collect::<HashSet<_>>()
+        )
+        .unwrap_or_default();
+
+    let engines = if configured_engines.is_empty() {
+        [Engine::Docker, Engine::Podman].into_iter()
+            .filter(|engine| which::which(engine.command_name()).is_ok())
+            .collect::<Vec<_>>()
+    } else {
+        configured_engines.into_iter().collect()
+    };
+
+    if engines.is_empty() {
+        checks.add(STAGE_CONTAINER_ENGINE, "container engine is available", CheckOutcome::Skipped { reason: String::from("No container executors are configured and neither Docker nor Podman is installed.") });
+        return Vec::new();
+    }
+
+    let mut available = Vec::new();
+    for engine in engines {
+        let outcome = engine_available(&engine).await;
+        if matches!(outcome, CheckOutcome::Passed) {
+            available.push(Clone::clone(&engine));
+        }
+        checks.add(STAGE_CONTAINER_ENGINE, format!("{engine} is available"), outcome);
+    }
+    available
+}
+
+async fn engine_available(engine: &Engine) -> CheckOutcome {
+    let program = engine.command_name();
+    if which::which(program).is_err() {
+        return CheckOutcome::Failed { cause: format!("Command-line program `{program}` could not be found in the operating system PATH.") };
+    }
+    match Command::new(program).arg("info").output().await {
+        Ok(output) if output.status.success() => CheckOutcome::Passed,
+        Ok(output) => CheckOutcome::Failed { cause: format!("`{program} info` failed, e.g. because the daemon is not running or access is denied: {}", String::from_utf8_lossy(&output.stderr).trim()) },
+        Err(cause) => CheckOutcome::Failed { cause: format!("Failed to run `{program} info`: {cause}") },
+    }
+}
+
+async fn check_executor(checks: &mut Checks, engine: &Engine, image: &str) {
+    let program = engine.command_name();
+    let subject = format!("container from image '{image}' runs via {engine}");
+
+    let output = Command::new(program)
+        .args(["run", "--rm", image, "echo", EXECUTOR_MARKER])
+        .output().await;
+
+    let outcome = match output {
+        Ok(output) if output.status.success() && String::from_utf8_lossy(&output.stdout).contains(EXECUTOR_MARKER) => CheckOutcome::Passed,
+        Ok(output) if output.status.success() => CheckOutcome::Failed { cause: String::from("Container ran, but did not print the expected output.") },
+        Ok(output) => CheckOutcome::Failed { cause: format!("Container exited with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()) },
+        Err(cause) => CheckOutcome::Failed { cause: format!("Failed to run `{program} run`: {cause}") },
+    };
+    checks.add(STAGE_EXECUTOR, subject, outcome);
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_print_one_line_per_check_and_the_verdict() {
+        let peer_id = PeerId::random();
+        let report = SelfTestReport::new(peer_id, SystemTime::now(), vec![
+            SelfTestCheck { stage: String::from(STAGE_CARL), subject: String::from("CARL is reachable"), outcome: CheckOutcome::Passed },
+            SelfTestCheck { stage: String::from(STAGE_DEVICES), subject: String::from("CAN interface 'can0' is up"), outcome: CheckOutcome::Failed { cause: String::from("Interface 'can0' is down.") } },
+            SelfTestCheck { stage: String::from(STAGE_EXECUTOR), subject: String::from("test executor runs"), outcome: CheckOutcome::Skipped { reason: String::from("No image.") } },
+        ]);
+
+        let output = format_report(&report);
+
+        assert_that!(output, contains_substring("PASS  [carl] CARL is reachable"));
+        assert_that!(output, contains_substring("FAIL  [devices] CAN interface 'can0' is up: Interface 'can0' is down."));
+        assert_that!(output, contains_substring("SKIP  [executor] test executor runs: No image."));
+        assert_that!(output, contains_substring(&format!("Self-test of peer <{peer_id}> FAILED.")));
+    }
+}
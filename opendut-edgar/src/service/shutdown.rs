@@ -0,0 +1,122 @@
+use std::fmt;
+use std::time::Duration;
+
+use tokio::signal::unix::{signal, Signal, SignalKind};
+use tracing::{debug, error, info, warn};
+
+use opendut_carl_api::carl::broker;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+
+use crate::service::peer_configuration::NetworkInterfaceManagement;
+use crate::service::test_execution::executor_manager::ExecutorManagerRef;
+
+#[derive(Clone, Debug)]
+pub struct ShutdownOptions {
+    /// Time granted to the executors to stop their containers and queue their results, before the cluster networking is torn down.
+    pub executor_grace_period: Duration,
+}
+
+impl ShutdownOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let executor_grace_period = Duration::from_millis(config.get::<u64>("shutdown.executor.grace.period.ms")?);
+
+        Ok(ShutdownOptions { executor_grace_period })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// The host is powered off or rebooted.
+    HostShutdown,
+    /// Only the service was stopped, e.g. via `systemctl stop` or Ctrl+C.
+    ServiceStopped,
+}
+impl fmt::Display for ShutdownReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShutdownReason::HostShutdown => write!(f, "host shutdown"),
+            ShutdownReason::ServiceStopped => write!(f, "service stopped"),
+        }
+    }
+}
+
+/// Listens for SIGTERM, which systemd sends when stopping the service, and SIGINT.
+/// The handlers are installed on creation, so create this before any long-running work, to not miss a signal.
+pub struct ShutdownSignal {
+    terminate: Signal,
+    interrupt: Signal,
+}
+
+impl ShutdownSignal {
+    pub fn install() -> anyhow::Result<Self> {
+        Ok(Self {
+            terminate: signal(SignalKind::terminate())?,
+            interrupt: signal(SignalKind::interrupt())?,
+        })
+    }
+
+    pub async fn received(&mut self) -> ShutdownReason {
+        tokio::select! {
+            _ = self.terminate.recv() => info!("Received SIGTERM."),
+            _ = self.interrupt.recv() => info!("Received SIGINT."),
+        }
+        determine_reason().await
+    }
+}
+
+/// While the host shuts down, systemd reports the system state as "stopping".
+async fn determine_reason() -> ShutdownReason {
+    let output = tokio::process::Command::new("systemctl")
+        .arg("is-system-running")
+        .output().await;
+
+    match output {
+        Ok(output) if String::from_utf8_lossy(&output.stdout).trim() == "stopping" => ShutdownReason::HostShutdown,
+        Ok(_) => ShutdownReason::ServiceStopped,
+        Err(cause) => {
+            debug!("Could not determine whether the host is shutting down via `systemctl is-system-running`. Assuming only the service is stopped. Cause: {cause}");
+            ShutdownReason::ServiceStopped
+        }
+    }
+}
+
+/// Announces to CARL that this peer goes offline, then stops the executors and tears down the cluster networking.
+/// CARL is notified first, so that the following disconnect and the vanishing cluster networking are not reported as failures.
+pub async fn go_offline(
+    reason: ShutdownReason,
+    tx_outbound: Option<&broker::Upstream>,
+    executor_manager: &ExecutorManagerRef,
+    network_interface_management: &NetworkInterfaceManagement,
+    options: &ShutdownOptions,
+) {
+    info!("Going offline due to {reason}.");
+
+    match tx_outbound {
+        Some(tx_outbound) => {
+            let message = peer_messaging_broker::Upstream {
+                message: Some(peer_messaging_broker::upstream::Message::GoingOffline(peer_messaging_broker::GoingOffline {
+                    reason: reason.to_string(),
+                })),
+                context: None,
+            };
+            let _ignore_error =
+                tx_outbound.send(message).await
+                    .inspect_err(|cause| warn!("Failed to announce to CARL that this peer goes offline: {cause}"));
+        }
+        None => debug!("Not connected to CARL. Not announcing that this peer goes offline."),
+    }
+
+    let executors_stopped = executor_manager.lock().unwrap().terminate_executors_gracefully();
+    if tokio::time::timeout(options.executor_grace_period, executors_stopped).await.is_err() {
+        warn!("Executors did not stop within {} ms. Continuing shutdown.", options.executor_grace_period.as_millis());
+    }
+
+    if let NetworkInterfaceManagement::Enabled { network_interface_manager: _, buses } = network_interface_management {
+        for bus in buses {
+            let _ignore_error = bus.teardown().await
+                .inspect_err(|error| error!("Failed to tear down {} bus: {error}", bus.kind()));
+        }
+    }
+
+    info!("Teardown completed.");
+}
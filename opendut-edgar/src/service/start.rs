@@ -1,32 +1,58 @@
 use std::any::Any;
 use std::fmt::Debug;
 use std::ops::Not;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::Context;
+use opendut_carl_api::carl::broker;
 use opendut_carl_api::proto::services::peer_messaging_broker;
 use opendut_carl_api::proto::services::peer_messaging_broker::downstream::Message;
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, ApplyPeerConfigurationDelta, ReconnectHint, TracingContext};
+use opendut_types::peer::configuration::delta::{PeerConfigurationDelta, PeerConfigurationVersion};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
 use opendut_types::peer::PeerId;
 use opendut_util::settings::LoadedConfig;
 use opendut_util::telemetry;
+use opendut_util::telemetry::LogFilterHandle;
 use opendut_util::telemetry::logging::LoggingConfig;
+use opendut_util::telemetry::redaction::Redactor;
 use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
+use prost::Message as _;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tonic::Code;
 use tracing::{debug, error, info, trace, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-use crate::common::{carl, settings};
-use crate::service::can_manager::{CanManager, CanManagerRef};
+use crate::common::{carl, network_managers, settings};
+use crate::common::enrollment::{EnrollmentState, EnrollmentStateFile, ReEnrollmentRequired};
+use crate::service::bus::BusRef;
+use crate::service::bus::can::CanBus;
+use crate::service::bus::ethernet::EthernetBus;
+use crate::service::can_manager::{CanManager, CanManagerRef, CanMirrorOptions};
 use crate::service::network_interface::manager::{NetworkInterfaceManager, NetworkInterfaceManagerRef};
+use crate::service::cluster_readiness::ClusterReadinessOptions;
+use crate::service::config_reload::{self, ConfigReloadOptions, Reloadable};
+use crate::service::data_plane_metrics::{self, DataPlaneMetricsOptions};
+use crate::service::local_api;
+use crate::service::local_api::{LocalApiOptions, LocalApiState, LocalApiStateRef};
+use crate::service::network_manager_check::{NetworkManagerCheck, NetworkManagerCheckOptions};
+use crate::service::peer_command;
 use crate::service::peer_configuration::{ApplyPeerConfigurationParams, ClusterMetricsOptions, NetworkInterfaceManagement};
+use crate::service::shutdown;
+use crate::service::shutdown::{ShutdownOptions, ShutdownSignal};
+use crate::service::packet_capture::{PacketCapture, PacketCaptureOptions};
+use crate::service::storage_quota::{StorageQuotaCheck, StorageQuotaOptions};
+use crate::service::test_execution::can_gateway::ExecutorCanGateways;
+use crate::service::test_execution::energy_meter::{EnergyMeter, EnergyMeterOptions};
+use crate::service::test_execution::executor_status::ExecutorStatuses;
 use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef};
+use crate::service::test_execution::secrets::ExecutorSecrets;
+use crate::service::test_execution::upload_queue::{UploadQueue, UploadQueueOptions};
 use crate::service::vpn;
 
 const BANNER: &str = r"
@@ -54,14 +80,17 @@ pub async fn launch(id_override: Option<PeerId>) -> anyhow::Result<()> {
 }
 
 pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow::Result<()> {
-    let settings = settings::load_with_overrides(settings_override)?;
+    let settings = settings::load_with_overrides(Clone::clone(&settings_override))?;
 
     let self_id = settings.config.get::<PeerId>(settings::key::peer::id)
         .context("Failed to read ID from configuration.\n\nRun `edgar setup` before launching the service.")?;
 
     let service_instance_id = self_id.to_string();
 
-    let logging_config = LoggingConfig::load(&settings.config)?;
+    let logging_config = LoggingConfig {
+        redactor: Redactor::load(&settings.config)?,
+        ..LoggingConfig::load(&settings.config)?
+    };
     let opentelemetry = Opentelemetry::load(&settings.config, service_instance_id).await?;
     
     let mut shutdown = telemetry::initialize_with_config(logging_config, opentelemetry.clone()).await?;
@@ -71,34 +100,104 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
         telemetry::metrics::initialize_metrics_collection(cpu_collection_interval_ms, meter_providers);
     }
 
-    let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
-    crate::service::peer_configuration::spawn_peer_configurations_handler(rx_peer_configuration).await?;
+    let enrollment_state_file = EnrollmentStateFile::load(&settings.config)?;
+
+    let result = match enrollment_state_file.read()? {
+        EnrollmentState::Enrolled => {
+            let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
+            crate::service::peer_configuration::spawn_peer_configurations_handler(rx_peer_configuration).await?;
 
-    run_stream_receiver(self_id, settings, tx_peer_configuration).await?;
+            run_stream_receiver(self_id, settings, settings_override, Clone::clone(&shutdown.log_filter), tx_peer_configuration).await
+        }
+        EnrollmentState::ReEnrollmentRequired { reason } => Err(ReEnrollmentRequired { reason }.into()),
+    };
+
+    if let Err(error) = result {
+        let re_enrollment_required = error.downcast::<ReEnrollmentRequired>()?;
+        if let Err(cause) = enrollment_state_file.mark_re_enrollment_required(&re_enrollment_required) {
+            error!("Could not persist that re-enrollment is required at '{}': {cause:#}", enrollment_state_file.path().display());
+        }
+        await_re_enrollment(re_enrollment_required).await?;
+    }
 
     shutdown.shutdown();
 
     Ok(())
 }
 
+/// Keeps EDGAR in the ReEnrollmentRequired state, without connecting to CARL, until the service is stopped.
+/// Re-enrolling restarts the service, so EDGAR does not keep retrying with rejected credentials in the meantime.
+async fn await_re_enrollment(re_enrollment_required: ReEnrollmentRequired) -> anyhow::Result<()> {
+    error!("{re_enrollment_required}");
+
+    let reason = ShutdownSignal::install()?.received().await;
+    info!("Stopping while waiting for re-enrollment ({reason}).");
+    Ok(())
+}
+
 pub async fn run_stream_receiver(
     self_id: PeerId,
     settings: LoadedConfig,
+    settings_override: config::Config,
+    log_filter: LogFilterHandle,
     tx_peer_configuration: mpsc::Sender<ApplyPeerConfigurationParams>,
 ) -> anyhow::Result<()> {
 
     info!("Started with ID <{self_id}> and configuration: {settings:?}");
 
+    let remote_address = vpn::retrieve_remote_host(&settings).await?;
+    
+    let timeout_duration = Duration::from_millis(settings.config.get::<u64>("carl.disconnect.timeout.ms")?);
+
+    let mut carl = carl::connect(&settings.config).await?;
+
+    let local_api = LocalApiState::new(self_id, Clone::clone(&carl));
+    local_api::spawn(LocalApiOptions::load(&settings.config)?, Arc::clone(&local_api)).await?;
+
+    let energy_meter = EnergyMeter::new(EnergyMeterOptions::load(&settings.config)?);
+
+    let upload_queue = UploadQueue::load(UploadQueueOptions::load(&settings.config)?).await?;
+    upload_queue.spawn();
+
+    let network_manager_check = NetworkManagerCheck::spawn(
+        NetworkManagerCheckOptions::load(&settings.config)?,
+        network_managers::managed_interface_patterns(&[crate::common::default_bridge_name(), crate::common::default_can_bridge_name()]),
+    );
+
+    let storage_quota_check = StorageQuotaCheck::spawn(StorageQuotaOptions::load(&settings.config)?);
+
     let handle_stream_info = {
-        let executor_manager: ExecutorManagerRef = ExecutorManager::create();
+        let executor_secrets = ExecutorSecrets::load(&settings.config, Clone::clone(&carl), self_id)?;
+        let executor_can_gateways = ExecutorCanGateways::load(&settings.config)?;
+        let log_redactor = Redactor::load(&settings.config)?;
+        let executor_statuses = ExecutorStatuses::default();
+        let executor_manager: ExecutorManagerRef = ExecutorManager::create(executor_secrets, executor_can_gateways, Clone::clone(&energy_meter), Clone::clone(&upload_queue), log_redactor, Clone::clone(&executor_statuses));
+
+        let prometheus_endpoint = data_plane_metrics::spawn(
+            DataPlaneMetricsOptions::load(&settings.config)?,
+            vec![crate::common::default_bridge_name(), crate::common::default_can_bridge_name()],
+            executor_statuses,
+        ).await?;
+
+        config_reload::spawn(
+            ConfigReloadOptions::load(&settings.config)?,
+            &settings,
+            settings_override,
+            Reloadable { log_filter, upload_queue: Clone::clone(&upload_queue), prometheus_endpoint },
+        )?;
 
         let network_interface_management = {
             let network_interface_management_enabled = settings.config.get::<bool>("network.interface.management.enabled")?;
             if network_interface_management_enabled {
                 let network_interface_manager: NetworkInterfaceManagerRef = NetworkInterfaceManager::create()?;
-                let can_manager: CanManagerRef = CanManager::create(Arc::clone(&network_interface_manager));
+                let can_manager: CanManagerRef = CanManager::create(Arc::clone(&network_interface_manager), CanMirrorOptions::load(&settings.config)?);
+
+                let buses: Vec<BusRef> = vec![
+                    Arc::new(EthernetBus::new(Arc::clone(&network_interface_manager))),
+                    Arc::new(CanBus::new(can_manager)),
+                ];
 
-                NetworkInterfaceManagement::Enabled { network_interface_manager, can_manager }
+                NetworkInterfaceManagement::Enabled { network_interface_manager, buses }
             } else {
                 NetworkInterfaceManagement::Disabled
             }
@@ -108,6 +207,14 @@ pub async fn run_stream_receiver(
         let target_bandwidth_kbit_per_second = settings.config.get::<u64>("opentelemetry.metrics.cluster.target.bandwidth.kilobit.per.second")?;
         let rperf_backoff_max_elapsed_time = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.rperf.backoff.max.elapsed.time.ms")?);
 
+        let cluster_readiness_options = ClusterReadinessOptions::load(&settings.config)?;
+
+        let packet_capture = PacketCapture::new(self_id, PacketCaptureOptions::load(&settings.config)?, Clone::clone(&upload_queue));
+
+        let configuration_capture_file = settings.config.get_string("peer.configuration.capture.file")?;
+        let configuration_capture_file = configuration_capture_file.is_empty().not()
+            .then(|| PathBuf::from(configuration_capture_file));
+
         HandleStreamInfo {
             self_id,
             network_interface_management,
@@ -117,16 +224,67 @@ pub async fn run_stream_receiver(
                 target_bandwidth_kbit_per_second,
                 rperf_backoff_max_elapsed_time,
             },
+            cluster_readiness_options,
+            local_api,
+            packet_capture,
+            configuration_capture_file,
+            applied_configuration: Mutex::new(None),
         }
     };
 
-    let remote_address = vpn::retrieve_remote_host(&settings).await?;
-    
-    let timeout_duration = Duration::from_millis(settings.config.get::<u64>("carl.disconnect.timeout.ms")?);
+    let shutdown_options = ShutdownOptions::load(&settings.config)?;
+    let mut shutdown_signal = ShutdownSignal::install()?;
 
-    let mut carl = carl::connect(&settings.config).await?;
+    loop {
+        let (rx_inbound, tx_outbound) = tokio::select! {
+            stream = carl::open_stream(self_id, &remote_address, &mut carl) => match stream {
+                Ok(stream) => stream,
+                Err(error) => match ReEnrollmentRequired::from_open_stream_error(&error) {
+                    Some(re_enrollment_required) => return Err(re_enrollment_required.into()),
+                    None => return Err(error.into()),
+                },
+            },
+            reason = shutdown_signal.received() => {
+                shutdown::go_offline(reason, None, &handle_stream_info.executor_manager, &handle_stream_info.network_interface_management, &shutdown_options).await;
+                break;
+            }
+        };
+        handle_stream_info.local_api.set_stream(Some(Clone::clone(&tx_outbound))).await;
+        energy_meter.set_stream(Some(Clone::clone(&tx_outbound))).await;
+        network_manager_check.set_stream(Some(Clone::clone(&tx_outbound))).await;
+        storage_quota_check.set_stream(Some(Clone::clone(&tx_outbound))).await;
+
+        let reconnect_hint = tokio::select! {
+            reconnect_hint = receive_stream(rx_inbound, Clone::clone(&tx_outbound), timeout_duration, &handle_stream_info, &tx_peer_configuration) => reconnect_hint?,
+            reason = shutdown_signal.received() => {
+                shutdown::go_offline(reason, Some(&tx_outbound), &handle_stream_info.executor_manager, &handle_stream_info.network_interface_management, &shutdown_options).await;
+                None
+            }
+        };
+        handle_stream_info.local_api.set_stream(None).await;
+        energy_meter.set_stream(None).await;
+        network_manager_check.set_stream(None).await;
+        storage_quota_check.set_stream(None).await;
+
+        match reconnect_hint {
+            Some(reconnect_hint) => {
+                carl = carl::reconnect(&settings.config, reconnect_hint).await?;
+            }
+            None => break,
+        }
+    }
 
-    let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, &remote_address, &mut carl).await?;
+    Ok(())
+}
+
+/// Handles the messages of the stream until it ends. Returns the hint to reconnect, when CARL asked to disconnect, e.g. because it is shutting down.
+async fn receive_stream(
+    mut rx_inbound: broker::Downstream,
+    tx_outbound: broker::Upstream,
+    timeout_duration: Duration,
+    handle_stream_info: &HandleStreamInfo,
+    tx_peer_configuration: &mpsc::Sender<ApplyPeerConfigurationParams>,
+) -> anyhow::Result<Option<ReconnectHint>> {
 
     loop {
         let received = tokio::time::timeout(timeout_duration, rx_inbound.message()).await;
@@ -134,12 +292,16 @@ pub async fn run_stream_receiver(
         match received {
             Ok(received) => match received {
                 Ok(Some(message)) => {
-                    handle_stream_message(
+                    let reconnect_hint = handle_stream_message(
                         message,
-                        &handle_stream_info,
+                        handle_stream_info,
                         &tx_outbound,
-                        &tx_peer_configuration,
-                    ).await?
+                        tx_peer_configuration,
+                    ).await?;
+
+                    if reconnect_hint.is_some() {
+                        return Ok(reconnect_hint);
+                    }
                 }
                 Err(status) => {
                     warn!("CARL sent a gRPC error status: {status}");
@@ -152,6 +314,11 @@ pub async fn run_stream_receiver(
                             continue
                         }
 
+                        Code::Unauthenticated
+                        | Code::PermissionDenied => { //credentials of this peer were revoked or expired
+                            return Err(ReEnrollmentRequired { reason: status.to_string() }.into());
+                        }
+
                         Code::Aborted
                         | Code::Cancelled
                         | Code::DataLoss
@@ -160,10 +327,8 @@ pub async fn run_stream_receiver(
                         | Code::InvalidArgument
                         | Code::NotFound
                         | Code::OutOfRange
-                        | Code::PermissionDenied
                         | Code::ResourceExhausted
                         | Code::Unimplemented
-                        | Code::Unauthenticated
                         | Code::Unknown
                         => panic!("Received potentially bad gRPC error: {status}"), //In production, SystemD will restart EDGAR with a delay. A crash is mainly more visible.
                     }
@@ -180,7 +345,7 @@ pub async fn run_stream_receiver(
         }
     }
 
-    Ok(())
+    Ok(None)
 }
 
 
@@ -189,6 +354,18 @@ struct HandleStreamInfo {
     pub network_interface_management: NetworkInterfaceManagement,
     pub executor_manager: ExecutorManagerRef,
     pub cluster_metrics_options: ClusterMetricsOptions,
+    pub cluster_readiness_options: ClusterReadinessOptions,
+    pub local_api: LocalApiStateRef,
+    pub packet_capture: PacketCapture,
+    /// Where to write each received ApplyPeerConfiguration message to, for validating it via `edgar validate-configuration`.
+    pub configuration_capture_file: Option<PathBuf>,
+    /// Last configuration received from CARL, which configuration deltas are applied onto.
+    pub applied_configuration: Mutex<Option<AppliedPeerConfiguration>>,
+}
+
+struct AppliedPeerConfiguration {
+    version: PeerConfigurationVersion,
+    configuration: PeerConfiguration,
 }
 
 async fn handle_stream_message(
@@ -196,7 +373,7 @@ async fn handle_stream_message(
     handle_stream_info: &HandleStreamInfo,
     tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
     peer_configuration_sender: &mpsc::Sender<ApplyPeerConfigurationParams>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<ReconnectHint>> {
 
     if let peer_messaging_broker::Downstream { message: Some(message), context } = message {
         if matches!(message, Message::Pong(_)).not() {
@@ -205,35 +382,66 @@ async fn handle_stream_message(
 
         match message {
             Message::Pong(_) => {
-                sleep(Duration::from_secs(5)).await;
+                //delay the next ping in the background, so that other messages, like probes, are not held up
+                let tx_outbound = Clone::clone(tx_outbound);
+                tokio::spawn(async move {
+                    sleep(Duration::from_secs(5)).await;
+                    let message = peer_messaging_broker::Upstream {
+                        message: Some(peer_messaging_broker::upstream::Message::Ping(peer_messaging_broker::Ping {})),
+                        context: None
+                    };
+                    let _ignore_error =
+                        tx_outbound.send(message).await
+                            .inspect_err(|cause| debug!("Failed to send ping to CARL: {cause}"));
+                });
+            }
+            Message::Probe(probe) => {
                 let message = peer_messaging_broker::Upstream {
-                    message: Some(peer_messaging_broker::upstream::Message::Ping(peer_messaging_broker::Ping {})),
+                    message: Some(peer_messaging_broker::upstream::Message::ProbeResponse(peer_messaging_broker::ProbeResponse { id: probe.id })),
                     context: None
                 };
                 let _ignore_error =
                     tx_outbound.send(message).await
-                        .inspect_err(|cause| debug!("Failed to send ping to CARL: {cause}"));
+                        .inspect_err(|cause| debug!("Failed to respond to probe from CARL: {cause}"));
+            }
+            Message::ApplyPeerConfiguration(message) => apply_peer_configuration_raw(message, context, handle_stream_info, tx_outbound, peer_configuration_sender).await?,
+            Message::ApplyPeerConfigurationDelta(message) => apply_peer_configuration_delta_raw(message, context, handle_stream_info, tx_outbound, peer_configuration_sender).await?,
+            Message::PeerCommand(command) => {
+                let applied_configuration = handle_stream_info.applied_configuration.lock().unwrap().as_ref()
+                    .map(|applied| format!("Version {}:\n{:#?}", applied.version, applied.configuration));
+                peer_command::handle(command, applied_configuration, &handle_stream_info.packet_capture, tx_outbound);
+            }
+            Message::Disconnect(disconnect) => {
+                let reconnect_hint = disconnect.reconnect_hint.unwrap_or_default();
+                info!("CARL asked to disconnect. Reconnecting in {} ms.", reconnect_hint.retry_after_ms);
+                return Ok(Some(reconnect_hint));
             }
-            Message::ApplyPeerConfiguration(message) => apply_peer_configuration_raw(message, context, handle_stream_info, peer_configuration_sender).await?,
         }
     } else {
         ignore(message)
     }
 
-    Ok(())
+    Ok(None)
 }
 
 async fn apply_peer_configuration_raw(
     message: ApplyPeerConfiguration,
     context: Option<TracingContext>,
     handle_stream_info: &HandleStreamInfo,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
     peer_configuration_sender: &mpsc::Sender<ApplyPeerConfigurationParams>,
 ) -> anyhow::Result<()> {
 
+    if let Some(capture_file) = &handle_stream_info.configuration_capture_file {
+        let _ignore_result = fs_err::write(capture_file, message.encode_to_vec())
+            .inspect_err(|cause| warn!("Failed to capture received peer configuration:\n  {cause}"));
+    }
+
     match message.clone() {
         ApplyPeerConfiguration {
             old_configuration: Some(old_peer_configuration),
             configuration: Some(peer_configuration),
+            configuration_version,
         } => {
 
             let span = Span::current();
@@ -245,17 +453,14 @@ async fn apply_peer_configuration_raw(
                     match PeerConfiguration::try_from(peer_configuration) {
                         Ok(peer_configuration) => {
                             info!("Received OldPeerConfiguration: {old_peer_configuration:?}");
-                            info!("Received PeerConfiguration: {peer_configuration:?}");
-                            
-                            let apply_config_params = ApplyPeerConfigurationParams {
-                                self_id: handle_stream_info.self_id,
-                                peer_configuration,
-                                old_peer_configuration,
-                                network_interface_management: handle_stream_info.network_interface_management.clone(),
-                                executor_manager: Arc::clone(&handle_stream_info.executor_manager),
-                                cluster_metrics_options: handle_stream_info.cluster_metrics_options.clone(),
-                            };
-                            peer_configuration_sender.send(apply_config_params).await?
+                            info!("Received PeerConfiguration in version {configuration_version}: {peer_configuration:?}");
+
+                            *handle_stream_info.applied_configuration.lock().unwrap() = Some(AppliedPeerConfiguration {
+                                version: configuration_version,
+                                configuration: Clone::clone(&peer_configuration),
+                            });
+
+                            apply_peer_configuration(old_peer_configuration, peer_configuration, handle_stream_info, tx_outbound, peer_configuration_sender).await?
                         }
                         Err(error) => error!("Illegal PeerConfiguration: {error}"),
                     }
@@ -268,6 +473,100 @@ async fn apply_peer_configuration_raw(
     Ok(())
 }
 
+async fn apply_peer_configuration_delta_raw(
+    message: ApplyPeerConfigurationDelta,
+    context: Option<TracingContext>,
+    handle_stream_info: &HandleStreamInfo,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+    peer_configuration_sender: &mpsc::Sender<ApplyPeerConfigurationParams>,
+) -> anyhow::Result<()> {
+
+    match message.clone() {
+        ApplyPeerConfigurationDelta {
+            old_configuration: Some(old_peer_configuration),
+            delta: Some(delta),
+        } => {
+
+            let span = Span::current();
+            set_parent_context(&span, context);
+            let _span = span.enter();
+
+            let old_peer_configuration = match OldPeerConfiguration::try_from(old_peer_configuration) {
+                Ok(old_peer_configuration) => old_peer_configuration,
+                Err(error) => {
+                    error!("Illegal OldPeerConfiguration: {error}");
+                    return Ok(());
+                }
+            };
+            let delta = match PeerConfigurationDelta::try_from(delta) {
+                Ok(delta) => delta,
+                Err(error) => {
+                    error!("Illegal PeerConfigurationDelta: {error}");
+                    return Ok(());
+                }
+            };
+
+            let peer_configuration = {
+                let mut applied_configuration = handle_stream_info.applied_configuration.lock().unwrap();
+                match applied_configuration.as_ref() {
+                    Some(applied) if applied.version == delta.base_version => {
+                        let peer_configuration = delta.apply_to(&applied.configuration);
+                        *applied_configuration = Some(AppliedPeerConfiguration {
+                            version: delta.version,
+                            configuration: Clone::clone(&peer_configuration),
+                        });
+                        Some(peer_configuration)
+                    }
+                    _ => None,
+                }
+            };
+
+            match peer_configuration {
+                Some(peer_configuration) => {
+                    info!("Received OldPeerConfiguration: {old_peer_configuration:?}");
+                    info!("Received delta of {} parameters, resulting in PeerConfiguration in version {}: {peer_configuration:?}", delta.len(), delta.version);
+
+                    apply_peer_configuration(old_peer_configuration, peer_configuration, handle_stream_info, tx_outbound, peer_configuration_sender).await?
+                }
+                None => {
+                    warn!("Received PeerConfiguration delta based on version {}, which does not match the applied configuration. Requesting full configuration.", delta.base_version);
+                    let message = peer_messaging_broker::Upstream {
+                        message: Some(peer_messaging_broker::upstream::Message::RequestPeerConfiguration(peer_messaging_broker::RequestPeerConfiguration {})),
+                        context: None,
+                    };
+                    tx_outbound.send(message).await?
+                }
+            }
+        }
+        _ => ignore(message),
+    }
+    Ok(())
+}
+
+async fn apply_peer_configuration(
+    old_peer_configuration: OldPeerConfiguration,
+    peer_configuration: PeerConfiguration,
+    handle_stream_info: &HandleStreamInfo,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+    peer_configuration_sender: &mpsc::Sender<ApplyPeerConfigurationParams>,
+) -> anyhow::Result<()> {
+
+    handle_stream_info.local_api.set_cluster_assignment(Clone::clone(&old_peer_configuration.cluster_assignment)).await;
+
+    let apply_config_params = ApplyPeerConfigurationParams {
+        self_id: handle_stream_info.self_id,
+        peer_configuration,
+        old_peer_configuration,
+        network_interface_management: handle_stream_info.network_interface_management.clone(),
+        executor_manager: Arc::clone(&handle_stream_info.executor_manager),
+        cluster_metrics_options: handle_stream_info.cluster_metrics_options.clone(),
+        cluster_readiness_options: handle_stream_info.cluster_readiness_options.clone(),
+        tx_outbound: Clone::clone(tx_outbound),
+    };
+    peer_configuration_sender.send(apply_config_params).await?;
+    Ok(())
+}
+
 fn set_parent_context(span: &Span, context: Option<TracingContext>) {
     if let Some(context) = context {
         let propagator = TraceContextPropagator::new();
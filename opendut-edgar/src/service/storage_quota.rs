@@ -0,0 +1,433 @@
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use opendut_carl_api::carl::broker;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+
+/// Kind of data stored on the peer, each with its own directory and quota.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StorageCategory {
+    Recordings,
+    Results,
+    Journals,
+    Caches,
+}
+
+impl StorageCategory {
+    pub const ALL: [StorageCategory; 4] = [StorageCategory::Recordings, StorageCategory::Results, StorageCategory::Journals, StorageCategory::Caches];
+
+    fn tag(&self) -> &'static str {
+        match self {
+            StorageCategory::Recordings => "recordings",
+            StorageCategory::Results => "results",
+            StorageCategory::Journals => "journals",
+            StorageCategory::Caches => "caches",
+        }
+    }
+}
+
+impl Display for StorageCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+/// How to get back below the quota, when a category exceeds it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Delete the least recently modified files first.
+    DeleteOldest,
+    /// Delete the largest files first, to free the space with as few deletions as possible.
+    DeleteLargest,
+    /// Let journald delete its archived journal files via `journalctl --vacuum-size`.
+    VacuumJournal,
+    /// Only report the usage, e.g. for directories, which manage their size themselves.
+    ReportOnly,
+}
+
+impl EvictionPolicy {
+    fn tag(&self) -> &'static str {
+        match self {
+            EvictionPolicy::DeleteOldest => "delete-oldest",
+            EvictionPolicy::DeleteLargest => "delete-largest",
+            EvictionPolicy::VacuumJournal => "vacuum-journal",
+            EvictionPolicy::ReportOnly => "report-only",
+        }
+    }
+}
+
+impl Display for EvictionPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+impl FromStr for EvictionPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        [EvictionPolicy::DeleteOldest, EvictionPolicy::DeleteLargest, EvictionPolicy::VacuumJournal, EvictionPolicy::ReportOnly]
+            .into_iter()
+            .find(|policy| policy.tag() == value)
+            .ok_or_else(|| anyhow::anyhow!("Unknown eviction policy '{value}'. Expected one of 'delete-oldest', 'delete-largest', 'vacuum-journal' or 'report-only'."))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoryQuota {
+    pub category: StorageCategory,
+    pub directory: PathBuf,
+    pub size_limit_bytes: u64,
+    pub eviction: EvictionPolicy,
+}
+
+#[derive(Clone, Debug)]
+pub enum StorageQuotaOptions {
+    Enabled {
+        /// Time to wait between two checks.
+        interval: Duration,
+        quotas: Vec<CategoryQuota>,
+    },
+    Disabled,
+}
+
+impl StorageQuotaOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("storage.quota.enabled")?;
+
+        if enabled {
+            let interval = Duration::from_millis(config.get::<u64>("storage.quota.check.interval.ms")?);
+
+            let quotas = StorageCategory::ALL.into_iter()
+                .map(|category| {
+                    let prefix = format!("storage.quota.{category}");
+                    Ok(CategoryQuota {
+                        category,
+                        directory: PathBuf::from(config.get_string(&format!("{prefix}.directory"))?),
+                        size_limit_bytes: config.get::<u64>(&format!("{prefix}.size.limit.bytes"))?,
+                        eviction: EvictionPolicy::from_str(&config.get_string(&format!("{prefix}.eviction"))?)?,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            Ok(StorageQuotaOptions::Enabled { interval, quotas })
+        } else {
+            Ok(StorageQuotaOptions::Disabled)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoryUsage {
+    pub quota: CategoryQuota,
+    pub used_bytes: u64,
+    pub evicted_files: u64,
+    pub evicted_bytes: u64,
+    pub filesystem_available_bytes: Option<u64>,
+}
+
+impl CategoryUsage {
+    pub fn is_exceeded(&self) -> bool {
+        self.used_bytes > self.quota.size_limit_bytes
+    }
+}
+
+/// Periodically measures the disk usage of each storage category and evicts files according to the category's policy,
+/// when it exceeds its quota, so that e.g. long recording sessions do not fill up the root filesystem and take the peer down.
+/// The usage is reported to CARL after every check.
+#[derive(Clone)]
+pub struct StorageQuotaCheck {
+    tx_outbound: Arc<RwLock<Option<broker::Upstream>>>,
+}
+
+impl StorageQuotaCheck {
+    pub fn spawn(options: StorageQuotaOptions) -> Self {
+        let check = Self {
+            tx_outbound: Default::default(),
+        };
+
+        if let StorageQuotaOptions::Enabled { interval, quotas } = options {
+            let check = Clone::clone(&check);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(interval);
+                loop {
+                    interval.tick().await;
+
+                    let quotas = Clone::clone(&quotas);
+                    let usages = tokio::task::spawn_blocking(move || {
+                        quotas.into_iter()
+                            .filter_map(|quota| {
+                                enforce_quota(Clone::clone(&quota))
+                                    .inspect_err(|cause| warn!("Failed to check the storage quota of {} in '{}': {cause}", quota.category, quota.directory.display()))
+                                    .ok()
+                            })
+                            .collect::<Vec<_>>()
+                    }).await;
+
+                    match usages {
+                        Ok(usages) => check.report(usages).await,
+                        Err(cause) => warn!("Failed to check the storage quotas: {cause}"),
+                    }
+                }
+            });
+        } else {
+            debug!("Checking the storage quotas is disabled.");
+        }
+
+        check
+    }
+
+    pub async fn set_stream(&self, tx_outbound: Option<broker::Upstream>) {
+        *self.tx_outbound.write().await = tx_outbound;
+    }
+
+    async fn report(&self, usages: Vec<CategoryUsage>) {
+        let message = peer_messaging_broker::Upstream {
+            message: Some(peer_messaging_broker::upstream::Message::StorageUsage(peer_messaging_broker::StorageUsage {
+                categories: usages.into_iter()
+                    .map(|usage| peer_messaging_broker::StorageCategoryUsage {
+                        category: usage.quota.category.to_string(),
+                        directory: usage.quota.directory.display().to_string(),
+                        eviction_policy: usage.quota.eviction.to_string(),
+                        used_bytes: usage.used_bytes,
+                        quota_bytes: usage.quota.size_limit_bytes,
+                        evicted_files: usage.evicted_files,
+                        evicted_bytes: usage.evicted_bytes,
+                        filesystem_available_bytes: usage.filesystem_available_bytes,
+                    })
+                    .collect(),
+            })),
+            context: None,
+        };
+
+        match self.tx_outbound.read().await.as_ref() {
+            Some(tx_outbound) => {
+                if let Err(cause) = tx_outbound.send(message).await {
+                    warn!("Failed to report the storage usage to CARL: {cause}");
+                }
+            }
+            None => debug!("Not connected to CARL. Not reporting the storage usage."),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct StoredFile {
+    path: PathBuf,
+    size_bytes: u64,
+    modified: SystemTime,
+}
+
+/// Lists the files below the directory. A missing directory is treated as empty, as it is only created once something is stored.
+fn stored_files(directory: &Path) -> anyhow::Result<Vec<StoredFile>> {
+    if directory.exists().not() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(directory) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(cause) if cause.io_error().map(|cause| cause.kind() == ErrorKind::NotFound).unwrap_or(false) => continue, //deleted meanwhile
+            Err(cause) => return Err(cause.into()),
+        };
+        if entry.file_type().is_file() {
+            let metadata = entry.metadata()?;
+            files.push(StoredFile {
+                path: entry.into_path(),
+                size_bytes: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+    }
+    Ok(files)
+}
+
+fn used_bytes(files: &[StoredFile]) -> u64 {
+    files.iter().map(|file| file.size_bytes).sum()
+}
+
+fn filesystem_available_bytes(directory: &Path) -> Option<u64> {
+    let existing_ancestor = directory.ancestors().find(|ancestor| ancestor.exists())?;
+    let statistics = nix::sys::statvfs::statvfs(existing_ancestor).ok()?;
+    #[allow(clippy::unnecessary_cast)] //the types of the fields differ between platforms
+    Some(statistics.blocks_available() as u64 * statistics.fragment_size() as u64)
+}
+
+/// Measures the usage of the category and evicts files according to its policy, if it exceeds its quota.
+fn enforce_quota(quota: CategoryQuota) -> anyhow::Result<CategoryUsage> {
+    let mut files = stored_files(&quota.directory)?;
+    let mut used = used_bytes(&files);
+    let mut evicted_files = 0;
+    let mut evicted_bytes = 0;
+
+    if used > quota.size_limit_bytes {
+        match quota.eviction {
+            EvictionPolicy::DeleteOldest | EvictionPolicy::DeleteLargest => {
+                files.sort_by_key(|file| file.modified);
+                // The most recently modified file may still be written, e.g. by a running recording, so deleting it would not free any space.
+                files.pop();
+
+                if quota.eviction == EvictionPolicy::DeleteLargest {
+                    files.sort_by_key(|file| std::cmp::Reverse(file.size_bytes));
+                }
+
+                for file in files {
+                    if used <= quota.size_limit_bytes {
+                        break;
+                    }
+                    match std::fs::remove_file(&file.path) {
+                        Ok(()) => {
+                            debug!("Evicted '{}' with {} bytes to meet the storage quota of {}.", file.path.display(), file.size_bytes, quota.category);
+                            used = used.saturating_sub(file.size_bytes);
+                            evicted_files += 1;
+                            evicted_bytes += file.size_bytes;
+                        }
+                        Err(cause) if cause.kind() == ErrorKind::NotFound => {
+                            used = used.saturating_sub(file.size_bytes);
+                        }
+                        Err(cause) => warn!("Failed to evict '{}' to meet the storage quota of {}: {cause}", file.path.display(), quota.category),
+                    }
+                }
+            }
+            EvictionPolicy::VacuumJournal => {
+                let output = Command::new("journalctl")
+                    .arg(format!("--vacuum-size={}", quota.size_limit_bytes))
+                    .arg(format!("--directory={}", quota.directory.display()))
+                    .output()?;
+                if output.status.success().not() {
+                    warn!("Failed to vacuum the journal in '{}': {}", quota.directory.display(), String::from_utf8_lossy(&output.stderr).trim());
+                }
+                let remaining = stored_files(&quota.directory)?;
+                let remaining_bytes = used_bytes(&remaining);
+                evicted_files = files.len().saturating_sub(remaining.len()) as u64;
+                evicted_bytes = used.saturating_sub(remaining_bytes);
+                used = remaining_bytes;
+            }
+            EvictionPolicy::ReportOnly => {}
+        }
+
+        if evicted_files > 0 {
+            info!("Evicted {evicted_files} files with {evicted_bytes} bytes from {} in '{}' to meet its quota of {} bytes.", quota.category, quota.directory.display(), quota.size_limit_bytes);
+        }
+        if used > quota.size_limit_bytes {
+            warn!("Storage of {} in '{}' uses {used} bytes and exceeds its quota of {} bytes.", quota.category, quota.directory.display(), quota.size_limit_bytes);
+        }
+    }
+
+    let filesystem_available_bytes = filesystem_available_bytes(&quota.directory);
+
+    Ok(CategoryUsage {
+        quota,
+        used_bytes: used,
+        evicted_files,
+        evicted_bytes,
+        filesystem_available_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn write_file(directory: &Path, name: &str, size_bytes: usize, modified: SystemTime) -> anyhow::Result<PathBuf> {
+        let path = directory.join(name);
+        std::fs::write(&path, vec![0u8; size_bytes])?;
+        File::options().write(true).open(&path)?.set_modified(modified)?;
+        Ok(path)
+    }
+
+    fn quota(directory: &Path, size_limit_bytes: u64, eviction: EvictionPolicy) -> CategoryQuota {
+        CategoryQuota {
+            category: StorageCategory::Recordings,
+            directory: directory.to_owned(),
+            size_limit_bytes,
+            eviction,
+        }
+    }
+
+    #[test]
+    fn should_delete_the_oldest_files_until_below_the_quota() -> anyhow::Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        let now = SystemTime::now();
+        let oldest = write_file(temp.path(), "oldest", 400, now - Duration::from_secs(30))?;
+        let older = write_file(temp.path(), "older", 400, now - Duration::from_secs(20))?;
+        let newest = write_file(temp.path(), "newest", 400, now - Duration::from_secs(10))?;
+
+        let usage = enforce_quota(quota(temp.path(), 1000, EvictionPolicy::DeleteOldest))?;
+
+        assert_that!(usage.used_bytes, eq(800));
+        assert_that!(usage.evicted_files, eq(1));
+        assert_that!(usage.evicted_bytes, eq(400));
+        assert_that!(oldest.exists(), eq(false));
+        assert_that!(older.exists(), eq(true));
+        assert_that!(newest.exists(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_delete_the_largest_files_but_keep_the_most_recent_one() -> anyhow::Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        let now = SystemTime::now();
+        let small = write_file(temp.path(), "small", 100, now - Duration::from_secs(30))?;
+        let large = write_file(temp.path(), "large", 600, now - Duration::from_secs(20))?;
+        let recording = write_file(temp.path(), "recording", 900, now - Duration::from_secs(10))?;
+
+        let usage = enforce_quota(quota(temp.path(), 500, EvictionPolicy::DeleteLargest))?;
+
+        assert_that!(large.exists(), eq(false));
+        assert_that!(small.exists(), eq(false));
+        assert_that!(recording.exists(), eq(true));
+        assert_that!(usage.used_bytes, eq(900));
+        assert_that!(usage.is_exceeded(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_report_the_usage_with_the_report_only_policy() -> anyhow::Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        let now = SystemTime::now();
+        let first = write_file(temp.path(), "first", 400, now - Duration::from_secs(20))?;
+        write_file(temp.path(), "second", 400, now)?;
+
+        let usage = enforce_quota(quota(temp.path(), 500, EvictionPolicy::ReportOnly))?;
+
+        assert_that!(first.exists(), eq(true));
+        assert_that!(usage.used_bytes, eq(800));
+        assert_that!(usage.evicted_files, eq(0));
+        assert_that!(usage.is_exceeded(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_treat_a_missing_directory_as_empty() -> anyhow::Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+
+        let usage = enforce_quota(quota(&temp.path().join("recordings"), 500, EvictionPolicy::DeleteOldest))?;
+
+        assert_that!(usage.used_bytes, eq(0));
+        assert_that!(usage.filesystem_available_bytes, some(gt(0)));
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_the_eviction_policies() -> anyhow::Result<()> {
+        assert_that!(EvictionPolicy::from_str("delete-oldest")?, eq(EvictionPolicy::DeleteOldest));
+        assert_that!(EvictionPolicy::from_str("vacuum-journal")?, eq(EvictionPolicy::VacuumJournal));
+        assert_that!(EvictionPolicy::from_str("delete-newest").is_err(), eq(true));
+        Ok(())
+    }
+}
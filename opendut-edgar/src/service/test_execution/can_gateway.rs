@@ -0,0 +1,331 @@
+use std::io::ErrorKind;
+use std::ops::Not;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use socketcan::{CanFrame, EmbeddedFrame, ExtendedId, Frame, Id, StandardId};
+use socketcan_isotp::IsoTpSocket;
+use tokio::fs::{self, DirBuilder};
+use tokio::io::{unix::AsyncFd, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, trace, warn};
+
+use opendut_types::peer::executor::container::{ContainerCanGateway, ContainerCanGatewayProtocol};
+use opendut_types::peer::executor::ExecutorId;
+
+const GATEWAY_DIRECTORY_MODE: u32 = 0o755;
+/// The container may run as any user, so everyone needs to be able to connect.
+/// Access is restricted by only mounting the directory into the container of the executor.
+const GATEWAY_SOCKET_MODE: u32 = 0o666;
+const MAX_ISOTP_PAYLOAD_LENGTH: usize = 4095;
+
+/// Provides container executors with access to CAN interfaces of the peer, without running them in privileged mode.
+/// For each [ContainerCanGateway] of an executor, a Unix socket is created, which is mounted into the container.
+/// EDGAR opens the SocketCAN socket itself and forwards messages between both sockets,
+/// dropping any frames with CAN IDs, which the executor is not allowed to use.
+#[derive(Clone, Debug)]
+pub struct ExecutorCanGateways {
+    directory: PathBuf,
+}
+
+impl ExecutorCanGateways {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let directory = PathBuf::from(config.get_string("executor.can.gateway.directory")?);
+        Ok(Self { directory })
+    }
+
+    /// Returns `None`, if no gateways are configured, so that no directory needs to be mounted.
+    pub async fn open(&self, executor_id: ExecutorId, gateways: &[ContainerCanGateway]) -> Result<Option<OpenedCanGateways>, Error> {
+        if gateways.is_empty() {
+            return Ok(None);
+        }
+
+        let directory = self.directory.join(executor_id.to_string());
+
+        match fs::remove_dir_all(&directory).await {
+            Ok(()) => warn!("Removed leftover CAN gateway sockets of executor <{executor_id}> from '{}'.", directory.display()),
+            Err(cause) if cause.kind() == ErrorKind::NotFound => {}
+            Err(cause) => return Err(Error::Io { path: directory, cause }),
+        }
+
+        fs::create_dir_all(&self.directory).await
+            .map_err(|cause| Error::Io { path: self.directory.clone(), cause })?;
+
+        DirBuilder::new()
+            .mode(GATEWAY_DIRECTORY_MODE)
+            .create(&directory).await
+            .map_err(|cause| Error::Io { path: directory.clone(), cause })?;
+
+        let mut opened = OpenedCanGateways { directory, tasks: Vec::new() };
+
+        for gateway in gateways {
+            let path = opened.directory.join(gateway.socket_name());
+
+            let listener = UnixListener::bind(&path)
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+            fs::set_permissions(&path, std::fs::Permissions::from_mode(GATEWAY_SOCKET_MODE)).await
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+
+            let gateway = Clone::clone(gateway);
+            opened.tasks.push(tokio::spawn(async move {
+                accept_connections(listener, gateway, executor_id).await;
+            }));
+        }
+
+        debug!("Opened {} CAN gateway(s) of executor <{executor_id}> in '{}'.", gateways.len(), opened.directory.display());
+        Ok(Some(opened))
+    }
+}
+
+/// Directory holding the gateway sockets of one executor. The gateways are closed and the directory is removed when this value is dropped.
+pub struct OpenedCanGateways {
+    directory: PathBuf,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl OpenedCanGateways {
+    pub fn path(&self) -> &Path {
+        &self.directory
+    }
+}
+
+impl Drop for OpenedCanGateways {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        match std::fs::remove_dir_all(&self.directory) {
+            Ok(()) => debug!("Closed CAN gateways in '{}'.", self.directory.display()),
+            Err(cause) => warn!("Failed to remove CAN gateway sockets in '{}': {cause}", self.directory.display()),
+        }
+    }
+}
+
+async fn accept_connections(listener: UnixListener, gateway: ContainerCanGateway, executor_id: ExecutorId) {
+    let mut connections = Vec::new();
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                info!("Executor <{executor_id}> connected to CAN gateway '{gateway}'.");
+                let gateway = Clone::clone(&gateway);
+                connections.push(AbortOnDrop(tokio::spawn(async move {
+                    match forward(stream, &gateway).await {
+                        Ok(()) => debug!("Executor <{executor_id}> disconnected from CAN gateway '{gateway}'."),
+                        Err(cause) => warn!("CAN gateway '{gateway}' of executor <{executor_id}> failed: {cause}"),
+                    }
+                })));
+                connections.retain(|connection| connection.0.is_finished().not());
+            }
+            Err(cause) => {
+                warn!("Failed to accept connection on CAN gateway '{gateway}' of executor <{executor_id}>: {cause}");
+                return;
+            }
+        }
+    }
+}
+
+/// Ensures connections are closed when the gateway is closed, as aborting a task does not abort the tasks it spawned.
+struct AbortOnDrop(JoinHandle<()>);
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+async fn forward(stream: UnixStream, gateway: &ContainerCanGateway) -> Result<(), Error> {
+    let (reader, mut writer) = stream.into_split();
+
+    // Reading is done in a separate task, as reading a message from the stream is not cancel-safe.
+    let (tx, mut rx) = mpsc::channel::<GatewayMessage>(100);
+    let _reader = AbortOnDrop(tokio::spawn(async move {
+        let mut reader = reader;
+        while let Ok(Some(message)) = GatewayMessage::read(&mut reader).await {
+            if tx.send(message).await.is_err() {
+                break;
+            }
+        }
+    }));
+
+    let interface = gateway.interface().name();
+
+    match gateway.protocol() {
+        ContainerCanGatewayProtocol::Raw { .. } => {
+            let socket = socketcan::tokio::CanSocket::open(&interface)
+                .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+
+            loop {
+                tokio::select! {
+                    message = rx.recv() => {
+                        let Some(message) = message else { return Ok(()) };
+
+                        if gateway.is_allowed(message.id).not() {
+                            warn!("Dropping frame with CAN ID {:#X} sent by executor via CAN gateway '{gateway}', as the ID is not allowed.", message.id);
+                            continue;
+                        }
+                        let Some(frame) = can_id(message.id).and_then(|id| CanFrame::new(id, &message.payload)) else {
+                            warn!("Dropping invalid frame with CAN ID {:#X} and {} bytes sent by executor via CAN gateway '{gateway}'.", message.id, message.payload.len());
+                            continue;
+                        };
+                        socket.write_frame(frame).await
+                            .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                    }
+                    frame = socket.read_frame() => {
+                        let frame = frame
+                            .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+
+                        if let CanFrame::Data(frame) = frame {
+                            if gateway.is_allowed(frame.raw_id()) {
+                                let message = GatewayMessage { id: frame.raw_id(), payload: frame.data().to_vec() };
+                                message.write(&mut writer).await
+                                    .map_err(Error::Connection)?;
+                            } else {
+                                trace!("Not forwarding frame with CAN ID {:#X} to executor via CAN gateway '{gateway}'.", frame.raw_id());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        ContainerCanGatewayProtocol::IsoTp { tx_id, rx_id } => {
+            let (tx_id, rx_id) = (*tx_id, *rx_id);
+
+            let socket = can_id(tx_id).zip(can_id(rx_id))
+                .ok_or(Error::Other { message: format!("Invalid CAN IDs for ISO-TP in CAN gateway '{gateway}'.") })
+                .and_then(|(source, destination)| {
+                    let socket = IsoTpSocket::open(&interface, source, destination)
+                        .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause: std::io::Error::other(cause) })?;
+                    socket.set_nonblocking(true)
+                        .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                    Ok(socket)
+                })?;
+            let mut socket = AsyncFd::new(socket)
+                .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+
+            loop {
+                tokio::select! {
+                    message = rx.recv() => {
+                        let Some(message) = message else { return Ok(()) };
+
+                        if message.id != tx_id {
+                            warn!("Dropping ISO-TP message with CAN ID {:#X} sent by executor via CAN gateway '{gateway}', as only the ID {tx_id:#X} is allowed.", message.id);
+                            continue;
+                        }
+                        if message.payload.len() > MAX_ISOTP_PAYLOAD_LENGTH {
+                            warn!("Dropping ISO-TP message with {} bytes sent by executor via CAN gateway '{gateway}', as at most {MAX_ISOTP_PAYLOAD_LENGTH} bytes are supported.", message.payload.len());
+                            continue;
+                        }
+                        loop {
+                            let mut guard = socket.writable_mut().await
+                                .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                            match guard.try_io(|socket| socket.get_ref().write(&message.payload)) {
+                                Ok(result) => {
+                                    result.map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                                    break;
+                                }
+                                Err(_would_block) => continue,
+                            }
+                        }
+                    }
+                    guard = socket.readable_mut() => {
+                        let mut guard = guard
+                            .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                        if let Ok(payload) = guard.try_io(|socket| socket.get_mut().read().map(|payload| payload.to_vec())) {
+                            let payload = payload
+                                .map_err(|cause| Error::CanSocket { interface: interface.clone(), cause })?;
+                            let message = GatewayMessage { id: rx_id, payload };
+                            message.write(&mut writer).await
+                                .map_err(Error::Connection)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn can_id(id: u32) -> Option<Id> {
+    if id <= u32::from(StandardId::MAX.as_raw()) {
+        StandardId::new(id as u16).map(Id::Standard)
+    } else {
+        ExtendedId::new(id).map(Id::Extended)
+    }
+}
+
+/// Message exchanged with the executor via the gateway socket,
+/// consisting of the CAN ID (4 bytes, big-endian), the payload length (2 bytes, big-endian) and the payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct GatewayMessage {
+    id: u32,
+    payload: Vec<u8>,
+}
+
+impl GatewayMessage {
+    /// Returns `None`, if the stream was closed between messages.
+    async fn read(reader: &mut (impl AsyncRead + Unpin)) -> std::io::Result<Option<Self>> {
+        let id = match reader.read_u32().await {
+            Ok(id) => id,
+            Err(cause) if cause.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(cause) => return Err(cause),
+        };
+        let length = reader.read_u16().await?;
+        let mut payload = vec![0; usize::from(length)];
+        reader.read_exact(&mut payload).await?;
+
+        Ok(Some(Self { id, payload }))
+    }
+
+    async fn write(&self, writer: &mut (impl AsyncWrite + Unpin)) -> std::io::Result<()> {
+        let length = u16::try_from(self.payload.len())
+            .map_err(|_| std::io::Error::new(ErrorKind::InvalidInput, "Payload of gateway message is too long."))?;
+
+        let mut buffer = Vec::with_capacity(6 + self.payload.len());
+        buffer.extend_from_slice(&self.id.to_be_bytes());
+        buffer.extend_from_slice(&length.to_be_bytes());
+        buffer.extend_from_slice(&self.payload);
+        writer.write_all(&buffer).await?;
+        writer.flush().await
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to provide executor CAN gateway at '{path}': {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+    #[error("Failure on CAN socket of interface '{interface}': {cause}")]
+    CanSocket { interface: String, cause: std::io::Error },
+    #[error("Failure on connection with executor: {0}")]
+    Connection(std::io::Error),
+    #[error("{message}")]
+    Other { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_read_written_gateway_messages() -> anyhow::Result<()> {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        let first = GatewayMessage { id: 0x7E0, payload: vec![0x02, 0x10, 0x03] };
+        let second = GatewayMessage { id: 0x1234_5678, payload: vec![] };
+        first.write(&mut client).await?;
+        second.write(&mut client).await?;
+        drop(client);
+
+        assert_eq!(GatewayMessage::read(&mut server).await?, Some(first));
+        assert_eq!(GatewayMessage::read(&mut server).await?, Some(second));
+        assert_eq!(GatewayMessage::read(&mut server).await?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn should_determine_standard_or_extended_can_ids() {
+        assert_eq!(can_id(0x7FF), StandardId::new(0x7FF).map(Id::Standard));
+        assert_eq!(can_id(0x800), ExtendedId::new(0x800).map(Id::Extended));
+        assert_eq!(can_id(0x2000_0000), None);
+    }
+}
@@ -1,16 +1,24 @@
-use std::{env, io::{Cursor, ErrorKind, Write}, path::PathBuf, process::Stdio};
+use std::{env, io::{Cursor, ErrorKind, Write}, path::PathBuf, process::Stdio, sync::Arc};
 
 use anyhow::Result;
 use tokio::{fs::{self, File}, io::{AsyncBufReadExt, AsyncReadExt, BufReader}, process::{Child, Command}, sync::{mpsc, watch}};
 use tracing::{error, info, warn};
-use url::Url;
 use uuid::Uuid;
 use walkdir::WalkDir;
 use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFileOptions}, ZipWriter};
 
-use opendut_types::peer::executor::{container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ResultsUrl};
+use opendut_types::util::net::NetworkInterfaceName;
+use opendut_util::telemetry::redaction::Redactor;
+use opendut_types::peer::executor::sandbox::ExecutorSandbox;
+use opendut_types::peer::executor::{container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, ContainerVolume, Engine, CONTAINER_CAN_GATEWAY_DIRECTORY, CONTAINER_SECRETS_DIRECTORY}, ExecutorId, ResultsUrl};
 
-use crate::service::test_execution::webdav_client::{self, WebdavClient};
+use crate::service::test_execution::can_gateway::{self, ExecutorCanGateways, OpenedCanGateways};
+use crate::service::test_execution::energy_meter::{self, EnergyMeter};
+use crate::service::test_execution::executor_status::{ExecutorHealth, ExecutorStatuses};
+use crate::service::test_execution::network_attachment;
+use crate::service::test_execution::sandbox;
+use crate::service::test_execution::secrets::{self, ExecutorSecrets, MaterializedSecrets};
+use crate::service::test_execution::upload_queue::{self, UploadKind, UploadQueue};
 
 #[derive(Debug)]
 enum ContainerState {
@@ -23,6 +31,7 @@ enum ContainerState {
 }
 
 pub struct ContainerConfiguration {
+    pub executor_id: ExecutorId,
     pub name: ContainerName,
     pub engine: Engine,
     pub image: ContainerImage,
@@ -32,47 +41,87 @@ pub struct ContainerConfiguration {
     pub ports: Vec<ContainerPortSpec>,
     pub devices: Vec<ContainerDevice>,
     pub volumes: Vec<ContainerVolume>,
+    pub secrets: Vec<ContainerSecret>,
+    pub can_gateways: Vec<ContainerCanGateway>,
+    pub network_attachment: ContainerNetworkAttachment,
+    /// Bridge of the cluster, which a dedicated interface of the [ContainerNetworkAttachment] is connected to.
+    pub bridge_name: NetworkInterfaceName,
     pub results_url: Option<ResultsUrl>,
+    pub sandbox: ExecutorSandbox,
 }
 
 pub struct ContainerManager{
     config: ContainerConfiguration,
     results_dir: PathBuf,
-    webdav_client: WebdavClient,
+    executor_secrets: ExecutorSecrets,
+    executor_can_gateways: ExecutorCanGateways,
+    energy_meter: EnergyMeter,
+    upload_queue: UploadQueue,
+    log_redactor: Arc<Redactor>,
+    executor_statuses: ExecutorStatuses,
     termination_channel_rx: watch::Receiver<bool>,
 }
 
 const MONITOR_INTERVAL_MS: u64 = 1000;
 const RESULTS_READY_FILE: &str = ".results_ready";
 const CONTAINER_RESULTS_DIRECTORY: &str = "/results";
+/// Logs beyond this size are not uploaded, to bound the memory used for buffering them.
+const MAX_UPLOADED_LOG_BYTES: usize = 16 * 1024 * 1024;
 
 impl ContainerManager {
 
-    pub fn new(container_configuration: ContainerConfiguration, termination_channel_rx: watch::Receiver<bool>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(container_configuration: ContainerConfiguration, executor_secrets: ExecutorSecrets, executor_can_gateways: ExecutorCanGateways, energy_meter: EnergyMeter, upload_queue: UploadQueue, log_redactor: Arc<Redactor>, executor_statuses: ExecutorStatuses, termination_channel_rx: watch::Receiver<bool>) -> Self {
         Self { 
             config: container_configuration,
             results_dir: env::temp_dir().join(format!("opendut-edgar-results_{}", Uuid::new_v4())),
-            webdav_client: WebdavClient::new("some_dummy_token".to_string()), // TODO: Authenticate with actual token
+            executor_secrets,
+            executor_can_gateways,
+            energy_meter,
+            upload_queue,
+            log_redactor,
+            executor_statuses,
             termination_channel_rx
         }
     }
 
     pub async fn start(&mut self) {
+        self.set_health(ExecutorHealth::Starting);
         match self.run().await {
             Ok(_) => (),
-            Err(cause) => error!("{}", cause.to_string()),
+            Err(cause) => {
+                self.set_health(ExecutorHealth::Failed);
+                error!("{}", cause.to_string())
+            }
         }
     }
 
+    fn set_health(&self, health: ExecutorHealth) {
+        self.executor_statuses.set(self.config.executor_id, &self.config.name.to_string(), health);
+    }
+
     async fn run(&mut self) -> Result<(), Error> {
         let mut results_uploaded = false;
 
         self.create_results_dir().await?;
-        let container_name = self.start_container().await?;
+
+        // Wiped when dropped, i.e. when this function returns, regardless of whether the container exited or an error occurred.
+        let secrets = self.executor_secrets.materialize(self.config.executor_id, &self.config.secrets).await
+            .map_err(|cause| Error::Secrets { container_name: self.config.name.clone(), cause })?;
+
+        // Closed when dropped, like the secrets.
+        let can_gateways = self.executor_can_gateways.open(self.config.executor_id, &self.config.can_gateways).await
+            .map_err(|cause| Error::CanGateways { container_name: self.config.name.clone(), cause })?;
+
+        let container_name = self.start_container(secrets.as_ref(), can_gateways.as_ref()).await?;
+
+        // Stops sampling when dropped, like the secrets.
+        let mut energy_measurement = self.energy_meter.start(self.config.executor_id);
         let mut log_reader = 
             ContainerLogReader::create(
                 self.config.engine.command_name(), 
-                container_name.clone()
+                container_name.clone(),
+                Arc::clone(&self.log_redactor),
             )?;
 
         loop {
@@ -90,15 +139,27 @@ impl ContainerManager {
             }
 
             match self.get_container_state(&container_name).await? {
-                ContainerState::Running => (),
+                ContainerState::Running => self.set_health(ExecutorHealth::Running),
                 ContainerState::Exited => {
+                    self.set_health(ExecutorHealth::Exited);
+                    if let Some(energy_measurement) = energy_measurement.take() {
+                        energy_measurement.finish(&self.results_dir).await
+                            .map_err(|cause| Error::EnergyMeasurement { container_name: self.config.name.clone(), cause })?;
+                        results_uploaded = false; // upload again, to include the energy measurements
+                    }
                     if ! results_uploaded {
                         self.remove_result_ready_indicator().await?;
                         self.upload_results().await?;
                     }
+                    log_reader.read().await;
+                    self.upload_logs(log_reader.take()).await?;
                     break
                 },
                 state => {
+                    self.set_health(match state {
+                        ContainerState::Created | ContainerState::Restarting => ExecutorHealth::Starting,
+                        _ => ExecutorHealth::Unhealthy,
+                    });
                     warn!("Unexpected container state of '{}': {:?}", self.config.name, state)
                 },
             }
@@ -130,12 +191,16 @@ impl ContainerManager {
         
     }
 
-    async fn start_container(&mut self) -> Result<String, Error>{
+    async fn start_container(&mut self, secrets: Option<&MaterializedSecrets>, can_gateways: Option<&OpenedCanGateways>) -> Result<String, Error>{
 
         let mut cmd = Command::new(self.config.engine.command_name());
         cmd.arg("run");
         cmd.arg("--detach");
-        cmd.arg("--net=host");
+        if self.config.network_attachment.is_host() {
+            cmd.arg("--net=host");
+        } else {
+            cmd.arg("--network=none");
+        }
 
         // TODO: Determining the name like this and then creating the container is theoretically susceptible to race conditions
         let mut container_name = String::new();
@@ -148,6 +213,14 @@ impl ContainerManager {
         cmd.args(["--name", container_name.as_str()]);
 
         cmd.args(["--mount", format!("type=bind,source={},target={}", self.results_dir.to_string_lossy(), CONTAINER_RESULTS_DIRECTORY).as_str()]);
+
+        if let Some(secrets) = secrets {
+            cmd.args(["--mount", format!("type=bind,source={},target={},readonly", secrets.path().to_string_lossy(), CONTAINER_SECRETS_DIRECTORY).as_str()]);
+        }
+
+        if let Some(can_gateways) = can_gateways {
+            cmd.args(["--mount", format!("type=bind,source={},target={}", can_gateways.path().to_string_lossy(), CONTAINER_CAN_GATEWAY_DIRECTORY).as_str()]);
+        }
         
         for env in &self.config.envs {
             cmd.args(["--env", &format!("{}={}", env.name(), env.value())]);
@@ -162,6 +235,10 @@ impl ContainerManager {
             cmd.args(["--devices", device.value()]);
         }
 
+        let sandbox_arguments = sandbox::container_run_arguments(&self.config.sandbox)
+            .map_err(|cause| Error::Sandbox { container_name: self.config.name.clone(), cause })?;
+        cmd.args(sandbox_arguments);
+
         cmd.arg(self.config.image.to_string());
 
         if let ContainerCommand::Value(command) = &self.config.command {
@@ -177,6 +254,11 @@ impl ContainerManager {
 
         if output.status.success() {
             info!("Started container {}", self.config.name);
+
+            if let Err(cause) = network_attachment::attach(&self.config.engine, &container_name, &self.config.network_attachment, &self.config.bridge_name).await {
+                self.stop_container(&container_name).await?;
+                return Err(Error::NetworkAttachment { container_name: self.config.name.clone(), cause });
+            }
             Ok(container_name)
         } else {
             Err(Error::Other { message: format!("Starting container '{}' failed: {}", self.config.name, String::from_utf8_lossy(&output.stderr)).to_string()})
@@ -221,7 +303,7 @@ impl ContainerManager {
     }
 
     async fn upload_results(&self) -> Result<(), Error>{
-        info!("Starting upload for results of {}", self.config.name);
+        info!("Queueing upload for results of {}", self.config.name);
         let results_url = match &self.config.results_url {
             Some(results_url) => results_url.value(),
             None => {
@@ -235,26 +317,29 @@ impl ContainerManager {
         let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::BZIP2).large_file(false);
         create_zip_from_directory(&mut zipped_data, &self.results_dir, zip_options).await.map_err(|cause| Error::ResultZipping { path: self.results_dir.clone(), cause })?;
 
-        self.webdav_client.create_collection_path(results_url.clone())
+        let file_name = format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), self.config.name);
+        self.upload_queue.enqueue(UploadKind::Results, results_url.clone(), &file_name, zipped_data)
             .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_url.clone(), cause })?;
-
-        let results_file_url = results_url.join(
-            format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), self.config.name).as_str()
-        ).map_err(|cause| Error::Other { message: format!("Failed to construct URL for results directory: {}", cause) })?;
+            .map_err(|cause| Error::ResultQueueing { container_name: self.config.name.clone(), cause })?;
 
-        let response = self.webdav_client.put(zipped_data, results_file_url.clone())
-            .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_file_url.clone(), cause })?;
+        Ok(())
+    }
 
-        match response.status().is_success() {
-            true => {
-                info!("Successfully uploaded results of {}", self.config.name);
-                Ok(())
-            },
-            false => Err(Error::ResultUploadingServer { container_name: self.config.name.clone(), url: results_file_url.clone(), status: response.status() }),
+    /// Logs are queued with a lower priority than results, so that they do not delay the results on a slow uplink.
+    async fn upload_logs(&self, logs: Vec<u8>) -> Result<(), Error> {
+        let Some(results_url) = &self.config.results_url else {
+            return Ok(());
+        };
+        if logs.is_empty() {
+            return Ok(());
         }
 
+        let file_name = format!("{}_{}.log", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), self.config.name);
+        self.upload_queue.enqueue(UploadKind::Logs, results_url.value().clone(), &file_name, logs)
+            .await
+            .map_err(|cause| Error::ResultQueueing { container_name: self.config.name.clone(), cause })?;
+
+        Ok(())
     }
 
     async fn create_results_dir(&mut self) -> Result<(), Error>{
@@ -313,10 +398,18 @@ pub enum Error {
     CommandLineProgramExecution { command: String, cause: std::io::Error },
     #[error("Failure while creating a ZIP archive of the test results at '{path}' : {cause}")]
     ResultZipping { path: PathBuf, cause: anyhow::Error },
-    #[error("Failure while uploading test results to '{url}': {cause}")]
-    ResultUploadingInternal { url: Url, cause: webdav_client::Error },
-    #[error("Failure while uploading test results for '{container_name}' to '{url}' (HTTP status {status})")]
-    ResultUploadingServer { container_name: ContainerName, url: Url, status: reqwest::StatusCode },
+    #[error("Failure while queueing the upload of test results for '{container_name}': {cause}")]
+    ResultQueueing { container_name: ContainerName, cause: upload_queue::Error },
+    #[error("Failure while providing secrets for '{container_name}': {cause}")]
+    Secrets { container_name: ContainerName, cause: secrets::Error },
+    #[error("Failure while providing CAN gateways for '{container_name}': {cause}")]
+    CanGateways { container_name: ContainerName, cause: can_gateway::Error },
+    #[error("Failure while attaching '{container_name}' to the cluster network: {cause}")]
+    NetworkAttachment { container_name: ContainerName, cause: network_attachment::Error },
+    #[error("Failure while sandboxing '{container_name}': {cause}")]
+    Sandbox { container_name: ContainerName, cause: sandbox::Error },
+    #[error("Failure while measuring the energy consumption during '{container_name}': {cause}")]
+    EnergyMeasurement { container_name: ContainerName, cause: energy_meter::Error },
     #[error("{message}")]
     Other { message: String },
 }
@@ -324,10 +417,12 @@ pub enum Error {
 struct ContainerLogReader {
     _log_proc: Child,
     receiver: mpsc::Receiver<Vec<u8>>,
+    logs: Vec<u8>,
+    redactor: Arc<Redactor>,
 }
 
 impl ContainerLogReader {
-    pub fn create(engine: &str, container_name: String) -> Result<Self, Error> {
+    pub fn create(engine: &str, container_name: String, redactor: Arc<Redactor>) -> Result<Self, Error> {
         let mut cmd = Command::new(engine);
         cmd.args(["logs", "--timestamps", "--follow"]);
         cmd.arg(container_name);
@@ -370,6 +465,8 @@ impl ContainerLogReader {
             Self {
                 _log_proc: child,
                 receiver: rx,
+                logs: Vec::new(),
+                redactor,
             }
         )
     }
@@ -377,8 +474,16 @@ impl ContainerLogReader {
 
     async fn read(&mut self) {
         while let Ok(line) = self.receiver.try_recv() {
-            info!("Received line: {:?}", String::from_utf8_lossy(&line))
+            let line = self.redactor.redact_bytes(&line);
+            info!("Received line: {:?}", String::from_utf8_lossy(&line));
+            if self.logs.len() + line.len() <= MAX_UPLOADED_LOG_BYTES {
+                self.logs.extend_from_slice(&line);
+            }
         }
     }
+
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.logs)
+    }
     
 }
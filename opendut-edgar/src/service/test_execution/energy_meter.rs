@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use opendut_carl_api::carl::broker;
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_types::peer::executor::ExecutorId;
+
+/// TCP port, on which most lab power analyzers and smart PDUs accept raw SCPI commands.
+const DEFAULT_SCPI_PORT: u16 = 5025;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+pub const ENERGY_MEASUREMENTS_FILE: &str = "energy.csv";
+
+#[derive(Clone, Debug)]
+pub enum EnergyMeterOptions {
+    Disabled,
+    Enabled {
+        name: String,
+        address: EnergyMeterAddress,
+        voltage_query: String,
+        current_query: String,
+        sample_interval: Duration,
+        report_interval: Duration,
+    },
+}
+impl EnergyMeterOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("executor.energy.meter.enabled")?;
+
+        if enabled {
+            let address = config.get_string("executor.energy.meter.address")?;
+            let address = EnergyMeterAddress::parse(&address)?;
+
+            Ok(EnergyMeterOptions::Enabled {
+                name: config.get_string("executor.energy.meter.name")?,
+                address,
+                voltage_query: config.get_string("executor.energy.meter.query.voltage")?,
+                current_query: config.get_string("executor.energy.meter.query.current")?,
+                sample_interval: Duration::from_millis(config.get::<u64>("executor.energy.meter.sample.interval.ms")?),
+                report_interval: Duration::from_millis(config.get::<u64>("executor.energy.meter.report.interval.ms")?),
+            })
+        } else {
+            Ok(EnergyMeterOptions::Disabled)
+        }
+    }
+}
+
+/// Connection to the meter, e.g. `tcp://192.168.56.20:5025` or `serial:///dev/ttyUSB0`.
+/// The line settings of serial devices (baud rate etc.) are expected to be configured by the operating system, e.g. via `stty`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnergyMeterAddress {
+    Tcp { host: String, port: u16 },
+    Serial { device: PathBuf },
+}
+impl EnergyMeterAddress {
+    pub fn parse(address: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(address)
+            .map_err(|cause| anyhow::anyhow!("Invalid address '{address}' of energy meter: {cause}"))?;
+
+        match url.scheme() {
+            "tcp" => {
+                let host = url.host_str()
+                    .ok_or_else(|| anyhow::anyhow!("Address '{address}' of energy meter has no host."))?;
+                Ok(EnergyMeterAddress::Tcp { host: host.to_owned(), port: url.port().unwrap_or(DEFAULT_SCPI_PORT) })
+            }
+            "serial" => Ok(EnergyMeterAddress::Serial { device: PathBuf::from(url.path()) }),
+            other => Err(anyhow::anyhow!("Unsupported scheme '{other}' in address '{address}' of energy meter, expected 'tcp' or 'serial'.")),
+        }
+    }
+}
+
+trait ScpiStream: AsyncBufRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncBufRead + AsyncWrite + Unpin + Send> ScpiStream for T {}
+
+/// Sends SCPI queries line by line and reads the response line of each.
+struct ScpiClient {
+    stream: Box<dyn ScpiStream>,
+}
+impl ScpiClient {
+    async fn connect(address: &EnergyMeterAddress) -> Result<Self, Error> {
+        let stream: Box<dyn ScpiStream> = match address {
+            EnergyMeterAddress::Tcp { host, port } => {
+                let stream = tokio::net::TcpStream::connect((host.as_str(), *port)).await
+                    .map_err(|cause| Error::Connect { address: format!("{host}:{port}"), cause })?;
+                Box::new(BufReader::new(stream))
+            }
+            EnergyMeterAddress::Serial { device } => {
+                let file = tokio::fs::OpenOptions::new().read(true).write(true).open(device).await
+                    .map_err(|cause| Error::Connect { address: device.display().to_string(), cause })?;
+                Box::new(BufReader::new(file))
+            }
+        };
+        Ok(Self { stream })
+    }
+
+    async fn query(&mut self, query: &str) -> Result<f64, Error> {
+        let exchange = async {
+            self.stream.write_all(format!("{query}\n").as_bytes()).await?;
+            self.stream.flush().await?;
+            let mut response = String::new();
+            self.stream.read_line(&mut response).await?;
+            Ok::<_, std::io::Error>(response)
+        };
+        let response = tokio::time::timeout(QUERY_TIMEOUT, exchange).await
+            .map_err(|_| Error::Timeout { query: query.to_owned() })?
+            .map_err(|cause| Error::Query { query: query.to_owned(), cause })?;
+
+        parse_scpi_number(&response)
+            .ok_or_else(|| Error::InvalidResponse { query: query.to_owned(), response: response.trim().to_owned() })
+    }
+}
+
+/// Parses numeric SCPI responses like `+1.234500E+01`. Some meters append the unit, e.g. `12.345V`.
+fn parse_scpi_number(response: &str) -> Option<f64> {
+    let response = response.trim();
+    let number = response.trim_end_matches(|c: char| c.is_ascii_alphabetic() && c != 'E' && c != 'e');
+    number.parse::<f64>().ok()
+        .or_else(|| response.split(',').next()?.trim().parse::<f64>().ok())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    pub elapsed: Duration,
+    pub voltage: f64,
+    pub current: f64,
+}
+impl Sample {
+    pub fn power(&self) -> f64 {
+        self.voltage * self.current
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnergySummary {
+    pub samples: usize,
+    pub duration: Duration,
+    pub average_power_watts: f64,
+    pub peak_power_watts: f64,
+    pub energy_watt_hours: f64,
+}
+impl EnergySummary {
+    /// Integrates the power between consecutive samples with the trapezoidal rule.
+    pub fn from_samples(samples: &[Sample]) -> Self {
+        let energy_joules = samples.windows(2)
+            .map(|pair| {
+                let interval = pair[1].elapsed.saturating_sub(pair[0].elapsed).as_secs_f64();
+                (pair[0].power() + pair[1].power()) / 2.0 * interval
+            })
+            .sum::<f64>();
+
+        let duration = match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) => last.elapsed.saturating_sub(first.elapsed),
+            _ => Duration::ZERO,
+        };
+
+        let average_power_watts = if duration.is_zero() {
+            samples.first().map(Sample::power).unwrap_or_default()
+        } else {
+            energy_joules / duration.as_secs_f64()
+        };
+
+        Self {
+            samples: samples.len(),
+            duration,
+            average_power_watts,
+            peak_power_watts: samples.iter().map(Sample::power).fold(0.0, f64::max),
+            energy_watt_hours: energy_joules / 3600.0,
+        }
+    }
+}
+
+/// Samples an energy meter attached to the peer, while executors run, to measure the power consumption of the DUT.
+#[derive(Clone)]
+pub struct EnergyMeter {
+    options: EnergyMeterOptions,
+    tx_outbound: Arc<RwLock<Option<broker::Upstream>>>,
+}
+impl EnergyMeter {
+    pub fn new(options: EnergyMeterOptions) -> Self {
+        Self { options, tx_outbound: Default::default() }
+    }
+
+    pub async fn set_stream(&self, tx_outbound: Option<broker::Upstream>) {
+        *self.tx_outbound.write().await = tx_outbound;
+    }
+
+    /// Starts sampling for the executor. Returns `None`, if no energy meter is configured.
+    /// Sampling stops, when the returned measurement is finished or dropped.
+    pub fn start(&self, executor_id: ExecutorId) -> Option<EnergyMeasurement> {
+        let EnergyMeterOptions::Enabled { name, address, voltage_query, current_query, sample_interval, report_interval } = Clone::clone(&self.options) else {
+            return None;
+        };
+        let (tx_stop, mut rx_stop) = watch::channel(false);
+        let tx_outbound = Arc::clone(&self.tx_outbound);
+        let started_at = SystemTime::now();
+
+        let task = tokio::spawn(async move {
+            let mut samples = Vec::new();
+            let mut client: Option<ScpiClient> = None;
+            let start = Instant::now();
+            let mut last_report = Instant::now();
+            let mut interval = tokio::time::interval(sample_interval);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = rx_stop.changed() => break,
+                }
+
+                if client.is_none() {
+                    match ScpiClient::connect(&address).await {
+                        Ok(connected) => client = Some(connected),
+                        Err(cause) => {
+                            warn!("Could not connect to energy meter '{name}': {cause}");
+                            continue;
+                        }
+                    }
+                }
+                if let Some(connected) = client.as_mut() {
+                    let sample = async {
+                        let voltage = connected.query(&voltage_query).await?;
+                        let current = connected.query(&current_query).await?;
+                        Ok::<_, Error>(Sample { elapsed: start.elapsed(), voltage, current })
+                    }.await;
+
+                    match sample {
+                        Ok(sample) => samples.push(sample),
+                        Err(cause) => {
+                            warn!("Failed to sample energy meter '{name}', reconnecting: {cause}");
+                            client = None;
+                        }
+                    }
+                }
+
+                if last_report.elapsed() >= report_interval {
+                    last_report = Instant::now();
+                    report(&tx_outbound, executor_id, &name, started_at, &samples, false).await;
+                }
+            }
+
+            report(&tx_outbound, executor_id, &name, started_at, &samples, true).await;
+            samples
+        });
+
+        debug!("Started measuring energy of executor <{executor_id}>.");
+        Some(EnergyMeasurement { executor_id, tx_stop, task })
+    }
+}
+
+async fn report(tx_outbound: &RwLock<Option<broker::Upstream>>, executor_id: ExecutorId, meter: &str, started_at: SystemTime, samples: &[Sample], completed: bool) {
+    let summary = EnergySummary::from_samples(samples);
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::EnergyMeasurement(peer_messaging_broker::EnergyMeasurement {
+            executor_id: Some(executor_id.into()),
+            meter: meter.to_owned(),
+            started_unix_millis: started_at.duration_since(SystemTime::UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            duration_ms: summary.duration.as_millis() as u64,
+            samples: summary.samples as u64,
+            average_power_watts: summary.average_power_watts,
+            peak_power_watts: summary.peak_power_watts,
+            energy_watt_hours: summary.energy_watt_hours,
+            completed,
+        })),
+        context: None,
+    };
+
+    match tx_outbound.read().await.as_ref() {
+        Some(tx_outbound) => {
+            let _ignore_result = tx_outbound.send(message).await
+                .inspect_err(|cause| warn!("Failed to report energy measurement of executor <{executor_id}> to CARL: {cause}"));
+        }
+        None => debug!("Not connected to CARL. Not reporting energy measurement of executor <{executor_id}>."),
+    }
+}
+
+pub struct EnergyMeasurement {
+    executor_id: ExecutorId,
+    tx_stop: watch::Sender<bool>,
+    task: JoinHandle<Vec<Sample>>,
+}
+impl EnergyMeasurement {
+    /// Stops sampling and writes the samples as CSV into the results directory, so that they are uploaded with the executor's results.
+    pub async fn finish(self, results_dir: &std::path::Path) -> Result<EnergySummary, Error> {
+        let Self { executor_id, tx_stop, task } = self;
+
+        let _ignore_result = tx_stop.send(true);
+        let samples = task.await
+            .map_err(|cause| Error::Sampling { cause: cause.to_string() })?;
+
+        let mut csv = String::from("elapsed_ms,voltage_v,current_a,power_w\n");
+        for sample in &samples {
+            csv.push_str(&format!("{},{},{},{}\n", sample.elapsed.as_millis(), sample.voltage, sample.current, sample.power()));
+        }
+        let path = results_dir.join(ENERGY_MEASUREMENTS_FILE);
+        tokio::fs::write(&path, csv).await
+            .map_err(|cause| Error::WriteResults { path, cause })?;
+
+        let summary = EnergySummary::from_samples(&samples);
+        info!("Measured {:.3} Wh with an average of {:.2} W during execution of executor <{executor_id}>.", summary.energy_watt_hours, summary.average_power_watts);
+        Ok(summary)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to connect to energy meter at '{address}': {cause}")]
+    Connect { address: String, cause: std::io::Error },
+    #[error("Failed to query '{query}' from energy meter: {cause}")]
+    Query { query: String, cause: std::io::Error },
+    #[error("Energy meter did not respond to '{query}' in time.")]
+    Timeout { query: String },
+    #[error("Energy meter responded to '{query}' with '{response}', which is not a number.")]
+    InvalidResponse { query: String, response: String },
+    #[error("Sampling of energy meter failed: {cause}")]
+    Sampling { cause: String },
+    #[error("Failed to write energy measurements to '{path}': {cause}")]
+    WriteResults { path: PathBuf, cause: std::io::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_integrate_power_over_samples() {
+        let samples = vec![
+            Sample { elapsed: Duration::from_secs(0), voltage: 12.0, current: 1.0 },
+            Sample { elapsed: Duration::from_secs(1800), voltage: 12.0, current: 1.0 },
+            Sample { elapsed: Duration::from_secs(3600), voltage: 12.0, current: 3.0 },
+        ];
+
+        let summary = EnergySummary::from_samples(&samples);
+
+        assert_eq!(summary.samples, 3);
+        assert_eq!(summary.duration, Duration::from_secs(3600));
+        assert_eq!(summary.energy_watt_hours, 18.0);
+        assert_eq!(summary.average_power_watts, 18.0);
+        assert_eq!(summary.peak_power_watts, 36.0);
+    }
+
+    #[test]
+    fn should_parse_scpi_numbers() {
+        assert_eq!(parse_scpi_number("+1.234500E+01\n"), Some(12.345));
+        assert_eq!(parse_scpi_number("0.52A\r\n"), Some(0.52));
+        assert_eq!(parse_scpi_number("12.1,OK"), Some(12.1));
+        assert_eq!(parse_scpi_number("ERROR"), None);
+    }
+
+    #[test]
+    fn should_parse_energy_meter_addresses() -> anyhow::Result<()> {
+        assert_eq!(EnergyMeterAddress::parse("tcp://192.168.56.20")?, EnergyMeterAddress::Tcp { host: String::from("192.168.56.20"), port: DEFAULT_SCPI_PORT });
+        assert_eq!(EnergyMeterAddress::parse("serial:///dev/ttyUSB0")?, EnergyMeterAddress::Serial { device: PathBuf::from("/dev/ttyUSB0") });
+        assert!(EnergyMeterAddress::parse("http://192.168.56.20").is_err());
+        Ok(())
+    }
+}
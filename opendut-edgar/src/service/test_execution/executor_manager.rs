@@ -1,27 +1,49 @@
+use std::future::Future;
 use std::sync::{Arc, Mutex};
 
 use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorKind}};
+use opendut_types::util::net::NetworkInterfaceName;
+use opendut_util::telemetry::redaction::Redactor;
 use tokio::sync::watch::{self, Sender};
 use tracing::{debug, warn};
 
+use crate::service::test_execution::can_gateway::ExecutorCanGateways;
 use crate::service::test_execution::container_manager::{ContainerManager, ContainerConfiguration};
+use crate::service::test_execution::energy_meter::EnergyMeter;
+use crate::service::test_execution::executor_status::ExecutorStatuses;
+use crate::service::test_execution::secrets::ExecutorSecrets;
+use crate::service::test_execution::upload_queue::UploadQueue;
 
 pub type ExecutorManagerRef = Arc<Mutex<ExecutorManager>>;
 
 #[derive(Debug)]
 pub struct ExecutorManager {
     tx_termination_channels: Vec<Sender<bool>>,
+    executor_secrets: ExecutorSecrets,
+    executor_can_gateways: ExecutorCanGateways,
+    energy_meter: EnergyMeter,
+    upload_queue: UploadQueue,
+    /// Applied to the logs of executors, before they are uploaded.
+    log_redactor: Arc<Redactor>,
+    executor_statuses: ExecutorStatuses,
 }
 
 impl ExecutorManager {
-    pub fn create() -> ExecutorManagerRef {
+    pub fn create(executor_secrets: ExecutorSecrets, executor_can_gateways: ExecutorCanGateways, energy_meter: EnergyMeter, upload_queue: UploadQueue, log_redactor: Redactor, executor_statuses: ExecutorStatuses) -> ExecutorManagerRef {
         Arc::new(Mutex::new(Self {
             tx_termination_channels: Vec::new(),
+            executor_secrets,
+            executor_can_gateways,
+            energy_meter,
+            upload_queue,
+            log_redactor: Arc::new(log_redactor),
+            executor_statuses,
         }))
     }
 
-    pub fn create_new_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>) {
+    pub fn create_new_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>, bridge_name: &NetworkInterfaceName) {
         debug!("Creating executors.");
+        self.executor_statuses.clear(); //the executors of the previous configuration were terminated beforehand
 
         let executors = executors.into_iter()
             .filter_map(|executor| { //TODO properly handle Present vs. Absent
@@ -36,7 +58,7 @@ impl ExecutorManager {
 
             let (tx, rx) = watch::channel(false);
 
-            let ExecutorDescriptor { id: _, kind, results_url } = executor;
+            let ExecutorDescriptor { id, kind, results_url, sandbox } = executor;
 
             match kind {
                 ExecutorKind::Executable => warn!("Executing Executable not yet implemented."),
@@ -50,8 +72,12 @@ impl ExecutorManager {
                     ports,
                     command,
                     args,
+                    secrets,
+                    can_gateways,
+                    network_attachment,
                 } => {
                     let container_config = ContainerConfiguration{
+                        executor_id: id,
                         name,
                         engine,
                         image,
@@ -62,9 +88,20 @@ impl ExecutorManager {
                         ports,
                         devices,
                         volumes,
+                        secrets,
+                        can_gateways,
+                        network_attachment,
+                        bridge_name: Clone::clone(bridge_name),
+                        sandbox,
                     };
+                    let executor_secrets = Clone::clone(&self.executor_secrets);
+                    let executor_can_gateways = Clone::clone(&self.executor_can_gateways);
+                    let energy_meter = Clone::clone(&self.energy_meter);
+                    let upload_queue = Clone::clone(&self.upload_queue);
+                    let log_redactor = Arc::clone(&self.log_redactor);
+                    let executor_statuses = Clone::clone(&self.executor_statuses);
                     tokio::spawn(async move {
-                        ContainerManager::new(container_config, rx).start().await;
+                        ContainerManager::new(container_config, executor_secrets, executor_can_gateways, energy_meter, upload_queue, log_redactor, executor_statuses, rx).start().await;
                     });
                 }
             }
@@ -73,12 +110,24 @@ impl ExecutorManager {
     }
 
     pub fn terminate_executors(&mut self) {
+        let _ = self.terminate_executors_gracefully();
+    }
+
+    /// Terminates the executors and returns a future, which completes once all of them stopped,
+    /// i.e. their containers exited and their results were queued for uploading.
+    pub fn terminate_executors_gracefully(&mut self) -> impl Future<Output=()> + Send + 'static {
         debug!("Terminating executors.");
         for tx_termination_channel in &self.tx_termination_channels {
             if let Err(cause) = tx_termination_channel.send(true) {
                 warn!("Failed to send termination signal to executor, perhaps it already terminated? Cause: {cause}");
             }
         }
-        self.tx_termination_channels.clear();
+        let tx_termination_channels = std::mem::take(&mut self.tx_termination_channels);
+
+        async move {
+            for tx_termination_channel in tx_termination_channels {
+                tx_termination_channel.closed().await; //closed, when the executor dropped its receiver
+            }
+        }
     }
 }
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use opendut_types::peer::executor::ExecutorId;
+
+/// Health of the executors of this peer, as last observed by their container managers, e.g. to be reported as metrics.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutorStatuses {
+    inner: Arc<Mutex<HashMap<ExecutorId, ExecutorStatus>>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutorStatus {
+    pub name: String,
+    pub health: ExecutorHealth,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutorHealth {
+    /// The container is being created or restarted.
+    Starting,
+    Running,
+    /// The container exited on its own or was stopped.
+    Exited,
+    /// The container is paused or dead, i.e. not running without having exited.
+    Unhealthy,
+    /// The container manager failed, e.g. the container could not be started.
+    Failed,
+}
+
+impl ExecutorHealth {
+    pub const ALL: [ExecutorHealth; 5] = [ExecutorHealth::Starting, ExecutorHealth::Running, ExecutorHealth::Exited, ExecutorHealth::Unhealthy, ExecutorHealth::Failed];
+}
+
+impl fmt::Display for ExecutorHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorHealth::Starting => write!(f, "starting"),
+            ExecutorHealth::Running => write!(f, "running"),
+            ExecutorHealth::Exited => write!(f, "exited"),
+            ExecutorHealth::Unhealthy => write!(f, "unhealthy"),
+            ExecutorHealth::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl ExecutorStatuses {
+    pub fn set(&self, executor_id: ExecutorId, name: &str, health: ExecutorHealth) {
+        self.inner.lock().unwrap()
+            .insert(executor_id, ExecutorStatus { name: name.to_owned(), health });
+    }
+
+    /// Forgets the executors of a previous peer configuration.
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().clear();
+    }
+
+    /// Sorted by name, so that reports are stable.
+    pub fn snapshot(&self) -> Vec<(ExecutorId, ExecutorStatus)> {
+        let mut statuses = self.inner.lock().unwrap()
+            .iter()
+            .map(|(executor_id, status)| (*executor_id, Clone::clone(status)))
+            .collect::<Vec<_>>();
+        statuses.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+        statuses
+    }
+}
@@ -1,3 +1,10 @@
+pub mod can_gateway;
 pub mod container_manager;
+pub mod energy_meter;
+pub mod executor_status;
+pub mod network_attachment;
+pub mod sandbox;
+pub mod secrets;
+pub mod upload_queue;
 mod webdav_client;
 pub mod executor_manager;
\ No newline at end of file
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+use opendut_types::peer::executor::container::{CommandName, ContainerNetworkAttachment, Engine, CONTAINER_NETWORK_ATTACHMENT_INTERFACE};
+use opendut_types::util::net::NetworkInterfaceName;
+
+/// `IFF_UP` from `linux/if.h`, as reported in `/sys/class/net/<interface>/flags`.
+const INTERFACE_FLAG_UP: u32 = 0x1;
+
+/// Provides a container with a dedicated interface on the cluster network, for test tools which require Layer 2 visibility with their own MAC address.
+/// The container is started without network (`--network=none`) and the interface is moved into its network namespace afterwards.
+///
+/// No cleanup is necessary: When the container exits, its network namespace is destroyed,
+/// which deletes a macvlan interface and returns an SR-IOV virtual function to the host.
+pub async fn attach(engine: &Engine, container_name: &str, attachment: &ContainerNetworkAttachment, bridge_name: &NetworkInterfaceName) -> Result<(), Error> {
+    if attachment.is_host() {
+        return Ok(());
+    }
+    let pid = container_pid(engine, container_name).await?;
+
+    let interface = match attachment {
+        ContainerNetworkAttachment::Host => return Ok(()),
+        ContainerNetworkAttachment::Macvlan => {
+            let interface = format!("macvlan{pid}"); //interface names are limited to 15 characters
+            run(Command::new("ip").args(["link", "add", &interface, "link", bridge_name.name().as_str(), "type", "macvlan", "mode", "bridge"])).await?;
+            debug!("Created macvlan interface '{interface}' on bridge '{bridge_name}' for container '{container_name}'.");
+
+            if let Err(cause) = move_into_container(&interface, pid).await {
+                if let Err(cause) = run(Command::new("ip").args(["link", "delete", &interface])).await {
+                    warn!("Failed to delete macvlan interface '{interface}' after failed attachment: {cause}");
+                }
+                return Err(cause);
+            }
+            interface
+        }
+        ContainerNetworkAttachment::SrIov { physical_function } => {
+            let interface = find_free_virtual_function(physical_function).await?;
+            debug!("Using SR-IOV virtual function '{interface}' of '{physical_function}' for container '{container_name}'.");
+
+            move_into_container(&interface, pid).await?;
+            interface
+        }
+    };
+
+    info!("Attached interface '{interface}' as '{CONTAINER_NETWORK_ATTACHMENT_INTERFACE}' to container '{container_name}' ({attachment}).");
+    Ok(())
+}
+
+async fn container_pid(engine: &Engine, container_name: &str) -> Result<u32, Error> {
+    let output = Command::new(engine.command_name())
+        .args(["inspect", "-f", "{{.State.Pid}}", container_name])
+        .output().await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} inspect", engine.command_name()), cause })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.trim().parse::<u32>().ok()
+        .filter(|pid| output.status.success() && *pid != 0)
+        .ok_or_else(|| Error::ContainerNotRunning { container_name: container_name.to_owned(), output: format!("{}{}", stdout.trim(), String::from_utf8_lossy(&output.stderr).trim()) })
+}
+
+async fn move_into_container(interface: &str, pid: u32) -> Result<(), Error> {
+    let pid = pid.to_string();
+    run(Command::new("ip").args(["link", "set", interface, "netns", &pid])).await?;
+    run(Command::new("nsenter").args(["--target", &pid, "--net", "ip", "link", "set", interface, "name", CONTAINER_NETWORK_ATTACHMENT_INTERFACE, "up"])).await?;
+    Ok(())
+}
+
+/// Virtual functions, which are already in use by a container, are not visible in the network namespace of EDGAR anymore.
+/// Virtual functions, which are up, are assumed to be in use by the host.
+async fn find_free_virtual_function(physical_function: &NetworkInterfaceName) -> Result<String, Error> {
+    let device_directory = Path::new("/sys/class/net").join(physical_function.name()).join("device");
+
+    let mut entries = fs::read_dir(&device_directory).await
+        .map_err(|cause| Error::Io { path: device_directory.clone(), cause })?;
+
+    let mut virtual_functions = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(|cause| Error::Io { path: device_directory.clone(), cause })? {
+        if entry.file_name().to_string_lossy().starts_with("virtfn") {
+            virtual_functions.push(entry.path().join("net"));
+        }
+    }
+    virtual_functions.sort();
+
+    for net_directory in virtual_functions {
+        let Ok(mut interfaces) = fs::read_dir(&net_directory).await else { continue };
+
+        while let Ok(Some(interface)) = interfaces.next_entry().await {
+            let interface = interface.file_name().to_string_lossy().into_owned();
+            if is_up(&interface).await? {
+                continue;
+            }
+            return Ok(interface);
+        }
+    }
+
+    Err(Error::NoFreeVirtualFunction { physical_function: Clone::clone(physical_function) })
+}
+
+async fn is_up(interface: &str) -> Result<bool, Error> {
+    let path = Path::new("/sys/class/net").join(interface).join("flags");
+    let flags = fs::read_to_string(&path).await
+        .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+
+    let flags = u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16)
+        .map_err(|cause| Error::Io { path, cause: std::io::Error::other(cause) })?;
+
+    Ok(flags & INTERFACE_FLAG_UP != 0)
+}
+
+async fn run(command: &mut Command) -> Result<(), Error> {
+    let command_string = format!("{:?}", command.as_std());
+
+    let output = command.output().await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: Clone::clone(&command_string), cause })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(Error::CommandFailed { command: command_string, stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned() })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to execute '{command}': {cause}")]
+    CommandLineProgramExecution { command: String, cause: std::io::Error },
+    #[error("Command '{command}' failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+    #[error("Could not determine the process of container '{container_name}', perhaps it already exited? Output: {output}")]
+    ContainerNotRunning { container_name: String, output: String },
+    #[error("No free SR-IOV virtual function found for '{physical_function}'. Virtual functions can be enabled via '/sys/class/net/{physical_function}/device/sriov_numvfs'.")]
+    NoFreeVirtualFunction { physical_function: NetworkInterfaceName },
+    #[error("Failed to read '{path}': {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use opendut_types::peer::executor::sandbox::{ExecutorSandbox, SeccompProfile};
+
+/// Arguments for `docker run` or `podman run`, which make the container engine enforce the [ExecutorSandbox].
+///
+/// Both engines resolve user and group names within the image, so accounts which only exist on the peer have to be given by their numeric ID.
+pub fn container_run_arguments(sandbox: &ExecutorSandbox) -> Result<Vec<String>, Error> {
+    let mut arguments = vec![];
+
+    if let Some(user) = &sandbox.user {
+        arguments.push(String::from("--user"));
+        arguments.push(user.to_string());
+    }
+
+    match &sandbox.seccomp_profile {
+        SeccompProfile::Default => {}
+        SeccompProfile::Unconfined => {
+            arguments.push(String::from("--security-opt"));
+            arguments.push(String::from("seccomp=unconfined"));
+        }
+        SeccompProfile::File(path) => {
+            if !path.is_file() {
+                return Err(Error::SeccompProfileNotFound { path: Clone::clone(path) });
+            }
+            arguments.push(String::from("--security-opt"));
+            arguments.push(format!("seccomp={}", path.display()));
+        }
+    }
+
+    if sandbox.read_only_filesystem {
+        arguments.push(String::from("--read-only"));
+    }
+
+    Ok(arguments)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Seccomp profile '{path}' does not exist on this peer.")]
+    SeccompProfileNotFound { path: PathBuf },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::peer::executor::sandbox::ExecutorUser;
+
+    use super::*;
+
+    #[test]
+    fn should_not_restrict_an_unrestricted_container() -> anyhow::Result<()> {
+        let arguments = container_run_arguments(&ExecutorSandbox::default())?;
+
+        assert_that!(arguments, empty());
+        Ok(())
+    }
+
+    #[test]
+    fn should_restrict_user_seccomp_and_filesystem_of_a_container() -> anyhow::Result<()> {
+        let sandbox = ExecutorSandbox {
+            user: Some(ExecutorUser::try_from("1000:1000")?),
+            seccomp_profile: SeccompProfile::Unconfined,
+            read_only_filesystem: true,
+        };
+
+        let arguments = container_run_arguments(&sandbox)?;
+
+        assert_that!(arguments.join(" "), eq("--user 1000:1000 --security-opt seccomp=unconfined --read-only"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_for_a_missing_seccomp_profile() -> anyhow::Result<()> {
+        let sandbox = ExecutorSandbox {
+            seccomp_profile: SeccompProfile::try_from("/nonexistent/seccomp.json")?,
+            ..Default::default()
+        };
+
+        assert!(container_run_arguments(&sandbox).is_err());
+        Ok(())
+    }
+}
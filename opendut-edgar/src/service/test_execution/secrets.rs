@@ -0,0 +1,106 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use tokio::fs::{self, DirBuilder, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
+
+use opendut_carl_api::carl::broker::GetExecutorSecretsError;
+use opendut_carl_api::carl::{CarlClient, ClientError};
+use opendut_types::peer::executor::container::ContainerSecret;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+const SECRETS_DIRECTORY_MODE: u32 = 0o700;
+const SECRET_FILE_MODE: u32 = 0o400;
+
+/// Retrieves the secrets referenced by an executor from CARL and writes them into files,
+/// which are then mounted into the container. The secrets directory should be located on a tmpfs,
+/// so that the values never touch persistent storage.
+#[derive(Clone)]
+pub struct ExecutorSecrets {
+    carl: CarlClient,
+    self_id: PeerId,
+    directory: PathBuf,
+}
+
+impl ExecutorSecrets {
+    pub fn load(config: &config::Config, carl: CarlClient, self_id: PeerId) -> anyhow::Result<Self> {
+        let directory = PathBuf::from(config.get_string("executor.secrets.directory")?);
+        Ok(Self { carl, self_id, directory })
+    }
+
+    /// Returns `None`, if no secrets are referenced, so that no directory needs to be mounted.
+    pub async fn materialize(&self, executor_id: ExecutorId, secrets: &[ContainerSecret]) -> Result<Option<MaterializedSecrets>, Error> {
+        if secrets.is_empty() {
+            return Ok(None);
+        }
+
+        let mut carl = Clone::clone(&self.carl);
+        let values = carl.broker.get_executor_secrets(self.self_id, executor_id).await
+            .map_err(|cause| Error::Retrieval { executor_id, cause })?;
+
+        let directory = self.directory.join(executor_id.to_string());
+
+        match fs::remove_dir_all(&directory).await {
+            Ok(()) => warn!("Removed leftover secrets of executor <{executor_id}> from '{}'.", directory.display()),
+            Err(cause) if cause.kind() == ErrorKind::NotFound => {}
+            Err(cause) => return Err(Error::Io { path: directory, cause }),
+        }
+
+        fs::create_dir_all(&self.directory).await
+            .map_err(|cause| Error::Io { path: self.directory.clone(), cause })?;
+
+        DirBuilder::new()
+            .mode(SECRETS_DIRECTORY_MODE)
+            .create(&directory).await
+            .map_err(|cause| Error::Io { path: directory.clone(), cause })?;
+
+        let materialized = MaterializedSecrets { directory };
+
+        for secret in values {
+            let path = materialized.directory.join(secret.name.value());
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(SECRET_FILE_MODE)
+                .open(&path).await
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+            file.write_all(&secret.value).await
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+            file.sync_all().await
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+        }
+
+        debug!("Materialized {} secret(s) of executor <{executor_id}> into '{}'.", secrets.len(), materialized.directory.display());
+        Ok(Some(materialized))
+    }
+}
+
+/// Directory holding the secret files of one executor. It is wiped when this value is dropped.
+pub struct MaterializedSecrets {
+    directory: PathBuf,
+}
+
+impl MaterializedSecrets {
+    pub fn path(&self) -> &Path {
+        &self.directory
+    }
+}
+
+impl Drop for MaterializedSecrets {
+    fn drop(&mut self) {
+        match std::fs::remove_dir_all(&self.directory) {
+            Ok(()) => debug!("Wiped executor secrets in '{}'.", self.directory.display()),
+            Err(cause) => warn!("Failed to wipe executor secrets in '{}': {cause}", self.directory.display()),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to retrieve secrets of executor <{executor_id}> from CARL: {cause}")]
+    Retrieval { executor_id: ExecutorId, cause: ClientError<GetExecutorSecretsError> },
+    #[error("Failed to write executor secrets to '{path}': {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+}
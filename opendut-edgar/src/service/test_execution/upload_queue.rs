@@ -0,0 +1,511 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use crate::service::test_execution::webdav_client::{self, WebdavClient};
+
+const DATA_FILE_EXTENSION: &str = "data";
+const METADATA_FILE_EXTENSION: &str = "json";
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
+/// Kind of data to upload. Kinds are uploaded in the order of declaration, i.e. results are uploaded before logs and logs before packet captures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UploadKind {
+    Results,
+    Logs,
+    PacketCaptures,
+}
+
+impl UploadKind {
+    fn tag(&self) -> &'static str {
+        match self {
+            UploadKind::Results => "results",
+            UploadKind::Logs => "logs",
+            UploadKind::PacketCaptures => "captures",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "results" => Some(UploadKind::Results),
+            "logs" => Some(UploadKind::Logs),
+            "captures" => Some(UploadKind::PacketCaptures),
+            _ => None,
+        }
+    }
+
+    /// Whether queued uploads of this kind may be dropped to make room for newer uploads of the same kind.
+    /// Results are never dropped, newer results are rejected instead.
+    fn is_droppable(&self) -> bool {
+        match self {
+            UploadKind::Results => false,
+            UploadKind::Logs => true,
+            UploadKind::PacketCaptures => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UploadQueueOptions {
+    directory: PathBuf,
+    size_limit_bytes: u64,
+    retry_interval_min: Duration,
+    retry_interval_max: Duration,
+}
+
+impl UploadQueueOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            directory: PathBuf::from(config.get_string("executor.upload.queue.directory")?),
+            size_limit_bytes: config.get::<u64>("executor.upload.queue.size.limit.bytes")?,
+            retry_interval_min: Duration::from_millis(config.get::<u64>("executor.upload.queue.retry.interval.min.ms")?),
+            retry_interval_max: Duration::from_millis(config.get::<u64>("executor.upload.queue.retry.interval.max.ms")?),
+        })
+    }
+}
+
+/// Persists uploads of executors on disk, until they were transferred to their WebDAV server.
+/// Enqueuing only writes to disk, so executors do not stall when the uplink is slow or unavailable.
+/// Uploads are transferred one at a time by a background task, results before logs and otherwise oldest first,
+/// retrying with an increasing interval while the server cannot be reached. Queued uploads are resumed after a restart of EDGAR.
+/// When the size limit is reached, the oldest logs are dropped to make room. Results are never dropped, but rejected instead.
+#[derive(Clone)]
+pub struct UploadQueue {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    options: RwLock<UploadQueueOptions>,
+    state: Mutex<State>,
+    enqueued: Notify,
+    webdav_client: WebdavClient,
+}
+
+#[derive(Default)]
+struct State {
+    entries: BTreeMap<EntryKey, Entry>,
+    size_bytes: u64,
+    next_sequence: u64,
+}
+
+/// Orders entries by kind first and then by the order in which they were enqueued.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct EntryKey {
+    kind: UploadKind,
+    sequence: u64,
+}
+
+impl EntryKey {
+    fn file_name(&self, extension: &str) -> String {
+        format!("{:020}.{}.{extension}", self.sequence, self.kind.tag())
+    }
+
+    fn parse(file_name: &str) -> Option<(Self, &str)> {
+        let mut parts = file_name.splitn(3, '.');
+        let sequence = parts.next()?.parse::<u64>().ok()?;
+        let kind = UploadKind::from_tag(parts.next()?)?;
+        let extension = parts.next()?;
+        Some((Self { kind, sequence }, extension))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    metadata: EntryMetadata,
+    size_bytes: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EntryMetadata {
+    /// Collection on the WebDAV server, which is created before uploading.
+    collection: String,
+    url: String,
+}
+
+impl UploadQueue {
+    /// Loads the uploads, which remained queued from a previous run of EDGAR.
+    pub async fn load(options: UploadQueueOptions) -> Result<Self, Error> {
+        let directory = options.directory.clone();
+        fs::create_dir_all(&directory).await
+            .map_err(|cause| Error::Io { path: directory.clone(), cause })?;
+
+        let mut state = State::default();
+
+        let mut read_dir = fs::read_dir(&directory).await
+            .map_err(|cause| Error::Io { path: directory.clone(), cause })?;
+        let mut files = Vec::new();
+        while let Some(dir_entry) = read_dir.next_entry().await.map_err(|cause| Error::Io { path: directory.clone(), cause })? {
+            files.push(dir_entry.path());
+        }
+
+        for path in &files {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let Some((key, extension)) = EntryKey::parse(&file_name) else {
+                continue;
+            };
+            state.next_sequence = state.next_sequence.max(key.sequence + 1);
+
+            if extension != METADATA_FILE_EXTENSION {
+                continue;
+            }
+            let data_path = directory.join(key.file_name(DATA_FILE_EXTENSION));
+            let entry = async {
+                let metadata = fs::read(path).await.ok()?;
+                let metadata = serde_json::from_slice::<EntryMetadata>(&metadata).ok()?;
+                let size_bytes = fs::metadata(&data_path).await.ok()?.len();
+                Some(Entry { metadata, size_bytes })
+            }.await;
+
+            match entry {
+                Some(entry) => {
+                    state.size_bytes += entry.size_bytes;
+                    state.entries.insert(key, entry);
+                }
+                None => warn!("Discarding incomplete upload '{}' from the upload queue.", path.display()),
+            }
+        }
+
+        // Files of interrupted enqueue operations or uploads, which were not fully removed.
+        for path in &files {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            if let Some((key, extension)) = EntryKey::parse(&file_name) {
+                let is_queued = matches!(extension, DATA_FILE_EXTENSION | METADATA_FILE_EXTENSION) && state.entries.contains_key(&key);
+                if is_queued.not() {
+                    remove_file(path).await;
+                }
+            }
+        }
+
+        if state.entries.is_empty().not() {
+            info!("Resuming {} queued upload(s) with {} bytes from '{}'.", state.entries.len(), state.size_bytes, directory.display());
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                options: RwLock::new(options),
+                state: Mutex::new(state),
+                enqueued: Notify::new(),
+                webdav_client: WebdavClient::new("some_dummy_token".to_string()), // TODO: Authenticate with actual token
+            })
+        })
+    }
+
+    /// Writes the data into the queue, from where it is uploaded to `collection` joined with `file_name`.
+    pub async fn enqueue(&self, kind: UploadKind, collection: Url, file_name: &str, data: Vec<u8>) -> Result<(), Error> {
+        let url = collection.join(file_name)
+            .map_err(|cause| Error::InvalidUrl { collection: collection.clone(), file_name: file_name.to_owned(), cause })?;
+
+        let size_bytes = data.len() as u64;
+        let size_limit_bytes = self.inner.options.read().unwrap().size_limit_bytes;
+        if size_bytes > size_limit_bytes {
+            return Err(Error::TooLarge { url, size_bytes, size_limit_bytes });
+        }
+
+        let mut state = self.inner.state.lock().await;
+
+        let dropped = state.select_dropped(kind, size_bytes, size_limit_bytes)
+            .ok_or_else(|| Error::Full { url: url.clone(), size_bytes, size_limit_bytes })?;
+        for key in dropped {
+            if let Some(entry) = state.remove(&key) {
+                warn!("Upload queue is full. Dropping queued {} upload to '{}' with {} bytes.", key.kind.tag(), entry.metadata.url, entry.size_bytes);
+                self.remove_files(&key).await;
+            }
+        }
+
+        let key = EntryKey { kind, sequence: state.next_sequence };
+        state.next_sequence += 1;
+
+        let metadata = EntryMetadata { collection: collection.to_string(), url: url.to_string() };
+        self.write_files(&key, &metadata, &data).await?;
+
+        state.size_bytes += size_bytes;
+        state.entries.insert(key, Entry { metadata, size_bytes });
+        drop(state);
+
+        debug!("Queued {} upload to '{url}' with {size_bytes} bytes.", kind.tag());
+        self.inner.enqueued.notify_one();
+        Ok(())
+    }
+
+    /// Applies changed limits and retry intervals to the running queue, e.g. after the configuration file was edited.
+    /// The directory is kept, as the queued uploads are stored there. Changing it requires a restart.
+    pub fn reconfigure(&self, options: UploadQueueOptions) {
+        let mut current = self.inner.options.write().unwrap();
+        *current = UploadQueueOptions {
+            directory: Clone::clone(&current.directory),
+            ..options
+        };
+    }
+
+    /// Spawns the task, which transfers the queued uploads.
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let queue = Clone::clone(self);
+        tokio::spawn(async move {
+            queue.run().await;
+        })
+    }
+
+    async fn run(&self) {
+        let mut retry_interval = self.inner.options.read().unwrap().retry_interval_min;
+
+        loop {
+            let next = self.inner.state.lock().await.entries.iter()
+                .next()
+                .map(|(key, entry)| (*key, Clone::clone(&entry.metadata)));
+
+            let Some((key, metadata)) = next else {
+                self.inner.enqueued.notified().await;
+                continue;
+            };
+
+            match self.upload(&key, &metadata).await {
+                Ok(()) => {
+                    info!("Uploaded queued {} to '{}'.", key.kind.tag(), metadata.url);
+                    self.complete(&key).await;
+                    retry_interval = self.inner.options.read().unwrap().retry_interval_min;
+                }
+                Err(cause) if cause.is_permanent() => {
+                    warn!("Discarding queued {} upload, which the server rejected: {cause}", key.kind.tag());
+                    self.complete(&key).await;
+                }
+                Err(cause) => {
+                    warn!("Failed to upload queued {}. Retrying in {} seconds: {cause}", key.kind.tag(), retry_interval.as_secs());
+                    tokio::time::sleep(retry_interval).await;
+                    let retry_interval_max = self.inner.options.read().unwrap().retry_interval_max;
+                    retry_interval = (retry_interval * 2).min(retry_interval_max);
+                }
+            }
+        }
+    }
+
+    async fn upload(&self, key: &EntryKey, metadata: &EntryMetadata) -> Result<(), Error> {
+        let url = Url::parse(&metadata.url)
+            .map_err(|cause| Error::InvalidStoredUrl { url: metadata.url.clone(), cause })?;
+        let collection = Url::parse(&metadata.collection)
+            .map_err(|cause| Error::InvalidStoredUrl { url: metadata.collection.clone(), cause })?;
+
+        let path = self.path(key, DATA_FILE_EXTENSION);
+        let data = fs::read(&path).await
+            .map_err(|cause| Error::Io { path, cause })?;
+
+        self.inner.webdav_client.create_collection_path(collection).await
+            .map_err(|cause| Error::Uploading { url: url.clone(), cause })?;
+
+        let response = self.inner.webdav_client.put(data, url.clone()).await
+            .map_err(|cause| Error::Uploading { url: url.clone(), cause })?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(Error::Server { url, status })
+        }
+    }
+
+    async fn complete(&self, key: &EntryKey) {
+        let mut state = self.inner.state.lock().await;
+        if state.remove(key).is_some() {
+            self.remove_files(key).await;
+        }
+    }
+
+    /// Writes the data first and the metadata last, each via a temporary file,
+    /// so that an entry is only loaded after a restart, if it was written completely.
+    async fn write_files(&self, key: &EntryKey, metadata: &EntryMetadata, data: &[u8]) -> Result<(), Error> {
+        let metadata = serde_json::to_vec(metadata)
+            .map_err(|cause| Error::Metadata { cause })?;
+
+        for (extension, content) in [(DATA_FILE_EXTENSION, data), (METADATA_FILE_EXTENSION, metadata.as_slice())] {
+            let path = self.path(key, extension);
+            let temporary_path = path.with_extension(format!("{extension}.{TEMPORARY_FILE_EXTENSION}"));
+
+            fs::write(&temporary_path, content).await
+                .map_err(|cause| Error::Io { path: temporary_path.clone(), cause })?;
+            fs::rename(&temporary_path, &path).await
+                .map_err(|cause| Error::Io { path: path.clone(), cause })?;
+        }
+        Ok(())
+    }
+
+    async fn remove_files(&self, key: &EntryKey) {
+        remove_file(&self.path(key, METADATA_FILE_EXTENSION)).await;
+        remove_file(&self.path(key, DATA_FILE_EXTENSION)).await;
+    }
+
+    fn path(&self, key: &EntryKey, extension: &str) -> PathBuf {
+        self.inner.options.read().unwrap().directory.join(key.file_name(extension))
+    }
+}
+
+impl State {
+    /// Determines which queued entries to drop, so that an upload of the given kind and size fits into the queue.
+    /// Entries of lower priority are dropped first, then droppable entries of the same kind, oldest first.
+    /// Returns `None`, if the upload does not fit, even after dropping all of them.
+    fn select_dropped(&self, kind: UploadKind, size_bytes: u64, size_limit_bytes: u64) -> Option<Vec<EntryKey>> {
+        let mut candidates = self.entries.iter()
+            .filter(|(key, _)| key.kind > kind || (key.kind == kind && kind.is_droppable()))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(key, _)| (std::cmp::Reverse(key.kind), key.sequence));
+
+        let mut dropped = Vec::new();
+        let mut remaining_bytes = self.size_bytes;
+        let mut candidates = candidates.into_iter();
+
+        while remaining_bytes + size_bytes > size_limit_bytes {
+            let (key, entry) = candidates.next()?;
+            remaining_bytes -= entry.size_bytes;
+            dropped.push(*key);
+        }
+        Some(dropped)
+    }
+
+    fn remove(&mut self, key: &EntryKey) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.size_bytes -= entry.size_bytes;
+        Some(entry)
+    }
+}
+
+async fn remove_file(path: &Path) {
+    match fs::remove_file(path).await {
+        Ok(()) => {}
+        Err(cause) if cause.kind() == ErrorKind::NotFound => {}
+        Err(cause) => warn!("Failed to remove '{}' from the upload queue: {cause}", path.display()),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Upload to '{url}' with {size_bytes} bytes exceeds the size limit of the upload queue of {size_limit_bytes} bytes.")]
+    TooLarge { url: Url, size_bytes: u64, size_limit_bytes: u64 },
+    #[error("Upload queue is full. Upload to '{url}' with {size_bytes} bytes does not fit into the size limit of {size_limit_bytes} bytes, without dropping queued results.")]
+    Full { url: Url, size_bytes: u64, size_limit_bytes: u64 },
+    #[error("Failed to construct URL from '{collection}' and '{file_name}': {cause}")]
+    InvalidUrl { collection: Url, file_name: String, cause: url::ParseError },
+    #[error("Queued upload has an invalid URL '{url}': {cause}")]
+    InvalidStoredUrl { url: String, cause: url::ParseError },
+    #[error("Failed to serialize metadata of upload: {cause}")]
+    Metadata { cause: serde_json::Error },
+    #[error("Failure while accessing '{path}' of the upload queue: {cause}")]
+    Io { path: PathBuf, cause: std::io::Error },
+    #[error("Failure while uploading to '{url}': {cause}")]
+    Uploading { url: Url, cause: webdav_client::Error },
+    #[error("Failure while uploading to '{url}' (HTTP status {status})")]
+    Server { url: Url, status: reqwest::StatusCode },
+}
+
+impl Error {
+    /// Whether retrying the upload cannot succeed, e.g. because the server rejected it as unauthorized.
+    fn is_permanent(&self) -> bool {
+        match self {
+            Error::InvalidStoredUrl { .. } => true,
+            Error::Io { cause, .. } => cause.kind() == ErrorKind::NotFound,
+            Error::Server { status, .. } => {
+                status.is_client_error()
+                    && *status != reqwest::StatusCode::REQUEST_TIMEOUT
+                    && *status != reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    fn options(directory: &Path, size_limit_bytes: u64) -> UploadQueueOptions {
+        UploadQueueOptions {
+            directory: directory.to_owned(),
+            size_limit_bytes,
+            retry_interval_min: Duration::from_millis(10),
+            retry_interval_max: Duration::from_millis(100),
+        }
+    }
+
+    async fn queued(queue: &UploadQueue) -> Vec<(UploadKind, String)> {
+        queue.inner.state.lock().await.entries.iter()
+            .map(|(key, entry)| (key.kind, Clone::clone(&entry.metadata.url)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_resume_queued_uploads_after_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let collection = Url::parse("http://localhost/results/")?;
+
+        let queue = UploadQueue::load(options(temp.path(), 1024)).await?;
+        queue.enqueue(UploadKind::Logs, collection.clone(), "executor.log", vec![1; 10]).await?;
+        queue.enqueue(UploadKind::Results, collection.clone(), "executor.zip", vec![2; 10]).await?;
+        drop(queue);
+
+        fs::write(temp.path().join(format!("{:020}.results.data.tmp", 5)), [3; 10]).await?;
+
+        let queue = UploadQueue::load(options(temp.path(), 1024)).await?;
+        assert_eq!(queued(&queue).await, vec![
+            (UploadKind::Results, String::from("http://localhost/results/executor.zip")),
+            (UploadKind::Logs, String::from("http://localhost/results/executor.log")),
+        ]);
+        assert_eq!(queue.inner.state.lock().await.size_bytes, 20);
+        assert_eq!(queue.inner.state.lock().await.next_sequence, 6);
+        assert!(temp.path().join(format!("{:020}.results.data.tmp", 5)).exists().not());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_drop_logs_before_rejecting_results() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let collection = Url::parse("http://localhost/results/")?;
+
+        let queue = UploadQueue::load(options(temp.path(), 20)).await?;
+        queue.enqueue(UploadKind::Logs, collection.clone(), "first.log", vec![0; 8]).await?;
+        queue.enqueue(UploadKind::Results, collection.clone(), "first.zip", vec![0; 8]).await?;
+        queue.enqueue(UploadKind::Results, collection.clone(), "second.zip", vec![0; 8]).await?;
+
+        assert_eq!(queued(&queue).await, vec![
+            (UploadKind::Results, String::from("http://localhost/results/first.zip")),
+            (UploadKind::Results, String::from("http://localhost/results/second.zip")),
+        ]);
+
+        let result = queue.enqueue(UploadKind::Results, collection.clone(), "third.zip", vec![0; 8]).await;
+        assert!(matches!(result, Err(Error::Full { .. })));
+
+        let result = queue.enqueue(UploadKind::Logs, collection.clone(), "second.log", vec![0; 8]).await;
+        assert!(matches!(result, Err(Error::Full { .. })));
+
+        let result = queue.enqueue(UploadKind::Results, collection, "huge.zip", vec![0; 21]).await;
+        assert!(matches!(result, Err(Error::TooLarge { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_apply_a_changed_size_limit_but_keep_the_directory() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let other = TempDir::new()?;
+        let collection = Url::parse("http://localhost/results/")?;
+
+        let queue = UploadQueue::load(options(temp.path(), 20)).await?;
+        queue.reconfigure(options(other.path(), 10));
+
+        let result = queue.enqueue(UploadKind::Results, collection.clone(), "large.zip", vec![0; 12]).await;
+        assert!(matches!(result, Err(Error::TooLarge { size_limit_bytes: 10, .. })));
+
+        queue.enqueue(UploadKind::Results, collection, "small.zip", vec![0; 8]).await?;
+        assert!(temp.path().join(format!("{:020}.results.data", 0)).exists());
+
+        Ok(())
+    }
+}
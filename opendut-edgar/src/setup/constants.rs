@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use crate::common::constants::edgar_install_directory;
+use crate::setup::os;
 
 
 pub fn executable_install_path() -> anyhow::Result<PathBuf> {
@@ -19,17 +20,27 @@ pub const SYSTEMD_SERVICE_FILE_NAME: &str = "opendut-edgar.service";
 
 pub const KERNEL_MODULE_LOAD_RULE_PREFIX: &str = "opendut-edgar";
 
+#[cfg(not(windows))]
 pub fn default_carl_ca_certificate_path() -> PathBuf {
     PathBuf::from("/etc/opendut/tls/ca.pem")
 }
+#[cfg(not(windows))]
 pub fn default_checksum_carl_ca_certificate_file() -> PathBuf {
     PathBuf::from("/etc/opendut/tls/.ca.pem.checksum")
 }
+#[cfg(windows)]
+pub fn default_carl_ca_certificate_path() -> PathBuf {
+    crate::setup::os::windows::program_data_dir().join("opendut").join("tls").join("ca.pem")
+}
+#[cfg(windows)]
+pub fn default_checksum_carl_ca_certificate_file() -> PathBuf {
+    crate::setup::os::windows::program_data_dir().join("opendut").join("tls").join(".ca.pem.checksum")
+}
 pub fn default_os_cert_store_ca_certificate_path() -> PathBuf {
-    PathBuf::from("/usr/local/share/ca-certificates/opendut-ca.crt")
+    os::current::cert_store::default_ca_certificate_path()
 }
 pub fn default_checksum_os_cert_store_ca_certificate_file() -> PathBuf {
-    PathBuf::from("/usr/local/share/ca-certificates/.opendut-ca.crt.checksum")
+    os::current::cert_store::default_checksum_ca_certificate_file()
 }
 
 pub fn default_config_merge_suggestion_file_path() -> PathBuf {
@@ -79,4 +90,7 @@ pub mod rperf {
     }
 }
 
+#[cfg(not(windows))]
 pub const REQUIRED_COMMAND_LINE_PROGRAMS: [(&str, &str); 3] = [("systemctl", "--version"), ("cannelloni", "-h"), ("cangw", "-s")];
+#[cfg(windows)]
+pub const REQUIRED_COMMAND_LINE_PROGRAMS: [(&str, &str); 2] = [("sc.exe", "query"), ("certutil.exe", "-?")];
@@ -2,7 +2,7 @@ use std::net::Ipv4Addr;
 
 pub use crate::common::task::runner::RunMode;
 
-mod constants;
+pub(crate) mod constants;
 
 pub mod start;
 
@@ -12,6 +12,7 @@ mod plugin;
 #[allow(non_camel_case_types)]
 mod tasks;
 
+mod os;
 mod util;
 pub mod write_configuration;
 
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::setup::util::CommandRunner;
+
+pub mod cert_store {
+    use super::*;
+    use anyhow::Context;
+
+    use crate::setup::util;
+
+    pub fn default_ca_certificate_path() -> PathBuf {
+        PathBuf::from("/usr/local/share/ca-certificates/opendut-ca.crt")
+    }
+    pub fn default_checksum_ca_certificate_file() -> PathBuf {
+        PathBuf::from("/usr/local/share/ca-certificates/.opendut-ca.crt.checksum")
+    }
+
+    /// Updates the OS certificate store, after the certificate was written to its path, as NetBird and reqwest (for result uploading to WebDAV) read from there.
+    pub fn add(_certificate_path: &Path, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        command_runner.run(
+            &mut Command::new(update_ca_certificates()?)
+        ).context("update-ca-certificates could not be executed successfully!")?;
+        Ok(())
+    }
+
+    /// Removes the certificate file and updates the OS certificate store. Returns whether the certificate was installed.
+    pub fn remove(certificate_path: &Path, command_runner: &dyn CommandRunner) -> anyhow::Result<bool> {
+        let removed = util::remove_if_exists(certificate_path)?;
+        if removed {
+            command_runner.run(
+                Command::new(update_ca_certificates()?).arg("--fresh")
+            ).context("update-ca-certificates could not be executed successfully!")?;
+        }
+        Ok(removed)
+    }
+
+    pub fn planned_commands(_certificate_path: &Path) -> Vec<String> {
+        vec![String::from("update-ca-certificates")]
+    }
+
+    fn update_ca_certificates() -> anyhow::Result<PathBuf> {
+        which::which("update-ca-certificates")
+            .context(String::from("No command `update-ca-certificates` found. Ensure your system provides this command."))
+    }
+}
+
+/// Controls the services via systemd. Creating the service file of EDGAR is done by [`crate::setup::tasks::CreateServiceFile`].
+pub mod service {
+    use super::*;
+
+    use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
+
+    pub const EDGAR_SERVICE_NAME: &str = SYSTEMD_SERVICE_FILE_NAME;
+
+    pub fn is_installed(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<bool> {
+        let output = command_runner.query(
+            Command::new("systemctl").arg("cat").arg(name)
+        )?;
+        Ok(output.status.success())
+    }
+
+    pub fn restart(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        command_runner.run(
+            Command::new("systemctl").arg("restart").arg(name)
+        )?;
+        Ok(())
+    }
+
+    /// Stops the service, if it is running. Errors are ignored, e.g. when the service is not installed.
+    pub fn stop(name: &str, command_runner: &dyn CommandRunner) {
+        let _ = command_runner.query(
+            Command::new("systemctl").arg("stop").arg(name)
+        );
+    }
+
+    /// Starts the service on boot and (re-)starts it right away.
+    pub fn enable_and_restart(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        command_runner.run(
+            Command::new("systemctl").arg("stop").arg(name)
+        )?; //systemctl always return status code 0, even if already stopped
+
+        command_runner.run(
+            Command::new("systemctl").args(["enable", "--now"]).arg(name) //enable and start
+        )?;
+        Ok(())
+    }
+
+    /// Stops the service and no longer starts it on boot. Errors are ignored, e.g. when the service is not installed.
+    pub fn disable_and_stop(name: &str, command_runner: &dyn CommandRunner) {
+        let _ = command_runner.query(
+            Command::new("systemctl").args(["disable", "--now"]).arg(name) //disable and stop
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! Operating system specific parts of the setup tasks, i.e. the certificate store and the service manager.
+//! Both variants are compiled on every operating system, so that the commands they run can be tested anywhere,
+//! while the tasks use the variant of the operating system they run on via [`current`].
+
+use cfg_if::cfg_if;
+
+#[cfg_attr(windows, allow(dead_code))]
+pub mod linux;
+#[cfg_attr(not(windows), allow(dead_code))]
+pub mod windows;
+
+cfg_if! {
+    if #[cfg(windows)] {
+        pub use windows as current;
+    } else {
+        pub use linux as current;
+    }
+}
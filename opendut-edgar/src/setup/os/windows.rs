@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::setup::util::CommandRunner;
+
+/// Directory for data shared by all users, usually `C:\ProgramData`.
+pub fn program_data_dir() -> PathBuf {
+    std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+}
+
+/// Adds the CA certificate to the "Trusted Root Certification Authorities" store of the local machine, which is used instead of `update-ca-certificates`.
+pub mod cert_store {
+    use super::*;
+    use anyhow::Context;
+
+    use crate::setup::util;
+
+    pub fn default_ca_certificate_path() -> PathBuf {
+        program_data_dir().join("opendut").join("tls").join("opendut-ca.crt")
+    }
+    pub fn default_checksum_ca_certificate_file() -> PathBuf {
+        program_data_dir().join("opendut").join("tls").join(".opendut-ca.crt.checksum")
+    }
+
+    pub fn add(certificate_path: &Path, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        command_runner.run(
+            &mut add_command(certificate_path)
+        ).context("certutil could not add the CA certificate to the certificate store!")?;
+        Ok(())
+    }
+
+    /// Removes the certificate from the certificate store, before removing the file, as the certificate is identified by the thumbprint of the file.
+    /// Returns whether the certificate was installed.
+    pub fn remove(certificate_path: &Path, command_runner: &dyn CommandRunner) -> anyhow::Result<bool> {
+        if certificate_path.exists() {
+            command_runner.run(
+                &mut remove_command(certificate_path)
+            ).context("The CA certificate could not be removed from the certificate store!")?;
+        }
+        util::remove_if_exists(certificate_path)
+    }
+
+    pub fn planned_commands(certificate_path: &Path) -> Vec<String> {
+        vec![format!("certutil.exe -addstore -f Root {}", certificate_path.display())]
+    }
+
+    fn add_command(certificate_path: &Path) -> Command {
+        let mut command = Command::new("certutil.exe");
+        command.args(["-addstore", "-f", "Root"]).arg(certificate_path);
+        command
+    }
+
+    fn remove_command(certificate_path: &Path) -> Command {
+        let script = format!(
+            "$thumbprint = (Get-PfxCertificate -FilePath '{}').Thumbprint; Get-ChildItem Cert:\\LocalMachine\\Root | Where-Object Thumbprint -eq $thumbprint | Remove-Item",
+            certificate_path.display(),
+        );
+        let mut command = Command::new("powershell.exe");
+        command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+        command
+    }
+}
+
+/// Controls the services via the service control manager (`sc.exe`), which is used instead of systemd.
+pub mod service {
+    use super::*;
+    use std::ops::Not;
+
+    pub const EDGAR_SERVICE_NAME: &str = "opendut-edgar";
+
+    /// Exit code of `sc.exe`, when the service to stop is not running.
+    const ERROR_SERVICE_NOT_ACTIVE: i32 = 1062;
+
+    pub fn is_installed(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<bool> {
+        let output = command_runner.query(
+            Command::new("sc.exe").arg("query").arg(name)
+        )?;
+        Ok(output.status.success())
+    }
+
+    /// Creates the service, which runs the executable with the given arguments, or updates it, if it already exists.
+    /// The service is started on boot and restarted 30 seconds after a failure, like the systemd service on Linux.
+    pub fn create_or_update(name: &str, display_name: &str, command_line: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        let action = if is_installed(name, command_runner)? { "config" } else { "create" };
+
+        command_runner.run(
+            Command::new("sc.exe")
+                .arg(action).arg(name)
+                .arg("binPath=").arg(command_line)
+                .arg("start=").arg("delayed-auto")
+                .arg("DisplayName=").arg(display_name)
+        )?;
+
+        command_runner.run(
+            Command::new("sc.exe")
+                .arg("failure").arg(name)
+                .arg("reset=").arg("86400")
+                .arg("actions=").arg("restart/30000/restart/30000/restart/30000")
+        )?;
+        Ok(())
+    }
+
+    /// Stops and deletes the service. Returns whether the service was installed.
+    pub fn delete(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<bool> {
+        if is_installed(name, command_runner)?.not() {
+            return Ok(false);
+        }
+        stop(name, command_runner);
+        command_runner.run(
+            Command::new("sc.exe").arg("delete").arg(name)
+        )?;
+        Ok(true)
+    }
+
+    pub fn restart(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        let output = command_runner.query(
+            Command::new("sc.exe").arg("stop").arg(name)
+        )?;
+        if output.status.success().not() && output.status.code() != Some(ERROR_SERVICE_NOT_ACTIVE) {
+            anyhow::bail!("Failed to stop service '{name}': {}", String::from_utf8_lossy(&output.stdout));
+        }
+        //`sc.exe stop` returns before the service is stopped, so wait for it, as starting a stopping service fails
+        wait_until_stopped(name, command_runner)?;
+
+        command_runner.run(
+            Command::new("sc.exe").arg("start").arg(name)
+        )?;
+        Ok(())
+    }
+
+    /// Stops the service, if it is running. Errors are ignored, e.g. when the service is not installed.
+    pub fn stop(name: &str, command_runner: &dyn CommandRunner) {
+        let _ = command_runner.query(
+            Command::new("sc.exe").arg("stop").arg(name)
+        );
+        let _ = wait_until_stopped(name, command_runner);
+    }
+
+    /// Starts the service on boot and (re-)starts it right away.
+    pub fn enable_and_restart(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        command_runner.run(
+            Command::new("sc.exe").arg("config").arg(name).arg("start=").arg("delayed-auto")
+        )?;
+        restart(name, command_runner)
+    }
+
+    /// Stops the service and no longer starts it on boot. Errors are ignored, e.g. when the service is not installed.
+    pub fn disable_and_stop(name: &str, command_runner: &dyn CommandRunner) {
+        stop(name, command_runner);
+        let _ = command_runner.query(
+            Command::new("sc.exe").arg("config").arg(name).arg("start=").arg("disabled")
+        );
+    }
+
+    fn wait_until_stopped(name: &str, command_runner: &dyn CommandRunner) -> anyhow::Result<()> {
+        const RETRIES: usize = 50;
+        const INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+        for _ in 1..=RETRIES {
+            let output = command_runner.query(
+                Command::new("sc.exe").arg("query").arg(name)
+            )?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if output.status.success().not() || stdout.contains("STOPPED") {
+                return Ok(());
+            }
+            std::thread::sleep(INTERVAL);
+        }
+        anyhow::bail!("Service '{name}' did not stop within {} ms.", RETRIES as u128 * INTERVAL.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::setup::util::RecordingCommandRunner;
+
+    #[test]
+    fn should_add_the_ca_certificate_to_the_root_store_of_the_local_machine() -> anyhow::Result<()> {
+        let command_runner = RecordingCommandRunner::default();
+
+        cert_store::add(&PathBuf::from(r"C:\ProgramData\opendut\tls\opendut-ca.crt"), &command_runner)?;
+
+        assert_eq!(command_runner.recorded(), vec![
+            String::from(r#""certutil.exe" "-addstore" "-f" "Root" "C:\\ProgramData\\opendut\\tls\\opendut-ca.crt""#),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_create_the_service_with_delayed_start_and_restart_on_failure() -> anyhow::Result<()> {
+        let command_runner = RecordingCommandRunner::default();
+
+        service::create_or_update("opendut-edgar", "openDuT EDGAR", r#""C:\opendut\opendut-edgar.exe" service"#, &command_runner)?;
+
+        let recorded = command_runner.recorded();
+        assert_eq!(recorded.len(), 3);
+        assert_eq!(recorded[0], r#""sc.exe" "query" "opendut-edgar""#);
+        assert!(recorded[1].starts_with(r#""sc.exe" "config" "opendut-edgar" "binPath=""#), "Expected existing service to be updated, got: {}", recorded[1]); //the recording runner reports every service as installed
+        assert!(recorded[1].ends_with(r#""start=" "delayed-auto" "DisplayName=" "openDuT EDGAR""#));
+        assert!(recorded[2].starts_with(r#""sc.exe" "failure" "opendut-edgar""#));
+        Ok(())
+    }
+}
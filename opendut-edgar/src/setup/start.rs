@@ -1,17 +1,20 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{anyhow, bail, Context};
 use tracing::info;
 use url::Url;
 
+use crate::common::enrollment::{EnrollmentState, EnrollmentStateFile};
+use crate::common::{carl, network_managers, settings};
 use crate::common::task::runner::RunMode;
 use crate::common::task::{runner, Task};
 use crate::service::network_interface::manager::NetworkInterfaceManager;
 use crate::setup::write_configuration;
 use crate::setup::util::running_in_docker;
 use crate::setup::{tasks, Leader, User};
-use opendut_types::peer::PeerSetup;
+use opendut_types::peer::provisioning::PeerProvisioning;
+use opendut_types::peer::{PeerId, PeerSetup};
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_types::vpn::netbird::SetupKey;
 use opendut_types::vpn::VpnPeerConfiguration;
@@ -21,11 +24,53 @@ use std::env;
 use std::ops::Not;
 use crate::cli::DryRun;
 
-#[allow(clippy::box_default)]
-pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mtu: u16) -> anyhow::Result<()> {
+pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mtu: u16, exclude_from_network_managers: bool) -> anyhow::Result<()> {
 
     let peer_setup = PeerSetup::decode(&setup_string)
         .context("Failed to decode Setup-String.")?;
+    if peer_setup.is_expired() {
+        bail!("The Setup-String of peer <{}> has expired. Generate a new one via CLEO or LEA.", peer_setup.id);
+    }
+
+    managed_with_setup(dry_run, no_confirm, peer_setup, mtu, exclude_from_network_managers).await
+}
+
+/// Registers this host as a new peer by claiming a provisioning token at CARL and then sets it up with the received PeerSetup.
+pub async fn provisioned(dry_run: DryRun, no_confirm: bool, provisioning_string: String, mtu: u16, exclude_from_network_managers: bool) -> anyhow::Result<()> {
+
+    let provisioning = PeerProvisioning::decode(&provisioning_string)
+        .context("Failed to decode Provisioning-String.")?;
+    let token = provisioning.token;
+
+    println!("Using provisioning token: {token}");
+    println!("Will register at CARL: {}", provisioning.carl);
+
+    if dry_run == DryRun::Yes {
+        println!("Not claiming the provisioning token in a dry-run, as each token can only be claimed once.");
+        return Ok(());
+    }
+
+    let should_run = no_confirm || user_confirmation(&dry_run)?;
+    if should_run.not() {
+        return Ok(());
+    }
+
+    let hostname = nix::unistd::gethostname()
+        .map(|hostname| hostname.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let settings = settings::load_with_overrides(config::Config::default())?;
+    let mut carl = carl::connect_unauthenticated(&settings.config, &provisioning.carl, &provisioning.ca).await?;
+
+    let (peer_id, peer_setup) = carl.peers.claim_provisioning_token(token, provisioning.secret, hostname).await
+        .map_err(|cause| anyhow!("Failed to claim provisioning token <{token}>: {cause}"))?;
+    info!("Claimed provisioning token <{token}> as peer <{peer_id}>.");
+
+    managed_with_setup(dry_run, true, peer_setup, mtu, exclude_from_network_managers).await
+}
+
+#[allow(clippy::box_default)]
+async fn managed_with_setup(dry_run: DryRun, no_confirm: bool, peer_setup: PeerSetup, mtu: u16, exclude_from_network_managers: bool) -> anyhow::Result<()> {
 
     let service_user = determine_service_user_name();
     info!("Using service user '{}'.", service_user.name);
@@ -64,15 +109,12 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::CheckCarlReachable),
+        Box::new(tasks::ReportHardwareCapabilities::default()),
         Box::new(tasks::CopyExecutable),
         Box::new(tasks::copy_rperf::CopyRperf),
-
-        Box::new(tasks::LoadKernelModules::default()),
     ]);
 
-    if !running_in_docker() {
-        tasks.push(Box::new(tasks::CreateKernelModuleLoadRule))
-    }
+    tasks.append(&mut network_configuration_tasks(exclude_from_network_managers));
 
     match peer_setup.vpn {
         VpnPeerConfiguration::Disabled => {
@@ -89,19 +131,10 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         }
     };
 
-    if service_user.is_root() {
-        info!("Service should run via root user. Skipping setup of custom service user.");
-    } else {
-        info!("Setting up custom service user '{}'.", service_user.name);
-        tasks.append(&mut vec![
-            Box::new(tasks::CreateUser { service_user: service_user.clone() }),
-            Box::new(tasks::ClaimFileOwnership { service_user: service_user.clone() }),
-            Box::new(tasks::RequestLinuxNetworkCapability),
-        ]);
-    }
+    tasks.append(&mut service_user_tasks(&service_user));
 
     tasks.append(&mut vec![
-        Box::new(tasks::CreateServiceFile::with_service_user(service_user)),
+        create_service_task(service_user)?,
         Box::new(tasks::RestartService),
     ]);
 
@@ -109,7 +142,61 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         DryRun::Yes => RunMode::SetupDryRun,
         DryRun::No => RunMode::Setup,
     };
-    runner::run(run_mode, &tasks).await
+    runner::run(run_mode, tasks).await
+}
+
+/// Replaces the credentials of this peer with the ones from a new Setup-String, without repeating the other steps of the setup.
+/// The peer keeps its ID, its local configuration and data, e.g. results in the upload queue.
+pub async fn re_enroll(dry_run: DryRun, no_confirm: bool, setup_string: String) -> anyhow::Result<()> {
+
+    let peer_setup = PeerSetup::decode(&setup_string)
+        .context("Failed to decode Setup-String.")?;
+    if peer_setup.is_expired() {
+        bail!("The Setup-String of peer <{}> has expired. Generate a new one via CLEO or LEA.", peer_setup.id);
+    }
+
+    let settings = settings::load_with_overrides(config::Config::default())?;
+    let configured_id = settings.config.get::<PeerId>(settings::key::peer::id)
+        .context("Failed to read ID from configuration. Only peers, which were set up before, can be re-enrolled. Run `opendut-edgar setup managed` instead.")?;
+
+    if configured_id != peer_setup.id {
+        bail!("The Setup-String belongs to peer <{}>, but this host is set up as peer <{configured_id}>. Re-enrolling keeps the identity of the peer. Run `opendut-edgar setup managed` to set up this host as a different peer.", peer_setup.id);
+    }
+
+    let enrollment_state_file = EnrollmentStateFile::load(&settings.config)?;
+    match enrollment_state_file.read()? {
+        EnrollmentState::Enrolled => println!("Peer <{configured_id}> is not marked as requiring re-enrollment. Replacing its credentials nonetheless."),
+        EnrollmentState::ReEnrollmentRequired { reason } => println!("Re-enrolling peer <{configured_id}>, as its credentials were rejected:\n  {reason}"),
+    }
+    println!("Will connect to CARL at: {}", peer_setup.carl);
+
+    let should_run = no_confirm || user_confirmation(&dry_run)?;
+    if should_run.not() {
+        return Ok(());
+    }
+
+    if dry_run.not() {
+        write_configuration::WriteConfiguration::for_re_enrollment(
+            write_configuration::ConfigOverride {
+                peer_id: peer_setup.id,
+                carl_url: peer_setup.carl,
+                auth_config: peer_setup.auth_config,
+            },
+        ).execute().await?;
+    }
+
+    let tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
+        Box::new(tasks::CheckCarlReachable),
+        Box::new(tasks::ClearReEnrollmentRequired { enrollment_state_file }),
+        Box::new(tasks::RestartService),
+    ];
+
+    let run_mode = match dry_run {
+        DryRun::Yes => RunMode::SetupDryRun,
+        DryRun::No => RunMode::Setup,
+    };
+    runner::run(run_mode, tasks).await
 }
 
 #[allow(clippy::box_default, clippy::too_many_arguments)]
@@ -122,6 +209,7 @@ pub async fn unmanaged(
     device_interfaces: HashSet<NetworkInterfaceName>,
     leader: Leader,
     mtu: u16,
+    exclude_from_network_managers: bool,
 ) -> anyhow::Result<()> {
     let should_run = no_confirm || user_confirmation(&dry_run)?;
     if should_run.not() {
@@ -129,6 +217,7 @@ pub async fn unmanaged(
     }
 
     let network_interface_manager = NetworkInterfaceManager::create()?;
+    let network_manager_interface_patterns = network_managers::managed_interface_patterns(&[bridge_name.clone()]);
 
     let mut tasks: Vec<Box<dyn Task>> = vec![];
     
@@ -154,13 +243,70 @@ pub async fn unmanaged(
         Box::new(tasks::copy_rperf::CopyRperf),
     ]);
 
+    if exclude_from_network_managers {
+        tasks.push(Box::new(tasks::ExcludeFromNetworkManagers { interface_patterns: network_manager_interface_patterns }));
+    }
+
     let run_mode = match dry_run {
         DryRun::Yes => RunMode::SetupDryRun,
         DryRun::No => RunMode::Setup,
     };
-    runner::run(run_mode, &tasks).await
+    runner::run(run_mode, tasks).await
 }
 
+/// Reverts the changes of a previous setup in reverse order, regardless of the setup mode used.
+/// The configuration, the service user and the ownership of files are kept, so that the host can be set up again as the same peer.
+#[allow(clippy::box_default)]
+pub async fn uninstall(dry_run: DryRun, no_confirm: bool) -> anyhow::Result<()> {
+    let crate_version = crate::app_info::CRATE_VERSION;
+    let should_run = match dry_run {
+        DryRun::No => {
+            println!("This will remove EDGAR {crate_version} from your system, including its service, NetBird, the CA certificates and the network interfaces of the cluster.");
+            no_confirm || crate::setup::user_confirmation_prompt("Do you want to continue?")?
+        }
+        DryRun::Yes => {
+            println!("Pretending to remove EDGAR {crate_version} from your system.");
+            true
+        }
+    };
+    if should_run.not() {
+        println!("Aborting.");
+        info!("Aborting, because user did not confirm uninstallation.");
+        return Ok(());
+    }
+
+    let network_interface_manager = NetworkInterfaceManager::create()?;
+    let bridge_name = crate::common::default_bridge_name();
+    let network_manager_interface_patterns = network_managers::managed_interface_patterns(&[bridge_name.clone(), crate::common::default_can_bridge_name()]);
+
+    // In the order of the setup, as the tasks are undone in reverse order.
+    let mut tasks: Vec<Box<dyn Task>> = vec![];
+    if let Some(write_ca_certificate) = tasks::WriteCaCertificate::installed()? {
+        tasks.push(Box::new(write_ca_certificate));
+    }
+    tasks.append(&mut vec![
+        Box::new(tasks::CopyExecutable),
+        Box::new(tasks::copy_rperf::CopyRperf),
+    ]);
+    if !running_in_docker() {
+        tasks.push(Box::new(tasks::CreateKernelModuleLoadRule));
+    }
+    tasks.append(&mut vec![
+        Box::new(tasks::ExcludeFromNetworkManagers { interface_patterns: network_manager_interface_patterns }),
+        Box::new(tasks::netbird::Unpack::default()),
+        Box::new(tasks::netbird::InstallService),
+        Box::new(tasks::network_interface::CreateBridge { network_interface_manager: Arc::clone(&network_interface_manager), bridge_name: bridge_name.clone() }),
+        Box::new(tasks::network_interface::CreateGreInterfaces { network_interface_manager, bridge_name, leader: Leader::Local }),
+        create_service_task(determine_service_user_name())?,
+        Box::new(tasks::RestartService),
+    ]);
+
+    let run_mode = match dry_run {
+        DryRun::Yes => RunMode::SetupDryRun,
+        DryRun::No => RunMode::Setup,
+    };
+    runner::undo(run_mode, &tasks).await
+}
 
 pub async fn init_logging() -> anyhow::Result<()> {
 
@@ -171,6 +317,8 @@ pub async fn init_logging() -> anyhow::Result<()> {
     let logging_config = telemetry::logging::LoggingConfig {
         logging_stdout: false,
         file_logging,
+        level: None,
+        redactor: telemetry::redaction::Redactor::new(&[])?, //setup strings and secrets are passed on the command line
     };
     let opentelemetry_config = Opentelemetry::Disabled;
     
@@ -179,6 +327,59 @@ pub async fn init_logging() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tasks, which prepare the network configuration of the host for EDGAR, i.e. load the kernel modules for the cluster networking
+/// and keep third-party network managers away from EDGAR's interfaces. On Windows, there is neither to prepare.
+#[allow(clippy::box_default)]
+fn network_configuration_tasks(exclude_from_network_managers: bool) -> Vec<Box<dyn Task>> {
+    if cfg!(windows) {
+        info!("Running on Windows. Skipping the network configuration tasks, which only apply to Linux.");
+        return Vec::new();
+    }
+
+    let mut tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(tasks::LoadKernelModules::default()),
+    ];
+
+    if !running_in_docker() {
+        tasks.push(Box::new(tasks::CreateKernelModuleLoadRule))
+    }
+
+    if exclude_from_network_managers {
+        let interface_patterns = network_managers::managed_interface_patterns(&[crate::common::default_bridge_name(), crate::common::default_can_bridge_name()]);
+        tasks.push(Box::new(tasks::ExcludeFromNetworkManagers { interface_patterns }));
+    }
+    tasks
+}
+
+#[cfg(not(windows))]
+fn service_user_tasks(service_user: &User) -> Vec<Box<dyn Task>> {
+    if service_user.is_root() {
+        info!("Service should run via root user. Skipping setup of custom service user.");
+        vec![]
+    } else {
+        info!("Setting up custom service user '{}'.", service_user.name);
+        vec![
+            Box::new(tasks::CreateUser { service_user: service_user.clone() }),
+            Box::new(tasks::ClaimFileOwnership { service_user: service_user.clone() }),
+            Box::new(tasks::RequestLinuxNetworkCapability),
+        ]
+    }
+}
+#[cfg(windows)]
+fn service_user_tasks(service_user: &User) -> Vec<Box<dyn Task>> {
+    info!("Running on Windows. The service runs as LocalSystem, so the service user '{}' is not set up.", service_user.name);
+    vec![]
+}
+
+#[cfg(not(windows))]
+fn create_service_task(service_user: User) -> anyhow::Result<Box<dyn Task>> {
+    Ok(Box::new(tasks::CreateServiceFile::with_service_user(service_user)))
+}
+#[cfg(windows)]
+fn create_service_task(_service_user: User) -> anyhow::Result<Box<dyn Task>> {
+    Ok(Box::new(tasks::windows::CreateWindowsService::installed_executable()?))
+}
+
 fn determine_service_user_name() -> User {
     const DEFAULT_SERVICE_USER_NAME: &str = "opendut";
 
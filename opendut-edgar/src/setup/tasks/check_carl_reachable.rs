@@ -4,7 +4,8 @@ use config::Config;
 
 use crate::common;
 use crate::common::settings;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, Task, TaskFulfilled, TaskId};
+use crate::setup::tasks::WriteCaCertificate;
 
 pub struct CheckCarlReachable;
 
@@ -13,6 +14,9 @@ impl Task for CheckCarlReachable {
     fn description(&self) -> String {
         String::from("Check CARL Reachable")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::After(vec![TaskId::of::<WriteCaCertificate>()])
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         Ok(TaskFulfilled::Unchecked)
     }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::process::Command;
 use async_trait::async_trait;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, Task, TaskFulfilled};
 use crate::setup::constants::REQUIRED_COMMAND_LINE_PROGRAMS;
 use crate::setup::util::EvaluateRequiringSuccess;
 
@@ -13,6 +13,9 @@ impl Task for CheckCommandLinePrograms {
     fn description(&self) -> String {
         String::from("Check availabilty of needed command line programs")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         Ok(TaskFulfilled::Unchecked)
     }
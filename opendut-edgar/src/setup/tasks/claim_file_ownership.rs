@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 use async_trait::async_trait;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::User;
 use crate::setup::util::chown;
 
@@ -32,4 +34,10 @@ impl Task for ClaimFileOwnership {
         }
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let changes = DIRS.iter()
+            .map(|dir| SystemChange::ChangeOwnership { path: PathBuf::from(dir), user: self.service_user.name.clone() })
+            .collect();
+        Ok(changes)
+    }
 }
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::common::enrollment::{EnrollmentState, EnrollmentStateFile};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
+
+pub struct ClearReEnrollmentRequired {
+    pub enrollment_state_file: EnrollmentStateFile,
+}
+
+#[async_trait]
+impl Task for ClearReEnrollmentRequired {
+    fn description(&self) -> String {
+        String::from("Clear Re-Enrollment Required")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        match self.enrollment_state_file.read()? {
+            EnrollmentState::Enrolled => Ok(TaskFulfilled::Yes),
+            EnrollmentState::ReEnrollmentRequired { .. } => Ok(TaskFulfilled::No),
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        self.enrollment_state_file.clear()?;
+        Ok(Success::default())
+    }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::RemoveFile(self.enrollment_state_file.path().clone())])
+    }
+}
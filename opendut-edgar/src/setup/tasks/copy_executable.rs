@@ -1,12 +1,12 @@
 use crate::fs;
-use std::os::unix;
-use std::process::Command;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use crate::setup::constants::{executable_install_path, PATH_dir, SYSTEMD_SERVICE_FILE_NAME};
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::setup::constants::{executable_install_path, PATH_dir};
+use crate::setup::os::current::service::{self, EDGAR_SERVICE_NAME};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::util;
+use crate::setup::util::DefaultCommandRunner;
 
 pub struct CopyExecutable;
 
@@ -15,6 +15,9 @@ impl Task for CopyExecutable {
     fn description(&self) -> String {
         format!("Copy executable to \"{}\"", executable_install_path().unwrap().display())
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         let installed_path = executable_install_path()?;
         if installed_path.exists() {
@@ -33,23 +36,55 @@ impl Task for CopyExecutable {
         fs::create_dir_all(target_path.parent().unwrap())?;
 
         let executable_path = std::env::current_exe()?;
-        let executable_name = executable_path.file_name()
-            .context("Failed to retrieve file name of executable.")?;
 
         // Stop service to allow replacing executable. This also ensures the new executable is used in the service immediately.
-        let mut command = Command::new("systemctl");
-        let command = command.arg("stop").arg(SYSTEMD_SERVICE_FILE_NAME);
-        let _ = command.output(); //ignore errors, e.g. when service is not running.
+        service::stop(EDGAR_SERVICE_NAME, &DefaultCommandRunner); //ignore errors, e.g. when service is not running.
 
         fs::copy(&executable_path, &target_path)
             .context(format!("Error while copying file from '{}' to '{}'", executable_path.display(), target_path.display()))?;
 
-        let link_path = PATH_dir().join(executable_name);
-        if link_path.exists() {
-            fs::remove_file(&link_path)?;
+        #[cfg(unix)] //on Windows, the service calls the executable via its install path, as there is no common directory in the PATH to link it into
+        {
+            let executable_name = executable_path.file_name()
+                .context("Failed to retrieve file name of executable.")?;
+            let link_path = PATH_dir().join(executable_name);
+            if link_path.exists() {
+                fs::remove_file(&link_path)?;
+            }
+            std::os::unix::fs::symlink(&target_path, &link_path)
+                .context(format!("Error while linking executable from '{}' to PATH directory '{}'", target_path.display(), link_path.display()))?;
         }
-        unix::fs::symlink(&target_path, &link_path)
-            .context(format!("Error while linking executable from '{}' to PATH directory '{}'", target_path.display(), link_path.display()))?;
+
+        Ok(Success::default())
+    }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let target_path = executable_install_path()?;
+        let executable_path = std::env::current_exe()?;
+        let executable_name = executable_path.file_name()
+            .context("Failed to retrieve file name of executable.")?;
+
+        let mut changes = vec![
+            SystemChange::StopService(String::from(EDGAR_SERVICE_NAME)),
+            SystemChange::WriteFile(target_path.clone()),
+        ];
+        if cfg!(unix) {
+            changes.push(SystemChange::Symlink { link: PATH_dir().join(executable_name), target: target_path });
+        }
+        Ok(changes)
+    }
+
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        let target_path = executable_install_path()?;
+
+        service::stop(EDGAR_SERVICE_NAME, &DefaultCommandRunner); //ignore errors, e.g. when service is not installed.
+
+        let executable_name = target_path.file_name()
+            .context("Failed to retrieve file name of installed executable.")?;
+        util::remove_if_exists(PATH_dir().join(executable_name))?;
+        util::remove_if_exists(&target_path)?;
 
         Ok(Success::default())
     }
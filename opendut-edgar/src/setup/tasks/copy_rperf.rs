@@ -1,7 +1,7 @@
 use crate::fs;
 use anyhow::Result;
 use async_trait::async_trait;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 
 pub struct CopyRperf;
 
@@ -10,6 +10,9 @@ impl Task for CopyRperf {
     fn description(&self) -> String {
         String::from("Copy the rperf distribution")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
 
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         let rperf_path = crate::common::constants::rperf::executable_install_file();
@@ -30,4 +33,17 @@ impl Task for CopyRperf {
 
         Ok(Success::default())
     }
+
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::WriteFile(crate::common::constants::rperf::executable_install_file())])
+    }
+
+    fn undoable(&self) -> bool {
+        true
+    }
+
+    async fn undo(&self) -> Result<Success> {
+        crate::setup::util::remove_if_exists(crate::common::constants::rperf::executable_install_file())?;
+        Ok(Success::default())
+    }
 }
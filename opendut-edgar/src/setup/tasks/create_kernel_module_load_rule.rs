@@ -5,7 +5,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use opendut_edgar_kernel_modules::{required_kernel_modules, KernelModule};
 
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::constants::KERNEL_MODULE_LOAD_RULE_PREFIX;
 
 // Returns the file path for the configuation file that causes the kernel module to be loaded during boot
@@ -44,6 +44,9 @@ impl Task for CreateKernelModuleLoadRule {
 
         format!("Create rules to load kernel modules {kernel_modules_str} at boot time")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         for kernel_module in required_kernel_modules() {
             if !load_rule_file_path(&kernel_module).exists() {
@@ -74,4 +77,24 @@ impl Task for CreateKernelModuleLoadRule {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let mut changes = Vec::new();
+        for kernel_module in required_kernel_modules() {
+            changes.push(SystemChange::WriteFile(load_rule_file_path(&kernel_module)));
+            if !kernel_module.params.is_empty() {
+                changes.push(SystemChange::WriteFile(options_rule_file_path(&kernel_module)));
+            }
+        }
+        Ok(changes)
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        for kernel_module in required_kernel_modules() {
+            crate::setup::util::remove_if_exists(load_rule_file_path(&kernel_module))?;
+            crate::setup::util::remove_if_exists(options_rule_file_path(&kernel_module))?;
+        }
+        Ok(Success::default())
+    }
 }
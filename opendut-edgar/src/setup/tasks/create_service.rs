@@ -4,7 +4,7 @@ use std::process::Command;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::constants::executable_install_path;
 use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
 use crate::setup::{User, util};
@@ -27,7 +27,7 @@ fn systemd_file_content(service_user: &User) -> String {
 [Unit]
 Description=openDuT EDGAR
 Requires=network-online.target
-After=network-online.service
+After=network-online.target
 StartLimitIntervalSec=5
 StartLimitBurst=0
 
@@ -35,6 +35,9 @@ StartLimitBurst=0
 ExecStart={executable} service
 Restart=always
 RestartSec=30s
+# on stop, only EDGAR receives SIGTERM, to stop its executors and tear down the cluster networking by itself
+KillMode=mixed
+TimeoutStopSec=60s
 User={service_user}
 Group={service_user}
 
@@ -94,6 +97,27 @@ impl Task for CreateServiceFile {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![
+            SystemChange::WriteFile(self.systemd_file_path.clone()),
+            SystemChange::RunCommand(String::from("systemctl daemon-reload")),
+            SystemChange::WriteFile(self.checksum_systemd_file.clone()),
+        ])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        let removed = util::remove_if_exists(&self.systemd_file_path)?;
+        util::remove_if_exists(&self.checksum_systemd_file)?;
+
+        if removed {
+            self.command_runner.run(
+                Command::new("systemctl").arg("daemon-reload")
+            ).context("systemctl daemon-reload could not be executed successfully!")?;
+        }
+        Ok(Success::default())
+    }
 }
 
 impl CreateServiceFile {
@@ -113,7 +137,7 @@ mod tests {
     use assert_fs::TempDir;
 
     use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
-    use crate::common::task::{Task, TaskFulfilled};
+    use crate::common::task::{SystemChange, Task, TaskFulfilled};
     use crate::setup::tasks::CreateServiceFile;
     use crate::setup::User;
     use crate::setup::util::NoopCommandRunner;
@@ -139,4 +163,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_plan_the_files_written_during_execution() -> anyhow::Result<()> {
+        let temp = TempDir::new().unwrap();
+
+        let systemd_file_path = temp.child(SYSTEMD_SERVICE_FILE_NAME);
+        let checksum_systemd_file_path = temp.child(format!("{SYSTEMD_SERVICE_FILE_NAME}.checksum"));
+
+        let task = CreateServiceFile {
+            service_user: User { name: "testUser".to_string() },
+            systemd_file_path: systemd_file_path.to_path_buf(),
+            checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        let planned_changes = task.planned_changes()?;
+        assert_eq!(planned_changes, vec![
+            SystemChange::WriteFile(systemd_file_path.to_path_buf()),
+            SystemChange::RunCommand(String::from("systemctl daemon-reload")),
+            SystemChange::WriteFile(checksum_systemd_file_path.to_path_buf()),
+        ]);
+        systemd_file_path.assert(predicates::path::missing());
+
+        task.execute().await?;
+        systemd_file_path.assert(predicates::path::exists());
+        checksum_systemd_file_path.assert(predicates::path::exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_remove_the_service_file_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new().unwrap();
+
+        let systemd_file_path = temp.child(SYSTEMD_SERVICE_FILE_NAME);
+        let checksum_systemd_file_path = temp.child(format!("{SYSTEMD_SERVICE_FILE_NAME}.checksum"));
+
+        let task = CreateServiceFile {
+            service_user: User { name: "testUser".to_string() },
+            systemd_file_path: systemd_file_path.to_path_buf(),
+            checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        task.execute().await?;
+        task.undo().await?;
+        systemd_file_path.assert(predicates::path::missing());
+        checksum_systemd_file_path.assert(predicates::path::missing());
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::No);
+
+        task.undo().await?; //nothing left to undo
+
+        Ok(())
+    }
 }
@@ -6,7 +6,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use crate::setup::User;
 
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::util::EvaluateRequiringSuccess;
 
 fn passwd_file() -> PathBuf { PathBuf::from("/etc/passwd") }
@@ -20,6 +20,9 @@ impl Task for CreateUser {
     fn description(&self) -> String {
         format!("Create User \"{}\"", self.service_user.name)
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
 
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         let passwd = fs::read_to_string(passwd_file())?;
@@ -46,4 +49,8 @@ impl Task for CreateUser {
 
         Ok(Success::default())
     }
+
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::CreateUser(self.service_user.name.clone())])
+    }
 }
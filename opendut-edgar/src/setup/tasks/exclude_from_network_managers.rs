@@ -0,0 +1,90 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::common::network_managers;
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
+use crate::fs;
+use crate::setup::util::EvaluateRequiringSuccess;
+
+/// Installs configuration, which prevents NetworkManager and systemd-networkd from managing the interfaces of EDGAR.
+pub struct ExcludeFromNetworkManagers {
+    pub interface_patterns: Vec<String>,
+}
+
+#[async_trait]
+impl Task for ExcludeFromNetworkManagers {
+    fn description(&self) -> String {
+        format!("Exclude interfaces {} from third-party network managers", self.interface_patterns.join(", "))
+    }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        for (path, content) in network_managers::exclusion_files(&self.interface_patterns) {
+            let installed = fs::read_to_string(&path).ok();
+            if installed.as_ref() != Some(&content) {
+                return Ok(TaskFulfilled::No);
+            }
+        }
+        Ok(TaskFulfilled::Yes)
+    }
+    async fn execute(&self) -> Result<Success> {
+        let exclusion_files = network_managers::exclusion_files(&self.interface_patterns);
+        if exclusion_files.is_empty() {
+            return Ok(Success::message("Neither NetworkManager nor systemd-networkd is installed."));
+        }
+
+        for (path, content) in &exclusion_files {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, content)
+                .context(format!("Error while writing network manager exclusion file '{}'", path.display()))?;
+            info!("Wrote network manager exclusion file '{}'.", path.display());
+        }
+
+        reload("nmcli", &["general", "reload", "conf"]);
+        reload("networkctl", &["reload"]);
+
+        Ok(Success::message(format!(
+            "Wrote {}",
+            exclusion_files.iter().map(|(path, _)| path.display().to_string()).collect::<Vec<_>>().join(", ")
+        )))
+    }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let changes = network_managers::exclusion_files(&self.interface_patterns).into_iter()
+            .map(|(path, _)| SystemChange::WriteFile(path))
+            .collect();
+        Ok(changes)
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        let mut removed = vec![];
+        for (path, _) in network_managers::exclusion_files(&self.interface_patterns) {
+            if crate::setup::util::remove_if_exists(&path)? {
+                removed.push(path.display().to_string());
+            }
+        }
+        if removed.is_empty() {
+            return Ok(Success::default());
+        }
+
+        reload("nmcli", &["general", "reload", "conf"]);
+        reload("networkctl", &["reload"]);
+
+        Ok(Success::message(format!("Removed {}", removed.join(", "))))
+    }
+}
+
+/// Reloads the configuration of a network manager, if it is installed. Failures are not fatal, as the manager might not be running.
+fn reload(program: &str, args: &[&str]) {
+    if which::which(program).is_err() {
+        return;
+    }
+    let _ignore_result = Command::new(program).args(args)
+        .evaluate_requiring_success()
+        .inspect_err(|cause| warn!("Could not reload configuration via '{program}': {cause}"));
+}
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use crate::setup::util::running_in_docker;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 
 pub struct LoadKernelModules{
     loaded_module_file: PathBuf,
@@ -20,6 +20,9 @@ impl Task for LoadKernelModules {
 
         format!("Load Kernel Modules {kernel_modules_str}")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         for kernel_module in opendut_edgar_kernel_modules::required_kernel_modules() {
             if ! kernel_module.is_loaded(&self.loaded_module_file, &self.builtin_module_dir)? {
@@ -39,6 +42,15 @@ impl Task for LoadKernelModules {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let mut changes = Vec::new();
+        for kernel_module in opendut_edgar_kernel_modules::required_kernel_modules() {
+            if ! kernel_module.is_loaded(&self.loaded_module_file, &self.builtin_module_dir)? {
+                changes.push(SystemChange::LoadKernelModule(kernel_module.name));
+            }
+        }
+        Ok(changes)
+    }
 }
 
 impl Default for LoadKernelModules {
@@ -4,23 +4,40 @@ pub use check_carl_reachable::CheckCarlReachable;
 mod check_command_line_programs;
 pub use check_command_line_programs::CheckCommandLinePrograms;
 
+mod clear_re_enrollment_required;
+pub use clear_re_enrollment_required::ClearReEnrollmentRequired;
+
+#[cfg(not(windows))]
 mod claim_file_ownership;
+#[cfg(not(windows))]
 pub use claim_file_ownership::ClaimFileOwnership;
 
 mod copy_executable;
 pub use copy_executable::CopyExecutable;
 
+#[cfg(not(windows))]
 mod create_user;
+#[cfg(not(windows))]
 pub use create_user::CreateUser;
 
+#[cfg(not(windows))]
 mod create_service;
+#[cfg(not(windows))]
 pub use create_service::CreateServiceFile;
 
+mod exclude_from_network_managers;
+pub use exclude_from_network_managers::ExcludeFromNetworkManagers;
+
 pub mod netbird;
 
 pub mod network_interface;
 
+mod report_hardware_capabilities;
+pub use report_hardware_capabilities::ReportHardwareCapabilities;
+
+#[cfg(not(windows))]
 mod request_linux_network_capability;
+#[cfg(not(windows))]
 pub use request_linux_network_capability::RequestLinuxNetworkCapability;
 
 mod restart_service;
@@ -36,3 +53,6 @@ pub mod write_ca_certificate;
 pub use write_ca_certificate::WriteCaCertificate;
 
 pub mod copy_rperf;
+
+#[cfg(windows)]
+pub mod windows;
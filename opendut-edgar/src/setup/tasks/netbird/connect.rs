@@ -7,7 +7,8 @@ use url::Url;
 
 use opendut_types::vpn::netbird::SetupKey;
 
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled, TaskId};
+use crate::setup::tasks::netbird::RestartService;
 
 const UP_CHECK_RETRIES: usize = 50;
 const UP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
@@ -23,6 +24,9 @@ impl Task for Connect {
     fn description(&self) -> String {
         String::from("NetBird - Connect")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::After(vec![TaskId::of::<RestartService>()])
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         let mut client = opendut_netbird_client_api::client::Client::connect().await?;
         let is_up = client.check_is_up().await?;
@@ -51,4 +55,7 @@ impl Task for Connect {
         }
         Err(anyhow!("Connection to NetBird Management Service at '{}' was not up after {}*{} ms.", self.management_url, UP_CHECK_RETRIES, UP_CHECK_INTERVAL.as_millis()))
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::NetworkInterface(format!("Connect NetBird to management service at '{}' with MTU {}", self.management_url, self.mtu))])
+    }
 }
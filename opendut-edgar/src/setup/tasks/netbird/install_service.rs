@@ -1,10 +1,13 @@
+use std::ops::Not;
 use std::process::Command;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use crate::setup::constants::netbird;
-use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::util::EvaluateRequiringSuccess;
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled, TaskId};
+use crate::setup::tasks::netbird::Unpack;
+use crate::setup::os::current::service;
+use crate::setup::util::{DefaultCommandRunner, EvaluateRequiringSuccess};
 
 pub struct InstallService;
 
@@ -13,13 +16,11 @@ impl Task for InstallService {
     fn description(&self) -> String {
         String::from("NetBird - Install Service")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::After(vec![TaskId::of::<Unpack>()])
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let output = Command::new("systemctl")
-            .arg("cat")
-            .arg("netbird")
-            .output()?;
-
-        if output.status.success() {
+        if service::is_installed("netbird", &DefaultCommandRunner)? {
             Ok(TaskFulfilled::Yes)
         } else {
             Ok(TaskFulfilled::No)
@@ -35,4 +36,28 @@ impl Task for InstallService {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::InstallService(String::from("netbird"))])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        let netbird = netbird::unpacked_executable()?;
+        if netbird.exists().not() {
+            return Ok(Success::message("NetBird is not installed."));
+        }
+
+        let _ = Command::new(&netbird)
+            .arg("service")
+            .arg("stop")
+            .output(); //ignore errors, e.g. when service is not running.
+
+        let _ = Command::new(&netbird)
+            .arg("service")
+            .arg("uninstall")
+            .evaluate_requiring_success()?;
+
+        Ok(Success::default())
+    }
 }
@@ -1,11 +1,12 @@
-use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::util::EvaluateRequiringSuccess;
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled, TaskId};
+use crate::setup::tasks::netbird::InstallService;
+use crate::setup::os::current::service;
+use crate::setup::util::DefaultCommandRunner;
 
 const UP_CHECK_RETRIES: usize = 50;
 const UP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
@@ -17,14 +18,14 @@ impl Task for RestartService {
     fn description(&self) -> String {
         String::from("NetBird - (Re-)Start Service")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::After(vec![TaskId::of::<InstallService>()])
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
         Ok(TaskFulfilled::Unchecked)
     }
     async fn execute(&self) -> Result<Success> {
-        let _ = Command::new("systemctl")
-            .arg("restart")
-            .arg("netbird")
-            .evaluate_requiring_success()?;
+        service::restart("netbird", &DefaultCommandRunner)?;
 
         let socket_path = opendut_netbird_client_api::client::socket_path();
         for _ in 1..=UP_CHECK_RETRIES {
@@ -35,4 +36,7 @@ impl Task for RestartService {
         }
         Err(anyhow!("NetBird Client Socket was not available {UP_CHECK_RETRIES}*{} ms after service start.", UP_CHECK_INTERVAL.as_millis()))
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::RestartService(String::from("netbird"))])
+    }
 }
@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use flate2::read::GzDecoder;
 
 use crate::setup::{constants, util};
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 
 pub struct Unpack {
     from: PathBuf,
@@ -20,6 +20,9 @@ impl Task for Unpack {
     fn description(&self) -> String {
         String::from("NetBird - Unpack")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
 
         let unpacked_checksum_file = &self.checksum_unpack_file;
@@ -51,6 +54,20 @@ impl Task for Unpack {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![
+            SystemChange::WriteFile(self.to_dir.clone()),
+            SystemChange::WriteFile(self.checksum_unpack_file.clone()),
+        ])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        util::remove_if_exists(&self.to_dir)?;
+        util::remove_if_exists(&self.checksum_unpack_file)?;
+        Ok(Success::default())
+    }
 }
 impl Default for Unpack {
     fn default() -> Self {
@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use opendut_types::util::net::NetworkInterfaceName;
 
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 
 pub struct ConnectDeviceInterfaces {
     pub network_interface_manager: NetworkInterfaceManagerRef,
@@ -30,4 +30,10 @@ impl Task for ConnectDeviceInterfaces {
         }
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let changes = self.device_interfaces.iter()
+            .map(|interface| SystemChange::NetworkInterface(format!("Join '{interface}' to bridge '{}'", self.bridge_name)))
+            .collect();
+        Ok(changes)
+    }
 }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use opendut_types::util::net::NetworkInterfaceName;
 
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 
 pub struct CreateBridge {
     pub network_interface_manager: NetworkInterfaceManagerRef,
@@ -31,4 +31,16 @@ impl Task for CreateBridge {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::NetworkInterface(format!("Create bridge '{}'", self.bridge_name))])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        if let Some(bridge) = self.network_interface_manager.find_interface(&self.bridge_name).await? {
+            self.network_interface_manager.delete_interface(&bridge).await?;
+        }
+        Ok(Success::default())
+    }
 }
@@ -12,7 +12,7 @@ use opendut_types::util::net::NetworkInterfaceName;
 use crate::service::network_interface::gre;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 use crate::setup::Leader;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 
 pub struct CreateGreInterfaces {
     pub network_interface_manager: NetworkInterfaceManagerRef,
@@ -77,4 +77,18 @@ impl Task for CreateGreInterfaces {
             Ok(Success::message(format!("{number_of_remote_ips} interface(s) created; acting as leader with IP address '{local_ip}'")))
         }
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        let change = match &self.leader {
+            Leader::Local => format!("Create GRE interfaces to all peers in the NetBird network and join them to bridge '{}'", self.bridge_name),
+            Leader::Remote(remote_ip) => format!("Create GRE interface to leader '{remote_ip}' and join it to bridge '{}'", self.bridge_name),
+        };
+        Ok(vec![SystemChange::NetworkInterface(change)])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        gre::remove_existing_interfaces(Arc::clone(&self.network_interface_manager)).await?;
+        Ok(Success::default())
+    }
 }
@@ -0,0 +1,227 @@
+use std::fs;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use config::Config;
+use tracing::debug;
+
+use opendut_types::peer::hardware::{CanControllerCapabilities, HardwareCapabilities, NetworkInterfaceCapabilities};
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::common;
+use crate::common::settings;
+use crate::common::task::{Dependencies, Success, Task, TaskFulfilled, TaskId};
+use crate::setup::tasks::CheckCarlReachable;
+use crate::setup::util::running_in_docker;
+
+const ARPHRD_CAN: &str = "280";
+
+/// Detects the hardware capabilities of this host and stores them on the PeerDescriptor in CARL,
+/// so that clusters can be planned based on the hardware, which is actually available.
+pub struct ReportHardwareCapabilities {
+    sys_class_net: PathBuf,
+    proc_meminfo: PathBuf,
+}
+
+#[async_trait]
+impl Task for ReportHardwareCapabilities {
+    fn description(&self) -> String {
+        String::from("Report Hardware Capabilities")
+    }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::After(vec![TaskId::of::<CheckCarlReachable>()])
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        Ok(TaskFulfilled::Unchecked)
+    }
+    async fn execute(&self) -> Result<Success> {
+        let hardware = self.detect()?;
+        debug!("Detected hardware capabilities: {hardware:?}");
+
+        let settings = settings::load_with_overrides(Config::default())?;
+        let peer_id = settings.config.get::<PeerId>(settings::key::peer::id)
+            .context("Failed to read ID from configuration.")?;
+
+        let mut carl = common::carl::connect(&settings.config).await
+            .context("Failed to connect to CARL")?;
+
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .context("Failed to retrieve PeerDescriptor from CARL")?;
+
+        let message = format!(
+            "{} CAN controller(s), {} network interface(s), {} on {}",
+            hardware.can_controllers.len(),
+            hardware.network_interfaces.len(),
+            hardware.cpu_architecture,
+            hardware.virtualization.as_deref().unwrap_or("bare metal"),
+        );
+
+        peer_descriptor.hardware = Some(hardware);
+        carl.peers.store_peer_descriptor(peer_descriptor).await
+            .context("Failed to store hardware capabilities in CARL")?;
+
+        Ok(Success::message(message))
+    }
+}
+
+impl Default for ReportHardwareCapabilities {
+    fn default() -> Self {
+        Self {
+            sys_class_net: PathBuf::from("/sys/class/net"),
+            proc_meminfo: PathBuf::from("/proc/meminfo"),
+        }
+    }
+}
+
+impl ReportHardwareCapabilities {
+    fn detect(&self) -> Result<HardwareCapabilities> {
+        let cpu_cores = std::thread::available_parallelism()
+            .map(|cores| cores.get() as u32)
+            .unwrap_or(1);
+
+        let meminfo = fs::read_to_string(&self.proc_meminfo)
+            .context(format!("Failed to read '{}'", self.proc_meminfo.display()))?;
+
+        let mut can_controllers = vec![];
+        let mut network_interfaces = vec![];
+
+        for entry in fs::read_dir(&self.sys_class_net).context(format!("Failed to read '{}'", self.sys_class_net.display()))? {
+            let interface_dir = entry?.path();
+            let Some(interface) = interface_dir.file_name().and_then(|name| name.to_str()) else { continue };
+            let interface = NetworkInterfaceName::try_from(interface)?;
+
+            if read_trimmed(&interface_dir.join("type")).as_deref() == Some(ARPHRD_CAN) {
+                let fd_supported = can_fd_supported(&interface)
+                    .unwrap_or_else(|cause| {
+                        debug!("Could not determine CAN-FD support of interface '{interface}':\n  {cause}");
+                        false
+                    });
+                can_controllers.push(CanControllerCapabilities { interface, fd_supported });
+            }
+            else if interface_dir.join("device").exists() { //only physical interfaces
+                let speed_mbits = read_trimmed(&interface_dir.join("speed"))
+                    .and_then(|speed| speed.parse::<u32>().ok()); //speed is -1 or cannot be read, when the link is down
+                network_interfaces.push(NetworkInterfaceCapabilities { interface, speed_mbits });
+            }
+        }
+        can_controllers.sort_by_key(|controller| controller.interface.name());
+        network_interfaces.sort_by_key(|network_interface| network_interface.interface.name());
+
+        Ok(HardwareCapabilities {
+            cpu_architecture: String::from(std::env::consts::ARCH),
+            cpu_cores,
+            memory_bytes: parse_total_memory_bytes(&meminfo).unwrap_or_default(),
+            virtualization: detect_virtualization(),
+            can_controllers,
+            network_interfaces,
+        })
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+        .map(|value| value.trim().to_owned())
+}
+
+fn parse_total_memory_bytes(meminfo: &str) -> Option<u64> {
+    meminfo.lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|kilobytes| kilobytes.trim().parse::<u64>().ok())
+        .map(|kilobytes| kilobytes * 1024)
+}
+
+fn detect_virtualization() -> Option<String> {
+    if running_in_docker() {
+        return Some(String::from("docker"));
+    }
+    let output = Command::new("systemd-detect-virt").output().ok()?;
+    let virtualization = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+
+    (virtualization.is_empty().not() && virtualization != "none")
+        .then_some(virtualization)
+}
+
+/// CAN controllers, which support CAN-FD, report bit-timing constants for the data phase.
+fn can_fd_supported(interface: &NetworkInterfaceName) -> Result<bool> {
+    let output = Command::new("ip")
+        .args(["-details", "-json", "link", "show", &interface.name()])
+        .output()?;
+    let details = String::from_utf8(output.stdout)?;
+
+    parse_can_fd_supported(&details)
+}
+
+fn parse_can_fd_supported(details: &str) -> Result<bool> {
+    let details = serde_json::from_str::<serde_json::Value>(details)?;
+
+    let info_data = details.pointer("/0/linkinfo/info_data");
+    let has_data_bittiming = info_data
+        .and_then(|info_data| info_data.get("data_bittiming_const"))
+        .is_some();
+    let ctrlmode_supports_fd = info_data
+        .and_then(|info_data| info_data.get("ctrlmode_supported"))
+        .and_then(serde_json::Value::as_array)
+        .is_some_and(|modes| modes.iter().any(|mode| mode.as_str() == Some("FD")));
+
+    Ok(has_data_bittiming || ctrlmode_supports_fd)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn should_detect_can_controllers_and_physical_network_interfaces() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let sys_class_net = temp.child("net");
+        sys_class_net.child("eth0/type").write_str("1\n")?;
+        sys_class_net.child("eth0/speed").write_str("1000\n")?;
+        sys_class_net.child("eth0/device").create_dir_all()?;
+        sys_class_net.child("eth1/type").write_str("1\n")?;
+        sys_class_net.child("eth1/speed").write_str("-1\n")?;
+        sys_class_net.child("eth1/device").create_dir_all()?;
+        sys_class_net.child("br-opendut/type").write_str("1\n")?;
+        sys_class_net.child("vcan0/type").write_str("280\n")?;
+        let proc_meminfo = temp.child("meminfo");
+        proc_meminfo.write_str(indoc!("
+            MemTotal:        8048576 kB
+            MemFree:         1048576 kB
+        "))?;
+
+        let testee = ReportHardwareCapabilities {
+            sys_class_net: sys_class_net.to_path_buf(),
+            proc_meminfo: proc_meminfo.to_path_buf(),
+        };
+
+        let hardware = testee.detect()?;
+
+        assert_that!(hardware.memory_bytes, eq(8048576 * 1024));
+        assert_that!(hardware.can_controllers.len(), eq(1));
+        assert_that!(hardware.can_controllers[0].interface.name(), eq("vcan0"));
+        assert_that!(hardware.network_interfaces, eq(&vec![
+            NetworkInterfaceCapabilities { interface: NetworkInterfaceName::try_from("eth0")?, speed_mbits: Some(1000) },
+            NetworkInterfaceCapabilities { interface: NetworkInterfaceName::try_from("eth1")?, speed_mbits: None },
+        ]));
+        Ok(())
+    }
+
+    #[test]
+    fn should_detect_can_fd_support_from_data_bit_timing() -> anyhow::Result<()> {
+        let can_fd = r#"[{"ifname":"can0","linkinfo":{"info_kind":"can","info_data":{"ctrlmode":[],"data_bittiming_const":{"name":"mcp251xfd"}}}}]"#;
+        let classic_can = r#"[{"ifname":"can1","linkinfo":{"info_kind":"can","info_data":{"ctrlmode":[]}}}]"#;
+
+        assert_that!(parse_can_fd_supported(can_fd)?, eq(true));
+        assert_that!(parse_can_fd_supported(classic_can)?, eq(false));
+        Ok(())
+    }
+}
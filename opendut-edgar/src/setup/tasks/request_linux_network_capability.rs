@@ -3,7 +3,7 @@ use std::process::Command;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use crate::setup::constants;
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::util::EvaluateRequiringSuccess;
 
 /// The EDGAR Service needs to modify network interfaces.
@@ -41,4 +41,10 @@ impl Task for RequestLinuxNetworkCapability {
 
         Ok(Success::default())
     }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::GrantCapability {
+            executable: constants::executable_install_path()?,
+            capability: String::from("CAP_NET_ADMIN"),
+        }])
+    }
 }
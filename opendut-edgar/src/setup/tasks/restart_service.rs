@@ -1,10 +1,8 @@
-use std::process::Command;
-
 use anyhow::Result;
 use async_trait::async_trait;
-use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
-use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::util::EvaluateRequiringSuccess;
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
+use crate::setup::os::current::service::{self, EDGAR_SERVICE_NAME};
+use crate::setup::util::DefaultCommandRunner;
 
 pub struct RestartService;
 
@@ -17,15 +15,18 @@ impl Task for RestartService {
         Ok(TaskFulfilled::Unchecked)
     }
     async fn execute(&self) -> Result<Success> {
-        Command::new("systemctl")
-            .arg("stop")
-            .arg(SYSTEMD_SERVICE_FILE_NAME)
-            .evaluate_requiring_success()?; //systemctl always return status code 0, even if already stopped
+        service::enable_and_restart(EDGAR_SERVICE_NAME, &DefaultCommandRunner)?;
 
-        Command::new("systemctl")
-            .args(["enable", "--now"]) //enable and start
-            .arg(SYSTEMD_SERVICE_FILE_NAME)
-            .evaluate_requiring_success()?;
+        Ok(Success::default())
+    }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::RestartService(String::from(EDGAR_SERVICE_NAME))])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        service::disable_and_stop(EDGAR_SERVICE_NAME, &DefaultCommandRunner); //ignore errors, e.g. when service is not installed.
 
         Ok(Success::default())
     }
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crate::common::task::{Success, SystemChange, Task, TaskFulfilled};
+use crate::setup::constants::executable_install_path;
+use crate::setup::os::windows::service::{self, EDGAR_SERVICE_NAME};
+use crate::setup::util::{CommandRunner, DefaultCommandRunner};
+
+const DISPLAY_NAME: &str = "openDuT EDGAR";
+
+/// Registers EDGAR with the service control manager, which is used instead of a systemd service file.
+/// The service runs as LocalSystem, as Windows has no equivalent to granting only the capability to manage network interfaces to a service user.
+pub struct CreateWindowsService {
+    pub executable: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
+}
+
+#[async_trait]
+impl Task for CreateWindowsService {
+    fn description(&self) -> String {
+        String::from("Register Windows Service")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let output = self.command_runner.query(
+            std::process::Command::new("sc.exe").arg("qc").arg(EDGAR_SERVICE_NAME)
+        )?;
+        let configuration = String::from_utf8_lossy(&output.stdout);
+
+        if output.status.success() && configuration.contains(&self.command_line()) {
+            Ok(TaskFulfilled::Yes)
+        } else {
+            Ok(TaskFulfilled::No)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        service::create_or_update(EDGAR_SERVICE_NAME, DISPLAY_NAME, &self.command_line(), self.command_runner.as_ref())
+            .context(format!("Error while registering service '{EDGAR_SERVICE_NAME}'"))?;
+
+        Ok(Success::default())
+    }
+    fn planned_changes(&self) -> Result<Vec<SystemChange>> {
+        Ok(vec![SystemChange::InstallService(String::from(EDGAR_SERVICE_NAME))])
+    }
+    fn undoable(&self) -> bool {
+        true
+    }
+    async fn undo(&self) -> Result<Success> {
+        let removed = service::delete(EDGAR_SERVICE_NAME, self.command_runner.as_ref())?;
+
+        if removed {
+            Ok(Success::message(format!("Removed service '{EDGAR_SERVICE_NAME}'.")))
+        } else {
+            Ok(Success::default())
+        }
+    }
+}
+
+impl CreateWindowsService {
+    pub fn installed_executable() -> Result<Self> {
+        Ok(Self {
+            executable: executable_install_path()?,
+            command_runner: Box::new(DefaultCommandRunner),
+        })
+    }
+
+    /// Quoted, as the install path usually contains spaces, e.g. in `C:\Program Files`.
+    fn command_line(&self) -> String {
+        format!("\"{}\" service", self.executable.display())
+    }
+}
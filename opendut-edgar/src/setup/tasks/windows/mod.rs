@@ -0,0 +1,4 @@
+//! Variants of the setup tasks for Windows hosts, which differ from their Linux counterparts in more than the commands they run.
+
+mod create_service;
+pub use create_service::CreateWindowsService;
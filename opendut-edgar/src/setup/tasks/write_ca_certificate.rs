@@ -1,6 +1,7 @@
 use crate::fs;
+use std::ops::Not;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::str::FromStr;
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -8,8 +9,8 @@ use tracing::debug;
 
 use opendut_types::util::net::Certificate;
 
-use crate::setup::{constants, util};
-use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::setup::{constants, os, util};
+use crate::common::task::{Dependencies, Success, SystemChange, Task, TaskFulfilled};
 use crate::setup::util::{CommandRunner, DefaultCommandRunner};
 
 pub struct WriteCaCertificate {
@@ -27,6 +28,9 @@ impl Task for WriteCaCertificate {
     fn description(&self) -> String {
         String::from("Write CA Certificates")
     }
+    fn dependencies(&self) -> Dependencies {
+        Dependencies::none()
+    }
 
     async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
         let installed_carl_checksum_file = &self.checksum_carl_ca_certificate_file;
@@ -75,6 +79,34 @@ impl Task for WriteCaCertificate {
 
         Ok(Success::default())
     }
+
+    fn planned_changes(&self) -> anyhow::Result<Vec<SystemChange>> {
+        let mut changes = vec![
+            SystemChange::WriteFile(self.carl_ca_certificate_path.clone()),
+            SystemChange::WriteFile(self.checksum_carl_ca_certificate_file.clone()),
+            SystemChange::WriteFile(self.os_cert_store_ca_certificate_path.clone()),
+        ];
+        changes.extend(
+            os::current::cert_store::planned_commands(&self.os_cert_store_ca_certificate_path).into_iter()
+                .map(SystemChange::RunCommand)
+        );
+        changes.push(SystemChange::WriteFile(self.checksum_os_cert_store_ca_certificate_file.clone()));
+        Ok(changes)
+    }
+
+    fn undoable(&self) -> bool {
+        true
+    }
+
+    async fn undo(&self) -> anyhow::Result<Success> {
+        util::remove_if_exists(&self.carl_ca_certificate_path)?;
+        util::remove_if_exists(&self.checksum_carl_ca_certificate_file)?;
+
+        os::current::cert_store::remove(&self.os_cert_store_ca_certificate_path, self.command_runner.as_ref())?;
+        util::remove_if_exists(&self.checksum_os_cert_store_ca_certificate_file)?;
+
+        Ok(Success::default())
+    }
 }
 
 impl WriteCaCertificate {
@@ -88,6 +120,18 @@ impl WriteCaCertificate {
             command_runner: Box::new(DefaultCommandRunner),
         }
     }
+
+    /// Uses the certificate installed by a previous setup, e.g. to undo the setup. Returns `None`, if no certificate is installed.
+    pub fn installed() -> anyhow::Result<Option<Self>> {
+        let carl_ca_certificate_path = constants::default_carl_ca_certificate_path();
+        if carl_ca_certificate_path.exists().not() {
+            return Ok(None);
+        }
+        let certificate = Certificate::from_str(&fs::read_to_string(&carl_ca_certificate_path)?)
+            .context(format!("Failed to parse installed CA certificate at '{}'.", carl_ca_certificate_path.display()))?;
+
+        Ok(Some(Self::with_certificate(certificate)))
+    }
 }
 
 fn write_carl_certificate(new_certificate: &Certificate, carl_ca_certificate_path: &Path, checksum_carl_ca_certificate_file: &Path) -> anyhow::Result<()> {
@@ -130,12 +174,7 @@ fn write_os_cert_store_certificate(
         "Copying CA certificate from {:?} to {:?} was not possible.", carl_ca_certificate_path, os_cert_store_ca_certificate_path
     ))?;
 
-    let update_ca_certificates = which::which("update-ca-certificates")
-        .context(String::from("No command `update-ca-certificates` found. Ensure your system provides this command."))?;
-
-    command_runner.run(
-        &mut Command::new(update_ca_certificates) //Update OS certificate store, as NetBird and reqwest (for result uploading to WebDAV) reads from there
-    ).context("update-ca-certificates could not be executed successfully!")?;
+    os::current::cert_store::add(os_cert_store_ca_certificate_path, command_runner)?;
 
     let checksum = util::checksum::file(os_cert_store_ca_certificate_path)?;
     let checksum_unpack_file = checksum_os_cert_store_ca_certificate_file;
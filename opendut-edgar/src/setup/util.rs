@@ -11,12 +11,17 @@ use crate::setup::User;
 
 pub trait CommandRunner: Send + Sync {
     fn run(&self, command: &mut Command) -> anyhow::Result<Output>;
+    /// Runs the command without requiring success, e.g. to check whether a service is installed, where a failure is an answer rather than an error.
+    fn query(&self, command: &mut Command) -> anyhow::Result<Output>;
 }
 pub struct DefaultCommandRunner;
 impl CommandRunner for DefaultCommandRunner {
     fn run(&self, command: &mut Command) -> anyhow::Result<Output> {
         command.evaluate_requiring_success()
     }
+    fn query(&self, command: &mut Command) -> anyhow::Result<Output> {
+        Ok(command.output()?)
+    }
 }
 cfg_if! {
      if #[cfg(test)] {
@@ -24,11 +29,38 @@ cfg_if! {
         impl CommandRunner for NoopCommandRunner {
             fn run(&self, _command: &mut Command) -> anyhow::Result<Output> {
                 //do nothing
-                Ok(Output {
-                    status: std::process::ExitStatus::default(),
-                    stdout: b"dummy".to_vec(),
-                    stderr: b"dummy".to_vec(),
-                })
+                Ok(dummy_output())
+            }
+            fn query(&self, _command: &mut Command) -> anyhow::Result<Output> {
+                Ok(dummy_output())
+            }
+        }
+
+        /// Records the commands instead of running them, e.g. to check the commands for another operating system.
+        #[derive(Default)]
+        pub struct RecordingCommandRunner {
+            commands: std::sync::Mutex<Vec<String>>,
+        }
+        impl RecordingCommandRunner {
+            pub fn recorded(&self) -> Vec<String> {
+                self.commands.lock().unwrap().clone()
+            }
+        }
+        impl CommandRunner for RecordingCommandRunner {
+            fn run(&self, command: &mut Command) -> anyhow::Result<Output> {
+                self.commands.lock().unwrap().push(format!("{command:?}"));
+                Ok(dummy_output())
+            }
+            fn query(&self, command: &mut Command) -> anyhow::Result<Output> {
+                self.run(command)
+            }
+        }
+
+        fn dummy_output() -> Output {
+            Output {
+                status: std::process::ExitStatus::default(),
+                stdout: b"dummy".to_vec(),
+                stderr: b"dummy".to_vec(),
             }
         }
      }
@@ -58,6 +90,7 @@ impl EvaluateRequiringSuccess for Command {
     }
 }
 
+#[cfg(not(windows))]
 pub fn chown(user: &User, path: impl AsRef<Path>) -> anyhow::Result<()> {
     let path = path.as_ref();
     let name = &user.name;
@@ -97,6 +130,19 @@ pub mod checksum {
     }
 }
 
+/// Removes the file or directory, if it exists. Returns whether something was removed.
+pub fn remove_if_exists(path: impl AsRef<Path>) -> anyhow::Result<bool> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        crate::fs::remove_dir_all(path)?;
+    } else if path.exists() || path.is_symlink() {
+        crate::fs::remove_file(path)?;
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
 pub fn running_in_docker() -> bool {
     return Path::new("/.dockerenv").exists();
 }
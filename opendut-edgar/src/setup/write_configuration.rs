@@ -18,6 +18,8 @@ pub struct WriteConfiguration {
     config_merge_suggestion_file: PathBuf,
     config_override: ConfigOverride,
     require_confirmation: bool,
+    /// Overwrite mismatched configurations without asking, as when re-enrolling, where only the overridden values are expected to differ.
+    always_overwrite: bool,
 }
 pub struct ConfigOverride {
     pub peer_id: PeerId,
@@ -99,7 +101,7 @@ impl WriteConfiguration {
             self.config_file_to_write_to.exists().not()
             || self.config_file_to_write_to.metadata()?.len() == 0;
 
-        let should_overwrite = if target_file_empty {
+        let should_overwrite = if target_file_empty || self.always_overwrite {
             true
         } else if self.require_confirmation {
             crate::setup::user_confirmation_prompt("Settings file already exists, but contains mismatched configurations! Do you want to overwrite it?")?
@@ -132,6 +134,18 @@ impl WriteConfiguration {
             config_merge_suggestion_file: constants::default_config_merge_suggestion_file_path(),
             config_override,
             require_confirmation: if no_confirm { false } else { console::user_attended() },
+            always_overwrite: false,
+        }
+    }
+
+    /// Replaces the CARL address and credentials in the existing configuration, keeping all other values.
+    pub fn for_re_enrollment(config_override: ConfigOverride) -> Self {
+        Self {
+            config_file_to_write_to: settings::default_config_file_path(),
+            config_merge_suggestion_file: constants::default_config_merge_suggestion_file_path(),
+            config_override,
+            require_confirmation: false,
+            always_overwrite: true,
         }
     }
 
@@ -326,6 +340,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_overwrite_credentials_when_re_enrolling_but_keep_other_keys() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::Yes);
+        write_configuration.always_overwrite = true;
+
+        let config_file = ChildPath::new(write_configuration.config_file_to_write_to.clone());
+        let config_merge_suggestion_file = ChildPath::new(write_configuration.config_merge_suggestion_file.clone());
+
+        config_file.write_str(&format!(indoc!(r#"
+            [peer]
+            id = "{}"
+
+            [network.oidc.client]
+            id = "{}"
+            secret = "RevokedSecret"
+
+            [executor.upload.queue]
+            directory = "/data/upload-queue"
+        "#), fixture.peer_id, CLIENT_ID))?;
+
+        write_configuration.execute().await?;
+
+        assert!(predicate::path::missing().eval(&config_merge_suggestion_file));
+        let file_content = fs::read_to_string(&config_file)?;
+        assert!(predicate::str::contains("secret = \"ClientSecret\"").eval(&file_content));
+        assert!(predicate::str::contains("RevokedSecret").not().eval(&file_content));
+        assert!(predicate::str::contains("directory = \"/data/upload-queue\"").eval(&file_content));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_not_provide_a_merge_suggestion_if_the_existing_config_matches() -> anyhow::Result<()> {
         let fixture = Fixture::new();
@@ -391,6 +437,7 @@ mod tests {
             config_merge_suggestion_file: fixture.config_merge_suggestion_file.to_path_buf(),
             config_override,
             require_confirmation: false, //always disabled in unit tests
+            always_overwrite: false,
         }
     }
     enum AuthEnabled { Yes, No }
@@ -0,0 +1,83 @@
+use leptos::*;
+
+use opendut_carl_api::carl::bootstrap::{BootstrapStatus, BootstrapStepState};
+
+use crate::app::{ExpectGlobals, use_app_globals};
+use crate::components::{BasePageContainer, Initialized};
+
+#[component]
+pub fn BootstrapOverview() -> impl IntoView {
+
+    #[component]
+    fn inner() -> impl IntoView {
+
+        let globals = use_app_globals();
+
+        let status: Resource<(), BootstrapStatus> = create_local_resource(|| {}, move |_| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.bootstrap.get_status().await
+                    .expect("Failed to request the bootstrap status from carl.")
+            }
+        });
+
+        let rows = move || {
+            status.get().map(|status| {
+                status.steps().into_iter()
+                    .map(|(step, state)| {
+                        let (icon, text) = match state {
+                            BootstrapStepState::Pending => ("fa-solid fa-hourglass-half", String::from("Pending")),
+                            BootstrapStepState::Done { detail } => ("fa-solid fa-circle-check has-text-success", Clone::clone(detail)),
+                            BootstrapStepState::Skipped { reason } => ("fa-solid fa-forward has-text-grey", Clone::clone(reason)),
+                            BootstrapStepState::Failed { cause } => ("fa-solid fa-circle-xmark has-text-danger", Clone::clone(cause)),
+                        };
+                        view! {
+                            <tr>
+                                <td><i class=icon></i></td>
+                                <td>{ step.to_string() }</td>
+                                <td>{ text }</td>
+                            </tr>
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        let summary = move || {
+            status.get().map(|status| {
+                if status.restart_required {
+                    "The bootstrap has been completed. Restart CARL to apply the configuration."
+                } else if status.completed {
+                    "The bootstrap has been completed."
+                } else {
+                    "The bootstrap has not been completed yet. Perform the remaining steps via `opendut-cleo bootstrap`, using the bootstrap token from the bootstrap directory of CARL."
+                }
+            })
+        };
+
+        view! {
+            <BasePageContainer
+                title="Bootstrap"
+                breadcrumbs=Vec::new()
+                controls=view! { }
+            >
+                <div class="mt-4">
+                    <Transition fallback=move || view! { <p>"-"</p> }>
+                        <p class="mb-4">{ summary }</p>
+                        <table class="table is-bordered">
+                            <tbody>
+                                { rows }
+                            </tbody>
+                        </table>
+                    </Transition>
+                </div>
+            </BasePageContainer>
+        }
+    }
+
+    view! {
+        <Initialized>
+            <Inner />
+        </Initialized>
+    }
+}
@@ -0,0 +1,111 @@
+use leptos::*;
+
+use opendut_carl_api::carl::cluster::ClusterDeploymentSimulation;
+use opendut_types::cluster::ClusterConfiguration;
+
+use crate::app::{ExpectGlobals, use_app_globals};
+use crate::clusters::configurator::types::UserClusterConfiguration;
+use crate::util::i18n::{tr, trf};
+
+/// Shows what deploying the cluster, as currently configured, would result in, without storing the configuration.
+/// The preview is re-evaluated by CARL whenever the configuration changes, e.g. when devices are selected.
+#[component]
+pub fn DeploymentPreview(cluster_configuration: RwSignal<UserClusterConfiguration>) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let simulation = create_local_resource(
+        move || ClusterConfiguration::try_from(cluster_configuration.get()).ok(),
+        move |configuration| {
+            let mut carl = globals.expect_client();
+            async move {
+                match configuration {
+                    Some(configuration) => {
+                        let result = carl.cluster.simulate_cluster_deployment(configuration).await
+                            .map_err(|cause| cause.to_string());
+                        Some(result)
+                    }
+                    None => None,
+                }
+            }
+        }
+    );
+
+    let preview = move || {
+        match simulation.get().flatten() {
+            None => view! {
+                <p class="has-text-grey">{ tr("deployment-preview.incomplete") }</p>
+            }.into_view(),
+            Some(Err(cause)) => view! {
+                <p class="has-text-danger">{ trf("deployment-preview.failed", &[("cause", &cause)]) }</p>
+            }.into_view(),
+            Some(Ok(simulation)) => view! {
+                <SimulationOutcome simulation />
+            }.into_view(),
+        }
+    };
+
+    view! {
+        <div class="box mt-4">
+            <h5 class="title is-5">{ tr("deployment-preview.title") }</h5>
+            { preview }
+        </div>
+    }
+}
+
+#[component]
+fn SimulationOutcome(simulation: ClusterDeploymentSimulation) -> impl IntoView {
+
+    let status = if simulation.is_deployable() {
+        view! { <p class="has-text-success">{ tr("deployment-preview.deployable") }</p> }
+    } else {
+        view! { <p class="has-text-danger">{ tr("deployment-preview.not-deployable") }</p> }
+    };
+
+    let findings = simulation.violations.iter()
+        .map(ToString::to_string)
+        .chain(simulation.problems.iter().cloned())
+        .map(|finding| view! { <li>{ finding }</li> })
+        .collect_view();
+
+    let members = simulation.members.into_iter()
+        .map(|member| {
+            let role = if member.leader { tr("deployment-preview.leader") } else { tr("deployment-preview.member") };
+            let vpn_address = member.vpn_address
+                .map(|address| address.to_string())
+                .unwrap_or_else(|| String::from("-"));
+            let interfaces = member.device_interfaces.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            view! {
+                <tr>
+                    <td>{ member.peer_name.to_string() }</td>
+                    <td>{ role }</td>
+                    <td>{ vpn_address }</td>
+                    <td>{ interfaces }</td>
+                    <td>{ member.devices.len() }</td>
+                    <td>{ member.executors.len() }</td>
+                </tr>
+            }
+        })
+        .collect_view();
+
+    view! {
+        { status }
+        <ul class="mb-4">{ findings }</ul>
+        <table class="table is-fullwidth">
+            <thead>
+                <tr>
+                    <th>{ tr("deployment-preview.peer") }</th>
+                    <th>{ tr("deployment-preview.role") }</th>
+                    <th>{ tr("deployment-preview.vpn-address") }</th>
+                    <th>{ tr("deployment-preview.interfaces") }</th>
+                    <th>{ tr("deployment-preview.devices") }</th>
+                    <th>{ tr("deployment-preview.executors") }</th>
+                </tr>
+            </thead>
+            <tbody>{ members }</tbody>
+        </table>
+    }
+}
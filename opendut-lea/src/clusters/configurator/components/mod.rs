@@ -1,4 +1,5 @@
 mod controls;
+mod deployment_preview;
 mod device_selector;
 mod cluster_name_input;
 mod leader_selector;
@@ -6,6 +7,7 @@ mod leader_selector;
 use std::collections::HashSet;
 use leptos::{create_local_resource, Resource, Signal, SignalWith};
 pub use controls::Controls;
+pub use deployment_preview::DeploymentPreview;
 pub use device_selector::{DeviceSelector, DeviceSelection};
 pub use cluster_name_input::ClusterNameInput;
 pub use leader_selector::{LeaderSelector, LeaderSelection};
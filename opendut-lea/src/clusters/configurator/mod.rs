@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use leptos::*;
 use leptos_router::use_params_map;
 use opendut_types::cluster::{ClusterId};
@@ -53,6 +55,9 @@ pub fn ClusterConfigurator() -> impl IntoView {
                 name: UserInputValue::Left(UserInputError::from("Enter a valid cluster name.")),
                 devices: DeviceSelection::Left(String::from("Select at least two devices.")),
                 leader: LeaderSelection::Left(String::from("Select a leader.")),
+                peer_groups: HashSet::new(),
+                device_selectors: Vec::new(),
+                dependencies: Vec::new(),
             });
 
             create_local_resource(|| {}, move |_| { // TODO: maybe a action suits better here
@@ -63,6 +68,9 @@ pub fn ClusterConfigurator() -> impl IntoView {
                             user_configuration.name = UserInputValue::Right(configuration.name.value());
                             user_configuration.devices = DeviceSelection::Right(configuration.devices);
                             user_configuration.leader = LeaderSelection::Right(configuration.leader);
+                            user_configuration.peer_groups = configuration.peer_groups;
+                            user_configuration.device_selectors = configuration.device_selectors;
+                            user_configuration.dependencies = configuration.dependencies;
                         });
                     }
                 }
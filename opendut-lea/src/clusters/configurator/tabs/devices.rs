@@ -1,5 +1,6 @@
 use leptos::{component, IntoView, RwSignal, view};
 
+use crate::clusters::configurator::components::DeploymentPreview;
 use crate::clusters::configurator::DeviceSelector;
 use crate::clusters::configurator::types::UserClusterConfiguration;
 
@@ -9,6 +10,7 @@ pub fn DevicesTab(cluster_configuration: RwSignal<UserClusterConfiguration>) ->
     view! {
         <div>
             <DeviceSelector cluster_configuration=cluster_configuration/>
+            <DeploymentPreview cluster_configuration=cluster_configuration/>
         </div>
     }
 }
@@ -1,4 +1,7 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use std::collections::HashSet;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName, DeviceSelector};
+use opendut_types::peer::group::PeerGroupId;
 
 use crate::clusters::configurator::components::{DeviceSelection, LeaderSelection};
 use crate::components::UserInputValue;
@@ -22,6 +25,9 @@ pub struct UserClusterConfiguration {
     pub name: UserInputValue,
     pub devices: DeviceSelection,
     pub leader: LeaderSelection,
+    pub peer_groups: HashSet<PeerGroupId>,
+    pub device_selectors: Vec<DeviceSelector>,
+    pub dependencies: Vec<ClusterId>,
 }
 
 impl UserClusterConfiguration {
@@ -49,6 +55,9 @@ impl TryFrom<UserClusterConfiguration> for ClusterConfiguration {
             name,
             leader,
             devices,
+            peer_groups: configuration.peer_groups,
+            device_selectors: configuration.device_selectors,
+            dependencies: configuration.dependencies,
         })
     }
 }
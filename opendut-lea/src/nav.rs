@@ -83,6 +83,9 @@ pub fn Navbar() -> impl IntoView {
                                     <div class="px-2">
                                         <a class="is-size-7" href=routing::path::licenses>"Licenses"</a>
                                     </div>
+                                    <div class="px-2">
+                                        <a class="is-size-7" href=routing::path::bootstrap>"Bootstrap"</a>
+                                    </div>
                                 </div>
                             </div>
                         </div>
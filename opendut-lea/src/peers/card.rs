@@ -27,7 +27,7 @@ pub fn PeersCard() -> impl IntoView {
             for peer in registered {
                 let peer_state = carl.peers.get_peer_state(peer.id).await.expect("Failed to request state of peer.");
                 match peer_state {
-                    PeerState::Down => { offline_counter += 1 }
+                    PeerState::Down | PeerState::Unreachable { .. } => { offline_counter += 1 }
                     PeerState::Up { .. } => { online_counter += 1}
                 }
             };
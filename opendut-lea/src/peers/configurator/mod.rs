@@ -3,7 +3,7 @@ use crate::components::use_active_tab;
 use crate::components::{BasePageContainer, Breadcrumb, Initialized, UserInputError, UserInputValue};
 use crate::peers::configurator::components::Controls;
 use crate::peers::configurator::tabs::{DevicesTab, ExecutorTab, GeneralTab, NetworkTab, SetupTab, TabIdentifier};
-use crate::peers::configurator::types::{UserContainerEnv, UserDeviceConfiguration, UserNetworkInterface, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind, UserPeerNetwork};
+use crate::peers::configurator::types::{UserContainerEnv, UserDeviceConfiguration, UserExecutorSandbox, UserNetworkInterface, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind, UserPeerNetwork};
 use crate::routing::{navigate_to, WellKnownRoutes};
 use crate::util;
 use leptos::*;
@@ -58,6 +58,10 @@ pub fn PeerConfigurator() -> impl IntoView {
                 },
                 is_new: true,
                 executors: Vec::new(),
+                labels: Default::default(),
+                annotations: Default::default(),
+                hardware: Default::default(),
+                cluster_hooks: Default::default(),
             });
 
             let peer_configuration_resource = create_local_resource(|| {}, move |_| {
@@ -70,6 +74,10 @@ pub fn PeerConfigurator() -> impl IntoView {
                         peer_configuration.update(|user_configuration| {
                             user_configuration.name = UserInputValue::Right(configuration.name.value());
                             user_configuration.is_new = false;
+                            user_configuration.labels = configuration.labels;
+                            user_configuration.annotations = configuration.annotations;
+                            user_configuration.hardware = configuration.hardware;
+                            user_configuration.cluster_hooks = configuration.cluster_hooks;
                             user_configuration.location = UserInputValue::Right(configuration.location.unwrap_or_default().value());
                             user_configuration.devices = configuration.topology.devices.into_iter().map(|device| {
                                 let mut configured_clusters = vec![];
@@ -99,7 +107,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                                 })
                                 .collect();
                             for executor in configuration.executors.executors {
-                                let ExecutorDescriptor { id, kind, results_url } = executor;
+                                let ExecutorDescriptor { id, kind, results_url, sandbox } = executor;
 
                                 let kind = match kind {
                                     ExecutorKind::Executable => todo!(),
@@ -113,6 +121,9 @@ pub fn PeerConfigurator() -> impl IntoView {
                                         ports,
                                         command,
                                         args,
+                                        secrets,
+                                        can_gateways,
+                                        network_attachment,
                                     } => {
                                         let volumes = volumes.into_iter()
                                             .map(|volume| {
@@ -143,6 +154,16 @@ pub fn PeerConfigurator() -> impl IntoView {
                                                 create_rw_signal(UserInputValue::Right(arg.to_string()))
                                             })
                                             .collect::<Vec<_>>();
+                                        let secrets = secrets.into_iter()
+                                            .map(|secret| {
+                                                create_rw_signal(UserInputValue::Right(secret.to_string()))
+                                            })
+                                            .collect::<Vec<_>>();
+                                        let can_gateways = can_gateways.into_iter()
+                                            .map(|can_gateway| {
+                                                create_rw_signal(UserInputValue::Right(can_gateway.to_string()))
+                                            })
+                                            .collect::<Vec<_>>();
                                         UserPeerExecutorKind::Container {
                                             engine,
                                             name: UserInputValue::Right(name.into()),
@@ -153,6 +174,9 @@ pub fn PeerConfigurator() -> impl IntoView {
                                             ports,
                                             command: UserInputValue::Right(command.into()),
                                             args,
+                                            secrets,
+                                            can_gateways,
+                                            network_attachment: UserInputValue::Right(network_attachment.to_string()),
                                         }
                                     }
                                 };
@@ -162,6 +186,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                                         id,
                                         kind,
                                         results_url: UserInputValue::Right(results_url.map(|s| s.to_string()).unwrap_or(String::new())),
+                                        sandbox: UserExecutorSandbox::from(sandbox),
                                         is_collapsed: true
                                     })
                                 );
@@ -183,7 +208,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                     })
                     && peer_configuration.executors.iter().all(|executor| {
                         executor.with(|executor| {
-                            let UserPeerExecutor { id: _, kind, results_url, is_collapsed: _ } = executor;
+                            let UserPeerExecutor { id: _, kind, results_url, sandbox, is_collapsed: _ } = executor;
 
                             let kind_is_valid = match kind {
                                 UserPeerExecutorKind::Container {
@@ -196,6 +221,9 @@ pub fn PeerConfigurator() -> impl IntoView {
                                     ports,
                                     command,
                                     args,
+                                    secrets,
+                                    can_gateways,
+                                    network_attachment,
                                 } => {
                                     name.is_right()
                                         && image.is_right()
@@ -205,10 +233,13 @@ pub fn PeerConfigurator() -> impl IntoView {
                                         && ports.iter().all(|port| port.with(|port| port.is_right()))
                                         && command.is_right()
                                         && args.iter().all(|arg| arg.with(|arg| arg.is_right()))
+                                        && secrets.iter().all(|secret| secret.with(|secret| secret.is_right()))
+                                        && can_gateways.iter().all(|can_gateway| can_gateway.with(|can_gateway| can_gateway.is_right()))
+                                        && network_attachment.is_right()
                                 }
                             };
 
-                            kind_is_valid && results_url.is_right()
+                            kind_is_valid && results_url.is_right() && sandbox.is_valid()
                         })
                     })
                 })
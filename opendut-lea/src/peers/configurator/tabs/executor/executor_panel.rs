@@ -1,5 +1,6 @@
 use leptos::{component, create_read_slice, create_rw_signal, create_slice, event_target_value, IntoView, RwSignal,  SignalGet, SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked, view};
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine, IllegalContainerImage}, ExecutorId, ResultsUrl};
+use opendut_types::peer::executor::sandbox::{ExecutorUser, SeccompProfile};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerImage, ContainerName, ContainerNetworkAttachment, ContainerPortSpec, ContainerVolume, Engine, IllegalContainerImage}, ExecutorId, ResultsUrl};
 use strum::IntoEnumIterator;
 
 use crate::components::{ButtonColor, ButtonSize, ButtonState, ConfirmationButton, FontAwesomeIcon, IconButton, Toggled, UserInput, UserInputValue, VectorUserInput};
@@ -32,6 +33,10 @@ where
                     <ExecutorContainerPortsInput executor />
                     <ExecutorContainerCommandInput executor />
                     <ExecutorContainerArgsInput executor />
+                    <ExecutorContainerNetworkAttachmentInput executor />
+                    <ExecutorSandboxUserInput executor />
+                    <ExecutorSandboxSeccompProfileInput executor />
+                    <ExecutorSandboxReadOnlyFilesystemInput executor />
                     <ExecutorContainerResultsUrlInput executor />
                 </div>
             </div>
@@ -467,6 +472,149 @@ fn ExecutorContainerCommandInput(
     }
 }
 
+#[component]
+fn ExecutorContainerNetworkAttachmentInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match &executor.kind {
+                UserPeerExecutorKind::Container { network_attachment, .. } => { Clone::clone(network_attachment) }
+            }
+        },
+        move |executor, value| {
+            match &mut executor.kind {
+                UserPeerExecutorKind::Container { network_attachment, .. } => { *network_attachment = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        match ContainerNetworkAttachment::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="Network Attachment"
+            placeholder="host, macvlan or sriov:<physical-function>"
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorSandboxUserInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            Clone::clone(&executor.sandbox.user)
+        },
+        move |executor, value| {
+            executor.sandbox.user = value;
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match ExecutorUser::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="User"
+            placeholder="<user>[:<group>], e.g. 1000:1000"
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorSandboxSeccompProfileInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            Clone::clone(&executor.sandbox.seccomp_profile)
+        },
+        move |executor, value| {
+            executor.sandbox.seccomp_profile = value;
+        }
+    );
+
+    let validator = |input: String| {
+        match SeccompProfile::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="Seccomp Profile"
+            placeholder="default, unconfined or /path/to/profile.json"
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorSandboxReadOnlyFilesystemInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            executor.sandbox.read_only_filesystem
+        },
+        move |executor, value| {
+            executor.sandbox.read_only_filesystem = value;
+        }
+    );
+
+    view! {
+        <div class="field">
+            <label class="checkbox">
+                <input
+                    type="checkbox"
+                    checked=move || getter.get()
+                    on:click=move |_| setter.set(!getter.get_untracked())
+                />
+                " Read-only Filesystem"
+            </label>
+        </div>
+    }
+}
+
 #[component]
 fn ExecutorContainerArgsInput(
     executor: RwSignal<UserPeerExecutor>,
@@ -3,7 +3,7 @@ use opendut_types::peer::executor::container::Engine;
 use opendut_types::peer::executor::ExecutorId;
 use crate::components::UserInputValue;
 use crate::peers::configurator::tabs::executor::executor_panel::ExecutorPanel;
-use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind};
+use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserExecutorSandbox, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind};
 
 mod executor_panel;
 
@@ -77,8 +77,16 @@ fn ExecutorTable(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl In
                                         ports: vec![],
                                         command: UserInputValue::Right(String::from("")),
                                         args: vec![],
+                                        secrets: vec![],
+                                        can_gateways: vec![],
+                                        network_attachment: UserInputValue::Right(String::from("host")),
                                     },
                                     results_url: UserInputValue::Right(String::from("")),
+                                    sandbox: UserExecutorSandbox {
+                                        user: UserInputValue::Right(String::from("")),
+                                        seccomp_profile: UserInputValue::Right(String::from("default")),
+                                        read_only_filesystem: false,
+                                    },
                                     is_collapsed: false
                                 }
                             );
@@ -1,8 +1,12 @@
 use leptos::{RwSignal, SignalGetUntracked};
 use opendut_types::cluster::ClusterConfiguration;
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId};
+use opendut_types::peer::executor::sandbox::{ExecutorSandbox, ExecutorUser, SeccompProfile};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
+use opendut_types::peer::label::{PeerAnnotations, PeerLabels};
+use opendut_types::peer::hardware::HardwareCapabilities;
+use opendut_types::peer::hook::ClusterHook;
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, ContainerVolume, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
@@ -46,6 +50,11 @@ pub struct UserPeerConfiguration {
     pub devices: Vec<RwSignal<UserDeviceConfiguration>>,
     pub network: UserPeerNetwork,
     pub executors: Vec<RwSignal<UserPeerExecutor>>,
+    /// Not editable in LEA, but retained so that storing the peer does not drop them.
+    pub labels: PeerLabels,
+    pub annotations: PeerAnnotations,
+    pub hardware: Option<HardwareCapabilities>,
+    pub cluster_hooks: Vec<ClusterHook>,
     pub is_new: bool,
 }
 
@@ -54,9 +63,60 @@ pub struct UserPeerExecutor {
     pub id: ExecutorId,
     pub kind: UserPeerExecutorKind,
     pub results_url: UserInputValue,
+    pub sandbox: UserExecutorSandbox,
     pub is_collapsed: bool,
 }
 
+#[derive(Clone, Debug)]
+pub struct UserExecutorSandbox {
+    pub user: UserInputValue,
+    pub seccomp_profile: UserInputValue,
+    pub read_only_filesystem: bool,
+}
+
+impl UserExecutorSandbox {
+    pub fn is_valid(&self) -> bool {
+        self.user.is_right() && self.seccomp_profile.is_right()
+    }
+}
+
+impl From<ExecutorSandbox> for UserExecutorSandbox {
+    fn from(sandbox: ExecutorSandbox) -> Self {
+        Self {
+            user: UserInputValue::Right(sandbox.user.map(|user| user.to_string()).unwrap_or_default()),
+            seccomp_profile: UserInputValue::Right(sandbox.seccomp_profile.to_string()),
+            read_only_filesystem: sandbox.read_only_filesystem,
+        }
+    }
+}
+
+impl TryFrom<UserExecutorSandbox> for ExecutorSandbox {
+    type Error = PeerMisconfigurationError;
+
+    fn try_from(sandbox: UserExecutorSandbox) -> Result<Self, Self::Error> {
+        let UserExecutorSandbox { user, seccomp_profile, read_only_filesystem } = sandbox;
+
+        let user = user
+            .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+            .and_then(|user| {
+                if user.is_empty() {
+                    Ok(None)
+                } else {
+                    Some(ExecutorUser::try_from(user)
+                        .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)).transpose()
+                }
+            })?;
+        let seccomp_profile = seccomp_profile
+            .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+            .and_then(|seccomp_profile| {
+                SeccompProfile::try_from(seccomp_profile)
+                    .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)
+            })?;
+
+        Ok(ExecutorSandbox { user, seccomp_profile, read_only_filesystem })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum UserPeerExecutorKind {
     Container {
@@ -69,6 +129,9 @@ pub enum UserPeerExecutorKind {
         ports: Vec<RwSignal<UserInputValue>>,
         command: UserInputValue,
         args: Vec<RwSignal<UserInputValue>>,
+        secrets: Vec<RwSignal<UserInputValue>>,
+        can_gateways: Vec<RwSignal<UserInputValue>>,
+        network_attachment: UserInputValue,
     }
 }
 
@@ -167,6 +230,10 @@ impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
             executors: ExecutorDescriptors {
                 executors
             },
+            labels: configuration.labels,
+            annotations: configuration.annotations,
+            hardware: configuration.hardware,
+            cluster_hooks: configuration.cluster_hooks,
         })
     }
 }
@@ -226,7 +293,7 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
     type Error = PeerMisconfigurationError;
 
     fn try_from(configuration: UserPeerExecutor) -> Result<Self, Self::Error> {
-        let UserPeerExecutor { id, kind, results_url, is_collapsed: _ } = configuration;
+        let UserPeerExecutor { id, kind, results_url, sandbox, is_collapsed: _ } = configuration;
 
         let kind = match kind {
             UserPeerExecutorKind::Container {
@@ -239,6 +306,9 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                 ports,
                 command,
                 args,
+                secrets,
+                can_gateways,
+                network_attachment,
             } => {
                 let name = name
                     .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
@@ -290,6 +360,28 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                             .and_then(|arg| ContainerCommandArgument::try_from(arg).map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor))
                     })
                     .collect::<Result<Vec<_>, _>>()?;
+                let secrets = secrets
+                    .into_iter()
+                    .map(|signal| signal.get_untracked())
+                    .map(|secret| {
+                        secret.right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+                            .and_then(|secret| ContainerSecret::try_from(secret).map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let can_gateways = can_gateways
+                    .into_iter()
+                    .map(|signal| signal.get_untracked())
+                    .map(|can_gateway| {
+                        can_gateway.right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+                            .and_then(|can_gateway| ContainerCanGateway::try_from(can_gateway).map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let network_attachment = network_attachment
+                    .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+                    .and_then(|network_attachment| {
+                        ContainerNetworkAttachment::try_from(network_attachment)
+                            .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)
+                    })?;
                 let envs = envs
                     .into_iter()
                     .map(|signal| signal.get_untracked())
@@ -311,6 +403,9 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                     ports,
                     command,
                     args,
+                    secrets,
+                    can_gateways,
+                    network_attachment,
                 })
             }
         }?;
@@ -326,7 +421,9 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                 }
             })?;
 
-        Ok(ExecutorDescriptor { id, kind, results_url })
+        let sandbox = ExecutorSandbox::try_from(sandbox)?;
+
+        Ok(ExecutorDescriptor { id, kind, results_url, sandbox })
     }
 }
 
@@ -151,6 +151,12 @@ fn Row(
                     text: String::from("Connected. No errors."),
                 }
             }
+            PeerState::Unreachable { .. } => {
+                health::State {
+                    kind: health::StateKind::Yellow,
+                    text: String::from("Connected, but not heard from recently."),
+                }
+            }
         };
         create_signal(state)
     };
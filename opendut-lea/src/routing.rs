@@ -16,6 +16,7 @@ pub mod path {
     pub const dashboard: &str = "/";
 
     pub const about: &str = "/about";
+    pub const bootstrap: &str = "/bootstrap";
     pub const downloads: &str = "/downloads";
     pub const clusters_overview: &str = "/clusters";
     pub const error: &str = "/error";
@@ -80,6 +81,7 @@ mod routes {
     use crate::routing::{self, NotFound};
     use crate::user::UserOverview;
     use crate::about::AboutOverview;
+    use crate::bootstrap::BootstrapOverview;
     use crate::downloads::Downloads;
 
     #[component]
@@ -97,6 +99,7 @@ mod routes {
                         <Route path=routing::path::user view=|| view! { <UserOverview /> } />
                         <Route path=routing::path::licenses view=|| view! { <LicensesOverview /> } />
                         <Route path=routing::path::about view=|| view! { <AboutOverview /> } />
+                        <Route path=routing::path::bootstrap view=|| view! { <BootstrapOverview /> } />
                         <Route path=routing::path::error view=|| view! { <ErrorPage /> } />
                         <Route path="/*any" view=|| view! { <NotFound /> } />
                     </Routes>
@@ -0,0 +1,21 @@
+use std::fmt::Display;
+
+use leptos::window;
+use opendut_util_core::i18n::{self, Locale};
+
+/// Language of the browser, as configured by the user. Falls back to English, if it is not supported.
+pub fn browser_locale() -> Locale {
+    window().navigator().language()
+        .and_then(|language| Locale::from_tag(&language))
+        .unwrap_or_default()
+}
+
+/// Returns the message for the key in the language of the browser.
+pub fn tr(key: &str) -> String {
+    i18n::message(browser_locale(), key)
+}
+
+/// Returns the message for the key in the language of the browser, with its placeholders replaced by the arguments.
+pub fn trf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    i18n::format(browser_locale(), key, args)
+}
@@ -7,5 +7,6 @@ mod tick;
 pub mod net;
 pub mod view;
 pub mod clipboard;
+pub mod i18n;
 
 pub const NON_BREAKING_SPACE: &str = "\u{a0}";
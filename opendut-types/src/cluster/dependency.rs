@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::ops::Not;
+
+use crate::cluster::{ClusterConfiguration, ClusterId};
+
+/// Reason, why the dependencies of a cluster configuration were rejected.
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum IllegalClusterDependency {
+    #[error("Cluster <{cluster_id}> cannot depend on itself.")]
+    SelfReference { cluster_id: ClusterId },
+    #[error("Cluster <{cluster_id}> depends on cluster <{dependency_id}>, which does not exist.")]
+    UnknownCluster { cluster_id: ClusterId, dependency_id: ClusterId },
+    #[error("Dependencies of cluster <{cluster_id}> form a cycle: {}", cycle.iter().map(ToString::to_string).collect::<Vec<_>>().join(" -> "))]
+    Cycle { cluster_id: ClusterId, cycle: Vec<ClusterId> },
+}
+
+/// Checks that the dependencies of the cluster reference existing clusters and do not form a cycle,
+/// taking the cluster in place of an existing configuration with the same ID.
+pub fn validate_dependencies(cluster: &ClusterConfiguration, existing: &[ClusterConfiguration]) -> Result<(), IllegalClusterDependency> {
+    let cluster_id = cluster.id;
+
+    let mut dependencies: HashMap<ClusterId, &[ClusterId]> = existing.iter()
+        .map(|configuration| (configuration.id, configuration.dependencies.as_slice()))
+        .collect();
+    dependencies.insert(cluster_id, cluster.dependencies.as_slice());
+
+    for dependency_id in &cluster.dependencies {
+        if *dependency_id == cluster_id {
+            return Err(IllegalClusterDependency::SelfReference { cluster_id });
+        }
+        if dependencies.contains_key(dependency_id).not() {
+            return Err(IllegalClusterDependency::UnknownCluster { cluster_id, dependency_id: *dependency_id });
+        }
+    }
+
+    let mut path = vec![cluster_id];
+    match find_cycle(&dependencies, &mut path) {
+        Some(cycle) => Err(IllegalClusterDependency::Cycle { cluster_id, cycle }),
+        None => Ok(()),
+    }
+}
+
+/// Depth-first search along the dependencies of the last cluster of the path. Returns the path back to its first cluster, if there is one.
+fn find_cycle(dependencies: &HashMap<ClusterId, &[ClusterId]>, path: &mut Vec<ClusterId>) -> Option<Vec<ClusterId>> {
+    let current = *path.last()?;
+
+    for dependency_id in dependencies.get(&current).copied().unwrap_or_default() {
+        if *dependency_id == path[0] {
+            let mut cycle = Clone::clone(path);
+            cycle.push(*dependency_id);
+            return Some(cycle);
+        }
+        if path.contains(dependency_id) {
+            continue; //a cycle, which does not involve the validated cluster, is reported when that cluster is stored
+        }
+        path.push(*dependency_id);
+        if let Some(cycle) = find_cycle(dependencies, path) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// Clusters, which directly depend on the given cluster.
+pub fn dependents_of(cluster_id: ClusterId, clusters: &[ClusterConfiguration]) -> Vec<ClusterId> {
+    clusters.iter()
+        .filter(|cluster| cluster.dependencies.contains(&cluster_id))
+        .map(|cluster| cluster.id)
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use googletest::prelude::*;
+
+    use super::*;
+    use crate::cluster::ClusterName;
+    use crate::peer::PeerId;
+
+    fn cluster(id: ClusterId, dependencies: Vec<ClusterId>) -> ClusterConfiguration {
+        ClusterConfiguration {
+            id,
+            name: ClusterName::try_from("cluster").unwrap(),
+            leader: PeerId::random(),
+            devices: HashSet::new(),
+            peer_groups: HashSet::new(),
+            device_selectors: Vec::new(),
+            dependencies,
+        }
+    }
+
+    #[test]
+    fn should_accept_dependencies_on_existing_clusters() {
+        let infrastructure = ClusterId::random();
+        let network = ClusterId::random();
+        let existing = vec![
+            cluster(infrastructure, vec![]),
+            cluster(network, vec![infrastructure]),
+        ];
+
+        assert_that!(validate_dependencies(&cluster(ClusterId::random(), vec![infrastructure, network]), &existing), ok(anything()));
+    }
+
+    #[test]
+    fn should_reject_unknown_and_self_referencing_dependencies() {
+        let cluster_id = ClusterId::random();
+        let unknown = ClusterId::random();
+
+        assert_that!(
+            validate_dependencies(&cluster(cluster_id, vec![unknown]), &[]),
+            err(eq(&IllegalClusterDependency::UnknownCluster { cluster_id, dependency_id: unknown }))
+        );
+        assert_that!(
+            validate_dependencies(&cluster(cluster_id, vec![cluster_id]), &[]),
+            err(eq(&IllegalClusterDependency::SelfReference { cluster_id }))
+        );
+    }
+
+    #[test]
+    fn should_reject_a_dependency_cycle() {
+        let a = ClusterId::random();
+        let b = ClusterId::random();
+        let c = ClusterId::random();
+        let existing = vec![
+            cluster(a, vec![]),
+            cluster(b, vec![a]),
+            cluster(c, vec![b]),
+        ];
+
+        assert_that!(
+            validate_dependencies(&cluster(a, vec![c]), &existing),
+            err(eq(&IllegalClusterDependency::Cycle { cluster_id: a, cycle: vec![a, c, b, a] }))
+        );
+        assert_that!(dependents_of(a, &existing), eq(vec![b]));
+    }
+}
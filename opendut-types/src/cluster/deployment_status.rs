@@ -0,0 +1,167 @@
+use std::fmt;
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cluster::ClusterId;
+use crate::peer::PeerId;
+use crate::ShortName;
+
+/// Progress of rolling out a ClusterDeployment to the peers of the cluster.
+/// Each peer moves from `Pending` to `Configuring`, once CARL sent it the cluster assignment,
+/// and then to `Ready` or `Failed`, once it reported back whether it applied its configuration, e.g. its bridges and GRE tunnels.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ClusterDeploymentStatus {
+    pub id: ClusterId,
+    pub peers: Vec<PeerDeploymentStatus>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerDeploymentStatus {
+    pub peer_id: PeerId,
+    pub state: DeploymentState,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DeploymentState {
+    /// Waiting for all peers of the cluster to become available or for a free deployment slot.
+    Pending,
+    /// The cluster assignment was sent, but the peer did not report back yet.
+    Configuring,
+    Ready,
+    Failed { cause: String },
+}
+
+impl ClusterDeploymentStatus {
+    pub fn pending(id: ClusterId, peers: impl IntoIterator<Item=PeerId>) -> Self {
+        let peers = peers.into_iter()
+            .map(|peer_id| PeerDeploymentStatus { peer_id, state: DeploymentState::Pending })
+            .collect();
+        Self { id, peers }
+    }
+
+    pub fn get(&self, peer_id: PeerId) -> Option<&DeploymentState> {
+        self.peers.iter()
+            .find(|peer| peer.peer_id == peer_id)
+            .map(|peer| &peer.state)
+    }
+
+    /// Moves the peer into the given state, adding it, if it is not known yet, e.g. a leader without devices.
+    pub fn set(&mut self, peer_id: PeerId, state: DeploymentState) {
+        match self.peers.iter_mut().find(|peer| peer.peer_id == peer_id) {
+            Some(peer) => peer.state = state,
+            None => self.peers.push(PeerDeploymentStatus { peer_id, state }),
+        }
+    }
+
+    /// Starts a new rollout to the given members, forgetting peers, which are not members anymore.
+    pub fn configuring(&mut self, members: &[PeerId]) {
+        self.peers.retain(|peer| members.contains(&peer.peer_id));
+        for member in members {
+            self.set(*member, DeploymentState::Configuring);
+        }
+    }
+
+    /// Records the report of a peer about applying its configuration.
+    /// Reports of peers, which were not sent the cluster assignment yet, are ignored, as they refer to another configuration.
+    /// Returns whether the state of the peer changed.
+    pub fn report(&mut self, peer_id: PeerId, outcome: Result<(), String>) -> bool {
+        let Some(peer) = self.peers.iter_mut().find(|peer| peer.peer_id == peer_id) else {
+            return false;
+        };
+        if peer.state == DeploymentState::Pending {
+            return false;
+        }
+        let state = match outcome {
+            Ok(()) => DeploymentState::Ready,
+            Err(cause) => DeploymentState::Failed { cause },
+        };
+        let changed = peer.state != state;
+        peer.state = state;
+        changed
+    }
+
+    /// Aggregated state of the rollout: failed, if any peer failed, ready, once all peers are ready.
+    pub fn state(&self) -> DeploymentState {
+        let failed = self.peers.iter()
+            .filter(|peer| matches!(peer.state, DeploymentState::Failed { .. }))
+            .count();
+
+        if failed > 0 {
+            DeploymentState::Failed { cause: format!("{failed} of {} peers failed to apply their configuration.", self.peers.len()) }
+        } else if self.peers.is_empty().not() && self.peers.iter().all(|peer| peer.state == DeploymentState::Ready) {
+            DeploymentState::Ready
+        } else if self.peers.iter().any(|peer| peer.state != DeploymentState::Pending) {
+            DeploymentState::Configuring
+        } else {
+            DeploymentState::Pending
+        }
+    }
+}
+
+impl ShortName for DeploymentState {
+    fn short_name(&self) -> &'static str {
+        match self {
+            DeploymentState::Pending => "Pending",
+            DeploymentState::Configuring => "Configuring",
+            DeploymentState::Ready => "Ready",
+            DeploymentState::Failed { .. } => "Failed",
+        }
+    }
+}
+
+impl fmt::Display for DeploymentState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeploymentState::Failed { cause } => write!(f, "{}: {cause}", self.short_name()),
+            _ => write!(f, "{}", self.short_name()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_move_peers_from_pending_via_configuring_to_ready_or_failed() {
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let mut testee = ClusterDeploymentStatus::pending(ClusterId::random(), [peer_a, peer_b]);
+        assert_that!(testee.state(), eq(DeploymentState::Pending));
+
+        assert_that!(testee.report(peer_a, Ok(())), eq(false));
+        assert_that!(testee.get(peer_a), some(eq(&DeploymentState::Pending)));
+
+        testee.configuring(&[peer_a, peer_b]);
+        assert_that!(testee.state(), eq(DeploymentState::Configuring));
+
+        assert_that!(testee.report(peer_a, Ok(())), eq(true));
+        assert_that!(testee.get(peer_a), some(eq(&DeploymentState::Ready)));
+        assert_that!(testee.state(), eq(DeploymentState::Configuring));
+
+        assert_that!(testee.report(peer_b, Ok(())), eq(true));
+        assert_that!(testee.state(), eq(DeploymentState::Ready));
+
+        assert_that!(testee.report(peer_b, Err(String::from("cluster-network: failed"))), eq(true));
+        assert_that!(testee.get(peer_b), some(eq(&DeploymentState::Failed { cause: String::from("cluster-network: failed") })));
+        assert_that!(testee.state().short_name(), eq("Failed"));
+    }
+
+    #[test]
+    fn should_forget_peers_which_are_no_longer_members() {
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let leader = PeerId::random();
+        let mut testee = ClusterDeploymentStatus::pending(ClusterId::random(), [peer_a, peer_b]);
+
+        testee.configuring(&[peer_a, leader]);
+
+        assert_that!(testee.get(peer_b), none());
+        assert_that!(testee.get(leader), some(eq(&DeploymentState::Configuring)));
+        assert_that!(testee.report(peer_b, Ok(())), eq(false));
+    }
+}
@@ -6,11 +6,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub use assignment::*;
+pub use dependency::*;
+pub use deployment_status::*;
+pub use selector::*;
 
+use crate::peer::group::PeerGroupId;
 use crate::peer::PeerId;
 use crate::topology::DeviceId;
 
 mod assignment;
+mod dependency;
+mod deployment_status;
+mod selector;
 pub mod state;
 
 
@@ -149,6 +156,14 @@ pub struct ClusterConfiguration {
     pub name: ClusterName,
     pub leader: PeerId,
     pub devices: HashSet<DeviceId>,
+    /// Peer groups this cluster was composed from. The devices of their peers are contained in `devices`.
+    pub peer_groups: HashSet<PeerGroupId>,
+    /// Selectors, whose matching devices are bound to the cluster at deployment time, in addition to `devices`.
+    #[serde(default)]
+    pub device_selectors: Vec<DeviceSelector>,
+    /// Clusters, which have to be deployed and ready, before this cluster is deployed, e.g. a shared infrastructure cluster.
+    #[serde(default)]
+    pub dependencies: Vec<ClusterId>,
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
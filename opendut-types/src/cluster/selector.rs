@@ -0,0 +1,345 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Not;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::peer::label::{LabelSelector, PeerLabels};
+use crate::topology::{DeviceDescriptor, DeviceId, DeviceTag};
+use crate::util::net::NetworkInterfaceConfiguration;
+
+/// Declares devices, which CARL binds to a cluster at deployment time, out of the devices of the available peers.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DeviceSelector {
+    /// Tags, which a device needs to carry all of.
+    pub tags: Vec<DeviceTag>,
+    /// Kind of the device's network interface. Matches any kind, if `None`.
+    pub interface_kind: Option<DeviceInterfaceKind>,
+    /// Labels, which the peer of a device needs to match, e.g. `location=hall3,hw=raspberrypi`. Matches any peer, if empty.
+    #[serde(default)]
+    pub peer_labels: LabelSelector,
+    pub count: DeviceCount,
+}
+
+impl DeviceSelector {
+    pub fn matches(&self, device: &DeviceDescriptor, interface_kind: DeviceInterfaceKind, peer_labels: &PeerLabels) -> bool {
+        let tags_match = self.tags.iter().all(|tag| device.tags.contains(tag));
+        let kind_matches = match self.interface_kind {
+            Some(kind) => kind == interface_kind,
+            None => true,
+        };
+        tags_match && kind_matches && self.peer_labels.matches(peer_labels)
+    }
+}
+
+impl fmt::Display for DeviceSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tags = self.tags.iter().map(DeviceTag::value).collect::<Vec<_>>().join(",");
+        write!(f, "tags=[{tags}]")?;
+        if let Some(interface_kind) = self.interface_kind {
+            write!(f, " kind={interface_kind}")?;
+        }
+        if self.peer_labels.requirements.is_empty().not() {
+            write!(f, " peers=[{}]", self.peer_labels)?;
+        }
+        write!(f, " count={}", self.count)
+    }
+}
+
+/// Number of devices a selector binds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DeviceCount {
+    min: u32,
+    max: Option<u32>,
+}
+
+impl DeviceCount {
+    pub fn new(min: u32, max: Option<u32>) -> Result<Self, IllegalDeviceCount> {
+        match max {
+            Some(max) if max < min => Err(IllegalDeviceCount::MaxBelowMin { min, max }),
+            Some(0) => Err(IllegalDeviceCount::Zero),
+            _ => Ok(Self { min, max }),
+        }
+    }
+
+    /// Number of devices required, before the cluster can be deployed.
+    pub fn min(&self) -> u32 {
+        self.min
+    }
+
+    /// Number of devices bound at most. Unlimited, if `None`.
+    pub fn max(&self) -> Option<u32> {
+        self.max
+    }
+}
+
+impl fmt::Display for DeviceCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{}..{max}", self.min),
+            None => write!(f, "{}..", self.min),
+        }
+    }
+}
+
+impl FromStr for DeviceCount {
+    type Err = IllegalDeviceCount;
+
+    /// Parses an exact count like `2` or a range like `1..3` or `1..`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse = |number: &str| number.trim().parse::<u32>()
+            .map_err(|_| IllegalDeviceCount::Unparseable { value: value.to_owned() });
+
+        match value.split_once("..") {
+            Some((min, max)) => {
+                let min = if min.trim().is_empty() { 0 } else { parse(min)? };
+                let max = if max.trim().is_empty() { None } else { Some(parse(max)?) };
+                DeviceCount::new(min, max)
+            }
+            None => {
+                let count = parse(value)?;
+                DeviceCount::new(count, Some(count))
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum IllegalDeviceCount {
+    #[error("Maximum device count {max} is lower than the minimum device count {min}.")]
+    MaxBelowMin { min: u32, max: u32 },
+    #[error("Maximum device count must not be zero.")]
+    Zero,
+    #[error("Device count '{value}' could not be parsed. Expected a number like '2' or a range like '1..3' or '1..'.")]
+    Unparseable { value: String },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum DeviceInterfaceKind {
+    Ethernet,
+    Can,
+}
+
+impl From<&NetworkInterfaceConfiguration> for DeviceInterfaceKind {
+    fn from(configuration: &NetworkInterfaceConfiguration) -> Self {
+        match configuration {
+            NetworkInterfaceConfiguration::Ethernet => DeviceInterfaceKind::Ethernet,
+            NetworkInterfaceConfiguration::Can { .. } => DeviceInterfaceKind::Can,
+        }
+    }
+}
+
+impl fmt::Display for DeviceInterfaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceInterfaceKind::Ethernet => write!(f, "ethernet"),
+            DeviceInterfaceKind::Can => write!(f, "can"),
+        }
+    }
+}
+
+impl FromStr for DeviceInterfaceKind {
+    type Err = IllegalDeviceInterfaceKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "ethernet" => Ok(DeviceInterfaceKind::Ethernet),
+            "can" => Ok(DeviceInterfaceKind::Can),
+            _ => Err(IllegalDeviceInterfaceKind { value: value.to_owned() }),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+#[error("Unknown device interface kind '{value}'. Expected 'ethernet' or 'can'.")]
+pub struct IllegalDeviceInterfaceKind {
+    pub value: String,
+}
+
+/// A device of an available peer, which selectors can bind.
+#[derive(Clone, Debug)]
+pub struct SelectableDevice {
+    pub device: DeviceDescriptor,
+    pub interface_kind: DeviceInterfaceKind,
+    /// Labels of the peer, which the device belongs to.
+    pub peer_labels: PeerLabels,
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+#[error("Device selector '{selector}' requires at least {required} devices, but only {available} are available.")]
+pub struct UnsatisfiedDeviceSelector {
+    pub selector: String,
+    pub required: u32,
+    pub available: u32,
+}
+
+/// Binds devices to each selector in turn, up to its maximum count.
+/// Devices in `excluded` (e.g. devices explicitly contained in the cluster) and devices bound by a previous selector are not bound again.
+/// Devices are considered in the order of their names, so that the binding is stable while the device pool does not change.
+pub fn bind_devices(
+    selectors: &[DeviceSelector],
+    candidates: &[SelectableDevice],
+    excluded: &HashSet<DeviceId>,
+) -> Result<HashSet<DeviceId>, UnsatisfiedDeviceSelector> {
+    let mut candidates = candidates.iter().collect::<Vec<_>>();
+    candidates.sort_by(|a, b| {
+        a.device.name.value().cmp(b.device.name.value())
+            .then(a.device.id.0.cmp(&b.device.id.0))
+    });
+
+    let mut bound = HashSet::new();
+
+    for selector in selectors {
+        let matching = candidates.iter()
+            .filter(|candidate| excluded.contains(&candidate.device.id).not())
+            .filter(|candidate| bound.contains(&candidate.device.id).not())
+            .filter(|candidate| selector.matches(&candidate.device, candidate.interface_kind, &candidate.peer_labels))
+            .map(|candidate| candidate.device.id)
+            .collect::<Vec<_>>();
+
+        let available = u32::try_from(matching.len()).unwrap_or(u32::MAX);
+        if available < selector.count.min() {
+            return Err(UnsatisfiedDeviceSelector {
+                selector: selector.to_string(),
+                required: selector.count.min(),
+                available,
+            });
+        }
+
+        let take = selector.count.max()
+            .map_or(matching.len(), |max| max as usize);
+        bound.extend(matching.into_iter().take(take));
+    }
+
+    Ok(bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::peer::label::{LabelKey, LabelValue};
+    use crate::topology::DeviceName;
+    use crate::util::net::NetworkInterfaceId;
+
+    use super::*;
+
+    fn device(name: &str, tags: &[&str], interface_kind: DeviceInterfaceKind) -> SelectableDevice {
+        device_of_peer(name, tags, interface_kind, &[])
+    }
+
+    fn device_of_peer(name: &str, tags: &[&str], interface_kind: DeviceInterfaceKind, peer_labels: &[(&str, &str)]) -> SelectableDevice {
+        SelectableDevice {
+            device: DeviceDescriptor {
+                id: DeviceId::random(),
+                name: DeviceName::try_from(name).unwrap(),
+                description: None,
+                interface: NetworkInterfaceId::random(),
+                tags: tags.iter().map(|tag| DeviceTag::try_from(*tag).unwrap()).collect(),
+            },
+            interface_kind,
+            peer_labels: peer_labels.iter()
+                .map(|(key, value)| (LabelKey::try_from(*key).unwrap(), LabelValue::try_from(*value).unwrap()))
+                .collect(),
+        }
+    }
+
+    fn selector(tags: &[&str], interface_kind: Option<DeviceInterfaceKind>, count: &str) -> DeviceSelector {
+        DeviceSelector {
+            tags: tags.iter().map(|tag| DeviceTag::try_from(*tag).unwrap()).collect(),
+            interface_kind,
+            peer_labels: LabelSelector::default(),
+            count: count.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_parse_device_counts() -> Result<()> {
+        assert_that!("2".parse::<DeviceCount>(), ok(eq(DeviceCount::new(2, Some(2))?)));
+        assert_that!("1..3".parse::<DeviceCount>(), ok(eq(DeviceCount::new(1, Some(3))?)));
+        assert_that!("1..".parse::<DeviceCount>(), ok(eq(DeviceCount::new(1, None)?)));
+        assert_that!("3..1".parse::<DeviceCount>(), err(eq(IllegalDeviceCount::MaxBelowMin { min: 3, max: 1 })));
+        assert_that!("0".parse::<DeviceCount>(), err(eq(IllegalDeviceCount::Zero)));
+        assert_that!("two".parse::<DeviceCount>(), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_bind_matching_devices_up_to_the_maximum_count() -> Result<()> {
+        let ecu_a = device("ecu-a", &["ecu", "front"], DeviceInterfaceKind::Can);
+        let ecu_b = device("ecu-b", &["ecu"], DeviceInterfaceKind::Can);
+        let ecu_c = device("ecu-c", &["ecu"], DeviceInterfaceKind::Can);
+        let camera = device("camera", &["ecu"], DeviceInterfaceKind::Ethernet);
+        let candidates = vec![ecu_c.clone(), camera.clone(), ecu_b.clone(), ecu_a.clone()];
+
+        let selectors = vec![
+            selector(&["ecu"], Some(DeviceInterfaceKind::Can), "1..2"),
+            selector(&[], Some(DeviceInterfaceKind::Ethernet), "1"),
+        ];
+
+        let bound = bind_devices(&selectors, &candidates, &HashSet::new())?;
+
+        assert_that!(bound, eq(HashSet::from([ecu_a.device.id, ecu_b.device.id, camera.device.id])));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_bind_excluded_devices_or_devices_bound_by_a_previous_selector() -> Result<()> {
+        let ecu_a = device("ecu-a", &["ecu"], DeviceInterfaceKind::Can);
+        let ecu_b = device("ecu-b", &["ecu"], DeviceInterfaceKind::Can);
+        let ecu_c = device("ecu-c", &["ecu"], DeviceInterfaceKind::Can);
+        let candidates = vec![ecu_a.clone(), ecu_b.clone(), ecu_c.clone()];
+
+        let selectors = vec![
+            selector(&["ecu"], None, "1"),
+            selector(&["ecu"], None, "1.."),
+        ];
+
+        let bound = bind_devices(&selectors, &candidates, &HashSet::from([ecu_a.device.id]))?;
+
+        assert_that!(bound, eq(HashSet::from([ecu_b.device.id, ecu_c.device.id])));
+        Ok(())
+    }
+
+    #[test]
+    fn should_bind_only_devices_of_peers_matching_the_label_selector() -> Result<()> {
+        let hall3 = device_of_peer("ecu-a", &["ecu"], DeviceInterfaceKind::Can, &[("location", "hall3"), ("hw", "raspberrypi")]);
+        let hall3_other_hardware = device_of_peer("ecu-b", &["ecu"], DeviceInterfaceKind::Can, &[("location", "hall3"), ("hw", "x86")]);
+        let hall4 = device_of_peer("ecu-c", &["ecu"], DeviceInterfaceKind::Can, &[("location", "hall4"), ("hw", "raspberrypi")]);
+        let unlabeled = device("ecu-d", &["ecu"], DeviceInterfaceKind::Can);
+        let candidates = vec![hall3.clone(), hall3_other_hardware, hall4, unlabeled];
+
+        let selectors = vec![
+            DeviceSelector {
+                peer_labels: "location=hall3, hw=raspberrypi".parse()?,
+                ..selector(&[], None, "1..")
+            },
+        ];
+
+        let bound = bind_devices(&selectors, &candidates, &HashSet::new())?;
+
+        assert_that!(bound, eq(HashSet::from([hall3.device.id])));
+        assert_that!(selectors[0].to_string(), eq("tags=[] peers=[location=hall3,hw=raspberrypi] count=1.."));
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_if_too_few_devices_match() -> Result<()> {
+        let ecu_a = device("ecu-a", &["ecu"], DeviceInterfaceKind::Can);
+
+        let selectors = vec![
+            selector(&["ecu"], None, "2"),
+        ];
+
+        let result = bind_devices(&selectors, &[ecu_a], &HashSet::new());
+
+        assert_that!(result, err(eq(UnsatisfiedDeviceSelector {
+            selector: String::from("tags=[ecu] count=2"),
+            required: 2,
+            available: 1,
+        })));
+        Ok(())
+    }
+}
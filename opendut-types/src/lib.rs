@@ -8,6 +8,9 @@ pub mod vpn;
 pub mod util;
 pub mod resources;
 pub mod cleo;
+pub mod project;
+pub mod service_account;
+pub mod snapshot;
 
 pub trait ShortName {
     fn short_name(&self) -> &'static str;
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Not;
+
+use crate::peer::configuration::{Parameter, ParameterId, ParameterValue, PeerConfiguration};
+use crate::peer::ethernet::EthernetBridge;
+use crate::peer::executor::ExecutorDescriptor;
+use crate::peer::hook::ClusterHook;
+use crate::peer::restbus::RestbusChannel;
+
+/// Increases with each PeerConfiguration sent to a peer, so that a delta is only applied onto the configuration it was computed from.
+pub type PeerConfigurationVersion = u64;
+
+/// Changes between two versions of a [`PeerConfiguration`], to avoid sending the whole configuration for each change.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerConfigurationDelta {
+    pub base_version: PeerConfigurationVersion,
+    pub version: PeerConfigurationVersion,
+    pub executors: ParameterDelta<ExecutorDescriptor>,
+    pub ethernet_bridges: ParameterDelta<EthernetBridge>,
+    pub restbus_channels: ParameterDelta<RestbusChannel>,
+    pub cluster_hooks: ParameterDelta<ClusterHook>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterDelta<V: ParameterValue> {
+    /// Parameters, which were added or changed.
+    pub upserted: Vec<Parameter<V>>,
+    pub removed: Vec<ParameterId>,
+}
+impl<V: ParameterValue> Default for ParameterDelta<V> {
+    fn default() -> Self {
+        Self { upserted: vec![], removed: vec![] }
+    }
+}
+
+impl PeerConfigurationDelta {
+    /// Computes the changes from `previous` to `next`.
+    /// Returns `None`, if a configuration contains the same parameter multiple times, since the changes could then not be attributed unambiguously.
+    pub fn between(
+        previous: &PeerConfiguration,
+        next: &PeerConfiguration,
+        base_version: PeerConfigurationVersion,
+        version: PeerConfigurationVersion,
+    ) -> Option<Self> {
+        Some(Self {
+            base_version,
+            version,
+            executors: ParameterDelta::between(&previous.executors, &next.executors)?,
+            ethernet_bridges: ParameterDelta::between(&previous.ethernet_bridges, &next.ethernet_bridges)?,
+            restbus_channels: ParameterDelta::between(&previous.restbus_channels, &next.restbus_channels)?,
+            cluster_hooks: ParameterDelta::between(&previous.cluster_hooks, &next.cluster_hooks)?,
+        })
+    }
+
+    /// Applies the changes onto the configuration of the base version.
+    pub fn apply_to(&self, configuration: &PeerConfiguration) -> PeerConfiguration {
+        PeerConfiguration {
+            executors: self.executors.apply_to(&configuration.executors),
+            ethernet_bridges: self.ethernet_bridges.apply_to(&configuration.ethernet_bridges),
+            restbus_channels: self.restbus_channels.apply_to(&configuration.restbus_channels),
+            cluster_hooks: self.cluster_hooks.apply_to(&configuration.cluster_hooks),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.executors.is_empty() && self.ethernet_bridges.is_empty() && self.restbus_channels.is_empty() && self.cluster_hooks.is_empty()
+    }
+
+    /// Number of parameters contained in this delta.
+    pub fn len(&self) -> usize {
+        self.executors.len() + self.ethernet_bridges.len() + self.restbus_channels.len() + self.cluster_hooks.len()
+    }
+}
+
+impl<V: ParameterValue + Clone + PartialEq> ParameterDelta<V> {
+    fn between(previous: &[Parameter<V>], next: &[Parameter<V>]) -> Option<Self> {
+        let previous = unique_by_id(previous)?;
+        let next_ids = unique_by_id(next)?;
+
+        let upserted = next.iter()
+            .filter(|parameter| previous.get(&parameter.id).copied() != Some(*parameter))
+            .cloned()
+            .collect();
+
+        let removed = previous.keys()
+            .filter(|id| next_ids.contains_key(*id).not())
+            .cloned()
+            .collect();
+
+        Some(Self { upserted, removed })
+    }
+
+    fn apply_to(&self, parameters: &[Parameter<V>]) -> Vec<Parameter<V>> {
+        let removed = self.removed.iter().collect::<HashSet<_>>();
+        let mut upserted = self.upserted.iter()
+            .map(|parameter| (parameter.id, parameter))
+            .collect::<HashMap<_, _>>();
+
+        let mut result = parameters.iter()
+            .filter(|parameter| removed.contains(&parameter.id).not())
+            .map(|parameter| match upserted.remove(&parameter.id) {
+                Some(changed) => Clone::clone(changed),
+                None => Clone::clone(parameter),
+            })
+            .collect::<Vec<_>>();
+
+        result.extend( //added parameters, keeping the order in which they were sent
+            self.upserted.iter()
+                .filter(|parameter| upserted.contains_key(&parameter.id))
+                .cloned()
+        );
+        result
+    }
+}
+impl<V: ParameterValue> ParameterDelta<V> {
+    pub fn is_empty(&self) -> bool {
+        self.upserted.is_empty() && self.removed.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.upserted.len() + self.removed.len()
+    }
+}
+
+fn unique_by_id<V: ParameterValue>(parameters: &[Parameter<V>]) -> Option<HashMap<ParameterId, &Parameter<V>>> {
+    let by_id = parameters.iter()
+        .map(|parameter| (parameter.id, parameter))
+        .collect::<HashMap<_, _>>();
+
+    (by_id.len() == parameters.len()).then_some(by_id)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::configuration::ParameterTarget;
+    use crate::peer::executor::{ExecutorId, ExecutorKind, ResultsUrl};
+    use crate::util::net::NetworkInterfaceName;
+
+    #[test]
+    fn applying_the_delta_should_reproduce_the_next_configuration() {
+        let executor = |results_url: &str| ExecutorDescriptor {
+            id: ExecutorId::random(),
+            kind: ExecutorKind::Executable,
+            results_url: Some(ResultsUrl::try_from(results_url).unwrap()),
+            sandbox: Default::default(),
+        };
+        let kept = executor("https://example.com/kept");
+        let removed = executor("https://example.com/removed");
+        let added = executor("https://example.com/added");
+
+        let mut previous = PeerConfiguration::default();
+        previous.insert(Clone::clone(&kept), ParameterTarget::Present);
+        previous.insert(Clone::clone(&removed), ParameterTarget::Present);
+        previous.insert(EthernetBridge { name: NetworkInterfaceName::try_from("br-opendut").unwrap() }, ParameterTarget::Present);
+
+        let mut next = PeerConfiguration::default();
+        next.insert(kept, ParameterTarget::Present);
+        next.insert(added, ParameterTarget::Present);
+        next.insert(EthernetBridge { name: NetworkInterfaceName::try_from("br-opendut").unwrap() }, ParameterTarget::Absent);
+
+        let delta = PeerConfigurationDelta::between(&previous, &next, 1, 2).unwrap();
+
+        assert_eq!(delta.executors.upserted.len(), 1);
+        assert_eq!(delta.executors.removed, vec![previous.executors[1].id]);
+        assert_eq!(delta.ethernet_bridges.upserted.len(), 1);
+        assert_eq!(delta.apply_to(&previous), next);
+
+        let unchanged = PeerConfigurationDelta::between(&next, &next, 2, 3).unwrap();
+        assert!(unchanged.is_empty());
+    }
+}
@@ -1,10 +1,15 @@
 use crate::cluster::ClusterAssignment;
 use crate::peer::ethernet::EthernetBridge;
 use crate::peer::executor::ExecutorDescriptor;
+use crate::peer::hook::ClusterHook;
+use crate::peer::restbus::RestbusChannel;
 
 mod parameter;
 pub use parameter::*;
 
+pub mod delta;
+pub mod validation;
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct OldPeerConfiguration {
     pub cluster_assignment: Option<ClusterAssignment>,
@@ -15,6 +20,8 @@ pub struct OldPeerConfiguration {
 pub struct PeerConfiguration {
     pub executors: Vec<Parameter<ExecutorDescriptor>>,
     pub ethernet_bridges: Vec<Parameter<EthernetBridge>>,
+    pub restbus_channels: Vec<Parameter<RestbusChannel>>,
+    pub cluster_hooks: Vec<Parameter<ClusterHook>>,
     //TODO migrate more parameters
 }
 impl PeerConfiguration {
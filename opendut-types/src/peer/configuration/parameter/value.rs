@@ -2,6 +2,8 @@ use crate::peer::configuration::parameter::{Parameter, ParameterId};
 use crate::peer::configuration::PeerConfiguration;
 use crate::peer::ethernet::EthernetBridge;
 use crate::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use crate::peer::hook::ClusterHook;
+use crate::peer::restbus::RestbusChannel;
 use crate::OPENDUT_UUID_NAMESPACE;
 use std::any::Any;
 use std::hash::{DefaultHasher, Hash, Hasher};
@@ -70,6 +72,35 @@ impl ParameterValue for EthernetBridge {
     }
 }
 
+impl ParameterValue for RestbusChannel {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.bus.hash(&mut hasher);
+        self.interface.name().hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+    fn peer_configuration_field(peer_configuration: &mut PeerConfiguration) -> &mut Vec<Parameter<Self>> {
+        &mut peer_configuration.restbus_channels
+    }
+}
+
+impl ParameterValue for ClusterHook {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.name.hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+    fn peer_configuration_field(peer_configuration: &mut PeerConfiguration) -> &mut Vec<Parameter<Self>> {
+        &mut peer_configuration.cluster_hooks
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -82,12 +113,15 @@ mod tests {
         let mut peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            restbus_channels: vec![],
+            cluster_hooks: vec![],
         };
 
         let value = ExecutorDescriptor {
             id: ExecutorId::random(),
             kind: ExecutorKind::Executable,
-            results_url: None
+            results_url: None,
+            sandbox: Default::default(),
         };
         let target = ParameterTarget::Present;
         peer_configuration.insert(value.clone(), target);
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::peer::PeerId;
+
+/// Outcome of checking, whether a [PeerConfiguration](super::PeerConfiguration) can be applied on a host, without applying it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigurationValidationReport {
+    pub peer_id: PeerId,
+    /// Whether none of the checks failed.
+    pub applicable: bool,
+    pub checks: Vec<ConfigurationCheck>,
+}
+impl ConfigurationValidationReport {
+    pub fn new(peer_id: PeerId, checks: Vec<ConfigurationCheck>) -> Self {
+        let applicable = checks.iter()
+            .all(|check| !matches!(check.outcome, CheckOutcome::Failed { .. }));
+
+        Self { peer_id, applicable, checks }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigurationCheck {
+    /// Component of the configuration, which is checked, e.g. "cluster-network" or "executors".
+    pub component: String,
+    /// What is checked, e.g. "interface 'eth0' exists".
+    pub subject: String,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "kebab-case")]
+pub enum CheckOutcome {
+    Passed,
+    Failed { cause: String },
+    Skipped { reason: String },
+}
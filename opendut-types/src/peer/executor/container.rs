@@ -6,6 +6,8 @@ use std::ops::Not;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
+use crate::util::net::{NetworkInterfaceName, NetworkInterfaceNameError};
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
 #[serde(rename_all = "kebab-case")]
 pub enum Engine {
@@ -515,5 +517,398 @@ impl fmt::Display for ContainerCommandArgument {
     }
 }
 
+/// Name of a secret, which is provided to the container as a file in [CONTAINER_SECRETS_DIRECTORY],
+/// rather than as an environment variable, so that its value does not show up when inspecting the container.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ContainerSecret(String);
+
+pub const CONTAINER_SECRETS_DIRECTORY: &str = "/run/secrets";
+
+impl ContainerSecret {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerSecret {
+    #[error("Container secret must not be empty.")]
+    Empty,
+    #[error("Container secret '{value}' contains invalid characters. Only alphanumeric characters, '-', '_' and '.' are allowed and it must not start with '.'.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ContainerSecret {
+    type Error = IllegalContainerSecret;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalContainerSecret::Empty)
+        } else if value.starts_with('.')
+            || value.chars().any(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')) {
+            Err(IllegalContainerSecret::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerSecret {
+    type Error = IllegalContainerSecret;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerSecret::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerSecret {
+    type Err = IllegalContainerSecret;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerSecret::try_from(value)
+    }
+}
+
+impl From<ContainerSecret> for String {
+    fn from(value: ContainerSecret) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ContainerSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Access to a CAN interface of the peer, which EDGAR provides to the container as a Unix socket in [CONTAINER_CAN_GATEWAY_DIRECTORY],
+/// so that the container does not require privileged mode to use SocketCAN. EDGAR opens the CAN socket on behalf of the container
+/// and only forwards frames with the allowed CAN IDs in either direction.
+///
+/// Written as `<interface>:raw:<ids>` with a comma-separated list of CAN IDs or ID ranges, e.g. `can0:raw:0x100,0x200-0x2FF`,
+/// or as `<interface>:isotp:<tx-id>:<rx-id>`, e.g. `can0:isotp:0x7E0:0x7E8`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContainerCanGateway {
+    interface: NetworkInterfaceName,
+    protocol: ContainerCanGatewayProtocol,
+}
+
+/// Directory in the container, which holds one socket per [ContainerCanGateway], named as returned by [ContainerCanGateway::socket_name].
+/// Each message on these sockets consists of the CAN ID (4 bytes, big-endian), the payload length (2 bytes, big-endian) and the payload.
+/// For ISO-TP, the payload is a complete ISO-TP message and the CAN ID is the transmit ID when sending and the receive ID when receiving.
+pub const CONTAINER_CAN_GATEWAY_DIRECTORY: &str = "/run/opendut/can";
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ContainerCanGatewayProtocol {
+    Raw { allowed_ids: Vec<CanIdRange> },
+    IsoTp { tx_id: u32, rx_id: u32 },
+}
+
+/// Inclusive range of CAN IDs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct CanIdRange {
+    first: u32,
+    last: u32,
+}
+
+impl CanIdRange {
+    pub const MAX_ID: u32 = 0x1FFF_FFFF;
+
+    pub fn contains(&self, id: u32) -> bool {
+        self.first <= id && id <= self.last
+    }
+}
+
+impl fmt::Display for CanIdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first == self.last {
+            write!(f, "{:#X}", self.first)
+        } else {
+            write!(f, "{:#X}-{:#X}", self.first, self.last)
+        }
+    }
+}
+
+impl ContainerCanGateway {
+    pub fn interface(&self) -> &NetworkInterfaceName {
+        &self.interface
+    }
+
+    pub fn protocol(&self) -> &ContainerCanGatewayProtocol {
+        &self.protocol
+    }
+
+    /// Whether the container may send or receive frames with the given CAN ID via this gateway.
+    pub fn is_allowed(&self, id: u32) -> bool {
+        match &self.protocol {
+            ContainerCanGatewayProtocol::Raw { allowed_ids } => allowed_ids.iter().any(|range| range.contains(id)),
+            ContainerCanGatewayProtocol::IsoTp { tx_id, rx_id } => id == *tx_id || id == *rx_id,
+        }
+    }
+
+    /// File name of the socket in [CONTAINER_CAN_GATEWAY_DIRECTORY].
+    pub fn socket_name(&self) -> String {
+        match &self.protocol {
+            ContainerCanGatewayProtocol::Raw { .. } => format!("{}-raw.sock", self.interface),
+            ContainerCanGatewayProtocol::IsoTp { tx_id, rx_id } => format!("{}-isotp-{:#X}-{:#X}.sock", self.interface, tx_id, rx_id),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IllegalContainerCanGateway {
+    #[error("Container CAN gateway '{value}' is not of the form '<interface>:raw:<ids>' or '<interface>:isotp:<tx-id>:<rx-id>'.")]
+    InvalidFormat { value: String },
+    #[error("Container CAN gateway '{value}' has an invalid interface name: {cause}")]
+    InvalidInterface { value: String, cause: NetworkInterfaceNameError },
+    #[error("Container CAN gateway '{value}' contains the invalid CAN ID '{id}'. CAN IDs must be decimal or hexadecimal with the prefix '0x' and at most {:#X}.", CanIdRange::MAX_ID)]
+    InvalidCanId { value: String, id: String },
+    #[error("Container CAN gateway '{value}' does not allow any CAN IDs.")]
+    NoAllowedIds { value: String },
+}
+
+impl TryFrom<String> for ContainerCanGateway {
+    type Error = IllegalContainerCanGateway;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        fn parse_id(value: &str, id: &str) -> Result<u32, IllegalContainerCanGateway> {
+            let id = id.trim();
+            let parsed = match id.strip_prefix("0x").or_else(|| id.strip_prefix("0X")) {
+                Some(hex) => u32::from_str_radix(hex, 16),
+                None => id.parse::<u32>(),
+            };
+            parsed.ok()
+                .filter(|parsed| *parsed <= CanIdRange::MAX_ID)
+                .ok_or_else(|| IllegalContainerCanGateway::InvalidCanId { value: value.to_owned(), id: id.to_owned() })
+        }
+
+        let invalid_format = || IllegalContainerCanGateway::InvalidFormat { value: value.clone() };
+
+        let mut parts = value.split(':');
+        let interface = parts.next().ok_or_else(invalid_format)?;
+        let protocol = parts.next().ok_or_else(invalid_format)?;
+
+        let interface = NetworkInterfaceName::try_from(interface)
+            .map_err(|cause| IllegalContainerCanGateway::InvalidInterface { value: value.clone(), cause })?;
+
+        let protocol = match (protocol, parts.next(), parts.next(), parts.next()) {
+            ("raw", Some(ids), None, None) => {
+                let allowed_ids = ids.split(',')
+                    .filter(|range| range.trim().is_empty().not())
+                    .map(|range| {
+                        let (first, last) = range.split_once('-').unwrap_or((range, range));
+                        let first = parse_id(&value, first)?;
+                        let last = parse_id(&value, last)?;
+                        if first <= last {
+                            Ok(CanIdRange { first, last })
+                        } else {
+                            Err(IllegalContainerCanGateway::InvalidCanId { value: value.clone(), id: range.to_owned() })
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if allowed_ids.is_empty() {
+                    return Err(IllegalContainerCanGateway::NoAllowedIds { value });
+                }
+                ContainerCanGatewayProtocol::Raw { allowed_ids }
+            }
+            ("isotp", Some(tx_id), Some(rx_id), None) => {
+                ContainerCanGatewayProtocol::IsoTp {
+                    tx_id: parse_id(&value, tx_id)?,
+                    rx_id: parse_id(&value, rx_id)?,
+                }
+            }
+            _ => return Err(invalid_format()),
+        };
+
+        Ok(Self { interface, protocol })
+    }
+}
+
+impl TryFrom<&str> for ContainerCanGateway {
+    type Error = IllegalContainerCanGateway;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerCanGateway::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerCanGateway {
+    type Err = IllegalContainerCanGateway;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerCanGateway::try_from(value)
+    }
+}
+
+impl From<ContainerCanGateway> for String {
+    fn from(value: ContainerCanGateway) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ContainerCanGateway {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.protocol {
+            ContainerCanGatewayProtocol::Raw { allowed_ids } => {
+                let allowed_ids = allowed_ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "{}:raw:{}", self.interface, allowed_ids.join(","))
+            }
+            ContainerCanGatewayProtocol::IsoTp { tx_id, rx_id } => write!(f, "{}:isotp:{:#X}:{:#X}", self.interface, tx_id, rx_id),
+        }
+    }
+}
+
+/// How the container is connected to the network of the peer.
+///
+/// By default, the container shares the network namespace of the host, which includes the cluster bridge.
+/// Test tools, which require Layer 2 visibility on the bridged network with their own MAC address, can instead receive a dedicated interface,
+/// which EDGAR moves into the network namespace of the container as `eth0`:
+/// - `macvlan`: A macvlan interface on top of the cluster bridge.
+/// - `sriov:<physical-function>`: A free SR-IOV virtual function of the given network card, e.g. `sriov:enp1s0f0`.
+///   The physical function should be joined to the cluster bridge, for example as one of the peer's Ethernet interfaces.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ContainerNetworkAttachment {
+    #[default]
+    Host,
+    Macvlan,
+    SrIov { physical_function: NetworkInterfaceName },
+}
+
+/// Name of the dedicated interface in the container for [ContainerNetworkAttachment::Macvlan] and [ContainerNetworkAttachment::SrIov].
+pub const CONTAINER_NETWORK_ATTACHMENT_INTERFACE: &str = "eth0";
+
+impl ContainerNetworkAttachment {
+    pub fn is_host(&self) -> bool {
+        matches!(self, ContainerNetworkAttachment::Host)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IllegalContainerNetworkAttachment {
+    #[error("Container network attachment '{value}' is not one of 'host', 'macvlan' or 'sriov:<physical-function>'.")]
+    InvalidFormat { value: String },
+    #[error("Container network attachment '{value}' has an invalid physical function: {cause}")]
+    InvalidPhysicalFunction { value: String, cause: NetworkInterfaceNameError },
+}
+
+impl TryFrom<String> for ContainerNetworkAttachment {
+    type Error = IllegalContainerNetworkAttachment;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.trim() {
+            "" | "host" => Ok(ContainerNetworkAttachment::Host),
+            "macvlan" => Ok(ContainerNetworkAttachment::Macvlan),
+            other => match other.split_once(':') {
+                Some(("sriov", physical_function)) => {
+                    let physical_function = NetworkInterfaceName::try_from(physical_function)
+                        .map_err(|cause| IllegalContainerNetworkAttachment::InvalidPhysicalFunction { value: value.clone(), cause })?;
+                    Ok(ContainerNetworkAttachment::SrIov { physical_function })
+                }
+                _ => Err(IllegalContainerNetworkAttachment::InvalidFormat { value }),
+            }
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerNetworkAttachment {
+    type Error = IllegalContainerNetworkAttachment;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerNetworkAttachment::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerNetworkAttachment {
+    type Err = IllegalContainerNetworkAttachment;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerNetworkAttachment::try_from(value)
+    }
+}
+
+impl From<ContainerNetworkAttachment> for String {
+    fn from(value: ContainerNetworkAttachment) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ContainerNetworkAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerNetworkAttachment::Host => write!(f, "host"),
+            ContainerNetworkAttachment::Macvlan => write!(f, "macvlan"),
+            ContainerNetworkAttachment::SrIov { physical_function } => write!(f, "sriov:{physical_function}"),
+        }
+    }
+}
+
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum IllegalContainerConfiguration {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn A_raw_ContainerCanGateway_should_only_allow_the_specified_ids() -> Result<()> {
+        let gateway = ContainerCanGateway::try_from("can0:raw:0x100,0x200-0x2FF,42")?;
+
+        assert_that!(gateway.interface().name(), eq("can0"));
+        assert_that!(gateway.is_allowed(0x100), eq(true));
+        assert_that!(gateway.is_allowed(0x101), eq(false));
+        assert_that!(gateway.is_allowed(0x250), eq(true));
+        assert_that!(gateway.is_allowed(0x2FF), eq(true));
+        assert_that!(gateway.is_allowed(42), eq(true));
+        assert_that!(gateway.socket_name(), eq("can0-raw.sock"));
+        assert_that!(gateway.to_string(), eq("can0:raw:0x100,0x200-0x2FF,0x2A"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn An_isotp_ContainerCanGateway_should_only_allow_its_tx_and_rx_ids() -> Result<()> {
+        let gateway = ContainerCanGateway::try_from("can1:isotp:0x7e0:0x7e8")?;
+
+        assert_that!(gateway.protocol(), eq(&ContainerCanGatewayProtocol::IsoTp { tx_id: 0x7E0, rx_id: 0x7E8 }));
+        assert_that!(gateway.is_allowed(0x7E0), eq(true));
+        assert_that!(gateway.is_allowed(0x7E8), eq(true));
+        assert_that!(gateway.is_allowed(0x7DF), eq(false));
+        assert_that!(gateway.socket_name(), eq("can1-isotp-0x7E0-0x7E8.sock"));
+
+        let reparsed = ContainerCanGateway::try_from(gateway.to_string())?;
+        assert_that!(reparsed, eq(&gateway));
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_ContainerCanGateway_should_not_be_created_from_an_invalid_value() -> Result<()> {
+        assert!(ContainerCanGateway::try_from("can0").is_err());
+        assert!(ContainerCanGateway::try_from("can0:raw:").is_err());
+        assert!(ContainerCanGateway::try_from("can0:raw:0x300-0x200").is_err());
+        assert!(ContainerCanGateway::try_from("can0:raw:0x20000000").is_err());
+        assert!(ContainerCanGateway::try_from("can0:isotp:0x7E0").is_err());
+        assert!(ContainerCanGateway::try_from("can0:j1939:0x7E0").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn A_ContainerNetworkAttachment_should_be_parsed_from_its_string_form() -> Result<()> {
+        assert_that!(ContainerNetworkAttachment::try_from("host")?, eq(&ContainerNetworkAttachment::Host));
+        assert_that!(ContainerNetworkAttachment::try_from("macvlan")?, eq(&ContainerNetworkAttachment::Macvlan));
+
+        let sriov = ContainerNetworkAttachment::try_from("sriov:enp1s0f0")?;
+        assert_that!(sriov, eq(&ContainerNetworkAttachment::SrIov { physical_function: NetworkInterfaceName::try_from("enp1s0f0")? }));
+        assert_that!(sriov.to_string(), eq("sriov:enp1s0f0"));
+
+        assert!(ContainerNetworkAttachment::try_from("bridge").is_err());
+        assert!(ContainerNetworkAttachment::try_from("sriov:").is_err());
+        Ok(())
+    }
+}
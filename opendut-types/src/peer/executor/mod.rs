@@ -4,9 +4,11 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
-use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, ContainerVolume, ContainerDevice, ContainerEnvironmentVariable, ContainerPortSpec, ContainerCommand, ContainerCommandArgument, deserialize_container_environment_variable_vec};
+use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, ContainerVolume, ContainerDevice, ContainerEnvironmentVariable, ContainerPortSpec, ContainerCommand, ContainerCommandArgument, ContainerSecret, ContainerCanGateway, ContainerNetworkAttachment, deserialize_container_environment_variable_vec};
+use crate::peer::executor::sandbox::ExecutorSandbox;
 
 pub mod container;
+pub mod sandbox;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutorDescriptors {
@@ -20,6 +22,8 @@ pub struct ExecutorDescriptor {
     #[serde(flatten)]
     pub kind: ExecutorKind,
     pub results_url: Option<ResultsUrl>,
+    #[serde(default)]
+    pub sandbox: ExecutorSandbox,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -35,6 +39,11 @@ impl From<Uuid> for ExecutorId {
         Self { uuid }
     }
 }
+impl fmt::Display for ExecutorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uuid)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
@@ -57,6 +66,12 @@ pub enum ExecutorKind {
         command: ContainerCommand,
         #[serde(default)]
         args: Vec<ContainerCommandArgument>,
+        #[serde(default)]
+        secrets: Vec<ContainerSecret>,
+        #[serde(default)]
+        can_gateways: Vec<ContainerCanGateway>,
+        #[serde(default)]
+        network_attachment: ContainerNetworkAttachment,
     }
 }
 
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Restrictions, under which EDGAR runs an executor, so that untrusted test payloads cannot tamper with the peer.
+///
+/// By default, no restrictions apply and the executor runs with the privileges EDGAR or the container engine would use anyway.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ExecutorSandbox {
+    /// Unix user and optionally group, which the executor runs as.
+    #[serde(default)]
+    pub user: Option<ExecutorUser>,
+    #[serde(default)]
+    pub seccomp_profile: SeccompProfile,
+    /// Whether the root filesystem of the executor is mounted read-only.
+    /// The results directory, secrets, CAN gateways and explicitly configured volumes are not affected.
+    #[serde(default)]
+    pub read_only_filesystem: bool,
+}
+
+impl ExecutorSandbox {
+    pub fn is_unrestricted(&self) -> bool {
+        self == &ExecutorSandbox::default()
+    }
+}
+
+/// Maximum length of a Unix user or group name, as accepted by `useradd`.
+pub const UNIX_ACCOUNT_NAME_MAX_LENGTH: usize = 32;
+
+/// Unix user or group, given either by name or by numeric ID.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UnixAccount {
+    Name(String),
+    Id(u32),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IllegalUnixAccount {
+    #[error("Unix account name '{value}' is too long. Expected at most {max} characters, got {actual}.")]
+    InvalidLength { value: String, max: usize, actual: usize },
+    #[error("Unix account '{value}' is neither a numeric ID nor a valid name, which starts with a lowercase letter or an underscore, followed by lowercase letters, digits, underscores or dashes.")]
+    InvalidFormat { value: String },
+}
+
+impl TryFrom<&str> for UnixAccount {
+    type Error = IllegalUnixAccount;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Ok(id) = value.parse::<u32>() {
+            return Ok(UnixAccount::Id(id));
+        }
+        if value.len() > UNIX_ACCOUNT_NAME_MAX_LENGTH {
+            return Err(IllegalUnixAccount::InvalidLength { value: value.to_owned(), max: UNIX_ACCOUNT_NAME_MAX_LENGTH, actual: value.len() });
+        }
+
+        let mut chars = value.chars();
+        let starts_validly = chars.next()
+            .is_some_and(|first| first.is_ascii_lowercase() || first == '_');
+        let continues_validly = chars
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+
+        if starts_validly && continues_validly {
+            Ok(UnixAccount::Name(value.to_owned()))
+        } else {
+            Err(IllegalUnixAccount::InvalidFormat { value: value.to_owned() })
+        }
+    }
+}
+
+impl fmt::Display for UnixAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnixAccount::Name(name) => write!(f, "{name}"),
+            UnixAccount::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// Unix user and optionally group, which an executor runs as, in the form `<user>[:<group>]`, e.g. `tester`, `1000:1000` or `tester:dialout`.
+///
+/// Without a group, the primary group of the user is used.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ExecutorUser {
+    pub user: UnixAccount,
+    pub group: Option<UnixAccount>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IllegalExecutorUser {
+    #[error("Executor user '{value}' is not of the form '<user>[:<group>]'.")]
+    InvalidFormat { value: String },
+    #[error("Executor user '{value}' is invalid: {cause}")]
+    InvalidAccount { value: String, cause: IllegalUnixAccount },
+}
+
+impl TryFrom<String> for ExecutorUser {
+    type Error = IllegalExecutorUser;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+        let (user, group) = match trimmed.split_once(':') {
+            Some((user, group)) => (user, Some(group)),
+            None => (trimmed, None),
+        };
+        if user.is_empty() || group.is_some_and(str::is_empty) {
+            return Err(IllegalExecutorUser::InvalidFormat { value });
+        }
+
+        let user = UnixAccount::try_from(user)
+            .map_err(|cause| IllegalExecutorUser::InvalidAccount { value: value.clone(), cause })?;
+        let group = group.map(UnixAccount::try_from).transpose()
+            .map_err(|cause| IllegalExecutorUser::InvalidAccount { value: value.clone(), cause })?;
+
+        Ok(ExecutorUser { user, group })
+    }
+}
+
+impl TryFrom<&str> for ExecutorUser {
+    type Error = IllegalExecutorUser;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutorUser::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutorUser {
+    type Err = IllegalExecutorUser;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutorUser::try_from(value)
+    }
+}
+
+impl From<ExecutorUser> for String {
+    fn from(value: ExecutorUser) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ExecutorUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.group {
+            Some(group) => write!(f, "{}:{group}", self.user),
+            None => write!(f, "{}", self.user),
+        }
+    }
+}
+
+/// Seccomp profile, which restricts the system calls of an executor:
+/// - `default`: The default profile of the container engine. Executables are not restricted.
+/// - `unconfined`: No restrictions, also not for containers.
+/// - `<path>`: Absolute path to a seccomp profile in the JSON format of the container engines, which is located on the peer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SeccompProfile {
+    #[default]
+    Default,
+    Unconfined,
+    File(PathBuf),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IllegalSeccompProfile {
+    #[error("Seccomp profile '{value}' is not one of 'default', 'unconfined' or an absolute path to a profile.")]
+    InvalidFormat { value: String },
+}
+
+impl TryFrom<String> for SeccompProfile {
+    type Error = IllegalSeccompProfile;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.trim() {
+            "" | "default" => Ok(SeccompProfile::Default),
+            "unconfined" => Ok(SeccompProfile::Unconfined),
+            path if path.starts_with('/') => Ok(SeccompProfile::File(PathBuf::from(path))),
+            _ => Err(IllegalSeccompProfile::InvalidFormat { value }),
+        }
+    }
+}
+
+impl TryFrom<&str> for SeccompProfile {
+    type Error = IllegalSeccompProfile;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SeccompProfile::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for SeccompProfile {
+    type Err = IllegalSeccompProfile;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SeccompProfile::try_from(value)
+    }
+}
+
+impl From<SeccompProfile> for String {
+    fn from(value: SeccompProfile) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for SeccompProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeccompProfile::Default => write!(f, "default"),
+            SeccompProfile::Unconfined => write!(f, "unconfined"),
+            SeccompProfile::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn An_ExecutorUser_should_be_parsed_from_its_string_form() -> Result<()> {
+        let user = ExecutorUser::try_from("tester")?;
+        assert_that!(user, eq(&ExecutorUser { user: UnixAccount::Name(String::from("tester")), group: None }));
+
+        let user = ExecutorUser::try_from("1000:dialout")?;
+        assert_that!(user, eq(&ExecutorUser { user: UnixAccount::Id(1000), group: Some(UnixAccount::Name(String::from("dialout"))) }));
+        assert_that!(user.to_string(), eq("1000:dialout"));
+
+        assert!(ExecutorUser::try_from("").is_err());
+        assert!(ExecutorUser::try_from("tester:").is_err());
+        assert!(ExecutorUser::try_from(":1000").is_err());
+        assert!(ExecutorUser::try_from("Tester").is_err());
+        assert!(ExecutorUser::try_from("tester;rm").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn A_SeccompProfile_should_be_parsed_from_its_string_form() -> Result<()> {
+        assert_that!(SeccompProfile::try_from("")?, eq(&SeccompProfile::Default));
+        assert_that!(SeccompProfile::try_from("unconfined")?, eq(&SeccompProfile::Unconfined));
+
+        let profile = SeccompProfile::try_from("/etc/opendut/seccomp/strict.json")?;
+        assert_that!(profile, eq(&SeccompProfile::File(PathBuf::from("/etc/opendut/seccomp/strict.json"))));
+        assert_that!(profile.to_string(), eq("/etc/opendut/seccomp/strict.json"));
+
+        assert!(SeccompProfile::try_from("strict.json").is_err());
+        Ok(())
+    }
+}
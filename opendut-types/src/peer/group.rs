@@ -0,0 +1,238 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerGroupId(pub Uuid);
+
+impl PeerGroupId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerGroupId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal PeerGroupId: {value}")]
+pub struct IllegalPeerGroupId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for PeerGroupId {
+    type Error = IllegalPeerGroupId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalPeerGroupId { value: String::from(value) })
+    }
+}
+
+impl TryFrom<String> for PeerGroupId {
+    type Error = IllegalPeerGroupId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        PeerGroupId::try_from(value.as_str())
+    }
+}
+
+impl fmt::Display for PeerGroupId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerGroupName(pub(crate) String);
+
+impl PeerGroupName {
+    pub const MIN_LENGTH: usize = 4;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalPeerGroupName {
+    #[error(
+        "Peer group name '{value}' is too short. Expected at least {expected} characters, got {actual}."
+    )]
+    TooShort {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error(
+        "Peer group name '{value}' is too long. Expected at most {expected} characters, got {actual}."
+    )]
+    TooLong {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Peer group name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("Peer group name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<PeerGroupName> for String {
+    fn from(value: PeerGroupName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for PeerGroupName {
+    type Error = IllegalPeerGroupName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalPeerGroupName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalPeerGroupName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        } else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalPeerGroupName::InvalidStartEndCharacter { value })
+        } else if value
+            .chars()
+            .any(|c| crate::util::valid_characters_in_name(&c).not())
+        {
+            Err(IllegalPeerGroupName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for PeerGroupName {
+    type Error = IllegalPeerGroupName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PeerGroupName::try_from(value.to_owned())
+    }
+}
+
+impl fmt::Display for PeerGroupName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PeerGroupLabel(pub(crate) String);
+
+impl PeerGroupLabel {
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalPeerGroupLabel {
+    #[error(
+        "Peer group label '{value}' is too long. Expected at most {expected} characters, got {actual}."
+    )]
+    TooLong {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl From<PeerGroupLabel> for String {
+    fn from(value: PeerGroupLabel) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for PeerGroupLabel {
+    type Error = IllegalPeerGroupLabel;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length > Self::MAX_LENGTH {
+            Err(IllegalPeerGroupLabel::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for PeerGroupLabel {
+    type Error = IllegalPeerGroupLabel;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PeerGroupLabel::try_from(value.to_owned())
+    }
+}
+
+impl fmt::Display for PeerGroupLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Named set of peers, which can be referenced by ClusterConfigurations instead of listing each peer's devices individually.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerGroup {
+    pub id: PeerGroupId,
+    pub name: PeerGroupName,
+    pub labels: HashSet<PeerGroupLabel>,
+    pub peers: HashSet<PeerId>,
+}
+
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn A_PeerGroupName_should_contain_valid_characters() -> Result<()> {
+        let name = PeerGroupName::try_from("rack-01").expect("Failed to create peer group name");
+        assert_that!(name.value(), eq("rack-01"));
+        Ok(())
+    }
+
+    #[test]
+    fn A_PeerGroupName_should_not_start_with_an_underscore() -> Result<()> {
+        assert_that!(PeerGroupName::try_from("_rack-01").is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn A_PeerGroupLabel_should_not_be_too_long() -> Result<()> {
+        let value = "a".repeat(PeerGroupLabel::MAX_LENGTH + 1);
+        assert_that!(PeerGroupLabel::try_from(value).is_err(), eq(true));
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::net::NetworkInterfaceName;
+
+/// Hardware capabilities of the host, on which a peer runs, as detected during the setup of EDGAR.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareCapabilities {
+    pub cpu_architecture: String,
+    pub cpu_cores: u32,
+    pub memory_bytes: u64,
+    /// Virtualization technology, which the host runs in, e.g. `kvm` or `docker`. `None` when running on bare metal.
+    pub virtualization: Option<String>,
+    pub can_controllers: Vec<CanControllerCapabilities>,
+    pub network_interfaces: Vec<NetworkInterfaceCapabilities>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CanControllerCapabilities {
+    pub interface: NetworkInterfaceName,
+    pub fd_supported: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkInterfaceCapabilities {
+    pub interface: NetworkInterfaceName,
+    /// Link speed in Mbit/s. `None`, if the speed is unknown, e.g. for virtual interfaces or when the link is down.
+    pub speed_mbits: Option<u32>,
+}
@@ -0,0 +1,239 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HierarchyNodeId(pub Uuid);
+
+impl HierarchyNodeId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for HierarchyNodeId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal HierarchyNodeId: {value}")]
+pub struct IllegalHierarchyNodeId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for HierarchyNodeId {
+    type Error = IllegalHierarchyNodeId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalHierarchyNodeId { value: String::from(value) })
+    }
+}
+
+impl TryFrom<String> for HierarchyNodeId {
+    type Error = IllegalHierarchyNodeId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        HierarchyNodeId::try_from(value.as_str())
+    }
+}
+
+impl fmt::Display for HierarchyNodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HierarchyNodeName(pub(crate) String);
+
+impl HierarchyNodeName {
+    pub const MIN_LENGTH: usize = 4;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalHierarchyNodeName {
+    #[error(
+        "Hierarchy node name '{value}' is too short. Expected at least {expected} characters, got {actual}."
+    )]
+    TooShort {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error(
+        "Hierarchy node name '{value}' is too long. Expected at most {expected} characters, got {actual}."
+    )]
+    TooLong {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Hierarchy node name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("Hierarchy node name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<HierarchyNodeName> for String {
+    fn from(value: HierarchyNodeName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for HierarchyNodeName {
+    type Error = IllegalHierarchyNodeName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalHierarchyNodeName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalHierarchyNodeName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        } else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalHierarchyNodeName::InvalidStartEndCharacter { value })
+        } else if value
+            .chars()
+            .any(|c| crate::util::valid_characters_in_name(&c).not())
+        {
+            Err(IllegalHierarchyNodeName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for HierarchyNodeName {
+    type Error = IllegalHierarchyNodeName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        HierarchyNodeName::try_from(value.to_owned())
+    }
+}
+
+impl fmt::Display for HierarchyNodeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Level of a node in the hierarchy, which places peers geographically: sites contain rooms, which contain racks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum HierarchyLevel {
+    Site,
+    Room,
+    Rack,
+}
+
+impl HierarchyLevel {
+    /// Level, which the parent of a node on this level must have. `None` for top-level nodes.
+    pub fn parent_level(&self) -> Option<HierarchyLevel> {
+        match self {
+            HierarchyLevel::Site => None,
+            HierarchyLevel::Room => Some(HierarchyLevel::Site),
+            HierarchyLevel::Rack => Some(HierarchyLevel::Room),
+        }
+    }
+}
+
+impl fmt::Display for HierarchyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            HierarchyLevel::Site => "site",
+            HierarchyLevel::Room => "room",
+            HierarchyLevel::Rack => "rack",
+        };
+        write!(f, "{level}")
+    }
+}
+
+/// Site, room or rack, in which peers are located. Each peer is located in at most one node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HierarchyNode {
+    pub id: HierarchyNodeId,
+    pub name: HierarchyNodeName,
+    pub level: HierarchyLevel,
+    pub parent: Option<HierarchyNodeId>,
+    pub peers: HashSet<PeerId>,
+}
+
+/// Returns the node itself and its ancestors, starting with the node and ending with its top-level node.
+pub fn ancestors(nodes: &[HierarchyNode], node_id: HierarchyNodeId) -> Vec<&HierarchyNode> {
+    let mut ancestors: Vec<&HierarchyNode> = Vec::new();
+    let mut next = Some(node_id);
+
+    while let Some(node_id) = next {
+        let Some(node) = nodes.iter().find(|node| node.id == node_id) else { break };
+        if ancestors.iter().any(|ancestor| ancestor.id == node.id) { break } //guard against cycles
+        ancestors.push(node);
+        next = node.parent;
+    }
+    ancestors
+}
+
+/// Returns the site, in which the peer is located, if it is placed in the hierarchy.
+pub fn site_of_peer(nodes: &[HierarchyNode], peer_id: PeerId) -> Option<&HierarchyNode> {
+    let node = nodes.iter().find(|node| node.peers.contains(&peer_id))?;
+    ancestors(nodes, node.id).into_iter()
+        .find(|node| node.level == HierarchyLevel::Site)
+}
+
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn node(name: &str, level: HierarchyLevel, parent: Option<HierarchyNodeId>, peers: Vec<PeerId>) -> Result<HierarchyNode> {
+        Ok(HierarchyNode {
+            id: HierarchyNodeId::random(),
+            name: HierarchyNodeName::try_from(name).expect("Failed to create hierarchy node name"),
+            level,
+            parent,
+            peers: HashSet::from_iter(peers),
+        })
+    }
+
+    #[test]
+    fn A_HierarchyNodeName_should_not_start_with_an_underscore() -> Result<()> {
+        assert_that!(HierarchyNodeName::try_from("_munich").is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_determine_the_site_of_a_peer_in_a_rack() -> Result<()> {
+        let peer = PeerId::random();
+        let site = node("munich", HierarchyLevel::Site, None, vec![])?;
+        let room = node("lab-1", HierarchyLevel::Room, Some(site.id), vec![])?;
+        let rack = node("rack-01", HierarchyLevel::Rack, Some(room.id), vec![peer])?;
+        let nodes = vec![site.clone(), room, rack];
+
+        assert_that!(site_of_peer(&nodes, peer), some(eq(&site)));
+        assert_that!(site_of_peer(&nodes, PeerId::random()), none());
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Script, which EDGAR runs on the peer right before joining or right after leaving the network of a cluster,
+/// to integrate site-specific equipment, e.g. to switch external relays or to notify a bench controller.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ClusterHook {
+    pub name: ClusterHookName,
+    pub stage: ClusterHookStage,
+    /// Content of the script, which is run with `/bin/sh`.
+    pub script: String,
+    /// Duration after which the script is terminated and considered failed.
+    pub timeout: Duration,
+    pub failure_policy: ClusterHookFailurePolicy,
+}
+
+impl ClusterHook {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ClusterHookName(String);
+
+impl ClusterHookName {
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalClusterHookName {
+    #[error("Name of the cluster hook must not be empty.")]
+    Empty,
+    #[error("Name of the cluster hook '{value}' is too long. Expected at most {max} characters.", max = ClusterHookName::MAX_LENGTH)]
+    TooLong { value: String },
+    #[error("Name of the cluster hook '{value}' contains invalid characters. Only alphanumeric characters, '-' and '_' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ClusterHookName {
+    type Error = IllegalClusterHookName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalClusterHookName::Empty)
+        } else if value.chars().count() > Self::MAX_LENGTH {
+            Err(IllegalClusterHookName::TooLong { value })
+        } else if value.chars().any(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')) {
+            Err(IllegalClusterHookName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ClusterHookName {
+    type Error = IllegalClusterHookName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ClusterHookName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ClusterHookName {
+    type Err = IllegalClusterHookName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ClusterHookName::try_from(value)
+    }
+}
+
+impl From<ClusterHookName> for String {
+    fn from(value: ClusterHookName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ClusterHookName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// When a [`ClusterHook`] is run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClusterHookStage {
+    /// Before the interfaces of the peer are connected to the network of a newly assigned cluster.
+    BeforeJoin,
+    /// After the interfaces of the peer were disconnected from the network of a cluster, which the peer is no longer assigned to.
+    AfterLeave,
+}
+
+impl fmt::Display for ClusterHookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ClusterHookStage::BeforeJoin => "before-join",
+            ClusterHookStage::AfterLeave => "after-leave",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// How EDGAR proceeds, when a [`ClusterHook`] exits with a non-zero status or exceeds its timeout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClusterHookFailurePolicy {
+    /// Skip the remaining hooks of the stage. Before joining, the peer then does not join the cluster and does not start its executors.
+    #[default]
+    Abort,
+    /// Log the failure and continue as if the hook succeeded.
+    Ignore,
+}
+
+impl fmt::Display for ClusterHookFailurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ClusterHookFailurePolicy::Abort => "abort",
+            ClusterHookFailurePolicy::Ignore => "ignore",
+        };
+        write!(f, "{value}")
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Key-value pairs attached to a peer, which can be used to select peers.
+pub type PeerLabels = BTreeMap<LabelKey, LabelValue>;
+
+/// Key-value pairs attached to a peer, which carry free-form information and cannot be used to select peers.
+pub type PeerAnnotations = BTreeMap<LabelKey, AnnotationValue>;
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LabelKey(String);
+
+impl LabelKey {
+    pub const MAX_LENGTH: usize = 63;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalLabelKey {
+    #[error("Label key must not be empty.")]
+    Empty,
+    #[error("Label key '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Label key '{value}' contains invalid characters. Only alphanumeric characters, '-', '_', '.' and '/' are allowed and it must start with an alphanumeric character.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for LabelKey {
+    type Error = IllegalLabelKey;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if value.is_empty() {
+            Err(IllegalLabelKey::Empty)
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalLabelKey::TooLong { value, expected: Self::MAX_LENGTH, actual: length })
+        } else if !value.starts_with(|c: char| c.is_ascii_alphanumeric())
+            || value.chars().any(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))) {
+            Err(IllegalLabelKey::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for LabelKey {
+    type Error = IllegalLabelKey;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LabelKey::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for LabelKey {
+    type Err = IllegalLabelKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        LabelKey::try_from(value)
+    }
+}
+
+impl From<LabelKey> for String {
+    fn from(value: LabelKey) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for LabelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LabelValue(String);
+
+impl LabelValue {
+    pub const MAX_LENGTH: usize = 63;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalLabelValue {
+    #[error("Label value '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Label value '{value}' contains invalid characters. Only alphanumeric characters, '-', '_' and '.' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for LabelValue {
+    type Error = IllegalLabelValue;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length > Self::MAX_LENGTH {
+            Err(IllegalLabelValue::TooLong { value, expected: Self::MAX_LENGTH, actual: length })
+        } else if value.chars().any(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))) {
+            Err(IllegalLabelValue::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for LabelValue {
+    type Error = IllegalLabelValue;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LabelValue::try_from(value.to_owned())
+    }
+}
+
+impl From<LabelValue> for String {
+    fn from(value: LabelValue) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for LabelValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AnnotationValue(String);
+
+impl AnnotationValue {
+    pub const MAX_LENGTH: usize = 1024;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalAnnotationValue {
+    #[error("Annotation value is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { expected: usize, actual: usize },
+}
+
+impl TryFrom<String> for AnnotationValue {
+    type Error = IllegalAnnotationValue;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length > Self::MAX_LENGTH {
+            Err(IllegalAnnotationValue::TooLong { expected: Self::MAX_LENGTH, actual: length })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for AnnotationValue {
+    type Error = IllegalAnnotationValue;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        AnnotationValue::try_from(value.to_owned())
+    }
+}
+
+impl From<AnnotationValue> for String {
+    fn from(value: AnnotationValue) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for AnnotationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Condition on the labels of a resource. All requirements need to be fulfilled for a resource to be selected.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LabelSelector {
+    pub requirements: Vec<LabelRequirement>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LabelRequirement {
+    Equals { key: LabelKey, value: LabelValue },
+    NotEquals { key: LabelKey, value: LabelValue },
+    Exists { key: LabelKey },
+    NotExists { key: LabelKey },
+}
+
+impl LabelSelector {
+    pub fn matches(&self, labels: &PeerLabels) -> bool {
+        self.requirements.iter().all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl LabelRequirement {
+    pub fn matches(&self, labels: &PeerLabels) -> bool {
+        match self {
+            LabelRequirement::Equals { key, value } => labels.get(key) == Some(value),
+            LabelRequirement::NotEquals { key, value } => labels.get(key) != Some(value),
+            LabelRequirement::Exists { key } => labels.contains_key(key),
+            LabelRequirement::NotExists { key } => !labels.contains_key(key),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalLabelSelector {
+    #[error("Label selector requirement '{requirement}' is invalid: {cause}")]
+    InvalidKey { requirement: String, cause: IllegalLabelKey },
+    #[error("Label selector requirement '{requirement}' is invalid: {cause}")]
+    InvalidValue { requirement: String, cause: IllegalLabelValue },
+}
+
+/// Parses a comma-separated list of requirements, e.g. `location=hall3,rack!=7,gpu,!decommissioned`.
+impl FromStr for LabelSelector {
+    type Err = IllegalLabelSelector;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let requirements = value.split(',')
+            .map(str::trim)
+            .filter(|requirement| !requirement.is_empty())
+            .map(LabelRequirement::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LabelSelector { requirements })
+    }
+}
+
+impl FromStr for LabelRequirement {
+    type Err = IllegalLabelSelector;
+
+    fn from_str(requirement: &str) -> Result<Self, Self::Err> {
+        let parse_key = |key: &str| LabelKey::try_from(key.trim())
+            .map_err(|cause| IllegalLabelSelector::InvalidKey { requirement: requirement.to_owned(), cause });
+        let parse_value = |value: &str| LabelValue::try_from(value.trim())
+            .map_err(|cause| IllegalLabelSelector::InvalidValue { requirement: requirement.to_owned(), cause });
+
+        if let Some((key, value)) = requirement.split_once("!=") {
+            Ok(LabelRequirement::NotEquals { key: parse_key(key)?, value: parse_value(value)? })
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            Ok(LabelRequirement::Equals { key: parse_key(key)?, value: parse_value(value)? })
+        } else if let Some(key) = requirement.strip_prefix('!') {
+            Ok(LabelRequirement::NotExists { key: parse_key(key)? })
+        } else {
+            Ok(LabelRequirement::Exists { key: parse_key(requirement)? })
+        }
+    }
+}
+
+impl fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requirements = self.requirements.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", requirements.join(","))
+    }
+}
+
+impl fmt::Display for LabelRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelRequirement::Equals { key, value } => write!(f, "{key}={value}"),
+            LabelRequirement::NotEquals { key, value } => write!(f, "{key}!={value}"),
+            LabelRequirement::Exists { key } => write!(f, "{key}"),
+            LabelRequirement::NotExists { key } => write!(f, "!{key}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LabelChange {
+    Set { key: LabelKey, value: LabelValue },
+    Remove { key: LabelKey },
+}
+
+impl LabelChange {
+    pub fn apply(&self, labels: &mut PeerLabels) {
+        match self {
+            LabelChange::Set { key, value } => { labels.insert(Clone::clone(key), Clone::clone(value)); }
+            LabelChange::Remove { key } => { labels.remove(key); }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationChange {
+    Set { key: LabelKey, value: AnnotationValue },
+    Remove { key: LabelKey },
+}
+
+impl AnnotationChange {
+    pub fn apply(&self, annotations: &mut PeerAnnotations) {
+        match self {
+            AnnotationChange::Set { key, value } => { annotations.insert(Clone::clone(key), Clone::clone(value)); }
+            AnnotationChange::Remove { key } => { annotations.remove(key); }
+        }
+    }
+}
+
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn A_LabelKey_should_start_with_an_alphanumeric_character() -> Result<()> {
+        assert_that!(LabelKey::try_from("opendut.eclipse.org/rack").is_ok(), eq(true));
+        assert_that!(LabelKey::try_from("-rack").is_err(), eq(true));
+        assert_that!(LabelKey::try_from("").is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn A_LabelValue_may_be_empty_but_must_not_contain_whitespace() -> Result<()> {
+        assert_that!(LabelValue::try_from("").is_ok(), eq(true));
+        assert_that!(LabelValue::try_from("hall 3").is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn A_LabelSelector_should_match_only_labels_fulfilling_all_requirements() -> Result<()> {
+        let selector = LabelSelector::from_str("location=hall3, rack!=7, gpu, !decommissioned")?;
+        assert_that!(selector.requirements.len(), eq(4));
+
+        let mut labels = PeerLabels::from([
+            (LabelKey::try_from("location")?, LabelValue::try_from("hall3")?),
+            (LabelKey::try_from("rack")?, LabelValue::try_from("8")?),
+            (LabelKey::try_from("gpu")?, LabelValue::try_from("")?),
+        ]);
+        assert_that!(selector.matches(&labels), eq(true));
+
+        LabelChange::Set { key: LabelKey::try_from("rack")?, value: LabelValue::try_from("7")? }.apply(&mut labels);
+        assert_that!(selector.matches(&labels), eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn An_empty_LabelSelector_should_match_everything() -> Result<()> {
+        let selector = LabelSelector::from_str("")?;
+        assert_that!(selector.matches(&PeerLabels::new()), eq(true));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::ops::Not;
+use std::time::SystemTime;
 
 use base64::Engine;
 use base64::prelude::BASE64_URL_SAFE;
@@ -8,6 +9,9 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::peer::executor::ExecutorDescriptors;
+use crate::peer::hardware::HardwareCapabilities;
+use crate::peer::hook::ClusterHook;
+use crate::peer::label::{PeerAnnotations, PeerLabels};
 use crate::topology::{DeviceDescriptor, Topology};
 use crate::util::net::{AuthConfig, Certificate, NetworkInterfaceDescriptor, NetworkInterfaceName};
 use crate::vpn::VpnPeerConfiguration;
@@ -16,6 +20,15 @@ pub mod state;
 pub mod executor;
 pub mod configuration;
 pub mod ethernet;
+pub mod group;
+pub mod hierarchy;
+pub mod label;
+pub mod hardware;
+pub mod hook;
+pub mod restbus;
+pub mod provisioning;
+pub mod self_test;
+pub mod setup;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -249,6 +262,16 @@ pub struct PeerDescriptor {
     pub network: PeerNetworkDescriptor,
     pub topology: Topology,
     pub executors: ExecutorDescriptors,
+    #[serde(default)]
+    pub labels: PeerLabels,
+    #[serde(default)]
+    pub annotations: PeerAnnotations,
+    /// Hardware capabilities reported by EDGAR during setup. `None`, if the peer was not set up yet.
+    #[serde(default)]
+    pub hardware: Option<HardwareCapabilities>,
+    /// Scripts, which EDGAR runs right before joining and right after leaving the network of a cluster.
+    #[serde(default)]
+    pub cluster_hooks: Vec<ClusterHook>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -258,9 +281,17 @@ pub struct PeerSetup {
     pub ca: Certificate,
     pub auth_config: AuthConfig,
     pub vpn: VpnPeerConfiguration,
+    /// Point in time, after which CARL rejects the setup, unless the peer connected with it before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<SystemTime>,
 }
 
 impl PeerSetup {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| SystemTime::now() >= expires_at)
+    }
+
     pub fn encode(&self) -> Result<String, PeerSetupEncodeError> {
         let json = serde_json::to_string(self).map_err(|cause| PeerSetupEncodeError {
             details: format!("Serialization failed due to: {}", cause),
@@ -344,6 +375,7 @@ mod tests {
                 management_url: Url::parse("https://netbird.opendut.local/api")?,
                 setup_key: SetupKey::from(Uuid::parse_str("d79c202f-bbbf-4997-844e-678f27606e1c")?),
             },
+            expires_at: None,
         };
 
         let encoded = setup.encode()?;
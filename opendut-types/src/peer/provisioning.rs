@@ -0,0 +1,166 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use crate::peer::label::PeerLabels;
+use crate::peer::{PeerId, PeerLocation};
+use crate::util::net::Certificate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProvisioningTokenId(pub Uuid);
+
+impl ProvisioningTokenId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ProvisioningTokenId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ProvisioningTokenId: {value}")]
+pub struct IllegalProvisioningTokenId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ProvisioningTokenId {
+    type Error = IllegalProvisioningTokenId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalProvisioningTokenId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ProvisioningTokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Defaults for the PeerDescriptor, which CARL creates when a device claims a provisioning token on first contact.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerTemplate {
+    pub location: Option<PeerLocation>,
+    #[serde(default)]
+    pub labels: PeerLabels,
+}
+
+/// Single-use token from a pool, which is flashed onto a device during manufacturing,
+/// so that the device can register itself as a peer without anyone interacting with CARL beforehand.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProvisioningToken {
+    pub id: ProvisioningTokenId,
+    /// Name of the pool, e.g. the production batch, which the token was generated for.
+    pub pool: String,
+    pub template: PeerTemplate,
+    /// Hash of the secret part of the token. The secret itself is only handed out once, when generating the token.
+    pub secret_hash: String,
+    /// User, on whose behalf the credentials of the claiming peer are registered.
+    pub created_by: String,
+    pub created_at: SystemTime,
+    /// Peer, which was created when the token was claimed. `None`, while the token is unused.
+    pub claimed_by: Option<PeerId>,
+}
+
+impl ProvisioningToken {
+    pub fn is_claimed(&self) -> bool {
+        self.claimed_by.is_some()
+    }
+}
+
+/// Contents of a provisioning string, which a device presents to CARL on first contact, to receive its PeerSetup.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerProvisioning {
+    pub token: ProvisioningTokenId,
+    pub secret: String,
+    pub carl: Url,
+    pub ca: Certificate,
+}
+
+impl PeerProvisioning {
+    pub fn encode(&self) -> Result<String, PeerProvisioningEncodeError> {
+        let json = serde_json::to_string(self).map_err(|cause| PeerProvisioningEncodeError {
+            details: format!("Serialization failed due to: {}", cause),
+        })?;
+
+        let compressed = {
+            let mut buffer = Vec::new();
+            crate::util::brotli::compress(&mut buffer, json.as_bytes())
+                .map_err(|cause| PeerProvisioningEncodeError {
+                    details: format!("Compression failed due to: {}", cause),
+                })?;
+            buffer
+        };
+
+        Ok(BASE64_URL_SAFE.encode(compressed))
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, PeerProvisioningDecodeError> {
+        let compressed = BASE64_URL_SAFE
+            .decode(encoded.trim().as_bytes())
+            .map_err(|cause| PeerProvisioningDecodeError {
+                details: format!("Base64 decoding failed due to: {}", cause),
+            })?;
+
+        let json = {
+            let mut buffer = Vec::new();
+            crate::util::brotli::decompress(&mut buffer, compressed.as_slice())
+                .map_err(|cause| PeerProvisioningDecodeError {
+                    details: format!("Decompression failed due to: {}", cause),
+                })?;
+            buffer
+        };
+
+        serde_json::from_slice(&json).map_err(|cause| PeerProvisioningDecodeError {
+            details: format!("Deserialization failed due to: {}", cause),
+        })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Provisioning string could not be encoded. {details}")]
+pub struct PeerProvisioningEncodeError {
+    details: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Provisioning string could not be decoded. {details}")]
+pub struct PeerProvisioningDecodeError {
+    details: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use pem::Pem;
+
+    use super::*;
+
+    #[test]
+    fn A_PeerProvisioning_should_be_encodable_and_decodable() -> Result<()> {
+        let provisioning = PeerProvisioning {
+            token: ProvisioningTokenId::random(),
+            secret: String::from("5d1b0f7e6c2a4e9b8f3d2c1a0b9e8d7c"),
+            carl: Url::parse("https://carl.opendut.local")?,
+            ca: Certificate(Pem::new("Test Tag".to_string(), vec![])),
+        };
+
+        let encoded = provisioning.encode()?;
+        let decoded = PeerProvisioning::decode(&encoded)?;
+
+        assert_that!(decoded, eq(&provisioning));
+        Ok(())
+    }
+}
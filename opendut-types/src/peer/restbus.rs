@@ -0,0 +1,94 @@
+use std::ops::Not;
+
+use crate::topology::{DeviceDescriptor, DeviceTag};
+use crate::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+/// Binds a bus (CanCluster) of the restbus simulation to the SocketCAN interface, which its frames are sent on.
+/// Each channel of a multi-channel CAN adapter is a separate interface, so several buses can be bound to the same adapter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RestbusChannel {
+    /// Name of the CanCluster in the communication database.
+    pub bus: String,
+    pub interface: NetworkInterfaceName,
+}
+
+impl RestbusChannel {
+    /// Devices tagged with this prefix followed by the name of a CanCluster, e.g. `restbus:CAN_Powertrain`,
+    /// bind that CanCluster to the network interface of the device.
+    pub const DEVICE_TAG_PREFIX: &'static str = "restbus:";
+
+    pub fn device_tag(bus: &str) -> Option<DeviceTag> {
+        DeviceTag::try_from(format!("{}{bus}", Self::DEVICE_TAG_PREFIX)).ok()
+    }
+
+    /// Collects the channels from the tags of the devices. Devices of the same bus on the same interface result in one channel.
+    pub fn from_devices(devices: &[DeviceDescriptor], interfaces: &[NetworkInterfaceDescriptor]) -> Vec<RestbusChannel> {
+        let mut channels = Vec::<RestbusChannel>::new();
+
+        for device in devices {
+            let Some(interface) = interfaces.iter().find(|interface| interface.id == device.interface) else {
+                continue;
+            };
+            for tag in &device.tags {
+                let Some(bus) = tag.value().strip_prefix(Self::DEVICE_TAG_PREFIX) else {
+                    continue;
+                };
+                let channel = RestbusChannel {
+                    bus: bus.to_owned(),
+                    interface: Clone::clone(&interface.name),
+                };
+                if channels.contains(&channel).not() {
+                    channels.push(channel);
+                }
+            }
+        }
+        channels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+    use crate::topology::{DeviceId, DeviceName};
+    use crate::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceId};
+
+    #[test]
+    fn should_collect_restbus_channels_from_device_tags() -> Result<()> {
+        let interface = |name: &str| -> Result<NetworkInterfaceDescriptor> {
+            Ok(NetworkInterfaceDescriptor {
+                id: NetworkInterfaceId::random(),
+                name: NetworkInterfaceName::try_from(name)?,
+                configuration: NetworkInterfaceConfiguration::Ethernet,
+            })
+        };
+        let device = |name: &str, interface: &NetworkInterfaceDescriptor, tags: &[&str]| -> Result<DeviceDescriptor> {
+            Ok(DeviceDescriptor {
+                id: DeviceId::random(),
+                name: DeviceName::try_from(name)?,
+                description: None,
+                interface: interface.id,
+                tags: tags.iter().map(|tag| DeviceTag::try_from(*tag)).collect::<Result<_, _>>()?,
+            })
+        };
+        let can0 = interface("can0")?;
+        let can1 = interface("can1")?;
+
+        let channels = RestbusChannel::from_devices(
+            &[
+                device("ecu-a", &can0, &["restbus:CAN_Powertrain"])?,
+                device("ecu-b", &can0, &["restbus:CAN_Powertrain", "other"])?,
+                device("ecu-c", &can1, &["restbus:CAN_Chassis"])?,
+                device("ecu-d", &can1, &[])?,
+            ],
+            &[can0, can1],
+        );
+
+        assert_eq!(channels, vec![
+            RestbusChannel { bus: String::from("CAN_Powertrain"), interface: NetworkInterfaceName::try_from("can0")? },
+            RestbusChannel { bus: String::from("CAN_Chassis"), interface: NetworkInterfaceName::try_from("can1")? },
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::peer::configuration::validation::CheckOutcome;
+use crate::peer::PeerId;
+
+/// Outcome of the self-test of a peer, which verifies the chain from the peer to CARL, the VPN, its devices and the container engine, e.g. after its setup.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SelfTestReport {
+    pub peer_id: PeerId,
+    pub checked_at: SystemTime,
+    /// Whether none of the checks failed.
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+impl SelfTestReport {
+    pub fn new(peer_id: PeerId, checked_at: SystemTime, checks: Vec<SelfTestCheck>) -> Self {
+        let passed = checks.iter()
+            .all(|check| !matches!(check.outcome, CheckOutcome::Failed { .. }));
+
+        Self { peer_id, checked_at, passed, checks }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SelfTestCheck {
+    /// Stage of the chain, which is checked, e.g. "carl", "vpn", "devices", "container-engine" or "executor".
+    pub stage: String,
+    /// What is checked, e.g. "interface 'can0' is up".
+    pub subject: String,
+    #[serde(flatten)]
+    pub outcome: CheckOutcome,
+}
@@ -0,0 +1,61 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::peer::PeerId;
+
+/// Record of the setup string, which was generated last for a peer.
+/// It allows CARL to reject a setup string, which was not used before it expired, or which was revoked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerSetupIssuance {
+    pub peer_id: PeerId,
+    pub issued_by: String,
+    pub issued_at: SystemTime,
+    /// `None`, if the setup string does not expire.
+    pub expires_at: Option<SystemTime>,
+    /// When the peer first connected with the setup string. A setup string, which was used, does not expire anymore.
+    pub used_at: Option<SystemTime>,
+    pub revoked_at: Option<SystemTime>,
+}
+
+impl PeerSetupIssuance {
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.used_at.is_none()
+            && self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_only_expire_when_unused() {
+        let issued_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_731_000_000);
+        let testee = PeerSetupIssuance {
+            peer_id: PeerId::try_from("01bf3f8c-cc7c-4114-9520-91bce71dcead").unwrap(),
+            issued_by: String::from("alice"),
+            issued_at,
+            expires_at: Some(issued_at + Duration::from_secs(3600)),
+            used_at: None,
+            revoked_at: None,
+        };
+
+        assert_that!(testee.is_expired(issued_at + Duration::from_secs(60)), eq(false));
+        assert_that!(testee.is_expired(issued_at + Duration::from_secs(3600)), eq(true));
+
+        let used = PeerSetupIssuance { used_at: Some(issued_at + Duration::from_secs(60)), ..testee.clone() };
+        assert_that!(used.is_expired(issued_at + Duration::from_secs(7200)), eq(false));
+
+        let unlimited = PeerSetupIssuance { expires_at: None, ..testee };
+        assert_that!(unlimited.is_expired(issued_at + Duration::from_secs(7200)), eq(false));
+    }
+}
@@ -9,6 +9,12 @@ pub enum PeerState {
         inner: PeerUpState,
         remote_host: IpAddr,
     },
+    /// The peer is still connected, but did not send any message within the heartbeat timeout.
+    /// Contains the state it had while up, which is restored once it is heard from again.
+    Unreachable {
+        inner: PeerUpState,
+        remote_host: IpAddr,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -39,6 +45,7 @@ impl ShortName for PeerState {
                 PeerUpState::Blocked(PeerBlockedState::Member) => "Member",
                 PeerUpState::Blocked(PeerBlockedState::Undeploying) => "Undeploying",
             }
+            PeerState::Unreachable { .. } => "Unreachable",
             PeerState::Down => "Down",
         }
     }
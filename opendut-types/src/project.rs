@@ -0,0 +1,244 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cluster::ClusterId;
+use crate::peer::PeerId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProjectId(pub Uuid);
+
+impl ProjectId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ProjectId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ProjectId: {value}")]
+pub struct IllegalProjectId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ProjectId {
+    type Error = IllegalProjectId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalProjectId { value: String::from(value) })
+    }
+}
+
+impl FromStr for ProjectId {
+    type Err = IllegalProjectId;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ProjectId::try_from(value)
+    }
+}
+
+impl fmt::Display for ProjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProjectName(String);
+
+impl ProjectName {
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalProjectName {
+    #[error("Name of the project must not be empty.")]
+    Empty,
+    #[error("Name of the project '{value}' is too long. Expected at most {max} characters.", max = ProjectName::MAX_LENGTH)]
+    TooLong { value: String },
+    #[error("Name of the project '{value}' contains invalid characters. Only alphanumeric characters, '-' and '_' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ProjectName {
+    type Error = IllegalProjectName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalProjectName::Empty)
+        } else if value.chars().count() > Self::MAX_LENGTH {
+            Err(IllegalProjectName::TooLong { value })
+        } else if value.chars().any(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')) {
+            Err(IllegalProjectName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ProjectName {
+    type Error = IllegalProjectName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ProjectName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ProjectName {
+    type Err = IllegalProjectName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ProjectName::try_from(value)
+    }
+}
+
+impl From<ProjectName> for String {
+    fn from(value: ProjectName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ProjectName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Member of a project, either a single user or all users of a group, as named in the claims of the identity provider.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ProjectMember {
+    User { name: String },
+    Group { name: String },
+}
+
+impl ProjectMember {
+    const USER_PREFIX: &'static str = "user:";
+    const GROUP_PREFIX: &'static str = "group:";
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal project member '{value}'. Expected 'user:<name>' or 'group:<name>'.")]
+pub struct IllegalProjectMember {
+    pub value: String,
+}
+
+impl TryFrom<String> for ProjectMember {
+    type Error = IllegalProjectMember;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let member = if let Some(name) = value.strip_prefix(Self::USER_PREFIX) {
+            ProjectMember::User { name: name.to_owned() }
+        } else if let Some(name) = value.strip_prefix(Self::GROUP_PREFIX) {
+            ProjectMember::Group { name: name.to_owned() }
+        } else {
+            return Err(IllegalProjectMember { value });
+        };
+
+        match &member {
+            ProjectMember::User { name } | ProjectMember::Group { name } if name.trim().is_empty() => Err(IllegalProjectMember { value }),
+            _ => Ok(member),
+        }
+    }
+}
+
+impl FromStr for ProjectMember {
+    type Err = IllegalProjectMember;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ProjectMember::try_from(value.to_owned())
+    }
+}
+
+impl From<ProjectMember> for String {
+    fn from(value: ProjectMember) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ProjectMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectMember::User { name } => write!(f, "{}{name}", Self::USER_PREFIX),
+            ProjectMember::Group { name } => write!(f, "{}{name}", Self::GROUP_PREFIX),
+        }
+    }
+}
+
+/// Groups peers and clusters, so that users only see and change the resources of the projects they are members of.
+/// Peers and clusters, which are not assigned to any project, remain accessible to all users.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    pub id: ProjectId,
+    pub name: ProjectName,
+    pub members: BTreeSet<ProjectMember>,
+    pub peers: HashSet<PeerId>,
+    pub clusters: HashSet<ClusterId>,
+}
+
+impl Project {
+    /// Whether the user is a member, either by their name or via one of their groups.
+    pub fn has_member<'a>(&self, user_name: &str, groups: impl IntoIterator<Item=&'a String>) -> bool {
+        let groups = groups.into_iter().collect::<Vec<_>>();
+        self.members.iter().any(|member| match member {
+            ProjectMember::User { name } => name == user_name,
+            ProjectMember::Group { name } => groups.iter().any(|group| *group == name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_members_from_their_string_representation() {
+        let user = ProjectMember::from_str("user:alice");
+        assert_that!(user, ok(eq(&ProjectMember::User { name: String::from("alice") })));
+
+        let group = ProjectMember::from_str("group:/lab/powertrain");
+        assert_that!(group, ok(eq(&ProjectMember::Group { name: String::from("/lab/powertrain") })));
+
+        assert_that!(ProjectMember::from_str("alice"), err(anything()));
+        assert_that!(ProjectMember::from_str("user:"), err(anything()));
+        assert_that!(ProjectMember::from_str("group:/lab").map(|member| member.to_string()), ok(eq("group:/lab")));
+    }
+
+    #[test]
+    fn should_check_membership_by_user_name_or_group() -> googletest::Result<()> {
+        let project = Project {
+            id: ProjectId::random(),
+            name: ProjectName::try_from("powertrain").or_fail()?,
+            members: [
+                ProjectMember::from_str("user:alice").or_fail()?,
+                ProjectMember::from_str("group:/lab/powertrain").or_fail()?,
+            ].into(),
+            peers: HashSet::new(),
+            clusters: HashSet::new(),
+        };
+
+        assert_that!(project.has_member("alice", &[]), eq(true));
+        assert_that!(project.has_member("bob", &[String::from("/lab/powertrain")]), eq(true));
+        assert_that!(project.has_member("bob", &[String::from("/lab/chassis")]), eq(false));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use crate::proto::{ConversionError, ConversionErrorBuilder};
-use crate::proto::topology::DeviceId;
+use crate::proto::peer::group::PeerGroupId;
+use crate::proto::peer::label::LabelSelector;
+use crate::proto::topology::{DeviceId, DeviceTag};
 
 include!(concat!(env!("OUT_DIR"), "/opendut.types.cluster.rs"));
 
@@ -59,6 +61,15 @@ impl From<crate::cluster::ClusterConfiguration> for ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::from)
                         .collect(),
+            peer_groups: configuration.peer_groups.into_iter()
+                        .map(PeerGroupId::from)
+                        .collect(),
+            device_selectors: configuration.device_selectors.into_iter()
+                        .map(DeviceSelector::from)
+                        .collect(),
+            dependencies: configuration.dependencies.into_iter()
+                        .map(ClusterId::from)
+                        .collect(),
         }
     }
 }
@@ -88,6 +99,64 @@ impl TryFrom<ClusterConfiguration> for crate::cluster::ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::try_into)
                         .collect::<Result<_, _>>()?,
+            peer_groups: configuration.peer_groups.into_iter()
+                        .map(PeerGroupId::try_into)
+                        .collect::<Result<_, _>>()?,
+            device_selectors: configuration.device_selectors.into_iter()
+                        .map(DeviceSelector::try_into)
+                        .collect::<Result<_, _>>()?,
+            dependencies: configuration.dependencies.into_iter()
+                        .map(ClusterId::try_into)
+                        .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<crate::cluster::DeviceSelector> for DeviceSelector {
+    fn from(selector: crate::cluster::DeviceSelector) -> Self {
+        Self {
+            tags: selector.tags.into_iter()
+                .map(DeviceTag::from)
+                .collect(),
+            interface_kind: selector.interface_kind.map(|kind| match kind {
+                crate::cluster::DeviceInterfaceKind::Ethernet => device_selector::InterfaceKind::Ethernet(DeviceInterfaceKindEthernet {}),
+                crate::cluster::DeviceInterfaceKind::Can => device_selector::InterfaceKind::Can(DeviceInterfaceKindCan {}),
+            }),
+            min_count: selector.count.min(),
+            max_count: selector.count.max(),
+            peer_labels: Some(LabelSelector::from(selector.peer_labels)),
+        }
+    }
+}
+
+impl TryFrom<DeviceSelector> for crate::cluster::DeviceSelector {
+    type Error = ConversionError;
+
+    fn try_from(selector: DeviceSelector) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<DeviceSelector, crate::cluster::DeviceSelector>;
+
+        let tags = selector.tags.into_iter()
+            .map(DeviceTag::try_into)
+            .collect::<Result<_, _>>()?;
+
+        let interface_kind = selector.interface_kind.map(|kind| match kind {
+            device_selector::InterfaceKind::Ethernet(_) => crate::cluster::DeviceInterfaceKind::Ethernet,
+            device_selector::InterfaceKind::Can(_) => crate::cluster::DeviceInterfaceKind::Can,
+        });
+
+        let peer_labels = selector.peer_labels
+            .map(LabelSelector::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
+        let count = crate::cluster::DeviceCount::new(selector.min_count, selector.max_count)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        Ok(Self {
+            tags,
+            interface_kind,
+            peer_labels,
+            count,
         })
     }
 }
@@ -116,6 +185,91 @@ impl TryFrom<ClusterDeployment> for crate::cluster::ClusterDeployment {
     }
 }
 
+impl From<crate::cluster::ClusterDeploymentStatus> for ClusterDeploymentStatus {
+    fn from(status: crate::cluster::ClusterDeploymentStatus) -> Self {
+        Self {
+            id: Some(status.id.into()),
+            peers: status.peers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ClusterDeploymentStatus> for crate::cluster::ClusterDeploymentStatus {
+    type Error = ConversionError;
+
+    fn try_from(status: ClusterDeploymentStatus) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterDeploymentStatus, crate::cluster::ClusterDeploymentStatus>;
+
+        let id: crate::cluster::ClusterId = status.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let peers = status.peers.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { id, peers })
+    }
+}
+
+impl From<crate::cluster::PeerDeploymentStatus> for PeerDeploymentStatus {
+    fn from(status: crate::cluster::PeerDeploymentStatus) -> Self {
+        Self {
+            peer_id: Some(status.peer_id.into()),
+            state: Some(status.state.into()),
+        }
+    }
+}
+
+impl TryFrom<PeerDeploymentStatus> for crate::cluster::PeerDeploymentStatus {
+    type Error = ConversionError;
+
+    fn try_from(status: PeerDeploymentStatus) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerDeploymentStatus, crate::cluster::PeerDeploymentStatus>;
+
+        let peer_id: crate::peer::PeerId = status.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+
+        let state: crate::cluster::DeploymentState = status.state
+            .ok_or(ErrorBuilder::field_not_set("state"))?
+            .try_into()?;
+
+        Ok(Self { peer_id, state })
+    }
+}
+
+impl From<crate::cluster::DeploymentState> for DeploymentState {
+    fn from(state: crate::cluster::DeploymentState) -> Self {
+        let inner = match state {
+            crate::cluster::DeploymentState::Pending => deployment_state::Inner::Pending(DeploymentStatePending {}),
+            crate::cluster::DeploymentState::Configuring => deployment_state::Inner::Configuring(DeploymentStateConfiguring {}),
+            crate::cluster::DeploymentState::Ready => deployment_state::Inner::Ready(DeploymentStateReady {}),
+            crate::cluster::DeploymentState::Failed { cause } => deployment_state::Inner::Failed(DeploymentStateFailed { cause }),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<DeploymentState> for crate::cluster::DeploymentState {
+    type Error = ConversionError;
+
+    fn try_from(state: DeploymentState) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<DeploymentState, crate::cluster::DeploymentState>;
+
+        let inner = state.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let state = match inner {
+            deployment_state::Inner::Pending(_) => crate::cluster::DeploymentState::Pending,
+            deployment_state::Inner::Configuring(_) => crate::cluster::DeploymentState::Configuring,
+            deployment_state::Inner::Ready(_) => crate::cluster::DeploymentState::Ready,
+            deployment_state::Inner::Failed(DeploymentStateFailed { cause }) => crate::cluster::DeploymentState::Failed { cause },
+        };
+        Ok(state)
+    }
+}
+
 impl From<crate::cluster::state::ClusterState> for ClusterState {
     fn from(state: crate::cluster::state::ClusterState) -> Self {
         match state {
@@ -29,6 +29,8 @@ impl From<crate::peer::configuration::PeerConfiguration> for PeerConfiguration {
         Self {
             executors: value.executors.into_iter().map(From::from).collect(),
             ethernet_bridges: value.ethernet_bridges.into_iter().map(From::from).collect(),
+            restbus_channels: value.restbus_channels.into_iter().map(From::from).collect(),
+            cluster_hooks: value.cluster_hooks.into_iter().map(From::from).collect(),
         }
     }
 }
@@ -39,6 +41,53 @@ impl TryFrom<PeerConfiguration> for crate::peer::configuration::PeerConfiguratio
         Ok(crate::peer::configuration::PeerConfiguration {
             executors: value.executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
             ethernet_bridges: value.ethernet_bridges.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            restbus_channels: value.restbus_channels.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            cluster_hooks: value.cluster_hooks.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<crate::peer::configuration::delta::PeerConfigurationDelta> for PeerConfigurationDelta {
+    fn from(value: crate::peer::configuration::delta::PeerConfigurationDelta) -> Self {
+        Self {
+            base_version: value.base_version,
+            version: value.version,
+            upserted_executors: value.executors.upserted.into_iter().map(From::from).collect(),
+            removed_executors: value.executors.removed.into_iter().map(From::from).collect(),
+            upserted_ethernet_bridges: value.ethernet_bridges.upserted.into_iter().map(From::from).collect(),
+            removed_ethernet_bridges: value.ethernet_bridges.removed.into_iter().map(From::from).collect(),
+            upserted_restbus_channels: value.restbus_channels.upserted.into_iter().map(From::from).collect(),
+            removed_restbus_channels: value.restbus_channels.removed.into_iter().map(From::from).collect(),
+            upserted_cluster_hooks: value.cluster_hooks.upserted.into_iter().map(From::from).collect(),
+            removed_cluster_hooks: value.cluster_hooks.removed.into_iter().map(From::from).collect(),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationDelta> for crate::peer::configuration::delta::PeerConfigurationDelta {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationDelta) -> Result<Self, Self::Error> {
+        use crate::peer::configuration::delta::ParameterDelta;
+
+        Ok(crate::peer::configuration::delta::PeerConfigurationDelta {
+            base_version: value.base_version,
+            version: value.version,
+            executors: ParameterDelta {
+                upserted: value.upserted_executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                removed: value.removed_executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            },
+            ethernet_bridges: ParameterDelta {
+                upserted: value.upserted_ethernet_bridges.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                removed: value.removed_ethernet_bridges.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            },
+            restbus_channels: ParameterDelta {
+                upserted: value.upserted_restbus_channels.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                removed: value.removed_restbus_channels.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            },
+            cluster_hooks: ParameterDelta {
+                upserted: value.upserted_cluster_hooks.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                removed: value.removed_cluster_hooks.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            },
         })
     }
 }
@@ -122,6 +171,86 @@ mod ethernet_bridge {
     }
 }
 
+mod restbus_channel {
+    use super::*;
+    type Model = crate::peer::configuration::Parameter<crate::peer::restbus::RestbusChannel>;
+    type Proto = PeerConfigurationParameterRestbusChannel;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+
+            let channel: crate::proto::peer::restbus::RestbusChannel = value.value.clone().into();
+            let parameter = PeerConfigurationParameter::from(value);
+
+            Self {
+                parameter: Some(parameter),
+                value: Some(channel),
+            }
+        }
+    }
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let parameter = value.parameter
+                .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+            let channel: crate::peer::restbus::RestbusChannel = value.value
+                .ok_or(ErrorBuilder::field_not_set("value"))?
+                .try_into()?;
+
+            Ok(Self {
+                id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+                dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+                value: channel,
+            })
+        }
+    }
+}
+
+mod cluster_hook {
+    use super::*;
+    type Model = crate::peer::configuration::Parameter<crate::peer::hook::ClusterHook>;
+    type Proto = PeerConfigurationParameterClusterHook;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+
+            let hook: crate::proto::peer::hook::ClusterHook = value.value.clone().into();
+            let parameter = PeerConfigurationParameter::from(value);
+
+            Self {
+                parameter: Some(parameter),
+                value: Some(hook),
+            }
+        }
+    }
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let parameter = value.parameter
+                .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+            let hook: crate::peer::hook::ClusterHook = value.value
+                .ok_or(ErrorBuilder::field_not_set("value"))?
+                .try_into()?;
+
+            Ok(Self {
+                id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+                dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+                value: hook,
+            })
+        }
+    }
+}
+
 impl<V: crate::peer::configuration::ParameterValue> From<crate::peer::configuration::Parameter<V>> for PeerConfigurationParameter {
     fn from(value: crate::peer::configuration::Parameter<V>) -> Self {
         Self {
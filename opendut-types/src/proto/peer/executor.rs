@@ -48,6 +48,9 @@ impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
                 ports,
                 command,
                 args,
+                secrets,
+                can_gateways,
+                network_attachment,
             } => {
                 Some(executor_descriptor::Kind::Container(
                     Container {
@@ -60,6 +63,9 @@ impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
                         ports: ports.into_iter().map(|port| port.into()).collect(),
                         command: Some(command.into()),
                         args: args.into_iter().map(|arg| arg.into()).collect(),
+                        secrets: secrets.into_iter().map(|secret| secret.into()).collect(),
+                        can_gateways: can_gateways.into_iter().map(|can_gateway| can_gateway.into()).collect(),
+                        network_attachment: Some(network_attachment.into()),
                     }
                 ))
             }
@@ -69,6 +75,7 @@ impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
             id,
             kind: executor_kind,
             results_url: value.results_url.map(|results_url| results_url.into()),
+            sandbox: Some(value.sandbox.into()),
         }
     }
 }
@@ -101,6 +108,9 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                     ports,
                     command,
                     args,
+                    secrets,
+                    can_gateways,
+                    network_attachment,
                 } = descriptor;
                 let engine = engine
                     .ok_or(ErrorBuilder::field_not_set("engine"))?
@@ -134,6 +144,18 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                     .into_iter()
                     .map(TryFrom::try_from)
                     .collect::<Result<_, _>>()?;
+                let secrets = secrets
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let can_gateways = can_gateways
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let network_attachment = network_attachment
+                    .map(TryFrom::try_from)
+                    .transpose()?
+                    .unwrap_or_default();
                 
                 crate::peer::executor::ExecutorKind::Container {
                     engine,
@@ -145,17 +167,26 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                     ports,
                     command,
                     args,
+                    secrets,
+                    can_gateways,
+                    network_attachment,
                 }
             }
         };
 
         let results_url = value.results_url.map(TryFrom::try_from).transpose()?;
 
+        let sandbox = value.sandbox
+            .map(TryFrom::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(
             crate::peer::executor::ExecutorDescriptor {
                 id,
                 kind: result_kind,
                 results_url,
+                sandbox,
             }
         )
     }
@@ -379,6 +410,63 @@ impl TryFrom<ContainerCommandArgument> for crate::peer::executor::container::Con
     }
 }
 
+impl From<crate::peer::executor::container::ContainerSecret> for ContainerSecret {
+    fn from(value: crate::peer::executor::container::ContainerSecret) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerSecret> for crate::peer::executor::container::ContainerSecret {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerSecret) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerSecret, crate::peer::executor::container::ContainerSecret>;
+
+        crate::peer::executor::container::ContainerSecret::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerCanGateway> for ContainerCanGateway {
+    fn from(value: crate::peer::executor::container::ContainerCanGateway) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerCanGateway> for crate::peer::executor::container::ContainerCanGateway {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerCanGateway) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerCanGateway, crate::peer::executor::container::ContainerCanGateway>;
+
+        crate::peer::executor::container::ContainerCanGateway::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerNetworkAttachment> for ContainerNetworkAttachment {
+    fn from(value: crate::peer::executor::container::ContainerNetworkAttachment) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerNetworkAttachment> for crate::peer::executor::container::ContainerNetworkAttachment {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerNetworkAttachment) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerNetworkAttachment, crate::peer::executor::container::ContainerNetworkAttachment>;
+
+        crate::peer::executor::container::ContainerNetworkAttachment::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
 impl From<crate::peer::executor::ResultsUrl> for ResultsUrl {
     fn from(value: crate::peer::executor::ResultsUrl) -> Self {
         Self {
@@ -397,3 +485,71 @@ impl TryFrom<ResultsUrl> for crate::peer::executor::ResultsUrl{
             .map_err(|cause| ErrorBuilder::message(cause.to_string()))
     }
 }
+
+impl From<crate::peer::executor::sandbox::ExecutorSandbox> for ExecutorSandbox {
+    fn from(value: crate::peer::executor::sandbox::ExecutorSandbox) -> Self {
+        Self {
+            user: value.user.map(|user| user.into()),
+            seccomp_profile: Some(value.seccomp_profile.into()),
+            read_only_filesystem: value.read_only_filesystem,
+        }
+    }
+}
+
+impl TryFrom<ExecutorSandbox> for crate::peer::executor::sandbox::ExecutorSandbox {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorSandbox) -> Result<Self, Self::Error> {
+        let user = value.user
+            .map(TryFrom::try_from)
+            .transpose()?;
+        let seccomp_profile = value.seccomp_profile
+            .map(TryFrom::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            user,
+            seccomp_profile,
+            read_only_filesystem: value.read_only_filesystem,
+        })
+    }
+}
+
+impl From<crate::peer::executor::sandbox::ExecutorUser> for ExecutorUser {
+    fn from(value: crate::peer::executor::sandbox::ExecutorUser) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutorUser> for crate::peer::executor::sandbox::ExecutorUser {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorUser) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorUser, crate::peer::executor::sandbox::ExecutorUser>;
+
+        crate::peer::executor::sandbox::ExecutorUser::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::sandbox::SeccompProfile> for SeccompProfile {
+    fn from(value: crate::peer::executor::sandbox::SeccompProfile) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<SeccompProfile> for crate::peer::executor::sandbox::SeccompProfile {
+    type Error = ConversionError;
+
+    fn try_from(value: SeccompProfile) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<SeccompProfile, crate::peer::executor::sandbox::SeccompProfile>;
+
+        crate::peer::executor::sandbox::SeccompProfile::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
@@ -0,0 +1,104 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::peer::PeerId;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.group.rs"));
+
+impl From<crate::peer::group::PeerGroupId> for PeerGroupId {
+    fn from(value: crate::peer::group::PeerGroupId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PeerGroupId> for crate::peer::group::PeerGroupId {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerGroupId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerGroupId, crate::peer::group::PeerGroupId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::group::PeerGroupName> for PeerGroupName {
+    fn from(value: crate::peer::group::PeerGroupName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<PeerGroupName> for crate::peer::group::PeerGroupName {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerGroupName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerGroupName, crate::peer::group::PeerGroupName>;
+
+        crate::peer::group::PeerGroupName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::group::PeerGroupLabel> for PeerGroupLabel {
+    fn from(value: crate::peer::group::PeerGroupLabel) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<PeerGroupLabel> for crate::peer::group::PeerGroupLabel {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerGroupLabel) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerGroupLabel, crate::peer::group::PeerGroupLabel>;
+
+        crate::peer::group::PeerGroupLabel::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::group::PeerGroup> for PeerGroup {
+    fn from(value: crate::peer::group::PeerGroup) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            labels: value.labels.into_iter()
+                .map(PeerGroupLabel::from)
+                .collect(),
+            peers: value.peers.into_iter()
+                .map(PeerId::from)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<PeerGroup> for crate::peer::group::PeerGroup {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerGroup) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerGroup, crate::peer::group::PeerGroup>;
+
+        let id: crate::peer::group::PeerGroupId = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let name: crate::peer::group::PeerGroupName = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            name,
+            labels: value.labels.into_iter()
+                .map(PeerGroupLabel::try_into)
+                .collect::<Result<_, _>>()?,
+            peers: value.peers.into_iter()
+                .map(PeerId::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
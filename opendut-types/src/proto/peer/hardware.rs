@@ -0,0 +1,110 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.hardware.rs"));
+
+
+mod hardware_capabilities {
+    use super::*;
+    type Model = crate::peer::hardware::HardwareCapabilities;
+    type Proto = HardwareCapabilities;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                cpu_architecture: value.cpu_architecture,
+                cpu_cores: value.cpu_cores,
+                memory_bytes: value.memory_bytes,
+                virtualization: value.virtualization,
+                can_controllers: value.can_controllers.into_iter().map(CanControllerCapabilities::from).collect(),
+                network_interfaces: value.network_interfaces.into_iter().map(NetworkInterfaceCapabilities::from).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            let can_controllers = value.can_controllers.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?;
+
+            let network_interfaces = value.network_interfaces.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?;
+
+            Ok(crate::peer::hardware::HardwareCapabilities {
+                cpu_architecture: value.cpu_architecture,
+                cpu_cores: value.cpu_cores,
+                memory_bytes: value.memory_bytes,
+                virtualization: value.virtualization,
+                can_controllers,
+                network_interfaces,
+            })
+        }
+    }
+}
+
+mod can_controller_capabilities {
+    use super::*;
+    type Model = crate::peer::hardware::CanControllerCapabilities;
+    type Proto = CanControllerCapabilities;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                interface: Some(value.interface.into()),
+                fd_supported: value.fd_supported,
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let interface = value.interface
+                .ok_or(ErrorBuilder::field_not_set("interface"))?
+                .try_into()?;
+
+            Ok(crate::peer::hardware::CanControllerCapabilities {
+                interface,
+                fd_supported: value.fd_supported,
+            })
+        }
+    }
+}
+
+mod network_interface_capabilities {
+    use super::*;
+    type Model = crate::peer::hardware::NetworkInterfaceCapabilities;
+    type Proto = NetworkInterfaceCapabilities;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                interface: Some(value.interface.into()),
+                speed_mbits: value.speed_mbits,
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let interface = value.interface
+                .ok_or(ErrorBuilder::field_not_set("interface"))?
+                .try_into()?;
+
+            Ok(crate::peer::hardware::NetworkInterfaceCapabilities {
+                interface,
+                speed_mbits: value.speed_mbits,
+            })
+        }
+    }
+}
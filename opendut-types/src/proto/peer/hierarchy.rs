@@ -0,0 +1,117 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::peer::PeerId;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.hierarchy.rs"));
+
+impl From<crate::peer::hierarchy::HierarchyNodeId> for HierarchyNodeId {
+    fn from(value: crate::peer::hierarchy::HierarchyNodeId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<HierarchyNodeId> for crate::peer::hierarchy::HierarchyNodeId {
+    type Error = ConversionError;
+
+    fn try_from(value: HierarchyNodeId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<HierarchyNodeId, crate::peer::hierarchy::HierarchyNodeId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::hierarchy::HierarchyNodeName> for HierarchyNodeName {
+    fn from(value: crate::peer::hierarchy::HierarchyNodeName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<HierarchyNodeName> for crate::peer::hierarchy::HierarchyNodeName {
+    type Error = ConversionError;
+
+    fn try_from(value: HierarchyNodeName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<HierarchyNodeName, crate::peer::hierarchy::HierarchyNodeName>;
+
+        crate::peer::hierarchy::HierarchyNodeName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::hierarchy::HierarchyLevel> for HierarchyLevel {
+    fn from(value: crate::peer::hierarchy::HierarchyLevel) -> Self {
+        let level = match value {
+            crate::peer::hierarchy::HierarchyLevel::Site => hierarchy_level::Level::Site(HierarchyLevelSite {}),
+            crate::peer::hierarchy::HierarchyLevel::Room => hierarchy_level::Level::Room(HierarchyLevelRoom {}),
+            crate::peer::hierarchy::HierarchyLevel::Rack => hierarchy_level::Level::Rack(HierarchyLevelRack {}),
+        };
+        Self {
+            level: Some(level)
+        }
+    }
+}
+
+impl TryFrom<HierarchyLevel> for crate::peer::hierarchy::HierarchyLevel {
+    type Error = ConversionError;
+
+    fn try_from(value: HierarchyLevel) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<HierarchyLevel, crate::peer::hierarchy::HierarchyLevel>;
+
+        let level = match value.level.ok_or(ErrorBuilder::field_not_set("level"))? {
+            hierarchy_level::Level::Site(_) => crate::peer::hierarchy::HierarchyLevel::Site,
+            hierarchy_level::Level::Room(_) => crate::peer::hierarchy::HierarchyLevel::Room,
+            hierarchy_level::Level::Rack(_) => crate::peer::hierarchy::HierarchyLevel::Rack,
+        };
+        Ok(level)
+    }
+}
+
+impl From<crate::peer::hierarchy::HierarchyNode> for HierarchyNode {
+    fn from(value: crate::peer::hierarchy::HierarchyNode) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            level: Some(value.level.into()),
+            parent: value.parent.map(HierarchyNodeId::from),
+            peers: value.peers.into_iter()
+                .map(PeerId::from)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<HierarchyNode> for crate::peer::hierarchy::HierarchyNode {
+    type Error = ConversionError;
+
+    fn try_from(value: HierarchyNode) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<HierarchyNode, crate::peer::hierarchy::HierarchyNode>;
+
+        let id: crate::peer::hierarchy::HierarchyNodeId = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let name: crate::peer::hierarchy::HierarchyNodeName = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        let level: crate::peer::hierarchy::HierarchyLevel = value.level
+            .ok_or(ErrorBuilder::field_not_set("level"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            name,
+            level,
+            parent: value.parent
+                .map(HierarchyNodeId::try_into)
+                .transpose()?,
+            peers: value.peers.into_iter()
+                .map(PeerId::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
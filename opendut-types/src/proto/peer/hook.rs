@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.hook.rs"));
+
+
+mod cluster_hook {
+    use super::*;
+    type Model = crate::peer::hook::ClusterHook;
+    type Proto = ClusterHook;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                name: Some(value.name.into()),
+                stage: Some(value.stage.into()),
+                script: value.script,
+                timeout_ms: value.timeout.as_millis() as u64,
+                failure_policy: Some(value.failure_policy.into()),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let name = value.name
+                .ok_or(ErrorBuilder::field_not_set("name"))?
+                .try_into()?;
+
+            let stage = value.stage
+                .ok_or(ErrorBuilder::field_not_set("stage"))?
+                .try_into()?;
+
+            let failure_policy = value.failure_policy
+                .ok_or(ErrorBuilder::field_not_set("failure_policy"))?
+                .try_into()?;
+
+            Ok(crate::peer::hook::ClusterHook {
+                name,
+                stage,
+                script: value.script,
+                timeout: Duration::from_millis(value.timeout_ms),
+                failure_policy,
+            })
+        }
+    }
+}
+
+mod cluster_hook_name {
+    use super::*;
+    type Model = crate::peer::hook::ClusterHookName;
+    type Proto = ClusterHookName;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self { value: value.into() }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            crate::peer::hook::ClusterHookName::try_from(value.value)
+                .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+        }
+    }
+}
+
+impl From<crate::peer::hook::ClusterHookStage> for ClusterHookStage {
+    fn from(value: crate::peer::hook::ClusterHookStage) -> Self {
+        let inner = match value {
+            crate::peer::hook::ClusterHookStage::BeforeJoin => cluster_hook_stage::Inner::BeforeJoin(ClusterHookStageBeforeJoin {}),
+            crate::peer::hook::ClusterHookStage::AfterLeave => cluster_hook_stage::Inner::AfterLeave(ClusterHookStageAfterLeave {}),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<ClusterHookStage> for crate::peer::hook::ClusterHookStage {
+    type Error = ConversionError;
+
+    fn try_from(value: ClusterHookStage) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterHookStage, crate::peer::hook::ClusterHookStage>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            cluster_hook_stage::Inner::BeforeJoin(_) => crate::peer::hook::ClusterHookStage::BeforeJoin,
+            cluster_hook_stage::Inner::AfterLeave(_) => crate::peer::hook::ClusterHookStage::AfterLeave,
+        };
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::hook::ClusterHookFailurePolicy> for ClusterHookFailurePolicy {
+    fn from(value: crate::peer::hook::ClusterHookFailurePolicy) -> Self {
+        let inner = match value {
+            crate::peer::hook::ClusterHookFailurePolicy::Abort => cluster_hook_failure_policy::Inner::Abort(ClusterHookFailurePolicyAbort {}),
+            crate::peer::hook::ClusterHookFailurePolicy::Ignore => cluster_hook_failure_policy::Inner::Ignore(ClusterHookFailurePolicyIgnore {}),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<ClusterHookFailurePolicy> for crate::peer::hook::ClusterHookFailurePolicy {
+    type Error = ConversionError;
+
+    fn try_from(value: ClusterHookFailurePolicy) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterHookFailurePolicy, crate::peer::hook::ClusterHookFailurePolicy>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            cluster_hook_failure_policy::Inner::Abort(_) => crate::peer::hook::ClusterHookFailurePolicy::Abort,
+            cluster_hook_failure_policy::Inner::Ignore(_) => crate::peer::hook::ClusterHookFailurePolicy::Ignore,
+        };
+        Ok(result)
+    }
+}
@@ -0,0 +1,226 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.label.rs"));
+
+impl From<crate::peer::label::LabelKey> for LabelKey {
+    fn from(value: crate::peer::label::LabelKey) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<LabelKey> for crate::peer::label::LabelKey {
+    type Error = ConversionError;
+
+    fn try_from(value: LabelKey) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<LabelKey, crate::peer::label::LabelKey>;
+
+        crate::peer::label::LabelKey::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::label::LabelValue> for LabelValue {
+    fn from(value: crate::peer::label::LabelValue) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<LabelValue> for crate::peer::label::LabelValue {
+    type Error = ConversionError;
+
+    fn try_from(value: LabelValue) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<LabelValue, crate::peer::label::LabelValue>;
+
+        crate::peer::label::LabelValue::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::label::AnnotationValue> for AnnotationValue {
+    fn from(value: crate::peer::label::AnnotationValue) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<AnnotationValue> for crate::peer::label::AnnotationValue {
+    type Error = ConversionError;
+
+    fn try_from(value: AnnotationValue) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<AnnotationValue, crate::peer::label::AnnotationValue>;
+
+        crate::peer::label::AnnotationValue::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<(crate::peer::label::LabelKey, crate::peer::label::LabelValue)> for Label {
+    fn from((key, value): (crate::peer::label::LabelKey, crate::peer::label::LabelValue)) -> Self {
+        Self {
+            key: Some(key.into()),
+            value: Some(value.into()),
+        }
+    }
+}
+
+impl TryFrom<Label> for (crate::peer::label::LabelKey, crate::peer::label::LabelValue) {
+    type Error = ConversionError;
+
+    fn try_from(value: Label) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<Label, (crate::peer::label::LabelKey, crate::peer::label::LabelValue)>;
+
+        let key = value.key
+            .ok_or(ErrorBuilder::field_not_set("key"))?
+            .try_into()?;
+        let value = value.value
+            .ok_or(ErrorBuilder::field_not_set("value"))?
+            .try_into()?;
+        Ok((key, value))
+    }
+}
+
+impl From<(crate::peer::label::LabelKey, crate::peer::label::AnnotationValue)> for Annotation {
+    fn from((key, value): (crate::peer::label::LabelKey, crate::peer::label::AnnotationValue)) -> Self {
+        Self {
+            key: Some(key.into()),
+            value: Some(value.into()),
+        }
+    }
+}
+
+impl TryFrom<Annotation> for (crate::peer::label::LabelKey, crate::peer::label::AnnotationValue) {
+    type Error = ConversionError;
+
+    fn try_from(value: Annotation) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<Annotation, (crate::peer::label::LabelKey, crate::peer::label::AnnotationValue)>;
+
+        let key = value.key
+            .ok_or(ErrorBuilder::field_not_set("key"))?
+            .try_into()?;
+        let value = value.value
+            .ok_or(ErrorBuilder::field_not_set("value"))?
+            .try_into()?;
+        Ok((key, value))
+    }
+}
+
+impl From<crate::peer::label::LabelSelector> for LabelSelector {
+    fn from(value: crate::peer::label::LabelSelector) -> Self {
+        Self {
+            requirements: value.requirements.into_iter().map(LabelRequirement::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<LabelSelector> for crate::peer::label::LabelSelector {
+    type Error = ConversionError;
+
+    fn try_from(value: LabelSelector) -> Result<Self, Self::Error> {
+        let requirements = value.requirements.into_iter()
+            .map(crate::peer::label::LabelRequirement::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(crate::peer::label::LabelSelector { requirements })
+    }
+}
+
+impl From<crate::peer::label::LabelRequirement> for LabelRequirement {
+    fn from(value: crate::peer::label::LabelRequirement) -> Self {
+        let kind = match value {
+            crate::peer::label::LabelRequirement::Equals { key, value } => label_requirement::Kind::Equals(Label::from((key, value))),
+            crate::peer::label::LabelRequirement::NotEquals { key, value } => label_requirement::Kind::NotEquals(Label::from((key, value))),
+            crate::peer::label::LabelRequirement::Exists { key } => label_requirement::Kind::Exists(key.into()),
+            crate::peer::label::LabelRequirement::NotExists { key } => label_requirement::Kind::NotExists(key.into()),
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<LabelRequirement> for crate::peer::label::LabelRequirement {
+    type Error = ConversionError;
+
+    fn try_from(value: LabelRequirement) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<LabelRequirement, crate::peer::label::LabelRequirement>;
+
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?;
+
+        let result = match kind {
+            label_requirement::Kind::Equals(label) => {
+                let (key, value) = label.try_into()?;
+                crate::peer::label::LabelRequirement::Equals { key, value }
+            }
+            label_requirement::Kind::NotEquals(label) => {
+                let (key, value) = label.try_into()?;
+                crate::peer::label::LabelRequirement::NotEquals { key, value }
+            }
+            label_requirement::Kind::Exists(key) => crate::peer::label::LabelRequirement::Exists { key: key.try_into()? },
+            label_requirement::Kind::NotExists(key) => crate::peer::label::LabelRequirement::NotExists { key: key.try_into()? },
+        };
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::label::LabelChange> for LabelChange {
+    fn from(value: crate::peer::label::LabelChange) -> Self {
+        let kind = match value {
+            crate::peer::label::LabelChange::Set { key, value } => label_change::Kind::Set(Label::from((key, value))),
+            crate::peer::label::LabelChange::Remove { key } => label_change::Kind::Remove(key.into()),
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<LabelChange> for crate::peer::label::LabelChange {
+    type Error = ConversionError;
+
+    fn try_from(value: LabelChange) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<LabelChange, crate::peer::label::LabelChange>;
+
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?;
+
+        let result = match kind {
+            label_change::Kind::Set(label) => {
+                let (key, value) = label.try_into()?;
+                crate::peer::label::LabelChange::Set { key, value }
+            }
+            label_change::Kind::Remove(key) => crate::peer::label::LabelChange::Remove { key: key.try_into()? },
+        };
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::label::AnnotationChange> for AnnotationChange {
+    fn from(value: crate::peer::label::AnnotationChange) -> Self {
+        let kind = match value {
+            crate::peer::label::AnnotationChange::Set { key, value } => annotation_change::Kind::Set(Annotation::from((key, value))),
+            crate::peer::label::AnnotationChange::Remove { key } => annotation_change::Kind::Remove(key.into()),
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<AnnotationChange> for crate::peer::label::AnnotationChange {
+    type Error = ConversionError;
+
+    fn try_from(value: AnnotationChange) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<AnnotationChange, crate::peer::label::AnnotationChange>;
+
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?;
+
+        let result = match kind {
+            annotation_change::Kind::Set(annotation) => {
+                let (key, value) = annotation.try_into()?;
+                crate::peer::label::AnnotationChange::Set { key, value }
+            }
+            annotation_change::Kind::Remove(key) => crate::peer::label::AnnotationChange::Remove { key: key.try_into()? },
+        };
+        Ok(result)
+    }
+}
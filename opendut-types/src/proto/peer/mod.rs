@@ -6,7 +6,14 @@ use super::util::{NetworkInterfaceDescriptor, NetworkInterfaceName};
 
 pub mod configuration;
 pub mod executor;
+pub mod group;
+pub mod hierarchy;
+pub mod label;
+pub mod hardware;
+pub mod hook;
+pub mod provisioning;
 mod ethernet;
+mod restbus;
 
 include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.rs"));
 
@@ -123,6 +130,10 @@ impl From<crate::peer::PeerDescriptor> for PeerDescriptor {
             network: Some(value.network.into()),
             topology: Some(value.topology.into()),
             executors: Some(value.executors.into()),
+            labels: value.labels.into_iter().map(label::Label::from).collect(),
+            annotations: value.annotations.into_iter().map(label::Annotation::from).collect(),
+            hardware: value.hardware.map(hardware::HardwareCapabilities::from),
+            cluster_hooks: value.cluster_hooks.into_iter().map(hook::ClusterHook::from).collect(),
         }
     }
 }
@@ -156,7 +167,23 @@ impl TryFrom<PeerDescriptor> for crate::peer::PeerDescriptor {
         let executors = value.executors
             .ok_or(ErrorBuilder::field_not_set("executors"))?
             .try_into()?;
-        
+
+        let labels = value.labels.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        let annotations = value.annotations.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        let hardware = value.hardware
+            .map(crate::peer::hardware::HardwareCapabilities::try_from)
+            .transpose()?;
+
+        let cluster_hooks = value.cluster_hooks.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
         Ok(crate::peer::PeerDescriptor {
             id,
             name,
@@ -164,6 +191,10 @@ impl TryFrom<PeerDescriptor> for crate::peer::PeerDescriptor {
             network,
             topology,
             executors,
+            labels,
+            annotations,
+            hardware,
+            cluster_hooks,
         })
     }
 }
@@ -176,6 +207,9 @@ impl From<crate::peer::PeerSetup> for PeerSetup {
             ca: Some(value.ca.into()),
             vpn: Some(value.vpn.into()),
             auth_config: Some(value.auth_config.into()),
+            expires_at_unix_millis: value.expires_at
+                .and_then(|expires_at| expires_at.duration_since(std::time::SystemTime::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as u64),
         }
     }
 }
@@ -207,12 +241,16 @@ impl TryFrom<PeerSetup> for crate::peer::PeerSetup {
             .ok_or(ErrorBuilder::field_not_set("auth_config"))?
             .try_into()?;
 
+        let expires_at = value.expires_at_unix_millis
+            .map(|millis| std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(millis));
+
         Ok(Self {
             id,
             carl,
             ca,
             auth_config,
             vpn,
+            expires_at,
         })
     }
 }
@@ -225,6 +263,15 @@ impl From<crate::peer::state::PeerState> for PeerState {
                     inner: Some(peer_state::Inner::Down(PeerStateDown {}))
                 }
             },
+            crate::peer::state::PeerState::Unreachable { inner, remote_host } => {
+                let last_up = match PeerState::from(crate::peer::state::PeerState::Up { inner, remote_host }).inner {
+                    Some(peer_state::Inner::Up(last_up)) => Some(last_up),
+                    _ => None,
+                };
+                PeerState {
+                    inner: Some(peer_state::Inner::Unreachable(PeerStateUnreachable { last_up }))
+                }
+            },
             crate::peer::state::PeerState::Up { inner, remote_host } => {
                 let remote_host: proto::util::IpAddress = remote_host.into();
                 let remote_host = Some(remote_host);
@@ -293,6 +340,17 @@ impl TryFrom<PeerState> for crate::peer::state::PeerState {
             peer_state::Inner::Down(_) => {
                 Ok(crate::peer::state::PeerState::Down)
             }
+            peer_state::Inner::Unreachable(PeerStateUnreachable { last_up }) => {
+                let last_up = last_up
+                    .ok_or(ErrorBuilder::field_not_set("last_up"))?;
+
+                match crate::peer::state::PeerState::try_from(PeerState { inner: Some(peer_state::Inner::Up(last_up)) })? {
+                    crate::peer::state::PeerState::Up { inner, remote_host } => {
+                        Ok(crate::peer::state::PeerState::Unreachable { inner, remote_host })
+                    }
+                    _ => Err(ErrorBuilder::message("Inner 'Unreachable' state is not an 'Up' state")),
+                }
+            }
             peer_state::Inner::Up(PeerStateUp { inner, remote_host }) => {
 
                 let remote_host: std::net::IpAddr = remote_host
@@ -467,6 +525,21 @@ mod tests {
             );
         }
 
+        { // Unreachable/Blocked/Member
+            let native = crate::peer::state::PeerState::Unreachable {
+                inner: crate::peer::state::PeerUpState::Blocked(
+                    crate::peer::state::PeerBlockedState::Member
+                ),
+                remote_host: native_remote_host,
+            };
+            let proto: PeerState = Clone::clone(&native).into();
+
+            assert_that!(
+                crate::peer::state::PeerState::try_from(Clone::clone(&proto)),
+                ok(eq(&native))
+            );
+        }
+
         { // Up/Blocked/Undeploying
             let native = crate::peer::state::PeerState::Up {
                 inner: crate::peer::state::PeerUpState::Blocked(
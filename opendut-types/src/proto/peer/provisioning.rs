@@ -0,0 +1,113 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::peer::label;
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.provisioning.rs"));
+
+
+mod provisioning_token_id {
+    use super::*;
+    type Model = crate::peer::provisioning::ProvisioningTokenId;
+    type Proto = ProvisioningTokenId;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                uuid: Some(value.0.into()),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            value.uuid
+                .ok_or(ErrorBuilder::field_not_set("uuid"))
+                .map(|uuid| Model::from(uuid::Uuid::from(uuid)))
+        }
+    }
+}
+
+mod peer_template {
+    use super::*;
+    type Model = crate::peer::provisioning::PeerTemplate;
+    type Proto = PeerTemplate;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                location: value.location.map(Into::into),
+                labels: value.labels.into_iter().map(label::Label::from).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            let location = value.location
+                .map(crate::peer::PeerLocation::try_from)
+                .transpose()?;
+
+            let labels = value.labels.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?;
+
+            Ok(Model {
+                location,
+                labels,
+            })
+        }
+    }
+}
+
+mod peer_provisioning {
+    use super::*;
+    type Model = crate::peer::provisioning::PeerProvisioning;
+    type Proto = PeerProvisioning;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                token: Some(value.token.into()),
+                secret: value.secret,
+                carl: Some(value.carl.into()),
+                ca: Some(value.ca.into()),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let token = value.token
+                .ok_or(ErrorBuilder::field_not_set("token"))?
+                .try_into()?;
+
+            if value.secret.is_empty() {
+                return Err(ErrorBuilder::message("Secret of the provisioning token must not be empty."));
+            }
+
+            let carl = value.carl
+                .ok_or(ErrorBuilder::field_not_set("carl"))?
+                .try_into()?;
+
+            let ca = value.ca
+                .ok_or(ErrorBuilder::field_not_set("ca"))?
+                .try_into()?;
+
+            Ok(Model {
+                token,
+                secret: value.secret,
+                carl,
+                ca,
+            })
+        }
+    }
+}
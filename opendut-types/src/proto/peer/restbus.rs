@@ -0,0 +1,40 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.restbus.rs"));
+
+
+mod restbus_channel {
+    use super::*;
+    type Model = crate::peer::restbus::RestbusChannel;
+    type Proto = RestbusChannel;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+            Self {
+                bus: value.bus,
+                interface: Some(value.interface.into()),
+            }
+        }
+    }
+
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            if value.bus.is_empty() {
+                return Err(ErrorBuilder::message("Name of the bus must not be empty."));
+            }
+
+            let interface = value.interface
+                .ok_or(ErrorBuilder::field_not_set("interface"))?
+                .try_into()?;
+
+            Ok(crate::peer::restbus::RestbusChannel {
+                bus: value.bus,
+                interface,
+            })
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Not;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cluster::{ClusterConfiguration, ClusterDeployment};
+use crate::peer::group::PeerGroup;
+use crate::peer::PeerDescriptor;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SnapshotId(pub Uuid);
+
+impl SnapshotId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for SnapshotId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal SnapshotId: {value}")]
+pub struct IllegalSnapshotId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for SnapshotId {
+    type Error = IllegalSnapshotId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalSnapshotId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for SnapshotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Name of a snapshot, e.g. "before holiday shutdown". Unlike the names of other resources, it may contain arbitrary characters.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SnapshotName(pub(crate) String);
+
+impl SnapshotName {
+    pub const MAX_LENGTH: usize = 128;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalSnapshotName {
+    #[error("Snapshot name must not be empty.")]
+    Empty,
+    #[error("Snapshot name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong {
+        value: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl TryFrom<String> for SnapshotName {
+    type Error = IllegalSnapshotName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim().to_owned();
+        let length = value.chars().count();
+        if value.is_empty() {
+            Err(IllegalSnapshotName::Empty)
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalSnapshotName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for SnapshotName {
+    type Error = IllegalSnapshotName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SnapshotName::try_from(value.to_owned())
+    }
+}
+
+impl From<SnapshotName> for String {
+    fn from(value: SnapshotName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for SnapshotName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Copy of all user-defined resources at a point in time, which can be compared with other snapshots and restored.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceSnapshot {
+    pub id: SnapshotId,
+    pub name: SnapshotName,
+    pub created_at: SystemTime,
+    pub content: SnapshotContent,
+}
+
+/// Resources contained in a snapshot. Runtime state, like the state of peers, is not part of a snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotContent {
+    pub peers: Vec<PeerDescriptor>,
+    pub peer_groups: Vec<PeerGroup>,
+    pub cluster_configurations: Vec<ClusterConfiguration>,
+    pub cluster_deployments: Vec<ClusterDeployment>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SnapshotResourceKind {
+    Peer,
+    PeerGroup,
+    ClusterConfiguration,
+    ClusterDeployment,
+}
+
+impl SnapshotResourceKind {
+    pub const ALL: [SnapshotResourceKind; 4] = [
+        SnapshotResourceKind::Peer,
+        SnapshotResourceKind::PeerGroup,
+        SnapshotResourceKind::ClusterConfiguration,
+        SnapshotResourceKind::ClusterDeployment,
+    ];
+
+    pub fn value(&self) -> &'static str {
+        match self {
+            SnapshotResourceKind::Peer => "peer",
+            SnapshotResourceKind::PeerGroup => "peer-group",
+            SnapshotResourceKind::ClusterConfiguration => "cluster-configuration",
+            SnapshotResourceKind::ClusterDeployment => "cluster-deployment",
+        }
+    }
+}
+
+impl fmt::Display for SnapshotResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown snapshot resource kind '{value}'. Expected one of: peer, peer-group, cluster-configuration, cluster-deployment.")]
+pub struct IllegalSnapshotResourceKind {
+    pub value: String,
+}
+
+impl TryFrom<&str> for SnapshotResourceKind {
+    type Error = IllegalSnapshotResourceKind;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SnapshotResourceKind::ALL.into_iter()
+            .find(|kind| kind.value() == value)
+            .ok_or_else(|| IllegalSnapshotResourceKind { value: String::from(value) })
+    }
+}
+
+/// Identifies a single resource within a snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SnapshotResourceReference {
+    pub kind: SnapshotResourceKind,
+    pub id: Uuid,
+}
+
+impl fmt::Display for SnapshotResourceReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind, self.id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SnapshotChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotChange::Added => write!(f, "added"),
+            SnapshotChange::Removed => write!(f, "removed"),
+            SnapshotChange::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiffEntry {
+    pub resource: SnapshotResourceReference,
+    /// Name of the resource in the newer snapshot or, if it was removed, in the older snapshot.
+    pub name: String,
+    pub change: SnapshotChange,
+}
+
+impl SnapshotContent {
+    /// Determines which resources were added, removed or changed from this content to the `newer` content.
+    /// Entries are sorted by kind and ID.
+    pub fn diff(&self, newer: &SnapshotContent) -> Vec<SnapshotDiffEntry> {
+        let older = self.entries();
+        let newer = newer.entries();
+
+        let mut diff = Vec::new();
+
+        for (reference, (name, old_value)) in &older {
+            match newer.get(reference) {
+                None => diff.push(SnapshotDiffEntry { resource: *reference, name: Clone::clone(name), change: SnapshotChange::Removed }),
+                Some((new_name, new_value)) if new_value != old_value => {
+                    diff.push(SnapshotDiffEntry { resource: *reference, name: Clone::clone(new_name), change: SnapshotChange::Changed })
+                }
+                Some(_) => {}
+            }
+        }
+        for (reference, (name, _)) in &newer {
+            if older.contains_key(reference).not() {
+                diff.push(SnapshotDiffEntry { resource: *reference, name: Clone::clone(name), change: SnapshotChange::Added });
+            }
+        }
+
+        diff.sort_by_key(|entry| entry.resource);
+        diff
+    }
+
+    pub fn references(&self) -> Vec<SnapshotResourceReference> {
+        let mut references = self.entries().into_keys().collect::<Vec<_>>();
+        references.sort();
+        references
+    }
+
+    /// Returns the name and a comparable representation of each resource.
+    /// The resources are compared via their JSON representation, so that the order of elements in collections does not matter.
+    fn entries(&self) -> HashMap<SnapshotResourceReference, (String, serde_json::Value)> {
+        fn entry<R: Serialize>(kind: SnapshotResourceKind, id: Uuid, name: String, resource: &R) -> (SnapshotResourceReference, (String, serde_json::Value)) {
+            let value = serde_json::to_value(resource).unwrap_or_default();
+            (SnapshotResourceReference { kind, id }, (name, normalize(value)))
+        }
+
+        let mut entries = HashMap::new();
+        entries.extend(self.peers.iter().map(|peer| entry(SnapshotResourceKind::Peer, peer.id.uuid, peer.name.to_string(), peer)));
+        entries.extend(self.peer_groups.iter().map(|peer_group| entry(SnapshotResourceKind::PeerGroup, peer_group.id.0, String::from(Clone::clone(&peer_group.name)), peer_group)));
+        entries.extend(self.cluster_configurations.iter().map(|configuration| entry(SnapshotResourceKind::ClusterConfiguration, configuration.id.0, configuration.name.to_string(), configuration)));
+        entries.extend(self.cluster_deployments.iter().map(|deployment| entry(SnapshotResourceKind::ClusterDeployment, deployment.id.0, deployment.id.to_string(), deployment)));
+        entries
+    }
+}
+
+/// Sorts arrays, so that the representation of sets and of lists read from the database is independent of their order.
+fn normalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(values) => {
+            let mut values = values.into_iter().map(normalize).collect::<Vec<_>>();
+            values.sort_by_key(|value| value.to_string());
+            serde_json::Value::Array(values)
+        }
+        serde_json::Value::Object(map) => {
+            serde_json::Value::Object(map.into_iter().map(|(key, value)| (key, normalize(value))).collect())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::cluster::ClusterId;
+    use crate::peer::group::PeerGroupId;
+
+    use super::*;
+
+    fn peer_group(id: PeerGroupId, name: &str) -> PeerGroup {
+        PeerGroup {
+            id,
+            name: crate::peer::group::PeerGroupName::try_from(String::from(name)).unwrap(),
+            labels: Default::default(),
+            peers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn should_determine_added_removed_and_changed_resources() {
+        let unchanged = PeerGroupId::random();
+        let changed = PeerGroupId::random();
+        let removed = PeerGroupId::random();
+        let added = ClusterId::random();
+
+        let older = SnapshotContent {
+            peer_groups: vec![
+                peer_group(unchanged, "unchanged"),
+                peer_group(changed, "before"),
+                peer_group(removed, "removed"),
+            ],
+            ..Default::default()
+        };
+        let newer = SnapshotContent {
+            peer_groups: vec![
+                peer_group(changed, "after"),
+                peer_group(unchanged, "unchanged"),
+            ],
+            cluster_deployments: vec![ClusterDeployment { id: added }],
+            ..Default::default()
+        };
+
+        let diff = older.diff(&newer);
+
+        assert_that!(diff, unordered_elements_are![
+            eq(&SnapshotDiffEntry {
+                resource: SnapshotResourceReference { kind: SnapshotResourceKind::PeerGroup, id: changed.0 },
+                name: String::from("after"),
+                change: SnapshotChange::Changed,
+            }),
+            eq(&SnapshotDiffEntry {
+                resource: SnapshotResourceReference { kind: SnapshotResourceKind::PeerGroup, id: removed.0 },
+                name: String::from("removed"),
+                change: SnapshotChange::Removed,
+            }),
+            eq(&SnapshotDiffEntry {
+                resource: SnapshotResourceReference { kind: SnapshotResourceKind::ClusterDeployment, id: added.0 },
+                name: added.to_string(),
+                change: SnapshotChange::Added,
+            }),
+        ]);
+    }
+
+    #[test]
+    fn should_reject_empty_snapshot_names() {
+        assert!(SnapshotName::try_from("  ").is_err());
+        assert_that!(SnapshotName::try_from(" before holiday shutdown ").map(String::from).ok(), some(eq("before holiday shutdown")));
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -13,6 +14,7 @@ use crate::confidential::client::ConfidentialClient;
 pub struct TonicAuthenticationService {
     inner: Channel,
     confidential_client: Option<Arc<ConfidentialClient>>,
+    static_token: Option<StaticToken>,
 }
 
 impl TonicAuthenticationService {
@@ -23,8 +25,31 @@ impl TonicAuthenticationService {
         TonicAuthenticationService {
             inner,
             confidential_client,
+            static_token: None,
         }
     }
+
+    /// Sends the given token with each request instead of retrieving one from the identity provider, e.g. the token of a service account.
+    pub fn with_static_token(
+        inner: Channel,
+        token: String,
+    ) -> Self {
+        TonicAuthenticationService {
+            inner,
+            confidential_client: None,
+            static_token: Some(StaticToken(token)),
+        }
+    }
+}
+
+/// Token, which is not printed in debug output, as it is a long-lived secret.
+#[derive(Clone)]
+struct StaticToken(String);
+
+impl fmt::Debug for StaticToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StaticToken(<redacted>)")
+    }
 }
 
 impl Service<Request<BoxBody>> for TonicAuthenticationService {
@@ -44,8 +69,15 @@ impl Service<Request<BoxBody>> for TonicAuthenticationService {
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
         let confidential_client = self.confidential_client.clone();
+        let static_token = self.static_token.clone();
 
         Box::pin(async move {
+            if let Some(StaticToken(token)) = static_token {
+                let bearer_header = HeaderValue::from_str(format!("Bearer {token}").as_str())?;
+                request.headers_mut().insert("Authorization", bearer_header);
+                return Ok(inner.call(request).await?);
+            }
+
             let token_result = confidential_client.as_ref()
                 .map(|manager| manager.get_token());
 
@@ -10,13 +10,14 @@ use openidconnect::registration::EmptyAdditionalClientMetadata;
 use serde::Deserialize;
 use tracing::error;
 use url::Url;
+use uuid::Uuid;
 use opendut_types::resources::Id;
 use opendut_types::util::net::{ClientCredentials, ClientId, ClientSecret};
 
 use crate::confidential::client::{ConfidentialClient, ConfidentialClientRef};
 use crate::registration::config::RegistrationClientConfig;
 use crate::registration::error::WrappedClientRegistrationError;
-use crate::registration::resources::{ResourceHomeUrl, UserId};
+use crate::registration::resources::{ResourceHomeUrl, ResourceKind, UserId};
 
 pub type RegistrationClientRef = Arc<RegistrationClient>;
 
@@ -81,7 +82,7 @@ impl RegistrationClient {
         })
     }
 
-    pub async fn register_new_client_for_user(&self, resource_id: Id, user_id: UserId) -> Result<ClientCredentials, RegistrationClientError> {
+    pub async fn register_new_client_for_user(&self, resource_id: Id, resource_kind: ResourceKind, user_id: UserId) -> Result<ClientCredentials, RegistrationClientError> {
         match self.config.peer_credentials.clone() {
             Some(peer_credentials) => {
                 Ok(peer_credentials)
@@ -98,7 +99,7 @@ impl RegistrationClient {
                 let registration_url = self.config.registration_url.clone();
 
                 let client_name: ClientName = ClientName::new(resource_id.to_string());
-                let resource_uri = self.config.client_home_base_url.resource_url(resource_id, user_id)
+                let resource_uri = self.config.client_home_base_url.resource_url(resource_id, resource_kind, user_id)
                     .map_err(|error| RegistrationClientError::ClientParameter {
                         message: format!("Failed to create resource url for client: {:?}", error),
                         cause: Box::new(error),
@@ -239,3 +240,55 @@ pub struct Client {
     pub client_id: String,
     base_url: Option<String>,
 }
+
+impl Client {
+    /// The resource this client was registered for, as stored in its home URL: `/resources/<user>/<kind>/<resource-id>`.
+    pub fn resource_id(&self) -> Option<Id> {
+        let resource_path = self.resource_path()?;
+        let resource_id = resource_path.last()?;
+        Uuid::parse_str(resource_id).ok().map(Id::from)
+    }
+
+    /// Returns `None` for clients registered before the resource kind was stored in the home URL.
+    pub fn resource_kind(&self) -> Option<ResourceKind> {
+        match self.resource_path()?.as_slice() {
+            [_user, kind, _resource_id] => ResourceKind::from_path_segment(kind),
+            _ => None,
+        }
+    }
+
+    fn resource_path(&self) -> Option<Vec<String>> {
+        let base_url = Url::parse(self.base_url.as_ref()?).ok()?;
+        let mut segments = base_url.path_segments()?;
+        segments.find(|segment| *segment == "resources")?;
+        Some(segments.map(String::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn client_with_base_url(base_url: &str) -> Client {
+        Client { client_id: String::from("client"), base_url: Some(String::from(base_url)) }
+    }
+
+    #[test]
+    fn should_determine_the_resource_of_a_client() -> anyhow::Result<()> {
+        let resource_id = Id::random();
+
+        let client = client_with_base_url(&format!("https://carl:443/resources/testUser/peer/{}", resource_id.value()));
+        assert_that!(client.resource_id(), some(eq(resource_id)));
+        assert_that!(client.resource_kind(), some(eq(ResourceKind::Peer)));
+
+        let legacy_client = client_with_base_url(&format!("https://carl:443/resources/testUser/{}", resource_id.value()));
+        assert_that!(legacy_client.resource_id(), some(eq(resource_id)));
+        assert_that!(legacy_client.resource_kind(), none());
+
+        let unrelated_client = client_with_base_url("https://example.com/");
+        assert_that!(unrelated_client.resource_id(), none());
+        Ok(())
+    }
+}
@@ -38,13 +38,38 @@ impl ResourceHomeUrl {
         self.0.clone()
     }
     
-    pub fn resource_url(&self, resource_id: resources::Id, user_id: UserId) -> Result<Url, ResourceHomeUrlError> {
-        let path = format!("/resources/{}/{}", user_id.value, resource_id.value());
+    pub fn resource_url(&self, resource_id: resources::Id, resource_kind: ResourceKind, user_id: UserId) -> Result<Url, ResourceHomeUrlError> {
+        let path = format!("/resources/{}/{}/{}", user_id.value, resource_kind.path_segment(), resource_id.value());
         self.0.join(&path)
             .map_err(|error| ResourceHomeUrlError(format!("Failed to create resource URL for resource_id='{}': {}", resource_id.value(), error)))
     }
 }
 
+/// Kind of resource an OIDC client is registered for. Stored in the client's home URL,
+/// so that clients can be matched against the resources again, e.g. to find orphaned clients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceKind {
+    Peer,
+    Cleo,
+}
+
+impl ResourceKind {
+    pub fn path_segment(&self) -> &'static str {
+        match self {
+            ResourceKind::Peer => "peer",
+            ResourceKind::Cleo => "cleo",
+        }
+    }
+
+    pub fn from_path_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "peer" => Some(ResourceKind::Peer),
+            "cleo" => Some(ResourceKind::Cleo),
+            _ => None,
+        }
+    }
+}
+
 pub struct UserId {
     pub value: String,
 }
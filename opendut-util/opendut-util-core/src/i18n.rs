@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Language of the messages shown to the user by CLEO and LEA.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::German];
+
+    /// Parses a language tag, like `de`, `de-DE` or a POSIX locale like `de_DE.UTF-8`.
+    /// Only the language is considered, the region and encoding are ignored.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match language.as_str() {
+            "en" => Some(Locale::English),
+            "de" => Some(Locale::German),
+            _ => None,
+        }
+    }
+
+    /// Determines the locale from the environment variables `LC_ALL`, `LC_MESSAGES` and `LANG`, in this order of precedence,
+    /// as POSIX specifies it. Falls back to English, if none of them is set to a supported language.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .find(|value| value.is_empty().not())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+        }
+    }
+
+    fn catalog_source(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../i18n/en.catalog"),
+            Locale::German => include_str!("../i18n/de.catalog"),
+        }
+    }
+
+    fn catalog(&self) -> &'static Catalog {
+        static ENGLISH: OnceLock<Catalog> = OnceLock::new();
+        static GERMAN: OnceLock<Catalog> = OnceLock::new();
+
+        let cell = match self {
+            Locale::English => &ENGLISH,
+            Locale::German => &GERMAN,
+        };
+        cell.get_or_init(|| Catalog::parse(self.catalog_source()))
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unsupported language '{tag}'. Supported are: {supported}")]
+pub struct UnsupportedLocale {
+    tag: String,
+    supported: String,
+}
+
+impl FromStr for Locale {
+    type Err = UnsupportedLocale;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        Locale::from_tag(tag)
+            .ok_or_else(|| UnsupportedLocale {
+                tag: tag.to_owned(),
+                supported: Locale::ALL.map(|locale| locale.tag()).join(", "),
+            })
+    }
+}
+
+struct Catalog {
+    messages: HashMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    /// Parses lines in the format `key = message`. Empty lines and lines starting with `#` are skipped.
+    fn parse(source: &'static str) -> Self {
+        let messages = source.lines()
+            .map(str::trim)
+            .filter(|line| line.is_empty().not() && line.starts_with('#').not())
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, message)| (key.trim(), message.trim()))
+            .collect();
+        Self { messages }
+    }
+}
+
+/// Returns the message for the key in the given locale.
+/// Falls back to the English message, if the locale's catalog does not contain the key, and to the key itself, if no catalog does.
+pub fn message(locale: Locale, key: &str) -> String {
+    locale.catalog().messages.get(key)
+        .or_else(|| Locale::English.catalog().messages.get(key))
+        .map(|message| message.to_string())
+        .unwrap_or_else(|| key.to_owned())
+}
+
+/// Like [`message`], but replaces the placeholders `{name}` in the message with the corresponding argument.
+/// ```
+/// use opendut_util_core::i18n::{self, Locale};
+///
+/// let message = i18n::format(Locale::German, "cluster-deployment.delete.success", &[("cluster_id", &"1234")]);
+/// assert_eq!(message, "Cluster-Deployment für die ClusterID '1234' gelöscht.");
+/// ```
+pub fn format(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter()
+        .fold(message(locale, key), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_language_tags() {
+        assert_eq!(Locale::from_tag("de"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("de_DE.UTF-8"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("EN_us"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("C"), None);
+        assert!(Locale::from_str("fr").is_err());
+    }
+
+    #[test]
+    fn should_provide_all_english_keys_in_every_catalog() {
+        let english = Locale::English.catalog();
+        for locale in Locale::ALL {
+            let catalog = locale.catalog();
+            let missing = english.messages.keys()
+                .filter(|key| catalog.messages.contains_key(*key).not())
+                .collect::<Vec<_>>();
+            assert!(missing.is_empty(), "Catalog '{locale}' is missing the keys: {missing:?}");
+        }
+    }
+
+    #[test]
+    fn should_fall_back_to_the_key_for_unknown_messages() {
+        assert_eq!(message(Locale::German, "does-not-exist"), "does-not-exist");
+    }
+
+    #[test]
+    fn should_replace_placeholders() {
+        let message = format(Locale::English, "peer.delete.in-use", &[("clusters", &"a, b")]);
+        assert_eq!(message, "Cannot delete peer because it is used in following clusters: a, b");
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fmt::Write;
+use std::ops::Not;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use uuid::Uuid;
+
+pub type TraceCapturesRef = Arc<TraceCaptures>;
+
+static TRACE_CAPTURES: OnceLock<TraceCapturesRef> = OnceLock::new();
+
+/// The trace captures of this process, which are filled by the layer registered when initializing telemetry.
+pub fn trace_captures() -> TraceCapturesRef {
+    Arc::clone(TRACE_CAPTURES.get_or_init(|| Arc::new(TraceCaptures::default())))
+}
+
+/// Which records are captured.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceCaptureScope {
+    /// Records, which mention the value in their message or fields, or in the fields of one of their spans, e.g. the ID of a peer.
+    /// The label describes what the value is, e.g. `peer`.
+    Value { label: String, value: String },
+    /// Records within a span of this name. Case and underscores are ignored, so that `ListPeerDescriptors` matches `list_peer_descriptors`.
+    SpanName(String),
+}
+
+impl TraceCaptureScope {
+    fn matches(&self, spans: &[CapturedSpan], fields: &str) -> bool {
+        match self {
+            TraceCaptureScope::Value { value, .. } => {
+                fields.contains(value.as_str())
+                    || spans.iter().any(|span| span.fields.contains(value.as_str()))
+            }
+            TraceCaptureScope::SpanName(name) => {
+                let name = normalize_span_name(name);
+                spans.iter().any(|span| normalize_span_name(span.name) == name)
+            }
+        }
+    }
+}
+
+fn normalize_span_name(name: &str) -> String {
+    name.chars()
+        .filter(|char| *char != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct TraceCaptureInfo {
+    pub id: Uuid,
+    pub scope: TraceCaptureScope,
+    pub level: Level,
+    pub started_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub record_count: usize,
+    /// Number of the oldest records, which were discarded, because the maximum number of records was exceeded.
+    pub dropped_record_count: usize,
+}
+
+impl TraceCaptureInfo {
+    pub fn is_active(&self, now: SystemTime) -> bool {
+        now < self.expires_at
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CapturedRecord {
+    pub timestamp: SystemTime,
+    pub level: Level,
+    pub target: String,
+    /// Names of the spans this record was emitted in, starting with the outermost span.
+    pub spans: Vec<String>,
+    /// Message and fields of the record.
+    pub fields: String,
+}
+
+struct TraceCapture {
+    info: TraceCaptureInfo,
+    max_records: usize,
+    records: VecDeque<CapturedRecord>,
+}
+
+/// Temporarily raised verbosity for a narrow scope, e.g. a single peer, with the resulting records being kept in memory,
+/// so that a misbehaving deployment can be diagnosed without enabling debug logging for everything.
+#[derive(Default)]
+pub struct TraceCaptures {
+    captures: RwLock<HashMap<Uuid, TraceCapture>>,
+    /// Whether any capture might still be active, to avoid locking for every span and event when none is.
+    any_active: AtomicBool,
+}
+
+impl TraceCaptures {
+    pub fn start(&self, scope: TraceCaptureScope, level: Level, duration: Duration, max_records: usize) -> TraceCaptureInfo {
+        let started_at = SystemTime::now();
+        let info = TraceCaptureInfo {
+            id: Uuid::new_v4(),
+            scope,
+            level,
+            started_at,
+            expires_at: started_at + duration,
+            record_count: 0,
+            dropped_record_count: 0,
+        };
+
+        let mut captures = self.captures.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        captures.insert(info.id, TraceCapture {
+            info: Clone::clone(&info),
+            max_records,
+            records: VecDeque::new(),
+        });
+        self.any_active.store(true, Ordering::Relaxed);
+
+        info
+    }
+
+    pub fn list(&self) -> Vec<TraceCaptureInfo> {
+        let captures = self.captures.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut infos = captures.values()
+            .map(|capture| Clone::clone(&capture.info))
+            .collect::<Vec<_>>();
+        infos.sort_by_key(|info| info.started_at);
+        infos
+    }
+
+    /// Returns the records captured so far, which is possible while the capture is still active.
+    pub fn records(&self, id: Uuid) -> Option<(TraceCaptureInfo, Vec<CapturedRecord>)> {
+        let captures = self.captures.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        captures.get(&id)
+            .map(|capture| (Clone::clone(&capture.info), capture.records.iter().cloned().collect()))
+    }
+
+    /// Stops the capture, if still active, and discards its records.
+    pub fn remove(&self, id: Uuid) -> Option<TraceCaptureInfo> {
+        let mut captures = self.captures.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        captures.remove(&id)
+            .map(|capture| capture.info)
+    }
+
+    fn max_active_level(&self) -> Option<Level> {
+        if self.any_active.load(Ordering::Relaxed).not() {
+            return None;
+        }
+        let now = SystemTime::now();
+        let captures = self.captures.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let max_level = captures.values()
+            .filter(|capture| capture.info.is_active(now))
+            .map(|capture| capture.info.level)
+            .max();
+
+        if max_level.is_none() {
+            self.any_active.store(false, Ordering::Relaxed);
+        }
+        max_level
+    }
+
+    fn record(&self, level: Level, target: &str, spans: &[CapturedSpan], fields: String) {
+        let now = SystemTime::now();
+        let mut captures = self.captures.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        for capture in captures.values_mut() {
+            if capture.info.is_active(now).not()
+                || level > capture.info.level
+                || capture.info.scope.matches(spans, &fields).not() {
+                continue;
+            }
+
+            if capture.records.len() >= capture.max_records {
+                capture.records.pop_front();
+                capture.info.dropped_record_count += 1;
+            }
+            capture.records.push_back(CapturedRecord {
+                timestamp: now,
+                level,
+                target: target.to_owned(),
+                spans: spans.iter().map(|span| span.name.to_owned()).collect(),
+                fields: Clone::clone(&fields),
+            });
+            capture.info.record_count = capture.records.len();
+        }
+    }
+}
+
+/// Layer filling the trace captures. It only receives spans and events while a capture is active,
+/// at which point spans and events are enabled up to the level of the capture, regardless of the otherwise configured filters.
+pub(crate) fn layer<S>() -> impl Layer<S>
+where S: Subscriber + for<'a> LookupSpan<'a> {
+    let captures = trace_captures();
+    TraceCaptureLayer { captures: Arc::clone(&captures) }
+        .with_filter(TraceCaptureFilter { captures })
+}
+
+struct TraceCaptureFilter {
+    captures: TraceCapturesRef,
+}
+
+impl<S> Filter<S> for TraceCaptureFilter {
+    fn enabled(&self, metadata: &Metadata<'_>, _: &Context<'_, S>) -> bool {
+        self.captures.max_active_level()
+            .is_some_and(|max_level| *metadata.level() <= max_level)
+    }
+
+    fn callsite_enabled(&self, _: &'static Metadata<'static>) -> Interest {
+        // Whether a callsite is enabled changes when captures are started or expire, so it must not be cached.
+        Interest::sometimes()
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        Some(tracing::level_filters::LevelFilter::TRACE)
+    }
+}
+
+struct TraceCaptureLayer {
+    captures: TraceCapturesRef,
+}
+
+struct CapturedSpan {
+    name: &'static str,
+    fields: String,
+}
+
+/// Fields of a span, as recorded for matching the scope of a capture.
+struct SpanFields(String);
+
+impl<S> Layer<S> for TraceCaptureLayer
+where S: Subscriber + for<'a> LookupSpan<'a> {
+    fn on_new_span(&self, attributes: &Attributes<'_>, id: &tracing::span::Id, context: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attributes.record(&mut visitor);
+
+        if let Some(span) = context.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.0));
+        }
+    }
+
+    fn on_record(&self, id: &tracing::span::Id, values: &Record<'_>, context: Context<'_, S>) {
+        if let Some(span) = context.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+                let mut visitor = FieldVisitor(std::mem::take(fields));
+                values.record(&mut visitor);
+                *fields = visitor.0;
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, context: Context<'_, S>) {
+        let spans = context.event_scope(event)
+            .map(|scope| {
+                scope.from_root()
+                    .map(|span| CapturedSpan {
+                        name: span.name(),
+                        fields: span.extensions().get::<SpanFields>()
+                            .map(|SpanFields(fields)| Clone::clone(fields))
+                            .unwrap_or_default(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.captures.record(*metadata.level(), metadata.target(), &spans, visitor.0);
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(String);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"))
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.0.is_empty().not() {
+            self.0.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.0, "{value:?}");
+        } else {
+            let _ = write!(self.0, "{}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn should_only_capture_records_within_scope_and_level() {
+        let captures = Arc::new(TraceCaptures::default());
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                TraceCaptureLayer { captures: Arc::clone(&captures) }
+                    .with_filter(TraceCaptureFilter { captures: Arc::clone(&captures) })
+            );
+
+        let capture = captures.start(TraceCaptureScope::Value { label: String::from("peer"), value: String::from("peer-a") }, Level::DEBUG, Duration::from_secs(60), 10);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("handle_peer", peer_id = "peer-a");
+            span.in_scope(|| {
+                tracing::debug!("Within scope.");
+                tracing::trace!("Above level of capture.");
+            });
+            tracing::debug!(peer_id = "peer-b", "Other peer.");
+            tracing::debug!("Mentioning peer-a in message.");
+        });
+
+        let (info, records) = captures.records(capture.id).unwrap();
+        assert_eq!(info.record_count, 2);
+        assert_eq!(records[0].spans, vec![String::from("handle_peer")]);
+        assert_eq!(records[0].fields, "Within scope.");
+        assert_eq!(records[1].fields, "Mentioning peer-a in message.");
+    }
+
+    #[test]
+    fn should_drop_oldest_records_when_exceeding_maximum() {
+        let captures = TraceCaptures::default();
+        let capture = captures.start(TraceCaptureScope::SpanName(String::from("ListPeers")), Level::TRACE, Duration::from_secs(60), 2);
+
+        let spans = [CapturedSpan { name: "list_peers", fields: String::new() }];
+        for index in 0..3 {
+            captures.record(Level::INFO, "test", &spans, format!("{index}"));
+        }
+        captures.record(Level::INFO, "test", &[], String::from("outside of span"));
+
+        let (info, records) = captures.records(capture.id).unwrap();
+        assert_eq!(info.dropped_record_count, 1);
+        assert_eq!(records.iter().map(|record| record.fields.as_str()).collect::<Vec<_>>(), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn should_not_capture_after_expiry() {
+        let captures = TraceCaptures::default();
+        let capture = captures.start(TraceCaptureScope::Value { label: String::from("peer"), value: String::from("peer-a") }, Level::TRACE, Duration::ZERO, 10);
+
+        captures.record(Level::INFO, "test", &[], String::from("peer-a"));
+
+        assert_eq!(captures.max_active_level(), None);
+        assert_eq!(captures.records(capture.id).unwrap().1.len(), 0);
+    }
+}
@@ -1,16 +1,25 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
+use std::ops::Not;
 use std::path::PathBuf;
-use opentelemetry::KeyValue;
-use opentelemetry::logs::LogError;
-use opentelemetry_otlp::WithExportConfig;
+use std::sync::Arc;
+use opentelemetry::{global, KeyValue};
+use opentelemetry::logs::{LogError, LoggerProvider as _};
+use opentelemetry_otlp::{LogExporterBuilder, WithExportConfig};
 use opentelemetry_sdk::{Resource, runtime};
+use opentelemetry_sdk::logs::{BatchLogProcessor, LoggerProvider};
 use opendut_auth::confidential::blocking::client::{ConfClientArcMutex, ConfidentialClientRef};
 use crate::telemetry::opentelemetry_types::Endpoint;
+use crate::telemetry::redaction::{RedactingLogProcessor, Redactor};
 
 #[derive(Default, Clone)]
 pub struct LoggingConfig {
     pub logging_stdout: bool,
     pub file_logging: Option<PathBuf>,
+    /// Overrides the `OPENDUT_LOG` environment variable, e.g. `info` or `opendut=debug,tower=warn`.
+    pub level: Option<String>,
+    /// Masks secrets in all log outputs. Does not mask anything by default.
+    pub redactor: Redactor,
 }
 
 impl LoggingConfig {
@@ -21,17 +30,33 @@ impl LoggingConfig {
                 field: field.clone(),
             })?;
 
+        let level = config.get_string("logging.level").ok()
+            .filter(|level| level.is_empty().not());
+
         Ok(LoggingConfig {
             logging_stdout,
             file_logging: None,
+            level,
+            redactor: Redactor::default(),
         })
     }
 }
 
-pub fn init_logger(telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>, endpoint: &Endpoint, service_name: impl Into<String>, service_instance_id: impl Into<String>) -> Result<opentelemetry_sdk::logs::Logger, LogError> {
-    opentelemetry_otlp::new_pipeline()
-        .logging()
-        .with_log_config(
+pub fn init_logger(telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>, endpoint: &Endpoint, service_name: impl Into<String>, service_instance_id: impl Into<String>, redactor: Arc<Redactor>) -> Result<opentelemetry_sdk::logs::Logger, LogError> {
+    let exporter = LogExporterBuilder::from(
+        opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_interceptor(telemetry_interceptor)
+            .with_endpoint(Clone::clone(&endpoint.url))
+    ).build_log_exporter()?;
+
+    //built like `opentelemetry_otlp::new_pipeline().logging().install_batch()`, but redacting the records before they are exported
+    let processor = RedactingLogProcessor::new(
+        BatchLogProcessor::builder(exporter, runtime::Tokio).build(),
+        redactor,
+    );
+    let provider = LoggerProvider::builder()
+        .with_config(
             opentelemetry_sdk::logs::Config::default().with_resource(Resource::new(vec![
                 KeyValue::new(
                     opentelemetry_semantic_conventions::resource::SERVICE_NAME,
@@ -42,13 +67,12 @@ pub fn init_logger(telemetry_interceptor: ConfClientArcMutex<Option<Confidential
                 ),
             ])),
         )
-        .with_exporter(
-            opentelemetry_otlp::new_exporter()
-                .tonic()
-                .with_interceptor(telemetry_interceptor)
-                .with_endpoint(Clone::clone(&endpoint.url)),
-        )
-        .install_batch(runtime::Tokio)
+        .with_log_processor(processor)
+        .build();
+
+    let logger = provider.versioned_logger(Cow::Borrowed("opentelemetry-otlp"), None, None, None);
+    let _ = global::set_logger_provider(provider);
+    Ok(logger)
 }
 
 #[derive(Debug, thiserror::Error)]
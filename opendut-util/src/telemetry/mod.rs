@@ -1,5 +1,7 @@
+pub mod capture;
 pub mod opentelemetry_types;
 pub mod logging;
+pub mod redaction;
 mod traces;
 pub mod metrics;
 
@@ -15,16 +17,19 @@ use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_sdk::logs::Logger;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::Mutex;
-use tracing::error;
+use tracing::{error, Subscriber};
 use tracing_subscriber::filter::Directive;
 use tracing_subscriber::filter::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
+use tracing_subscriber::Layer;
 use tracing_subscriber::util::SubscriberInitExt;
 use opendut_auth::confidential::blocking::client::{AuthError, ConfClientArcMutex};
 use opendut_auth::confidential::error::ConfidentialClientError;
 use crate::telemetry::logging::{LoggingConfig, LoggingConfigError};
 use crate::telemetry::metrics::{NamedMeterProvider, NamedMeterProviderKindCpu, NamedMeterProviderKindDefault, NamedMeterProviders};
 use crate::telemetry::opentelemetry_types::{Opentelemetry, OpentelemetryConfigError};
+use crate::telemetry::redaction::RedactingMakeWriter;
 
 pub const DEFAULT_METER_NAME: &str = "opendut_meter";
 
@@ -34,6 +39,8 @@ pub enum Error {
     TracingFilterFromEnv { #[from] source: tracing_subscriber::filter::FromEnvError },
     #[error("Unable to initialize tracing: {source}")]
     TracingFilterParse { #[from] source: tracing_subscriber::filter::ParseError },
+    #[error("Unable to change the log level: {source}")]
+    TracingFilterReload { #[from] source: reload::Error },
     #[error("Unable to set initialize tracing: {source}")]
     TracingInit { #[from] source: tracing_subscriber::util::TryInitError },
     #[error("Unable to create the opentelemetry tracer: {source}")]
@@ -56,16 +63,20 @@ pub async fn initialize_with_config(
 ) -> Result<ShutdownHandle, Error> {
 
     global::set_text_map_propagator(TraceContextPropagator::new());
-    
-    let tracing_filter = EnvFilter::builder()
-        .with_default_directive(Directive::from_str("opendut=trace")?)
-        .with_env_var("OPENDUT_LOG")
-        .from_env()?;
+
+    // Applied per layer rather than globally, so that trace captures can raise the verbosity for a narrow scope,
+    // without these records showing up in the other outputs.
+    let mut log_filter = LogFilterHandle::default();
+    let level = logging_config.level.as_deref();
+
+    let redactor = Arc::new(logging_config.redactor);
 
     let stdout_logging_layer =
         if logging_config.logging_stdout {
             let stdout_logging_layer = tracing_subscriber::fmt::layer()
-                .compact();
+                .compact()
+                .with_writer(RedactingMakeWriter::new(std::io::stdout, Arc::clone(&redactor)))
+                .with_filter(log_filter.reloadable(tracing_filter(level)?));
             Some(stdout_logging_layer)
         } else {
             None
@@ -81,7 +92,8 @@ pub async fn initialize_with_config(
                 .unwrap_or_else(|cause| panic!("Failed to open log file at '{}': {cause}", log_file.display()));
 
             Some(tracing_subscriber::fmt::layer()
-                .with_writer(log_file))
+                .with_writer(RedactingMakeWriter::new(log_file, Arc::clone(&redactor)))
+                .with_filter(log_filter.reloadable(tracing_filter(level)?)))
         } else {
             None
         };
@@ -99,7 +111,7 @@ pub async fn initialize_with_config(
 
             let tracer = traces::init_tracer(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize tracer.");
             
-            let logger = logging::init_logger(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize logs.");
+            let logger = logging::init_logger(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone(), Arc::clone(&redactor)).expect("Failed to initialize logs.");
 
             let logger_provider: GlobalLoggerProvider = logger_provider();
             let logger_layer = OpenTelemetryTracingBridge::new(&logger_provider);
@@ -134,15 +146,60 @@ pub async fn initialize_with_config(
         (None, None, None, None)
     };
 
+    let tracer_layer = match tracer {
+        Some(tracer) => Some(tracing_opentelemetry::layer().with_tracer(tracer).with_filter(log_filter.reloadable(tracing_filter(level)?))),
+        None => None,
+    };
+    let logger_layer = match logger_layer {
+        Some(logger_layer) => Some(logger_layer.with_filter(log_filter.reloadable(tracing_filter(level)?))),
+        None => None,
+    };
+
     tracing_subscriber::registry()
         .with(stdout_logging_layer)
-        .with(tracing_filter)
         .with(file_logging_layer)
-        .with(tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
+        .with(tracer_layer)
         .with(logger_layer)
+        .with(capture::layer())
         .try_init()?;
 
-    Ok(ShutdownHandle { _logger: logger, meter_providers })
+    Ok(ShutdownHandle { _logger: logger, meter_providers, log_filter })
+}
+
+/// Filters by the configured level, e.g. `info` or `opendut=debug,tower=warn`, or otherwise by the `OPENDUT_LOG` environment variable.
+fn tracing_filter(level: Option<&str>) -> Result<EnvFilter, Error> {
+    match level {
+        Some(level) => Ok(EnvFilter::builder().parse(level)?),
+        None => Ok(EnvFilter::builder()
+            .with_default_directive(Directive::from_str("opendut=trace")?)
+            .with_env_var("OPENDUT_LOG")
+            .from_env()?),
+    }
+}
+
+type ReloadFilter = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Changes the level of all log outputs while running, e.g. when the configuration file was edited.
+#[derive(Clone, Default)]
+pub struct LogFilterHandle {
+    reload_filters: Arc<Vec<ReloadFilter>>,
+}
+impl LogFilterHandle {
+    /// Applies the given level to all log outputs, or the level from the `OPENDUT_LOG` environment variable, if `None`.
+    pub fn reload(&self, level: Option<&str>) -> Result<(), Error> {
+        for reload_filter in self.reload_filters.iter() {
+            reload_filter(tracing_filter(level)?)?;
+        }
+        Ok(())
+    }
+
+    fn reloadable<S: Subscriber + 'static>(&mut self, filter: EnvFilter) -> reload::Layer<EnvFilter, S> {
+        let (filter, handle) = reload::Layer::new(filter);
+        Arc::get_mut(&mut self.reload_filters)
+            .expect("Filters should only be registered before the handle is shared.")
+            .push(Box::new(move |filter: EnvFilter| handle.reload(filter)));
+        filter
+    }
 }
 
 
@@ -153,6 +210,7 @@ pub struct ShutdownHandle {
         NamedMeterProvider<NamedMeterProviderKindDefault>,
         NamedMeterProvider<NamedMeterProviderKindCpu>
     )>,
+    pub log_filter: LogFilterHandle,
 }
 impl ShutdownHandle {
     pub fn shutdown(&mut self) {
@@ -30,6 +30,7 @@ pub trait Client {
     async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> Result<netbird::Policy, GetPoliciesError>;
     async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> Result<(), RequestError>;
     async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> Result<netbird::SetupKey, CreateSetupKeyError>;
+    async fn check_netbird_connection(&self) -> Result<(), RequestError>;
 }
 
 pub struct DefaultClient {
@@ -312,6 +313,17 @@ impl Client for DefaultClient {
 
         Ok(result)
     }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn check_netbird_connection(&self) -> Result<(), RequestError> {
+        let url = routes::groups(self.netbird_url.clone());
+        let request = Request::new(Method::GET, url);
+
+        self.requester.handle(request).await?
+            .error_for_status().map_err(RequestError::IllegalStatus)?;
+
+        Ok(())
+    }
 }
 
 fn post_json_request(url: Url, body: impl Serialize) -> Result<Request, RequestError> {
@@ -12,7 +12,7 @@ pub use netbird::Token as NetbirdToken;
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
-use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+use opendut_vpn::{CheckConnectionError, CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
 
 use crate::client::{Client, DefaultClient};
 use crate::netbird::error::{CreateClientError, CreateSetupKeyError, GetGroupError, GetPoliciesError, RequestError};
@@ -255,6 +255,12 @@ impl VpnManagementClient for NetbirdManagementClient {
             setup_key: opendut_types::vpn::netbird::SetupKey::from(setup_key.key),
         })
     }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn check_connection(&self) -> Result<(), CheckConnectionError> {
+        self.inner.check_netbird_connection().await
+            .map_err(|error| CheckConnectionError::ConnectionFailure { url: self.management_url.to_string(), error: error.into() })
+    }
 }
 
 #[cfg(test)]
@@ -459,6 +465,7 @@ mod test {
             async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> std::result::Result<netbird::Policy, GetPoliciesError>;
             async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> std::result::Result<(), RequestError>;
             async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> std::result::Result<netbird::SetupKey, CreateSetupKeyError>;
+            async fn check_netbird_connection(&self) -> std::result::Result<(), RequestError>;
         }
     }
 }
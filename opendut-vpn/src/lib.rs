@@ -16,6 +16,9 @@ pub trait VpnManagementClient {
     async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError>;
 
     async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError>;
+
+    /// Checks that the VPN management service is reachable and accepts the configured credentials.
+    async fn check_connection(&self) -> Result<(), CheckConnectionError>;
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -83,3 +86,12 @@ pub enum CreateVpnPeerConfigurationError {
         error: Box<dyn std::error::Error>
     },
 }
+
+#[derive(thiserror::Error, Debug)]
+pub enum CheckConnectionError {
+    #[error("Could not connect to the VPN management service at '{url}':\n  {error}")]
+    ConnectionFailure {
+        url: String,
+        error: Box<dyn std::error::Error>,
+    },
+}
@@ -209,6 +209,8 @@ impl Fixture {
         let empty_peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            restbus_channels: vec![],
+            cluster_hooks: vec![],
         };
         let empty_old_peer_configuration = OldPeerConfiguration { cluster_assignment: None };
 
@@ -228,6 +230,9 @@ async fn store_cluster_configuration(leader: PeerId, devices: impl Iterator<Item
         name: ClusterName::try_from(format!("cluster-{cluster_id}"))?,
         leader,
         devices,
+        peer_groups: HashSet::new(),
+        device_selectors: Vec::new(),
+        dependencies: Vec::new(),
     };
 
     carl_client.inner().await.cluster.store_cluster_configuration(cluster_configuration.clone()).await?;
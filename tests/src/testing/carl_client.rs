@@ -30,6 +30,7 @@ impl TestCarlClient {
 
             match edgar_state {
                 PeerState::Up { .. } => Ok(()),
+                PeerState::Unreachable { .. } => Err(backoff::Error::transient(anyhow!("Peer is connected, but unreachable!"))),
                 PeerState::Down => Err(backoff::Error::transient(anyhow!("No peers registered in time!")))
             }
         }).await?;
@@ -37,6 +37,10 @@ pub async fn store_peer_descriptor(carl_client: &TestCarlClient) -> anyhow::Resu
         executors: ExecutorDescriptors {
             executors: vec![],
         },
+        labels: Default::default(),
+        annotations: Default::default(),
+        hardware: Default::default(),
+        cluster_hooks: Default::default(),
     };
 
     carl_client.inner().await.peers
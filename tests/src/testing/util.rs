@@ -2,6 +2,7 @@ use crate::testing::peer_configuration_listener::PeerConfigurationReceiver;
 use opendut_types::peer::PeerId;
 use opendut_types::util::Port;
 use opendut_util::settings::LoadedConfig;
+use opendut_util::telemetry::LogFilterHandle;
 use std::future::Future;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -29,6 +30,7 @@ pub fn spawn_carl() -> anyhow::Result<Port> {
         .set_override("vpn.enabled", false)?
         .set_override("serve.ui.presence_check", false)?
         .set_override("network.oidc.enabled", false)?
+        .set_override("bootstrap.enabled", false)?
         // ensure the development certificates are used
         // even if ~/.config/opendut/carl/config.toml is present with different values for the test environment in opendut-vm
         .set_override("network.tls.certificate", "resources/development/tls/insecure-development-carl.pem")?
@@ -55,7 +57,7 @@ pub async fn spawn_edgar_with_peer_configuration_receiver(peer_id: PeerId, carl_
 
     let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
     tokio::spawn(async move {
-        opendut_edgar::testing::service::start::run_stream_receiver(peer_id, edgar_config, tx_peer_configuration).await
+        opendut_edgar::testing::service::start::run_stream_receiver(peer_id, edgar_config, config::Config::default(), LogFilterHandle::default(), tx_peer_configuration).await
             .expect("EDGAR crashed")
     });
     Ok(PeerConfigurationReceiver { inner: rx_peer_configuration })
@@ -69,6 +71,7 @@ pub(super) fn load_edgar_config(carl_port: Port, peer_id: PeerId) -> anyhow::Res
         .set_override("network.carl.port", carl_port.0)?
         .set_override("network.connect.retries", 100)?
         .set_override("network.oidc.enabled", false)?
+        .set_override("configuration.reload.enabled", false)?
         .build()?;
 
     opendut_edgar::testing::settings::load_with_overrides(settings_overrides)